@@ -105,6 +105,10 @@ client_request_definitions! {
         params: v2::ThreadStartParams,
         response: v2::ThreadStartResponse,
     },
+    ConversationValidateOptions => "conversation/validateOptions" {
+        params: v2::ConversationValidateOptionsParams,
+        response: v2::ConversationValidateOptionsResponse,
+    },
     ThreadResume => "thread/resume" {
         params: v2::ThreadResumeParams,
         response: v2::ThreadResumeResponse,
@@ -138,6 +142,10 @@ client_request_definitions! {
         params: v2::ModelListParams,
         response: v2::ModelListResponse,
     },
+    ModelRefresh => "model/refresh" {
+        params: v2::ModelRefreshParams,
+        response: v2::ModelRefreshResponse,
+    },
 
     McpServerOauthLogin => "mcpServer/oauth/login" {
         params: v2::McpServerOauthLoginParams,
@@ -474,6 +482,13 @@ server_request_definitions! {
         response: v2::FileChangeRequestApprovalResponse,
     },
 
+    /// Sent when a thread's estimated cost reached `session_cost_limit_usd`,
+    /// before the turn sends its first model request.
+    CostLimitRequestApproval => "turn/costLimit/requestApproval" {
+        params: v2::CostLimitRequestApprovalParams,
+        response: v2::CostLimitRequestApprovalResponse,
+    },
+
     /// DEPRECATED APIs below
     /// Request to approve a patch.
     /// This request is used for Turns started via the legacy APIs (i.e. SendUserTurn, SendUserMessage).
@@ -535,6 +550,9 @@ server_notification_definitions! {
     McpServerOauthLoginCompleted => "mcpServer/oauthLogin/completed" (v2::McpServerOauthLoginCompletedNotification),
     AccountUpdated => "account/updated" (v2::AccountUpdatedNotification),
     AccountRateLimitsUpdated => "account/rateLimits/updated" (v2::AccountRateLimitsUpdatedNotification),
+    ModelListUpdated => "model/list/updated" (v2::ModelListUpdatedNotification),
+    ModelDefaultChanged => "model/default/changed" (v2::ModelDefaultChangedNotification),
+    ModelRefreshProgress => "model/refresh/progress" (v2::ModelRefreshProgressNotification),
     ReasoningSummaryTextDelta => "item/reasoning/summaryTextDelta" (v2::ReasoningSummaryTextDeltaNotification),
     ReasoningSummaryPartAdded => "item/reasoning/summaryPartAdded" (v2::ReasoningSummaryPartAddedNotification),
     ReasoningTextDelta => "item/reasoning/textDelta" (v2::ReasoningTextDeltaNotification),
@@ -589,6 +607,7 @@ mod tests {
                 developer_instructions: None,
                 compact_prompt: None,
                 include_apply_patch_tool: None,
+                allow_unknown_model: None,
             },
         };
         assert_eq!(
@@ -824,7 +843,8 @@ mod tests {
                 "id": 6,
                 "params": {
                     "limit": null,
-                    "cursor": null
+                    "cursor": null,
+                    "ifRevision": null
                 }
             }),
             serde_json::to_value(&request)?,
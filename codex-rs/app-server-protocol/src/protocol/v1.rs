@@ -63,6 +63,10 @@ pub struct NewConversationParams {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub compact_prompt: Option<String>,
     pub include_apply_patch_tool: Option<bool>,
+    /// Start the conversation even when `model` is not in the catalog of its
+    /// provider, for providers whose catalog does not list every model.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub allow_unknown_model: Option<bool>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
@@ -122,6 +126,11 @@ pub struct ConversationSummary {
     pub cli_version: String,
     pub source: SessionSource,
     pub git_info: Option<ConversationGitInfo>,
+    /// Model slug of the conversation's first turn, when it was recorded.
+    pub model: Option<String>,
+    /// Catalog display name for `model`, or the slug itself when the catalog
+    /// does not list it.
+    pub model_display_name: Option<String>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
@@ -11,7 +11,11 @@ use codex_protocol::config_types::Verbosity;
 use codex_protocol::items::AgentMessageContent as CoreAgentMessageContent;
 use codex_protocol::items::TurnItem as CoreTurnItem;
 use codex_protocol::models::ResponseItem;
+use codex_protocol::openai_models::Capability;
+use codex_protocol::openai_models::EffortSelection;
+use codex_protocol::openai_models::ModelPreset as CoreModelPreset;
 use codex_protocol::openai_models::ReasoningEffort;
+use codex_protocol::openai_models::ReasoningEffortPreset as CoreReasoningEffortPreset;
 use codex_protocol::parse_command::ParsedCommand as CoreParsedCommand;
 use codex_protocol::plan_tool::PlanItemArg as CorePlanItemArg;
 use codex_protocol::plan_tool::StepStatus as CorePlanStepStatus;
@@ -102,6 +106,12 @@ pub enum CodexErrorInfo {
         #[ts(rename = "httpStatusCode")]
         http_status_code: Option<u16>,
     },
+    /// The provider no longer serves the selected model.
+    ModelUnavailable {
+        #[serde(rename = "presetId")]
+        #[ts(rename = "presetId")]
+        preset_id: String,
+    },
     Other,
 }
 
@@ -126,6 +136,9 @@ impl From<CoreCodexErrorInfo> for CodexErrorInfo {
             CoreCodexErrorInfo::ResponseTooManyFailedAttempts { http_status_code } => {
                 CodexErrorInfo::ResponseTooManyFailedAttempts { http_status_code }
             }
+            CoreCodexErrorInfo::ModelUnavailable { preset_id } => {
+                CodexErrorInfo::ModelUnavailable { preset_id }
+            }
             CoreCodexErrorInfo::Other => CodexErrorInfo::Other,
         }
     }
@@ -322,7 +335,10 @@ pub struct ProfileV2 {
     pub model: Option<String>,
     pub model_provider: Option<String>,
     pub approval_policy: Option<AskForApproval>,
-    pub model_reasoning_effort: Option<ReasoningEffort>,
+    /// `"auto"` or an effort name, as written in config.toml.
+    #[ts(type = "string | null")]
+    #[schemars(with = "Option<String>")]
+    pub model_reasoning_effort: Option<EffortSelection>,
     pub model_reasoning_summary: Option<ReasoningSummary>,
     pub model_verbosity: Option<Verbosity>,
     pub chatgpt_base_url: Option<String>,
@@ -351,7 +367,10 @@ pub struct Config {
     pub instructions: Option<String>,
     pub developer_instructions: Option<String>,
     pub compact_prompt: Option<String>,
-    pub model_reasoning_effort: Option<ReasoningEffort>,
+    /// `"auto"` or an effort name, as written in config.toml.
+    #[ts(type = "string | null")]
+    #[schemars(with = "Option<String>")]
+    pub model_reasoning_effort: Option<EffortSelection>,
     pub model_reasoning_summary: Option<ReasoningSummary>,
     pub model_verbosity: Option<Verbosity>,
     #[serde(default, flatten)]
@@ -828,12 +847,55 @@ pub struct ModelListParams {
     pub cursor: Option<String>,
     /// Optional page size; defaults to a reasonable server-side value.
     pub limit: Option<u32>,
+    /// Position of the first model to return, for clients that page by
+    /// index, such as virtualized lists. Cannot be combined with `cursor`.
+    #[serde(default)]
+    pub offset: Option<u32>,
+    /// Only list the models whose display name or id fuzzy-matches this
+    /// text, best match first and catalog order among equal matches. Pages
+    /// are cut from the filtered list.
+    #[serde(default)]
+    pub query: Option<String>,
+    /// Revision from a previous response. When the catalog is still at this
+    /// revision the server replies `notModified` without any models. A
+    /// `query` or `capability` is part of the revision, so only a response
+    /// to the same filters matches. Ignored when a page is requested with
+    /// `limit`, `cursor` or `offset`.
+    #[ts(type = "number | null")]
+    pub if_revision: Option<u64>,
+    /// Only list the models with this capability, e.g. `images` to offer a
+    /// model that accepts the image a turn was rejected for.
+    #[serde(default)]
+    pub capability: Option<Capability>,
+    /// List the catalog of the selected provider as served from this base
+    /// URL, e.g. the gateway of one of several tenants, instead of its
+    /// configured `base_url`. Must be listed in `allowed_catalog_base_urls`.
+    /// Revisions are counted per base URL.
+    #[serde(default)]
+    pub catalog_base_url: Option<String>,
 }
 
+/// The layout of [`Model`] this server writes, sent as `presetSchemaVersion`.
+pub const MODEL_PRESET_SCHEMA_VERSION: u32 = 1;
+
+/// A model as `model/list` and `model/list/updated` serialize it.
+///
+/// IDE clients parse this JSON directly, so its layout is versioned. Within a
+/// [`MODEL_PRESET_SCHEMA_VERSION`], fields are only ever added, and only
+/// optional ones: every field a version lists keeps its name and JSON type,
+/// and clients should ignore fields they do not know. Renaming, retyping, or
+/// removing a field bumps the version. A bump is a reviewed change that adds
+/// a golden file under `app-server-protocol/tests/fixtures/model/` for the
+/// new version; the tests in `tests/model_schema.rs` hold the serializer to
+/// the golden file of the current version.
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
 #[serde(rename_all = "camelCase")]
 #[ts(export_to = "v2/")]
 pub struct Model {
+    /// Always [`MODEL_PRESET_SCHEMA_VERSION`] in what this server sends.
+    /// Missing in JSON written before the field existed, which is version 1.
+    #[serde(default = "first_model_preset_schema_version")]
+    pub preset_schema_version: u32,
     pub id: String,
     pub model: String,
     pub display_name: String,
@@ -842,6 +904,44 @@ pub struct Model {
     pub default_reasoning_effort: ReasoningEffort,
     // Only one model should be marked as default.
     pub is_default: bool,
+    /// Whether the model needs the sandbox to allow network access. Clients
+    /// can warn when the thread's sandbox blocks it.
+    #[serde(default)]
+    pub requires_network: bool,
+    /// Set on the entries of `model/list/updated` that the refresh added.
+    /// Always false in `model/list` responses.
+    #[serde(default)]
+    pub newly_added: bool,
+    /// Fields the catalog served for the model that this server does not
+    /// know, passed on as served.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub extra: HashMap<String, JsonValue>,
+}
+
+fn first_model_preset_schema_version() -> u32 {
+    1
+}
+
+impl From<CoreModelPreset> for Model {
+    fn from(value: CoreModelPreset) -> Self {
+        Self {
+            preset_schema_version: MODEL_PRESET_SCHEMA_VERSION,
+            id: value.id,
+            model: value.model,
+            display_name: value.display_name,
+            description: value.description,
+            supported_reasoning_efforts: value
+                .supported_reasoning_efforts
+                .into_iter()
+                .map(ReasoningEffortOption::from)
+                .collect(),
+            default_reasoning_effort: value.default_reasoning_effort,
+            is_default: value.is_default,
+            requires_network: value.requires_network,
+            newly_added: false,
+            extra: HashMap::new(),
+        }
+    }
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
@@ -852,6 +952,15 @@ pub struct ReasoningEffortOption {
     pub description: String,
 }
 
+impl From<CoreReasoningEffortPreset> for ReasoningEffortOption {
+    fn from(value: CoreReasoningEffortPreset) -> Self {
+        Self {
+            reasoning_effort: value.effort,
+            description: value.description,
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
 #[serde(rename_all = "camelCase")]
 #[ts(export_to = "v2/")]
@@ -860,6 +969,161 @@ pub struct ModelListResponse {
     /// Opaque cursor to pass to the next call to continue after the last item.
     /// If None, there are no more items to return.
     pub next_cursor: Option<String>,
+    /// Number of models matching the request across all pages.
+    pub total_count: u32,
+    /// Revision of the listed models, a digest of the models and the
+    /// credentials they were listed with. It changes whenever the listing
+    /// does, whether a refresh or a config change caused it, and is the same
+    /// after a server restart that lists the same models. A page listed at
+    /// another revision than the pages before it means the catalog changed in
+    /// between.
+    #[ts(type = "number")]
+    pub revision: u64,
+    /// Set when `ifRevision` matched; `data` is then empty.
+    pub not_modified: bool,
+    /// Credentials the catalog was requested with, which decide which of the
+    /// listed models will work.
+    pub catalog_auth: ModelCatalogAuth,
+    /// Catalogs whose last refresh failed. Their previously listed models are
+    /// still in `data`, so clients should tell the user the list may be stale.
+    /// Reported even when `notModified` is set.
+    pub warnings: Vec<ModelCatalogWarning>,
+    /// Set when the catalog recommends a newer client than this one. The
+    /// listed models keep working; clients should only suggest upgrading.
+    pub client_version_notice: Option<ModelClientVersionNotice>,
+    /// The last rebuild of the catalog's background refresher, for
+    /// diagnostics. `null` until the first rebuild finishes.
+    pub last_rebuild: Option<ModelCatalogRebuild>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ModelCatalogRebuild {
+    /// Every trigger the rebuild ran for, in the order they arrived:
+    /// `started`, `config_reloaded`, `auth_changed`, `manual`, or `scheduled`.
+    pub reasons: Vec<String>,
+    /// Revision of the presets once the rebuild finished; unchanged when the
+    /// rebuild left them as they were.
+    #[ts(type = "number")]
+    pub revision: u64,
+    /// Unix timestamp (in seconds) of when the rebuild finished.
+    #[ts(type = "number")]
+    pub finished_at: i64,
+    /// Why the refresh the rebuild ran for failed, if it did.
+    pub error: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ModelRefreshParams {}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ModelRefreshResponse {
+    /// Identifies the refresh in `model/refresh/progress` notifications.
+    pub task_id: String,
+    /// Set when a refresh was already running; `taskId` is then that
+    /// refresh's, and no second one is started.
+    pub already_running: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ModelRefreshProgressNotification {
+    pub task_id: String,
+    pub progress: ModelRefreshProgress,
+}
+
+/// A step of a `model/refresh`. Each refresh reports `started`, then either
+/// `fetched` and `completed`, or `failed`.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(tag = "type", rename_all = "camelCase")]
+#[ts(tag = "type")]
+#[ts(export_to = "v2/")]
+pub enum ModelRefreshProgress {
+    #[serde(rename = "started", rename_all = "camelCase")]
+    #[ts(rename = "started", rename_all = "camelCase")]
+    Started {},
+    /// The catalogs were fetched; the catalog now lists `modelCount` models.
+    #[serde(rename = "fetched", rename_all = "camelCase")]
+    #[ts(rename = "fetched", rename_all = "camelCase")]
+    Fetched { model_count: u32 },
+    /// The refresh failed. The models listed before are kept.
+    #[serde(rename = "failed", rename_all = "camelCase")]
+    #[ts(rename = "failed", rename_all = "camelCase")]
+    Failed { message: String },
+    /// The refresh finished at this catalog revision. When the catalog
+    /// changed, `model/list/updated` announces the new revision as well.
+    #[serde(rename = "completed", rename_all = "camelCase")]
+    #[ts(rename = "completed", rename_all = "camelCase")]
+    Completed {
+        #[ts(type = "number")]
+        revision: u64,
+    },
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ModelCatalogWarning {
+    /// Catalog provider that failed, or null for the built-in remote catalog.
+    pub provider_id: Option<String>,
+    /// Unix timestamp (in seconds) of the last successful fetch, if any.
+    #[ts(type = "number | null")]
+    pub fetched_at: Option<i64>,
+    /// Unix timestamp (in seconds) of the failed refresh.
+    #[ts(type = "number")]
+    pub failed_at: i64,
+    pub error: String,
+    /// Ready-to-show summary, e.g. "model list is 3h stale: gateway: ...".
+    pub message: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ModelClientVersionNotice {
+    /// Oldest client version the catalog recommends, as it was served.
+    pub min_client_version: String,
+    /// Version of this app server.
+    pub client_version: String,
+    /// Ready-to-show notice, e.g. "your Codex version (0.59.0) is below the
+    /// minimum recommended by your gateway (0.60.0); please upgrade".
+    pub message: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ModelCatalogAuth {
+    /// Display name of the provider whose catalog is listed.
+    pub provider: String,
+    /// Host (and port) of the catalog endpoint.
+    pub host: Option<String>,
+    /// Null when the catalog is requested without credentials.
+    pub auth_mode: Option<AuthMode>,
+    pub credential_source: Option<ModelCatalogCredentialSource>,
+    /// Email of the signed-in ChatGPT account.
+    pub account: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(tag = "type", rename_all = "camelCase")]
+#[ts(tag = "type")]
+#[ts(export_to = "v2/")]
+pub enum ModelCatalogCredentialSource {
+    /// `auth.json` or the keyring, written by `codex login`.
+    Stored,
+    /// An environment variable such as `CODEX_API_KEY` or the provider's `env_key`.
+    #[serde(rename_all = "camelCase")]
+    #[ts(rename_all = "camelCase")]
+    EnvVar { name: String },
+    /// `experimental_bearer_token` in the provider's config.
+    Config,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
@@ -964,6 +1228,11 @@ pub struct ThreadStartParams {
     pub config: Option<HashMap<String, JsonValue>>,
     pub base_instructions: Option<String>,
     pub developer_instructions: Option<String>,
+    /// Run the thread against the selected provider at this base URL, e.g.
+    /// the gateway of one of several tenants, for both its model catalog and
+    /// its turns. Must be listed in `allowed_catalog_base_urls`.
+    #[serde(default)]
+    pub catalog_base_url: Option<String>,
     /// If true, opt into emitting raw response items on the event stream.
     ///
     /// This is for internal use only (e.g. Codex Cloud).
@@ -985,6 +1254,99 @@ pub struct ThreadStartResponse {
     pub reasoning_effort: Option<ReasoningEffort>,
 }
 
+/// The options of a `thread/start`, to check without starting a thread.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ConversationValidateOptionsParams {
+    /// Model id or slug.
+    pub model: Option<String>,
+    pub model_provider: Option<String>,
+    pub cwd: Option<String>,
+    pub effort: Option<ReasoningEffort>,
+    pub approval_policy: Option<AskForApproval>,
+    pub sandbox: Option<SandboxMode>,
+    pub config: Option<HashMap<String, JsonValue>>,
+    /// Capabilities the thread will need from its model, e.g. `images` when
+    /// the first turn attaches a screenshot.
+    #[serde(default)]
+    pub required_capabilities: Vec<Capability>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ConversationValidateOptionsResponse {
+    /// True when no issue is an error, so `thread/start` would accept the
+    /// options.
+    pub valid: bool,
+    /// The model a thread started with the options would run. Null when the
+    /// options do not make a valid config.
+    pub model: Option<String>,
+    pub issues: Vec<ConversationOptionIssue>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ConversationOptionIssue {
+    pub severity: ConversationOptionIssueSeverity,
+    pub code: ConversationOptionIssueCode,
+    /// The option the issue is about, when it is about one.
+    pub field: Option<ConversationOptionField>,
+    pub message: String,
+    /// Values for `field` that would resolve the issue, best first.
+    pub suggestions: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub enum ConversationOptionIssueSeverity {
+    /// `thread/start` would reject the options.
+    Error,
+    /// A thread would start, but not quite as requested.
+    Warning,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub enum ConversationOptionIssueCode {
+    /// The options, together with config.toml, do not make a valid config,
+    /// e.g. an approval policy the admin requirements do not allow.
+    InvalidConfig,
+    /// The catalog of the provider does not list the model.
+    UnknownModel,
+    /// The model does not offer the effort.
+    UnsupportedEffort,
+    /// The model lacks one of `requiredCapabilities`.
+    MissingCapability,
+    /// The admin requirements do not allow the sandbox.
+    DisallowedSandbox,
+    /// The model is retired; threads run its successor.
+    RetiredModel,
+    /// The provider recently refused the model to the signed-in account.
+    UnavailableForAccount,
+    /// The model needs ChatGPT sign-in, not an API key.
+    RequiresChatgptAuth,
+    /// `preset_overrides` moves the effort into the model's range.
+    EffortClamped,
+    /// The model takes no reasoning effort.
+    EffortIgnored,
+    /// The model needs network access, which the sandbox blocks.
+    RequiresNetwork,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub enum ConversationOptionField {
+    Model,
+    Effort,
+    Sandbox,
+}
+
 #[derive(Serialize, Deserialize, Debug, Default, Clone, PartialEq, JsonSchema, TS)]
 #[serde(rename_all = "camelCase")]
 #[ts(export_to = "v2/")]
@@ -1196,6 +1558,29 @@ pub struct AccountUpdatedNotification {
     pub auth_mode: Option<AuthMode>,
 }
 
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ModelListUpdatedNotification {
+    /// New catalog revision; pass it as `ifRevision` to `model/list`.
+    #[ts(type = "number")]
+    pub revision: u64,
+    /// Models this revision added, each with `newlyAdded: true`.
+    #[serde(default)]
+    pub added_models: Vec<Model>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ModelDefaultChangedNotification {
+    /// The catalog's default before the refresh.
+    pub previous_model: String,
+    /// The new default, which threads started without a `model` use. `None`
+    /// when the catalog lists no model any more.
+    pub model: Option<String>,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
 #[serde(rename_all = "camelCase")]
 #[ts(export_to = "v2/")]
@@ -1869,6 +2254,27 @@ pub struct FileChangeRequestApprovalResponse {
     pub decision: ApprovalDecision,
 }
 
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct CostLimitRequestApprovalParams {
+    pub thread_id: String,
+    pub turn_id: String,
+    /// Estimated cost of the thread so far, in USD.
+    pub cost_usd: f64,
+    /// The configured `session_cost_limit_usd`.
+    pub limit_usd: f64,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct CostLimitRequestApprovalResponse {
+    /// `accept` runs this turn, `acceptForSession` also stops asking for the
+    /// rest of the thread, and anything else ends the turn.
+    pub decision: ApprovalDecision,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
 #[serde(rename_all = "camelCase")]
 #[ts(export_to = "v2/")]
@@ -0,0 +1,142 @@
+use std::path::PathBuf;
+
+use codex_app_server_protocol::MODEL_PRESET_SCHEMA_VERSION;
+use codex_app_server_protocol::Model;
+use codex_protocol::openai_models::ModelPreset;
+use codex_protocol::openai_models::ReasoningEffort;
+use codex_protocol::openai_models::ReasoningEffortPreset;
+use pretty_assertions::assert_eq;
+use serde_json::Value;
+
+fn golden_path(version: u32) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/fixtures/model")
+        .join(format!("v{version}.json"))
+}
+
+fn golden(version: u32) -> Value {
+    let path = golden_path(version);
+    let contents = std::fs::read_to_string(&path)
+        .unwrap_or_else(|err| panic!("failed to read {}: {err}", path.display()));
+    serde_json::from_str(&contents)
+        .unwrap_or_else(|err| panic!("{} is not JSON: {err}", path.display()))
+}
+
+/// A preset with every optional field set, so that fields added later show
+/// up in the serialized form next to the golden ones.
+fn full_preset() -> ModelPreset {
+    ModelPreset::builder("gateway/oca-large", "oca-large")
+        .display_name("OCA Large")
+        .description("Large model")
+        .supported_reasoning_efforts(vec![
+            ReasoningEffortPreset {
+                effort: ReasoningEffort::Low,
+                description: "Fast".to_string(),
+                max_output_tokens: Some(4_096),
+                request_timeout_secs: Some(30),
+                stream_idle_timeout_secs: None,
+            },
+            ReasoningEffortPreset {
+                effort: ReasoningEffort::High,
+                description: "Thorough".to_string(),
+                max_output_tokens: None,
+                request_timeout_secs: None,
+                stream_idle_timeout_secs: None,
+            },
+        ])
+        .default_reasoning_effort(ReasoningEffort::High)
+        .is_default(true)
+        .model_provider("gateway".to_string())
+        .context_window(272_000)
+        .requires_network(true)
+        .knowledge_cutoff("2025-01-31".to_string())
+        .build()
+        .expect("valid preset")
+}
+
+fn json_type(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+/// Every field of `golden` is in `actual` under the same name with the same
+/// JSON type, recursing into objects and into the first element of arrays.
+fn assert_compatible(golden: &Value, actual: &Value, at: &str) {
+    assert_eq!(
+        json_type(actual),
+        json_type(golden),
+        "{at} changed type: {actual}"
+    );
+    match (golden, actual) {
+        (Value::Object(golden), Value::Object(actual)) => {
+            for (key, golden_value) in golden {
+                let Some(actual_value) = actual.get(key) else {
+                    panic!("{at}.{key} is missing from {actual:?}");
+                };
+                assert_compatible(golden_value, actual_value, &format!("{at}.{key}"));
+            }
+        }
+        (Value::Array(golden), Value::Array(actual)) => {
+            if let (Some(golden_item), Some(actual_item)) = (golden.first(), actual.first()) {
+                assert_compatible(golden_item, actual_item, &format!("{at}[0]"));
+            }
+        }
+        _ => {}
+    }
+}
+
+#[test]
+fn the_serializer_still_writes_the_current_golden_layout() {
+    let golden = golden(MODEL_PRESET_SCHEMA_VERSION);
+    assert_eq!(
+        golden["presetSchemaVersion"],
+        Value::from(MODEL_PRESET_SCHEMA_VERSION),
+        "{} names another version",
+        golden_path(MODEL_PRESET_SCHEMA_VERSION).display()
+    );
+
+    let actual = serde_json::to_value(Model::from(full_preset())).expect("serialize");
+    assert_compatible(&golden, &actual, "model");
+    assert_eq!(
+        actual["presetSchemaVersion"],
+        Value::from(MODEL_PRESET_SCHEMA_VERSION)
+    );
+}
+
+#[test]
+fn every_schema_version_up_to_the_current_one_has_a_golden_file() {
+    for version in 1..=MODEL_PRESET_SCHEMA_VERSION {
+        assert!(
+            golden_path(version).is_file(),
+            "bumping MODEL_PRESET_SCHEMA_VERSION needs {}",
+            golden_path(version).display()
+        );
+    }
+    assert!(
+        !golden_path(MODEL_PRESET_SCHEMA_VERSION + 1).exists(),
+        "a golden file exists for a version this build does not write"
+    );
+}
+
+#[test]
+fn v1_json_still_deserializes() {
+    let v1 = golden(1);
+    let model: Model = serde_json::from_value(v1.clone()).expect("v1 deserializes");
+    assert_eq!(model.preset_schema_version, 1);
+    assert_eq!(serde_json::to_value(&model).expect("serialize"), v1);
+
+    // JSON written before the version field existed is version 1.
+    let mut unversioned = v1;
+    unversioned
+        .as_object_mut()
+        .expect("object")
+        .remove("presetSchemaVersion");
+    let model: Model = serde_json::from_value(unversioned).expect("unversioned deserializes");
+    assert_eq!(model.preset_schema_version, 1);
+}
@@ -15,6 +15,8 @@ use codex_app_server_protocol::CommandExecutionRequestApprovalParams;
 use codex_app_server_protocol::CommandExecutionRequestApprovalResponse;
 use codex_app_server_protocol::CommandExecutionStatus;
 use codex_app_server_protocol::ContextCompactedNotification;
+use codex_app_server_protocol::CostLimitRequestApprovalParams;
+use codex_app_server_protocol::CostLimitRequestApprovalResponse;
 use codex_app_server_protocol::DeprecationNoticeNotification;
 use codex_app_server_protocol::ErrorNotification;
 use codex_app_server_protocol::ExecCommandApprovalParams;
@@ -241,6 +243,20 @@ pub(crate) async fn apply_bespoke_event_handling(
                 });
             }
         },
+        EventMsg::CostLimitApprovalRequest(request) => {
+            let params = CostLimitRequestApprovalParams {
+                thread_id: conversation_id.to_string(),
+                turn_id: request.turn_id.clone(),
+                cost_usd: request.cost_usd,
+                limit_usd: request.limit_usd,
+            };
+            let rx = outgoing
+                .send_request(ServerRequestPayload::CostLimitRequestApproval(params))
+                .await;
+            tokio::spawn(async move {
+                on_cost_limit_approval_response(request.turn_id, rx, conversation).await;
+            });
+        }
         // TODO(celia): properly construct McpToolCall TurnItem in core.
         EventMsg::McpToolCallBegin(begin_event) => {
             let notification = construct_mcp_tool_call_notification(
@@ -1021,6 +1037,43 @@ async fn on_exec_approval_response(
     }
 }
 
+async fn on_cost_limit_approval_response(
+    turn_id: String,
+    receiver: oneshot::Receiver<JsonValue>,
+    conversation: Arc<CodexConversation>,
+) {
+    let decision = match receiver.await {
+        Ok(value) => serde_json::from_value::<CostLimitRequestApprovalResponse>(value)
+            .map(|response| match response.decision {
+                ApprovalDecision::Accept
+                | ApprovalDecision::AcceptWithExecpolicyAmendment { .. } => {
+                    ReviewDecision::Approved
+                }
+                ApprovalDecision::AcceptForSession => ReviewDecision::ApprovedForSession,
+                ApprovalDecision::Decline => ReviewDecision::Denied,
+                ApprovalDecision::Cancel => ReviewDecision::Abort,
+            })
+            .unwrap_or_else(|err| {
+                error!("failed to deserialize CostLimitRequestApprovalResponse: {err}");
+                ReviewDecision::Denied
+            }),
+        Err(err) => {
+            error!("request failed: {err:?}");
+            ReviewDecision::Denied
+        }
+    };
+
+    if let Err(err) = conversation
+        .submit(Op::CostLimitApproval {
+            id: turn_id,
+            decision,
+        })
+        .await
+    {
+        error!("failed to submit CostLimitApproval: {err}");
+    }
+}
+
 const REVIEW_FALLBACK_MESSAGE: &str = "Reviewer failed to output a response.";
 
 fn render_review_output_text(output: &ReviewOutputEvent) -> String {
@@ -1556,6 +1609,7 @@ mod tests {
                 total_tokens: 23,
             },
             model_context_window: Some(4096),
+            per_model_usage: Vec::new(),
         };
         let rate_limits = RateLimitSnapshot {
             primary: Some(RateLimitWindow {
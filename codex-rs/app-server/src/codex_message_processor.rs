@@ -2,6 +2,10 @@ use crate::bespoke_event_handling::apply_bespoke_event_handling;
 use crate::error_code::INTERNAL_ERROR_CODE;
 use crate::error_code::INVALID_REQUEST_ERROR_CODE;
 use crate::fuzzy_file_search::run_fuzzy_file_search;
+use crate::models::SupportedModels;
+use crate::models::added_models;
+use crate::models::default_model_changed;
+use crate::models::refreshed_model_count;
 use crate::models::supported_models;
 use crate::outgoing_message::OutgoingMessageSender;
 use crate::outgoing_message::OutgoingNotification;
@@ -24,7 +28,13 @@ use codex_app_server_protocol::CancelLoginChatGptResponse;
 use codex_app_server_protocol::ClientRequest;
 use codex_app_server_protocol::CommandExecParams;
 use codex_app_server_protocol::ConversationGitInfo;
+use codex_app_server_protocol::ConversationOptionField;
+use codex_app_server_protocol::ConversationOptionIssue;
+use codex_app_server_protocol::ConversationOptionIssueCode;
+use codex_app_server_protocol::ConversationOptionIssueSeverity;
 use codex_app_server_protocol::ConversationSummary;
+use codex_app_server_protocol::ConversationValidateOptionsParams;
+use codex_app_server_protocol::ConversationValidateOptionsResponse;
 use codex_app_server_protocol::ExecOneOffCommandResponse;
 use codex_app_server_protocol::FeedbackUploadParams;
 use codex_app_server_protocol::FeedbackUploadResponse;
@@ -61,6 +71,10 @@ use codex_app_server_protocol::McpServerOauthLoginResponse;
 use codex_app_server_protocol::McpServerStatus;
 use codex_app_server_protocol::ModelListParams;
 use codex_app_server_protocol::ModelListResponse;
+use codex_app_server_protocol::ModelListUpdatedNotification;
+use codex_app_server_protocol::ModelRefreshProgress;
+use codex_app_server_protocol::ModelRefreshProgressNotification;
+use codex_app_server_protocol::ModelRefreshResponse;
 use codex_app_server_protocol::NewConversationParams;
 use codex_app_server_protocol::NewConversationResponse;
 use codex_app_server_protocol::RemoveConversationListenerParams;
@@ -130,6 +144,12 @@ use codex_core::find_conversation_path_by_id_str;
 use codex_core::git_info::git_diff_to_remote;
 use codex_core::mcp::collect_mcp_snapshot;
 use codex_core::mcp::group_tools_by_server;
+use codex_core::models_manager::catalog::PresetCatalog;
+use codex_core::models_manager::catalog_handle::ModelCatalogHandle;
+use codex_core::models_manager::refresh_events::CatalogRefreshEvent;
+use codex_core::models_manager::refresh_events::RefreshReason;
+use codex_core::models_manager::validation::InvalidSelection;
+use codex_core::models_manager::validation::SelectionWarning;
 use codex_core::parse_cursor;
 use codex_core::protocol::EventMsg;
 use codex_core::protocol::Op;
@@ -145,12 +165,15 @@ use codex_login::ShutdownHandle;
 use codex_login::run_login_server;
 use codex_protocol::ConversationId;
 use codex_protocol::config_types::ForcedLoginMethod;
+use codex_protocol::config_types::SandboxMode as CoreSandboxMode;
 use codex_protocol::items::TurnItem;
 use codex_protocol::models::ResponseItem;
+use codex_protocol::openai_models::ReasoningEffort;
 use codex_protocol::protocol::GitInfo as CoreGitInfo;
 use codex_protocol::protocol::McpAuthStatus as CoreMcpAuthStatus;
 use codex_protocol::protocol::RateLimitSnapshot as CoreRateLimitSnapshot;
 use codex_protocol::protocol::RolloutItem;
+use codex_protocol::protocol::SandboxPolicy as CoreSandboxPolicy;
 use codex_protocol::protocol::SessionMetaLine;
 use codex_protocol::protocol::USER_MESSAGE_BEGIN;
 use codex_protocol::user_input::UserInput as CoreInputItem;
@@ -163,11 +186,13 @@ use std::io::Error as IoError;
 use std::path::Path;
 use std::path::PathBuf;
 use std::sync::Arc;
+use std::sync::OnceLock;
 use std::sync::atomic::AtomicBool;
 use std::sync::atomic::Ordering;
 use std::time::Duration;
 use tokio::select;
 use tokio::sync::Mutex;
+use tokio::sync::broadcast::error::RecvError;
 use tokio::sync::oneshot;
 use toml::Value as TomlValue;
 use tracing::error;
@@ -209,6 +234,12 @@ impl Drop for ActiveLogin {
 }
 
 /// Handles JSON-RPC messages for Codex conversations.
+/// What a loaded thread runs with, for checking the efforts its turns set.
+struct ThreadModel {
+    model: String,
+    config: Arc<Config>,
+}
+
 pub(crate) struct CodexMessageProcessor {
     auth_manager: Arc<AuthManager>,
     conversation_manager: Arc<ConversationManager>,
@@ -222,6 +253,15 @@ pub(crate) struct CodexMessageProcessor {
     pending_interrupts: PendingInterrupts,
     turn_summary_store: TurnSummaryStore,
     pending_fuzzy_searches: Arc<Mutex<HashMap<String, Arc<AtomicBool>>>>,
+    // The config each loaded conversation was started with and the model it
+    // last ran with, so efforts its turns override are checked against them.
+    thread_models: Arc<Mutex<HashMap<ConversationId, ThreadModel>>>,
+    // The task id of the `model/refresh` running now, which later requests
+    // join instead of starting another.
+    model_refresh: Arc<Mutex<Option<String>>>,
+    // Rebuilds the configured catalog, coalescing refreshes, sign-ins and
+    // config reloads; started on first use.
+    model_catalog: OnceLock<ModelCatalogHandle>,
     feedback: CodexFeedback,
 }
 
@@ -256,6 +296,59 @@ impl CodexMessageProcessor {
 
         Ok((conversation_id, conversation))
     }
+
+    /// Settle an effort a client asked for on `model`, or on the session model
+    /// of `config` when `None`, as the CLI does: one the model does not offer
+    /// is replaced by the closest it does, or rejected under
+    /// `strict_model_validation`.
+    async fn resolve_requested_effort(
+        &self,
+        config: &Config,
+        model: Option<&str>,
+        effort: ReasoningEffort,
+    ) -> Result<ReasoningEffort, JSONRPCErrorError> {
+        let models_manager = self.conversation_manager.models_manager_for(config);
+        let resolution = PresetCatalog::new(&models_manager, config)
+            .resolve_effort(model, effort)
+            .await
+            .map_err(|invalid| invalid_selection_error(&invalid))?;
+        if let Some(message) = &resolution.warning {
+            warn!("{message}");
+        }
+        Ok(resolution.effort)
+    }
+
+    async fn remember_thread(
+        &self,
+        conversation_id: ConversationId,
+        model: &str,
+        config: Arc<Config>,
+    ) {
+        self.thread_models.lock().await.insert(
+            conversation_id,
+            ThreadModel {
+                model: model.to_string(),
+                config,
+            },
+        );
+    }
+
+    async fn remember_thread_model(&self, conversation_id: ConversationId, model: &str) {
+        if let Some(thread) = self.thread_models.lock().await.get_mut(&conversation_id) {
+            thread.model = model.to_string();
+        }
+    }
+
+    /// The config `conversation_id` was started with, and the model it last
+    /// ran with. Threads loaded before this server tracked them fall back to
+    /// the server's config.
+    async fn thread_model(&self, conversation_id: ConversationId) -> (Arc<Config>, Option<String>) {
+        match self.thread_models.lock().await.get(&conversation_id) {
+            Some(thread) => (Arc::clone(&thread.config), Some(thread.model.clone())),
+            None => (Arc::clone(&self.config), None),
+        }
+    }
+
     pub fn new(
         auth_manager: Arc<AuthManager>,
         conversation_manager: Arc<ConversationManager>,
@@ -277,10 +370,106 @@ impl CodexMessageProcessor {
             pending_interrupts: Arc::new(Mutex::new(HashMap::new())),
             turn_summary_store: Arc::new(Mutex::new(HashMap::new())),
             pending_fuzzy_searches: Arc::new(Mutex::new(HashMap::new())),
+            thread_models: Arc::new(Mutex::new(HashMap::new())),
+            model_refresh: Arc::new(Mutex::new(None)),
+            model_catalog: OnceLock::new(),
             feedback,
         }
     }
 
+    /// Tell the client whenever the model catalog changes, so it can re-list
+    /// with `ifRevision` instead of polling. Each notification lists the
+    /// models the change added.
+    pub(crate) fn spawn_model_list_update_notifier(&self) {
+        let models_manager = self.conversation_manager.get_models_manager();
+        let mut revisions = models_manager.subscribe_catalog_revision();
+        let outgoing = Arc::clone(&self.outgoing);
+        // Listed as `model/list` lists, so the revision sent matches the one
+        // a re-list reports.
+        let mut config = (*self.config).clone();
+        config.features.enable(Feature::RemoteModels);
+        tokio::spawn(async move {
+            let mut previous = models_manager.known_models(&config).await;
+            let mut previous_revision = models_manager.listed_revision(&config).await;
+            while revisions.changed().await.is_ok() {
+                revisions.borrow_and_update();
+                let revision = models_manager.listed_revision(&config).await;
+                if revision == previous_revision {
+                    continue;
+                }
+                previous_revision = revision;
+                let current = models_manager.known_models(&config).await;
+                let added_models = added_models(&previous, &current);
+                previous = current;
+                outgoing
+                    .send_server_notification(ServerNotification::ModelListUpdated(
+                        ModelListUpdatedNotification {
+                            revision,
+                            added_models,
+                        },
+                    ))
+                    .await;
+            }
+        });
+    }
+
+    /// Keep the configured catalog rebuilt in the background from now on.
+    /// `model/refresh`, sign-ins, sign-outs and config writes all queue
+    /// rebuilds on it, so a burst of them makes one rebuild, which `model/list`
+    /// reports as `lastRebuild`.
+    pub(crate) fn spawn_model_catalog(&self) {
+        self.model_catalog();
+    }
+
+    fn model_catalog(&self) -> &ModelCatalogHandle {
+        self.model_catalog.get_or_init(|| {
+            // Listed as `model/list` lists.
+            let mut config = (*self.config).clone();
+            config.features.enable(Feature::RemoteModels);
+            ModelCatalogHandle::new(self.conversation_manager.get_models_manager(), config)
+        })
+    }
+
+    /// Rebuild the configured catalog with the config as written now, after
+    /// a config write that may have changed the provider or catalog sources.
+    pub(crate) async fn reload_model_catalog_config(&self) {
+        match self.load_latest_config().await {
+            Ok(mut config) => {
+                config.features.enable(Feature::RemoteModels);
+                self.model_catalog().reload_config(config);
+            }
+            Err(err) => warn!("not rebuilding the model catalog: {}", err.message),
+        }
+    }
+
+    /// Tell the client whenever a catalog refresh moves the default model,
+    /// which threads started without a `model` use.
+    pub(crate) fn spawn_default_model_notifier(&self) {
+        let mut events = self
+            .conversation_manager
+            .get_models_manager()
+            .subscribe_refresh_events();
+        let outgoing = Arc::clone(&self.outgoing);
+        tokio::spawn(async move {
+            loop {
+                match events.recv().await {
+                    Ok(event) => {
+                        if let Some(notification) = default_model_changed(event) {
+                            outgoing
+                                .send_server_notification(ServerNotification::ModelDefaultChanged(
+                                    notification,
+                                ))
+                                .await;
+                        }
+                    }
+                    // Missed events are gone; the next move is still sent.
+                    Err(RecvError::Lagged(_)) => {}
+                    Err(RecvError::Closed) => return,
+                }
+            }
+        });
+    }
+
     async fn load_latest_config(&self) -> Result<Config, JSONRPCErrorError> {
         Config::load_with_cli_overrides(self.cli_overrides.clone())
             .await
@@ -359,6 +548,9 @@ impl CodexMessageProcessor {
             ClientRequest::ThreadStart { request_id, params } => {
                 self.thread_start(request_id, params).await;
             }
+            ClientRequest::ConversationValidateOptions { request_id, params } => {
+                self.conversation_validate_options(request_id, params).await;
+            }
             ClientRequest::ThreadResume { request_id, params } => {
                 self.thread_resume(request_id, params).await;
             }
@@ -402,6 +594,12 @@ impl CodexMessageProcessor {
                         .await;
                 });
             }
+            ClientRequest::ModelRefresh {
+                request_id,
+                params: _,
+            } => {
+                self.model_refresh(request_id).await;
+            }
             ClientRequest::McpServerOauthLogin { request_id, params } => {
                 self.mcp_server_oauth_login(request_id, params).await;
             }
@@ -557,6 +755,7 @@ impl CodexMessageProcessor {
         ) {
             Ok(()) => {
                 self.auth_manager.reload();
+                self.model_catalog().auth_changed();
                 Ok(())
             }
             Err(err) => Err(JSONRPCErrorError {
@@ -666,6 +865,7 @@ impl CodexMessageProcessor {
                     let outgoing_clone = self.outgoing.clone();
                     let active_login = self.active_login.clone();
                     let auth_manager = self.auth_manager.clone();
+                    let model_catalog = self.model_catalog().clone();
                     let auth_url = server.auth_url.clone();
                     tokio::spawn(async move {
                         let (success, error_msg) = match tokio::time::timeout(
@@ -695,6 +895,7 @@ impl CodexMessageProcessor {
 
                         if success {
                             auth_manager.reload();
+                            model_catalog.auth_changed();
 
                             // Notify clients with the actual current auth mode.
                             let current_auth_method = auth_manager.auth().map(|a| a.mode);
@@ -756,6 +957,7 @@ impl CodexMessageProcessor {
                     let outgoing_clone = self.outgoing.clone();
                     let active_login = self.active_login.clone();
                     let auth_manager = self.auth_manager.clone();
+                    let model_catalog = self.model_catalog().clone();
                     let auth_url = server.auth_url.clone();
                     tokio::spawn(async move {
                         let (success, error_msg) = match tokio::time::timeout(
@@ -785,6 +987,7 @@ impl CodexMessageProcessor {
 
                         if success {
                             auth_manager.reload();
+                            model_catalog.auth_changed();
 
                             // Notify clients with the actual current auth mode.
                             let current_auth_method = auth_manager.auth().map(|a| a.mode);
@@ -897,6 +1100,7 @@ impl CodexMessageProcessor {
                 data: None,
             });
         }
+        self.model_catalog().auth_changed();
 
         // Reflect the current auth method after logout (likely None).
         Ok(self.auth_manager.auth().map(|auth| auth.mode))
@@ -1251,7 +1455,9 @@ impl CodexMessageProcessor {
             developer_instructions,
             compact_prompt,
             include_apply_patch_tool,
+            allow_unknown_model,
         } = params;
+        let model_requested = model.is_some();
 
         let overrides = ConfigOverrides {
             model,
@@ -1277,8 +1483,9 @@ impl CodexMessageProcessor {
                 serde_json::json!(true),
             );
         }
+        let effort_requested = cli_overrides.contains_key("model_reasoning_effort");
 
-        let config = match derive_config_from_params(overrides, Some(cli_overrides)).await {
+        let mut config = match derive_config_from_params(overrides, Some(cli_overrides)).await {
             Ok(config) => config,
             Err(err) => {
                 let error = JSONRPCErrorError {
@@ -1291,6 +1498,33 @@ impl CodexMessageProcessor {
             }
         };
 
+        // Reject a model or effort the catalog does not offer now, rather
+        // than on the first turn after the client has shown the conversation.
+        if !allow_unknown_model.unwrap_or(false) {
+            if model_requested {
+                let models_manager = self.conversation_manager.get_models_manager();
+                if let Err(invalid) = PresetCatalog::new(&models_manager, &config)
+                    .check_selection(config.model.as_deref(), None)
+                    .await
+                {
+                    self.outgoing
+                        .send_error(request_id, invalid_selection_error(&invalid))
+                        .await;
+                    return;
+                }
+            }
+            if let Some(effort) = config.model_reasoning_effort.filter(|_| effort_requested) {
+                match self.resolve_requested_effort(&config, None, effort).await {
+                    Ok(effort) => config.model_reasoning_effort = Some(effort),
+                    Err(error) => {
+                        self.outgoing.send_error(request_id, error).await;
+                        return;
+                    }
+                }
+            }
+        }
+
+        let thread_config = Arc::new(config.clone());
         match self.conversation_manager.new_conversation(config).await {
             Ok(conversation_id) => {
                 let NewConversation {
@@ -1298,6 +1532,8 @@ impl CodexMessageProcessor {
                     session_configured,
                     ..
                 } = conversation_id;
+                self.remember_thread(conversation_id, &session_configured.model, thread_config)
+                    .await;
                 let response = NewConversationResponse {
                     conversation_id,
                     model: session_configured.model,
@@ -1318,6 +1554,10 @@ impl CodexMessageProcessor {
     }
 
     async fn thread_start(&mut self, request_id: RequestId, params: ThreadStartParams) {
+        let effort_requested = params
+            .config
+            .as_ref()
+            .is_some_and(|config| config.contains_key("model_reasoning_effort"));
         let overrides = self.build_thread_config_overrides(
             params.model,
             params.model_provider,
@@ -1328,7 +1568,7 @@ impl CodexMessageProcessor {
             params.developer_instructions,
         );
 
-        let config = match derive_config_from_params(overrides, params.config).await {
+        let mut config = match derive_config_from_params(overrides, params.config).await {
             Ok(config) => config,
             Err(err) => {
                 let error = JSONRPCErrorError {
@@ -1340,7 +1580,28 @@ impl CodexMessageProcessor {
                 return;
             }
         };
+        if let Some(base_url) = params.catalog_base_url.as_deref()
+            && let Err(err) = config.override_catalog_base_url(base_url)
+        {
+            let error = JSONRPCErrorError {
+                code: INVALID_REQUEST_ERROR_CODE,
+                message: format!("invalid catalogBaseUrl: {err}"),
+                data: None,
+            };
+            self.outgoing.send_error(request_id, error).await;
+            return;
+        }
+        if let Some(effort) = config.model_reasoning_effort.filter(|_| effort_requested) {
+            match self.resolve_requested_effort(&config, None, effort).await {
+                Ok(effort) => config.model_reasoning_effort = Some(effort),
+                Err(error) => {
+                    self.outgoing.send_error(request_id, error).await;
+                    return;
+                }
+            }
+        }
 
+        let thread_config = Arc::new(config.clone());
         match self.conversation_manager.new_conversation(config).await {
             Ok(new_conv) => {
                 let NewConversation {
@@ -1348,6 +1609,8 @@ impl CodexMessageProcessor {
                     session_configured,
                     ..
                 } = new_conv;
+                self.remember_thread(conversation_id, &session_configured.model, thread_config)
+                    .await;
                 let rollout_path = session_configured.rollout_path.clone();
                 let fallback_provider = self.config.model_provider_id.as_str();
 
@@ -1427,6 +1690,91 @@ impl CodexMessageProcessor {
     }
 
     #[allow(clippy::too_many_arguments)]
+    /// Run the checks a `thread/start` with `params` would go through, and
+    /// report every issue found instead of failing on the first. No thread
+    /// is started.
+    async fn conversation_validate_options(
+        &self,
+        request_id: RequestId,
+        params: ConversationValidateOptionsParams,
+    ) {
+        let ConversationValidateOptionsParams {
+            model,
+            model_provider,
+            cwd,
+            effort,
+            approval_policy,
+            sandbox,
+            config,
+            required_capabilities,
+        } = params;
+        let overrides = self.build_thread_config_overrides(
+            model,
+            model_provider,
+            cwd,
+            approval_policy,
+            None,
+            None,
+            None,
+        );
+
+        // The sandbox is checked against the admin requirements on its own,
+        // so a disallowed one is reported as such and the rest still checked.
+        let mut issues = Vec::new();
+        let mut derived = derive_config_from_params(overrides.clone(), config.clone()).await;
+        if let (Some(sandbox), Ok(base)) = (sandbox, &derived) {
+            match disallowed_sandbox_issue(base, sandbox.to_core()) {
+                Some(issue) => issues.push(issue),
+                None => {
+                    let overrides = ConfigOverrides {
+                        sandbox_mode: Some(sandbox.to_core()),
+                        ..overrides
+                    };
+                    derived = derive_config_from_params(overrides, config).await;
+                }
+            }
+        }
+
+        let config = match derived {
+            Ok(config) => config,
+            Err(err) => {
+                let response = ConversationValidateOptionsResponse {
+                    valid: false,
+                    model: None,
+                    issues: vec![ConversationOptionIssue {
+                        severity: ConversationOptionIssueSeverity::Error,
+                        code: ConversationOptionIssueCode::InvalidConfig,
+                        field: None,
+                        message: format!("error deriving config: {err}"),
+                        suggestions: Vec::new(),
+                    }],
+                };
+                self.outgoing.send_response(request_id, response).await;
+                return;
+            }
+        };
+
+        let models_manager = self.conversation_manager.get_models_manager();
+        let report = PresetCatalog::new(&models_manager, &config)
+            .validate_options(effort, &required_capabilities)
+            .await;
+        issues.extend(
+            report
+                .errors
+                .iter()
+                .map(invalid_selection_issue)
+                .chain(report.warnings.iter().map(selection_warning_issue)),
+        );
+        let response = ConversationValidateOptionsResponse {
+            valid: issues
+                .iter()
+                .all(|issue| issue.severity != ConversationOptionIssueSeverity::Error),
+            issues,
+            model: Some(report.model),
+        };
+        self.outgoing.send_response(request_id, response).await;
+    }
+
     fn build_thread_config_overrides(
         &self,
         model: Option<String>,
@@ -1661,6 +2009,7 @@ impl CodexMessageProcessor {
 
         let fallback_model_provider = config.model_provider_id.clone();
 
+        let thread_config = Arc::new(config.clone());
         match self
             .conversation_manager
             .resume_conversation_with_history(
@@ -1675,6 +2024,8 @@ impl CodexMessageProcessor {
                 session_configured,
                 ..
             }) => {
+                self.remember_thread(conversation_id, &session_configured.model, thread_config)
+                    .await;
                 let SessionConfiguredEvent {
                     rollout_path,
                     initial_messages,
@@ -1879,6 +2230,10 @@ impl CodexMessageProcessor {
                         session_meta_line.git.as_ref(),
                         fallback_provider.as_str(),
                     )
+                    .map(|summary| ConversationSummary {
+                        model: it.model,
+                        ..summary
+                    })
                 })
                 .collect::<Vec<_>>();
             if filtered.len() > remaining {
@@ -1912,9 +2267,31 @@ impl CodexMessageProcessor {
             }
         }
 
+        self.attach_model_display_names(&mut items);
         Ok((items, next_cursor))
     }
 
+    /// Name each listed conversation's model from the catalog already
+    /// loaded, falling back to the slug for models it does not know.
+    fn attach_model_display_names(&self, items: &mut [ConversationSummary]) {
+        let models_manager = self.conversation_manager.get_models_manager();
+        let slugs: Vec<&str> = items
+            .iter()
+            .filter_map(|item| item.model.as_deref())
+            .collect();
+        let names: HashMap<String, String> = PresetCatalog::new(&models_manager, &self.config)
+            .find_many(&slugs)
+            .into_iter()
+            .map(|(slug, preset)| (slug.to_string(), preset.display_name))
+            .collect();
+        for item in items {
+            item.model_display_name = item
+                .model
+                .as_ref()
+                .map(|slug| names.get(slug).unwrap_or(slug).clone());
+        }
+    }
+
     async fn list_models(
         outgoing: Arc<OutgoingMessageSender>,
         conversation_manager: Arc<ConversationManager>,
@@ -1922,16 +2299,63 @@ impl CodexMessageProcessor {
         request_id: RequestId,
         params: ModelListParams,
     ) {
-        let ModelListParams { limit, cursor } = params;
+        let ModelListParams {
+            limit,
+            cursor,
+            offset,
+            query,
+            if_revision,
+            capability,
+            catalog_base_url,
+        } = params;
+        if cursor.is_some() && offset.is_some() {
+            let error = JSONRPCErrorError {
+                code: INVALID_REQUEST_ERROR_CODE,
+                message: "cursor and offset cannot both be set".to_string(),
+                data: None,
+            };
+            outgoing.send_error(request_id, error).await;
+            return;
+        }
         let mut config = (*config).clone();
         config.features.enable(Feature::RemoteModels);
-        let models = supported_models(conversation_manager, &config).await;
+        if let Some(base_url) = catalog_base_url.as_deref()
+            && let Err(err) = config.override_catalog_base_url(base_url)
+        {
+            let error = JSONRPCErrorError {
+                code: INVALID_REQUEST_ERROR_CODE,
+                message: format!("invalid catalogBaseUrl: {err}"),
+                data: None,
+            };
+            outgoing.send_error(request_id, error).await;
+            return;
+        }
+        let SupportedModels {
+            revision,
+            catalog_auth,
+            warnings,
+            client_version_notice,
+            last_rebuild,
+            models,
+        } = supported_models(conversation_manager, &config, capability, query.as_deref()).await;
         let total = models.len();
+        let total_count = u32::try_from(total).unwrap_or(u32::MAX);
 
-        if total == 0 {
+        // A page is only one part of the listing, so `ifRevision` is only
+        // honoured for whole listings.
+        let paged = limit.is_some() || cursor.is_some() || offset.is_some();
+        let not_modified = !paged && if_revision == Some(revision);
+        if total == 0 || not_modified {
             let response = ModelListResponse {
                 data: Vec::new(),
                 next_cursor: None,
+                total_count,
+                revision,
+                not_modified,
+                catalog_auth,
+                warnings,
+                client_version_notice,
+                last_rebuild,
             };
             outgoing.send_response(request_id, response).await;
             return;
@@ -1952,13 +2376,14 @@ impl CodexMessageProcessor {
                     return;
                 }
             },
-            None => 0,
+            None => offset.unwrap_or(0) as usize,
         };
 
         if start > total {
+            let param = if offset.is_some() { "offset" } else { "cursor" };
             let error = JSONRPCErrorError {
                 code: INVALID_REQUEST_ERROR_CODE,
-                message: format!("cursor {start} exceeds total models {total}"),
+                message: format!("{param} {start} exceeds total models {total}"),
                 data: None,
             };
             outgoing.send_error(request_id, error).await;
@@ -1975,10 +2400,132 @@ impl CodexMessageProcessor {
         let response = ModelListResponse {
             data: items,
             next_cursor,
+            total_count,
+            revision,
+            not_modified: false,
+            catalog_auth,
+            warnings,
+            client_version_notice,
+            last_rebuild,
         };
         outgoing.send_response(request_id, response).await;
     }
 
+    /// Start re-fetching every catalog in the background, or join the refresh
+    /// already running, and report its steps as `model/refresh/progress`.
+    /// The refreshed list reaches clients through `model/list/updated`.
+    async fn model_refresh(&self, request_id: RequestId) {
+        let mut config = (*self.config).clone();
+        config.features.enable(Feature::RemoteModels);
+        if config.offline {
+            let error = JSONRPCErrorError {
+                code: INVALID_REQUEST_ERROR_CODE,
+                message: "model/refresh is unavailable in offline mode".to_string(),
+                data: None,
+            };
+            self.outgoing.send_error(request_id, error).await;
+            return;
+        }
+
+        let mut running = self.model_refresh.lock().await;
+        if let Some(task_id) = running.as_ref() {
+            let response = ModelRefreshResponse {
+                task_id: task_id.clone(),
+                already_running: true,
+            };
+            self.outgoing.send_response(request_id, response).await;
+            return;
+        }
+        let task_id = Uuid::new_v4().to_string();
+        *running = Some(task_id.clone());
+        drop(running);
+        let response = ModelRefreshResponse {
+            task_id: task_id.clone(),
+            already_running: false,
+        };
+        self.outgoing.send_response(request_id, response).await;
+
+        let outgoing = Arc::clone(&self.outgoing);
+        let models_manager = self.conversation_manager.models_manager_for(&config);
+        let running = Arc::clone(&self.model_refresh);
+        // Queued now, so it joins any rebuild a sign-in or config write just
+        // queued rather than running after it.
+        let rebuild = self.model_catalog().refresh_and_wait();
+        tokio::spawn(async move {
+            let notification = |progress| {
+                ServerNotification::ModelRefreshProgress(ModelRefreshProgressNotification {
+                    task_id: task_id.clone(),
+                    progress,
+                })
+            };
+            // `Started` is sent when the catalog layer reports the refresh
+            // starting, or once it ended if that lossy report was missed. The
+            // outcome comes from the refresh itself, since the layer's events
+            // do not say which refresh they end when a background one overlaps.
+            let mut events = models_manager.subscribe_refresh_events();
+            let refresh = async {
+                match rebuild.await.error {
+                    Some(message) => Err(message),
+                    None => refreshed_model_count(&models_manager, &config).await,
+                }
+            };
+            tokio::pin!(refresh);
+            let mut started = false;
+            let result = loop {
+                tokio::select! {
+                    result = &mut refresh => break result,
+                    event = events.recv() => {
+                        if !started
+                            && matches!(
+                                event,
+                                Ok(CatalogRefreshEvent::RefreshStarted {
+                                    reason: RefreshReason::Manual
+                                })
+                            )
+                        {
+                            started = true;
+                            outgoing
+                                .send_server_notification(notification(
+                                    ModelRefreshProgress::Started {},
+                                ))
+                                .await;
+                        }
+                    }
+                }
+            };
+            if !started {
+                outgoing
+                    .send_server_notification(notification(ModelRefreshProgress::Started {}))
+                    .await;
+            }
+            // Cleared before the last notification, so a client may start
+            // the next refresh as soon as it sees this one end.
+            *running.lock().await = None;
+            match result {
+                Ok(model_count) => {
+                    outgoing
+                        .send_server_notification(notification(ModelRefreshProgress::Fetched {
+                            model_count,
+                        }))
+                        .await;
+                    let revision = models_manager.listed_revision(&config).await;
+                    outgoing
+                        .send_server_notification(notification(ModelRefreshProgress::Completed {
+                            revision,
+                        }))
+                        .await;
+                }
+                Err(message) => {
+                    outgoing
+                        .send_server_notification(notification(ModelRefreshProgress::Failed {
+                            message,
+                        }))
+                        .await;
+                }
+            }
+        });
+    }
+
     async fn mcp_server_oauth_login(
         &self,
         request_id: RequestId,
@@ -2202,6 +2749,7 @@ impl CodexMessageProcessor {
                     developer_instructions,
                     compact_prompt,
                     include_apply_patch_tool,
+                    allow_unknown_model: _,
                 } = overrides;
 
                 // Persist windows sandbox feature.
@@ -2312,6 +2860,7 @@ impl CodexMessageProcessor {
             }
         };
 
+        let thread_config = Arc::new(config.clone());
         match self
             .conversation_manager
             .resume_conversation_with_history(
@@ -2326,6 +2875,8 @@ impl CodexMessageProcessor {
                 session_configured,
                 ..
             }) => {
+                self.remember_thread(conversation_id, &session_configured.model, thread_config)
+                    .await;
                 self.outgoing
                     .send_server_notification(ServerNotification::SessionConfigured(
                         SessionConfiguredNotification {
@@ -2479,6 +3030,7 @@ impl CodexMessageProcessor {
             .await
         {
             info!("conversation {conversation_id} was active; shutting down");
+            self.thread_models.lock().await.remove(&conversation_id);
             let conversation_clone = conversation.clone();
             let notify = Arc::new(tokio::sync::Notify::new());
             let notify_clone = notify.clone();
@@ -2613,6 +3165,21 @@ impl CodexMessageProcessor {
             self.outgoing.send_error(request_id, error).await;
             return;
         };
+        let (thread_config, _) = self.thread_model(conversation_id).await;
+        let effort = match effort {
+            Some(effort) => match self
+                .resolve_requested_effort(&thread_config, Some(&model), effort)
+                .await
+            {
+                Ok(effort) => Some(effort),
+                Err(error) => {
+                    self.outgoing.send_error(request_id, error).await;
+                    return;
+                }
+            },
+            None => None,
+        };
+        self.remember_thread_model(conversation_id, &model).await;
 
         let mapped_items: Vec<CoreInputItem> = items
             .into_iter()
@@ -2700,14 +3267,38 @@ impl CodexMessageProcessor {
         let _ = conversation.submit(Op::Interrupt).await;
     }
 
-    async fn turn_start(&self, request_id: RequestId, params: TurnStartParams) {
-        let (_, conversation) = match self.conversation_from_thread_id(&params.thread_id).await {
-            Ok(v) => v,
-            Err(error) => {
-                self.outgoing.send_error(request_id, error).await;
-                return;
+    async fn turn_start(&self, request_id: RequestId, mut params: TurnStartParams) {
+        let (conversation_id, conversation) =
+            match self.conversation_from_thread_id(&params.thread_id).await {
+                Ok(v) => v,
+                Err(error) => {
+                    self.outgoing.send_error(request_id, error).await;
+                    return;
+                }
+            };
+
+        // An effort is checked against the thread's own config and the model
+        // the turn will run: the one it names, else the one the thread last
+        // ran with, if known.
+        let (thread_config, thread_model) = self.thread_model(conversation_id).await;
+        let model = params.model.clone().or(thread_model);
+        if let Some(effort) = params.effort
+            && let Some(model) = &model
+        {
+            match self
+                .resolve_requested_effort(&thread_config, Some(model), effort)
+                .await
+            {
+                Ok(effort) => params.effort = Some(effort),
+                Err(error) => {
+                    self.outgoing.send_error(request_id, error).await;
+                    return;
+                }
             }
-        };
+        }
+        if let Some(model) = &params.model {
+            self.remember_thread_model(conversation_id, model).await;
+        }
 
         // Map v2 input items to core input items.
         let mapped_items: Vec<CoreInputItem> = params
@@ -2731,6 +3322,7 @@ impl CodexMessageProcessor {
                     approval_policy: params.approval_policy.map(AskForApproval::to_core),
                     sandbox_policy: params.sandbox_policy.map(|p| p.to_core()),
                     model: params.model,
+                    model_provider: None,
                     effort: params.effort.map(Some),
                     summary: params.summary,
                 })
@@ -2877,6 +3469,7 @@ impl CodexMessageProcessor {
 
         let mut config = self.config.as_ref().clone();
         config.model = Some(self.config.review_model.clone());
+        let thread_config = Arc::new(config.clone());
 
         let NewConversation {
             conversation_id,
@@ -2892,6 +3485,8 @@ impl CodexMessageProcessor {
                 message: format!("error creating detached review conversation: {err}"),
                 data: None,
             })?;
+        self.remember_thread(conversation_id, &session_configured.model, thread_config)
+            .await;
 
         if let Err(err) = self
             .attach_conversation_listener(conversation_id, false, ApiVersion::V2)
@@ -3340,6 +3935,171 @@ fn errors_to_info(
         .collect()
 }
 
+/// The error for a request naming a model or effort the catalog does not
+/// offer. `data` carries the valid choices so clients can
+/// offer them without another round trip.
+fn invalid_selection_error(invalid: &InvalidSelection) -> JSONRPCErrorError {
+    let data = match invalid {
+        InvalidSelection::UnknownModel {
+            model,
+            known,
+            suggestions,
+            ..
+        } => serde_json::json!({
+            "model": model,
+            "validIds": known,
+            "suggestions": suggestions,
+        }),
+        InvalidSelection::UnsupportedEffort {
+            model,
+            effort,
+            supported,
+        } => serde_json::json!({
+            "model": model,
+            "effort": effort,
+            "supportedEfforts": supported,
+        }),
+        InvalidSelection::DisabledEffort { effort } => serde_json::json!({
+            "effort": effort,
+            "disabledBy": "disabled_reasoning_efforts",
+        }),
+        InvalidSelection::MissingCapability {
+            model,
+            capability,
+            supporting,
+        } => serde_json::json!({
+            "model": model,
+            "capability": capability,
+            "supportingModels": supporting,
+        }),
+    };
+    JSONRPCErrorError {
+        code: INVALID_REQUEST_ERROR_CODE,
+        message: invalid.to_string(),
+        data: Some(data),
+    }
+}
+
+/// The `conversation/validateOptions` issue for a model or effort `thread/start`
+/// would reject.
+fn invalid_selection_issue(invalid: &InvalidSelection) -> ConversationOptionIssue {
+    let (code, field, suggestions) = match invalid {
+        InvalidSelection::UnknownModel { suggestions, .. } => (
+            ConversationOptionIssueCode::UnknownModel,
+            ConversationOptionField::Model,
+            suggestions.clone(),
+        ),
+        InvalidSelection::UnsupportedEffort { supported, .. } => (
+            ConversationOptionIssueCode::UnsupportedEffort,
+            ConversationOptionField::Effort,
+            supported.iter().map(ToString::to_string).collect(),
+        ),
+        InvalidSelection::DisabledEffort { .. } => (
+            ConversationOptionIssueCode::UnsupportedEffort,
+            ConversationOptionField::Effort,
+            Vec::new(),
+        ),
+        InvalidSelection::MissingCapability { supporting, .. } => (
+            ConversationOptionIssueCode::MissingCapability,
+            ConversationOptionField::Model,
+            supporting.clone(),
+        ),
+    };
+    ConversationOptionIssue {
+        severity: ConversationOptionIssueSeverity::Error,
+        code,
+        field: Some(field),
+        message: invalid.to_string(),
+        suggestions,
+    }
+}
+
+/// The `conversation/validateOptions` issue for a `sandbox` the admin
+/// requirements in `config` do not allow, suggesting the modes they do.
+fn disallowed_sandbox_issue(
+    config: &Config,
+    sandbox: CoreSandboxMode,
+) -> Option<ConversationOptionIssue> {
+    let allows = |mode| {
+        let policy = match mode {
+            CoreSandboxMode::ReadOnly => CoreSandboxPolicy::new_read_only_policy(),
+            CoreSandboxMode::WorkspaceWrite => CoreSandboxPolicy::new_workspace_write_policy(),
+            CoreSandboxMode::DangerFullAccess => CoreSandboxPolicy::DangerFullAccess,
+        };
+        config.sandbox_policy.can_set(&policy).is_ok()
+    };
+    if allows(sandbox) {
+        return None;
+    }
+    let allowed: Vec<String> = [
+        CoreSandboxMode::ReadOnly,
+        CoreSandboxMode::WorkspaceWrite,
+        CoreSandboxMode::DangerFullAccess,
+    ]
+    .into_iter()
+    .filter(|&mode| allows(mode))
+    .map(|mode| mode.to_string())
+    .collect();
+    Some(ConversationOptionIssue {
+        severity: ConversationOptionIssueSeverity::Error,
+        code: ConversationOptionIssueCode::DisallowedSandbox,
+        field: Some(ConversationOptionField::Sandbox),
+        message: format!(
+            "sandbox `{sandbox}` is not allowed by the admin requirements; they allow {}.",
+            allowed
+                .iter()
+                .map(|mode| format!("`{mode}`"))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+        suggestions: allowed,
+    })
+}
+
+/// The `conversation/validateOptions` issue for something a thread would start
+/// with anyway.
+fn selection_warning_issue(warning: &SelectionWarning) -> ConversationOptionIssue {
+    let (code, field, suggestions) = match warning {
+        SelectionWarning::RetiredModel { successor, .. } => (
+            ConversationOptionIssueCode::RetiredModel,
+            ConversationOptionField::Model,
+            vec![successor.clone()],
+        ),
+        SelectionWarning::UnavailableForAccount { .. } => (
+            ConversationOptionIssueCode::UnavailableForAccount,
+            ConversationOptionField::Model,
+            Vec::new(),
+        ),
+        SelectionWarning::RequiresChatgptAuth { .. } => (
+            ConversationOptionIssueCode::RequiresChatgptAuth,
+            ConversationOptionField::Model,
+            Vec::new(),
+        ),
+        SelectionWarning::EffortClamped { effort, .. } => (
+            ConversationOptionIssueCode::EffortClamped,
+            ConversationOptionField::Effort,
+            vec![effort.to_string()],
+        ),
+        SelectionWarning::EffortIgnored { .. } => (
+            ConversationOptionIssueCode::EffortIgnored,
+            ConversationOptionField::Effort,
+            Vec::new(),
+        ),
+        SelectionWarning::RequiresNetwork { .. } => (
+            ConversationOptionIssueCode::RequiresNetwork,
+            ConversationOptionField::Model,
+            Vec::new(),
+        ),
+    };
+    ConversationOptionIssue {
+        severity: ConversationOptionIssueSeverity::Warning,
+        code,
+        field: Some(field),
+        message: warning.to_string(),
+        suggestions,
+    }
+}
+
 async fn derive_config_from_params(
     overrides: ConfigOverrides,
     cli_overrides: Option<HashMap<String, serde_json::Value>>,
@@ -3409,6 +4169,8 @@ async fn read_summary_from_rollout(
         cli_version: session_meta.cli_version,
         source: session_meta.source,
         git_info,
+        model: None,
+        model_display_name: None,
     })
 }
 
@@ -3454,6 +4216,8 @@ fn extract_conversation_summary(
         cli_version: session_meta.cli_version.clone(),
         source: session_meta.source.clone(),
         git_info,
+        model: None,
+        model_display_name: None,
     })
 }
 
@@ -3484,6 +4248,7 @@ fn summary_to_thread(summary: ConversationSummary) -> Thread {
         cli_version,
         source,
         git_info,
+        ..
     } = summary;
 
     let created_at = parse_datetime(timestamp.as_deref());
@@ -3566,6 +4331,8 @@ mod tests {
             cli_version: "0.0.0".to_string(),
             source: SessionSource::VSCode,
             git_info: None,
+            model: None,
+            model_display_name: None,
         };
 
         assert_eq!(summary, expected);
@@ -3614,6 +4381,8 @@ mod tests {
             cli_version: String::new(),
             source: SessionSource::VSCode,
             git_info: None,
+            model: None,
+            model_display_name: None,
         };
 
         assert_eq!(summary, expected);
@@ -128,6 +128,10 @@ impl MessageProcessor {
                     self.outgoing.send_response(request_id, response).await;
 
                     self.initialized = true;
+                    self.codex_message_processor.spawn_model_catalog();
+                    self.codex_message_processor
+                        .spawn_model_list_update_notifier();
+                    self.codex_message_processor.spawn_default_model_notifier();
 
                     return;
                 }
@@ -192,7 +196,12 @@ impl MessageProcessor {
         params: ConfigValueWriteParams,
     ) {
         match self.config_api.write_value(params).await {
-            Ok(response) => self.outgoing.send_response(request_id, response).await,
+            Ok(response) => {
+                self.outgoing.send_response(request_id, response).await;
+                self.codex_message_processor
+                    .reload_model_catalog_config()
+                    .await;
+            }
             Err(error) => self.outgoing.send_error(request_id, error).await,
         }
     }
@@ -203,7 +212,12 @@ impl MessageProcessor {
         params: ConfigBatchWriteParams,
     ) {
         match self.config_api.batch_write(params).await {
-            Ok(response) => self.outgoing.send_response(request_id, response).await,
+            Ok(response) => {
+                self.outgoing.send_response(request_id, response).await;
+                self.codex_message_processor
+                    .reload_model_catalog_config()
+                    .await;
+            }
             Err(error) => self.outgoing.send_error(request_id, error).await,
         }
     }
@@ -1,46 +1,291 @@
+use std::collections::HashSet;
 use std::sync::Arc;
 
 use codex_app_server_protocol::Model;
-use codex_app_server_protocol::ReasoningEffortOption;
+use codex_app_server_protocol::ModelCatalogAuth;
+use codex_app_server_protocol::ModelCatalogCredentialSource;
+use codex_app_server_protocol::ModelCatalogRebuild;
+use codex_app_server_protocol::ModelCatalogWarning;
+use codex_app_server_protocol::ModelClientVersionNotice;
+use codex_app_server_protocol::ModelDefaultChangedNotification;
+use codex_common::search_model_presets;
 use codex_core::ConversationManager;
 use codex_core::config::Config;
+use codex_core::models_manager::client_version::ClientVersionNotice;
+use codex_core::models_manager::diff::diff_catalogs;
+use codex_core::models_manager::manager::ModelList;
+use codex_core::models_manager::manager::ModelsManager;
+use codex_core::models_manager::manager::content_revision;
+use codex_core::models_manager::provenance::CatalogAuth;
+use codex_core::models_manager::provenance::CatalogSource;
+use codex_core::models_manager::provenance::CatalogWarning;
+use codex_core::models_manager::provenance::CredentialSource;
+use codex_core::models_manager::provenance::PresetSource;
+use codex_core::models_manager::rebuild_queue::CatalogRebuild;
+use codex_core::models_manager::refresh_events::CatalogRefreshEvent;
+use codex_protocol::openai_models::Capability;
 use codex_protocol::openai_models::ModelPreset;
-use codex_protocol::openai_models::ReasoningEffortPreset;
 
+/// What `model/list` reports about the catalog.
+pub struct SupportedModels {
+    /// Catalog revision the models were listed at.
+    pub revision: u64,
+    /// Credentials the catalog was requested with.
+    pub catalog_auth: ModelCatalogAuth,
+    /// Catalogs that failed to refresh.
+    pub warnings: Vec<ModelCatalogWarning>,
+    /// Set when the catalog recommends a newer client than this one.
+    pub client_version_notice: Option<ModelClientVersionNotice>,
+    /// The last rebuild of the catalog's background refresher, if it has one.
+    pub last_rebuild: Option<ModelCatalogRebuild>,
+    pub models: Vec<Model>,
+}
+
+/// The models to offer clients, only those with `capability` when it is set
+/// and those matching `query`, best match first, when that is.
+/// A config pointed at a tenant gateway lists that tenant's catalog.
 pub async fn supported_models(
     conversation_manager: Arc<ConversationManager>,
     config: &Config,
-) -> Vec<Model> {
-    conversation_manager
-        .list_models(config)
+    capability: Option<Capability>,
+    query: Option<&str>,
+) -> SupportedModels {
+    let ModelList {
+        mut revision,
+        auth,
+        warnings,
+        models,
+        mut extra,
+        client_version_notice,
+        last_rebuild,
+        ..
+    } = conversation_manager
+        .models_manager_for(config)
+        .model_list(config)
+        .await;
+    let models = match capability {
+        Some(capability) => models
+            .into_iter()
+            .filter(|preset| preset.supports(capability))
+            .collect(),
+        None => models,
+    };
+    let models = match query {
+        Some(query) => search_model_presets(&models, query)
+            .into_iter()
+            .cloned()
+            .collect(),
+        None => models,
+    };
+    // A filtered listing is revised by what it kept, so `ifRevision` from
+    // one filter never vouches for another's models.
+    if capability.is_some() || query.is_some() {
+        revision = content_revision(&auth, &models);
+    }
+    SupportedModels {
+        revision,
+        catalog_auth: catalog_auth_from_core(auth),
+        warnings: warnings
+            .into_iter()
+            .map(catalog_warning_from_core)
+            .collect(),
+        client_version_notice: client_version_notice.map(client_version_notice_from_core),
+        last_rebuild: last_rebuild.map(catalog_rebuild_from_core),
+        models: models
+            .into_iter()
+            .map(|preset| {
+                let extra = extra.remove(&preset.id).unwrap_or_default();
+                Model {
+                    extra,
+                    ..Model::from(preset)
+                }
+            })
+            .collect(),
+    }
+}
+
+fn catalog_rebuild_from_core(rebuild: CatalogRebuild) -> ModelCatalogRebuild {
+    let CatalogRebuild {
+        reasons,
+        revision,
+        finished_at,
+        error,
+    } = rebuild;
+    ModelCatalogRebuild {
+        reasons: reasons
+            .into_iter()
+            .map(|reason| reason.as_str().to_string())
+            .collect(),
+        revision: revision.0,
+        finished_at: finished_at.timestamp(),
+        error,
+    }
+}
+
+fn client_version_notice_from_core(notice: ClientVersionNotice) -> ModelClientVersionNotice {
+    let message = notice.to_string();
+    let ClientVersionNotice {
+        min_client_version,
+        client_version,
+    } = notice;
+    ModelClientVersionNotice {
+        min_client_version,
+        client_version,
+        message,
+    }
+}
+
+/// Count the models the catalogs served after a refresh, leaving out
+/// built-in and bundled ones. A refresh whose active provider serves a
+/// `model_catalog` that could not be listed fails.
+pub async fn refreshed_model_count(
+    models_manager: &ModelsManager,
+    config: &Config,
+) -> Result<u32, String> {
+    if config.model_provider.model_catalog.is_some()
+        && let Some(warning) = models_manager
+            .catalog_warnings()
+            .await
+            .into_iter()
+            .find(|warning| warning.provider_id.as_ref() == Some(&config.model_provider_id))
+    {
+        return Err(warning.to_string());
+    }
+    let fetched = models_manager
+        .catalog_status(config)
         .await
+        .presets
         .into_iter()
-        .map(model_from_preset)
-        .collect()
+        .filter(|preset| {
+            !matches!(
+                preset.source,
+                PresetSource::Builtin
+                    | PresetSource::Remote {
+                        source: CatalogSource::Bundled
+                    }
+            )
+        })
+        .count();
+    Ok(u32::try_from(fetched).unwrap_or(u32::MAX))
+}
+
+fn catalog_warning_from_core(warning: CatalogWarning) -> ModelCatalogWarning {
+    let message = warning.to_string();
+    let CatalogWarning {
+        provider_id,
+        fetched_at,
+        failed_at,
+        error,
+        unauthorized: _,
+        token_attempts: _,
+    } = warning;
+    ModelCatalogWarning {
+        provider_id,
+        fetched_at: fetched_at.map(|fetched_at| fetched_at.timestamp()),
+        failed_at: failed_at.timestamp(),
+        error,
+        message,
+    }
 }
 
-fn model_from_preset(preset: ModelPreset) -> Model {
-    Model {
-        id: preset.id.to_string(),
-        model: preset.model.to_string(),
-        display_name: preset.display_name.to_string(),
-        description: preset.description.to_string(),
-        supported_reasoning_efforts: reasoning_efforts_from_preset(
-            preset.supported_reasoning_efforts,
-        ),
-        default_reasoning_effort: preset.default_reasoning_effort,
-        is_default: preset.is_default,
+fn catalog_auth_from_core(auth: CatalogAuth) -> ModelCatalogAuth {
+    let CatalogAuth {
+        provider,
+        host,
+        mode,
+        credential,
+        account,
+    } = auth;
+    ModelCatalogAuth {
+        provider,
+        host,
+        auth_mode: mode,
+        credential_source: credential.map(|credential| match credential {
+            CredentialSource::Stored => ModelCatalogCredentialSource::Stored,
+            CredentialSource::EnvVar { name } => ModelCatalogCredentialSource::EnvVar { name },
+            CredentialSource::Config => ModelCatalogCredentialSource::Config,
+        }),
+        account,
     }
 }
 
-fn reasoning_efforts_from_preset(
-    efforts: Vec<ReasoningEffortPreset>,
-) -> Vec<ReasoningEffortOption> {
-    efforts
+/// Presets that `current` adds over `previous`, flagged `newly_added`.
+pub fn added_models(previous: &[ModelPreset], current: &[ModelPreset]) -> Vec<Model> {
+    let added = diff_catalogs(previous, current).added;
+    let mut emitted = HashSet::new();
+    current
         .iter()
-        .map(|preset| ReasoningEffortOption {
-            reasoning_effort: preset.effort,
-            description: preset.description.to_string(),
+        .filter(|preset| added.contains(&preset.id) && emitted.insert(preset.id.as_str()))
+        .cloned()
+        .map(|preset| Model {
+            newly_added: true,
+            ..Model::from(preset)
         })
         .collect()
 }
+
+/// The `model/default/changed` notification for `event`, when it reports a
+/// move of the catalog's default.
+pub fn default_model_changed(
+    event: CatalogRefreshEvent,
+) -> Option<ModelDefaultChangedNotification> {
+    match event {
+        CatalogRefreshEvent::DefaultModelChanged { previous, current } => {
+            Some(ModelDefaultChangedNotification {
+                previous_model: previous,
+                model: current,
+            })
+        }
+        CatalogRefreshEvent::RefreshStarted { .. }
+        | CatalogRefreshEvent::RefreshSucceeded { .. }
+        | CatalogRefreshEvent::RefreshFailed { .. } => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codex_common::test_catalog::catalog;
+    use codex_common::test_catalog::preset;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn added_models_flags_only_new_entries() {
+        let previous = catalog([preset("oca-small"), preset("oca-medium")]);
+        let current = catalog([
+            preset("oca-small"),
+            preset("oca-medium"),
+            preset("oca-large"),
+        ]);
+        let new_preset = current[2].clone();
+
+        assert_eq!(
+            added_models(&previous, &current),
+            vec![Model {
+                newly_added: true,
+                ..Model::from(new_preset)
+            }]
+        );
+        assert_eq!(added_models(&current, &current), Vec::new());
+    }
+
+    #[test]
+    fn only_a_moved_default_is_forwarded() {
+        assert_eq!(
+            default_model_changed(CatalogRefreshEvent::DefaultModelChanged {
+                previous: "oca-small".to_string(),
+                current: Some("oca-large".to_string()),
+            }),
+            Some(ModelDefaultChangedNotification {
+                previous_model: "oca-small".to_string(),
+                model: Some("oca-large".to_string()),
+            })
+        );
+        assert_eq!(
+            default_model_changed(CatalogRefreshEvent::RefreshFailed {
+                error: "unreachable".to_string(),
+            }),
+            None
+        );
+    }
+}
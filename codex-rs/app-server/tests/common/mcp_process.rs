@@ -20,6 +20,7 @@ use codex_app_server_protocol::ClientNotification;
 use codex_app_server_protocol::ConfigBatchWriteParams;
 use codex_app_server_protocol::ConfigReadParams;
 use codex_app_server_protocol::ConfigValueWriteParams;
+use codex_app_server_protocol::ConversationValidateOptionsParams;
 use codex_app_server_protocol::FeedbackUploadParams;
 use codex_app_server_protocol::GetAccountParams;
 use codex_app_server_protocol::GetAuthStatusParams;
@@ -33,6 +34,7 @@ use codex_app_server_protocol::JSONRPCResponse;
 use codex_app_server_protocol::ListConversationsParams;
 use codex_app_server_protocol::LoginApiKeyParams;
 use codex_app_server_protocol::ModelListParams;
+use codex_app_server_protocol::ModelRefreshParams;
 use codex_app_server_protocol::NewConversationParams;
 use codex_app_server_protocol::RemoveConversationListenerParams;
 use codex_app_server_protocol::RequestId;
@@ -298,6 +300,16 @@ impl McpProcess {
         self.send_request("thread/start", params).await
     }
 
+    /// Send a `conversation/validateOptions` JSON-RPC request.
+    pub async fn send_conversation_validate_options_request(
+        &mut self,
+        params: ConversationValidateOptionsParams,
+    ) -> anyhow::Result<i64> {
+        let params = Some(serde_json::to_value(params)?);
+        self.send_request("conversation/validateOptions", params)
+            .await
+    }
+
     /// Send a `thread/resume` JSON-RPC request.
     pub async fn send_thread_resume_request(
         &mut self,
@@ -334,6 +346,15 @@ impl McpProcess {
         self.send_request("model/list", params).await
     }
 
+    /// Send a `model/refresh` JSON-RPC request.
+    pub async fn send_model_refresh_request(
+        &mut self,
+        params: ModelRefreshParams,
+    ) -> anyhow::Result<i64> {
+        let params = Some(serde_json::to_value(params)?);
+        self.send_request("model/refresh", params).await
+    }
+
     /// Send a `resumeConversation` JSON-RPC request.
     pub async fn send_resume_conversation_request(
         &mut self,
@@ -473,6 +494,16 @@ impl McpProcess {
         self.send_request("fuzzyFileSearch", Some(params)).await
     }
 
+    /// Send a `method` request with hand-written `params`, for payloads the
+    /// typed params cannot express.
+    pub async fn send_raw_request(
+        &mut self,
+        method: &str,
+        params: serde_json::Value,
+    ) -> anyhow::Result<i64> {
+        self.send_request(method, Some(params)).await
+    }
+
     async fn send_request(
         &mut self,
         method: &str,
@@ -1,6 +1,7 @@
 use chrono::DateTime;
 use chrono::Utc;
-use codex_core::models_manager::model_presets::all_model_presets;
+use codex_core::models_manager::catalog::CatalogLoadOptions;
+use codex_core::models_manager::catalog::PresetCatalog;
 use codex_protocol::openai_models::ConfigShellToolType;
 use codex_protocol::openai_models::ModelInfo;
 use codex_protocol::openai_models::ModelPreset;
@@ -13,6 +14,7 @@ use std::path::Path;
 fn preset_to_info(preset: &ModelPreset, priority: i32) -> ModelInfo {
     ModelInfo {
         slug: preset.id.clone(),
+        preset_id: None,
         display_name: preset.display_name.clone(),
         description: Some(preset.description.clone()),
         default_reasoning_level: preset.default_reasoning_effort,
@@ -23,18 +25,43 @@ fn preset_to_info(preset: &ModelPreset, priority: i32) -> ModelInfo {
         } else {
             ModelVisibility::Hide
         },
+        is_default: false,
         supported_in_api: true,
         priority,
         upgrade: preset.upgrade.as_ref().map(|u| u.id.clone()),
         base_instructions: None,
+        instructions_addendum: None,
+        wire_api: None,
         supports_reasoning_summaries: false,
+        supports_reasoning: None,
+        reasoning_summary_format: None,
+        supports_stateful_responses: None,
         support_verbosity: false,
         default_verbosity: None,
         apply_patch_tool_type: None,
         truncation_policy: TruncationPolicyConfig::bytes(10_000),
         supports_parallel_tool_calls: false,
         context_window: None,
+        context_window_unit: None,
+        context_window_source: None,
+        max_output_tokens: None,
+        supports_temperature: preset.supports_temperature,
+        default_temperature: preset.default_temperature,
+        supports_top_p: preset.supports_top_p,
+        default_top_p: preset.default_top_p,
+        supports_images: preset.supports_images,
+        requires_network: false,
+        max_concurrent_requests: preset.max_concurrent_requests,
+        request_headers: None,
+        prompt_cache_key_strategy: preset.prompt_cache_key_strategy,
+        fallback_models: preset.fallback_models.clone(),
+        disabled_tools: preset.disabled_tools.clone(),
+        prefer_apply_patch_freeform: None,
+        enable_web_search: None,
+        knowledge_cutoff: preset.knowledge_cutoff.clone(),
+        released_at: preset.released_at.clone(),
         experimental_supported_tools: Vec::new(),
+        extra: Default::default(),
     }
 }
 
@@ -44,11 +71,8 @@ fn preset_to_info(preset: &ModelPreset, priority: i32) -> ModelInfo {
 /// The cache will be treated as fresh (within TTL) and used instead of fetching from the network.
 /// Uses the built-in model presets from ModelsManager, converted to ModelInfo format.
 pub fn write_models_cache(codex_home: &Path) -> std::io::Result<()> {
-    // Get all presets and filter for show_in_picker (same as builtin_model_presets does)
-    let presets: Vec<&ModelPreset> = all_model_presets()
-        .iter()
-        .filter(|preset| preset.show_in_picker)
-        .collect();
+    let presets: Vec<&ModelPreset> =
+        PresetCatalog::static_presets(CatalogLoadOptions::default()).collect();
     // Convert presets to ModelInfo, assigning priorities (higher = earlier in list)
     // Priority is used for sorting, so first model gets highest priority
     let models: Vec<ModelInfo> = presets
@@ -0,0 +1,334 @@
+use anyhow::Result;
+use app_test_support::McpProcess;
+use app_test_support::create_mock_chat_completions_server;
+use app_test_support::to_response;
+use codex_app_server_protocol::JSONRPCError;
+use codex_app_server_protocol::JSONRPCResponse;
+use codex_app_server_protocol::NewConversationParams;
+use codex_app_server_protocol::NewConversationResponse;
+use codex_app_server_protocol::RequestId;
+use codex_app_server_protocol::ThreadStartParams;
+use codex_app_server_protocol::ThreadStartResponse;
+use codex_app_server_protocol::TurnStartParams;
+use codex_app_server_protocol::UserInput as V2UserInput;
+use codex_protocol::openai_models::ReasoningEffort;
+use pretty_assertions::assert_eq;
+use serde_json::json;
+use std::collections::HashMap;
+use std::path::Path;
+use tempfile::TempDir;
+use tokio::time::timeout;
+
+const DEFAULT_READ_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(10);
+const INVALID_REQUEST_ERROR_CODE: i64 = -32600;
+
+#[tokio::test]
+async fn new_conversation_rejects_a_model_missing_from_the_catalog() -> Result<()> {
+    let server = create_mock_chat_completions_server(Vec::new()).await;
+    let codex_home = TempDir::new()?;
+    create_gateway_config_toml(codex_home.path(), &server.uri())?;
+    let mut mcp = McpProcess::new(codex_home.path()).await?;
+    timeout(DEFAULT_READ_TIMEOUT, mcp.initialize()).await??;
+
+    let request_id = mcp
+        .send_new_conversation_request(NewConversationParams {
+            model: Some("oca-lrge".to_string()),
+            ..Default::default()
+        })
+        .await?;
+    let err: JSONRPCError = timeout(
+        DEFAULT_READ_TIMEOUT,
+        mcp.read_stream_until_error_message(RequestId::Integer(request_id)),
+    )
+    .await??;
+
+    assert_eq!(err.error.code, INVALID_REQUEST_ERROR_CODE);
+    assert_eq!(
+        err.error.message,
+        "model `oca-lrge` is not in the model catalog for provider `Gateway`. Did you mean `oca-large`? Run `codex models list` to see the available models."
+    );
+    assert_eq!(
+        err.error.data,
+        Some(json!({
+            "model": "oca-lrge",
+            "validIds": ["gateway/oca-large", "oca-large", "gateway/oca-small", "oca-small"],
+            "suggestions": ["oca-large"],
+        }))
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn new_conversation_rejects_an_effort_the_model_does_not_offer() -> Result<()> {
+    let codex_home = TempDir::new()?;
+    create_effort_config_toml(codex_home.path(), true)?;
+    let mut mcp = McpProcess::new(codex_home.path()).await?;
+    timeout(DEFAULT_READ_TIMEOUT, mcp.initialize()).await??;
+
+    let request_id = mcp
+        .send_new_conversation_request(NewConversationParams {
+            model: Some("gpt-5.1-codex-mini".to_string()),
+            config: Some(HashMap::from([(
+                "model_reasoning_effort".to_string(),
+                json!("minimal"),
+            )])),
+            ..Default::default()
+        })
+        .await?;
+    let err: JSONRPCError = timeout(
+        DEFAULT_READ_TIMEOUT,
+        mcp.read_stream_until_error_message(RequestId::Integer(request_id)),
+    )
+    .await??;
+
+    assert_eq!(err.error.code, INVALID_REQUEST_ERROR_CODE);
+    assert_eq!(
+        err.error.data,
+        Some(json!({
+            "model": "gpt-5.1-codex-mini",
+            "effort": "minimal",
+            "supportedEfforts": ["medium", "high"],
+        }))
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn new_conversation_uses_the_closest_offered_effort_without_strict_validation() -> Result<()>
+{
+    let codex_home = TempDir::new()?;
+    create_effort_config_toml(codex_home.path(), false)?;
+    let mut mcp = McpProcess::new(codex_home.path()).await?;
+    timeout(DEFAULT_READ_TIMEOUT, mcp.initialize()).await??;
+
+    let request_id = mcp
+        .send_new_conversation_request(NewConversationParams {
+            model: Some("gpt-5.1-codex-mini".to_string()),
+            config: Some(HashMap::from([(
+                "model_reasoning_effort".to_string(),
+                json!("minimal"),
+            )])),
+            ..Default::default()
+        })
+        .await?;
+    let response: JSONRPCResponse = timeout(
+        DEFAULT_READ_TIMEOUT,
+        mcp.read_stream_until_response_message(RequestId::Integer(request_id)),
+    )
+    .await??;
+    let NewConversationResponse {
+        reasoning_effort, ..
+    } = to_response::<NewConversationResponse>(response)?;
+
+    assert_eq!(reasoning_effort, Some(ReasoningEffort::Medium));
+    Ok(())
+}
+
+#[tokio::test]
+async fn turn_start_rejects_an_effort_the_thread_model_does_not_offer() -> Result<()> {
+    let codex_home = TempDir::new()?;
+    create_effort_config_toml(codex_home.path(), true)?;
+    let mut mcp = McpProcess::new(codex_home.path()).await?;
+    timeout(DEFAULT_READ_TIMEOUT, mcp.initialize()).await??;
+
+    let request_id = mcp
+        .send_thread_start_request(ThreadStartParams {
+            model: Some("gpt-5.1-codex-mini".to_string()),
+            ..Default::default()
+        })
+        .await?;
+    let response: JSONRPCResponse = timeout(
+        DEFAULT_READ_TIMEOUT,
+        mcp.read_stream_until_response_message(RequestId::Integer(request_id)),
+    )
+    .await??;
+    let ThreadStartResponse { thread, .. } = to_response::<ThreadStartResponse>(response)?;
+
+    // The turn names no model, so the effort is checked against the one
+    // the thread was started with.
+    let request_id = mcp
+        .send_turn_start_request(TurnStartParams {
+            thread_id: thread.id,
+            input: vec![V2UserInput::Text {
+                text: "Hello".to_string(),
+            }],
+            effort: Some(ReasoningEffort::Minimal),
+            ..Default::default()
+        })
+        .await?;
+    let err: JSONRPCError = timeout(
+        DEFAULT_READ_TIMEOUT,
+        mcp.read_stream_until_error_message(RequestId::Integer(request_id)),
+    )
+    .await??;
+
+    assert_eq!(err.error.code, INVALID_REQUEST_ERROR_CODE);
+    assert_eq!(
+        err.error.data,
+        Some(json!({
+            "model": "gpt-5.1-codex-mini",
+            "effort": "minimal",
+            "supportedEfforts": ["medium", "high"],
+        }))
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn turn_start_rejects_a_disabled_effort_without_strict_validation() -> Result<()> {
+    let codex_home = TempDir::new()?;
+    std::fs::write(
+        codex_home.path().join("config.toml"),
+        r#"
+approval_policy = "never"
+sandbox_mode = "danger-full-access"
+disabled_reasoning_efforts = ["high"]
+"#,
+    )?;
+    let mut mcp = McpProcess::new(codex_home.path()).await?;
+    timeout(DEFAULT_READ_TIMEOUT, mcp.initialize()).await??;
+
+    let request_id = mcp
+        .send_thread_start_request(ThreadStartParams {
+            model: Some("gpt-5.1-codex-mini".to_string()),
+            ..Default::default()
+        })
+        .await?;
+    let response: JSONRPCResponse = timeout(
+        DEFAULT_READ_TIMEOUT,
+        mcp.read_stream_until_response_message(RequestId::Integer(request_id)),
+    )
+    .await??;
+    let ThreadStartResponse { thread, .. } = to_response::<ThreadStartResponse>(response)?;
+
+    // Unlike an effort the model lacks, a disabled one is not swapped for
+    // the closest one left.
+    let request_id = mcp
+        .send_turn_start_request(TurnStartParams {
+            thread_id: thread.id,
+            input: vec![V2UserInput::Text {
+                text: "Hello".to_string(),
+            }],
+            effort: Some(ReasoningEffort::High),
+            ..Default::default()
+        })
+        .await?;
+    let err: JSONRPCError = timeout(
+        DEFAULT_READ_TIMEOUT,
+        mcp.read_stream_until_error_message(RequestId::Integer(request_id)),
+    )
+    .await??;
+
+    assert_eq!(err.error.code, INVALID_REQUEST_ERROR_CODE);
+    assert_eq!(
+        err.error.message,
+        "reasoning effort `high` is disabled by `disabled_reasoning_efforts`."
+    );
+    assert_eq!(
+        err.error.data,
+        Some(json!({
+            "effort": "high",
+            "disabledBy": "disabled_reasoning_efforts",
+        }))
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn unknown_effort_is_rejected_as_an_invalid_request() -> Result<()> {
+    let codex_home = TempDir::new()?;
+    create_effort_config_toml(codex_home.path(), false)?;
+    let mut mcp = McpProcess::new(codex_home.path()).await?;
+    timeout(DEFAULT_READ_TIMEOUT, mcp.initialize()).await??;
+
+    let request_id = mcp
+        .send_raw_request(
+            "turn/start",
+            json!({
+                "threadId": "67e55044-10b1-426f-9247-bb680e5fe0c8",
+                "input": [],
+                "effort": "turbo",
+            }),
+        )
+        .await?;
+    let err: JSONRPCError = timeout(
+        DEFAULT_READ_TIMEOUT,
+        mcp.read_stream_until_error_message(RequestId::Integer(request_id)),
+    )
+    .await??;
+
+    assert_eq!(err.error.code, INVALID_REQUEST_ERROR_CODE);
+    assert!(
+        err.error
+            .message
+            .contains("unknown reasoning effort `turbo`; expected one of: none, minimal, low, medium, high, xhigh"),
+        "{}",
+        err.error.message
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn allow_unknown_model_skips_the_catalog_check() -> Result<()> {
+    let server = create_mock_chat_completions_server(Vec::new()).await;
+    let codex_home = TempDir::new()?;
+    create_gateway_config_toml(codex_home.path(), &server.uri())?;
+    let mut mcp = McpProcess::new(codex_home.path()).await?;
+    timeout(DEFAULT_READ_TIMEOUT, mcp.initialize()).await??;
+
+    let request_id = mcp
+        .send_new_conversation_request(NewConversationParams {
+            model: Some("oca-custom".to_string()),
+            allow_unknown_model: Some(true),
+            ..Default::default()
+        })
+        .await?;
+    let response: JSONRPCResponse = timeout(
+        DEFAULT_READ_TIMEOUT,
+        mcp.read_stream_until_response_message(RequestId::Integer(request_id)),
+    )
+    .await??;
+    let NewConversationResponse { model, .. } = to_response::<NewConversationResponse>(response)?;
+
+    assert_eq!(model, "oca-custom");
+    Ok(())
+}
+
+/// The built-in provider, with `strict_model_validation` set as given.
+fn create_effort_config_toml(codex_home: &Path, strict: bool) -> std::io::Result<()> {
+    std::fs::write(
+        codex_home.join("config.toml"),
+        format!(
+            r#"
+approval_policy = "never"
+sandbox_mode = "danger-full-access"
+strict_model_validation = {strict}
+"#
+        ),
+    )
+}
+
+/// A provider whose catalog is the fixed model list in its config.
+fn create_gateway_config_toml(codex_home: &Path, server_uri: &str) -> std::io::Result<()> {
+    std::fs::write(
+        codex_home.join("config.toml"),
+        format!(
+            r#"
+model = "oca-large"
+approval_policy = "never"
+sandbox_mode = "danger-full-access"
+
+model_provider = "gateway"
+
+[model_providers.gateway]
+name = "Gateway"
+base_url = "{server_uri}/v1"
+wire_api = "chat"
+model_catalog = "none"
+models = ["oca-large", "oca-small"]
+request_max_retries = 0
+stream_max_retries = 0
+"#
+        ),
+    )
+}
@@ -0,0 +1,293 @@
+use std::time::Duration;
+
+use anyhow::Result;
+use app_test_support::McpProcess;
+use app_test_support::create_final_assistant_message_sse_response;
+use app_test_support::create_mock_chat_completions_server_unchecked;
+use app_test_support::to_response;
+use app_test_support::write_models_cache;
+use codex_app_server_protocol::JSONRPCError;
+use codex_app_server_protocol::JSONRPCResponse;
+use codex_app_server_protocol::ModelCatalogRebuild;
+use codex_app_server_protocol::ModelListParams;
+use codex_app_server_protocol::ModelListResponse;
+use codex_app_server_protocol::RequestId;
+use codex_app_server_protocol::ThreadStartParams;
+use codex_app_server_protocol::ThreadStartResponse;
+use codex_app_server_protocol::TurnStartParams;
+use codex_app_server_protocol::UserInput as V2UserInput;
+use pretty_assertions::assert_eq;
+use serde_json::json;
+use tempfile::TempDir;
+use tokio::time::timeout;
+use wiremock::Mock;
+use wiremock::MockServer;
+use wiremock::ResponseTemplate;
+use wiremock::matchers::method;
+use wiremock::matchers::path;
+
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(10);
+const INVALID_REQUEST_ERROR_CODE: i64 = -32600;
+
+/// A gateway that lists `model` and answers one turn.
+async fn gateway(model: &str) -> Result<MockServer> {
+    let server = create_mock_chat_completions_server_unchecked(vec![
+        create_final_assistant_message_sse_response("Done")?,
+    ])
+    .await;
+    Mock::given(method("GET"))
+        .and(path("/v1/models"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "object": "list",
+            "data": [{"id": model, "object": "model"}]
+        })))
+        .mount(&server)
+        .await;
+    Ok(server)
+}
+
+fn write_config(codex_home: &TempDir, default: &MockServer, tenants: &[&MockServer]) -> Result<()> {
+    let allowed = tenants
+        .iter()
+        .map(|server| format!("\"{}/v1\"", server.uri()))
+        .collect::<Vec<_>>()
+        .join(", ");
+    std::fs::write(
+        codex_home.path().join("config.toml"),
+        format!(
+            r#"
+model = "mock-model"
+approval_policy = "never"
+sandbox_mode = "read-only"
+model_provider = "gateway"
+allowed_catalog_base_urls = [{allowed}]
+
+[model_providers.gateway]
+name = "Gateway"
+base_url = "{}/v1"
+wire_api = "chat"
+model_catalog = "openai"
+request_max_retries = 0
+stream_max_retries = 0
+"#,
+            default.uri()
+        ),
+    )?;
+    Ok(())
+}
+
+async fn gateway_model_ids(
+    mcp: &mut McpProcess,
+    catalog_base_url: Option<String>,
+) -> Result<Vec<String>> {
+    let request_id = mcp
+        .send_list_models_request(ModelListParams {
+            catalog_base_url,
+            ..Default::default()
+        })
+        .await?;
+    let response: JSONRPCResponse = timeout(
+        DEFAULT_TIMEOUT,
+        mcp.read_stream_until_response_message(RequestId::Integer(request_id)),
+    )
+    .await??;
+    let ModelListResponse { data, .. } = to_response::<ModelListResponse>(response)?;
+    Ok(data
+        .into_iter()
+        .map(|model| model.id)
+        .filter(|id| id.starts_with("gateway/"))
+        .collect())
+}
+
+async fn last_rebuild(
+    mcp: &mut McpProcess,
+    catalog_base_url: Option<String>,
+) -> Result<Option<ModelCatalogRebuild>> {
+    let request_id = mcp
+        .send_list_models_request(ModelListParams {
+            catalog_base_url,
+            ..Default::default()
+        })
+        .await?;
+    let response: JSONRPCResponse = timeout(
+        DEFAULT_TIMEOUT,
+        mcp.read_stream_until_response_message(RequestId::Integer(request_id)),
+    )
+    .await??;
+    let ModelListResponse { last_rebuild, .. } = to_response::<ModelListResponse>(response)?;
+    Ok(last_rebuild)
+}
+
+async fn chat_requests(server: &MockServer) -> usize {
+    server
+        .received_requests()
+        .await
+        .unwrap_or_default()
+        .iter()
+        .filter(|request| request.url.path() == "/v1/chat/completions")
+        .count()
+}
+
+#[tokio::test]
+async fn threads_of_two_tenants_run_against_their_own_gateways() -> Result<()> {
+    let default = gateway("shared-model").await?;
+    let tenant_a = gateway("oca-a").await?;
+    let tenant_b = gateway("oca-b").await?;
+    let codex_home = TempDir::new()?;
+    write_models_cache(codex_home.path())?;
+    write_config(&codex_home, &default, &[&tenant_a, &tenant_b])?;
+    let mut mcp = McpProcess::new(codex_home.path()).await?;
+    timeout(DEFAULT_TIMEOUT, mcp.initialize()).await??;
+
+    // Both tenants' threads are open at once.
+    let mut threads = Vec::new();
+    for tenant in [&tenant_a, &tenant_b] {
+        let request_id = mcp
+            .send_thread_start_request(ThreadStartParams {
+                catalog_base_url: Some(format!("{}/v1/", tenant.uri())),
+                ..Default::default()
+            })
+            .await?;
+        let response: JSONRPCResponse = timeout(
+            DEFAULT_TIMEOUT,
+            mcp.read_stream_until_response_message(RequestId::Integer(request_id)),
+        )
+        .await??;
+        let ThreadStartResponse { thread, .. } = to_response::<ThreadStartResponse>(response)?;
+        threads.push(thread);
+    }
+
+    assert_eq!(
+        gateway_model_ids(&mut mcp, Some(format!("{}/v1", tenant_a.uri()))).await?,
+        vec!["gateway/oca-a".to_string()]
+    );
+    assert_eq!(
+        gateway_model_ids(&mut mcp, Some(format!("{}/v1", tenant_b.uri()))).await?,
+        vec!["gateway/oca-b".to_string()]
+    );
+    assert_eq!(
+        gateway_model_ids(&mut mcp, None).await?,
+        vec!["gateway/shared-model".to_string()],
+        "requests without catalogBaseUrl keep the configured catalog"
+    );
+
+    for thread in &threads {
+        let request_id = mcp
+            .send_turn_start_request(TurnStartParams {
+                thread_id: thread.id.clone(),
+                input: vec![V2UserInput::Text {
+                    text: "Hello".to_string(),
+                }],
+                ..Default::default()
+            })
+            .await?;
+        timeout(
+            DEFAULT_TIMEOUT,
+            mcp.read_stream_until_response_message(RequestId::Integer(request_id)),
+        )
+        .await??;
+        timeout(
+            DEFAULT_TIMEOUT,
+            mcp.read_stream_until_notification_message("turn/completed"),
+        )
+        .await??;
+    }
+
+    assert_eq!(
+        (
+            chat_requests(&tenant_a).await > 0,
+            chat_requests(&tenant_b).await > 0,
+            chat_requests(&default).await
+        ),
+        (true, true, 0),
+        "each thread's turn goes to its tenant's gateway"
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn unlisted_catalog_base_urls_are_rejected() -> Result<()> {
+    let default = gateway("shared-model").await?;
+    let tenant_a = gateway("oca-a").await?;
+    let codex_home = TempDir::new()?;
+    write_models_cache(codex_home.path())?;
+    write_config(&codex_home, &default, &[&tenant_a])?;
+    let mut mcp = McpProcess::new(codex_home.path()).await?;
+    timeout(DEFAULT_TIMEOUT, mcp.initialize()).await??;
+
+    let request_id = mcp
+        .send_list_models_request(ModelListParams {
+            catalog_base_url: Some("https://elsewhere.example.com/v1".to_string()),
+            ..Default::default()
+        })
+        .await?;
+    let error: JSONRPCError = timeout(
+        DEFAULT_TIMEOUT,
+        mcp.read_stream_until_error_message(RequestId::Integer(request_id)),
+    )
+    .await??;
+    assert_eq!(error.error.code, INVALID_REQUEST_ERROR_CODE);
+    assert_eq!(
+        error.error.message,
+        "invalid catalogBaseUrl: catalog base URL `https://elsewhere.example.com/v1` is not listed in `allowed_catalog_base_urls`"
+    );
+
+    let request_id = mcp
+        .send_thread_start_request(ThreadStartParams {
+            catalog_base_url: Some("https://elsewhere.example.com/v1".to_string()),
+            ..Default::default()
+        })
+        .await?;
+    let error: JSONRPCError = timeout(
+        DEFAULT_TIMEOUT,
+        mcp.read_stream_until_error_message(RequestId::Integer(request_id)),
+    )
+    .await??;
+    assert_eq!(error.error.code, INVALID_REQUEST_ERROR_CODE);
+    Ok(())
+}
+
+#[tokio::test]
+async fn tenant_listings_report_the_last_catalog_rebuild() -> Result<()> {
+    let default = gateway("shared-model").await?;
+    let tenant = gateway("oca-a").await?;
+    let codex_home = TempDir::new()?;
+    write_models_cache(codex_home.path())?;
+    write_config(&codex_home, &default, &[&tenant])?;
+    let mut mcp = McpProcess::new(codex_home.path()).await?;
+    timeout(DEFAULT_TIMEOUT, mcp.initialize()).await??;
+
+    // The tenant's refresher starts with the first listing and rebuilds in
+    // the background, so list until its first rebuild is reported.
+    let tenant_url = Some(format!("{}/v1", tenant.uri()));
+    let rebuild = timeout(DEFAULT_TIMEOUT, async {
+        loop {
+            if let Some(rebuild) = last_rebuild(&mut mcp, tenant_url.clone()).await? {
+                return anyhow::Ok(rebuild);
+            }
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+    })
+    .await??;
+    assert_eq!(rebuild.reasons, vec!["started".to_string()]);
+    assert!(rebuild.finished_at > 0, "{rebuild:?}");
+
+    // The configured catalog is rebuilt on its own, so it reports a rebuild
+    // of its own rather than the tenant's.
+    let configured = timeout(DEFAULT_TIMEOUT, async {
+        loop {
+            if let Some(rebuild) = last_rebuild(&mut mcp, None).await? {
+                return anyhow::Ok(rebuild);
+            }
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+    })
+    .await??;
+    assert_eq!(configured.reasons, vec!["started".to_string()]);
+    assert_eq!(
+        last_rebuild(&mut mcp, tenant_url).await?,
+        Some(rebuild),
+        "the tenant's rebuild is unchanged"
+    );
+    Ok(())
+}
@@ -0,0 +1,222 @@
+use anyhow::Result;
+use app_test_support::McpProcess;
+use app_test_support::create_mock_chat_completions_server;
+use app_test_support::to_response;
+use codex_app_server_protocol::AskForApproval;
+use codex_app_server_protocol::ConversationOptionField;
+use codex_app_server_protocol::ConversationOptionIssue;
+use codex_app_server_protocol::ConversationOptionIssueCode;
+use codex_app_server_protocol::ConversationOptionIssueSeverity;
+use codex_app_server_protocol::ConversationValidateOptionsParams;
+use codex_app_server_protocol::ConversationValidateOptionsResponse;
+use codex_app_server_protocol::JSONRPCResponse;
+use codex_app_server_protocol::RequestId;
+use codex_app_server_protocol::SandboxMode;
+use codex_protocol::openai_models::ReasoningEffort;
+use pretty_assertions::assert_eq;
+use std::path::Path;
+use tempfile::TempDir;
+use tokio::time::timeout;
+
+const DEFAULT_READ_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(10);
+
+async fn validate_options(
+    codex_home: &Path,
+    params: ConversationValidateOptionsParams,
+) -> Result<ConversationValidateOptionsResponse> {
+    validate_options_with_env(codex_home, &[], params).await
+}
+
+async fn validate_options_with_env(
+    codex_home: &Path,
+    env_overrides: &[(&str, Option<&str>)],
+    params: ConversationValidateOptionsParams,
+) -> Result<ConversationValidateOptionsResponse> {
+    let mut mcp = McpProcess::new_with_env(codex_home, env_overrides).await?;
+    timeout(DEFAULT_READ_TIMEOUT, mcp.initialize()).await??;
+
+    let request_id = mcp
+        .send_conversation_validate_options_request(params)
+        .await?;
+    let response: JSONRPCResponse = timeout(
+        DEFAULT_READ_TIMEOUT,
+        mcp.read_stream_until_response_message(RequestId::Integer(request_id)),
+    )
+    .await??;
+    to_response::<ConversationValidateOptionsResponse>(response)
+}
+
+#[tokio::test]
+async fn valid_options_report_no_issues_and_start_no_thread() -> Result<()> {
+    let codex_home = TempDir::new()?;
+    create_default_config_toml(codex_home.path())?;
+
+    let response = validate_options(
+        codex_home.path(),
+        ConversationValidateOptionsParams {
+            model: Some("gpt-5.1-codex-mini".to_string()),
+            effort: Some(ReasoningEffort::High),
+            approval_policy: Some(AskForApproval::OnRequest),
+            sandbox: Some(SandboxMode::ReadOnly),
+            ..Default::default()
+        },
+    )
+    .await?;
+
+    assert_eq!(
+        response,
+        ConversationValidateOptionsResponse {
+            valid: true,
+            model: Some("gpt-5.1-codex-mini".to_string()),
+            issues: Vec::new(),
+        }
+    );
+    assert!(
+        !codex_home.path().join("sessions").exists(),
+        "validating options must not record a session"
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn unsupported_effort_is_an_error_suggesting_the_offered_efforts() -> Result<()> {
+    let codex_home = TempDir::new()?;
+    create_default_config_toml(codex_home.path())?;
+
+    let response = validate_options(
+        codex_home.path(),
+        ConversationValidateOptionsParams {
+            model: Some("gpt-5.1-codex-mini".to_string()),
+            effort: Some(ReasoningEffort::Minimal),
+            ..Default::default()
+        },
+    )
+    .await?;
+
+    assert_eq!(
+        response,
+        ConversationValidateOptionsResponse {
+            valid: false,
+            model: Some("gpt-5.1-codex-mini".to_string()),
+            issues: vec![ConversationOptionIssue {
+                severity: ConversationOptionIssueSeverity::Error,
+                code: ConversationOptionIssueCode::UnsupportedEffort,
+                field: Some(ConversationOptionField::Effort),
+                message: "model `gpt-5.1-codex-mini` does not support reasoning effort `minimal`; it supports `medium`, `high`.".to_string(),
+                suggestions: vec!["medium".to_string(), "high".to_string()],
+            }],
+        }
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn unknown_model_is_an_error_suggesting_close_matches() -> Result<()> {
+    let server = create_mock_chat_completions_server(Vec::new()).await;
+    let codex_home = TempDir::new()?;
+    create_gateway_config_toml(codex_home.path(), &server.uri())?;
+
+    let response = validate_options(
+        codex_home.path(),
+        ConversationValidateOptionsParams {
+            model: Some("oca-lrge".to_string()),
+            ..Default::default()
+        },
+    )
+    .await?;
+
+    assert_eq!(
+        response,
+        ConversationValidateOptionsResponse {
+            valid: false,
+            model: Some("oca-lrge".to_string()),
+            issues: vec![ConversationOptionIssue {
+                severity: ConversationOptionIssueSeverity::Error,
+                code: ConversationOptionIssueCode::UnknownModel,
+                field: Some(ConversationOptionField::Model),
+                message: "model `oca-lrge` is not in the model catalog for provider `Gateway`. Did you mean `oca-large`? Run `codex models list` to see the available models.".to_string(),
+                suggestions: vec!["oca-large".to_string()],
+            }],
+        }
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn disallowed_sandbox_is_an_error_suggesting_the_allowed_modes() -> Result<()> {
+    let codex_home = TempDir::new()?;
+    std::fs::write(
+        codex_home.path().join("config.toml"),
+        r#"approval_policy = "never"
+"#,
+    )?;
+    let managed_path = codex_home.path().join("managed_config.toml");
+    std::fs::write(
+        &managed_path,
+        r#"sandbox_mode = "read-only"
+"#,
+    )?;
+    let managed_path = managed_path.display().to_string();
+
+    let response = validate_options_with_env(
+        codex_home.path(),
+        &[("CODEX_MANAGED_CONFIG_PATH", Some(&managed_path))],
+        ConversationValidateOptionsParams {
+            model: Some("gpt-5.1-codex-mini".to_string()),
+            sandbox: Some(SandboxMode::DangerFullAccess),
+            ..Default::default()
+        },
+    )
+    .await?;
+
+    assert_eq!(
+        response,
+        ConversationValidateOptionsResponse {
+            valid: false,
+            model: Some("gpt-5.1-codex-mini".to_string()),
+            issues: vec![ConversationOptionIssue {
+                severity: ConversationOptionIssueSeverity::Error,
+                code: ConversationOptionIssueCode::DisallowedSandbox,
+                field: Some(ConversationOptionField::Sandbox),
+                message: "sandbox `danger-full-access` is not allowed by the admin requirements; they allow `read-only`.".to_string(),
+                suggestions: vec!["read-only".to_string()],
+            }],
+        }
+    );
+    Ok(())
+}
+
+fn create_default_config_toml(codex_home: &Path) -> std::io::Result<()> {
+    std::fs::write(
+        codex_home.join("config.toml"),
+        r#"
+approval_policy = "never"
+sandbox_mode = "danger-full-access"
+"#,
+    )
+}
+
+/// A provider whose catalog is the fixed model list in its config.
+fn create_gateway_config_toml(codex_home: &Path, server_uri: &str) -> std::io::Result<()> {
+    std::fs::write(
+        codex_home.join("config.toml"),
+        format!(
+            r#"
+model = "oca-large"
+approval_policy = "never"
+sandbox_mode = "danger-full-access"
+
+model_provider = "gateway"
+
+[model_providers.gateway]
+name = "Gateway"
+base_url = "{server_uri}/v1"
+wire_api = "chat"
+model_catalog = "none"
+models = ["oca-large", "oca-small"]
+request_max_retries = 0
+stream_max_retries = 0
+"#
+        ),
+    )
+}
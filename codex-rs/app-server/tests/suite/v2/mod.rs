@@ -1,6 +1,9 @@
 mod account;
+mod catalog_base_url;
 mod config_rpc;
+mod conversation_validate_options;
 mod model_list;
+mod model_refresh;
 mod rate_limits;
 mod review;
 mod thread_archive;
@@ -1,21 +1,37 @@
+use std::collections::HashMap;
 use std::time::Duration;
 
 use anyhow::Result;
 use anyhow::anyhow;
+use app_test_support::ChatGptAuthFixture;
 use app_test_support::McpProcess;
 use app_test_support::to_response;
+use app_test_support::write_chatgpt_auth;
 use app_test_support::write_models_cache;
+use codex_app_server_protocol::AuthMode;
 use codex_app_server_protocol::JSONRPCError;
 use codex_app_server_protocol::JSONRPCResponse;
+use codex_app_server_protocol::MODEL_PRESET_SCHEMA_VERSION;
 use codex_app_server_protocol::Model;
+use codex_app_server_protocol::ModelCatalogAuth;
+use codex_app_server_protocol::ModelCatalogCredentialSource;
 use codex_app_server_protocol::ModelListParams;
 use codex_app_server_protocol::ModelListResponse;
+use codex_app_server_protocol::ModelRefreshParams;
 use codex_app_server_protocol::ReasoningEffortOption;
 use codex_app_server_protocol::RequestId;
+use codex_core::auth::AuthCredentialsStoreMode;
+use codex_core::auth::login_with_api_key;
 use codex_protocol::openai_models::ReasoningEffort;
 use pretty_assertions::assert_eq;
+use serde_json::json;
 use tempfile::TempDir;
 use tokio::time::timeout;
+use wiremock::Mock;
+use wiremock::MockServer;
+use wiremock::ResponseTemplate;
+use wiremock::matchers::method;
+use wiremock::matchers::path;
 
 const DEFAULT_TIMEOUT: Duration = Duration::from_secs(10);
 const INVALID_REQUEST_ERROR_CODE: i64 = -32600;
@@ -32,6 +48,11 @@ async fn list_models_returns_all_models_with_large_limit() -> Result<()> {
         .send_list_models_request(ModelListParams {
             limit: Some(100),
             cursor: None,
+            if_revision: None,
+            capability: None,
+            catalog_base_url: None,
+            offset: None,
+            query: None,
         })
         .await?;
 
@@ -44,10 +65,12 @@ async fn list_models_returns_all_models_with_large_limit() -> Result<()> {
     let ModelListResponse {
         data: items,
         next_cursor,
+        ..
     } = to_response::<ModelListResponse>(response)?;
 
     let expected_models = vec![
         Model {
+            preset_schema_version: MODEL_PRESET_SCHEMA_VERSION,
             id: "gpt-5.2".to_string(),
             model: "gpt-5.2".to_string(),
             display_name: "gpt-5.2".to_string(),
@@ -79,8 +102,12 @@ async fn list_models_returns_all_models_with_large_limit() -> Result<()> {
             ],
             default_reasoning_effort: ReasoningEffort::Medium,
             is_default: true,
+            requires_network: false,
+            newly_added: false,
+            extra: HashMap::new(),
         },
         Model {
+            preset_schema_version: MODEL_PRESET_SCHEMA_VERSION,
             id: "gpt-5.1-codex-mini".to_string(),
             model: "gpt-5.1-codex-mini".to_string(),
             display_name: "gpt-5.1-codex-mini".to_string(),
@@ -98,8 +125,12 @@ async fn list_models_returns_all_models_with_large_limit() -> Result<()> {
             ],
             default_reasoning_effort: ReasoningEffort::Medium,
             is_default: false,
+            requires_network: false,
+            newly_added: false,
+            extra: HashMap::new(),
         },
         Model {
+            preset_schema_version: MODEL_PRESET_SCHEMA_VERSION,
             id: "gpt-5.1-codex-max".to_string(),
             model: "gpt-5.1-codex-max".to_string(),
             display_name: "gpt-5.1-codex-max".to_string(),
@@ -125,8 +156,12 @@ async fn list_models_returns_all_models_with_large_limit() -> Result<()> {
             ],
             default_reasoning_effort: ReasoningEffort::Medium,
             is_default: false,
+            requires_network: false,
+            newly_added: false,
+            extra: HashMap::new(),
         },
         Model {
+            preset_schema_version: MODEL_PRESET_SCHEMA_VERSION,
             id: "gpt-5.2-codex".to_string(),
             model: "gpt-5.2-codex".to_string(),
             display_name: "gpt-5.2-codex".to_string(),
@@ -152,6 +187,9 @@ async fn list_models_returns_all_models_with_large_limit() -> Result<()> {
             ],
             default_reasoning_effort: ReasoningEffort::Medium,
             is_default: false,
+            requires_network: false,
+            newly_added: false,
+            extra: HashMap::new(),
         },
     ];
 
@@ -172,6 +210,11 @@ async fn list_models_pagination_works() -> Result<()> {
         .send_list_models_request(ModelListParams {
             limit: Some(1),
             cursor: None,
+            if_revision: None,
+            capability: None,
+            catalog_base_url: None,
+            offset: None,
+            query: None,
         })
         .await?;
 
@@ -184,6 +227,7 @@ async fn list_models_pagination_works() -> Result<()> {
     let ModelListResponse {
         data: first_items,
         next_cursor: first_cursor,
+        ..
     } = to_response::<ModelListResponse>(first_response)?;
 
     assert_eq!(first_items.len(), 1);
@@ -194,6 +238,11 @@ async fn list_models_pagination_works() -> Result<()> {
         .send_list_models_request(ModelListParams {
             limit: Some(1),
             cursor: Some(next_cursor.clone()),
+            if_revision: None,
+            capability: None,
+            catalog_base_url: None,
+            offset: None,
+            query: None,
         })
         .await?;
 
@@ -206,6 +255,7 @@ async fn list_models_pagination_works() -> Result<()> {
     let ModelListResponse {
         data: second_items,
         next_cursor: second_cursor,
+        ..
     } = to_response::<ModelListResponse>(second_response)?;
 
     assert_eq!(second_items.len(), 1);
@@ -216,6 +266,11 @@ async fn list_models_pagination_works() -> Result<()> {
         .send_list_models_request(ModelListParams {
             limit: Some(1),
             cursor: Some(third_cursor.clone()),
+            if_revision: None,
+            capability: None,
+            catalog_base_url: None,
+            offset: None,
+            query: None,
         })
         .await?;
 
@@ -228,6 +283,7 @@ async fn list_models_pagination_works() -> Result<()> {
     let ModelListResponse {
         data: third_items,
         next_cursor: third_cursor,
+        ..
     } = to_response::<ModelListResponse>(third_response)?;
 
     assert_eq!(third_items.len(), 1);
@@ -238,6 +294,11 @@ async fn list_models_pagination_works() -> Result<()> {
         .send_list_models_request(ModelListParams {
             limit: Some(1),
             cursor: Some(fourth_cursor.clone()),
+            if_revision: None,
+            capability: None,
+            catalog_base_url: None,
+            offset: None,
+            query: None,
         })
         .await?;
 
@@ -250,6 +311,7 @@ async fn list_models_pagination_works() -> Result<()> {
     let ModelListResponse {
         data: fourth_items,
         next_cursor: fourth_cursor,
+        ..
     } = to_response::<ModelListResponse>(fourth_response)?;
 
     assert_eq!(fourth_items.len(), 1);
@@ -270,6 +332,11 @@ async fn list_models_rejects_invalid_cursor() -> Result<()> {
         .send_list_models_request(ModelListParams {
             limit: None,
             cursor: Some("invalid".to_string()),
+            if_revision: None,
+            capability: None,
+            catalog_base_url: None,
+            offset: None,
+            query: None,
         })
         .await?;
 
@@ -284,3 +351,520 @@ async fn list_models_rejects_invalid_cursor() -> Result<()> {
     assert_eq!(error.error.message, "invalid cursor: invalid");
     Ok(())
 }
+
+#[tokio::test]
+async fn list_models_reports_not_modified_for_current_revision() -> Result<()> {
+    let codex_home = TempDir::new()?;
+    write_models_cache(codex_home.path())?;
+    let mut mcp = McpProcess::new(codex_home.path()).await?;
+
+    timeout(DEFAULT_TIMEOUT, mcp.initialize()).await??;
+
+    let first_request = mcp
+        .send_list_models_request(ModelListParams::default())
+        .await?;
+    let first_response: JSONRPCResponse = timeout(
+        DEFAULT_TIMEOUT,
+        mcp.read_stream_until_response_message(RequestId::Integer(first_request)),
+    )
+    .await??;
+    let first = to_response::<ModelListResponse>(first_response)?;
+    assert!(!first.data.is_empty());
+    assert!(!first.not_modified);
+
+    let second_request = mcp
+        .send_list_models_request(ModelListParams {
+            if_revision: Some(first.revision),
+            ..Default::default()
+        })
+        .await?;
+    let second_response: JSONRPCResponse = timeout(
+        DEFAULT_TIMEOUT,
+        mcp.read_stream_until_response_message(RequestId::Integer(second_request)),
+    )
+    .await??;
+    let second = to_response::<ModelListResponse>(second_response)?;
+
+    assert_eq!(
+        second,
+        ModelListResponse {
+            data: Vec::new(),
+            next_cursor: None,
+            total_count: first.total_count,
+            revision: first.revision,
+            not_modified: true,
+            catalog_auth: first.catalog_auth.clone(),
+            warnings: first.warnings.clone(),
+            client_version_notice: None,
+            // The background rebuild of the catalog may finish in between.
+            last_rebuild: second.last_rebuild.clone(),
+        }
+    );
+
+    let stale_request = mcp
+        .send_list_models_request(ModelListParams {
+            if_revision: Some(first.revision.wrapping_add(1)),
+            ..Default::default()
+        })
+        .await?;
+    let stale_response: JSONRPCResponse = timeout(
+        DEFAULT_TIMEOUT,
+        mcp.read_stream_until_response_message(RequestId::Integer(stale_request)),
+    )
+    .await??;
+    let stale = to_response::<ModelListResponse>(stale_response)?;
+    assert_eq!(stale.data, first.data);
+    assert!(!stale.not_modified);
+    Ok(())
+}
+
+async fn list_catalog_auth(codex_home: &TempDir) -> Result<ModelCatalogAuth> {
+    let mut mcp = McpProcess::new_with_env(codex_home.path(), &[("OPENAI_BASE_URL", None)]).await?;
+    timeout(DEFAULT_TIMEOUT, mcp.initialize()).await??;
+
+    let request_id = mcp
+        .send_list_models_request(ModelListParams::default())
+        .await?;
+    let response: JSONRPCResponse = timeout(
+        DEFAULT_TIMEOUT,
+        mcp.read_stream_until_response_message(RequestId::Integer(request_id)),
+    )
+    .await??;
+    Ok(to_response::<ModelListResponse>(response)?.catalog_auth)
+}
+
+#[tokio::test]
+async fn list_models_reports_missing_credentials() -> Result<()> {
+    let codex_home = TempDir::new()?;
+    write_models_cache(codex_home.path())?;
+
+    assert_eq!(
+        list_catalog_auth(&codex_home).await?,
+        ModelCatalogAuth {
+            provider: "OpenAI".to_string(),
+            host: Some("api.openai.com".to_string()),
+            auth_mode: None,
+            credential_source: None,
+            account: None,
+        }
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn list_models_reports_stored_api_key() -> Result<()> {
+    let codex_home = TempDir::new()?;
+    write_models_cache(codex_home.path())?;
+    login_with_api_key(
+        codex_home.path(),
+        "sk-test-key",
+        AuthCredentialsStoreMode::File,
+    )?;
+
+    assert_eq!(
+        list_catalog_auth(&codex_home).await?,
+        ModelCatalogAuth {
+            provider: "OpenAI".to_string(),
+            host: Some("api.openai.com".to_string()),
+            auth_mode: Some(AuthMode::ApiKey),
+            credential_source: Some(ModelCatalogCredentialSource::Stored),
+            account: None,
+        }
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn list_models_reports_chatgpt_account() -> Result<()> {
+    let codex_home = TempDir::new()?;
+    write_models_cache(codex_home.path())?;
+    write_chatgpt_auth(
+        codex_home.path(),
+        ChatGptAuthFixture::new("access-chatgpt").email("user@example.com"),
+        AuthCredentialsStoreMode::File,
+    )?;
+
+    assert_eq!(
+        list_catalog_auth(&codex_home).await?,
+        ModelCatalogAuth {
+            provider: "OpenAI".to_string(),
+            host: Some("chatgpt.com".to_string()),
+            auth_mode: Some(AuthMode::ChatGPT),
+            credential_source: Some(ModelCatalogCredentialSource::Stored),
+            account: Some("user@example.com".to_string()),
+        }
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn list_models_warns_about_unreachable_catalog_providers() -> Result<()> {
+    let codex_home = TempDir::new()?;
+    write_models_cache(codex_home.path())?;
+    let gateway = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/v1/models"))
+        .respond_with(ResponseTemplate::new(503))
+        .mount(&gateway)
+        .await;
+    std::fs::write(
+        codex_home.path().join("config.toml"),
+        format!(
+            r#"
+[model_providers.gateway]
+name = "Gateway"
+base_url = "{}/v1"
+model_catalog = "openai"
+"#,
+            gateway.uri()
+        ),
+    )?;
+    let mut mcp = McpProcess::new(codex_home.path()).await?;
+    timeout(DEFAULT_TIMEOUT, mcp.initialize()).await??;
+
+    let request_id = mcp
+        .send_list_models_request(ModelListParams::default())
+        .await?;
+    let response: JSONRPCResponse = timeout(
+        DEFAULT_TIMEOUT,
+        mcp.read_stream_until_response_message(RequestId::Integer(request_id)),
+    )
+    .await??;
+    let ModelListResponse { data, warnings, .. } = to_response::<ModelListResponse>(response)?;
+
+    assert!(!data.is_empty(), "built-in models are still listed");
+    let [warning] = warnings.as_slice() else {
+        panic!("expected one warning: {warnings:?}");
+    };
+    assert_eq!(warning.provider_id.as_deref(), Some("gateway"));
+    assert_eq!(warning.fetched_at, None);
+    assert!(
+        warning
+            .message
+            .starts_with("model list could not be refreshed: gateway: "),
+        "unexpected message: {}",
+        warning.message
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn list_models_passes_on_unknown_catalog_fields() -> Result<()> {
+    let codex_home = TempDir::new()?;
+    write_models_cache(codex_home.path())?;
+    // A newer catalog served a field this version does not know.
+    let cache_path = codex_home.path().join("models_cache.json");
+    let mut cache: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(&cache_path)?)?;
+    let models = cache["models"]
+        .as_array_mut()
+        .ok_or_else(|| anyhow!("cache has no models"))?;
+    for model in models {
+        if model["slug"] == "gpt-5.1-codex-mini" {
+            model["quota_tier"] = json!({"tier": "gold"});
+        }
+    }
+    std::fs::write(&cache_path, serde_json::to_string_pretty(&cache)?)?;
+    std::fs::write(
+        codex_home.path().join("config.toml"),
+        r#"
+catalog_locale = "C"
+
+[features]
+remote_models = true
+"#,
+    )?;
+    let mut mcp = McpProcess::new(codex_home.path()).await?;
+    timeout(DEFAULT_TIMEOUT, mcp.initialize()).await??;
+
+    let request_id = mcp
+        .send_list_models_request(ModelListParams::default())
+        .await?;
+    let response: JSONRPCResponse = timeout(
+        DEFAULT_TIMEOUT,
+        mcp.read_stream_until_response_message(RequestId::Integer(request_id)),
+    )
+    .await??;
+
+    let data = response.result["data"]
+        .as_array()
+        .ok_or_else(|| anyhow!("response has no data"))?;
+    let extra_by_id: HashMap<&str, Option<&serde_json::Value>> = data
+        .iter()
+        .filter_map(|model| Some((model["id"].as_str()?, model.get("extra"))))
+        .filter(|(id, _)| ["gpt-5.1-codex-mini", "gpt-5.1-codex-max"].contains(id))
+        .collect();
+    assert_eq!(
+        extra_by_id,
+        HashMap::from([
+            (
+                "gpt-5.1-codex-mini",
+                Some(&json!({"quota_tier": {"tier": "gold"}}))
+            ),
+            ("gpt-5.1-codex-max", None),
+        ])
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn list_models_reports_a_newer_minimum_client_version() -> Result<()> {
+    let codex_home = TempDir::new()?;
+    write_models_cache(codex_home.path())?;
+    let cache_path = codex_home.path().join("models_cache.json");
+    let mut cache: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(&cache_path)?)?;
+    cache["min_client_version"] = json!("999.0.0");
+    std::fs::write(&cache_path, serde_json::to_string_pretty(&cache)?)?;
+    std::fs::write(
+        codex_home.path().join("config.toml"),
+        r#"
+catalog_locale = "C"
+
+[features]
+remote_models = true
+"#,
+    )?;
+    let mut mcp = McpProcess::new(codex_home.path()).await?;
+    timeout(DEFAULT_TIMEOUT, mcp.initialize()).await??;
+
+    let request_id = mcp
+        .send_list_models_request(ModelListParams::default())
+        .await?;
+    let response: JSONRPCResponse = timeout(
+        DEFAULT_TIMEOUT,
+        mcp.read_stream_until_response_message(RequestId::Integer(request_id)),
+    )
+    .await??;
+    let ModelListResponse {
+        data,
+        client_version_notice,
+        ..
+    } = to_response::<ModelListResponse>(response)?;
+
+    let notice = client_version_notice.ok_or_else(|| anyhow!("expected a notice"))?;
+    assert_eq!(notice.min_client_version, "999.0.0");
+    assert!(
+        notice
+            .message
+            .contains("is below the minimum recommended by your gateway (999.0.0)"),
+        "{}",
+        notice.message
+    );
+    assert!(!data.is_empty(), "the models are still listed");
+    Ok(())
+}
+
+/// A codex home whose only catalog is `gateway`'s.
+fn gateway_codex_home(gateway: &MockServer) -> Result<TempDir> {
+    let codex_home = TempDir::new()?;
+    std::fs::write(
+        codex_home.path().join("config.toml"),
+        format!(
+            r#"
+model_provider = "gateway"
+model_catalog_sources = ["providers"]
+
+[model_providers.gateway]
+name = "Gateway"
+base_url = "{}/v1"
+model_catalog = "openai"
+"#,
+            gateway.uri()
+        ),
+    )?;
+    Ok(codex_home)
+}
+
+/// Serve `count` models, `oca-000` onwards, from the gateway's `/models`.
+async fn serve_gateway_models(gateway: &MockServer, count: usize) {
+    let data: Vec<serde_json::Value> = (0..count)
+        .map(|index| json!({"id": format!("oca-{index:03}"), "object": "model"}))
+        .collect();
+    Mock::given(method("GET"))
+        .and(path("/v1/models"))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_json(json!({"object": "list", "data": data})),
+        )
+        .mount(gateway)
+        .await;
+}
+
+async fn list_models(mcp: &mut McpProcess, params: ModelListParams) -> Result<ModelListResponse> {
+    let request_id = mcp.send_list_models_request(params).await?;
+    let response: JSONRPCResponse = timeout(
+        DEFAULT_TIMEOUT,
+        mcp.read_stream_until_response_message(RequestId::Integer(request_id)),
+    )
+    .await??;
+    to_response::<ModelListResponse>(response)
+}
+
+fn ids(models: &[Model]) -> Vec<String> {
+    models.iter().map(|model| model.id.clone()).collect()
+}
+
+#[tokio::test]
+async fn list_models_pages_by_offset_through_a_large_catalog() -> Result<()> {
+    let gateway = MockServer::start().await;
+    serve_gateway_models(&gateway, 300).await;
+    let codex_home = gateway_codex_home(&gateway)?;
+    let mut mcp = McpProcess::new(codex_home.path()).await?;
+    timeout(DEFAULT_TIMEOUT, mcp.initialize()).await??;
+
+    let everything = list_models(&mut mcp, ModelListParams::default()).await?;
+    assert_eq!(everything.data.len(), 300);
+    assert_eq!(everything.total_count, 300);
+    assert_eq!(everything.next_cursor, None);
+
+    let mut paged = Vec::new();
+    let mut offset = 0;
+    loop {
+        let page = list_models(
+            &mut mcp,
+            ModelListParams {
+                offset: Some(offset),
+                limit: Some(64),
+                ..Default::default()
+            },
+        )
+        .await?;
+        assert_eq!(
+            (page.total_count, page.revision),
+            (300, everything.revision)
+        );
+        offset += page.data.len() as u32;
+        paged.extend(page.data);
+        if page.next_cursor.is_none() {
+            break;
+        }
+        assert_eq!(page.next_cursor, Some(offset.to_string()));
+    }
+    assert_eq!(ids(&paged), ids(&everything.data));
+
+    let filtered = list_models(
+        &mut mcp,
+        ModelListParams {
+            query: Some("99".to_string()),
+            limit: Some(2),
+            ..Default::default()
+        },
+    )
+    .await?;
+    assert_eq!(
+        (
+            ids(&filtered.data),
+            filtered.total_count,
+            filtered.next_cursor
+        ),
+        (
+            vec!["oca-099".to_string(), "oca-199".to_string()],
+            3,
+            Some("2".to_string())
+        )
+    );
+
+    let searched = list_models(
+        &mut mcp,
+        ModelListParams {
+            query: Some("99".to_string()),
+            if_revision: Some(everything.revision),
+            ..Default::default()
+        },
+    )
+    .await?;
+    assert!(
+        !searched.not_modified,
+        "the unfiltered revision must not vouch for a filtered listing"
+    );
+    assert_eq!(searched.data.len(), 3);
+    let searched_again = list_models(
+        &mut mcp,
+        ModelListParams {
+            query: Some("99".to_string()),
+            if_revision: Some(searched.revision),
+            ..Default::default()
+        },
+    )
+    .await?;
+    assert!(searched_again.not_modified);
+
+    let page = list_models(
+        &mut mcp,
+        ModelListParams {
+            limit: Some(64),
+            if_revision: Some(everything.revision),
+            ..Default::default()
+        },
+    )
+    .await?;
+    assert!(!page.not_modified, "pages ignore ifRevision");
+    assert_eq!(page.data.len(), 64);
+
+    let request_id = mcp
+        .send_list_models_request(ModelListParams {
+            cursor: Some("2".to_string()),
+            offset: Some(2),
+            ..Default::default()
+        })
+        .await?;
+    let error: JSONRPCError = timeout(
+        DEFAULT_TIMEOUT,
+        mcp.read_stream_until_error_message(RequestId::Integer(request_id)),
+    )
+    .await??;
+    assert_eq!(error.error.code, INVALID_REQUEST_ERROR_CODE);
+    assert_eq!(error.error.message, "cursor and offset cannot both be set");
+    Ok(())
+}
+
+#[tokio::test]
+async fn list_models_pages_report_a_catalog_change_in_between() -> Result<()> {
+    let gateway = MockServer::start().await;
+    serve_gateway_models(&gateway, 300).await;
+    let codex_home = gateway_codex_home(&gateway)?;
+    let mut mcp = McpProcess::new(codex_home.path()).await?;
+    timeout(DEFAULT_TIMEOUT, mcp.initialize()).await??;
+
+    let first_page = list_models(
+        &mut mcp,
+        ModelListParams {
+            limit: Some(100),
+            ..Default::default()
+        },
+    )
+    .await?;
+    assert_eq!(first_page.total_count, 300);
+
+    gateway.reset().await;
+    serve_gateway_models(&gateway, 301).await;
+    let request_id = mcp
+        .send_model_refresh_request(ModelRefreshParams::default())
+        .await?;
+    timeout(
+        DEFAULT_TIMEOUT,
+        mcp.read_stream_until_response_message(RequestId::Integer(request_id)),
+    )
+    .await??;
+    timeout(
+        DEFAULT_TIMEOUT,
+        mcp.read_stream_until_notification_message("model/list/updated"),
+    )
+    .await??;
+
+    let second_page = list_models(
+        &mut mcp,
+        ModelListParams {
+            cursor: first_page.next_cursor.clone(),
+            limit: Some(100),
+            ..Default::default()
+        },
+    )
+    .await?;
+    assert_ne!(
+        second_page.revision, first_page.revision,
+        "the second page is listed at a newer revision than the first"
+    );
+    assert_eq!(second_page.total_count, 301);
+    Ok(())
+}
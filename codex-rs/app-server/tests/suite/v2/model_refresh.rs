@@ -0,0 +1,248 @@
+use std::time::Duration;
+
+use anyhow::Result;
+use app_test_support::McpProcess;
+use app_test_support::to_response;
+use codex_app_server_protocol::JSONRPCResponse;
+use codex_app_server_protocol::ModelCatalogRebuild;
+use codex_app_server_protocol::ModelListParams;
+use codex_app_server_protocol::ModelListResponse;
+use codex_app_server_protocol::ModelRefreshParams;
+use codex_app_server_protocol::ModelRefreshProgress;
+use codex_app_server_protocol::ModelRefreshProgressNotification;
+use codex_app_server_protocol::ModelRefreshResponse;
+use codex_app_server_protocol::RequestId;
+use pretty_assertions::assert_eq;
+use serde_json::json;
+use tempfile::TempDir;
+use tokio::time::timeout;
+use wiremock::Mock;
+use wiremock::MockServer;
+use wiremock::ResponseTemplate;
+use wiremock::matchers::method;
+use wiremock::matchers::path;
+
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// A codex home whose only fetched catalog is `gateway`'s.
+fn codex_home_for(gateway: &MockServer) -> Result<TempDir> {
+    let codex_home = TempDir::new()?;
+    std::fs::write(
+        codex_home.path().join("config.toml"),
+        format!(
+            r#"
+model_provider = "gateway"
+model_catalog_sources = ["builtin", "providers"]
+
+[model_providers.gateway]
+name = "Gateway"
+base_url = "{}/v1"
+model_catalog = "openai"
+"#,
+            gateway.uri()
+        ),
+    )?;
+    Ok(codex_home)
+}
+
+async fn serve_models(gateway: &MockServer, response: ResponseTemplate) {
+    Mock::given(method("GET"))
+        .and(path("/v1/models"))
+        .respond_with(response)
+        .mount(gateway)
+        .await;
+}
+
+fn listing(models: &[&str]) -> ResponseTemplate {
+    let data: Vec<serde_json::Value> = models
+        .iter()
+        .map(|id| json!({"id": id, "object": "model"}))
+        .collect();
+    ResponseTemplate::new(200).set_body_json(json!({"object": "list", "data": data}))
+}
+
+async fn start_refresh(mcp: &mut McpProcess) -> Result<ModelRefreshResponse> {
+    let request_id = mcp
+        .send_model_refresh_request(ModelRefreshParams::default())
+        .await?;
+    let response: JSONRPCResponse = timeout(
+        DEFAULT_TIMEOUT,
+        mcp.read_stream_until_response_message(RequestId::Integer(request_id)),
+    )
+    .await??;
+    to_response::<ModelRefreshResponse>(response)
+}
+
+/// The `lastRebuild` `model/list` reports for the configured catalog.
+async fn last_rebuild(mcp: &mut McpProcess) -> Result<Option<ModelCatalogRebuild>> {
+    let request_id = mcp
+        .send_list_models_request(ModelListParams::default())
+        .await?;
+    let response: JSONRPCResponse = timeout(
+        DEFAULT_TIMEOUT,
+        mcp.read_stream_until_response_message(RequestId::Integer(request_id)),
+    )
+    .await??;
+    Ok(to_response::<ModelListResponse>(response)?.last_rebuild)
+}
+
+/// Wait for the rebuild the server starts its catalog with to finish.
+async fn wait_for_first_rebuild(mcp: &mut McpProcess) -> Result<ModelCatalogRebuild> {
+    timeout(DEFAULT_TIMEOUT, async {
+        loop {
+            if let Some(rebuild) = last_rebuild(mcp).await? {
+                return anyhow::Ok(rebuild);
+            }
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+    })
+    .await?
+}
+
+/// The progress notifications of one refresh, through its last step.
+async fn read_progress(mcp: &mut McpProcess) -> Result<Vec<ModelRefreshProgressNotification>> {
+    let mut steps = Vec::new();
+    loop {
+        let notification = timeout(
+            DEFAULT_TIMEOUT,
+            mcp.read_stream_until_notification_message("model/refresh/progress"),
+        )
+        .await??;
+        let step: ModelRefreshProgressNotification =
+            serde_json::from_value(notification.params.unwrap_or_default())?;
+        let last = matches!(
+            step.progress,
+            ModelRefreshProgress::Completed { .. } | ModelRefreshProgress::Failed { .. }
+        );
+        steps.push(step);
+        if last {
+            return Ok(steps);
+        }
+    }
+}
+
+#[tokio::test]
+async fn model_refresh_reports_each_step() -> Result<()> {
+    let gateway = MockServer::start().await;
+    serve_models(&gateway, listing(&["oca-large", "oca-small"])).await;
+    let codex_home = codex_home_for(&gateway)?;
+    let mut mcp = McpProcess::new(codex_home.path()).await?;
+    timeout(DEFAULT_TIMEOUT, mcp.initialize()).await??;
+
+    let ModelRefreshResponse {
+        task_id,
+        already_running,
+    } = start_refresh(&mut mcp).await?;
+    assert!(!already_running);
+
+    let steps = read_progress(&mut mcp).await?;
+    assert!(steps.iter().all(|step| step.task_id == task_id));
+    let progress: Vec<ModelRefreshProgress> = steps.into_iter().map(|step| step.progress).collect();
+    let [
+        ModelRefreshProgress::Started {},
+        ModelRefreshProgress::Fetched { model_count },
+        ModelRefreshProgress::Completed { revision },
+    ] = progress.as_slice()
+    else {
+        panic!("unexpected steps: {progress:?}");
+    };
+    assert_eq!(*model_count, 2);
+
+    let request_id = mcp
+        .send_list_models_request(ModelListParams::default())
+        .await?;
+    let response: JSONRPCResponse = timeout(
+        DEFAULT_TIMEOUT,
+        mcp.read_stream_until_response_message(RequestId::Integer(request_id)),
+    )
+    .await??;
+    let listed = to_response::<ModelListResponse>(response)?;
+    assert_eq!(
+        listed.revision, *revision,
+        "a re-list after the refresh reports the revision it completed at"
+    );
+    let rebuild = listed
+        .last_rebuild
+        .expect("the refresh rebuilt the catalog");
+    assert!(
+        rebuild.reasons.contains(&"manual".to_string()),
+        "{rebuild:?}"
+    );
+    assert_eq!(rebuild.error, None);
+    Ok(())
+}
+
+#[tokio::test]
+async fn model_refresh_reports_a_failed_catalog() -> Result<()> {
+    let gateway = MockServer::start().await;
+    serve_models(&gateway, ResponseTemplate::new(503)).await;
+    let codex_home = codex_home_for(&gateway)?;
+    let mut mcp = McpProcess::new(codex_home.path()).await?;
+    timeout(DEFAULT_TIMEOUT, mcp.initialize()).await??;
+
+    start_refresh(&mut mcp).await?;
+
+    let steps = read_progress(&mut mcp).await?;
+    let progress: Vec<ModelRefreshProgress> = steps.into_iter().map(|step| step.progress).collect();
+    let [
+        ModelRefreshProgress::Started {},
+        ModelRefreshProgress::Failed { message },
+    ] = progress.as_slice()
+    else {
+        panic!("unexpected steps: {progress:?}");
+    };
+    assert!(
+        message.starts_with("model list could not be refreshed: gateway: "),
+        "unexpected message: {message}"
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn concurrent_model_refreshes_share_one_task() -> Result<()> {
+    let gateway = MockServer::start().await;
+    serve_models(
+        &gateway,
+        listing(&["oca-large"]).set_delay(Duration::from_millis(500)),
+    )
+    .await;
+    let codex_home = codex_home_for(&gateway)?;
+    let mut mcp = McpProcess::new(codex_home.path()).await?;
+    timeout(DEFAULT_TIMEOUT, mcp.initialize()).await??;
+    // The catalog is first listed when the server starts keeping it rebuilt.
+    wait_for_first_rebuild(&mut mcp).await?;
+    let listings = |requests: Vec<wiremock::Request>| {
+        requests
+            .iter()
+            .filter(|request| request.url.path() == "/v1/models")
+            .count()
+    };
+    let before = listings(gateway.received_requests().await.unwrap_or_default());
+
+    let first = start_refresh(&mut mcp).await?;
+    let second = start_refresh(&mut mcp).await?;
+    assert_eq!(
+        second,
+        ModelRefreshResponse {
+            task_id: first.task_id.clone(),
+            already_running: true,
+        }
+    );
+
+    let steps = read_progress(&mut mcp).await?;
+    let last = steps.last().map(|step| &step.progress);
+    assert!(
+        matches!(last, Some(ModelRefreshProgress::Completed { .. })),
+        "{steps:?}"
+    );
+    assert_eq!(
+        listings(gateway.received_requests().await.unwrap_or_default()),
+        before + 1
+    );
+
+    // Once it has finished, the next request starts a new refresh.
+    let third = start_refresh(&mut mcp).await?;
+    assert!(!third.already_running);
+    assert_ne!(third.task_id, first.task_id);
+    Ok(())
+}
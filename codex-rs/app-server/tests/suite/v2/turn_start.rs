@@ -11,6 +11,7 @@ use app_test_support::to_response;
 use codex_app_server_protocol::ApprovalDecision;
 use codex_app_server_protocol::CommandExecutionRequestApprovalResponse;
 use codex_app_server_protocol::CommandExecutionStatus;
+use codex_app_server_protocol::CostLimitRequestApprovalResponse;
 use codex_app_server_protocol::FileChangeOutputDeltaNotification;
 use codex_app_server_protocol::FileChangeRequestApprovalResponse;
 use codex_app_server_protocol::ItemCompletedNotification;
@@ -32,6 +33,7 @@ use codex_app_server_protocol::TurnStatus;
 use codex_app_server_protocol::UserInput as V2UserInput;
 use codex_core::protocol_config_types::ReasoningSummary;
 use codex_protocol::openai_models::ReasoningEffort;
+use core_test_support::responses;
 use core_test_support::skip_if_no_network;
 use pretty_assertions::assert_eq;
 use std::path::Path;
@@ -1057,6 +1059,121 @@ unified_exec = true
 }
 
 // Helper to create a config.toml pointing at the mock model server.
+#[tokio::test]
+async fn turn_start_asks_before_a_turn_past_the_cost_limit_v2() -> Result<()> {
+    let server = responses::start_mock_server().await;
+    // 200k input tokens at $10 per million cost $2, past the $1 limit.
+    let response_mock = responses::mount_sse_once(
+        &server,
+        responses::sse(vec![
+            responses::ev_response_created("resp-1"),
+            responses::ev_assistant_message("msg-1", "done"),
+            responses::ev_completed_with_tokens("resp-1", 200_000),
+        ]),
+    )
+    .await;
+
+    let codex_home = TempDir::new()?;
+    std::fs::write(
+        codex_home.path().join("config.toml"),
+        format!(
+            r#"
+model = "mock-model"
+approval_policy = "never"
+sandbox_mode = "read-only"
+model_provider = "priced"
+session_cost_limit_usd = 1.0
+
+[model_providers.priced]
+name = "Priced"
+base_url = "{}/v1"
+wire_api = "responses"
+request_max_retries = 0
+stream_max_retries = 0
+
+[model_providers.priced.model_pricing."mock-model"]
+input_per_million = 10.0
+output_per_million = 10.0
+"#,
+            server.uri()
+        ),
+    )?;
+
+    let mut mcp = McpProcess::new(codex_home.path()).await?;
+    timeout(DEFAULT_READ_TIMEOUT, mcp.initialize()).await??;
+
+    let start_id = mcp
+        .send_thread_start_request(ThreadStartParams::default())
+        .await?;
+    let start_resp: JSONRPCResponse = timeout(
+        DEFAULT_READ_TIMEOUT,
+        mcp.read_stream_until_response_message(RequestId::Integer(start_id)),
+    )
+    .await??;
+    let ThreadStartResponse { thread, .. } = to_response::<ThreadStartResponse>(start_resp)?;
+
+    // The first turn starts below the limit, so it runs without asking.
+    start_text_turn(&mut mcp, &thread.id, "first").await?;
+    timeout(
+        DEFAULT_READ_TIMEOUT,
+        mcp.read_stream_until_notification_message("turn/completed"),
+    )
+    .await??;
+
+    let turn_id = start_text_turn(&mut mcp, &thread.id, "second").await?;
+    let server_req = timeout(
+        DEFAULT_READ_TIMEOUT,
+        mcp.read_stream_until_request_message(),
+    )
+    .await??;
+    let ServerRequest::CostLimitRequestApproval { request_id, params } = server_req else {
+        panic!("expected CostLimitRequestApproval request")
+    };
+    assert_eq!(params.thread_id, thread.id);
+    assert_eq!(params.turn_id, turn_id);
+    assert_eq!(params.cost_usd, 2.0);
+    assert_eq!(params.limit_usd, 1.0);
+
+    mcp.send_response(
+        request_id,
+        serde_json::to_value(CostLimitRequestApprovalResponse {
+            decision: ApprovalDecision::Decline,
+        })?,
+    )
+    .await?;
+    timeout(
+        DEFAULT_READ_TIMEOUT,
+        mcp.read_stream_until_notification_message("turn/completed"),
+    )
+    .await??;
+
+    assert_eq!(
+        response_mock.requests().len(),
+        1,
+        "the declined turn sends no request"
+    );
+    Ok(())
+}
+
+/// Start a turn on `thread_id` with `text` as its input, returning its id.
+async fn start_text_turn(mcp: &mut McpProcess, thread_id: &str, text: &str) -> Result<String> {
+    let turn_id = mcp
+        .send_turn_start_request(TurnStartParams {
+            thread_id: thread_id.to_string(),
+            input: vec![V2UserInput::Text {
+                text: text.to_string(),
+            }],
+            ..Default::default()
+        })
+        .await?;
+    let turn_resp: JSONRPCResponse = timeout(
+        DEFAULT_READ_TIMEOUT,
+        mcp.read_stream_until_response_message(RequestId::Integer(turn_id)),
+    )
+    .await??;
+    Ok(to_response::<TurnStartResponse>(turn_resp)?.turn.id)
+}
+
 fn create_config_toml(
     codex_home: &Path,
     server_uri: &str,
@@ -2,12 +2,15 @@ use std::path::PathBuf;
 
 use clap::Parser;
 use codex_common::CliConfigOverrides;
+use codex_core::AuthManager;
 use codex_core::config::Config;
+use codex_core::models_manager::manager::ModelsManager;
 
 use crate::chatgpt_token::init_chatgpt_token_from_auth;
 use crate::get_task::GetTaskResponse;
 use crate::get_task::OutputItem;
 use crate::get_task::PrOutputItem;
+use crate::get_task::TaskMetadata;
 use crate::get_task::get_task;
 
 /// Applies the latest diff from a Codex agent task.
@@ -34,7 +37,30 @@ pub async fn run_apply_command(
         .await?;
 
     let task_response = get_task(&config, apply_cli.task_id).await?;
-    apply_diff_from_task(task_response, cwd).await
+    let produced_by = produced_by(&task_response.model(), &config).await;
+    apply_diff_from_task(task_response, cwd).await?;
+    if let Some(label) = produced_by {
+        println!("Produced by {label}");
+    }
+    Ok(())
+}
+
+/// The task's model, by its display name in the local catalog or its raw
+/// slug when the catalog does not list it, followed by the effort.
+async fn produced_by(metadata: &TaskMetadata, config: &Config) -> Option<String> {
+    let model = metadata.model.as_deref()?;
+    let auth_manager = AuthManager::shared(
+        config.codex_home.clone(),
+        false,
+        config.cli_auth_credentials_store_mode,
+    );
+    let display_name = ModelsManager::new(auth_manager)
+        .display_name(model, config)
+        .await;
+    Some(match &metadata.reasoning_effort {
+        Some(effort) => format!("{display_name} ({effort})"),
+        None => display_name,
+    })
 }
 
 pub async fn apply_diff_from_task(
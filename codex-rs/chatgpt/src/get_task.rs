@@ -6,6 +6,89 @@ use crate::chatgpt_client::chatgpt_get_request;
 #[derive(Debug, Deserialize)]
 pub struct GetTaskResponse {
     pub current_diff_task_turn: Option<AssistantTurn>,
+    #[serde(default)]
+    pub current_assistant_turn: Option<WorklogTurn>,
+    #[serde(default)]
+    pub task: Option<TaskInfo>,
+}
+
+impl GetTaskResponse {
+    /// The model the task ran with: the `model_slug` of its last assistant
+    /// message, else the model recorded at submission. The recorded effort is
+    /// kept only while it belongs to that model.
+    pub fn model(&self) -> TaskMetadata {
+        let submitted = self
+            .task
+            .as_ref()
+            .map(|task| &task.metadata)
+            .cloned()
+            .unwrap_or_default();
+        let ran_with = self.current_assistant_turn.as_ref().and_then(|turn| {
+            turn.worklog
+                .messages
+                .iter()
+                .rev()
+                .filter(|message| message.author.role.as_deref() == Some("assistant"))
+                .find_map(|message| message.metadata.model_slug.clone())
+                .filter(|slug| !slug.is_empty())
+        });
+        match ran_with {
+            Some(model) if submitted.model.as_ref() == Some(&model) => submitted,
+            Some(model) => TaskMetadata {
+                model: Some(model),
+                reasoning_effort: None,
+            },
+            None => submitted,
+        }
+    }
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct TaskInfo {
+    #[serde(default)]
+    pub metadata: TaskMetadata,
+}
+
+/// The model a task was submitted with, when it recorded one. The effort is
+/// kept as sent so an unfamiliar value never stops the diff from applying.
+#[derive(Debug, Default, Clone, PartialEq, Deserialize)]
+pub struct TaskMetadata {
+    #[serde(default)]
+    pub model: Option<String>,
+    #[serde(default)]
+    pub reasoning_effort: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct WorklogTurn {
+    #[serde(default)]
+    pub worklog: Worklog,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct Worklog {
+    #[serde(default)]
+    pub messages: Vec<WorklogMessage>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct WorklogMessage {
+    #[serde(default)]
+    pub author: WorklogAuthor,
+    #[serde(default)]
+    pub metadata: WorklogMessageMetadata,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct WorklogAuthor {
+    #[serde(default)]
+    pub role: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct WorklogMessageMetadata {
+    #[serde(default)]
+    pub model_slug: Option<String>,
 }
 
 // Only relevant fields for our extraction
@@ -0,0 +1,125 @@
+use std::path::PathBuf;
+
+use anyhow::Context;
+use anyhow::Result;
+use codex_common::CliConfigOverrides;
+use codex_core::config::find_codex_home;
+use codex_core::config::load_config_as_toml_with_cli_overrides;
+use codex_core::config::load_config_toml_file;
+use codex_core::models_manager::bundle::Finding;
+use codex_core::models_manager::bundle::Severity;
+use codex_core::models_manager::bundle::validate_bundle;
+use codex_protocol::openai_models::ModelsResponse;
+use codex_utils_absolute_path::AbsolutePathBuf;
+
+/// Subcommands:
+/// - `validate` — check config.toml against a catalog file before rolling both out
+#[derive(Debug, clap::Parser)]
+pub struct ConfigCli {
+    #[clap(flatten)]
+    pub config_overrides: CliConfigOverrides,
+
+    #[command(subcommand)]
+    pub subcommand: ConfigSubcommand,
+}
+
+#[derive(Debug, clap::Subcommand)]
+pub enum ConfigSubcommand {
+    /// Check config.toml against a models.json catalog, using the catalog as
+    /// the only source of models. Exits with status 1 when any error is found.
+    Validate(ValidateArgs),
+}
+
+#[derive(Debug, clap::Parser)]
+pub struct ValidateArgs {
+    /// Catalog in the format of the `/models` response, such as the
+    /// `models.json` shipped alongside a managed config.
+    #[arg(long, value_name = "FILE")]
+    pub catalog: PathBuf,
+
+    /// Config file to check on its own, such as a managed config before it
+    /// is rolled out. Defaults to the config Codex would load here.
+    #[arg(long = "config", value_name = "FILE")]
+    pub config: Option<PathBuf>,
+
+    /// Output the findings as JSON.
+    #[arg(long)]
+    pub json: bool,
+}
+
+impl ConfigCli {
+    pub async fn run(self) -> Result<()> {
+        let ConfigCli {
+            config_overrides,
+            subcommand,
+        } = self;
+
+        match subcommand {
+            ConfigSubcommand::Validate(args) => {
+                run_validate(&config_overrides, args).await?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+async fn run_validate(config_overrides: &CliConfigOverrides, args: ValidateArgs) -> Result<()> {
+    let ValidateArgs {
+        catalog,
+        config,
+        json,
+    } = args;
+    let contents = std::fs::read_to_string(&catalog)
+        .with_context(|| format!("failed to read catalog {}", catalog.display()))?;
+    let catalog: ModelsResponse = serde_json::from_str(&contents)
+        .with_context(|| format!("{} is not a valid model catalog", catalog.display()))?;
+
+    let overrides = config_overrides
+        .parse_overrides()
+        .map_err(anyhow::Error::msg)?;
+    // Only the raw keys are read, so a config that would fail to load, such
+    // as one naming a preset the catalog lacks, is still reported key by key.
+    let config_toml = match config {
+        Some(path) => load_config_toml_file(&path, &overrides)
+            .with_context(|| format!("failed to read config {}", path.display()))?,
+        None => load_config_as_toml_with_cli_overrides(
+            &find_codex_home().context("failed to find the Codex home directory")?,
+            &AbsolutePathBuf::current_dir()?,
+            overrides,
+        )
+        .await
+        .context("failed to load configuration")?,
+    };
+
+    let findings = validate_bundle(&config_toml, &catalog.models);
+    print_findings(&findings, json)?;
+    if findings
+        .iter()
+        .any(|finding| finding.severity == Severity::Error)
+    {
+        std::process::exit(1);
+    }
+    Ok(())
+}
+
+fn print_findings(findings: &[Finding], json: bool) -> Result<()> {
+    if json {
+        println!("{}", serde_json::to_string_pretty(findings)?);
+        return Ok(());
+    }
+    if findings.is_empty() {
+        println!("No problems found.");
+        return Ok(());
+    }
+    for finding in findings {
+        println!("{finding}");
+    }
+    let errors = findings
+        .iter()
+        .filter(|finding| finding.severity == Severity::Error)
+        .count();
+    let warnings = findings.len() - errors;
+    println!("{errors} error(s), {warnings} warning(s).");
+    Ok(())
+}
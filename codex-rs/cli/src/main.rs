@@ -30,11 +30,19 @@ use owo_colors::OwoColorize;
 use std::path::PathBuf;
 use supports_color::Stream;
 
+mod config_cmd;
 mod mcp_cmd;
+mod model_fetch_cmd;
+mod models_cmd;
+mod rollout_cmd;
 #[cfg(not(windows))]
 mod wsl_paths;
 
+use crate::config_cmd::ConfigCli;
 use crate::mcp_cmd::McpCli;
+use crate::model_fetch_cmd::ModelFetchCommand;
+use crate::models_cmd::ModelsCli;
+use crate::rollout_cmd::RolloutCommand;
 
 use codex_core::config::Config;
 use codex_core::config::ConfigOverrides;
@@ -68,6 +76,11 @@ struct MultitoolCli {
     #[clap(flatten)]
     pub feature_toggles: FeatureToggles,
 
+    /// Skip all model catalog fetching and use bundled presets plus the on-disk
+    /// cache. Equivalent to `-c offline=true`.
+    #[arg(long, global = true)]
+    offline: bool,
+
     #[clap(flatten)]
     interactive: TuiCli,
 
@@ -96,6 +109,12 @@ enum Subcommand {
     /// [experimental] Run the Codex MCP server (stdio transport).
     McpServer,
 
+    /// Inspect the models available to Codex.
+    Models(ModelsCli),
+
+    /// Check configuration, e.g. against a catalog file before rollout.
+    Config(ConfigCli),
+
     /// [experimental] Run the app server or related tooling.
     AppServer(AppServerCommand),
 
@@ -177,6 +196,14 @@ enum SandboxCommand {
 
     /// Run a command under Windows restricted token (Windows only).
     Windows(WindowsCommand),
+
+    /// Print the session header of a rollout file, including the model
+    /// catalog the session started with, or the whole transcript as Markdown.
+    Rollout(RolloutCommand),
+
+    /// Fetch a provider's model catalog once and report each phase of the
+    /// request, for diagnosing connectivity problems.
+    ModelFetch(ModelFetchCommand),
 }
 
 #[derive(Debug, Parser)]
@@ -322,6 +349,21 @@ fn handle_app_exit(exit_info: AppExitInfo) -> anyhow::Result<()> {
     Ok(())
 }
 
+/// Run `codex exec`, exiting with the status of a failure that has one of
+/// its own, such as reaching `session_cost_limit_usd`.
+async fn run_exec(
+    exec_cli: ExecCli,
+    codex_linux_sandbox_exe: Option<PathBuf>,
+) -> anyhow::Result<()> {
+    if let Err(err) = codex_exec::run_main(exec_cli, codex_linux_sandbox_exe).await {
+        if let Some(code) = codex_exec::failure_exit_code(&err) {
+            std::process::exit(code);
+        }
+        return Err(err);
+    }
+    Ok(())
+}
+
 /// Run the update action and print the result.
 fn run_update_action(action: UpdateAction) -> anyhow::Result<()> {
     println!();
@@ -437,6 +479,7 @@ async fn cli_main(codex_linux_sandbox_exe: Option<PathBuf>) -> anyhow::Result<()
     let MultitoolCli {
         config_overrides: mut root_config_overrides,
         feature_toggles,
+        offline,
         mut interactive,
         subcommand,
     } = MultitoolCli::parse();
@@ -444,6 +487,11 @@ async fn cli_main(codex_linux_sandbox_exe: Option<PathBuf>) -> anyhow::Result<()
     // Fold --enable/--disable into config overrides so they flow to all subcommands.
     let toggle_overrides = feature_toggles.to_overrides()?;
     root_config_overrides.raw_overrides.extend(toggle_overrides);
+    if offline {
+        root_config_overrides
+            .raw_overrides
+            .push("offline=true".to_string());
+    }
 
     match subcommand {
         None => {
@@ -459,7 +507,7 @@ async fn cli_main(codex_linux_sandbox_exe: Option<PathBuf>) -> anyhow::Result<()
                 &mut exec_cli.config_overrides,
                 root_config_overrides.clone(),
             );
-            codex_exec::run_main(exec_cli, codex_linux_sandbox_exe).await?;
+            run_exec(exec_cli, codex_linux_sandbox_exe).await?;
         }
         Some(Subcommand::Review(review_args)) => {
             let mut exec_cli = ExecCli::try_parse_from(["codex", "exec"])?;
@@ -468,7 +516,7 @@ async fn cli_main(codex_linux_sandbox_exe: Option<PathBuf>) -> anyhow::Result<()
                 &mut exec_cli.config_overrides,
                 root_config_overrides.clone(),
             );
-            codex_exec::run_main(exec_cli, codex_linux_sandbox_exe).await?;
+            run_exec(exec_cli, codex_linux_sandbox_exe).await?;
         }
         Some(Subcommand::McpServer) => {
             codex_mcp_server::run_main(codex_linux_sandbox_exe, root_config_overrides).await?;
@@ -478,6 +526,20 @@ async fn cli_main(codex_linux_sandbox_exe: Option<PathBuf>) -> anyhow::Result<()
             prepend_config_flags(&mut mcp_cli.config_overrides, root_config_overrides.clone());
             mcp_cli.run().await?;
         }
+        Some(Subcommand::Models(mut models_cli)) => {
+            prepend_config_flags(
+                &mut models_cli.config_overrides,
+                root_config_overrides.clone(),
+            );
+            models_cli.run().await?;
+        }
+        Some(Subcommand::Config(mut config_cli)) => {
+            prepend_config_flags(
+                &mut config_cli.config_overrides,
+                root_config_overrides.clone(),
+            );
+            config_cli.run().await?;
+        }
         Some(Subcommand::AppServer(app_server_cli)) => match app_server_cli.subcommand {
             None => {
                 codex_app_server::run_main(codex_linux_sandbox_exe, root_config_overrides).await?;
@@ -591,6 +653,16 @@ async fn cli_main(codex_linux_sandbox_exe: Option<PathBuf>) -> anyhow::Result<()
                 )
                 .await?;
             }
+            SandboxCommand::Rollout(rollout_cli) => {
+                rollout_cmd::run_rollout_command(rollout_cli).await?;
+            }
+            SandboxCommand::ModelFetch(mut model_fetch_cli) => {
+                prepend_config_flags(
+                    &mut model_fetch_cli.config_overrides,
+                    root_config_overrides.clone(),
+                );
+                model_fetch_cmd::run_model_fetch_command(model_fetch_cli).await?;
+            }
         },
         Some(Subcommand::Execpolicy(ExecpolicyCommand { sub })) => match sub {
             ExecpolicySubcommand::Check(cmd) => run_execpolicycheck(cmd)?,
@@ -795,6 +867,7 @@ mod tests {
             config_overrides: root_overrides,
             subcommand,
             feature_toggles: _,
+            offline: _,
         } = cli;
 
         let Subcommand::Resume(ResumeCommand {
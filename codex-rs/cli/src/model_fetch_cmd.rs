@@ -0,0 +1,112 @@
+use anyhow::Context;
+use anyhow::Result;
+use anyhow::bail;
+use codex_common::CliConfigOverrides;
+use codex_core::config::Config;
+use codex_core::models_manager::catalog_export::IMPORTED_CATALOG_FILE;
+use codex_core::models_manager::fetch_diagnostics::FetchDiagnosis;
+use codex_core::models_manager::fetch_diagnostics::PhaseStatus;
+use codex_core::models_manager::fetch_diagnostics::diagnose_provider_fetch;
+
+/// Fetch a provider's model catalog once, the way a refresh would, and report
+/// how long each phase of the request took. Nothing is cached.
+#[derive(Debug, clap::Parser)]
+pub struct ModelFetchCommand {
+    /// Provider to fetch from, as named under `[model_providers]`. Defaults
+    /// to the active `model_provider`.
+    #[arg(long, value_name = "ID")]
+    pub provider: Option<String>,
+
+    /// Fetch from this base URL instead of the provider's `base_url`.
+    #[arg(long, value_name = "URL")]
+    pub base_url: Option<String>,
+
+    /// Print the report as JSON.
+    #[arg(long, default_value_t = false)]
+    pub json: bool,
+
+    #[clap(skip)]
+    pub config_overrides: CliConfigOverrides,
+}
+
+pub async fn run_model_fetch_command(cmd: ModelFetchCommand) -> Result<()> {
+    let overrides = cmd
+        .config_overrides
+        .parse_overrides()
+        .map_err(anyhow::Error::msg)?;
+    let config = Config::load_with_cli_overrides(overrides)
+        .await
+        .context("failed to load configuration")?;
+    if config.offline {
+        bail!("cannot fetch models while offline; drop `--offline` or set `offline = false`");
+    }
+    let provider_id = cmd
+        .provider
+        .unwrap_or_else(|| config.model_provider_id.clone());
+    let imported = config.codex_home.join(IMPORTED_CATALOG_FILE);
+    if imported.exists() {
+        eprintln!(
+            "Note: sessions list the catalog imported at {}, not what this fetch returns; `codex models import --clear` removes it.",
+            imported.display()
+        );
+    }
+    let diagnosis = diagnose_provider_fetch(&config, &provider_id, cmd.base_url).await?;
+
+    if cmd.json {
+        println!("{}", serde_json::to_string_pretty(&diagnosis)?);
+    } else {
+        print_diagnosis(&diagnosis);
+    }
+    if let Some(phase) = diagnosis.failed_phase() {
+        bail!("model fetch for provider `{provider_id}` failed at the {phase} phase");
+    }
+    Ok(())
+}
+
+fn print_diagnosis(diagnosis: &FetchDiagnosis) {
+    println!(
+        "Model catalog fetch for provider `{}`",
+        diagnosis.provider_id
+    );
+    println!("  {} {}", diagnosis.method, diagnosis.url);
+    if let Some(proxy) = &diagnosis.proxy {
+        println!("  proxy: {proxy}");
+    }
+    println!("Request headers:");
+    for header in &diagnosis.request_headers {
+        println!("  {}: {}", header.name, header.value);
+    }
+    if let Some(token) = &diagnosis.token {
+        println!("Token: {token}");
+    }
+    println!("Phases:");
+    for report in &diagnosis.phases {
+        let status = match report.status {
+            PhaseStatus::Ok => "ok",
+            PhaseStatus::Failed => "FAILED",
+            PhaseStatus::Skipped => "skipped",
+        };
+        let elapsed = report
+            .elapsed_ms
+            .map(|ms| format!("{ms} ms"))
+            .unwrap_or_default();
+        println!(
+            "  {:<10}  {status:<7}  {elapsed:>8}  {}",
+            report.phase.to_string(),
+            report.detail
+        );
+    }
+    let mut summary = Vec::new();
+    if let Some(status) = diagnosis.http_status {
+        summary.push(format!("HTTP {status}"));
+    }
+    if let Some(bytes) = diagnosis.payload_bytes {
+        summary.push(format!("{bytes} bytes"));
+    }
+    if let Some(count) = diagnosis.model_count {
+        summary.push(format!("{count} models"));
+    }
+    if !summary.is_empty() {
+        println!("Result: {}", summary.join(", "));
+    }
+}
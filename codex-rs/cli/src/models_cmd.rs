@@ -0,0 +1,1089 @@
+use anyhow::Context;
+use anyhow::Result;
+use anyhow::bail;
+use chrono::DateTime;
+use chrono::SecondsFormat;
+use chrono::Utc;
+use codex_common::CliConfigOverrides;
+use codex_common::ModelValueParser;
+use codex_common::display_width::display_width;
+use codex_common::display_width::pad_to_width;
+use codex_common::enumerate_model_effort_matrix;
+use codex_common::fuzzy_match::fuzzy_match;
+use codex_common::plain_model_fields;
+use codex_core::AuthManager;
+use codex_core::config::CONFIG_TOML_FILE;
+use codex_core::config::Config;
+use codex_core::config::ConfigOverrides;
+use codex_core::config::edit::ConfigEditsBuilder;
+use codex_core::config::types::ModelCatalogSource;
+use codex_core::models_manager::catalog_export::CatalogExport;
+use codex_core::models_manager::catalog_export::install_imported_catalog;
+use codex_core::models_manager::catalog_export::remove_imported_catalog;
+use codex_core::models_manager::diff::diff_catalogs;
+use codex_core::models_manager::manager::ModelList;
+use codex_core::models_manager::manager::ModelsManager;
+use codex_core::models_manager::provenance::CacheLookup;
+use codex_core::models_manager::provenance::CatalogFreshness;
+use codex_core::models_manager::provenance::CatalogSource;
+use codex_core::models_manager::provenance::CatalogStatus;
+use codex_core::models_manager::provenance::PresetProvenance;
+use codex_core::models_manager::provenance::PresetSource;
+use codex_core::models_manager::rebuild_queue::CatalogRebuild;
+use codex_core::models_manager::rebuild_queue::RebuildReason;
+use codex_core::models_manager::resolution::ConsideredSetting;
+use codex_core::models_manager::resolution::ModelResolution;
+use codex_core::models_manager::resolution::resolve_model;
+use codex_protocol::openai_models::ContextWindowSource;
+use codex_protocol::openai_models::ContextWindowUnit;
+use codex_protocol::openai_models::EffortSelection;
+use codex_protocol::openai_models::MIN_UNHINTED_CONTEXT_WINDOW;
+use codex_protocol::openai_models::ModelPreset;
+use std::collections::HashSet;
+use std::io::IsTerminal;
+use std::io::Write;
+use std::path::Path;
+use std::path::PathBuf;
+
+/// Upper bound on "did you mean" suggestions for an unknown model.
+const MAX_SUGGESTIONS: usize = 3;
+
+/// Subcommands:
+/// - `list` — list the models available in the picker (with `--json` or `--ids`)
+/// - `efforts` — describe the reasoning efforts a model supports
+/// - `show` — show the details of one model
+/// - `matrix` — list every model and effort combination, for eval harnesses
+/// - `refresh` — re-fetch model catalogs, ignoring the cache TTL
+/// - `set-default` — write a model from the catalog into config.toml
+/// - `status` — show where the catalog came from and how fresh it is
+/// - `which` — show the model and effort a session would start with, and why
+/// - `export` — write the catalog and its provenance to a file for bug reports
+/// - `import` — check an export, or list it in place of the fetched catalogs
+#[derive(Debug, clap::Parser)]
+pub struct ModelsCli {
+    #[clap(flatten)]
+    pub config_overrides: CliConfigOverrides,
+
+    #[command(subcommand)]
+    pub subcommand: ModelsSubcommand,
+}
+
+#[derive(Debug, clap::Subcommand)]
+pub enum ModelsSubcommand {
+    /// List built-in models plus models served by providers with a `model_catalog`.
+    List(ListArgs),
+
+    /// Describe each reasoning effort a model supports.
+    Efforts(EffortsArgs),
+
+    /// Show the details of one model, including how current it is.
+    Show(ShowArgs),
+
+    /// List every model crossed with each reasoning effort it supports.
+    Matrix(MatrixArgs),
+
+    /// Re-fetch the remote and provider model catalogs, ignoring the cache TTL.
+    Refresh,
+
+    /// Make a model from the catalog the default in config.toml.
+    SetDefault(SetDefaultArgs),
+
+    /// Show where each model came from, cache freshness, and the last fetch error.
+    Status(StatusArgs),
+
+    /// Show the model and reasoning effort a session would start with, and
+    /// which setting decided each.
+    Which(WhichArgs),
+
+    /// Write the merged catalog, with where each model came from and how the
+    /// catalogs were last fetched, for a bug report.
+    Export(ExportArgs),
+
+    /// Check a catalog written by `codex models export`, or load it in place
+    /// of the fetched catalogs.
+    Import(ImportArgs),
+}
+
+#[derive(Debug, clap::Parser)]
+pub struct ListArgs {
+    /// Output the models as JSON.
+    #[arg(long, conflicts_with_all = ["ids", "plain"])]
+    pub json: bool,
+
+    /// Print only model ids, one per line, for shell completion scripts.
+    #[arg(long, conflicts_with = "plain")]
+    pub ids: bool,
+
+    /// Print each model as labeled lines, one field per line, instead of a
+    /// table; for screen readers.
+    #[arg(long)]
+    pub plain: bool,
+}
+
+#[derive(Debug, clap::Parser)]
+pub struct EffortsArgs {
+    /// Preset id or model slug, as shown by `codex models list`.
+    pub model: String,
+}
+
+#[derive(Debug, clap::Parser)]
+pub struct ShowArgs {
+    /// Preset id or model slug, as shown by `codex models list`.
+    pub model: String,
+
+    /// Output the preset as JSON.
+    #[arg(long)]
+    pub json: bool,
+}
+
+#[derive(Debug, clap::Parser)]
+pub struct StatusArgs {
+    /// Output the catalog status as JSON.
+    #[arg(long)]
+    pub json: bool,
+}
+
+#[derive(Debug, clap::Parser)]
+pub struct MatrixArgs {
+    /// Output the combinations as JSON.
+    #[arg(long)]
+    pub json: bool,
+
+    /// Also include models hidden from the picker.
+    #[arg(long)]
+    pub include_hidden: bool,
+}
+
+#[derive(Debug, clap::Parser)]
+pub struct WhichArgs {
+    /// Model the session would be started with, as for `codex --model`.
+    #[arg(long, short = 'm', value_parser = ModelValueParser)]
+    pub model: Option<String>,
+
+    /// Configuration profile from config.toml, as for `codex --profile`.
+    #[arg(long = "profile", short = 'p')]
+    pub config_profile: Option<String>,
+
+    /// Output the resolution as JSON.
+    #[arg(long)]
+    pub json: bool,
+}
+
+#[derive(Debug, clap::Parser)]
+pub struct ExportArgs {
+    /// File to write; the export goes to stdout without one.
+    #[arg(long, short = 'o', value_name = "FILE")]
+    pub output: Option<PathBuf>,
+}
+
+#[derive(Debug, clap::Parser)]
+pub struct ImportArgs {
+    /// Export to read, as written by `codex models export`.
+    #[arg(required_unless_present = "clear", conflicts_with = "clear")]
+    pub file: Option<PathBuf>,
+
+    /// List the exported models in place of every catalog, read-only and
+    /// without fetching, until `--clear`. Without it the file is only
+    /// checked.
+    #[arg(long)]
+    pub as_source: bool,
+
+    /// Remove the imported catalog, so the catalogs are fetched again.
+    #[arg(long)]
+    pub clear: bool,
+}
+
+#[derive(Debug, clap::Parser)]
+pub struct SetDefaultArgs {
+    /// Preset id or model slug, as shown by `codex models list`.
+    pub model: String,
+
+    /// Also write the model's default reasoning effort.
+    #[arg(long)]
+    pub with_effort: bool,
+
+    /// Write into `[profiles.<name>]` instead of the top level.
+    #[arg(long, value_name = "NAME")]
+    pub profile: Option<String>,
+}
+
+impl ModelsCli {
+    pub async fn run(self) -> Result<()> {
+        let ModelsCli {
+            config_overrides,
+            subcommand,
+        } = self;
+
+        match subcommand {
+            ModelsSubcommand::List(args) => {
+                run_list(&config_overrides, args).await?;
+            }
+            ModelsSubcommand::Efforts(args) => {
+                run_efforts(&config_overrides, args).await?;
+            }
+            ModelsSubcommand::Show(args) => {
+                run_show(&config_overrides, args).await?;
+            }
+            ModelsSubcommand::Matrix(args) => {
+                run_matrix(&config_overrides, args).await?;
+            }
+            ModelsSubcommand::Refresh => {
+                run_refresh(&config_overrides).await?;
+            }
+            ModelsSubcommand::SetDefault(args) => {
+                run_set_default(&config_overrides, args).await?;
+            }
+            ModelsSubcommand::Status(args) => {
+                run_status(&config_overrides, args).await?;
+            }
+            ModelsSubcommand::Which(args) => {
+                run_which(&config_overrides, args).await?;
+            }
+            ModelsSubcommand::Export(args) => {
+                run_export(&config_overrides, args).await?;
+            }
+            ModelsSubcommand::Import(args) => {
+                run_import(&config_overrides, args).await?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+async fn load_models_manager(
+    config_overrides: &CliConfigOverrides,
+) -> Result<(Config, ModelsManager)> {
+    load_models_manager_with(config_overrides, ConfigOverrides::default()).await
+}
+
+async fn load_models_manager_with(
+    config_overrides: &CliConfigOverrides,
+    harness_overrides: ConfigOverrides,
+) -> Result<(Config, ModelsManager)> {
+    let overrides = config_overrides
+        .parse_overrides()
+        .map_err(anyhow::Error::msg)?;
+    let config =
+        Config::load_with_cli_overrides_and_harness_overrides(overrides, harness_overrides)
+            .await
+            .context("failed to load configuration")?;
+    let auth_manager = AuthManager::shared(
+        config.codex_home.clone(),
+        true,
+        config.cli_auth_credentials_store_mode,
+    );
+    Ok((config, ModelsManager::new(auth_manager)))
+}
+
+async fn run_refresh(config_overrides: &CliConfigOverrides) -> Result<()> {
+    let (config, models_manager) = load_models_manager(config_overrides).await?;
+    if config.offline {
+        bail!("cannot refresh models while offline; drop `--offline` or set `offline = false`");
+    }
+    if config.model_provider.has_fixed_models() {
+        bail!(
+            "the model catalog is disabled for provider `{}` (`model_catalog = \"none\"`); its models come from the `models` list in config",
+            config.model_provider_id
+        );
+    }
+    if let Some(imported) = models_manager.imported_catalog(&config) {
+        bail!(
+            "the catalog imported at {} is listed instead of fetched catalogs; run `codex models import --clear` to fetch them again",
+            imported.path.display()
+        );
+    }
+    let before = models_manager.cached_models(&config).await;
+    models_manager
+        .refresh_all_models(&config)
+        .await
+        .context("failed to refresh models")?;
+    let after = models_manager.list_models(&config).await;
+    let count = after.len();
+    // Provider-served models are never cached, so only the cached part of the
+    // catalog has a baseline to compare against.
+    let cached_after: Vec<ModelPreset> = after
+        .into_iter()
+        .filter(|preset| preset.model_provider.is_none())
+        .collect();
+    let diff = diff_catalogs(&before, &cached_after);
+    println!("Refreshed model catalog ({count} models available): {diff}.");
+    Ok(())
+}
+
+async fn run_list(config_overrides: &CliConfigOverrides, list_args: ListArgs) -> Result<()> {
+    let (config, models_manager) = load_models_manager(config_overrides).await?;
+    // A stale cache is refreshed in the background while this lists it; the
+    // command does not wait for that refresh before it exits.
+    print_list(&config, &models_manager, list_args).await
+}
+
+async fn print_list(
+    config: &Config,
+    models_manager: &ModelsManager,
+    list_args: ListArgs,
+) -> Result<()> {
+    let ModelList {
+        auth,
+        warnings,
+        freshness,
+        models: presets,
+        client_version_notice,
+        ..
+    } = models_manager.model_list(config).await;
+
+    // Machine-readable listings keep stdout clean, so the notice goes to
+    // stderr there.
+    if let Some(notice) = &client_version_notice
+        && (list_args.json || list_args.ids)
+    {
+        eprintln!("Notice: {notice}");
+    }
+
+    if list_args.json {
+        let output = serde_json::to_string_pretty(&presets)?;
+        println!("{output}");
+        return Ok(());
+    }
+
+    if list_args.ids {
+        let mut seen = HashSet::new();
+        for preset in &presets {
+            if seen.insert(preset.model.as_str()) {
+                println!("{}", preset.model);
+            }
+        }
+        return Ok(());
+    }
+
+    match freshness {
+        CatalogFreshness::Fresh => println!("Catalog: {auth}"),
+        CatalogFreshness::Revalidating => println!("Catalog: {auth} (cached, refreshing…)"),
+    }
+    if let Some(notice) = &client_version_notice {
+        println!("Notice: {notice}");
+    }
+    for warning in &warnings {
+        println!("Warning: {warning}");
+    }
+    if presets.is_empty() {
+        println!("No models available.");
+        return Ok(());
+    }
+
+    if list_args.plain {
+        for preset in &presets {
+            println!();
+            for (label, value) in plain_model_fields(preset) {
+                println!("{label}: {value}");
+            }
+        }
+        return Ok(());
+    }
+
+    let rows: Vec<[String; 3]> = presets
+        .iter()
+        .map(|preset| {
+            let model = if preset.is_default {
+                format!("{} (default)", preset.model)
+            } else {
+                preset.model.clone()
+            };
+            let provider = preset
+                .model_provider
+                .clone()
+                .unwrap_or_else(|| "built-in".to_string());
+            let mut description = if preset.description.is_empty() {
+                "-".to_string()
+            } else {
+                preset.description.clone()
+            };
+            if let Some(reason) = &preset.unavailable_for_account {
+                description = format!("{description} [unavailable for this account: {reason}]");
+            }
+            [model, provider, description]
+        })
+        .collect();
+
+    // Gateway model names can hold emoji and CJK, so columns are measured in
+    // terminal cells rather than bytes.
+    let mut widths = ["Model", "Provider", "Description"].map(display_width);
+    for row in &rows {
+        for (i, cell) in row.iter().enumerate() {
+            widths[i] = widths[i].max(display_width(cell));
+        }
+    }
+
+    println!(
+        "{}  {}  Description",
+        pad_to_width("Model", widths[0]),
+        pad_to_width("Provider", widths[1]),
+    );
+    for [model, provider, description] in rows {
+        println!(
+            "{}  {}  {description}",
+            pad_to_width(&model, widths[0]),
+            pad_to_width(&provider, widths[1]),
+        );
+    }
+
+    Ok(())
+}
+
+async fn run_efforts(
+    config_overrides: &CliConfigOverrides,
+    efforts_args: EffortsArgs,
+) -> Result<()> {
+    let EffortsArgs { model } = efforts_args;
+    let (config, models_manager) = load_models_manager(config_overrides).await?;
+    let presets = models_manager.list_models(&config).await;
+    let Some(preset) = find_preset(&presets, &model) else {
+        bail!("{}", unknown_model_message(&model, &presets));
+    };
+
+    if preset.supported_reasoning_efforts.is_empty() {
+        println!(
+            "{} does not support configurable reasoning effort.",
+            preset.model
+        );
+        return Ok(());
+    }
+
+    let rows: Vec<(String, &str)> = preset
+        .supported_reasoning_efforts
+        .iter()
+        .map(|option| {
+            let effort = if option.effort == preset.default_reasoning_effort {
+                format!("{} (default)", option.effort)
+            } else {
+                option.effort.to_string()
+            };
+            (effort, option.description.as_str())
+        })
+        .collect();
+    let width = rows
+        .iter()
+        .map(|(effort, _)| effort.len())
+        .max()
+        .unwrap_or(0);
+
+    println!("Reasoning efforts for {}:", preset.model);
+    for (effort, description) in rows {
+        println!("  {effort:<width$}  {description}");
+    }
+
+    Ok(())
+}
+
+async fn run_show(config_overrides: &CliConfigOverrides, show_args: ShowArgs) -> Result<()> {
+    let ShowArgs { model, json } = show_args;
+    let (config, models_manager) = load_models_manager(config_overrides).await?;
+    // Hidden presets can still be started with `--model`, so they can be shown.
+    let presets = models_manager.list_all_models(&config).await;
+    let Some(preset) = find_preset(&presets, &model) else {
+        bail!("{}", unknown_model_message(&model, &presets));
+    };
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(preset)?);
+        return Ok(());
+    }
+
+    println!("{}", preset.display_name);
+    let efforts: Vec<String> = preset
+        .supported_reasoning_efforts
+        .iter()
+        .map(|option| option.effort.to_string())
+        .collect();
+    let rows = [
+        ("id", Some(preset.id.clone())),
+        ("model", Some(preset.model.clone())),
+        (
+            "provider",
+            Some(
+                preset
+                    .model_provider
+                    .clone()
+                    .unwrap_or_else(|| "built-in".to_string()),
+            ),
+        ),
+        (
+            "description",
+            (!preset.description.is_empty()).then(|| preset.description.clone()),
+        ),
+        (
+            "default effort",
+            (!efforts.is_empty()).then(|| preset.default_reasoning_effort.to_string()),
+        ),
+        ("efforts", (!efforts.is_empty()).then(|| efforts.join(", "))),
+        ("context window", context_window_display(preset)),
+        ("knowledge cutoff", preset.knowledge_cutoff.clone()),
+        ("released", preset.released_at.clone()),
+    ];
+    for (label, value) in rows {
+        if let Some(value) = value {
+            println!("  {label}: {value}");
+        }
+    }
+    Ok(())
+}
+
+/// The context window of `preset` in tokens, with the unit and field a
+/// `/models` entry served it in, or why the served value was ignored.
+fn context_window_display(preset: &ModelPreset) -> Option<String> {
+    let Some(source) = &preset.context_window_source else {
+        return preset
+            .context_window
+            .map(|tokens| format!("{tokens} tokens"));
+    };
+    let ContextWindowSource {
+        field,
+        value,
+        unit,
+        ignored,
+    } = source;
+    if *ignored {
+        let reason = match unit {
+            Some(unit) => format!("too large in {unit}"),
+            None => format!("no unit and below {MIN_UNHINTED_CONTEXT_WINDOW}"),
+        };
+        return Some(format!(
+            "unknown (ignored {value} from `{field}`: {reason})"
+        ));
+    }
+    let tokens = preset.context_window?;
+    Some(match unit {
+        Some(ContextWindowUnit::Kilotokens) => {
+            format!("{tokens} tokens ({value} kilotokens from `{field}`)")
+        }
+        Some(ContextWindowUnit::Tokens) => format!("{tokens} tokens (from `{field}`)"),
+        None => format!("{tokens} tokens (assumed tokens, from `{field}`)"),
+    })
+}
+
+async fn run_matrix(config_overrides: &CliConfigOverrides, matrix_args: MatrixArgs) -> Result<()> {
+    let MatrixArgs {
+        json,
+        include_hidden,
+    } = matrix_args;
+    let (config, models_manager) = load_models_manager(config_overrides).await?;
+    let catalog = if include_hidden {
+        models_manager.list_all_models(&config).await
+    } else {
+        models_manager.list_models(&config).await
+    };
+    let matrix = enumerate_model_effort_matrix(&catalog, include_hidden);
+
+    if json {
+        let entries: Vec<serde_json::Value> = matrix
+            .into_iter()
+            .map(|(preset, effort)| serde_json::json!({ "preset": preset, "effort": effort }))
+            .collect();
+        println!("{}", serde_json::to_string_pretty(&entries)?);
+        return Ok(());
+    }
+
+    let width = matrix
+        .iter()
+        .map(|(preset, _)| display_width(&preset.id))
+        .max()
+        .unwrap_or(0);
+    for (preset, effort) in &matrix {
+        let effort = effort.map_or_else(|| "-".to_string(), |effort| effort.to_string());
+        println!("{}  {effort}", pad_to_width(&preset.id, width));
+    }
+    Ok(())
+}
+
+async fn run_status(config_overrides: &CliConfigOverrides, status_args: StatusArgs) -> Result<()> {
+    let (config, models_manager) = load_models_manager(config_overrides).await?;
+    // Populate the catalog the same way a session would before reporting on it.
+    models_manager.list_models(&config).await;
+    let status = models_manager.catalog_status(&config).await;
+
+    if status_args.json {
+        let output = serde_json::to_string_pretty(&status)?;
+        println!("{output}");
+        return Ok(());
+    }
+
+    let CatalogStatus {
+        offline,
+        sources,
+        remote,
+        providers,
+        presets,
+        // Only a TUI session prefetches; this process never does.
+        prefetch: _,
+        schedule,
+        imported,
+        last_rebuild,
+    } = status;
+    println!("Offline: {}", if offline { "yes" } else { "no" });
+    if let Some(imported) = imported {
+        println!(
+            "Imported catalog: {} (read-only, revision {}, exported at {} by Codex {}); no catalog is fetched",
+            imported.path.display(),
+            imported.revision,
+            imported
+                .exported_at
+                .to_rfc3339_opts(SecondsFormat::Secs, true),
+            imported.codex_version
+        );
+    }
+    let sources: Vec<&str> = sources
+        .into_iter()
+        .map(ModelCatalogSource::as_str)
+        .collect();
+    println!("Catalog sources: {}", sources.join(", "));
+    println!(
+        "Cache TTL: {}s, refresh interval: {}s (±{}% jitter)",
+        schedule.cache_ttl_ms / 1_000,
+        schedule.refresh_interval_ms / 1_000,
+        (schedule.jitter * 100.0).round()
+    );
+    // This process never rebuilds; the rebuild reported is the last one an
+    // app server or embedder recorded under this home.
+    match last_rebuild {
+        Some(CatalogRebuild {
+            reasons,
+            revision,
+            finished_at,
+            error,
+        }) => {
+            let reasons: Vec<&str> = reasons.into_iter().map(RebuildReason::as_str).collect();
+            println!(
+                "Last catalog rebuild: {} (revision {}) at {}",
+                reasons.join(", "),
+                revision.0,
+                format_fetched_at(Some(finished_at))
+            );
+            if let Some(error) = error {
+                println!("  refresh failed: {error}");
+            }
+        }
+        None => println!("Last catalog rebuild: -"),
+    }
+
+    let source = match remote.source {
+        CatalogSource::Bundled => "bundled snapshot",
+        CatalogSource::DiskCache => "disk cache",
+        CatalogSource::Live => "live fetch",
+    };
+    println!("Remote catalog: {source}");
+    println!("  host: {}", remote.host.as_deref().unwrap_or("-"));
+    println!("  fetched at: {}", format_fetched_at(remote.fetched_at));
+    let cache = match remote.cache {
+        Some(CacheLookup::Hit) => "hit",
+        Some(CacheLookup::Stale) => "stale",
+        Some(CacheLookup::Miss) => "miss",
+        None => "not checked",
+    };
+    println!("  cache: {cache}");
+    println!(
+        "  last error: {}",
+        remote.last_error.as_deref().unwrap_or("-")
+    );
+
+    for provider in providers {
+        println!("Provider {}:", provider.provider_id);
+        println!("  host: {}", provider.host.as_deref().unwrap_or("-"));
+        println!("  fetched at: {}", format_fetched_at(provider.fetched_at));
+        println!("  models: {}", provider.model_count);
+        println!(
+            "  last error: {}",
+            provider.last_error.as_deref().unwrap_or("-")
+        );
+    }
+
+    println!("Models:");
+    let width = presets
+        .iter()
+        .map(|preset| display_width(&preset.id))
+        .max()
+        .unwrap_or(0);
+    for PresetProvenance {
+        id,
+        source,
+        overridden,
+    } in presets
+    {
+        let source = match source {
+            PresetSource::Builtin => "built-in".to_string(),
+            PresetSource::Remote {
+                source: CatalogSource::Bundled,
+            } => "remote (bundled)".to_string(),
+            PresetSource::Remote {
+                source: CatalogSource::DiskCache,
+            } => "remote (disk cache)".to_string(),
+            PresetSource::Remote {
+                source: CatalogSource::Live,
+            } => "remote (live)".to_string(),
+            PresetSource::Provider { provider_id } => format!("provider {provider_id}"),
+        };
+        let overridden = if overridden { ", overridden" } else { "" };
+        println!("  {}  {source}{overridden}", pad_to_width(&id, width));
+    }
+
+    Ok(())
+}
+
+async fn run_which(config_overrides: &CliConfigOverrides, which_args: WhichArgs) -> Result<()> {
+    let WhichArgs {
+        model,
+        config_profile,
+        json,
+    } = which_args;
+    let harness_overrides = ConfigOverrides {
+        model: model.clone(),
+        config_profile,
+        ..Default::default()
+    };
+    let (config, models_manager) =
+        load_models_manager_with(config_overrides, harness_overrides).await?;
+    let resolution = resolve_model(&models_manager, &config, model.as_deref()).await;
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&resolution)?);
+        return Ok(());
+    }
+
+    let ModelResolution {
+        model_sources,
+        effort_sources,
+        preset_id,
+        model,
+        replaces_retired,
+        effort,
+        effort_is_auto,
+        provider_id,
+        context_window,
+    } = resolution;
+    print_sources("Model", &model_sources);
+    print_sources("Reasoning effort", &effort_sources);
+
+    println!("Resolved:");
+    println!("  preset: {}", preset_id.as_deref().unwrap_or("-"));
+    match replaces_retired {
+        Some(retired) => println!("  model: {model} (replaces retired {retired})"),
+        None => println!("  model: {model}"),
+    }
+    let effort = match effort {
+        Some(effort) if effort_is_auto => EffortSelection::Auto.label(effort),
+        Some(effort) => effort.to_string(),
+        None => "-".to_string(),
+    };
+    println!("  effort: {effort}");
+    println!("  provider: {provider_id}");
+    println!(
+        "  context window: {}",
+        context_window.map_or_else(|| "-".to_string(), |tokens| tokens.to_string())
+    );
+    Ok(())
+}
+
+async fn run_export(config_overrides: &CliConfigOverrides, export_args: ExportArgs) -> Result<()> {
+    let (config, models_manager) = load_models_manager(config_overrides).await?;
+    let export = models_manager.export_catalog(&config).await;
+    let json = export.to_json()?;
+    match export_args.output {
+        Some(path) => {
+            std::fs::write(&path, json)
+                .with_context(|| format!("failed to write {}", path.display()))?;
+            println!(
+                "Exported {} models at catalog revision {} to {}.",
+                export.presets.len(),
+                export.revision,
+                path.display()
+            );
+        }
+        None => {
+            let mut stdout = std::io::stdout().lock();
+            stdout.write_all(&json)?;
+            writeln!(stdout)?;
+        }
+    }
+    Ok(())
+}
+
+async fn run_import(config_overrides: &CliConfigOverrides, import_args: ImportArgs) -> Result<()> {
+    let ImportArgs {
+        file,
+        as_source,
+        clear,
+    } = import_args;
+    let overrides = config_overrides
+        .parse_overrides()
+        .map_err(anyhow::Error::msg)?;
+    let config = Config::load_with_cli_overrides(overrides)
+        .await
+        .context("failed to load configuration")?;
+    if clear {
+        if remove_imported_catalog(&config.codex_home).await? {
+            println!("Removed the imported catalog; the catalogs are fetched again.");
+        } else {
+            println!("No catalog is imported.");
+        }
+        return Ok(());
+    }
+    let Some(file) = file else {
+        bail!("pass the export to import, or `--clear`");
+    };
+    let export = CatalogExport::read(&file).await?;
+    println!(
+        "{}: {} models at catalog revision {}, exported at {} by Codex {}.",
+        file.display(),
+        export.presets.len(),
+        export.revision,
+        export
+            .exported_at
+            .to_rfc3339_opts(SecondsFormat::Secs, true),
+        export.codex_version
+    );
+    if !as_source {
+        println!("Pass `--as-source` to list these models in place of the fetched catalogs.");
+        return Ok(());
+    }
+    let (export, dropped_headers) = export.scoped_to(&config.model_provider_id);
+    if dropped_headers > 0 {
+        println!(
+            "Dropped the request headers of {dropped_headers} models; imported models never send them."
+        );
+    }
+    let installed = install_imported_catalog(&config.codex_home, &export)
+        .await
+        .with_context(|| {
+            format!(
+                "failed to install the catalog under {}",
+                config.codex_home.display()
+            )
+        })?;
+    println!(
+        "Installed as a read-only catalog source at {}. Sessions on provider `{}` list only these models and fetch no catalog until `codex models import --clear`.",
+        installed.display(),
+        config.model_provider_id
+    );
+    Ok(())
+}
+
+/// One line per source considered, highest precedence first, with the
+/// winner marked.
+fn print_sources(setting: &str, sources: &[ConsideredSetting]) {
+    println!("{setting} (highest precedence first):");
+    let width = sources
+        .iter()
+        .map(|source| source.source.len())
+        .max()
+        .unwrap_or(0);
+    for ConsideredSetting { source, value, won } in sources {
+        let marker = if *won { "*" } else { " " };
+        let value = value.as_deref().unwrap_or("-");
+        println!("  {marker} {source:<width$}  {value}");
+    }
+}
+
+fn format_fetched_at(fetched_at: Option<DateTime<Utc>>) -> String {
+    let Some(fetched_at) = fetched_at else {
+        return "-".to_string();
+    };
+    let age = Utc::now()
+        .signed_duration_since(fetched_at)
+        .num_seconds()
+        .max(0);
+    format!(
+        "{} ({age}s ago)",
+        fetched_at.to_rfc3339_opts(SecondsFormat::Secs, true)
+    )
+}
+
+async fn run_set_default(
+    config_overrides: &CliConfigOverrides,
+    set_default_args: SetDefaultArgs,
+) -> Result<()> {
+    let SetDefaultArgs {
+        model,
+        with_effort,
+        profile,
+    } = set_default_args;
+
+    let (config, models_manager) = load_models_manager(config_overrides).await?;
+    let presets = models_manager.list_models(&config).await;
+    let Some(preset) = find_preset(&presets, &model) else {
+        bail!("{}", unknown_model_message(&model, &presets));
+    };
+
+    let config_path = config.codex_home.join(CONFIG_TOML_FILE);
+    let current = CurrentModelSettings::read(&config_path, profile.as_deref())?;
+
+    let default_effort = preset.default_reasoning_effort;
+    let write_effort = with_effort
+        || (current.effort != Some(EffortSelection::Explicit(default_effort))
+            && std::io::stdin().is_terminal()
+            && confirm(&format!(
+                "Also set model_reasoning_effort to \"{default_effort}\"? [y/N] "
+            ))?);
+    let effort = if write_effort {
+        Some(EffortSelection::Explicit(default_effort))
+    } else {
+        current.effort
+    };
+
+    // Presets without a provider of their own are the built-in and remote
+    // ones. A provider with a `model_catalog` only serves its own presets, so
+    // when the table names one, it is removed along with its model.
+    let stale_provider = preset.model_provider.is_none()
+        && current
+            .model_provider
+            .as_deref()
+            .is_some_and(|provider_id| {
+                config
+                    .model_providers
+                    .get(provider_id)
+                    .is_some_and(|provider| provider.model_catalog.is_some())
+            });
+    let mut edits = ConfigEditsBuilder::new(&config.codex_home)
+        .with_profile(profile.as_deref())
+        .set_model_with_effort_selection(Some(&preset.model), effort);
+    if preset.model_provider.is_some() || stale_provider {
+        edits = edits.set_model_provider(preset.model_provider.as_deref());
+    }
+    edits
+        .apply()
+        .await
+        .with_context(|| format!("failed to write {}", config_path.display()))?;
+
+    let table = match current.profile.as_deref() {
+        Some(profile) => format!("[profiles.{profile}] in "),
+        None => String::new(),
+    };
+    println!("Updated {table}{}:", config_path.display());
+    print_change("model", current.model.as_deref(), &preset.model);
+    if let Some(model_provider) = preset.model_provider.as_deref() {
+        print_change(
+            "model_provider",
+            current.model_provider.as_deref(),
+            model_provider,
+        );
+    } else if let Some(previous) = current.model_provider.as_deref().filter(|_| stale_provider) {
+        println!("  model_provider = \"{previous}\" (removed)");
+    }
+    if write_effort {
+        let previous = current.effort.map(|effort| effort.to_string());
+        print_change(
+            "model_reasoning_effort",
+            previous.as_deref(),
+            &default_effort.to_string(),
+        );
+    }
+
+    Ok(())
+}
+
+/// The values `set-default` may overwrite, read from the same table the edit
+/// targets: the `--profile` table, else the active `profile`, else the top level.
+#[derive(Debug, Default)]
+struct CurrentModelSettings {
+    profile: Option<String>,
+    model: Option<String>,
+    model_provider: Option<String>,
+    effort: Option<EffortSelection>,
+}
+
+impl CurrentModelSettings {
+    fn read(config_path: &Path, profile: Option<&str>) -> Result<Self> {
+        let contents = match std::fs::read_to_string(config_path) {
+            Ok(contents) => contents,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => String::new(),
+            Err(err) => {
+                return Err(err)
+                    .with_context(|| format!("failed to read {}", config_path.display()));
+            }
+        };
+        let root: toml::Table = toml::from_str(&contents)
+            .with_context(|| format!("failed to parse {}", config_path.display()))?;
+
+        let profile = profile.map(ToOwned::to_owned).or_else(|| {
+            root.get("profile")
+                .and_then(toml::Value::as_str)
+                .map(ToOwned::to_owned)
+        });
+        let table = match profile.as_deref() {
+            Some(profile) => root
+                .get("profiles")
+                .and_then(|profiles| profiles.get(profile))
+                .and_then(toml::Value::as_table),
+            None => Some(&root),
+        };
+        let string_value = |key: &str| {
+            table
+                .and_then(|table| table.get(key))
+                .and_then(toml::Value::as_str)
+                .map(ToOwned::to_owned)
+        };
+        let effort = table
+            .and_then(|table| table.get("model_reasoning_effort"))
+            .cloned()
+            .map(toml::Value::try_into::<EffortSelection>)
+            .transpose()
+            .context("invalid model_reasoning_effort")?;
+
+        Ok(Self {
+            model: string_value("model"),
+            model_provider: string_value("model_provider"),
+            effort,
+            profile,
+        })
+    }
+}
+
+/// Match on preset id first, then on model slug.
+fn find_preset<'a>(presets: &'a [ModelPreset], model: &str) -> Option<&'a ModelPreset> {
+    presets
+        .iter()
+        .find(|preset| preset.id == model)
+        .or_else(|| presets.iter().find(|preset| preset.model == model))
+}
+
+fn unknown_model_message(model: &str, presets: &[ModelPreset]) -> String {
+    let mut matches: Vec<(i32, &str)> = presets
+        .iter()
+        .filter_map(|preset| {
+            [preset.id.as_str(), preset.model.as_str()]
+                .into_iter()
+                .filter_map(|candidate| fuzzy_match(candidate, model).map(|(_, score)| score))
+                .min()
+                .map(|score| (score, preset.id.as_str()))
+        })
+        .collect();
+    matches.sort_by_key(|(score, _)| *score);
+
+    let suggestions: Vec<&str> = matches
+        .into_iter()
+        .map(|(_, id)| id)
+        .take(MAX_SUGGESTIONS)
+        .collect();
+    if suggestions.is_empty() {
+        format!("unknown model '{model}'. Run `codex models list` to see available models.")
+    } else {
+        format!(
+            "unknown model '{model}'. Did you mean: {}?",
+            suggestions.join(", ")
+        )
+    }
+}
+
+fn confirm(prompt: &str) -> Result<bool> {
+    print!("{prompt}");
+    std::io::stdout().flush()?;
+    let mut answer = String::new();
+    std::io::stdin().read_line(&mut answer)?;
+    Ok(matches!(answer.trim(), "y" | "Y" | "yes" | "Yes"))
+}
+
+fn print_change(key: &str, previous: Option<&str>, new: &str) {
+    match previous {
+        Some(previous) if previous == new => println!("  {key} = \"{new}\" (unchanged)"),
+        Some(previous) => println!("  {key}: \"{previous}\" -> \"{new}\""),
+        None => println!("  {key} = \"{new}\""),
+    }
+}
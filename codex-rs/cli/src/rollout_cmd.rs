@@ -0,0 +1,181 @@
+use std::path::PathBuf;
+
+use anyhow::Context;
+use anyhow::Result;
+use codex_core::RolloutRecorder;
+use codex_core::read_head_for_summary;
+use codex_protocol::protocol::EventMsg;
+use codex_protocol::protocol::RolloutItem;
+use codex_protocol::protocol::SessionCatalog;
+use codex_protocol::protocol::SessionMetaLine;
+use codex_protocol::protocol::TurnContextItem;
+
+/// Print the session header of a rollout file.
+#[derive(Debug, clap::Parser)]
+pub struct RolloutCommand {
+    /// Path to a rollout `.jsonl` file, e.g. under `~/.codex/sessions`.
+    #[arg(value_name = "FILE")]
+    pub path: PathBuf,
+
+    /// Print the header as JSON.
+    #[arg(long, default_value_t = false)]
+    pub json: bool,
+
+    /// Print the whole transcript as Markdown, headed by the model the
+    /// session ran on.
+    #[arg(long, default_value_t = false, conflicts_with = "json")]
+    pub markdown: bool,
+}
+
+pub async fn run_rollout_command(cmd: RolloutCommand) -> Result<()> {
+    if cmd.markdown {
+        let history = RolloutRecorder::get_rollout_history(&cmd.path)
+            .await
+            .with_context(|| format!("failed to read {}", cmd.path.display()))?;
+        print!("{}", render_markdown(&history.get_rollout_items()));
+        return Ok(());
+    }
+
+    let head = read_head_for_summary(&cmd.path)
+        .await
+        .with_context(|| format!("failed to read {}", cmd.path.display()))?;
+    let meta_line = head
+        .into_iter()
+        .find_map(|value| serde_json::from_value::<SessionMetaLine>(value).ok())
+        .with_context(|| format!("no session header in {}", cmd.path.display()))?;
+
+    if cmd.json {
+        println!("{}", serde_json::to_string_pretty(&meta_line)?);
+        return Ok(());
+    }
+
+    let meta = &meta_line.meta;
+    println!("Session: {}", meta.id);
+    println!("  started: {}", meta.timestamp);
+    println!("  cwd: {}", meta.cwd.display());
+    println!("  source: {}", meta.source);
+    println!("  originator: {}", meta.originator);
+    println!("  cli version: {}", meta.cli_version);
+    println!(
+        "  model provider: {}",
+        meta.model_provider.as_deref().unwrap_or("-")
+    );
+    println!(
+        "  model catalog: {}",
+        format_catalog(meta.model_catalog.as_ref())
+    );
+    if let Some(branch) = meta_line.git.as_ref().and_then(|git| git.branch.as_deref()) {
+        println!("  git branch: {branch}");
+    }
+    Ok(())
+}
+
+/// One line such as `live, revision abc123, fetched at 2025-06-01T08:00:00Z`.
+/// Rollouts written before the catalog was recorded have none.
+fn format_catalog(catalog: Option<&SessionCatalog>) -> String {
+    let Some(catalog) = catalog else {
+        return "not recorded".to_string();
+    };
+    let mut parts = vec![catalog.source.to_string()];
+    if let Some(revision) = &catalog.revision {
+        parts.push(format!("revision {revision}"));
+    }
+    if let Some(fetched_at) = &catalog.fetched_at {
+        parts.push(format!("fetched at {fetched_at}"));
+    }
+    parts.join(", ")
+}
+
+/// The transcript of a rollout as Markdown. The header describes the model
+/// from what was recorded with the first turn, not from the live catalog,
+/// which may have changed since. A turn on another model or effort than the
+/// one before it gets an inline note.
+fn render_markdown(items: &[RolloutItem]) -> String {
+    let meta = items.iter().find_map(|item| match item {
+        RolloutItem::SessionMeta(meta_line) => Some(&meta_line.meta),
+        _ => None,
+    });
+    let first_turn = items.iter().find_map(|item| match item {
+        RolloutItem::TurnContext(turn) => Some(turn),
+        _ => None,
+    });
+
+    let mut blocks = vec![match meta {
+        Some(meta) => format!("# Session {}", meta.id),
+        None => "# Session".to_string(),
+    }];
+    let mut header = Vec::new();
+    if let Some(turn) = first_turn {
+        header.push(format!("- **Model:** {}", describe_model(turn)));
+        header.push(format!("- **Reasoning effort:** {}", describe_effort(turn)));
+        let context_window = turn
+            .preset
+            .as_ref()
+            .and_then(|preset| preset.context_window)
+            .map_or_else(
+                || "not recorded".to_string(),
+                |window| format!("{window} tokens"),
+            );
+        header.push(format!("- **Context window:** {context_window}"));
+        if let Some(preset) = &turn.preset {
+            header.push(format!(
+                "- **Preset:** `{}` from the {}",
+                preset.id, preset.source
+            ));
+        }
+    }
+    if let Some(meta) = meta {
+        header.push(format!(
+            "- **Model catalog:** {}",
+            format_catalog(meta.model_catalog.as_ref())
+        ));
+        header.push(format!("- **Started:** {}", meta.timestamp));
+        header.push(format!("- **Working directory:** {}", meta.cwd.display()));
+    }
+    if !header.is_empty() {
+        blocks.push(header.join("\n"));
+    }
+
+    let mut current = first_turn;
+    for item in items {
+        match item {
+            RolloutItem::TurnContext(turn) => {
+                if let Some(previous) = current
+                    && (previous.model != turn.model || previous.effort != turn.effort)
+                {
+                    blocks.push(format!(
+                        "> Switched to {}, reasoning effort {}.",
+                        describe_model(turn),
+                        describe_effort(turn)
+                    ));
+                }
+                current = Some(turn);
+            }
+            RolloutItem::EventMsg(EventMsg::UserMessage(message)) => {
+                blocks.push(format!("## User\n\n{}", message.message.trim_end()));
+            }
+            RolloutItem::EventMsg(EventMsg::AgentMessage(message)) => {
+                blocks.push(format!("## Codex\n\n{}", message.message.trim_end()));
+            }
+            _ => {}
+        }
+    }
+
+    let mut markdown = blocks.join("\n\n");
+    markdown.push('\n');
+    markdown
+}
+
+/// The display name and slug, e.g. ``GPT-5.1 Codex (`gpt-5.1-codex`)``, or
+/// the slug alone when no preset was recorded.
+fn describe_model(turn: &TurnContextItem) -> String {
+    match &turn.preset {
+        Some(preset) => format!("{} (`{}`)", preset.display_name, turn.model),
+        None => format!("`{}`", turn.model),
+    }
+}
+
+fn describe_effort(turn: &TurnContextItem) -> String {
+    turn.effort
+        .map_or_else(|| "model default".to_string(), |effort| effort.to_string())
+}
@@ -0,0 +1,129 @@
+use std::path::Path;
+use std::path::PathBuf;
+
+use anyhow::Result;
+use predicates::str::contains;
+use pretty_assertions::assert_eq;
+use tempfile::TempDir;
+
+fn codex_command(codex_home: &Path) -> Result<assert_cmd::Command> {
+    let mut cmd = assert_cmd::Command::new(codex_utils_cargo_bin::cargo_bin("codex")?);
+    cmd.env("CODEX_HOME", codex_home);
+    Ok(cmd)
+}
+
+/// The catalog bundled with this build, as IT would ship it.
+fn bundled_catalog() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("../core/models.json")
+}
+
+#[test]
+fn validate_reports_each_finding_and_fails() -> Result<()> {
+    let codex_home = TempDir::new()?;
+    std::fs::write(
+        codex_home.path().join("config.toml"),
+        r#"model = "gpt-5.3"
+review_model = "gpt-5"
+
+[profiles.fast]
+model = "gpt-5.1-codex-mini"
+model_reasoning_effort = "low"
+
+[preset_overrides."o3"]
+max_effort = "low"
+"#,
+    )?;
+
+    let output = codex_command(codex_home.path())?
+        .args(["config", "validate", "--json", "--catalog"])
+        .arg(bundled_catalog())
+        .output()?;
+
+    assert_eq!(output.status.code(), Some(1));
+    let findings: Vec<serde_json::Value> = serde_json::from_slice(&output.stdout)?;
+    let config_findings: Vec<(&str, &str, &str)> = findings
+        .iter()
+        .filter(|finding| finding["code"] != "unknown_catalog_reference")
+        .map(|finding| {
+            (
+                finding["severity"].as_str().unwrap_or_default(),
+                finding["code"].as_str().unwrap_or_default(),
+                finding["location"].as_str().unwrap_or_default(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        config_findings,
+        vec![
+            ("error", "unknown_model", "model"),
+            ("warning", "hidden_model", "review_model"),
+            (
+                "error",
+                "unsupported_effort",
+                "profiles.fast.model_reasoning_effort"
+            ),
+            ("warning", "ignored_override", "preset_overrides.\"o3\""),
+        ]
+    );
+    Ok(())
+}
+
+#[test]
+fn validate_succeeds_on_a_consistent_bundle() -> Result<()> {
+    let codex_home = TempDir::new()?;
+    std::fs::write(
+        codex_home.path().join("config.toml"),
+        r#"model = "gpt-5.2"
+model_reasoning_effort = "high"
+"#,
+    )?;
+
+    codex_command(codex_home.path())?
+        .args(["config", "validate", "--catalog"])
+        .arg(bundled_catalog())
+        .assert()
+        .success()
+        .stdout(contains("0 error(s)"));
+    Ok(())
+}
+
+#[test]
+fn validate_checks_a_config_file_that_would_not_load() -> Result<()> {
+    let codex_home = TempDir::new()?;
+    let staged = TempDir::new()?;
+    let config_path = staged.path().join("managed_config.toml");
+    std::fs::write(
+        &config_path,
+        r#"profile = "missing"
+review_model_preset = "not-a-preset"
+"#,
+    )?;
+
+    let output = codex_command(codex_home.path())?
+        .args(["config", "validate", "--json", "--config"])
+        .arg(&config_path)
+        .arg("--catalog")
+        .arg(bundled_catalog())
+        .output()?;
+
+    assert_eq!(output.status.code(), Some(1));
+    let findings: Vec<serde_json::Value> = serde_json::from_slice(&output.stdout)?;
+    let config_findings: Vec<(&str, &str)> = findings
+        .iter()
+        .filter(|finding| finding["code"] != "unknown_catalog_reference")
+        .map(|finding| {
+            (
+                finding["code"].as_str().unwrap_or_default(),
+                finding["location"].as_str().unwrap_or_default(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        config_findings,
+        vec![
+            ("unknown_model", "review_model_preset"),
+            ("unknown_profile", "profile"),
+        ]
+    );
+    Ok(())
+}
@@ -0,0 +1,123 @@
+use std::path::Path;
+
+use anyhow::Result;
+use predicates::prelude::PredicateBooleanExt;
+use predicates::str::contains;
+use pretty_assertions::assert_eq;
+use tempfile::TempDir;
+
+fn codex_command(codex_home: &Path) -> Result<assert_cmd::Command> {
+    let mut cmd = assert_cmd::Command::new(codex_utils_cargo_bin::cargo_bin("codex")?);
+    cmd.env("CODEX_HOME", codex_home);
+    Ok(cmd)
+}
+
+fn write_gateway_config(codex_home: &Path, base_url: &str) -> Result<()> {
+    std::fs::write(
+        codex_home.join("config.toml"),
+        format!(
+            r#"
+model_provider = "gateway"
+
+[model_providers.gateway]
+name = "Gateway"
+base_url = "{base_url}"
+experimental_bearer_token = "sk-secret-token"
+model_catalog = "openai"
+"#
+        ),
+    )?;
+    Ok(())
+}
+
+#[test]
+fn model_fetch_pinpoints_a_refused_connection() -> Result<()> {
+    let listener = std::net::TcpListener::bind("127.0.0.1:0")?;
+    let addr = listener.local_addr()?;
+    drop(listener);
+    let codex_home = TempDir::new()?;
+    write_gateway_config(codex_home.path(), &format!("http://{addr}/v1"))?;
+
+    let mut cmd = codex_command(codex_home.path())?;
+    cmd.args(["debug", "model-fetch"])
+        .assert()
+        .failure()
+        .stdout(contains(format!("GET http://{addr}/v1/models")))
+        .stdout(contains("authorization: Bearer [REDACTED]"))
+        .stdout(contains("sk-secret-token").not())
+        .stderr(contains(
+            "model fetch for provider `gateway` failed at the connect phase",
+        ));
+
+    Ok(())
+}
+
+#[test]
+fn model_fetch_json_marks_later_phases_not_reached() -> Result<()> {
+    let listener = std::net::TcpListener::bind("127.0.0.1:0")?;
+    let addr = listener.local_addr()?;
+    drop(listener);
+    let codex_home = TempDir::new()?;
+    write_gateway_config(codex_home.path(), "http://unused.invalid/v1")?;
+
+    let mut cmd = codex_command(codex_home.path())?;
+    let output = cmd
+        .args([
+            "debug",
+            "model-fetch",
+            "--json",
+            "--base-url",
+            &format!("http://{addr}/v1"),
+        ])
+        .output()?;
+    assert!(!output.status.success());
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+
+    let phases: Vec<(String, String)> = report["phases"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .map(|phase| {
+            (
+                phase["phase"].as_str().unwrap_or_default().to_string(),
+                phase["status"].as_str().unwrap_or_default().to_string(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        phases,
+        vec![
+            ("dns".to_string(), "ok".to_string()),
+            ("connect".to_string(), "failed".to_string()),
+            ("tls".to_string(), "skipped".to_string()),
+            ("first_byte".to_string(), "skipped".to_string()),
+            ("total".to_string(), "skipped".to_string()),
+            ("parse".to_string(), "skipped".to_string()),
+        ]
+    );
+
+    Ok(())
+}
+
+#[test]
+fn model_fetch_requires_a_provider_catalog() -> Result<()> {
+    let codex_home = TempDir::new()?;
+    std::fs::write(
+        codex_home.path().join("config.toml"),
+        r#"
+model_provider = "plain"
+
+[model_providers.plain]
+name = "Plain"
+base_url = "http://127.0.0.1:9/v1"
+"#,
+    )?;
+
+    let mut cmd = codex_command(codex_home.path())?;
+    cmd.args(["debug", "model-fetch"])
+        .assert()
+        .failure()
+        .stderr(contains("provider `plain` has no `model_catalog`"));
+
+    Ok(())
+}
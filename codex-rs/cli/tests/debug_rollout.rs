@@ -0,0 +1,181 @@
+use std::path::Path;
+
+use anyhow::Result;
+use predicates::str::contains;
+use pretty_assertions::assert_eq;
+use serde_json::json;
+use tempfile::TempDir;
+
+fn codex_command(codex_home: &Path) -> Result<assert_cmd::Command> {
+    let mut cmd = assert_cmd::Command::new(codex_utils_cargo_bin::cargo_bin("codex")?);
+    cmd.env("CODEX_HOME", codex_home);
+    Ok(cmd)
+}
+
+fn session_meta(model_catalog: Option<serde_json::Value>) -> serde_json::Value {
+    let mut payload = json!({
+        "id": "67e55044-10b1-426f-9247-bb680e5fe0c8",
+        "timestamp": "2025-06-01T08:00:00.000Z",
+        "cwd": "/tmp/project",
+        "originator": "codex_cli_rs",
+        "cli_version": "0.0.0",
+        "instructions": null,
+        "source": "cli",
+        "model_provider": "openai",
+    });
+    if let Some(model_catalog) = model_catalog {
+        payload["model_catalog"] = model_catalog;
+    }
+    rollout_line("session_meta", payload)
+}
+
+fn rollout_line(kind: &str, payload: serde_json::Value) -> serde_json::Value {
+    json!({
+        "timestamp": "2025-06-01T08:00:00.000Z",
+        "type": kind,
+        "payload": payload,
+    })
+}
+
+fn write_rollout(dir: &Path, name: &str, model_catalog: Option<serde_json::Value>) -> Result<()> {
+    write_lines(dir, name, &[session_meta(model_catalog)])
+}
+
+fn write_lines(dir: &Path, name: &str, lines: &[serde_json::Value]) -> Result<()> {
+    let contents: String = lines.iter().map(|line| format!("{line}\n")).collect();
+    std::fs::write(dir.join(name), contents)?;
+    Ok(())
+}
+
+fn turn_context(model: &str, effort: &str, preset: Option<serde_json::Value>) -> serde_json::Value {
+    let mut payload = json!({
+        "cwd": "/tmp/project",
+        "approval_policy": "never",
+        "sandbox_policy": { "type": "read-only" },
+        "model": model,
+        "effort": effort,
+        "summary": "auto",
+    });
+    if let Some(preset) = preset {
+        payload["preset"] = preset;
+    }
+    rollout_line("turn_context", payload)
+}
+
+fn message(kind: &str, text: &str) -> serde_json::Value {
+    rollout_line("event_msg", json!({ "type": kind, "message": text }))
+}
+
+#[test]
+fn prints_the_recorded_model_catalog() -> Result<()> {
+    let codex_home = TempDir::new()?;
+    write_rollout(
+        codex_home.path(),
+        "rollout.jsonl",
+        Some(json!({
+            "revision": "catalog-7",
+            "source": "live",
+            "fetched_at": "2025-06-01T07:59:00+00:00",
+        })),
+    )?;
+
+    let mut cmd = codex_command(codex_home.path())?;
+    cmd.args(["debug", "rollout"])
+        .arg(codex_home.path().join("rollout.jsonl"))
+        .assert()
+        .success()
+        .stdout(contains("Session: 67e55044-10b1-426f-9247-bb680e5fe0c8"))
+        .stdout(contains(
+            "model catalog: live, revision catalog-7, fetched at 2025-06-01T07:59:00+00:00",
+        ));
+
+    Ok(())
+}
+
+#[test]
+fn older_rollouts_without_a_catalog_still_load() -> Result<()> {
+    let codex_home = TempDir::new()?;
+    write_rollout(codex_home.path(), "rollout.jsonl", None)?;
+
+    let mut cmd = codex_command(codex_home.path())?;
+    cmd.args(["debug", "rollout"])
+        .arg(codex_home.path().join("rollout.jsonl"))
+        .assert()
+        .success()
+        .stdout(contains("model catalog: not recorded"));
+
+    Ok(())
+}
+
+#[test]
+fn markdown_export_describes_the_recorded_model_and_notes_switches() -> Result<()> {
+    let codex_home = TempDir::new()?;
+    write_lines(
+        codex_home.path(),
+        "rollout.jsonl",
+        &[
+            session_meta(Some(json!({
+                "revision": "catalog-7",
+                "source": "live",
+                "fetched_at": "2025-06-01T07:59:00+00:00",
+            }))),
+            message("user_message", "Fix the build"),
+            turn_context(
+                "gpt-5.1-codex",
+                "high",
+                Some(json!({
+                    "id": "gpt-5.1-codex",
+                    "display_name": "GPT-5.1 Codex",
+                    "context_window": 272000,
+                    "source": { "kind": "remote", "catalog": "live" },
+                })),
+            ),
+            message("agent_message", "Fixed the missing import."),
+            message("user_message", "Now run the tests"),
+            // A model the catalog had no preset for: only the slug is known.
+            turn_context("local-model", "medium", None),
+            message("agent_message", "All tests pass."),
+        ],
+    )?;
+
+    let mut cmd = codex_command(codex_home.path())?;
+    let output = cmd
+        .args(["debug", "rollout", "--markdown"])
+        .arg(codex_home.path().join("rollout.jsonl"))
+        .output()?;
+    assert!(output.status.success());
+
+    assert_eq!(
+        String::from_utf8(output.stdout)?,
+        "\
+# Session 67e55044-10b1-426f-9247-bb680e5fe0c8
+
+- **Model:** GPT-5.1 Codex (`gpt-5.1-codex`)
+- **Reasoning effort:** high
+- **Context window:** 272000 tokens
+- **Preset:** `gpt-5.1-codex` from the remote catalog (live)
+- **Model catalog:** live, revision catalog-7, fetched at 2025-06-01T07:59:00+00:00
+- **Started:** 2025-06-01T08:00:00.000Z
+- **Working directory:** /tmp/project
+
+## User
+
+Fix the build
+
+## Codex
+
+Fixed the missing import.
+
+## User
+
+Now run the tests
+
+> Switched to `local-model`, reasoning effort medium.
+
+## Codex
+
+All tests pass.
+"
+    );
+    Ok(())
+}
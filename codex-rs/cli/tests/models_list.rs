@@ -0,0 +1,887 @@
+use std::path::Path;
+
+use anyhow::Result;
+use codex_common::display_width::display_width;
+use codex_protocol::openai_models::ModelPreset;
+use predicates::prelude::PredicateBooleanExt;
+use predicates::str::contains;
+use pretty_assertions::assert_eq;
+use tempfile::TempDir;
+
+fn codex_command(codex_home: &Path) -> Result<assert_cmd::Command> {
+    let mut cmd = assert_cmd::Command::new(codex_utils_cargo_bin::cargo_bin("codex")?);
+    cmd.env("CODEX_HOME", codex_home);
+    Ok(cmd)
+}
+
+#[test]
+fn list_json_includes_builtin_presets() -> Result<()> {
+    let codex_home = TempDir::new()?;
+
+    let mut cmd = codex_command(codex_home.path())?;
+    let output = cmd.args(["models", "list", "--json"]).output()?;
+    assert!(output.status.success());
+    let presets: Vec<ModelPreset> = serde_json::from_slice(&output.stdout)?;
+
+    assert!(
+        presets
+            .iter()
+            .any(|preset| preset.model == "gpt-5.1-codex-max"),
+        "expected built-in presets in output: {presets:?}"
+    );
+    assert_eq!(
+        presets
+            .iter()
+            .filter_map(|preset| preset.model_provider.clone())
+            .collect::<Vec<_>>(),
+        Vec::<String>::new(),
+        "no catalog providers are configured"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn refresh_errors_when_offline() -> Result<()> {
+    let codex_home = TempDir::new()?;
+
+    let mut cmd = codex_command(codex_home.path())?;
+    cmd.args(["--offline", "models", "refresh"])
+        .assert()
+        .failure()
+        .stderr(contains("cannot refresh models while offline"));
+
+    Ok(())
+}
+
+#[test]
+fn offline_config_key_also_blocks_refresh() -> Result<()> {
+    let codex_home = TempDir::new()?;
+    std::fs::write(codex_home.path().join("config.toml"), "offline = true\n")?;
+
+    let mut cmd = codex_command(codex_home.path())?;
+    cmd.args(["models", "refresh"])
+        .assert()
+        .failure()
+        .stderr(contains("cannot refresh models while offline"));
+
+    Ok(())
+}
+
+#[test]
+fn status_json_reports_offline_bundled_catalog() -> Result<()> {
+    let codex_home = TempDir::new()?;
+
+    let mut cmd = codex_command(codex_home.path())?;
+    let output = cmd
+        .args(["--offline", "models", "status", "--json"])
+        .output()?;
+    assert!(output.status.success());
+    let status: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+
+    assert_eq!(status["offline"], serde_json::json!(true));
+    assert_eq!(
+        status["sources"],
+        serde_json::json!(["remote", "builtin", "providers"])
+    );
+    assert_eq!(status["remote"]["source"], serde_json::json!("bundled"));
+    assert_eq!(status["providers"], serde_json::json!([]));
+    assert!(
+        status["presets"]
+            .as_array()
+            .is_some_and(|presets| !presets.is_empty()),
+        "expected presets in status output: {status}"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn status_shows_the_configured_catalog_order() -> Result<()> {
+    let codex_home = TempDir::new()?;
+    std::fs::write(
+        codex_home.path().join("config.toml"),
+        r#"model_catalog_sources = ["builtin", "providers"]"#,
+    )?;
+
+    let mut cmd = codex_command(codex_home.path())?;
+    cmd.args(["--offline", "models", "status"])
+        .assert()
+        .success()
+        .stdout(contains("Catalog sources: builtin, providers"));
+
+    Ok(())
+}
+
+#[test]
+fn status_reports_the_last_recorded_catalog_rebuild() -> Result<()> {
+    let codex_home = TempDir::new()?;
+    // Rebuilds are kept per catalog; a tenant gateway's must not be reported
+    // for the configured catalog.
+    std::fs::write(
+        codex_home.path().join("models_last_rebuild.json"),
+        r#"{"catalogs": {
+            "https://chatgpt.com/backend-api/codex as OpenAI via api.openai.com, no credentials":
+                {"reasons": ["config_reloaded", "manual"], "revision": 3, "finished_at": "2026-01-02T03:04:05Z"},
+            "https://gw.example.test/v1 as Gateway via gw.example.test, no credentials":
+                {"reasons": ["started"], "revision": 1, "finished_at": "2026-01-02T03:05:00Z"}
+        }}"#,
+    )?;
+    let signed_out = |mut cmd: assert_cmd::Command| {
+        for var in ["CODEX_API_KEY", "OPENAI_API_KEY", "OPENAI_BASE_URL"] {
+            cmd.env_remove(var);
+        }
+        cmd
+    };
+
+    let mut cmd = signed_out(codex_command(codex_home.path())?);
+    cmd.args(["--offline", "models", "status"])
+        .assert()
+        .success()
+        .stdout(contains(
+            "Last catalog rebuild: config_reloaded, manual (revision 3) at 2026-01-02T03:04:05Z",
+        ));
+
+    let mut cmd = signed_out(codex_command(codex_home.path())?);
+    let output = cmd
+        .args(["--offline", "models", "status", "--json"])
+        .output()?;
+    assert!(output.status.success());
+    let status: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    assert_eq!(
+        status["last_rebuild"],
+        serde_json::json!({
+            "reasons": ["config_reloaded", "manual"],
+            "revision": 3,
+            "finished_at": "2026-01-02T03:04:05Z",
+        })
+    );
+
+    Ok(())
+}
+
+const FIXED_GATEWAY_CONFIG: &str = r#"
+model_provider = "gateway"
+
+[model_providers.gateway]
+name = "Gateway"
+base_url = "http://127.0.0.1:9/v1"
+model_catalog = "none"
+models = ["oca-large", "oca-small"]
+"#;
+
+#[test]
+fn list_uses_fixed_models_without_fetching() -> Result<()> {
+    let codex_home = TempDir::new()?;
+    std::fs::write(codex_home.path().join("config.toml"), FIXED_GATEWAY_CONFIG)?;
+
+    let mut cmd = codex_command(codex_home.path())?;
+    let output = cmd.args(["models", "list", "--json"]).output()?;
+    assert!(output.status.success());
+    let presets: Vec<ModelPreset> = serde_json::from_slice(&output.stdout)?;
+
+    assert_eq!(
+        presets
+            .iter()
+            .filter(|preset| preset.model_provider.as_deref() == Some("gateway"))
+            .map(|preset| preset.id.as_str())
+            .collect::<Vec<_>>(),
+        vec!["gateway/oca-large", "gateway/oca-small"]
+    );
+
+    Ok(())
+}
+
+#[test]
+fn refresh_errors_when_catalog_is_disabled() -> Result<()> {
+    let codex_home = TempDir::new()?;
+    std::fs::write(codex_home.path().join("config.toml"), FIXED_GATEWAY_CONFIG)?;
+
+    let mut cmd = codex_command(codex_home.path())?;
+    cmd.args(["models", "refresh"])
+        .assert()
+        .failure()
+        .stderr(contains(
+            "the model catalog is disabled for provider `gateway` (`model_catalog = \"none\"`)",
+        ));
+
+    Ok(())
+}
+
+#[test]
+fn efforts_describe_each_supported_effort() -> Result<()> {
+    let codex_home = TempDir::new()?;
+
+    let mut cmd = codex_command(codex_home.path())?;
+    let output = cmd
+        .args(["--offline", "models", "efforts", "gpt-5.1-codex-max"])
+        .output()?;
+    assert!(output.status.success());
+
+    assert_eq!(
+        String::from_utf8(output.stdout)?,
+        "\
+Reasoning efforts for gpt-5.1-codex-max:
+  low               Fast responses with lighter reasoning
+  medium (default)  Balances speed and reasoning depth for everyday tasks
+  high              Greater reasoning depth for complex problems
+  xhigh             Extra high reasoning depth for complex problems
+"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn efforts_leave_out_disabled_efforts() -> Result<()> {
+    let codex_home = TempDir::new()?;
+    std::fs::write(
+        codex_home.path().join("config.toml"),
+        "disabled_reasoning_efforts = [\"medium\"]\n",
+    )?;
+
+    let mut cmd = codex_command(codex_home.path())?;
+    let output = cmd
+        .args(["--offline", "models", "efforts", "gpt-5.1-codex-max"])
+        .output()?;
+    assert!(output.status.success());
+
+    // The default moves off the disabled effort to the closest one left.
+    assert_eq!(
+        String::from_utf8(output.stdout)?,
+        "\
+Reasoning efforts for gpt-5.1-codex-max:
+  low (default)  Fast responses with lighter reasoning
+  high           Greater reasoning depth for complex problems
+  xhigh          Extra high reasoning depth for complex problems
+"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn efforts_suggest_close_matches_for_unknown_models() -> Result<()> {
+    let codex_home = TempDir::new()?;
+
+    let mut cmd = codex_command(codex_home.path())?;
+    cmd.args(["--offline", "models", "efforts", "gpt-5.1-codex-mx"])
+        .assert()
+        .failure()
+        .stderr(contains("unknown model 'gpt-5.1-codex-mx'. Did you mean:"));
+
+    Ok(())
+}
+
+#[test]
+fn show_prints_the_dates_the_catalog_gives() -> Result<()> {
+    let codex_home = TempDir::new()?;
+
+    let mut cmd = codex_command(codex_home.path())?;
+    let output = cmd
+        .args(["--offline", "models", "show", "gpt-5"])
+        .output()?;
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8(output.stdout)?,
+        "\
+gpt-5
+  id: gpt-5
+  model: gpt-5
+  provider: built-in
+  description: Broad world knowledge with strong general reasoning.
+  default effort: medium
+  efforts: minimal, low, medium, high
+  knowledge cutoff: 2024-09-30
+  released: 2025-08-07
+"
+    );
+
+    // Models the catalog gives no dates for show neither line.
+    let mut cmd = codex_command(codex_home.path())?;
+    let output = cmd
+        .args(["--offline", "models", "show", "gpt-5.1-codex-max"])
+        .output()?;
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout)?;
+    assert!(!stdout.contains("knowledge cutoff"), "{stdout}");
+    assert!(!stdout.contains("released"), "{stdout}");
+
+    Ok(())
+}
+
+#[test]
+fn show_names_the_unit_and_field_of_the_context_window() -> Result<()> {
+    let codex_home = TempDir::new()?;
+    std::fs::write(
+        codex_home.path().join("config.toml"),
+        "[features]\nremote_models = true\n",
+    )?;
+    let entry = |slug: &str, context_window: serde_json::Value, source: serde_json::Value| {
+        serde_json::json!({
+            "slug": slug,
+            "display_name": slug,
+            "description": null,
+            "default_reasoning_level": "medium",
+            "supported_reasoning_levels": [{"effort": "medium", "description": "medium"}],
+            "shell_type": "shell_command",
+            "visibility": "list",
+            "supported_in_api": true,
+            "priority": 1,
+            "upgrade": null,
+            "base_instructions": null,
+            "supports_reasoning_summaries": false,
+            "support_verbosity": false,
+            "default_verbosity": null,
+            "apply_patch_tool_type": null,
+            "truncation_policy": {"mode": "bytes", "limit": 10_000},
+            "supports_parallel_tool_calls": false,
+            "context_window": context_window,
+            "context_window_source": source,
+            "experimental_supported_tools": [],
+        })
+    };
+    std::fs::write(
+        codex_home.path().join("models_cache.json"),
+        serde_json::to_vec(&serde_json::json!({
+            "fetched_at": chrono::Utc::now().to_rfc3339(),
+            "models": [
+                entry(
+                    "oca-kilo",
+                    serde_json::json!(128_000),
+                    serde_json::json!({"field": "maxInputTokens", "value": 128, "unit": "kilotokens"}),
+                ),
+                entry(
+                    "oca-raw",
+                    serde_json::json!(200_000),
+                    serde_json::json!({"field": "context_window", "value": 200_000}),
+                ),
+                entry(
+                    "oca-small",
+                    serde_json::Value::Null,
+                    serde_json::json!({"field": "context_window", "value": 128, "ignored": true}),
+                ),
+            ],
+        }))?,
+    )?;
+
+    for (model, expected) in [
+        (
+            "oca-kilo",
+            "  context window: 128000 tokens (128 kilotokens from `maxInputTokens`)",
+        ),
+        (
+            "oca-raw",
+            "  context window: 200000 tokens (assumed tokens, from `context_window`)",
+        ),
+        (
+            "oca-small",
+            "  context window: unknown (ignored 128 from `context_window`: no unit and below 2048)",
+        ),
+    ] {
+        let mut cmd = codex_command(codex_home.path())?;
+        let output = cmd.args(["--offline", "models", "show", model]).output()?;
+        assert!(output.status.success(), "{model}");
+        let stdout = String::from_utf8(output.stdout)?;
+        assert!(stdout.lines().any(|line| line == expected), "{stdout}");
+    }
+
+    Ok(())
+}
+
+#[test]
+fn list_plain_prints_one_labeled_field_per_line() -> Result<()> {
+    let codex_home = TempDir::new()?;
+
+    let mut cmd = codex_command(codex_home.path())?;
+    let output = cmd
+        .args(["--offline", "models", "list", "--plain"])
+        .output()?;
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout)?;
+
+    // Models are separated by a blank line and every field sits on its own
+    // labeled line, in the same order for each model.
+    let gpt_5_2 = stdout
+        .split("\n\n")
+        .find(|block| block.starts_with("Name: gpt-5.2\nModel: gpt-5.2\n"))
+        .unwrap_or_else(|| panic!("no gpt-5.2 block in:\n{stdout}"));
+    assert_eq!(
+        gpt_5_2.trim_end(),
+        "\
+Name: gpt-5.2
+Model: gpt-5.2
+Id: gpt-5.2
+Provider: built-in
+Default: no
+Description: Latest frontier model with improvements across knowledge, reasoning and coding
+Default effort: medium
+Efforts: low, medium, high, xhigh
+Knowledge cutoff: 2025-08-31
+Released: 2025-12-11"
+    );
+    assert!(
+        stdout
+            .split("\n\n")
+            .skip(1)
+            .all(|block| block.starts_with("Name: ")),
+        "{stdout}"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn list_ids_prints_one_model_per_line() -> Result<()> {
+    let codex_home = TempDir::new()?;
+    std::fs::write(codex_home.path().join("config.toml"), FIXED_GATEWAY_CONFIG)?;
+
+    let mut cmd = codex_command(codex_home.path())?;
+    let output = cmd.args(["models", "list", "--ids"]).output()?;
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout)?;
+    let ids: Vec<&str> = stdout.lines().collect();
+
+    for expected in ["gpt-5.1-codex-max", "oca-large", "oca-small"] {
+        assert!(ids.contains(&expected), "missing {expected} in {ids:?}");
+    }
+    assert!(
+        ids.iter()
+            .all(|id| !id.is_empty() && !id.contains(char::is_whitespace)),
+        "ids should be bare model slugs: {ids:?}"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn completions_offer_builtin_models() -> Result<()> {
+    let codex_home = TempDir::new()?;
+
+    let mut cmd = codex_command(codex_home.path())?;
+    let output = cmd.args(["completion", "bash"]).output()?;
+    assert!(output.status.success());
+
+    assert!(String::from_utf8(output.stdout)?.contains("gpt-5.1-codex-max"));
+
+    Ok(())
+}
+
+fn first_line(output: &std::process::Output) -> Result<String> {
+    let stdout = String::from_utf8(output.stdout.clone())?;
+    Ok(stdout.lines().next().unwrap_or_default().to_string())
+}
+
+#[test]
+fn list_reports_catalog_credentials() -> Result<()> {
+    let codex_home = TempDir::new()?;
+
+    let mut cmd = codex_command(codex_home.path())?;
+    let output = cmd
+        .env_remove("OPENAI_BASE_URL")
+        .env_remove("CODEX_API_KEY")
+        .args(["--offline", "models", "list"])
+        .output()?;
+    assert!(output.status.success());
+    assert_eq!(
+        first_line(&output)?,
+        "Catalog: OpenAI via api.openai.com, no credentials"
+    );
+
+    std::fs::write(
+        codex_home.path().join("config.toml"),
+        format!("{FIXED_GATEWAY_CONFIG}env_key = \"GATEWAY_KEY\"\n"),
+    )?;
+    let mut cmd = codex_command(codex_home.path())?;
+    let output = cmd
+        .env("GATEWAY_KEY", "sk-gateway")
+        .args(["models", "list"])
+        .output()?;
+    assert!(output.status.success());
+    assert_eq!(
+        first_line(&output)?,
+        "Catalog: Gateway via 127.0.0.1:9, API key from $GATEWAY_KEY"
+    );
+
+    Ok(())
+}
+
+/// `(model, effort)` pairs from `codex models matrix --json`.
+fn matrix_pairs(codex_home: &Path, extra_args: &[&str]) -> Result<Vec<(String, Option<String>)>> {
+    let mut cmd = codex_command(codex_home)?;
+    let output = cmd
+        .args(["--offline", "models", "matrix", "--json"])
+        .args(extra_args)
+        .output()?;
+    assert!(output.status.success());
+    let entries: Vec<serde_json::Value> = serde_json::from_slice(&output.stdout)?;
+    Ok(entries
+        .iter()
+        .map(|entry| {
+            (
+                entry["preset"]["model"]
+                    .as_str()
+                    .unwrap_or_default()
+                    .to_string(),
+                entry["effort"].as_str().map(str::to_string),
+            )
+        })
+        .collect())
+}
+
+#[test]
+fn matrix_crosses_each_listed_model_with_its_efforts() -> Result<()> {
+    let codex_home = TempDir::new()?;
+
+    let pairs = matrix_pairs(codex_home.path(), &[])?;
+    let max: Vec<Option<String>> = pairs
+        .iter()
+        .filter(|(model, _)| model == "gpt-5.1-codex-max")
+        .map(|(_, effort)| effort.clone())
+        .collect();
+    assert_eq!(
+        max,
+        ["low", "medium", "high", "xhigh"]
+            .map(|effort| Some(effort.to_string()))
+            .to_vec()
+    );
+    assert!(
+        !pairs.iter().any(|(model, _)| model == "gpt-5"),
+        "hidden presets are excluded by default: {pairs:?}"
+    );
+
+    let with_hidden = matrix_pairs(codex_home.path(), &["--include-hidden"])?;
+    assert!(
+        with_hidden.iter().any(|(model, _)| model == "gpt-5"),
+        "--include-hidden lists hidden presets: {with_hidden:?}"
+    );
+
+    Ok(())
+}
+
+/// `codex models which --json` for a `CODEX_HOME` holding `config_toml`.
+fn which_json(config_toml: &str, args: &[&str]) -> Result<serde_json::Value> {
+    let codex_home = TempDir::new()?;
+    std::fs::write(codex_home.path().join("config.toml"), config_toml)?;
+
+    let mut cmd = codex_command(codex_home.path())?;
+    let output = cmd
+        .arg("--offline")
+        .args(args)
+        .args(["models", "which", "--json"])
+        .output()?;
+    assert!(output.status.success(), "{output:?}");
+    Ok(serde_json::from_slice(&output.stdout)?)
+}
+
+/// The source that decided `setting` (`model_sources` or `effort_sources`).
+fn winning_source(resolution: &serde_json::Value, setting: &str) -> Option<String> {
+    resolution[setting]
+        .as_array()?
+        .iter()
+        .find(|source| source["won"] == serde_json::json!(true))
+        .and_then(|source| source["source"].as_str())
+        .map(str::to_string)
+}
+
+const LAYERED_CONFIG: &str = r#"
+model = "gpt-5.1"
+model_reasoning_effort = "low"
+
+[profiles.fast]
+model = "gpt-5.1-codex-mini"
+model_reasoning_effort = "auto"
+"#;
+
+#[test]
+fn which_uses_the_top_level_model_without_overrides() -> Result<()> {
+    let resolution = which_json(LAYERED_CONFIG, &[])?;
+
+    assert_eq!(resolution["model"], "gpt-5.1");
+    assert_eq!(resolution["preset_id"], "gpt-5.1");
+    assert_eq!(resolution["effort"], "low");
+    assert_eq!(resolution["effort_is_auto"], false);
+    assert_eq!(resolution["provider_id"], "openai");
+    let source = winning_source(&resolution, "model_sources").unwrap_or_default();
+    assert!(source.starts_with("user config "), "{source}");
+
+    Ok(())
+}
+
+#[test]
+fn which_prefers_a_profile_over_config_overrides() -> Result<()> {
+    let resolution = which_json(
+        LAYERED_CONFIG,
+        &["-c", "model=\"gpt-5.1-codex-max\"", "--profile", "fast"],
+    )?;
+
+    assert_eq!(resolution["model"], "gpt-5.1-codex-mini");
+    assert_eq!(
+        winning_source(&resolution, "model_sources")
+            .unwrap_or_default()
+            .split(" in ")
+            .next(),
+        Some("profile `fast`")
+    );
+    // `auto` in the profile defers to the model's own default.
+    assert_eq!(resolution["effort_is_auto"], true);
+    assert_eq!(resolution["effort"], "medium");
+
+    Ok(())
+}
+
+#[test]
+fn which_prefers_the_model_flag_over_everything() -> Result<()> {
+    let codex_home = TempDir::new()?;
+    std::fs::write(codex_home.path().join("config.toml"), LAYERED_CONFIG)?;
+
+    let mut cmd = codex_command(codex_home.path())?;
+    let output = cmd
+        .args(["--offline", "-c", "model=\"gpt-5.1\"", "models", "which"])
+        .args([
+            "--profile",
+            "fast",
+            "--model",
+            "gpt-5.1-codex-max",
+            "--json",
+        ])
+        .output()?;
+    assert!(output.status.success(), "{output:?}");
+    let resolution: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+
+    assert_eq!(resolution["model"], "gpt-5.1-codex-max");
+    assert_eq!(
+        winning_source(&resolution, "model_sources").as_deref(),
+        Some("--model")
+    );
+
+    Ok(())
+}
+
+#[test]
+fn which_reports_a_retired_model_and_its_successor() -> Result<()> {
+    let resolution = which_json("model = \"codex-mini-latest\"\n", &[])?;
+
+    assert_eq!(resolution["model"], "gpt-5.1-codex-mini");
+    assert_eq!(resolution["replaces_retired"], "codex-mini-latest");
+
+    let codex_home = TempDir::new()?;
+    std::fs::write(
+        codex_home.path().join("config.toml"),
+        "model = \"codex-mini-latest\"\n",
+    )?;
+    let mut cmd = codex_command(codex_home.path())?;
+    cmd.args(["--offline", "models", "which"])
+        .assert()
+        .success()
+        .stdout(contains(
+            "model: gpt-5.1-codex-mini (replaces retired codex-mini-latest)",
+        ));
+
+    Ok(())
+}
+
+#[test]
+fn list_flags_models_refused_to_the_account() -> Result<()> {
+    let codex_home = TempDir::new()?;
+    std::fs::write(
+        codex_home.path().join("model_availability.json"),
+        serde_json::to_vec(&serde_json::json!({
+            "unavailable": [{
+                "account": "OpenAI via api.openai.com, no credentials",
+                "model": "gpt-5.1-codex-mini",
+                "reason": "not on your plan",
+                "marked_at": chrono::Utc::now().to_rfc3339(),
+            }],
+        }))?,
+    )?;
+
+    let mut cmd = codex_command(codex_home.path())?;
+    cmd.env_remove("OPENAI_BASE_URL")
+        .env_remove("CODEX_API_KEY")
+        .args(["--offline", "models", "list"])
+        .assert()
+        .success()
+        .stdout(contains("[unavailable for this account: not on your plan]"));
+
+    let mut cmd = codex_command(codex_home.path())?;
+    let output = cmd
+        .env_remove("OPENAI_BASE_URL")
+        .env_remove("CODEX_API_KEY")
+        .args(["--offline", "models", "list", "--json"])
+        .output()?;
+    assert!(output.status.success());
+    let presets: Vec<ModelPreset> = serde_json::from_slice(&output.stdout)?;
+    assert_eq!(
+        presets
+            .iter()
+            .filter_map(|preset| Some((
+                preset.model.as_str(),
+                preset.unavailable_for_account.as_deref()?
+            )))
+            .collect::<Vec<_>>(),
+        vec![("gpt-5.1-codex-mini", "not on your plan")]
+    );
+
+    Ok(())
+}
+
+#[test]
+fn list_aligns_wide_model_names() -> Result<()> {
+    let codex_home = TempDir::new()?;
+    std::fs::write(
+        codex_home.path().join("config.toml"),
+        r#"
+model_provider = "gateway"
+
+[model_providers.gateway]
+name = "Gateway"
+base_url = "http://127.0.0.1:9/v1"
+model_catalog = "none"
+models = ["模型-large", "🚀-small", "oca-mini"]
+"#,
+    )?;
+
+    let mut cmd = codex_command(codex_home.path())?;
+    let output = cmd.args(["models", "list"]).output()?;
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout)?;
+
+    // The provider column starts at the same terminal column on every row,
+    // however many bytes the name before it takes.
+    let provider_columns: Vec<usize> = stdout
+        .lines()
+        .filter_map(|line| {
+            let (name, _) = line.split_once("  gateway")?;
+            Some(display_width(name))
+        })
+        .collect();
+    assert_eq!(provider_columns.len(), 3, "{stdout}");
+    assert!(
+        provider_columns
+            .iter()
+            .all(|column| *column == provider_columns[0]),
+        "{stdout}"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn list_notes_a_newer_minimum_client_version() -> Result<()> {
+    let codex_home = TempDir::new()?;
+    std::fs::write(
+        codex_home.path().join("config.toml"),
+        "catalog_locale = \"C\"\n\n[features]\nremote_models = true\n",
+    )?;
+    std::fs::write(
+        codex_home.path().join("models_cache.json"),
+        serde_json::to_vec(&serde_json::json!({
+            "fetched_at": chrono::Utc::now().to_rfc3339(),
+            "models": [],
+            "min_client_version": "999.0.0",
+        }))?,
+    )?;
+    let notice = format!(
+        "Notice: your Codex version ({}) is below the minimum recommended by your gateway (999.0.0); please upgrade",
+        env!("CARGO_PKG_VERSION")
+    );
+
+    let mut cmd = codex_command(codex_home.path())?;
+    let output = cmd.args(["--offline", "models", "list"]).output()?;
+    assert!(output.status.success(), "the notice never blocks listing");
+    let stdout = String::from_utf8(output.stdout)?;
+    assert_eq!(stdout.lines().nth(1), Some(notice.as_str()), "{stdout}");
+
+    // JSON output stays parseable, with the notice on stderr.
+    let mut cmd = codex_command(codex_home.path())?;
+    let output = cmd
+        .args(["--offline", "models", "list", "--json"])
+        .output()?;
+    assert!(output.status.success());
+    let presets: Vec<ModelPreset> = serde_json::from_slice(&output.stdout)?;
+    assert!(!presets.is_empty());
+    assert!(String::from_utf8(output.stderr)?.contains(&notice));
+
+    Ok(())
+}
+
+#[test]
+fn an_imported_export_is_listed_in_place_of_the_catalogs() -> Result<()> {
+    let codex_home = TempDir::new()?;
+    let export_path = codex_home.path().join("cat.json");
+
+    codex_command(codex_home.path())?
+        .args(["--offline", "models", "export", "-o"])
+        .arg(&export_path)
+        .assert()
+        .success()
+        .stdout(contains("Exported"));
+    let mut export: serde_json::Value = serde_json::from_slice(&std::fs::read(&export_path)?)?;
+    // Keep one preset, so the listing shows the import rather than the
+    // built-in presets.
+    let presets = export["presets"].as_array_mut().expect("presets");
+    presets.retain(|preset| preset["model"] == "gpt-5.1-codex-max");
+    std::fs::write(&export_path, serde_json::to_vec(&export)?)?;
+
+    codex_command(codex_home.path())?
+        .args(["models", "import", "--as-source"])
+        .arg(&export_path)
+        .assert()
+        .success()
+        .stdout(contains("read-only catalog source"));
+
+    let output = codex_command(codex_home.path())?
+        .args(["--offline", "models", "list", "--json"])
+        .output()?;
+    assert!(output.status.success());
+    let presets: Vec<ModelPreset> = serde_json::from_slice(&output.stdout)?;
+    assert_eq!(
+        presets
+            .iter()
+            .map(|preset| preset.model.as_str())
+            .collect::<Vec<_>>(),
+        vec!["gpt-5.1-codex-max"]
+    );
+
+    codex_command(codex_home.path())?
+        .args(["--offline", "models", "status"])
+        .assert()
+        .success()
+        .stdout(contains("Imported catalog:"));
+    codex_command(codex_home.path())?
+        .args(["models", "refresh"])
+        .assert()
+        .failure()
+        .stderr(contains("codex models import --clear"));
+
+    codex_command(codex_home.path())?
+        .args(["models", "import", "--clear"])
+        .assert()
+        .success()
+        .stdout(contains("Removed the imported catalog"));
+    codex_command(codex_home.path())?
+        .args(["--offline", "models", "status"])
+        .assert()
+        .success()
+        .stdout(contains("Imported catalog:").not());
+
+    Ok(())
+}
+
+#[test]
+fn import_refuses_an_export_from_a_newer_codex() -> Result<()> {
+    let codex_home = TempDir::new()?;
+    let export_path = codex_home.path().join("cat.json");
+    std::fs::write(&export_path, r#"{"schema_version": 99}"#)?;
+
+    codex_command(codex_home.path())?
+        .args(["models", "import", "--as-source"])
+        .arg(&export_path)
+        .assert()
+        .failure()
+        .stderr(contains("newer Codex"));
+    assert!(!codex_home.path().join("models_import.json").exists());
+
+    Ok(())
+}
@@ -0,0 +1,144 @@
+use std::path::Path;
+
+use anyhow::Result;
+use predicates::str::contains;
+use pretty_assertions::assert_eq;
+use tempfile::TempDir;
+
+fn codex_command(codex_home: &Path) -> Result<assert_cmd::Command> {
+    let mut cmd = assert_cmd::Command::new(codex_utils_cargo_bin::cargo_bin("codex")?);
+    cmd.env("CODEX_HOME", codex_home);
+    Ok(cmd)
+}
+
+fn read_config(codex_home: &Path) -> Result<String> {
+    Ok(std::fs::read_to_string(codex_home.join("config.toml"))?)
+}
+
+#[test]
+fn set_default_updates_model_and_keeps_formatting() -> Result<()> {
+    let codex_home = TempDir::new()?;
+    std::fs::write(
+        codex_home.path().join("config.toml"),
+        r#"# Global comment
+model = "gpt-5.1-codex-max" # picked last week
+model_reasoning_effort = "high"
+"#,
+    )?;
+
+    let mut cmd = codex_command(codex_home.path())?;
+    cmd.args(["models", "set-default", "gpt-5.2"])
+        .assert()
+        .success()
+        .stdout(contains(r#"model: "gpt-5.1-codex-max" -> "gpt-5.2""#));
+
+    assert_eq!(
+        read_config(codex_home.path())?,
+        r#"# Global comment
+model = "gpt-5.2" # picked last week
+model_reasoning_effort = "high"
+"#
+    );
+
+    Ok(())
+}
+
+#[test]
+fn set_default_with_effort_targets_profile() -> Result<()> {
+    let codex_home = TempDir::new()?;
+    std::fs::write(
+        codex_home.path().join("config.toml"),
+        r#"model = "gpt-5.1-codex-max"
+
+[profiles.work]
+model = "gpt-5.1-codex-mini"
+"#,
+    )?;
+
+    let mut cmd = codex_command(codex_home.path())?;
+    cmd.args([
+        "models",
+        "set-default",
+        "gpt-5.2",
+        "--with-effort",
+        "--profile",
+        "work",
+    ])
+    .assert()
+    .success()
+    .stdout(contains("Updated [profiles.work] in"))
+    .stdout(contains(r#"model_reasoning_effort = "medium""#));
+
+    assert_eq!(
+        read_config(codex_home.path())?,
+        r#"model = "gpt-5.1-codex-max"
+
+[profiles.work]
+model = "gpt-5.2"
+model_reasoning_effort = "medium"
+"#
+    );
+
+    Ok(())
+}
+
+#[test]
+fn set_default_removes_a_catalog_provider_that_does_not_serve_the_model() -> Result<()> {
+    let codex_home = TempDir::new()?;
+    std::fs::write(
+        codex_home.path().join("config.toml"),
+        r#"model = "oca-large"
+model_provider = "gateway"
+
+[model_providers.gateway]
+name = "Gateway"
+base_url = "http://127.0.0.1:9/v1"
+model_catalog = "none"
+models = ["oca-large"]
+"#,
+    )?;
+
+    let mut cmd = codex_command(codex_home.path())?;
+    cmd.args(["models", "set-default", "gpt-5.2"])
+        .assert()
+        .success()
+        .stdout(contains(r#"model_provider = "gateway" (removed)"#));
+
+    assert_eq!(
+        read_config(codex_home.path())?,
+        r#"model = "gpt-5.2"
+
+[model_providers.gateway]
+name = "Gateway"
+base_url = "http://127.0.0.1:9/v1"
+model_catalog = "none"
+models = ["oca-large"]
+"#
+    );
+
+    Ok(())
+}
+
+#[test]
+fn set_default_rejects_unknown_model_with_suggestions() -> Result<()> {
+    let codex_home = TempDir::new()?;
+
+    let mut cmd = codex_command(codex_home.path())?;
+    cmd.args(["models", "set-default", "codex-max"])
+        .assert()
+        .failure()
+        .stderr(contains("unknown model 'codex-max'. Did you mean:"))
+        .stderr(contains("gpt-5.1-codex-max"));
+
+    let mut cmd = codex_command(codex_home.path())?;
+    cmd.args(["models", "set-default", "not-a-model"])
+        .assert()
+        .failure()
+        .stderr(contains(
+            "unknown model 'not-a-model'. Run `codex models list` to see available models.",
+        ));
+
+    assert!(!codex_home.path().join("config.toml").exists());
+
+    Ok(())
+}
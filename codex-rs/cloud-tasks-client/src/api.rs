@@ -1,5 +1,6 @@
 use chrono::DateTime;
 use chrono::Utc;
+use codex_protocol::openai_models::ReasoningEffort;
 use serde::Deserialize;
 use serde::Serialize;
 
@@ -47,6 +48,29 @@ pub struct TaskSummary {
     /// Number of assistant attempts (best-of-N), when reported by the backend.
     #[serde(default)]
     pub attempt_total: Option<usize>,
+    /// Model the task ran with, when its details report one.
+    #[serde(default)]
+    pub model: Option<TaskModel>,
+}
+
+/// A task's model and effort. New tasks record the preset they are submitted
+/// with in their metadata under the same keys, next to `best_of_n`; once the
+/// task has run, the model its assistant messages name takes precedence.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TaskModel {
+    pub model: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reasoning_effort: Option<ReasoningEffort>,
+}
+
+impl TaskModel {
+    /// `display_name` followed by the effort, e.g. `gpt-5.1-codex-max (high)`.
+    pub fn label(&self, display_name: &str) -> String {
+        match self.reasoning_effort {
+            Some(effort) => format!("{display_name} ({effort})"),
+            None => display_name.to_string(),
+        }
+    }
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
@@ -87,11 +111,17 @@ pub struct ApplyOutcome {
     pub skipped_paths: Vec<String>,
     #[serde(default)]
     pub conflict_paths: Vec<String>,
+    /// Model that produced the applied diff, when the task recorded one.
+    #[serde(default)]
+    pub model: Option<TaskModel>,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct CreatedTask {
     pub id: TaskId,
+    /// Model recorded with the submission, if any.
+    #[serde(default)]
+    pub model: Option<TaskModel>,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Default)]
@@ -148,6 +178,8 @@ pub trait CloudBackend: Send + Sync {
         diff_override: Option<String>,
     ) -> Result<ApplyOutcome>;
     async fn apply_task(&self, id: TaskId, diff_override: Option<String>) -> Result<ApplyOutcome>;
+    /// Submit a new task. `model` is recorded in the task metadata so results
+    /// can say which model produced them.
     async fn create_task(
         &self,
         env_id: &str,
@@ -155,5 +187,6 @@ pub trait CloudBackend: Send + Sync {
         git_ref: &str,
         qa_mode: bool,
         best_of_n: usize,
+        model: Option<&TaskModel>,
     ) -> Result<CreatedTask>;
 }
@@ -6,6 +6,7 @@ use crate::CloudTaskError;
 use crate::DiffSummary;
 use crate::Result;
 use crate::TaskId;
+use crate::TaskModel;
 use crate::TaskStatus;
 use crate::TaskSummary;
 use crate::TurnAttempt;
@@ -106,9 +107,10 @@ impl CloudBackend for HttpClient {
         git_ref: &str,
         qa_mode: bool,
         best_of_n: usize,
+        model: Option<&TaskModel>,
     ) -> Result<crate::CreatedTask> {
         self.tasks_api()
-            .create(env_id, prompt, git_ref, qa_mode, best_of_n)
+            .create(env_id, prompt, git_ref, qa_mode, best_of_n, model)
             .await
     }
 }
@@ -209,6 +211,7 @@ mod api {
                 .get("is_review")
                 .and_then(Value::as_bool)
                 .unwrap_or(false);
+            let model = task_model_from_details(&parsed);
             Ok(TaskSummary {
                 id,
                 title,
@@ -219,6 +222,7 @@ mod api {
                 summary,
                 is_review,
                 attempt_total,
+                model,
             })
         }
 
@@ -296,6 +300,7 @@ mod api {
             git_ref: &str,
             qa_mode: bool,
             best_of_n: usize,
+            model: Option<&TaskModel>,
         ) -> Result<crate::CreatedTask> {
             let mut input_items: Vec<serde_json::Value> = Vec::new();
             input_items.push(serde_json::json!({
@@ -322,13 +327,10 @@ mod api {
                 "input_items": input_items,
             });
 
-            if best_of_n > 1
+            if let Some(metadata) = task_metadata(best_of_n, model)
                 && let Some(obj) = request_body.as_object_mut()
             {
-                obj.insert(
-                    "metadata".to_string(),
-                    serde_json::json!({ "best_of_n": best_of_n }),
-                );
+                obj.insert("metadata".to_string(), metadata);
             }
 
             match self.backend.create_task(request_body).await {
@@ -338,7 +340,10 @@ mod api {
                         env_id,
                         prompt.chars().count()
                     ));
-                    Ok(crate::CreatedTask { id: TaskId(id) })
+                    Ok(crate::CreatedTask {
+                        id: TaskId(id),
+                        model: model.cloned(),
+                    })
                 }
                 Err(e) => {
                     append_error_log(&format!(
@@ -407,15 +412,31 @@ mod api {
             preflight: bool,
         ) -> Result<ApplyOutcome> {
             let id = task_id.0.clone();
-            let diff = match diff_override {
-                Some(diff) => diff,
+            let (diff, model) = match diff_override {
+                Some(diff) if preflight => (diff, None),
+                Some(diff) => {
+                    // The details are only needed for the model here, so a
+                    // failed fetch leaves it unknown instead of failing.
+                    let model = self
+                        .backend
+                        .get_task_details_with_body(&id)
+                        .await
+                        .ok()
+                        .and_then(|(_, body, _)| task_model_from_body(&body));
+                    (diff, model)
+                }
                 None => {
-                    let details = self.backend.get_task_details(&id).await.map_err(|e| {
+                    let (details, body, _ct) = self
+                        .backend
+                        .get_task_details_with_body(&id)
+                        .await
+                        .map_err(|e| {
                         CloudTaskError::Http(format!("get_task_details failed: {e}"))
                     })?;
-                    details.unified_diff().ok_or_else(|| {
+                    let diff = details.unified_diff().ok_or_else(|| {
                         CloudTaskError::Msg(format!("No diff available for task {id}"))
-                    })?
+                    })?;
+                    (diff, task_model_from_body(&body))
                 }
             };
 
@@ -432,6 +453,7 @@ mod api {
                         .to_string(),
                     skipped_paths: Vec::new(),
                     conflict_paths: Vec::new(),
+                    model,
                 });
             }
 
@@ -530,10 +552,80 @@ mod api {
                 message,
                 skipped_paths: r.skipped_paths,
                 conflict_paths: r.conflicted_paths,
+                model,
             })
         }
     }
 
+    /// Metadata recorded with a new task, or `None` when there is nothing to
+    /// record.
+    pub(super) fn task_metadata(best_of_n: usize, model: Option<&TaskModel>) -> Option<Value> {
+        let mut metadata = serde_json::Map::new();
+        if best_of_n > 1 {
+            metadata.insert("best_of_n".to_string(), serde_json::json!(best_of_n));
+        }
+        if let Some(model) = model
+            && let Ok(Value::Object(fields)) = serde_json::to_value(model)
+        {
+            metadata.extend(fields);
+        }
+        (!metadata.is_empty()).then_some(Value::Object(metadata))
+    }
+
+    /// The model a task ran with: the `model_slug` of its last assistant
+    /// message, else the model [`task_metadata`] recorded at submission. The
+    /// recorded effort is kept only while it belongs to that model.
+    pub(super) fn task_model_from_details(details: &Value) -> Option<TaskModel> {
+        let submitted = details
+            .get("task")
+            .and_then(|task| task.get("metadata"))
+            .or_else(|| details.get("metadata"))
+            .and_then(|metadata| serde_json::from_value::<TaskModel>(metadata.clone()).ok());
+        let Some(ran_with) = assistant_model_slug(details) else {
+            return submitted;
+        };
+        let reasoning_effort = submitted
+            .filter(|submitted| submitted.model == ran_with)
+            .and_then(|submitted| submitted.reasoning_effort);
+        Some(TaskModel {
+            model: ran_with,
+            reasoning_effort,
+        })
+    }
+
+    /// The model the backend names on the last assistant message of the
+    /// current turn.
+    fn assistant_model_slug(details: &Value) -> Option<String> {
+        details
+            .get("current_assistant_turn")?
+            .get("worklog")?
+            .get("messages")?
+            .as_array()?
+            .iter()
+            .rev()
+            .filter(|message| {
+                message
+                    .get("author")
+                    .and_then(|author| author.get("role"))
+                    .and_then(Value::as_str)
+                    == Some("assistant")
+            })
+            .find_map(|message| {
+                message
+                    .get("metadata")
+                    .and_then(|metadata| metadata.get("model_slug"))
+                    .and_then(Value::as_str)
+                    .filter(|slug| !slug.is_empty())
+                    .map(str::to_string)
+            })
+    }
+
+    fn task_model_from_body(body: &str) -> Option<TaskModel> {
+        serde_json::from_str::<Value>(body)
+            .ok()
+            .and_then(|details| task_model_from_details(&details))
+    }
+
     fn details_path(base_url: &str, id: &str) -> Option<String> {
         if base_url.contains("/backend-api") {
             Some(format!("{base_url}/wham/tasks/{id}"))
@@ -702,6 +794,8 @@ mod api {
                 .as_ref()
                 .is_some_and(|prs| !prs.is_empty()),
             attempt_total: attempt_total_from_status_display(status_display),
+            // Listings carry no task metadata; the summary reads it.
+            model: None,
         }
     }
 
@@ -879,3 +973,84 @@ fn append_error_log(message: &str) {
         let _ = writeln!(f, "[{ts}] {message}");
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::api::task_metadata;
+    use super::api::task_model_from_details;
+    use super::*;
+    use codex_protocol::openai_models::ReasoningEffort;
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+
+    #[test]
+    fn submitted_model_round_trips_through_task_metadata() {
+        let model = TaskModel {
+            model: "gpt-5.1-codex-max".to_string(),
+            reasoning_effort: Some(ReasoningEffort::High),
+        };
+        let metadata = task_metadata(2, Some(&model)).expect("metadata recorded");
+        assert_eq!(
+            metadata,
+            json!({
+                "best_of_n": 2,
+                "model": "gpt-5.1-codex-max",
+                "reasoning_effort": "high",
+            })
+        );
+
+        let details = json!({ "task": { "id": "task_1", "metadata": metadata } });
+        assert_eq!(task_model_from_details(&details), Some(model.clone()));
+        assert_eq!(model.label("gpt-5.1-codex-max"), "gpt-5.1-codex-max (high)");
+    }
+
+    #[test]
+    fn the_model_a_task_ran_with_wins_over_the_submitted_one() {
+        let metadata = json!({ "model": "gpt-5.1-codex-max", "reasoning_effort": "high" });
+        let ran_with = |slug: &str| {
+            json!({
+                "task": { "metadata": metadata },
+                "current_assistant_turn": { "worklog": { "messages": [
+                    { "author": { "role": "assistant" }, "metadata": { "model_slug": "older" } },
+                    { "author": { "role": "assistant" }, "metadata": { "model_slug": slug } },
+                    { "author": { "role": "tool" }, "metadata": { "model_slug": "ignored" } },
+                ] } },
+            })
+        };
+
+        assert_eq!(
+            task_model_from_details(&ran_with("gpt-5.1-codex")),
+            Some(TaskModel {
+                model: "gpt-5.1-codex".to_string(),
+                reasoning_effort: None,
+            }),
+            "the submitted effort belongs to another model"
+        );
+        assert_eq!(
+            task_model_from_details(&ran_with("gpt-5.1-codex-max")),
+            Some(TaskModel {
+                model: "gpt-5.1-codex-max".to_string(),
+                reasoning_effort: Some(ReasoningEffort::High),
+            })
+        );
+    }
+
+    #[test]
+    fn tasks_without_a_recorded_model_read_back_none() {
+        assert_eq!(task_metadata(1, None), None);
+        assert_eq!(
+            task_model_from_details(&json!({ "task": { "metadata": { "best_of_n": 2 } } })),
+            None
+        );
+        assert_eq!(task_model_from_details(&json!({ "task": {} })), None);
+
+        let without_effort = TaskModel {
+            model: "gpt-5.2".to_string(),
+            reasoning_effort: None,
+        };
+        assert_eq!(
+            task_metadata(1, Some(&without_effort)),
+            Some(json!({ "model": "gpt-5.2" }))
+        );
+    }
+}
@@ -9,6 +9,7 @@ pub use api::CreatedTask;
 pub use api::DiffSummary;
 pub use api::Result;
 pub use api::TaskId;
+pub use api::TaskModel;
 pub use api::TaskStatus;
 pub use api::TaskSummary;
 pub use api::TaskText;
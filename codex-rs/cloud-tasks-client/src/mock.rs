@@ -5,11 +5,13 @@ use crate::CloudTaskError;
 use crate::DiffSummary;
 use crate::Result;
 use crate::TaskId;
+use crate::TaskModel;
 use crate::TaskStatus;
 use crate::TaskSummary;
 use crate::TurnAttempt;
 use crate::api::TaskText;
 use chrono::Utc;
+use codex_protocol::openai_models::ReasoningEffort;
 
 #[derive(Clone, Default)]
 pub struct MockClient;
@@ -42,6 +44,7 @@ impl CloudBackend for MockClient {
             let id = TaskId(id_str.to_string());
             let diff = mock_diff_for(&id);
             let (a, d) = count_from_unified(&diff);
+            let model = mock_model_for(&id);
             out.push(TaskSummary {
                 id,
                 title: title.to_string(),
@@ -56,6 +59,7 @@ impl CloudBackend for MockClient {
                 },
                 is_review: false,
                 attempt_total: Some(if id_str == "T-1000" { 2 } else { 1 }),
+                model,
             });
         }
         Ok(out)
@@ -97,6 +101,7 @@ impl CloudBackend for MockClient {
             message: format!("Applied task {} locally (mock)", id.0),
             skipped_paths: Vec::new(),
             conflict_paths: Vec::new(),
+            model: mock_model_for(&id),
         })
     }
 
@@ -111,6 +116,7 @@ impl CloudBackend for MockClient {
             message: format!("Preflight passed for task {} (mock)", id.0),
             skipped_paths: Vec::new(),
             conflict_paths: Vec::new(),
+            model: mock_model_for(&id),
         })
     }
 
@@ -139,10 +145,14 @@ impl CloudBackend for MockClient {
         git_ref: &str,
         qa_mode: bool,
         best_of_n: usize,
+        model: Option<&TaskModel>,
     ) -> Result<crate::CreatedTask> {
         let _ = (env_id, prompt, git_ref, qa_mode, best_of_n);
         let id = format!("task_local_{}", chrono::Utc::now().timestamp_millis());
-        Ok(crate::CreatedTask { id: TaskId(id) })
+        Ok(crate::CreatedTask {
+            id: TaskId(id),
+            model: model.cloned(),
+        })
     }
 }
 
@@ -160,6 +170,13 @@ fn mock_diff_for(id: &TaskId) -> String {
     }
 }
 
+fn mock_model_for(id: &TaskId) -> Option<TaskModel> {
+    (id.0 == "T-1000").then(|| TaskModel {
+        model: "gpt-5.1-codex-max".to_string(),
+        reasoning_effort: Some(ReasoningEffort::High),
+    })
+}
+
 fn count_from_unified(diff: &str) -> (usize, usize) {
     if let Ok(patch) = diffy::Patch::from_str(diff) {
         patch
@@ -328,7 +328,11 @@ pub enum AppEvent {
         attempts: Vec<codex_cloud_tasks_client::TurnAttempt>,
     },
     /// Background completion of new task submission
-    NewTaskSubmitted(Result<codex_cloud_tasks_client::CreatedTask, String>),
+    NewTaskSubmitted {
+        result: Result<codex_cloud_tasks_client::CreatedTask, String>,
+        /// How the model recorded with the submission is shown, if any.
+        model_label: Option<String>,
+    },
     /// Background completion of apply preflight when opening modal or on demand
     ApplyPreflightFinished {
         id: TaskId,
@@ -342,6 +346,8 @@ pub enum AppEvent {
     ApplyFinished {
         id: TaskId,
         result: std::result::Result<codex_cloud_tasks_client::ApplyOutcome, String>,
+        /// How the model that produced the diff is shown, when recorded.
+        model_label: Option<String>,
     },
 }
 
@@ -381,6 +387,7 @@ mod tests {
                     summary: codex_cloud_tasks_client::DiffSummary::default(),
                     is_review: false,
                     attempt_total: Some(1),
+                    model: None,
                 });
             }
             Ok(out)
@@ -461,6 +468,7 @@ mod tests {
             _git_ref: &str,
             _qa_mode: bool,
             _best_of_n: usize,
+            _model: Option<&codex_cloud_tasks_client::TaskModel>,
         ) -> codex_cloud_tasks_client::Result<codex_cloud_tasks_client::CreatedTask> {
             Err(codex_cloud_tasks_client::CloudTaskError::Unimplemented(
                 "not used in test",
@@ -36,6 +36,7 @@ struct ApplyJob {
 struct BackendContext {
     backend: Arc<dyn codex_cloud_tasks_client::CloudBackend>,
     base_url: String,
+    catalog: Option<Arc<util::LocalCatalog>>,
 }
 
 async fn init_backend(user_agent_suffix: &str) -> anyhow::Result<BackendContext> {
@@ -47,11 +48,13 @@ async fn init_backend(user_agent_suffix: &str) -> anyhow::Result<BackendContext>
         .unwrap_or_else(|_| "https://chatgpt.com/backend-api".to_string());
 
     set_user_agent_suffix(user_agent_suffix);
+    let catalog = util::LocalCatalog::load().await.map(Arc::new);
 
     if use_mock {
         return Ok(BackendContext {
             backend: Arc::new(codex_cloud_tasks_client::MockClient),
             base_url,
+            catalog,
         });
     }
 
@@ -101,6 +104,7 @@ async fn init_backend(user_agent_suffix: &str) -> anyhow::Result<BackendContext>
     Ok(BackendContext {
         backend: Arc::new(http),
         base_url,
+        catalog,
     })
 }
 
@@ -163,6 +167,10 @@ async fn run_exec_command(args: crate::cli::ExecCommand) -> anyhow::Result<()> {
     let prompt = resolve_query_input(query)?;
     let env_id = resolve_environment_id(&ctx, &environment).await?;
     let git_ref = resolve_git_ref(branch.as_ref()).await;
+    let model = match &ctx.catalog {
+        Some(catalog) => Some(catalog.submission_model().await),
+        None => None,
+    };
     let created = codex_cloud_tasks_client::CloudBackend::create_task(
         &*ctx.backend,
         &env_id,
@@ -170,6 +178,7 @@ async fn run_exec_command(args: crate::cli::ExecCommand) -> anyhow::Result<()> {
         &git_ref,
         false,
         attempts,
+        model.as_ref(),
     )
     .await?;
     let url = util::task_url(&ctx.base_url, &created.id.0);
@@ -405,6 +414,7 @@ fn summary_line(summary: &codex_cloud_tasks_client::DiffSummary, colorize: bool)
 
 fn format_task_status_lines(
     task: &codex_cloud_tasks_client::TaskSummary,
+    model_label: Option<&str>,
     now: chrono::DateTime<Utc>,
     colorize: bool,
 ) -> Vec<String> {
@@ -450,6 +460,15 @@ fn format_task_status_lines(
             meta_parts.push(id.to_string());
         }
     }
+    if let Some(label) = model_label {
+        meta_parts.push(if colorize {
+            label
+                .if_supports_color(Stream::Stdout, |t| t.dimmed())
+                .to_string()
+        } else {
+            label.to_string()
+        });
+    }
     let when = format_relative_time(now, task.updated_at);
     meta_parts.push(if colorize {
         when.as_str()
@@ -475,9 +494,13 @@ async fn run_status_command(args: crate::cli::StatusCommand) -> anyhow::Result<(
     let task_id = parse_task_id(&args.task_id)?;
     let summary =
         codex_cloud_tasks_client::CloudBackend::get_task_summary(&*ctx.backend, task_id).await?;
+    let model_label = match &summary.model {
+        Some(model) => Some(util::model_label(ctx.catalog.as_deref(), model).await),
+        None => None,
+    };
     let now = Utc::now();
     let colorize = supports_color::on(SupportStream::Stdout).is_some();
-    for line in format_task_status_lines(&summary, now, colorize) {
+    for line in format_task_status_lines(&summary, model_label.as_deref(), now, colorize) {
         println!("{line}");
     }
     if !matches!(summary.status, TaskStatus::Ready) {
@@ -506,7 +529,14 @@ async fn run_apply_command(args: crate::cli::ApplyCommand) -> anyhow::Result<()>
         Some(selected.diff.clone()),
     )
     .await?;
-    println!("{}", outcome.message);
+    let model_label = match &outcome.model {
+        Some(model) => Some(util::model_label(ctx.catalog.as_deref(), model).await),
+        None => None,
+    };
+    println!(
+        "{}",
+        apply_result_summary(&outcome.message, model_label.as_deref())
+    );
     if !matches!(
         outcome.status,
         codex_cloud_tasks_client::ApplyStatus::Success
@@ -516,6 +546,15 @@ async fn run_apply_command(args: crate::cli::ApplyCommand) -> anyhow::Result<()>
     Ok(())
 }
 
+/// The apply result message, naming the model that produced the diff when the
+/// task recorded it.
+fn apply_result_summary(message: &str, model_label: Option<&str>) -> String {
+    match model_label {
+        Some(label) => format!("{message} (produced by {label})"),
+        None => message.to_string(),
+    }
+}
+
 fn level_from_status(status: codex_cloud_tasks_client::ApplyStatus) -> app::ApplyResultLevel {
     match status {
         codex_cloud_tasks_client::ApplyStatus::Success => app::ApplyResultLevel::Success,
@@ -589,6 +628,7 @@ fn spawn_preflight(
 fn spawn_apply(
     app: &mut app::App,
     backend: &Arc<dyn codex_cloud_tasks_client::CloudBackend>,
+    catalog: Option<&Arc<util::LocalCatalog>>,
     tx: &UnboundedSender<app::AppEvent>,
     frame_tx: &UnboundedSender<Instant>,
     job: ApplyJob,
@@ -606,6 +646,7 @@ fn spawn_apply(
     let _ = frame_tx.send(Instant::now() + Duration::from_millis(100));
 
     let backend = backend.clone();
+    let catalog = catalog.cloned();
     let tx = tx.clone();
     tokio::spawn(async move {
         let ApplyJob {
@@ -620,13 +661,21 @@ fn spawn_apply(
         .await;
 
         let event = match result {
-            Ok(outcome) => app::AppEvent::ApplyFinished {
-                id: task_id,
-                result: Ok(outcome),
-            },
+            Ok(outcome) => {
+                let model_label = match &outcome.model {
+                    Some(model) => Some(util::model_label(catalog.as_deref(), model).await),
+                    None => None,
+                };
+                app::AppEvent::ApplyFinished {
+                    id: task_id,
+                    result: Ok(outcome),
+                    model_label,
+                }
+            }
             Err(e) => app::AppEvent::ApplyFinished {
                 id: task_id,
                 result: Err(format!("{e}")),
+                model_label: None,
             },
         };
 
@@ -665,7 +714,9 @@ pub async fn run_main(cli: Cli, _codex_linux_sandbox_exe: Option<PathBuf>) -> an
         .try_init();
 
     info!("Launching Cloud Tasks list UI");
-    let BackendContext { backend, .. } = init_backend("codex_cloud_tasks_tui").await?;
+    let BackendContext {
+        backend, catalog, ..
+    } = init_backend("codex_cloud_tasks_tui").await?;
     let backend = backend;
 
     // Terminal setup
@@ -889,14 +940,17 @@ pub async fn run_main(cli: Cli, _codex_linux_sandbox_exe: Option<PathBuf>) -> an
                             needs_redraw = true;
                             let _ = frame_tx.send(Instant::now());
                         }
-                        app::AppEvent::NewTaskSubmitted(result) => {
+                        app::AppEvent::NewTaskSubmitted { result, model_label } => {
                             match result {
                                 Ok(created) => {
                                     append_error_log(format!("new-task: created id={}", created.id.0));
-                                    app.status = format!("Submitted as {}", created.id.0);
+                                    let submitted = match model_label {
+                                        Some(label) => format!("Submitted as {} with {label}", created.id.0),
+                                        None => format!("Submitted as {}", created.id.0),
+                                    };
                                     app.new_task = None;
                                     // Refresh tasks in background for current filter
-                                    app.status = format!("Submitted as {} — refreshing…", created.id.0);
+                                    app.status = format!("{submitted} — refreshing…");
                                     app.refresh_inflight = true;
                                     app.list_generation = app.list_generation.saturating_add(1);
                                     needs_redraw = true;
@@ -1187,7 +1241,7 @@ pub async fn run_main(cli: Cli, _codex_linux_sandbox_exe: Option<PathBuf>) -> an
                             app.details_inflight = false;
                             needs_redraw = true;
                         }
-                        app::AppEvent::ApplyFinished { id, result } => {
+                        app::AppEvent::ApplyFinished { id, result, model_label } => {
                             // Only update if the modal still corresponds to this id.
                             if let Some(m) = &app.apply_modal {
                                 if m.task_id != id { continue; }
@@ -1197,7 +1251,7 @@ pub async fn run_main(cli: Cli, _codex_linux_sandbox_exe: Option<PathBuf>) -> an
                             app.apply_inflight = false;
                             match result {
                                 Ok(outcome) => {
-                                    app.status = outcome.message.clone();
+                                    app.status = apply_result_summary(&outcome.message, model_label.as_deref());
                                     if matches!(outcome.status, codex_cloud_tasks_client::ApplyStatus::Success) {
                                         app.apply_modal = None;
                                         app.diff_overlay = None;
@@ -1409,14 +1463,23 @@ pub async fn run_main(cli: Cli, _codex_linux_sandbox_exe: Option<PathBuf>) -> an
                                                 app.status = "Submitting new task…".to_string();
                                                 let tx = tx.clone();
                                                 let backend = Arc::clone(&backend);
+                                                let catalog = catalog.clone();
                                                 let best_of_n = page.best_of_n;
                                                 tokio::spawn(async move {
                                                     let git_ref = resolve_git_ref(None).await;
+                                                    let model = match &catalog {
+                                                        Some(catalog) => Some(catalog.submission_model().await),
+                                                        None => None,
+                                                    };
 
-                                                    let result = codex_cloud_tasks_client::CloudBackend::create_task(&*backend, &env, &text, &git_ref, false, best_of_n).await;
-                                                    let evt = match result {
-                                                        Ok(ok) => app::AppEvent::NewTaskSubmitted(Ok(ok)),
-                                                        Err(e) => app::AppEvent::NewTaskSubmitted(Err(format!("{e}"))),
+                                                    let result = codex_cloud_tasks_client::CloudBackend::create_task(&*backend, &env, &text, &git_ref, false, best_of_n, model.as_ref()).await;
+                                                    let model_label = match result.as_ref().ok().and_then(|created| created.model.as_ref()) {
+                                                        Some(model) => Some(util::model_label(catalog.as_deref(), model).await),
+                                                        None => None,
+                                                    };
+                                                    let evt = app::AppEvent::NewTaskSubmitted {
+                                                        result: result.map_err(|e| format!("{e}")),
+                                                        model_label,
                                                     };
                                                     let _ = tx.send(evt);
                                                 });
@@ -1449,7 +1512,7 @@ pub async fn run_main(cli: Cli, _codex_linux_sandbox_exe: Option<PathBuf>) -> an
                                             task_id: m.task_id.clone(),
                                             diff_override: m.diff_override.clone(),
                                         };
-                                        if spawn_apply(&mut app, &backend, &tx, &frame_tx, job) {
+                                        if spawn_apply(&mut app, &backend, catalog.as_ref(), &tx, &frame_tx, job) {
                                             app.status = format!("Applying '{title}'...");
                                         }
                                         needs_redraw = true;
@@ -2034,6 +2097,7 @@ mod tests {
     use codex_cloud_tasks_client::DiffSummary;
     use codex_cloud_tasks_client::MockClient;
     use codex_cloud_tasks_client::TaskId;
+    use codex_cloud_tasks_client::TaskModel;
     use codex_cloud_tasks_client::TaskStatus;
     use codex_cloud_tasks_client::TaskSummary;
     use codex_tui::ComposerAction;
@@ -2141,8 +2205,9 @@ mod tests {
             },
             is_review: false,
             attempt_total: None,
+            model: None,
         };
-        let lines = format_task_status_lines(&task, now, false);
+        let lines = format_task_status_lines(&task, None, now, false);
         assert_eq!(
             lines,
             vec![
@@ -2166,8 +2231,9 @@ mod tests {
             summary: DiffSummary::default(),
             is_review: false,
             attempt_total: Some(1),
+            model: None,
         };
-        let lines = format_task_status_lines(&task, now, false);
+        let lines = format_task_status_lines(&task, None, now, false);
         assert_eq!(
             lines,
             vec![
@@ -2178,6 +2244,42 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn task_models_missing_locally_render_as_the_raw_slug() {
+        let model = TaskModel {
+            model: "cloud-only-model".to_string(),
+            reasoning_effort: None,
+        };
+        let label = util::model_label(None, &model).await;
+        assert_eq!(label, "cloud-only-model");
+
+        let now = Utc::now();
+        let task = TaskSummary {
+            id: TaskId("task_3".to_string()),
+            title: "Recorded model".to_string(),
+            status: TaskStatus::Ready,
+            updated_at: now,
+            environment_id: None,
+            environment_label: Some("Env".to_string()),
+            summary: DiffSummary::default(),
+            is_review: false,
+            attempt_total: Some(1),
+            model: Some(model),
+        };
+        assert_eq!(
+            format_task_status_lines(&task, Some(&label), now, false)[1],
+            "Env  •  cloud-only-model  •  0s ago"
+        );
+        assert_eq!(
+            apply_result_summary("Applied task task_3 locally (1 files)", Some(&label)),
+            "Applied task task_3 locally (1 files) (produced by cloud-only-model)"
+        );
+        assert_eq!(
+            apply_result_summary("Applied task task_3 locally (1 files)", None),
+            "Applied task task_3 locally (1 files)"
+        );
+    }
+
     #[tokio::test]
     async fn collect_attempt_diffs_includes_sibling_attempts() {
         let backend = MockClient;
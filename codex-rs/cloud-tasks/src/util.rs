@@ -4,7 +4,9 @@ use chrono::Local;
 use chrono::Utc;
 use reqwest::header::HeaderMap;
 
+use codex_cloud_tasks_client::TaskModel;
 use codex_core::config::Config;
+use codex_core::models_manager::manager::ModelsManager;
 use codex_login::AuthManager;
 
 pub fn set_user_agent_suffix(suffix: &str) {
@@ -69,6 +71,59 @@ pub async fn load_auth_manager() -> Option<AuthManager> {
     ))
 }
 
+/// The local model catalog: the configured model goes out with new tasks, and
+/// the models tasks report are shown by their display names.
+pub struct LocalCatalog {
+    config: Config,
+    models_manager: ModelsManager,
+}
+
+impl LocalCatalog {
+    pub async fn load() -> Option<Self> {
+        // TODO: pass in cli overrides once cloud tasks properly support them.
+        let config = Config::load_with_cli_overrides(Vec::new()).await.ok()?;
+        let auth_manager = AuthManager::shared(
+            config.codex_home.clone(),
+            false,
+            config.cli_auth_credentials_store_mode,
+        );
+        Some(Self {
+            config,
+            models_manager: ModelsManager::new(auth_manager),
+        })
+    }
+
+    /// The preset id and effort a new session would use.
+    pub async fn submission_model(&self) -> TaskModel {
+        let model = self
+            .models_manager
+            .get_model(&self.config.model, &self.config)
+            .await;
+        TaskModel {
+            model: self
+                .models_manager
+                .preset_id_for_model(&model, &self.config.model_provider_id)
+                .await,
+            reasoning_effort: self.config.model_reasoning_effort,
+        }
+    }
+}
+
+/// How a task's model is shown: the local catalog's display name, or the raw
+/// slug when the catalog does not list it or could not be loaded.
+pub async fn model_label(catalog: Option<&LocalCatalog>, model: &TaskModel) -> String {
+    let display_name = match catalog {
+        Some(catalog) => {
+            catalog
+                .models_manager
+                .display_name(&model.model, &catalog.config)
+                .await
+        }
+        None => model.model.clone(),
+    };
+    model.label(&display_name)
+}
+
 /// Build headers for ChatGPT-backed requests: `User-Agent`, optional `Authorization`,
 /// and optional `ChatGPT-Account-Id`.
 pub async fn build_chatgpt_headers() -> HeaderMap {
@@ -12,8 +12,14 @@ pub trait AuthProvider: Send + Sync {
     }
 }
 
+/// The bearer token to send, if any. An empty token is treated as none, since
+/// `Authorization: Bearer ` with nothing after it is malformed.
+pub(crate) fn non_empty_token<A: AuthProvider>(auth: &A) -> Option<String> {
+    auth.bearer_token().filter(|token| !token.trim().is_empty())
+}
+
 pub(crate) fn add_auth_headers<A: AuthProvider>(auth: &A, mut req: Request) -> Request {
-    if let Some(token) = auth.bearer_token()
+    if let Some(token) = non_empty_token(auth)
         && let Ok(header) = format!("Bearer {token}").parse()
     {
         let _ = req.headers.insert(http::header::AUTHORIZATION, header);
@@ -25,3 +31,14 @@ pub(crate) fn add_auth_headers<A: AuthProvider>(auth: &A, mut req: Request) -> R
     }
     req
 }
+
+/// Azure OpenAI expects the key in an `api-key` header rather than as a bearer
+/// token on its management-style endpoints such as `/openai/deployments`.
+pub(crate) fn add_azure_api_key_header<A: AuthProvider>(auth: &A, mut req: Request) -> Request {
+    if let Some(token) = non_empty_token(auth)
+        && let Ok(header) = token.parse()
+    {
+        let _ = req.headers.insert("api-key", header);
+    }
+    req
+}
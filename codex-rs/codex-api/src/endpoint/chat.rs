@@ -3,6 +3,7 @@ use crate::auth::AuthProvider;
 use crate::common::Prompt as ApiPrompt;
 use crate::common::ResponseEvent;
 use crate::common::ResponseStream;
+use crate::common::Sampling;
 use crate::endpoint::streaming::StreamingClient;
 use crate::error::ApiError;
 use crate::provider::Provider;
@@ -53,6 +54,7 @@ impl<T: HttpTransport, A: AuthProvider> ChatClient<T, A> {
         &self,
         model: &str,
         prompt: &ApiPrompt,
+        sampling: Sampling,
         conversation_id: Option<String>,
         session_source: Option<SessionSource>,
     ) -> Result<ResponseStream, ApiError> {
@@ -60,6 +62,7 @@ impl<T: HttpTransport, A: AuthProvider> ChatClient<T, A> {
 
         let request =
             ChatRequestBuilder::new(model, &prompt.instructions, &prompt.input, &prompt.tools)
+                .sampling(sampling)
                 .conversation_id(conversation_id)
                 .session_source(session_source)
                 .build(self.streaming.provider())?;
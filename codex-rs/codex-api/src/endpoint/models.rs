@@ -1,22 +1,47 @@
 use crate::auth::AuthProvider;
 use crate::auth::add_auth_headers;
+use crate::auth::add_azure_api_key_header;
+use crate::auth::non_empty_token;
 use crate::error::ApiError;
 use crate::provider::Provider;
 use crate::telemetry::run_with_request_telemetry;
 use codex_client::HttpTransport;
+use codex_client::Request;
 use codex_client::RequestTelemetry;
+use codex_protocol::openai_models::ContextWindowSource;
+use codex_protocol::openai_models::ContextWindowUnit;
+use codex_protocol::openai_models::MIN_UNHINTED_CONTEXT_WINDOW;
 use codex_protocol::openai_models::ModelInfo;
-use codex_protocol::openai_models::ModelsResponse;
 use http::HeaderMap;
 use http::Method;
+use http::header::CONTENT_TYPE;
 use http::header::ETAG;
+use serde::Deserialize;
+use sha2::Digest;
+use sha2::Sha256;
+use std::collections::HashMap;
+use std::collections::HashSet;
 use std::sync::Arc;
+use tracing::debug;
+use tracing::warn;
+
+/// `api-version` used for the Azure deployments listing when the provider does
+/// not pin one through its query params.
+pub const AZURE_DEPLOYMENTS_API_VERSION: &str = "2024-10-21";
+
+/// Largest decoded catalog response accepted unless the caller overrides it.
+pub const DEFAULT_MAX_MODELS_RESPONSE_BYTES: usize = 8 * 1024 * 1024;
+
+/// Most characters of an unexpected response body quoted in an error.
+const MAX_SNIPPET_CHARS: usize = 120;
 
 pub struct ModelsClient<T: HttpTransport, A: AuthProvider> {
     transport: T,
     provider: Provider,
     auth: A,
     request_telemetry: Option<Arc<dyn RequestTelemetry>>,
+    max_response_bytes: usize,
+    path: Option<String>,
 }
 
 impl<T: HttpTransport, A: AuthProvider> ModelsClient<T, A> {
@@ -26,6 +51,8 @@ impl<T: HttpTransport, A: AuthProvider> ModelsClient<T, A> {
             provider,
             auth,
             request_telemetry: None,
+            max_response_bytes: DEFAULT_MAX_MODELS_RESPONSE_BYTES,
+            path: None,
         }
     }
 
@@ -34,18 +61,53 @@ impl<T: HttpTransport, A: AuthProvider> ModelsClient<T, A> {
         self
     }
 
-    fn path(&self) -> &'static str {
-        "models"
+    /// Reject catalog responses whose decoded body is larger than `limit` bytes.
+    pub fn with_max_response_bytes(mut self, limit: usize) -> Self {
+        self.max_response_bytes = limit;
+        self
+    }
+
+    /// Request the catalog from `path`, relative to the provider base URL,
+    /// instead of `models` (or `deployments` for Azure). A path starting
+    /// with `/` is taken from the root of the base URL's host, and a full URL
+    /// is requested as-is; callers are expected to have checked its host.
+    pub fn with_path(mut self, path: Option<String>) -> Self {
+        self.path = path;
+        self
+    }
+
+    /// The credentials catalog requests are sent with.
+    pub fn auth(&self) -> &A {
+        &self.auth
+    }
+
+    /// Whether catalog requests carry a bearer token or API key.
+    pub fn has_credentials(&self) -> bool {
+        non_empty_token(&self.auth).is_some()
+    }
+
+    fn catalog_request(&self, default_path: &str) -> Request {
+        let path = self.path.as_deref().unwrap_or(default_path);
+        let mut req = self.provider.build_request(Method::GET, path);
+        if path.contains("://") {
+            req.url = path.to_string();
+        } else if path.starts_with('/') {
+            // Keep the query the provider adds, such as Azure's `api-version`.
+            let query = req.url.find('?').map_or("", |start| &req.url[start..]);
+            req.url = format!("{}{path}{query}", origin(&self.provider.base_url));
+        }
+        req
     }
 
     pub async fn list_models(
         &self,
         client_version: &str,
         extra_headers: HeaderMap,
-    ) -> Result<(Vec<ModelInfo>, Option<String>), ApiError> {
+    ) -> Result<ModelsListing, ApiError> {
         let builder = || {
-            let mut req = self.provider.build_request(Method::GET, self.path());
+            let mut req = self.catalog_request("models");
             req.headers.extend(extra_headers.clone());
+            req.max_response_bytes = Some(self.max_response_bytes);
 
             let separator = if req.url.contains('?') { '&' } else { '?' };
             req.url = format!("{}{}client_version={client_version}", req.url, separator);
@@ -67,16 +129,534 @@ impl<T: HttpTransport, A: AuthProvider> ModelsClient<T, A> {
             .and_then(|value| value.to_str().ok())
             .map(ToString::to_string);
 
-        let ModelsResponse { models } = serde_json::from_slice::<ModelsResponse>(&resp.body)
-            .map_err(|e| {
-                ApiError::Stream(format!(
-                    "failed to decode models response: {e}; body: {}",
-                    String::from_utf8_lossy(&resp.body)
-                ))
-            })?;
+        check_json_content(&resp.headers, &resp.body)?;
+        Ok(ModelsListing {
+            etag: header_etag,
+            ..decode_models_listing(&resp.body)?
+        })
+    }
+
+    /// List model ids from an OpenAI-compatible `GET /models` endpoint, as
+    /// served by LM Studio, vLLM, and similar local servers.
+    pub async fn list_openai_compatible_models(
+        &self,
+        extra_headers: HeaderMap,
+    ) -> Result<CatalogListing<String>, ApiError> {
+        let resp = run_with_request_telemetry(
+            self.provider.retry.to_policy(),
+            self.request_telemetry.clone(),
+            || self.openai_compatible_models_request(extra_headers.clone()),
+            |req| self.transport.execute(req),
+        )
+        .await?;
+        check_json_content(&resp.headers, &resp.body)?;
+        decode_openai_compatible_models(&resp.body)
+    }
+
+    /// The request [`Self::list_openai_compatible_models`] sends, auth
+    /// headers included.
+    pub fn openai_compatible_models_request(&self, extra_headers: HeaderMap) -> Request {
+        let mut req = self.catalog_request("models");
+        req.headers.extend(extra_headers);
+        req.max_response_bytes = Some(self.max_response_bytes);
+        add_auth_headers(&self.auth, req)
+    }
+
+    /// List the deployments of an Azure OpenAI resource via
+    /// `GET deployments?api-version=...`, relative to the provider base URL
+    /// (typically `https://<resource>.openai.azure.com/openai`).
+    pub async fn list_azure_deployments(
+        &self,
+        extra_headers: HeaderMap,
+    ) -> Result<CatalogListing<AzureDeployment>, ApiError> {
+        let resp = run_with_request_telemetry(
+            self.provider.retry.to_policy(),
+            self.request_telemetry.clone(),
+            || self.azure_deployments_request(extra_headers.clone()),
+            |req| self.transport.execute(req),
+        )
+        .await?;
+        check_json_content(&resp.headers, &resp.body)?;
+        decode_azure_deployments(&resp.body)
+    }
+
+    /// The request [`Self::list_azure_deployments`] sends, including the
+    /// `api-key` header.
+    pub fn azure_deployments_request(&self, extra_headers: HeaderMap) -> Request {
+        let has_api_version = self
+            .provider
+            .query_params
+            .as_ref()
+            .is_some_and(|params| params.contains_key("api-version"));
+        let mut req = self.catalog_request("deployments");
+        req.headers.extend(extra_headers);
+        req.max_response_bytes = Some(self.max_response_bytes);
+
+        if !has_api_version {
+            let separator = if req.url.contains('?') { '&' } else { '?' };
+            req.url = format!(
+                "{}{separator}api-version={AZURE_DEPLOYMENTS_API_VERSION}",
+                req.url
+            );
+        }
+
+        add_azure_api_key_header(&self.auth, req)
+    }
+}
+
+/// Fail with [`ApiError::UnexpectedContent`] when a success response is
+/// plainly not JSON, rather than leave it to a decoder that can only say
+/// "expected value at line 1". That is an empty body, one that is or looks
+/// like HTML, or one labelled with another content type that does not start
+/// like JSON. A JSON body with a wrong or missing label passes, since some
+/// servers mislabel their responses.
+pub fn check_json_content(headers: &HeaderMap, body: &[u8]) -> Result<(), ApiError> {
+    let content_type = headers
+        .get(CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .map(str::trim)
+        .filter(|value| !value.is_empty());
+    let media_type = content_type
+        .and_then(|value| value.split(';').next())
+        .map(|value| value.trim().to_ascii_lowercase());
+    let body = body.strip_prefix(b"\xef\xbb\xbf").unwrap_or(body);
+    let start = body
+        .iter()
+        .position(|byte| !byte.is_ascii_whitespace())
+        .map_or(&[][..], |index| &body[index..]);
+
+    let labelled_html = media_type
+        .as_deref()
+        .is_some_and(|media_type| matches!(media_type, "text/html" | "application/xhtml+xml"));
+    let labelled_other = media_type.as_deref().is_some_and(|media_type| {
+        media_type != "application/json"
+            && media_type != "text/json"
+            && !media_type.ends_with("+json")
+    });
+    let unexpected = match start.first() {
+        None => true,
+        Some(b'<') => true,
+        Some(b'{' | b'[') => labelled_html,
+        Some(_) => labelled_other,
+    };
+    if !unexpected {
+        return Ok(());
+    }
+    Err(ApiError::UnexpectedContent {
+        content_type: content_type.map(str::to_string),
+        snippet: snippet(start),
+    })
+}
+
+/// The start of `body` on one line, cut to [`MAX_SNIPPET_CHARS`].
+fn snippet(body: &[u8]) -> String {
+    let head = &body[..body.len().min(MAX_SNIPPET_CHARS * 4)];
+    let text = String::from_utf8_lossy(head);
+    let words = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    match words.char_indices().nth(MAX_SNIPPET_CHARS) {
+        Some((index, _)) => format!("{}…", &words[..index]),
+        None => words,
+    }
+}
+
+/// `scheme://host[:port]` of `url`, without its path.
+fn origin(url: &str) -> &str {
+    let host_start = url.find("://").map_or(0, |scheme_end| scheme_end + 3);
+    match url[host_start..].find(['/', '?']) {
+        Some(path_start) => &url[..host_start + path_start],
+        None => url,
+    }
+}
+
+/// The listing in the body of a `/models` response, without its ETag.
+///
+/// Any bytes may come back from a gateway, so this never panics: a body that
+/// is not a catalog is an error, and an entry that is not a model is logged
+/// and skipped. Errors quote only the start of the body.
+pub fn decode_models_listing(body: &[u8]) -> Result<ModelsListing, ApiError> {
+    let RawModelsResponse {
+        models,
+        min_client_version,
+    } = serde_json::from_slice::<RawModelsResponse>(body)
+        .map_err(|e| decode_error("models response", &e, body))?;
+
+    Ok(ModelsListing {
+        models: disambiguate_preset_ids(models.into_iter().filter_map(decode_model).collect()),
+        etag: None,
+        min_client_version: min_client_version_str(min_client_version),
+    })
+}
+
+/// Model ids from the body of an OpenAI-compatible `GET /models` response.
+pub fn decode_openai_compatible_models(body: &[u8]) -> Result<CatalogListing<String>, ApiError> {
+    let OpenAiModelsResponse {
+        data,
+        min_client_version,
+    } = serde_json::from_slice::<OpenAiModelsResponse>(body)
+        .map_err(|e| decode_error("models response", &e, body))?;
+    Ok(CatalogListing {
+        entries: data.into_iter().map(|entry| entry.id).collect(),
+        min_client_version: min_client_version_str(min_client_version),
+    })
+}
+
+/// Deployments from the body of an Azure `GET deployments` response.
+pub fn decode_azure_deployments(body: &[u8]) -> Result<CatalogListing<AzureDeployment>, ApiError> {
+    let AzureDeploymentsResponse {
+        data,
+        min_client_version,
+    } = serde_json::from_slice::<AzureDeploymentsResponse>(body)
+        .map_err(|e| decode_error("deployments response", &e, body))?;
+    Ok(CatalogListing {
+        entries: data.into_iter().map(Into::into).collect(),
+        min_client_version: min_client_version_str(min_client_version),
+    })
+}
+
+/// A `min_client_version` envelope field, which is dropped unless it is a
+/// string.
+fn min_client_version_str(value: Option<serde_json::Value>) -> Option<String> {
+    value
+        .as_ref()
+        .and_then(serde_json::Value::as_str)
+        .map(str::to_string)
+}
+
+/// A decode failure that quotes only the start of the body, and of the
+/// decoder's message, which can repeat a value from the body in full.
+fn decode_error(what: &str, err: &serde_json::Error, body: &[u8]) -> ApiError {
+    ApiError::Stream(format!(
+        "failed to decode {what}: {}; body: {}",
+        snippet(err.to_string().as_bytes()),
+        snippet(body)
+    ))
+}
+
+/// What a `/models` catalog served.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ModelsListing {
+    pub models: Vec<ModelInfo>,
+    /// The `ETag` response header, for revalidating the listing later.
+    pub etag: Option<String>,
+    /// The oldest client version the catalog recommends, as it was served.
+    /// Anything but a string is dropped here; see
+    /// [`ModelsResponse::min_client_version`](codex_protocol::openai_models::ModelsResponse).
+    pub min_client_version: Option<String>,
+}
+
+/// What a provider catalog listed: model ids or deployments.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CatalogListing<T> {
+    pub entries: Vec<T>,
+    /// The oldest client version the catalog recommends, read from a
+    /// `min_client_version` field beside the listing, as in
+    /// [`ModelsListing::min_client_version`].
+    pub min_client_version: Option<String>,
+}
+
+/// A single Azure OpenAI deployment: the deployment name callers address and
+/// the underlying model it serves.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AzureDeployment {
+    pub id: String,
+    pub model: String,
+    /// Provisioned capacity (in capacity units), when reported.
+    pub capacity: Option<u64>,
+    /// Scale type or SKU name, e.g. `standard` or `GlobalStandard`.
+    pub scale_type: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct AzureDeploymentsResponse {
+    data: Vec<AzureDeploymentEntry>,
+    #[serde(default)]
+    min_client_version: Option<serde_json::Value>,
+}
+
+#[derive(Debug, Deserialize)]
+struct AzureDeploymentEntry {
+    id: String,
+    model: String,
+    #[serde(default)]
+    scale_settings: Option<AzureScaleSettings>,
+    #[serde(default)]
+    sku: Option<AzureSku>,
+}
+
+#[derive(Debug, Deserialize)]
+struct AzureScaleSettings {
+    #[serde(default)]
+    scale_type: Option<String>,
+    #[serde(default)]
+    capacity: Option<u64>,
+}
+
+#[derive(Debug, Deserialize)]
+struct AzureSku {
+    #[serde(default)]
+    name: Option<String>,
+    #[serde(default)]
+    capacity: Option<u64>,
+}
+
+impl From<AzureDeploymentEntry> for AzureDeployment {
+    fn from(entry: AzureDeploymentEntry) -> Self {
+        let (sku_name, sku_capacity) = entry
+            .sku
+            .map(|sku| (sku.name, sku.capacity))
+            .unwrap_or_default();
+        let (scale_type, scale_capacity) = entry
+            .scale_settings
+            .map(|settings| (settings.scale_type, settings.capacity))
+            .unwrap_or_default();
+        Self {
+            id: entry.id,
+            model: entry.model,
+            capacity: sku_capacity.or(scale_capacity),
+            scale_type: sku_name.or(scale_type),
+        }
+    }
+}
+
+/// `ModelsResponse` with each entry left undecoded, so that one malformed
+/// entry drops only that model instead of the whole catalog.
+#[derive(Debug, Deserialize)]
+struct RawModelsResponse {
+    models: Vec<serde_json::Value>,
+    /// Left undecoded too, so a malformed value is ignored rather than
+    /// failing the catalog.
+    #[serde(default)]
+    min_client_version: Option<serde_json::Value>,
+}
+
+/// Other names proxies use for [`ModelInfo`] fields, as `(name, field)`. When
+/// an entry carries several names for one field, the first non-null one in
+/// the order `field`, then this list, wins.
+const FIELD_ALIASES: [(&str, &str); 7] = [
+    ("contextWindow", "context_window"),
+    ("max_input_tokens", "context_window"),
+    ("maxInputTokens", "context_window"),
+    ("contextWindowUnit", "context_window_unit"),
+    ("displayName", "display_name"),
+    ("model_name", "display_name"),
+    ("modelName", "display_name"),
+];
+
+fn decode_model(mut entry: serde_json::Value) -> Option<ModelInfo> {
+    lift_litellm_identity(&mut entry);
+    let slug = entry
+        .get("slug")
+        .and_then(serde_json::Value::as_str)
+        .unwrap_or("<unknown>")
+        .to_string();
+    let context_window_field = normalize_model_entry(&mut entry, &slug);
+    let mut model: ModelInfo = serde_json::from_value(entry)
+        .inspect_err(|err| {
+            warn!(
+                "skipping model `{}` from models response: {}",
+                snippet(slug.as_bytes()),
+                snippet(err.to_string().as_bytes())
+            );
+        })
+        .ok()?;
+    apply_context_window_unit(&mut model, context_window_field);
+    Some(model)
+}
+
+/// Turn the served `context_window` into tokens and record how it was read.
+/// A value in kilotokens is multiplied out. One below
+/// [`MIN_UNHINTED_CONTEXT_WINDOW`] with no unit is more likely kilotokens
+/// than a real window, so it is ignored with a warning rather than trusted.
+fn apply_context_window_unit(model: &mut ModelInfo, field: Option<String>) {
+    let unit = model.context_window_unit.take();
+    let Some(value) = model.context_window else {
+        return;
+    };
+    let tokens = match unit {
+        Some(ContextWindowUnit::Kilotokens) => value.checked_mul(1_000),
+        Some(ContextWindowUnit::Tokens) => Some(value),
+        None => (value >= MIN_UNHINTED_CONTEXT_WINDOW).then_some(value),
+    };
+    let field = field.unwrap_or_else(|| "context_window".to_string());
+    if tokens.is_none() {
+        warn!(
+            "ignoring context window {value} of model `{}` from models response: {}",
+            snippet(model.slug.as_bytes()),
+            match unit {
+                Some(unit) => format!("too large in {unit}"),
+                None => format!(
+                    "below {MIN_UNHINTED_CONTEXT_WINDOW} tokens with no `context_window_unit`"
+                ),
+            }
+        );
+    }
+    model.context_window = tokens;
+    model.context_window_source = Some(ContextWindowSource {
+        field,
+        value,
+        unit,
+        ignored: tokens.is_none(),
+    });
+}
+
+/// Give a LiteLLM entry the identity [`ModelInfo`] expects. The slug
+/// requests use defaults to `litellm_params.model`. Several entries may route
+/// to that one model under different `model_name`s, so an entry whose
+/// `model_name`, slugified, differs from its slug gets that as its preset id.
+fn lift_litellm_identity(entry: &mut serde_json::Value) {
+    let Some(fields) = entry.as_object_mut() else {
+        return;
+    };
+    if !fields.contains_key("slug")
+        && let Some(model) = fields
+            .get("litellm_params")
+            .and_then(|params| params.get("model"))
+            .filter(|model| model.is_string())
+            .cloned()
+    {
+        fields.insert("slug".to_string(), model);
+    }
+    let Some(slug) = fields.get("slug").and_then(serde_json::Value::as_str) else {
+        return;
+    };
+    if fields.contains_key("preset_id") {
+        return;
+    }
+    let preset_id = fields
+        .get("model_name")
+        .and_then(serde_json::Value::as_str)
+        .map(slugify)
+        .filter(|id| !id.is_empty() && id != slug);
+    if let Some(preset_id) = preset_id {
+        fields.insert("preset_id".to_string(), preset_id.into());
+    }
+}
+
+/// `name` lowercased, with each run of characters other than ASCII letters,
+/// digits, `.`, `_`, and `/` replaced by one `-`.
+fn slugify(name: &str) -> String {
+    let mut slug = String::new();
+    for c in name.chars() {
+        if c.is_ascii_alphanumeric() || matches!(c, '.' | '_' | '/') {
+            slug.push(c.to_ascii_lowercase());
+        } else if !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
+    slug.trim_matches('-').to_string()
+}
+
+/// Make the preset ids of `models` unique. An entry that matches an earlier
+/// one in id, slug, and display name is a duplicate and is dropped. Entries
+/// that still share an id all get `-` and the first 8 hex digits of the
+/// SHA-256 of their slug and display name appended, which keeps each id the
+/// same across fetches whatever order the catalog lists them in.
+fn disambiguate_preset_ids(models: Vec<ModelInfo>) -> Vec<ModelInfo> {
+    fn preset_id(model: &ModelInfo) -> &str {
+        model.preset_id.as_deref().unwrap_or(&model.slug)
+    }
 
-        Ok((models, header_etag))
+    let mut seen = HashSet::new();
+    let models: Vec<ModelInfo> = models
+        .into_iter()
+        .filter(|model| {
+            let key = (
+                preset_id(model).to_string(),
+                model.slug.clone(),
+                model.display_name.clone(),
+            );
+            let first = seen.insert(key);
+            if !first {
+                warn!(
+                    "skipping duplicate model `{}` from models response",
+                    model.slug
+                );
+            }
+            first
+        })
+        .collect();
+
+    let mut counts: HashMap<String, usize> = HashMap::new();
+    for model in &models {
+        *counts.entry(preset_id(model).to_string()).or_default() += 1;
+    }
+    models
+        .into_iter()
+        .map(|mut model| {
+            let id = preset_id(&model).to_string();
+            if counts.get(&id).is_some_and(|count| *count > 1) {
+                let digest = Sha256::digest(format!("{}\n{}", model.slug, model.display_name));
+                let hash: String = digest
+                    .iter()
+                    .take(4)
+                    .map(|byte| format!("{byte:02x}"))
+                    .collect();
+                model.preset_id = Some(format!("{id}-{hash}"));
+            }
+            model
+        })
+        .collect()
+}
+
+/// Rewrite a model entry as served by a LiteLLM-style proxy into the shape
+/// [`ModelInfo`] expects: fields nested under `model_info` are lifted to the
+/// top level, unless the top level already has them, and aliased field names
+/// are renamed. Returns the name `context_window` was served under, such as
+/// `model_info.max_input_tokens`, when it was served at all.
+fn normalize_model_entry(entry: &mut serde_json::Value, slug: &str) -> Option<String> {
+    let fields = entry.as_object_mut()?;
+    let mut shape = Vec::new();
+    let mut lifted = HashSet::new();
+    if let Some(serde_json::Value::Object(nested)) = fields.remove("model_info") {
+        for (name, value) in nested {
+            if !fields.contains_key(&name) {
+                lifted.insert(name.clone());
+                fields.insert(name, value);
+            }
+        }
+        shape.push("nested `model_info`".to_string());
+    }
+    let mut context_window_field = fields
+        .get("context_window")
+        .is_some_and(|value| !value.is_null())
+        .then(|| "context_window".to_string());
+    for (alias, field) in FIELD_ALIASES {
+        let Some(value) = fields.remove(alias) else {
+            continue;
+        };
+        shape.push(format!("`{alias}` for `{field}`"));
+        if fields.get(field).is_none_or(serde_json::Value::is_null) {
+            if field == "context_window" && !value.is_null() {
+                context_window_field = Some(alias.to_string());
+            }
+            fields.insert(field.to_string(), value);
+        }
     }
+    if !shape.is_empty() {
+        debug!(
+            "model `{slug}` from models response uses {}",
+            shape.join(", ")
+        );
+    }
+    context_window_field.map(|name| {
+        if lifted.contains(&name) {
+            format!("model_info.{name}")
+        } else {
+            name
+        }
+    })
+}
+
+#[derive(Debug, Deserialize)]
+struct OpenAiModelsResponse {
+    data: Vec<OpenAiModelEntry>,
+    #[serde(default)]
+    min_client_version: Option<serde_json::Value>,
+}
+
+#[derive(Debug, Deserialize)]
+struct OpenAiModelEntry {
+    id: String,
 }
 
 #[cfg(test)]
@@ -89,6 +669,8 @@ mod tests {
     use codex_client::Response;
     use codex_client::StreamResponse;
     use codex_client::TransportError;
+    use codex_protocol::openai_models::ModelPreset;
+    use codex_protocol::openai_models::ModelsResponse;
     use http::HeaderMap;
     use http::StatusCode;
     use pretty_assertions::assert_eq;
@@ -108,7 +690,7 @@ mod tests {
         fn default() -> Self {
             Self {
                 last_request: Arc::new(Mutex::new(None)),
-                body: Arc::new(ModelsResponse { models: Vec::new() }),
+                body: Arc::new(ModelsResponse::default()),
                 etag: None,
             }
         }
@@ -144,6 +726,24 @@ mod tests {
         }
     }
 
+    #[derive(Clone)]
+    struct KeyAuth;
+
+    impl AuthProvider for KeyAuth {
+        fn bearer_token(&self) -> Option<String> {
+            Some("azure-key".to_string())
+        }
+    }
+
+    #[derive(Clone)]
+    struct TokenAuth(&'static str);
+
+    impl AuthProvider for TokenAuth {
+        fn bearer_token(&self) -> Option<String> {
+            Some(self.0.to_string())
+        }
+    }
+
     fn provider(base_url: &str) -> Provider {
         Provider {
             name: "test".to_string(),
@@ -162,9 +762,44 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn authorization_is_sent_only_for_non_empty_tokens() {
+        for (token, expected) in [
+            ("", None),
+            ("  ", None),
+            ("catalog-token", Some("Bearer catalog-token")),
+        ] {
+            let transport = CapturingTransport {
+                last_request: Arc::new(Mutex::new(None)),
+                body: Arc::new(ModelsResponse::default()),
+                etag: None,
+            };
+            let client = ModelsClient::new(
+                transport.clone(),
+                provider("https://example.com/api/codex"),
+                TokenAuth(token),
+            );
+
+            client
+                .list_models("0.99.0", HeaderMap::new())
+                .await
+                .expect("request should succeed");
+
+            let request = transport.last_request.lock().unwrap().clone().unwrap();
+            assert_eq!(
+                request
+                    .headers
+                    .get(http::header::AUTHORIZATION)
+                    .and_then(|value| value.to_str().ok()),
+                expected,
+                "token {token:?}"
+            );
+        }
+    }
+
     #[tokio::test]
     async fn appends_client_version_query() {
-        let response = ModelsResponse { models: Vec::new() };
+        let response = ModelsResponse::default();
 
         let transport = CapturingTransport {
             last_request: Arc::new(Mutex::new(None)),
@@ -178,7 +813,7 @@ mod tests {
             DummyAuth,
         );
 
-        let (models, _) = client
+        let ModelsListing { models, .. } = client
             .list_models("0.99.0", HeaderMap::new())
             .await
             .expect("request should succeed");
@@ -227,6 +862,7 @@ mod tests {
                 }))
                 .unwrap(),
             ],
+            min_client_version: None,
         };
 
         let transport = CapturingTransport {
@@ -241,7 +877,7 @@ mod tests {
             DummyAuth,
         );
 
-        let (models, _) = client
+        let ModelsListing { models, .. } = client
             .list_models("0.99.0", HeaderMap::new())
             .await
             .expect("request should succeed");
@@ -254,7 +890,7 @@ mod tests {
 
     #[tokio::test]
     async fn list_models_includes_etag() {
-        let response = ModelsResponse { models: Vec::new() };
+        let response = ModelsResponse::default();
 
         let transport = CapturingTransport {
             last_request: Arc::new(Mutex::new(None)),
@@ -268,7 +904,7 @@ mod tests {
             DummyAuth,
         );
 
-        let (models, etag) = client
+        let ModelsListing { models, etag, .. } = client
             .list_models("0.1.0", HeaderMap::new())
             .await
             .expect("request should succeed");
@@ -276,4 +912,671 @@ mod tests {
         assert_eq!(models.len(), 0);
         assert_eq!(etag, Some("\"abc\"".to_string()));
     }
+
+    #[tokio::test]
+    async fn list_models_passes_on_the_minimum_client_version() {
+        let client_for = |min_client_version: serde_json::Value| {
+            ModelsClient::new(
+                JsonTransport {
+                    last_request: Arc::new(Mutex::new(None)),
+                    body: json!({"models": [], "min_client_version": min_client_version}),
+                },
+                provider("https://example.com/api/codex"),
+                DummyAuth,
+            )
+        };
+
+        let listing = client_for(json!("0.60.0"))
+            .list_models("0.1.0", HeaderMap::new())
+            .await
+            .expect("request should succeed");
+        assert_eq!(listing.min_client_version.as_deref(), Some("0.60.0"));
+
+        // A malformed value is dropped without failing the catalog.
+        let listing = client_for(json!([0, 60, 0]))
+            .list_models("0.1.0", HeaderMap::new())
+            .await
+            .expect("request should succeed");
+        assert_eq!(listing.min_client_version, None);
+    }
+
+    #[tokio::test]
+    async fn provider_listings_pass_on_the_minimum_client_version() {
+        let client_for = |body: serde_json::Value| {
+            ModelsClient::new(
+                JsonTransport {
+                    last_request: Arc::new(Mutex::new(None)),
+                    body,
+                },
+                provider("https://gateway.example.com/v1"),
+                DummyAuth,
+            )
+        };
+
+        let listing = client_for(json!({
+            "object": "list",
+            "data": [{"id": "oca-large", "object": "model"}],
+            "min_client_version": "0.60.0",
+        }))
+        .list_openai_compatible_models(HeaderMap::new())
+        .await
+        .expect("request should succeed");
+        assert_eq!(
+            listing,
+            CatalogListing {
+                entries: vec!["oca-large".to_string()],
+                min_client_version: Some("0.60.0".to_string()),
+            }
+        );
+
+        let listing = client_for(json!({"data": [], "min_client_version": 60}))
+            .list_azure_deployments(HeaderMap::new())
+            .await
+            .expect("request should succeed");
+        assert_eq!(listing.min_client_version, None);
+    }
+
+    #[derive(Clone)]
+    struct JsonTransport {
+        last_request: Arc<Mutex<Option<Request>>>,
+        body: serde_json::Value,
+    }
+
+    #[async_trait]
+    impl HttpTransport for JsonTransport {
+        async fn execute(&self, req: Request) -> Result<Response, TransportError> {
+            *self.last_request.lock().unwrap() = Some(req);
+            Ok(Response {
+                status: StatusCode::OK,
+                headers: HeaderMap::new(),
+                body: serde_json::to_vec(&self.body).unwrap().into(),
+            })
+        }
+
+        async fn stream(&self, _req: Request) -> Result<StreamResponse, TransportError> {
+            Err(TransportError::Build("stream should not run".to_string()))
+        }
+    }
+
+    fn litellm_model(slug: &str, context_window: serde_json::Value) -> serde_json::Value {
+        json!({
+            "slug": slug,
+            "display_name": slug,
+            "description": null,
+            "default_reasoning_level": "medium",
+            "supported_reasoning_levels": [{"effort": "medium", "description": "medium"}],
+            "shell_type": "shell_command",
+            "visibility": "list",
+            "supported_in_api": true,
+            "priority": 1,
+            "upgrade": null,
+            "base_instructions": null,
+            "supports_reasoning_summaries": false,
+            "support_verbosity": false,
+            "default_verbosity": null,
+            "apply_patch_tool_type": null,
+            "truncation_policy": {"mode": "bytes", "limit": 10_000},
+            "supports_parallel_tool_calls": false,
+            "context_window": context_window,
+            "max_output_tokens": 32000.0,
+            "experimental_supported_tools": [],
+        })
+    }
+
+    /// A LiteLLM entry routed under `model_name` to `underlying`.
+    fn routed_entry(model_name: &str, underlying: &str) -> serde_json::Value {
+        let mut entry = litellm_model(underlying, json!(128000));
+        let fields = entry.as_object_mut().expect("entry is an object");
+        fields.remove("slug");
+        fields.remove("display_name");
+        fields.insert("model_name".to_string(), json!(model_name));
+        fields.insert("litellm_params".to_string(), json!({"model": underlying}));
+        entry
+    }
+
+    fn preset_ids(entries: Vec<serde_json::Value>) -> Vec<(String, String, String)> {
+        disambiguate_preset_ids(entries.into_iter().filter_map(decode_model).collect())
+            .into_iter()
+            .map(ModelPreset::from)
+            .map(|preset| (preset.id, preset.model, preset.display_name))
+            .collect()
+    }
+
+    #[test]
+    fn entries_sharing_an_underlying_model_get_ids_from_their_names() {
+        let ids = preset_ids(vec![
+            routed_entry("OCA Large (EU)", "azure/gpt-5"),
+            routed_entry("oca-large-us", "azure/gpt-5"),
+            routed_entry("azure/gpt-5", "azure/gpt-5"),
+        ]);
+
+        assert_eq!(
+            ids,
+            vec![
+                (
+                    "oca-large-eu".to_string(),
+                    "azure/gpt-5".to_string(),
+                    "OCA Large (EU)".to_string()
+                ),
+                (
+                    "oca-large-us".to_string(),
+                    "azure/gpt-5".to_string(),
+                    "oca-large-us".to_string()
+                ),
+                (
+                    "azure/gpt-5".to_string(),
+                    "azure/gpt-5".to_string(),
+                    "azure/gpt-5".to_string()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn colliding_ids_get_a_hash_that_is_stable_across_fetches() {
+        let first = preset_ids(vec![
+            routed_entry("OCA Large", "azure/gpt-5"),
+            routed_entry("oca large", "openai/gpt-5"),
+            routed_entry("OCA Small", "azure/gpt-5-mini"),
+        ]);
+        let reordered = preset_ids(vec![
+            routed_entry("OCA Small", "azure/gpt-5-mini"),
+            routed_entry("oca large", "openai/gpt-5"),
+            routed_entry("OCA Large", "azure/gpt-5"),
+        ]);
+
+        let ids: Vec<&str> = first.iter().map(|(id, _, _)| id.as_str()).collect();
+        assert_eq!(ids.len(), 3);
+        assert!(ids[0].starts_with("oca-large-") && ids[0].len() == "oca-large-".len() + 8);
+        assert!(ids[1].starts_with("oca-large-") && ids[1].len() == "oca-large-".len() + 8);
+        assert_ne!(ids[0], ids[1]);
+        assert_eq!(ids[2], "oca-small");
+
+        let mut sorted_first = first.clone();
+        sorted_first.sort();
+        let mut sorted_reordered = reordered;
+        sorted_reordered.sort();
+        assert_eq!(sorted_first, sorted_reordered);
+    }
+
+    #[test]
+    fn exact_duplicate_entries_are_listed_once() {
+        let ids = preset_ids(vec![
+            routed_entry("OCA Large", "azure/gpt-5"),
+            routed_entry("OCA Large", "azure/gpt-5"),
+        ]);
+
+        assert_eq!(
+            ids,
+            vec![(
+                "oca-large".to_string(),
+                "azure/gpt-5".to_string(),
+                "OCA Large".to_string()
+            )]
+        );
+    }
+
+    #[tokio::test]
+    async fn lenient_token_counts_and_malformed_entries_are_skipped() {
+        let transport = JsonTransport {
+            last_request: Arc::new(Mutex::new(None)),
+            body: json!({
+                "models": [
+                    litellm_model("as-string", json!("128000")),
+                    litellm_model("not-a-number", json!("lots")),
+                    litellm_model("as-float", json!(200000.0)),
+                    litellm_model("zero", json!(0)),
+                ],
+            }),
+        };
+
+        let client = ModelsClient::new(
+            transport,
+            provider("https://example.com/api/codex"),
+            DummyAuth,
+        );
+
+        let ModelsListing { models, .. } = client
+            .list_models("0.99.0", HeaderMap::new())
+            .await
+            .expect("request should succeed");
+
+        assert_eq!(
+            models
+                .iter()
+                .map(|model| (
+                    model.slug.as_str(),
+                    model.context_window,
+                    model.max_output_tokens
+                ))
+                .collect::<Vec<_>>(),
+            vec![
+                ("as-string", Some(128_000), Some(32_000)),
+                ("as-float", Some(200_000), Some(32_000)),
+                ("zero", None, Some(32_000)),
+            ]
+        );
+    }
+
+    /// The same model as two LiteLLM proxy versions serve it.
+    fn proxy_v1_entry() -> serde_json::Value {
+        let mut entry = litellm_model("oca-large", json!(128000));
+        entry["display_name"] = json!("OCA Large");
+        entry
+    }
+
+    fn proxy_v2_entry() -> serde_json::Value {
+        let mut entry = litellm_model("oca-large", json!(null));
+        let fields = entry.as_object_mut().expect("entry is an object");
+        fields.remove("display_name");
+        fields.remove("context_window");
+        fields.insert("model_name".to_string(), json!("OCA Large"));
+        fields.insert(
+            "model_info".to_string(),
+            json!({"max_input_tokens": "128000", "max_output_tokens": 32000}),
+        );
+        fields.remove("max_output_tokens");
+        entry
+    }
+
+    #[test]
+    fn both_proxy_versions_decode_to_the_same_preset() {
+        let v1 = decode_model(proxy_v1_entry()).expect("v1 entry decodes");
+        let v2 = decode_model(proxy_v2_entry()).expect("v2 entry decodes");
+
+        assert_eq!(v2.display_name, "OCA Large");
+        assert_eq!(v2.context_window, Some(128_000));
+        assert_eq!(v2.max_output_tokens, Some(32_000));
+        let (mut v1, mut v2) = (ModelPreset::from(v1), ModelPreset::from(v2));
+        let field = |preset: &mut ModelPreset| {
+            preset
+                .context_window_source
+                .take()
+                .map(|source| source.field)
+        };
+        assert_eq!(field(&mut v1).as_deref(), Some("context_window"));
+        assert_eq!(
+            field(&mut v2).as_deref(),
+            Some("model_info.max_input_tokens")
+        );
+        assert_eq!(v2, v1);
+    }
+
+    #[test]
+    fn context_windows_without_a_unit_are_tokens() {
+        let model = decode_model(proxy_v1_entry()).expect("entry decodes");
+
+        assert_eq!(model.context_window, Some(128_000));
+        assert_eq!(
+            model.context_window_source,
+            Some(ContextWindowSource {
+                field: "context_window".to_string(),
+                value: 128_000,
+                unit: None,
+                ignored: false,
+            })
+        );
+    }
+
+    #[test]
+    fn context_windows_in_kilotokens_are_multiplied_out() {
+        let mut entry = litellm_model("oca-large", json!(null));
+        entry["maxInputTokens"] = json!(128);
+        entry["contextWindowUnit"] = json!("kilotokens");
+        let model = decode_model(entry).expect("entry decodes");
+
+        assert_eq!(model.context_window, Some(128_000));
+        assert_eq!(model.context_window_unit, None, "the unit is applied once");
+        assert_eq!(
+            model.context_window_source,
+            Some(ContextWindowSource {
+                field: "maxInputTokens".to_string(),
+                value: 128,
+                unit: Some(ContextWindowUnit::Kilotokens),
+                ignored: false,
+            })
+        );
+
+        let mut entry = litellm_model("oca-large", json!(i64::MAX / 10));
+        entry["context_window_unit"] = json!("kilotokens");
+        let model = decode_model(entry).expect("entry decodes");
+        assert_eq!(model.context_window, None, "too large in tokens");
+    }
+
+    #[test]
+    fn small_context_windows_without_a_unit_are_ignored() {
+        let model = decode_model(litellm_model("oca-large", json!(128))).expect("entry decodes");
+
+        assert_eq!(model.context_window, None);
+        assert_eq!(
+            model.context_window_source,
+            Some(ContextWindowSource {
+                field: "context_window".to_string(),
+                value: 128,
+                unit: None,
+                ignored: true,
+            })
+        );
+
+        let mut entry = litellm_model("tiny", json!(512));
+        entry["context_window_unit"] = json!("tokens");
+        let model = decode_model(entry).expect("entry decodes");
+        assert_eq!(model.context_window, Some(512), "a named unit is trusted");
+    }
+
+    #[test]
+    fn codex_field_names_win_over_aliases_unless_null() {
+        let mut entry = proxy_v1_entry();
+        entry["model_name"] = json!("oca-large-group");
+        entry["max_input_tokens"] = json!(64000);
+        entry["modelName"] = json!("ignored");
+        let model = decode_model(entry).expect("entry decodes");
+        assert_eq!(model.display_name, "OCA Large");
+        assert_eq!(model.context_window, Some(128_000));
+
+        let mut entry = proxy_v1_entry();
+        entry["context_window"] = json!(null);
+        entry["contextWindow"] = json!(96000);
+        entry["max_input_tokens"] = json!(64000);
+        let model = decode_model(entry).expect("entry decodes");
+        assert_eq!(model.context_window, Some(96_000));
+    }
+
+    #[test]
+    fn model_info_accepts_alias_names_directly() {
+        let mut entry = proxy_v1_entry();
+        let fields = entry.as_object_mut().expect("entry is an object");
+        let display_name = fields.remove("display_name").expect("display_name");
+        let context_window = fields.remove("context_window").expect("context_window");
+        fields.insert("model_name".to_string(), display_name);
+        fields.insert("max_input_tokens".to_string(), context_window);
+
+        let model: ModelInfo = serde_json::from_value(entry).expect("aliases deserialize");
+        assert_eq!(model.display_name, "OCA Large");
+        assert_eq!(model.context_window, Some(128_000));
+    }
+
+    fn content_type(value: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            CONTENT_TYPE,
+            value.parse().expect("valid content type header"),
+        );
+        headers
+    }
+
+    #[test]
+    fn json_bodies_pass_the_content_check_whatever_their_label() {
+        for (headers, body) in [
+            (content_type("application/json"), "{\"data\": []}"),
+            (content_type("application/vnd.api+json"), "{}"),
+            (content_type("text/plain"), "\u{feff}  [1, 2]"),
+            (HeaderMap::new(), "{\"models\": []}"),
+            (content_type("application/json"), "null"),
+        ] {
+            assert_eq!(check_json_content(&headers, body.as_bytes()).ok(), Some(()));
+        }
+    }
+
+    #[test]
+    fn content_check_quotes_the_start_of_the_body_on_one_line() {
+        let body = format!("<html>\n  <body>\n{}</body></html>", "x".repeat(200));
+        let err =
+            check_json_content(&HeaderMap::new(), body.as_bytes()).expect_err("HTML is not JSON");
+        let (content_type, snippet) = match err {
+            ApiError::UnexpectedContent {
+                content_type,
+                snippet,
+            } => (content_type, snippet),
+            other => panic!("expected UnexpectedContent, got {other:?}"),
+        };
+        assert_eq!(content_type, None);
+        assert_eq!(
+            snippet,
+            format!("<html> <body> {}…", "x".repeat(MAX_SNIPPET_CHARS - 14))
+        );
+    }
+
+    #[tokio::test]
+    async fn parses_openai_compatible_models_response() {
+        let transport = JsonTransport {
+            last_request: Arc::new(Mutex::new(None)),
+            body: json!({
+                "object": "list",
+                "data": [
+                    {"id": "qwen2.5-coder-7b", "object": "model", "owned_by": "organization_owner"},
+                    {"id": "openai/gpt-oss-20b", "object": "model"},
+                ],
+            }),
+        };
+
+        let client = ModelsClient::new(
+            transport.clone(),
+            provider("http://localhost:1234/v1"),
+            DummyAuth,
+        );
+
+        let models = client
+            .list_openai_compatible_models(HeaderMap::new())
+            .await
+            .expect("request should succeed");
+
+        assert_eq!(
+            models,
+            vec![
+                "qwen2.5-coder-7b".to_string(),
+                "openai/gpt-oss-20b".to_string()
+            ]
+        );
+        let url = transport
+            .last_request
+            .lock()
+            .unwrap()
+            .as_ref()
+            .unwrap()
+            .url
+            .clone();
+        assert_eq!(url, "http://localhost:1234/v1/models");
+    }
+
+    #[tokio::test]
+    async fn catalog_path_override_replaces_default_path() {
+        let transport = JsonTransport {
+            last_request: Arc::new(Mutex::new(None)),
+            body: json!({"object": "list", "data": [{"id": "oca-large"}]}),
+        };
+
+        let client = ModelsClient::new(
+            transport.clone(),
+            provider("https://gateway.example.com/v1/"),
+            DummyAuth,
+        )
+        .with_path(Some("catalog/models".to_string()));
+
+        let models = client
+            .list_openai_compatible_models(HeaderMap::new())
+            .await
+            .expect("request should succeed");
+
+        assert_eq!(models, vec!["oca-large".to_string()]);
+        let url = transport
+            .last_request
+            .lock()
+            .unwrap()
+            .as_ref()
+            .unwrap()
+            .url
+            .clone();
+        assert_eq!(url, "https://gateway.example.com/v1/catalog/models");
+    }
+
+    #[tokio::test]
+    async fn catalog_path_from_the_host_root_leaves_the_base_path() {
+        let transport = JsonTransport {
+            last_request: Arc::new(Mutex::new(None)),
+            body: json!({"models": []}),
+        };
+
+        let client = ModelsClient::new(
+            transport.clone(),
+            provider("https://gateway.example.com:8443/v1/"),
+            DummyAuth,
+        )
+        .with_path(Some("/ai/litellm/v1/model/info".to_string()));
+
+        client
+            .list_models("0.99.0", HeaderMap::new())
+            .await
+            .expect("request should succeed");
+
+        let url = transport
+            .last_request
+            .lock()
+            .unwrap()
+            .as_ref()
+            .unwrap()
+            .url
+            .clone();
+        assert_eq!(
+            url,
+            "https://gateway.example.com:8443/ai/litellm/v1/model/info?client_version=0.99.0"
+        );
+    }
+
+    #[tokio::test]
+    async fn catalog_url_override_is_requested_as_is() {
+        let transport = JsonTransport {
+            last_request: Arc::new(Mutex::new(None)),
+            body: json!({"models": []}),
+        };
+
+        let client = ModelsClient::new(
+            transport.clone(),
+            provider("https://gateway.example.com/v1"),
+            DummyAuth,
+        )
+        .with_path(Some(
+            "https://gateway.example.com/ai/litellm/v1/model/info".to_string(),
+        ));
+
+        client
+            .list_models("0.99.0", HeaderMap::new())
+            .await
+            .expect("request should succeed");
+
+        let url = transport
+            .last_request
+            .lock()
+            .unwrap()
+            .as_ref()
+            .unwrap()
+            .url
+            .clone();
+        assert_eq!(
+            url,
+            "https://gateway.example.com/ai/litellm/v1/model/info?client_version=0.99.0"
+        );
+    }
+
+    #[tokio::test]
+    async fn lists_azure_deployments_with_api_key_header() {
+        let transport = JsonTransport {
+            last_request: Arc::new(Mutex::new(None)),
+            body: json!({
+                "object": "list",
+                "data": [
+                    {
+                        "id": "gpt4o-prod",
+                        "model": "gpt-4o",
+                        "status": "succeeded",
+                        "scale_settings": {"scale_type": "standard"},
+                        "sku": {"name": "GlobalStandard", "capacity": 50},
+                    },
+                    {
+                        "id": "codex-eval",
+                        "model": "gpt-5-codex",
+                        "scale_settings": {"scale_type": "manual", "capacity": 10},
+                    },
+                ],
+            }),
+        };
+
+        let client = ModelsClient::new(
+            transport.clone(),
+            provider("https://example.openai.azure.com/openai"),
+            KeyAuth,
+        );
+
+        let deployments = client
+            .list_azure_deployments(HeaderMap::new())
+            .await
+            .expect("request should succeed");
+
+        assert_eq!(
+            deployments.entries,
+            vec![
+                AzureDeployment {
+                    id: "gpt4o-prod".to_string(),
+                    model: "gpt-4o".to_string(),
+                    capacity: Some(50),
+                    scale_type: Some("GlobalStandard".to_string()),
+                },
+                AzureDeployment {
+                    id: "codex-eval".to_string(),
+                    model: "gpt-5-codex".to_string(),
+                    capacity: Some(10),
+                    scale_type: Some("manual".to_string()),
+                },
+            ]
+        );
+        let request = transport.last_request.lock().unwrap().clone().unwrap();
+        assert_eq!(
+            request.url,
+            format!(
+                "https://example.openai.azure.com/openai/deployments?api-version={AZURE_DEPLOYMENTS_API_VERSION}"
+            )
+        );
+        assert_eq!(
+            request
+                .headers
+                .get("api-key")
+                .and_then(|value| value.to_str().ok()),
+            Some("azure-key")
+        );
+        assert_eq!(request.headers.get(http::header::AUTHORIZATION), None);
+    }
+
+    #[tokio::test]
+    async fn azure_deployments_respect_configured_api_version() {
+        let transport = JsonTransport {
+            last_request: Arc::new(Mutex::new(None)),
+            body: json!({"data": []}),
+        };
+        let mut azure = provider("https://example.openai.azure.com/openai");
+        azure.query_params = Some(
+            [("api-version".to_string(), "2025-04-01-preview".to_string())]
+                .into_iter()
+                .collect(),
+        );
+
+        let client = ModelsClient::new(transport.clone(), azure, DummyAuth);
+        let deployments = client
+            .list_azure_deployments(HeaderMap::new())
+            .await
+            .expect("request should succeed");
+
+        assert_eq!(deployments.entries, Vec::new());
+        let url = transport
+            .last_request
+            .lock()
+            .unwrap()
+            .as_ref()
+            .unwrap()
+            .url
+            .clone();
+        assert_eq!(
+            url,
+            "https://example.openai.azure.com/openai/deployments?api-version=2025-04-01-preview"
+        );
+    }
 }
@@ -2,6 +2,7 @@ use crate::auth::AuthProvider;
 use crate::common::Prompt as ApiPrompt;
 use crate::common::Reasoning;
 use crate::common::ResponseStream;
+use crate::common::Sampling;
 use crate::common::TextControls;
 use crate::endpoint::streaming::StreamingClient;
 use crate::error::ApiError;
@@ -29,6 +30,8 @@ pub struct ResponsesOptions {
     pub include: Vec<String>,
     pub prompt_cache_key: Option<String>,
     pub text: Option<TextControls>,
+    pub max_output_tokens: Option<i64>,
+    pub sampling: Sampling,
     pub store_override: Option<bool>,
     pub conversation_id: Option<String>,
     pub session_source: Option<SessionSource>,
@@ -71,6 +74,8 @@ impl<T: HttpTransport, A: AuthProvider> ResponsesClient<T, A> {
             include,
             prompt_cache_key,
             text,
+            max_output_tokens,
+            sampling,
             store_override,
             conversation_id,
             session_source,
@@ -84,6 +89,8 @@ impl<T: HttpTransport, A: AuthProvider> ResponsesClient<T, A> {
             .include(include)
             .prompt_cache_key(prompt_cache_key)
             .text(text)
+            .max_output_tokens(max_output_tokens)
+            .sampling(sampling)
             .conversation(conversation_id)
             .session_source(session_source)
             .store_override(store_override)
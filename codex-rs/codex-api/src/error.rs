@@ -12,6 +12,9 @@ pub enum ApiError {
     Api { status: StatusCode, message: String },
     #[error("stream error: {0}")]
     Stream(String),
+    /// The stream went longer than its idle timeout without an event.
+    #[error("idle timeout waiting for SSE after {0:?}")]
+    IdleTimeout(Duration),
     #[error("context window exceeded")]
     ContextWindowExceeded,
     #[error("quota exceeded")]
@@ -25,6 +28,25 @@ pub enum ApiError {
     },
     #[error("rate limit: {0}")]
     RateLimit(String),
+    /// A success response that is plainly not JSON, such as the login page
+    /// a captive portal or SSO proxy serves in place of the API.
+    #[error("expected a JSON response but got {}", describe_content(.content_type.as_deref(), .snippet))]
+    UnexpectedContent {
+        content_type: Option<String>,
+        /// The start of the body, on one line.
+        snippet: String,
+    },
+}
+
+/// What a response with `content_type` whose body starts with `snippet`
+/// held, for error messages.
+pub fn describe_content(content_type: Option<&str>, snippet: &str) -> String {
+    match (content_type, snippet.is_empty()) {
+        (Some(content_type), true) => format!("an empty `{content_type}` body"),
+        (None, true) => "an empty body".to_string(),
+        (Some(content_type), false) => format!("`{content_type}` starting `{snippet}`"),
+        (None, false) => format!("a body without a content type starting `{snippet}`"),
+    }
 }
 
 impl From<RateLimitError> for ApiError {
@@ -18,14 +18,24 @@ pub use crate::common::Prompt;
 pub use crate::common::ResponseEvent;
 pub use crate::common::ResponseStream;
 pub use crate::common::ResponsesApiRequest;
+pub use crate::common::Sampling;
 pub use crate::common::create_text_param_for_request;
 pub use crate::endpoint::chat::AggregateStreamExt;
 pub use crate::endpoint::chat::ChatClient;
 pub use crate::endpoint::compact::CompactClient;
+pub use crate::endpoint::models::AzureDeployment;
+pub use crate::endpoint::models::CatalogListing;
+pub use crate::endpoint::models::DEFAULT_MAX_MODELS_RESPONSE_BYTES;
 pub use crate::endpoint::models::ModelsClient;
+pub use crate::endpoint::models::ModelsListing;
+pub use crate::endpoint::models::check_json_content;
+pub use crate::endpoint::models::decode_azure_deployments;
+pub use crate::endpoint::models::decode_models_listing;
+pub use crate::endpoint::models::decode_openai_compatible_models;
 pub use crate::endpoint::responses::ResponsesClient;
 pub use crate::endpoint::responses::ResponsesOptions;
 pub use crate::error::ApiError;
+pub use crate::error::describe_content;
 pub use crate::provider::Provider;
 pub use crate::provider::WireApi;
 pub use crate::requests::ChatRequest;
@@ -88,6 +88,7 @@ impl Provider {
             headers: self.headers.clone(),
             body: None,
             timeout: None,
+            max_response_bytes: None,
         }
     }
 
@@ -1,3 +1,4 @@
+use crate::common::Sampling;
 use crate::error::ApiError;
 use crate::provider::Provider;
 use crate::requests::headers::build_conversation_headers;
@@ -24,6 +25,7 @@ pub struct ChatRequestBuilder<'a> {
     instructions: &'a str,
     input: &'a [ResponseItem],
     tools: &'a [Value],
+    sampling: Sampling,
     conversation_id: Option<String>,
     session_source: Option<SessionSource>,
 }
@@ -40,11 +42,17 @@ impl<'a> ChatRequestBuilder<'a> {
             instructions,
             input,
             tools,
+            sampling: Sampling::default(),
             conversation_id: None,
             session_source: None,
         }
     }
 
+    pub fn sampling(mut self, sampling: Sampling) -> Self {
+        self.sampling = sampling;
+        self
+    }
+
     pub fn conversation_id(mut self, id: Option<String>) -> Self {
         self.conversation_id = id;
         self
@@ -291,12 +299,20 @@ impl<'a> ChatRequestBuilder<'a> {
             }
         }
 
-        let payload = json!({
+        let mut payload = json!({
             "model": self.model,
             "messages": messages,
             "stream": true,
             "tools": self.tools,
         });
+        if let Some(obj) = payload.as_object_mut() {
+            if let Some(temperature) = self.sampling.temperature {
+                obj.insert("temperature".to_string(), json!(temperature));
+            }
+            if let Some(top_p) = self.sampling.top_p {
+                obj.insert("top_p".to_string(), json!(top_p));
+            }
+        }
 
         let mut headers = build_conversation_headers(self.conversation_id);
         if let Some(subagent) = subagent_header(&self.session_source) {
@@ -407,6 +423,26 @@ mod tests {
         );
     }
 
+    #[test]
+    fn sampling_parameters_are_sent_only_when_set() {
+        let prompt_input = Vec::new();
+        let unset = ChatRequestBuilder::new("gpt-test", "inst", &prompt_input, &[])
+            .build(&provider())
+            .expect("request");
+        assert_eq!(unset.body.get("temperature"), None);
+        assert_eq!(unset.body.get("top_p"), None);
+
+        let req = ChatRequestBuilder::new("gpt-test", "inst", &prompt_input, &[])
+            .sampling(Sampling {
+                temperature: Some(0.5),
+                top_p: None,
+            })
+            .build(&provider())
+            .expect("request");
+        assert_eq!(req.body["temperature"], 0.5);
+        assert_eq!(req.body.get("top_p"), None);
+    }
+
     #[test]
     fn groups_consecutive_tool_calls_into_a_single_assistant_message() {
         let prompt_input = vec![
@@ -1,5 +1,6 @@
 use crate::common::Reasoning;
 use crate::common::ResponsesApiRequest;
+use crate::common::Sampling;
 use crate::common::TextControls;
 use crate::error::ApiError;
 use crate::provider::Provider;
@@ -10,6 +11,7 @@ use codex_protocol::models::ResponseItem;
 use codex_protocol::protocol::SessionSource;
 use http::HeaderMap;
 use serde_json::Value;
+use std::borrow::Cow;
 
 /// Assembled request body plus headers for a Responses stream request.
 pub struct ResponsesRequest {
@@ -28,6 +30,8 @@ pub struct ResponsesRequestBuilder<'a> {
     include: Vec<String>,
     prompt_cache_key: Option<String>,
     text: Option<TextControls>,
+    max_output_tokens: Option<i64>,
+    sampling: Sampling,
     conversation_id: Option<String>,
     session_source: Option<SessionSource>,
     store_override: Option<bool>,
@@ -74,6 +78,16 @@ impl<'a> ResponsesRequestBuilder<'a> {
         self
     }
 
+    pub fn max_output_tokens(mut self, max_output_tokens: Option<i64>) -> Self {
+        self.max_output_tokens = max_output_tokens;
+        self
+    }
+
+    pub fn sampling(mut self, sampling: Sampling) -> Self {
+        self.sampling = sampling;
+        self
+    }
+
     pub fn conversation(mut self, conversation_id: Option<String>) -> Self {
         self.conversation_id = conversation_id;
         self
@@ -106,14 +120,29 @@ impl<'a> ResponsesRequestBuilder<'a> {
             .ok_or_else(|| ApiError::Stream("missing input for responses request".into()))?;
         let tools = self.tools.unwrap_or_default();
 
+        // A stateful request is stored and refers to earlier items by id. A
+        // stateless one must carry the whole context, so reasoning that only
+        // the server's store holds, e.g. from turns on a stateful model
+        // before a switch, cannot be sent and is left out.
         let store = self
             .store_override
             .unwrap_or_else(|| provider.is_azure_responses_endpoint());
+        let input: Cow<'_, [ResponseItem]> = if store || !input.iter().any(is_stored_only) {
+            Cow::Borrowed(input)
+        } else {
+            Cow::Owned(
+                input
+                    .iter()
+                    .filter(|item| !is_stored_only(item))
+                    .cloned()
+                    .collect(),
+            )
+        };
 
         let req = ResponsesApiRequest {
             model,
             instructions,
-            input,
+            input: &input,
             tools,
             tool_choice: "auto",
             parallel_tool_calls: self.parallel_tool_calls,
@@ -123,13 +152,16 @@ impl<'a> ResponsesRequestBuilder<'a> {
             include: self.include,
             prompt_cache_key: self.prompt_cache_key,
             text: self.text,
+            max_output_tokens: self.max_output_tokens,
+            temperature: self.sampling.temperature,
+            top_p: self.sampling.top_p,
         };
 
         let mut body = serde_json::to_value(&req)
             .map_err(|e| ApiError::Stream(format!("failed to encode responses request: {e}")))?;
 
-        if store && provider.is_azure_responses_endpoint() {
-            attach_item_ids(&mut body, input);
+        if store {
+            attach_item_ids(&mut body, &input);
         }
 
         let mut headers = self.headers;
@@ -142,6 +174,18 @@ impl<'a> ResponsesRequestBuilder<'a> {
     }
 }
 
+/// Whether `item` is reasoning that can only be replayed from the server's
+/// store.
+fn is_stored_only(item: &ResponseItem) -> bool {
+    matches!(
+        item,
+        ResponseItem::Reasoning {
+            encrypted_content: None,
+            ..
+        }
+    )
+}
+
 fn attach_item_ids(payload_json: &mut Value, original_items: &[ResponseItem]) {
     let Some(input_value) = payload_json.get_mut("input") else {
         return;
@@ -244,4 +288,85 @@ mod tests {
             Some(&HeaderValue::from_static("review"))
         );
     }
+
+    fn mixed_history() -> Vec<ResponseItem> {
+        vec![
+            ResponseItem::Reasoning {
+                id: "rs-stored".into(),
+                summary: Vec::new(),
+                content: None,
+                encrypted_content: None,
+            },
+            ResponseItem::Reasoning {
+                id: "rs-inline".into(),
+                summary: Vec::new(),
+                content: None,
+                encrypted_content: Some("opaque".into()),
+            },
+            ResponseItem::Message {
+                id: Some("m1".into()),
+                role: "assistant".into(),
+                content: Vec::new(),
+            },
+        ]
+    }
+
+    fn input_shape(body: &Value) -> Vec<(String, Option<String>)> {
+        body["input"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .map(|item| {
+                (
+                    item["type"].as_str().unwrap_or_default().to_string(),
+                    item.get("id").and_then(Value::as_str).map(str::to_string),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn stateless_requests_resend_the_context_without_stored_items() {
+        let provider = provider("azure", "https://example.openai.azure.com/v1");
+        let input = mixed_history();
+
+        let request = ResponsesRequestBuilder::new("gpt-test", "inst", &input)
+            .store_override(Some(false))
+            .build(&provider)
+            .expect("request");
+
+        assert_eq!(request.body.get("store"), Some(&Value::Bool(false)));
+        assert_eq!(
+            input_shape(&request.body),
+            vec![
+                ("reasoning".to_string(), None),
+                ("message".to_string(), None)
+            ]
+        );
+        assert_eq!(
+            request.body["input"][0]["encrypted_content"].as_str(),
+            Some("opaque")
+        );
+    }
+
+    #[test]
+    fn stateful_requests_refer_to_stored_items_by_id() {
+        let provider = provider("openai", "https://api.openai.com/v1");
+        let input = mixed_history();
+
+        let request = ResponsesRequestBuilder::new("gpt-test", "inst", &input)
+            .store_override(Some(true))
+            .build(&provider)
+            .expect("request");
+
+        assert_eq!(request.body.get("store"), Some(&Value::Bool(true)));
+        assert_eq!(
+            input_shape(&request.body),
+            vec![
+                ("reasoning".to_string(), Some("rs-stored".to_string())),
+                ("reasoning".to_string(), Some("rs-inline".to_string())),
+                ("message".to_string(), Some("m1".to_string())),
+            ]
+        );
+    }
 }
@@ -93,7 +93,7 @@ pub async fn process_chat_sse<S>(
             }
             Err(_) => {
                 let _ = tx_event
-                    .send(Err(ApiError::Stream("idle timeout waiting for SSE".into())))
+                    .send(Err(ApiError::IdleTimeout(idle_timeout)))
                     .await;
                 return;
             }
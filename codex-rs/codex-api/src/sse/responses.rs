@@ -179,7 +179,7 @@ pub async fn process_sse(
             }
             Err(_) => {
                 let _ = tx_event
-                    .send(Err(ApiError::Stream("idle timeout waiting for SSE".into())))
+                    .send(Err(ApiError::IdleTimeout(idle_timeout)))
                     .await;
                 return;
             }
@@ -411,6 +411,41 @@ mod tests {
         Duration::from_millis(1000)
     }
 
+    #[tokio::test]
+    async fn idle_timeout_error_names_the_budget() {
+        let (tx, mut rx) = mpsc::channel::<Result<ResponseEvent, ApiError>>(1);
+        let stream = futures::stream::pending::<Result<bytes::Bytes, TransportError>>();
+        tokio::spawn(process_sse(
+            Box::pin(stream),
+            tx,
+            Duration::from_millis(20),
+            None,
+        ));
+
+        let error = rx.recv().await.expect("an event").expect_err("a timeout");
+        assert_eq!(error.to_string(), "idle timeout waiting for SSE after 20ms");
+        assert_matches!(error, ApiError::IdleTimeout(after) if after == Duration::from_millis(20));
+    }
+
+    #[tokio::test]
+    async fn each_stream_waits_for_its_own_idle_budget() {
+        for budget in [Duration::from_millis(20), Duration::from_millis(200)] {
+            let (tx, mut rx) = mpsc::channel::<Result<ResponseEvent, ApiError>>(1);
+            let stream = futures::stream::pending::<Result<bytes::Bytes, TransportError>>();
+            let started = std::time::Instant::now();
+            tokio::spawn(process_sse(Box::pin(stream), tx, budget, None));
+
+            let error = rx.recv().await.expect("an event").expect_err("a timeout");
+            let waited = started.elapsed();
+
+            assert_matches!(error, ApiError::IdleTimeout(after) if after == budget);
+            assert!(
+                waited >= budget,
+                "gave up after {waited:?}, budget {budget:?}"
+            );
+        }
+    }
+
     #[tokio::test]
     async fn parses_items_and_completed() {
         let item1 = json!({
@@ -0,0 +1,337 @@
+//! The `/models` decode path against whatever a gateway might send back.
+//!
+//! The payloads under `tests/fixtures/models_decode` are pinned to the
+//! outcome they decode to; add one whenever a payload from the wild, or from
+//! the generators below, misbehaves. The generators mutate those payloads and
+//! build catalogs out of random JSON. Their cases are seeded, so every run
+//! tries the same ones; set `CODEX_MODELS_FUZZ_CASES` to try more, e.g.
+//! `CODEX_MODELS_FUZZ_CASES=200000 cargo test -p codex-api --test models_decode`.
+
+use std::panic::AssertUnwindSafe;
+use std::panic::catch_unwind;
+use std::path::Path;
+
+use codex_api::ApiError;
+use codex_api::ModelsListing;
+use codex_api::decode_models_listing;
+use codex_protocol::openai_models::ModelPreset;
+use codex_protocol::openai_models::ReasoningSummaryFormat;
+use pretty_assertions::assert_eq;
+use serde_json::Map;
+use serde_json::Value;
+use serde_json::json;
+
+const FIXTURES: [&str; 6] = [
+    "codex_catalog.json",
+    "litellm_catalog.json",
+    "token_counts_at_the_limits.json",
+    "deep_nesting.json",
+    "models_not_a_list.json",
+    "entries_of_every_type.json",
+];
+
+const DEFAULT_CASES: u64 = 2_000;
+
+/// Longest error message a payload may produce, however large it is.
+const MAX_ERROR_CHARS: usize = 1_024;
+
+/// Fragments spliced into payloads: structure, numbers at and past the
+/// integer limits, awkward strings, and the field names the decoder treats
+/// specially.
+const TOKENS: [&str; 30] = [
+    "{",
+    "}",
+    "[",
+    "]",
+    ",",
+    ":",
+    "\"",
+    "null",
+    "true",
+    "-0",
+    "1e400",
+    "-1e400",
+    "128000.0",
+    "0.5",
+    "9223372036854775807",
+    "9223372036854775808",
+    "-9223372036854775809",
+    "18446744073709551616",
+    "\"\\ud800\"",
+    "\"\\u0000\"",
+    "\"\u{feff}\"",
+    "\"128000\"",
+    "\"slug\"",
+    "\"model_info\"",
+    "\"litellm_params\"",
+    "\"model_name\"",
+    "\"context_window\"",
+    "\"max_input_tokens\"",
+    "\"supported_reasoning_levels\"",
+    "\"min_client_version\"",
+];
+
+/// Field names of `ModelInfo` and its aliases, for the catalog generator.
+const FIELDS: [&str; 16] = [
+    "slug",
+    "preset_id",
+    "display_name",
+    "default_reasoning_level",
+    "supported_reasoning_levels",
+    "shell_type",
+    "visibility",
+    "priority",
+    "truncation_policy",
+    "context_window",
+    "maxInputTokens",
+    "max_output_tokens",
+    "request_headers",
+    "reasoning_summary_format",
+    "model_info",
+    "litellm_params",
+];
+
+fn fixture(name: &str) -> Vec<u8> {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/fixtures/models_decode")
+        .join(name);
+    std::fs::read(&path).unwrap_or_else(|err| panic!("read {}: {err}", path.display()))
+}
+
+fn cases() -> u64 {
+    std::env::var("CODEX_MODELS_FUZZ_CASES")
+        .ok()
+        .and_then(|cases| cases.parse().ok())
+        .unwrap_or(DEFAULT_CASES)
+}
+
+/// Decode `body` the way a refresh does, turning each model into a preset,
+/// and check that failing did not quote the body at length.
+fn decode(body: &[u8]) -> Result<ModelsListing, ApiError> {
+    let result = decode_models_listing(body);
+    match &result {
+        Ok(listing) => {
+            for model in &listing.models {
+                let _ = ModelPreset::from(model.clone());
+            }
+        }
+        Err(err) => {
+            let chars = err.to_string().chars().count();
+            assert!(chars <= MAX_ERROR_CHARS, "{chars} characters of error");
+        }
+    }
+    result
+}
+
+/// Decode a generated payload, naming the case and the payload if it panics.
+fn decode_case(case: u64, body: &[u8]) {
+    if catch_unwind(AssertUnwindSafe(|| decode(body))).is_err() {
+        panic!(
+            "case {case} panicked; check the payload in as a fixture:\n{}",
+            String::from_utf8_lossy(body)
+        );
+    }
+}
+
+/// xorshift64*: a fixed seed gives the same cases on every run.
+struct Rng(u64);
+
+impl Rng {
+    fn next(&mut self) -> u64 {
+        let mut x = self.0;
+        x ^= x >> 12;
+        x ^= x << 25;
+        x ^= x >> 27;
+        self.0 = x;
+        x.wrapping_mul(0x2545_f491_4f6c_dd1d)
+    }
+
+    fn below(&mut self, n: usize) -> usize {
+        (self.next() % n as u64) as usize
+    }
+
+    fn chance(&mut self, one_in: usize) -> bool {
+        self.below(one_in) == 0
+    }
+}
+
+fn mutate(rng: &mut Rng, body: &mut Vec<u8>) {
+    for _ in 0..=rng.below(4) {
+        let at = rng.below(body.len() + 1);
+        match rng.below(5) {
+            0 if at < body.len() => body[at] = rng.next() as u8,
+            1 => {
+                let end = (at + rng.below(32)).min(body.len());
+                body.drain(at..end);
+            }
+            2 => {
+                let end = (at + rng.below(64)).min(body.len());
+                let copy = body[at..end].to_vec();
+                body.splice(at..at, copy);
+            }
+            3 => body.truncate(at),
+            _ => {
+                let token = TOKENS[rng.below(TOKENS.len())];
+                body.splice(at..at, token.bytes());
+            }
+        }
+    }
+}
+
+fn random_value(rng: &mut Rng, depth: usize) -> Value {
+    let kinds = if depth == 0 { 6 } else { 8 };
+    match rng.below(kinds) {
+        0 => Value::Null,
+        1 => Value::Bool(rng.chance(2)),
+        2 => json!(rng.next() as i64),
+        3 => json!(rng.next()),
+        4 => json!((rng.next() as i64) as f64 / 7.0),
+        5 => Value::String(
+            TOKENS[rng.below(TOKENS.len())]
+                .trim_matches('"')
+                .to_string(),
+        ),
+        6 => Value::Array(
+            (0..rng.below(4))
+                .map(|_| random_value(rng, depth - 1))
+                .collect(),
+        ),
+        _ => random_object(rng, depth - 1),
+    }
+}
+
+fn random_object(rng: &mut Rng, depth: usize) -> Value {
+    let mut fields = Map::new();
+    for _ in 0..rng.below(5) {
+        let name = FIELDS[rng.below(FIELDS.len())];
+        fields.insert(name.to_string(), random_value(rng, depth));
+    }
+    Value::Object(fields)
+}
+
+/// A model entry from a fixture with some of its fields replaced, removed,
+/// or added.
+fn random_entry(rng: &mut Rng, seeds: &[Value]) -> Value {
+    let mut entry = seeds[rng.below(seeds.len())].clone();
+    if let Some(fields) = entry.as_object_mut() {
+        for _ in 0..=rng.below(3) {
+            let name = FIELDS[rng.below(FIELDS.len())];
+            if rng.chance(3) {
+                fields.remove(name);
+            } else {
+                fields.insert(name.to_string(), random_value(rng, 3));
+            }
+        }
+    }
+    entry
+}
+
+fn seed_entries() -> Vec<Value> {
+    ["codex_catalog.json", "litellm_catalog.json"]
+        .into_iter()
+        .flat_map(|name| {
+            let catalog: Value = serde_json::from_slice(&fixture(name)).expect("valid fixture");
+            catalog["models"].as_array().cloned().unwrap_or_default()
+        })
+        .collect()
+}
+
+fn listed(listing: &ModelsListing) -> Vec<(&str, Option<i64>)> {
+    listing
+        .models
+        .iter()
+        .map(|model| (model.slug.as_str(), model.context_window))
+        .collect()
+}
+
+#[test]
+fn fixtures_decode_to_their_pinned_outcomes() {
+    let codex = decode(&fixture("codex_catalog.json")).expect("codex catalog");
+    assert_eq!(
+        listed(&codex),
+        vec![("gpt-test", Some(272_000)), ("gpt-test-mini", None)]
+    );
+    assert_eq!(codex.min_client_version.as_deref(), Some("0.60.0"));
+    assert_eq!(
+        codex.models[0].reasoning_summary_format,
+        Some(ReasoningSummaryFormat::Headed)
+    );
+    assert_eq!(codex.models[1].supports_stateful_responses, Some(false));
+
+    let litellm = decode(&fixture("litellm_catalog.json")).expect("litellm catalog");
+    assert_eq!(listed(&litellm), vec![("azure/gpt-5", Some(128_000))]);
+    assert_eq!(litellm.models[0].preset_id.as_deref(), Some("oca-large"));
+    assert_eq!(litellm.min_client_version, None);
+
+    let limits = decode(&fixture("token_counts_at_the_limits.json")).expect("token counts");
+    assert_eq!(
+        listed(&limits),
+        vec![("largest", Some(i64::MAX)), ("negative", None)],
+        "counts past i64 skip their model"
+    );
+
+    for name in ["deep_nesting.json", "models_not_a_list.json"] {
+        assert!(decode(&fixture(name)).is_err(), "{name} should not decode");
+    }
+
+    let every_type = decode(&fixture("entries_of_every_type.json")).expect("a list of entries");
+    assert_eq!(listed(&every_type), Vec::new());
+}
+
+#[test]
+fn mutated_fixtures_never_panic() {
+    let fixtures: Vec<Vec<u8>> = FIXTURES.into_iter().map(fixture).collect();
+    let mut rng = Rng(0x5eed_c0de);
+    for case in 0..cases() {
+        let mut body = fixtures[rng.below(fixtures.len())].clone();
+        mutate(&mut rng, &mut body);
+        decode_case(case, &body);
+    }
+}
+
+#[test]
+fn generated_catalogs_never_panic() {
+    let seeds = seed_entries();
+    let mut rng = Rng(0xca7a_1095);
+    for case in 0..cases() {
+        let models: Vec<Value> = (0..rng.below(4))
+            .map(|_| {
+                if rng.chance(4) {
+                    random_value(&mut rng, 3)
+                } else {
+                    random_entry(&mut rng, &seeds)
+                }
+            })
+            .collect();
+        let mut catalog = json!({ "models": models });
+        if rng.chance(2) {
+            catalog["min_client_version"] = random_value(&mut rng, 2);
+        }
+        let body = serde_json::to_vec(&catalog).expect("serialize catalog");
+        decode_case(case, &body);
+    }
+}
+
+#[test]
+fn huge_strings_are_decoded_but_never_quoted_in_full() {
+    let huge = "x".repeat(4 * 1024 * 1024);
+
+    let mut entry = seed_entries().remove(0);
+    entry["display_name"] = Value::String(huge.clone());
+    let body = serde_json::to_vec(&json!({ "models": [entry] })).expect("serialize catalog");
+    let listing = decode(&body).expect("a long name is still a name");
+    assert_eq!(listing.models[0].display_name.len(), huge.len());
+
+    // The decoder's own message repeats the string it did not expect.
+    let body = serde_json::to_vec(&json!({ "models": huge })).expect("serialize catalog");
+    assert!(decode(&body).is_err());
+}
+
+#[test]
+fn nesting_past_the_recursion_limit_is_an_error() {
+    for (depth, decodes) in [(100, true), (1_000, false), (100_000, false)] {
+        let nested = format!("{}{}", "[".repeat(depth), "]".repeat(depth));
+        let body = format!(r#"{{"models": [{{"slug": "deep", "extra": {nested}}}]}}"#);
+        assert_eq!(decode(body.as_bytes()).is_ok(), decodes, "depth {depth}");
+    }
+}
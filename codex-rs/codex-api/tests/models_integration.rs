@@ -1,5 +1,9 @@
+use assert_matches::assert_matches;
+use codex_api::ApiError;
 use codex_api::AuthProvider;
 use codex_api::ModelsClient;
+use codex_api::ModelsListing;
+use codex_api::TransportError;
 use codex_api::provider::Provider;
 use codex_api::provider::RetryConfig;
 use codex_api::provider::WireApi;
@@ -61,14 +65,23 @@ async fn models_client_hits_models_endpoint() {
                 ReasoningEffortPreset {
                     effort: ReasoningEffort::Low,
                     description: ReasoningEffort::Low.to_string(),
+                    max_output_tokens: None,
+                    request_timeout_secs: None,
+                    stream_idle_timeout_secs: None,
                 },
                 ReasoningEffortPreset {
                     effort: ReasoningEffort::Medium,
                     description: ReasoningEffort::Medium.to_string(),
+                    max_output_tokens: None,
+                    request_timeout_secs: None,
+                    stream_idle_timeout_secs: None,
                 },
                 ReasoningEffortPreset {
                     effort: ReasoningEffort::High,
                     description: ReasoningEffort::High.to_string(),
+                    max_output_tokens: None,
+                    request_timeout_secs: None,
+                    stream_idle_timeout_secs: None,
                 },
             ],
             shell_type: ConfigShellToolType::ShellCommand,
@@ -77,6 +90,8 @@ async fn models_client_hits_models_endpoint() {
             priority: 1,
             upgrade: None,
             base_instructions: None,
+            instructions_addendum: None,
+            wire_api: None,
             supports_reasoning_summaries: false,
             support_verbosity: false,
             default_verbosity: None,
@@ -84,8 +99,33 @@ async fn models_client_hits_models_endpoint() {
             truncation_policy: TruncationPolicyConfig::bytes(10_000),
             supports_parallel_tool_calls: false,
             context_window: None,
+            context_window_unit: None,
+            context_window_source: None,
+            max_output_tokens: None,
+            supports_temperature: None,
+            default_temperature: None,
+            supports_top_p: None,
+            default_top_p: None,
+            request_headers: None,
             experimental_supported_tools: Vec::new(),
+            extra: Default::default(),
+            max_concurrent_requests: None,
+            prompt_cache_key_strategy: None,
+            fallback_models: None,
+            knowledge_cutoff: None,
+            released_at: None,
+            supports_images: true,
+            preset_id: None,
+            requires_network: false,
+            disabled_tools: Vec::new(),
+            reasoning_summary_format: None,
+            is_default: false,
+            supports_stateful_responses: None,
+            supports_reasoning: None,
+            prefer_apply_patch_freeform: None,
+            enable_web_search: None,
         }],
+        min_client_version: None,
     };
 
     Mock::given(method("GET"))
@@ -101,7 +141,7 @@ async fn models_client_hits_models_endpoint() {
     let transport = ReqwestTransport::new(reqwest::Client::new());
     let client = ModelsClient::new(transport, provider(&base_url), DummyAuth);
 
-    let (models, _) = client
+    let ModelsListing { models, .. } = client
         .list_models("0.1.0", HeaderMap::new())
         .await
         .expect("models request should succeed");
@@ -117,3 +157,197 @@ async fn models_client_hits_models_endpoint() {
     assert_eq!(received[0].method, Method::GET.as_str());
     assert_eq!(received[0].url.path(), "/api/codex/models");
 }
+
+/// 400 OpenAI-style model entries (`oca-model-000`..`oca-model-399`), gzipped.
+const GZIPPED_MODELS: &[u8] = include_bytes!("fixtures/openai_models_400.json.gz");
+/// Decoded size of [`GZIPPED_MODELS`].
+const GZIPPED_MODELS_DECODED_LEN: usize = 25_228;
+
+async fn mount_gzipped_models(server: &MockServer) {
+    Mock::given(method("GET"))
+        .and(path("/v1/models"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_raw(GZIPPED_MODELS, "application/json")
+                .insert_header("content-encoding", "gzip"),
+        )
+        .mount(server)
+        .await;
+}
+
+#[tokio::test]
+async fn models_client_decodes_gzipped_catalog() {
+    let server = MockServer::start().await;
+    mount_gzipped_models(&server).await;
+
+    let transport = ReqwestTransport::new(reqwest::Client::new());
+    let client = ModelsClient::new(
+        transport,
+        provider(&format!("{}/v1", server.uri())),
+        DummyAuth,
+    );
+
+    let ids = client
+        .list_openai_compatible_models(HeaderMap::new())
+        .await
+        .expect("gzipped listing should decode")
+        .entries;
+
+    let expected: Vec<String> = (0..400).map(|i| format!("oca-model-{i:03}")).collect();
+    assert_eq!(ids, expected);
+
+    let received = server
+        .received_requests()
+        .await
+        .expect("should capture requests");
+    let accept_encoding = received[0]
+        .headers
+        .get("accept-encoding")
+        .and_then(|value| value.to_str().ok())
+        .unwrap_or_default();
+    assert!(
+        accept_encoding.contains("gzip"),
+        "catalog request should negotiate compression, got {accept_encoding:?}"
+    );
+}
+
+#[tokio::test]
+async fn models_client_rejects_oversized_catalog() {
+    let server = MockServer::start().await;
+    let padding = "x".repeat(4096);
+    Mock::given(method("GET"))
+        .and(path("/v1/models"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "object": "list",
+            "data": [{"id": padding, "object": "model"}],
+        })))
+        .mount(&server)
+        .await;
+
+    let transport = ReqwestTransport::new(reqwest::Client::new());
+    let client = ModelsClient::new(
+        transport,
+        provider(&format!("{}/v1", server.uri())),
+        DummyAuth,
+    )
+    .with_max_response_bytes(1024);
+
+    let err = client
+        .list_openai_compatible_models(HeaderMap::new())
+        .await
+        .expect_err("oversized listing should be rejected");
+    assert_matches!(
+        err,
+        ApiError::Transport(TransportError::ResponseTooLarge { limit: 1024 })
+    );
+}
+
+#[tokio::test]
+async fn models_client_limits_decoded_size_of_compressed_catalog() {
+    let server = MockServer::start().await;
+    mount_gzipped_models(&server).await;
+
+    // The compressed payload fits easily; the limit applies to what it expands to.
+    let limit = GZIPPED_MODELS_DECODED_LEN - 1;
+    assert!(GZIPPED_MODELS.len() < limit);
+    let transport = ReqwestTransport::new(reqwest::Client::new());
+    let client = ModelsClient::new(
+        transport,
+        provider(&format!("{}/v1", server.uri())),
+        DummyAuth,
+    )
+    .with_max_response_bytes(limit);
+
+    let err = client
+        .list_openai_compatible_models(HeaderMap::new())
+        .await
+        .expect_err("decoded listing exceeds the limit");
+    assert_matches!(
+        err,
+        ApiError::Transport(TransportError::ResponseTooLarge { limit: actual }) if actual == limit
+    );
+}
+
+/// Lists OpenAI-compatible models from a server answering `GET /v1/models`
+/// with `body` labelled `content_type`.
+async fn list_models_served_as(
+    body: &'static str,
+    content_type: &str,
+) -> Result<Vec<String>, ApiError> {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/v1/models"))
+        .respond_with(ResponseTemplate::new(200).set_body_raw(body, content_type))
+        .mount(&server)
+        .await;
+
+    let transport = ReqwestTransport::new(reqwest::Client::new());
+    let client = ModelsClient::new(
+        transport,
+        provider(&format!("{}/v1", server.uri())),
+        DummyAuth,
+    );
+    client
+        .list_openai_compatible_models(HeaderMap::new())
+        .await
+        .map(|listing| listing.entries)
+}
+
+#[tokio::test]
+async fn models_client_reports_html_login_page() {
+    let err = list_models_served_as(
+        "<!DOCTYPE html>\n<html>\n  <head><title>Sign in</title></head>\n</html>\n",
+        "text/html; charset=utf-8",
+    )
+    .await
+    .expect_err("a login page is not a catalog");
+    assert_matches!(
+        &err,
+        ApiError::UnexpectedContent { content_type: Some(content_type), snippet }
+            if content_type == "text/html; charset=utf-8"
+                && snippet == "<!DOCTYPE html> <html> <head><title>Sign in</title></head> </html>"
+    );
+    assert_eq!(
+        err.to_string(),
+        "expected a JSON response but got `text/html; charset=utf-8` starting `<!DOCTYPE html> <html> <head><title>Sign in</title></head> </html>`"
+    );
+}
+
+#[tokio::test]
+async fn models_client_reports_empty_body() {
+    let err = list_models_served_as("", "application/json")
+        .await
+        .expect_err("an empty body is not a catalog");
+    assert_matches!(
+        &err,
+        ApiError::UnexpectedContent { content_type: Some(content_type), snippet }
+            if content_type == "application/json" && snippet.is_empty()
+    );
+    assert_eq!(
+        err.to_string(),
+        "expected a JSON response but got an empty `application/json` body"
+    );
+}
+
+#[tokio::test]
+async fn models_client_reports_non_json_content_type() {
+    let err = list_models_served_as("Welcome to the guest network", "text/plain")
+        .await
+        .expect_err("a plain-text page is not a catalog");
+    assert_matches!(
+        err,
+        ApiError::UnexpectedContent { content_type: Some(content_type), snippet }
+            if content_type == "text/plain" && snippet == "Welcome to the guest network"
+    );
+}
+
+#[tokio::test]
+async fn models_client_accepts_mislabelled_json() {
+    let models = list_models_served_as(
+        r#"{"object": "list", "data": [{"id": "local-model", "object": "model"}]}"#,
+        "text/plain",
+    )
+    .await
+    .expect("JSON labelled text/plain still decodes");
+    assert_eq!(models, vec!["local-model".to_string()]);
+}
@@ -1,3 +1,4 @@
+use crate::spki_pinning::SpkiPinMismatch;
 use http::HeaderMap;
 use http::StatusCode;
 use thiserror::Error;
@@ -18,6 +19,11 @@ pub enum TransportError {
     Network(String),
     #[error("request build error: {0}")]
     Build(String),
+    #[error("response body exceeds the {limit}-byte limit")]
+    ResponseTooLarge { limit: usize },
+    /// The TLS handshake was refused because the server key is not pinned.
+    #[error(transparent)]
+    SpkiPinMismatch(SpkiPinMismatch),
 }
 
 #[derive(Debug, Error)]
@@ -2,8 +2,10 @@ mod default_client;
 mod error;
 mod request;
 mod retry;
+mod spki_pinning;
 mod sse;
 mod telemetry;
+mod tls_probe;
 mod transport;
 
 pub use crate::default_client::CodexHttpClient;
@@ -16,8 +18,14 @@ pub use crate::retry::RetryOn;
 pub use crate::retry::RetryPolicy;
 pub use crate::retry::backoff;
 pub use crate::retry::run_with_retry;
+pub use crate::spki_pinning::SpkiPin;
+pub use crate::spki_pinning::SpkiPinMismatch;
+pub use crate::spki_pinning::find_pin_mismatch;
+pub use crate::spki_pinning::pinned_tls_config;
 pub use crate::sse::sse_stream;
 pub use crate::telemetry::RequestTelemetry;
+pub use crate::tls_probe::native_tls_config;
+pub use crate::tls_probe::tls_handshake;
 pub use crate::transport::ByteStream;
 pub use crate::transport::HttpTransport;
 pub use crate::transport::ReqwestTransport;
@@ -12,6 +12,9 @@ pub struct Request {
     pub headers: HeaderMap,
     pub body: Option<Value>,
     pub timeout: Option<Duration>,
+    /// Fail with [`crate::TransportError::ResponseTooLarge`] once the decoded
+    /// body grows past this many bytes. Only applies to `execute`.
+    pub max_response_bytes: Option<usize>,
 }
 
 impl Request {
@@ -22,6 +25,7 @@ impl Request {
             headers: HeaderMap::new(),
             body: None,
             timeout: None,
+            max_response_bytes: None,
         }
     }
 
@@ -0,0 +1,307 @@
+//! Public key pinning for provider endpoints.
+//!
+//! A provider can list the SHA-256 hashes of the `SubjectPublicKeyInfo` its
+//! servers present. The certificate chain is still validated against the
+//! system certificate store; on top of that the leaf certificate's key must
+//! match one of the pins, so a certificate issued by a compromised or
+//! intercepting CA is refused.
+
+use std::error::Error as StdError;
+use std::fmt;
+use std::str::FromStr;
+use std::sync::Arc;
+
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use rustls::ClientConfig;
+use rustls::DigitallySignedStruct;
+use rustls::RootCertStore;
+use rustls::SignatureScheme;
+use rustls::client::WebPkiServerVerifier;
+use rustls::client::danger::HandshakeSignatureValid;
+use rustls::client::danger::ServerCertVerified;
+use rustls::client::danger::ServerCertVerifier;
+use rustls::crypto::CryptoProvider;
+use rustls::pki_types::CertificateDer;
+use rustls::pki_types::ServerName;
+use rustls::pki_types::UnixTime;
+use sha2::Digest;
+use sha2::Sha256;
+use thiserror::Error;
+use webpki::EndEntityCert;
+
+/// The base64-encoded SHA-256 hash of a DER `SubjectPublicKeyInfo`, as
+/// printed by `openssl x509 -pubkey | openssl pkey -pubin -outform der |
+/// openssl dgst -sha256 -binary | base64`.
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SpkiPin([u8; 32]);
+
+impl FromStr for SpkiPin {
+    type Err = String;
+
+    fn from_str(pin: &str) -> Result<Self, Self::Err> {
+        let bytes = STANDARD
+            .decode(pin.trim())
+            .map_err(|err| format!("`{pin}` is not valid base64: {err}"))?;
+        let hash: [u8; 32] = bytes.try_into().map_err(|bytes: Vec<u8>| {
+            format!(
+                "`{pin}` decodes to {} bytes; a SHA-256 hash is 32 bytes",
+                bytes.len()
+            )
+        })?;
+        Ok(Self(hash))
+    }
+}
+
+impl fmt::Display for SpkiPin {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&STANDARD.encode(self.0))
+    }
+}
+
+impl fmt::Debug for SpkiPin {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "SpkiPin({self})")
+    }
+}
+
+/// The server presented a valid certificate whose public key matches none of
+/// the pins configured for it.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[error(
+    "the public key presented by {host} (sha256 {observed}) matches none of the provider's `pinned_spki_sha256` entries"
+)]
+pub struct SpkiPinMismatch {
+    pub host: String,
+    /// Pin of the key the server presented, in the configured format.
+    pub observed: String,
+}
+
+/// TLS settings that validate the chain against the system certificate store
+/// and then require the leaf key to match one of `pins`.
+pub fn pinned_tls_config(pins: Vec<SpkiPin>) -> Result<ClientConfig, String> {
+    let provider = Arc::new(rustls::crypto::ring::default_provider());
+    let roots = native_roots()?;
+    let inner = WebPkiServerVerifier::builder_with_provider(Arc::new(roots), Arc::clone(&provider))
+        .build()
+        .map_err(|err| err.to_string())?;
+    let verifier = PinnedVerifier {
+        inner,
+        pins,
+        provider: Arc::clone(&provider),
+    };
+    let mut config = ClientConfig::builder_with_provider(provider)
+        .with_safe_default_protocol_versions()
+        .map_err(|err| err.to_string())?
+        .dangerous()
+        .with_custom_certificate_verifier(Arc::new(verifier))
+        .with_no_client_auth();
+    config.alpn_protocols = vec![b"h2".to_vec(), b"http/1.1".to_vec()];
+    Ok(config)
+}
+
+/// The system certificate store, skipping certificates that fail to parse.
+pub(crate) fn native_roots() -> Result<RootCertStore, String> {
+    let mut roots = RootCertStore::empty();
+    let native = rustls_native_certs::load_native_certs();
+    for err in &native.errors {
+        tracing::warn!("failed to load a system root certificate: {err}");
+    }
+    let (added, _) = roots.add_parsable_certificates(native.certs);
+    if added == 0 {
+        return Err("no usable certificates in the system certificate store".to_string());
+    }
+    Ok(roots)
+}
+
+/// The pin mismatch behind `err`, if a refused handshake caused it. The
+/// mismatch travels through reqwest, hyper, and rustls errors, and `io::Error`
+/// hides its payload from `source()`, so each layer is unwrapped explicitly.
+pub fn find_pin_mismatch(err: &(dyn StdError + 'static)) -> Option<SpkiPinMismatch> {
+    let mut current = Some(err);
+    while let Some(err) = current {
+        if let Some(mismatch) = err.downcast_ref::<SpkiPinMismatch>() {
+            return Some(mismatch.clone());
+        }
+        if let Some(rustls::Error::Other(other)) = err.downcast_ref::<rustls::Error>() {
+            let inner: &(dyn StdError + 'static) = other.0.as_ref();
+            current = Some(inner);
+            continue;
+        }
+        if let Some(inner) = err
+            .downcast_ref::<std::io::Error>()
+            .and_then(std::io::Error::get_ref)
+        {
+            let inner: &(dyn StdError + 'static) = inner;
+            current = Some(inner);
+            continue;
+        }
+        current = err.source();
+    }
+    None
+}
+
+#[derive(Debug)]
+struct PinnedVerifier {
+    inner: Arc<WebPkiServerVerifier>,
+    pins: Vec<SpkiPin>,
+    provider: Arc<CryptoProvider>,
+}
+
+impl ServerCertVerifier for PinnedVerifier {
+    fn verify_server_cert(
+        &self,
+        end_entity: &CertificateDer<'_>,
+        intermediates: &[CertificateDer<'_>],
+        server_name: &ServerName<'_>,
+        ocsp_response: &[u8],
+        now: UnixTime,
+    ) -> Result<ServerCertVerified, rustls::Error> {
+        let verified = self.inner.verify_server_cert(
+            end_entity,
+            intermediates,
+            server_name,
+            ocsp_response,
+            now,
+        )?;
+        let observed = spki_pin(end_entity).ok_or(rustls::Error::InvalidCertificate(
+            rustls::CertificateError::BadEncoding,
+        ))?;
+        check_pin(&self.pins, observed, &server_name.to_str())
+            .map(|()| verified)
+            .map_err(|mismatch| rustls::Error::Other(rustls::OtherError(Arc::new(mismatch))))
+    }
+
+    fn verify_tls12_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        rustls::crypto::verify_tls12_signature(
+            message,
+            cert,
+            dss,
+            &self.provider.signature_verification_algorithms,
+        )
+    }
+
+    fn verify_tls13_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        rustls::crypto::verify_tls13_signature(
+            message,
+            cert,
+            dss,
+            &self.provider.signature_verification_algorithms,
+        )
+    }
+
+    fn supported_verify_schemes(&self) -> Vec<SignatureScheme> {
+        self.provider
+            .signature_verification_algorithms
+            .supported_schemes()
+    }
+}
+
+fn check_pin(pins: &[SpkiPin], observed: SpkiPin, host: &str) -> Result<(), SpkiPinMismatch> {
+    if pins.contains(&observed) {
+        Ok(())
+    } else {
+        Err(SpkiPinMismatch {
+            host: host.to_string(),
+            observed: observed.to_string(),
+        })
+    }
+}
+
+/// Hash of the `subjectPublicKeyInfo` of a DER certificate, or `None` when
+/// the certificate cannot be parsed.
+fn spki_pin(cert: &CertificateDer<'_>) -> Option<SpkiPin> {
+    let cert = EndEntityCert::try_from(cert).ok()?;
+    Some(SpkiPin(
+        Sha256::digest(cert.subject_public_key_info().as_ref()).into(),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    /// A self-signed certificate for `oca.example.com`, and the pin of its
+    /// key as the `openssl` pipeline on [`SpkiPin`] prints it.
+    const LEAF: &[u8] = include_bytes!("../tests/fixtures/pinned_leaf.der");
+    const LEAF_PIN: &str = "pQ1HUuxpeDWBD97EenR4fD21z4wT/LxshfyJSk2xlzQ=";
+
+    #[test]
+    fn pins_must_be_base64_sha256_hashes() {
+        let hash = [7u8; 32];
+        let pin: SpkiPin = STANDARD.encode(hash).parse().expect("valid pin");
+        assert_eq!(pin, SpkiPin(hash));
+        assert_eq!(pin.to_string(), STANDARD.encode(hash));
+
+        let err = "not base64!".parse::<SpkiPin>().expect_err("bad base64");
+        assert!(err.contains("is not valid base64"), "{err}");
+
+        let err = STANDARD
+            .encode([7u8; 20])
+            .parse::<SpkiPin>()
+            .expect_err("wrong length");
+        assert!(err.contains("decodes to 20 bytes"), "{err}");
+    }
+
+    #[test]
+    fn the_pin_of_a_certificate_matches_openssl() {
+        let pin: SpkiPin = LEAF_PIN.parse().expect("valid pin");
+        assert_eq!(spki_pin(&CertificateDer::from(LEAF)), Some(pin));
+    }
+
+    #[test]
+    fn truncated_certificates_have_no_pin() {
+        assert_eq!(
+            spki_pin(&CertificateDer::from(&LEAF[..LEAF.len() - 1])),
+            None
+        );
+        assert_eq!(
+            spki_pin(&CertificateDer::from(&[0x30, 0x85, 0, 0][..])),
+            None
+        );
+    }
+
+    #[test]
+    fn a_key_outside_the_pin_set_names_the_observed_hash() {
+        let pinned = SpkiPin([1; 32]);
+        let backup = SpkiPin([2; 32]);
+        let pins = [pinned, backup];
+        assert_eq!(check_pin(&pins, backup, "oca.example.com"), Ok(()));
+
+        let observed = SpkiPin([3; 32]);
+        let mismatch = check_pin(&pins, observed, "oca.example.com").expect_err("mismatch");
+        assert_eq!(
+            mismatch,
+            SpkiPinMismatch {
+                host: "oca.example.com".to_string(),
+                observed: observed.to_string(),
+            }
+        );
+        assert!(mismatch.to_string().contains(&observed.to_string()));
+    }
+
+    #[test]
+    fn a_mismatch_is_found_behind_tls_and_io_errors() {
+        let mismatch = SpkiPinMismatch {
+            host: "oca.example.com".to_string(),
+            observed: SpkiPin([3; 32]).to_string(),
+        };
+        let tls = rustls::Error::Other(rustls::OtherError(Arc::new(mismatch.clone())));
+        let io = std::io::Error::new(std::io::ErrorKind::InvalidData, tls);
+        assert_eq!(find_pin_mismatch(&io), Some(mismatch));
+
+        let unrelated = std::io::Error::new(std::io::ErrorKind::InvalidData, "bad record");
+        assert_eq!(find_pin_mismatch(&unrelated), None);
+    }
+}
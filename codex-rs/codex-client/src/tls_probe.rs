@@ -0,0 +1,88 @@
+//! A bare TLS handshake, timed on its own for connectivity diagnostics.
+//!
+//! reqwest does not report how long each phase of a request took, so callers
+//! that need to tell a TLS failure apart from a connect failure open a socket
+//! themselves and hand it to [`tls_handshake`].
+
+use std::io;
+use std::net::TcpStream;
+use std::sync::Arc;
+use std::time::Duration;
+
+use rustls::ClientConfig;
+use rustls::ClientConnection;
+use rustls::RootCertStore;
+use rustls::pki_types::ServerName;
+
+use crate::spki_pinning::native_roots;
+
+/// TLS settings that validate the chain against the system certificate store,
+/// as an unpinned provider client does.
+pub fn native_tls_config() -> Result<ClientConfig, String> {
+    tls_config(native_roots()?)
+}
+
+fn tls_config(roots: RootCertStore) -> Result<ClientConfig, String> {
+    let provider = Arc::new(rustls::crypto::ring::default_provider());
+    let mut config = ClientConfig::builder_with_provider(provider)
+        .with_safe_default_protocol_versions()
+        .map_err(|err| err.to_string())?
+        .with_root_certificates(roots)
+        .with_no_client_auth();
+    config.alpn_protocols = vec![b"h2".to_vec(), b"http/1.1".to_vec()];
+    Ok(config)
+}
+
+/// Complete a TLS handshake with `host` over `stream`, giving up once a read
+/// or write stalls for `timeout`. Blocks the calling thread.
+pub fn tls_handshake(
+    mut stream: TcpStream,
+    host: &str,
+    config: ClientConfig,
+    timeout: Duration,
+) -> io::Result<()> {
+    let server_name = ServerName::try_from(host.to_string()).map_err(|err| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("`{host}` is not a valid TLS server name: {err}"),
+        )
+    })?;
+    stream.set_read_timeout(Some(timeout))?;
+    stream.set_write_timeout(Some(timeout))?;
+    let mut conn = ClientConnection::new(Arc::new(config), server_name)
+        .map_err(|err| io::Error::other(err.to_string()))?;
+    while conn.is_handshaking() {
+        conn.complete_io(&mut stream)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Read;
+    use std::io::Write;
+    use std::net::TcpListener;
+
+    #[test]
+    fn handshake_with_a_plain_http_server_fails() {
+        let listener = TcpListener::bind("127.0.0.1:0").expect("bind");
+        let addr = listener.local_addr().expect("local addr");
+        let server = std::thread::spawn(move || {
+            let (mut socket, _) = listener.accept().expect("accept");
+            let mut buf = [0u8; 1024];
+            let _ = socket.read(&mut buf);
+            let _ = socket.write_all(b"HTTP/1.1 400 Bad Request\r\ncontent-length: 0\r\n\r\n");
+        });
+
+        let stream = TcpStream::connect(addr).expect("connect");
+        let config = tls_config(RootCertStore::empty()).expect("tls config");
+        let result = tls_handshake(stream, "localhost", config, Duration::from_secs(5));
+
+        assert!(
+            result.is_err(),
+            "expected the handshake to fail: {result:?}"
+        );
+        server.join().expect("server thread");
+    }
+}
@@ -3,8 +3,10 @@ use crate::default_client::CodexRequestBuilder;
 use crate::error::TransportError;
 use crate::request::Request;
 use crate::request::Response;
+use crate::spki_pinning::find_pin_mismatch;
 use async_trait::async_trait;
 use bytes::Bytes;
+use bytes::BytesMut;
 use futures::StreamExt;
 use futures::stream::BoxStream;
 use http::HeaderMap;
@@ -57,9 +59,37 @@ impl ReqwestTransport {
         Ok(builder)
     }
 
+    /// Read the decoded body chunk by chunk, failing at the first chunk that
+    /// would take it past `limit`, so no more than `limit` bytes are ever
+    /// held. A `Content-Length` over the limit fails before anything is read.
+    async fn read_body_with_limit(
+        resp: reqwest::Response,
+        limit: usize,
+    ) -> Result<Bytes, TransportError> {
+        if resp
+            .content_length()
+            .and_then(|len| usize::try_from(len).ok())
+            .is_some_and(|len| len > limit)
+        {
+            return Err(TransportError::ResponseTooLarge { limit });
+        }
+        let mut body = BytesMut::new();
+        let mut chunks = resp.bytes_stream();
+        while let Some(chunk) = chunks.next().await {
+            let chunk = chunk.map_err(Self::map_error)?;
+            if body.len() + chunk.len() > limit {
+                return Err(TransportError::ResponseTooLarge { limit });
+            }
+            body.extend_from_slice(&chunk);
+        }
+        Ok(body.freeze())
+    }
+
     fn map_error(err: reqwest::Error) -> TransportError {
         if err.is_timeout() {
             TransportError::Timeout
+        } else if let Some(mismatch) = find_pin_mismatch(&err) {
+            TransportError::SpkiPinMismatch(mismatch)
         } else {
             TransportError::Network(err.to_string())
         }
@@ -78,11 +108,15 @@ impl HttpTransport for ReqwestTransport {
             );
         }
 
+        let max_response_bytes = req.max_response_bytes;
         let builder = self.build(req)?;
         let resp = builder.send().await.map_err(Self::map_error)?;
         let status = resp.status();
         let headers = resp.headers().clone();
-        let bytes = resp.bytes().await.map_err(Self::map_error)?;
+        let bytes = match max_response_bytes {
+            Some(limit) => Self::read_body_with_limit(resp, limit).await?,
+            None => resp.bytes().await.map_err(Self::map_error)?,
+        };
         if !status.is_success() {
             let body = String::from_utf8(bytes.to_vec()).ok();
             return Err(TransportError::Http {
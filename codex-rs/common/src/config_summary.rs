@@ -1,5 +1,6 @@
 use codex_core::WireApi;
 use codex_core::config::Config;
+use codex_protocol::openai_models::EffortSelection;
 
 use crate::sandbox_summary::summarize_sandbox_policy;
 
@@ -21,7 +22,7 @@ pub fn create_config_summary_entries(config: &Config, model: &str) -> Vec<(&'sta
             .map(|effort| effort.to_string());
         entries.push((
             "reasoning effort",
-            reasoning_effort.unwrap_or_else(|| "none".to_string()),
+            reasoning_effort.unwrap_or_else(|| EffortSelection::AUTO.to_string()),
         ));
         entries.push((
             "reasoning summaries",
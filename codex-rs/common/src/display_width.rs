@@ -0,0 +1,93 @@
+//! Fitting text into terminal columns.
+//!
+//! Model names from gateways can contain emoji, CJK, and combining
+//! characters, whose byte or char counts say nothing about how many columns
+//! they take. These helpers measure terminal width instead, and only ever
+//! cut between grapheme clusters so a flag or an accented letter is never
+//! split in half.
+
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
+
+const ELLIPSIS: &str = "…";
+
+/// How many terminal columns `text` takes.
+pub fn display_width(text: &str) -> usize {
+    UnicodeWidthStr::width(text)
+}
+
+/// `text` cut to at most `width` columns, ending in `…` when anything was
+/// cut.
+pub fn truncate_to_width(text: &str, width: usize) -> String {
+    if display_width(text) <= width {
+        return text.to_string();
+    }
+    let Some(budget) = width.checked_sub(display_width(ELLIPSIS)) else {
+        return String::new();
+    };
+    let mut truncated = String::new();
+    let mut used = 0;
+    for grapheme in text.graphemes(true) {
+        let grapheme_width = display_width(grapheme);
+        if used + grapheme_width > budget {
+            break;
+        }
+        used += grapheme_width;
+        truncated.push_str(grapheme);
+    }
+    truncated.push_str(ELLIPSIS);
+    truncated
+}
+
+/// `text` padded with spaces to `width` columns. Text that is already wider
+/// is returned as is.
+pub fn pad_to_width(text: &str, width: usize) -> String {
+    let padding = width.saturating_sub(display_width(text));
+    format!("{text}{}", " ".repeat(padding))
+}
+
+/// `text` truncated to `width` columns and then padded to exactly `width`,
+/// for a table cell.
+pub fn fit_to_width(text: &str, width: usize) -> String {
+    pad_to_width(&truncate_to_width(text, width), width)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn width_counts_columns_not_bytes() {
+        assert_eq!(display_width("gpt-5.1"), 7);
+        assert_eq!(display_width("模型"), 4);
+        assert_eq!(display_width("🚀"), 2);
+        assert_eq!(display_width("cafe\u{301}"), 4);
+    }
+
+    #[test]
+    fn truncation_cuts_between_graphemes() {
+        assert_eq!(truncate_to_width("gpt-5.1-codex", 8), "gpt-5.1…");
+        assert_eq!(truncate_to_width("gpt-5.1", 8), "gpt-5.1");
+        // A wide character that would straddle the edge is dropped whole.
+        assert_eq!(truncate_to_width("模型大全", 6), "模型…");
+        assert_eq!(truncate_to_width("模型大全", 5), "模型…");
+        assert_eq!(truncate_to_width("🚀🚀🚀", 4), "🚀…");
+        // The combining accent stays with its letter.
+        assert_eq!(truncate_to_width("cafe\u{301}s", 5), "cafe\u{301}…");
+        assert_eq!(truncate_to_width("gpt", 1), "…");
+        assert_eq!(truncate_to_width("gpt", 0), "");
+    }
+
+    #[test]
+    fn fitting_pads_to_the_exact_width() {
+        assert_eq!(pad_to_width("模型", 6), "模型  ");
+        assert_eq!(pad_to_width("gpt-5.1-codex", 4), "gpt-5.1-codex");
+        for name in ["gpt-5.1-codex", "模型大全", "🚀 rocket", "cafe\u{301}"] {
+            for width in 1..10 {
+                let cell = fit_to_width(name, width);
+                assert_eq!(display_width(&cell), width, "{name:?} at {width}");
+            }
+        }
+    }
+}
@@ -13,6 +13,12 @@ mod sandbox_mode_cli_arg;
 #[cfg(feature = "cli")]
 pub use sandbox_mode_cli_arg::SandboxModeCliArg;
 
+#[cfg(feature = "cli")]
+mod model_cli_arg;
+
+#[cfg(feature = "cli")]
+pub use model_cli_arg::ModelValueParser;
+
 #[cfg(feature = "cli")]
 pub mod format_env_display;
 
@@ -30,6 +36,23 @@ pub use sandbox_summary::summarize_sandbox_policy;
 mod config_summary;
 
 pub use config_summary::create_config_summary_entries;
+
+mod model_matrix;
+
+pub use model_matrix::enumerate_model_effort_matrix;
+
+mod model_fields;
+
+pub use model_fields::plain_model_fields;
+
+mod model_search;
+
+pub use model_search::search_model_presets;
+
+#[cfg(any(test, feature = "test-support"))]
+pub mod test_catalog;
+// Display-width-aware truncation and padding for terminal columns
+pub mod display_width;
 // Shared fuzzy matcher (used by TUI selection popups and other UI filtering)
 pub mod fuzzy_match;
 // Shared approval presets (AskForApproval + Sandbox) used by TUI and MCP server
@@ -0,0 +1,70 @@
+//! Value parser for the `--model` (`-m`) CLI option.
+//!
+//! Any model id is accepted because custom providers serve models that are
+//! only known at runtime. The built-in presets are reported as possible
+//! values so that `--help` lists them and generated shell completions can
+//! offer them; `codex models list --ids` covers the rest at runtime.
+
+use clap::builder::PossibleValue;
+use clap::builder::TypedValueParser;
+use codex_core::models_manager::catalog::CatalogLoadOptions;
+use codex_core::models_manager::catalog::PresetCatalog;
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct ModelValueParser;
+
+impl TypedValueParser for ModelValueParser {
+    type Value = String;
+
+    fn parse_ref(
+        &self,
+        cmd: &clap::Command,
+        arg: Option<&clap::Arg>,
+        value: &std::ffi::OsStr,
+    ) -> Result<Self::Value, clap::Error> {
+        clap::builder::StringValueParser::new().parse_ref(cmd, arg, value)
+    }
+
+    fn possible_values(&self) -> Option<Box<dyn Iterator<Item = PossibleValue> + '_>> {
+        Some(Box::new(
+            PresetCatalog::static_presets(CatalogLoadOptions::default()).map(|preset| {
+                PossibleValue::new(preset.model.as_str()).help(preset.description.as_str())
+            }),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+    use pretty_assertions::assert_eq;
+
+    #[derive(Debug, Parser)]
+    struct Cli {
+        #[arg(long, short = 'm', value_parser = ModelValueParser)]
+        model: Option<String>,
+    }
+
+    #[test]
+    fn accepts_models_outside_the_builtin_catalog() {
+        let cli =
+            Cli::try_parse_from(["codex", "-m", "oca-large"]).unwrap_or_else(|err| panic!("{err}"));
+        assert_eq!(cli.model.as_deref(), Some("oca-large"));
+    }
+
+    #[test]
+    fn reports_builtin_models_as_possible_values() {
+        let values: Vec<String> = ModelValueParser
+            .possible_values()
+            .into_iter()
+            .flatten()
+            .map(|value| value.get_name().to_string())
+            .collect();
+        let builtin: Vec<String> = PresetCatalog::static_presets(CatalogLoadOptions::default())
+            .map(|preset| preset.model.clone())
+            .collect();
+        assert_eq!(values, builtin);
+        assert!(values.iter().any(|model| model == "gpt-5.1-codex-max"));
+    }
+}
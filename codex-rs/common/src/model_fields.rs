@@ -0,0 +1,117 @@
+use codex_protocol::openai_models::ModelPreset;
+
+/// The fields of `preset` as labeled values, for listings that screen
+/// readers read out line by line rather than as a table.
+///
+/// The order never changes: name, model, id, provider, default, description,
+/// default effort, efforts, context window, knowledge cutoff, released, and
+/// unavailable for this account. A field the preset does not have is left
+/// out, and the others keep their places.
+pub fn plain_model_fields(preset: &ModelPreset) -> Vec<(&'static str, String)> {
+    let efforts: Vec<String> = preset
+        .supported_reasoning_efforts
+        .iter()
+        .map(|option| option.effort.to_string())
+        .collect();
+    let fields = [
+        ("Name", Some(preset.display_name.clone())),
+        ("Model", Some(preset.model.clone())),
+        ("Id", Some(preset.id.clone())),
+        (
+            "Provider",
+            Some(
+                preset
+                    .model_provider
+                    .clone()
+                    .unwrap_or_else(|| "built-in".to_string()),
+            ),
+        ),
+        (
+            "Default",
+            Some(if preset.is_default { "yes" } else { "no" }.to_string()),
+        ),
+        (
+            "Description",
+            (!preset.description.is_empty()).then(|| preset.description.clone()),
+        ),
+        (
+            "Default effort",
+            (!efforts.is_empty()).then(|| preset.default_reasoning_effort.to_string()),
+        ),
+        ("Efforts", (!efforts.is_empty()).then(|| efforts.join(", "))),
+        (
+            "Context window",
+            preset
+                .context_window
+                .map(|tokens| format!("{tokens} tokens")),
+        ),
+        ("Knowledge cutoff", preset.knowledge_cutoff.clone()),
+        ("Released", preset.released_at.clone()),
+        (
+            "Unavailable for this account",
+            preset.unavailable_for_account.clone(),
+        ),
+    ];
+    fields
+        .into_iter()
+        .filter_map(|(label, value)| Some((label, value?)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_catalog::PresetBuilderExt;
+    use crate::test_catalog::preset;
+    use crate::test_catalog::provider_preset;
+    use codex_protocol::openai_models::ReasoningEffort;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn fields_keep_their_order_and_missing_ones_are_left_out() {
+        let mut full = provider_preset("gateway", "oca-large")
+            .display_name("OCA Large")
+            .description("Large model")
+            .efforts([ReasoningEffort::Low, ReasoningEffort::High])
+            .default_reasoning_effort(ReasoningEffort::High)
+            .default()
+            .build()
+            .expect("valid preset");
+        full.context_window = Some(272_000);
+        full.knowledge_cutoff = Some("2025-01-31".to_string());
+        full.released_at = Some("2025-06-01".to_string());
+        full.unavailable_for_account = Some("not on your plan".to_string());
+
+        assert_eq!(
+            plain_model_fields(&full),
+            vec![
+                ("Name", "OCA Large".to_string()),
+                ("Model", "oca-large".to_string()),
+                ("Id", "gateway/oca-large".to_string()),
+                ("Provider", "gateway".to_string()),
+                ("Default", "yes".to_string()),
+                ("Description", "Large model".to_string()),
+                ("Default effort", "high".to_string()),
+                ("Efforts", "low, high".to_string()),
+                ("Context window", "272000 tokens".to_string()),
+                ("Knowledge cutoff", "2025-01-31".to_string()),
+                ("Released", "2025-06-01".to_string()),
+                (
+                    "Unavailable for this account",
+                    "not on your plan".to_string()
+                ),
+            ]
+        );
+
+        assert_eq!(
+            plain_model_fields(&preset("oca-small").build().expect("valid preset")),
+            vec![
+                ("Name", "oca-small".to_string()),
+                ("Model", "oca-small".to_string()),
+                ("Id", "oca-small".to_string()),
+                ("Provider", "built-in".to_string()),
+                ("Default", "no".to_string()),
+            ]
+        );
+    }
+}
@@ -0,0 +1,92 @@
+use codex_protocol::openai_models::ModelPreset;
+use codex_protocol::openai_models::ReasoningEffort;
+
+/// Every `(preset, effort)` pair a harness can run, for instance to evaluate
+/// each model at each effort it supports.
+///
+/// Presets keep their catalog order and efforts the order the preset lists
+/// them in, so the same catalog always yields the same matrix. A preset
+/// without configurable efforts yields a single `None` entry. Presets hidden
+/// from the picker are skipped unless `include_hidden` is set.
+pub fn enumerate_model_effort_matrix(
+    catalog: &[ModelPreset],
+    include_hidden: bool,
+) -> Vec<(ModelPreset, Option<ReasoningEffort>)> {
+    let mut matrix = Vec::new();
+    for preset in catalog
+        .iter()
+        .filter(|preset| include_hidden || preset.show_in_picker)
+    {
+        if preset.supported_reasoning_efforts.is_empty() {
+            matrix.push((preset.clone(), None));
+            continue;
+        }
+        let mut efforts: Vec<ReasoningEffort> = Vec::new();
+        for option in &preset.supported_reasoning_efforts {
+            if !efforts.contains(&option.effort) {
+                efforts.push(option.effort);
+            }
+        }
+        matrix.extend(
+            efforts
+                .into_iter()
+                .map(|effort| (preset.clone(), Some(effort))),
+        );
+    }
+    matrix
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_catalog::PresetBuilderExt;
+    use crate::test_catalog::catalog;
+    use crate::test_catalog::preset;
+    use pretty_assertions::assert_eq;
+
+    fn pairs(matrix: &[(ModelPreset, Option<ReasoningEffort>)]) -> Vec<(&str, Option<String>)> {
+        matrix
+            .iter()
+            .map(|(preset, effort)| (preset.id.as_str(), effort.map(|effort| effort.to_string())))
+            .collect()
+    }
+
+    #[test]
+    fn matrix_keeps_catalog_and_effort_order() {
+        let catalog = catalog([
+            preset("oca-large").efforts([ReasoningEffort::High, ReasoningEffort::Low]),
+            preset("oca-plain"),
+            preset("oca-small").efforts([ReasoningEffort::Medium]),
+        ]);
+
+        assert_eq!(
+            pairs(&enumerate_model_effort_matrix(&catalog, false)),
+            vec![
+                ("oca-large", Some("high".to_string())),
+                ("oca-large", Some("low".to_string())),
+                ("oca-plain", None),
+                ("oca-small", Some("medium".to_string())),
+            ]
+        );
+    }
+
+    #[test]
+    fn hidden_presets_are_excluded_unless_requested() {
+        let catalog = catalog([
+            preset("oca-old").efforts([ReasoningEffort::Low]).hidden(),
+            preset("oca-new").efforts([ReasoningEffort::Low]),
+        ]);
+
+        assert_eq!(
+            pairs(&enumerate_model_effort_matrix(&catalog, false)),
+            vec![("oca-new", Some("low".to_string()))]
+        );
+        assert_eq!(
+            pairs(&enumerate_model_effort_matrix(&catalog, true)),
+            vec![
+                ("oca-old", Some("low".to_string())),
+                ("oca-new", Some("low".to_string())),
+            ]
+        );
+    }
+}
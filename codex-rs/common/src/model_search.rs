@@ -0,0 +1,73 @@
+use codex_protocol::openai_models::ModelPreset;
+
+use crate::fuzzy_match::fuzzy_match;
+
+/// The presets whose display name or id fuzzy-matches `query`, best match
+/// first, for pickers and paged listings that filter the catalog.
+///
+/// Presets matching equally well keep their catalog order, so the same
+/// catalog and query always give the same list, and pages cut from it line
+/// up. A blank query matches every preset.
+pub fn search_model_presets<'a>(catalog: &'a [ModelPreset], query: &str) -> Vec<&'a ModelPreset> {
+    let query = query.trim();
+    if query.is_empty() {
+        return catalog.iter().collect();
+    }
+    let mut ranked: Vec<(i32, &ModelPreset)> = catalog
+        .iter()
+        .filter_map(|preset| {
+            [preset.display_name.as_str(), preset.id.as_str()]
+                .into_iter()
+                .filter_map(|candidate| fuzzy_match(candidate, query).map(|(_, score)| score))
+                .min()
+                .map(|score| (score, preset))
+        })
+        .collect();
+    ranked.sort_by_key(|(score, _)| *score);
+    ranked.into_iter().map(|(_, preset)| preset).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_catalog::catalog;
+    use crate::test_catalog::preset;
+    use pretty_assertions::assert_eq;
+
+    fn ids(presets: &[&ModelPreset]) -> Vec<String> {
+        presets.iter().map(|preset| preset.id.clone()).collect()
+    }
+
+    #[test]
+    fn best_matches_come_first_and_ties_keep_catalog_order() {
+        let catalog = catalog([
+            preset("oca-9-9"),
+            preset("oca-large-099"),
+            preset("oca-small").display_name("Small 99"),
+            preset("oca-199"),
+            preset("oca-medium"),
+            preset("99-turbo"),
+        ]);
+
+        assert_eq!(
+            ids(&search_model_presets(&catalog, " 99 ")),
+            vec![
+                "99-turbo",
+                "oca-large-099",
+                "oca-small",
+                "oca-199",
+                "oca-9-9"
+            ]
+        );
+    }
+
+    #[test]
+    fn a_blank_query_matches_everything_in_catalog_order() {
+        let catalog = catalog([preset("oca-b"), preset("oca-a")]);
+
+        assert_eq!(
+            ids(&search_model_presets(&catalog, "  ")),
+            vec!["oca-b", "oca-a"]
+        );
+    }
+}
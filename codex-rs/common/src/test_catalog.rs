@@ -0,0 +1,346 @@
+//! Fake model catalogs for tests.
+//!
+//! [`preset`] starts a [`ModelPreset::builder`], [`PresetBuilderExt`] adds
+//! shorthands to it, and [`catalog`] puts several presets together, so a
+//! test names only what it cares about:
+//!
+//! ```ignore
+//! let presets = catalog([
+//!     preset("oca-large").efforts([Low, High]).default(),
+//!     preset("oca-small").hidden(),
+//! ]);
+//! ```
+//!
+//! Whatever is asked for, the result satisfies [`check_catalog`]. Tests of
+//! code that must reject bad catalogs start from the [`invalid`] helpers
+//! instead. Only built for this crate's tests and with the `test-support`
+//! feature, which crates enable as a dev-dependency.
+
+use std::collections::HashSet;
+
+use codex_protocol::openai_models::ModelPreset;
+use codex_protocol::openai_models::ModelPresetBuilder;
+use codex_protocol::openai_models::ModelPresetError;
+use codex_protocol::openai_models::ReasoningEffort;
+use codex_protocol::openai_models::ReasoningEffortPreset;
+
+/// Start a preset with id and model slug `id`. As for any
+/// [`ModelPreset::builder`], it offers no reasoning efforts, is listed in the
+/// picker, is served by the session's provider, and is not the default.
+pub fn preset(id: &str) -> ModelPresetBuilder {
+    ModelPreset::builder(id, id)
+}
+
+/// Start a preset for `model` served from the catalog of provider
+/// `provider_id`, under the namespaced id `<provider_id>/<model>`.
+pub fn provider_preset(provider_id: &str, model: &str) -> ModelPresetBuilder {
+    ModelPreset::builder(format!("{provider_id}/{model}"), model)
+        .model_provider(provider_id.to_string())
+}
+
+/// `n` listed presets, `model-0` to `model-{n-1}`, offering low, medium and
+/// high effort with medium as their default. `model-0` is the default.
+pub fn fake_catalog(n: usize) -> Vec<ModelPreset> {
+    catalog((0..n).map(|index| {
+        preset(&format!("model-{index}"))
+            .efforts([
+                ReasoningEffort::Low,
+                ReasoningEffort::Medium,
+                ReasoningEffort::High,
+            ])
+            .default_reasoning_effort(ReasoningEffort::Medium)
+    }))
+}
+
+/// The presets in order, with exactly one default: the one marked
+/// [`PresetBuilderExt::default`], or else the first that [`check_catalog`]
+/// allows to be the default.
+///
+/// Panics when a preset does not build, two presets share an id or several
+/// are marked default, since that catalog could not come out of the models
+/// manager; use [`invalid`] for those.
+pub fn catalog(presets: impl IntoIterator<Item = ModelPresetBuilder>) -> Vec<ModelPreset> {
+    let mut presets: Vec<ModelPreset> = presets
+        .into_iter()
+        .map(|builder| {
+            builder
+                .build()
+                .unwrap_or_else(|err| panic!("fake preset is invalid: {err}"))
+        })
+        .collect();
+    if !presets.iter().any(|preset| preset.is_default)
+        && let Some(preset) = presets.iter_mut().find(|preset| can_be_default(preset))
+    {
+        preset.is_default = true;
+    }
+    if let Err(violation) = check_catalog(&presets) {
+        panic!("fake catalog is invalid: {violation}");
+    }
+    presets
+}
+
+/// Shorthands for the settings tests change most often.
+pub trait PresetBuilderExt: Sized {
+    /// Offer `efforts`, in order, each described by its name. Repeats are
+    /// dropped. The default effort becomes the first; set
+    /// [`ModelPresetBuilder::default_reasoning_effort`] afterwards to pick
+    /// another of them.
+    fn efforts(self, efforts: impl IntoIterator<Item = ReasoningEffort>) -> Self;
+
+    /// Make this the catalog's default preset.
+    fn default(self) -> Self;
+
+    /// Hide the preset from the picker.
+    fn hidden(self) -> Self;
+}
+
+impl PresetBuilderExt for ModelPresetBuilder {
+    fn efforts(self, efforts: impl IntoIterator<Item = ReasoningEffort>) -> Self {
+        let mut offered: Vec<ReasoningEffort> = Vec::new();
+        for effort in efforts {
+            if !offered.contains(&effort) {
+                offered.push(effort);
+            }
+        }
+        let builder = match offered.first() {
+            Some(&first) => self.default_reasoning_effort(first),
+            None => self,
+        };
+        builder.supported_reasoning_efforts(offered.into_iter().map(effort_preset).collect())
+    }
+
+    fn default(self) -> Self {
+        self.is_default(true)
+    }
+
+    fn hidden(self) -> Self {
+        self.show_in_picker(false)
+    }
+}
+
+fn effort_preset(effort: ReasoningEffort) -> ReasoningEffortPreset {
+    ReasoningEffortPreset {
+        effort,
+        description: effort.to_string(),
+        max_output_tokens: None,
+        request_timeout_secs: None,
+        stream_idle_timeout_secs: None,
+    }
+}
+
+/// What [`check_catalog`] found wrong with a catalog.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CatalogViolation {
+    InvalidPreset(ModelPresetError),
+    DuplicateId(String),
+    /// Presets could be the default, but none is.
+    NoDefault,
+    /// More than one preset is marked default, listed by id.
+    SeveralDefaults(Vec<String>),
+    /// The default is hidden or only served by its own provider.
+    DefaultNotAllowed(String),
+}
+
+impl std::fmt::Display for CatalogViolation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidPreset(err) => write!(f, "{err}"),
+            Self::DuplicateId(id) => write!(f, "two presets have the id `{id}`"),
+            Self::NoDefault => write!(f, "no preset is the default"),
+            Self::SeveralDefaults(ids) => {
+                write!(f, "presets {} are all marked default", ids.join(", "))
+            }
+            Self::DefaultNotAllowed(id) => write!(
+                f,
+                "default preset `{id}` is hidden or only served by its own provider"
+            ),
+        }
+    }
+}
+
+/// Check what the models manager guarantees of the catalogs it serves: each
+/// preset is valid, ids are unique, and exactly one preset is the default,
+/// one listed in the picker and served by the session's provider. A catalog
+/// with no such preset has no default.
+pub fn check_catalog(presets: &[ModelPreset]) -> Result<(), CatalogViolation> {
+    let mut ids = HashSet::new();
+    for preset in presets {
+        preset.validate().map_err(CatalogViolation::InvalidPreset)?;
+        if !ids.insert(preset.id.as_str()) {
+            return Err(CatalogViolation::DuplicateId(preset.id.clone()));
+        }
+    }
+    let defaults: Vec<&ModelPreset> = presets.iter().filter(|preset| preset.is_default).collect();
+    match defaults.as_slice() {
+        [] if presets.iter().any(can_be_default) => Err(CatalogViolation::NoDefault),
+        [] => Ok(()),
+        [preset] if !can_be_default(preset) => {
+            Err(CatalogViolation::DefaultNotAllowed(preset.id.clone()))
+        }
+        [_] => Ok(()),
+        several => Err(CatalogViolation::SeveralDefaults(
+            several.iter().map(|preset| preset.id.clone()).collect(),
+        )),
+    }
+}
+
+fn can_be_default(preset: &ModelPreset) -> bool {
+    preset.show_in_picker && preset.model_provider.is_none()
+}
+
+/// Catalogs that break one invariant each, for tests of code that must
+/// notice. Apart from that one invariant they are [`fake_catalog`]s.
+pub mod invalid {
+    use super::*;
+
+    /// `model-1` is marked default as well as `model-0`.
+    pub fn two_defaults() -> Vec<ModelPreset> {
+        let mut presets = fake_catalog(3);
+        presets[1].is_default = true;
+        presets
+    }
+
+    /// No preset is the default.
+    pub fn no_default() -> Vec<ModelPreset> {
+        let mut presets = fake_catalog(3);
+        presets[0].is_default = false;
+        presets
+    }
+
+    /// The default, `model-0`, is hidden from the picker.
+    pub fn hidden_default() -> Vec<ModelPreset> {
+        let mut presets = fake_catalog(3);
+        presets[0].show_in_picker = false;
+        presets
+    }
+
+    /// `model-1` reuses the id of `model-0`.
+    pub fn duplicate_ids() -> Vec<ModelPreset> {
+        let mut presets = fake_catalog(3);
+        presets[1].id = presets[0].id.clone();
+        presets
+    }
+
+    /// `model-0` defaults to an effort it does not offer.
+    pub fn unsupported_default_effort() -> Vec<ModelPreset> {
+        let mut presets = fake_catalog(3);
+        presets[0].default_reasoning_effort = ReasoningEffort::XHigh;
+        presets
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn ids(presets: &[ModelPreset]) -> Vec<(&str, bool)> {
+        presets
+            .iter()
+            .map(|preset| (preset.id.as_str(), preset.is_default))
+            .collect()
+    }
+
+    #[test]
+    fn fake_catalogs_have_one_default() {
+        let presets = fake_catalog(3);
+        assert_eq!(
+            ids(&presets),
+            vec![("model-0", true), ("model-1", false), ("model-2", false)]
+        );
+        assert_eq!(check_catalog(&presets), Ok(()));
+        assert_eq!(check_catalog(&fake_catalog(0)), Ok(()));
+    }
+
+    #[test]
+    fn the_first_preset_that_may_be_the_default_becomes_it() {
+        let presets = catalog([
+            preset("old").hidden(),
+            provider_preset("lmstudio", "qwen"),
+            preset("oca-small"),
+            preset("oca-large"),
+        ]);
+        assert_eq!(
+            ids(&presets),
+            vec![
+                ("old", false),
+                ("lmstudio/qwen", false),
+                ("oca-small", true),
+                ("oca-large", false),
+            ]
+        );
+
+        let presets = catalog([preset("oca-small"), preset("oca-large").default()]);
+        assert_eq!(
+            ids(&presets),
+            vec![("oca-small", false), ("oca-large", true)]
+        );
+    }
+
+    #[test]
+    fn efforts_default_to_the_first_offered() {
+        let first = preset("oca")
+            .efforts([
+                ReasoningEffort::High,
+                ReasoningEffort::Low,
+                ReasoningEffort::High,
+            ])
+            .build()
+            .expect("valid preset");
+        assert_eq!(first.default_reasoning_effort, ReasoningEffort::High);
+        assert_eq!(
+            first
+                .supported_reasoning_efforts
+                .iter()
+                .map(|option| option.effort)
+                .collect::<Vec<_>>(),
+            vec![ReasoningEffort::High, ReasoningEffort::Low]
+        );
+
+        let picked = preset("oca")
+            .efforts([ReasoningEffort::Low, ReasoningEffort::Medium])
+            .default_reasoning_effort(ReasoningEffort::Medium)
+            .build()
+            .expect("valid preset");
+        assert_eq!(picked.default_reasoning_effort, ReasoningEffort::Medium);
+    }
+
+    #[test]
+    #[should_panic(expected = "presets oca-small, oca-large are all marked default")]
+    fn several_defaults_are_refused() {
+        catalog([preset("oca-small").default(), preset("oca-large").default()]);
+    }
+
+    #[test]
+    fn invalid_catalogs_break_one_invariant_each() {
+        let cases = [
+            (
+                invalid::two_defaults(),
+                CatalogViolation::SeveralDefaults(vec![
+                    "model-0".to_string(),
+                    "model-1".to_string(),
+                ]),
+            ),
+            (invalid::no_default(), CatalogViolation::NoDefault),
+            (
+                invalid::hidden_default(),
+                CatalogViolation::DefaultNotAllowed("model-0".to_string()),
+            ),
+            (
+                invalid::duplicate_ids(),
+                CatalogViolation::DuplicateId("model-0".to_string()),
+            ),
+            (
+                invalid::unsupported_default_effort(),
+                CatalogViolation::InvalidPreset(ModelPresetError::UnsupportedDefaultEffort {
+                    id: "model-0".to_string(),
+                    effort: ReasoningEffort::XHigh,
+                    auth_mode: None,
+                }),
+            ),
+        ];
+        for (presets, violation) in cases {
+            assert_eq!(check_catalog(&presets), Err(violation));
+        }
+    }
+}
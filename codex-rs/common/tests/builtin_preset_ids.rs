@@ -0,0 +1,16 @@
+use codex_core::models_manager::model_presets::NAMED_PRESET_IDS;
+use codex_core::models_manager::model_presets::PLUS_RATE_LIMIT_WARNING_PRESET_IDS;
+use codex_core::models_manager::model_presets::builtin_preset_ids;
+
+/// Frontends pick these presets by name; a rename in the preset table has to
+/// carry the constants along.
+#[test]
+fn every_named_preset_is_a_builtin_preset() {
+    let ids = builtin_preset_ids();
+    let missing: Vec<&str> = NAMED_PRESET_IDS
+        .into_iter()
+        .chain(PLUS_RATE_LIMIT_WARNING_PRESET_IDS)
+        .filter(|id| !ids.contains(id))
+        .collect();
+    assert!(missing.is_empty(), "not in model_presets.toml: {missing:?}");
+}
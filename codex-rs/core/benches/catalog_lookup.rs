@@ -0,0 +1,68 @@
+//! Cost of resolving a model name in a 500-entry catalog, by a scan of the
+//! presets as lookups were done before the index, and through the index a
+//! [`CatalogSnapshot`] builds. Run with
+//! `cargo bench -p codex-core --bench catalog_lookup`.
+
+use std::hint::black_box;
+
+use codex_core::models_manager::catalog::CatalogSnapshot;
+use codex_protocol::openai_models::ModelPreset;
+use criterion::Criterion;
+use criterion::criterion_group;
+use criterion::criterion_main;
+
+const CATALOG_LEN: usize = 500;
+
+/// Gateway presets listed under aliases, with slugs and aliases shared by
+/// several presets, as in a merged catalog.
+fn catalog() -> Vec<ModelPreset> {
+    (0..CATALOG_LEN)
+        .map(|n| {
+            ModelPreset::builder(format!("gw/model-{n}"), format!("model-{}", n % 450))
+                .display_name(format!("Model {}", n % 400))
+                .build()
+                .unwrap_or_else(|err| panic!("invalid preset: {err}"))
+        })
+        .collect()
+}
+
+/// The preset named `name` by id, model slug or display name, in that order
+/// of preference, found by scanning `presets`.
+fn scan_resolve<'a>(presets: &'a [ModelPreset], name: &str) -> Option<&'a ModelPreset> {
+    presets
+        .iter()
+        .find(|preset| preset.id == name)
+        .or_else(|| presets.iter().find(|preset| preset.model == name))
+        .or_else(|| presets.iter().find(|preset| preset.display_name == name))
+}
+
+fn resolve(c: &mut Criterion) {
+    let presets = catalog();
+    let snapshot = CatalogSnapshot::new(1, presets.clone());
+    let names: Vec<&str> = presets
+        .iter()
+        .flat_map(|preset| [&preset.id, &preset.model, &preset.display_name])
+        .map(String::as_str)
+        .chain(["missing"])
+        .collect();
+
+    let mut group = c.benchmark_group(format!("resolve_every_name_in_{CATALOG_LEN}_presets"));
+    group.bench_function("scan", |b| {
+        b.iter(|| {
+            for name in &names {
+                black_box(scan_resolve(&presets, black_box(name)));
+            }
+        });
+    });
+    group.bench_function("index", |b| {
+        b.iter(|| {
+            for name in &names {
+                black_box(snapshot.resolve(black_box(name)));
+            }
+        });
+    });
+    group.finish();
+}
+
+criterion_group!(benches, resolve);
+criterion_main!(benches);
@@ -4,8 +4,11 @@ use codex_api::AuthProvider as ApiAuthProvider;
 use codex_api::TransportError;
 use codex_api::error::ApiError;
 use codex_api::rate_limits::parse_rate_limit;
+use codex_app_server_protocol::AuthMode;
 use http::HeaderMap;
+use http::StatusCode;
 use serde::Deserialize;
+use serde_json::Value;
 
 use crate::auth::CodexAuth;
 use crate::error::CodexErr;
@@ -13,6 +16,10 @@ use crate::error::RetryLimitReachedError;
 use crate::error::UnexpectedResponseError;
 use crate::error::UsageLimitReachedError;
 use crate::model_provider_info::ModelProviderInfo;
+use crate::model_provider_info::WireApi;
+use crate::models_manager::provenance::TokenFreshness;
+use crate::models_manager::provenance::TokenSource;
+use crate::token_command::token_from_command;
 use crate::token_data::PlanType;
 
 pub(crate) fn map_api_error(err: ApiError) -> CodexErr {
@@ -22,6 +29,7 @@ pub(crate) fn map_api_error(err: ApiError) -> CodexErr {
         ApiError::UsageNotIncluded => CodexErr::UsageNotIncluded,
         ApiError::Retryable { message, delay } => CodexErr::Stream(message, delay),
         ApiError::Stream(msg) => CodexErr::Stream(msg, None),
+        err @ ApiError::IdleTimeout(_) => CodexErr::Stream(err.to_string(), None),
         ApiError::Api { status, message } => CodexErr::UnexpectedStatus(UnexpectedResponseError {
             status,
             body: message,
@@ -80,12 +88,110 @@ pub(crate) fn map_api_error(err: ApiError) -> CodexErr {
                 request_id: None,
             }),
             TransportError::Timeout => CodexErr::Timeout,
+            TransportError::ResponseTooLarge { limit } => CodexErr::ResponseTooLarge { limit },
+            TransportError::SpkiPinMismatch(mismatch) => CodexErr::SpkiPinMismatch(mismatch),
             TransportError::Network(msg) | TransportError::Build(msg) => {
                 CodexErr::Stream(msg, None)
             }
         },
         ApiError::RateLimit(msg) => CodexErr::Stream(msg, None),
+        ApiError::UnexpectedContent {
+            content_type,
+            snippet,
+        } => CodexErr::UnexpectedContent {
+            content_type,
+            snippet,
+        },
+    }
+}
+
+/// Error codes providers return when the requested model, or the Azure
+/// deployment standing in for it, does not exist.
+const MODEL_NOT_FOUND_CODES: &[&str] = &[
+    "model_not_found",
+    "model_not_available",
+    "invalid_model",
+    "DeploymentNotFound",
+];
+
+/// The provider's message when `err` says the requested model is not served,
+/// or `None` for any other error. Both wire APIs recognize the structured
+/// error codes above on a 400 or 404. OpenAI-compatible chat gateways often
+/// only say so in prose, so for `WireApi::Chat` a 404 whose message reports a
+/// missing model counts as well.
+pub(crate) fn model_unavailable_message(err: &ApiError, wire_api: WireApi) -> Option<String> {
+    let ApiError::Transport(TransportError::Http { status, body, .. }) = err else {
+        return None;
+    };
+    if *status != StatusCode::NOT_FOUND && *status != StatusCode::BAD_REQUEST {
+        return None;
+    }
+    let (codes, message) = error_codes_and_message(body.as_deref().unwrap_or_default());
+    let coded = codes
+        .iter()
+        .any(|code| MODEL_NOT_FOUND_CODES.contains(&code.as_str()));
+    let described = wire_api == WireApi::Chat
+        && *status == StatusCode::NOT_FOUND
+        && reports_missing_model(&message);
+    (coded || described).then_some(message)
+}
+
+/// Error codes providers return when the account may not use the model.
+const MODEL_FORBIDDEN_CODES: &[&str] = &[
+    "model_not_permitted",
+    "model_access_denied",
+    "permission_denied",
+    "insufficient_permissions",
+];
+
+/// The provider's message when `err` is a 403 refusing the requested model
+/// to the signed-in account. The error code above, or a message that names
+/// the model, tells it apart from other 403s such as a Cloudflare block.
+pub(crate) fn model_forbidden_message(err: &ApiError) -> Option<String> {
+    let ApiError::Transport(TransportError::Http { status, body, .. }) = err else {
+        return None;
+    };
+    if *status != StatusCode::FORBIDDEN {
+        return None;
     }
+    let body = body.as_deref().unwrap_or_default();
+    if body.contains("Cloudflare") {
+        return None;
+    }
+    let (codes, message) = error_codes_and_message(body);
+    let coded = codes
+        .iter()
+        .any(|code| MODEL_FORBIDDEN_CODES.contains(&code.as_str()));
+    (coded || message.to_ascii_lowercase().contains("model")).then_some(message)
+}
+
+/// The `code` and `type` of a provider error body, and its message or the
+/// whole body when it has none.
+fn error_codes_and_message(body: &str) -> (Vec<String>, String) {
+    let json = serde_json::from_str::<Value>(body).ok();
+    // OpenAI nests the details under `error`; some gateways put them at the
+    // top level.
+    let details = json.as_ref().map(|json| {
+        json.get("error")
+            .filter(|error| error.is_object())
+            .unwrap_or(json)
+    });
+    let field = |name: &str| details.and_then(|details| details.get(name)?.as_str());
+    let codes = ["code", "type"]
+        .into_iter()
+        .filter_map(field)
+        .map(str::to_string)
+        .collect();
+    let message = field("message").unwrap_or(body).trim().to_string();
+    (codes, message)
+}
+
+fn reports_missing_model(message: &str) -> bool {
+    let message = message.to_ascii_lowercase();
+    (message.contains("model") || message.contains("deployment"))
+        && ["not found", "does not exist", "no such"]
+            .iter()
+            .any(|phrase| message.contains(phrase))
 }
 
 fn extract_request_id(headers: Option<&HeaderMap>) -> Option<String> {
@@ -108,6 +214,16 @@ pub(crate) async fn auth_provider_from_auth(
         return Ok(CoreAuthProvider {
             token: Some(api_key),
             account_id: None,
+            token_freshness: None,
+        });
+    }
+
+    if let Some(command) = &provider.token_command {
+        let minted = token_from_command(command, provider.token_command_ttl()).await?;
+        return Ok(CoreAuthProvider {
+            token: Some(minted.token),
+            account_id: None,
+            token_freshness: Some(minted.freshness),
         });
     }
 
@@ -115,23 +231,48 @@ pub(crate) async fn auth_provider_from_auth(
         return Ok(CoreAuthProvider {
             token: Some(token),
             account_id: None,
+            token_freshness: None,
         });
     }
 
     if let Some(auth) = auth {
+        let refreshed_before = auth.last_refresh();
         let token = auth.get_token().await?;
+        let source = if auth.last_refresh() == refreshed_before {
+            TokenSource::Cached
+        } else {
+            TokenSource::Refreshed
+        };
         Ok(CoreAuthProvider {
             token: Some(token),
             account_id: auth.get_account_id(),
+            token_freshness: chatgpt_token_freshness(&auth, source),
         })
     } else {
         Ok(CoreAuthProvider {
             token: None,
             account_id: None,
+            token_freshness: None,
         })
     }
 }
 
+/// How old `auth`'s ChatGPT access token is, counted from its last refresh,
+/// with the time left until [`CodexAuth::get_token`] refreshes it again.
+fn chatgpt_token_freshness(auth: &CodexAuth, source: TokenSource) -> Option<TokenFreshness> {
+    if auth.mode != AuthMode::ChatGPT {
+        return None;
+    }
+    let now = Utc::now();
+    let millis =
+        |duration: chrono::Duration| u64::try_from(duration.num_milliseconds()).unwrap_or(0);
+    Some(TokenFreshness {
+        source,
+        age_ms: millis(now.signed_duration_since(auth.last_refresh()?)),
+        expires_in_ms: millis(auth.refresh_due_at()?.signed_duration_since(now)),
+    })
+}
+
 #[derive(Debug, Deserialize)]
 struct UsageErrorResponse {
     error: UsageErrorBody,
@@ -149,6 +290,24 @@ struct UsageErrorBody {
 pub(crate) struct CoreAuthProvider {
     token: Option<String>,
     account_id: Option<String>,
+    token_freshness: Option<TokenFreshness>,
+}
+
+impl CoreAuthProvider {
+    /// How old the `token_command` or ChatGPT token is, when the token came
+    /// from one.
+    pub(crate) fn token_freshness(&self) -> Option<TokenFreshness> {
+        self.token_freshness
+    }
+
+    /// Mark the token as refreshed for this request, after the auth manager
+    /// refreshed it because the previous one was turned away.
+    pub(crate) fn refreshed(mut self) -> Self {
+        if let Some(freshness) = &mut self.token_freshness {
+            freshness.source = TokenSource::Refreshed;
+        }
+        self
+    }
 }
 
 impl ApiAuthProvider for CoreAuthProvider {
@@ -160,3 +319,138 @@ impl ApiAuthProvider for CoreAuthProvider {
         self.account_id.clone()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn http_error(status: StatusCode, body: &str) -> ApiError {
+        ApiError::Transport(TransportError::Http {
+            status,
+            headers: None,
+            body: Some(body.to_string()),
+        })
+    }
+
+    #[test]
+    fn recognizes_model_not_found_code() {
+        let err = http_error(
+            StatusCode::NOT_FOUND,
+            r#"{"error":{"message":"The model `gpt-x` does not exist or you do not have access to it.","type":"invalid_request_error","param":null,"code":"model_not_found"}}"#,
+        );
+        assert_eq!(
+            model_unavailable_message(&err, WireApi::Responses),
+            Some("The model `gpt-x` does not exist or you do not have access to it.".to_string())
+        );
+    }
+
+    #[test]
+    fn recognizes_azure_missing_deployment_and_top_level_codes() {
+        let azure = http_error(
+            StatusCode::NOT_FOUND,
+            r#"{"error":{"code":"DeploymentNotFound","message":"The API deployment for this resource does not exist."}}"#,
+        );
+        assert_eq!(
+            model_unavailable_message(&azure, WireApi::Responses),
+            Some("The API deployment for this resource does not exist.".to_string())
+        );
+
+        let gateway = http_error(
+            StatusCode::BAD_REQUEST,
+            r#"{"object":"error","message":"unknown model","code":"invalid_model"}"#,
+        );
+        assert_eq!(
+            model_unavailable_message(&gateway, WireApi::Chat),
+            Some("unknown model".to_string())
+        );
+    }
+
+    #[test]
+    fn chat_gateways_are_matched_on_their_message() {
+        let err = http_error(
+            StatusCode::NOT_FOUND,
+            r#"{"object":"error","message":"The model `oca-large` does not exist.","type":"NotFoundError","code":404}"#,
+        );
+        assert_eq!(
+            model_unavailable_message(&err, WireApi::Chat),
+            Some("The model `oca-large` does not exist.".to_string())
+        );
+        assert_eq!(model_unavailable_message(&err, WireApi::Responses), None);
+
+        let plain = http_error(StatusCode::NOT_FOUND, "Model not found");
+        assert_eq!(
+            model_unavailable_message(&plain, WireApi::Chat),
+            Some("Model not found".to_string())
+        );
+    }
+
+    #[test]
+    fn other_errors_are_not_model_unavailable() {
+        let missing_route = http_error(StatusCode::NOT_FOUND, "404 page not found");
+        assert_eq!(
+            model_unavailable_message(&missing_route, WireApi::Chat),
+            None
+        );
+
+        let bad_request = http_error(
+            StatusCode::BAD_REQUEST,
+            r#"{"error":{"message":"Invalid value for 'input'.","code":"invalid_value"}}"#,
+        );
+        assert_eq!(
+            model_unavailable_message(&bad_request, WireApi::Responses),
+            None
+        );
+
+        let server_error = http_error(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            r#"{"error":{"code":"model_not_found"}}"#,
+        );
+        assert_eq!(
+            model_unavailable_message(&server_error, WireApi::Responses),
+            None
+        );
+    }
+
+    #[test]
+    fn recognizes_a_model_refused_to_the_account() {
+        let coded = http_error(
+            StatusCode::FORBIDDEN,
+            r#"{"error":{"message":"Your plan does not include this model.","code":"model_not_permitted"}}"#,
+        );
+        assert_eq!(
+            model_forbidden_message(&coded),
+            Some("Your plan does not include this model.".to_string())
+        );
+
+        let described = http_error(
+            StatusCode::FORBIDDEN,
+            r#"{"error":{"message":"You do not have access to model gpt-5.2-codex."}}"#,
+        );
+        assert_eq!(
+            model_forbidden_message(&described),
+            Some("You do not have access to model gpt-5.2-codex.".to_string())
+        );
+    }
+
+    #[test]
+    fn other_forbidden_errors_are_not_model_refusals() {
+        let cloudflare = http_error(
+            StatusCode::FORBIDDEN,
+            "<html>Cloudflare: this request was blocked (model gateway)</html>",
+        );
+        assert_eq!(model_forbidden_message(&cloudflare), None);
+
+        let unrelated = http_error(
+            StatusCode::FORBIDDEN,
+            r#"{"error":{"message":"Organization is suspended."}}"#,
+        );
+        assert_eq!(model_forbidden_message(&unrelated), None);
+
+        let not_found = http_error(
+            StatusCode::NOT_FOUND,
+            r#"{"error":{"code":"model_not_permitted"}}"#,
+        );
+        assert_eq!(model_forbidden_message(&not_found), None);
+    }
+}
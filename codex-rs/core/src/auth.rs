@@ -1,5 +1,6 @@
 mod storage;
 
+use chrono::DateTime;
 use chrono::Utc;
 use reqwest::StatusCode;
 use serde::Deserialize;
@@ -234,6 +235,19 @@ impl CodexAuth {
             })
     }
 
+    /// When the ChatGPT tokens were last refreshed, as `auth.json` records.
+    pub fn last_refresh(&self) -> Option<DateTime<Utc>> {
+        self.get_current_auth_json()
+            .and_then(|auth_dot_json| auth_dot_json.last_refresh)
+    }
+
+    /// When [`Self::get_token`] refreshes the ChatGPT tokens before handing
+    /// them out again.
+    pub fn refresh_due_at(&self) -> Option<DateTime<Utc>> {
+        self.last_refresh()
+            .map(|last_refresh| last_refresh + chrono::Duration::days(TOKEN_REFRESH_INTERVAL))
+    }
+
     fn get_current_auth_json(&self) -> Option<AuthDotJson> {
         #[expect(clippy::unwrap_used)]
         self.auth_dot_json.lock().unwrap().clone()
@@ -1066,6 +1080,9 @@ pub struct AuthManager {
     inner: RwLock<CachedAuth>,
     enable_codex_api_key_env: bool,
     auth_credentials_store_mode: AuthCredentialsStoreMode,
+    /// Set on managers from [`AuthManager::signed_out`], which never load
+    /// or remove the stored credentials.
+    signed_out: bool,
 }
 
 impl AuthManager {
@@ -1090,6 +1107,7 @@ impl AuthManager {
             inner: RwLock::new(CachedAuth { auth }),
             enable_codex_api_key_env,
             auth_credentials_store_mode,
+            signed_out: false,
         }
     }
 
@@ -1109,6 +1127,7 @@ impl AuthManager {
             inner: RwLock::new(cached),
             enable_codex_api_key_env: false,
             auth_credentials_store_mode: AuthCredentialsStoreMode::File,
+            signed_out: false,
         })
     }
 
@@ -1121,6 +1140,19 @@ impl AuthManager {
             inner: RwLock::new(cached),
             enable_codex_api_key_env: false,
             auth_credentials_store_mode: AuthCredentialsStoreMode::File,
+            signed_out: false,
+        })
+    }
+
+    /// A manager with no credentials that never loads any, for requests to a
+    /// host the signed-in account's credentials were not issued for.
+    pub fn signed_out(&self) -> Arc<Self> {
+        Arc::new(Self {
+            codex_home: self.codex_home.clone(),
+            inner: RwLock::new(CachedAuth { auth: None }),
+            enable_codex_api_key_env: false,
+            auth_credentials_store_mode: self.auth_credentials_store_mode,
+            signed_out: true,
         })
     }
 
@@ -1136,6 +1168,9 @@ impl AuthManager {
     /// Force a reload of the auth information from auth.json. Returns
     /// whether the auth value changed.
     pub fn reload(&self) -> bool {
+        if self.signed_out {
+            return false;
+        }
         let new_auth = load_auth(
             &self.codex_home,
             self.enable_codex_api_key_env,
@@ -1200,6 +1235,9 @@ impl AuthManager {
     /// reloads the in‑memory auth cache so callers immediately observe the
     /// unauthenticated state.
     pub fn logout(&self) -> std::io::Result<bool> {
+        if self.signed_out {
+            return Ok(false);
+        }
         let removed = super::auth::logout(&self.codex_home, self.auth_credentials_store_mode)?;
         // Always reload to clear any cached auth (even if file absent).
         self.reload();
@@ -1209,4 +1247,12 @@ impl AuthManager {
     pub fn get_auth_mode(&self) -> Option<AuthMode> {
         self.auth().map(|a| a.mode)
     }
+
+    /// Whether the current API key came from `CODEX_API_KEY` rather than
+    /// `auth.json` or the keyring.
+    pub fn auth_from_env(&self) -> bool {
+        self.enable_codex_api_key_env
+            && self.get_auth_mode() == Some(AuthMode::ApiKey)
+            && read_codex_api_key_from_env().is_some()
+    }
 }
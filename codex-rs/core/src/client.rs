@@ -2,7 +2,10 @@ use std::sync::Arc;
 
 use crate::api_bridge::auth_provider_from_auth;
 use crate::api_bridge::map_api_error;
+use crate::api_bridge::model_forbidden_message;
+use crate::api_bridge::model_unavailable_message;
 use codex_api::AggregateStreamExt;
+use codex_api::AuthProvider as ApiAuthProvider;
 use codex_api::ChatClient as ApiChatClient;
 use codex_api::CompactClient as ApiCompactClient;
 use codex_api::CompactionInput as ApiCompactionInput;
@@ -12,6 +15,7 @@ use codex_api::ReqwestTransport;
 use codex_api::ResponseStream as ApiResponseStream;
 use codex_api::ResponsesClient as ApiResponsesClient;
 use codex_api::ResponsesOptions as ApiResponsesOptions;
+use codex_api::Sampling;
 use codex_api::SseTelemetry;
 use codex_api::TransportError;
 use codex_api::common::Reasoning;
@@ -22,6 +26,7 @@ use codex_otel::otel_manager::OtelManager;
 use codex_protocol::ConversationId;
 use codex_protocol::config_types::ReasoningSummary as ReasoningSummaryConfig;
 use codex_protocol::models::ResponseItem;
+use codex_protocol::openai_models::PromptCacheKeyStrategy;
 use codex_protocol::openai_models::ReasoningEffort as ReasoningEffortConfig;
 use codex_protocol::protocol::SessionSource;
 use eventsource_stream::Event;
@@ -32,8 +37,13 @@ use http::HeaderValue;
 use http::StatusCode as HttpStatusCode;
 use reqwest::StatusCode;
 use serde_json::Value;
+use sha2::Digest;
+use sha2::Sha256;
 use std::time::Duration;
+use tokio::sync::OwnedSemaphorePermit;
 use tokio::sync::mpsc;
+use tokio::time::Instant;
+use tracing::debug;
 use tracing::warn;
 
 use crate::AuthManager;
@@ -42,14 +52,19 @@ use crate::client_common::Prompt;
 use crate::client_common::ResponseEvent;
 use crate::client_common::ResponseStream;
 use crate::config::Config;
-use crate::default_client::build_reqwest_client;
+use crate::default_client::build_reqwest_client_for_provider;
 use crate::error::CodexErr;
+use crate::error::ModelUnavailableError;
 use crate::error::Result;
 use crate::features::FEATURES;
 use crate::flags::CODEX_RS_SSE_FIXTURE;
 use crate::model_provider_info::ModelProviderInfo;
 use crate::model_provider_info::WireApi;
+use crate::models_manager::model_family::IdleTimeoutSource;
 use crate::models_manager::model_family::ModelFamily;
+use crate::models_manager::model_family::RequestTimeoutSource;
+use crate::request_limits::acquire_request_permit;
+use crate::token_command::invalidate_token;
 use crate::tools::spec::create_tools_json_for_chat_completions_api;
 use crate::tools::spec::create_tools_json_for_responses_api;
 
@@ -100,6 +115,74 @@ impl ModelClient {
             .map(|w| w.saturating_mul(effective_context_window_percent) / 100)
     }
 
+    /// Sampling parameters for a request: the configured values, falling back
+    /// to the model defaults, with any the model rejects left out.
+    fn sampling(&self, model_family: &ModelFamily) -> Sampling {
+        let temperature = self.config.model_temperature;
+        let top_p = self.config.model_top_p;
+        if temperature.is_some() && !model_family.supports_temperature {
+            warn!(
+                "model_temperature is set but ignored as the model does not support it: {}",
+                model_family.family
+            );
+        }
+        if top_p.is_some() && !model_family.supports_top_p {
+            warn!(
+                "model_top_p is set but ignored as the model does not support it: {}",
+                model_family.family
+            );
+        }
+        Sampling {
+            temperature: model_family.resolve_temperature(temperature),
+            top_p: model_family.resolve_top_p(top_p),
+        }
+    }
+
+    /// How long a streamed response may go without an event at the effort
+    /// this client requests at, and which setting decided it.
+    fn stream_idle_timeout(&self, model_family: &ModelFamily) -> (Duration, IdleTimeoutSource) {
+        let (timeout, source) =
+            model_family.resolve_stream_idle_timeout(self.effort, &self.provider);
+        debug!(
+            "stream idle timeout for {} at {:?} effort: {timeout:?}, from {source}",
+            model_family.slug, self.effort
+        );
+        (timeout, source)
+    }
+
+    /// When a request sent now must have finished streaming, for the effort
+    /// this client requests at; `None` when nothing limits it as a whole.
+    fn request_deadline(&self, model_family: &ModelFamily) -> Option<RequestDeadline> {
+        let (budget, source) = model_family.resolve_request_timeout(self.effort)?;
+        debug!(
+            "request timeout for {} at {:?} effort: {budget:?}, from {source}",
+            model_family.slug, self.effort
+        );
+        Some(RequestDeadline {
+            at: Instant::now() + budget,
+            budget,
+            source,
+        })
+    }
+
+    /// Map a failed stream request, recognizing a provider that does not
+    /// serve this client's model, or does not serve it to this account.
+    fn map_stream_error(&self, err: ApiError) -> CodexErr {
+        let (message, for_account) = match model_unavailable_message(&err, self.provider.wire_api) {
+            Some(message) => (message, false),
+            None => match model_forbidden_message(&err) {
+                Some(message) => (message, true),
+                None => return map_api_error(err),
+            },
+        };
+        CodexErr::ModelUnavailable(ModelUnavailableError {
+            model: self.get_model(),
+            preset_id: None,
+            message,
+            for_account,
+        })
+    }
+
     pub fn config(&self) -> Arc<Config> {
         Arc::clone(&self.config)
     }
@@ -114,26 +197,59 @@ impl ModelClient {
     /// For Chat providers, the underlying stream is optionally aggregated
     /// based on the `show_raw_agent_reasoning` flag in the config.
     pub async fn stream(&self, prompt: &Prompt) -> Result<ResponseStream> {
-        match self.provider.wire_api {
-            WireApi::Responses => self.stream_responses_api(prompt).await,
-            WireApi::Chat => {
-                let api_stream = self.stream_chat_completions(prompt).await?;
-
-                if self.config.show_raw_agent_reasoning {
-                    Ok(map_response_stream(
-                        api_stream.streaming_mode(),
-                        self.otel_manager.clone(),
-                    ))
-                } else {
-                    Ok(map_response_stream(
-                        api_stream.aggregate(),
-                        self.otel_manager.clone(),
-                    ))
+        let permit = self.request_permit().await;
+        let model_family = self.get_model_family();
+        let (_, idle_timeout_source) = self.stream_idle_timeout(&model_family);
+        let deadline = self.request_deadline(&model_family);
+        let request = async {
+            match self.provider.wire_api {
+                WireApi::Responses => {
+                    self.stream_responses_api(prompt, permit, idle_timeout_source, deadline)
+                        .await
+                }
+                WireApi::Chat => {
+                    let api_stream = self.stream_chat_completions(prompt).await?;
+
+                    if self.config.show_raw_agent_reasoning {
+                        Ok(map_response_stream(
+                            api_stream.streaming_mode(),
+                            self.otel_manager.clone(),
+                            permit,
+                            idle_timeout_source,
+                            deadline,
+                        ))
+                    } else {
+                        Ok(map_response_stream(
+                            api_stream.aggregate(),
+                            self.otel_manager.clone(),
+                            permit,
+                            idle_timeout_source,
+                            deadline,
+                        ))
+                    }
                 }
             }
+        };
+        match deadline {
+            Some(deadline) => tokio::time::timeout_at(deadline.at, request)
+                .await
+                .unwrap_or_else(|_| Err(deadline.error())),
+            None => request.await,
         }
     }
 
+    /// Wait for a slot under the model's `max_concurrent_requests`, if it has
+    /// one. The permit is held until the request and its stream finish.
+    async fn request_permit(&self) -> Option<OwnedSemaphorePermit> {
+        let model_family = self.get_model_family();
+        acquire_request_permit(
+            &self.provider.name,
+            model_family.get_model_slug(),
+            model_family.max_concurrent_requests,
+        )
+        .await
+    }
+
     /// Streams a turn via the OpenAI Chat Completions API.
     ///
     /// This path is only used when the provider is configured with
@@ -150,17 +266,25 @@ impl ModelClient {
         let instructions = prompt.get_full_instructions(&model_family).into_owned();
         let tools_json = create_tools_json_for_chat_completions_api(&prompt.tools)?;
         let api_prompt = build_api_prompt(prompt, instructions, tools_json);
+        let sampling = self.sampling(&model_family);
         let conversation_id = self.conversation_id.to_string();
         let session_source = self.session_source.clone();
+        let model_headers = model_family.request_header_map();
+        let (stream_idle_timeout, _) = self.stream_idle_timeout(&model_family);
 
         let mut refreshed = false;
         loop {
             let auth = auth_manager.as_ref().and_then(|m| m.auth());
-            let api_provider = self
+            let mut api_provider = self
                 .provider
                 .to_api_provider(auth.as_ref().map(|a| a.mode))?;
+            api_provider.headers.extend(model_headers.clone());
+            api_provider.stream_idle_timeout = stream_idle_timeout;
             let api_auth = auth_provider_from_auth(auth.clone(), &self.provider).await?;
-            let transport = ReqwestTransport::new(build_reqwest_client());
+            let transport = ReqwestTransport::new(build_reqwest_client_for_provider(
+                &self.provider,
+                self.config.user_agent_suffix.as_deref(),
+            )?);
             let (request_telemetry, sse_telemetry) = self.build_streaming_telemetry();
             let client = ApiChatClient::new(transport, api_provider, api_auth)
                 .with_telemetry(Some(request_telemetry), Some(sse_telemetry));
@@ -169,6 +293,7 @@ impl ModelClient {
                 .stream_prompt(
                     &self.get_model(),
                     &api_prompt,
+                    sampling,
                     Some(conversation_id.clone()),
                     Some(session_source.clone()),
                 )
@@ -179,10 +304,17 @@ impl ModelClient {
                 Err(ApiError::Transport(TransportError::Http { status, .. }))
                     if status == StatusCode::UNAUTHORIZED =>
                 {
-                    handle_unauthorized(status, &mut refreshed, &auth_manager, &auth).await?;
+                    handle_unauthorized(
+                        status,
+                        &mut refreshed,
+                        &self.provider,
+                        &auth_manager,
+                        &auth,
+                    )
+                    .await?;
                     continue;
                 }
-                Err(err) => return Err(map_api_error(err)),
+                Err(err) => return Err(self.map_stream_error(err)),
             }
         }
     }
@@ -191,12 +323,25 @@ impl ModelClient {
     ///
     /// Handles SSE fixtures, reasoning summaries, verbosity, and the
     /// `text` controls used for output schemas.
-    async fn stream_responses_api(&self, prompt: &Prompt) -> Result<ResponseStream> {
+    async fn stream_responses_api(
+        &self,
+        prompt: &Prompt,
+        permit: Option<OwnedSemaphorePermit>,
+        idle_timeout_source: IdleTimeoutSource,
+        deadline: Option<RequestDeadline>,
+    ) -> Result<ResponseStream> {
         if let Some(path) = &*CODEX_RS_SSE_FIXTURE {
             warn!(path, "Streaming from fixture");
-            let stream = codex_api::stream_from_fixture(path, self.provider.stream_idle_timeout())
-                .map_err(map_api_error)?;
-            return Ok(map_response_stream(stream, self.otel_manager.clone()));
+            let (idle_timeout, _) = self.stream_idle_timeout(&self.get_model_family());
+            let stream =
+                codex_api::stream_from_fixture(path, idle_timeout).map_err(map_api_error)?;
+            return Ok(map_response_stream(
+                stream,
+                self.otel_manager.clone(),
+                None,
+                idle_timeout_source,
+                deadline,
+            ));
         }
 
         let auth_manager = self.auth_manager.clone();
@@ -204,14 +349,14 @@ impl ModelClient {
         let instructions = prompt.get_full_instructions(&model_family).into_owned();
         let tools_json: Vec<Value> = create_tools_json_for_responses_api(&prompt.tools)?;
 
-        let reasoning = if model_family.supports_reasoning_summaries {
+        let max_output_tokens = model_family
+            .resolve_max_output_tokens(self.effort, self.config.model_max_output_tokens);
+        let sampling = self.sampling(&model_family);
+
+        let reasoning = if model_family.sends_reasoning() {
             Some(Reasoning {
-                effort: self.effort.or(model_family.default_reasoning_effort),
-                summary: if self.summary == ReasoningSummaryConfig::None {
-                    None
-                } else {
-                    Some(self.summary)
-                },
+                effort: model_family.request_reasoning_effort(self.effort),
+                summary: model_family.request_reasoning_summary(self.summary),
             })
         } else {
             None
@@ -241,15 +386,27 @@ impl ModelClient {
         let api_prompt = build_api_prompt(prompt, instructions.clone(), tools_json);
         let conversation_id = self.conversation_id.to_string();
         let session_source = self.session_source.clone();
+        let model_headers = model_family.request_header_map();
+        let (stream_idle_timeout, _) = self.stream_idle_timeout(&model_family);
 
         let mut refreshed = false;
         loop {
             let auth = auth_manager.as_ref().and_then(|m| m.auth());
-            let api_provider = self
+            let mut api_provider = self
                 .provider
                 .to_api_provider(auth.as_ref().map(|a| a.mode))?;
+            api_provider.headers.extend(model_headers.clone());
+            api_provider.stream_idle_timeout = stream_idle_timeout;
             let api_auth = auth_provider_from_auth(auth.clone(), &self.provider).await?;
-            let transport = ReqwestTransport::new(build_reqwest_client());
+            let prompt_cache_key = prompt_cache_key(
+                model_family.prompt_cache_key_strategy,
+                &conversation_id,
+                &api_auth,
+            );
+            let transport = ReqwestTransport::new(build_reqwest_client_for_provider(
+                &self.provider,
+                self.config.user_agent_suffix.as_deref(),
+            )?);
             let (request_telemetry, sse_telemetry) = self.build_streaming_telemetry();
             let client = ApiResponsesClient::new(transport, api_provider, api_auth)
                 .with_telemetry(Some(request_telemetry), Some(sse_telemetry));
@@ -257,9 +414,11 @@ impl ModelClient {
             let options = ApiResponsesOptions {
                 reasoning: reasoning.clone(),
                 include: include.clone(),
-                prompt_cache_key: Some(conversation_id.clone()),
+                prompt_cache_key,
                 text: text.clone(),
-                store_override: None,
+                max_output_tokens,
+                sampling,
+                store_override: model_family.supports_stateful_responses,
                 conversation_id: Some(conversation_id.clone()),
                 session_source: Some(session_source.clone()),
                 extra_headers: beta_feature_headers(&self.config),
@@ -271,15 +430,28 @@ impl ModelClient {
 
             match stream_result {
                 Ok(stream) => {
-                    return Ok(map_response_stream(stream, self.otel_manager.clone()));
+                    return Ok(map_response_stream(
+                        stream,
+                        self.otel_manager.clone(),
+                        permit,
+                        idle_timeout_source,
+                        deadline,
+                    ));
                 }
                 Err(ApiError::Transport(TransportError::Http { status, .. }))
                     if status == StatusCode::UNAUTHORIZED =>
                 {
-                    handle_unauthorized(status, &mut refreshed, &auth_manager, &auth).await?;
+                    handle_unauthorized(
+                        status,
+                        &mut refreshed,
+                        &self.provider,
+                        &auth_manager,
+                        &auth,
+                    )
+                    .await?;
                     continue;
                 }
-                Err(err) => return Err(map_api_error(err)),
+                Err(err) => return Err(self.map_stream_error(err)),
             }
         }
     }
@@ -328,13 +500,17 @@ impl ModelClient {
         if prompt.input.is_empty() {
             return Ok(Vec::new());
         }
+        let _permit = self.request_permit().await;
         let auth_manager = self.auth_manager.clone();
         let auth = auth_manager.as_ref().and_then(|m| m.auth());
         let api_provider = self
             .provider
             .to_api_provider(auth.as_ref().map(|a| a.mode))?;
         let api_auth = auth_provider_from_auth(auth.clone(), &self.provider).await?;
-        let transport = ReqwestTransport::new(build_reqwest_client());
+        let transport = ReqwestTransport::new(build_reqwest_client_for_provider(
+            &self.provider,
+            self.config.user_agent_suffix.as_deref(),
+        )?);
         let request_telemetry = self.build_request_telemetry();
         let client = ApiCompactClient::new(transport, api_provider, api_auth)
             .with_telemetry(Some(request_telemetry));
@@ -398,6 +574,28 @@ fn build_api_prompt(prompt: &Prompt, instructions: String, tools_json: Vec<Value
     }
 }
 
+/// The `prompt_cache_key` a request made under `strategy` carries. It is
+/// worked out for every request from the model in use, so switching presets
+/// mid-conversation never carries a key over to a strategy that forbids it.
+fn prompt_cache_key(
+    strategy: PromptCacheKeyStrategy,
+    conversation_id: &str,
+    auth: &impl ApiAuthProvider,
+) -> Option<String> {
+    match strategy {
+        PromptCacheKeyStrategy::None => None,
+        PromptCacheKeyStrategy::PerConversation => Some(conversation_id.to_string()),
+        PromptCacheKeyStrategy::PerUser => {
+            // The account id survives token refreshes; without one, the API
+            // key is what identifies the user. Either is hashed so the key
+            // does not reveal it.
+            let identity = auth.account_id().or_else(|| auth.bearer_token())?;
+            let digest = format!("{:x}", Sha256::digest(identity.as_bytes()));
+            Some(format!("user-{}", digest.get(..32).unwrap_or(&digest)))
+        }
+    }
+}
+
 fn beta_feature_headers(config: &Config) -> ApiHeaderMap {
     let enabled = FEATURES
         .iter()
@@ -419,7 +617,36 @@ fn beta_feature_headers(config: &Config) -> ApiHeaderMap {
     headers
 }
 
-fn map_response_stream<S>(api_stream: S, otel_manager: OtelManager) -> ResponseStream
+/// The whole-request budget of a model request: once `at` passes, the
+/// request is abandoned even while its response is still streaming.
+#[derive(Debug, Clone, Copy)]
+struct RequestDeadline {
+    at: Instant,
+    budget: Duration,
+    source: RequestTimeoutSource,
+}
+
+impl RequestDeadline {
+    fn error(&self) -> CodexErr {
+        CodexErr::Stream(
+            format!(
+                "request timed out after {:?}, set by {}",
+                self.budget, self.source
+            ),
+            None,
+        )
+    }
+}
+
+/// Forward `api_stream` as a [`ResponseStream`], holding `permit` until the
+/// stream ends, or until `deadline` cuts it short.
+fn map_response_stream<S>(
+    api_stream: S,
+    otel_manager: OtelManager,
+    permit: Option<OwnedSemaphorePermit>,
+    idle_timeout_source: IdleTimeoutSource,
+    deadline: Option<RequestDeadline>,
+) -> ResponseStream
 where
     S: futures::Stream<Item = std::result::Result<ResponseEvent, ApiError>>
         + Unpin
@@ -429,9 +656,29 @@ where
     let (tx_event, rx_event) = mpsc::channel::<Result<ResponseEvent>>(1600);
 
     tokio::spawn(async move {
+        let _permit = permit;
         let mut logged_error = false;
         let mut api_stream = api_stream;
-        while let Some(event) = api_stream.next().await {
+        loop {
+            let next = match deadline {
+                Some(deadline) => {
+                    match tokio::time::timeout_at(deadline.at, api_stream.next()).await {
+                        Ok(next) => next,
+                        Err(_) => {
+                            let err = deadline.error();
+                            if !logged_error {
+                                otel_manager.see_event_completed_failed(&err);
+                            }
+                            let _ = tx_event.send(Err(err)).await;
+                            return;
+                        }
+                    }
+                }
+                None => api_stream.next().await,
+            };
+            let Some(event) = next else {
+                break;
+            };
             match event {
                 Ok(ResponseEvent::Completed {
                     response_id,
@@ -463,7 +710,16 @@ where
                     }
                 }
                 Err(err) => {
-                    let mapped = map_api_error(err);
+                    let mapped = match err {
+                        // Say which setting to raise.
+                        ApiError::IdleTimeout(after) => CodexErr::Stream(
+                            format!(
+                                "idle timeout waiting for SSE after {after:?}, set by {idle_timeout_source}"
+                            ),
+                            None,
+                        ),
+                        err => map_api_error(err),
+                    };
                     if !logged_error {
                         otel_manager.see_event_completed_failed(&mapped);
                         logged_error = true;
@@ -479,13 +735,15 @@ where
     ResponseStream { rx_event }
 }
 
-/// Handles a 401 response by optionally refreshing ChatGPT tokens once.
+/// Handles a 401 response by refreshing the credentials once: the token
+/// printed by the provider's `token_command`, or else ChatGPT tokens.
 ///
 /// When refresh succeeds, the caller should retry the API call; otherwise
 /// the mapped `CodexErr` is returned to the caller.
 async fn handle_unauthorized(
     status: StatusCode,
     refreshed: &mut bool,
+    provider: &ModelProviderInfo,
     auth_manager: &Option<Arc<AuthManager>>,
     auth: &Option<crate::auth::CodexAuth>,
 ) -> Result<()> {
@@ -493,7 +751,13 @@ async fn handle_unauthorized(
         return Err(map_unauthorized_status(status));
     }
 
-    if let Some(manager) = auth_manager.as_ref()
+    if provider.env_key.is_none()
+        && let Some(command) = &provider.token_command
+    {
+        invalidate_token(command);
+        *refreshed = true;
+        Ok(())
+    } else if let Some(manager) = auth_manager.as_ref()
         && let Some(auth) = auth.as_ref()
         && auth.mode == AuthMode::ChatGPT
     {
@@ -558,3 +822,73 @@ impl SseTelemetry for ApiTelemetry {
         self.otel_manager.log_sse_event(result, duration);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn test_otel_manager() -> OtelManager {
+        OtelManager::new(
+            ConversationId::new(),
+            "gpt-5.1-codex",
+            "gpt-5.1-codex",
+            None,
+            None,
+            None,
+            false,
+            "test".to_string(),
+            SessionSource::Exec,
+        )
+    }
+
+    #[tokio::test]
+    async fn the_request_deadline_ends_a_stream_and_names_its_setting() {
+        let budget = Duration::from_millis(20);
+        let mut stream = map_response_stream(
+            futures::stream::pending::<std::result::Result<ResponseEvent, ApiError>>(),
+            test_otel_manager(),
+            None,
+            IdleTimeoutSource::Default,
+            Some(RequestDeadline {
+                at: Instant::now() + budget,
+                budget,
+                source: RequestTimeoutSource::Effort(ReasoningEffortConfig::High),
+            }),
+        );
+
+        let err = stream
+            .next()
+            .await
+            .expect("an event")
+            .expect_err("a timeout");
+        assert_eq!(
+            err.to_string(),
+            "stream disconnected before completion: request timed out after 20ms, set by \
+             `request_timeout_secs` on the high effort"
+        );
+        assert!(stream.next().await.is_none(), "the stream ends");
+    }
+
+    #[tokio::test]
+    async fn an_idle_timeout_names_the_override_in_effect() {
+        let mut stream = map_response_stream(
+            futures::stream::iter([Err(ApiError::IdleTimeout(Duration::from_secs(600)))]),
+            test_otel_manager(),
+            None,
+            IdleTimeoutSource::Effort(ReasoningEffortConfig::XHigh),
+            None,
+        );
+
+        let err = stream
+            .next()
+            .await
+            .expect("an event")
+            .expect_err("a timeout");
+        assert_eq!(
+            err.to_string(),
+            "stream disconnected before completion: idle timeout waiting for SSE after 600s, set \
+             by `stream_idle_timeout_secs` on the xhigh effort"
+        );
+    }
+}
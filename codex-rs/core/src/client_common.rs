@@ -58,13 +58,17 @@ impl Prompt {
             ToolSpec::Freeform(f) => f.name == "apply_patch",
             _ => false,
         });
-        if self.base_instructions_override.is_none()
+        let instructions = if self.base_instructions_override.is_none()
             && model.needs_special_apply_patch_instructions
             && !is_apply_patch_tool_present
         {
             Cow::Owned(format!("{base}\n{APPLY_PATCH_TOOL_INSTRUCTIONS}"))
         } else {
             Cow::Borrowed(base)
+        };
+        match model.instructions_addendum.as_deref() {
+            Some(addendum) => Cow::Owned(format!("{instructions}\n\n{addendum}")),
+            None => instructions,
         }
     }
 
@@ -329,6 +333,45 @@ mod tests {
         }
     }
 
+    #[test]
+    fn get_full_instructions_appends_instructions_addendum() {
+        let config = test_config();
+        let mut model_family = ModelsManager::construct_model_family_offline("gpt-5.1", &config);
+        model_family.instructions_addendum =
+            Some("Respond in the internal citation format.".to_string());
+
+        let prompt = Prompt::default();
+        assert_eq!(
+            prompt.get_full_instructions(&model_family),
+            format!(
+                "{}\n\nRespond in the internal citation format.",
+                model_family.base_instructions
+            )
+        );
+
+        let prompt = Prompt {
+            base_instructions_override: Some("Custom base.".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(
+            prompt.get_full_instructions(&model_family),
+            "Custom base.\n\nRespond in the internal citation format."
+        );
+    }
+
+    #[test]
+    fn provider_instructions_addendum_applies_through_config() {
+        let mut config = test_config();
+        config.model_provider.instructions_addendum = Some("Cite sources.".to_string());
+
+        let model_family = ModelsManager::construct_model_family_offline("gpt-5.1", &config);
+
+        assert_eq!(
+            model_family.instructions_addendum,
+            Some("Cite sources.".to_string())
+        );
+    }
+
     #[test]
     fn serializes_text_verbosity_when_set() {
         let input: Vec<ResponseItem> = vec![];
@@ -349,6 +392,9 @@ mod tests {
                 verbosity: Some(OpenAiVerbosity::Low),
                 format: None,
             }),
+            max_output_tokens: None,
+            temperature: None,
+            top_p: None,
         };
 
         let v = serde_json::to_value(&req).expect("json");
@@ -387,6 +433,9 @@ mod tests {
             include: vec![],
             prompt_cache_key: None,
             text: Some(text_controls),
+            max_output_tokens: None,
+            temperature: None,
+            top_p: None,
         };
 
         let v = serde_json::to_value(&req).expect("json");
@@ -423,6 +472,9 @@ mod tests {
             include: vec![],
             prompt_cache_key: None,
             text: None,
+            max_output_tokens: None,
+            temperature: None,
+            top_p: None,
         };
 
         let v = serde_json::to_value(&req).expect("json");
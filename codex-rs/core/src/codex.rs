@@ -16,6 +16,7 @@ use crate::compact_remote::run_inline_remote_auto_compact_task;
 use crate::exec_policy::ExecPolicyManager;
 use crate::features::Feature;
 use crate::features::Features;
+use crate::models_manager::locale::resolve_catalog_locale;
 use crate::models_manager::manager::ModelsManager;
 use crate::models_manager::model_family::ModelFamily;
 use crate::parse_command::parse_command;
@@ -43,6 +44,7 @@ use codex_protocol::protocol::SessionSource;
 use codex_protocol::protocol::TaskStartedEvent;
 use codex_protocol::protocol::TurnAbortReason;
 use codex_protocol::protocol::TurnContextItem;
+use codex_protocol::protocol::TurnPreset;
 use codex_rmcp_client::ElicitationResponse;
 use futures::future::BoxFuture;
 use futures::prelude::*;
@@ -78,12 +80,19 @@ use crate::client_common::ResponseEvent;
 use crate::compact::collect_user_messages;
 use crate::config::Config;
 use crate::config::Constrained;
+use crate::config::ConstraintError;
 use crate::config::ConstraintResult;
 use crate::config::GhostSnapshotConfig;
+use crate::config::edit::ConfigEditsBuilder;
+use crate::config::project_trust::ignored_settings_notice;
 use crate::config::types::ShellEnvironmentPolicy;
 use crate::context_manager::ContextManager;
+use crate::cost_guard::SessionCost;
+use crate::cost_guard::limit_reached_message;
+use crate::cost_guard::session_cost;
 use crate::environment_context::EnvironmentContext;
 use crate::error::CodexErr;
+use crate::error::ModelUnavailableError;
 use crate::error::Result as CodexResult;
 #[cfg(test)]
 use crate::exec::StreamOutput;
@@ -98,11 +107,14 @@ use crate::protocol::AgentReasoningSectionBreakEvent;
 use crate::protocol::ApplyPatchApprovalRequestEvent;
 use crate::protocol::AskForApproval;
 use crate::protocol::BackgroundEventEvent;
+use crate::protocol::CostLimitApprovalRequestEvent;
 use crate::protocol::DeprecationNoticeEvent;
 use crate::protocol::ErrorEvent;
 use crate::protocol::Event;
 use crate::protocol::EventMsg;
 use crate::protocol::ExecApprovalRequestEvent;
+use crate::protocol::ModelChangeSource;
+use crate::protocol::ModelChangedEvent;
 use crate::protocol::Op;
 use crate::protocol::RateLimitSnapshot;
 use crate::protocol::ReasoningContentDeltaEvent;
@@ -132,6 +144,8 @@ use crate::skills::build_skill_injections;
 use crate::state::ActiveTurn;
 use crate::state::SessionServices;
 use crate::state::SessionState;
+use crate::status_file::SessionStatus;
+use crate::status_file::StatusFile;
 use crate::tasks::GhostSnapshotTask;
 use crate::tasks::ReviewTask;
 use crate::tasks::SessionTask;
@@ -149,12 +163,15 @@ use crate::user_instructions::UserInstructions;
 use crate::user_notification::UserNotification;
 use crate::util::backoff;
 use codex_async_utils::OrCancelExt;
+use codex_otel::catalog::ModelSelectionSource;
 use codex_otel::otel_manager::OtelManager;
 use codex_protocol::config_types::ReasoningSummary as ReasoningSummaryConfig;
 use codex_protocol::models::ContentItem;
 use codex_protocol::models::ResponseInputItem;
 use codex_protocol::models::ResponseItem;
+use codex_protocol::openai_models::ModelPreset;
 use codex_protocol::openai_models::ReasoningEffort as ReasoningEffortConfig;
+use codex_protocol::openai_models::ReasoningEffortPreset;
 use codex_protocol::protocol::CodexErrorInfo;
 use codex_protocol::protocol::InitialHistory;
 use codex_protocol::user_input::UserInput;
@@ -180,6 +197,7 @@ pub struct CodexSpawnOk {
 pub(crate) const INITIAL_SUBMIT_ID: &str = "";
 pub(crate) const SUBMISSION_CHANNEL_CAPACITY: usize = 64;
 static CHAT_WIRE_API_DEPRECATION_EMITTED: AtomicBool = AtomicBool::new(false);
+static RETIRED_MODEL_NOTICE_EMITTED: AtomicBool = AtomicBool::new(false);
 
 fn maybe_push_chat_wire_api_deprecation(
     config: &Config,
@@ -205,6 +223,64 @@ fn maybe_push_chat_wire_api_deprecation(
     });
 }
 
+/// Tell the user that their configured `model` was retired, once per
+/// successor: the notice is recorded under `[notice.model_migrations]`, and
+/// later sessions in the same process skip it. With `rewrite_retired_models`,
+/// `config.toml` is updated to the successor too.
+async fn maybe_push_retired_model_notice(
+    config: &Config,
+    post_session_configured_events: &mut Vec<Event>,
+) {
+    let Some(retired) = config.model.as_deref() else {
+        return;
+    };
+    let Some(successor) = ModelsManager::retired_model_successor(retired, config) else {
+        return;
+    };
+    let successor = successor.model.as_str();
+    let acknowledged = config
+        .notices
+        .model_migrations
+        .get(retired)
+        .is_some_and(|to| to == successor);
+    if acknowledged && !config.rewrite_retired_models {
+        return;
+    }
+
+    let mut edits = ConfigEditsBuilder::new(&config.codex_home);
+    if !acknowledged {
+        edits = edits.record_model_migration_seen(retired, successor);
+    }
+    if config.rewrite_retired_models {
+        edits = edits.rename_model(retired, successor);
+    }
+    if let Err(err) = edits.apply().await {
+        warn!("failed to record the retired model `{retired}`: {err}");
+    }
+    if acknowledged
+        || RETIRED_MODEL_NOTICE_EMITTED
+            .compare_exchange(false, true, Ordering::SeqCst, Ordering::SeqCst)
+            .is_err()
+    {
+        return;
+    }
+
+    let details = if config.rewrite_retired_models {
+        format!("`model` in config.toml now names `{successor}`.")
+    } else {
+        format!(
+            "Set `model = \"{successor}\"` in config.toml, or set `rewrite_retired_models = true` to let Codex update it."
+        )
+    };
+    post_session_configured_events.push(Event {
+        id: INITIAL_SUBMIT_ID.to_owned(),
+        msg: EventMsg::DeprecationNotice(DeprecationNoticeEvent {
+            summary: format!("Model `{retired}` has been retired. Using `{successor}` instead."),
+            details: Some(details),
+        }),
+    });
+}
+
 impl Codex {
     /// Spawn a new [`Codex`] and initialize the session.
     pub async fn spawn(
@@ -253,9 +329,49 @@ impl Codex {
         {
             error!("failed to refresh available models: {err:?}");
         }
-        let model = models_manager.get_model(&config.model, &config).await;
-        let session_configuration = SessionConfiguration {
+        // Local catalog providers may be slow or offline; list them in the
+        // background so the picker fills in without delaying session start.
+        // Offline, only the model lists fixed by config are loaded.
+        let mut provider_refresh = (!config.catalog_providers().is_empty()).then(|| {
+            let models_manager = Arc::clone(&models_manager);
+            let config = Arc::clone(&config);
+            tokio::spawn(async move {
+                models_manager.refresh_provider_models(&config).await;
+            })
+        });
+        // A provider-served catalog is still loading at this point, so the
+        // configured model is checked once it lands; strict mode waits for it.
+        let defer_model_check =
+            config.model_provider.model_catalog.is_some() && provider_refresh.is_some();
+        if defer_model_check
+            && config.strict_model_validation
+            && let Some(refresh) = provider_refresh.take()
+            && let Err(err) = refresh.await
+        {
+            error!("failed to refresh provider models: {err}");
+        }
+        let unknown_model_warning = match config.model.as_deref() {
+            Some(configured) if !defer_model_check || config.strict_model_validation => {
+                models_manager
+                    .unknown_model_warning(configured, &config)
+                    .await
+            }
+            _ => None,
+        };
+        if config.strict_model_validation
+            && let Some(message) = &unknown_model_warning
+        {
+            return Err(CodexErr::Fatal(format!(
+                "{message} Set `strict_model_validation = false` to start anyway."
+            )));
+        }
+        let (model, model_family) = models_manager.resolve_session_model(&config).await;
+        config
+            .model_provider
+            .check_model_wire_api(&model, model_family.wire_api)?;
+        let mut session_configuration = SessionConfiguration {
             provider: config.model_provider.clone(),
+            provider_id: config.model_provider_id.clone(),
             model: model.clone(),
             model_reasoning_effort: config.model_reasoning_effort,
             model_reasoning_summary: config.model_reasoning_summary,
@@ -269,6 +385,7 @@ impl Codex {
             original_config_do_not_use: Arc::clone(&config),
             session_source,
         };
+        let effort_notice = session_configuration.clamp_effort();
 
         // Generate a unique ID for the lifetime of this Codex session.
         let session_source_clone = session_configuration.session_source.clone();
@@ -291,6 +408,83 @@ impl Codex {
         })?;
         let conversation_id = session.conversation_id;
 
+        if let Some(message) = unknown_model_warning {
+            session.warn_at_startup(message).await;
+        }
+        if let Some(message) = effort_notice {
+            session.warn_at_startup(message).await;
+        }
+        let startup_configuration = session.state.lock().await.session_configuration.clone();
+        if let Some(message) = session
+            .ignored_effort_notice(None, &startup_configuration)
+            .await
+        {
+            session.warn_at_startup(message).await;
+        }
+        if let Some(message) = ignored_settings_notice(&config.ignored_project_settings) {
+            session.warn_at_startup(message).await;
+        }
+        match provider_refresh {
+            Some(refresh) if defer_model_check => {
+                let session = Arc::clone(&session);
+                let models_manager = Arc::clone(&models_manager);
+                let config = Arc::clone(&config);
+                tokio::spawn(async move {
+                    if let Err(err) = refresh.await {
+                        error!("failed to refresh provider models: {err}");
+                        return;
+                    }
+                    if let Some(configured) = config.model.as_deref()
+                        && let Some(message) = models_manager
+                            .unknown_model_warning(configured, &config)
+                            .await
+                    {
+                        session.warn_at_startup(message).await;
+                    }
+                    if let Some(message) =
+                        models_manager.ignored_effort_warning(&model, &config).await
+                    {
+                        session.warn_at_startup(message).await;
+                    }
+                    for message in models_manager.preset_override_warnings(&config).await {
+                        session.warn_at_startup(message).await;
+                    }
+                    if let Some(message) = models_manager.instruction_model_warning(&config).await {
+                        session.warn_at_startup(message).await;
+                    }
+                });
+            }
+            refresh => {
+                if let Some(message) = models_manager.ignored_effort_warning(&model, &config).await
+                {
+                    session.warn_at_startup(message).await;
+                }
+                // Overrides and project docs may name provider-served
+                // presets, so they are checked once those are listed.
+                if !config.model_preset_overrides.is_empty() || config.validate_instruction_models {
+                    let session = Arc::clone(&session);
+                    let models_manager = Arc::clone(&models_manager);
+                    let config = Arc::clone(&config);
+                    tokio::spawn(async move {
+                        if let Some(refresh) = refresh
+                            && let Err(err) = refresh.await
+                        {
+                            error!("failed to refresh provider models: {err}");
+                            return;
+                        }
+                        for message in models_manager.preset_override_warnings(&config).await {
+                            session.warn_at_startup(message).await;
+                        }
+                        if let Some(message) =
+                            models_manager.instruction_model_warning(&config).await
+                        {
+                            session.warn_at_startup(message).await;
+                        }
+                    });
+                }
+            }
+        }
+
         // This task will run until Op::Shutdown is received.
         tokio::spawn(submission_loop(session, config, rx_sub));
         let codex = Codex {
@@ -387,12 +581,23 @@ impl TurnContext {
             .as_deref()
             .unwrap_or(compact::SUMMARIZATION_PROMPT)
     }
+
+    /// What the turn's reasoning effort means for the active model, from its
+    /// preset. `None` when the preset does not describe it.
+    pub(crate) fn reasoning_effort_description(&self) -> Option<String> {
+        self.client
+            .get_model_family()
+            .effort_description(self.client.get_reasoning_effort())
+            .map(str::to_string)
+    }
 }
 
 #[derive(Clone)]
 pub(crate) struct SessionConfiguration {
-    /// Provider identifier ("openai", "openrouter", ...).
+    /// Provider serving `model`.
     provider: ModelProviderInfo,
+    /// Id of `provider` under `[model_providers]` ("openai", "openrouter", ...).
+    provider_id: String,
 
     /// If not specified, server will use its default model.
     model: String,
@@ -438,6 +643,16 @@ impl SessionConfiguration {
         if let Some(model) = updates.model.clone() {
             next_configuration.model = model;
         }
+        if let Some(provider_id) = updates.model_provider.as_ref() {
+            let providers = &self.original_config_do_not_use.model_providers;
+            let provider = providers.get(provider_id).ok_or_else(|| {
+                let mut known: Vec<&str> = providers.keys().map(String::as_str).collect();
+                known.sort_unstable();
+                ConstraintError::invalid_value(provider_id.clone(), format!("{known:?}"))
+            })?;
+            next_configuration.provider = provider.clone();
+            next_configuration.provider_id.clone_from(provider_id);
+        }
         if let Some(effort) = updates.reasoning_effort {
             next_configuration.model_reasoning_effort = effort;
         }
@@ -455,6 +670,31 @@ impl SessionConfiguration {
         }
         Ok(next_configuration)
     }
+
+    /// Move the effort into the range `preset_overrides` sets for the model,
+    /// returning a notice for the user when it had to change. `Auto` is left
+    /// alone: it resolves to the model default, which is clamped already.
+    pub(crate) fn clamp_effort(&mut self) -> Option<String> {
+        let effort = self.model_reasoning_effort?;
+        let limits = self
+            .original_config_do_not_use
+            .preset_overrides
+            .get(&self.model)?;
+        let clamped = limits.clamp(effort);
+        if clamped == effort {
+            return None;
+        }
+        self.model_reasoning_effort = Some(clamped);
+        let bound = if !effort.is_within(limits.min_effort, None) {
+            "below the minimum"
+        } else {
+            "above the maximum"
+        };
+        Some(format!(
+            "`{effort}` reasoning effort is {bound} configured for `{}`; using `{clamped}`.",
+            self.model
+        ))
+    }
 }
 
 #[derive(Default, Clone)]
@@ -463,11 +703,59 @@ pub(crate) struct SessionSettingsUpdate {
     pub(crate) approval_policy: Option<AskForApproval>,
     pub(crate) sandbox_policy: Option<SandboxPolicy>,
     pub(crate) model: Option<String>,
+    pub(crate) model_provider: Option<String>,
     pub(crate) reasoning_effort: Option<Option<ReasoningEffortConfig>>,
     pub(crate) reasoning_summary: Option<ReasoningSummaryConfig>,
     pub(crate) final_output_json_schema: Option<Option<Value>>,
 }
 
+impl SessionSettingsUpdate {
+    /// Take out the model, provider, and effort where they differ from
+    /// `current`; `None` when none of them does.
+    fn take_model_switch(&mut self, current: &SessionConfiguration) -> Option<Self> {
+        let switch = Self {
+            model: self.model.take().filter(|model| *model != current.model),
+            model_provider: self
+                .model_provider
+                .take()
+                .filter(|provider_id| *provider_id != current.provider_id),
+            reasoning_effort: self
+                .reasoning_effort
+                .take()
+                .filter(|effort| *effort != current.model_reasoning_effort),
+            ..Default::default()
+        };
+        (switch.model.is_some()
+            || switch.model_provider.is_some()
+            || switch.reasoning_effort.is_some())
+        .then_some(switch)
+    }
+
+    /// `self` with every setting `later` makes replaced by it.
+    fn merge(self, later: Self) -> Self {
+        Self {
+            cwd: later.cwd.or(self.cwd),
+            approval_policy: later.approval_policy.or(self.approval_policy),
+            sandbox_policy: later.sandbox_policy.or(self.sandbox_policy),
+            model: later.model.or(self.model),
+            model_provider: later.model_provider.or(self.model_provider),
+            reasoning_effort: later.reasoning_effort.or(self.reasoning_effort),
+            reasoning_summary: later.reasoning_summary.or(self.reasoning_summary),
+            final_output_json_schema: later
+                .final_output_json_schema
+                .or(self.final_output_json_schema),
+        }
+    }
+}
+
+/// A settings update as applied to the session.
+pub(crate) struct SettingsChange {
+    previous: SessionConfiguration,
+    updated: SessionConfiguration,
+    /// Set when the effort had to be moved into the model's range.
+    effort_notice: Option<String>,
+}
+
 impl Session {
     /// Don't expand the number of mutated arguments on config. We are in the process of getting rid of it.
     fn build_per_turn_config(session_configuration: &SessionConfiguration) -> Config {
@@ -476,6 +764,10 @@ impl Session {
         let mut per_turn_config = (*config).clone();
         per_turn_config.model_reasoning_effort = session_configuration.model_reasoning_effort;
         per_turn_config.model_reasoning_summary = session_configuration.model_reasoning_summary;
+        per_turn_config.model_provider = session_configuration.provider.clone();
+        per_turn_config
+            .model_provider_id
+            .clone_from(&session_configuration.provider_id);
         per_turn_config.features = config.features.clone();
         per_turn_config
     }
@@ -569,6 +861,7 @@ impl Session {
                         conversation_id,
                         session_configuration.user_instructions.clone(),
                         session_source,
+                        Some(models_manager.session_catalog().await),
                     ),
                 )
             }
@@ -619,6 +912,7 @@ impl Session {
             });
         }
         maybe_push_chat_wire_api_deprecation(&config, &mut post_session_configured_events);
+        maybe_push_retired_model_notice(&config, &mut post_session_configured_events).await;
 
         // todo(aibrahim): why are we passing model here while it can change?
         let otel_manager = OtelManager::new(
@@ -631,7 +925,8 @@ impl Session {
             config.otel.log_user_prompt,
             terminal::user_agent(),
             session_configuration.session_source.clone(),
-        );
+        )
+        .with_catalog_telemetry(config.otel.catalog_telemetry());
 
         otel_manager.conversation_starts(
             config.model_provider.name.as_str(),
@@ -644,6 +939,19 @@ impl Session {
             config.mcp_servers.keys().map(String::as_str).collect(),
             config.active_profile.clone(),
         );
+        let preset_id = models_manager
+            .preset_id_for_model(&session_configuration.model, &config.model_provider_id)
+            .await;
+        otel_manager.model_selected(
+            &session_configuration.model,
+            &preset_id,
+            session_configuration.model_reasoning_effort,
+            if config.model.is_some() {
+                ModelSelectionSource::Config
+            } else {
+                ModelSelectionSource::Default
+            },
+        );
 
         let mut default_shell = shell::default_user_shell();
         // Create the mutable state for the Session.
@@ -669,6 +977,9 @@ impl Session {
             models_manager: Arc::clone(&models_manager),
             tool_approvals: Mutex::new(ApprovalStore::default()),
             skills_manager,
+            status_file: config
+                .write_status_file
+                .then(|| StatusFile::new(&config.codex_home, &conversation_id.to_string())),
         };
 
         let sess = Arc::new(Session {
@@ -681,6 +992,13 @@ impl Session {
             next_internal_sub_id: AtomicU64::new(0),
         });
 
+        let model_family = sess
+            .services
+            .models_manager
+            .construct_model_family(session_configuration.model.as_str(), &config)
+            .await;
+        let instructions_addendum_active = model_family.instructions_addendum.is_some();
+
         // Dispatch the SessionConfiguredEvent first and then report any errors.
         // If resuming, include converted initial messages in the payload so UIs can render them immediately.
         let initial_messages = initial_history.get_event_msgs();
@@ -690,6 +1008,9 @@ impl Session {
                 session_id: conversation_id,
                 model: session_configuration.model.clone(),
                 model_provider_id: config.model_provider_id.clone(),
+                model_catalog_offline: config.offline,
+                instructions_addendum_active,
+                disabled_tools: model_family.disabled_tools,
                 approval_policy: session_configuration.approval_policy.value(),
                 sandbox_policy: session_configuration.sandbox_policy.get().clone(),
                 cwd: session_configuration.cwd.clone(),
@@ -704,6 +1025,7 @@ impl Session {
         for event in events {
             sess.send_event_raw(event).await;
         }
+        sess.refresh_status_file().await;
 
         // Construct sandbox_state before initialize() so it can be sent to each
         // MCP server immediately after it becomes ready (avoiding blocking).
@@ -839,18 +1161,254 @@ impl Session {
         &self,
         updates: SessionSettingsUpdate,
     ) -> ConstraintResult<()> {
+        let updates = self.defer_model_switch(updates).await;
+        let change = self.apply_settings(&updates).await.inspect_err(|err| {
+            warn!("rejected session settings update: {err}");
+        })?;
+        self.report_settings_change(INITIAL_SUBMIT_ID, change).await;
+        Ok(())
+    }
+
+    /// While a turn runs, hold back the model, provider, and effort that
+    /// `updates` changes, so the turn finishes on the model it started with.
+    /// They wait on the [`ActiveTurn`] and are applied when it ends; the rest
+    /// is returned to apply now. An update the session would reject is
+    /// returned whole, to be rejected as usual.
+    async fn defer_model_switch(
+        &self,
+        mut updates: SessionSettingsUpdate,
+    ) -> SessionSettingsUpdate {
+        let mut active = self.active_turn.lock().await;
+        let Some(turn) = active.as_mut() else {
+            return updates;
+        };
+        let current = self.state.lock().await.session_configuration.clone();
+        if current.apply(&updates).is_err() {
+            return updates;
+        }
+        // Compare against what the turn will switch to, so that picking the
+        // running model again takes back a queued switch.
+        let pending = turn
+            .queued_model_switch
+            .as_ref()
+            .and_then(|queued| current.apply(queued).ok())
+            .unwrap_or(current);
+        if let Some(switch) = updates.take_model_switch(&pending) {
+            info!("queueing a model switch until the running turn ends");
+            turn.queued_model_switch = Some(match turn.queued_model_switch.take() {
+                Some(queued) => queued.merge(switch),
+                None => switch,
+            });
+        }
+        updates
+    }
+
+    /// Apply `updates` to the session configuration.
+    async fn apply_settings(
+        &self,
+        updates: &SessionSettingsUpdate,
+    ) -> ConstraintResult<SettingsChange> {
         let mut state = self.state.lock().await;
+        let mut updated = state.session_configuration.apply(updates)?;
+        let effort_notice = updated.clamp_effort();
+        let previous = std::mem::replace(&mut state.session_configuration, updated.clone());
+        Ok(SettingsChange {
+            previous,
+            updated,
+            effort_notice,
+        })
+    }
 
-        match state.session_configuration.apply(&updates) {
-            Ok(updated) => {
-                state.session_configuration = updated;
-                Ok(())
-            }
-            Err(err) => {
-                warn!("rejected session settings update: {err}");
-                Err(err)
-            }
+    /// Tell clients about an applied settings update: a move to another
+    /// model, and notices about its effort.
+    async fn report_settings_change(&self, sub_id: &str, change: SettingsChange) {
+        let SettingsChange {
+            previous,
+            updated,
+            effort_notice,
+        } = change;
+        self.record_model_switch(sub_id, &previous, &updated).await;
+        let ignored_notice = self.ignored_effort_notice(Some(&previous), &updated).await;
+        for message in effort_notice.into_iter().chain(ignored_notice) {
+            self.send_event_raw(Event {
+                id: sub_id.to_owned(),
+                msg: EventMsg::Warning(WarningEvent { message }),
+            })
+            .await;
+        }
+        self.refresh_status_file().await;
+    }
+
+    /// Apply the model switch queued while a turn ran, now that it has ended.
+    /// Callers apply it before another turn can start and report it after.
+    pub(crate) async fn apply_queued_model_switch(
+        &self,
+        switch: SessionSettingsUpdate,
+    ) -> Option<SettingsChange> {
+        self.apply_settings(&switch)
+            .await
+            .inspect_err(|err| warn!("dropping the model switch queued during the turn: {err}"))
+            .ok()
+    }
+
+    pub(crate) async fn report_queued_model_switch(&self, change: SettingsChange) {
+        self.report_settings_change(INITIAL_SUBMIT_ID, change).await;
+    }
+
+    /// Emit the `model_selected` telemetry event when `next` runs another
+    /// model than `previous_model`.
+    /// Report a switch to another model or provider, as a client asked for.
+    /// The history is kept as it is; the next request sends it to `next`.
+    async fn record_model_switch(
+        &self,
+        sub_id: &str,
+        previous: &SessionConfiguration,
+        next: &SessionConfiguration,
+    ) {
+        if next.model == previous.model && next.provider_id == previous.provider_id {
+            return;
+        }
+        let preset_id = self
+            .services
+            .models_manager
+            .preset_id_for_model(&next.model, &next.provider_id)
+            .await;
+        self.services.otel_manager.model_selected(
+            &next.model,
+            &preset_id,
+            next.model_reasoning_effort,
+            ModelSelectionSource::User,
+        );
+        self.send_event_raw(Event {
+            id: sub_id.to_string(),
+            msg: EventMsg::ModelChanged(ModelChangedEvent {
+                previous_model: previous.model.clone(),
+                model: next.model.clone(),
+                source: ModelChangeSource::Selected,
+                previous_model_provider: Some(previous.provider_id.clone()),
+                model_provider: Some(next.provider_id.clone()),
+            }),
+        })
+        .await;
+    }
+
+    /// A notice that `next` sets an effort its model takes none of, so
+    /// requests go without it. `None` when `previous` already ran the same
+    /// model at the same effort: the user has been told once.
+    async fn ignored_effort_notice(
+        &self,
+        previous: Option<&SessionConfiguration>,
+        next: &SessionConfiguration,
+    ) -> Option<String> {
+        let effort = next
+            .model_reasoning_effort
+            .filter(|effort| *effort != ReasoningEffortConfig::None)?;
+        if previous.is_some_and(|previous| {
+            previous.model == next.model
+                && previous.provider_id == next.provider_id
+                && previous.model_reasoning_effort == next.model_reasoning_effort
+        }) {
+            return None;
         }
+        let model_family = self
+            .services
+            .models_manager
+            .construct_model_family(&next.model, &Self::build_per_turn_config(next))
+            .await;
+        model_family.ignores_reasoning_effort().then(|| {
+            format!(
+                "`{}` does not take a reasoning effort; ignoring the configured `{effort}` effort.",
+                next.model
+            )
+        })
+    }
+
+    /// The preset serving the model of `turn_context`, as recorded with the
+    /// turn in the rollout.
+    pub(crate) async fn turn_preset(&self, turn_context: &TurnContext) -> Option<TurnPreset> {
+        let config = turn_context.client.config();
+        let (preset, source) = self
+            .services
+            .models_manager
+            .active_preset(
+                &turn_context.client.get_model(),
+                &config.model_provider_id,
+                &config,
+            )
+            .await?;
+        Some(TurnPreset {
+            id: preset.id,
+            display_name: preset.display_name,
+            context_window: turn_context.client.get_model_family().context_window,
+            source: source.into(),
+        })
+    }
+
+    /// Bring the status file in line with the current model, effort, and
+    /// context usage. Does nothing unless `write_status_file` is set.
+    async fn refresh_status_file(&self) {
+        let Some(status_file) = &self.services.status_file else {
+            return;
+        };
+        let (model, effort, config, token_info) = {
+            let state = self.state.lock().await;
+            let session_configuration = &state.session_configuration;
+            (
+                session_configuration.model.clone(),
+                session_configuration.model_reasoning_effort,
+                Arc::clone(&session_configuration.original_config_do_not_use),
+                state.token_info(),
+            )
+        };
+        let models_manager = &self.services.models_manager;
+        let active = models_manager
+            .active_preset(&model, &config.model_provider_id, &config)
+            .await;
+        let bare_effort = |effort: ReasoningEffortConfig| ReasoningEffortPreset {
+            effort,
+            description: config
+                .effort_descriptions
+                .get(&effort)
+                .cloned()
+                .unwrap_or_default(),
+            max_output_tokens: None,
+            request_timeout_secs: None,
+            stream_idle_timeout_secs: None,
+        };
+        let effort = match &active {
+            Some((preset, _)) if !preset.supported_reasoning_efforts.is_empty() => {
+                let effort = effort.unwrap_or(preset.default_reasoning_effort);
+                Some(
+                    preset
+                        .supported_reasoning_efforts
+                        .iter()
+                        .find(|option| option.effort == effort)
+                        .cloned()
+                        .unwrap_or_else(|| bare_effort(effort)),
+                )
+            }
+            _ => effort.map(bare_effort),
+        };
+        let context_used_percent = token_info.and_then(|info| {
+            info.model_context_window.map(|window| {
+                100 - info
+                    .last_token_usage
+                    .percent_of_context_window_remaining(window)
+            })
+        });
+        let (preset, preset_source) = active.unzip();
+        status_file
+            .update(SessionStatus {
+                session_id: self.conversation_id.to_string(),
+                model,
+                preset_id: preset.as_ref().map(|preset| preset.id.clone()),
+                display_name: preset.map(|preset| preset.display_name),
+                effort,
+                context_used_percent,
+                catalog: models_manager.session_catalog().await,
+                preset_source,
+            })
+            .await;
     }
 
     pub(crate) async fn new_turn_with_sub_id(
@@ -858,29 +1416,26 @@ impl Session {
         sub_id: String,
         updates: SessionSettingsUpdate,
     ) -> ConstraintResult<Arc<TurnContext>> {
-        let (session_configuration, sandbox_policy_changed) = {
-            let mut state = self.state.lock().await;
-            match state.session_configuration.clone().apply(&updates) {
-                Ok(next) => {
-                    let sandbox_policy_changed =
-                        state.session_configuration.sandbox_policy != next.sandbox_policy;
-                    state.session_configuration = next.clone();
-                    (next, sandbox_policy_changed)
-                }
-                Err(err) => {
-                    drop(state);
-                    self.send_event_raw(Event {
-                        id: sub_id.clone(),
-                        msg: EventMsg::Error(ErrorEvent {
-                            message: err.to_string(),
-                            codex_error_info: Some(CodexErrorInfo::BadRequest),
-                        }),
-                    })
-                    .await;
-                    return Err(err);
-                }
+        // Input sent while a turn runs joins that turn, on its model.
+        let updates = self.defer_model_switch(updates).await;
+        let change = match self.apply_settings(&updates).await {
+            Ok(change) => change,
+            Err(err) => {
+                self.send_event_raw(Event {
+                    id: sub_id.clone(),
+                    msg: EventMsg::Error(ErrorEvent {
+                        message: err.to_string(),
+                        codex_error_info: Some(CodexErrorInfo::BadRequest),
+                    }),
+                })
+                .await;
+                return Err(err);
             }
         };
+        let sandbox_policy_changed =
+            change.previous.sandbox_policy != change.updated.sandbox_policy;
+        let session_configuration = change.updated.clone();
+        self.report_settings_change(&sub_id, change).await;
 
         Ok(self
             .new_turn_from_configuration(
@@ -899,7 +1454,7 @@ impl Session {
         final_output_json_schema: Option<Option<Value>>,
         sandbox_policy_changed: bool,
     ) -> Arc<TurnContext> {
-        let per_turn_config = Self::build_per_turn_config(&session_configuration);
+        let mut per_turn_config = Self::build_per_turn_config(&session_configuration);
 
         if sandbox_policy_changed {
             let sandbox_state = SandboxState {
@@ -924,10 +1479,20 @@ impl Session {
             .models_manager
             .construct_model_family(session_configuration.model.as_str(), &per_turn_config)
             .await;
+        if let Err(err) = session_configuration
+            .provider
+            .check_model_wire_api(&session_configuration.model, model_family.wire_api)
+        {
+            warn!("{err}; using the provider's default wire API");
+        }
+        let provider = session_configuration
+            .provider
+            .with_model_wire_api(model_family.wire_api);
+        per_turn_config.model_provider = provider.clone();
         let mut turn_context: TurnContext = Self::make_turn_context(
             Some(Arc::clone(&self.services.auth_manager)),
             &self.services.otel_manager,
-            session_configuration.provider.clone(),
+            provider,
             &session_configuration,
             per_turn_config,
             model_family,
@@ -954,6 +1519,111 @@ impl Session {
             .await
     }
 
+    /// A copy of `turn_context` that runs on the first fallback its model's
+    /// preset lists that the catalog has, or `None` when there is none. The
+    /// session's own model is left alone, so later turns use it again.
+    async fn fallback_turn(&self, turn_context: &TurnContext) -> Option<Arc<TurnContext>> {
+        let model_family = turn_context.client.get_model_family();
+        let config = turn_context.client.config();
+        let model = self
+            .services
+            .models_manager
+            .fallback_model(
+                &model_family.fallback_models,
+                &turn_provider_id(turn_context),
+                &config,
+            )
+            .await?;
+        let mut session_configuration = {
+            let state = self.state.lock().await;
+            state.session_configuration.clone()
+        };
+        session_configuration.model = model;
+        Some(
+            self.new_turn_from_configuration(
+                turn_context.sub_id.clone(),
+                session_configuration,
+                Some(turn_context.final_output_json_schema.clone()),
+                false,
+            )
+            .await,
+        )
+    }
+
+    /// The preset set as config `key` to `id`, resolved through the catalog,
+    /// with the provider serving it: its own, or `turn_context`'s for built-in
+    /// and remote presets. When the catalog does not list it, warns that the
+    /// flow runs on `fallback` instead and returns `None`.
+    pub(crate) async fn flow_preset(
+        &self,
+        turn_context: &TurnContext,
+        key: &str,
+        id: &str,
+        fallback: &str,
+    ) -> Option<(ModelPreset, ModelProviderInfo)> {
+        let config = turn_context.client.config();
+        let resolved = self
+            .services
+            .models_manager
+            .preset_by_id(id, &config)
+            .await
+            .and_then(|preset| {
+                let provider = match &preset.model_provider {
+                    Some(provider_id) => config.model_providers.get(provider_id)?.clone(),
+                    None => turn_context.client.get_provider(),
+                };
+                Some((preset, provider))
+            });
+        if resolved.is_none() {
+            let message = format!(
+                "`{key} = \"{id}\"` is not in the model catalog; using `{fallback}` instead."
+            );
+            self.send_event(turn_context, EventMsg::Warning(WarningEvent { message }))
+                .await;
+        }
+        resolved
+    }
+
+    /// The client compaction sends its requests through: `turn_context`'s,
+    /// or one for the preset `compact_model_preset` names, at that preset's
+    /// default effort.
+    pub(crate) async fn compaction_client(&self, turn_context: &TurnContext) -> ModelClient {
+        let config = turn_context.client.config();
+        let Some(id) = config.compact_model_preset.as_deref() else {
+            return turn_context.client.clone();
+        };
+        let Some((preset, provider)) = self
+            .flow_preset(
+                turn_context,
+                "compact_model_preset",
+                id,
+                &turn_context.client.get_model(),
+            )
+            .await
+        else {
+            return turn_context.client.clone();
+        };
+        let mut session_configuration = {
+            let state = self.state.lock().await;
+            state.session_configuration.clone()
+        };
+        session_configuration.model_reasoning_effort = preset_effort(&preset);
+        session_configuration.model = preset.model;
+        session_configuration.provider = provider;
+        if let Some(provider_id) = preset.model_provider {
+            session_configuration.provider_id = provider_id;
+        }
+        self.new_turn_from_configuration(
+            turn_context.sub_id.clone(),
+            session_configuration,
+            None,
+            false,
+        )
+        .await
+        .client
+        .clone()
+    }
+
     fn build_environment_update_item(
         &self,
         previous: Option<&Arc<TurnContext>>,
@@ -993,6 +1663,15 @@ impl Session {
         }
     }
 
+    async fn warn_at_startup(&self, message: String) {
+        warn!("{message}");
+        self.send_event_raw(Event {
+            id: INITIAL_SUBMIT_ID.to_owned(),
+            msg: EventMsg::Warning(WarningEvent { message }),
+        })
+        .await;
+    }
+
     pub(crate) async fn send_event_raw(&self, event: Event) {
         // Persist the event into rollout (recorder filters as needed)
         let rollout_items = vec![RolloutItem::EventMsg(event.msg.clone())];
@@ -1164,6 +1843,85 @@ impl Session {
         }
     }
 
+    /// Once the session's estimated cost reached `session_cost_limit_usd`,
+    /// ask whether the turn may run. Returns `false` when it should stop
+    /// before its first model request.
+    pub(crate) async fn approve_session_cost(&self, turn_context: &TurnContext) -> bool {
+        let config = turn_context.client.config();
+        let Some(limit_usd) = config.session_cost_limit_usd else {
+            return true;
+        };
+        let cost = self.session_cost(&config).await;
+        let sub_id = turn_context.sub_id.clone();
+        {
+            let state = self.state.lock().await;
+            if !state.cost_guard.needs_approval(cost.usd, limit_usd) {
+                return true;
+            }
+        }
+
+        let (tx_approve, rx_approve) = oneshot::channel();
+        {
+            let mut active = self.active_turn.lock().await;
+            if let Some(at) = active.as_mut() {
+                let mut ts = at.turn_state.lock().await;
+                ts.insert_pending_approval(sub_id.clone(), tx_approve);
+            }
+        }
+        let event = EventMsg::CostLimitApprovalRequest(CostLimitApprovalRequestEvent {
+            turn_id: sub_id.clone(),
+            cost_usd: cost.usd,
+            limit_usd,
+        });
+        self.send_event(turn_context, event).await;
+        let decision = rx_approve.await.unwrap_or_default();
+
+        let approved = {
+            let mut state = self.state.lock().await;
+            state.cost_guard.record_decision(&decision)
+        };
+        if !approved {
+            let message = limit_reached_message(cost.usd, limit_usd);
+            self.send_event(turn_context, EventMsg::Warning(WarningEvent { message }))
+                .await;
+        }
+        approved
+    }
+
+    /// Estimated cost of the usage recorded so far, priced by the presets
+    /// currently known.
+    async fn session_cost(&self, config: &Config) -> SessionCost {
+        let per_model_usage = {
+            let state = self.state.lock().await;
+            state
+                .token_info()
+                .map(|info| info.per_model_usage)
+                .unwrap_or_default()
+        };
+        let pricing = self.services.models_manager.model_pricing(config).await;
+        session_cost(&per_model_usage, &pricing)
+    }
+
+    /// Warn once the estimated cost passes `session_cost_warning_usd`, and
+    /// note the models it leaves out, when any cost threshold is configured.
+    async fn warn_about_session_cost(&self, turn_context: &TurnContext) {
+        let config = turn_context.client.config();
+        if config.session_cost_warning_usd.is_none() && config.session_cost_limit_usd.is_none() {
+            return;
+        }
+        let cost = self.session_cost(&config).await;
+        let warnings = {
+            let mut state = self.state.lock().await;
+            state
+                .cost_guard
+                .warnings(&cost, config.session_cost_warning_usd)
+        };
+        for message in warnings {
+            self.send_event(turn_context, EventMsg::Warning(WarningEvent { message }))
+                .await;
+        }
+    }
+
     pub async fn resolve_elicitation(
         &self,
         server_name: String,
@@ -1269,6 +2027,17 @@ impl Session {
         self.features.enabled(feature)
     }
 
+    /// Log, once per session, that the active preset overrides the `parallel`
+    /// feature.
+    async fn note_parallel_tool_calls_disabled(&self, model: &str) {
+        let mut state = self.state.lock().await;
+        if state.parallel_tool_calls_disabled_logged {
+            return;
+        }
+        state.parallel_tool_calls_disabled_logged = true;
+        info!("parallel tool calls are disabled for {model}: its preset does not support them");
+    }
+
     pub(crate) fn features(&self) -> Features {
         self.features.clone()
     }
@@ -1333,12 +2102,16 @@ impl Session {
             let mut state = self.state.lock().await;
             if let Some(token_usage) = token_usage {
                 state.update_token_info_from_usage(
+                    &turn_context.client.get_model(),
                     token_usage,
                     turn_context.client.get_model_context_window(),
                 );
             }
         }
         self.send_token_count_event(turn_context).await;
+        if token_usage.is_some() {
+            self.warn_about_session_cost(turn_context).await;
+        }
     }
 
     pub(crate) async fn recompute_token_usage(&self, turn_context: &TurnContext) {
@@ -1355,6 +2128,7 @@ impl Session {
                 total_token_usage: TokenUsage::default(),
                 last_token_usage: TokenUsage::default(),
                 model_context_window: None,
+                per_model_usage: Vec::new(),
             });
 
             info.last_token_usage = TokenUsage {
@@ -1393,6 +2167,7 @@ impl Session {
         };
         let event = EventMsg::TokenCount(TokenCountEvent { info, rate_limits });
         self.send_event(turn_context, event).await;
+        self.refresh_status_file().await;
     }
 
     pub(crate) async fn set_total_tokens_full(&self, turn_context: &TurnContext) {
@@ -1608,6 +2383,7 @@ async fn submission_loop(sess: Arc<Session>, config: Arc<Config>, rx_sub: Receiv
                 approval_policy,
                 sandbox_policy,
                 model,
+                model_provider,
                 effort,
                 summary,
             } => {
@@ -1619,6 +2395,7 @@ async fn submission_loop(sess: Arc<Session>, config: Arc<Config>, rx_sub: Receiv
                         approval_policy,
                         sandbox_policy,
                         model,
+                        model_provider,
                         reasoning_effort: effort,
                         reasoning_summary: summary,
                         ..Default::default()
@@ -1636,6 +2413,9 @@ async fn submission_loop(sess: Arc<Session>, config: Arc<Config>, rx_sub: Receiv
             Op::PatchApproval { id, decision } => {
                 handlers::patch_approval(&sess, id, decision).await;
             }
+            Op::CostLimitApproval { id, decision } => {
+                handlers::cost_limit_approval(&sess, id, decision).await;
+            }
             Op::AddToHistory { text } => {
                 handlers::add_to_history(&sess, &config, text).await;
             }
@@ -1696,6 +2476,8 @@ mod handlers {
 
     use crate::codex::spawn_review_thread;
     use crate::config::Config;
+    use crate::custom_prompts::PromptModelVariables;
+    use crate::custom_prompts::render_model_variables;
     use crate::features::Feature;
     use crate::mcp::auth::compute_auth_statuses;
     use crate::mcp::collect_mcp_snapshot_from_manager;
@@ -1705,6 +2487,7 @@ mod handlers {
     use crate::tasks::UndoTask;
     use crate::tasks::UserShellCommandTask;
     use codex_protocol::custom_prompts::CustomPrompt;
+    use codex_protocol::openai_models::Capability;
     use codex_protocol::protocol::CodexErrorInfo;
     use codex_protocol::protocol::ErrorEvent;
     use codex_protocol::protocol::Event;
@@ -1771,6 +2554,7 @@ mod handlers {
                     approval_policy: Some(approval_policy),
                     sandbox_policy: Some(sandbox_policy),
                     model: Some(model),
+                    model_provider: None,
                     reasoning_effort: Some(effort),
                     reasoning_summary: Some(summary),
                     final_output_json_schema: Some(final_output_json_schema),
@@ -1784,6 +2568,28 @@ mod handlers {
             // new_turn_with_sub_id already emits the error event.
             return;
         };
+        let has_images = items
+            .iter()
+            .any(|item| matches!(item, UserInput::Image { .. } | UserInput::LocalImage { .. }));
+        if has_images && !current_context.client.get_model_family().supports_images {
+            // Reject before anything is recorded, so the conversation can go
+            // on once the user switches models or drops the image.
+            let invalid = sess.services.models_manager.missing_capability(
+                &current_context.client.get_model(),
+                Capability::Images,
+                &current_context.client.config(),
+            );
+            sess.send_event(
+                &current_context,
+                EventMsg::Error(ErrorEvent {
+                    message: invalid.to_string(),
+                    codex_error_info: Some(CodexErrorInfo::BadRequest),
+                }),
+            )
+            .await;
+            return;
+        }
+        let items = render_prompt_model_variables(sess, &current_context, items).await;
         current_context
             .client
             .get_otel_manager()
@@ -1804,6 +2610,74 @@ mod handlers {
         }
     }
 
+    /// Fill in the `{{model.*}}` variables of custom prompts from the model
+    /// the turn runs on, so every client gets them and a `/model` switch is
+    /// reflected. Unknown fields render empty, with a warning.
+    async fn render_prompt_model_variables(
+        sess: &Session,
+        turn_context: &TurnContext,
+        items: Vec<UserInput>,
+    ) -> Vec<UserInput> {
+        let has_variables = items
+            .iter()
+            .any(|item| matches!(item, UserInput::Text { text } if text.contains("{{")));
+        if !has_variables {
+            return items;
+        }
+        let model = turn_context.client.get_model();
+        let model_family = turn_context.client.get_model_family();
+        let display_name = sess
+            .services
+            .models_manager
+            .list_models(&turn_context.client.config())
+            .await
+            .into_iter()
+            .find(|preset| preset.model == model)
+            .map_or(model, |preset| preset.display_name);
+        let vars = PromptModelVariables {
+            display_name,
+            context_window: model_family.context_window,
+            effort: turn_context
+                .client
+                .get_reasoning_effort()
+                .or(model_family.default_reasoning_effort),
+        };
+
+        let mut unknown_variables: Vec<String> = Vec::new();
+        let items = items
+            .into_iter()
+            .map(|item| match item {
+                UserInput::Text { text } => {
+                    let rendered = render_model_variables(&text, &vars);
+                    for name in rendered.unknown_variables {
+                        if !unknown_variables.contains(&name) {
+                            unknown_variables.push(name);
+                        }
+                    }
+                    UserInput::Text {
+                        text: rendered.text,
+                    }
+                }
+                item => item,
+            })
+            .collect();
+        if !unknown_variables.is_empty() {
+            let names = unknown_variables
+                .iter()
+                .map(|name| format!("{{{{{name}}}}}"))
+                .collect::<Vec<_>>()
+                .join(", ");
+            sess.send_event(
+                turn_context,
+                EventMsg::Warning(WarningEvent {
+                    message: format!("Unknown prompt variable(s) {names} rendered empty."),
+                }),
+            )
+            .await;
+        }
+        items
+    }
+
     pub async fn run_user_shell_command(
         sess: &Arc<Session>,
         sub_id: String,
@@ -1882,6 +2756,10 @@ mod handlers {
         }
     }
 
+    pub async fn cost_limit_approval(sess: &Arc<Session>, id: String, decision: ReviewDecision) {
+        sess.notify_approval(&id, decision).await;
+    }
+
     pub async fn add_to_history(sess: &Arc<Session>, config: &Arc<Config>, text: String) {
         let id = sess.conversation_id;
         let config = Arc::clone(config);
@@ -2053,6 +2931,10 @@ mod handlers {
             sess.send_event_raw(event).await;
         }
 
+        if let Some(status_file) = &sess.services.status_file {
+            status_file.remove().await;
+        }
+
         let event = Event {
             id: sub_id,
             msg: EventMsg::ShutdownComplete,
@@ -2101,7 +2983,30 @@ async fn spawn_review_thread(
     sub_id: String,
     resolved: crate::review_prompts::ResolvedReviewRequest,
 ) {
-    let model = config.review_model.clone();
+    let review_preset = match config.review_model_preset.as_deref() {
+        Some(id) => {
+            sess.flow_preset(
+                &parent_turn_context,
+                "review_model_preset",
+                id,
+                &config.review_model,
+            )
+            .await
+        }
+        None => None,
+    };
+    let (model, provider, provider_id, effort) = match review_preset {
+        Some((preset, provider)) => {
+            let effort = preset_effort(&preset);
+            (preset.model, provider, preset.model_provider, effort)
+        }
+        None => (
+            config.review_model.clone(),
+            parent_turn_context.client.get_provider(),
+            None,
+            Some(ReasoningEffortConfig::Low),
+        ),
+    };
     let review_model_family = sess
         .services
         .models_manager
@@ -2119,20 +3024,25 @@ async fn spawn_review_thread(
 
     let base_instructions = REVIEW_PROMPT.to_string();
     let review_prompt = resolved.prompt.clone();
-    let provider = parent_turn_context.client.get_provider();
     let auth_manager = parent_turn_context.client.get_auth_manager();
     let model_family = review_model_family.clone();
 
-    // Build per‑turn client with the requested model/family.
+    // Build per‑turn client with the requested model/family. The review
+    // sub-agent starts from this config, so it carries the model too.
     let mut per_turn_config = (*config).clone();
-    per_turn_config.model_reasoning_effort = Some(ReasoningEffortConfig::Low);
+    per_turn_config.model = Some(model.clone());
+    per_turn_config.model_provider = provider.clone();
+    if let Some(provider_id) = provider_id {
+        per_turn_config.model_provider_id = provider_id;
+    }
+    per_turn_config.model_reasoning_effort = effort;
     per_turn_config.model_reasoning_summary = ReasoningSummaryConfig::Detailed;
     per_turn_config.features = review_features.clone();
 
-    let otel_manager = parent_turn_context.client.get_otel_manager().with_model(
-        config.review_model.as_str(),
-        review_model_family.slug.as_str(),
-    );
+    let otel_manager = parent_turn_context
+        .client
+        .get_otel_manager()
+        .with_model(model.as_str(), review_model_family.slug.as_str());
 
     let per_turn_config = Arc::new(per_turn_config);
     let client = ModelClient::new(
@@ -2240,9 +3150,23 @@ pub(crate) async fn run_task(
     }
     let event = EventMsg::TaskStarted(TaskStartedEvent {
         model_context_window: turn_context.client.get_model_context_window(),
+        reasoning_effort: turn_context.client.get_reasoning_effort(),
+        reasoning_effort_description: turn_context.reasoning_effort_description(),
+        reasoning_summary_format: Some(
+            turn_context
+                .client
+                .get_model_family()
+                .reasoning_summary_format,
+        ),
     });
     sess.send_event(&turn_context, event).await;
 
+    // Asked once per turn, before anything of the turn is recorded, so a
+    // declined turn leaves the history as it was.
+    if !sess.approve_session_cost(&turn_context).await {
+        return None;
+    }
+
     let skills_outcome = sess.enabled(Feature::Skills).then(|| {
         sess.services
             .skills_manager
@@ -2350,6 +3274,11 @@ pub(crate) async fn run_task(
                 );
                 state.history.replace_last_turn_images("Invalid image");
             }
+            Err(CodexErr::ModelUnavailable(err)) => {
+                info!("Turn error: {err:#}");
+                report_model_unavailable(&sess, &turn_context, err).await;
+                break;
+            }
             Err(e) => {
                 info!("Turn error: {e:#}");
                 let event = EventMsg::Error(e.to_error_event(None));
@@ -2363,11 +3292,63 @@ pub(crate) async fn run_task(
     last_agent_message
 }
 
+/// The provider no longer serves the turn's model. Refresh the catalogs so
+/// clients can offer the models that remain, or, when only this account was
+/// refused, mark the model unavailable to it. Then report the error along with
+/// the preset the model was selected from. The conversation stays intact.
+async fn report_model_unavailable(
+    sess: &Arc<Session>,
+    turn_context: &Arc<TurnContext>,
+    mut err: ModelUnavailableError,
+) {
+    let config = turn_context.client.config();
+    let provider_id = turn_provider_id(turn_context);
+    let models_manager = &sess.services.models_manager;
+    err.preset_id = Some(
+        models_manager
+            .preset_id_for_model(&err.model, &provider_id)
+            .await,
+    );
+    if err.for_account {
+        // The model is still served, so there is nothing to refresh; grey it
+        // out for this account instead.
+        models_manager
+            .mark_unavailable_for_account(&err.model, &err.message, &config)
+            .await;
+    } else if !config.offline
+        && let Err(refresh_err) = models_manager.refresh_all_models(&config).await
+    {
+        warn!("failed to refresh the model catalog: {refresh_err}");
+    }
+    let event = CodexErr::ModelUnavailable(err).to_error_event(None);
+    sess.send_event(turn_context, EventMsg::Error(event)).await;
+}
+
+/// Id of the provider serving `turn_context`, as named under
+/// `[model_providers]`.
+fn turn_provider_id(turn_context: &TurnContext) -> String {
+    let config = turn_context.client.config();
+    let provider = turn_context.client.provider();
+    config
+        .model_providers
+        .iter()
+        .find(|(_, info)| *info == provider)
+        .map_or_else(|| config.model_provider_id.clone(), |(id, _)| id.clone())
+}
+
+/// The effort a flow running on `preset` requests: the preset's default, or
+/// none for a model without reasoning efforts.
+fn preset_effort(preset: &ModelPreset) -> Option<ReasoningEffortConfig> {
+    (!preset.supported_reasoning_efforts.is_empty()).then_some(preset.default_reasoning_effort)
+}
+
 async fn run_auto_compact(sess: &Arc<Session>, turn_context: &Arc<TurnContext>) {
-    if should_use_remote_compact_task(sess.as_ref(), &turn_context.client.get_provider()) {
-        run_inline_remote_auto_compact_task(Arc::clone(sess), Arc::clone(turn_context)).await;
+    let client = sess.compaction_client(turn_context).await;
+    if should_use_remote_compact_task(sess.as_ref(), &client.get_provider()) {
+        run_inline_remote_auto_compact_task(Arc::clone(sess), Arc::clone(turn_context), client)
+            .await;
     } else {
-        run_inline_auto_compact_task(Arc::clone(sess), Arc::clone(turn_context)).await;
+        run_inline_auto_compact_task(Arc::clone(sess), Arc::clone(turn_context), client).await;
     }
 }
 
@@ -2381,42 +3362,25 @@ async fn run_auto_compact(sess: &Arc<Session>, turn_context: &Arc<TurnContext>)
 )]
 async fn run_turn(
     sess: Arc<Session>,
-    turn_context: Arc<TurnContext>,
+    mut turn_context: Arc<TurnContext>,
     turn_diff_tracker: SharedTurnDiffTracker,
     input: Vec<ResponseItem>,
     cancellation_token: CancellationToken,
 ) -> CodexResult<TurnRunResult> {
-    let mcp_tools = sess
+    let mcp_tools: HashMap<String, mcp_types::Tool> = sess
         .services
         .mcp_connection_manager
         .read()
         .await
         .list_all_tools()
         .or_cancel(&cancellation_token)
-        .await?;
-    let router = Arc::new(ToolRouter::from_config(
-        &turn_context.tools_config,
-        Some(
-            mcp_tools
-                .into_iter()
-                .map(|(name, tool)| (name, tool.tool))
-                .collect(),
-        ),
-    ));
-
-    let model_supports_parallel = turn_context
-        .client
-        .get_model_family()
-        .supports_parallel_tool_calls;
-
-    let prompt = Prompt {
-        input,
-        tools: router.specs(),
-        parallel_tool_calls: model_supports_parallel && sess.enabled(Feature::ParallelToolCalls),
-        base_instructions_override: turn_context.base_instructions.clone(),
-        output_schema: turn_context.final_output_json_schema.clone(),
-    };
+        .await?
+        .into_iter()
+        .map(|(name, tool)| (name, tool.tool))
+        .collect();
+    let (mut router, mut prompt) = build_turn_prompt(&sess, &turn_context, &mcp_tools, input).await;
 
+    let mut fell_back = false;
     let mut retries = 0;
     loop {
         match try_run_turn(
@@ -2452,8 +3416,36 @@ async fn run_turn(
             Err(e @ CodexErr::QuotaExceeded) => return Err(e),
             Err(e @ CodexErr::InvalidImageRequest()) => return Err(e),
             Err(e @ CodexErr::InvalidRequest(_)) => return Err(e),
+            Err(e @ CodexErr::ModelUnavailable(_)) => return Err(e),
             Err(e @ CodexErr::RefreshTokenFailed(_)) => return Err(e),
+            Err(e @ CodexErr::SpkiPinMismatch(_)) => return Err(e),
             Err(e) => {
+                // A model at capacity gets one retry on its preset's first
+                // fallback. The next turn starts on the session's model again.
+                if !fell_back && e.is_capacity_error() {
+                    fell_back = true;
+                    if let Some(fallback) = sess.fallback_turn(&turn_context).await {
+                        let previous_model = turn_context.client.get_model();
+                        let model = fallback.client.get_model();
+                        warn!("{previous_model} is at capacity - retrying the turn on {model}");
+                        sess.send_event(
+                            &turn_context,
+                            EventMsg::ModelChanged(ModelChangedEvent {
+                                previous_model,
+                                model,
+                                source: ModelChangeSource::Fallback,
+                                previous_model_provider: Some(turn_provider_id(&turn_context)),
+                                model_provider: Some(turn_provider_id(&fallback)),
+                            }),
+                        )
+                        .await;
+                        let input = std::mem::take(&mut prompt.input);
+                        (router, prompt) =
+                            build_turn_prompt(&sess, &fallback, &mcp_tools, input).await;
+                        turn_context = fallback;
+                        continue;
+                    }
+                }
                 // Use the configured provider-specific stream retry budget.
                 let max_retries = turn_context.client.get_provider().stream_max_retries();
                 if retries < max_retries {
@@ -2485,6 +3477,37 @@ async fn run_turn(
     }
 }
 
+/// The tool router for `turn_context`'s model and the prompt to send it.
+async fn build_turn_prompt(
+    sess: &Session,
+    turn_context: &TurnContext,
+    mcp_tools: &HashMap<String, mcp_types::Tool>,
+    input: Vec<ResponseItem>,
+) -> (Arc<ToolRouter>, Prompt) {
+    let router = Arc::new(ToolRouter::from_config(
+        &turn_context.tools_config,
+        Some(mcp_tools.clone()),
+    ));
+
+    let model_family = turn_context.client.get_model_family();
+    let parallel_tool_calls = if model_family.preset_forbids_parallel_tool_calls() {
+        sess.note_parallel_tool_calls_disabled(model_family.get_model_slug())
+            .await;
+        false
+    } else {
+        model_family.supports_parallel_tool_calls && sess.enabled(Feature::ParallelToolCalls)
+    };
+
+    let prompt = Prompt {
+        input,
+        tools: router.specs(),
+        parallel_tool_calls,
+        base_instructions_override: turn_context.base_instructions.clone(),
+        output_schema: turn_context.final_output_json_schema.clone(),
+    };
+    (router, prompt)
+}
+
 #[derive(Debug)]
 struct TurnRunResult {
     needs_follow_up: bool,
@@ -2532,22 +3555,32 @@ async fn try_run_turn(
         sandbox_policy: turn_context.sandbox_policy.clone(),
         model: turn_context.client.get_model(),
         effort: turn_context.client.get_reasoning_effort(),
+        effort_description: turn_context.reasoning_effort_description(),
         summary: turn_context.client.get_reasoning_summary(),
         base_instructions: turn_context.base_instructions.clone(),
         user_instructions: turn_context.user_instructions.clone(),
         developer_instructions: turn_context.developer_instructions.clone(),
         final_output_json_schema: turn_context.final_output_json_schema.clone(),
         truncation_policy: Some(turn_context.truncation_policy.into()),
+        preset: sess.turn_preset(&turn_context).await,
     });
 
     feedback_tags!(
-        model = turn_context.client.get_model(),
         approval_policy = turn_context.approval_policy,
         sandbox_policy = turn_context.sandbox_policy,
-        effort = turn_context.client.get_reasoning_effort(),
         auth_mode = sess.services.auth_manager.get_auth_mode(),
         features = sess.features.enabled_features(),
     );
+    sess.services
+        .models_manager
+        .feedback_snapshot(
+            &turn_context.client.get_model(),
+            turn_context.client.get_reasoning_effort(),
+            turn_context.client.get_model_family().context_window,
+            &turn_context.client.config(),
+        )
+        .await
+        .record();
 
     sess.persist_rollout_items(&[rollout_item]).await;
     let mut stream = turn_context
@@ -2640,11 +3673,19 @@ async fn try_run_turn(
                 sess.update_rate_limits(&turn_context, snapshot).await;
             }
             ResponseEvent::ModelsEtag(etag) => {
-                // Update internal state with latest models etag
-                sess.services
-                    .models_manager
-                    .refresh_if_new_etag(etag, sess.features.enabled(Feature::RemoteModels))
-                    .await;
+                // Update internal state with latest models etag. Offline sessions
+                // keep the catalog they started with.
+                if !turn_context.client.config().offline {
+                    sess.services
+                        .models_manager
+                        .refresh_if_new_etag(
+                            etag,
+                            sess.features.enabled(Feature::RemoteModels),
+                            resolve_catalog_locale(turn_context.client.config()).as_deref(),
+                            turn_context.client.config().user_agent_suffix.as_deref(),
+                        )
+                        .await;
+                }
             }
             ResponseEvent::Completed {
                 response_id: _,
@@ -2652,6 +3693,14 @@ async fn try_run_turn(
             } => {
                 sess.update_token_usage_info(&turn_context, token_usage.as_ref())
                     .await;
+                // A model refused to this account earlier works again.
+                sess.services
+                    .models_manager
+                    .mark_available_for_account(
+                        &turn_context.client.get_model(),
+                        &turn_context.client.config(),
+                    )
+                    .await;
                 should_emit_turn_diff = true;
 
                 break Ok(TurnRunResult {
@@ -2773,6 +3822,7 @@ mod tests {
     use super::*;
     use crate::CodexAuth;
     use crate::config::ConfigBuilder;
+    use crate::config::types::PresetOverride;
     use crate::exec::ExecToolCallOutput;
     use crate::function_tool::FunctionCallError;
     use crate::shell::default_user_shell;
@@ -2864,6 +3914,7 @@ mod tests {
                 total_tokens: 7,
             },
             model_context_window: Some(1_000),
+            per_model_usage: Vec::new(),
         };
         let info2 = TokenUsageInfo {
             total_token_usage: TokenUsage {
@@ -2881,6 +3932,7 @@ mod tests {
                 total_tokens: 35,
             },
             model_context_window: Some(2_000),
+            per_model_usage: Vec::new(),
         };
 
         rollout_items.push(RolloutItem::EventMsg(EventMsg::TokenCount(
@@ -2933,6 +3985,135 @@ mod tests {
         assert_eq!(expected, actual);
     }
 
+    #[tokio::test]
+    async fn session_configuration_apply_switches_model_provider() {
+        let codex_home = tempfile::tempdir().expect("create temp dir");
+        let config = build_test_config(codex_home.path()).await;
+        let config = Arc::new(config);
+        let model = ModelsManager::get_model_offline(config.model.as_deref());
+        let session_configuration = SessionConfiguration {
+            provider: config.model_provider.clone(),
+            provider_id: config.model_provider_id.clone(),
+            model,
+            model_reasoning_effort: config.model_reasoning_effort,
+            model_reasoning_summary: config.model_reasoning_summary,
+            developer_instructions: config.developer_instructions.clone(),
+            user_instructions: config.user_instructions.clone(),
+            base_instructions: config.base_instructions.clone(),
+            compact_prompt: config.compact_prompt.clone(),
+            approval_policy: config.approval_policy.clone(),
+            sandbox_policy: config.sandbox_policy.clone(),
+            cwd: config.cwd.clone(),
+            original_config_do_not_use: Arc::clone(&config),
+            session_source: SessionSource::Exec,
+        };
+
+        let updated = session_configuration
+            .apply(&SessionSettingsUpdate {
+                model: Some("qwen2.5-coder-7b".to_string()),
+                model_provider: Some("lmstudio".to_string()),
+                ..Default::default()
+            })
+            .expect("known provider should apply");
+        assert_eq!(updated.model, "qwen2.5-coder-7b");
+        assert_eq!(updated.provider, config.model_providers["lmstudio"]);
+        let per_turn_config = Session::build_per_turn_config(&updated);
+        assert_eq!(
+            (
+                per_turn_config.model_provider_id.as_str(),
+                &per_turn_config.model_provider
+            ),
+            ("lmstudio", &config.model_providers["lmstudio"])
+        );
+
+        let err = session_configuration
+            .apply(&SessionSettingsUpdate {
+                model_provider: Some("missing".to_string()),
+                ..Default::default()
+            })
+            .expect_err("unknown provider should be rejected");
+        let mut known: Vec<&str> = config.model_providers.keys().map(String::as_str).collect();
+        known.sort_unstable();
+        assert_eq!(
+            err,
+            ConstraintError::invalid_value("missing", format!("{known:?}"))
+        );
+    }
+
+    #[tokio::test]
+    async fn session_effort_is_clamped_into_the_configured_range() {
+        let codex_home = tempfile::tempdir().expect("create temp dir");
+        let mut config = build_test_config(codex_home.path()).await;
+        config.preset_overrides.insert(
+            "gpt-5.1-codex".to_string(),
+            PresetOverride {
+                min_effort: Some(ReasoningEffortConfig::Low),
+                max_effort: Some(ReasoningEffortConfig::High),
+                default_effort_by_auth: None,
+            },
+        );
+        let config = Arc::new(config);
+        let session_configuration = SessionConfiguration {
+            provider: config.model_provider.clone(),
+            provider_id: config.model_provider_id.clone(),
+            model: "gpt-5.1-codex".to_string(),
+            model_reasoning_effort: None,
+            model_reasoning_summary: config.model_reasoning_summary,
+            developer_instructions: config.developer_instructions.clone(),
+            user_instructions: config.user_instructions.clone(),
+            base_instructions: config.base_instructions.clone(),
+            compact_prompt: config.compact_prompt.clone(),
+            approval_policy: config.approval_policy.clone(),
+            sandbox_policy: config.sandbox_policy.clone(),
+            cwd: config.cwd.clone(),
+            original_config_do_not_use: Arc::clone(&config),
+            session_source: SessionSource::Exec,
+        };
+        let with_effort = |effort| {
+            session_configuration
+                .apply(&SessionSettingsUpdate {
+                    reasoning_effort: Some(effort),
+                    ..Default::default()
+                })
+                .expect("effort updates apply")
+        };
+
+        let mut raised = with_effort(Some(ReasoningEffortConfig::Minimal));
+        assert_eq!(
+            raised.clamp_effort().as_deref(),
+            Some(
+                "`minimal` reasoning effort is below the minimum configured for `gpt-5.1-codex`; using `low`."
+            )
+        );
+        assert_eq!(
+            raised.model_reasoning_effort,
+            Some(ReasoningEffortConfig::Low)
+        );
+
+        let mut lowered = with_effort(Some(ReasoningEffortConfig::XHigh));
+        assert_eq!(
+            lowered.clamp_effort().as_deref(),
+            Some(
+                "`xhigh` reasoning effort is above the maximum configured for `gpt-5.1-codex`; using `high`."
+            )
+        );
+        assert_eq!(
+            lowered.model_reasoning_effort,
+            Some(ReasoningEffortConfig::High)
+        );
+
+        let mut in_range = with_effort(Some(ReasoningEffortConfig::Medium));
+        assert_eq!(in_range.clamp_effort(), None);
+        assert_eq!(
+            in_range.model_reasoning_effort,
+            Some(ReasoningEffortConfig::Medium)
+        );
+
+        let mut auto = with_effort(None);
+        assert_eq!(auto.clamp_effort(), None);
+        assert_eq!(auto.model_reasoning_effort, None);
+    }
+
     #[tokio::test]
     async fn set_rate_limits_retains_previous_credits() {
         let codex_home = tempfile::tempdir().expect("create temp dir");
@@ -2941,6 +4122,7 @@ mod tests {
         let model = ModelsManager::get_model_offline(config.model.as_deref());
         let session_configuration = SessionConfiguration {
             provider: config.model_provider.clone(),
+            provider_id: config.model_provider_id.clone(),
             model,
             model_reasoning_effort: config.model_reasoning_effort,
             model_reasoning_summary: config.model_reasoning_summary,
@@ -3007,6 +4189,7 @@ mod tests {
         let model = ModelsManager::get_model_offline(config.model.as_deref());
         let session_configuration = SessionConfiguration {
             provider: config.model_provider.clone(),
+            provider_id: config.model_provider_id.clone(),
             model,
             model_reasoning_effort: config.model_reasoning_effort,
             model_reasoning_summary: config.model_reasoning_summary,
@@ -3214,6 +4397,7 @@ mod tests {
         let model = ModelsManager::get_model_offline(config.model.as_deref());
         let session_configuration = SessionConfiguration {
             provider: config.model_provider.clone(),
+            provider_id: config.model_provider_id.clone(),
             model,
             model_reasoning_effort: config.model_reasoning_effort,
             model_reasoning_summary: config.model_reasoning_summary,
@@ -3256,6 +4440,7 @@ mod tests {
             models_manager: Arc::clone(&models_manager),
             tool_approvals: Mutex::new(ApprovalStore::default()),
             skills_manager,
+            status_file: None,
         };
 
         let turn_context = Session::make_turn_context(
@@ -3301,6 +4486,7 @@ mod tests {
         let model = ModelsManager::get_model_offline(config.model.as_deref());
         let session_configuration = SessionConfiguration {
             provider: config.model_provider.clone(),
+            provider_id: config.model_provider_id.clone(),
             model,
             model_reasoning_effort: config.model_reasoning_effort,
             model_reasoning_summary: config.model_reasoning_summary,
@@ -3343,6 +4529,7 @@ mod tests {
             models_manager: Arc::clone(&models_manager),
             tool_approvals: Mutex::new(ApprovalStore::default()),
             skills_manager,
+            status_file: None,
         };
 
         let turn_context = Arc::new(Session::make_turn_context(
@@ -2,10 +2,13 @@ use std::sync::Arc;
 
 use crate::ModelProviderInfo;
 use crate::Prompt;
+use crate::client::ModelClient;
 use crate::client_common::ResponseEvent;
 use crate::codex::Session;
 use crate::codex::TurnContext;
 use crate::codex::get_last_assistant_message_from_turn;
+use crate::context_manager::ContextManager;
+use crate::context_manager::estimate_item_token_count;
 use crate::error::CodexErr;
 use crate::error::Result as CodexResult;
 use crate::features::Feature;
@@ -42,42 +45,47 @@ pub(crate) fn should_use_remote_compact_task(
 pub(crate) async fn run_inline_auto_compact_task(
     sess: Arc<Session>,
     turn_context: Arc<TurnContext>,
+    client: ModelClient,
 ) {
     let prompt = turn_context.compact_prompt().to_string();
     let input = vec![UserInput::Text { text: prompt }];
 
-    run_compact_task_inner(sess, turn_context, input).await;
+    run_compact_task_inner(sess, turn_context, client, input).await;
 }
 
+/// Compact the history of `turn_context`'s session, sending the summary
+/// request through `client`, see [`Session::compaction_client`].
 pub(crate) async fn run_compact_task(
     sess: Arc<Session>,
     turn_context: Arc<TurnContext>,
+    client: ModelClient,
     input: Vec<UserInput>,
 ) {
     let start_event = EventMsg::TaskStarted(TaskStartedEvent {
         model_context_window: turn_context.client.get_model_context_window(),
+        reasoning_effort: turn_context.client.get_reasoning_effort(),
+        reasoning_effort_description: turn_context.reasoning_effort_description(),
+        reasoning_summary_format: Some(
+            turn_context
+                .client
+                .get_model_family()
+                .reasoning_summary_format,
+        ),
     });
     sess.send_event(&turn_context, start_event).await;
-    run_compact_task_inner(sess.clone(), turn_context, input).await;
+    run_compact_task_inner(sess.clone(), turn_context, client, input).await;
 }
 
 async fn run_compact_task_inner(
     sess: Arc<Session>,
     turn_context: Arc<TurnContext>,
+    client: ModelClient,
     input: Vec<UserInput>,
 ) {
     let initial_input_for_turn: ResponseInputItem = ResponseInputItem::from(input);
+    let prompt_item: ResponseItem = initial_input_for_turn.into();
 
     let mut history = sess.clone_history().await;
-    history.record_items(
-        &[initial_input_for_turn.into()],
-        turn_context.truncation_policy,
-    );
-
-    let mut truncated_count = 0usize;
-
-    let max_retries = turn_context.client.get_provider().stream_max_retries();
-    let mut retries = 0;
 
     let rollout_item = RolloutItem::TurnContext(TurnContextItem {
         cwd: turn_context.cwd.clone(),
@@ -85,22 +93,82 @@ async fn run_compact_task_inner(
         sandbox_policy: turn_context.sandbox_policy.clone(),
         model: turn_context.client.get_model(),
         effort: turn_context.client.get_reasoning_effort(),
+        effort_description: turn_context.reasoning_effort_description(),
         summary: turn_context.client.get_reasoning_summary(),
         base_instructions: turn_context.base_instructions.clone(),
         user_instructions: turn_context.user_instructions.clone(),
         developer_instructions: turn_context.developer_instructions.clone(),
         final_output_json_schema: turn_context.final_output_json_schema.clone(),
         truncation_policy: Some(turn_context.truncation_policy.into()),
+        preset: sess.turn_preset(&turn_context).await,
     });
     sess.persist_rollout_items(&[rollout_item]).await;
 
+    // The budget is the summarizing model's, which may not be the session's.
+    let model_family = client.get_model_family();
+    let chunks = model_family.summarization_max_tokens().and_then(|budget| {
+        let fixed_tokens = approx_tokens(model_family.base_instructions.as_str())
+            .saturating_add(estimate_item_token_count(&prompt_item));
+        summarization_chunks(history.get_history_for_prompt(), fixed_tokens, budget)
+    });
+
+    let Some(chunks) = chunks else {
+        history.record_items(&[prompt_item], turn_context.truncation_policy);
+        if !run_summary_pass(&sess, &turn_context, &client, history).await {
+            return;
+        }
+        finish_compaction(&sess, &turn_context, &client).await;
+        return;
+    };
+
+    // Each pass summarizes the summary so far together with the next chunk,
+    // so the last pass covers the whole history in order.
+    let pass_count = chunks.len();
+    let mut summary: Option<String> = None;
+    for chunk in chunks {
+        let mut pass = ContextManager::new();
+        let carried = summary.as_deref().map(summary_message);
+        pass.record_items(
+            carried.iter().chain(&chunk).chain([&prompt_item]),
+            turn_context.truncation_policy,
+        );
+        if !run_summary_pass(&sess, &turn_context, &client, pass).await {
+            return;
+        }
+        summary = get_last_assistant_message_from_turn(&sess.clone_history().await.get_history());
+    }
+    sess.notify_background_event(
+        turn_context.as_ref(),
+        format!(
+            "Summarized the conversation in {pass_count} passes so each summary request fits the model's summarization budget."
+        ),
+    )
+    .await;
+    finish_compaction(&sess, &turn_context, &client).await;
+}
+
+/// Send one summarization request for `history`, retrying on stream errors
+/// and dropping the oldest items while it overflows the context window.
+/// `false` when the pass was interrupted or failed; the error has been
+/// reported by then.
+async fn run_summary_pass(
+    sess: &Arc<Session>,
+    turn_context: &Arc<TurnContext>,
+    client: &ModelClient,
+    mut history: ContextManager,
+) -> bool {
+    let mut truncated_count = 0usize;
+
+    let max_retries = client.get_provider().stream_max_retries();
+    let mut retries = 0;
+
     loop {
         let turn_input = history.get_history_for_prompt();
         let prompt = Prompt {
             input: turn_input.clone(),
             ..Default::default()
         };
-        let attempt_result = drain_to_completed(&sess, turn_context.as_ref(), &prompt).await;
+        let attempt_result = drain_to_completed(sess, turn_context.as_ref(), client, &prompt).await;
 
         match attempt_result {
             Ok(()) => {
@@ -113,10 +181,10 @@ async fn run_compact_task_inner(
                     )
                     .await;
                 }
-                break;
+                return true;
             }
             Err(CodexErr::Interrupted) => {
-                return;
+                return false;
             }
             Err(e @ CodexErr::ContextWindowExceeded) => {
                 if turn_input.len() > 1 {
@@ -131,8 +199,8 @@ async fn run_compact_task_inner(
                 }
                 sess.set_total_tokens_full(turn_context.as_ref()).await;
                 let event = EventMsg::Error(e.to_error_event(None));
-                sess.send_event(&turn_context, event).await;
-                return;
+                sess.send_event(turn_context, event).await;
+                return false;
             }
             Err(e) => {
                 if retries < max_retries {
@@ -148,13 +216,86 @@ async fn run_compact_task_inner(
                     continue;
                 } else {
                     let event = EventMsg::Error(e.to_error_event(None));
-                    sess.send_event(&turn_context, event).await;
-                    return;
+                    sess.send_event(turn_context, event).await;
+                    return false;
                 }
             }
         }
     }
+}
+
+/// Split `items` into consecutive chunks small enough that each fits one
+/// summarization request of at most `budget` tokens, oldest first, or `None`
+/// when a single request fits them all.
+///
+/// `fixed_tokens` is what every request carries besides its chunk: the
+/// instructions and the summarization prompt. A quarter of the rest is left
+/// for the summary carried over from the previous pass. When nothing is
+/// left, the history is not split and goes out in one request as before. A
+/// tool output stays in the chunk of its call, and an item too large for
+/// any chunk gets one to itself; a single such item is not split either.
+fn summarization_chunks(
+    items: Vec<ResponseItem>,
+    fixed_tokens: i64,
+    budget: i64,
+) -> Option<Vec<Vec<ResponseItem>>> {
+    let total = items
+        .iter()
+        .map(estimate_item_token_count)
+        .fold(fixed_tokens, i64::saturating_add);
+    if total <= budget {
+        return None;
+    }
+    let capacity = budget.saturating_sub(fixed_tokens) / 4 * 3;
+    if capacity <= 0 {
+        return None;
+    }
+
+    let mut chunks: Vec<Vec<ResponseItem>> = Vec::new();
+    let mut current: Vec<ResponseItem> = Vec::new();
+    let mut current_tokens = 0i64;
+    for item in items {
+        let tokens = estimate_item_token_count(&item);
+        let is_output = matches!(
+            item,
+            ResponseItem::FunctionCallOutput { .. } | ResponseItem::CustomToolCallOutput { .. }
+        );
+        if !current.is_empty() && !is_output && current_tokens.saturating_add(tokens) > capacity {
+            chunks.push(std::mem::take(&mut current));
+            current_tokens = 0;
+        }
+        current_tokens = current_tokens.saturating_add(tokens);
+        current.push(item);
+    }
+    if !current.is_empty() {
+        chunks.push(current);
+    }
+    (chunks.len() > 1).then_some(chunks)
+}
+
+/// The summary of the earlier passes as it opens the next pass's request,
+/// shaped like the summary a compacted history starts from.
+fn summary_message(summary: &str) -> ResponseItem {
+    ResponseItem::Message {
+        id: None,
+        role: "user".to_string(),
+        content: vec![ContentItem::InputText {
+            text: format!("{SUMMARY_PREFIX}\n{summary}"),
+        }],
+    }
+}
+
+fn approx_tokens(text: &str) -> i64 {
+    i64::try_from(approx_token_count(text)).unwrap_or(i64::MAX)
+}
 
+/// Replace the session history with the summary of the last pass and
+/// announce the compaction.
+async fn finish_compaction(
+    sess: &Arc<Session>,
+    turn_context: &Arc<TurnContext>,
+    client: &ModelClient,
+) {
     let history_snapshot = sess.clone_history().await.get_history();
     let summary_suffix =
         get_last_assistant_message_from_turn(&history_snapshot).unwrap_or_default();
@@ -170,7 +311,7 @@ async fn run_compact_task_inner(
         .collect();
     new_history.extend(ghost_snapshots);
     sess.replace_history(new_history).await;
-    sess.recompute_token_usage(&turn_context).await;
+    sess.recompute_token_usage(turn_context).await;
 
     let rollout_item = RolloutItem::Compacted(CompactedItem {
         message: summary_text.clone(),
@@ -178,13 +319,15 @@ async fn run_compact_task_inner(
     });
     sess.persist_rollout_items(&[rollout_item]).await;
 
-    let event = EventMsg::ContextCompacted(ContextCompactedEvent {});
-    sess.send_event(&turn_context, event).await;
+    let event = EventMsg::ContextCompacted(ContextCompactedEvent {
+        model: Some(client.get_model()),
+    });
+    sess.send_event(turn_context, event).await;
 
     let warning = EventMsg::Warning(WarningEvent {
         message: "Heads up: Long conversations and multiple compactions can cause the model to be less accurate. Start a new conversation when possible to keep conversations small and targeted.".to_string(),
     });
-    sess.send_event(&turn_context, warning).await;
+    sess.send_event(turn_context, warning).await;
 }
 
 pub fn content_items_to_text(content: &[ContentItem]) -> Option<String> {
@@ -293,9 +436,10 @@ fn build_compacted_history_with_limit(
 async fn drain_to_completed(
     sess: &Session,
     turn_context: &TurnContext,
+    client: &ModelClient,
     prompt: &Prompt,
 ) -> CodexResult<()> {
-    let mut stream = turn_context.client.clone().stream(prompt).await?;
+    let mut stream = client.clone().stream(prompt).await?;
     loop {
         let maybe_event = stream.next().await;
         let Some(event) = maybe_event else {
@@ -458,6 +602,90 @@ mod tests {
         assert_eq!(summary_text, "SUMMARY");
     }
 
+    fn user_message(text: &str) -> ResponseItem {
+        ResponseItem::Message {
+            id: None,
+            role: "user".to_string(),
+            content: vec![ContentItem::InputText {
+                text: text.to_string(),
+            }],
+        }
+    }
+
+    fn chunk_texts(chunks: &[Vec<ResponseItem>]) -> Vec<Vec<String>> {
+        chunks
+            .iter()
+            .map(|chunk| {
+                chunk
+                    .iter()
+                    .map(|item| match item {
+                        ResponseItem::Message { content, .. } => {
+                            content_items_to_text(content).unwrap_or_default()
+                        }
+                        ResponseItem::FunctionCall { call_id, .. } => format!("call {call_id}"),
+                        ResponseItem::FunctionCallOutput { call_id, .. } => {
+                            format!("output {call_id}")
+                        }
+                        other => panic!("unexpected item {other:?}"),
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn history_within_the_budget_is_not_split() {
+        let items = vec![user_message("first"), user_message("second")];
+        assert_eq!(summarization_chunks(items.clone(), 10, 1_000), None);
+        // With no room besides the instructions there is nothing to split
+        // into, so the history goes out whole as before.
+        assert_eq!(summarization_chunks(items, 1_000, 1_000), None);
+    }
+
+    #[test]
+    fn history_over_the_budget_is_split_in_order() {
+        let a = "a".repeat(400);
+        let b = "b".repeat(400);
+        let c = "c".repeat(400);
+        let items = vec![user_message(&a), user_message(&b), user_message(&c)];
+        let item_tokens = estimate_item_token_count(&items[0]);
+
+        // Room for one message per chunk, with a quarter kept for the summary.
+        let budget = 10 + item_tokens * 4 / 3 + 4;
+        let chunks = summarization_chunks(items, 10, budget).expect("split");
+        assert_eq!(chunk_texts(&chunks), vec![vec![a], vec![b], vec![c]]);
+    }
+
+    #[test]
+    fn tool_outputs_stay_with_their_call() {
+        let call = ResponseItem::FunctionCall {
+            id: None,
+            name: "shell".to_string(),
+            arguments: "{}".to_string(),
+            call_id: "call-1".to_string(),
+        };
+        let output = ResponseItem::FunctionCallOutput {
+            call_id: "call-1".to_string(),
+            output: codex_protocol::models::FunctionCallOutputPayload {
+                content: "x".repeat(400),
+                ..Default::default()
+            },
+        };
+        let before = "before".repeat(60);
+        let items = vec![user_message(&before), call, output, user_message("after")];
+        let budget = 10 + estimate_item_token_count(&items[0]) * 4 / 3 + 4;
+
+        let chunks = summarization_chunks(items, 10, budget).expect("split");
+        assert_eq!(
+            chunk_texts(&chunks),
+            vec![
+                vec![before],
+                vec!["call call-1".to_string(), "output call-1".to_string()],
+                vec!["after".to_string()],
+            ]
+        );
+    }
+
     #[test]
     fn build_token_limited_compacted_history_appends_summary_message() {
         let initial_context: Vec<ResponseItem> = Vec::new();
@@ -1,6 +1,7 @@
 use std::sync::Arc;
 
 use crate::Prompt;
+use crate::client::ModelClient;
 use crate::codex::Session;
 use crate::codex::TurnContext;
 use crate::error::Result as CodexResult;
@@ -14,21 +15,38 @@ use codex_protocol::models::ResponseItem;
 pub(crate) async fn run_inline_remote_auto_compact_task(
     sess: Arc<Session>,
     turn_context: Arc<TurnContext>,
+    client: ModelClient,
 ) {
-    run_remote_compact_task_inner(&sess, &turn_context).await;
+    run_remote_compact_task_inner(&sess, &turn_context, &client).await;
 }
 
-pub(crate) async fn run_remote_compact_task(sess: Arc<Session>, turn_context: Arc<TurnContext>) {
+pub(crate) async fn run_remote_compact_task(
+    sess: Arc<Session>,
+    turn_context: Arc<TurnContext>,
+    client: ModelClient,
+) {
     let start_event = EventMsg::TaskStarted(TaskStartedEvent {
         model_context_window: turn_context.client.get_model_context_window(),
+        reasoning_effort: turn_context.client.get_reasoning_effort(),
+        reasoning_effort_description: turn_context.reasoning_effort_description(),
+        reasoning_summary_format: Some(
+            turn_context
+                .client
+                .get_model_family()
+                .reasoning_summary_format,
+        ),
     });
     sess.send_event(&turn_context, start_event).await;
 
-    run_remote_compact_task_inner(&sess, &turn_context).await;
+    run_remote_compact_task_inner(&sess, &turn_context, &client).await;
 }
 
-async fn run_remote_compact_task_inner(sess: &Arc<Session>, turn_context: &Arc<TurnContext>) {
-    if let Err(err) = run_remote_compact_task_inner_impl(sess, turn_context).await {
+async fn run_remote_compact_task_inner(
+    sess: &Arc<Session>,
+    turn_context: &Arc<TurnContext>,
+    client: &ModelClient,
+) {
+    if let Err(err) = run_remote_compact_task_inner_impl(sess, turn_context, client).await {
         let event = EventMsg::Error(
             err.to_error_event(Some("Error running remote compact task".to_string())),
         );
@@ -39,6 +57,7 @@ async fn run_remote_compact_task_inner(sess: &Arc<Session>, turn_context: &Arc<T
 async fn run_remote_compact_task_inner_impl(
     sess: &Arc<Session>,
     turn_context: &Arc<TurnContext>,
+    client: &ModelClient,
 ) -> CodexResult<()> {
     let mut history = sess.clone_history().await;
     let prompt = Prompt {
@@ -49,10 +68,7 @@ async fn run_remote_compact_task_inner_impl(
         output_schema: None,
     };
 
-    let mut new_history = turn_context
-        .client
-        .compact_conversation_history(&prompt)
-        .await?;
+    let mut new_history = client.compact_conversation_history(&prompt).await?;
     // Required to keep `/undo` available after compaction
     let ghost_snapshots: Vec<ResponseItem> = history
         .get_history()
@@ -74,7 +90,9 @@ async fn run_remote_compact_task_inner_impl(
     sess.persist_rollout_items(&[RolloutItem::Compacted(compacted_item)])
         .await;
 
-    let event = EventMsg::ContextCompacted(ContextCompactedEvent {});
+    let event = EventMsg::ContextCompacted(ContextCompactedEvent {
+        model: Some(client.get_model()),
+    });
     sess.send_event(turn_context, event).await;
 
     Ok(())
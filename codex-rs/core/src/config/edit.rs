@@ -3,6 +3,7 @@ use crate::config::types::McpServerConfig;
 use crate::config::types::Notice;
 use anyhow::Context;
 use codex_protocol::config_types::TrustLevel;
+use codex_protocol::openai_models::EffortSelection;
 use codex_protocol::openai_models::ReasoningEffort;
 use std::collections::BTreeMap;
 use std::path::Path;
@@ -17,11 +18,18 @@ use toml_edit::value;
 /// Discrete config mutations supported by the persistence engine.
 #[derive(Clone, Debug)]
 pub enum ConfigEdit {
-    /// Update the active (or default) model selection and optional reasoning effort.
+    /// Update the active (or default) model selection and optional reasoning
+    /// effort. An effort of `None` removes the key.
     SetModel {
         model: Option<String>,
-        effort: Option<ReasoningEffort>,
+        effort: Option<EffortSelection>,
     },
+    /// Update the active (or default) `model_provider` value. `None` removes
+    /// the key.
+    SetModelProvider(Option<String>),
+    /// Replace `model = from` with `model = to` at the top level and in
+    /// every profile, leaving other values alone.
+    RenameModel { from: String, to: String },
     /// Toggle the acknowledgement flag under `[notice]`.
     SetNoticeHideFullAccessWarning(bool),
     /// Toggle the Windows world-writable directories warning acknowledgement flag.
@@ -265,6 +273,11 @@ impl ConfigDocument {
                 );
                 mutated
             }),
+            ConfigEdit::SetModelProvider(provider) => Ok(self.write_profile_value(
+                &["model_provider"],
+                provider.as_ref().map(|provider| value(provider.clone())),
+            )),
+            ConfigEdit::RenameModel { from, to } => Ok(self.rename_model(from, to)),
             ConfigEdit::SetNoticeHideFullAccessWarning(acknowledged) => Ok(self.write_value(
                 Scope::Global,
                 &[Notice::TABLE_KEY, "hide_full_access_warning"],
@@ -334,6 +347,36 @@ impl ConfigDocument {
         self.remove(segments)
     }
 
+    fn rename_model(&mut self, from: &str, to: &str) -> bool {
+        let root = self.doc.as_table();
+        let mut targets: Vec<Vec<String>> = Vec::new();
+        if root.get("model").and_then(TomlItem::as_str) == Some(from) {
+            targets.push(vec!["model".to_string()]);
+        }
+        if let Some(profiles) = root.get("profiles").and_then(TomlItem::as_table_like) {
+            for (name, profile) in profiles.iter() {
+                if profile
+                    .as_table_like()
+                    .and_then(|profile| profile.get("model"))
+                    .and_then(TomlItem::as_str)
+                    == Some(from)
+                {
+                    targets.push(vec![
+                        "profiles".to_string(),
+                        name.to_string(),
+                        "model".to_string(),
+                    ]);
+                }
+            }
+        }
+
+        let mut mutated = false;
+        for segments in targets {
+            mutated |= self.insert(&segments, value(to));
+        }
+        mutated
+    }
+
     fn replace_mcp_servers(&mut self, servers: &BTreeMap<String, McpServerConfig>) -> bool {
         if servers.is_empty() {
             return self.clear(Scope::Global, &["mcp_servers"]);
@@ -588,7 +631,17 @@ impl ConfigEditsBuilder {
         self
     }
 
-    pub fn set_model(mut self, model: Option<&str>, effort: Option<ReasoningEffort>) -> Self {
+    pub fn set_model(self, model: Option<&str>, effort: Option<ReasoningEffort>) -> Self {
+        self.set_model_with_effort_selection(model, effort.map(EffortSelection::Explicit))
+    }
+
+    /// Like [`Self::set_model`], but can also write `model_reasoning_effort =
+    /// "auto"`.
+    pub fn set_model_with_effort_selection(
+        mut self,
+        model: Option<&str>,
+        effort: Option<EffortSelection>,
+    ) -> Self {
         self.edits.push(ConfigEdit::SetModel {
             model: model.map(ToOwned::to_owned),
             effort,
@@ -596,6 +649,21 @@ impl ConfigEditsBuilder {
         self
     }
 
+    pub fn rename_model(mut self, from: &str, to: &str) -> Self {
+        self.edits.push(ConfigEdit::RenameModel {
+            from: from.to_string(),
+            to: to.to_string(),
+        });
+        self
+    }
+
+    pub fn set_model_provider(mut self, provider: Option<&str>) -> Self {
+        self.edits.push(ConfigEdit::SetModelProvider(
+            provider.map(ToOwned::to_owned),
+        ));
+        self
+    }
+
     pub fn set_hide_full_access_warning(mut self, acknowledged: bool) -> Self {
         self.edits
             .push(ConfigEdit::SetNoticeHideFullAccessWarning(acknowledged));
@@ -706,7 +774,7 @@ mod tests {
             None,
             &[ConfigEdit::SetModel {
                 model: Some("gpt-5.1-codex".to_string()),
-                effort: Some(ReasoningEffort::High),
+                effort: Some(EffortSelection::Explicit(ReasoningEffort::High)),
             }],
         )
         .expect("persist");
@@ -865,7 +933,7 @@ profiles = { fast = { model = "gpt-4o", sandbox_mode = "strict" } }
             None,
             &[ConfigEdit::SetModel {
                 model: None,
-                effort: Some(ReasoningEffort::High),
+                effort: Some(EffortSelection::Explicit(ReasoningEffort::High)),
             }],
         )
         .expect("persist");
@@ -900,7 +968,7 @@ model_reasoning_effort = "low"
             None,
             &[ConfigEdit::SetModel {
                 model: Some("o5-preview".to_string()),
-                effort: Some(ReasoningEffort::Minimal),
+                effort: Some(EffortSelection::Explicit(ReasoningEffort::Minimal)),
             }],
         )
         .expect("persist");
@@ -1420,6 +1488,77 @@ model_reasoning_effort = "high"
         assert_eq!(contents, expected);
     }
 
+    #[tokio::test]
+    async fn auto_effort_selection_is_written_as_auto() {
+        let tmp = tempdir().expect("tmpdir");
+        let codex_home = tmp.path().to_path_buf();
+        std::fs::write(
+            codex_home.join(CONFIG_TOML_FILE),
+            r#"model_reasoning_effort = "high"
+
+[profiles.dev]
+model_reasoning_effort = "low"
+"#,
+        )
+        .expect("seed config");
+
+        ConfigEditsBuilder::new(&codex_home)
+            .with_profile(Some("dev"))
+            .set_model_with_effort_selection(Some("gpt-5.1-codex"), Some(EffortSelection::Auto))
+            .apply()
+            .await
+            .expect("persist");
+
+        let contents =
+            std::fs::read_to_string(codex_home.join(CONFIG_TOML_FILE)).expect("read config");
+        let expected = r#"model_reasoning_effort = "high"
+
+[profiles.dev]
+model_reasoning_effort = "auto"
+model = "gpt-5.1-codex"
+"#;
+        assert_eq!(contents, expected);
+    }
+
+    #[test]
+    fn rename_model_rewrites_every_table_using_the_old_slug() {
+        let tmp = tempdir().expect("tmpdir");
+        let codex_home = tmp.path();
+        std::fs::write(
+            codex_home.join(CONFIG_TOML_FILE),
+            r#"# retired soon
+model = "codex-mini-latest" # keep me
+model_reasoning_effort = "high"
+
+[profiles.dev]
+model = "codex-mini-latest"
+
+[profiles.other]
+model = "o3"
+"#,
+        )
+        .expect("seed config");
+
+        ConfigEditsBuilder::new(codex_home)
+            .rename_model("codex-mini-latest", "gpt-5.1-codex-mini")
+            .apply_blocking()
+            .expect("persist");
+
+        let contents =
+            std::fs::read_to_string(codex_home.join(CONFIG_TOML_FILE)).expect("read config");
+        let expected = r#"# retired soon
+model = "gpt-5.1-codex-mini" # keep me
+model_reasoning_effort = "high"
+
+[profiles.dev]
+model = "gpt-5.1-codex-mini"
+
+[profiles.other]
+model = "o3"
+"#;
+        assert_eq!(contents, expected);
+    }
+
     #[test]
     fn blocking_builder_set_model_round_trips_back_and_forth() {
         let tmp = tempdir().expect("tmpdir");
@@ -2,11 +2,14 @@ use crate::auth::AuthCredentialsStoreMode;
 use crate::config::types::DEFAULT_OTEL_ENVIRONMENT;
 use crate::config::types::History;
 use crate::config::types::McpServerConfig;
+use crate::config::types::ModelCatalogSource;
+use crate::config::types::ModelPresetOverride;
 use crate::config::types::Notice;
 use crate::config::types::Notifications;
 use crate::config::types::OtelConfig;
 use crate::config::types::OtelConfigToml;
 use crate::config::types::OtelExporterKind;
+use crate::config::types::PresetOverride;
 use crate::config::types::SandboxWorkspaceWrite;
 use crate::config::types::ScrollInputMode;
 use crate::config::types::ShellEnvironmentPolicy;
@@ -16,28 +19,41 @@ use crate::config::types::UriBasedFileOpener;
 use crate::config_loader::ConfigLayerStack;
 use crate::config_loader::ConfigRequirements;
 use crate::config_loader::LoaderOverrides;
+use crate::config_loader::build_cli_overrides_layer;
 use crate::config_loader::load_config_layers_state;
+use crate::config_loader::merge_toml_values;
+use crate::default_client::sanitize_user_agent_suffix;
 use crate::features::Feature;
 use crate::features::FeatureOverrides;
 use crate::features::Features;
 use crate::features::FeaturesToml;
 use crate::git_info::resolve_root_git_project_for_trust;
 use crate::model_provider_info::LMSTUDIO_OSS_PROVIDER_ID;
+use crate::model_provider_info::ModelCatalog;
 use crate::model_provider_info::ModelProviderInfo;
 use crate::model_provider_info::OLLAMA_OSS_PROVIDER_ID;
+use crate::model_provider_info::ProviderCatalogConfig;
 use crate::model_provider_info::built_in_model_providers;
+use crate::model_provider_info::normalize_base_url;
+use crate::models_manager::manager::DEFAULT_MODEL_CACHE_MAX_STALENESS;
+use crate::models_manager::model_presets::DEFAULT_REVIEW_PRESET_ID;
+use crate::models_manager::request_headers::validate_request_headers;
+use crate::models_manager::state_gc::DEFAULT_MODEL_STATE_RETENTION;
 use crate::project_doc::DEFAULT_PROJECT_DOC_FILENAME;
 use crate::project_doc::LOCAL_PROJECT_DOC_FILENAME;
 use crate::protocol::AskForApproval;
 use crate::protocol::SandboxPolicy;
 use codex_app_server_protocol::Tools;
 use codex_app_server_protocol::UserSavedConfig;
+use codex_otel::catalog::ModelIdKey;
 use codex_protocol::config_types::ForcedLoginMethod;
 use codex_protocol::config_types::ReasoningSummary;
 use codex_protocol::config_types::SandboxMode;
 use codex_protocol::config_types::TrustLevel;
 use codex_protocol::config_types::Verbosity;
+use codex_protocol::openai_models::EffortSelection;
 use codex_protocol::openai_models::ReasoningEffort;
+use codex_protocol::openai_models::ReasoningSummaryFormat;
 use codex_rmcp_client::OAuthCredentialsStoreMode;
 use codex_utils_absolute_path::AbsolutePathBuf;
 use codex_utils_absolute_path::AbsolutePathBufGuard;
@@ -50,6 +66,7 @@ use std::collections::HashMap;
 use std::io::ErrorKind;
 use std::path::Path;
 use std::path::PathBuf;
+use std::time::Duration;
 #[cfg(test)]
 use tempfile::tempdir;
 
@@ -60,6 +77,7 @@ use toml_edit::DocumentMut;
 mod constraint;
 pub mod edit;
 pub mod profile;
+pub mod project_trust;
 pub mod service;
 pub mod types;
 pub use constraint::Constrained;
@@ -69,7 +87,7 @@ pub use constraint::ConstraintResult;
 pub use service::ConfigService;
 pub use service::ConfigServiceError;
 
-const OPENAI_DEFAULT_REVIEW_MODEL: &str = "gpt-5.1-codex-max";
+const OPENAI_DEFAULT_REVIEW_MODEL: &str = DEFAULT_REVIEW_PRESET_ID;
 
 pub use codex_git::GhostSnapshotConfig;
 
@@ -78,6 +96,10 @@ pub use codex_git::GhostSnapshotConfig;
 /// the context window.
 pub(crate) const PROJECT_DOC_MAX_BYTES: usize = 32 * 1024; // 32 KiB
 
+/// Default `catalog_refresh_jitter`: the cache TTL and refresh interval each
+/// vary by up to 20% either way between processes.
+pub(crate) const DEFAULT_CATALOG_REFRESH_JITTER: f64 = 0.2;
+
 pub const CONFIG_TOML_FILE: &str = "config.toml";
 
 #[cfg(test)]
@@ -104,12 +126,29 @@ pub struct Config {
     /// Model used specifically for review sessions. Defaults to "gpt-5.1-codex-max".
     pub review_model: String,
 
+    /// Id of the preset reviews run on, in place of `review_model`.
+    pub review_model_preset: Option<String>,
+
+    /// Id of the preset compaction runs on, in place of the session model.
+    pub compact_model_preset: Option<String>,
+
     /// Size of the context window for the model, in tokens.
     pub model_context_window: Option<i64>,
 
     /// Token usage threshold triggering auto-compaction of conversation history.
     pub model_auto_compact_token_limit: Option<i64>,
 
+    /// Cap on output tokens per request. Clamped to the model's maximum, and
+    /// superseded by a cap set on the selected reasoning effort.
+    pub model_max_output_tokens: Option<i64>,
+
+    /// Sampling temperature. Overrides the model default, and is dropped for
+    /// models that do not accept one.
+    pub model_temperature: Option<f32>,
+
+    /// Nucleus sampling `top_p`, handled like `model_temperature`.
+    pub model_top_p: Option<f32>,
+
     /// Key into the model_providers map that specifies which provider to use.
     pub model_provider_id: String,
 
@@ -262,12 +301,19 @@ pub struct Config {
     /// Combined provider map (defaults merged with user-defined overrides).
     pub model_providers: HashMap<String, ModelProviderInfo>,
 
+    /// Project config settings left out because the workspace is not trusted.
+    pub ignored_project_settings: Vec<project_trust::IgnoredProjectSetting>,
+
     /// Maximum number of bytes to include from an AGENTS.md project doc file.
     pub project_doc_max_bytes: usize,
 
     /// Additional filenames to try when looking for project-level docs.
     pub project_doc_fallback_filenames: Vec<String>,
 
+    /// Check `@model:` references in project docs against the model catalog
+    /// at session start, warning about models it does not list.
+    pub validate_instruction_models: bool,
+
     // todo(aibrahim): this should be used in the override model family
     /// Token budget applied when storing tool/function outputs in the context manager.
     pub tool_output_token_limit: Option<usize>,
@@ -292,7 +338,8 @@ pub struct Config {
     pub codex_linux_sandbox_exe: Option<PathBuf>,
 
     /// Value to use for `reasoning.effort` when making a request using the
-    /// Responses API.
+    /// Responses API. `None` is the `auto` selection: the active model's
+    /// default applies, and follows the model when it changes.
     pub model_reasoning_effort: Option<ReasoningEffort>,
 
     /// If not "none", the value to use for `reasoning.summary` when making a
@@ -302,6 +349,11 @@ pub struct Config {
     /// Optional override to force-enable reasoning summaries for the configured model.
     pub model_supports_reasoning_summaries: Option<bool>,
 
+    /// How to render the model's reasoning summaries. `None` leaves it to the
+    /// active model's preset; see
+    /// [`crate::models_manager::model_family::ModelFamily::reasoning_summary_format`].
+    pub model_reasoning_summary_format: Option<ReasoningSummaryFormat>,
+
     /// Optional verbosity control for GPT-5 models (Responses API `text.verbosity`).
     pub model_verbosity: Option<Verbosity>,
 
@@ -348,6 +400,87 @@ pub struct Config {
     /// Defaults to `true`.
     pub check_for_update_on_startup: bool,
 
+    /// When `true`, Codex never reaches out to list models: the picker is
+    /// built from bundled presets and the on-disk models cache only.
+    pub offline: bool,
+
+    /// When `true`, a configured `model` missing from the catalog fails
+    /// session start instead of producing a warning.
+    pub strict_model_validation: bool,
+
+    /// When `true`, a retired built-in `model` in `config.toml` is rewritten
+    /// to its successor at session start.
+    pub rewrite_retired_models: bool,
+
+    /// When `true`, the TUI starts listing models in the background as soon
+    /// as it starts, so the model picker opens with a warm catalog.
+    pub prefetch_model_catalog: bool,
+
+    /// When `true`, each session keeps `$CODEX_HOME/run/<session>.json` up to
+    /// date with its model, effort, and context usage.
+    pub write_status_file: bool,
+
+    /// Estimated session cost in USD past which a warning is shown once.
+    pub session_cost_warning_usd: Option<f64>,
+
+    /// Estimated session cost in USD at which each turn asks before it
+    /// starts whether to go on.
+    pub session_cost_limit_usd: Option<f64>,
+
+    /// Catalogs consulted for model presets, highest precedence first. A
+    /// model listed by several keeps the entry from the first.
+    pub model_catalog_sources: Vec<ModelCatalogSource>,
+
+    /// Sanitized token appended to the User-Agent of model catalog and model
+    /// API requests, e.g. to identify an embedder behind a gateway.
+    pub user_agent_suffix: Option<String>,
+
+    /// Locale sent as `Accept-Language` on model catalog requests. `None`
+    /// falls back to the system locale; an empty string sends none.
+    pub catalog_locale: Option<String>,
+
+    /// Fraction by which this process varies the catalog cache TTL and the
+    /// background refresh interval, so processes started together do not
+    /// refetch together.
+    pub catalog_refresh_jitter: f64,
+
+    /// How old a models cache past its TTL may be and still be served while
+    /// a background refresh replaces it. Older caches are refetched first.
+    pub model_cache_max_staleness: Duration,
+
+    /// How long per-model state such as seen models and availability marks
+    /// is kept after the last catalog that listed the model.
+    pub model_state_retention: Duration,
+
+    /// Base URLs app-server requests may point the selected provider at with
+    /// `catalogBaseUrl`, normalized like provider `base_url`s.
+    pub allowed_catalog_base_urls: Vec<String>,
+
+    /// The base URL set by [`Config::override_catalog_base_url`]. Configs
+    /// loaded from `config.toml` never set it.
+    pub catalog_base_url: Option<String>,
+
+    /// Set by [`Config::override_catalog_base_url`] when the tenant gateway
+    /// is on another origin than the provider's own `base_url`. Neither the
+    /// provider's credentials nor the signed-in account's are sent there.
+    pub catalog_credentials_withheld: bool,
+
+    /// Replacement descriptions for reasoning efforts, shown in place of the
+    /// ones the presets give, e.g. to translate them.
+    pub effort_descriptions: HashMap<ReasoningEffort, String>,
+
+    /// Reasoning efforts removed from every preset. Requests for one are
+    /// rejected, and defaults that point at one use the nearest effort left.
+    pub disabled_reasoning_efforts: Vec<ReasoningEffort>,
+
+    /// Reasoning effort limits per model slug. Efforts outside a model's
+    /// range are hidden from the picker and clamped into it on requests.
+    pub preset_overrides: HashMap<String, PresetOverride>,
+
+    /// Display names and descriptions per preset id, applied on top of
+    /// whichever catalog listed the preset.
+    pub model_preset_overrides: HashMap<String, ModelPresetOverride>,
+
     /// When true, disables burst-paste detection for typed input entirely.
     /// All characters are inserted as they are received, and no buffering
     /// or placeholder replacement will occur for fast keypress bursts.
@@ -363,6 +496,7 @@ pub struct ConfigBuilder {
     cli_overrides: Option<Vec<(String, TomlValue)>>,
     harness_overrides: Option<ConfigOverrides>,
     loader_overrides: Option<LoaderOverrides>,
+    user_agent_suffix: Option<String>,
 }
 
 impl ConfigBuilder {
@@ -386,12 +520,21 @@ impl ConfigBuilder {
         self
     }
 
+    /// Suffix appended to the User-Agent of the model catalog and model API
+    /// requests made with this config, taking precedence over
+    /// `user_agent_suffix` in config.toml.
+    pub fn user_agent_suffix(mut self, user_agent_suffix: impl Into<String>) -> Self {
+        self.user_agent_suffix = Some(user_agent_suffix.into());
+        self
+    }
+
     pub async fn build(self) -> std::io::Result<Config> {
         let Self {
             codex_home,
             cli_overrides,
             harness_overrides,
             loader_overrides,
+            user_agent_suffix,
         } = self;
         let codex_home = codex_home.map_or_else(find_codex_home, std::io::Result::Ok)?;
         let cli_overrides = cli_overrides.unwrap_or_default();
@@ -413,12 +556,16 @@ impl ConfigBuilder {
         let config_toml: ConfigToml = merged_toml
             .try_into()
             .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
-        Config::load_config_with_layer_stack(
+        let mut config = Config::load_config_with_layer_stack(
             config_toml,
             harness_overrides,
             codex_home,
             config_layer_stack,
-        )
+        )?;
+        if let Some(user_agent_suffix) = user_agent_suffix {
+            config.user_agent_suffix = sanitize_user_agent_suffix(&user_agent_suffix);
+        }
+        Ok(config)
     }
 }
 
@@ -450,6 +597,67 @@ impl Config {
             .build()
             .await
     }
+
+    /// Point the selected provider at `base_url`, for an app-server request
+    /// that names the gateway of one of several tenants. Its catalog and its
+    /// turns then both go there. `base_url` must be listed in
+    /// `allowed_catalog_base_urls`, and the provider must fetch its models
+    /// from a `model_catalog`. Credentials only go to the origin they were
+    /// configured for: a gateway elsewhere is reached without them, see
+    /// [`Config::catalog_credentials_withheld`].
+    pub fn override_catalog_base_url(&mut self, base_url: &str) -> std::io::Result<()> {
+        let invalid = |message: String| std::io::Error::new(ErrorKind::InvalidInput, message);
+        let base_url = normalize_base_url(base_url).map_err(invalid)?.url;
+        if !self.allowed_catalog_base_urls.contains(&base_url) {
+            return Err(invalid(format!(
+                "catalog base URL `{base_url}` is not listed in `allowed_catalog_base_urls`"
+            )));
+        }
+        let provider_id = &self.model_provider_id;
+        if !matches!(
+            self.model_provider.model_catalog,
+            Some(ModelCatalog::Openai | ModelCatalog::Azure)
+        ) {
+            return Err(invalid(format!(
+                "model provider `{provider_id}` has no `model_catalog` to fetch from `{base_url}`"
+            )));
+        }
+        let withhold = !same_origin(self.model_provider.base_url.as_deref(), &base_url);
+        for provider in std::iter::once(&mut self.model_provider)
+            .chain(self.model_providers.get_mut(provider_id.as_str()))
+        {
+            provider.base_url = Some(base_url.clone());
+            if withhold {
+                provider.withhold_credentials();
+            }
+        }
+        self.catalog_base_url = Some(base_url);
+        self.catalog_credentials_withheld |= withhold;
+        Ok(())
+    }
+
+    /// Catalog settings of every provider that declares a `model_catalog`,
+    /// sorted by provider id. In offline mode only providers with
+    /// `model_catalog = "none"` are returned, since their models come from
+    /// config.
+    pub fn catalog_providers(&self) -> Vec<ProviderCatalogConfig> {
+        let mut providers: Vec<ProviderCatalogConfig> = self
+            .model_providers
+            .iter()
+            .filter_map(|(provider_id, provider)| {
+                Some(ProviderCatalogConfig {
+                    provider_id: provider_id.clone(),
+                    catalog: provider.model_catalog?,
+                    path: provider.models_path.clone(),
+                    provider: provider.clone(),
+                    user_agent_suffix: self.user_agent_suffix.clone(),
+                })
+            })
+            .filter(|catalog| !self.offline || catalog.catalog == ModelCatalog::None)
+            .collect();
+        providers.sort_by(|a, b| a.provider_id.cmp(&b.provider_id));
+        providers
+    }
 }
 
 /// DEPRECATED: Use [Config::load_with_cli_overrides()] instead because working
@@ -477,6 +685,25 @@ pub async fn load_config_as_toml_with_cli_overrides(
     Ok(cfg)
 }
 
+/// Read the config file at `path` on its own, without the other config
+/// layers, with `cli_overrides` applied on top. Relative paths resolve against
+/// the file's directory. Nothing beyond deserializing is checked, so tools
+/// that report problems can inspect a config that would not load.
+pub fn load_config_toml_file(
+    path: &Path,
+    cli_overrides: &[(String, TomlValue)],
+) -> std::io::Result<ConfigToml> {
+    let contents = std::fs::read_to_string(path)?;
+    let mut root_value: TomlValue =
+        toml::from_str(&contents).map_err(|e| std::io::Error::new(ErrorKind::InvalidData, e))?;
+    if !cli_overrides.is_empty() {
+        merge_toml_values(&mut root_value, &build_cli_overrides_layer(cli_overrides));
+    }
+    let path = std::path::absolute(path)?;
+    let base_dir = path.parent().unwrap_or(&path);
+    deserialize_config_toml_with_base(root_value, base_dir)
+}
+
 fn deserialize_config_toml_with_base(
     root_value: TomlValue,
     config_base_dir: &Path,
@@ -540,6 +767,315 @@ fn ensure_no_inline_bearer_tokens(value: &TomlValue) -> std::io::Result<()> {
     Ok(())
 }
 
+/// A provider with `model_catalog = "none"` is never asked for its models, so
+/// it must list them; otherwise the model picker would be empty.
+fn validate_fixed_model_lists(
+    model_providers: &HashMap<String, ModelProviderInfo>,
+) -> std::io::Result<()> {
+    let mut unlisted: Vec<&String> = model_providers
+        .iter()
+        .filter(|(_, provider)| {
+            provider.has_fixed_models() && provider.models.as_ref().is_none_or(Vec::is_empty)
+        })
+        .map(|(id, _)| id)
+        .collect();
+    unlisted.sort();
+    match unlisted.first() {
+        Some(id) => Err(std::io::Error::new(
+            ErrorKind::InvalidInput,
+            format!(
+                "model provider `{id}` has `model_catalog = \"none\"` but no `models`; list the models it serves"
+            ),
+        )),
+        None => Ok(()),
+    }
+}
+
+/// Catalog endpoints are checked at load so a malformed URL, or one that
+/// would fetch untrusted metadata over plain http, fails before any request.
+fn validate_catalog_endpoints(
+    model_providers: &HashMap<String, ModelProviderInfo>,
+) -> std::io::Result<()> {
+    let mut provider_ids: Vec<&String> = model_providers.keys().collect();
+    provider_ids.sort();
+    for provider_id in provider_ids {
+        if let Err(err) = model_providers[provider_id].validate_catalog_endpoint() {
+            return Err(std::io::Error::new(
+                ErrorKind::InvalidInput,
+                format!("model provider `{provider_id}` {err}"),
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// `allowed_catalog_base_urls`, normalized so a requested URL matches however
+/// its slashes are written.
+fn catalog_base_url_allowlist(urls: &[String]) -> std::io::Result<Vec<String>> {
+    urls.iter()
+        .map(|url| {
+            normalize_base_url(url)
+                .map(|normalized| normalized.url)
+                .map_err(|err| {
+                    std::io::Error::new(
+                        ErrorKind::InvalidInput,
+                        format!("`allowed_catalog_base_urls` entry {err}"),
+                    )
+                })
+        })
+        .collect()
+}
+
+/// Whether `url` has the scheme, host and port of the provider's configured
+/// `base_url`. A provider without one talks to the default OpenAI endpoint,
+/// which no tenant gateway shares.
+fn same_origin(configured: Option<&str>, url: &str) -> bool {
+    let origin = |url: &str| url::Url::parse(url).ok().map(|url| url.origin());
+    configured.and_then(origin).is_some_and(|configured| {
+        configured.is_tuple() && origin(url).is_some_and(|origin| origin == configured)
+    })
+}
+
+/// Cost thresholds must be positive amounts, and a warning after the limit
+/// would never be seen.
+fn validate_session_cost_thresholds(
+    warning_usd: Option<f64>,
+    limit_usd: Option<f64>,
+) -> std::io::Result<()> {
+    for (key, value) in [
+        ("session_cost_warning_usd", warning_usd),
+        ("session_cost_limit_usd", limit_usd),
+    ] {
+        if let Some(value) = value
+            && !(value.is_finite() && value > 0.0)
+        {
+            return Err(std::io::Error::new(
+                ErrorKind::InvalidInput,
+                format!("`{key}` must be a positive amount, got {value}"),
+            ));
+        }
+    }
+    if let (Some(warning_usd), Some(limit_usd)) = (warning_usd, limit_usd)
+        && warning_usd > limit_usd
+    {
+        return Err(std::io::Error::new(
+            ErrorKind::InvalidInput,
+            format!(
+                "`session_cost_warning_usd` ({warning_usd}) is above `session_cost_limit_usd` ({limit_usd})"
+            ),
+        ));
+    }
+    Ok(())
+}
+
+/// The jitter must leave the TTL positive, so it stays below 1.
+fn catalog_refresh_jitter(value: Option<f64>) -> std::io::Result<f64> {
+    let Some(value) = value else {
+        return Ok(DEFAULT_CATALOG_REFRESH_JITTER);
+    };
+    if !(0.0..1.0).contains(&value) {
+        return Err(std::io::Error::new(
+            ErrorKind::InvalidInput,
+            format!("`catalog_refresh_jitter` must be at least 0 and below 1, got {value}"),
+        ));
+    }
+    Ok(value)
+}
+
+/// A preset set as `key` for a configured provider must be one that provider
+/// can serve: it needs a `model_catalog`, and with `model_catalog = "none"`
+/// the model must be in its `models`. Any other id may be served by the
+/// remote catalog, which is not loaded yet, so it is checked against the
+/// catalog when the flow starts.
+fn validate_flow_preset(
+    key: &str,
+    id: &str,
+    model_providers: &HashMap<String, ModelProviderInfo>,
+) -> std::io::Result<()> {
+    let Some((provider_id, model)) = id.split_once('/') else {
+        return Ok(());
+    };
+    let Some(provider) = model_providers.get(provider_id) else {
+        return Ok(());
+    };
+    let message = if provider.model_catalog.is_none() {
+        format!(
+            "`{key} = \"{id}\"` names provider `{provider_id}`, which has no `model_catalog` to serve presets from"
+        )
+    } else if provider.has_fixed_models()
+        && !provider
+            .models
+            .as_ref()
+            .is_some_and(|models| models.iter().any(|listed| listed == model))
+    {
+        format!(
+            "`{key} = \"{id}\"` names model `{model}`, which provider `{provider_id}` does not list in `models`"
+        )
+    } else {
+        return Ok(());
+    };
+    Err(std::io::Error::new(ErrorKind::InvalidInput, message))
+}
+
+/// A preset moved to a provider the config does not define could never be
+/// served, so this is reported at load rather than when the preset is picked.
+fn validate_preset_providers(
+    overrides: &HashMap<String, ModelPresetOverride>,
+    model_providers: &HashMap<String, ModelProviderInfo>,
+) -> std::io::Result<()> {
+    let mut ids: Vec<&String> = overrides.keys().collect();
+    ids.sort();
+    for id in ids {
+        if let Some(provider_id) = &overrides[id].model_provider
+            && !model_providers.contains_key(provider_id)
+        {
+            return Err(std::io::Error::new(
+                ErrorKind::InvalidInput,
+                format!(
+                    "`model_preset_overrides.\"{id}\"` names unknown model provider `{provider_id}`"
+                ),
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Key pins are parsed at load so a typo in a hash is reported up front
+/// instead of refusing every connection to the provider.
+/// The configured catalog order, the default when unset, or built-in
+/// presets alone when the list is empty. A source may be listed once.
+fn catalog_sources(
+    configured: Option<&[ModelCatalogSource]>,
+) -> std::io::Result<Vec<ModelCatalogSource>> {
+    let Some(configured) = configured else {
+        return Ok(ModelCatalogSource::DEFAULT_ORDER.to_vec());
+    };
+    if configured.is_empty() {
+        return Ok(vec![ModelCatalogSource::Builtin]);
+    }
+    for (index, source) in configured.iter().enumerate() {
+        if configured[..index].contains(source) {
+            return Err(std::io::Error::new(
+                ErrorKind::InvalidInput,
+                format!(
+                    "`model_catalog_sources` lists `{}` more than once",
+                    source.as_str()
+                ),
+            ));
+        }
+    }
+    Ok(configured.to_vec())
+}
+
+/// A `min_effort` above the `max_effort` of the same model leaves no effort
+/// to request, so it is rejected at load.
+fn preset_overrides(
+    overrides: HashMap<String, PresetOverride>,
+) -> std::io::Result<HashMap<String, PresetOverride>> {
+    let mut models: Vec<&String> = overrides.keys().collect();
+    models.sort();
+    for model in models {
+        let PresetOverride {
+            min_effort: Some(min),
+            max_effort: Some(max),
+            ..
+        } = overrides[model]
+        else {
+            continue;
+        };
+        if !max.is_within(Some(min), None) {
+            return Err(std::io::Error::new(
+                ErrorKind::InvalidInput,
+                format!(
+                    "`preset_overrides.\"{model}\"` sets `min_effort = \"{min}\"` above `max_effort = \"{max}\"`"
+                ),
+            ));
+        }
+    }
+    Ok(overrides)
+}
+
+fn validate_spki_pins(model_providers: &HashMap<String, ModelProviderInfo>) -> std::io::Result<()> {
+    let mut provider_ids: Vec<&String> = model_providers.keys().collect();
+    provider_ids.sort();
+    for provider_id in provider_ids {
+        if let Err(err) = model_providers[provider_id].spki_pins() {
+            return Err(std::io::Error::new(
+                ErrorKind::InvalidInput,
+                format!("model provider `{provider_id}` {err}"),
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Base URLs are normalized at load so the catalog and model requests are
+/// built from the same URL, and so a malformed one is reported up front
+/// instead of as an opaque request error.
+fn normalize_base_urls(
+    model_providers: &mut HashMap<String, ModelProviderInfo>,
+) -> std::io::Result<()> {
+    let mut provider_ids: Vec<String> = model_providers.keys().cloned().collect();
+    provider_ids.sort();
+    for provider_id in provider_ids {
+        let Some(base_url) = model_providers
+            .get_mut(&provider_id)
+            .and_then(|provider| provider.base_url.as_mut())
+        else {
+            continue;
+        };
+        let normalized = normalize_base_url(base_url).map_err(|err| {
+            std::io::Error::new(
+                ErrorKind::InvalidInput,
+                format!("model provider `{provider_id}` {err}"),
+            )
+        })?;
+        if normalized.assumed_https {
+            tracing::warn!(
+                "model provider `{provider_id}` `base_url` `{base_url}` has no scheme; using `{}`",
+                normalized.url
+            );
+        }
+        *base_url = normalized.url;
+    }
+    Ok(())
+}
+
+/// Per-model request headers are checked once at load so a typo or a
+/// credential header fails fast instead of being sent (or dropped) on every
+/// turn. Header names are lowercased in place.
+fn normalize_model_request_headers(
+    model_providers: &mut HashMap<String, ModelProviderInfo>,
+) -> std::io::Result<()> {
+    let mut provider_ids: Vec<&String> = model_providers.keys().collect();
+    provider_ids.sort();
+    let provider_ids: Vec<String> = provider_ids.into_iter().cloned().collect();
+    for provider_id in provider_ids {
+        let Some(by_model) = model_providers
+            .get_mut(&provider_id)
+            .and_then(|provider| provider.model_request_headers.as_mut())
+        else {
+            continue;
+        };
+        let mut models: Vec<String> = by_model.keys().cloned().collect();
+        models.sort();
+        for model in models {
+            let Some(headers) = by_model.get_mut(&model) else {
+                continue;
+            };
+            *headers = validate_request_headers(headers).map_err(|err| {
+                std::io::Error::new(
+                    ErrorKind::InvalidInput,
+                    format!(
+                        "model provider `{provider_id}` sets an invalid request header for model `{model}`: {err}"
+                    ),
+                )
+            })?;
+        }
+    }
+    Ok(())
+}
+
 pub(crate) fn set_project_trust_level_inner(
     doc: &mut DocumentMut,
     project_path: &Path,
@@ -672,6 +1208,11 @@ pub struct ConfigToml {
     pub model: Option<String>,
     /// Review model override used by the `/review` feature.
     pub review_model: Option<String>,
+    /// Id of a model preset reviews run on, taking precedence over
+    /// `review_model`, e.g. `"gpt-5.1-codex-max"` or `"gateway/oca-large"`.
+    pub review_model_preset: Option<String>,
+    /// Id of a model preset compaction runs on instead of the session model.
+    pub compact_model_preset: Option<String>,
 
     /// Provider to use from the model_providers map.
     pub model_provider: Option<String>,
@@ -682,6 +1223,17 @@ pub struct ConfigToml {
     /// Token usage threshold triggering auto-compaction of conversation history.
     pub model_auto_compact_token_limit: Option<i64>,
 
+    /// Cap on output tokens per request. Clamped to the model's maximum, and
+    /// superseded by a cap set on the selected reasoning effort.
+    pub model_max_output_tokens: Option<i64>,
+
+    /// Sampling temperature. Overrides the model default, and is dropped for
+    /// models that do not accept one.
+    pub model_temperature: Option<f32>,
+
+    /// Nucleus sampling `top_p`, handled like `model_temperature`.
+    pub model_top_p: Option<f32>,
+
     /// Default approval policy for executing commands.
     pub approval_policy: Option<AskForApproval>,
 
@@ -745,6 +1297,11 @@ pub struct ConfigToml {
     /// Ordered list of fallback filenames to look for when AGENTS.md is missing.
     pub project_doc_fallback_filenames: Option<Vec<String>>,
 
+    /// When true, `@model:<id>` references in AGENTS.md and the other project
+    /// docs are checked against the model catalog at session start, and
+    /// models it does not list are named in a warning. Defaults to false.
+    pub validate_instruction_models: Option<bool>,
+
     /// Token budget applied when storing tool/function outputs in the context manager.
     pub tool_output_token_limit: Option<usize>,
 
@@ -774,7 +1331,8 @@ pub struct ConfigToml {
     /// Defaults to `false`.
     pub show_raw_agent_reasoning: Option<bool>,
 
-    pub model_reasoning_effort: Option<ReasoningEffort>,
+    /// `"auto"` or an effort name; see [`EffortSelection`].
+    pub model_reasoning_effort: Option<EffortSelection>,
     pub model_reasoning_summary: Option<ReasoningSummary>,
     /// Optional verbosity control for GPT-5 models (Responses API `text.verbosity`).
     pub model_verbosity: Option<Verbosity>,
@@ -782,6 +1340,10 @@ pub struct ConfigToml {
     /// Override to force-enable reasoning summaries for the configured model.
     pub model_supports_reasoning_summaries: Option<bool>,
 
+    /// `"headed"` or `"plain"`: how the model writes reasoning summaries.
+    /// Unset, the active model's preset decides.
+    pub model_reasoning_summary_format: Option<ReasoningSummaryFormat>,
+
     /// Base URL for requests to ChatGPT (as opposed to the OpenAI API).
     pub chatgpt_base_url: Option<String>,
 
@@ -808,6 +1370,93 @@ pub struct ConfigToml {
     /// Defaults to `true`.
     pub check_for_update_on_startup: Option<bool>,
 
+    /// When `true`, skip every model catalog request and rely on bundled
+    /// presets plus the on-disk models cache. Defaults to `false`.
+    pub offline: Option<bool>,
+
+    /// When `true`, refuse to start a session whose configured `model` is not
+    /// in the model catalog. Defaults to `false`, which only warns.
+    pub strict_model_validation: Option<bool>,
+
+    /// When `true`, rewrite a retired built-in `model` in this file to the
+    /// model replacing it. Defaults to `false`, which resolves it silently.
+    pub rewrite_retired_models: Option<bool>,
+
+    /// When `true`, the TUI fetches the model catalog in the background at
+    /// startup. Ignored in offline mode. Defaults to `false`.
+    pub prefetch_model_catalog: Option<bool>,
+
+    /// When `true`, write a JSON status file per session under
+    /// `$CODEX_HOME/run` for shell prompt and status bar integrations.
+    /// Defaults to `false`.
+    pub write_status_file: Option<bool>,
+
+    /// Warn once when the session's estimated cost, its token usage priced
+    /// by the presets of the models that used them, passes this many USD.
+    pub session_cost_warning_usd: Option<f64>,
+
+    /// Once the session's estimated cost reaches this many USD, ask before
+    /// each turn whether to run it. `codex exec` declines and exits with
+    /// status 4.
+    pub session_cost_limit_usd: Option<f64>,
+
+    /// Catalogs to read model presets from, highest precedence first:
+    /// `"builtin"`, `"remote"` and `"providers"`. Sources left out are not
+    /// consulted; an empty list means built-in presets only. Defaults to
+    /// `["remote", "builtin", "providers"]`.
+    pub model_catalog_sources: Option<Vec<ModelCatalogSource>>,
+
+    /// Catalog for the selected provider when it does not declare its own
+    /// `model_catalog`. Set to `"none"` to never fetch a model list.
+    pub model_catalog: Option<ModelCatalog>,
+
+    /// Appended to the User-Agent header after the built-in components.
+    /// Non-printable-ASCII characters are replaced with `_` and the value is
+    /// capped at 128 characters.
+    pub user_agent_suffix: Option<String>,
+
+    /// Locale (e.g. `fr-FR`) sent as `Accept-Language` on model catalog
+    /// requests so servers can localize model descriptions. Defaults to the
+    /// system locale; set to `""` to send none.
+    pub catalog_locale: Option<String>,
+
+    /// How far each process may move the catalog cache TTL and background
+    /// refresh interval from their defaults, as a fraction: `0.2` (the
+    /// default) allows ±20%. `0` turns the jitter off.
+    pub catalog_refresh_jitter: Option<f64>,
+
+    /// Hours a models cache past its five-minute TTL is still listed right
+    /// away while a refresh replaces it in the background. Older caches are
+    /// refetched before anything is listed. Defaults to 24.
+    pub model_cache_max_staleness_hours: Option<u64>,
+
+    /// Days the state Codex keeps per model, such as which models were
+    /// announced and which the account was refused, outlives the last
+    /// catalog that listed the model. Defaults to 90.
+    pub model_state_retention_days: Option<u64>,
+
+    /// Base URLs an app-server client may select per request with
+    /// `catalogBaseUrl`, e.g. the gateways of the tenants one IDE serves.
+    /// Requests naming any other URL are rejected. Defaults to none.
+    pub allowed_catalog_base_urls: Option<Vec<String>>,
+
+    /// Descriptions to show for reasoning efforts instead of the presets'
+    /// own, keyed by effort (`low`, `high`, ...). They apply to every model.
+    pub effort_descriptions: Option<HashMap<ReasoningEffort, String>>,
+
+    /// Reasoning efforts no model may run at, e.g. `["high", "xhigh"]` to
+    /// keep costs down. They are removed from every preset.
+    pub disabled_reasoning_efforts: Option<Vec<ReasoningEffort>>,
+
+    /// Limits on the presets of a model, keyed by model slug, e.g.
+    /// `[preset_overrides."gpt-5.1-codex"]` with `min_effort = "medium"`.
+    pub preset_overrides: Option<HashMap<String, PresetOverride>>,
+
+    /// Wording to show for a preset instead of its catalog's, keyed by preset
+    /// id, e.g. `[model_preset_overrides."gpt-5.1-codex"]` with
+    /// `display_name`, `description` and `effort_descriptions`.
+    pub model_preset_overrides: Option<HashMap<String, ModelPresetOverride>>,
+
     /// When true, disables burst-paste detection for typed input entirely.
     /// All characters are inserted as they are received, and no buffering
     /// or placeholder replacement will occur for fast keypress bursts.
@@ -847,7 +1496,9 @@ impl From<ConfigToml> for UserSavedConfig {
             forced_chatgpt_workspace_id: config_toml.forced_chatgpt_workspace_id,
             forced_login_method: config_toml.forced_login_method,
             model: config_toml.model,
-            model_reasoning_effort: config_toml.model_reasoning_effort,
+            model_reasoning_effort: config_toml
+                .model_reasoning_effort
+                .and_then(EffortSelection::explicit),
             model_reasoning_summary: config_toml.model_reasoning_summary,
             model_verbosity: config_toml.model_verbosity,
             tools: config_toml.tools.map(From::from),
@@ -1191,25 +1842,55 @@ impl Config {
             || config_profile.sandbox_mode.is_some()
             || cfg.sandbox_mode.is_some();
 
+        let mut configured_providers = cfg.model_providers;
+        let ignored_project_settings = project_trust::gate_project_provider_fields(
+            &config_layer_stack,
+            &resolved_cwd,
+            &mut configured_providers,
+        )?;
         let mut model_providers = built_in_model_providers();
         // Merge user-defined providers into the built-in list.
-        for (key, provider) in cfg.model_providers.into_iter() {
+        for (key, provider) in configured_providers.into_iter() {
             model_providers.entry(key).or_insert(provider);
         }
+        normalize_base_urls(&mut model_providers)?;
+        normalize_model_request_headers(&mut model_providers)?;
 
         let model_provider_id = model_provider
             .or(config_profile.model_provider)
             .or(cfg.model_provider)
             .unwrap_or_else(|| "openai".to_string());
-        let model_provider = model_providers
-            .get(&model_provider_id)
-            .ok_or_else(|| {
-                std::io::Error::new(
-                    std::io::ErrorKind::NotFound,
-                    format!("Model provider `{model_provider_id}` not found"),
-                )
-            })?
-            .clone();
+        let model_provider = model_providers.get_mut(&model_provider_id).ok_or_else(|| {
+            std::io::Error::new(
+                std::io::ErrorKind::NotFound,
+                format!("Model provider `{model_provider_id}` not found"),
+            )
+        })?;
+        if model_provider.model_catalog.is_none() {
+            model_provider.model_catalog = cfg.model_catalog;
+        }
+        let model_provider = model_provider.clone();
+        validate_fixed_model_lists(&model_providers)?;
+        validate_catalog_endpoints(&model_providers)?;
+        validate_spki_pins(&model_providers)?;
+        for (key, id) in [
+            ("review_model_preset", cfg.review_model_preset.as_deref()),
+            ("compact_model_preset", cfg.compact_model_preset.as_deref()),
+        ] {
+            if let Some(id) = id {
+                validate_flow_preset(key, id, &model_providers)?;
+            }
+        }
+        if let Some(overrides) = &cfg.model_preset_overrides {
+            validate_preset_providers(overrides, &model_providers)?;
+        }
+        let model_catalog_sources = catalog_sources(cfg.model_catalog_sources.as_deref())?;
+        let allowed_catalog_base_urls = catalog_base_url_allowlist(
+            cfg.allowed_catalog_base_urls.as_deref().unwrap_or_default(),
+        )?;
+        let preset_overrides = preset_overrides(cfg.preset_overrides.clone().unwrap_or_default())?;
+        validate_session_cost_thresholds(cfg.session_cost_warning_usd, cfg.session_cost_limit_usd)?;
+        let catalog_refresh_jitter = catalog_refresh_jitter(cfg.catalog_refresh_jitter)?;
 
         let shell_environment_policy = cfg.shell_environment_policy.into();
 
@@ -1312,11 +1993,42 @@ impl Config {
             .set(sandbox_policy)
             .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidInput, format!("{e}")))?;
 
+        let model_reasoning_effort = config_profile
+            .model_reasoning_effort
+            .or(cfg.model_reasoning_effort)
+            .and_then(EffortSelection::explicit);
+        let disabled_reasoning_efforts = cfg.disabled_reasoning_efforts.unwrap_or_default();
+        if let Some(effort) = model_reasoning_effort
+            && disabled_reasoning_efforts.contains(&effort)
+        {
+            return Err(std::io::Error::new(
+                ErrorKind::InvalidInput,
+                format!("reasoning effort `{effort}` is disabled by `disabled_reasoning_efforts`"),
+            ));
+        }
+
+        let hash_model_ids = cfg
+            .otel
+            .as_ref()
+            .and_then(|otel| otel.hash_model_ids)
+            .unwrap_or(false);
+        let model_id_key = hash_model_ids.then(|| {
+            ModelIdKey::load_or_create(&codex_home).unwrap_or_else(|err| {
+                tracing::warn!("failed to store the key for hashing model ids: {err}");
+                ModelIdKey::random()
+            })
+        });
+
         let config = Self {
             model,
             review_model,
+            review_model_preset: cfg.review_model_preset,
+            compact_model_preset: cfg.compact_model_preset,
             model_context_window: cfg.model_context_window,
             model_auto_compact_token_limit: cfg.model_auto_compact_token_limit,
+            model_max_output_tokens: cfg.model_max_output_tokens,
+            model_temperature: cfg.model_temperature,
+            model_top_p: cfg.model_top_p,
             model_provider_id,
             model_provider,
             cwd: resolved_cwd,
@@ -1338,7 +2050,9 @@ impl Config {
             // is important in code to differentiate the mode from the store implementation.
             mcp_oauth_credentials_store_mode: cfg.mcp_oauth_credentials_store.unwrap_or_default(),
             model_providers,
+            ignored_project_settings,
             project_doc_max_bytes: cfg.project_doc_max_bytes.unwrap_or(PROJECT_DOC_MAX_BYTES),
+            validate_instruction_models: cfg.validate_instruction_models.unwrap_or(false),
             project_doc_fallback_filenames: cfg
                 .project_doc_fallback_filenames
                 .unwrap_or_default()
@@ -1364,14 +2078,13 @@ impl Config {
                 .show_raw_agent_reasoning
                 .or(show_raw_agent_reasoning)
                 .unwrap_or(false),
-            model_reasoning_effort: config_profile
-                .model_reasoning_effort
-                .or(cfg.model_reasoning_effort),
+            model_reasoning_effort,
             model_reasoning_summary: config_profile
                 .model_reasoning_summary
                 .or(cfg.model_reasoning_summary)
                 .unwrap_or_default(),
             model_supports_reasoning_summaries: cfg.model_supports_reasoning_summaries,
+            model_reasoning_summary_format: cfg.model_reasoning_summary_format,
             model_verbosity: config_profile.model_verbosity.or(cfg.model_verbosity),
             chatgpt_base_url: config_profile
                 .chatgpt_base_url
@@ -1389,6 +2102,37 @@ impl Config {
             windows_wsl_setup_acknowledged: cfg.windows_wsl_setup_acknowledged.unwrap_or(false),
             notices: cfg.notice.unwrap_or_default(),
             check_for_update_on_startup,
+            offline: cfg.offline.unwrap_or(false),
+            strict_model_validation: cfg.strict_model_validation.unwrap_or(false),
+            rewrite_retired_models: cfg.rewrite_retired_models.unwrap_or(false),
+            prefetch_model_catalog: cfg.prefetch_model_catalog.unwrap_or(false),
+            write_status_file: cfg.write_status_file.unwrap_or(false),
+            session_cost_warning_usd: cfg.session_cost_warning_usd,
+            session_cost_limit_usd: cfg.session_cost_limit_usd,
+            model_catalog_sources,
+            user_agent_suffix: cfg
+                .user_agent_suffix
+                .as_deref()
+                .and_then(sanitize_user_agent_suffix),
+            catalog_locale: cfg.catalog_locale,
+            catalog_refresh_jitter,
+            model_cache_max_staleness: cfg
+                .model_cache_max_staleness_hours
+                .map_or(DEFAULT_MODEL_CACHE_MAX_STALENESS, |hours| {
+                    Duration::from_secs(hours.saturating_mul(60 * 60))
+                }),
+            model_state_retention: cfg
+                .model_state_retention_days
+                .map_or(DEFAULT_MODEL_STATE_RETENTION, |days| {
+                    Duration::from_secs(days.saturating_mul(24 * 60 * 60))
+                }),
+            allowed_catalog_base_urls,
+            catalog_base_url: None,
+            catalog_credentials_withheld: false,
+            effort_descriptions: cfg.effort_descriptions.unwrap_or_default(),
+            disabled_reasoning_efforts,
+            preset_overrides,
+            model_preset_overrides: cfg.model_preset_overrides.unwrap_or_default(),
             disable_paste_burst: cfg.disable_paste_burst.unwrap_or(false),
             tui_notifications: cfg
                 .tui
@@ -1431,6 +2175,7 @@ impl Config {
                     environment,
                     exporter,
                     trace_exporter,
+                    model_id_key,
                 }
             },
         };
@@ -1542,6 +2287,7 @@ mod tests {
     use crate::features::Feature;
 
     use super::*;
+    use codex_protocol::openai_models::EffortByAuth;
     use core_test_support::test_absolute_path;
     use pretty_assertions::assert_eq;
 
@@ -1822,62 +2568,83 @@ trust_level = "trusted"
     }
 
     #[test]
-    fn config_honors_explicit_keyring_auth_store_mode() -> std::io::Result<()> {
+    fn fixed_model_catalog_requires_models() -> std::io::Result<()> {
         let codex_home = TempDir::new()?;
-        let cfg = ConfigToml {
-            cli_auth_credentials_store: Some(AuthCredentialsStoreMode::Keyring),
-            ..Default::default()
-        };
+        let cfg = toml::from_str::<ConfigToml>(
+            r#"
+model_provider = "gateway"
 
-        let config = Config::load_from_base_config_with_overrides(
+[model_providers.gateway]
+name = "Gateway"
+model_catalog = "none"
+"#,
+        )
+        .expect("TOML deserialization should succeed");
+
+        let err = Config::load_from_base_config_with_overrides(
             cfg,
             ConfigOverrides::default(),
             codex_home.path().to_path_buf(),
-        )?;
+        )
+        .expect_err("a fixed catalog without models should be rejected");
 
+        assert_eq!(err.kind(), ErrorKind::InvalidInput);
         assert_eq!(
-            config.cli_auth_credentials_store_mode,
-            AuthCredentialsStoreMode::Keyring,
+            err.to_string(),
+            "model provider `gateway` has `model_catalog = \"none\"` but no `models`; list the models it serves"
         );
 
         Ok(())
     }
 
     #[test]
-    fn config_defaults_to_auto_oauth_store_mode() -> std::io::Result<()> {
+    fn credential_request_headers_are_rejected() -> std::io::Result<()> {
         let codex_home = TempDir::new()?;
-        let cfg = ConfigToml::default();
+        let cfg = toml::from_str::<ConfigToml>(
+            r#"
+model_provider = "gateway"
 
-        let config = Config::load_from_base_config_with_overrides(
+[model_providers.gateway]
+name = "Gateway"
+
+[model_providers.gateway.model_request_headers.oca-large]
+X-Model-Group = "large"
+Authorization = "Bearer ${GATEWAY_TOKEN}"
+"#,
+        )
+        .expect("TOML deserialization should succeed");
+
+        let err = Config::load_from_base_config_with_overrides(
             cfg,
             ConfigOverrides::default(),
             codex_home.path().to_path_buf(),
-        )?;
+        )
+        .expect_err("a per-model Authorization header should be rejected");
 
+        assert_eq!(err.kind(), ErrorKind::InvalidInput);
         assert_eq!(
-            config.mcp_oauth_credentials_store_mode,
-            OAuthCredentialsStoreMode::Auto,
+            err.to_string(),
+            "model provider `gateway` sets an invalid request header for model `oca-large`: `Authorization` carries credentials and cannot be set per model; configure it on the provider"
         );
 
         Ok(())
     }
 
     #[test]
-    fn profile_legacy_toggles_override_base() -> std::io::Result<()> {
+    fn request_header_names_are_lowercased_at_load() -> std::io::Result<()> {
         let codex_home = TempDir::new()?;
-        let mut profiles = HashMap::new();
-        profiles.insert(
-            "work".to_string(),
-            ConfigProfile {
-                tools_view_image: Some(false),
-                ..Default::default()
-            },
-        );
-        let cfg = ConfigToml {
-            profiles,
-            profile: Some("work".to_string()),
-            ..Default::default()
-        };
+        let cfg = toml::from_str::<ConfigToml>(
+            r#"
+model_provider = "gateway"
+
+[model_providers.gateway]
+name = "Gateway"
+
+[model_providers.gateway.model_request_headers.oca-large]
+X-Model-Group = "large"
+"#,
+        )
+        .expect("TOML deserialization should succeed");
 
         let config = Config::load_from_base_config_with_overrides(
             cfg,
@@ -1885,28 +2652,826 @@ trust_level = "trusted"
             codex_home.path().to_path_buf(),
         )?;
 
-        assert!(!config.features.enabled(Feature::ViewImageTool));
+        assert_eq!(
+            config.model_provider.request_headers_for("oca-large"),
+            Some(&HashMap::from([(
+                "x-model-group".to_string(),
+                "large".to_string()
+            )]))
+        );
 
         Ok(())
     }
 
     #[test]
-    fn profile_sandbox_mode_overrides_base() -> std::io::Result<()> {
+    fn global_model_catalog_applies_to_selected_provider() -> std::io::Result<()> {
         let codex_home = TempDir::new()?;
-        let mut profiles = HashMap::new();
-        profiles.insert(
-            "work".to_string(),
-            ConfigProfile {
-                sandbox_mode: Some(SandboxMode::DangerFullAccess),
-                ..Default::default()
-            },
-        );
-        let cfg = ConfigToml {
-            profiles,
-            profile: Some("work".to_string()),
-            sandbox_mode: Some(SandboxMode::ReadOnly),
-            ..Default::default()
-        };
+        let cfg = toml::from_str::<ConfigToml>(
+            r#"
+model_provider = "gateway"
+model_catalog = "none"
+
+[model_providers.gateway]
+name = "Gateway"
+models = ["oca-large"]
+
+[model_providers.listed]
+name = "Listed"
+model_catalog = "openai"
+"#,
+        )
+        .expect("TOML deserialization should succeed");
+
+        let config = Config::load_from_base_config_with_overrides(
+            cfg,
+            ConfigOverrides::default(),
+            codex_home.path().to_path_buf(),
+        )?;
+
+        assert_eq!(
+            config.model_provider.model_catalog,
+            Some(ModelCatalog::None)
+        );
+        assert_eq!(
+            config.model_providers["gateway"].model_catalog,
+            Some(ModelCatalog::None)
+        );
+        assert_eq!(
+            config.model_providers["listed"].model_catalog,
+            Some(ModelCatalog::Openai)
+        );
+
+        Ok(())
+    }
+
+    fn load_provider_config(toml: &str) -> std::io::Result<Config> {
+        let codex_home = TempDir::new()?;
+        let cfg = toml::from_str::<ConfigToml>(toml).expect("TOML deserialization should succeed");
+        Config::load_from_base_config_with_overrides(
+            cfg,
+            ConfigOverrides::default(),
+            codex_home.path().to_path_buf(),
+        )
+    }
+
+    #[test]
+    fn catalog_endpoint_must_be_a_valid_https_url() {
+        let cases = [
+            (
+                r#"
+[model_providers.gateway]
+name = "Gateway"
+base_url = "not a url"
+model_catalog = "openai"
+"#,
+                "model provider `gateway` `base_url` `not a url` is not a valid URL: relative URL without a base",
+            ),
+            (
+                r#"
+[model_providers.gateway]
+name = "Gateway"
+base_url = "http://gateway.example.com/v1"
+model_catalog = "openai"
+"#,
+                "model provider `gateway` lists models over plain http from `http://gateway.example.com/v1`; use https or set `allow_insecure_catalog = true`",
+            ),
+            (
+                r#"
+[model_providers.gateway]
+name = "Gateway"
+base_url = "https://gateway.example.com/v1"
+model_catalog = "openai"
+models_path = "https://elsewhere.example.com/models"
+"#,
+                "model provider `gateway` `models_path` `https://elsewhere.example.com/models` points at a different host than `base_url`",
+            ),
+            (
+                r#"
+[model_providers.gateway]
+name = "Gateway"
+base_url = "https://gateway.example.com/v1"
+model_catalog = "openai"
+models_path = "https://gateway.example.com:8443/ai/litellm/v1/model/info"
+"#,
+                "model provider `gateway` `models_path` `https://gateway.example.com:8443/ai/litellm/v1/model/info` points at a different host than `base_url`",
+            ),
+        ];
+        for (toml, expected) in cases {
+            let err = load_provider_config(toml).expect_err("config should be rejected");
+            assert_eq!(err.kind(), ErrorKind::InvalidInput);
+            assert_eq!(err.to_string(), expected);
+        }
+    }
+
+    #[test]
+    fn base_urls_are_normalized_at_load() {
+        let config = load_provider_config(
+            r#"
+model_provider = "gateway"
+
+[model_providers.gateway]
+name = "Gateway"
+base_url = "gw.example.com//litellm/"
+model_catalog = "openai"
+"#,
+        )
+        .expect("a scheme-less base URL is accepted");
+        assert_eq!(
+            config.model_provider.base_url.as_deref(),
+            Some("https://gw.example.com/litellm")
+        );
+
+        let err = load_provider_config(
+            r#"
+[model_providers.gateway]
+name = "Gateway"
+base_url = "https://gw.example.com/v1?api-version=1"
+"#,
+        )
+        .expect_err("a query string should be rejected");
+        assert_eq!(err.kind(), ErrorKind::InvalidInput);
+        assert_eq!(
+            err.to_string(),
+            "model provider `gateway` `base_url` `https://gw.example.com/v1?api-version=1` must not include a query string or fragment; use `https://gw.example.com/v1` and set query parameters in `query_params`"
+        );
+    }
+
+    #[test]
+    fn model_catalog_sources_are_validated_at_load() {
+        let sources =
+            |toml: &str| load_provider_config(toml).map(|config| config.model_catalog_sources);
+
+        assert_eq!(
+            sources("").expect("default order"),
+            ModelCatalogSource::DEFAULT_ORDER.to_vec()
+        );
+        assert_eq!(
+            sources(r#"model_catalog_sources = ["builtin", "remote"]"#).expect("custom order"),
+            vec![ModelCatalogSource::Builtin, ModelCatalogSource::Remote]
+        );
+        assert_eq!(
+            sources("model_catalog_sources = []").expect("empty list"),
+            vec![ModelCatalogSource::Builtin]
+        );
+
+        let err = sources(r#"model_catalog_sources = ["remote", "builtin", "remote"]"#)
+            .expect_err("a repeated source should be rejected");
+        assert_eq!(err.kind(), ErrorKind::InvalidInput);
+        assert_eq!(
+            err.to_string(),
+            "`model_catalog_sources` lists `remote` more than once"
+        );
+
+        let err = toml::from_str::<ConfigToml>(r#"model_catalog_sources = ["builtin", "oca"]"#)
+            .expect_err("an unknown source should be rejected");
+        assert!(
+            err.to_string().contains(
+                "unknown variant `oca`, expected one of `builtin`, `remote`, `providers`"
+            ),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn flow_presets_of_configured_providers_are_validated_at_load() {
+        let providers = r#"
+[model_providers.gateway]
+name = "Gateway"
+base_url = "https://gateway.example.com/v1"
+wire_api = "chat"
+model_catalog = "none"
+models = ["oca-large", "oca-small"]
+
+[model_providers.plain]
+name = "Plain"
+base_url = "https://plain.example.com/v1"
+wire_api = "chat"
+"#;
+        let config = load_provider_config(&format!(
+            "review_model_preset = \"gpt-5.1-codex-max\"\ncompact_model_preset = \"gateway/oca-small\"\n{providers}"
+        ))
+        .expect("known presets should load");
+        assert_eq!(
+            (
+                config.review_model_preset.as_deref(),
+                config.compact_model_preset.as_deref()
+            ),
+            (Some("gpt-5.1-codex-max"), Some("gateway/oca-small"))
+        );
+
+        // Ids the remote catalog may serve are checked when the flow starts.
+        let config = load_provider_config(&format!(
+            "review_model_preset = \"gpt-9-turbo\"\ncompact_model_preset = \"acme/gpt-9-mini\"\n{providers}"
+        ))
+        .expect("ids outside the configured providers should load");
+        assert_eq!(
+            (
+                config.review_model_preset.as_deref(),
+                config.compact_model_preset.as_deref()
+            ),
+            (Some("gpt-9-turbo"), Some("acme/gpt-9-mini"))
+        );
+
+        let cases = [
+            (
+                "review_model_preset = \"gateway/oca-huge\"",
+                "`review_model_preset = \"gateway/oca-huge\"` names model `oca-huge`, which provider `gateway` does not list in `models`",
+            ),
+            (
+                "compact_model_preset = \"plain/oca-large\"",
+                "`compact_model_preset = \"plain/oca-large\"` names provider `plain`, which has no `model_catalog` to serve presets from",
+            ),
+        ];
+        for (setting, expected) in cases {
+            let err = load_provider_config(&format!("{setting}\n{providers}"))
+                .expect_err("a preset its provider cannot serve should be rejected");
+            assert_eq!(err.kind(), ErrorKind::InvalidInput);
+            assert_eq!(err.to_string(), expected);
+        }
+    }
+
+    #[test]
+    fn preset_providers_must_be_configured() {
+        let gateway = r#"
+[model_providers.gateway]
+name = "Gateway"
+base_url = "https://gateway.example.com/v1"
+wire_api = "responses"
+"#;
+        let config = load_provider_config(&format!(
+            "[model_preset_overrides.\"gpt-5.1-codex-max\"]\nmodel_provider = \"gateway\"\n{gateway}"
+        ))
+        .expect("a configured provider should load");
+        assert_eq!(
+            config.model_preset_overrides["gpt-5.1-codex-max"]
+                .model_provider
+                .as_deref(),
+            Some("gateway")
+        );
+
+        let err = load_provider_config(&format!(
+            "[model_preset_overrides.\"gpt-5.1-codex-max\"]\nmodel_provider = \"elsewhere\"\n{gateway}"
+        ))
+        .expect_err("an unknown provider should be rejected");
+        assert_eq!(err.kind(), ErrorKind::InvalidInput);
+        assert_eq!(
+            err.to_string(),
+            "`model_preset_overrides.\"gpt-5.1-codex-max\"` names unknown model provider `elsewhere`"
+        );
+    }
+
+    #[test]
+    fn catalog_refresh_jitter_must_stay_below_one() {
+        let config = load_provider_config("").expect("default config should load");
+        assert_eq!(
+            config.catalog_refresh_jitter,
+            DEFAULT_CATALOG_REFRESH_JITTER
+        );
+        let config =
+            load_provider_config("catalog_refresh_jitter = 0").expect("no jitter should load");
+        assert_eq!(config.catalog_refresh_jitter, 0.0);
+
+        let err = load_provider_config("catalog_refresh_jitter = 1.5")
+            .expect_err("a jitter past the TTL is rejected");
+        assert_eq!(err.kind(), ErrorKind::InvalidInput);
+        assert_eq!(
+            err.to_string(),
+            "`catalog_refresh_jitter` must be at least 0 and below 1, got 1.5"
+        );
+    }
+
+    #[test]
+    fn model_cache_max_staleness_is_set_in_hours() {
+        let config = load_provider_config("").expect("default config should load");
+        assert_eq!(
+            config.model_cache_max_staleness,
+            Duration::from_secs(24 * 60 * 60)
+        );
+        let config = load_provider_config("model_cache_max_staleness_hours = 0")
+            .expect("turning stale listings off should load");
+        assert_eq!(config.model_cache_max_staleness, Duration::ZERO);
+    }
+
+    #[test]
+    fn model_state_retention_is_set_in_days() {
+        let config = load_provider_config("").expect("default config should load");
+        assert_eq!(
+            config.model_state_retention,
+            Duration::from_secs(90 * 24 * 60 * 60)
+        );
+        let config = load_provider_config("model_state_retention_days = 7")
+            .expect("a shorter retention should load");
+        assert_eq!(
+            config.model_state_retention,
+            Duration::from_secs(7 * 24 * 60 * 60)
+        );
+    }
+
+    #[test]
+    fn session_cost_thresholds_must_be_ordered_positive_amounts() {
+        let config =
+            load_provider_config("session_cost_warning_usd = 5.0\nsession_cost_limit_usd = 20")
+                .expect("thresholds should load");
+        assert_eq!(
+            (
+                config.session_cost_warning_usd,
+                config.session_cost_limit_usd
+            ),
+            (Some(5.0), Some(20.0))
+        );
+
+        let err = load_provider_config("session_cost_limit_usd = 0")
+            .expect_err("a zero limit is rejected");
+        assert_eq!(err.kind(), ErrorKind::InvalidInput);
+        assert_eq!(
+            err.to_string(),
+            "`session_cost_limit_usd` must be a positive amount, got 0"
+        );
+
+        let err =
+            load_provider_config("session_cost_warning_usd = 30.0\nsession_cost_limit_usd = 20.0")
+                .expect_err("a warning past the limit is rejected");
+        assert_eq!(
+            err.to_string(),
+            "`session_cost_warning_usd` (30) is above `session_cost_limit_usd` (20)"
+        );
+    }
+
+    #[test]
+    fn catalog_base_url_overrides_are_checked_against_the_allowlist() {
+        let toml = r#"
+model_provider = "gateway"
+allowed_catalog_base_urls = ["https://tenant-a.example.com/v1/", "tenant-b.example.com//v1", "https://gateway.example.com/tenant-c/v1"]
+
+[model_providers.gateway]
+name = "Gateway"
+base_url = "https://gateway.example.com/v1"
+model_catalog = "openai"
+env_key = "GATEWAY_API_KEY"
+experimental_bearer_token = "gateway-token"
+"#;
+        let config = load_provider_config(toml).expect("allowlist should load");
+        assert_eq!(
+            config.allowed_catalog_base_urls,
+            vec![
+                "https://tenant-a.example.com/v1".to_string(),
+                "https://tenant-b.example.com/v1".to_string(),
+                "https://gateway.example.com/tenant-c/v1".to_string(),
+            ]
+        );
+
+        let mut tenant = config.clone();
+        tenant
+            .override_catalog_base_url("https://tenant-b.example.com/v1/")
+            .expect("listed base URL");
+        let expected = Some("https://tenant-b.example.com/v1".to_string());
+        assert_eq!(tenant.catalog_base_url, expected);
+        assert_eq!(tenant.model_provider.base_url, expected);
+        let catalog = tenant
+            .catalog_providers()
+            .into_iter()
+            .find(|catalog| catalog.provider_id == "gateway")
+            .expect("gateway catalog");
+        assert_eq!(
+            catalog.provider.base_url, expected,
+            "the catalog is fetched from the tenant too"
+        );
+        assert!(tenant.catalog_credentials_withheld);
+        for provider in [&tenant.model_provider, &catalog.provider] {
+            assert_eq!(provider.env_key, None, "no credentials go to another host");
+            assert_eq!(provider.experimental_bearer_token, None);
+            assert!(provider.allow_anonymous_catalog);
+        }
+
+        let mut same_host = config.clone();
+        same_host
+            .override_catalog_base_url("https://gateway.example.com/tenant-c/v1")
+            .expect("listed base URL");
+        assert!(!same_host.catalog_credentials_withheld);
+        assert_eq!(
+            same_host.model_provider.env_key.as_deref(),
+            Some("GATEWAY_API_KEY"),
+            "the provider's own host keeps its credentials"
+        );
+        assert_eq!(
+            same_host
+                .model_provider
+                .experimental_bearer_token
+                .as_deref(),
+            Some("gateway-token")
+        );
+
+        let err = config
+            .clone()
+            .override_catalog_base_url("https://evil.example.com/v1")
+            .expect_err("unlisted base URL");
+        assert_eq!(err.kind(), ErrorKind::InvalidInput);
+        assert_eq!(
+            err.to_string(),
+            "catalog base URL `https://evil.example.com/v1` is not listed in `allowed_catalog_base_urls`"
+        );
+
+        let mut fixed = load_provider_config(&toml.replace(
+            r#"model_catalog = "openai""#,
+            "model_catalog = \"none\"\nmodels = [\"oca-large\"]",
+        ))
+        .expect("fixed list should load");
+        let err = fixed
+            .override_catalog_base_url("https://tenant-a.example.com/v1")
+            .expect_err("nothing to fetch");
+        assert_eq!(
+            err.to_string(),
+            "model provider `gateway` has no `model_catalog` to fetch from `https://tenant-a.example.com/v1`"
+        );
+
+        let err = load_provider_config(
+            r#"allowed_catalog_base_urls = ["https://a.example.com/?tenant=1"]"#,
+        )
+        .expect_err("query strings are rejected");
+        assert_eq!(err.kind(), ErrorKind::InvalidInput);
+        assert!(
+            err.to_string()
+                .starts_with("`allowed_catalog_base_urls` entry `base_url`"),
+            "unexpected error: {err}"
+        );
+    }
+
+    #[test]
+    fn preset_effort_limits_are_validated_at_load() {
+        let config = load_provider_config(
+            r#"
+[preset_overrides."gpt-5.1-codex"]
+min_effort = "medium"
+max_effort = "high"
+"#,
+        )
+        .expect("a valid range");
+        assert_eq!(
+            config.preset_overrides.get("gpt-5.1-codex"),
+            Some(&PresetOverride {
+                min_effort: Some(ReasoningEffort::Medium),
+                max_effort: Some(ReasoningEffort::High),
+                default_effort_by_auth: None,
+            })
+        );
+
+        let err = load_provider_config(
+            r#"
+[preset_overrides."gpt-5.1-codex"]
+min_effort = "high"
+max_effort = "low"
+"#,
+        )
+        .expect_err("an empty range should be rejected");
+        assert_eq!(err.kind(), ErrorKind::InvalidInput);
+        assert_eq!(
+            err.to_string(),
+            r#"`preset_overrides."gpt-5.1-codex"` sets `min_effort = "high"` above `max_effort = "low"`"#
+        );
+    }
+
+    #[test]
+    fn disabled_reasoning_efforts_reject_a_configured_effort() {
+        let config = load_provider_config(
+            r#"
+disabled_reasoning_efforts = ["high", "xhigh"]
+model_reasoning_effort = "medium"
+"#,
+        )
+        .expect("medium is not disabled");
+        assert_eq!(
+            config.disabled_reasoning_efforts,
+            vec![ReasoningEffort::High, ReasoningEffort::XHigh]
+        );
+
+        let err = load_provider_config(
+            r#"
+disabled_reasoning_efforts = ["high"]
+model_reasoning_effort = "high"
+"#,
+        )
+        .expect_err("a disabled effort should be rejected");
+        assert_eq!(err.kind(), ErrorKind::InvalidInput);
+        assert_eq!(
+            err.to_string(),
+            "reasoning effort `high` is disabled by `disabled_reasoning_efforts`"
+        );
+    }
+
+    #[test]
+    fn preset_overrides_set_default_efforts_per_auth_mode() {
+        let config = load_provider_config(
+            r#"
+[preset_overrides."gpt-5-codex"]
+default_effort_by_auth = { apikey = "minimal", chatgpt = "high" }
+"#,
+        )
+        .expect("a valid mapping");
+        assert_eq!(
+            config.preset_overrides.get("gpt-5-codex"),
+            Some(&PresetOverride {
+                min_effort: None,
+                max_effort: None,
+                default_effort_by_auth: Some(EffortByAuth {
+                    apikey: Some(ReasoningEffort::Minimal),
+                    chatgpt: Some(ReasoningEffort::High),
+                }),
+            })
+        );
+    }
+
+    #[test]
+    fn spki_pins_must_be_base64_sha256_hashes() {
+        let pinned = |pins: &str| {
+            load_provider_config(&format!(
+                r#"
+[model_providers.oca]
+name = "OCA"
+base_url = "https://oca.example.com/v1"
+pinned_spki_sha256 = {pins}
+"#
+            ))
+        };
+
+        let pin = "r/mIkG3eEpVdm+u/ko/cwxzOMo1bk4TyHIlByibiA5E=";
+        let config = pinned(&format!(r#"["{pin}"]"#)).expect("valid pins should load");
+        assert_eq!(
+            config.model_providers["oca"].pinned_spki_sha256,
+            Some(vec![pin.to_string()])
+        );
+
+        let err = pinned(r#"["c2hvcnQ="]"#).expect_err("a short hash should be rejected");
+        assert_eq!(err.kind(), ErrorKind::InvalidInput);
+        assert_eq!(
+            err.to_string(),
+            "model provider `oca` `pinned_spki_sha256` entry `c2hvcnQ=` decodes to 5 bytes; a SHA-256 hash is 32 bytes"
+        );
+
+        let err = pinned(r#"["not base64!"]"#).expect_err("bad base64 should be rejected");
+        assert_eq!(err.kind(), ErrorKind::InvalidInput);
+        assert!(
+            err.to_string().starts_with(
+                "model provider `oca` `pinned_spki_sha256` entry `not base64!` is not valid base64"
+            ),
+            "{err}"
+        );
+
+        let err = pinned("[]").expect_err("an empty pin list should be rejected");
+        assert_eq!(
+            err.to_string(),
+            "model provider `oca` `pinned_spki_sha256` must list at least one key hash"
+        );
+    }
+
+    #[test]
+    fn insecure_catalogs_are_allowed_on_loopback_or_by_opt_out() -> std::io::Result<()> {
+        let config = load_provider_config(
+            r#"
+[model_providers.lmstudio-local]
+name = "LM Studio"
+base_url = "http://localhost:1234/v1"
+model_catalog = "openai"
+
+[model_providers.lan]
+name = "LAN"
+base_url = "http://10.0.0.5:8000/v1"
+model_catalog = "openai"
+allow_insecure_catalog = true
+
+[model_providers.chat-only]
+name = "Chat only"
+base_url = "http://10.0.0.6:8000/v1"
+"#,
+        )?;
+
+        let ids: Vec<String> = config
+            .catalog_providers()
+            .into_iter()
+            .map(|catalog| catalog.provider_id)
+            .collect();
+        assert_eq!(ids, vec!["lan".to_string(), "lmstudio-local".to_string()]);
+        Ok(())
+    }
+
+    #[test]
+    fn catalog_providers_carry_the_path_override() -> std::io::Result<()> {
+        let config = load_provider_config(
+            r#"
+[model_providers.gateway]
+name = "Gateway"
+base_url = "https://gateway.example.com/v1"
+model_catalog = "openai"
+models_path = "catalog/models"
+"#,
+        )?;
+
+        let gateway = config.model_providers["gateway"].clone();
+        assert_eq!(
+            config.catalog_providers(),
+            vec![ProviderCatalogConfig {
+                provider_id: "gateway".to_string(),
+                catalog: ModelCatalog::Openai,
+                path: Some("catalog/models".to_string()),
+                provider: gateway,
+                user_agent_suffix: None,
+            }]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn models_path_accepts_prefixes_and_same_host_urls() -> std::io::Result<()> {
+        let config = load_provider_config(
+            r#"
+[model_providers.prefixed]
+name = "Prefixed"
+base_url = "https://gateway.example.com/v1"
+model_catalog = "openai"
+models_path = "/ai/litellm/v1/model/info"
+
+[model_providers.absolute]
+name = "Absolute"
+base_url = "https://gateway.example.com/v1"
+model_catalog = "openai"
+models_path = "https://gateway.example.com/ai/litellm/v1/model/info"
+"#,
+        )?;
+
+        let paths: Vec<(String, Option<String>)> = config
+            .catalog_providers()
+            .into_iter()
+            .map(|catalog| (catalog.provider_id, catalog.path))
+            .collect();
+        assert_eq!(
+            paths,
+            vec![
+                (
+                    "absolute".to_string(),
+                    Some("https://gateway.example.com/ai/litellm/v1/model/info".to_string()),
+                ),
+                (
+                    "prefixed".to_string(),
+                    Some("/ai/litellm/v1/model/info".to_string()),
+                ),
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn config_honors_explicit_keyring_auth_store_mode() -> std::io::Result<()> {
+        let codex_home = TempDir::new()?;
+        let cfg = ConfigToml {
+            cli_auth_credentials_store: Some(AuthCredentialsStoreMode::Keyring),
+            ..Default::default()
+        };
+
+        let config = Config::load_from_base_config_with_overrides(
+            cfg,
+            ConfigOverrides::default(),
+            codex_home.path().to_path_buf(),
+        )?;
+
+        assert_eq!(
+            config.cli_auth_credentials_store_mode,
+            AuthCredentialsStoreMode::Keyring,
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn config_sanitizes_user_agent_suffix() -> std::io::Result<()> {
+        let codex_home = TempDir::new()?;
+        let cfg = ConfigToml {
+            user_agent_suffix: Some(" acme-gateway/1.2\r\n(eu) ".to_string()),
+            ..Default::default()
+        };
+
+        let config = Config::load_from_base_config_with_overrides(
+            cfg,
+            ConfigOverrides::default(),
+            codex_home.path().to_path_buf(),
+        )?;
+
+        assert_eq!(
+            config.user_agent_suffix.as_deref(),
+            Some("acme-gateway/1.2__(eu)")
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn auto_effort_in_a_profile_overrides_an_explicit_top_level_effort() -> anyhow::Result<()> {
+        let codex_home = TempDir::new()?;
+        let cfg: ConfigToml = toml::from_str(
+            r#"
+model_reasoning_effort = "high"
+
+[profiles.auto]
+model_reasoning_effort = "auto"
+"#,
+        )?;
+        assert_eq!(
+            (
+                cfg.model_reasoning_effort,
+                cfg.profiles["auto"].model_reasoning_effort
+            ),
+            (
+                Some(EffortSelection::Explicit(ReasoningEffort::High)),
+                Some(EffortSelection::Auto)
+            )
+        );
+
+        let top_level = Config::load_from_base_config_with_overrides(
+            cfg.clone(),
+            ConfigOverrides::default(),
+            codex_home.path().to_path_buf(),
+        )?;
+        let auto = Config::load_from_base_config_with_overrides(
+            cfg,
+            ConfigOverrides {
+                config_profile: Some("auto".to_string()),
+                ..Default::default()
+            },
+            codex_home.path().to_path_buf(),
+        )?;
+
+        assert_eq!(
+            (
+                top_level.model_reasoning_effort,
+                auto.model_reasoning_effort
+            ),
+            (Some(ReasoningEffort::High), None)
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn config_defaults_to_auto_oauth_store_mode() -> std::io::Result<()> {
+        let codex_home = TempDir::new()?;
+        let cfg = ConfigToml::default();
+
+        let config = Config::load_from_base_config_with_overrides(
+            cfg,
+            ConfigOverrides::default(),
+            codex_home.path().to_path_buf(),
+        )?;
+
+        assert_eq!(
+            config.mcp_oauth_credentials_store_mode,
+            OAuthCredentialsStoreMode::Auto,
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn profile_legacy_toggles_override_base() -> std::io::Result<()> {
+        let codex_home = TempDir::new()?;
+        let mut profiles = HashMap::new();
+        profiles.insert(
+            "work".to_string(),
+            ConfigProfile {
+                tools_view_image: Some(false),
+                ..Default::default()
+            },
+        );
+        let cfg = ConfigToml {
+            profiles,
+            profile: Some("work".to_string()),
+            ..Default::default()
+        };
+
+        let config = Config::load_from_base_config_with_overrides(
+            cfg,
+            ConfigOverrides::default(),
+            codex_home.path().to_path_buf(),
+        )?;
+
+        assert!(!config.features.enabled(Feature::ViewImageTool));
+
+        Ok(())
+    }
+
+    #[test]
+    fn profile_sandbox_mode_overrides_base() -> std::io::Result<()> {
+        let codex_home = TempDir::new()?;
+        let mut profiles = HashMap::new();
+        profiles.insert(
+            "work".to_string(),
+            ConfigProfile {
+                sandbox_mode: Some(SandboxMode::DangerFullAccess),
+                ..Default::default()
+            },
+        );
+        let cfg = ConfigToml {
+            profiles,
+            profile: Some("work".to_string()),
+            sandbox_mode: Some(SandboxMode::ReadOnly),
+            ..Default::default()
+        };
 
         let config = Config::load_from_base_config_with_overrides(
             cfg,
@@ -2834,7 +4399,10 @@ url = "https://example.com/mcp"
         let parsed: ConfigToml = toml::from_str(&serialized)?;
 
         assert_eq!(parsed.model.as_deref(), Some("gpt-5.1-codex"));
-        assert_eq!(parsed.model_reasoning_effort, Some(ReasoningEffort::High));
+        assert_eq!(
+            parsed.model_reasoning_effort,
+            Some(EffortSelection::Explicit(ReasoningEffort::High))
+        );
 
         Ok(())
     }
@@ -2865,7 +4433,10 @@ model = "gpt-4.1"
         let parsed: ConfigToml = toml::from_str(&serialized)?;
 
         assert_eq!(parsed.model.as_deref(), Some("o4-mini"));
-        assert_eq!(parsed.model_reasoning_effort, Some(ReasoningEffort::High));
+        assert_eq!(
+            parsed.model_reasoning_effort,
+            Some(EffortSelection::Explicit(ReasoningEffort::High))
+        );
         assert_eq!(
             parsed
                 .profiles
@@ -2898,7 +4469,7 @@ model = "gpt-4.1"
         assert_eq!(profile.model.as_deref(), Some("gpt-5.1-codex"));
         assert_eq!(
             profile.model_reasoning_effort,
-            Some(ReasoningEffort::Medium)
+            Some(EffortSelection::Explicit(ReasoningEffort::Medium))
         );
 
         Ok(())
@@ -2938,7 +4509,7 @@ model = "gpt-5.1-codex"
         assert_eq!(dev_profile.model.as_deref(), Some("o4-high"));
         assert_eq!(
             dev_profile.model_reasoning_effort,
-            Some(ReasoningEffort::Medium)
+            Some(EffortSelection::Explicit(ReasoningEffort::Medium))
         );
 
         assert_eq!(
@@ -3097,6 +4668,21 @@ model_verbosity = "high"
             stream_max_retries: Some(10),
             stream_idle_timeout_ms: Some(300_000),
             requires_openai_auth: false,
+            model_catalog: None,
+            skip_models_preflight: false,
+            allow_anonymous_catalog: false,
+            allow_empty_catalog: false,
+            instructions_addendum: None,
+            supported_wire_apis: None,
+            model_pricing: None,
+            models_max_response_bytes: None,
+            models: None,
+            model_request_headers: None,
+            models_path: None,
+            allow_insecure_catalog: false,
+            pinned_spki_sha256: None,
+            token_command: None,
+            token_command_ttl_secs: None,
         };
         let model_provider_map = {
             let mut model_provider_map = built_in_model_providers();
@@ -3152,8 +4738,13 @@ model_verbosity = "high"
             Config {
                 model: Some("o3".to_string()),
                 review_model: OPENAI_DEFAULT_REVIEW_MODEL.to_string(),
+                review_model_preset: None,
+                compact_model_preset: None,
                 model_context_window: None,
                 model_auto_compact_token_limit: None,
+                model_max_output_tokens: None,
+                model_temperature: None,
+                model_top_p: None,
                 model_provider_id: "openai".to_string(),
                 model_provider: fixture.openai_provider.clone(),
                 approval_policy: Constrained::allow_any(AskForApproval::Never),
@@ -3169,6 +4760,7 @@ model_verbosity = "high"
                 mcp_oauth_credentials_store_mode: Default::default(),
                 model_providers: fixture.model_provider_map.clone(),
                 project_doc_max_bytes: PROJECT_DOC_MAX_BYTES,
+                validate_instruction_models: false,
                 project_doc_fallback_filenames: Vec::new(),
                 tool_output_token_limit: None,
                 codex_home: fixture.codex_home(),
@@ -3181,6 +4773,7 @@ model_verbosity = "high"
                 model_reasoning_effort: Some(ReasoningEffort::High),
                 model_reasoning_summary: ReasoningSummary::Detailed,
                 model_supports_reasoning_summaries: None,
+                model_reasoning_summary_format: None,
                 model_verbosity: None,
                 chatgpt_base_url: "https://chatgpt.com/backend-api/".to_string(),
                 base_instructions: None,
@@ -3198,6 +4791,26 @@ model_verbosity = "high"
                 windows_wsl_setup_acknowledged: false,
                 notices: Default::default(),
                 check_for_update_on_startup: true,
+                offline: false,
+                strict_model_validation: false,
+                rewrite_retired_models: false,
+                prefetch_model_catalog: false,
+                write_status_file: false,
+                session_cost_warning_usd: None,
+                session_cost_limit_usd: None,
+                model_catalog_sources: ModelCatalogSource::DEFAULT_ORDER.to_vec(),
+                user_agent_suffix: None,
+                catalog_locale: None,
+                catalog_refresh_jitter: DEFAULT_CATALOG_REFRESH_JITTER,
+                model_cache_max_staleness: DEFAULT_MODEL_CACHE_MAX_STALENESS,
+                model_state_retention: DEFAULT_MODEL_STATE_RETENTION,
+                allowed_catalog_base_urls: Vec::new(),
+                catalog_base_url: None,
+                catalog_credentials_withheld: false,
+                effort_descriptions: HashMap::new(),
+                disabled_reasoning_efforts: Vec::new(),
+                preset_overrides: HashMap::new(),
+                model_preset_overrides: HashMap::new(),
                 disable_paste_burst: false,
                 tui_notifications: Default::default(),
                 animations: true,
@@ -3212,6 +4825,7 @@ model_verbosity = "high"
                 tui_scroll_wheel_like_max_duration_ms: None,
                 tui_scroll_invert: false,
                 otel: OtelConfig::default(),
+                ignored_project_settings: Vec::new(),
             },
             o3_profile_config
         );
@@ -3235,8 +4849,13 @@ model_verbosity = "high"
         let expected_gpt3_profile_config = Config {
             model: Some("gpt-3.5-turbo".to_string()),
             review_model: OPENAI_DEFAULT_REVIEW_MODEL.to_string(),
+            review_model_preset: None,
+            compact_model_preset: None,
             model_context_window: None,
             model_auto_compact_token_limit: None,
+            model_max_output_tokens: None,
+            model_temperature: None,
+            model_top_p: None,
             model_provider_id: "openai-chat-completions".to_string(),
             model_provider: fixture.openai_chat_completions_provider.clone(),
             approval_policy: Constrained::allow_any(AskForApproval::UnlessTrusted),
@@ -3252,6 +4871,7 @@ model_verbosity = "high"
             mcp_oauth_credentials_store_mode: Default::default(),
             model_providers: fixture.model_provider_map.clone(),
             project_doc_max_bytes: PROJECT_DOC_MAX_BYTES,
+            validate_instruction_models: false,
             project_doc_fallback_filenames: Vec::new(),
             tool_output_token_limit: None,
             codex_home: fixture.codex_home(),
@@ -3264,6 +4884,7 @@ model_verbosity = "high"
             model_reasoning_effort: None,
             model_reasoning_summary: ReasoningSummary::default(),
             model_supports_reasoning_summaries: None,
+            model_reasoning_summary_format: None,
             model_verbosity: None,
             chatgpt_base_url: "https://chatgpt.com/backend-api/".to_string(),
             base_instructions: None,
@@ -3281,6 +4902,26 @@ model_verbosity = "high"
             windows_wsl_setup_acknowledged: false,
             notices: Default::default(),
             check_for_update_on_startup: true,
+            offline: false,
+            strict_model_validation: false,
+            rewrite_retired_models: false,
+            prefetch_model_catalog: false,
+            write_status_file: false,
+            session_cost_warning_usd: None,
+            session_cost_limit_usd: None,
+            model_catalog_sources: ModelCatalogSource::DEFAULT_ORDER.to_vec(),
+            user_agent_suffix: None,
+            catalog_locale: None,
+            catalog_refresh_jitter: DEFAULT_CATALOG_REFRESH_JITTER,
+            model_cache_max_staleness: DEFAULT_MODEL_CACHE_MAX_STALENESS,
+            model_state_retention: DEFAULT_MODEL_STATE_RETENTION,
+            allowed_catalog_base_urls: Vec::new(),
+            catalog_base_url: None,
+            catalog_credentials_withheld: false,
+            effort_descriptions: HashMap::new(),
+            disabled_reasoning_efforts: Vec::new(),
+            preset_overrides: HashMap::new(),
+            model_preset_overrides: HashMap::new(),
             disable_paste_burst: false,
             tui_notifications: Default::default(),
             animations: true,
@@ -3295,6 +4936,7 @@ model_verbosity = "high"
             tui_scroll_wheel_like_max_duration_ms: None,
             tui_scroll_invert: false,
             otel: OtelConfig::default(),
+            ignored_project_settings: Vec::new(),
         };
 
         assert_eq!(expected_gpt3_profile_config, gpt3_profile_config);
@@ -3333,8 +4975,13 @@ model_verbosity = "high"
         let expected_zdr_profile_config = Config {
             model: Some("o3".to_string()),
             review_model: OPENAI_DEFAULT_REVIEW_MODEL.to_string(),
+            review_model_preset: None,
+            compact_model_preset: None,
             model_context_window: None,
             model_auto_compact_token_limit: None,
+            model_max_output_tokens: None,
+            model_temperature: None,
+            model_top_p: None,
             model_provider_id: "openai".to_string(),
             model_provider: fixture.openai_provider.clone(),
             approval_policy: Constrained::allow_any(AskForApproval::OnFailure),
@@ -3350,6 +4997,7 @@ model_verbosity = "high"
             mcp_oauth_credentials_store_mode: Default::default(),
             model_providers: fixture.model_provider_map.clone(),
             project_doc_max_bytes: PROJECT_DOC_MAX_BYTES,
+            validate_instruction_models: false,
             project_doc_fallback_filenames: Vec::new(),
             tool_output_token_limit: None,
             codex_home: fixture.codex_home(),
@@ -3362,6 +5010,7 @@ model_verbosity = "high"
             model_reasoning_effort: None,
             model_reasoning_summary: ReasoningSummary::default(),
             model_supports_reasoning_summaries: None,
+            model_reasoning_summary_format: None,
             model_verbosity: None,
             chatgpt_base_url: "https://chatgpt.com/backend-api/".to_string(),
             base_instructions: None,
@@ -3379,6 +5028,26 @@ model_verbosity = "high"
             windows_wsl_setup_acknowledged: false,
             notices: Default::default(),
             check_for_update_on_startup: true,
+            offline: false,
+            strict_model_validation: false,
+            rewrite_retired_models: false,
+            prefetch_model_catalog: false,
+            write_status_file: false,
+            session_cost_warning_usd: None,
+            session_cost_limit_usd: None,
+            model_catalog_sources: ModelCatalogSource::DEFAULT_ORDER.to_vec(),
+            user_agent_suffix: None,
+            catalog_locale: None,
+            catalog_refresh_jitter: DEFAULT_CATALOG_REFRESH_JITTER,
+            model_cache_max_staleness: DEFAULT_MODEL_CACHE_MAX_STALENESS,
+            model_state_retention: DEFAULT_MODEL_STATE_RETENTION,
+            allowed_catalog_base_urls: Vec::new(),
+            catalog_base_url: None,
+            catalog_credentials_withheld: false,
+            effort_descriptions: HashMap::new(),
+            disabled_reasoning_efforts: Vec::new(),
+            preset_overrides: HashMap::new(),
+            model_preset_overrides: HashMap::new(),
             disable_paste_burst: false,
             tui_notifications: Default::default(),
             animations: true,
@@ -3393,6 +5062,7 @@ model_verbosity = "high"
             tui_scroll_wheel_like_max_duration_ms: None,
             tui_scroll_invert: false,
             otel: OtelConfig::default(),
+            ignored_project_settings: Vec::new(),
         };
 
         assert_eq!(expected_zdr_profile_config, zdr_profile_config);
@@ -3417,8 +5087,13 @@ model_verbosity = "high"
         let expected_gpt5_profile_config = Config {
             model: Some("gpt-5.1".to_string()),
             review_model: OPENAI_DEFAULT_REVIEW_MODEL.to_string(),
+            review_model_preset: None,
+            compact_model_preset: None,
             model_context_window: None,
             model_auto_compact_token_limit: None,
+            model_max_output_tokens: None,
+            model_temperature: None,
+            model_top_p: None,
             model_provider_id: "openai".to_string(),
             model_provider: fixture.openai_provider.clone(),
             approval_policy: Constrained::allow_any(AskForApproval::OnFailure),
@@ -3434,6 +5109,7 @@ model_verbosity = "high"
             mcp_oauth_credentials_store_mode: Default::default(),
             model_providers: fixture.model_provider_map.clone(),
             project_doc_max_bytes: PROJECT_DOC_MAX_BYTES,
+            validate_instruction_models: false,
             project_doc_fallback_filenames: Vec::new(),
             tool_output_token_limit: None,
             codex_home: fixture.codex_home(),
@@ -3446,6 +5122,7 @@ model_verbosity = "high"
             model_reasoning_effort: Some(ReasoningEffort::High),
             model_reasoning_summary: ReasoningSummary::Detailed,
             model_supports_reasoning_summaries: None,
+            model_reasoning_summary_format: None,
             model_verbosity: Some(Verbosity::High),
             chatgpt_base_url: "https://chatgpt.com/backend-api/".to_string(),
             base_instructions: None,
@@ -3463,6 +5140,26 @@ model_verbosity = "high"
             windows_wsl_setup_acknowledged: false,
             notices: Default::default(),
             check_for_update_on_startup: true,
+            offline: false,
+            strict_model_validation: false,
+            rewrite_retired_models: false,
+            prefetch_model_catalog: false,
+            write_status_file: false,
+            session_cost_warning_usd: None,
+            session_cost_limit_usd: None,
+            model_catalog_sources: ModelCatalogSource::DEFAULT_ORDER.to_vec(),
+            user_agent_suffix: None,
+            catalog_locale: None,
+            catalog_refresh_jitter: DEFAULT_CATALOG_REFRESH_JITTER,
+            model_cache_max_staleness: DEFAULT_MODEL_CACHE_MAX_STALENESS,
+            model_state_retention: DEFAULT_MODEL_STATE_RETENTION,
+            allowed_catalog_base_urls: Vec::new(),
+            catalog_base_url: None,
+            catalog_credentials_withheld: false,
+            effort_descriptions: HashMap::new(),
+            disabled_reasoning_efforts: Vec::new(),
+            preset_overrides: HashMap::new(),
+            model_preset_overrides: HashMap::new(),
             disable_paste_burst: false,
             tui_notifications: Default::default(),
             animations: true,
@@ -3477,6 +5174,7 @@ model_verbosity = "high"
             tui_scroll_wheel_like_max_duration_ms: None,
             tui_scroll_invert: false,
             otel: OtelConfig::default(),
+            ignored_project_settings: Vec::new(),
         };
 
         assert_eq!(expected_gpt5_profile_config, gpt5_profile_config);
@@ -6,7 +6,7 @@ use crate::protocol::AskForApproval;
 use codex_protocol::config_types::ReasoningSummary;
 use codex_protocol::config_types::SandboxMode;
 use codex_protocol::config_types::Verbosity;
-use codex_protocol::openai_models::ReasoningEffort;
+use codex_protocol::openai_models::EffortSelection;
 
 /// Collection of common configuration options that a user can define as a unit
 /// in `config.toml`.
@@ -18,7 +18,7 @@ pub struct ConfigProfile {
     pub model_provider: Option<String>,
     pub approval_policy: Option<AskForApproval>,
     pub sandbox_mode: Option<SandboxMode>,
-    pub model_reasoning_effort: Option<ReasoningEffort>,
+    pub model_reasoning_effort: Option<EffortSelection>,
     pub model_reasoning_summary: Option<ReasoningSummary>,
     pub model_verbosity: Option<Verbosity>,
     pub chatgpt_base_url: Option<String>,
@@ -41,7 +41,9 @@ impl From<ConfigProfile> for codex_app_server_protocol::Profile {
             model: config_profile.model,
             model_provider: config_profile.model_provider,
             approval_policy: config_profile.approval_policy,
-            model_reasoning_effort: config_profile.model_reasoning_effort,
+            model_reasoning_effort: config_profile
+                .model_reasoning_effort
+                .and_then(EffortSelection::explicit),
             model_reasoning_summary: config_profile.model_reasoning_summary,
             model_verbosity: config_profile.model_verbosity,
             chatgpt_base_url: config_profile.chatgpt_base_url,
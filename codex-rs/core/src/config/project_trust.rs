@@ -0,0 +1,146 @@
+//! Project config, the `.codex/config.toml` files inside a workspace, may set
+//! the provider fields that shape prompts, outbound request headers, or the
+//! command run for a bearer token only once the workspace is trusted. Until
+//! then those fields keep the values the other layers give them, and the
+//! session says which settings were ignored.
+
+use std::collections::HashMap;
+use std::io::ErrorKind;
+use std::path::Path;
+
+use codex_app_server_protocol::ConfigLayerSource;
+use codex_utils_absolute_path::AbsolutePathBuf;
+use toml::Value as TomlValue;
+
+use super::ConfigToml;
+use super::ProjectConfig;
+use crate::config_loader::ConfigLayerStack;
+use crate::config_loader::ConfigLayerStackOrdering;
+use crate::config_loader::merge_toml_values;
+use crate::model_provider_info::ModelProviderInfo;
+
+/// `model_providers.<id>` fields that project config may only set in a
+/// trusted workspace.
+const GATED_PROVIDER_FIELDS: [&str; 3] = [
+    "instructions_addendum",
+    "model_request_headers",
+    "token_command",
+];
+
+/// A project config setting that was ignored because the workspace is not
+/// trusted.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IgnoredProjectSetting {
+    /// Dotted key of the setting, e.g.
+    /// `model_providers.gateway.instructions_addendum`.
+    pub key: String,
+    /// The `.codex` folder whose `config.toml` made the setting.
+    pub dot_codex_folder: AbsolutePathBuf,
+}
+
+/// Reset the gated fields project config sets in `model_providers` to what
+/// the other layers set, unless the workspace at `cwd` is trusted. Trust is
+/// read from the other layers as well, so a project cannot vouch for itself.
+pub(super) fn gate_project_provider_fields(
+    layers: &ConfigLayerStack,
+    cwd: &Path,
+    model_providers: &mut HashMap<String, ModelProviderInfo>,
+) -> std::io::Result<Vec<IgnoredProjectSetting>> {
+    let mut gated = Vec::new();
+    for layer in layers.get_layers(ConfigLayerStackOrdering::LowestPrecedenceFirst) {
+        let ConfigLayerSource::Project { dot_codex_folder } = &layer.name else {
+            continue;
+        };
+        let Some(providers) = layer
+            .config
+            .get("model_providers")
+            .and_then(TomlValue::as_table)
+        else {
+            continue;
+        };
+        for (provider_id, provider) in providers {
+            for field in GATED_PROVIDER_FIELDS {
+                if provider.get(field).is_some() {
+                    gated.push((provider_id.clone(), field, dot_codex_folder.clone()));
+                }
+            }
+        }
+    }
+    if gated.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let outside_project = config_outside_project(layers);
+    if active_project(&outside_project, cwd)?.is_some_and(|project| project.is_trusted()) {
+        return Ok(Vec::new());
+    }
+
+    let mut ignored = Vec::new();
+    for (provider_id, field, dot_codex_folder) in gated {
+        let value = outside_project
+            .get("model_providers")
+            .and_then(|providers| providers.get(&provider_id))
+            .and_then(|provider| provider.get(field))
+            .cloned();
+        if let Some(provider) = model_providers.get_mut(&provider_id) {
+            match field {
+                "instructions_addendum" => provider.instructions_addendum = parse(value)?,
+                "token_command" => provider.token_command = parse(value)?,
+                _ => provider.model_request_headers = parse(value)?,
+            }
+        }
+        ignored.push(IgnoredProjectSetting {
+            key: format!("model_providers.{provider_id}.{field}"),
+            dot_codex_folder,
+        });
+    }
+    Ok(ignored)
+}
+
+/// The startup warning naming the `ignored` settings, if there are any.
+pub fn ignored_settings_notice(ignored: &[IgnoredProjectSetting]) -> Option<String> {
+    if ignored.is_empty() {
+        return None;
+    }
+    let settings: Vec<String> = ignored
+        .iter()
+        .map(|setting| {
+            format!(
+                "`{}` (set in {})",
+                setting.key,
+                setting.dot_codex_folder.as_path().display()
+            )
+        })
+        .collect();
+    Some(format!(
+        "Ignored project config {} because this workspace is not trusted. Trust the workspace to apply it.",
+        settings.join(", ")
+    ))
+}
+
+/// The merged config of every layer except the project ones.
+fn config_outside_project(layers: &ConfigLayerStack) -> TomlValue {
+    let mut merged = TomlValue::Table(toml::map::Map::new());
+    for layer in layers.get_layers(ConfigLayerStackOrdering::LowestPrecedenceFirst) {
+        if !matches!(layer.name, ConfigLayerSource::Project { .. }) {
+            merge_toml_values(&mut merged, &layer.config);
+        }
+    }
+    merged
+}
+
+fn active_project(config: &TomlValue, cwd: &Path) -> std::io::Result<Option<ProjectConfig>> {
+    let projects: Option<HashMap<String, ProjectConfig>> = parse(config.get("projects").cloned())?;
+    let config = ConfigToml {
+        projects,
+        ..Default::default()
+    };
+    Ok(config.get_active_project(cwd))
+}
+
+fn parse<T: serde::de::DeserializeOwned>(value: Option<TomlValue>) -> std::io::Result<Option<T>> {
+    value
+        .map(TomlValue::try_into)
+        .transpose()
+        .map_err(|err| std::io::Error::new(ErrorKind::InvalidData, err))
+}
@@ -638,6 +638,8 @@ mod tests {
     use super::*;
     use anyhow::Result;
     use codex_app_server_protocol::AskForApproval;
+    use codex_protocol::openai_models::EffortSelection;
+    use codex_protocol::openai_models::ReasoningEffort;
     use codex_utils_absolute_path::AbsolutePathBuf;
     use pretty_assertions::assert_eq;
     use tempfile::tempdir;
@@ -815,6 +817,37 @@ remote_compaction = true
         }
     }
 
+    #[tokio::test]
+    async fn read_reports_auto_reasoning_effort() {
+        let tmp = tempdir().expect("tempdir");
+        std::fs::write(
+            tmp.path().join(CONFIG_TOML_FILE),
+            r#"model_reasoning_effort = "auto"
+
+[profiles.deep]
+model_reasoning_effort = "high"
+"#,
+        )
+        .unwrap();
+
+        let service = ConfigService::new(tmp.path().to_path_buf(), vec![]);
+        let response = service
+            .read(ConfigReadParams {
+                include_layers: false,
+            })
+            .await
+            .expect("response");
+
+        assert_eq!(
+            response.config.model_reasoning_effort,
+            Some(EffortSelection::Auto)
+        );
+        assert_eq!(
+            response.config.profiles["deep"].model_reasoning_effort,
+            Some(EffortSelection::Explicit(ReasoningEffort::High))
+        );
+    }
+
     #[tokio::test]
     async fn write_value_reports_override() {
         let tmp = tempdir().expect("tempdir");
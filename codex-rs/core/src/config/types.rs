@@ -3,6 +3,11 @@
 // Note this file should generally be restricted to simple struct/enum
 // definitions that do not contain business logic.
 
+use codex_otel::catalog::CatalogTelemetry;
+use codex_otel::catalog::ModelIdKey;
+use codex_protocol::openai_models::EffortByAuth;
+use codex_protocol::openai_models::ModelPreset;
+use codex_protocol::openai_models::ReasoningEffort;
 use codex_utils_absolute_path::AbsolutePathBuf;
 use std::collections::BTreeMap;
 use std::collections::HashMap;
@@ -328,6 +333,9 @@ pub struct OtelConfigToml {
 
     /// Optional trace exporter
     pub trace_exporter: Option<OtelExporterKind>,
+
+    /// Report model and preset ids as hashes in exported events
+    pub hash_model_ids: Option<bool>,
 }
 
 /// Effective OTEL settings after defaults are applied.
@@ -337,6 +345,19 @@ pub struct OtelConfig {
     pub environment: String,
     pub exporter: OtelExporterKind,
     pub trace_exporter: OtelExporterKind,
+    /// The install's key when `hash_model_ids` is set.
+    pub model_id_key: Option<ModelIdKey>,
+}
+
+impl OtelConfig {
+    /// The catalog events are part of the exported telemetry, so they are on
+    /// exactly when a log exporter is configured.
+    pub fn catalog_telemetry(&self) -> CatalogTelemetry {
+        CatalogTelemetry {
+            enabled: !matches!(self.exporter, OtelExporterKind::None),
+            model_id_key: self.model_id_key,
+        }
+    }
 }
 
 impl Default for OtelConfig {
@@ -346,6 +367,7 @@ impl Default for OtelConfig {
             environment: DEFAULT_OTEL_ENVIRONMENT.to_owned(),
             exporter: OtelExporterKind::None,
             trace_exporter: OtelExporterKind::None,
+            model_id_key: None,
         }
     }
 }
@@ -385,6 +407,110 @@ impl Default for ScrollInputMode {
     }
 }
 
+/// Limits set in config on the presets of one model, under
+/// `[preset_overrides."<model>"]`.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PresetOverride {
+    /// Lowest reasoning effort requests to the model may use.
+    pub min_effort: Option<ReasoningEffort>,
+    /// Highest reasoning effort requests to the model may use.
+    pub max_effort: Option<ReasoningEffort>,
+    /// Default effort per auth mode, replacing the preset's own
+    /// `default_reasoning_effort_by_auth` for the modes it sets.
+    pub default_effort_by_auth: Option<EffortByAuth>,
+}
+
+impl PresetOverride {
+    pub fn allows(&self, effort: ReasoningEffort) -> bool {
+        effort.is_within(self.min_effort, self.max_effort)
+    }
+
+    /// The nearest effort to `effort` that these limits allow.
+    pub fn clamp(&self, effort: ReasoningEffort) -> ReasoningEffort {
+        effort.clamp_to(self.min_effort, self.max_effort)
+    }
+}
+
+/// Wording set in config for one preset, under
+/// `[model_preset_overrides."<id>"]`, replacing whatever its catalog says,
+/// tools to keep from it on top of those the catalog disables, and the
+/// provider to serve it from. None of these is a [`PresetOverride`] setting,
+/// so the two tables never disagree about a preset.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct ModelPresetOverride {
+    pub display_name: Option<String>,
+    pub description: Option<String>,
+    /// Description per reasoning effort, keyed by effort name. Kept as
+    /// written so a name this build does not know only warns.
+    #[serde(default)]
+    pub effort_descriptions: BTreeMap<String, String>,
+    /// Names of tools, such as `web_search`, not offered to the model.
+    #[serde(default)]
+    pub disabled_tools: Vec<String>,
+    /// Id of the `model_providers` entry that serves the preset instead of
+    /// the session's provider, so picking it also switches providers.
+    pub model_provider: Option<String>,
+}
+
+impl ModelPresetOverride {
+    /// The configured description of `effort`, if any.
+    pub fn effort_description(&self, effort: ReasoningEffort) -> Option<&str> {
+        self.effort_descriptions
+            .iter()
+            .find(|(name, _)| name.parse::<ReasoningEffort>().ok() == Some(effort))
+            .map(|(_, description)| description.as_str())
+    }
+
+    pub fn apply_to(&self, preset: &mut ModelPreset) {
+        if let Some(display_name) = &self.display_name {
+            preset.display_name.clone_from(display_name);
+        }
+        if let Some(description) = &self.description {
+            preset.description.clone_from(description);
+        }
+        for effort in &mut preset.supported_reasoning_efforts {
+            if let Some(description) = self.effort_description(effort.effort) {
+                effort.description = description.to_string();
+            }
+        }
+        for tool in &self.disabled_tools {
+            if !preset.disabled_tools.contains(tool) {
+                preset.disabled_tools.push(tool.clone());
+            }
+        }
+        if let Some(provider_id) = &self.model_provider {
+            preset.model_provider = Some(provider_id.clone());
+        }
+    }
+}
+
+/// A catalog of model presets, as named in `model_catalog_sources`.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ModelCatalogSource {
+    /// The presets compiled into this build.
+    Builtin,
+    /// Model metadata from the `/models` endpoint, its on-disk cache, or the
+    /// snapshot bundled with this build.
+    Remote,
+    /// Models listed by configured providers that declare a `model_catalog`.
+    Providers,
+}
+
+impl ModelCatalogSource {
+    /// Order used when `model_catalog_sources` is unset: remote metadata
+    /// replaces the built-in presets it also lists, and provider models follow.
+    pub const DEFAULT_ORDER: [Self; 3] = [Self::Remote, Self::Builtin, Self::Providers];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Builtin => "builtin",
+            Self::Remote => "remote",
+            Self::Providers => "providers",
+        }
+    }
+}
+
 /// Collection of settings that are specific to the TUI.
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
 pub struct Tui {
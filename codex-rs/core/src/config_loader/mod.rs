@@ -26,6 +26,7 @@ use toml::Value as TomlValue;
 
 pub use config_requirements::ConfigRequirements;
 pub use merge::merge_toml_values;
+pub(crate) use overrides::build_cli_overrides_layer;
 pub use state::ConfigLayerEntry;
 pub use state::ConfigLayerStack;
 pub use state::ConfigLayerStackOrdering;
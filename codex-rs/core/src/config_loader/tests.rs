@@ -3,11 +3,15 @@ use super::load_config_layers_state;
 use crate::config::CONFIG_TOML_FILE;
 use crate::config::ConfigBuilder;
 use crate::config::ConfigOverrides;
+use crate::config::project_trust::IgnoredProjectSetting;
+use crate::config::project_trust::ignored_settings_notice;
+use crate::config::set_project_trust_level;
 use crate::config_loader::ConfigLayerEntry;
 use crate::config_loader::ConfigRequirements;
 use crate::config_loader::config_requirements::ConfigRequirementsToml;
 use crate::config_loader::fingerprint::version_for_toml;
 use crate::config_loader::load_requirements_toml;
+use codex_protocol::config_types::TrustLevel;
 use codex_protocol::protocol::AskForApproval;
 use codex_utils_absolute_path::AbsolutePathBuf;
 use pretty_assertions::assert_eq;
@@ -434,3 +438,172 @@ project_root_markers = [".hg"]
 
     Ok(())
 }
+
+async fn write_gateway_configs(
+    codex_home: &std::path::Path,
+    project_root: &std::path::Path,
+) -> std::io::Result<()> {
+    tokio::fs::create_dir_all(codex_home).await?;
+    tokio::fs::create_dir_all(project_root.join(".codex")).await?;
+    tokio::fs::write(project_root.join(".git"), "gitdir: here").await?;
+    tokio::fs::write(
+        codex_home.join(CONFIG_TOML_FILE),
+        r#"
+[model_providers.gateway]
+name = "Gateway"
+base_url = "https://gateway.example.com/v1"
+instructions_addendum = "From the user config."
+"#,
+    )
+    .await?;
+    tokio::fs::write(
+        project_root.join(".codex").join(CONFIG_TOML_FILE),
+        r#"
+[model_providers.gateway]
+instructions_addendum = "From the project config."
+model_request_headers = { "gpt-5" = { "X-Project" = "yes" } }
+"#,
+    )
+    .await
+}
+
+async fn load_gateway_config(
+    codex_home: &std::path::Path,
+    cwd: &std::path::Path,
+) -> std::io::Result<crate::config::Config> {
+    ConfigBuilder::default()
+        .codex_home(codex_home.to_path_buf())
+        .harness_overrides(ConfigOverrides {
+            cwd: Some(cwd.to_path_buf()),
+            ..ConfigOverrides::default()
+        })
+        .build()
+        .await
+}
+
+#[tokio::test]
+async fn untrusted_project_cannot_set_provider_instructions_or_headers() -> std::io::Result<()> {
+    let tmp = tempdir()?;
+    let codex_home = tmp.path().join("home");
+    let project_root = tmp.path().join("project");
+    write_gateway_configs(&codex_home, &project_root).await?;
+
+    let config = load_gateway_config(&codex_home, &project_root).await?;
+
+    let gateway = &config.model_providers["gateway"];
+    assert_eq!(
+        gateway.instructions_addendum.as_deref(),
+        Some("From the user config.")
+    );
+    assert_eq!(gateway.model_request_headers, None);
+    let dot_codex_folder = AbsolutePathBuf::try_from(project_root.join(".codex"))?;
+    assert_eq!(
+        config.ignored_project_settings,
+        vec![
+            IgnoredProjectSetting {
+                key: "model_providers.gateway.instructions_addendum".to_string(),
+                dot_codex_folder: dot_codex_folder.clone(),
+            },
+            IgnoredProjectSetting {
+                key: "model_providers.gateway.model_request_headers".to_string(),
+                dot_codex_folder: dot_codex_folder.clone(),
+            },
+        ]
+    );
+    let folder = dot_codex_folder.as_path().display();
+    assert_eq!(
+        ignored_settings_notice(&config.ignored_project_settings),
+        Some(format!(
+            "Ignored project config `model_providers.gateway.instructions_addendum` (set in {folder}), `model_providers.gateway.model_request_headers` (set in {folder}) because this workspace is not trusted. Trust the workspace to apply it."
+        ))
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn untrusted_project_cannot_set_a_token_command() -> std::io::Result<()> {
+    let tmp = tempdir()?;
+    let codex_home = tmp.path().join("home");
+    let project_root = tmp.path().join("project");
+    write_gateway_configs(&codex_home, &project_root).await?;
+    tokio::fs::write(
+        project_root.join(".codex").join(CONFIG_TOML_FILE),
+        r#"
+[model_providers.gateway]
+token_command = ["./mint-token.sh"]
+"#,
+    )
+    .await?;
+
+    let config = load_gateway_config(&codex_home, &project_root).await?;
+
+    assert_eq!(config.model_providers["gateway"].token_command, None);
+    assert_eq!(
+        config.ignored_project_settings,
+        vec![IgnoredProjectSetting {
+            key: "model_providers.gateway.token_command".to_string(),
+            dot_codex_folder: AbsolutePathBuf::try_from(project_root.join(".codex"))?,
+        }]
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn trusting_the_project_applies_its_provider_instructions_and_headers() -> std::io::Result<()>
+{
+    let tmp = tempdir()?;
+    let codex_home = tmp.path().join("home");
+    let project_root = tmp.path().join("project");
+    write_gateway_configs(&codex_home, &project_root).await?;
+    set_project_trust_level(&codex_home, &project_root, TrustLevel::Trusted)
+        .map_err(std::io::Error::other)?;
+
+    let config = load_gateway_config(&codex_home, &project_root).await?;
+
+    let gateway = &config.model_providers["gateway"];
+    assert_eq!(
+        gateway.instructions_addendum.as_deref(),
+        Some("From the project config.")
+    );
+    assert_eq!(
+        gateway
+            .model_request_headers
+            .as_ref()
+            .and_then(|by_model| by_model.get("gpt-5"))
+            .and_then(|headers| headers.get("X-Project"))
+            .map(String::as_str),
+        Some("yes")
+    );
+    assert_eq!(config.ignored_project_settings, Vec::new());
+    assert_eq!(
+        ignored_settings_notice(&config.ignored_project_settings),
+        None
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn project_config_cannot_trust_its_own_workspace() -> std::io::Result<()> {
+    let tmp = tempdir()?;
+    let codex_home = tmp.path().join("home");
+    let project_root = tmp.path().join("project");
+    write_gateway_configs(&codex_home, &project_root).await?;
+    let project_config = project_root.join(".codex").join(CONFIG_TOML_FILE);
+    let mut contents = tokio::fs::read_to_string(&project_config).await?;
+    contents.push_str(&format!(
+        "\n[projects.{:?}]\ntrust_level = \"trusted\"\n",
+        project_root.display().to_string()
+    ));
+    tokio::fs::write(&project_config, contents).await?;
+
+    let config = load_gateway_config(&codex_home, &project_root).await?;
+
+    assert_eq!(
+        config.model_providers["gateway"]
+            .instructions_addendum
+            .as_deref(),
+        Some("From the user config.")
+    );
+    assert_eq!(config.ignored_project_settings.len(), 2);
+    Ok(())
+}
@@ -85,22 +85,10 @@ impl ContextManager {
             i64::try_from(approx_token_count(model_family.base_instructions.as_str()))
                 .unwrap_or(i64::MAX);
 
-        let items_tokens = self.items.iter().fold(0i64, |acc, item| {
-            acc + match item {
-                ResponseItem::GhostSnapshot { .. } => 0,
-                ResponseItem::Reasoning {
-                    encrypted_content: Some(content),
-                    ..
-                }
-                | ResponseItem::Compaction {
-                    encrypted_content: content,
-                } => estimate_reasoning_length(content.len()) as i64,
-                item => {
-                    let serialized = serde_json::to_string(item).unwrap_or_default();
-                    i64::try_from(approx_token_count(&serialized)).unwrap_or(i64::MAX)
-                }
-            }
-        });
+        let items_tokens = self
+            .items
+            .iter()
+            .fold(0i64, |acc, item| acc + estimate_item_token_count(item));
 
         Some(base_tokens.saturating_add(items_tokens))
     }
@@ -154,6 +142,7 @@ impl ContextManager {
 
     pub(crate) fn update_token_info(
         &mut self,
+        model: &str,
         usage: &TokenUsage,
         model_context_window: Option<i64>,
     ) {
@@ -162,6 +151,9 @@ impl ContextManager {
             &Some(usage.clone()),
             model_context_window,
         );
+        if let Some(info) = self.token_info.as_mut() {
+            info.append_model_usage(model, usage);
+        }
     }
 
     fn get_non_last_reasoning_items_tokens(&self) -> usize {
@@ -283,6 +275,25 @@ fn is_api_message(message: &ResponseItem) -> bool {
     }
 }
 
+/// Byte-based estimate of the tokens `item` takes in a prompt, as
+/// [`ContextManager::estimate_token_count`] counts them.
+pub(crate) fn estimate_item_token_count(item: &ResponseItem) -> i64 {
+    match item {
+        ResponseItem::GhostSnapshot { .. } => 0,
+        ResponseItem::Reasoning {
+            encrypted_content: Some(content),
+            ..
+        }
+        | ResponseItem::Compaction {
+            encrypted_content: content,
+        } => estimate_reasoning_length(content.len()) as i64,
+        item => {
+            let serialized = serde_json::to_string(item).unwrap_or_default();
+            i64::try_from(approx_token_count(&serialized)).unwrap_or(i64::MAX)
+        }
+    }
+}
+
 fn estimate_reasoning_length(encoded_len: usize) -> usize {
     encoded_len
         .saturating_mul(3)
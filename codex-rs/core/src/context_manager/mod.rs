@@ -2,3 +2,4 @@ mod history;
 mod normalize;
 
 pub(crate) use history::ContextManager;
+pub(crate) use history::estimate_item_token_count;
@@ -11,6 +11,9 @@ use crate::config::Config;
 use crate::error::CodexErr;
 use crate::error::Result as CodexResult;
 use crate::models_manager::manager::ModelsManager;
+use crate::models_manager::tenants::TENANT_IDLE_TTL;
+use crate::models_manager::tenants::TenantCatalogs;
+use crate::models_manager::tenants::TenantKey;
 use crate::protocol::Event;
 use crate::protocol::EventMsg;
 use crate::protocol::SessionConfiguredEvent;
@@ -44,6 +47,9 @@ pub struct ConversationManager {
     conversations: Arc<RwLock<HashMap<ConversationId, Arc<CodexConversation>>>>,
     auth_manager: Arc<AuthManager>,
     models_manager: Arc<ModelsManager>,
+    /// Managers of the tenant gateways configs select with
+    /// [`Config::override_catalog_base_url`].
+    tenant_catalogs: TenantCatalogs,
     skills_manager: Arc<SkillsManager>,
     session_source: SessionSource,
     #[cfg(any(test, feature = "test-support"))]
@@ -58,6 +64,7 @@ impl ConversationManager {
             auth_manager: auth_manager.clone(),
             session_source,
             models_manager: Arc::new(ModelsManager::new(auth_manager)),
+            tenant_catalogs: TenantCatalogs::new(TENANT_IDLE_TTL),
             skills_manager,
             #[cfg(any(test, feature = "test-support"))]
             _test_codex_home_guard: None,
@@ -90,6 +97,7 @@ impl ConversationManager {
             auth_manager: auth_manager.clone(),
             session_source: SessionSource::Exec,
             models_manager: Arc::new(ModelsManager::with_provider(auth_manager, provider)),
+            tenant_catalogs: TenantCatalogs::new(TENANT_IDLE_TTL),
             skills_manager,
             _test_codex_home_guard: None,
         }
@@ -104,12 +112,10 @@ impl ConversationManager {
     }
 
     pub async fn new_conversation(&self, config: Config) -> CodexResult<NewConversation> {
-        self.spawn_conversation(
-            config,
-            self.auth_manager.clone(),
-            self.models_manager.clone(),
-        )
-        .await
+        let auth_manager = auth_manager_for(&config, &self.auth_manager);
+        let models_manager = self.models_manager_with(&config, &auth_manager);
+        self.spawn_conversation(config, auth_manager, models_manager)
+            .await
     }
 
     async fn spawn_conversation(
@@ -196,13 +202,15 @@ impl ConversationManager {
         initial_history: InitialHistory,
         auth_manager: Arc<AuthManager>,
     ) -> CodexResult<NewConversation> {
+        let auth_manager = auth_manager_for(&config, &auth_manager);
+        let models_manager = self.models_manager_with(&config, &auth_manager);
         let CodexSpawnOk {
             codex,
             conversation_id,
         } = Codex::spawn(
             config,
             auth_manager,
-            self.models_manager.clone(),
+            models_manager,
             self.skills_manager.clone(),
             initial_history,
             self.session_source.clone(),
@@ -237,14 +245,15 @@ impl ConversationManager {
         let history = truncate_before_nth_user_message(history, nth_user_message);
 
         // Spawn a new conversation with the computed initial history.
-        let auth_manager = self.auth_manager.clone();
+        let auth_manager = auth_manager_for(&config, &self.auth_manager);
+        let models_manager = self.models_manager_with(&config, &auth_manager);
         let CodexSpawnOk {
             codex,
             conversation_id,
         } = Codex::spawn(
             config,
             auth_manager,
-            self.models_manager.clone(),
+            models_manager,
             self.skills_manager.clone(),
             history,
             self.session_source.clone(),
@@ -261,6 +270,48 @@ impl ConversationManager {
     pub fn get_models_manager(&self) -> Arc<ModelsManager> {
         self.models_manager.clone()
     }
+
+    /// The manager that lists models for `config`: the one of its tenant
+    /// gateway when [`Config::override_catalog_base_url`] set one, otherwise
+    /// the shared one. Tenant managers no request has used for a while are
+    /// dropped; see [`crate::models_manager::tenants`].
+    pub fn models_manager_for(&self, config: &Config) -> Arc<ModelsManager> {
+        self.models_manager_with(config, &auth_manager_for(config, &self.auth_manager))
+    }
+
+    /// [`Self::models_manager_for`], with a tenant catalog fetched with the
+    /// credentials `auth_manager` holds and kept apart from other
+    /// credentials' catalogs of the same gateway.
+    fn models_manager_with(
+        &self,
+        config: &Config,
+        auth_manager: &Arc<AuthManager>,
+    ) -> Arc<ModelsManager> {
+        match config.catalog_base_url.as_deref() {
+            Some(base_url) => self.tenant_catalogs.models_manager(
+                TenantKey::new(base_url, config, auth_manager),
+                config,
+                || self.models_manager.sibling(Arc::clone(auth_manager)),
+            ),
+            None => self.get_models_manager(),
+        }
+    }
+
+    /// The tenant base URLs whose catalogs are currently kept up to date.
+    pub fn tenant_catalog_base_urls(&self) -> Vec<String> {
+        self.tenant_catalogs.base_urls()
+    }
+}
+
+/// The credentials a conversation on `config` may use: none when its tenant
+/// gateway is on another host than the provider's credentials were
+/// configured for, see [`Config::catalog_credentials_withheld`].
+fn auth_manager_for(config: &Config, auth_manager: &Arc<AuthManager>) -> Arc<AuthManager> {
+    if config.catalog_credentials_withheld {
+        auth_manager.signed_out()
+    } else {
+        Arc::clone(auth_manager)
+    }
 }
 
 /// Return a prefix of `items` obtained by cutting strictly before the nth user message
@@ -0,0 +1,195 @@
+//! Guardrails on a session's estimated cost.
+//!
+//! The estimate prices each per-model usage bucket with the pricing of the
+//! preset serving that slug. Past `session_cost_warning_usd` the session warns
+//! once; at `session_cost_limit_usd` every turn asks before its first model
+//! request whether to go on. Models without pricing count as zero, and the
+//! session says so once per model.
+
+use std::collections::HashMap;
+use std::collections::HashSet;
+
+use codex_protocol::openai_models::ModelPricing;
+use codex_protocol::protocol::ModelTokenUsage;
+use codex_protocol::protocol::ReviewDecision;
+
+/// Estimated cost of the usage recorded so far.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct SessionCost {
+    pub(crate) usd: f64,
+    /// Models that used tokens but have no pricing, in usage order.
+    pub(crate) unpriced_models: Vec<String>,
+}
+
+pub(crate) fn session_cost(
+    per_model_usage: &[ModelTokenUsage],
+    pricing: &HashMap<String, ModelPricing>,
+) -> SessionCost {
+    let mut usd = 0.0;
+    let mut unpriced_models = Vec::new();
+    for bucket in per_model_usage {
+        match pricing.get(&bucket.model) {
+            Some(price) => usd += price.estimate_cost(&bucket.token_usage),
+            None => unpriced_models.push(bucket.model.clone()),
+        }
+    }
+    SessionCost {
+        usd,
+        unpriced_models,
+    }
+}
+
+/// What the session already told the user about its cost.
+#[derive(Debug, Default)]
+pub(crate) struct CostGuard {
+    warned: bool,
+    noted_unpriced: HashSet<String>,
+    approved_for_session: bool,
+}
+
+impl CostGuard {
+    /// Warnings to show for `cost`: a note for each unpriced model not
+    /// mentioned yet, then the soft threshold warning the first time `cost`
+    /// passes it.
+    pub(crate) fn warnings(&mut self, cost: &SessionCost, warning_usd: Option<f64>) -> Vec<String> {
+        let mut warnings = Vec::new();
+        for model in &cost.unpriced_models {
+            if self.noted_unpriced.insert(model.clone()) {
+                warnings.push(format!(
+                    "Model `{model}` has no pricing, so its usage counts as $0 toward the session cost."
+                ));
+            }
+        }
+        if let Some(warning_usd) = warning_usd
+            && cost.usd >= warning_usd
+            && !self.warned
+        {
+            self.warned = true;
+            warnings.push(format!(
+                "Estimated session cost is ${:.2}, past `session_cost_warning_usd` (${warning_usd:.2}).",
+                cost.usd
+            ));
+        }
+        warnings
+    }
+
+    /// Whether a turn starting at `cost_usd` must ask before it runs.
+    pub(crate) fn needs_approval(&self, cost_usd: f64, limit_usd: f64) -> bool {
+        cost_usd >= limit_usd && !self.approved_for_session
+    }
+
+    /// Record the answer to a turn's prompt. Returns whether the turn may
+    /// run.
+    pub(crate) fn record_decision(&mut self, decision: &ReviewDecision) -> bool {
+        match decision {
+            ReviewDecision::Approved => true,
+            ReviewDecision::ApprovedForSession => {
+                self.approved_for_session = true;
+                true
+            }
+            ReviewDecision::ApprovedExecpolicyAmendment { .. }
+            | ReviewDecision::Denied
+            | ReviewDecision::Abort => false,
+        }
+    }
+}
+
+/// Why a turn stopped when its prompt was declined.
+pub(crate) fn limit_reached_message(cost_usd: f64, limit_usd: f64) -> String {
+    format!(
+        "Skipped the turn: estimated session cost ${cost_usd:.2} reached `session_cost_limit_usd` (${limit_usd:.2})."
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codex_protocol::protocol::TokenUsage;
+    use pretty_assertions::assert_eq;
+
+    fn usage(model: &str, input_tokens: i64, output_tokens: i64) -> ModelTokenUsage {
+        ModelTokenUsage {
+            model: model.to_string(),
+            token_usage: TokenUsage {
+                input_tokens,
+                output_tokens,
+                total_tokens: input_tokens + output_tokens,
+                ..Default::default()
+            },
+        }
+    }
+
+    fn pricing() -> HashMap<String, ModelPricing> {
+        HashMap::from([(
+            "priced".to_string(),
+            ModelPricing {
+                input_per_million: 2.0,
+                cached_input_per_million: None,
+                output_per_million: 10.0,
+            },
+        )])
+    }
+
+    #[test]
+    fn the_warning_fires_once_when_the_threshold_is_crossed() {
+        let mut guard = CostGuard::default();
+        let below = session_cost(&[usage("priced", 1_000_000, 100_000)], &pricing());
+        assert_eq!(below.usd, 3.0);
+        assert_eq!(guard.warnings(&below, Some(5.0)), Vec::<String>::new());
+
+        let above = session_cost(&[usage("priced", 2_000_000, 200_000)], &pricing());
+        assert_eq!(
+            guard.warnings(&above, Some(5.0)),
+            vec![
+                "Estimated session cost is $6.00, past `session_cost_warning_usd` ($5.00)."
+                    .to_string()
+            ]
+        );
+        assert_eq!(guard.warnings(&above, Some(5.0)), Vec::<String>::new());
+    }
+
+    #[test]
+    fn unpriced_models_count_as_zero_with_a_note() {
+        let mut guard = CostGuard::default();
+        let per_model_usage = [
+            usage("priced", 1_000_000, 0),
+            usage("local-llama", 5_000_000, 5_000_000),
+        ];
+        let cost = session_cost(&per_model_usage, &pricing());
+        assert_eq!(
+            cost,
+            SessionCost {
+                usd: 2.0,
+                unpriced_models: vec!["local-llama".to_string()],
+            }
+        );
+        assert_eq!(
+            guard.warnings(&cost, None),
+            vec![
+                "Model `local-llama` has no pricing, so its usage counts as $0 toward the session cost."
+                    .to_string()
+            ]
+        );
+        assert_eq!(
+            guard.warnings(&cost, None),
+            Vec::<String>::new(),
+            "each model is noted once"
+        );
+    }
+
+    #[test]
+    fn approvals_last_for_the_turn_or_the_session() {
+        let mut guard = CostGuard::default();
+        assert!(!guard.needs_approval(9.99, 10.0));
+        assert!(guard.needs_approval(10.0, 10.0));
+
+        assert!(guard.record_decision(&ReviewDecision::Approved));
+        assert!(guard.needs_approval(12.0, 10.0), "the next turn asks again");
+
+        assert!(!guard.record_decision(&ReviewDecision::Denied));
+        assert!(guard.needs_approval(12.0, 10.0));
+
+        assert!(guard.record_decision(&ReviewDecision::ApprovedForSession));
+        assert!(!guard.needs_approval(50.0, 10.0));
+    }
+}
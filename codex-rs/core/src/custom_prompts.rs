@@ -1,4 +1,5 @@
 use codex_protocol::custom_prompts::CustomPrompt;
+use codex_protocol::openai_models::ReasoningEffort;
 use std::collections::HashSet;
 use std::path::Path;
 use std::path::PathBuf;
@@ -144,9 +145,74 @@ fn parse_frontmatter(content: &str) -> (Option<String>, Option<String>, String)
     (desc, hint, body)
 }
 
+/// Values for the `{{model.*}}` template variables, taken from the model that
+/// is active when the prompt is sent.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PromptModelVariables {
+    pub display_name: String,
+    pub context_window: Option<i64>,
+    pub effort: Option<ReasoningEffort>,
+}
+
+/// Prompt text after `{{model.*}}` variables have been substituted.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RenderedPrompt {
+    pub text: String,
+    /// Names of `model.*` fields that are not known; they render empty.
+    pub unknown_variables: Vec<String>,
+}
+
+/// Substitute `{{model.display_name}}`, `{{model.context_window}}` and
+/// `{{model.effort}}` in `content`. Whitespace inside the braces is allowed.
+/// Unknown `model.*` fields render as an empty string and are reported in
+/// [`RenderedPrompt::unknown_variables`]; any other `{{...}}` is left as-is.
+pub fn render_model_variables(content: &str, vars: &PromptModelVariables) -> RenderedPrompt {
+    let mut text = String::with_capacity(content.len());
+    let mut unknown_variables = Vec::new();
+    let mut rest = content;
+    while let Some(start) = rest.find("{{") {
+        let after_open = &rest[start + 2..];
+        let Some(end) = after_open.find("}}") else {
+            break;
+        };
+        let name = after_open[..end].trim();
+        let Some(field) = name.strip_prefix("model.") else {
+            text.push_str(&rest[..start + 2]);
+            rest = after_open;
+            continue;
+        };
+        text.push_str(&rest[..start]);
+        match field {
+            "display_name" => text.push_str(&vars.display_name),
+            "context_window" => {
+                if let Some(context_window) = vars.context_window {
+                    text.push_str(&context_window.to_string());
+                }
+            }
+            "effort" => {
+                if let Some(effort) = vars.effort {
+                    text.push_str(&effort.to_string());
+                }
+            }
+            _ => {
+                if !unknown_variables.iter().any(|known| known == name) {
+                    unknown_variables.push(name.to_string());
+                }
+            }
+        }
+        rest = &after_open[end + 2..];
+    }
+    text.push_str(rest);
+    RenderedPrompt {
+        text,
+        unknown_variables,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use pretty_assertions::assert_eq;
     use std::fs;
     use tempfile::tempdir;
 
@@ -241,4 +307,44 @@ mod tests {
         assert_eq!(hint.as_deref(), Some("[arg]"));
         assert_eq!(body, "First line\r\nSecond line\r\n");
     }
+
+    #[test]
+    fn renders_model_variables() {
+        let vars = PromptModelVariables {
+            display_name: "OCA Large".to_string(),
+            context_window: Some(200_000),
+            effort: Some(ReasoningEffort::High),
+        };
+        let rendered = render_model_variables(
+            "Using {{model.display_name}} ({{ model.context_window }} tokens) at {{model.effort}} for $1 and {{other}}",
+            &vars,
+        );
+        assert_eq!(
+            rendered,
+            RenderedPrompt {
+                text: "Using OCA Large (200000 tokens) at high for $1 and {{other}}".to_string(),
+                unknown_variables: Vec::new(),
+            }
+        );
+    }
+
+    #[test]
+    fn unknown_model_variables_render_empty() {
+        let vars = PromptModelVariables {
+            display_name: "OCA Large".to_string(),
+            context_window: None,
+            effort: None,
+        };
+        let rendered = render_model_variables(
+            "[{{model.vendor}}][{{model.context_window}}][{{model.vendor}}][{{model.effort}}] {{model.display_name",
+            &vars,
+        );
+        assert_eq!(
+            rendered,
+            RenderedPrompt {
+                text: "[][][][] {{model.display_name".to_string(),
+                unknown_variables: vec!["model.vendor".to_string()],
+            }
+        );
+    }
 }
@@ -1,10 +1,18 @@
+use crate::error::CodexErr;
+use crate::error::Result as CoreResult;
+use crate::model_provider_info::ModelProviderInfo;
+use crate::models_manager::preflight::PREFLIGHT_TIMEOUT;
 use crate::spawn::CODEX_SANDBOX_ENV_VAR;
 use codex_client::CodexHttpClient;
 pub use codex_client::CodexRequestBuilder;
+use codex_client::SpkiPin;
+use codex_client::pinned_tls_config;
 use reqwest::header::HeaderValue;
+use std::collections::HashMap;
 use std::sync::LazyLock;
 use std::sync::Mutex;
 use std::sync::OnceLock;
+use std::sync::PoisonError;
 
 /// Set this to add a suffix to the User-Agent string.
 ///
@@ -22,6 +30,8 @@ use std::sync::OnceLock;
 /// The full user agent string is returned from the mcp initialize response.
 /// Parenthesis will be added by Codex. This should only specify what goes inside of the parenthesis.
 pub static USER_AGENT_SUFFIX: LazyLock<Mutex<Option<String>>> = LazyLock::new(|| Mutex::new(None));
+/// Longest custom suffix we forward; longer values are truncated.
+pub const MAX_USER_AGENT_SUFFIX_LEN: usize = 128;
 pub const DEFAULT_ORIGINATOR: &str = "codex_cli_rs";
 pub const CODEX_INTERNAL_ORIGINATOR_OVERRIDE_ENV_VAR: &str = "CODEX_INTERNAL_ORIGINATOR_OVERRIDE";
 
@@ -70,7 +80,28 @@ pub fn originator() -> &'static Originator {
     ORIGINATOR.get_or_init(|| get_originator_value(None))
 }
 
+/// Trim `value`, replace anything outside printable ASCII with an underscore,
+/// and cap it at [`MAX_USER_AGENT_SUFFIX_LEN`] characters. Returns `None` when
+/// nothing is left.
+pub fn sanitize_user_agent_suffix(value: &str) -> Option<String> {
+    let sanitized: String = value
+        .trim()
+        .chars()
+        .map(|ch| if matches!(ch, ' '..='~') { ch } else { '_' })
+        .take(MAX_USER_AGENT_SUFFIX_LEN)
+        .collect();
+    let sanitized = sanitized.trim_end();
+    (!sanitized.is_empty()).then(|| sanitized.to_string())
+}
+
 pub fn get_codex_user_agent() -> String {
+    get_codex_user_agent_with_suffix(None)
+}
+
+/// [`get_codex_user_agent`] followed by `user_agent_suffix`, the config's
+/// `user_agent_suffix`. Unlike [`USER_AGENT_SUFFIX`] it is not wrapped in
+/// parentheses, and it only goes on the clients it is passed to.
+pub fn get_codex_user_agent_with_suffix(user_agent_suffix: Option<&str>) -> String {
     let build_version = env!("CARGO_PKG_VERSION");
     let os_info = os_info::get();
     let prefix = format!(
@@ -90,8 +121,11 @@ pub fn get_codex_user_agent() -> String {
         .map(str::trim)
         .filter(|value| !value.is_empty())
         .map_or_else(String::new, |value| format!(" ({value})"));
+    let custom_suffix = user_agent_suffix
+        .and_then(sanitize_user_agent_suffix)
+        .map_or_else(String::new, |value| format!(" {value}"));
 
-    let candidate = format!("{prefix}{suffix}");
+    let candidate = format!("{prefix}{suffix}{custom_suffix}");
     sanitize_user_agent(candidate, &prefix)
 }
 
@@ -134,21 +168,147 @@ pub fn create_client() -> CodexHttpClient {
 }
 
 pub fn build_reqwest_client() -> reqwest::Client {
+    client_builder(None)
+        .build()
+        .unwrap_or_else(|_| fallback_client())
+}
+
+/// Like [`build_reqwest_client`], but for requests to `provider`: when the
+/// provider sets `pinned_spki_sha256`, the client only completes handshakes
+/// with servers presenting one of the pinned keys. Unlike the unpinned
+/// client, failing to build one is an error rather than a silent fallback to
+/// a client without the pins. Its User-Agent ends with `user_agent_suffix`.
+pub fn build_reqwest_client_for_provider(
+    provider: &ModelProviderInfo,
+    user_agent_suffix: Option<&str>,
+) -> CoreResult<reqwest::Client> {
+    build_pinned_client(provider, PinnedClientKind::Requests, user_agent_suffix)
+}
+
+/// Like [`build_reqwest_client_for_provider`], for fetching `provider`'s model
+/// catalog. Only these clients ask for gzip or deflate responses, which pay
+/// off on large listings; every other client, streamed responses included,
+/// leaves compression off.
+pub fn build_reqwest_client_for_catalog(
+    provider: &ModelProviderInfo,
+    user_agent_suffix: Option<&str>,
+) -> CoreResult<reqwest::Client> {
+    build_pinned_client(provider, PinnedClientKind::Catalog, user_agent_suffix)
+}
+
+/// Like [`build_reqwest_client_for_provider`], for the reachability preflight
+/// before a model listing. Only connecting is bounded, by
+/// [`PREFLIGHT_TIMEOUT`]; once connected, the preflight waits for the
+/// response like any other request.
+pub(crate) fn build_reqwest_client_for_preflight(
+    provider: &ModelProviderInfo,
+    user_agent_suffix: Option<&str>,
+) -> CoreResult<reqwest::Client> {
+    build_pinned_client(provider, PinnedClientKind::Preflight, user_agent_suffix)
+}
+
+/// Which of the clients for a provider is built.
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+enum PinnedClientKind {
+    Requests,
+    Catalog,
+    Preflight,
+}
+
+/// Pinned clients already built. Setting one up loads the system certificate
+/// store, so each provider's is built once and then shared, as a
+/// `reqwest::Client` is a handle to one connection pool.
+static PINNED_CLIENTS: LazyLock<Mutex<HashMap<PinnedClientKey, reqwest::Client>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// What a pinned client is built from. The User-Agent is part of it because
+/// configs with different `user_agent_suffix` values share the cache.
+#[derive(PartialEq, Eq, Hash)]
+struct PinnedClientKey {
+    provider: String,
+    base_url: Option<String>,
+    pins: Vec<SpkiPin>,
+    kind: PinnedClientKind,
+    user_agent: String,
+}
+
+fn build_pinned_client(
+    provider: &ModelProviderInfo,
+    kind: PinnedClientKind,
+    user_agent_suffix: Option<&str>,
+) -> CoreResult<reqwest::Client> {
+    let pins = provider
+        .spki_pins()
+        .map_err(|err| CodexErr::Fatal(format!("model provider `{}` {err}", provider.name)))?;
+    let builder = match kind {
+        PinnedClientKind::Requests => client_builder(user_agent_suffix),
+        PinnedClientKind::Catalog => client_builder(user_agent_suffix).gzip(true).deflate(true),
+        PinnedClientKind::Preflight => {
+            client_builder(user_agent_suffix).connect_timeout(PREFLIGHT_TIMEOUT)
+        }
+    };
+    let Some(pins) = pins else {
+        return Ok(builder.build().unwrap_or_else(|_| fallback_client()));
+    };
+    let key = PinnedClientKey {
+        provider: provider.name.clone(),
+        base_url: provider.base_url.clone(),
+        pins: pins.clone(),
+        kind,
+        user_agent: get_codex_user_agent_with_suffix(user_agent_suffix),
+    };
+    let mut clients = PINNED_CLIENTS
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner);
+    if let Some(client) = clients.get(&key) {
+        return Ok(client.clone());
+    }
+    let tls = pinned_tls_config(pins).map_err(|err| {
+        CodexErr::Fatal(format!(
+            "cannot set up key pinning for model provider `{}`: {err}",
+            provider.name
+        ))
+    })?;
+    let client = builder.use_preconfigured_tls(tls).build().map_err(|err| {
+        CodexErr::Fatal(format!(
+            "cannot build a pinned client for model provider `{}`: {err}",
+            provider.name
+        ))
+    })?;
+    clients.insert(key, client.clone());
+    Ok(client)
+}
+
+/// A bare client for when [`client_builder`]'s cannot be built. It leaves
+/// compression off as well, where `reqwest::Client::new` would negotiate it.
+#[expect(clippy::expect_used)]
+fn fallback_client() -> reqwest::Client {
+    reqwest::Client::builder()
+        .no_gzip()
+        .no_deflate()
+        .build()
+        .expect("a client without configuration builds, as in reqwest::Client::new")
+}
+
+fn client_builder(user_agent_suffix: Option<&str>) -> reqwest::ClientBuilder {
     use reqwest::header::HeaderMap;
 
     let mut headers = HeaderMap::new();
     headers.insert("originator", originator().header_value.clone());
-    let ua = get_codex_user_agent();
+    let ua = get_codex_user_agent_with_suffix(user_agent_suffix);
 
     let mut builder = reqwest::Client::builder()
         // Set UA via dedicated helper to avoid header validation pitfalls
         .user_agent(ua)
-        .default_headers(headers);
+        .default_headers(headers)
+        // The catalog fetch turns these features on for the whole workspace;
+        // see `build_reqwest_client_for_catalog`.
+        .no_gzip()
+        .no_deflate();
     if is_sandboxed() {
         builder = builder.no_proxy();
     }
-
-    builder.build().unwrap_or_else(|_| reqwest::Client::new())
+    builder
 }
 
 fn is_sandboxed() -> bool {
@@ -159,6 +319,7 @@ fn is_sandboxed() -> bool {
 mod tests {
     use super::*;
     use core_test_support::skip_if_no_network;
+    use pretty_assertions::assert_eq;
 
     #[test]
     fn test_get_codex_user_agent() {
@@ -216,6 +377,56 @@ mod tests {
         assert_eq!(ua_header.to_str().unwrap(), expected_ua);
     }
 
+    #[tokio::test]
+    async fn only_catalog_clients_negotiate_compression() {
+        skip_if_no_network!();
+
+        use wiremock::Mock;
+        use wiremock::MockServer;
+        use wiremock::ResponseTemplate;
+        use wiremock::matchers::method;
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(200))
+            .mount(&server)
+            .await;
+        let provider = ModelProviderInfo::create_openai_provider();
+        let catalog = build_reqwest_client_for_catalog(&provider, None).expect("catalog client");
+        let streaming =
+            build_reqwest_client_for_provider(&provider, None).expect("provider client");
+
+        for client in [&catalog, &streaming] {
+            client
+                .get(server.uri())
+                .send()
+                .await
+                .expect("failed to send request");
+        }
+
+        let requests = server
+            .received_requests()
+            .await
+            .expect("failed to fetch received requests");
+        let accept_encoding: Vec<Option<&str>> = requests
+            .iter()
+            .map(|request| {
+                request
+                    .headers
+                    .get("accept-encoding")
+                    .and_then(|value| value.to_str().ok())
+            })
+            .collect();
+        let [catalog_encoding, streaming_encoding] = accept_encoding.as_slice() else {
+            panic!("expected two requests, got {accept_encoding:?}");
+        };
+        assert!(
+            catalog_encoding.is_some_and(|value| value.contains("gzip")),
+            "catalog request should negotiate compression, got {catalog_encoding:?}"
+        );
+        assert_eq!(*streaming_encoding, None);
+    }
+
     #[test]
     fn test_invalid_suffix_is_sanitized() {
         let prefix = "codex_cli_rs/0.0.0";
@@ -238,6 +449,19 @@ mod tests {
         );
     }
 
+    #[test]
+    fn custom_suffix_is_sanitized_and_capped() {
+        assert_eq!(
+            sanitize_user_agent_suffix("  acme-gateway/1.2 (tenant=\u{e9}t\u{e9})\n "),
+            Some("acme-gateway/1.2 (tenant=_t_)".to_string())
+        );
+        assert_eq!(sanitize_user_agent_suffix(" \t "), None);
+        assert_eq!(
+            sanitize_user_agent_suffix(&"x".repeat(MAX_USER_AGENT_SUFFIX_LEN + 10)),
+            Some("x".repeat(MAX_USER_AGENT_SUFFIX_LEN))
+        );
+    }
+
     #[test]
     #[cfg(target_os = "macos")]
     fn test_macos() {
@@ -8,6 +8,7 @@ use chrono::Datelike;
 use chrono::Local;
 use chrono::Utc;
 use codex_async_utils::CancelErr;
+use codex_client::SpkiPinMismatch;
 use codex_protocol::ConversationId;
 use codex_protocol::protocol::CodexErrorInfo;
 use codex_protocol::protocol::ErrorEvent;
@@ -103,6 +104,11 @@ pub enum CodexErr {
     #[error("{0}")]
     InvalidRequest(String),
 
+    /// The provider does not serve the requested model, typically because it
+    /// was removed from the provider's catalog.
+    #[error("{0}")]
+    ModelUnavailable(ModelUnavailableError),
+
     /// Invalid image.
     #[error("Image poisoning")]
     InvalidImageRequest(),
@@ -116,6 +122,11 @@ pub enum CodexErr {
     #[error("{0}")]
     ConnectionFailed(ConnectionFailedError),
 
+    /// The provider's server presented a key outside its `pinned_spki_sha256`
+    /// list. Not retried: the same server would present the same key.
+    #[error("{0}")]
+    SpkiPinMismatch(SpkiPinMismatch),
+
     #[error("Quota exceeded. Check your plan and billing details.")]
     QuotaExceeded,
 
@@ -127,6 +138,47 @@ pub enum CodexErr {
     #[error("We're currently experiencing high demand, which may cause temporary errors.")]
     InternalServerError,
 
+    /// A model catalog turned away a request sent without credentials.
+    #[error(
+        "catalog requires login: {0} rejected an anonymous request; sign in or configure credentials for it"
+    )]
+    CatalogRequiresLogin(String),
+
+    /// A provider lacks a setting a request to it needs, such as the
+    /// `base_url` its model catalog is listed from.
+    #[error("model provider `{provider}` has no `{field}`; set it to list the provider's models")]
+    MissingConfiguration {
+        provider: String,
+        field: &'static str,
+    },
+
+    /// A provider's `token_command` did not print a bearer token. `message`
+    /// quotes the command's stderr, truncated and with secrets redacted.
+    #[error("token command `{program}` failed: {message}")]
+    TokenCommandFailed { program: String, message: String },
+
+    /// A model catalog answered with an empty list, which usually means the
+    /// provider's catalog endpoint is misconfigured.
+    #[error(
+        "model catalog at {0} listed no models; check the provider's `base_url` and `models_path`, or set `allow_empty_catalog = true` if it is meant to list none"
+    )]
+    EmptyCatalog(String),
+
+    /// A model catalog answered with something other than JSON, typically a
+    /// captive portal or SSO proxy serving its login page in place of the API.
+    #[error(
+        "model catalog response is not JSON: got {}; a captive portal or SSO proxy may be intercepting requests, so sign in through a browser or check your proxy settings",
+        codex_api::describe_content(.content_type.as_deref(), .snippet)
+    )]
+    UnexpectedContent {
+        content_type: Option<String>,
+        snippet: String,
+    },
+
+    /// A bounded response (such as a model catalog listing) exceeded its size limit.
+    #[error("response body exceeds the {limit}-byte limit")]
+    ResponseTooLarge { limit: usize },
+
     /// Retry limit exceeded.
     #[error("{0}")]
     RetryLimit(RetryLimitReachedError),
@@ -236,6 +288,34 @@ pub enum RefreshTokenFailedReason {
     Other,
 }
 
+#[derive(Debug)]
+pub struct ModelUnavailableError {
+    /// Slug sent to the provider.
+    pub model: String,
+    /// Id of the catalog preset the model was selected from, once the
+    /// session has resolved it.
+    pub preset_id: Option<String>,
+    /// The provider's explanation.
+    pub message: String,
+    /// The provider serves the model, but not to the signed-in account.
+    pub for_account: bool,
+}
+
+impl std::fmt::Display for ModelUnavailableError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let from = if self.for_account {
+            "to this account"
+        } else {
+            "from the provider"
+        };
+        write!(
+            f,
+            "model `{}` is not available {from}: {}. Choose another model to continue.",
+            self.model, self.message
+        )
+    }
+}
+
 #[derive(Debug)]
 pub struct UnexpectedResponseError {
     pub status: StatusCode,
@@ -445,13 +525,17 @@ impl CodexErr {
             CodexErr::RetryLimit(_) => CodexErrorInfo::ResponseTooManyFailedAttempts {
                 http_status_code: self.http_status_code_value(),
             },
-            CodexErr::ConnectionFailed(_) => CodexErrorInfo::HttpConnectionFailed {
-                http_status_code: self.http_status_code_value(),
-            },
+            CodexErr::ConnectionFailed(_) | CodexErr::UnexpectedContent { .. } => {
+                CodexErrorInfo::HttpConnectionFailed {
+                    http_status_code: self.http_status_code_value(),
+                }
+            }
             CodexErr::ResponseStreamFailed(_) => CodexErrorInfo::ResponseStreamConnectionFailed {
                 http_status_code: self.http_status_code_value(),
             },
-            CodexErr::RefreshTokenFailed(_) => CodexErrorInfo::Unauthorized,
+            CodexErr::RefreshTokenFailed(_)
+            | CodexErr::CatalogRequiresLogin(_)
+            | CodexErr::TokenCommandFailed { .. } => CodexErrorInfo::Unauthorized,
             CodexErr::SessionConfiguredNotFirstEvent
             | CodexErr::InternalServerError
             | CodexErr::InternalAgentDied => CodexErrorInfo::InternalServerError,
@@ -459,6 +543,9 @@ impl CodexErr {
                 CodexErrorInfo::BadRequest
             }
             CodexErr::Sandbox(_) => CodexErrorInfo::SandboxError,
+            CodexErr::ModelUnavailable(err) => CodexErrorInfo::ModelUnavailable {
+                preset_id: err.preset_id.clone().unwrap_or_else(|| err.model.clone()),
+            },
             _ => CodexErrorInfo::Other,
         }
     }
@@ -485,6 +572,13 @@ impl CodexErr {
         };
         http_status_code.as_ref().map(StatusCode::as_u16)
     }
+
+    /// Whether the provider turned the request away for lack of capacity,
+    /// after its own retries. Only these errors move a turn to a fallback
+    /// model.
+    pub fn is_capacity_error(&self) -> bool {
+        self.http_status_code_value() == Some(StatusCode::SERVICE_UNAVAILABLE.as_u16())
+    }
 }
 
 pub fn get_error_message_ui(e: &CodexErr) -> String {
@@ -585,6 +679,28 @@ mod tests {
         );
     }
 
+    #[test]
+    fn only_service_unavailable_is_a_capacity_error() {
+        let status = |status| {
+            CodexErr::UnexpectedStatus(UnexpectedResponseError {
+                status,
+                body: String::new(),
+                request_id: None,
+            })
+        };
+        assert!(status(StatusCode::SERVICE_UNAVAILABLE).is_capacity_error());
+        assert!(
+            CodexErr::RetryLimit(RetryLimitReachedError {
+                status: StatusCode::SERVICE_UNAVAILABLE,
+                request_id: None,
+            })
+            .is_capacity_error()
+        );
+        assert!(!status(StatusCode::BAD_GATEWAY).is_capacity_error());
+        assert!(!CodexErr::InternalServerError.is_capacity_error());
+        assert!(!CodexErr::Stream("disconnected".to_string(), None).is_capacity_error());
+    }
+
     #[test]
     fn sandbox_denied_uses_aggregated_output_when_stderr_empty() {
         let output = ExecToolCallOutput {
@@ -127,6 +127,9 @@ pub struct LegacyFeatureUsage {
 #[derive(Debug, Clone, Default, PartialEq)]
 pub struct Features {
     enabled: BTreeSet<Feature>,
+    /// Features turned on or off after the defaults, by the config, an
+    /// override, or the session.
+    configured: BTreeSet<Feature>,
     legacy_usages: BTreeSet<LegacyFeatureUsage>,
 }
 
@@ -158,6 +161,7 @@ impl Features {
         }
         Self {
             enabled: set,
+            configured: BTreeSet::new(),
             legacy_usages: BTreeSet::new(),
         }
     }
@@ -168,14 +172,22 @@ impl Features {
 
     pub fn enable(&mut self, f: Feature) -> &mut Self {
         self.enabled.insert(f);
+        self.configured.insert(f);
         self
     }
 
     pub fn disable(&mut self, f: Feature) -> &mut Self {
         self.enabled.remove(&f);
+        self.configured.insert(f);
         self
     }
 
+    /// Whether `f` was set either way rather than left at its default, so
+    /// that a model's preset may decide it instead.
+    pub fn is_configured(&self, f: Feature) -> bool {
+        self.configured.contains(&f)
+    }
+
     pub fn record_legacy_usage_force(&mut self, alias: &str, feature: Feature) {
         self.legacy_usages.insert(LegacyFeatureUsage {
             alias: alias.to_string(),
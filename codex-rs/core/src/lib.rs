@@ -20,6 +20,7 @@ mod command_safety;
 pub mod config;
 pub mod config_loader;
 mod context_manager;
+mod cost_guard;
 pub mod custom_prompts;
 pub mod env;
 mod environment_context;
@@ -43,9 +44,11 @@ mod model_provider_info;
 pub mod parse_command;
 pub mod path_utils;
 pub mod powershell;
+mod request_limits;
 pub mod sandboxing;
 mod stream_events_utils;
 mod text_encoding;
+mod token_command;
 pub mod token_data;
 mod truncate;
 mod unified_exec;
@@ -54,6 +57,7 @@ pub use model_provider_info::CHAT_WIRE_API_DEPRECATION_SUMMARY;
 pub use model_provider_info::DEFAULT_LMSTUDIO_PORT;
 pub use model_provider_info::DEFAULT_OLLAMA_PORT;
 pub use model_provider_info::LMSTUDIO_OSS_PROVIDER_ID;
+pub use model_provider_info::ModelCatalog;
 pub use model_provider_info::ModelProviderInfo;
 pub use model_provider_info::OLLAMA_OSS_PROVIDER_ID;
 pub use model_provider_info::WireApi;
@@ -78,6 +82,7 @@ pub mod shell;
 pub mod shell_snapshot;
 pub mod skills;
 pub mod spawn;
+pub mod status_file;
 pub mod terminal;
 mod tools;
 pub mod turn_diff_tracker;
@@ -9,6 +9,9 @@ use codex_api::Provider as ApiProvider;
 use codex_api::WireApi as ApiWireApi;
 use codex_api::provider::RetryConfig as ApiRetryConfig;
 use codex_app_server_protocol::AuthMode;
+use codex_client::SpkiPin;
+pub use codex_protocol::config_types::WireApi;
+use codex_protocol::openai_models::ModelPricing;
 use http::HeaderMap;
 use http::header::HeaderName;
 use http::header::HeaderValue;
@@ -17,11 +20,15 @@ use serde::Serialize;
 use std::collections::HashMap;
 use std::env::VarError;
 use std::time::Duration;
+use url::Host;
+use url::Url;
 
+use crate::error::CodexErr;
 use crate::error::EnvVarError;
 const DEFAULT_STREAM_IDLE_TIMEOUT_MS: u64 = 300_000;
 const DEFAULT_STREAM_MAX_RETRIES: u64 = 5;
 const DEFAULT_REQUEST_MAX_RETRIES: u64 = 4;
+const DEFAULT_TOKEN_COMMAND_TTL_SECS: u64 = 300;
 /// Hard cap for user-configured `stream_max_retries`.
 const MAX_STREAM_MAX_RETRIES: u64 = 100;
 /// Hard cap for user-configured `request_max_retries`.
@@ -30,21 +37,22 @@ pub const CHAT_WIRE_API_DEPRECATION_SUMMARY: &str = r#"Support for the "chat" wi
 
 const OPENAI_PROVIDER_NAME: &str = "OpenAI";
 
-/// Wire protocol that the provider speaks. Most third-party services only
-/// implement the classic OpenAI Chat Completions JSON schema, whereas OpenAI
-/// itself (and a handful of others) additionally expose the more modern
-/// *Responses* API. The two protocols use different request/response shapes
-/// and *cannot* be auto-detected at runtime, therefore each provider entry
-/// must declare which one it expects.
-#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+/// Endpoint shape a provider exposes for enumerating the models it serves.
+/// Providers without a catalog only surface the built-in presets.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
-pub enum WireApi {
-    /// The Responses API exposed by OpenAI at `/v1/responses`.
-    Responses,
+pub enum ModelCatalog {
+    /// OpenAI-compatible `GET /models` returning `{"data": [{"id": ...}]}`, as
+    /// served by LM Studio, vLLM, and most local inference servers.
+    Openai,
 
-    /// Regular Chat Completions compatible with `/v1/chat/completions`.
-    #[default]
-    Chat,
+    /// Azure OpenAI `GET /openai/deployments`, authenticated with the
+    /// provider key in an `api-key` header.
+    Azure,
+
+    /// No catalog endpoint is ever queried: the provider's `models` list is
+    /// its whole catalog. For gateways whose model list is fixed by policy.
+    None,
 }
 
 /// Serializable representation of a provider definition.
@@ -99,6 +107,132 @@ pub struct ModelProviderInfo {
     /// and API key (if needed) comes from the "env_key" environment variable.
     #[serde(default)]
     pub requires_openai_auth: bool,
+
+    /// When set, Codex lists the models served by this provider in the model
+    /// picker and `codex models list` by querying the given catalog endpoint.
+    #[serde(default)]
+    pub model_catalog: Option<ModelCatalog>,
+
+    /// Skip the quick `HEAD` reachability probe sent before listing models.
+    /// Set this for gateways that hang on `HEAD` requests.
+    #[serde(default)]
+    pub skip_models_preflight: bool,
+
+    /// The model catalog can be read without signing in. Catalog requests to
+    /// this provider then carry no credentials, even when some are available.
+    #[serde(default)]
+    pub allow_anonymous_catalog: bool,
+
+    /// The model catalog may legitimately list no models. Otherwise an empty
+    /// listing is treated as a failed refresh, and the models listed before
+    /// are kept.
+    #[serde(default)]
+    pub allow_empty_catalog: bool,
+
+    /// Extra system instructions appended to the base instructions for every
+    /// model served by this provider.
+    pub instructions_addendum: Option<String>,
+
+    /// Wire APIs this provider can serve. Defaults to just `wire_api`; list
+    /// both for gateways (e.g. LiteLLM) whose models differ in which API they
+    /// speak, so a model preset can pick the one it needs.
+    pub supported_wire_apis: Option<Vec<WireApi>>,
+
+    /// Per-model token prices keyed by model slug, copied onto the presets
+    /// this provider serves so the TUI can estimate session cost.
+    pub model_pricing: Option<HashMap<String, ModelPricing>>,
+
+    /// Largest decoded model catalog response accepted from this provider, in
+    /// bytes. Defaults to 8 MiB; larger listings fail instead of being buffered.
+    pub models_max_response_bytes: Option<usize>,
+
+    /// Model slugs this provider serves when `model_catalog = "none"`. They
+    /// are listed as-is, without contacting the provider.
+    pub models: Option<Vec<String>>,
+
+    /// Extra HTTP headers sent with every request for a given model, keyed
+    /// by model slug, e.g. the `x-model-group` routing hint a LiteLLM gateway
+    /// expects. Values may reference environment variables as `${VAR}`.
+    /// Credential headers such as `Authorization` are rejected.
+    pub model_request_headers: Option<HashMap<String, HashMap<String, String>>>,
+
+    /// Path of the catalog endpoint relative to `base_url`, replacing the
+    /// default `models` (`deployments` for Azure). A path starting with `/`
+    /// is taken from the root of `base_url`'s host, and a full URL on the
+    /// same host is used as-is, for gateways that mount the catalog under a
+    /// different prefix than the API.
+    pub models_path: Option<String>,
+
+    /// Allow listing models from a plain `http` base URL on a remote host.
+    /// Loopback addresses are always allowed.
+    #[serde(default)]
+    pub allow_insecure_catalog: bool,
+
+    /// Base64 SHA-256 hashes of the `SubjectPublicKeyInfo` the provider's
+    /// servers may present. When set, catalog and model requests to this
+    /// provider are refused unless the certificate, besides chaining to the
+    /// system certificate store, carries one of these keys.
+    pub pinned_spki_sha256: Option<Vec<String>>,
+
+    /// Program and arguments of a command that prints the bearer token for
+    /// this provider, for gateways that hand out short-lived session tokens.
+    /// Used when `env_key` is not set. The token is reused for
+    /// `token_command_ttl_secs` and minted again when the provider answers
+    /// `401 Unauthorized`.
+    pub token_command: Option<Vec<String>>,
+
+    /// How long a token printed by `token_command` is reused, in seconds.
+    /// Defaults to 300.
+    pub token_command_ttl_secs: Option<u64>,
+}
+
+/// One provider's model catalog settings. Built only by
+/// [`crate::config::Config::catalog_providers`], so the id, endpoint, and
+/// limits used for a listing always come from the same provider entry.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProviderCatalogConfig {
+    pub provider_id: String,
+    pub catalog: ModelCatalog,
+    /// Catalog path override, see [`ModelProviderInfo::models_path`].
+    pub path: Option<String>,
+    pub provider: ModelProviderInfo,
+    /// The config's `user_agent_suffix`, sent on the listing's User-Agent.
+    pub user_agent_suffix: Option<String>,
+}
+
+impl ProviderCatalogConfig {
+    /// The `base_url` the catalog is listed from. Unlike other requests, a
+    /// listing never falls back to the default OpenAI endpoint, which would
+    /// send this provider's credentials to another server.
+    pub(crate) fn base_url(&self) -> crate::error::Result<&str> {
+        match self.provider.base_url.as_deref() {
+            Some(base_url) if !base_url.trim().is_empty() => Ok(base_url),
+            _ => Err(CodexErr::MissingConfiguration {
+                provider: self.provider_id.clone(),
+                field: "base_url",
+            }),
+        }
+    }
+
+    /// Fails when the listing would authenticate with a blank
+    /// `experimental_bearer_token`, instead of sending an empty `Bearer`
+    /// header. An anonymous catalog, or one whose token comes from `env_key`
+    /// or `token_command`, never reads that setting.
+    pub(crate) fn require_bearer_token(&self) -> crate::error::Result<()> {
+        let provider = &self.provider;
+        let reads_bearer_token = !provider.allow_anonymous_catalog
+            && provider.env_key.is_none()
+            && provider.token_command.is_none();
+        match provider.experimental_bearer_token.as_deref() {
+            Some(token) if reads_bearer_token && token.trim().is_empty() => {
+                Err(CodexErr::MissingConfiguration {
+                    provider: self.provider_id.clone(),
+                    field: "experimental_bearer_token",
+                })
+            }
+            _ => Ok(()),
+        }
+    }
 }
 
 impl ModelProviderInfo {
@@ -164,6 +298,163 @@ impl ModelProviderInfo {
         })
     }
 
+    /// Checks the catalog endpoint: `models_path` must be a relative path or
+    /// a URL on the `base_url` host, and for a provider with a
+    /// `model_catalog` the base URL must parse and use `https`, unless it
+    /// points at a loopback host or `allow_insecure_catalog` is set.
+    pub fn validate_catalog_endpoint(&self) -> Result<(), String> {
+        self.validate_models_path()?;
+        if matches!(self.model_catalog, None | Some(ModelCatalog::None)) {
+            return Ok(());
+        }
+        if let Some(base_url) = &self.base_url {
+            let url = Url::parse(base_url)
+                .map_err(|err| format!("`base_url` `{base_url}` is not a valid URL: {err}"))?;
+            let loopback = match url.host() {
+                Some(Host::Domain(domain)) => domain.eq_ignore_ascii_case("localhost"),
+                Some(Host::Ipv4(ip)) => ip.is_loopback(),
+                Some(Host::Ipv6(ip)) => ip.is_loopback(),
+                None => false,
+            };
+            match url.scheme() {
+                "https" => {}
+                "http" if loopback || self.allow_insecure_catalog => {}
+                "http" => {
+                    return Err(format!(
+                        "lists models over plain http from `{base_url}`; use https or set `allow_insecure_catalog = true`"
+                    ));
+                }
+                scheme => {
+                    return Err(format!(
+                        "`base_url` `{base_url}` uses unsupported scheme `{scheme}`"
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// The parsed `pinned_spki_sha256` entries, or `None` when the provider
+    /// does not pin its keys. Every entry must be a base64 SHA-256 hash, and
+    /// an empty list is rejected rather than read as "pin nothing".
+    pub fn spki_pins(&self) -> Result<Option<Vec<SpkiPin>>, String> {
+        let Some(pins) = &self.pinned_spki_sha256 else {
+            return Ok(None);
+        };
+        if pins.is_empty() {
+            return Err("`pinned_spki_sha256` must list at least one key hash".to_string());
+        }
+        pins.iter()
+            .map(|pin| {
+                pin.parse::<SpkiPin>()
+                    .map_err(|err| format!("`pinned_spki_sha256` entry {err}"))
+            })
+            .collect::<Result<Vec<_>, _>>()
+            .map(Some)
+    }
+
+    /// An absolute `models_path` is accepted only when it has the same
+    /// scheme, host, and port as `base_url`, so an override can move the
+    /// catalog under a prefix but cannot send credentials to another server.
+    fn validate_models_path(&self) -> Result<(), String> {
+        let Some(path) = &self.models_path else {
+            return Ok(());
+        };
+        if path.trim().is_empty() {
+            return Err("`models_path` must not be empty".to_string());
+        }
+        let url = if path.starts_with("//") {
+            None
+        } else {
+            match Url::parse(path) {
+                Ok(url) => Some(url),
+                Err(_) => return Ok(()),
+            }
+        };
+        let base_url = self
+            .base_url
+            .as_deref()
+            .and_then(|base_url| Url::parse(base_url).ok());
+        match (url, base_url) {
+            (Some(url), Some(base_url))
+                if url.scheme() == base_url.scheme()
+                    && url.host() == base_url.host()
+                    && url.port_or_known_default() == base_url.port_or_known_default() =>
+            {
+                Ok(())
+            }
+            _ => Err(format!(
+                "`models_path` `{path}` points at a different host than `base_url`"
+            )),
+        }
+    }
+
+    /// Whether the model list is fixed by config rather than fetched.
+    pub fn has_fixed_models(&self) -> bool {
+        self.model_catalog == Some(ModelCatalog::None)
+    }
+
+    /// Configured token prices for `model`, if any.
+    pub fn pricing_for(&self, model: &str) -> Option<ModelPricing> {
+        self.model_pricing
+            .as_ref()
+            .and_then(|pricing| pricing.get(model))
+            .copied()
+    }
+
+    /// Configured request headers for `model`, if any.
+    pub fn request_headers_for(&self, model: &str) -> Option<&HashMap<String, String>> {
+        self.model_request_headers
+            .as_ref()
+            .and_then(|headers| headers.get(model))
+    }
+
+    /// Whether requests to this provider can use `wire_api`.
+    pub fn supports_wire_api(&self, wire_api: WireApi) -> bool {
+        match &self.supported_wire_apis {
+            Some(supported) => supported.contains(&wire_api),
+            None => self.wire_api == wire_api,
+        }
+    }
+
+    /// Fails when `model` asks for a wire API this provider cannot serve.
+    pub fn check_model_wire_api(
+        &self,
+        model: &str,
+        wire_api: Option<WireApi>,
+    ) -> crate::error::Result<()> {
+        let Some(wire_api) = wire_api else {
+            return Ok(());
+        };
+        if self.supports_wire_api(wire_api) {
+            return Ok(());
+        }
+        let supported = match &self.supported_wire_apis {
+            Some(supported) => supported
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+                .join(", "),
+            None => self.wire_api.to_string(),
+        };
+        Err(CodexErr::UnsupportedOperation(format!(
+            "model `{model}` requires the `{wire_api}` wire API, but provider `{}` only supports `{supported}`. Add \"{wire_api}\" to `supported_wire_apis` for this provider or pick a different model.",
+            self.name
+        )))
+    }
+
+    /// Copy of this provider that routes requests over the wire API the
+    /// active model asks for, when the provider supports it.
+    pub(crate) fn with_model_wire_api(&self, wire_api: Option<WireApi>) -> Self {
+        let mut provider = self.clone();
+        if let Some(wire_api) = wire_api
+            && self.supports_wire_api(wire_api)
+        {
+            provider.wire_api = wire_api;
+        }
+        provider
+    }
+
     /// If `env_key` is Some, returns the API key for this provider if present
     /// (and non-empty) in the environment. If `env_key` is required but
     /// cannot be found, returns an error.
@@ -204,12 +495,36 @@ impl ModelProviderInfo {
             .min(MAX_STREAM_MAX_RETRIES)
     }
 
+    /// Forget every credential this provider would send, for a gateway on
+    /// another origin than the one they were configured for: the API key,
+    /// bearer token and token command, the configured headers, which may
+    /// carry one too, and the OpenAI login. Its catalog is then listed
+    /// anonymously.
+    pub(crate) fn withhold_credentials(&mut self) {
+        self.env_key = None;
+        self.experimental_bearer_token = None;
+        self.token_command = None;
+        self.http_headers = None;
+        self.env_http_headers = None;
+        self.requires_openai_auth = false;
+        self.allow_anonymous_catalog = true;
+    }
+
     /// Effective idle timeout for streaming responses.
     pub fn stream_idle_timeout(&self) -> Duration {
         self.stream_idle_timeout_ms
             .map(Duration::from_millis)
             .unwrap_or(Duration::from_millis(DEFAULT_STREAM_IDLE_TIMEOUT_MS))
     }
+
+    /// How long a token printed by `token_command` is reused.
+    pub fn token_command_ttl(&self) -> Duration {
+        Duration::from_secs(
+            self.token_command_ttl_secs
+                .unwrap_or(DEFAULT_TOKEN_COMMAND_TTL_SECS),
+        )
+    }
+
     pub fn create_openai_provider() -> ModelProviderInfo {
         ModelProviderInfo {
             name: OPENAI_PROVIDER_NAME.into(),
@@ -247,6 +562,21 @@ impl ModelProviderInfo {
             stream_max_retries: None,
             stream_idle_timeout_ms: None,
             requires_openai_auth: true,
+            model_catalog: None,
+            skip_models_preflight: false,
+            allow_anonymous_catalog: false,
+            allow_empty_catalog: false,
+            instructions_addendum: None,
+            supported_wire_apis: None,
+            model_pricing: None,
+            models_max_response_bytes: None,
+            models: None,
+            model_request_headers: None,
+            models_path: None,
+            allow_insecure_catalog: false,
+            pinned_spki_sha256: None,
+            token_command: None,
+            token_command_ttl_secs: None,
         }
     }
 
@@ -305,6 +635,46 @@ pub fn create_oss_provider(default_provider_port: u16, wire_api: WireApi) -> Mod
     create_oss_provider_with_base_url(&codex_oss_base_url, wire_api)
 }
 
+/// A `base_url` as it is used for requests.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct NormalizedBaseUrl {
+    pub(crate) url: String,
+    /// The configured URL had no scheme, so `https://` was assumed.
+    pub(crate) assumed_https: bool,
+}
+
+/// Clean up a configured `base_url` so that endpoint paths can be appended
+/// to it: surrounding whitespace, repeated slashes, and trailing slashes are
+/// dropped, and `https://` is assumed when no scheme is given. Query strings
+/// and fragments would end up in the middle of every request URL, so they
+/// are rejected with the URL to use instead.
+pub(crate) fn normalize_base_url(raw: &str) -> Result<NormalizedBaseUrl, String> {
+    let trimmed = raw.trim();
+    let (scheme, rest, assumed_https) = match trimmed.split_once("://") {
+        Some((scheme, rest)) => (scheme, rest, false),
+        None => ("https", trimmed, true),
+    };
+    let (path, suffix) = rest.split_at(rest.find(['?', '#']).unwrap_or(rest.len()));
+    let mut collapsed = String::with_capacity(path.len());
+    for ch in path.chars() {
+        if ch != '/' || !collapsed.ends_with('/') {
+            collapsed.push(ch);
+        }
+    }
+    let url = format!("{scheme}://{}", collapsed.trim_matches('/'));
+    if !suffix.is_empty() {
+        return Err(format!(
+            "`base_url` `{raw}` must not include a query string or fragment; use `{url}` and set query parameters in `query_params`"
+        ));
+    }
+    if let Err(err) = Url::parse(&url) {
+        // Report what is wrong with the URL as written, when that fails too.
+        let err = Url::parse(trimmed).err().unwrap_or(err);
+        return Err(format!("`base_url` `{raw}` is not a valid URL: {err}"));
+    }
+    Ok(NormalizedBaseUrl { url, assumed_https })
+}
+
 pub fn create_oss_provider_with_base_url(base_url: &str, wire_api: WireApi) -> ModelProviderInfo {
     ModelProviderInfo {
         name: "gpt-oss".into(),
@@ -320,6 +690,21 @@ pub fn create_oss_provider_with_base_url(base_url: &str, wire_api: WireApi) -> M
         stream_max_retries: None,
         stream_idle_timeout_ms: None,
         requires_openai_auth: false,
+        model_catalog: None,
+        skip_models_preflight: false,
+        allow_anonymous_catalog: false,
+        allow_empty_catalog: false,
+        instructions_addendum: None,
+        supported_wire_apis: None,
+        model_pricing: None,
+        models_max_response_bytes: None,
+        models: None,
+        model_request_headers: None,
+        models_path: None,
+        allow_insecure_catalog: false,
+        pinned_spki_sha256: None,
+        token_command: None,
+        token_command_ttl_secs: None,
     }
 }
 
@@ -348,6 +733,21 @@ base_url = "http://localhost:11434/v1"
             stream_max_retries: None,
             stream_idle_timeout_ms: None,
             requires_openai_auth: false,
+            model_catalog: None,
+            skip_models_preflight: false,
+            allow_anonymous_catalog: false,
+            allow_empty_catalog: false,
+            instructions_addendum: None,
+            supported_wire_apis: None,
+            model_pricing: None,
+            models_max_response_bytes: None,
+            models: None,
+            model_request_headers: None,
+            models_path: None,
+            allow_insecure_catalog: false,
+            pinned_spki_sha256: None,
+            token_command: None,
+            token_command_ttl_secs: None,
         };
 
         let provider: ModelProviderInfo = toml::from_str(azure_provider_toml).unwrap();
@@ -378,6 +778,21 @@ query_params = { api-version = "2025-04-01-preview" }
             stream_max_retries: None,
             stream_idle_timeout_ms: None,
             requires_openai_auth: false,
+            model_catalog: None,
+            skip_models_preflight: false,
+            allow_anonymous_catalog: false,
+            allow_empty_catalog: false,
+            instructions_addendum: None,
+            supported_wire_apis: None,
+            model_pricing: None,
+            models_max_response_bytes: None,
+            models: None,
+            model_request_headers: None,
+            models_path: None,
+            allow_insecure_catalog: false,
+            pinned_spki_sha256: None,
+            token_command: None,
+            token_command_ttl_secs: None,
         };
 
         let provider: ModelProviderInfo = toml::from_str(azure_provider_toml).unwrap();
@@ -411,6 +826,21 @@ env_http_headers = { "X-Example-Env-Header" = "EXAMPLE_ENV_VAR" }
             stream_max_retries: None,
             stream_idle_timeout_ms: None,
             requires_openai_auth: false,
+            model_catalog: None,
+            skip_models_preflight: false,
+            allow_anonymous_catalog: false,
+            allow_empty_catalog: false,
+            instructions_addendum: None,
+            supported_wire_apis: None,
+            model_pricing: None,
+            models_max_response_bytes: None,
+            models: None,
+            model_request_headers: None,
+            models_path: None,
+            allow_insecure_catalog: false,
+            pinned_spki_sha256: None,
+            token_command: None,
+            token_command_ttl_secs: None,
         };
 
         let provider: ModelProviderInfo = toml::from_str(azure_provider_toml).unwrap();
@@ -442,6 +872,21 @@ env_http_headers = { "X-Example-Env-Header" = "EXAMPLE_ENV_VAR" }
                 stream_max_retries: None,
                 stream_idle_timeout_ms: None,
                 requires_openai_auth: false,
+                model_catalog: None,
+                skip_models_preflight: false,
+                allow_anonymous_catalog: false,
+                allow_empty_catalog: false,
+                instructions_addendum: None,
+                supported_wire_apis: None,
+                model_pricing: None,
+                models_max_response_bytes: None,
+                models: None,
+                model_request_headers: None,
+                models_path: None,
+                allow_insecure_catalog: false,
+                pinned_spki_sha256: None,
+                token_command: None,
+                token_command_ttl_secs: None,
             };
             let api = provider.to_api_provider(None).expect("api provider");
             assert!(
@@ -464,6 +909,21 @@ env_http_headers = { "X-Example-Env-Header" = "EXAMPLE_ENV_VAR" }
             stream_max_retries: None,
             stream_idle_timeout_ms: None,
             requires_openai_auth: false,
+            model_catalog: None,
+            skip_models_preflight: false,
+            allow_anonymous_catalog: false,
+            allow_empty_catalog: false,
+            instructions_addendum: None,
+            supported_wire_apis: None,
+            model_pricing: None,
+            models_max_response_bytes: None,
+            models: None,
+            model_request_headers: None,
+            models_path: None,
+            allow_insecure_catalog: false,
+            pinned_spki_sha256: None,
+            token_command: None,
+            token_command_ttl_secs: None,
         };
         let named_api = named_provider.to_api_provider(None).expect("api provider");
         assert!(named_api.is_azure_responses_endpoint());
@@ -488,6 +948,21 @@ env_http_headers = { "X-Example-Env-Header" = "EXAMPLE_ENV_VAR" }
                 stream_max_retries: None,
                 stream_idle_timeout_ms: None,
                 requires_openai_auth: false,
+                model_catalog: None,
+                skip_models_preflight: false,
+                allow_anonymous_catalog: false,
+                allow_empty_catalog: false,
+                instructions_addendum: None,
+                supported_wire_apis: None,
+                model_pricing: None,
+                models_max_response_bytes: None,
+                models: None,
+                model_request_headers: None,
+                models_path: None,
+                allow_insecure_catalog: false,
+                pinned_spki_sha256: None,
+                token_command: None,
+                token_command_ttl_secs: None,
             };
             let api = provider.to_api_provider(None).expect("api provider");
             assert!(
@@ -496,4 +971,107 @@ env_http_headers = { "X-Example-Env-Header" = "EXAMPLE_ENV_VAR" }
             );
         }
     }
+
+    #[test]
+    fn model_wire_api_routes_only_over_supported_apis() {
+        let mut provider = ModelProviderInfo::create_openai_provider();
+        provider.wire_api = WireApi::Chat;
+
+        assert!(provider.check_model_wire_api("gpt-5.1", None).is_ok());
+        assert!(
+            provider
+                .check_model_wire_api("gpt-5.1", Some(WireApi::Chat))
+                .is_ok()
+        );
+        let err = provider
+            .check_model_wire_api("gpt-5.1", Some(WireApi::Responses))
+            .expect_err("chat-only provider cannot serve responses");
+        assert_eq!(
+            err.to_string(),
+            "unsupported operation: model `gpt-5.1` requires the `responses` wire API, but provider `OpenAI` only supports `chat`. Add \"responses\" to `supported_wire_apis` for this provider or pick a different model."
+        );
+        assert_eq!(
+            provider
+                .with_model_wire_api(Some(WireApi::Responses))
+                .wire_api,
+            WireApi::Chat
+        );
+
+        provider.supported_wire_apis = Some(vec![WireApi::Chat, WireApi::Responses]);
+        assert!(
+            provider
+                .check_model_wire_api("gpt-5.1", Some(WireApi::Responses))
+                .is_ok()
+        );
+        assert_eq!(
+            provider
+                .with_model_wire_api(Some(WireApi::Responses))
+                .wire_api,
+            WireApi::Responses
+        );
+        assert_eq!(provider.with_model_wire_api(None).wire_api, WireApi::Chat);
+    }
+
+    #[test]
+    fn base_urls_are_normalized() {
+        let cases = [
+            (
+                "https://api.example.com/v1",
+                "https://api.example.com/v1",
+                false,
+            ),
+            ("https://gw.example.com//", "https://gw.example.com", false),
+            (
+                "https://gw.example.com//litellm///v1/",
+                "https://gw.example.com/litellm/v1",
+                false,
+            ),
+            (
+                "  http://localhost:11434/v1  ",
+                "http://localhost:11434/v1",
+                false,
+            ),
+            (
+                "gw.example.com/litellm/",
+                "https://gw.example.com/litellm",
+                true,
+            ),
+            ("localhost:8080", "https://localhost:8080", true),
+        ];
+        for (raw, url, assumed_https) in cases {
+            assert_eq!(
+                normalize_base_url(raw),
+                Ok(NormalizedBaseUrl {
+                    url: url.to_string(),
+                    assumed_https,
+                }),
+                "{raw}"
+            );
+        }
+    }
+
+    #[test]
+    fn base_urls_with_a_query_or_fragment_are_rejected() {
+        let cases = [
+            (
+                "https://gw.example.com/v1?api-version=2024-10-01",
+                "`base_url` `https://gw.example.com/v1?api-version=2024-10-01` must not include a query string or fragment; use `https://gw.example.com/v1` and set query parameters in `query_params`",
+            ),
+            (
+                "gw.example.com//v1/#models",
+                "`base_url` `gw.example.com//v1/#models` must not include a query string or fragment; use `https://gw.example.com/v1` and set query parameters in `query_params`",
+            ),
+            (
+                "not a url",
+                "`base_url` `not a url` is not a valid URL: relative URL without a base",
+            ),
+            (
+                "https://",
+                "`base_url` `https://` is not a valid URL: empty host",
+            ),
+        ];
+        for (raw, expected) in cases {
+            assert_eq!(normalize_base_url(raw), Err(expected.to_string()), "{raw}");
+        }
+    }
 }
@@ -0,0 +1,197 @@
+//! Models the provider has refused to the signed-in account.
+//!
+//! Some plans do not include every model the catalog lists, and the provider
+//! only says so by rejecting requests. Each refusal is recorded per account in
+//! `$CODEX_HOME/model_availability.json` so the picker can grey the model out
+//! instead of letting every turn fail. A mark expires after
+//! [`UNAVAILABLE_TTL`], since plans change, and a successful request with the
+//! model clears it right away.
+
+use chrono::DateTime;
+use chrono::Utc;
+use serde::Deserialize;
+use serde::Serialize;
+use std::collections::BTreeSet;
+use std::io;
+use std::io::ErrorKind;
+use std::path::Path;
+use std::path::PathBuf;
+use std::time::Duration;
+use tracing::warn;
+
+const AVAILABILITY_FILE: &str = "model_availability.json";
+
+/// How long a refusal keeps a model marked unavailable.
+pub const UNAVAILABLE_TTL: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// One model the provider refused to one account.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UnavailableModel {
+    /// The credentials the model was requested with, as rendered by
+    /// [`super::provenance::CatalogAuth`].
+    pub account: String,
+    pub model: String,
+    /// The provider's explanation.
+    pub reason: String,
+    pub marked_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct AvailabilityFile {
+    unavailable: Vec<UnavailableModel>,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct AccountAvailability {
+    path: PathBuf,
+    unavailable: Vec<UnavailableModel>,
+}
+
+impl AccountAvailability {
+    /// Read the marks under `codex_home`. A missing or unreadable file yields
+    /// no marks.
+    pub(crate) fn load(codex_home: &Path) -> Self {
+        let path = codex_home.join(AVAILABILITY_FILE);
+        let unavailable = match std::fs::read(&path) {
+            Ok(contents) => match serde_json::from_slice::<AvailabilityFile>(&contents) {
+                Ok(file) => file.unavailable,
+                Err(err) => {
+                    warn!("ignoring unreadable {}: {err}", path.display());
+                    Vec::new()
+                }
+            },
+            Err(err) if err.kind() == ErrorKind::NotFound => Vec::new(),
+            Err(err) => {
+                warn!("failed to read {}: {err}", path.display());
+                Vec::new()
+            }
+        };
+        Self { path, unavailable }
+    }
+
+    pub(crate) fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Why `model` is unavailable to `account`, unless the mark has expired.
+    pub(crate) fn reason(&self, account: &str, model: &str, now: DateTime<Utc>) -> Option<&str> {
+        self.unavailable
+            .iter()
+            .find(|entry| {
+                entry.account == account && entry.model == model && !is_expired(entry, now)
+            })
+            .map(|entry| entry.reason.as_str())
+    }
+
+    /// Mark `model` unavailable to `account`, replacing any earlier mark.
+    /// Expired marks are dropped along the way.
+    pub(crate) fn mark(&mut self, account: &str, model: &str, reason: &str, now: DateTime<Utc>) {
+        self.unavailable.retain(|entry| {
+            !is_expired(entry, now) && !(entry.account == account && entry.model == model)
+        });
+        self.unavailable.push(UnavailableModel {
+            account: account.to_string(),
+            model: model.to_string(),
+            reason: reason.to_string(),
+            marked_at: now,
+        });
+    }
+
+    /// Clear the mark on `model` for `account`, returning whether there was
+    /// one.
+    pub(crate) fn clear(&mut self, account: &str, model: &str) -> bool {
+        let before = self.unavailable.len();
+        self.unavailable
+            .retain(|entry| !(entry.account == account && entry.model == model));
+        self.unavailable.len() != before
+    }
+
+    /// Every model with a mark for `account`, expired or not, sorted.
+    pub(crate) fn models(&self, account: &str) -> Vec<String> {
+        let models: BTreeSet<&str> = self
+            .unavailable
+            .iter()
+            .filter(|entry| entry.account == account)
+            .map(|entry| entry.model.as_str())
+            .collect();
+        models.into_iter().map(str::to_string).collect()
+    }
+
+    /// Drop the marks for `account` on models `keep` rejects, returning how
+    /// many there were. Marks for other accounts are left alone.
+    pub(crate) fn retain_models(&mut self, account: &str, keep: impl Fn(&str) -> bool) -> usize {
+        let before = self.unavailable.len();
+        self.unavailable
+            .retain(|entry| entry.account != account || keep(entry.model.as_str()));
+        before - self.unavailable.len()
+    }
+
+    /// The marks serialized for writing back to disk.
+    pub(crate) fn to_json(&self) -> io::Result<Vec<u8>> {
+        let file = AvailabilityFile {
+            unavailable: self.unavailable.clone(),
+        };
+        serde_json::to_vec_pretty(&file)
+            .map_err(|err| io::Error::new(ErrorKind::InvalidData, err.to_string()))
+    }
+}
+
+fn is_expired(entry: &UnavailableModel, now: DateTime<Utc>) -> bool {
+    now.signed_duration_since(entry.marked_at)
+        .to_std()
+        .is_ok_and(|age| age >= UNAVAILABLE_TTL)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use tempfile::tempdir;
+
+    const ACCOUNT: &str = "OpenAI via chatgpt.com, ChatGPT account me@example.com from auth.json";
+
+    #[test]
+    fn marks_persist_and_expire() {
+        let codex_home = tempdir().expect("temp dir");
+        let marked_at = Utc::now();
+        let mut availability = AccountAvailability::load(codex_home.path());
+        availability.mark(ACCOUNT, "gpt-5.2-codex", "not on your plan", marked_at);
+        std::fs::write(
+            availability.path(),
+            availability.to_json().expect("serialize marks"),
+        )
+        .expect("write marks");
+
+        let availability = AccountAvailability::load(codex_home.path());
+        assert_eq!(
+            availability.reason(ACCOUNT, "gpt-5.2-codex", marked_at),
+            Some("not on your plan")
+        );
+        assert_eq!(
+            availability.reason("another account", "gpt-5.2-codex", marked_at),
+            None
+        );
+        let ttl = chrono::Duration::from_std(UNAVAILABLE_TTL).expect("ttl fits");
+        assert_eq!(
+            availability.reason(ACCOUNT, "gpt-5.2-codex", marked_at + ttl),
+            None
+        );
+    }
+
+    #[test]
+    fn clearing_removes_only_that_model() {
+        let codex_home = tempdir().expect("temp dir");
+        let now = Utc::now();
+        let mut availability = AccountAvailability::load(codex_home.path());
+        availability.mark(ACCOUNT, "gpt-5.2-codex", "not on your plan", now);
+        availability.mark(ACCOUNT, "gpt-5.2", "not on your plan", now);
+
+        assert!(availability.clear(ACCOUNT, "gpt-5.2-codex"));
+        assert!(!availability.clear(ACCOUNT, "gpt-5.2-codex"));
+        assert_eq!(availability.reason(ACCOUNT, "gpt-5.2-codex", now), None);
+        assert_eq!(
+            availability.reason(ACCOUNT, "gpt-5.2", now),
+            Some("not on your plan")
+        );
+    }
+}
@@ -0,0 +1,528 @@
+//! Checks of a `config.toml` against a catalog file, for rolling the pair
+//! out together.
+//!
+//! Managed deployments ship a config and a `models.json` catalog to every
+//! machine. [`validate_bundle`] treats the catalog as the only source of
+//! models, so nothing is fetched and the built-in presets do not hide a
+//! model the catalog forgot. Findings that would make sessions fail are
+//! errors; those Codex works around at runtime are warnings.
+
+use std::collections::HashSet;
+use std::fmt;
+
+use codex_protocol::openai_models::EffortSelection;
+use codex_protocol::openai_models::ModelInfo;
+use codex_protocol::openai_models::ModelPreset;
+use codex_protocol::openai_models::ReasoningEffort;
+use serde::Serialize;
+
+use crate::config::ConfigToml;
+use crate::models_manager::validation::InvalidSelection;
+use crate::models_manager::validation::check_model;
+use crate::models_manager::validation::preset_override_warnings;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+        })
+    }
+}
+
+/// What a [`Finding`] is about, stable across releases for scripts to
+/// match on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FindingCode {
+    /// Two catalog entries have the same preset id.
+    DuplicateModel,
+    /// No catalog entry is shown in the model picker.
+    NoListedModels,
+    /// A catalog entry names an upgrade or fallback the catalog lacks.
+    UnknownCatalogReference,
+    /// A config key names a model the catalog lacks.
+    UnknownModel,
+    /// A config key names a model the catalog hides from the picker.
+    HiddenModel,
+    /// `profile` names a profile that `profiles` does not define.
+    UnknownProfile,
+    /// A configured effort is not one the model offers.
+    UnsupportedEffort,
+    /// A configured effort is listed in `disabled_reasoning_efforts`.
+    DisabledEffort,
+    /// A `preset_overrides` range leaves the model no effort to run at.
+    EmptyEffortRange,
+    /// An override names a model or effort the catalog lacks, so it is
+    /// ignored.
+    IgnoredOverride,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Finding {
+    pub severity: Severity,
+    pub code: FindingCode,
+    /// The config key or catalog entry at fault, e.g. `profiles.work.model`
+    /// or `catalog."gpt-5.1"`.
+    pub location: String,
+    pub message: String,
+}
+
+impl fmt::Display for Finding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}: {}", self.severity, self.location, self.message)
+    }
+}
+
+/// Every finding for `config` loaded against `catalog`, catalog findings
+/// first and then in the order of the config keys checked.
+pub fn validate_bundle(config: &ConfigToml, catalog: &[ModelInfo]) -> Vec<Finding> {
+    let mut check = BundleCheck {
+        config,
+        presets: catalog.iter().cloned().map(ModelPreset::from).collect(),
+        findings: Vec::new(),
+    };
+    check.catalog(catalog);
+    check.top_level();
+    check.profiles();
+    check.preset_overrides();
+    check.model_preset_overrides();
+    check.findings
+}
+
+struct BundleCheck<'a> {
+    config: &'a ConfigToml,
+    presets: Vec<ModelPreset>,
+    findings: Vec<Finding>,
+}
+
+impl BundleCheck<'_> {
+    fn push(
+        &mut self,
+        severity: Severity,
+        code: FindingCode,
+        location: impl Into<String>,
+        message: String,
+    ) {
+        self.findings.push(Finding {
+            severity,
+            code,
+            location: location.into(),
+            message,
+        });
+    }
+
+    fn find(&self, model: &str) -> Option<&ModelPreset> {
+        self.presets
+            .iter()
+            .find(|preset| preset.id == model || preset.model == model)
+    }
+
+    fn catalog(&mut self, catalog: &[ModelInfo]) {
+        let mut seen = HashSet::new();
+        let presets = self.presets.clone();
+        for preset in &presets {
+            if !seen.insert(preset.id.as_str()) {
+                self.push(
+                    Severity::Error,
+                    FindingCode::DuplicateModel,
+                    format!("catalog.\"{}\"", preset.id),
+                    format!(
+                        "`{}` is listed more than once; only the first entry is used.",
+                        preset.id
+                    ),
+                );
+            }
+        }
+        if !presets.iter().any(|preset| preset.show_in_picker) {
+            self.push(
+                Severity::Error,
+                FindingCode::NoListedModels,
+                "catalog",
+                "no model is shown in the model picker.".to_string(),
+            );
+        }
+        for info in catalog {
+            let references = info
+                .upgrade
+                .iter()
+                .map(|upgrade| ("upgrade", upgrade))
+                .chain(
+                    info.fallback_models
+                        .iter()
+                        .flatten()
+                        .map(|fallback| ("fallback_models", fallback)),
+                );
+            for (field, reference) in references {
+                if self.find(reference).is_none() {
+                    self.push(
+                        Severity::Warning,
+                        FindingCode::UnknownCatalogReference,
+                        format!("catalog.\"{}\".{field}", info.slug),
+                        format!("`{reference}` is not in the catalog, so it is never offered."),
+                    );
+                }
+            }
+        }
+    }
+
+    fn top_level(&mut self) {
+        let config = self.config;
+        if let Some(model) = config.model.as_deref() {
+            self.model("model", model);
+            if let Some(effort) = config
+                .model_reasoning_effort
+                .and_then(EffortSelection::explicit)
+            {
+                self.effort("model_reasoning_effort", model, effort);
+            }
+        }
+        if let Some(model) = config.review_model.as_deref() {
+            self.model("review_model", model);
+        }
+        if let Some(id) = config.review_model_preset.as_deref() {
+            self.model("review_model_preset", id);
+        }
+        if let Some(id) = config.compact_model_preset.as_deref() {
+            self.model("compact_model_preset", id);
+        }
+        if let Some(profile) = config.profile.as_deref()
+            && !config.profiles.contains_key(profile)
+        {
+            self.push(
+                Severity::Error,
+                FindingCode::UnknownProfile,
+                "profile",
+                format!("`{profile}` is not defined under `[profiles]`."),
+            );
+        }
+    }
+
+    fn profiles(&mut self) {
+        let config = self.config;
+        let mut names: Vec<&String> = config.profiles.keys().collect();
+        names.sort();
+        for name in names {
+            let profile = &config.profiles[name];
+            if let Some(model) = profile.model.as_deref() {
+                self.model(format!("profiles.{name}.model"), model);
+            }
+            let model = profile.model.as_deref().or(config.model.as_deref());
+            if let Some(model) = model
+                && let Some(effort) = profile
+                    .model_reasoning_effort
+                    .and_then(EffortSelection::explicit)
+            {
+                self.effort(
+                    format!("profiles.{name}.model_reasoning_effort"),
+                    model,
+                    effort,
+                );
+            }
+        }
+    }
+
+    /// Check that `model`, set at `location`, is listed and visible.
+    fn model(&mut self, location: impl Into<String>, model: &str) {
+        let location = location.into();
+        match self.find(model).map(|preset| preset.show_in_picker) {
+            Some(false) => self.push(
+                Severity::Warning,
+                FindingCode::HiddenModel,
+                location,
+                format!("`{model}` is hidden from the model picker, so users cannot pick it again after switching away."),
+            ),
+            Some(true) => {}
+            None => {
+                let known = self
+                    .presets
+                    .iter()
+                    .flat_map(|preset| [preset.id.clone(), preset.model.clone()])
+                    .collect();
+                let suggestions = match check_model(model, "", known) {
+                    Some(InvalidSelection::UnknownModel { suggestions, .. }) => suggestions,
+                    _ => Vec::new(),
+                };
+                let message = match suggestions.as_slice() {
+                    [] => format!("`{model}` is not in the catalog."),
+                    [only] => format!("`{model}` is not in the catalog. Did you mean `{only}`?"),
+                    many => format!(
+                        "`{model}` is not in the catalog. Did you mean one of {}?",
+                        many.iter()
+                            .map(|suggestion| format!("`{suggestion}`"))
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    ),
+                };
+                self.push(Severity::Error, FindingCode::UnknownModel, location, message);
+            }
+        }
+    }
+
+    /// Check that `effort`, set at `location`, is one `model` may run at.
+    /// Models the catalog lacks are reported by [`Self::model`] instead.
+    fn effort(&mut self, location: impl Into<String>, model: &str, effort: ReasoningEffort) {
+        let location = location.into();
+        if self
+            .config
+            .disabled_reasoning_efforts
+            .as_deref()
+            .unwrap_or_default()
+            .contains(&effort)
+        {
+            self.push(
+                Severity::Error,
+                FindingCode::DisabledEffort,
+                location,
+                format!("`{effort}` is listed in `disabled_reasoning_efforts`."),
+            );
+            return;
+        }
+        let Some(preset) = self.find(model) else {
+            return;
+        };
+        let supported: Vec<ReasoningEffort> = preset
+            .supported_reasoning_efforts
+            .iter()
+            .map(|option| option.effort)
+            .collect();
+        if !supported.is_empty() && !supported.contains(&effort) {
+            let supported: Vec<String> = supported
+                .iter()
+                .map(|effort| format!("`{effort}`"))
+                .collect();
+            self.push(
+                Severity::Error,
+                FindingCode::UnsupportedEffort,
+                location,
+                format!(
+                    "`{model}` does not support `{effort}`; it supports {}.",
+                    supported.join(", ")
+                ),
+            );
+        }
+    }
+
+    fn preset_overrides(&mut self) {
+        let config = self.config;
+        let Some(overrides) = config.preset_overrides.as_ref() else {
+            return;
+        };
+        let mut slugs: Vec<&String> = overrides.keys().collect();
+        slugs.sort();
+        for slug in slugs {
+            let location = format!("preset_overrides.\"{slug}\"");
+            let Some(preset) = self.presets.iter().find(|preset| &preset.model == slug) else {
+                self.push(
+                    Severity::Warning,
+                    FindingCode::IgnoredOverride,
+                    location,
+                    format!("no model in the catalog has the slug `{slug}`, so this is ignored."),
+                );
+                continue;
+            };
+            let limits = &overrides[slug];
+            let inverted = limits
+                .min_effort
+                .zip(limits.max_effort)
+                .is_some_and(|(min, max)| !min.is_within(None, Some(max)));
+            let empty = inverted
+                || (!preset.supported_reasoning_efforts.is_empty()
+                    && !preset
+                        .supported_reasoning_efforts
+                        .iter()
+                        .any(|option| limits.allows(option.effort)));
+            if empty {
+                self.push(
+                    Severity::Error,
+                    FindingCode::EmptyEffortRange,
+                    location,
+                    format!("the effort range leaves `{slug}` no reasoning effort it supports."),
+                );
+            }
+        }
+    }
+
+    fn model_preset_overrides(&mut self) {
+        let config = self.config;
+        let Some(overrides) = config.model_preset_overrides.as_ref() else {
+            return;
+        };
+        for warning in preset_override_warnings(overrides, &self.presets) {
+            self.push(
+                Severity::Warning,
+                FindingCode::IgnoredOverride,
+                "model_preset_overrides",
+                warning,
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codex_protocol::openai_models::ModelVisibility;
+    use codex_protocol::openai_models::ModelsResponse;
+    use pretty_assertions::assert_eq;
+
+    /// The bundled catalog cut down to three models: `gpt-5.2` and
+    /// `gpt-5.1-codex-mini` listed, `gpt-5` hidden.
+    fn catalog() -> Vec<ModelInfo> {
+        let response: ModelsResponse =
+            serde_json::from_str(include_str!("../../models.json")).expect("bundled catalog");
+        let mut models: Vec<ModelInfo> = response
+            .models
+            .into_iter()
+            .filter(|info| ["gpt-5.2", "gpt-5.1-codex-mini", "gpt-5"].contains(&info.slug.as_str()))
+            .collect();
+        for info in &mut models {
+            info.upgrade = None;
+        }
+        models
+    }
+
+    fn config(toml: &str) -> ConfigToml {
+        toml::from_str(toml).expect("valid config.toml")
+    }
+
+    fn codes(findings: &[Finding]) -> Vec<(Severity, FindingCode, &str)> {
+        findings
+            .iter()
+            .map(|finding| (finding.severity, finding.code, finding.location.as_str()))
+            .collect()
+    }
+
+    #[test]
+    fn a_consistent_bundle_has_no_findings() {
+        let config = config(
+            r#"
+model = "gpt-5.2"
+model_reasoning_effort = "high"
+
+[profiles.fast]
+model = "gpt-5.1-codex-mini"
+model_reasoning_effort = "medium"
+"#,
+        );
+        assert_eq!(validate_bundle(&config, &catalog()), Vec::new());
+    }
+
+    #[test]
+    fn a_bad_bundle_reports_each_finding() {
+        let mut catalog = catalog();
+        for info in &mut catalog {
+            if info.slug == "gpt-5.2" {
+                info.fallback_models = Some(vec!["gpt-4o".to_string()]);
+            }
+        }
+        let duplicate = catalog[0].clone();
+        catalog.push(duplicate);
+        let config = config(
+            r#"
+model = "gpt-5.3"
+review_model = "gpt-5"
+compact_model_preset = "gpt-5-mini"
+profile = "missing"
+disabled_reasoning_efforts = ["xhigh"]
+
+[profiles.deep]
+model = "gpt-5.1-codex-mini"
+model_reasoning_effort = "low"
+
+[profiles.max]
+model = "gpt-5.2"
+model_reasoning_effort = "xhigh"
+
+[preset_overrides."gpt-5.2"]
+min_effort = "high"
+max_effort = "low"
+
+[preset_overrides."o3"]
+max_effort = "low"
+
+[model_preset_overrides."gpt-4.1"]
+display_name = "Old"
+"#,
+        );
+
+        let findings = validate_bundle(&config, &catalog);
+
+        assert_eq!(
+            codes(&findings),
+            vec![
+                (
+                    Severity::Error,
+                    FindingCode::DuplicateModel,
+                    "catalog.\"gpt-5.1-codex-mini\""
+                ),
+                (
+                    Severity::Warning,
+                    FindingCode::UnknownCatalogReference,
+                    "catalog.\"gpt-5.2\".fallback_models"
+                ),
+                (Severity::Error, FindingCode::UnknownModel, "model"),
+                (Severity::Warning, FindingCode::HiddenModel, "review_model"),
+                (
+                    Severity::Error,
+                    FindingCode::UnknownModel,
+                    "compact_model_preset"
+                ),
+                (Severity::Error, FindingCode::UnknownProfile, "profile"),
+                (
+                    Severity::Error,
+                    FindingCode::UnsupportedEffort,
+                    "profiles.deep.model_reasoning_effort"
+                ),
+                (
+                    Severity::Error,
+                    FindingCode::DisabledEffort,
+                    "profiles.max.model_reasoning_effort"
+                ),
+                (
+                    Severity::Error,
+                    FindingCode::EmptyEffortRange,
+                    "preset_overrides.\"gpt-5.2\""
+                ),
+                (
+                    Severity::Warning,
+                    FindingCode::IgnoredOverride,
+                    "preset_overrides.\"o3\""
+                ),
+                (
+                    Severity::Warning,
+                    FindingCode::IgnoredOverride,
+                    "model_preset_overrides"
+                ),
+            ]
+        );
+        assert_eq!(
+            findings[2].to_string(),
+            "error: model: `gpt-5.3` is not in the catalog. Did you mean one of `gpt-5.2`, `gpt-5`?"
+        );
+        assert_eq!(
+            findings[6].message,
+            "`gpt-5.1-codex-mini` does not support `low`; it supports `medium`, `high`."
+        );
+    }
+
+    #[test]
+    fn a_catalog_that_hides_every_model_is_an_error() {
+        let mut catalog = catalog();
+        for info in &mut catalog {
+            info.visibility = ModelVisibility::Hide;
+        }
+        let findings = validate_bundle(&ConfigToml::default(), &catalog);
+        assert_eq!(
+            codes(&findings),
+            vec![(Severity::Error, FindingCode::NoListedModels, "catalog")]
+        );
+    }
+}
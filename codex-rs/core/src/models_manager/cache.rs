@@ -9,6 +9,8 @@ use std::path::Path;
 use std::time::Duration;
 use tokio::fs;
 
+use super::state_dir::write_state;
+
 /// Serialized snapshot of models and metadata cached on disk.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub(crate) struct ModelsCache {
@@ -16,9 +18,37 @@ pub(crate) struct ModelsCache {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub(crate) etag: Option<String>,
     pub(crate) models: Vec<ModelInfo>,
+    /// `Accept-Language` the models were fetched with. A cache is only reused
+    /// for the same locale.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) locale: Option<String>,
+    /// The catalog's `min_client_version`, so that a process listing from the
+    /// cache still hears about it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) min_client_version: Option<String>,
+    /// The model the catalog's default settled on, so that a later process
+    /// keeps it when the server only reorders its entries.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) default_model: Option<CachedDefault>,
+}
+
+/// The default of the catalog served from `base_url`. A cache written for
+/// another base URL says nothing about this one's default.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct CachedDefault {
+    pub(crate) base_url: String,
+    pub(crate) model: String,
 }
 
 impl ModelsCache {
+    /// The recorded default, when it was recorded for `base_url`.
+    pub(crate) fn default_for(&self, base_url: &str) -> Option<String> {
+        self.default_model
+            .as_ref()
+            .filter(|default| default.base_url == base_url)
+            .map(|default| default.model.clone())
+    }
+
     /// Returns `true` when the cache entry has not exceeded the configured TTL.
     pub(crate) fn is_fresh(&self, ttl: Duration) -> bool {
         if ttl.is_zero() {
@@ -46,11 +76,9 @@ pub(crate) async fn load_cache(path: &Path) -> io::Result<Option<ModelsCache>> {
 }
 
 /// Persist the cache contents to disk, creating parent directories as needed.
+/// Under a read-only home nothing is written; see [`write_state`].
 pub(crate) async fn save_cache(path: &Path, cache: &ModelsCache) -> io::Result<()> {
-    if let Some(parent) = path.parent() {
-        fs::create_dir_all(parent).await?;
-    }
     let json = serde_json::to_vec_pretty(cache)
         .map_err(|err| io::Error::new(ErrorKind::InvalidData, err.to_string()))?;
-    fs::write(path, json).await
+    write_state(path, &json).await
 }
@@ -0,0 +1,995 @@
+//! One entry point for reading the model presets.
+//!
+//! Async callers go through [`PresetCatalog::load`], which refreshes the
+//! catalogs as needed. Code that cannot await uses
+//! [`PresetCatalog::load_cached_or_static`], which only reads what is already
+//! in memory, or, on a render path that must not even fail,
+//! [`PresetCatalog::snapshot`]. Callers without a [`ModelsManager`], such as
+//! `--help` output, use [`PresetCatalog::static_presets`].
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use chrono::DateTime;
+use chrono::Utc;
+use codex_app_server_protocol::AuthMode;
+use codex_protocol::openai_models::Capability;
+use codex_protocol::openai_models::ModelPreset;
+use codex_protocol::openai_models::ReasoningEffort;
+use smol_str::SmolStr;
+use tokio::sync::TryLockError;
+
+use crate::config::Config;
+use crate::models_manager::catalog_export::ImportedCatalogInfo;
+use crate::models_manager::manager::ModelsManager;
+use crate::models_manager::model_presets::builtin_presets;
+use crate::models_manager::provenance::CatalogSource;
+use crate::models_manager::validation::InvalidSelection;
+use crate::models_manager::validation::SelectionReport;
+use crate::models_manager::validation::SelectionWarning;
+use crate::models_manager::validation::check_effort;
+use crate::models_manager::validation::disabled_effort;
+use crate::models_manager::validation::missing_capability;
+use crate::models_manager::validation::network_warning;
+
+/// Which presets [`PresetCatalog::load`] returns and how it gets them.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CatalogLoadOptions {
+    /// Keep presets hidden from the picker and those the current credentials
+    /// cannot use.
+    pub include_hidden: bool,
+    /// Read the on-disk cache, however old, instead of fetching. Ignored when
+    /// `include_hidden` is set.
+    pub cache_only: bool,
+}
+
+/// The picker presets as of one catalog revision, as served by
+/// [`PresetCatalog::snapshot`]. Lookups go through an index built with the
+/// snapshot, so they cost the same however many presets are listed.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CatalogSnapshot {
+    revision: u64,
+    presets: Vec<ModelPreset>,
+    index: PresetIndex,
+    status: CatalogSnapshotStatus,
+}
+
+/// Where the catalog behind a [`CatalogSnapshot`] was loaded from and how its
+/// last refresh went, for status displays. Nothing in it is secret: the
+/// endpoint is reduced to its host and the error is redacted.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CatalogSnapshotStatus {
+    /// Where the remote `/models` catalog was loaded from.
+    pub source: CatalogSource,
+    /// Host (and port) of the `/models` endpoint.
+    pub host: Option<String>,
+    /// ETag of the `/models` response, when the server sent one.
+    pub etag: Option<String>,
+    /// When the remote catalog was last fetched successfully.
+    pub fetched_at: Option<DateTime<Utc>>,
+    /// The most recent refresh failure of any catalog, until that catalog
+    /// is next refreshed successfully, and when it happened.
+    pub last_error: Option<(DateTime<Utc>, String)>,
+    /// The `models_cache.json` the remote catalog is cached in.
+    pub cache_path: PathBuf,
+    /// Presets the catalogs list that the picker leaves out: hidden ones and
+    /// those the current credentials cannot use.
+    pub hidden_count: usize,
+    /// The imported catalog listed instead of the fetched ones, if any.
+    pub imported: Option<ImportedCatalogInfo>,
+}
+
+impl CatalogSnapshot {
+    pub fn new(revision: u64, presets: Vec<ModelPreset>) -> Self {
+        let index = PresetIndex::new(&presets);
+        Self {
+            revision,
+            presets,
+            index,
+            status: CatalogSnapshotStatus::default(),
+        }
+    }
+
+    pub fn with_status(mut self, status: CatalogSnapshotStatus) -> Self {
+        self.status = status;
+        self
+    }
+
+    /// The [`ModelsManager::catalog_revision`] the presets were assembled at.
+    pub fn revision(&self) -> u64 {
+        self.revision
+    }
+
+    /// See [`CatalogSnapshotStatus`]. Kept current with the last refresh even
+    /// when the refresh left the presets, and so the revision, alone.
+    pub fn status(&self) -> &CatalogSnapshotStatus {
+        &self.status
+    }
+
+    /// The presets in catalog order, as the picker lists them.
+    pub fn presets(&self) -> &[ModelPreset] {
+        &self.presets
+    }
+
+    /// The preset for the model slug `model`, if it is listed.
+    pub fn find(&self, model: &str) -> Option<&ModelPreset> {
+        self.index.by_model(model).map(|index| &self.presets[index])
+    }
+
+    /// The preset named `name` by model slug or id, matched as
+    /// [`PresetCatalog::find_many`] matches, if it is listed.
+    pub fn find_by_model_or_id(&self, name: &str) -> Option<&ModelPreset> {
+        self.index
+            .by_model_or_id(name)
+            .map(|index| &self.presets[index])
+    }
+
+    /// The preset named `name` by id, model slug or display name, in that
+    /// order of preference, if it is listed.
+    pub fn resolve(&self, name: &str) -> Option<&ModelPreset> {
+        self.index.resolve(name).map(|index| &self.presets[index])
+    }
+}
+
+/// Positions of presets by id, by model slug and by alias, the display name
+/// the picker shows. Names match exactly, and when several presets share one
+/// the first in catalog order wins, as a scan of the list would find. Names
+/// are kept as [`SmolStr`], which holds most of them inline, so building the
+/// index for a revision allocates little more than the maps themselves.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(super) struct PresetIndex {
+    ids: HashMap<SmolStr, usize>,
+    models: HashMap<SmolStr, usize>,
+    aliases: HashMap<SmolStr, usize>,
+}
+
+impl PresetIndex {
+    pub(super) fn new(presets: &[ModelPreset]) -> Self {
+        let mut index = Self {
+            ids: HashMap::with_capacity(presets.len()),
+            models: HashMap::with_capacity(presets.len()),
+            aliases: HashMap::with_capacity(presets.len()),
+        };
+        for (position, preset) in presets.iter().enumerate() {
+            index
+                .ids
+                .entry(SmolStr::new(&preset.id))
+                .or_insert(position);
+            index
+                .models
+                .entry(SmolStr::new(&preset.model))
+                .or_insert(position);
+            index
+                .aliases
+                .entry(SmolStr::new(&preset.display_name))
+                .or_insert(position);
+        }
+        index
+    }
+
+    fn by_model(&self, model: &str) -> Option<usize> {
+        self.models.get(model).copied()
+    }
+
+    /// The first preset whose model slug or id is `name`.
+    fn by_model_or_id(&self, name: &str) -> Option<usize> {
+        match (self.models.get(name), self.ids.get(name)) {
+            (Some(&model), Some(&id)) => Some(model.min(id)),
+            (model, id) => model.or(id).copied(),
+        }
+    }
+
+    /// The preset whose id is `name`, else the first whose model slug is,
+    /// else the first whose display name is.
+    pub(super) fn resolve(&self, name: &str) -> Option<usize> {
+        self.ids
+            .get(name)
+            .or_else(|| self.models.get(name))
+            .or_else(|| self.aliases.get(name))
+            .copied()
+    }
+}
+
+/// The effort [`PresetCatalog::resolve_effort`] settled on for a request.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EffortResolution {
+    /// The requested effort, or the closest one the model offers when it
+    /// offers not the requested one and validation is not strict.
+    pub effort: ReasoningEffort,
+    /// Why `effort` is not the requested one, for the user.
+    pub warning: Option<String>,
+}
+
+/// The presets a [`ModelsManager`] serves for one [`Config`].
+#[derive(Clone, Copy)]
+pub struct PresetCatalog<'a> {
+    models_manager: &'a ModelsManager,
+    config: &'a Config,
+}
+
+impl<'a> PresetCatalog<'a> {
+    pub fn new(models_manager: &'a ModelsManager, config: &'a Config) -> Self {
+        Self {
+            models_manager,
+            config,
+        }
+    }
+
+    pub async fn load(&self, options: CatalogLoadOptions) -> Vec<ModelPreset> {
+        if options.include_hidden {
+            self.models_manager.list_all_models(self.config).await
+        } else if options.cache_only {
+            self.models_manager.cached_models(self.config).await
+        } else {
+            self.models_manager.list_models(self.config).await
+        }
+    }
+
+    /// The preset the picker selects initially. The merged catalog always
+    /// has exactly one listed default; this is `None` only when nothing is
+    /// listed at all.
+    pub async fn default(&self) -> Option<ModelPreset> {
+        self.load(CatalogLoadOptions::default())
+            .await
+            .into_iter()
+            .find(|preset| preset.is_default)
+    }
+
+    /// The picker presets already loaded, without any I/O. Fails while a
+    /// refresh holds the catalog; built-in presets are always loaded, so a
+    /// manager that has not fetched anything yet still returns them.
+    pub fn load_cached_or_static(&self) -> Result<Vec<ModelPreset>, TryLockError> {
+        self.models_manager.loaded_models(self.config)
+    }
+
+    /// The preset named `name` by model slug or id, matched as
+    /// [`CatalogSnapshot::find_by_model_or_id`] matches, among every preset
+    /// already loaded, hidden ones included, without any I/O. While a refresh
+    /// holds the catalog only the built-in presets are searched.
+    pub fn find_known(&self, name: &str) -> Option<ModelPreset> {
+        let presets = self.known_presets();
+        let index = PresetIndex::new(&presets).by_model_or_id(name)?;
+        presets.into_iter().nth(index)
+    }
+
+    /// The listed presets already loaded that have `capability`, in catalog
+    /// order, without any I/O. Presets the picker hides are left out, since
+    /// they are not offered as models to switch to.
+    pub fn filter_by_capability(&self, capability: Capability) -> Vec<ModelPreset> {
+        self.known_presets()
+            .into_iter()
+            .filter(|preset| preset.show_in_picker && preset.supports(capability))
+            .collect()
+    }
+
+    /// Every preset already loaded, hidden ones included, or the built-in
+    /// presets while a refresh holds the catalog.
+    fn known_presets(&self) -> Vec<ModelPreset> {
+        self.models_manager
+            .loaded_all_models(self.config)
+            .unwrap_or_else(|_| {
+                Self::static_presets(CatalogLoadOptions {
+                    include_hidden: true,
+                    ..Default::default()
+                })
+                .cloned()
+                .collect()
+            })
+    }
+
+    /// The picker presets as of the latest catalog revision, for code that
+    /// must neither await nor block, such as rendering. Refreshes publish a
+    /// new snapshot in one atomic swap, so while it is current reading it is
+    /// a single load. After a refresh changed the catalog, the first reader
+    /// rebuilds it from what is loaded, or keeps serving the previous one
+    /// while the refresh still holds the catalog.
+    ///
+    /// The manager keeps one snapshot, assembled for the [`Config`] of
+    /// whichever catalog built it last.
+    pub fn snapshot(&self) -> Arc<CatalogSnapshot> {
+        self.models_manager.snapshot(self.config)
+    }
+
+    /// Check a requested `model` and `effort` before a session is started
+    /// with them. The model goes through the same check as
+    /// `strict_model_validation` at session startup, after refreshing the
+    /// catalogs so provider listings are loaded. The effort is checked against
+    /// the preset of the model the session would resolve to, once clamped
+    /// into the range `preset_overrides` sets for that model, unless
+    /// `disabled_reasoning_efforts` rules it out for every model.
+    pub async fn check_selection(
+        &self,
+        model: Option<&str>,
+        effort: Option<ReasoningEffort>,
+    ) -> Result<(), InvalidSelection> {
+        let presets = self
+            .load(CatalogLoadOptions {
+                include_hidden: true,
+                ..Default::default()
+            })
+            .await;
+        if let Some(model) = model
+            && let Some(invalid) = self.models_manager.check_model(model, self.config).await
+        {
+            return Err(invalid);
+        }
+        let Some(effort) = effort else {
+            return Ok(());
+        };
+        if let Some(invalid) = disabled_effort(&self.config.disabled_reasoning_efforts, effort) {
+            return Err(invalid);
+        }
+        let (model, _) = self.models_manager.resolve_session_model(self.config).await;
+        let effort = self
+            .config
+            .preset_overrides
+            .get(&model)
+            .map_or(effort, |limits| limits.clamp(effort));
+        match check_effort(&presets, &model, &self.config.model_provider_id, effort) {
+            Some(invalid) => Err(invalid),
+            None => Ok(()),
+        }
+    }
+
+    /// Settle the effort a session or turn running `model`, or the model the
+    /// session would resolve to when `None`, is sent with. The effort is
+    /// checked as [`Self::check_selection`] checks it. One the model does not
+    /// offer is an error under `strict_model_validation`, and is otherwise
+    /// replaced, with a warning, by the closest effort the model offers. A
+    /// disabled effort is an error either way.
+    pub async fn resolve_effort(
+        &self,
+        model: Option<&str>,
+        requested: ReasoningEffort,
+    ) -> Result<EffortResolution, InvalidSelection> {
+        if let Some(invalid) = disabled_effort(&self.config.disabled_reasoning_efforts, requested) {
+            return Err(invalid);
+        }
+        let presets = self
+            .load(CatalogLoadOptions {
+                include_hidden: true,
+                ..Default::default()
+            })
+            .await;
+        let model = match model {
+            Some(model) => model.to_string(),
+            None => {
+                self.models_manager
+                    .resolve_session_model(self.config)
+                    .await
+                    .0
+            }
+        };
+        let effort = self
+            .config
+            .preset_overrides
+            .get(&model)
+            .map_or(requested, |limits| limits.clamp(requested));
+        let Some(invalid) = check_effort(&presets, &model, &self.config.model_provider_id, effort)
+        else {
+            // A clamp by `preset_overrides` alone is applied, and reported,
+            // by the session.
+            return Ok(EffortResolution {
+                effort: requested,
+                warning: None,
+            });
+        };
+        let nearest = match &invalid {
+            InvalidSelection::UnsupportedEffort { supported, .. } => effort.nearest_in(supported),
+            _ => None,
+        };
+        match nearest {
+            Some(nearest) if !self.config.strict_model_validation => Ok(EffortResolution {
+                effort: nearest,
+                warning: Some(format!("{invalid} Using `{nearest}` instead.")),
+            }),
+            _ => Err(invalid),
+        }
+    }
+
+    /// Check the options a session would be started with, for forms that
+    /// validate them as they are edited. The model is `model` from the
+    /// [`Config`], and goes through [`Self::check_selection`]'s checks; so
+    /// does `effort`, which is also reported when `preset_overrides` clamps
+    /// it or the model ignores it. The preset of the resolved model must
+    /// have every capability in `required`, and is checked against the
+    /// signed-in account and the sandbox's network access. Starts no session.
+    pub async fn validate_options(
+        &self,
+        effort: Option<ReasoningEffort>,
+        required: &[Capability],
+    ) -> SelectionReport {
+        let presets = self
+            .load(CatalogLoadOptions {
+                include_hidden: true,
+                ..Default::default()
+            })
+            .await;
+        let mut errors = Vec::new();
+        let mut warnings = Vec::new();
+        if let Some(requested) = self.config.model.as_deref() {
+            if let Some(successor) = ModelsManager::retired_model_successor(requested, self.config)
+            {
+                warnings.push(SelectionWarning::RetiredModel {
+                    model: requested.to_string(),
+                    successor: successor.model.clone(),
+                });
+            }
+            if let Some(invalid) = self
+                .models_manager
+                .check_model(requested, self.config)
+                .await
+            {
+                errors.push(invalid);
+            }
+        }
+
+        let (model, _) = self.models_manager.resolve_session_model(self.config).await;
+        let preset_id = self
+            .models_manager
+            .preset_id_for_model(&model, &self.config.model_provider_id)
+            .await;
+        let preset = presets.iter().find(|preset| preset.id == preset_id);
+        if let Some(preset) = preset {
+            if let Some(reason) = &preset.unavailable_for_account {
+                warnings.push(SelectionWarning::UnavailableForAccount {
+                    model: model.clone(),
+                    reason: reason.clone(),
+                });
+            }
+            if !preset.supported_in_api && self.models_manager.auth_mode() == Some(AuthMode::ApiKey)
+            {
+                warnings.push(SelectionWarning::RequiresChatgptAuth {
+                    model: model.clone(),
+                });
+            }
+            if let Some(warning) = network_warning(preset, self.config.sandbox_policy.get()) {
+                warnings.push(warning);
+            }
+            for &capability in required {
+                if !preset.supports(capability) {
+                    errors.push(missing_capability(
+                        &self.filter_by_capability(capability),
+                        &model,
+                        capability,
+                    ));
+                }
+            }
+        }
+
+        if let Some(requested) = effort
+            && let Some(invalid) =
+                disabled_effort(&self.config.disabled_reasoning_efforts, requested)
+        {
+            errors.push(invalid);
+        } else if let Some(requested) = effort {
+            let effort = self
+                .config
+                .preset_overrides
+                .get(&model)
+                .map_or(requested, |limits| limits.clamp(requested));
+            if effort != requested {
+                warnings.push(SelectionWarning::EffortClamped {
+                    model: model.clone(),
+                    requested,
+                    effort,
+                });
+            }
+            if let Some(invalid) =
+                check_effort(&presets, &model, &self.config.model_provider_id, effort)
+            {
+                errors.push(invalid);
+            } else if preset.is_some_and(|preset| preset.supported_reasoning_efforts.is_empty()) {
+                warnings.push(SelectionWarning::EffortIgnored {
+                    model: model.clone(),
+                    effort,
+                });
+            }
+        }
+
+        SelectionReport {
+            model,
+            errors,
+            warnings,
+        }
+    }
+
+    /// Look up the presets for many model slugs at once among the presets
+    /// already loaded, as [`Self::find_known`] looks up one, without any I/O.
+    /// A slug matches a preset's `model` or `id`, the first match in catalog
+    /// order winning; slugs without a preset are left out of the map.
+    pub fn find_many<'s>(&self, models: &[&'s str]) -> HashMap<&'s str, ModelPreset> {
+        if models.is_empty() {
+            return HashMap::new();
+        }
+        let presets = self.known_presets();
+        let index = PresetIndex::new(&presets);
+        models
+            .iter()
+            .filter_map(|&model| {
+                index
+                    .by_model_or_id(model)
+                    .map(|position| (model, presets[position].clone()))
+            })
+            .collect()
+    }
+
+    /// Built-in presets, for callers without a [`ModelsManager`].
+    pub fn static_presets(
+        options: CatalogLoadOptions,
+    ) -> impl Iterator<Item = &'static ModelPreset> {
+        builtin_presets()
+            .iter()
+            .filter(move |preset| options.include_hidden || preset.show_in_picker)
+    }
+}
+
+#[cfg(test)]
+#[allow(deprecated)]
+mod tests {
+    use super::*;
+    use crate::AuthManager;
+    use crate::CodexAuth;
+    use crate::config::ConfigBuilder;
+    use crate::config::types::PresetOverride;
+    use crate::model_provider_info::ModelCatalog;
+    use crate::model_provider_info::ModelProviderInfo;
+    use crate::model_provider_info::WireApi;
+    use crate::model_provider_info::create_oss_provider_with_base_url;
+    use crate::models_manager::model_presets::all_model_presets;
+    use crate::models_manager::model_presets::builtin_picker_presets;
+    use codex_protocol::openai_models::ReasoningEffortPreset;
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+    use tempfile::TempDir;
+    use wiremock::Mock;
+    use wiremock::MockServer;
+    use wiremock::ResponseTemplate;
+    use wiremock::matchers::method;
+    use wiremock::matchers::path;
+
+    fn ids<'p>(presets: impl Iterator<Item = &'p ModelPreset>) -> Vec<&'p str> {
+        presets.map(|preset| preset.id.as_str()).collect()
+    }
+
+    #[test]
+    fn builtin_picker_presets_delegates_to_static_presets() {
+        assert_eq!(
+            ids(builtin_picker_presets()),
+            ids(PresetCatalog::static_presets(CatalogLoadOptions::default()))
+        );
+    }
+
+    #[test]
+    fn all_model_presets_delegates_to_static_presets_with_hidden() {
+        let options = CatalogLoadOptions {
+            include_hidden: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            ids(all_model_presets().iter()),
+            ids(PresetCatalog::static_presets(options))
+        );
+        assert!(
+            all_model_presets()
+                .iter()
+                .any(|preset| !preset.show_in_picker),
+            "the built-ins should include a hidden preset for this test to mean anything"
+        );
+    }
+
+    fn presets(ids_and_models: &[(&str, &str)]) -> Vec<ModelPreset> {
+        ids_and_models
+            .iter()
+            .map(|(id, model)| {
+                ModelPreset::builder(*id, *model)
+                    .build()
+                    .expect("valid preset")
+            })
+            .collect()
+    }
+
+    /// Scans `presets` as [`PresetIndex::resolve`] looks them up.
+    fn scan_resolve<'a>(presets: &'a [ModelPreset], name: &str) -> Option<&'a ModelPreset> {
+        presets
+            .iter()
+            .find(|preset| preset.id == name)
+            .or_else(|| presets.iter().find(|preset| preset.model == name))
+            .or_else(|| presets.iter().find(|preset| preset.display_name == name))
+    }
+
+    /// A catalog of `len` gateway presets, each listed under an alias.
+    fn large_catalog(len: usize) -> Vec<ModelPreset> {
+        (0..len)
+            .map(|n| {
+                ModelPreset::builder(format!("gw/model-{n}"), format!("model-{}", n % 450))
+                    .display_name(format!("Model {}", n % 400))
+                    .build()
+                    .expect("valid preset")
+            })
+            .collect()
+    }
+
+    #[test]
+    fn colliding_ids_and_slugs_resolve_to_the_first_listed_preset() {
+        let presets = presets(&[
+            ("first", "shared"),
+            ("shared", "by-id"),
+            ("third", "shared"),
+            ("Shared", "Shared"),
+        ]);
+        let snapshot = CatalogSnapshot::new(7, presets.clone());
+
+        assert_eq!(snapshot.revision(), 7);
+        assert_eq!(snapshot.presets(), presets.as_slice());
+        assert_eq!(
+            snapshot.find("shared").map(|preset| preset.id.as_str()),
+            Some("first")
+        );
+        assert_eq!(
+            snapshot.find("Shared").map(|preset| preset.id.as_str()),
+            Some("Shared")
+        );
+        assert_eq!(snapshot.find("SHARED"), None, "lookups match names exactly");
+        assert_eq!(snapshot.find("first"), None, "`find` matches slugs only");
+
+        let index = PresetIndex::new(&presets);
+        assert_eq!(index.by_model_or_id("shared"), Some(0));
+        assert_eq!(index.by_model_or_id("by-id"), Some(1));
+        assert_eq!(index.by_model_or_id("third"), Some(2));
+        assert_eq!(index.by_model_or_id("missing"), None);
+        assert_eq!(
+            snapshot
+                .find_by_model_or_id("by-id")
+                .map(|preset| preset.id.as_str()),
+            Some("shared")
+        );
+    }
+
+    #[test]
+    fn colliding_aliases_resolve_after_ids_and_slugs() {
+        let presets: Vec<ModelPreset> = [
+            ("first", "slug-a", "Shared"),
+            ("second", "slug-b", "Shared"),
+            ("slug-b", "slug-c", "second"),
+            ("fourth", "Shared", "Fourth"),
+        ]
+        .into_iter()
+        .map(|(id, model, display_name)| {
+            ModelPreset::builder(id, model)
+                .display_name(display_name)
+                .build()
+                .expect("valid preset")
+        })
+        .collect();
+        let snapshot = CatalogSnapshot::new(1, presets.clone());
+        let resolved = |name: &str| snapshot.resolve(name).map(|preset| preset.id.as_str());
+
+        assert_eq!(resolved("second"), Some("second"), "an id beats an alias");
+        assert_eq!(resolved("slug-b"), Some("slug-b"), "an id beats a slug");
+        assert_eq!(resolved("Shared"), Some("fourth"), "a slug beats an alias");
+        assert_eq!(resolved("Fourth"), Some("fourth"));
+        assert_eq!(resolved("fourth"), Some("fourth"));
+        assert_eq!(resolved("shared"), None, "aliases match exactly");
+        assert_eq!(
+            snapshot.find_by_model_or_id("Fourth"),
+            None,
+            "only `resolve` matches aliases"
+        );
+
+        let presets: Vec<ModelPreset> = presets
+            .into_iter()
+            .filter(|preset| preset.id != "fourth")
+            .collect();
+        let snapshot = CatalogSnapshot::new(2, presets);
+        assert_eq!(
+            snapshot.resolve("Shared").map(|preset| preset.id.as_str()),
+            Some("first"),
+            "the first listed preset wins a shared alias"
+        );
+    }
+
+    #[test]
+    fn indexed_lookups_match_a_scan_of_a_large_catalog() {
+        let names: Vec<(String, String)> = (0..500)
+            .map(|n| (format!("gw/model-{n}"), format!("model-{}", n % 450)))
+            .chain([("model-3".to_string(), "aliased".to_string())])
+            .collect();
+        let pairs: Vec<(&str, &str)> = names
+            .iter()
+            .map(|(id, model)| (id.as_str(), model.as_str()))
+            .collect();
+        let presets = presets(&pairs);
+        let snapshot = CatalogSnapshot::new(1, presets.clone());
+        let index = PresetIndex::new(&presets);
+
+        for name in names
+            .iter()
+            .flat_map(|(id, model)| [id.as_str(), model.as_str()])
+            .chain(["MODEL-3", "missing"])
+        {
+            assert_eq!(
+                snapshot.find(name),
+                presets.iter().find(|preset| preset.model == name),
+                "find({name})"
+            );
+            assert_eq!(
+                index
+                    .by_model_or_id(name)
+                    .map(|position| &presets[position]),
+                presets
+                    .iter()
+                    .find(|preset| preset.model == name || preset.id == name),
+                "by_model_or_id({name})"
+            );
+        }
+
+        let presets = large_catalog(500);
+        let snapshot = CatalogSnapshot::new(1, presets.clone());
+        for name in presets
+            .iter()
+            .flat_map(|preset| [&preset.id, &preset.model, &preset.display_name])
+            .map(String::as_str)
+            .chain(["model 3", "missing"])
+        {
+            assert_eq!(
+                snapshot.resolve(name),
+                scan_resolve(&presets, name),
+                "resolve({name})"
+            );
+        }
+    }
+
+    /// A manager whose only catalog provider is a mock serving `models`,
+    /// which fails verification unless it is fetched exactly once.
+    async fn manager_with_catalog(
+        server: &MockServer,
+        models: &[&str],
+    ) -> (TempDir, Config, ModelsManager) {
+        let data: Vec<serde_json::Value> = models
+            .iter()
+            .map(|id| json!({"id": id, "object": "model"}))
+            .collect();
+        Mock::given(method("GET"))
+            .and(path("/models"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(json!({"object": "list", "data": data})),
+            )
+            .expect(1)
+            .mount(server)
+            .await;
+
+        let codex_home = TempDir::new().expect("temp dir");
+        let mut config = ConfigBuilder::default()
+            .codex_home(codex_home.path().to_path_buf())
+            .build()
+            .await
+            .expect("load default test config");
+        config.model_providers.insert(
+            "vllm".to_string(),
+            ModelProviderInfo {
+                name: "vLLM".into(),
+                model_catalog: Some(ModelCatalog::Openai),
+                ..create_oss_provider_with_base_url(&server.uri(), WireApi::Responses)
+            },
+        );
+        let auth_manager =
+            AuthManager::from_auth_for_testing(CodexAuth::from_api_key("Test API Key"));
+        let manager = ModelsManager::with_provider(
+            auth_manager,
+            create_oss_provider_with_base_url("http://example.test", WireApi::Responses),
+        );
+        (codex_home, config, manager)
+    }
+
+    #[tokio::test]
+    async fn find_many_resolves_from_the_loaded_catalog() {
+        let server = MockServer::start().await;
+        let (_codex_home, config, manager) =
+            manager_with_catalog(&server, &["qwen2.5-coder-7b", "llama-3.1-8b"]).await;
+        let catalog = PresetCatalog::new(&manager, &config);
+        catalog.load(CatalogLoadOptions::default()).await;
+        let builtin = builtin_presets().first().expect("a built-in preset");
+        let sessions = [
+            "qwen2.5-coder-7b",
+            builtin.model.as_str(),
+            "llama-3.1-8b",
+            "qwen2.5-coder-7b",
+            builtin.model.as_str(),
+        ];
+
+        // Resolved from what the load above fetched; the mock fails
+        // verification on a second fetch.
+        let found = catalog.find_many(&sessions);
+
+        let mut ids: Vec<_> = found
+            .iter()
+            .map(|(slug, preset)| (*slug, preset.id.as_str()))
+            .collect();
+        ids.sort_unstable();
+        let mut expected = vec![
+            (builtin.model.as_str(), builtin.id.as_str()),
+            ("llama-3.1-8b", "vllm/llama-3.1-8b"),
+            ("qwen2.5-coder-7b", "vllm/qwen2.5-coder-7b"),
+        ];
+        expected.sort_unstable();
+        assert_eq!(ids, expected);
+    }
+
+    #[tokio::test]
+    async fn find_many_leaves_unknown_slugs_out() {
+        let server = MockServer::start().await;
+        let (_codex_home, config, manager) =
+            manager_with_catalog(&server, &["qwen2.5-coder-7b"]).await;
+        let catalog = PresetCatalog::new(&manager, &config);
+        catalog.load(CatalogLoadOptions::default()).await;
+
+        let found = catalog.find_many(&["qwen2.5-coder-7b", "retired-model"]);
+
+        assert_eq!(
+            found.keys().copied().collect::<Vec<_>>(),
+            vec!["qwen2.5-coder-7b"]
+        );
+    }
+
+    #[tokio::test]
+    async fn find_known_includes_presets_the_picker_hides() {
+        let codex_home = TempDir::new().expect("temp dir");
+        let config = ConfigBuilder::default()
+            .codex_home(codex_home.path().to_path_buf())
+            .build()
+            .await
+            .expect("load default test config");
+        let manager = ModelsManager::with_provider(
+            AuthManager::from_auth_for_testing(CodexAuth::from_api_key("Test API Key")),
+            create_oss_provider_with_base_url("http://example.test", WireApi::Responses),
+        );
+        let hidden = builtin_presets()
+            .iter()
+            .find(|preset| !preset.show_in_picker)
+            .expect("a hidden built-in preset");
+        let catalog = PresetCatalog::new(&manager, &config);
+
+        assert_eq!(catalog.snapshot().find_by_model_or_id(&hidden.model), None);
+        assert_eq!(
+            catalog.find_known(&hidden.model).map(|preset| preset.id),
+            Some(hidden.id.clone())
+        );
+        assert_eq!(catalog.find_known("retired-model"), None);
+    }
+
+    #[tokio::test]
+    async fn filter_by_capability_leaves_hidden_presets_out() {
+        let codex_home = TempDir::new().expect("temp dir");
+        let config = ConfigBuilder::default()
+            .codex_home(codex_home.path().to_path_buf())
+            .build()
+            .await
+            .expect("load default test config");
+        let manager = ModelsManager::with_provider(
+            AuthManager::from_auth_for_testing(CodexAuth::from_api_key("Test API Key")),
+            create_oss_provider_with_base_url("http://example.test", WireApi::Responses),
+        );
+        let hidden = builtin_presets()
+            .iter()
+            .find(|preset| !preset.show_in_picker && preset.supports(Capability::Images))
+            .expect("a hidden built-in preset with image support");
+
+        let supporting =
+            PresetCatalog::new(&manager, &config).filter_by_capability(Capability::Images);
+
+        assert_eq!(
+            ids(supporting.iter()),
+            ids(PresetCatalog::static_presets(CatalogLoadOptions::default())
+                .filter(|preset| preset.supports(Capability::Images)))
+        );
+        assert!(!supporting.iter().any(|preset| preset.id == hidden.id));
+    }
+
+    #[tokio::test]
+    async fn default_is_a_listed_preset_outside_the_provider_catalogs() {
+        let server = MockServer::start().await;
+        let (_codex_home, config, manager) =
+            manager_with_catalog(&server, &["qwen2.5-coder-7b"]).await;
+
+        let default = PresetCatalog::new(&manager, &config)
+            .default()
+            .await
+            .expect("a default preset");
+
+        assert!(default.is_default);
+        assert!(default.show_in_picker);
+        assert_eq!(default.model_provider, None);
+    }
+
+    #[tokio::test]
+    async fn validate_options_reports_a_clamped_effort() {
+        let codex_home = TempDir::new().expect("temp dir");
+        let mut config = ConfigBuilder::default()
+            .codex_home(codex_home.path().to_path_buf())
+            .build()
+            .await
+            .expect("load default test config");
+        config.model = Some("gpt-5.1-codex-mini".to_string());
+        config.preset_overrides.insert(
+            "gpt-5.1-codex-mini".to_string(),
+            PresetOverride {
+                max_effort: Some(ReasoningEffort::Medium),
+                ..Default::default()
+            },
+        );
+        let manager = ModelsManager::new(AuthManager::from_auth_for_testing(
+            CodexAuth::from_api_key("Test API Key"),
+        ));
+        let catalog = PresetCatalog::new(&manager, &config);
+
+        let report = catalog
+            .validate_options(Some(ReasoningEffort::High), &[Capability::Reasoning])
+            .await;
+        assert_eq!(
+            report,
+            SelectionReport {
+                model: "gpt-5.1-codex-mini".to_string(),
+                errors: Vec::new(),
+                warnings: vec![SelectionWarning::EffortClamped {
+                    model: "gpt-5.1-codex-mini".to_string(),
+                    requested: ReasoningEffort::High,
+                    effort: ReasoningEffort::Medium,
+                }],
+            }
+        );
+        assert!(report.is_valid());
+    }
+
+    #[tokio::test]
+    async fn resolve_effort_clamps_or_rejects_per_the_validation_policy() {
+        let codex_home = TempDir::new().expect("temp dir");
+        let mut config = ConfigBuilder::default()
+            .codex_home(codex_home.path().to_path_buf())
+            .build()
+            .await
+            .expect("load default test config");
+        config.model = Some("gpt-5.1-codex-mini".to_string());
+        let manager = ModelsManager::new(AuthManager::from_auth_for_testing(
+            CodexAuth::from_api_key("Test API Key"),
+        ));
+
+        let resolved = PresetCatalog::new(&manager, &config)
+            .resolve_effort(None, ReasoningEffort::Minimal)
+            .await
+            .expect("warn mode clamps");
+        assert_eq!(
+            resolved,
+            EffortResolution {
+                effort: ReasoningEffort::Medium,
+                warning: Some(
+                    "model `gpt-5.1-codex-mini` does not support reasoning effort `minimal`; it supports `medium`, `high`. Using `medium` instead."
+                        .to_string()
+                ),
+            }
+        );
+        assert_eq!(
+            PresetCatalog::new(&manager, &config)
+                .resolve_effort(Some("gpt-5.1-codex-mini"), ReasoningEffort::High)
+                .await,
+            Ok(EffortResolution {
+                effort: ReasoningEffort::High,
+                warning: None,
+            })
+        );
+
+        config.strict_model_validation = true;
+        let invalid = PresetCatalog::new(&manager, &config)
+            .resolve_effort(None, ReasoningEffort::Minimal)
+            .await
+            .expect_err("strict mode rejects");
+        assert!(matches!(
+            invalid,
+            InvalidSelection::UnsupportedEffort {
+                effort: ReasoningEffort::Minimal,
+                ..
+            }
+        ));
+    }
+}
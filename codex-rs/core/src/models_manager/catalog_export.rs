@@ -0,0 +1,399 @@
+//! Catalog exports, for reproducing a user's model picker locally.
+//!
+//! `codex models export` writes the merged catalog, hidden presets included,
+//! with where each listed preset came from and how every catalog was last
+//! fetched. Hosts are already reduced to `host[:port]` in that bookkeeping,
+//! and errors are redacted once more on the way out.
+//!
+//! Presets' request header values are redacted too, since a gateway header
+//! may hold a literal token.
+//!
+//! `codex models import --as-source` installs an export as
+//! `$CODEX_HOME/models_import.json`, scoped to the importing config's
+//! provider. While that file is there the
+//! [`ModelsManager`](super::manager::ModelsManager) lists exactly its presets
+//! for sessions on that provider and fetches no catalog, so the picker shows
+//! what the user saw. An imported preset never sends request headers: they
+//! came from someone else's machine. The file is never written by a refresh;
+//! `codex models import --clear` removes it.
+
+use std::io;
+use std::io::ErrorKind;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use chrono::DateTime;
+use chrono::Utc;
+use codex_protocol::openai_models::ModelPreset;
+use serde::Deserialize;
+use serde::Serialize;
+use thiserror::Error;
+use tracing::warn;
+
+use super::provenance::FetchRecord;
+use super::provenance::PresetProvenance;
+use super::provenance::ProviderFetchRecord;
+use super::provenance::REDACTED;
+use super::provenance::redact_error;
+use crate::config::types::ModelCatalogSource;
+
+/// The newest export layout this build reads, and the one it writes. Bump it
+/// whenever a field changes in a way older builds would misread.
+pub const CATALOG_EXPORT_SCHEMA_VERSION: u32 = 1;
+
+/// The imported catalog under `CODEX_HOME`.
+pub const IMPORTED_CATALOG_FILE: &str = "models_import.json";
+
+/// A catalog as one process saw it.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CatalogExport {
+    pub schema_version: u32,
+    /// Version of the Codex that wrote the export.
+    pub codex_version: String,
+    pub exported_at: DateTime<Utc>,
+    /// The catalog revision at export.
+    pub revision: u64,
+    /// Provider the catalog was listed for. An installed import only applies
+    /// to sessions on this provider; exports written before it was recorded
+    /// apply to none.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model_provider_id: Option<String>,
+    /// `model_catalog_sources`, highest precedence first.
+    pub sources: Vec<ModelCatalogSource>,
+    pub remote: FetchRecord,
+    pub providers: Vec<ProviderFetchRecord>,
+    /// Where each preset the picker listed came from.
+    pub provenance: Vec<PresetProvenance>,
+    /// Every preset the catalogs listed, hidden ones included, in catalog
+    /// order.
+    pub presets: Vec<ModelPreset>,
+}
+
+/// Why an export could not be imported.
+#[derive(Debug, Error)]
+pub enum CatalogImportError {
+    #[error("failed to read {}: {source}", path.display())]
+    Read { path: PathBuf, source: io::Error },
+    #[error("{} is not a catalog export: {source}", path.display())]
+    Parse {
+        path: PathBuf,
+        source: serde_json::Error,
+    },
+    #[error(
+        "{} has catalog schema version {found}, but this Codex reads versions up to {supported}; import it with a newer Codex",
+        path.display()
+    )]
+    NewerSchema {
+        path: PathBuf,
+        found: u32,
+        supported: u32,
+    },
+}
+
+/// The imported catalog a manager lists, as `/status` and
+/// `codex models status` describe it.
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct ImportedCatalogInfo {
+    pub path: PathBuf,
+    pub codex_version: String,
+    pub exported_at: DateTime<Utc>,
+    /// The revision the catalog had where it was exported.
+    pub revision: u64,
+}
+
+/// An export installed with `codex models import --as-source`, as loaded by
+/// a manager.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct ImportedCatalog {
+    pub(crate) path: PathBuf,
+    pub(crate) export: Arc<CatalogExport>,
+}
+
+impl ImportedCatalog {
+    pub(crate) fn info(&self) -> ImportedCatalogInfo {
+        self.export.info(&self.path)
+    }
+
+    /// Whether sessions on `provider_id` list this catalog.
+    pub(crate) fn applies_to(&self, provider_id: &str) -> bool {
+        self.export.model_provider_id.as_deref() == Some(provider_id)
+    }
+}
+
+#[derive(Deserialize)]
+struct SchemaVersion {
+    schema_version: u32,
+}
+
+impl CatalogExport {
+    /// Redact what the fetch records say about failures, which may quote
+    /// tokens or full URLs, and the values of the presets' request headers,
+    /// which may be tokens themselves.
+    pub(crate) fn redacted(mut self) -> Self {
+        let redact = |error: &mut Option<String>| {
+            if let Some(message) = error {
+                *message = redact_error(message);
+            }
+        };
+        redact(&mut self.remote.last_error);
+        for provider in &mut self.providers {
+            redact(&mut provider.last_error);
+        }
+        for headers in self
+            .presets
+            .iter_mut()
+            .filter_map(|preset| preset.extra_request_headers.as_mut())
+        {
+            for value in headers.values_mut() {
+                *value = REDACTED.to_string();
+            }
+        }
+        self
+    }
+
+    /// The export as installed for sessions on `provider_id`: scoped to that
+    /// provider, with every request header dropped. How many presets had
+    /// headers dropped.
+    pub fn scoped_to(mut self, provider_id: &str) -> (Self, usize) {
+        self.model_provider_id = Some(provider_id.to_string());
+        let dropped = self.drop_request_headers();
+        (self, dropped)
+    }
+
+    fn drop_request_headers(&mut self) -> usize {
+        self.presets
+            .iter_mut()
+            .filter_map(|preset| preset.extra_request_headers.take())
+            .count()
+    }
+
+    pub fn to_json(&self) -> serde_json::Result<Vec<u8>> {
+        serde_json::to_vec_pretty(self)
+    }
+
+    /// Parse an export read from `path`. An export newer than
+    /// [`CATALOG_EXPORT_SCHEMA_VERSION`] is refused rather than read
+    /// partially.
+    pub fn from_json(path: &Path, contents: &[u8]) -> Result<Self, CatalogImportError> {
+        let parse_error = |source| CatalogImportError::Parse {
+            path: path.to_path_buf(),
+            source,
+        };
+        let SchemaVersion { schema_version } =
+            serde_json::from_slice(contents).map_err(parse_error)?;
+        if schema_version > CATALOG_EXPORT_SCHEMA_VERSION {
+            return Err(CatalogImportError::NewerSchema {
+                path: path.to_path_buf(),
+                found: schema_version,
+                supported: CATALOG_EXPORT_SCHEMA_VERSION,
+            });
+        }
+        serde_json::from_slice(contents).map_err(parse_error)
+    }
+
+    pub async fn read(path: &Path) -> Result<Self, CatalogImportError> {
+        let contents = tokio::fs::read(path)
+            .await
+            .map_err(|source| CatalogImportError::Read {
+                path: path.to_path_buf(),
+                source,
+            })?;
+        Self::from_json(path, &contents)
+    }
+
+    pub fn info(&self, path: &Path) -> ImportedCatalogInfo {
+        ImportedCatalogInfo {
+            path: path.to_path_buf(),
+            codex_version: self.codex_version.clone(),
+            exported_at: self.exported_at,
+            revision: self.revision,
+        }
+    }
+}
+
+/// Install `export` as the catalog every manager under `codex_home` created
+/// from now on lists for sessions on the provider it is scoped to, replacing
+/// any earlier import. See [`CatalogExport::scoped_to`].
+pub async fn install_imported_catalog(
+    codex_home: &Path,
+    export: &CatalogExport,
+) -> io::Result<PathBuf> {
+    let path = codex_home.join(IMPORTED_CATALOG_FILE);
+    tokio::fs::create_dir_all(codex_home).await?;
+    tokio::fs::write(&path, export.to_json()?).await?;
+    Ok(path)
+}
+
+/// Remove the imported catalog under `codex_home`. Whether there was one.
+pub async fn remove_imported_catalog(codex_home: &Path) -> io::Result<bool> {
+    match tokio::fs::remove_file(codex_home.join(IMPORTED_CATALOG_FILE)).await {
+        Ok(()) => Ok(true),
+        Err(err) if err.kind() == ErrorKind::NotFound => Ok(false),
+        Err(err) => Err(err),
+    }
+}
+
+/// The imported catalog under `codex_home`, if there is one this build can
+/// read. Anything else is logged and ignored, so a bad file never keeps
+/// Codex from listing models.
+pub(crate) fn load_imported_catalog(codex_home: &Path) -> Option<ImportedCatalog> {
+    let path = codex_home.join(IMPORTED_CATALOG_FILE);
+    let contents = match std::fs::read(&path) {
+        Ok(contents) => contents,
+        Err(err) if err.kind() == ErrorKind::NotFound => return None,
+        Err(err) => {
+            warn!("failed to read {}: {err}", path.display());
+            return None;
+        }
+    };
+    match CatalogExport::from_json(&path, &contents) {
+        Ok(mut export) => {
+            // Installed imports carry no headers; one placed by hand might.
+            if export.drop_request_headers() > 0 {
+                warn!(
+                    "ignoring the request headers of the imported catalog at {}",
+                    path.display()
+                );
+            }
+            Some(ImportedCatalog {
+                path,
+                export: Arc::new(export),
+            })
+        }
+        Err(err) => {
+            warn!("ignoring the imported catalog: {err}");
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models_manager::provenance::PresetSource;
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+    use std::collections::HashMap;
+    use tempfile::TempDir;
+
+    fn export() -> CatalogExport {
+        CatalogExport {
+            schema_version: CATALOG_EXPORT_SCHEMA_VERSION,
+            codex_version: "0.0.0".to_string(),
+            exported_at: DateTime::parse_from_rfc3339("2025-06-01T12:00:00Z")
+                .expect("timestamp")
+                .with_timezone(&Utc),
+            revision: 4,
+            model_provider_id: Some("openai".to_string()),
+            sources: ModelCatalogSource::DEFAULT_ORDER.to_vec(),
+            remote: FetchRecord {
+                host: Some("chatgpt.com".to_string()),
+                last_error: Some(
+                    "request to https://chatgpt.com/backend-api/codex/models?token=abc failed"
+                        .to_string(),
+                ),
+                ..FetchRecord::default()
+            },
+            providers: Vec::new(),
+            provenance: vec![PresetProvenance {
+                id: "gpt-test".to_string(),
+                source: PresetSource::Builtin,
+                overridden: false,
+            }],
+            presets: vec![
+                ModelPreset::builder("gpt-test", "gpt-test")
+                    .extra_request_headers(HashMap::from([(
+                        "x-gateway-token".to_string(),
+                        "tok-literal".to_string(),
+                    )]))
+                    .build()
+                    .expect("preset"),
+            ],
+        }
+    }
+
+    #[test]
+    fn exports_redact_request_header_values() {
+        let export = export().redacted();
+
+        assert_eq!(
+            export.presets[0].extra_request_headers,
+            Some(HashMap::from([(
+                "x-gateway-token".to_string(),
+                REDACTED.to_string()
+            )]))
+        );
+    }
+
+    #[test]
+    fn imports_are_scoped_to_the_target_provider_without_headers() {
+        let (export, dropped) = export().scoped_to("gateway");
+        let imported = ImportedCatalog {
+            path: PathBuf::from("models_import.json"),
+            export: Arc::new(export),
+        };
+
+        assert_eq!(dropped, 1);
+        assert_eq!(imported.export.presets[0].extra_request_headers, None);
+        assert!(imported.applies_to("gateway"));
+        assert!(!imported.applies_to("openai"));
+    }
+
+    #[tokio::test]
+    async fn an_installed_export_loads_back_unchanged() {
+        let codex_home = TempDir::new().expect("temp dir");
+        let (export, _) = export().redacted().scoped_to("openai");
+        assert!(
+            !export
+                .remote
+                .last_error
+                .as_deref()
+                .unwrap_or_default()
+                .contains("token=abc"),
+            "{:?}",
+            export.remote.last_error
+        );
+
+        let path = install_imported_catalog(codex_home.path(), &export)
+            .await
+            .expect("install");
+        assert_eq!(CatalogExport::read(&path).await.expect("read"), export);
+        assert_eq!(
+            load_imported_catalog(codex_home.path()),
+            Some(ImportedCatalog {
+                path,
+                export: Arc::new(export),
+            })
+        );
+
+        assert!(
+            remove_imported_catalog(codex_home.path())
+                .await
+                .expect("remove")
+        );
+        assert_eq!(load_imported_catalog(codex_home.path()), None);
+        assert!(
+            !remove_imported_catalog(codex_home.path())
+                .await
+                .expect("remove again")
+        );
+    }
+
+    #[test]
+    fn exports_from_a_newer_schema_are_refused() {
+        let mut newer = serde_json::to_value(export()).expect("serialize");
+        newer["schema_version"] = json!(CATALOG_EXPORT_SCHEMA_VERSION + 1);
+        // A newer layout may change any field; the version is checked first.
+        newer["presets"] = json!("a newer shape");
+        let contents = serde_json::to_vec(&newer).expect("serialize");
+
+        let err =
+            CatalogExport::from_json(Path::new("cat.json"), &contents).expect_err("newer schema");
+        assert!(
+            matches!(err, CatalogImportError::NewerSchema { found, .. } if found == CATALOG_EXPORT_SCHEMA_VERSION + 1),
+            "{err}"
+        );
+        assert!(err.to_string().contains("newer Codex"), "{err}");
+    }
+}
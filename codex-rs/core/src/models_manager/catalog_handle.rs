@@ -0,0 +1,471 @@
+//! A long-lived view of the picker presets, for code embedding codex-core
+//! directly rather than through the app server.
+//!
+//! A [`ModelCatalogHandle`] keeps the presets of a [`PresetCatalog`] loaded
+//! and republishes them whenever they change: after each background refresh,
+//! one per jittered cache TTL, skipped when the catalogs were just refreshed
+//! on request; after [`ModelCatalogHandle::reload_config`] or
+//! [`ModelCatalogHandle::auth_changed`]; and when a refresh started anywhere
+//! else, such as the model picker, changes the [`ModelsManager`]'s catalog.
+//! Triggers that arrive together or during a rebuild are coalesced into one
+//! rebuild, see [`rebuild_queue`](super::rebuild_queue). The background
+//! refresher stops once every handle is dropped.
+
+use std::sync::Arc;
+use std::sync::PoisonError;
+use std::sync::RwLock;
+use std::time::Duration;
+use std::time::Instant;
+
+use chrono::Utc;
+use codex_protocol::openai_models::ModelPreset;
+use serde::Deserialize;
+use serde::Serialize;
+use tokio::sync::watch;
+use tokio::task::JoinHandle;
+
+use crate::config::Config;
+use crate::models_manager::catalog::CatalogLoadOptions;
+use crate::models_manager::catalog::PresetCatalog;
+use crate::models_manager::manager::ModelsManager;
+use crate::models_manager::rebuild_queue::CatalogRebuild;
+use crate::models_manager::rebuild_queue::RebuildQueue;
+use crate::models_manager::rebuild_queue::RebuildReason;
+
+/// Counts the preset lists a [`ModelCatalogHandle`] has published. Zero is
+/// the list loaded when the handle was created; each rebuild that changes
+/// the presets adds one, and so does each change made elsewhere in the
+/// [`ModelsManager`].
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
+#[serde(transparent)]
+pub struct CatalogRevision(pub u64);
+
+/// Cheap to clone; all clones share one refresher.
+#[derive(Clone)]
+pub struct ModelCatalogHandle {
+    shared: Arc<Shared>,
+    _refresher: Arc<Refresher>,
+}
+
+struct Shared {
+    models_manager: Arc<ModelsManager>,
+    config: RwLock<Arc<Config>>,
+    presets: RwLock<Arc<[ModelPreset]>>,
+    revision: watch::Sender<CatalogRevision>,
+    rebuilds: RebuildQueue,
+}
+
+/// Aborts the refresher when the last handle goes away.
+struct Refresher(JoinHandle<()>);
+
+impl Drop for Refresher {
+    fn drop(&mut self) {
+        self.0.abort();
+    }
+}
+
+impl ModelCatalogHandle {
+    /// Start watching the catalog `models_manager` serves for `config`. The
+    /// presets already in memory are available at once; the first refresh
+    /// starts right away in the background. Must be called from within a
+    /// Tokio runtime.
+    pub fn new(models_manager: Arc<ModelsManager>, config: Config) -> Self {
+        let presets = PresetCatalog::new(&models_manager, &config)
+            .load_cached_or_static()
+            .unwrap_or_default();
+        let interval = models_manager.refresh_interval(&config);
+        let manager_revisions = models_manager.subscribe_catalog_revision();
+        let shared = Arc::new(Shared {
+            models_manager,
+            config: RwLock::new(Arc::new(config)),
+            presets: RwLock::new(presets.into()),
+            revision: watch::Sender::new(CatalogRevision::default()),
+            rebuilds: RebuildQueue::default(),
+        });
+        shared.rebuilds.request(RebuildReason::Started);
+        let refresher = tokio::spawn(run_refresher(
+            Arc::clone(&shared),
+            manager_revisions,
+            interval,
+        ));
+        Self {
+            shared,
+            _refresher: Arc::new(Refresher(refresher)),
+        }
+    }
+
+    /// The presets as last published.
+    pub fn current(&self) -> Arc<[ModelPreset]> {
+        Arc::clone(
+            &self
+                .shared
+                .presets
+                .read()
+                .unwrap_or_else(PoisonError::into_inner),
+        )
+    }
+
+    /// Watch for newly published presets; read them with [`Self::current`].
+    /// The channel closes once every handle is dropped.
+    pub fn subscribe(&self) -> watch::Receiver<CatalogRevision> {
+        self.shared.revision.subscribe()
+    }
+
+    /// Refresh every catalog now instead of waiting for the next background
+    /// refresh, bypassing the on-disk cache.
+    pub fn refresh(&self) {
+        self.shared.rebuilds.request(RebuildReason::Manual);
+    }
+
+    /// Like [`Self::refresh`], and resolve to the rebuild that took the
+    /// request, which may also have run for other triggers queued with it.
+    /// The refresh is queued at once, not when the future is first polled.
+    pub fn refresh_and_wait(&self) -> impl Future<Output = CatalogRebuild> + use<> {
+        let ticket = self.shared.rebuilds.request(RebuildReason::Manual);
+        let handle = self.clone();
+        async move { handle.shared.rebuilds.wait_for(ticket).await }
+    }
+
+    /// The last rebuild that finished and every trigger it ran for, or `None`
+    /// before the first one.
+    pub fn last_rebuild(&self) -> Option<CatalogRebuild> {
+        self.shared.rebuilds.last()
+    }
+
+    /// List presets for `config` from now on, e.g. after the embedder reloaded
+    /// `config.toml`, and refresh with it.
+    pub fn reload_config(&self, config: Config) {
+        *self
+            .shared
+            .config
+            .write()
+            .unwrap_or_else(PoisonError::into_inner) = Arc::new(config);
+        self.shared.rebuilds.request(RebuildReason::ConfigReloaded);
+    }
+
+    /// Refresh after the embedder signed in, signed out, or otherwise changed
+    /// the credentials the [`ModelsManager`]'s `AuthManager` holds, since they
+    /// decide which models are listed.
+    pub fn auth_changed(&self) {
+        self.shared.rebuilds.request(RebuildReason::AuthChanged);
+    }
+}
+
+impl Shared {
+    fn config(&self) -> Arc<Config> {
+        Arc::clone(&self.config.read().unwrap_or_else(PoisonError::into_inner))
+    }
+
+    /// Refresh the catalogs for `reasons`, bypassing the cache when a
+    /// refresh was asked for, and publish the presets under a new revision
+    /// if they changed.
+    async fn rebuild(&self, reasons: Vec<RebuildReason>) {
+        let config = self.config();
+        let error = if reasons.contains(&RebuildReason::Manual) {
+            self.models_manager
+                .refresh_all_models(&config)
+                .await
+                .err()
+                .map(|err| err.to_string())
+        } else {
+            None
+        };
+        let presets = PresetCatalog::new(&self.models_manager, &config)
+            .load(CatalogLoadOptions::default())
+            .await;
+        let changed = {
+            let mut current = self.presets.write().unwrap_or_else(PoisonError::into_inner);
+            let changed = **current != *presets;
+            if changed {
+                *current = presets.into();
+            }
+            changed
+        };
+        let revision = match *self.revision.borrow() {
+            CatalogRevision(revision) if changed => CatalogRevision(revision + 1),
+            unchanged => unchanged,
+        };
+        tracing::debug!(
+            ?reasons,
+            revision = revision.0,
+            changed,
+            "rebuilt the model catalog"
+        );
+        let rebuild = CatalogRebuild {
+            reasons,
+            revision,
+            finished_at: Utc::now(),
+            error,
+        };
+        self.models_manager
+            .record_catalog_rebuild(&config, rebuild.clone())
+            .await;
+        self.rebuilds.finished(rebuild);
+        // Published last, so a subscriber woken by the new revision finds the
+        // rebuild that made it already recorded.
+        if changed {
+            self.revision.send_replace(revision);
+        }
+    }
+
+    /// Publish the presets the manager already holds, if they changed.
+    async fn republish(&self) {
+        let config = self.config();
+        let presets = self.models_manager.known_models(&config).await;
+        {
+            let mut current = self.presets.write().unwrap_or_else(PoisonError::into_inner);
+            if **current == *presets {
+                return;
+            }
+            *current = presets.into();
+        }
+        self.revision.send_modify(|revision| revision.0 += 1);
+    }
+}
+
+async fn run_refresher(
+    shared: Arc<Shared>,
+    mut manager_revisions: watch::Receiver<u64>,
+    interval: Duration,
+) {
+    let mut due = Instant::now() + interval;
+    let mut reasons = shared.rebuilds.take();
+    loop {
+        if reasons.is_empty() {
+            shared.republish().await;
+        } else {
+            shared.rebuild(reasons).await;
+            due = Instant::now() + interval;
+        }
+        // The refresh above may itself have changed the manager's catalog.
+        manager_revisions.borrow_and_update();
+        reasons = tokio::select! {
+            _ = tokio::time::sleep_until(due.into()) => {
+                let rescheduled =
+                    next_due(due, shared.models_manager.last_manual_refresh(), interval);
+                let skipped = rescheduled > due;
+                due = rescheduled;
+                if !skipped {
+                    shared.rebuilds.request(RebuildReason::Scheduled);
+                }
+                shared.rebuilds.take()
+            }
+            reasons = shared.rebuilds.next() => reasons,
+            changed = manager_revisions.changed() => {
+                if changed.is_err() {
+                    return;
+                }
+                Vec::new()
+            }
+        };
+    }
+}
+
+/// When the refresh scheduled for `due` should run instead: a full interval
+/// after a manual refresh that happened since the cycle before it, or `due`.
+fn next_due(due: Instant, manual_refresh: Option<Instant>, interval: Duration) -> Instant {
+    manual_refresh.map_or(due, |manual| due.max(manual + interval))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::AuthManager;
+    use crate::CodexAuth;
+    use crate::config::ConfigBuilder;
+    use crate::model_provider_info::ModelCatalog;
+    use crate::model_provider_info::ModelProviderInfo;
+    use crate::model_provider_info::WireApi;
+    use crate::model_provider_info::create_oss_provider_with_base_url;
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+    use tempfile::TempDir;
+    use wiremock::Mock;
+    use wiremock::MockServer;
+    use wiremock::ResponseTemplate;
+    use wiremock::matchers::method;
+    use wiremock::matchers::path;
+
+    async fn serve_models(server: &MockServer, models: &[&str]) {
+        let data: Vec<serde_json::Value> = models
+            .iter()
+            .map(|id| json!({"id": id, "object": "model"}))
+            .collect();
+        server.reset().await;
+        Mock::given(method("GET"))
+            .and(path("/models"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(json!({"object": "list", "data": data})),
+            )
+            .mount(server)
+            .await;
+    }
+
+    fn provider_ids(presets: &[ModelPreset]) -> Vec<&str> {
+        let mut ids: Vec<&str> = presets
+            .iter()
+            .filter(|preset| preset.model_provider.is_some())
+            .map(|preset| preset.id.as_str())
+            .collect();
+        ids.sort_unstable();
+        ids
+    }
+
+    #[test]
+    fn a_manual_refresh_pushes_back_the_next_cycle() {
+        let interval = Duration::from_secs(300);
+        let start = Instant::now();
+        let due = start + interval;
+        assert_eq!(next_due(due, None, interval), due);
+        // Refreshed when the previous cycle ran: this one is still due.
+        assert_eq!(next_due(due, Some(start), interval), due);
+        // Refreshed a minute before the cycle: it starts over from there.
+        let manual = due - Duration::from_secs(60);
+        assert_eq!(next_due(due, Some(manual), interval), manual + interval);
+    }
+
+    #[tokio::test]
+    async fn embedder_observes_a_refresh_through_the_handle() {
+        let server = MockServer::start().await;
+        serve_models(&server, &["qwen2.5-coder-7b"]).await;
+        let codex_home = TempDir::new().expect("temp dir");
+        let mut config = ConfigBuilder::default()
+            .codex_home(codex_home.path().to_path_buf())
+            .build()
+            .await
+            .expect("load default test config");
+        config.model_providers.insert(
+            "vllm".to_string(),
+            ModelProviderInfo {
+                name: "vLLM".into(),
+                model_catalog: Some(ModelCatalog::Openai),
+                ..create_oss_provider_with_base_url(&server.uri(), WireApi::Responses)
+            },
+        );
+        let auth_manager =
+            AuthManager::from_auth_for_testing(CodexAuth::from_api_key("Test API Key"));
+        let manager = Arc::new(ModelsManager::with_provider(
+            auth_manager,
+            create_oss_provider_with_base_url("http://example.test", WireApi::Responses),
+        ));
+
+        // The embedder creates a handle and waits for the first refresh.
+        let handle = ModelCatalogHandle::new(Arc::clone(&manager), config);
+        let mut revisions = handle.subscribe();
+        revisions
+            .wait_for(|revision| *revision >= CatalogRevision(1))
+            .await
+            .expect("the first refresh publishes");
+        assert_eq!(
+            provider_ids(&handle.current()),
+            vec!["vllm/qwen2.5-coder-7b"]
+        );
+
+        // The provider starts serving another model; a refresh picks it up.
+        serve_models(&server, &["qwen2.5-coder-7b", "llama-3.1-8b"]).await;
+        handle.refresh();
+        revisions
+            .wait_for(|revision| *revision >= CatalogRevision(2))
+            .await
+            .expect("the refresh publishes");
+        assert_eq!(
+            provider_ids(&handle.current()),
+            vec!["vllm/llama-3.1-8b", "vllm/qwen2.5-coder-7b"]
+        );
+
+        // Dropping the last handle stops the refresher and closes the channel.
+        drop(handle);
+        assert!(revisions.changed().await.is_err());
+    }
+
+    #[tokio::test]
+    async fn triggers_during_a_rebuild_coalesce_into_one_more() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/models"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(json!({
+                        "object": "list",
+                        "data": [{"id": "qwen2.5-coder-7b", "object": "model"}],
+                    }))
+                    .set_delay(Duration::from_millis(200)),
+            )
+            .mount(&server)
+            .await;
+        let codex_home = TempDir::new().expect("temp dir");
+        let mut config = ConfigBuilder::default()
+            .codex_home(codex_home.path().to_path_buf())
+            .build()
+            .await
+            .expect("load default test config");
+        config.model_providers.insert(
+            "vllm".to_string(),
+            ModelProviderInfo {
+                name: "vLLM".into(),
+                model_catalog: Some(ModelCatalog::Openai),
+                ..create_oss_provider_with_base_url(&server.uri(), WireApi::Responses)
+            },
+        );
+        let auth_manager =
+            AuthManager::from_auth_for_testing(CodexAuth::from_api_key("Test API Key"));
+        let manager = Arc::new(ModelsManager::with_provider(
+            auth_manager,
+            create_oss_provider_with_base_url("http://example.test", WireApi::Responses),
+        ));
+
+        let handle = ModelCatalogHandle::new(Arc::clone(&manager), config.clone());
+        let mut revisions = handle.subscribe();
+        let observer = tokio::spawn({
+            let mut revisions = handle.subscribe();
+            async move {
+                let mut seen = Vec::new();
+                while revisions.changed().await.is_ok() {
+                    seen.push(*revisions.borrow_and_update());
+                }
+                seen
+            }
+        });
+        // Wait until the first rebuild is waiting on the provider.
+        while server
+            .received_requests()
+            .await
+            .unwrap_or_default()
+            .is_empty()
+        {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+
+        handle.reload_config(config.clone());
+        handle.auth_changed();
+        let refreshed = handle.refresh_and_wait();
+        handle.reload_config(config);
+
+        let last = refreshed.await;
+        assert_eq!(handle.last_rebuild(), Some(last.clone()));
+        assert_eq!(manager.last_catalog_rebuild(), Some(last.clone()));
+        assert_eq!(
+            last.reasons,
+            vec![
+                RebuildReason::ConfigReloaded,
+                RebuildReason::AuthChanged,
+                RebuildReason::Manual,
+            ]
+        );
+        assert_eq!(last.error, None);
+        // The provider still lists the same model, so the follow-up rebuild
+        // published nothing new.
+        assert_eq!(last.revision, CatalogRevision(1));
+
+        // Nothing was left over for a third rebuild.
+        tokio::time::sleep(Duration::from_millis(300)).await;
+        assert_eq!(handle.last_rebuild(), Some(last));
+        assert_eq!(*revisions.borrow(), CatalogRevision(1));
+
+        drop(handle);
+        let seen = observer.await.expect("observer");
+        assert_eq!(seen, vec![CatalogRevision(1)]);
+    }
+}
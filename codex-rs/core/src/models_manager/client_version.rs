@@ -0,0 +1,127 @@
+//! The minimum client version a catalog recommends.
+//!
+//! A gateway that wants to retire old protocol behaviors sets
+//! `min_client_version` on its `/models` response. It never stops an older
+//! client from working: such a client only gets a notice asking to upgrade.
+
+use std::cmp::Ordering;
+use std::fmt;
+
+use semver::Version;
+use serde::Serialize;
+use tracing::warn;
+
+/// The version of this build.
+pub const CLIENT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// The running client is older than its catalog recommends.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ClientVersionNotice {
+    /// The version the catalog recommends at least, as it was served.
+    pub min_client_version: String,
+    pub client_version: String,
+}
+
+impl fmt::Display for ClientVersionNotice {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "your Codex version ({}) is below the minimum recommended by your gateway ({}); please upgrade",
+            self.client_version, self.min_client_version
+        )
+    }
+}
+
+/// The notice to show when `client_version` is below the highest of the
+/// `min_client_versions` the catalogs recommend. A minimum that is not a
+/// version is logged and ignored.
+pub(crate) fn client_version_notice<'a>(
+    min_client_versions: impl IntoIterator<Item = &'a str>,
+    client_version: &str,
+) -> Option<ClientVersionNotice> {
+    let current = parse_version(client_version)?;
+    let (min_client_version, minimum) = min_client_versions
+        .into_iter()
+        .filter_map(|min_client_version| match parse_version(min_client_version) {
+            Some(minimum) => Some((min_client_version, minimum)),
+            None => {
+                warn!(
+                    "ignoring malformed min_client_version `{min_client_version}` from the catalog"
+                );
+                None
+            }
+        })
+        .max_by(|(_, left), (_, right)| left.cmp_precedence(right))?;
+    (current.cmp_precedence(&minimum) == Ordering::Less).then(|| ClientVersionNotice {
+        min_client_version: min_client_version.to_string(),
+        client_version: client_version.to_string(),
+    })
+}
+
+/// `version` as semver, allowing surrounding whitespace and a leading `v`.
+/// Build metadata is parsed but never decides an ordering.
+fn parse_version(version: &str) -> Option<Version> {
+    let version = version.trim();
+    Version::parse(version.strip_prefix('v').unwrap_or(version)).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn below(min_client_version: &str, client_version: &str) -> bool {
+        client_version_notice([min_client_version], client_version).is_some()
+    }
+
+    #[test]
+    fn compares_versions_as_semver() {
+        assert!(below("0.60.0", "0.59.9"));
+        assert!(below("1.0.0", "0.99.99"));
+        assert!(below("0.10.0", "0.9.0"), "numeric, not lexical");
+        assert!(!below("0.60.0", "0.60.0"));
+        assert!(!below("0.60.0", "0.61.0"));
+
+        // A prerelease comes before its release.
+        assert!(below("0.60.0", "0.60.0-alpha.3"));
+        assert!(!below("0.60.0-alpha.3", "0.60.0"));
+        assert!(below("0.60.0-alpha.10", "0.60.0-alpha.9"));
+        assert!(below("0.60.0-beta", "0.60.0-alpha.9"));
+
+        // A leading `v` and build metadata are ignored.
+        assert!(below("v0.60.0", "0.59.0"));
+        assert!(!below("0.60.0+build.7", "0.60.0"));
+    }
+
+    #[test]
+    fn malformed_versions_are_ignored() {
+        for malformed in [
+            "", "latest", "0.60", "0.60.0.1", "0.6x.0", "0.60.0-", "-1.0.0",
+        ] {
+            assert_eq!(
+                client_version_notice([malformed], "0.1.0"),
+                None,
+                "{malformed:?}"
+            );
+        }
+        assert_eq!(client_version_notice([], "0.1.0"), None);
+        assert_eq!(client_version_notice(["0.60.0"], "dev"), None);
+    }
+
+    #[test]
+    fn the_highest_minimum_wins() {
+        let notice = client_version_notice(["0.58.0", "latest", "0.61.0", "0.60.0"], "0.59.0")
+            .expect("below the highest minimum");
+        assert_eq!(notice.min_client_version, "0.61.0");
+        assert_eq!(client_version_notice(["0.58.0", "0.59.0"], "0.59.0"), None);
+    }
+
+    #[test]
+    fn notice_names_both_versions() {
+        let notice = client_version_notice(["0.60.0"], "0.59.0").expect("below minimum");
+        assert_eq!(
+            notice.to_string(),
+            "your Codex version (0.59.0) is below the minimum recommended by your gateway (0.60.0); please upgrade"
+        );
+    }
+}
@@ -0,0 +1,365 @@
+use std::collections::BTreeMap;
+use std::fmt;
+
+use codex_protocol::openai_models::ModelPreset;
+use codex_protocol::openai_models::ReasoningEffort;
+
+/// What changed between two preset catalogs. Presets are matched by `id`;
+/// every list is sorted by id so the result does not depend on catalog order.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CatalogDiff {
+    /// Ids present only in the new catalog.
+    pub added: Vec<String>,
+    /// Ids present only in the old catalog.
+    pub removed: Vec<String>,
+    /// Presets present in both catalogs whose tracked fields differ.
+    pub changed: Vec<PresetChange>,
+}
+
+/// Field-level changes to a single preset.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PresetChange {
+    pub id: String,
+    pub changes: Vec<FieldChange>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FieldChange {
+    Description {
+        old: String,
+        new: String,
+    },
+    ContextWindow {
+        old: Option<i64>,
+        new: Option<i64>,
+    },
+    IsDefault {
+        old: bool,
+        new: bool,
+    },
+    Efforts {
+        old: Vec<ReasoningEffort>,
+        new: Vec<ReasoningEffort>,
+    },
+}
+
+impl CatalogDiff {
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+}
+
+/// Compare two catalogs. When a catalog lists the same id twice, the first
+/// entry wins.
+pub fn diff_catalogs(old: &[ModelPreset], new: &[ModelPreset]) -> CatalogDiff {
+    let old = index_by_id(old);
+    let new = index_by_id(new);
+
+    let added = new
+        .keys()
+        .filter(|id| !old.contains_key(*id))
+        .map(|id| (*id).to_string())
+        .collect();
+    let removed = old
+        .keys()
+        .filter(|id| !new.contains_key(*id))
+        .map(|id| (*id).to_string())
+        .collect();
+    let changed = old
+        .iter()
+        .filter_map(|(id, old_preset)| {
+            let new_preset = new.get(id)?;
+            let changes = field_changes(old_preset, new_preset);
+            (!changes.is_empty()).then(|| PresetChange {
+                id: (*id).to_string(),
+                changes,
+            })
+        })
+        .collect();
+
+    CatalogDiff {
+        added,
+        removed,
+        changed,
+    }
+}
+
+fn index_by_id(presets: &[ModelPreset]) -> BTreeMap<&str, &ModelPreset> {
+    let mut index = BTreeMap::new();
+    for preset in presets {
+        index.entry(preset.id.as_str()).or_insert(preset);
+    }
+    index
+}
+
+fn field_changes(old: &ModelPreset, new: &ModelPreset) -> Vec<FieldChange> {
+    let mut changes = Vec::new();
+    if old.description != new.description {
+        changes.push(FieldChange::Description {
+            old: old.description.clone(),
+            new: new.description.clone(),
+        });
+    }
+    if old.context_window != new.context_window {
+        changes.push(FieldChange::ContextWindow {
+            old: old.context_window,
+            new: new.context_window,
+        });
+    }
+    if old.is_default != new.is_default {
+        changes.push(FieldChange::IsDefault {
+            old: old.is_default,
+            new: new.is_default,
+        });
+    }
+    let old_efforts = efforts(old);
+    let new_efforts = efforts(new);
+    if old_efforts != new_efforts {
+        changes.push(FieldChange::Efforts {
+            old: old_efforts,
+            new: new_efforts,
+        });
+    }
+    changes
+}
+
+fn efforts(preset: &ModelPreset) -> Vec<ReasoningEffort> {
+    preset
+        .supported_reasoning_efforts
+        .iter()
+        .map(|preset| preset.effort)
+        .collect()
+}
+
+/// Renders a one-line summary such as
+/// `+2 models, gpt-5 context window 272k→400k`.
+impl fmt::Display for CatalogDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_empty() {
+            return f.write_str("no changes");
+        }
+        let mut parts = Vec::new();
+        if !self.added.is_empty() {
+            parts.push(format!("+{}", model_count(self.added.len())));
+        }
+        if !self.removed.is_empty() {
+            parts.push(format!("-{}", model_count(self.removed.len())));
+        }
+        for PresetChange { id, changes } in &self.changed {
+            for change in changes {
+                parts.push(match change {
+                    FieldChange::Description { .. } => format!("{id} description changed"),
+                    FieldChange::ContextWindow { old, new } => format!(
+                        "{id} context window {}→{}",
+                        format_tokens(*old),
+                        format_tokens(*new)
+                    ),
+                    FieldChange::IsDefault { new: true, .. } => format!("{id} is now the default"),
+                    FieldChange::IsDefault { new: false, .. } => {
+                        format!("{id} is no longer the default")
+                    }
+                    FieldChange::Efforts { old, new } => format!(
+                        "{id} efforts {}→{}",
+                        format_efforts(old),
+                        format_efforts(new)
+                    ),
+                });
+            }
+        }
+        f.write_str(&parts.join(", "))
+    }
+}
+
+fn model_count(count: usize) -> String {
+    if count == 1 {
+        "1 model".to_string()
+    } else {
+        format!("{count} models")
+    }
+}
+
+fn format_tokens(tokens: Option<i64>) -> String {
+    match tokens {
+        Some(tokens) if tokens != 0 && tokens % 1000 == 0 => format!("{}k", tokens / 1000),
+        Some(tokens) => tokens.to_string(),
+        None => "unknown".to_string(),
+    }
+}
+
+fn format_efforts(efforts: &[ReasoningEffort]) -> String {
+    if efforts.is_empty() {
+        return "none".to_string();
+    }
+    efforts
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models_manager::model_presets::builtin_model_presets;
+    use codex_protocol::openai_models::ReasoningEffortPreset;
+    use pretty_assertions::assert_eq;
+
+    fn catalog() -> Vec<ModelPreset> {
+        builtin_model_presets(None)
+    }
+
+    #[test]
+    fn reports_added_removed_and_changed_presets() {
+        let old = catalog();
+        let mut new = catalog();
+        let removed = new.remove(0);
+        let mut added = removed.clone();
+        added.id = "zz-new".to_string();
+        new.push(added.clone());
+        let mut second = added;
+        second.id = "zz-newer".to_string();
+        new.push(second);
+        let changed_id = new[0].id.clone();
+        let changed = &mut new[0];
+        changed.context_window = Some(400_000);
+        changed.description = "Updated".to_string();
+
+        let diff = diff_catalogs(&old, &new);
+
+        assert_eq!(
+            diff,
+            CatalogDiff {
+                added: vec!["zz-new".to_string(), "zz-newer".to_string()],
+                removed: vec![removed.id],
+                changed: vec![PresetChange {
+                    id: changed_id,
+                    changes: vec![
+                        FieldChange::Description {
+                            old: old[1].description.clone(),
+                            new: "Updated".to_string(),
+                        },
+                        FieldChange::ContextWindow {
+                            old: None,
+                            new: Some(400_000),
+                        },
+                    ],
+                }],
+            }
+        );
+    }
+
+    #[test]
+    fn summary_is_concise() {
+        let mut old = catalog();
+        old[0].context_window = Some(272_000);
+        let mut new = old.clone();
+        new[0].context_window = Some(400_000);
+        new[0].supported_reasoning_efforts = vec![ReasoningEffortPreset {
+            effort: ReasoningEffort::Low,
+            description: "low".to_string(),
+            max_output_tokens: None,
+            request_timeout_secs: None,
+            stream_idle_timeout_secs: None,
+        }];
+        new[0].is_default = !old[0].is_default;
+        for id in ["a", "b"] {
+            let mut extra = old[1].clone();
+            extra.id = id.to_string();
+            new.push(extra);
+        }
+        let id = old[0].id.clone();
+        let default_change = if new[0].is_default {
+            format!("{id} is now the default")
+        } else {
+            format!("{id} is no longer the default")
+        };
+        let old_efforts = format_efforts(&efforts(&old[0]));
+
+        assert_eq!(
+            diff_catalogs(&old, &new).to_string(),
+            format!(
+                "+2 models, {id} context window 272k→400k, {default_change}, {id} efforts {old_efforts}→low"
+            )
+        );
+        assert_eq!(diff_catalogs(&new, &new).to_string(), "no changes");
+    }
+
+    /// Deterministic xorshift so the property checks below are reproducible.
+    struct Rng(u64);
+
+    impl Rng {
+        fn next(&mut self) -> u64 {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 7;
+            self.0 ^= self.0 << 17;
+            self.0
+        }
+
+        fn below(&mut self, bound: usize) -> usize {
+            (self.next() % bound as u64) as usize
+        }
+    }
+
+    /// A random catalog drawn from the builtin presets: a random subset, with
+    /// random ids, descriptions, context windows, default flags, and efforts.
+    fn random_catalog(rng: &mut Rng) -> Vec<ModelPreset> {
+        let builtins = catalog();
+        (0..rng.below(8))
+            .map(|_| {
+                let mut preset = builtins[rng.below(builtins.len())].clone();
+                preset.id = format!("model-{}", rng.below(6));
+                preset.description = format!("description {}", rng.below(3));
+                preset.context_window = [None, Some(128_000), Some(272_000)][rng.below(3)];
+                preset.is_default = rng.below(2) == 0;
+                let keep = rng.below(preset.supported_reasoning_efforts.len() + 1);
+                preset.supported_reasoning_efforts.truncate(keep);
+                preset
+            })
+            .collect()
+    }
+
+    #[test]
+    fn diff_of_a_catalog_with_itself_is_empty() {
+        let mut rng = Rng(0x5eed);
+        for _ in 0..500 {
+            let catalog = random_catalog(&mut rng);
+            assert_eq!(diff_catalogs(&catalog, &catalog), CatalogDiff::default());
+        }
+    }
+
+    #[test]
+    fn reversing_a_diff_swaps_added_and_removed() {
+        let mut rng = Rng(0xc0ffee);
+        for _ in 0..500 {
+            let a = random_catalog(&mut rng);
+            let b = random_catalog(&mut rng);
+            let forward = diff_catalogs(&a, &b);
+            let backward = diff_catalogs(&b, &a);
+
+            assert_eq!(forward.added, backward.removed);
+            assert_eq!(forward.removed, backward.added);
+            let reversed: Vec<PresetChange> = backward
+                .changed
+                .into_iter()
+                .map(|PresetChange { id, changes }| PresetChange {
+                    id,
+                    changes: changes.into_iter().map(reverse).collect(),
+                })
+                .collect();
+            assert_eq!(forward.changed, reversed);
+        }
+    }
+
+    fn reverse(change: FieldChange) -> FieldChange {
+        match change {
+            FieldChange::Description { old, new } => {
+                FieldChange::Description { old: new, new: old }
+            }
+            FieldChange::ContextWindow { old, new } => {
+                FieldChange::ContextWindow { old: new, new: old }
+            }
+            FieldChange::IsDefault { old, new } => FieldChange::IsDefault { old: new, new: old },
+            FieldChange::Efforts { old, new } => FieldChange::Efforts { old: new, new: old },
+        }
+    }
+}
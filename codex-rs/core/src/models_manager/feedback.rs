@@ -0,0 +1,87 @@
+//! Model and catalog details for feedback reports.
+//!
+//! Every turn records a [`ModelFeedbackSnapshot`] as feedback tags, so a
+//! report filed afterwards says which preset the session last ran with, where
+//! that preset came from, and what state the catalog was in.
+
+use codex_protocol::openai_models::ReasoningEffort;
+use serde::Serialize;
+
+use crate::feedback_tags;
+use crate::models_manager::provenance::PresetSource;
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ModelFeedbackSnapshot {
+    /// Model slug sent to the provider.
+    pub model: String,
+    /// Catalog preset the model was selected from, when it is in the catalog.
+    pub preset_id: Option<String>,
+    pub preset_source: Option<PresetSource>,
+    /// The model's full context window, in tokens.
+    pub context_window: Option<i64>,
+    pub effort: Option<ReasoningEffort>,
+    /// ETag of the remote catalog in use.
+    pub catalog_revision: Option<String>,
+    pub catalog_fetched_at: Option<String>,
+    /// The most recent failed catalog refresh, with credentials and URLs
+    /// reduced to their origin.
+    pub catalog_error: Option<String>,
+}
+
+impl ModelFeedbackSnapshot {
+    /// Record the snapshot as feedback tags, replacing the previous turn's.
+    pub(crate) fn record(&self) {
+        feedback_tags!(
+            model = self.model,
+            preset_id = self.preset_id,
+            preset_source = self.preset_source,
+            context_window = self.context_window,
+            effort = self.effort,
+            catalog_revision = self.catalog_revision,
+            catalog_fetched_at = self.catalog_fetched_at,
+            catalog_error = self.catalog_error,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models_manager::provenance::CatalogSource;
+    use codex_feedback::CodexFeedback;
+    use pretty_assertions::assert_eq;
+    use tracing_subscriber::prelude::*;
+
+    #[test]
+    fn record_tags_the_feedback_report() {
+        let feedback = CodexFeedback::new();
+        let subscriber = tracing_subscriber::registry().with(feedback.metadata_layer());
+        let snapshot = ModelFeedbackSnapshot {
+            model: "gpt-5.1-codex".to_string(),
+            preset_id: Some("gpt-5.1-codex".to_string()),
+            preset_source: Some(PresetSource::Remote {
+                source: CatalogSource::Live,
+            }),
+            context_window: Some(272_000),
+            effort: Some(ReasoningEffort::High),
+            catalog_revision: Some("rev-1".to_string()),
+            catalog_fetched_at: Some("2025-01-01T00:00:00+00:00".to_string()),
+            catalog_error: Some("model list is 3h stale: gateway: http://gateway.test".to_string()),
+        };
+
+        tracing::subscriber::with_default(subscriber, || snapshot.record());
+
+        let report = feedback.snapshot(None);
+        let tag = |key: &str| report.tags().get(key).map(String::as_str);
+        assert_eq!(tag("model"), Some("\"gpt-5.1-codex\""));
+        assert_eq!(tag("preset_id"), Some("Some(\"gpt-5.1-codex\")"));
+        assert_eq!(tag("preset_source"), Some("Some(Remote { source: Live })"));
+        assert_eq!(tag("context_window"), Some("Some(272000)"));
+        assert_eq!(tag("effort"), Some("Some(High)"));
+        assert_eq!(tag("catalog_revision"), Some("Some(\"rev-1\")"));
+        assert_eq!(
+            tag("catalog_error"),
+            Some("Some(\"model list is 3h stale: gateway: http://gateway.test\")")
+        );
+    }
+}
@@ -0,0 +1,640 @@
+//! A dry run of a provider catalog fetch, for `codex debug model-fetch`.
+//!
+//! The request is built by the same [`ModelsClient`] a refresh uses, with the
+//! same headers and credentials, and sent through the provider's HTTP client.
+//! Before sending it, DNS, TCP connect, and the TLS handshake are probed one
+//! at a time on a separate socket so a failure can be pinned to a phase.
+//! Nothing is cached and no catalog state is touched.
+
+use std::fmt;
+use std::net::SocketAddr;
+use std::time::Duration;
+use std::time::Instant;
+
+use codex_api::ApiError;
+use codex_api::ReqwestTransport;
+use codex_api::TransportError;
+use codex_api::check_json_content;
+use codex_api::decode_azure_deployments;
+use codex_api::decode_openai_compatible_models;
+use codex_client::HttpTransport;
+use codex_client::Request;
+use codex_client::native_tls_config;
+use codex_client::pinned_tls_config;
+use codex_client::tls_handshake;
+use futures::StreamExt;
+use http::HeaderMap;
+use http::Uri;
+use hyper_util::client::proxy::matcher::Matcher;
+use serde::Serialize;
+use tokio::net::TcpStream;
+use tokio::net::lookup_host;
+use tokio::time::timeout;
+use url::Url;
+
+use super::locale::resolve_catalog_locale;
+use super::manager::catalog_headers;
+use super::manager::catalog_models_client;
+use super::provenance::REDACTED;
+use super::provenance::TokenFreshness;
+use super::provenance::redact_error;
+use super::request_headers::carries_credentials;
+use crate::api_bridge::map_api_error;
+use crate::config::Config;
+use crate::default_client::build_reqwest_client_for_catalog;
+use crate::default_client::get_codex_user_agent_with_suffix;
+use crate::default_client::originator;
+use crate::error::CodexErr;
+use crate::error::Result as CoreResult;
+use crate::model_provider_info::ModelCatalog;
+use crate::model_provider_info::ProviderCatalogConfig;
+
+/// Limit on each of the DNS, connect, and TLS probes.
+const PROBE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Limit on the catalog request itself when the provider sets none.
+const FETCH_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Longest failure detail kept from a response body.
+const MAX_DETAIL_CHARS: usize = 300;
+
+/// One step of the fetch, in the order they run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FetchPhase {
+    Dns,
+    Connect,
+    Tls,
+    FirstByte,
+    Total,
+    Parse,
+}
+
+const PHASES: [FetchPhase; 6] = [
+    FetchPhase::Dns,
+    FetchPhase::Connect,
+    FetchPhase::Tls,
+    FetchPhase::FirstByte,
+    FetchPhase::Total,
+    FetchPhase::Parse,
+];
+
+impl fmt::Display for FetchPhase {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            FetchPhase::Dns => "DNS",
+            FetchPhase::Connect => "connect",
+            FetchPhase::Tls => "TLS",
+            FetchPhase::FirstByte => "first byte",
+            FetchPhase::Total => "total",
+            FetchPhase::Parse => "parse",
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PhaseStatus {
+    Ok,
+    Failed,
+    /// Not applicable to this request, or not reached after a failure.
+    Skipped,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct PhaseReport {
+    pub phase: FetchPhase,
+    pub status: PhaseStatus,
+    /// How long the phase took. First byte and total are measured from when
+    /// the request was sent.
+    pub elapsed_ms: Option<u64>,
+    pub detail: String,
+}
+
+/// A request header as sent, with credentials masked.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct RedactedHeader {
+    pub name: String,
+    pub value: String,
+}
+
+/// What a dry-run catalog fetch did, safe to paste into a bug report.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct FetchDiagnosis {
+    pub provider_id: String,
+    pub catalog: ModelCatalog,
+    pub method: String,
+    /// The catalog URL without credentials or secret query values.
+    pub url: String,
+    /// The proxy the request goes through, reduced the same way.
+    pub proxy: Option<String>,
+    pub request_headers: Vec<RedactedHeader>,
+    /// Whether the request carried a cached or freshly minted
+    /// `token_command` token; `None` for other credentials.
+    pub token: Option<TokenFreshness>,
+    /// Every phase, in order, including skipped ones.
+    pub phases: Vec<PhaseReport>,
+    pub http_status: Option<u16>,
+    pub payload_bytes: Option<usize>,
+    pub model_count: Option<usize>,
+}
+
+impl FetchDiagnosis {
+    /// The phase that failed, if any did.
+    pub fn failed_phase(&self) -> Option<FetchPhase> {
+        self.phases
+            .iter()
+            .find(|report| report.status == PhaseStatus::Failed)
+            .map(|report| report.phase)
+    }
+
+    fn record(
+        &mut self,
+        phase: FetchPhase,
+        status: PhaseStatus,
+        started: Option<Instant>,
+        detail: String,
+    ) {
+        self.phases.push(PhaseReport {
+            phase,
+            status,
+            elapsed_ms: started
+                .map(|started| u64::try_from(started.elapsed().as_millis()).unwrap_or(u64::MAX)),
+            detail,
+        });
+    }
+
+    fn pass(&mut self, phase: FetchPhase, started: Instant, detail: String) {
+        self.record(phase, PhaseStatus::Ok, Some(started), detail);
+    }
+
+    fn skip(&mut self, phase: FetchPhase, detail: &str) {
+        self.record(phase, PhaseStatus::Skipped, None, detail.to_string());
+    }
+
+    /// Record `phase` as failed and every later phase as not reached.
+    fn fail(mut self, phase: FetchPhase, started: Instant, detail: &str) -> Self {
+        self.record(
+            phase,
+            PhaseStatus::Failed,
+            Some(started),
+            truncate_detail(&redact_error(detail)),
+        );
+        for later in PHASES.iter().skip_while(|&&p| p != phase).skip(1) {
+            self.skip(*later, "not reached");
+        }
+        self
+    }
+}
+
+/// Dry-run the catalog fetch of the provider configured as `provider_id`,
+/// from `base_url` instead of its own when one is given.
+pub async fn diagnose_provider_fetch(
+    config: &Config,
+    provider_id: &str,
+    base_url: Option<String>,
+) -> CoreResult<FetchDiagnosis> {
+    let Some(provider) = config.model_providers.get(provider_id) else {
+        return Err(CodexErr::Fatal(format!(
+            "no model provider `{provider_id}` is configured"
+        )));
+    };
+    let Some(catalog) = provider.model_catalog else {
+        return Err(CodexErr::Fatal(format!(
+            "provider `{provider_id}` has no `model_catalog`, so Codex never fetches its models"
+        )));
+    };
+    let mut provider = provider.clone();
+    if let Some(base_url) = base_url {
+        provider.base_url = Some(base_url);
+    }
+    let catalog = ProviderCatalogConfig {
+        provider_id: provider_id.to_string(),
+        catalog,
+        path: provider.models_path.clone(),
+        provider,
+        user_agent_suffix: config.user_agent_suffix.clone(),
+    };
+    diagnose_catalog_fetch(&catalog, resolve_catalog_locale(config).as_deref()).await
+}
+
+/// Fetch `catalog` once and report each phase. Fails only when the request
+/// cannot be built, for instance because the provider's API key is unset;
+/// network and parse failures are reported in the diagnosis.
+pub async fn diagnose_catalog_fetch(
+    catalog: &ProviderCatalogConfig,
+    catalog_locale: Option<&str>,
+) -> CoreResult<FetchDiagnosis> {
+    let http_client =
+        build_reqwest_client_for_catalog(&catalog.provider, catalog.user_agent_suffix.as_deref())?;
+    let client = catalog_models_client(catalog, http_client.clone()).await?;
+    let headers = catalog_headers(catalog_locale);
+    let (request, decode): (Request, fn(&[u8]) -> Result<usize, ApiError>) = match catalog.catalog {
+        ModelCatalog::Openai => (
+            client.openai_compatible_models_request(headers),
+            count_openai_models,
+        ),
+        ModelCatalog::Azure => (
+            client.azure_deployments_request(headers),
+            count_azure_deployments,
+        ),
+        ModelCatalog::None => {
+            return Err(CodexErr::Fatal(format!(
+                "provider `{}` takes its models from config (`model_catalog = \"none\"`); there is nothing to fetch",
+                catalog.provider_id
+            )));
+        }
+    };
+    let url = Url::parse(&request.url).map_err(|err| {
+        CodexErr::Fatal(format!(
+            "model catalog URL for provider `{}` is invalid: {err}",
+            catalog.provider_id
+        ))
+    })?;
+    let proxy = system_proxy(&url);
+    let mut diagnosis = FetchDiagnosis {
+        provider_id: catalog.provider_id.clone(),
+        catalog: catalog.catalog,
+        method: request.method.to_string(),
+        url: redact_url(&url),
+        proxy: proxy.as_ref().map(redact_url),
+        request_headers: redact_headers(&request.headers, catalog.user_agent_suffix.as_deref()),
+        token: client.auth().token_freshness(),
+        phases: Vec::new(),
+        http_status: None,
+        payload_bytes: None,
+        model_count: None,
+    };
+
+    // With a proxy the socket goes to the proxy, and TLS with the provider is
+    // tunnelled through it, so only DNS and connect can be probed directly.
+    let target = proxy.as_ref().unwrap_or(&url);
+    let started = Instant::now();
+    let (Some(host), Some(port)) = (target.host_str(), target.port_or_known_default()) else {
+        return Ok(diagnosis.fail(FetchPhase::Dns, started, "the URL has no host"));
+    };
+    let host = host.trim_start_matches('[').trim_end_matches(']');
+    let addrs: Vec<SocketAddr> = match timeout(PROBE_TIMEOUT, lookup_host((host, port))).await {
+        Ok(Ok(addrs)) => addrs.collect(),
+        Ok(Err(err)) => return Ok(diagnosis.fail(FetchPhase::Dns, started, &err.to_string())),
+        Err(_) => return Ok(diagnosis.fail(FetchPhase::Dns, started, "timed out")),
+    };
+    if addrs.is_empty() {
+        return Ok(diagnosis.fail(FetchPhase::Dns, started, "no addresses"));
+    }
+    let resolved = addrs
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join(", ");
+    diagnosis.pass(FetchPhase::Dns, started, format!("{host} -> {resolved}"));
+
+    let started = Instant::now();
+    let stream = match timeout(PROBE_TIMEOUT, TcpStream::connect(addrs.as_slice())).await {
+        Ok(Ok(stream)) => stream,
+        Ok(Err(err)) => return Ok(diagnosis.fail(FetchPhase::Connect, started, &err.to_string())),
+        Err(_) => return Ok(diagnosis.fail(FetchPhase::Connect, started, "timed out")),
+    };
+    let peer = stream
+        .peer_addr()
+        .map_or_else(|_| resolved.clone(), |addr| addr.to_string());
+    diagnosis.pass(FetchPhase::Connect, started, format!("connected to {peer}"));
+
+    if proxy.is_some() {
+        diagnosis.skip(
+            FetchPhase::Tls,
+            "tunnelled through the proxy; included in first byte",
+        );
+    } else if url.scheme() != "https" {
+        diagnosis.skip(FetchPhase::Tls, "plain HTTP");
+    } else {
+        let started = Instant::now();
+        if let Err(err) = probe_tls(catalog, stream, host.to_string()).await {
+            return Ok(diagnosis.fail(FetchPhase::Tls, started, &err));
+        }
+        diagnosis.pass(FetchPhase::Tls, started, "handshake completed".to_string());
+    }
+
+    let mut request = request;
+    request.timeout = request.timeout.or(Some(FETCH_TIMEOUT));
+    let limit = request.max_response_bytes;
+    let transport = ReqwestTransport::new(http_client);
+    let started = Instant::now();
+    let response = match transport.stream(request).await {
+        Ok(response) => response,
+        Err(TransportError::Http { status, body, .. }) => {
+            diagnosis.http_status = Some(status.as_u16());
+            diagnosis.payload_bytes = body.as_ref().map(String::len);
+            diagnosis.pass(FetchPhase::FirstByte, started, format!("HTTP {status}"));
+            let detail = match body.as_deref().map(str::trim) {
+                Some(body) if !body.is_empty() => format!("HTTP {status}: {body}"),
+                _ => format!("HTTP {status}"),
+            };
+            return Ok(diagnosis.fail(FetchPhase::Total, started, &detail));
+        }
+        Err(err) => return Ok(diagnosis.fail(FetchPhase::FirstByte, started, &err.to_string())),
+    };
+    diagnosis.http_status = Some(response.status.as_u16());
+    diagnosis.pass(
+        FetchPhase::FirstByte,
+        started,
+        format!("HTTP {}", response.status),
+    );
+
+    let response_headers = response.headers;
+    let mut body = Vec::new();
+    let mut chunks = response.bytes;
+    while let Some(chunk) = chunks.next().await {
+        let chunk = match chunk {
+            Ok(chunk) => chunk,
+            Err(err) => return Ok(diagnosis.fail(FetchPhase::Total, started, &err.to_string())),
+        };
+        body.extend_from_slice(&chunk);
+        if let Some(limit) = limit
+            && body.len() > limit
+        {
+            let detail = TransportError::ResponseTooLarge { limit }.to_string();
+            return Ok(diagnosis.fail(FetchPhase::Total, started, &detail));
+        }
+    }
+    diagnosis.payload_bytes = Some(body.len());
+    diagnosis.pass(FetchPhase::Total, started, format!("{} bytes", body.len()));
+
+    let started = Instant::now();
+    if let Err(err) = check_json_content(&response_headers, &body) {
+        let detail = map_api_error(err).to_string();
+        return Ok(diagnosis.fail(FetchPhase::Parse, started, &detail));
+    }
+    match decode(&body) {
+        Ok(count) => {
+            diagnosis.model_count = Some(count);
+            diagnosis.pass(FetchPhase::Parse, started, format!("{count} models"));
+            Ok(diagnosis)
+        }
+        Err(err) => Ok(diagnosis.fail(FetchPhase::Parse, started, &err.to_string())),
+    }
+}
+
+fn count_openai_models(body: &[u8]) -> Result<usize, ApiError> {
+    decode_openai_compatible_models(body).map(|listing| listing.entries.len())
+}
+
+fn count_azure_deployments(body: &[u8]) -> Result<usize, ApiError> {
+    decode_azure_deployments(body).map(|listing| listing.entries.len())
+}
+
+/// Hand-shake over `stream` with the TLS settings the provider client uses,
+/// pins included.
+async fn probe_tls(
+    catalog: &ProviderCatalogConfig,
+    stream: TcpStream,
+    host: String,
+) -> Result<(), String> {
+    let config = match catalog.provider.spki_pins()? {
+        Some(pins) => pinned_tls_config(pins)?,
+        None => native_tls_config()?,
+    };
+    let stream = stream.into_std().map_err(|err| err.to_string())?;
+    stream
+        .set_nonblocking(false)
+        .map_err(|err| err.to_string())?;
+    tokio::task::spawn_blocking(move || tls_handshake(stream, &host, config, PROBE_TIMEOUT))
+        .await
+        .map_err(|err| err.to_string())?
+        .map_err(|err| err.to_string())
+}
+
+/// The proxy the catalog client sends `url` through, if any. This is the
+/// matcher reqwest's system proxy support is built on, so it reads the same
+/// environment variables and, on macOS and Windows, the same system settings.
+fn system_proxy(url: &Url) -> Option<Url> {
+    let uri: Uri = url.as_str().parse().ok()?;
+    let intercept = Matcher::from_system().intercept(&uri)?;
+    Url::parse(&intercept.uri().to_string()).ok()
+}
+
+/// `url` without user info, and with secret-looking query values masked.
+fn redact_url(url: &Url) -> String {
+    let mut url = url.clone();
+    let _ = url.set_username("");
+    let _ = url.set_password(None);
+    if url.query().is_some() {
+        let pairs: Vec<(String, String)> = url
+            .query_pairs()
+            .map(|(name, value)| {
+                let value = if is_secret_name(&name) {
+                    REDACTED.to_string()
+                } else {
+                    value.into_owned()
+                };
+                (name.into_owned(), value)
+            })
+            .collect();
+        url.query_pairs_mut().clear().extend_pairs(pairs);
+    }
+    url.to_string()
+}
+
+/// The headers the request carries, including the ones the HTTP client adds
+/// to every request, with credential values masked. An `Authorization` value
+/// keeps its scheme so a missing `Bearer` prefix is still visible.
+fn redact_headers(headers: &HeaderMap, user_agent_suffix: Option<&str>) -> Vec<RedactedHeader> {
+    let mut redacted = vec![
+        RedactedHeader {
+            name: "user-agent".to_string(),
+            value: get_codex_user_agent_with_suffix(user_agent_suffix),
+        },
+        RedactedHeader {
+            name: "originator".to_string(),
+            value: originator().value.clone(),
+        },
+    ];
+    for (name, value) in headers {
+        let name = name.as_str();
+        let value = value.to_str().unwrap_or("<non-ASCII value>");
+        let value = if carries_credentials(name) || is_secret_name(name) {
+            match value.split_once(' ') {
+                Some((scheme, _)) if name == "authorization" => format!("{scheme} {REDACTED}"),
+                _ => REDACTED.to_string(),
+            }
+        } else {
+            value.to_string()
+        };
+        redacted.push(RedactedHeader {
+            name: name.to_string(),
+            value,
+        });
+    }
+    redacted
+}
+
+fn is_secret_name(name: &str) -> bool {
+    let name = name.to_ascii_lowercase();
+    ["key", "token", "secret", "password", "sig"]
+        .iter()
+        .any(|marker| name.contains(marker))
+}
+
+fn truncate_detail(detail: &str) -> String {
+    match detail.char_indices().nth(MAX_DETAIL_CHARS) {
+        Some((idx, _)) => format!("{}…", &detail[..idx]),
+        None => detail.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model_provider_info::ModelProviderInfo;
+    use pretty_assertions::assert_eq;
+    use wiremock::Mock;
+    use wiremock::MockServer;
+    use wiremock::ResponseTemplate;
+    use wiremock::matchers::header;
+    use wiremock::matchers::method;
+    use wiremock::matchers::path;
+
+    fn gateway(base_url: &str) -> ProviderCatalogConfig {
+        let provider: ModelProviderInfo = toml::from_str(&format!(
+            r#"
+name = "Gateway"
+base_url = "{base_url}"
+experimental_bearer_token = "sk-secret-token"
+model_catalog = "openai"
+request_max_retries = 0
+"#
+        ))
+        .expect("provider config");
+        ProviderCatalogConfig {
+            provider_id: "gateway".to_string(),
+            catalog: ModelCatalog::Openai,
+            path: None,
+            provider,
+            user_agent_suffix: None,
+        }
+    }
+
+    fn statuses(diagnosis: &FetchDiagnosis) -> Vec<(FetchPhase, PhaseStatus)> {
+        diagnosis
+            .phases
+            .iter()
+            .map(|report| (report.phase, report.status))
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn successful_fetch_reports_every_phase() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/v1/models"))
+            .and(header("authorization", "Bearer sk-secret-token"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "data": [{"id": "oca-large"}, {"id": "oca-small"}],
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let diagnosis = diagnose_catalog_fetch(&gateway(&format!("{}/v1", server.uri())), None)
+            .await
+            .expect("diagnosis");
+
+        assert_eq!(
+            statuses(&diagnosis),
+            vec![
+                (FetchPhase::Dns, PhaseStatus::Ok),
+                (FetchPhase::Connect, PhaseStatus::Ok),
+                (FetchPhase::Tls, PhaseStatus::Skipped),
+                (FetchPhase::FirstByte, PhaseStatus::Ok),
+                (FetchPhase::Total, PhaseStatus::Ok),
+                (FetchPhase::Parse, PhaseStatus::Ok),
+            ]
+        );
+        assert_eq!(diagnosis.failed_phase(), None);
+        assert_eq!(diagnosis.http_status, Some(200));
+        assert_eq!(diagnosis.model_count, Some(2));
+        assert!(diagnosis.payload_bytes.is_some_and(|bytes| bytes > 0));
+        assert_eq!(
+            diagnosis
+                .request_headers
+                .iter()
+                .find(|header| header.name == "authorization")
+                .map(|header| header.value.as_str()),
+            Some("Bearer [REDACTED]")
+        );
+    }
+
+    #[tokio::test]
+    async fn refused_connection_fails_the_connect_phase() {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").expect("bind");
+        let addr = listener.local_addr().expect("local addr");
+        drop(listener);
+
+        let diagnosis = diagnose_catalog_fetch(&gateway(&format!("http://{addr}/v1")), None)
+            .await
+            .expect("diagnosis");
+
+        assert_eq!(diagnosis.failed_phase(), Some(FetchPhase::Connect));
+        assert_eq!(
+            statuses(&diagnosis),
+            vec![
+                (FetchPhase::Dns, PhaseStatus::Ok),
+                (FetchPhase::Connect, PhaseStatus::Failed),
+                (FetchPhase::Tls, PhaseStatus::Skipped),
+                (FetchPhase::FirstByte, PhaseStatus::Skipped),
+                (FetchPhase::Total, PhaseStatus::Skipped),
+                (FetchPhase::Parse, PhaseStatus::Skipped),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn https_to_a_plain_http_server_fails_the_tls_phase() {
+        let server = MockServer::start().await;
+        let base_url = format!("{}/v1", server.uri().replacen("http://", "https://", 1));
+
+        let diagnosis = diagnose_catalog_fetch(&gateway(&base_url), None)
+            .await
+            .expect("diagnosis");
+
+        assert_eq!(diagnosis.failed_phase(), Some(FetchPhase::Tls));
+        assert_eq!(diagnosis.http_status, None);
+    }
+
+    #[tokio::test]
+    async fn error_status_fails_the_total_phase_with_the_body() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/v1/models"))
+            .respond_with(ResponseTemplate::new(401).set_body_string("invalid api key"))
+            .mount(&server)
+            .await;
+
+        let diagnosis = diagnose_catalog_fetch(&gateway(&format!("{}/v1", server.uri())), None)
+            .await
+            .expect("diagnosis");
+
+        assert_eq!(diagnosis.failed_phase(), Some(FetchPhase::Total));
+        assert_eq!(diagnosis.http_status, Some(401));
+        assert_eq!(
+            diagnosis
+                .phases
+                .iter()
+                .find(|report| report.phase == FetchPhase::Total)
+                .map(|report| report.detail.as_str()),
+            Some("HTTP 401 Unauthorized: invalid api key")
+        );
+    }
+
+    #[test]
+    fn secret_query_values_are_masked() {
+        let url =
+            Url::parse("https://user:pw@gateway.example.com/v1/models?api-version=1&api_key=abc")
+                .expect("url");
+
+        assert_eq!(
+            redact_url(&url),
+            "https://gateway.example.com/v1/models?api-version=1&api_key=%5BREDACTED%5D"
+        );
+    }
+}
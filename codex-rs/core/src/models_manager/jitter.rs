@@ -0,0 +1,54 @@
+//! Per-process jitter for the catalog cache TTL and the background refresh
+//! interval.
+//!
+//! When a whole team starts Codex at the same time, a fixed TTL has every
+//! process refetch the catalog at the same moment, once per TTL. Each process
+//! draws one random offset when it first needs it and stretches or shrinks
+//! both durations by that offset for as long as it runs, which spreads the
+//! refetches out.
+
+use std::sync::LazyLock;
+use std::time::Duration;
+
+use rand::Rng;
+
+/// Where this process falls in the jitter range, from -1 (shortest) to 1
+/// (longest). Drawn once, so a process keeps a steady schedule.
+static PROCESS_OFFSET: LazyLock<f64> = LazyLock::new(|| rand::rng().random_range(-1.0..=1.0));
+
+/// `base` moved by up to `fraction` of itself, by this process's offset.
+pub(crate) fn jittered(base: Duration, fraction: f64) -> Duration {
+    jittered_by(base, fraction, *PROCESS_OFFSET)
+}
+
+fn jittered_by(base: Duration, fraction: f64, offset: f64) -> Duration {
+    base.mul_f64(1.0 + fraction * offset)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn jitter_stays_within_the_configured_fraction() {
+        let base = Duration::from_secs(300);
+        let lowest = Duration::from_secs(240);
+        let highest = Duration::from_secs(360);
+        for step in 0..=20 {
+            let offset = f64::from(step) / 10.0 - 1.0;
+            let ttl = jittered_by(base, 0.2, offset);
+            assert!(
+                (lowest..=highest).contains(&ttl),
+                "{ttl:?} is outside ±20% of {base:?}"
+            );
+        }
+        assert_eq!(jittered_by(base, 0.2, -1.0), lowest);
+        assert_eq!(jittered_by(base, 0.2, 1.0), highest);
+
+        let ttl = jittered(base, 0.2);
+        assert!((lowest..=highest).contains(&ttl), "{ttl:?}");
+        assert_eq!(jittered(base, 0.2), ttl, "drawn once per process");
+        assert_eq!(jittered(base, 0.0), base);
+    }
+}
@@ -0,0 +1,56 @@
+use crate::config::Config;
+
+/// The locale sent as `Accept-Language` on catalog requests: `catalog_locale`
+/// from config, or the system locale when that is unset. An empty or
+/// unrecognized value sends none.
+pub(crate) fn resolve_catalog_locale(config: &Config) -> Option<String> {
+    match config.catalog_locale.as_deref() {
+        Some(configured) => normalize_locale(configured),
+        None => sys_locale::get_locale()
+            .as_deref()
+            .and_then(normalize_locale),
+    }
+}
+
+/// Turn a POSIX (`en_US.UTF-8`) or BCP 47 (`en-US`) locale into a language
+/// tag. `C`, `POSIX`, and anything that is not a plain tag yield `None`.
+fn normalize_locale(raw: &str) -> Option<String> {
+    let tag = raw
+        .trim()
+        .split(['.', '@'])
+        .next()
+        .unwrap_or_default()
+        .replace('_', "-");
+    if tag.is_empty() || tag.eq_ignore_ascii_case("c") || tag.eq_ignore_ascii_case("posix") {
+        return None;
+    }
+    tag.split('-')
+        .all(|part| {
+            !part.is_empty() && part.len() <= 8 && part.chars().all(|ch| ch.is_ascii_alphanumeric())
+        })
+        .then_some(tag)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn posix_and_bcp47_locales_become_language_tags() {
+        assert_eq!(normalize_locale("fr_FR.UTF-8"), Some("fr-FR".to_string()));
+        assert_eq!(normalize_locale("de_DE@euro"), Some("de-DE".to_string()));
+        assert_eq!(normalize_locale(" pt-BR "), Some("pt-BR".to_string()));
+        assert_eq!(
+            normalize_locale("zh-Hant-TW"),
+            Some("zh-Hant-TW".to_string())
+        );
+    }
+
+    #[test]
+    fn missing_and_unsupported_locales_send_nothing() {
+        for raw in ["", "C", "C.UTF-8", "POSIX", "fr-CH, fr;q=0.9", "en--US"] {
+            assert_eq!(normalize_locale(raw), None, "{raw:?}");
+        }
+    }
+}
@@ -1,36 +1,135 @@
+use arc_swap::ArcSwapOption;
+use chrono::DateTime;
 use chrono::Utc;
+use codex_api::ApiError;
+use codex_api::AzureDeployment;
+use codex_api::CatalogListing;
+use codex_api::DEFAULT_MAX_MODELS_RESPONSE_BYTES;
 use codex_api::ModelsClient;
+use codex_api::ModelsListing;
 use codex_api::ReqwestTransport;
+use codex_api::TransportError;
 use codex_app_server_protocol::AuthMode;
+use codex_otel::catalog::CatalogTelemetry;
+use codex_protocol::openai_models::Capability;
+use codex_protocol::openai_models::EffortByAuth;
 use codex_protocol::openai_models::ModelInfo;
 use codex_protocol::openai_models::ModelPreset;
+use codex_protocol::openai_models::ModelPresetError;
+use codex_protocol::openai_models::ModelPricing;
+use codex_protocol::openai_models::ModelVisibility;
 use codex_protocol::openai_models::ModelsResponse;
+use codex_protocol::openai_models::ReasoningEffort;
+use codex_protocol::protocol::CodexErrorInfo;
+use codex_protocol::protocol::SessionCatalog;
 use http::HeaderMap;
+use http::HeaderValue;
+use http::StatusCode;
+use http::header::ACCEPT_LANGUAGE;
+use sha2::Digest;
+use sha2::Sha256;
+use std::collections::HashMap;
 use std::collections::HashSet;
+use std::path::Path;
 use std::path::PathBuf;
 use std::sync::Arc;
+use std::sync::Mutex;
+use std::sync::PoisonError;
 use std::time::Duration;
+use std::time::Instant;
 use tokio::sync::RwLock;
 use tokio::sync::TryLockError;
+use tokio::sync::broadcast;
+use tokio::sync::watch;
+use tokio::task::JoinHandle;
+use tracing::debug;
 use tracing::error;
+use tracing::info;
+use tracing::warn;
+use url::Url;
 
+use super::availability::AccountAvailability;
 use super::cache;
+use super::cache::CachedDefault;
 use super::cache::ModelsCache;
+use super::catalog_export::CATALOG_EXPORT_SCHEMA_VERSION;
+use super::catalog_export::CatalogExport;
+use super::catalog_export::ImportedCatalog;
+use super::catalog_export::ImportedCatalogInfo;
+use super::catalog_export::load_imported_catalog;
+use super::client_version;
+use super::client_version::CLIENT_VERSION;
+use super::client_version::ClientVersionNotice;
+use super::feedback::ModelFeedbackSnapshot;
+use super::jitter;
+use super::locale::resolve_catalog_locale;
+use super::preflight::preflight;
+use super::provenance::CacheLookup;
+use super::provenance::CatalogAuth;
+use super::provenance::CatalogFreshness;
+use super::provenance::CatalogSchedule;
+use super::provenance::CatalogSource;
+use super::provenance::CatalogStatus;
+use super::provenance::CatalogWarning;
+use super::provenance::CredentialSource;
+use super::provenance::FetchRecord;
+use super::provenance::PrefetchStats;
+use super::provenance::PresetProvenance;
+use super::provenance::PresetSource;
+use super::provenance::ProviderFetchRecord;
+use super::provenance::TokenFreshness;
+use super::provenance::redact_error;
+use super::provenance::redact_host;
+use super::rebuild_queue::CatalogRebuild;
+use super::rebuild_queue::read_last_rebuild;
+use super::rebuild_queue::write_last_rebuild;
+use super::refresh_events::CatalogRefreshEvent;
+use super::refresh_events::RefreshEvents;
+use super::refresh_events::RefreshReason;
+use super::sanitize::sanitize_model_info;
+use super::sanitize::sanitize_model_preset;
+use super::state_dir;
+use super::state_gc;
+use super::state_gc::ModelStateScope;
+use super::validation::InvalidSelection;
+use crate::api_bridge::CoreAuthProvider;
 use crate::api_bridge::auth_provider_from_auth;
 use crate::api_bridge::map_api_error;
 use crate::auth::AuthManager;
+use crate::auth::CODEX_API_KEY_ENV_VAR;
+use crate::auth::CodexAuth;
+use crate::auth::RefreshTokenError;
 use crate::config::Config;
-use crate::default_client::build_reqwest_client;
+use crate::config::types::ModelCatalogSource;
+use crate::default_client::build_reqwest_client_for_catalog;
+use crate::default_client::build_reqwest_client_for_preflight;
+use crate::error::CodexErr;
 use crate::error::Result as CoreResult;
 use crate::features::Feature;
+use crate::model_provider_info::ModelCatalog;
 use crate::model_provider_info::ModelProviderInfo;
+use crate::model_provider_info::ProviderCatalogConfig;
+use crate::models_manager::catalog::CatalogSnapshot;
+use crate::models_manager::catalog::CatalogSnapshotStatus;
+use crate::models_manager::catalog::PresetCatalog;
 use crate::models_manager::model_family::ModelFamily;
+use crate::models_manager::model_family::known_family_for_model;
+use crate::models_manager::model_presets::DEFAULT_API_PRESET_ID;
+use crate::models_manager::model_presets::DEFAULT_CHATGPT_PRESET_ID;
+use crate::models_manager::model_presets::all_builtin_model_presets;
 use crate::models_manager::model_presets::builtin_model_presets;
+use crate::models_manager::model_presets::builtin_model_slugs;
+use crate::models_manager::model_presets::builtin_presets;
+use crate::models_manager::model_presets::retired_model_successor;
+use crate::project_doc;
+use crate::token_command::invalidate_token;
 
-const MODEL_CACHE_FILE: &str = "models_cache.json";
+pub(crate) const MODEL_CACHE_FILE: &str = "models_cache.json";
 const DEFAULT_MODEL_CACHE_TTL: Duration = Duration::from_secs(300);
-const OPENAI_DEFAULT_API_MODEL: &str = "gpt-5.1-codex-max";
-const OPENAI_DEFAULT_CHATGPT_MODEL: &str = "gpt-5.2-codex";
+/// Default `model_cache_max_staleness_hours`.
+pub(crate) const DEFAULT_MODEL_CACHE_MAX_STALENESS: Duration = Duration::from_secs(24 * 60 * 60);
+const OPENAI_DEFAULT_API_MODEL: &str = DEFAULT_API_PRESET_ID;
+const OPENAI_DEFAULT_CHATGPT_MODEL: &str = DEFAULT_CHATGPT_PRESET_ID;
 const CODEX_AUTO_BALANCED_MODEL: &str = "codex-auto-balanced";
 
 /// Coordinates remote model discovery plus cached metadata on disk.
@@ -38,26 +137,108 @@ const CODEX_AUTO_BALANCED_MODEL: &str = "codex-auto-balanced";
 pub struct ModelsManager {
     // todo(aibrahim) merge available_models and model family creation into one struct
     local_models: Vec<ModelPreset>,
-    remote_models: RwLock<Vec<ModelInfo>>,
+    remote_models: Arc<RwLock<Vec<ModelInfo>>>,
+    provider_models: Arc<RwLock<Vec<ModelPreset>>>,
+    remote_fetch: Arc<RwLock<FetchRecord>>,
+    provider_fetches: Arc<RwLock<Vec<ProviderFetchRecord>>>,
     auth_manager: Arc<AuthManager>,
-    etag: RwLock<Option<String>>,
+    etag: Arc<RwLock<Option<String>>>,
     codex_home: PathBuf,
     cache_ttl: Duration,
-    provider: ModelProviderInfo,
+    provider: Arc<ModelProviderInfo>,
+    /// Bumped whenever a refresh changes the remote or provider-served models,
+    /// or the credentials the catalog is requested with change.
+    revision: Arc<watch::Sender<u64>>,
+    /// The credentials reported by the last [`Self::model_list`].
+    last_catalog_auth: RwLock<Option<CatalogAuth>>,
+    /// Synchronous so the TUI can record picker openings without awaiting.
+    prefetch: Mutex<PrefetchStats>,
+    /// Models the provider refused to an account; synchronous so listings
+    /// that cannot await still grey them out.
+    availability: Mutex<AccountAvailability>,
+    /// What [`PresetCatalog::snapshot`] serves; swapped whole, never locked.
+    snapshot: ArcSwapOption<CatalogSnapshot>,
+    /// The background refresh started when a stale cache was served.
+    revalidation: Mutex<Option<JoinHandle<()>>>,
+    /// The background refresh started when provider catalogs listed more
+    /// than a cache TTL ago were served.
+    provider_revalidation: Mutex<Option<JoinHandle<()>>>,
+    /// When [`Self::refresh_all_models`] last ran, so scheduled refreshes
+    /// right after it can be skipped.
+    manual_refresh: Mutex<Option<Instant>>,
+    /// See [`Self::subscribe_refresh_events`].
+    refresh_events: RefreshEvents,
+    /// Slug of the remote catalog's default; see [`sticky_default`].
+    remote_default: Arc<Mutex<Option<String>>>,
+    /// Listed in place of every other catalog, which are then never fetched;
+    /// see [`super::catalog_export`].
+    imported: Option<ImportedCatalog>,
+    /// See [`Self::last_catalog_rebuild`].
+    last_rebuild: Mutex<Option<CatalogRebuild>>,
+    /// The catalog and generation the state was last pruned for; see
+    /// [`Self::collect_stale_model_state`].
+    state_gc_pass: Mutex<Option<(String, u64)>>,
+    /// The cached catalog last reported to telemetry; see
+    /// [`Self::record_cache_load`].
+    reported_cache: Mutex<Option<(&'static str, DateTime<Utc>)>>,
+}
+
+/// A listing of the catalog together with what it was resolved against.
+#[derive(Debug, Clone)]
+pub struct ModelList {
+    /// See [`ModelsManager::listed_revision`].
+    pub revision: u64,
+    pub auth: CatalogAuth,
+    /// See [`ModelsManager::catalog_warnings`].
+    pub warnings: Vec<CatalogWarning>,
+    /// See [`ModelsManager::catalog_freshness`].
+    pub freshness: CatalogFreshness,
+    pub models: Vec<ModelPreset>,
+    /// Fields the catalog served that this version does not know, by preset
+    /// id. Only models that had any are present.
+    pub extra: HashMap<String, HashMap<String, serde_json::Value>>,
+    /// See [`ModelsManager::client_version_notice`].
+    pub client_version_notice: Option<ClientVersionNotice>,
+    /// See [`ModelsManager::last_catalog_rebuild`].
+    pub last_rebuild: Option<CatalogRebuild>,
 }
 
 impl ModelsManager {
     /// Construct a manager scoped to the provided `AuthManager`.
     pub fn new(auth_manager: Arc<AuthManager>) -> Self {
         let codex_home = auth_manager.codex_home().to_path_buf();
+        // Probed now so a read-only home is logged at startup, not at the
+        // first write of catalog state.
+        state_dir::is_writable(&codex_home);
+        let availability = Mutex::new(AccountAvailability::load(&codex_home));
+        let imported = load_imported_catalog(&codex_home);
         Self {
             local_models: builtin_model_presets(auth_manager.get_auth_mode()),
-            remote_models: RwLock::new(Self::load_remote_models_from_file().unwrap_or_default()),
+            remote_models: Arc::new(RwLock::new(
+                Self::load_remote_models_from_file().unwrap_or_default(),
+            )),
+            provider_models: Arc::new(RwLock::new(Vec::new())),
+            remote_fetch: Arc::new(RwLock::new(FetchRecord::default())),
+            provider_fetches: Arc::new(RwLock::new(Vec::new())),
             auth_manager,
-            etag: RwLock::new(None),
+            etag: Arc::new(RwLock::new(None)),
             codex_home,
             cache_ttl: DEFAULT_MODEL_CACHE_TTL,
-            provider: ModelProviderInfo::create_openai_provider(),
+            provider: Arc::new(ModelProviderInfo::create_openai_provider()),
+            revision: Arc::new(watch::Sender::new(0)),
+            last_catalog_auth: RwLock::new(None),
+            prefetch: Mutex::new(PrefetchStats::default()),
+            availability,
+            snapshot: ArcSwapOption::empty(),
+            revalidation: Mutex::new(None),
+            provider_revalidation: Mutex::new(None),
+            manual_refresh: Mutex::new(None),
+            refresh_events: RefreshEvents::new(),
+            remote_default: Arc::new(Mutex::new(None)),
+            imported,
+            last_rebuild: Mutex::new(None),
+            state_gc_pass: Mutex::new(None),
+            reported_cache: Mutex::new(None),
         }
     }
 
@@ -65,344 +246,6029 @@ impl ModelsManager {
     /// Construct a manager scoped to the provided `AuthManager` with a specific provider. Used for integration tests.
     pub fn with_provider(auth_manager: Arc<AuthManager>, provider: ModelProviderInfo) -> Self {
         let codex_home = auth_manager.codex_home().to_path_buf();
+        state_dir::is_writable(&codex_home);
+        let availability = Mutex::new(AccountAvailability::load(&codex_home));
+        let imported = load_imported_catalog(&codex_home);
         Self {
             local_models: builtin_model_presets(auth_manager.get_auth_mode()),
-            remote_models: RwLock::new(Self::load_remote_models_from_file().unwrap_or_default()),
+            remote_models: Arc::new(RwLock::new(
+                Self::load_remote_models_from_file().unwrap_or_default(),
+            )),
+            provider_models: Arc::new(RwLock::new(Vec::new())),
+            remote_fetch: Arc::new(RwLock::new(FetchRecord::default())),
+            provider_fetches: Arc::new(RwLock::new(Vec::new())),
             auth_manager,
-            etag: RwLock::new(None),
+            etag: Arc::new(RwLock::new(None)),
             codex_home,
             cache_ttl: DEFAULT_MODEL_CACHE_TTL,
-            provider,
+            provider: Arc::new(provider),
+            revision: Arc::new(watch::Sender::new(0)),
+            last_catalog_auth: RwLock::new(None),
+            prefetch: Mutex::new(PrefetchStats::default()),
+            availability,
+            snapshot: ArcSwapOption::empty(),
+            revalidation: Mutex::new(None),
+            provider_revalidation: Mutex::new(None),
+            manual_refresh: Mutex::new(None),
+            refresh_events: RefreshEvents::new(),
+            remote_default: Arc::new(Mutex::new(None)),
+            imported,
+            last_rebuild: Mutex::new(None),
+            state_gc_pass: Mutex::new(None),
+            reported_cache: Mutex::new(None),
         }
     }
 
+    /// A manager with the same remote catalog and cache settings but catalog
+    /// state of its own, for the gateway of one tenant, fetching with the
+    /// credentials `auth_manager` holds.
+    pub(crate) fn sibling(&self, auth_manager: Arc<AuthManager>) -> Self {
+        let mut manager = Self::new(auth_manager);
+        manager.provider = Arc::clone(&self.provider);
+        manager.cache_ttl = self.cache_ttl;
+        manager
+    }
+
     /// Fetch the latest remote models, using the on-disk cache when still fresh.
+    /// A cache past its TTL but younger than `model_cache_max_staleness` is used as well,
+    /// while a background refresh replaces it; see [`Self::catalog_freshness`].
+    /// In offline mode the cache is used regardless of age and nothing is fetched.
     pub async fn refresh_available_models_with_cache(&self, config: &Config) -> CoreResult<()> {
-        if !config.features.enabled(Feature::RemoteModels)
+        if !config
+            .model_catalog_sources
+            .contains(&ModelCatalogSource::Remote)
+            || !config.features.enabled(Feature::RemoteModels)
             || self.auth_manager.get_auth_mode() == Some(AuthMode::ApiKey)
+            || config.model_provider.has_fixed_models()
+            || self.imported(config).is_some()
         {
             return Ok(());
         }
-        if self.try_load_cache().await {
+        let catalog_locale = resolve_catalog_locale(config);
+        let catalog_locale = catalog_locale.as_deref();
+        let telemetry = config.otel.catalog_telemetry();
+        let started = Instant::now();
+        if config.offline {
+            if self.try_load_cache(None, catalog_locale).await == CacheLookup::Hit {
+                self.record_cache_load(telemetry, "disk_cache", started)
+                    .await;
+            }
             return Ok(());
         }
-        self.refresh_available_models_no_cache(config.features.enabled(Feature::RemoteModels))
+        match self
+            .try_load_cache(Some(self.cache_ttl(config)), catalog_locale)
+            .await
+        {
+            CacheLookup::Hit => {
+                self.record_cache_load(telemetry, "disk_cache", started)
+                    .await;
+                return Ok(());
+            }
+            CacheLookup::Stale
+                if self
+                    .load_cache_if_fresh(Some(config.model_cache_max_staleness), catalog_locale)
+                    .await
+                    == CacheLookup::Hit =>
+            {
+                self.record_cache_load(telemetry, "stale_disk_cache", started)
+                    .await;
+                self.revalidate_in_background(catalog_locale, config.user_agent_suffix.as_deref());
+                return Ok(());
+            }
+            CacheLookup::Stale | CacheLookup::Miss => {}
+        }
+        self.refresh_available_models_no_cache(
+            config.features.enabled(Feature::RemoteModels),
+            catalog_locale,
+            config.user_agent_suffix.as_deref(),
+            RefreshReason::CacheMiss,
+        )
+        .await?;
+        self.record_remote_fetch(telemetry, "live", started).await;
+        Ok(())
+    }
+
+    /// Report the remote catalog loaded from the disk cache as `source`,
+    /// unless that cache was the last one reported. The cache is read on
+    /// every listing, so only a change of cache or of source is reported.
+    async fn record_cache_load(
+        &self,
+        telemetry: CatalogTelemetry,
+        source: &'static str,
+        started: Instant,
+    ) {
+        let state = self
+            .remote_fetch
+            .read()
             .await
+            .fetched_at
+            .map(|fetched_at| (source, fetched_at));
+        {
+            let mut reported = self
+                .reported_cache
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner);
+            if state.is_some() && *reported == state {
+                return;
+            }
+            *reported = state;
+        }
+        self.record_remote_fetch(telemetry, source, started).await;
+    }
+
+    /// Report the remote catalog loaded from `source` to `telemetry`.
+    async fn record_remote_fetch(
+        &self,
+        telemetry: CatalogTelemetry,
+        source: &str,
+        started: Instant,
+    ) {
+        let model_count = self.remote_models.read().await.len();
+        telemetry.catalog_fetch_completed(
+            source,
+            model_count,
+            self.catalog_revision(),
+            started.elapsed(),
+        );
     }
 
+    /// Fetch the remote catalog for `reason`, reporting it to
+    /// [`Self::subscribe_refresh_events`].
     pub(crate) async fn refresh_available_models_no_cache(
         &self,
         remote_models_feature: bool,
+        catalog_locale: Option<&str>,
+        user_agent_suffix: Option<&str>,
+        reason: RefreshReason,
     ) -> CoreResult<()> {
-        if !remote_models_feature || self.auth_manager.get_auth_mode() == Some(AuthMode::ApiKey) {
+        if !self.fetches_remote_catalog(remote_models_feature) {
             return Ok(());
         }
-        let auth = self.auth_manager.auth();
-        let api_provider = self.provider.to_api_provider(Some(AuthMode::ChatGPT))?;
-        let api_auth = auth_provider_from_auth(auth.clone(), &self.provider).await?;
-        let transport = ReqwestTransport::new(build_reqwest_client());
-        let client = ModelsClient::new(transport, api_provider, api_auth);
-
-        let client_version = format_client_version_to_whole();
-        let (models, etag) = client
-            .list_models(&client_version, HeaderMap::new())
+        self.remote_catalog(user_agent_suffix)
+            .refresh_reporting(reason, catalog_locale)
             .await
-            .map_err(map_api_error)?;
+    }
 
-        self.apply_remote_models(models.clone()).await;
-        *self.etag.write().await = etag.clone();
-        self.persist_cache(&models, etag).await;
-        Ok(())
+    fn fetches_remote_catalog(&self, remote_models_feature: bool) -> bool {
+        remote_models_feature && self.auth_manager.get_auth_mode() != Some(AuthMode::ApiKey)
     }
 
-    pub async fn list_models(&self, config: &Config) -> Vec<ModelPreset> {
-        if let Err(err) = self.refresh_available_models_with_cache(config).await {
-            error!("failed to refresh available models: {err}");
+    /// Refresh the remote catalog in a background task, unless one is
+    /// already running, so the stale cache just served gets replaced.
+    fn revalidate_in_background(
+        &self,
+        catalog_locale: Option<&str>,
+        user_agent_suffix: Option<&str>,
+    ) {
+        let Ok(mut revalidation) = self.revalidation.lock() else {
+            return;
+        };
+        if revalidation
+            .as_ref()
+            .is_some_and(|task| !task.is_finished())
+        {
+            return;
         }
-        let remote_models = self.remote_models(config).await;
-        self.build_available_models(remote_models)
+        let remote = self.remote_catalog(user_agent_suffix);
+        let catalog_locale = catalog_locale.map(str::to_string);
+        *revalidation = Some(tokio::spawn(async move {
+            if let Err(err) = remote
+                .refresh_reporting(RefreshReason::StaleCache, catalog_locale.as_deref())
+                .await
+            {
+                error!("failed to revalidate the models cache: {err}");
+            }
+        }));
     }
 
-    pub fn try_list_models(&self, config: &Config) -> Result<Vec<ModelPreset>, TryLockError> {
-        let remote_models = self.try_get_remote_models(config)?;
-        Ok(self.build_available_models(remote_models))
+    /// Whether the catalogs last served are being refreshed in the
+    /// background, because they were served past their cache TTL.
+    pub fn catalog_freshness(&self) -> CatalogFreshness {
+        let running = |task: &Mutex<Option<JoinHandle<()>>>| {
+            task.lock()
+                .is_ok_and(|task| task.as_ref().is_some_and(|task| !task.is_finished()))
+        };
+        if running(&self.revalidation) || running(&self.provider_revalidation) {
+            CatalogFreshness::Revalidating
+        } else {
+            CatalogFreshness::Fresh
+        }
     }
 
-    fn find_family_for_model(slug: &str) -> ModelFamily {
-        super::model_family::find_family_for_model(slug)
+    /// Wait for the background refreshes started by serving stale catalogs,
+    /// if any are running, e.g. before a short-lived process exits.
+    pub async fn finish_revalidation(&self) {
+        for revalidation in [&self.revalidation, &self.provider_revalidation] {
+            let task = revalidation
+                .lock()
+                .ok()
+                .and_then(|mut revalidation| revalidation.take());
+            if let Some(task) = task
+                && let Err(err) = task.await
+            {
+                error!("models cache revalidation did not finish: {err}");
+            }
+        }
     }
 
-    /// Look up the requested model family while applying remote metadata overrides.
-    pub async fn construct_model_family(&self, model: &str, config: &Config) -> ModelFamily {
-        Self::find_family_for_model(model)
-            .with_remote_overrides(self.remote_models(config).await)
-            .with_config_overrides(config)
+    /// The remote catalog, fetched with `user_agent_suffix` on its
+    /// User-Agent.
+    fn remote_catalog(&self, user_agent_suffix: Option<&str>) -> RemoteCatalog {
+        RemoteCatalog {
+            auth_manager: Arc::clone(&self.auth_manager),
+            provider: Arc::clone(&self.provider),
+            cache_path: self.cache_path(),
+            models: Arc::clone(&self.remote_models),
+            etag: Arc::clone(&self.etag),
+            fetch: Arc::clone(&self.remote_fetch),
+            revision: Arc::clone(&self.revision),
+            events: self.refresh_events.clone(),
+            default_model: Arc::clone(&self.remote_default),
+            user_agent_suffix: user_agent_suffix.map(str::to_string),
+        }
     }
 
-    pub async fn get_model(&self, model: &Option<String>, config: &Config) -> String {
-        if let Some(model) = model.as_ref() {
-            return model.to_string();
+    /// Re-list the models served by every configured provider that declares a
+    /// `model_catalog`. A provider that fails to respond keeps the models it
+    /// listed last time, and the failure is recorded for
+    /// [`Self::catalog_warnings`]. In offline
+    /// mode only providers with `model_catalog = "none"` are listed, since
+    /// their models come from config. Nothing is listed when
+    /// `model_catalog_sources` leaves providers out.
+    ///
+    /// Reported to [`Self::subscribe_refresh_events`] as one refresh, which
+    /// fails when any provider's catalog does.
+    pub async fn refresh_provider_models(&self, config: &Config) {
+        if self.lists_provider_catalogs(config) {
+            self.provider_catalogs()
+                .refresh(config, Some(RefreshReason::Relist), |_, _| true)
+                .await;
         }
-        if let Err(err) = self.refresh_available_models_with_cache(config).await {
-            error!("failed to refresh available models: {err}");
+    }
+
+    /// Like [`Self::refresh_provider_models`], but honouring the cache TTL:
+    /// a catalog listed within it is served as it is, and one listed before
+    /// it is served while a background task lists it again. Only catalogs
+    /// never listed yet are waited for; models from config are always
+    /// re-read, since that costs no request.
+    async fn refresh_provider_models_with_cache(&self, config: &Config) {
+        if !self.lists_provider_catalogs(config) {
+            return;
         }
-        // if codex-auto-balanced exists & signed in with chatgpt mode, return it, otherwise return the default model
-        let auth_mode = self.auth_manager.get_auth_mode();
-        let remote_models = self.remote_models(config).await;
-        if auth_mode == Some(AuthMode::ChatGPT)
-            && self
-                .build_available_models(remote_models)
-                .iter()
-                .any(|m| m.model == CODEX_AUTO_BALANCED_MODEL)
-        {
-            return CODEX_AUTO_BALANCED_MODEL.to_string();
-        } else if auth_mode == Some(AuthMode::ChatGPT) {
-            return OPENAI_DEFAULT_CHATGPT_MODEL.to_string();
+        let providers = self.provider_catalogs();
+        providers
+            .refresh(
+                config,
+                Some(RefreshReason::CacheMiss),
+                |catalog, previous| catalog.catalog == ModelCatalog::None || previous.is_none(),
+            )
+            .await;
+        let ttl = self.cache_ttl(config);
+        let now = Utc::now();
+        let stale = providers.fetches.read().await.iter().any(|record| {
+            record
+                .fetched_at
+                .max(record.last_error_at)
+                .and_then(|listed| now.signed_duration_since(listed).to_std().ok())
+                .is_some_and(|age| age >= ttl)
+        });
+        if stale {
+            self.relist_providers_in_background(config);
         }
-        OPENAI_DEFAULT_API_MODEL.to_string()
     }
-    pub async fn refresh_if_new_etag(&self, etag: String, remote_models_feature: bool) {
-        let current_etag = self.get_etag().await;
-        if current_etag.clone().is_some() && current_etag.as_deref() == Some(etag.as_str()) {
+
+    fn lists_provider_catalogs(&self, config: &Config) -> bool {
+        config
+            .model_catalog_sources
+            .contains(&ModelCatalogSource::Providers)
+            && self.imported(config).is_none()
+    }
+
+    /// Re-list the provider catalogs in a background task, unless one is
+    /// already running, so the stale listings just served get replaced.
+    fn relist_providers_in_background(&self, config: &Config) {
+        let Ok(mut revalidation) = self.provider_revalidation.lock() else {
             return;
-        }
-        if let Err(err) = self
-            .refresh_available_models_no_cache(remote_models_feature)
-            .await
+        };
+        if revalidation
+            .as_ref()
+            .is_some_and(|task| !task.is_finished())
         {
-            error!("failed to refresh available models: {err}");
+            return;
         }
+        let providers = self.provider_catalogs();
+        let config = config.clone();
+        *revalidation = Some(tokio::spawn(async move {
+            providers
+                .refresh(&config, Some(RefreshReason::StaleCache), |_, _| true)
+                .await;
+        }));
     }
 
-    #[cfg(any(test, feature = "test-support"))]
-    pub fn get_model_offline(model: Option<&str>) -> String {
-        model.unwrap_or(OPENAI_DEFAULT_CHATGPT_MODEL).to_string()
+    fn provider_catalogs(&self) -> ProviderCatalogs {
+        ProviderCatalogs {
+            models: Arc::clone(&self.provider_models),
+            fetches: Arc::clone(&self.provider_fetches),
+            revision: Arc::clone(&self.revision),
+            events: self.refresh_events.clone(),
+        }
     }
 
-    #[cfg(any(test, feature = "test-support"))]
-    /// Offline helper that builds a `ModelFamily` without consulting remote state.
-    pub fn construct_model_family_offline(model: &str, config: &Config) -> ModelFamily {
-        Self::find_family_for_model(model).with_config_overrides(config)
+    /// Warm every catalog a session would list, for `prefetch_model_catalog`.
+    /// Meant to be spawned at startup so nothing waits on it; does nothing in
+    /// offline mode.
+    pub async fn prefetch_catalog(&self, config: &Config) {
+        if config.offline {
+            return;
+        }
+        if let Ok(mut stats) = self.prefetch.lock() {
+            stats.started = true;
+        }
+        if let Err(err) = self.refresh_available_models_with_cache(config).await {
+            error!("failed to prefetch available models: {err}");
+        }
+        self.refresh_provider_models_with_cache(config).await;
+        if let Ok(mut stats) = self.prefetch.lock() {
+            stats.completed = true;
+        }
     }
 
-    async fn get_etag(&self) -> Option<String> {
-        self.etag.read().await.clone()
+    /// Record that the model picker was opened, counting whether the prefetch
+    /// had already warmed the catalog it shows.
+    pub fn note_picker_opened(&self) {
+        let Ok(mut stats) = self.prefetch.lock() else {
+            return;
+        };
+        if stats.completed {
+            stats.picker_served_from_prefetch += 1;
+        } else {
+            stats.picker_served_cold += 1;
+        }
+        debug!(
+            "model picker opened (served from prefetch: {}, cold: {})",
+            stats.picker_served_from_prefetch, stats.picker_served_cold
+        );
     }
 
-    /// Replace the cached remote models and rebuild the derived presets list.
-    async fn apply_remote_models(&self, models: Vec<ModelInfo>) {
-        *self.remote_models.write().await = models;
+    pub fn prefetch_stats(&self) -> PrefetchStats {
+        self.prefetch.lock().map(|stats| *stats).unwrap_or_default()
     }
 
-    fn load_remote_models_from_file() -> Result<Vec<ModelInfo>, std::io::Error> {
-        let file_contents = include_str!("../../models.json");
-        let response: ModelsResponse = serde_json::from_str(file_contents)?;
-        Ok(response.models)
+    /// The last rebuild a [`ModelCatalogHandle`] on this manager finished, or
+    /// `None` when no handle keeps it refreshed.
+    ///
+    /// [`ModelCatalogHandle`]: crate::models_manager::catalog_handle::ModelCatalogHandle
+    pub fn last_catalog_rebuild(&self) -> Option<CatalogRebuild> {
+        self.last_rebuild
+            .lock()
+            .map(|last| last.clone())
+            .unwrap_or_default()
     }
 
-    /// Attempt to satisfy the refresh from the cache when it matches the provider and TTL.
-    async fn try_load_cache(&self) -> bool {
-        // todo(aibrahim): think if we should store fetched_at in ModelsManager so we don't always need to read the disk
-        let cache_path = self.cache_path();
-        let cache = match cache::load_cache(&cache_path).await {
-            Ok(cache) => cache,
-            Err(err) => {
-                error!("failed to load models cache: {err}");
-                return false;
-            }
-        };
-        let cache = match cache {
-            Some(cache) => cache,
-            None => return false,
-        };
-        if !cache.is_fresh(self.cache_ttl) {
-            return false;
+    /// Remember `rebuild`, and record it under `$CODEX_HOME`, next to the
+    /// rebuilds of other catalogs, for `codex models status` in other
+    /// processes.
+    pub(crate) async fn record_catalog_rebuild(&self, config: &Config, rebuild: CatalogRebuild) {
+        let catalog = self.catalog_key(&self.catalog_auth(config));
+        if let Err(err) = write_last_rebuild(&self.codex_home, &catalog, &rebuild).await {
+            warn!("failed to record the last catalog rebuild: {err}");
+        }
+        if let Ok(mut last) = self.last_rebuild.lock() {
+            *last = Some(rebuild);
         }
-        let models = cache.models.clone();
-        *self.etag.write().await = cache.etag.clone();
-        self.apply_remote_models(models.clone()).await;
-        true
     }
 
-    /// Serialize the latest fetch to disk for reuse across future processes.
-    async fn persist_cache(&self, models: &[ModelInfo], etag: Option<String>) {
-        let cache = ModelsCache {
-            fetched_at: Utc::now(),
-            etag,
-            models: models.to_vec(),
-        };
-        let cache_path = self.cache_path();
-        if let Err(err) = cache::save_cache(&cache_path, &cache).await {
-            error!("failed to write models cache: {err}");
-        }
+    /// Catalogs whose most recent refresh failed, remote `/models` first and
+    /// then providers by id. Each warning lasts until that catalog is next
+    /// refreshed successfully.
+    pub async fn catalog_warnings(&self) -> Vec<CatalogWarning> {
+        let remote = self.remote_fetch.read().await.clone();
+        let providers = self.provider_fetches.read().await.clone();
+        Self::warnings_from_records(&remote, &providers)
     }
 
-    /// Merge remote model metadata into picker-ready presets, preserving existing entries.
-    fn build_available_models(&self, mut remote_models: Vec<ModelInfo>) -> Vec<ModelPreset> {
-        remote_models.sort_by(|a, b| a.priority.cmp(&b.priority));
+    /// Like [`Self::catalog_warnings`] without waiting, for callers that
+    /// cannot await, such as the TUI model picker.
+    pub fn try_catalog_warnings(&self) -> Result<Vec<CatalogWarning>, TryLockError> {
+        let remote = self.remote_fetch.try_read()?;
+        let providers = self.provider_fetches.try_read()?;
+        Ok(Self::warnings_from_records(&remote, &providers))
+    }
 
-        let remote_presets: Vec<ModelPreset> = remote_models.into_iter().map(Into::into).collect();
-        let existing_presets = self.local_models.clone();
-        let mut merged_presets = Self::merge_presets(remote_presets, existing_presets);
-        merged_presets = self.filter_visible_models(merged_presets);
+    /// Set when the remote catalog, or a provider catalog, recommends a
+    /// newer client than this one. Only ever a notice to show: this version
+    /// keeps working.
+    pub async fn client_version_notice(&self, config: &Config) -> Option<ClientVersionNotice> {
+        let remote = self.remote_fetch.read().await;
+        let providers = self.provider_fetches.read().await;
+        Self::client_version_notice_from_records(config, &remote, &providers)
+    }
 
-        let has_default = merged_presets.iter().any(|preset| preset.is_default);
-        if let Some(default) = merged_presets.first_mut()
-            && !has_default
-        {
-            default.is_default = true;
-        }
+    /// Like [`Self::client_version_notice`] without waiting.
+    pub fn try_client_version_notice(
+        &self,
+        config: &Config,
+    ) -> Result<Option<ClientVersionNotice>, TryLockError> {
+        let remote = self.remote_fetch.try_read()?;
+        let providers = self.provider_fetches.try_read()?;
+        Ok(Self::client_version_notice_from_records(
+            config, &remote, &providers,
+        ))
+    }
 
-        merged_presets
+    /// The notice for the highest minimum any catalog last served. The remote
+    /// catalog only counts while remote models are enabled.
+    fn client_version_notice_from_records(
+        config: &Config,
+        remote: &FetchRecord,
+        providers: &[ProviderFetchRecord],
+    ) -> Option<ClientVersionNotice> {
+        let remote = remote
+            .min_client_version
+            .as_deref()
+            .filter(|_| config.features.enabled(Feature::RemoteModels));
+        client_version::client_version_notice(
+            remote.into_iter().chain(
+                providers
+                    .iter()
+                    .filter_map(|record| record.min_client_version.as_deref()),
+            ),
+            CLIENT_VERSION,
+        )
     }
 
-    fn filter_visible_models(&self, models: Vec<ModelPreset>) -> Vec<ModelPreset> {
-        let chatgpt_mode = self.auth_manager.get_auth_mode() == Some(AuthMode::ChatGPT);
-        models
+    fn warnings_from_records(
+        remote: &FetchRecord,
+        providers: &[ProviderFetchRecord],
+    ) -> Vec<CatalogWarning> {
+        let remote_warning = match (&remote.last_error, remote.last_error_at) {
+            (Some(error), Some(failed_at)) => Some(CatalogWarning {
+                provider_id: None,
+                fetched_at: remote.fetched_at,
+                failed_at,
+                error: error.clone(),
+                unauthorized: false,
+                token_attempts: remote.token_attempts.clone(),
+            }),
+            _ => None,
+        };
+        remote_warning
             .into_iter()
-            .filter(|model| model.show_in_picker && (chatgpt_mode || model.supported_in_api))
+            .chain(providers.iter().filter_map(|record| {
+                Some(CatalogWarning {
+                    provider_id: Some(record.provider_id.clone()),
+                    fetched_at: record.fetched_at,
+                    failed_at: record.last_error_at?,
+                    error: record.last_error.clone()?,
+                    unauthorized: record.last_error_unauthorized,
+                    token_attempts: record.token_attempts.clone(),
+                })
+            }))
             .collect()
     }
 
-    fn merge_presets(
-        remote_presets: Vec<ModelPreset>,
-        existing_presets: Vec<ModelPreset>,
-    ) -> Vec<ModelPreset> {
-        if remote_presets.is_empty() {
-            return existing_presets;
+    /// Report where each catalog entry came from and how fresh it is. Reads the
+    /// bookkeeping left by earlier refreshes without fetching anything.
+    pub async fn catalog_status(&self, config: &Config) -> CatalogStatus {
+        let remote = self.remote_fetch.read().await.clone();
+        let remote_models = self.remote_models(config).await;
+        let presets = match self.imported(config) {
+            Some(imported) => imported.export.provenance.clone(),
+            None => self.preset_provenance(config, remote_models, &remote).await,
+        };
+        CatalogStatus {
+            offline: config.offline,
+            sources: config.model_catalog_sources.clone(),
+            remote,
+            providers: self.provider_fetches.read().await.clone(),
+            presets,
+            prefetch: self.prefetch_stats(),
+            schedule: CatalogSchedule {
+                cache_ttl_ms: as_millis(self.cache_ttl(config)),
+                refresh_interval_ms: as_millis(self.refresh_interval(config)),
+                jitter: config.catalog_refresh_jitter,
+            },
+            imported: self.imported_catalog(config),
+            last_rebuild: self.last_catalog_rebuild().or_else(|| {
+                read_last_rebuild(
+                    &self.codex_home,
+                    &self.catalog_key(&self.catalog_auth(config)),
+                )
+            }),
         }
+    }
 
-        let remote_slugs: HashSet<&str> = remote_presets
+    /// Where each listed preset came from.
+    async fn preset_provenance(
+        &self,
+        config: &Config,
+        remote_models: Vec<ModelInfo>,
+        remote: &FetchRecord,
+    ) -> Vec<PresetProvenance> {
+        let remote_slugs: HashSet<String> = remote_models
             .iter()
-            .map(|preset| preset.model.as_str())
+            .map(|model| model.slug.clone())
             .collect();
-
-        let mut merged_presets = remote_presets.clone();
-        for mut preset in existing_presets {
-            if remote_slugs.contains(preset.model.as_str()) {
-                continue;
+        self.build_available_models(
+            &config.model_catalog_sources,
+            self.imported(config),
+            remote_models,
+            &self.provider_models.read().await,
+        )
+        .into_iter()
+        .map(|preset| {
+            let source = match preset.model_provider {
+                Some(provider_id) => PresetSource::Provider { provider_id },
+                None if remote_slugs.contains(&preset.model) => PresetSource::Remote {
+                    source: remote.source,
+                },
+                None => PresetSource::Builtin,
+            };
+            PresetProvenance {
+                overridden: config.model_preset_overrides.contains_key(&preset.id),
+                id: preset.id,
+                source,
             }
-            preset.is_default = false;
-            merged_presets.push(preset);
+        })
+        .collect()
+    }
+
+    /// The catalog as this process sees it, for `codex models export`: every
+    /// preset, hidden ones included, with the bookkeeping of
+    /// [`Self::catalog_status`] and its errors redacted.
+    pub async fn export_catalog(&self, config: &Config) -> CatalogExport {
+        let presets = self.list_all_models(config).await;
+        let status = self.catalog_status(config).await;
+        CatalogExport {
+            schema_version: CATALOG_EXPORT_SCHEMA_VERSION,
+            codex_version: CLIENT_VERSION.to_string(),
+            exported_at: Utc::now(),
+            revision: self.catalog_revision(),
+            model_provider_id: Some(config.model_provider_id.clone()),
+            sources: status.sources,
+            remote: status.remote,
+            providers: status.providers,
+            provenance: status.presets,
+            presets,
         }
+        .redacted()
+    }
 
-        merged_presets
+    /// The imported catalog listed in place of the fetched ones for sessions
+    /// on `config`'s provider, if any.
+    pub fn imported_catalog(&self, config: &Config) -> Option<ImportedCatalogInfo> {
+        self.imported(config).map(ImportedCatalog::info)
     }
 
-    async fn remote_models(&self, config: &Config) -> Vec<ModelInfo> {
-        if config.features.enabled(Feature::RemoteModels) {
-            self.remote_models.read().await.clone()
+    /// The imported catalog, when it is scoped to `config`'s provider.
+    fn imported(&self, config: &Config) -> Option<&ImportedCatalog> {
+        self.imported
+            .as_ref()
+            .filter(|imported| imported.applies_to(&config.model_provider_id))
+    }
+
+    /// Re-fetch every catalog, bypassing the on-disk cache TTL. Callers are
+    /// expected to reject this in offline mode before calling it. The remote
+    /// catalog is skipped when the active provider's models are fixed by config.
+    ///
+    /// Reported to [`Self::subscribe_refresh_events`] as one refresh, which
+    /// fails when the remote catalog or the active provider's catalog does.
+    pub async fn refresh_all_models(&self, config: &Config) -> CoreResult<()> {
+        if let Ok(mut manual_refresh) = self.manual_refresh.lock() {
+            *manual_refresh = Some(Instant::now());
+        }
+        self.refresh_events.started(RefreshReason::Manual);
+        let before = self.catalog_model_ids().await;
+        let result = self.refresh_every_catalog(config).await;
+        let active_provider_failure = if config.model_provider.model_catalog.is_some() {
+            self.catalog_warnings()
+                .await
+                .into_iter()
+                .find(|warning| warning.provider_id.as_ref() == Some(&config.model_provider_id))
         } else {
-            Vec::new()
+            None
+        };
+        match (&result, active_provider_failure) {
+            (Err(err), _) => self.refresh_events.failed(err.to_string()),
+            (Ok(()), Some(warning)) => self.refresh_events.failed(warning.to_string()),
+            (Ok(()), None) => {
+                let after = self.catalog_model_ids().await;
+                self.refresh_events
+                    .succeeded(self.catalog_revision(), &before, &after);
+            }
         }
+        result
     }
 
-    fn try_get_remote_models(&self, config: &Config) -> Result<Vec<ModelInfo>, TryLockError> {
-        if config.features.enabled(Feature::RemoteModels) {
-            Ok(self.remote_models.try_read()?.clone())
-        } else {
-            Ok(Vec::new())
+    async fn refresh_every_catalog(&self, config: &Config) -> CoreResult<()> {
+        if !config.model_provider.has_fixed_models()
+            && config
+                .model_catalog_sources
+                .contains(&ModelCatalogSource::Remote)
+            && self.fetches_remote_catalog(config.features.enabled(Feature::RemoteModels))
+            && self.imported(config).is_none()
+        {
+            self.remote_catalog(config.user_agent_suffix.as_deref())
+                .refresh(resolve_catalog_locale(config).as_deref())
+                .await?;
+        }
+        // Reported as part of the manual refresh, not on its own.
+        if self.lists_provider_catalogs(config) {
+            self.provider_catalogs()
+                .refresh(config, None, |_, _| true)
+                .await;
         }
+        Ok(())
     }
 
-    fn cache_path(&self) -> PathBuf {
-        self.codex_home.join(MODEL_CACHE_FILE)
+    /// The remote model slugs and provider-served preset ids, for what a
+    /// refresh added and removed.
+    async fn catalog_model_ids(&self) -> HashSet<String> {
+        let remote = remote_model_ids(&self.remote_models.read().await);
+        let providers = self.provider_models.read().await;
+        remote
+            .into_iter()
+            .chain(providers.iter().map(|preset| preset.id.clone()))
+            .collect()
     }
-}
 
-/// Convert a client version string to a whole version string (e.g. "1.2.3-alpha.4" -> "1.2.3")
-fn format_client_version_to_whole() -> String {
-    format!(
-        "{}.{}.{}",
-        env!("CARGO_PKG_VERSION_MAJOR"),
-        env!("CARGO_PKG_VERSION_MINOR"),
-        env!("CARGO_PKG_VERSION_PATCH")
-    )
-}
+    /// Watch catalog refreshes start and end, e.g. to show that models are
+    /// syncing. Best-effort; see [`super::refresh_events`].
+    pub fn subscribe_refresh_events(&self) -> broadcast::Receiver<CatalogRefreshEvent> {
+        self.refresh_events.subscribe()
+    }
 
-#[cfg(test)]
-mod tests {
-    use super::cache::ModelsCache;
-    use super::*;
-    use crate::CodexAuth;
-    use crate::auth::AuthCredentialsStoreMode;
-    use crate::config::ConfigBuilder;
-    use crate::features::Feature;
-    use crate::model_provider_info::WireApi;
-    use codex_protocol::openai_models::ModelsResponse;
-    use core_test_support::responses::mount_models_once;
-    use pretty_assertions::assert_eq;
-    use serde_json::json;
-    use tempfile::tempdir;
-    use wiremock::MockServer;
+    /// The built-in and remote presets as known from the on-disk cache,
+    /// however old, without fetching anything. Provider-served models are not
+    /// cached and are left out.
+    pub async fn cached_models(&self, config: &Config) -> Vec<ModelPreset> {
+        self.try_load_cache(None, resolve_catalog_locale(config).as_deref())
+            .await;
+        let remote_models = self.remote_models(config).await;
+        self.assemble_models(config, remote_models, &[])
+    }
 
-    fn remote_model(slug: &str, display: &str, priority: i32) -> ModelInfo {
-        remote_model_with_visibility(slug, display, priority, "list")
+    /// Every preset currently known, including provider-served ones, without
+    /// refreshing or reading the cache. Used to compare catalogs before and
+    /// after a refresh.
+    pub async fn known_models(&self, config: &Config) -> Vec<ModelPreset> {
+        let remote_models = self.remote_models(config).await;
+        self.assemble_models(config, remote_models, &self.provider_models.read().await)
     }
 
-    fn remote_model_with_visibility(
-        slug: &str,
-        display: &str,
-        priority: i32,
-        visibility: &str,
-    ) -> ModelInfo {
-        serde_json::from_value(json!({
-            "slug": slug,
-            "display_name": display,
-            "description": format!("{display} desc"),
-            "default_reasoning_level": "medium",
-            "supported_reasoning_levels": [{"effort": "low", "description": "low"}, {"effort": "medium", "description": "medium"}],
-            "shell_type": "shell_command",
-            "visibility": visibility,
-            "minimal_client_version": [0, 1, 0],
-            "supported_in_api": true,
-            "priority": priority,
-            "upgrade": null,
-            "base_instructions": null,
-            "supports_reasoning_summaries": false,
-            "support_verbosity": false,
-            "default_verbosity": null,
-            "apply_patch_tool_type": null,
-            "truncation_policy": {"mode": "bytes", "limit": 10_000},
-            "supports_parallel_tool_calls": false,
-            "context_window": null,
-            "experimental_supported_tools": [],
-        }))
-        .expect("valid model")
+    /// Pricing of every priced model currently known, keyed by slug. When
+    /// several presets serve a slug, the first one listed wins. Reads what is
+    /// already loaded without refreshing anything.
+    pub async fn model_pricing(&self, config: &Config) -> HashMap<String, ModelPricing> {
+        let mut pricing = HashMap::new();
+        for preset in self.known_models(config).await {
+            if let Some(price) = preset.pricing {
+                pricing.entry(preset.model).or_insert(price);
+            }
+        }
+        pricing
     }
 
-    fn provider_for(base_url: String) -> ModelProviderInfo {
-        ModelProviderInfo {
-            name: "mock".into(),
-            base_url: Some(base_url),
-            env_key: None,
-            env_key_instructions: None,
-            experimental_bearer_token: None,
-            wire_api: WireApi::Responses,
-            query_params: None,
+    pub async fn list_models(&self, config: &Config) -> Vec<ModelPreset> {
+        self.model_list(config).await.models
+    }
+
+    /// Like [`Self::list_models`], but keeps presets hidden from the picker
+    /// and those the current credentials cannot use, in the same order.
+    pub async fn list_all_models(&self, config: &Config) -> Vec<ModelPreset> {
+        // Listing refreshes the catalogs the same way the picker does.
+        self.list_models(config).await;
+        let remote_models = self.remote_models(config).await;
+        self.assemble_all_models(config, remote_models, &self.provider_models.read().await)
+    }
+
+    /// Every preset already in memory, hidden ones included; backs
+    /// [`PresetCatalog::find_known`].
+    pub(super) fn loaded_all_models(
+        &self,
+        config: &Config,
+    ) -> Result<Vec<ModelPreset>, TryLockError> {
+        let remote_models = self.try_get_remote_models(config)?;
+        Ok(self.assemble_all_models(config, remote_models, &self.provider_models.try_read()?))
+    }
+
+    /// Like [`Self::list_models`], also returning the listed revision and the
+    /// credentials the catalog was requested with. The revision is computed
+    /// from the returned models and credentials, see [`Self::listed_revision`].
+    /// A change of credentials since the last listing also bumps
+    /// [`Self::catalog_revision`], since it changes which models will work.
+    pub async fn model_list(&self, config: &Config) -> ModelList {
+        let auth = self.catalog_auth(config);
+        let mut last_catalog_auth = self.last_catalog_auth.write().await;
+        if last_catalog_auth
+            .as_ref()
+            .is_some_and(|last_auth| *last_auth != auth)
+        {
+            self.bump_revision();
+        }
+        *last_catalog_auth = Some(auth.clone());
+        drop(last_catalog_auth);
+
+        if let Err(err) = self.refresh_available_models_with_cache(config).await {
+            error!("failed to refresh available models: {err}");
+        }
+        self.refresh_provider_models_with_cache(config).await;
+        let generation = self.catalog_revision();
+        let remote_models = self.remote_models(config).await;
+        let extra = remote_models
+            .iter()
+            .filter(|info| !info.extra.is_empty())
+            .map(|info| {
+                let id = info.preset_id.clone().unwrap_or_else(|| info.slug.clone());
+                (id, info.extra.clone())
+            })
+            .collect();
+        let provider_models = self.provider_models.read().await.clone();
+        let models = self.assemble_models(config, remote_models.clone(), &provider_models);
+        let hidden_count = self.hidden_count(config, remote_models, &provider_models, models.len());
+        let remote_fetch = self.remote_fetch.read().await.clone();
+        let provider_fetches = self.provider_fetches.read().await.clone();
+        let status = self.snapshot_status(
+            config,
+            &remote_fetch,
+            &provider_fetches,
+            self.get_etag().await,
+            hidden_count,
+        );
+        self.snapshot.store(Some(Arc::new(
+            CatalogSnapshot::new(generation, models.clone()).with_status(status),
+        )));
+        self.collect_stale_model_state(config, &auth, generation)
+            .await;
+        ModelList {
+            revision: content_revision(&auth, &models),
+            auth,
+            warnings: self.catalog_warnings().await,
+            freshness: self.catalog_freshness(),
+            models,
+            extra,
+            client_version_notice: self.client_version_notice(config).await,
+            last_rebuild: self.last_catalog_rebuild(),
+        }
+    }
+
+    /// How the user is signed in, which decides the models they can use.
+    pub(crate) fn auth_mode(&self) -> Option<AuthMode> {
+        self.auth_manager.get_auth_mode()
+    }
+
+    /// Describe the credentials used for the selected provider's catalog:
+    /// the provider's own key when it serves a `model_catalog`, otherwise the
+    /// signed-in Codex credentials used for the remote `/models` catalog.
+    pub fn catalog_auth(&self, config: &Config) -> CatalogAuth {
+        let provider = &config.model_provider;
+        if provider.model_catalog.is_some() || !provider.requires_openai_auth {
+            let credential = match &provider.env_key {
+                _ if provider.allow_anonymous_catalog => None,
+                Some(name) if provider.api_key().ok().flatten().is_some() => {
+                    Some(CredentialSource::EnvVar { name: name.clone() })
+                }
+                _ => provider
+                    .experimental_bearer_token
+                    .as_ref()
+                    .map(|_| CredentialSource::Config),
+            };
+            return CatalogAuth {
+                provider: provider.name.clone(),
+                host: provider
+                    .to_api_provider(None)
+                    .ok()
+                    .and_then(|api_provider| redact_host(&api_provider.base_url)),
+                mode: credential.as_ref().map(|_| AuthMode::ApiKey),
+                credential,
+                account: None,
+            };
+        }
+
+        let auth = self
+            .auth_manager
+            .auth()
+            .filter(|_| !self.provider.allow_anonymous_catalog);
+        let mode = auth.as_ref().map(|auth| auth.mode);
+        let credential = mode.map(|_| {
+            if self.auth_manager.auth_from_env() {
+                CredentialSource::EnvVar {
+                    name: CODEX_API_KEY_ENV_VAR.to_string(),
+                }
+            } else {
+                CredentialSource::Stored
+            }
+        });
+        CatalogAuth {
+            provider: self.provider.name.clone(),
+            host: self
+                .provider
+                .to_api_provider(mode)
+                .ok()
+                .and_then(|api_provider| redact_host(&api_provider.base_url)),
+            mode,
+            credential,
+            account: auth
+                .filter(|auth| auth.mode == AuthMode::ChatGPT)
+                .and_then(|auth| auth.get_account_email()),
+        }
+    }
+
+    /// Monotonic counter that changes whenever a refresh changes the catalog.
+    /// Refreshes that return the same models leave it alone. It starts over
+    /// in every process, so it only orders changes seen by this one; clients
+    /// are given [`Self::listed_revision`] instead.
+    pub fn catalog_revision(&self) -> u64 {
+        *self.revision.borrow()
+    }
+
+    /// The revision `model/list` reports for `config`: a digest of the models
+    /// listed and the credentials they were listed with. It changes whenever
+    /// the listing would, including after a config change that reshapes the
+    /// presets, and stays the same across restarts that list the same models.
+    /// Reads what is already loaded without refreshing anything.
+    pub async fn listed_revision(&self, config: &Config) -> u64 {
+        content_revision(&self.catalog_auth(config), &self.known_models(config).await)
+    }
+
+    /// The remote catalog state to record in the rollout of a new session.
+    pub async fn session_catalog(&self) -> SessionCatalog {
+        let remote = self.remote_fetch.read().await.clone();
+        SessionCatalog {
+            revision: self.get_etag().await,
+            source: remote.source.into(),
+            fetched_at: remote.fetched_at.map(|fetched_at| fetched_at.to_rfc3339()),
+        }
+    }
+
+    /// What a feedback report should say about a turn running `model` at
+    /// `effort`: its preset and where it came from, the catalog revision, and
+    /// the most recent catalog refresh failure.
+    pub async fn feedback_snapshot(
+        &self,
+        model: &str,
+        effort: Option<ReasoningEffort>,
+        context_window: Option<i64>,
+        config: &Config,
+    ) -> ModelFeedbackSnapshot {
+        let (preset, preset_source) = self
+            .active_preset(model, &config.model_provider_id, config)
+            .await
+            .unzip();
+        let catalog = self.session_catalog().await;
+        let catalog_error = self
+            .catalog_warnings()
+            .await
+            .into_iter()
+            .max_by_key(|warning| warning.failed_at)
+            .map(|warning| redact_error(&warning.to_string()));
+        ModelFeedbackSnapshot {
+            model: model.to_string(),
+            preset_id: preset.map(|preset| preset.id),
+            preset_source,
+            context_window,
+            effort,
+            catalog_revision: catalog.revision,
+            catalog_fetched_at: catalog.fetched_at,
+            catalog_error,
+        }
+    }
+
+    /// Watch [`Self::catalog_revision`] for changes.
+    pub fn subscribe_catalog_revision(&self) -> watch::Receiver<u64> {
+        self.revision.subscribe()
+    }
+
+    /// How long a fetched remote catalog is served from the on-disk cache,
+    /// moved by this process's `catalog_refresh_jitter`.
+    pub(crate) fn cache_ttl(&self, config: &Config) -> Duration {
+        jitter::jittered(self.cache_ttl, config.catalog_refresh_jitter)
+    }
+
+    /// How often a [`ModelCatalogHandle`] refreshes in the background: once
+    /// per jittered cache TTL, so the cache is never served past it for long.
+    ///
+    /// [`ModelCatalogHandle`]: crate::models_manager::catalog_handle::ModelCatalogHandle
+    pub(crate) fn refresh_interval(&self, config: &Config) -> Duration {
+        self.cache_ttl(config)
+    }
+
+    /// When the catalogs were last refreshed on request, ignoring the cache.
+    pub(crate) fn last_manual_refresh(&self) -> Option<Instant> {
+        self.manual_refresh.lock().ok().and_then(|refresh| *refresh)
+    }
+
+    #[deprecated(note = "use `PresetCatalog::load_cached_or_static`")]
+    pub fn try_list_models(&self, config: &Config) -> Result<Vec<ModelPreset>, TryLockError> {
+        PresetCatalog::new(self, config).load_cached_or_static()
+    }
+
+    /// The picker presets already in memory; backs
+    /// [`PresetCatalog::load_cached_or_static`].
+    pub(super) fn loaded_models(&self, config: &Config) -> Result<Vec<ModelPreset>, TryLockError> {
+        let remote_models = self.try_get_remote_models(config)?;
+        Ok(self.assemble_models(config, remote_models, &self.provider_models.try_read()?))
+    }
+
+    /// Backs [`PresetCatalog::snapshot`]. A snapshot older than the catalog
+    /// revision is rebuilt from what is loaded, with the same non-blocking
+    /// reads as [`Self::loaded_models`]; when a refresh holds the catalog the
+    /// older snapshot is served instead, or the built-in picker presets before
+    /// there is any. A current snapshot only has its status brought up to
+    /// date, since refreshes that change nothing still move the fetch time.
+    pub(super) fn snapshot(&self, config: &Config) -> Arc<CatalogSnapshot> {
+        let revision = self.catalog_revision();
+        let current = self.snapshot.load_full();
+        if let Some(current) = &current
+            && current.revision() == revision
+        {
+            return match self.try_snapshot_status(config, current.status().hidden_count) {
+                Ok(status) if status != *current.status() => {
+                    let updated = Arc::new(CatalogSnapshot::clone(current).with_status(status));
+                    self.snapshot.store(Some(Arc::clone(&updated)));
+                    updated
+                }
+                _ => Arc::clone(current),
+            };
+        }
+        match self.loaded_snapshot(config, revision) {
+            Ok(fresh) => {
+                let fresh = Arc::new(fresh);
+                self.snapshot.store(Some(Arc::clone(&fresh)));
+                fresh
+            }
+            Err(_) => current.unwrap_or_else(|| {
+                Arc::new(
+                    CatalogSnapshot::new(
+                        revision,
+                        self.local_models
+                            .iter()
+                            .filter(|preset| preset.show_in_picker)
+                            .cloned()
+                            .collect(),
+                    )
+                    .with_status(CatalogSnapshotStatus {
+                        cache_path: self.cache_path(),
+                        ..Default::default()
+                    }),
+                )
+            }),
+        }
+    }
+
+    fn loaded_snapshot(
+        &self,
+        config: &Config,
+        revision: u64,
+    ) -> Result<CatalogSnapshot, TryLockError> {
+        let remote_models = self.try_get_remote_models(config)?;
+        let provider_models = self.provider_models.try_read()?;
+        let presets = self.assemble_models(config, remote_models.clone(), &provider_models);
+        let hidden_count =
+            self.hidden_count(config, remote_models, &provider_models, presets.len());
+        let status = self.try_snapshot_status(config, hidden_count)?;
+        Ok(CatalogSnapshot::new(revision, presets).with_status(status))
+    }
+
+    /// How many of the presets the catalogs list are left out of the `listed`
+    /// ones the picker shows.
+    fn hidden_count(
+        &self,
+        config: &Config,
+        remote_models: Vec<ModelInfo>,
+        provider_models: &[ModelPreset],
+        listed: usize,
+    ) -> usize {
+        self.merged_presets(
+            &config.model_catalog_sources,
+            self.imported(config),
+            remote_models,
+            self.local_models.clone(),
+            provider_models,
+        )
+        .len()
+        .saturating_sub(listed)
+    }
+
+    fn try_snapshot_status(
+        &self,
+        config: &Config,
+        hidden_count: usize,
+    ) -> Result<CatalogSnapshotStatus, TryLockError> {
+        let remote = self.remote_fetch.try_read()?;
+        let providers = self.provider_fetches.try_read()?;
+        let etag = self.etag.try_read()?.clone();
+        Ok(self.snapshot_status(config, &remote, &providers, etag, hidden_count))
+    }
+
+    fn snapshot_status(
+        &self,
+        config: &Config,
+        remote: &FetchRecord,
+        providers: &[ProviderFetchRecord],
+        etag: Option<String>,
+        hidden_count: usize,
+    ) -> CatalogSnapshotStatus {
+        let last_error = Self::warnings_from_records(remote, providers)
+            .into_iter()
+            .max_by_key(|warning| warning.failed_at)
+            .map(|warning| (warning.failed_at, redact_error(&warning.to_string())));
+        CatalogSnapshotStatus {
+            source: remote.source,
+            host: remote.host.clone(),
+            etag,
+            fetched_at: remote.fetched_at,
+            last_error,
+            cache_path: self.cache_path(),
+            hidden_count,
+            imported: self.imported_catalog(config),
+        }
+    }
+
+    /// Id of the preset `model` was selected from when served by
+    /// `provider_id`: the namespaced id of a catalog provider's preset, or the
+    /// slug itself for built-in and remote presets.
+    pub async fn preset_id_for_model(&self, model: &str, provider_id: &str) -> String {
+        self.provider_models
+            .read()
+            .await
+            .iter()
+            .find(|preset| {
+                preset.model == model && preset.model_provider.as_deref() == Some(provider_id)
+            })
+            .map_or_else(|| model.to_string(), |preset| preset.id.clone())
+    }
+
+    /// The preset a session running `model` on `provider_id` was selected
+    /// from, hidden ones included, and where it came from. Reads what is
+    /// already loaded without refreshing anything.
+    pub async fn active_preset(
+        &self,
+        model: &str,
+        provider_id: &str,
+        config: &Config,
+    ) -> Option<(ModelPreset, PresetSource)> {
+        let id = self.preset_id_for_model(model, provider_id).await;
+        let from_remote = self
+            .remote_models(config)
+            .await
+            .iter()
+            .any(|remote| remote.slug == model);
+        let preset = self.preset_by_id(&id, config).await?;
+        let source = match &preset.model_provider {
+            Some(provider_id) => PresetSource::Provider {
+                provider_id: provider_id.clone(),
+            },
+            None if from_remote => PresetSource::Remote {
+                source: self.remote_fetch.read().await.source,
+            },
+            None => PresetSource::Builtin,
+        };
+        Some((preset, source))
+    }
+
+    /// The preset with `id`, hidden ones included, as the picker would offer
+    /// it. Reads what is already loaded without refreshing anything.
+    pub async fn preset_by_id(&self, id: &str, config: &Config) -> Option<ModelPreset> {
+        let remote_models = self.remote_models(config).await;
+        let mut models = self.merged_presets(
+            &config.model_catalog_sources,
+            self.imported(config),
+            remote_models,
+            all_builtin_model_presets(),
+            &self.provider_models.read().await,
+        );
+        Self::apply_effort_descriptions(&mut models, config);
+        Self::apply_preset_copy(&mut models, config);
+        self.apply_auth_default_efforts(&mut models, config);
+        Self::apply_effort_limits(&mut models, config);
+        Self::apply_disabled_efforts(&mut models, config);
+        models.into_iter().find(|preset| preset.id == id)
+    }
+
+    /// Display name of the preset whose id or slug is `model`, or `model`
+    /// itself when the local catalog does not list it. Reads the cache but
+    /// never fetches.
+    pub async fn display_name(&self, model: &str, config: &Config) -> String {
+        self.try_load_cache(None, resolve_catalog_locale(config).as_deref())
+            .await;
+        let presets = self.known_models(config).await;
+        presets
+            .iter()
+            .find(|preset| preset.id == model)
+            .or_else(|| presets.iter().find(|preset| preset.model == model))
+            .map_or_else(|| model.to_string(), |preset| preset.display_name.clone())
+    }
+
+    /// Slug of the first of `fallbacks` the catalog lists for `provider_id`
+    /// that the signed-in account may use. A fallback matches a preset's
+    /// `model` or `id`, hidden presets included. Reads what is already loaded
+    /// without refreshing anything.
+    pub async fn fallback_model(
+        &self,
+        fallbacks: &[String],
+        provider_id: &str,
+        config: &Config,
+    ) -> Option<String> {
+        if fallbacks.is_empty() {
+            return None;
+        }
+        let remote_models = self.remote_models(config).await;
+        let mut models = self.merged_presets(
+            &config.model_catalog_sources,
+            self.imported(config),
+            remote_models,
+            all_builtin_model_presets(),
+            &self.provider_models.read().await,
+        );
+        self.apply_account_availability(&mut models, config);
+        fallbacks.iter().find_map(|fallback| {
+            models
+                .iter()
+                .find(|preset| {
+                    (preset.model == *fallback || preset.id == *fallback)
+                        && preset
+                            .model_provider
+                            .as_deref()
+                            .is_none_or(|id| id == provider_id)
+                        && preset.unavailable_for_account.is_none()
+                })
+                .map(|preset| preset.model.clone())
+        })
+    }
+
+    /// Built-in and remote presets are served by the session's provider, so
+    /// they pick up its price sheet.
+    /// Effort descriptions set in config replace the presets' own.
+    fn apply_effort_descriptions(models: &mut [ModelPreset], config: &Config) {
+        if config.effort_descriptions.is_empty() {
+            return;
+        }
+        for effort in models
+            .iter_mut()
+            .flat_map(|preset| preset.supported_reasoning_efforts.iter_mut())
+        {
+            if let Some(description) = config.effort_descriptions.get(&effort.effort) {
+                effort.description = description.clone();
+            }
+        }
+    }
+
+    /// `model_preset_overrides` replaces the wording of the presets it names,
+    /// whichever catalog listed them. Runs after `effort_descriptions`, so a
+    /// preset's own effort wording wins over the shared one.
+    fn apply_preset_copy(models: &mut [ModelPreset], config: &Config) {
+        if config.model_preset_overrides.is_empty() {
+            return;
+        }
+        for preset in models.iter_mut() {
+            if let Some(overrides) = config.model_preset_overrides.get(&preset.id) {
+                overrides.apply_to(preset);
+            }
+        }
+    }
+
+    /// The warning for `@model:` references in project docs that name models
+    /// the catalog does not list, when `validate_instruction_models` is set.
+    /// The docs are sent as written either way.
+    pub async fn instruction_model_warning(&self, config: &Config) -> Option<String> {
+        if !config.validate_instruction_models {
+            return None;
+        }
+        let references = match project_doc::read_model_references(config).await {
+            Ok(references) => references,
+            Err(err) => {
+                warn!("failed to read project docs for model references: {err}");
+                return None;
+            }
+        };
+        let mut unknown = Vec::new();
+        for reference in &references {
+            if self.check_model(&reference.model, config).await.is_some() {
+                unknown.push(reference);
+            }
+        }
+        project_doc::unknown_model_references_message(&unknown, &config.cwd)
+    }
+
+    /// Warnings for `model_preset_overrides` entries that name a preset the
+    /// catalog does not list, or an effort the preset does not offer. Those
+    /// entries are ignored, so a config keeps loading as the catalog changes.
+    /// Reads what is already loaded without refreshing anything.
+    pub async fn preset_override_warnings(&self, config: &Config) -> Vec<String> {
+        if config.model_preset_overrides.is_empty() {
+            return Vec::new();
+        }
+        let remote_models = self.remote_models(config).await;
+        let presets = self.merged_presets(
+            &config.model_catalog_sources,
+            self.imported(config),
+            remote_models,
+            all_builtin_model_presets(),
+            &self.provider_models.read().await,
+        );
+        super::validation::preset_override_warnings(&config.model_preset_overrides, &presets)
+    }
+
+    /// Presets default to the effort set for the signed-in auth mode, ahead
+    /// of the effort limits so those still clamp it.
+    fn apply_auth_default_efforts(&self, models: &mut [ModelPreset], config: &Config) {
+        for preset in models.iter_mut() {
+            if let Some(effort) = self.auth_default_effort(&preset.model, Some(&*preset), config) {
+                preset.default_reasoning_effort = effort;
+            }
+        }
+    }
+
+    /// The default effort for `model` under the signed-in auth mode, from
+    /// `[preset_overrides]` and then from `preset`'s own mapping. `None`
+    /// leaves the plain default in place.
+    fn auth_default_effort(
+        &self,
+        model: &str,
+        preset: Option<&ModelPreset>,
+        config: &Config,
+    ) -> Option<ReasoningEffort> {
+        let auth_mode = self.auth_manager.get_auth_mode()?;
+        let configured = config
+            .preset_overrides
+            .get(model)
+            .and_then(|limits| limits.default_effort_by_auth)
+            .unwrap_or_default();
+        let from_preset = preset
+            .and_then(|preset| preset.default_reasoning_effort_by_auth)
+            .unwrap_or_default();
+        effort_for_auth(configured.or(from_preset), auth_mode)
+    }
+
+    /// Efforts outside the range `preset_overrides` sets for a model are
+    /// dropped, so the picker never offers them, and its default is moved
+    /// into the range so `Auto` resolves there too.
+    fn apply_effort_limits(models: &mut [ModelPreset], config: &Config) {
+        if config.preset_overrides.is_empty() {
+            return;
+        }
+        for preset in models.iter_mut() {
+            if let Some(limits) = config.preset_overrides.get(&preset.model) {
+                preset.limit_efforts(limits.min_effort, limits.max_effort);
+            }
+        }
+    }
+
+    /// Efforts in `disabled_reasoning_efforts` are dropped from every preset,
+    /// after the per-model limits, and defaults that pointed at one move to
+    /// the nearest effort left.
+    fn apply_disabled_efforts(models: &mut [ModelPreset], config: &Config) {
+        if config.disabled_reasoning_efforts.is_empty() {
+            return;
+        }
+        for preset in models.iter_mut() {
+            preset.disable_efforts(&config.disabled_reasoning_efforts);
+        }
+    }
+
+    fn apply_provider_pricing(models: &mut [ModelPreset], provider: &ModelProviderInfo) {
+        for preset in models
+            .iter_mut()
+            .filter(|preset| preset.model_provider.is_none() && preset.pricing.is_none())
+        {
+            preset.pricing = provider.pricing_for(&preset.model);
+        }
+    }
+
+    /// Check a `model` set in config against the catalog of the active
+    /// provider, returning a warning with close matches when it is missing.
+    /// See [`Self::check_model`].
+    pub async fn unknown_model_warning(&self, model: &str, config: &Config) -> Option<String> {
+        self.check_model(model, config)
+            .await
+            .map(|invalid| invalid.to_string())
+    }
+
+    /// A warning that the configured `model_reasoning_effort` will be ignored
+    /// because the preset `model` is selected from lists no efforts. Models
+    /// without a loaded preset are not checked.
+    pub async fn ignored_effort_warning(&self, model: &str, config: &Config) -> Option<String> {
+        let effort = config.model_reasoning_effort?;
+        let (preset, _) = self
+            .active_preset(model, &config.model_provider_id, config)
+            .await?;
+        super::validation::ignored_effort_warning(&preset, effort)
+    }
+
+    /// The error for a turn that needs `capability` from `model`, which lacks
+    /// it, naming the listed presets already loaded that have it.
+    pub fn missing_capability(
+        &self,
+        model: &str,
+        capability: Capability,
+        config: &Config,
+    ) -> InvalidSelection {
+        let supporting = PresetCatalog::new(self, config).filter_by_capability(capability);
+        super::validation::missing_capability(&supporting, model, capability)
+    }
+
+    /// Check `model` against the catalog of the active provider. Catalog
+    /// providers are checked against their own listing, and only once it has
+    /// loaded; OpenAI against the built-in and remote presets. Other providers
+    /// have no catalog to check against.
+    pub async fn check_model(&self, model: &str, config: &Config) -> Option<InvalidSelection> {
+        if Self::retired_model_successor(model, config).is_some() {
+            return None;
+        }
+        let provider = &config.model_provider;
+        let known: Vec<String> = if provider.model_catalog.is_some() {
+            let listed = self.provider_fetches.read().await.iter().any(|record| {
+                record.provider_id == config.model_provider_id && record.last_error.is_none()
+            });
+            if !listed {
+                return None;
+            }
+            self.provider_models
+                .read()
+                .await
+                .iter()
+                .filter(|preset| {
+                    preset.model_provider.as_deref() == Some(config.model_provider_id.as_str())
+                })
+                .flat_map(|preset| [preset.id.clone(), preset.model.clone()])
+                .collect()
+        } else if provider.requires_openai_auth {
+            let known: Vec<String> = builtin_model_slugs()
+                .map(str::to_string)
+                .chain(
+                    self.remote_models(config)
+                        .await
+                        .into_iter()
+                        .map(|model| model.slug),
+                )
+                .collect();
+            // Built-in families the catalog does not list (e.g. `o3`) are
+            // still valid; within a listed family only listed slugs are.
+            if let Some(family) = known_family_for_model(model)
+                && !known
+                    .iter()
+                    .filter_map(|slug| known_family_for_model(slug))
+                    .any(|listed| listed.family == family.family)
+            {
+                return None;
+            }
+            known
+        } else {
+            return None;
+        };
+        super::validation::check_model(model, &provider.name, known)
+    }
+
+    fn find_family_for_model(slug: &str) -> ModelFamily {
+        super::model_family::find_family_for_model(slug)
+    }
+
+    /// Look up the requested model family while applying preset and remote
+    /// metadata overrides.
+    pub async fn construct_model_family(&self, model: &str, config: &Config) -> ModelFamily {
+        let catalog_preset = self
+            .catalog_preset_for_model(model, &config.model_provider_id)
+            .await;
+        // A deployment or gateway alias gets the built-in metadata of the
+        // model behind it, while requests keep naming the alias.
+        let family = match catalog_preset
+            .as_ref()
+            .and_then(|preset| preset.upstream_model.as_deref())
+        {
+            Some(upstream) => ModelFamily {
+                slug: model.to_string(),
+                ..Self::find_family_for_model(upstream)
+            },
+            None => Self::find_family_for_model(model),
+        };
+        let mut family = family
+            .with_preset_overrides(self.local_preset_for_model(model))
+            .with_remote_overrides(self.remote_models(config).await)
+            .with_catalog_preset(catalog_preset.as_ref());
+        // Hidden built-ins are not in `local_models` but may still set an
+        // effort per auth mode.
+        let preset = catalog_preset.as_ref().or_else(|| {
+            builtin_presets()
+                .iter()
+                .find(|preset| preset.model == model)
+        });
+        if let Some(effort) = self.auth_default_effort(model, preset, config) {
+            family.default_reasoning_effort = Some(effort);
+        }
+        let id = self
+            .preset_id_for_model(model, &config.model_provider_id)
+            .await;
+        family
+            .with_config_overrides(config)
+            .with_preset_copy(config.model_preset_overrides.get(&id))
+    }
+
+    /// The preset `provider_id` serves `model` with from its own catalog.
+    async fn catalog_preset_for_model(
+        &self,
+        model: &str,
+        provider_id: &str,
+    ) -> Option<ModelPreset> {
+        self.provider_models
+            .read()
+            .await
+            .iter()
+            .find(|preset| {
+                preset.model == model && preset.model_provider.as_deref() == Some(provider_id)
+            })
+            .cloned()
+    }
+
+    fn local_preset_for_model(&self, model: &str) -> Option<&ModelPreset> {
+        self.local_models
+            .iter()
+            .find(|preset| preset.model == model)
+    }
+
+    /// The preset replacing a retired built-in `model`. Only OpenAI models
+    /// are built in, so other providers keep the configured slug.
+    pub fn retired_model_successor(model: &str, config: &Config) -> Option<&'static ModelPreset> {
+        if !config.model_provider.requires_openai_auth {
+            return None;
+        }
+        retired_model_successor(model)
+    }
+
+    pub async fn get_model(&self, model: &Option<String>, config: &Config) -> String {
+        if let Some(model) = model.as_ref() {
+            if let Some(successor) = Self::retired_model_successor(model, config) {
+                info!("model `{model}` is retired; using `{}`", successor.model);
+                return successor.model.clone();
+            }
+            return model.to_string();
+        }
+        if let Err(err) = self.refresh_available_models_with_cache(config).await {
+            error!("failed to refresh available models: {err}");
+        }
+        // if codex-auto-balanced exists & signed in with chatgpt mode, return it, otherwise return the default model
+        let auth_mode = self.auth_manager.get_auth_mode();
+        let remote_models = self.remote_models(config).await;
+        if auth_mode == Some(AuthMode::ChatGPT)
+            && self
+                .build_available_models(
+                    &config.model_catalog_sources,
+                    self.imported(config),
+                    remote_models,
+                    &[],
+                )
+                .iter()
+                .any(|m| m.model == CODEX_AUTO_BALANCED_MODEL)
+        {
+            return CODEX_AUTO_BALANCED_MODEL.to_string();
+        } else if auth_mode == Some(AuthMode::ChatGPT) {
+            return OPENAI_DEFAULT_CHATGPT_MODEL.to_string();
+        }
+        OPENAI_DEFAULT_API_MODEL.to_string()
+    }
+    /// The model slug and family a session started with `config` uses.
+    /// Session startup and `codex models which` both resolve through here.
+    pub async fn resolve_session_model(&self, config: &Config) -> (String, ModelFamily) {
+        let model = self.get_model(&config.model, config).await;
+        let model_family = self.construct_model_family(&model, config).await;
+        (model, model_family)
+    }
+
+    pub async fn refresh_if_new_etag(
+        &self,
+        etag: String,
+        remote_models_feature: bool,
+        catalog_locale: Option<&str>,
+        user_agent_suffix: Option<&str>,
+    ) {
+        let current_etag = self.get_etag().await;
+        if current_etag.clone().is_some() && current_etag.as_deref() == Some(etag.as_str()) {
+            return;
+        }
+        if let Err(err) = self
+            .refresh_available_models_no_cache(
+                remote_models_feature,
+                catalog_locale,
+                user_agent_suffix,
+                RefreshReason::EtagChanged,
+            )
+            .await
+        {
+            error!("failed to refresh available models: {err}");
+        }
+    }
+
+    #[cfg(any(test, feature = "test-support"))]
+    pub fn get_model_offline(model: Option<&str>) -> String {
+        model.unwrap_or(OPENAI_DEFAULT_CHATGPT_MODEL).to_string()
+    }
+
+    #[cfg(any(test, feature = "test-support"))]
+    /// Offline helper that builds a `ModelFamily` without consulting remote state.
+    pub fn construct_model_family_offline(model: &str, config: &Config) -> ModelFamily {
+        Self::find_family_for_model(model).with_config_overrides(config)
+    }
+
+    async fn get_etag(&self) -> Option<String> {
+        self.etag.read().await.clone()
+    }
+
+    /// Replace the cached remote models and rebuild the derived presets list.
+    /// Entries are sanitized first, since they come from the network or from
+    /// a cache file on disk.
+    async fn apply_remote_models(&self, models: Vec<ModelInfo>, persisted_default: Option<String>) {
+        self.remote_catalog(None)
+            .apply_models(models, persisted_default)
+            .await;
+    }
+
+    /// Record that the provider refused `model` to the credentials `config`
+    /// requests it with, so listings grey it out until the mark expires or
+    /// the model is used successfully.
+    pub async fn mark_unavailable_for_account(&self, model: &str, reason: &str, config: &Config) {
+        let account = self.catalog_auth(config).to_string();
+        let Ok(mut availability) = self.availability.lock() else {
+            return;
+        };
+        availability.mark(&account, model, reason, Utc::now());
+        let (path, json) = (availability.path().to_path_buf(), availability.to_json());
+        drop(availability);
+        self.bump_revision();
+        Self::write_availability(&path, json).await;
+    }
+
+    /// Clear the mark [`Self::mark_unavailable_for_account`] left on `model`,
+    /// after a request with it succeeded. Cheap when there is no mark.
+    pub async fn mark_available_for_account(&self, model: &str, config: &Config) {
+        let account = self.catalog_auth(config).to_string();
+        let Ok(mut availability) = self.availability.lock() else {
+            return;
+        };
+        if !availability.clear(&account, model) {
+            return;
+        }
+        let (path, json) = (availability.path().to_path_buf(), availability.to_json());
+        drop(availability);
+        self.bump_revision();
+        Self::write_availability(&path, json).await;
+    }
+
+    async fn write_availability(path: &Path, json: std::io::Result<Vec<u8>>) {
+        let result = match json {
+            Ok(json) => state_dir::write_state(path, &json).await,
+            Err(err) => Err(err),
+        };
+        if let Err(err) = result {
+            error!("failed to write {}: {err}", path.display());
+        }
+    }
+
+    /// Names the catalog this manager lists with `auth`, for state kept per
+    /// catalog under `$CODEX_HOME`: its base URL and who it is listed as.
+    fn catalog_key(&self, auth: &CatalogAuth) -> String {
+        let base_url = self
+            .provider
+            .to_api_provider(Some(AuthMode::ChatGPT))
+            .map(|provider| provider.base_url)
+            .unwrap_or_default();
+        format!("{base_url} as {auth}")
+    }
+
+    /// Prune the per-model state of models no catalog has listed for
+    /// `model_state_retention_days`; see [`state_gc`]. Run by
+    /// [`Self::model_list`], against what is already loaded, the first time
+    /// a catalog is listed at `generation` of [`Self::catalog_revision`]. The
+    /// pass covers this manager's catalog as listed with `auth`.
+    async fn collect_stale_model_state(
+        &self,
+        config: &Config,
+        auth: &CatalogAuth,
+        generation: u64,
+    ) {
+        // An imported catalog is fixed, and says nothing about which models
+        // the real catalogs still list.
+        if self.imported(config).is_some() {
+            return;
+        }
+        let catalog = self.catalog_key(auth);
+        {
+            let mut last_pass = self
+                .state_gc_pass
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner);
+            let pass = (catalog.clone(), generation);
+            if last_pass.as_ref() == Some(&pass) {
+                return;
+            }
+            *last_pass = Some(pass);
+        }
+        let remote_models = self.remote_models(config).await;
+        let listed = self.merged_presets(
+            &config.model_catalog_sources,
+            self.imported(config),
+            remote_models,
+            all_builtin_model_presets(),
+            &self.provider_models.read().await,
+        );
+        let account = auth.to_string();
+        let scope = ModelStateScope {
+            catalog: &catalog,
+            account: &account,
+        };
+        if let Err(err) = state_gc::collect_stale_model_state(
+            &self.codex_home,
+            scope,
+            &listed,
+            &self.availability,
+            config.model_state_retention,
+            Utc::now(),
+        )
+        .await
+        {
+            error!("failed to prune the state of unlisted models: {err}");
+        }
+    }
+
+    /// Set `unavailable_for_account` on the presets the active provider has
+    /// refused to the current credentials, and clear it everywhere else.
+    fn apply_account_availability(&self, models: &mut [ModelPreset], config: &Config) {
+        // An imported catalog keeps the marks of the account it was exported
+        // from.
+        if self.imported(config).is_some() {
+            return;
+        }
+        let account = self.catalog_auth(config).to_string();
+        let Ok(availability) = self.availability.lock() else {
+            return;
+        };
+        let now = Utc::now();
+        for preset in models {
+            let served_here = preset
+                .model_provider
+                .as_deref()
+                .is_none_or(|provider_id| provider_id == config.model_provider_id);
+            preset.unavailable_for_account = served_here
+                .then(|| availability.reason(&account, &preset.model, now))
+                .flatten()
+                .map(str::to_string);
+        }
+    }
+
+    fn bump_revision(&self) {
+        self.revision.send_modify(|revision| *revision += 1);
+    }
+
+    /// List the presets `catalog` serves, with the oldest client version it
+    /// recommends. When the catalog turns away a token printed by the
+    /// provider's `token_command`, the command is run again and the listing
+    /// retried once. How old the token each request carried was is pushed to
+    /// `token_attempts`.
+    async fn fetch_provider_presets(
+        catalog: &ProviderCatalogConfig,
+        catalog_locale: Option<&str>,
+        token_attempts: &mut Vec<TokenFreshness>,
+    ) -> CoreResult<CatalogListing<ModelPreset>> {
+        let result = Self::list_provider_presets(catalog, catalog_locale, token_attempts).await;
+        let provider = &catalog.provider;
+        if let Err(err) = &result
+            && err.http_status_code_value() == Some(StatusCode::UNAUTHORIZED.as_u16())
+            && provider.env_key.is_none()
+            && let Some(command) = &provider.token_command
+        {
+            invalidate_token(command);
+            return Self::list_provider_presets(catalog, catalog_locale, token_attempts).await;
+        }
+        result
+    }
+
+    async fn list_provider_presets(
+        catalog: &ProviderCatalogConfig,
+        catalog_locale: Option<&str>,
+        token_attempts: &mut Vec<TokenFreshness>,
+    ) -> CoreResult<CatalogListing<ModelPreset>> {
+        let provider_id = catalog.provider_id.as_str();
+        let provider = &catalog.provider;
+        let (presets, min_client_version): (Vec<ModelPreset>, _) = match catalog.catalog {
+            ModelCatalog::Openai => {
+                let client = Self::catalog_client(catalog, token_attempts).await?;
+                let listing = client
+                    .list_openai_compatible_models(catalog_headers(catalog_locale))
+                    .await
+                    .map_err(|err| map_catalog_error(err, &client, provider))?;
+                ensure_listed(&listing.entries, provider, || {
+                    client
+                        .openai_compatible_models_request(HeaderMap::new())
+                        .url
+                })?;
+                let presets = listing
+                    .entries
+                    .into_iter()
+                    .filter_map(|slug| {
+                        listed_preset(
+                            provider_id,
+                            Self::provider_preset(provider_id, provider, slug),
+                        )
+                    })
+                    .collect();
+                (presets, listing.min_client_version)
+            }
+            ModelCatalog::Azure => {
+                let client = Self::catalog_client(catalog, token_attempts).await?;
+                let listing = client
+                    .list_azure_deployments(catalog_headers(catalog_locale))
+                    .await
+                    .map_err(|err| map_catalog_error(err, &client, provider))?;
+                ensure_listed(&listing.entries, provider, || {
+                    client.azure_deployments_request(HeaderMap::new()).url
+                })?;
+                let presets = listing
+                    .entries
+                    .into_iter()
+                    .filter_map(|deployment| {
+                        listed_preset(
+                            provider_id,
+                            Self::azure_deployment_preset(provider_id, provider, deployment),
+                        )
+                    })
+                    .collect();
+                (presets, listing.min_client_version)
+            }
+            ModelCatalog::None => {
+                let presets = provider
+                    .models
+                    .iter()
+                    .flatten()
+                    .filter_map(|slug| {
+                        listed_preset(
+                            provider_id,
+                            Self::provider_preset(provider_id, provider, slug.clone()),
+                        )
+                    })
+                    .collect();
+                (presets, None)
+            }
+        };
+        Ok(CatalogListing {
+            entries: presets
+                .into_iter()
+                .filter_map(|preset| {
+                    sanitize_model_preset(preset)
+                        .inspect_err(|err| warn!("{err}"))
+                        .ok()
+                })
+                .collect(),
+            min_client_version,
+        })
+    }
+
+    async fn catalog_client(
+        catalog: &ProviderCatalogConfig,
+        token_attempts: &mut Vec<TokenFreshness>,
+    ) -> CoreResult<ModelsClient<ReqwestTransport, CoreAuthProvider>> {
+        let provider = &catalog.provider;
+        catalog.require_bearer_token()?;
+        let user_agent_suffix = catalog.user_agent_suffix.as_deref();
+        let http_client = build_reqwest_client_for_catalog(provider, user_agent_suffix)?;
+        if !provider.skip_models_preflight {
+            let preflight_client = build_reqwest_client_for_preflight(provider, user_agent_suffix)?;
+            preflight(&preflight_client, catalog.base_url()?).await?;
+        }
+        let client = catalog_models_client(catalog, http_client).await?;
+        if let Some(freshness) = client.auth().token_freshness() {
+            debug!(
+                provider_id = catalog.provider_id.as_str(),
+                token_source = %freshness.source,
+                token_age_ms = freshness.age_ms,
+                token_expires_in_ms = freshness.expires_in_ms,
+                "listing models with a token_command token"
+            );
+            token_attempts.push(freshness);
+        }
+        Ok(client)
+    }
+
+    /// Build a picker entry for a model served by a configured provider. The id
+    /// is namespaced by provider so identical slugs from different servers stay
+    /// distinct.
+    fn provider_preset(
+        provider_id: &str,
+        provider: &ModelProviderInfo,
+        slug: String,
+    ) -> Result<ModelPreset, ModelPresetError> {
+        let pricing = provider.pricing_for(&slug);
+        // The id is namespaced, so the display name is the bare slug.
+        let display_name = slug.clone();
+        let (supports_temperature, supports_top_p) = sampling_support(&slug);
+        ModelPreset::builder(format!("{provider_id}/{slug}"), slug)
+            .display_name(display_name)
+            .description(format!("Served by {}", provider.name))
+            .model_provider(provider_id.to_string())
+            .instructions_addendum(provider.instructions_addendum.clone())
+            .pricing(pricing)
+            .supports_temperature(supports_temperature)
+            .supports_top_p(supports_top_p)
+            .build()
+    }
+
+    /// Azure routes requests by deployment name, so the deployment is the
+    /// preset's model, with the id namespaced by provider like other provider
+    /// presets. The underlying model is kept as the upstream model.
+    fn azure_deployment_preset(
+        provider_id: &str,
+        provider: &ModelProviderInfo,
+        deployment: AzureDeployment,
+    ) -> Result<ModelPreset, ModelPresetError> {
+        let AzureDeployment {
+            id,
+            model,
+            capacity,
+            scale_type,
+        } = deployment;
+        let capacity = match (scale_type, capacity) {
+            (Some(scale_type), Some(capacity)) => format!(", {scale_type}, capacity {capacity}"),
+            (Some(scale_type), None) => format!(", {scale_type}"),
+            (None, Some(capacity)) => format!(", capacity {capacity}"),
+            (None, None) => String::new(),
+        };
+        let (supports_temperature, supports_top_p) = sampling_support(&model);
+        let pricing = provider
+            .pricing_for(&id)
+            .or_else(|| provider.pricing_for(&model));
+        ModelPreset::builder(format!("{provider_id}/{id}"), id.clone())
+            .display_name(id)
+            .description(format!("Azure deployment of {model}{capacity}"))
+            .model_provider(provider_id.to_string())
+            .upstream_model(model)
+            .instructions_addendum(provider.instructions_addendum.clone())
+            .pricing(pricing)
+            .supports_temperature(supports_temperature)
+            .supports_top_p(supports_top_p)
+            .build()
+    }
+
+    fn load_remote_models_from_file() -> Result<Vec<ModelInfo>, std::io::Error> {
+        let file_contents = include_str!("../../models.json");
+        let response: ModelsResponse = serde_json::from_str(file_contents)?;
+        Ok(response.models)
+    }
+
+    /// Attempt to satisfy the refresh from the cache when it matches the provider and TTL.
+    /// A `ttl` of `None` accepts the cache however old it is.
+    async fn try_load_cache(
+        &self,
+        ttl: Option<Duration>,
+        catalog_locale: Option<&str>,
+    ) -> CacheLookup {
+        let lookup = self.load_cache_if_fresh(ttl, catalog_locale).await;
+        self.remote_fetch.write().await.cache = Some(lookup);
+        lookup
+    }
+
+    async fn load_cache_if_fresh(
+        &self,
+        ttl: Option<Duration>,
+        catalog_locale: Option<&str>,
+    ) -> CacheLookup {
+        // todo(aibrahim): think if we should store fetched_at in ModelsManager so we don't always need to read the disk
+        let cache_path = self.cache_path();
+        let cache = match cache::load_cache(&cache_path).await {
+            Ok(cache) => cache,
+            Err(err) => {
+                error!("failed to load models cache: {err}");
+                return CacheLookup::Miss;
+            }
+        };
+        // Descriptions are localized, so a cache fetched for another locale is
+        // as good as no cache. Caches written before locales were recorded
+        // carry none and stay usable until they expire.
+        let cache = match cache {
+            Some(cache) if cache.locale.is_none() || cache.locale.as_deref() == catalog_locale => {
+                cache
+            }
+            _ => return CacheLookup::Miss,
+        };
+        if let Some(ttl) = ttl
+            && !cache.is_fresh(ttl)
+        {
+            return CacheLookup::Stale;
+        }
+        let models = cache.models.clone();
+        *self.etag.write().await = cache.etag.clone();
+        let persisted_default = self
+            .provider
+            .to_api_provider(Some(AuthMode::ChatGPT))
+            .ok()
+            .and_then(|provider| cache.default_for(&provider.base_url));
+        self.apply_remote_models(models.clone(), persisted_default)
+            .await;
+        let mut record = self.remote_fetch.write().await;
+        record.source = CatalogSource::DiskCache;
+        record.fetched_at = Some(cache.fetched_at);
+        record.min_client_version = cache.min_client_version.clone();
+        // Another process may have refreshed the cache since our fetch failed.
+        if record
+            .last_error_at
+            .is_some_and(|failed_at| failed_at <= cache.fetched_at)
+        {
+            record.last_error = None;
+            record.last_error_at = None;
+        }
+        CacheLookup::Hit
+    }
+
+    /// Merge remote model metadata into picker-ready presets, preserving existing entries.
+    /// The picker presets from `remote_models`, the built-ins and
+    /// `provider_models`, merged in the configured catalog order, with the
+    /// configured prices and effort descriptions applied.
+    fn assemble_models(
+        &self,
+        config: &Config,
+        remote_models: Vec<ModelInfo>,
+        provider_models: &[ModelPreset],
+    ) -> Vec<ModelPreset> {
+        let mut models = self.build_available_models(
+            &config.model_catalog_sources,
+            self.imported(config),
+            remote_models,
+            provider_models,
+        );
+        Self::apply_provider_pricing(&mut models, &config.model_provider);
+        Self::apply_effort_descriptions(&mut models, config);
+        Self::apply_preset_copy(&mut models, config);
+        self.apply_auth_default_efforts(&mut models, config);
+        Self::apply_effort_limits(&mut models, config);
+        Self::apply_disabled_efforts(&mut models, config);
+        self.apply_account_availability(&mut models, config);
+        models
+    }
+
+    /// Like [`Self::assemble_models`], keeping presets hidden from the picker
+    /// and those the current credentials cannot use.
+    fn assemble_all_models(
+        &self,
+        config: &Config,
+        remote_models: Vec<ModelInfo>,
+        provider_models: &[ModelPreset],
+    ) -> Vec<ModelPreset> {
+        let mut models = self.merged_presets(
+            &config.model_catalog_sources,
+            self.imported(config),
+            remote_models,
+            all_builtin_model_presets(),
+            provider_models,
+        );
+        self.enforce_single_default(&mut models);
+        Self::apply_provider_pricing(&mut models, &config.model_provider);
+        Self::apply_effort_descriptions(&mut models, config);
+        Self::apply_preset_copy(&mut models, config);
+        self.apply_auth_default_efforts(&mut models, config);
+        Self::apply_effort_limits(&mut models, config);
+        Self::apply_disabled_efforts(&mut models, config);
+        self.apply_account_availability(&mut models, config);
+        models
+    }
+
+    fn build_available_models(
+        &self,
+        sources: &[ModelCatalogSource],
+        imported: Option<&ImportedCatalog>,
+        remote_models: Vec<ModelInfo>,
+        provider_models: &[ModelPreset],
+    ) -> Vec<ModelPreset> {
+        let merged_presets = self.merged_presets(
+            sources,
+            imported,
+            remote_models,
+            self.local_models.clone(),
+            provider_models,
+        );
+        let mut merged_presets = self.filter_visible_models(merged_presets);
+        self.enforce_single_default(&mut merged_presets);
+        merged_presets
+    }
+
+    /// Leave exactly one default among the listed presets: the first listed
+    /// default in merge order, which is source precedence and then catalog
+    /// order, or else the first listed preset. Hidden presets and provider
+    /// presets, which only work with their own provider, never keep one.
+    fn enforce_single_default(&self, models: &mut [ModelPreset]) {
+        let mut chosen: Option<String> = None;
+        for preset in models.iter_mut().filter(|preset| preset.is_default) {
+            if !self.can_be_default(preset) {
+                warn!(
+                    "preset `{}` cannot be the default because it is hidden or provider-specific",
+                    preset.id
+                );
+                preset.is_default = false;
+            } else if let Some(chosen) = &chosen {
+                warn!(
+                    "presets `{chosen}` and `{}` are both marked default; keeping `{chosen}`",
+                    preset.id
+                );
+                preset.is_default = false;
+            } else {
+                chosen = Some(preset.id.clone());
+            }
+        }
+        if chosen.is_none()
+            && let Some(preset) = models.iter_mut().find(|preset| self.can_be_default(preset))
+        {
+            info!("no listed preset is marked default; using `{}`", preset.id);
+            preset.is_default = true;
+        }
+    }
+
+    fn can_be_default(&self, preset: &ModelPreset) -> bool {
+        preset.model_provider.is_none() && self.is_listed(preset)
+    }
+
+    fn is_listed(&self, preset: &ModelPreset) -> bool {
+        let chatgpt_mode = self.auth_manager.get_auth_mode() == Some(AuthMode::ChatGPT);
+        preset.show_in_picker && (chatgpt_mode || preset.supported_in_api)
+    }
+
+    /// The presets of the remote catalog by priority, with the default it
+    /// settled on marked. Until a catalog was applied, the entries keep the
+    /// default they carry.
+    fn remote_presets(&self, mut remote_models: Vec<ModelInfo>) -> Vec<ModelPreset> {
+        remote_models.sort_by(|a, b| a.priority.cmp(&b.priority));
+        let default = self
+            .remote_default
+            .lock()
+            .ok()
+            .and_then(|default| default.clone());
+        remote_models
+            .into_iter()
+            .map(|model| {
+                let mut preset = ModelPreset::from(model);
+                if let Some(default) = &default {
+                    preset.is_default = &preset.model == default;
+                }
+                preset
+            })
+            .collect()
+    }
+
+    fn filter_visible_models(&self, models: Vec<ModelPreset>) -> Vec<ModelPreset> {
+        models
+            .into_iter()
+            .filter(|model| self.is_listed(model))
+            .collect()
+    }
+
+    /// The catalogs merged by [`Self::merge_presets`], or the imported
+    /// catalog in their place.
+    fn merged_presets(
+        &self,
+        sources: &[ModelCatalogSource],
+        imported: Option<&ImportedCatalog>,
+        remote_models: Vec<ModelInfo>,
+        builtin_presets: Vec<ModelPreset>,
+        provider_models: &[ModelPreset],
+    ) -> Vec<ModelPreset> {
+        if let Some(imported) = imported {
+            return imported.export.presets.clone();
+        }
+        Self::merge_presets(
+            sources,
+            self.remote_presets(remote_models),
+            builtin_presets,
+            provider_models.to_vec(),
+        )
+    }
+
+    /// Concatenate the catalogs in `sources` order, leaving out catalogs not
+    /// listed. A preset is dropped when an earlier catalog already lists its
+    /// model for the same provider, and only the first catalog with any
+    /// presets keeps its default.
+    fn merge_presets(
+        sources: &[ModelCatalogSource],
+        remote_presets: Vec<ModelPreset>,
+        builtin_presets: Vec<ModelPreset>,
+        provider_presets: Vec<ModelPreset>,
+    ) -> Vec<ModelPreset> {
+        let mut catalogs = [
+            (ModelCatalogSource::Remote, remote_presets),
+            (ModelCatalogSource::Builtin, builtin_presets),
+            (ModelCatalogSource::Providers, provider_presets),
+        ];
+        let mut merged_presets: Vec<ModelPreset> = Vec::new();
+        for source in sources {
+            let Some((_, presets)) = catalogs.iter_mut().find(|(kind, _)| kind == source) else {
+                continue;
+            };
+            let listed: HashSet<(Option<String>, String)> = merged_presets
+                .iter()
+                .map(|preset| (preset.model_provider.clone(), preset.model.clone()))
+                .collect();
+            let demote_defaults = !merged_presets.is_empty();
+            for mut preset in std::mem::take(presets) {
+                if listed.contains(&(preset.model_provider.clone(), preset.model.clone())) {
+                    continue;
+                }
+                if demote_defaults {
+                    preset.is_default = false;
+                }
+                merged_presets.push(preset);
+            }
+        }
+        merged_presets
+    }
+
+    async fn remote_models(&self, config: &Config) -> Vec<ModelInfo> {
+        if config.features.enabled(Feature::RemoteModels) {
+            self.remote_models.read().await.clone()
+        } else {
+            Vec::new()
+        }
+    }
+
+    fn try_get_remote_models(&self, config: &Config) -> Result<Vec<ModelInfo>, TryLockError> {
+        if config.features.enabled(Feature::RemoteModels) {
+            Ok(self.remote_models.try_read()?.clone())
+        } else {
+            Ok(Vec::new())
+        }
+    }
+
+    fn cache_path(&self) -> PathBuf {
+        self.codex_home.join(MODEL_CACHE_FILE)
+    }
+}
+
+/// What a refresh of the provider catalogs reads and writes, shared with
+/// the [`ModelsManager`] like [`RemoteCatalog`].
+struct ProviderCatalogs {
+    models: Arc<RwLock<Vec<ModelPreset>>>,
+    fetches: Arc<RwLock<Vec<ProviderFetchRecord>>>,
+    revision: Arc<watch::Sender<u64>>,
+    events: RefreshEvents,
+}
+
+impl ProviderCatalogs {
+    /// List the catalogs `relist` picks, given each one's previous fetch
+    /// record, and keep what was listed before for the others. See
+    /// [`ModelsManager::refresh_provider_models`].
+    ///
+    /// With a `reason`, a refresh that requests any catalog is reported to
+    /// the manager's refresh events; catalogs fixed by config request nothing.
+    async fn refresh(
+        &self,
+        config: &Config,
+        reason: Option<RefreshReason>,
+        relist: impl Fn(&ProviderCatalogConfig, Option<&ProviderFetchRecord>) -> bool,
+    ) {
+        let providers = config.catalog_providers();
+        let catalog_locale = resolve_catalog_locale(config);
+        let previous_models = self.models.read().await.clone();
+        let previous_records = self.fetches.read().await.clone();
+        let previous_record = |provider_id: &str| {
+            previous_records
+                .iter()
+                .find(|record| record.provider_id == provider_id)
+        };
+        let reason = reason.filter(|_| {
+            providers.iter().any(|catalog| {
+                catalog.catalog != ModelCatalog::None
+                    && relist(catalog, previous_record(&catalog.provider_id))
+            })
+        });
+        if let Some(reason) = reason {
+            self.events.started(reason);
+        }
+        let mut first_error = None;
+
+        let mut listed = HashMap::new();
+        let mut completed = Vec::new();
+        for catalog in &providers {
+            let provider_id = &catalog.provider_id;
+            let previous = previous_record(provider_id);
+            if !relist(catalog, previous) {
+                continue;
+            }
+            let mut record = ProviderFetchRecord {
+                provider_id: provider_id.clone(),
+                host: catalog
+                    .provider
+                    .to_api_provider(None)
+                    .ok()
+                    .and_then(|api_provider| redact_host(&api_provider.base_url)),
+                fetched_at: previous.and_then(|record| record.fetched_at),
+                model_count: 0,
+                last_error: None,
+                last_error_at: None,
+                last_error_unauthorized: false,
+                token_attempts: Vec::new(),
+                min_client_version: previous.and_then(|record| record.min_client_version.clone()),
+            };
+            let started = Instant::now();
+            let presets = match ModelsManager::fetch_provider_presets(
+                catalog,
+                catalog_locale.as_deref(),
+                &mut record.token_attempts,
+            )
+            .await
+            {
+                Ok(listing) => {
+                    record.fetched_at = Some(Utc::now());
+                    record.min_client_version = listing.min_client_version;
+                    completed.push((listing.entries.len(), started.elapsed()));
+                    listing.entries
+                }
+                Err(err) => {
+                    error!("failed to list models for provider {provider_id}: {err}");
+                    let message = redact_error(&err.to_string());
+                    first_error.get_or_insert_with(|| format!("provider {provider_id}: {message}"));
+                    record.last_error = Some(message);
+                    record.last_error_at = Some(Utc::now());
+                    record.last_error_unauthorized = is_auth_failure(&err);
+                    previous_models
+                        .iter()
+                        .filter(|preset| provided_by(preset, provider_id))
+                        .cloned()
+                        .collect()
+                }
+            };
+            record.model_count = presets.len();
+            listed.insert(provider_id.clone(), (presets, record));
+        }
+
+        // What was not listed again is taken as it stands now rather than
+        // from the reads above, so a refresh that finished in between is
+        // not rolled back.
+        let mut provider_models = self.models.write().await;
+        let presets: Vec<ModelPreset> = providers
+            .iter()
+            .flat_map(|catalog| match listed.get(&catalog.provider_id) {
+                Some((presets, _)) => presets.clone(),
+                None => provider_models
+                    .iter()
+                    .filter(|preset| provided_by(preset, &catalog.provider_id))
+                    .cloned()
+                    .collect(),
+            })
+            .collect();
+        let (before, after) = (preset_ids(&provider_models), preset_ids(&presets));
+        if *provider_models != presets {
+            *provider_models = presets;
+            self.revision.send_modify(|revision| *revision += 1);
+        }
+        drop(provider_models);
+        if reason.is_some() {
+            match first_error {
+                Some(error) => self.events.failed(error),
+                None => self
+                    .events
+                    .succeeded(*self.revision.borrow(), &before, &after),
+            }
+        }
+        let mut fetches = self.fetches.write().await;
+        let records = providers
+            .iter()
+            .filter_map(|catalog| match listed.remove(&catalog.provider_id) {
+                Some((_, record)) => Some(record),
+                None => fetches
+                    .iter()
+                    .find(|record| record.provider_id == catalog.provider_id)
+                    .cloned(),
+            })
+            .collect();
+        *fetches = records;
+        drop(fetches);
+        let telemetry = config.otel.catalog_telemetry();
+        for (model_count, duration) in completed {
+            telemetry.catalog_fetch_completed(
+                "provider",
+                model_count,
+                *self.revision.borrow(),
+                duration,
+            );
+        }
+    }
+}
+
+fn provided_by(preset: &ModelPreset, provider_id: &str) -> bool {
+    preset.model_provider.as_deref() == Some(provider_id)
+}
+
+/// What a refresh of the remote `/models` catalog reads and writes, shared
+/// with the [`ModelsManager`] so a background refresh can outlive the call
+/// that started it.
+struct RemoteCatalog {
+    auth_manager: Arc<AuthManager>,
+    provider: Arc<ModelProviderInfo>,
+    cache_path: PathBuf,
+    models: Arc<RwLock<Vec<ModelInfo>>>,
+    etag: Arc<RwLock<Option<String>>>,
+    fetch: Arc<RwLock<FetchRecord>>,
+    revision: Arc<watch::Sender<u64>>,
+    events: RefreshEvents,
+    default_model: Arc<Mutex<Option<String>>>,
+    user_agent_suffix: Option<String>,
+}
+
+impl RemoteCatalog {
+    /// Like [`Self::refresh`], reporting it to the manager's refresh events.
+    async fn refresh_reporting(
+        &self,
+        reason: RefreshReason,
+        catalog_locale: Option<&str>,
+    ) -> CoreResult<()> {
+        self.events.started(reason);
+        let before = remote_model_ids(&self.models.read().await);
+        let result = self.refresh(catalog_locale).await;
+        match &result {
+            Ok(()) => {
+                let after = remote_model_ids(&self.models.read().await);
+                self.events
+                    .succeeded(*self.revision.borrow(), &before, &after);
+            }
+            Err(err) => self.events.failed(err.to_string()),
+        }
+        result
+    }
+
+    /// Fetch the catalog and record how the fetch went.
+    async fn refresh(&self, catalog_locale: Option<&str>) -> CoreResult<()> {
+        let mut token_attempts = Vec::new();
+        let result = self.fetch_models(catalog_locale, &mut token_attempts).await;
+        let mut record = self.fetch.write().await;
+        record.token_attempts = token_attempts;
+        match &result {
+            Ok(()) => {
+                record.source = CatalogSource::Live;
+                record.fetched_at = Some(Utc::now());
+                record.last_error = None;
+                record.last_error_at = None;
+            }
+            Err(err) => {
+                record.last_error = Some(redact_error(&err.to_string()));
+                record.last_error_at = Some(Utc::now());
+            }
+        }
+        result
+    }
+
+    /// Fetch the catalog and apply it. When the catalog turns away the
+    /// signed-in ChatGPT token, the token is refreshed and the fetch retried
+    /// once, as for model requests. How old the token each request carried
+    /// was is pushed to `token_attempts`.
+    async fn fetch_models(
+        &self,
+        catalog_locale: Option<&str>,
+        token_attempts: &mut Vec<TokenFreshness>,
+    ) -> CoreResult<()> {
+        let api_provider = self.provider.to_api_provider(Some(AuthMode::ChatGPT))?;
+        let base_url = api_provider.base_url.clone();
+        self.fetch.write().await.host = redact_host(&base_url);
+        let user_agent_suffix = self.user_agent_suffix.as_deref();
+        let http_client = build_reqwest_client_for_catalog(&self.provider, user_agent_suffix)?;
+        if !self.provider.skip_models_preflight {
+            let preflight_client =
+                build_reqwest_client_for_preflight(&self.provider, user_agent_suffix)?;
+            preflight(&preflight_client, &api_provider.base_url).await?;
+        }
+
+        let auth = self.auth_manager.auth();
+        let chatgpt = !self.provider.allow_anonymous_catalog
+            && auth
+                .as_ref()
+                .is_some_and(|auth| auth.mode == AuthMode::ChatGPT);
+        let api_auth = catalog_auth_provider(auth, &self.provider).await?;
+        let listing = match self
+            .list_models(&http_client, api_auth, catalog_locale, token_attempts)
+            .await
+        {
+            Err(err)
+                if chatgpt
+                    && err.http_status_code_value() == Some(StatusCode::UNAUTHORIZED.as_u16()) =>
+            {
+                self.auth_manager
+                    .refresh_token()
+                    .await
+                    .map_err(|err| match err {
+                        RefreshTokenError::Permanent(failed) => {
+                            CodexErr::RefreshTokenFailed(failed)
+                        }
+                        RefreshTokenError::Transient(other) => CodexErr::Io(other),
+                    })?;
+                let api_auth = catalog_auth_provider(self.auth_manager.auth(), &self.provider)
+                    .await?
+                    .refreshed();
+                self.list_models(&http_client, api_auth, catalog_locale, token_attempts)
+                    .await?
+            }
+            result => result?,
+        };
+        let ModelsListing {
+            models,
+            etag,
+            min_client_version,
+        } = listing;
+
+        let persisted_default = match cache::load_cache(&self.cache_path).await {
+            Ok(cache) => cache.and_then(|cache| cache.default_for(&base_url)),
+            Err(_) => None,
+        };
+        let default_model = self
+            .apply_models(models.clone(), persisted_default)
+            .await
+            .map(|model| CachedDefault { base_url, model });
+        *self.etag.write().await = etag.clone();
+        self.fetch.write().await.min_client_version = min_client_version.clone();
+        self.persist_cache(
+            &models,
+            etag,
+            min_client_version,
+            default_model,
+            catalog_locale,
+        )
+        .await;
+        Ok(())
+    }
+
+    async fn list_models(
+        &self,
+        http_client: &reqwest::Client,
+        api_auth: CoreAuthProvider,
+        catalog_locale: Option<&str>,
+        token_attempts: &mut Vec<TokenFreshness>,
+    ) -> CoreResult<ModelsListing> {
+        if let Some(freshness) = api_auth.token_freshness() {
+            debug!(
+                token_source = %freshness.source,
+                token_age_ms = freshness.age_ms,
+                token_expires_in_ms = freshness.expires_in_ms,
+                "listing models with a ChatGPT token"
+            );
+            token_attempts.push(freshness);
+        }
+        let api_provider = self.provider.to_api_provider(Some(AuthMode::ChatGPT))?;
+        let transport = ReqwestTransport::new(http_client.clone());
+        let client = ModelsClient::new(transport, api_provider, api_auth)
+            .with_max_response_bytes(
+                self.provider
+                    .models_max_response_bytes
+                    .unwrap_or(DEFAULT_MAX_MODELS_RESPONSE_BYTES),
+            )
+            .with_path(self.provider.models_path.clone());
+        let client_version = format_client_version_to_whole();
+        client
+            .list_models(&client_version, catalog_headers(catalog_locale))
+            .await
+            .map_err(|err| map_catalog_error(err, &client, &self.provider))
+    }
+
+    /// Replace the cached remote models, bumping the catalog revision when
+    /// they or their default changed, and return the default. Entries are
+    /// sanitized first, since they come from the network or from a cache
+    /// file on disk. `persisted_default` is the default a cache on disk
+    /// recorded, for a process that has not settled on one yet.
+    async fn apply_models(
+        &self,
+        models: Vec<ModelInfo>,
+        persisted_default: Option<String>,
+    ) -> Option<String> {
+        let models: Vec<ModelInfo> = models
+            .into_iter()
+            .filter_map(|info| {
+                sanitize_model_info(info)
+                    .inspect_err(|err| warn!("{err}"))
+                    .ok()
+            })
+            .collect();
+        let mut remote_models = self.models.write().await;
+        let (previous, default) = match self.default_model.lock() {
+            Ok(mut default) => {
+                let previous = default.clone();
+                *default = sticky_default(
+                    previous.as_deref().or(persisted_default.as_deref()),
+                    &models,
+                );
+                (previous, default.clone())
+            }
+            Err(_) => (None, None),
+        };
+        let default_changed = previous != default;
+        if let Some(previous) = previous
+            && default_changed
+        {
+            info!(
+                "the model catalog's default changed from `{previous}` to `{}`",
+                default.as_deref().unwrap_or("none")
+            );
+            self.events.default_changed(previous, default.clone());
+        }
+        if *remote_models != models || default_changed {
+            *remote_models = models;
+            self.revision.send_modify(|revision| *revision += 1);
+        }
+        default
+    }
+
+    /// Serialize the latest fetch to disk for reuse across future processes.
+    async fn persist_cache(
+        &self,
+        models: &[ModelInfo],
+        etag: Option<String>,
+        min_client_version: Option<String>,
+        default_model: Option<CachedDefault>,
+        catalog_locale: Option<&str>,
+    ) {
+        let cache = ModelsCache {
+            fetched_at: Utc::now(),
+            etag,
+            models: models.to_vec(),
+            locale: catalog_locale.map(str::to_string),
+            min_client_version,
+            default_model,
+        };
+        if let Err(err) = cache::save_cache(&self.cache_path, &cache).await {
+            error!("failed to write models cache: {err}");
+        }
+    }
+}
+
+/// The default of a remote catalog replacing one whose default was
+/// `previous`: the listed entry the catalog labels default, else `previous`
+/// while it is still listed, else the listed entry of highest priority, the
+/// lowest slug among entries that tie on it. Only
+/// a label or a removal moves the default, so a server that reorders its
+/// entries does not change what new conversations start with.
+fn sticky_default(previous: Option<&str>, models: &[ModelInfo]) -> Option<String> {
+    let listed = || {
+        models
+            .iter()
+            .filter(|model| model.visibility == ModelVisibility::List)
+    };
+    if let Some(labeled) = listed().find(|model| model.is_default) {
+        return Some(labeled.slug.clone());
+    }
+    if let Some(previous) = previous
+        && listed().any(|model| model.slug == previous)
+    {
+        return Some(previous.to_string());
+    }
+    listed()
+        .min_by(|a, b| {
+            a.priority
+                .cmp(&b.priority)
+                .then_with(|| a.slug.cmp(&b.slug))
+        })
+        .map(|model| model.slug.clone())
+}
+
+fn remote_model_ids(models: &[ModelInfo]) -> HashSet<String> {
+    models.iter().map(|model| model.slug.clone()).collect()
+}
+
+fn preset_ids(presets: &[ModelPreset]) -> HashSet<String> {
+    presets.iter().map(|preset| preset.id.clone()).collect()
+}
+
+/// Whether a provider-served `slug` accepts `temperature` and `top_p`, judged
+/// by its built-in family. Unknown models are taken to be conventional chat
+/// models that do.
+fn sampling_support(slug: &str) -> (bool, bool) {
+    known_family_for_model(slug).map_or((true, true), |family| {
+        (family.supports_temperature, family.supports_top_p)
+    })
+}
+
+/// The client that lists `catalog`, sending through `http_client`.
+pub(crate) async fn catalog_models_client(
+    catalog: &ProviderCatalogConfig,
+    http_client: reqwest::Client,
+) -> CoreResult<ModelsClient<ReqwestTransport, CoreAuthProvider>> {
+    let provider = &catalog.provider;
+    catalog.base_url()?;
+    catalog.require_bearer_token()?;
+    let api_provider = provider.to_api_provider(None)?;
+    let api_auth = catalog_auth_provider(None, provider).await?;
+    let transport = ReqwestTransport::new(http_client);
+    Ok(ModelsClient::new(transport, api_provider, api_auth)
+        .with_max_response_bytes(
+            provider
+                .models_max_response_bytes
+                .unwrap_or(DEFAULT_MAX_MODELS_RESPONSE_BYTES),
+        )
+        .with_path(catalog.path.clone()))
+}
+
+/// Credentials for a catalog request to `provider`: none when it allows
+/// anonymous catalog reads, otherwise the same as for its other requests.
+async fn catalog_auth_provider(
+    auth: Option<CodexAuth>,
+    provider: &ModelProviderInfo,
+) -> CoreResult<CoreAuthProvider> {
+    if provider.allow_anonymous_catalog {
+        return Ok(CoreAuthProvider::default());
+    }
+    auth_provider_from_auth(auth, provider).await
+}
+
+/// Whether `err` is a catalog turning away the credentials it was sent, or
+/// the lack of them.
+fn is_auth_failure(err: &CodexErr) -> bool {
+    matches!(err.to_codex_protocol_error(), CodexErrorInfo::Unauthorized)
+        || err.http_status_code_value().is_some_and(|status| {
+            status == StatusCode::UNAUTHORIZED.as_u16() || status == StatusCode::FORBIDDEN.as_u16()
+        })
+}
+
+/// A catalog that turns away a request sent without credentials needs a
+/// login, not a retry, so that case gets its own error.
+fn map_catalog_error(
+    err: ApiError,
+    client: &ModelsClient<ReqwestTransport, CoreAuthProvider>,
+    provider: &ModelProviderInfo,
+) -> CodexErr {
+    if !client.has_credentials()
+        && let ApiError::Transport(TransportError::Http { status, .. }) = &err
+        && matches!(*status, StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN)
+    {
+        return CodexErr::CatalogRequiresLogin(provider.name.clone());
+    }
+    map_api_error(err)
+}
+
+/// A catalog entry no preset can be built from, such as one with an empty
+/// id, is left out of the listing rather than failing the whole refresh.
+fn listed_preset(
+    provider_id: &str,
+    preset: Result<ModelPreset, ModelPresetError>,
+) -> Option<ModelPreset> {
+    preset
+        .inspect_err(|err| warn!("skipping a model listed by provider `{provider_id}`: {err}"))
+        .ok()
+}
+
+/// The effort `by_auth` sets for `auth_mode`.
+fn effort_for_auth(by_auth: EffortByAuth, auth_mode: AuthMode) -> Option<ReasoningEffort> {
+    match auth_mode {
+        AuthMode::ApiKey => by_auth.apikey,
+        AuthMode::ChatGPT => by_auth.chatgpt,
+    }
+}
+
+/// Fail with [`CodexErr::EmptyCatalog`] when a catalog listed nothing and its
+/// provider does not allow that, so the refresh keeps the models listed
+/// before. `request_url` is only built for the error.
+fn ensure_listed<T>(
+    listed: &[T],
+    provider: &ModelProviderInfo,
+    request_url: impl FnOnce() -> String,
+) -> CoreResult<()> {
+    if listed.is_empty() && !provider.allow_empty_catalog {
+        return Err(CodexErr::EmptyCatalog(catalog_endpoint(&request_url())));
+    }
+    Ok(())
+}
+
+/// `url` without credentials or query string, for naming a catalog endpoint
+/// in messages.
+fn catalog_endpoint(url: &str) -> String {
+    match Url::parse(url) {
+        Ok(mut url) => {
+            let _ = url.set_username("");
+            let _ = url.set_password(None);
+            url.set_query(None);
+            url.set_fragment(None);
+            url.to_string()
+        }
+        Err(_) => url.split(['?', '#']).next().unwrap_or(url).to_string(),
+    }
+}
+
+fn as_millis(duration: Duration) -> u64 {
+    u64::try_from(duration.as_millis()).unwrap_or(u64::MAX)
+}
+
+/// Extra headers for catalog requests: `Accept-Language` when a locale is
+/// configured and forms a valid header value.
+pub(crate) fn catalog_headers(catalog_locale: Option<&str>) -> HeaderMap {
+    let mut headers = HeaderMap::new();
+    if let Some(value) = catalog_locale.and_then(|locale| HeaderValue::from_str(locale).ok()) {
+        headers.insert(ACCEPT_LANGUAGE, value);
+    }
+    headers
+}
+
+/// Convert a client version string to a whole version string (e.g. "1.2.3-alpha.4" -> "1.2.3")
+fn format_client_version_to_whole() -> String {
+    format!(
+        "{}.{}.{}",
+        env!("CARGO_PKG_VERSION_MAJOR"),
+        env!("CARGO_PKG_VERSION_MINOR"),
+        env!("CARGO_PKG_VERSION_PATCH")
+    )
+}
+
+/// A digest of what a listing returned, stable across processes and
+/// releases as long as the serialized presets are. Listings filtered after
+/// [`ModelsManager::model_list`] use it to revise the filtered models.
+pub fn content_revision(auth: &CatalogAuth, models: &[ModelPreset]) -> u64 {
+    let mut hasher = Sha256::new();
+    if let Ok(auth) = serde_json::to_vec(auth) {
+        hasher.update(auth);
+    }
+    if let Ok(models) = serde_json::to_vec(models) {
+        hasher.update(models);
+    }
+    let digest = hasher.finalize();
+    let mut prefix = [0u8; 8];
+    prefix.copy_from_slice(&digest[..8]);
+    u64::from_be_bytes(prefix)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::cache::ModelsCache;
+    use super::*;
+    use crate::CodexAuth;
+    use crate::auth::AuthCredentialsStoreMode;
+    use crate::config::ConfigBuilder;
+    use crate::config::types::ModelPresetOverride;
+    use crate::config::types::OtelExporterKind;
+    use crate::config::types::OtelHttpProtocol;
+    use crate::config::types::PresetOverride;
+    use crate::features::Feature;
+    use crate::model_provider_info::WireApi;
+    use crate::models_manager::availability::UNAVAILABLE_TTL;
+    use crate::models_manager::catalog_export::install_imported_catalog;
+    use crate::models_manager::preflight::PREFLIGHT_TIMEOUT;
+    use crate::models_manager::provenance::TokenSource;
+    use crate::models_manager::sanitize::MAX_DESCRIPTION_CHARS;
+    use codex_protocol::openai_models::ModelsResponse;
+    use codex_protocol::openai_models::ReasoningSummaryFormat;
+    use core_test_support::responses::mount_models_once;
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+    use std::collections::BTreeMap;
+    use std::collections::HashMap;
+    use tempfile::tempdir;
+    use wiremock::Mock;
+    use wiremock::MockServer;
+    use wiremock::ResponseTemplate;
+    use wiremock::matchers::header;
+    use wiremock::matchers::method;
+    use wiremock::matchers::path;
+    use wiremock::matchers::query_param;
+
+    fn remote_model(slug: &str, display: &str, priority: i32) -> ModelInfo {
+        remote_model_with_visibility(slug, display, priority, "list")
+    }
+
+    fn remote_model_with_visibility(
+        slug: &str,
+        display: &str,
+        priority: i32,
+        visibility: &str,
+    ) -> ModelInfo {
+        serde_json::from_value(json!({
+            "slug": slug,
+            "display_name": display,
+            "description": format!("{display} desc"),
+            "default_reasoning_level": "medium",
+            "supported_reasoning_levels": [{"effort": "low", "description": "low"}, {"effort": "medium", "description": "medium"}],
+            "shell_type": "shell_command",
+            "visibility": visibility,
+            "minimal_client_version": [0, 1, 0],
+            "supported_in_api": true,
+            "priority": priority,
+            "upgrade": null,
+            "base_instructions": null,
+            "supports_reasoning_summaries": false,
+            "support_verbosity": false,
+            "default_verbosity": null,
+            "apply_patch_tool_type": null,
+            "truncation_policy": {"mode": "bytes", "limit": 10_000},
+            "supports_parallel_tool_calls": false,
+            "context_window": null,
+            "experimental_supported_tools": [],
+        }))
+        .expect("valid model")
+    }
+
+    fn provider_for(base_url: String) -> ModelProviderInfo {
+        ModelProviderInfo {
+            name: "mock".into(),
+            base_url: Some(base_url),
+            env_key: None,
+            env_key_instructions: None,
+            experimental_bearer_token: None,
+            wire_api: WireApi::Responses,
+            query_params: None,
             http_headers: None,
             env_http_headers: None,
             request_max_retries: Some(0),
             stream_max_retries: Some(0),
             stream_idle_timeout_ms: Some(5_000),
             requires_openai_auth: false,
+            model_catalog: None,
+            skip_models_preflight: false,
+            allow_anonymous_catalog: false,
+            allow_empty_catalog: false,
+            instructions_addendum: None,
+            supported_wire_apis: None,
+            model_pricing: None,
+            models_max_response_bytes: None,
+            models: None,
+            model_request_headers: None,
+            models_path: None,
+            allow_insecure_catalog: false,
+            pinned_spki_sha256: None,
+            token_command: None,
+            token_command_ttl_secs: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn refresh_available_models_sorts_and_marks_default() {
+        let server = MockServer::start().await;
+        let remote_models = vec![
+            remote_model("priority-low", "Low", 1),
+            remote_model("priority-high", "High", 0),
+        ];
+        let models_mock = mount_models_once(
+            &server,
+            ModelsResponse {
+                models: remote_models.clone(),
+                min_client_version: None,
+            },
+        )
+        .await;
+
+        let codex_home = tempdir().expect("temp dir");
+        let mut config = ConfigBuilder::default()
+            .codex_home(codex_home.path().to_path_buf())
+            .build()
+            .await
+            .expect("load default test config");
+        config.features.enable(Feature::RemoteModels);
+        let auth_manager =
+            AuthManager::from_auth_for_testing(CodexAuth::create_dummy_chatgpt_auth_for_testing());
+        let provider = provider_for(server.uri());
+        let manager = ModelsManager::with_provider(auth_manager, provider);
+
+        manager
+            .refresh_available_models_with_cache(&config)
+            .await
+            .expect("refresh succeeds");
+        let cached_remote = manager.remote_models(&config).await;
+        assert_eq!(cached_remote, remote_models);
+
+        let available = manager.list_models(&config).await;
+        let high_idx = available
+            .iter()
+            .position(|model| model.model == "priority-high")
+            .expect("priority-high should be listed");
+        let low_idx = available
+            .iter()
+            .position(|model| model.model == "priority-low")
+            .expect("priority-low should be listed");
+        assert!(
+            high_idx < low_idx,
+            "higher priority should be listed before lower priority"
+        );
+        assert!(
+            available[high_idx].is_default,
+            "highest priority should be default"
+        );
+        assert!(!available[low_idx].is_default);
+        assert_eq!(
+            models_mock.requests().len(),
+            1,
+            "expected a single /models request"
+        );
+    }
+
+    #[tokio::test]
+    async fn remote_and_cached_catalog_strings_are_sanitized() {
+        let server = MockServer::start().await;
+        let mut hostile = remote_model("oca-large", "OCA \u{1b}[31mLarge\u{1b}[0m", 0);
+        hostile.description = Some(format!("\u{1b}]0;pwned\u{7}{}", "x".repeat(10_000)));
+        let models_mock = mount_models_once(
+            &server,
+            ModelsResponse {
+                models: vec![hostile],
+                min_client_version: None,
+            },
+        )
+        .await;
+
+        let codex_home = tempdir().expect("temp dir");
+        let mut config = ConfigBuilder::default()
+            .codex_home(codex_home.path().to_path_buf())
+            .build()
+            .await
+            .expect("load default test config");
+        config.features.enable(Feature::RemoteModels);
+        let auth_manager = Arc::new(AuthManager::new(
+            codex_home.path().to_path_buf(),
+            false,
+            AuthCredentialsStoreMode::File,
+        ));
+        let expected_description = format!("{}…", "x".repeat(MAX_DESCRIPTION_CHARS - 1));
+
+        let manager =
+            ModelsManager::with_provider(auth_manager.clone(), provider_for(server.uri()));
+        manager
+            .refresh_available_models_with_cache(&config)
+            .await
+            .expect("refresh succeeds");
+        let fetched = manager.remote_models(&config).await;
+        assert_eq!(
+            (
+                fetched[0].display_name.as_str(),
+                fetched[0].description.as_deref()
+            ),
+            ("OCA Large", Some(expected_description.as_str()))
+        );
+
+        // A fresh manager reads the same entry back from the cache file.
+        let cached = ModelsManager::with_provider(auth_manager, provider_for(server.uri()));
+        cached
+            .refresh_available_models_with_cache(&config)
+            .await
+            .expect("cached refresh succeeds");
+        assert_eq!(cached.remote_models(&config).await, fetched);
+        assert_eq!(models_mock.requests().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn refresh_available_models_uses_cache_when_fresh() {
+        let server = MockServer::start().await;
+        let remote_models = vec![remote_model("cached", "Cached", 5)];
+        let models_mock = mount_models_once(
+            &server,
+            ModelsResponse {
+                models: remote_models.clone(),
+                min_client_version: None,
+            },
+        )
+        .await;
+
+        let codex_home = tempdir().expect("temp dir");
+        let mut config = ConfigBuilder::default()
+            .codex_home(codex_home.path().to_path_buf())
+            .build()
+            .await
+            .expect("load default test config");
+        config.features.enable(Feature::RemoteModels);
+        let auth_manager = Arc::new(AuthManager::new(
+            codex_home.path().to_path_buf(),
+            false,
+            AuthCredentialsStoreMode::File,
+        ));
+        let provider = provider_for(server.uri());
+        let manager = ModelsManager::with_provider(auth_manager, provider);
+
+        manager
+            .refresh_available_models_with_cache(&config)
+            .await
+            .expect("first refresh succeeds");
+        assert_eq!(
+            manager.remote_models(&config).await,
+            remote_models,
+            "remote cache should store fetched models"
+        );
+
+        // Second call should read from cache and avoid the network.
+        manager
+            .refresh_available_models_with_cache(&config)
+            .await
+            .expect("cached refresh succeeds");
+        assert_eq!(
+            manager.remote_models(&config).await,
+            remote_models,
+            "cache path should not mutate stored models"
+        );
+        assert_eq!(
+            models_mock.requests().len(),
+            1,
+            "cache hit should avoid a second /models request"
+        );
+    }
+
+    #[tokio::test]
+    #[tracing_test::traced_test]
+    async fn the_disk_cache_is_reported_once_per_cache() {
+        let server = MockServer::start().await;
+        mount_models_once(
+            &server,
+            ModelsResponse {
+                models: vec![remote_model("cached", "Cached", 5)],
+                min_client_version: None,
+            },
+        )
+        .await;
+
+        let codex_home = tempdir().expect("temp dir");
+        let mut config = ConfigBuilder::default()
+            .codex_home(codex_home.path().to_path_buf())
+            .build()
+            .await
+            .expect("load default test config");
+        config.features.enable(Feature::RemoteModels);
+        config.otel.exporter = OtelExporterKind::OtlpHttp {
+            endpoint: "http://127.0.0.1:4318/v1/logs".to_string(),
+            headers: HashMap::new(),
+            protocol: OtelHttpProtocol::Json,
+            tls: None,
+        };
+        let auth_manager = Arc::new(AuthManager::new(
+            codex_home.path().to_path_buf(),
+            false,
+            AuthCredentialsStoreMode::File,
+        ));
+        let manager = ModelsManager::with_provider(auth_manager, provider_for(server.uri()));
+
+        for _ in 0..3 {
+            manager
+                .refresh_available_models_with_cache(&config)
+                .await
+                .expect("refresh succeeds");
+        }
+
+        logs_assert(|lines: &[&str]| {
+            let sources: Vec<&str> = lines
+                .iter()
+                .filter(|line| line.contains("codex.catalog_fetch_completed"))
+                .filter_map(|line| {
+                    ["\"live\"", "\"disk_cache\""]
+                        .into_iter()
+                        .find(|source| line.contains(source))
+                })
+                .collect();
+            match sources.as_slice() {
+                ["\"live\"", "\"disk_cache\""] => Ok(()),
+                _ => Err(format!(
+                    "expected one live and one disk cache event: {sources:?}"
+                )),
+            }
+        });
+    }
+
+    #[tokio::test]
+    async fn catalog_locale_is_sent_and_keys_the_cache() {
+        let server = MockServer::start().await;
+        let french = vec![remote_model("modele", "Modèle", 1)];
+        Mock::given(method("GET"))
+            .and(path("/models"))
+            .and(header("accept-language", "fr-FR"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(ModelsResponse {
+                models: french.clone(),
+                min_client_version: None,
+            }))
+            .expect(1)
+            .mount(&server)
+            .await;
+        let german = vec![remote_model("modell", "Modell", 1)];
+        Mock::given(method("GET"))
+            .and(path("/models"))
+            .and(header("accept-language", "de-DE"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(ModelsResponse {
+                models: german.clone(),
+                min_client_version: None,
+            }))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let codex_home = tempdir().expect("temp dir");
+        let mut config = ConfigBuilder::default()
+            .codex_home(codex_home.path().to_path_buf())
+            .build()
+            .await
+            .expect("load default test config");
+        config.features.enable(Feature::RemoteModels);
+        config.catalog_locale = Some("fr_FR.UTF-8".to_string());
+        let auth_manager = Arc::new(AuthManager::new(
+            codex_home.path().to_path_buf(),
+            false,
+            AuthCredentialsStoreMode::File,
+        ));
+        let manager = ModelsManager::with_provider(auth_manager, provider_for(server.uri()));
+
+        manager
+            .refresh_available_models_with_cache(&config)
+            .await
+            .expect("french refresh succeeds");
+        assert_eq!(manager.remote_models(&config).await, french);
+
+        // The cache is still fresh, but it holds French descriptions.
+        config.catalog_locale = Some("de-DE".to_string());
+        manager
+            .refresh_available_models_with_cache(&config)
+            .await
+            .expect("german refresh succeeds");
+        assert_eq!(manager.remote_models(&config).await, german);
+
+        manager
+            .refresh_available_models_with_cache(&config)
+            .await
+            .expect("cached german refresh succeeds");
+        assert_eq!(manager.remote_models(&config).await, german);
+    }
+
+    #[tokio::test]
+    async fn empty_catalog_locale_sends_no_accept_language() {
+        let server = MockServer::start().await;
+        let models_mock = mount_models_once(
+            &server,
+            ModelsResponse {
+                models: vec![remote_model("plain", "Plain", 1)],
+                min_client_version: None,
+            },
+        )
+        .await;
+
+        let codex_home = tempdir().expect("temp dir");
+        let mut config = ConfigBuilder::default()
+            .codex_home(codex_home.path().to_path_buf())
+            .build()
+            .await
+            .expect("load default test config");
+        config.features.enable(Feature::RemoteModels);
+        config.catalog_locale = Some(String::new());
+        let auth_manager = Arc::new(AuthManager::new(
+            codex_home.path().to_path_buf(),
+            false,
+            AuthCredentialsStoreMode::File,
+        ));
+        let manager = ModelsManager::with_provider(auth_manager, provider_for(server.uri()));
+
+        manager
+            .refresh_available_models_with_cache(&config)
+            .await
+            .expect("refresh succeeds");
+
+        let requests = models_mock.requests();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].headers.get("accept-language"), None);
+    }
+
+    #[tokio::test]
+    async fn refresh_available_models_refetches_when_cache_stale() {
+        let server = MockServer::start().await;
+        let initial_models = vec![remote_model("stale", "Stale", 1)];
+        let initial_mock = mount_models_once(
+            &server,
+            ModelsResponse {
+                models: initial_models.clone(),
+                min_client_version: None,
+            },
+        )
+        .await;
+
+        let codex_home = tempdir().expect("temp dir");
+        let mut config = ConfigBuilder::default()
+            .codex_home(codex_home.path().to_path_buf())
+            .build()
+            .await
+            .expect("load default test config");
+        config.features.enable(Feature::RemoteModels);
+        let auth_manager = Arc::new(AuthManager::new(
+            codex_home.path().to_path_buf(),
+            false,
+            AuthCredentialsStoreMode::File,
+        ));
+        let provider = provider_for(server.uri());
+        let manager = ModelsManager::with_provider(auth_manager, provider);
+
+        manager
+            .refresh_available_models_with_cache(&config)
+            .await
+            .expect("initial refresh succeeds");
+
+        // Rewrite cache with a timestamp past `model_cache_max_staleness`, so it is too old
+        // to serve while refreshing and is refetched first.
+        let cache_path = codex_home.path().join(MODEL_CACHE_FILE);
+        let contents =
+            std::fs::read_to_string(&cache_path).expect("cache file should exist after refresh");
+        let mut cache: ModelsCache =
+            serde_json::from_str(&contents).expect("cache should deserialize");
+        cache.fetched_at = Utc::now() - chrono::Duration::hours(25);
+        std::fs::write(&cache_path, serde_json::to_string_pretty(&cache).unwrap())
+            .expect("cache rewrite succeeds");
+
+        let updated_models = vec![remote_model("fresh", "Fresh", 9)];
+        server.reset().await;
+        let refreshed_mock = mount_models_once(
+            &server,
+            ModelsResponse {
+                models: updated_models.clone(),
+                min_client_version: None,
+            },
+        )
+        .await;
+
+        manager
+            .refresh_available_models_with_cache(&config)
+            .await
+            .expect("second refresh succeeds");
+        assert_eq!(
+            manager.remote_models(&config).await,
+            updated_models,
+            "stale cache should trigger refetch"
+        );
+        assert_eq!(manager.catalog_freshness(), CatalogFreshness::Fresh);
+        assert_eq!(
+            initial_mock.requests().len(),
+            1,
+            "initial refresh should only hit /models once"
+        );
+        assert_eq!(
+            refreshed_mock.requests().len(),
+            1,
+            "stale cache refresh should fetch /models once"
+        );
+    }
+
+    #[tokio::test]
+    async fn a_newer_catalog_minimum_raises_a_notice_without_blocking() {
+        let server = MockServer::start().await;
+        mount_models_once(
+            &server,
+            ModelsResponse {
+                models: vec![remote_model("alpha", "Alpha", 1)],
+                min_client_version: Some("999.0.0".to_string()),
+            },
+        )
+        .await;
+
+        let codex_home = tempdir().expect("temp dir");
+        let mut config = ConfigBuilder::default()
+            .codex_home(codex_home.path().to_path_buf())
+            .build()
+            .await
+            .expect("load default test config");
+        config.features.enable(Feature::RemoteModels);
+        let auth_manager = Arc::new(AuthManager::new(
+            codex_home.path().to_path_buf(),
+            false,
+            AuthCredentialsStoreMode::File,
+        ));
+        let manager =
+            ModelsManager::with_provider(auth_manager.clone(), provider_for(server.uri()));
+        manager
+            .refresh_available_models_with_cache(&config)
+            .await
+            .expect("refresh succeeds");
+
+        let expected = ClientVersionNotice {
+            min_client_version: "999.0.0".to_string(),
+            client_version: CLIENT_VERSION.to_string(),
+        };
+        assert_eq!(
+            manager.client_version_notice(&config).await,
+            Some(expected.clone())
+        );
+        assert_eq!(
+            manager.try_client_version_notice(&config).ok().flatten(),
+            Some(expected.clone())
+        );
+
+        // A second process hears about it from the cache, and the models are
+        // listed all the same.
+        server.reset().await;
+        let manager = ModelsManager::with_provider(auth_manager, provider_for(server.uri()));
+        let list = manager.model_list(&config).await;
+        assert_eq!(list.client_version_notice, Some(expected));
+        assert!(list.models.iter().any(|preset| preset.id == "alpha"));
+
+        config.features.disable(Feature::RemoteModels);
+        assert_eq!(manager.client_version_notice(&config).await, None);
+    }
+
+    #[tokio::test]
+    async fn a_provider_catalog_minimum_raises_the_same_notice() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/v1/models"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "object": "list",
+                "data": [{"id": "oca-large", "object": "model"}],
+                "min_client_version": "999.0.0",
+            })))
+            .up_to_n_times(1)
+            .mount(&server)
+            .await;
+        let codex_home = tempdir().expect("temp dir");
+        let mut config = status_test_config(codex_home.path()).await;
+        config.model_providers.insert(
+            "gateway".to_string(),
+            ModelProviderInfo {
+                model_catalog: Some(ModelCatalog::Openai),
+                ..provider_for(format!("{}/v1", server.uri()))
+            },
+        );
+        let auth_manager =
+            AuthManager::from_auth_for_testing(CodexAuth::from_api_key("Test API Key"));
+        let manager =
+            ModelsManager::with_provider(auth_manager, provider_for("http://example.test".into()));
+
+        manager.refresh_provider_models(&config).await;
+        let expected = ClientVersionNotice {
+            min_client_version: "999.0.0".to_string(),
+            client_version: CLIENT_VERSION.to_string(),
+        };
+        assert_eq!(
+            manager.client_version_notice(&config).await,
+            Some(expected.clone())
+        );
+
+        // A failed relist keeps the minimum the catalog served last.
+        manager.refresh_provider_models(&config).await;
+        assert_eq!(
+            manager.try_client_version_notice(&config).ok().flatten(),
+            Some(expected)
+        );
+    }
+
+    #[tokio::test]
+    async fn unknown_catalog_fields_survive_the_cache() {
+        let server = MockServer::start().await;
+        let mut served = remote_model("alpha", "Alpha", 1);
+        served
+            .extra
+            .insert("quota_tier".to_string(), json!({"tier": "gold"}));
+        mount_models_once(
+            &server,
+            ModelsResponse {
+                models: vec![served],
+                min_client_version: None,
+            },
+        )
+        .await;
+
+        let codex_home = tempdir().expect("temp dir");
+        let mut config = ConfigBuilder::default()
+            .codex_home(codex_home.path().to_path_buf())
+            .build()
+            .await
+            .expect("load default test config");
+        config.features.enable(Feature::RemoteModels);
+        let auth_manager = Arc::new(AuthManager::new(
+            codex_home.path().to_path_buf(),
+            false,
+            AuthCredentialsStoreMode::File,
+        ));
+        let manager =
+            ModelsManager::with_provider(auth_manager.clone(), provider_for(server.uri()));
+        manager
+            .refresh_available_models_with_cache(&config)
+            .await
+            .expect("refresh succeeds");
+
+        let contents = std::fs::read_to_string(codex_home.path().join(MODEL_CACHE_FILE))
+            .expect("cache file should exist after refresh");
+        let cache: serde_json::Value =
+            serde_json::from_str(&contents).expect("cache should deserialize");
+        assert_eq!(cache["models"][0]["quota_tier"], json!({"tier": "gold"}));
+
+        // A second process reads the field back from the cache alone.
+        server.reset().await;
+        let manager = ModelsManager::with_provider(auth_manager, provider_for(server.uri()));
+        let list = manager.model_list(&config).await;
+        assert_eq!(
+            list.extra.get("alpha"),
+            Some(&HashMap::from([(
+                "quota_tier".to_string(),
+                json!({"tier": "gold"})
+            )]))
+        );
+        assert!(
+            server
+                .received_requests()
+                .await
+                .unwrap_or_default()
+                .is_empty()
+        );
+        assert!(
+            list.models.iter().any(|preset| preset.id == "alpha"),
+            "the preset is listed without the extra fields"
+        );
+    }
+
+    #[tokio::test]
+    async fn stale_cache_is_served_at_once_and_replaced_in_the_background() {
+        let server = MockServer::start().await;
+        let initial_models = vec![remote_model("stale", "Stale", 1)];
+        mount_models_once(
+            &server,
+            ModelsResponse {
+                models: initial_models.clone(),
+                min_client_version: None,
+            },
+        )
+        .await;
+
+        let codex_home = tempdir().expect("temp dir");
+        let mut config = ConfigBuilder::default()
+            .codex_home(codex_home.path().to_path_buf())
+            .build()
+            .await
+            .expect("load default test config");
+        config.features.enable(Feature::RemoteModels);
+        let auth_manager =
+            AuthManager::from_auth_for_testing(CodexAuth::create_dummy_chatgpt_auth_for_testing());
+        let manager = ModelsManager::with_provider(auth_manager, provider_for(server.uri()));
+        manager
+            .refresh_available_models_with_cache(&config)
+            .await
+            .expect("initial refresh succeeds");
+
+        // Past the TTL, but well within `max_staleness`.
+        let cache_path = codex_home.path().join(MODEL_CACHE_FILE);
+        let contents =
+            std::fs::read_to_string(&cache_path).expect("cache file should exist after refresh");
+        let mut cache: ModelsCache =
+            serde_json::from_str(&contents).expect("cache should deserialize");
+        cache.fetched_at = Utc::now() - chrono::Duration::hours(1);
+        std::fs::write(&cache_path, serde_json::to_string_pretty(&cache).unwrap())
+            .expect("cache rewrite succeeds");
+
+        server.reset().await;
+        let updated_models = vec![remote_model("fresh", "Fresh", 9)];
+        Mock::given(method("GET"))
+            .and(path("/models"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("content-type", "application/json")
+                    .set_body_json(ModelsResponse {
+                        models: updated_models.clone(),
+                        min_client_version: None,
+                    })
+                    .set_delay(Duration::from_millis(500)),
+            )
+            .up_to_n_times(1)
+            .expect(1)
+            .mount(&server)
+            .await;
+        let revision = manager.catalog_revision();
+
+        let started = Instant::now();
+        let listed = manager.model_list(&config).await;
+        assert!(
+            started.elapsed() < Duration::from_millis(500),
+            "the stale cache is returned without waiting for the refresh"
+        );
+        assert_eq!(listed.freshness, CatalogFreshness::Revalidating);
+        assert!(listed.models.iter().any(|preset| preset.model == "stale"));
+        assert_eq!(manager.remote_models(&config).await, initial_models);
+
+        manager.finish_revalidation().await;
+        assert_eq!(manager.remote_models(&config).await, updated_models);
+        assert_eq!(manager.catalog_freshness(), CatalogFreshness::Fresh);
+        assert!(manager.catalog_revision() > revision);
+        let refreshed: ModelsCache = serde_json::from_str(
+            &std::fs::read_to_string(&cache_path).expect("cache file is rewritten"),
+        )
+        .expect("cache should deserialize");
+        assert_eq!(refreshed.models, updated_models);
+
+        let listed = manager.model_list(&config).await;
+        assert_eq!(listed.freshness, CatalogFreshness::Fresh);
+        assert!(listed.models.iter().any(|preset| preset.model == "fresh"));
+    }
+
+    #[tokio::test]
+    async fn offline_mode_uses_stale_cache_without_network() {
+        let server = MockServer::start().await;
+        let codex_home = tempdir().expect("temp dir");
+        let mut config = ConfigBuilder::default()
+            .codex_home(codex_home.path().to_path_buf())
+            .build()
+            .await
+            .expect("load default test config");
+        config.features.enable(Feature::RemoteModels);
+        config.offline = true;
+        config.model_providers.insert(
+            "lmstudio-local".to_string(),
+            ModelProviderInfo {
+                model_catalog: Some(ModelCatalog::Openai),
+                ..provider_for(server.uri())
+            },
+        );
+
+        let cached_models = vec![remote_model("cached", "Cached", 1)];
+        let cache = ModelsCache {
+            fetched_at: Utc::now() - chrono::Duration::days(30),
+            etag: None,
+            models: cached_models.clone(),
+            locale: None,
+            min_client_version: None,
+            default_model: None,
+        };
+        cache::save_cache(&codex_home.path().join(MODEL_CACHE_FILE), &cache)
+            .await
+            .expect("cache write succeeds");
+
+        let auth_manager = Arc::new(AuthManager::new(
+            codex_home.path().to_path_buf(),
+            false,
+            AuthCredentialsStoreMode::File,
+        ));
+        let manager = ModelsManager::with_provider(auth_manager, provider_for(server.uri()));
+
+        let presets = manager.list_models(&config).await;
+        manager.get_model(&None, &config).await;
+
+        assert_eq!(manager.remote_models(&config).await, cached_models);
+        assert!(
+            presets.iter().all(|preset| preset.model_provider.is_none()),
+            "catalog providers must not be listed offline: {presets:?}"
+        );
+        assert_eq!(
+            server
+                .received_requests()
+                .await
+                .expect("request recording enabled")
+                .len(),
+            0,
+            "offline mode must not contact any endpoint"
+        );
+    }
+
+    #[tokio::test]
+    async fn fixed_model_catalog_never_contacts_the_provider() {
+        let server = MockServer::start().await;
+        let codex_home = tempdir().expect("temp dir");
+        let mut config = ConfigBuilder::default()
+            .codex_home(codex_home.path().to_path_buf())
+            .build()
+            .await
+            .expect("load default test config");
+        config.features.enable(Feature::RemoteModels);
+        let gateway = ModelProviderInfo {
+            model_catalog: Some(ModelCatalog::None),
+            models: Some(vec!["oca-large".to_string(), "oca-small".to_string()]),
+            ..provider_for(server.uri())
+        };
+        config.model_provider_id = "gateway".to_string();
+        config.model_provider = gateway.clone();
+        config
+            .model_providers
+            .insert("gateway".to_string(), gateway.clone());
+
+        let auth_manager = Arc::new(AuthManager::new(
+            codex_home.path().to_path_buf(),
+            false,
+            AuthCredentialsStoreMode::File,
+        ));
+        let manager = ModelsManager::with_provider(auth_manager, gateway);
+
+        let presets = manager.list_models(&config).await;
+        manager
+            .refresh_all_models(&config)
+            .await
+            .expect("refresh skips the fixed catalog");
+        config.offline = true;
+        manager.refresh_provider_models(&config).await;
+
+        let fixed_ids = |presets: &[ModelPreset]| -> Vec<String> {
+            presets
+                .iter()
+                .filter(|preset| preset.model_provider.as_deref() == Some("gateway"))
+                .map(|preset| preset.id.clone())
+                .collect()
+        };
+        let expected = vec![
+            "gateway/oca-large".to_string(),
+            "gateway/oca-small".to_string(),
+        ];
+        assert_eq!(fixed_ids(&presets), expected);
+        assert_eq!(
+            fixed_ids(&manager.provider_models.read().await),
+            expected,
+            "fixed models are listed offline too"
+        );
+        assert_eq!(
+            server
+                .received_requests()
+                .await
+                .expect("request recording enabled")
+                .len(),
+            0,
+            "a fixed catalog must not contact any endpoint"
+        );
+    }
+
+    #[tokio::test]
+    async fn refresh_available_models_drops_removed_remote_models() {
+        let server = MockServer::start().await;
+        let initial_models = vec![remote_model("remote-old", "Remote Old", 1)];
+        let initial_mock = mount_models_once(
+            &server,
+            ModelsResponse {
+                models: initial_models,
+                min_client_version: None,
+            },
+        )
+        .await;
+
+        let codex_home = tempdir().expect("temp dir");
+        let mut config = ConfigBuilder::default()
+            .codex_home(codex_home.path().to_path_buf())
+            .build()
+            .await
+            .expect("load default test config");
+        config.features.enable(Feature::RemoteModels);
+        let auth_manager =
+            AuthManager::from_auth_for_testing(CodexAuth::create_dummy_chatgpt_auth_for_testing());
+        let provider = provider_for(server.uri());
+        let mut manager = ModelsManager::with_provider(auth_manager, provider);
+        manager.cache_ttl = Duration::ZERO;
+        config.model_cache_max_staleness = Duration::ZERO;
+
+        manager
+            .refresh_available_models_with_cache(&config)
+            .await
+            .expect("initial refresh succeeds");
+
+        server.reset().await;
+        let refreshed_models = vec![remote_model("remote-new", "Remote New", 1)];
+        let refreshed_mock = mount_models_once(
+            &server,
+            ModelsResponse {
+                models: refreshed_models,
+                min_client_version: None,
+            },
+        )
+        .await;
+
+        manager
+            .refresh_available_models_with_cache(&config)
+            .await
+            .expect("second refresh succeeds");
+
+        let available = PresetCatalog::new(&manager, &config)
+            .load_cached_or_static()
+            .expect("models should be available");
+        assert!(
+            available.iter().any(|preset| preset.model == "remote-new"),
+            "new remote model should be listed"
+        );
+        assert!(
+            !available.iter().any(|preset| preset.model == "remote-old"),
+            "removed remote model should not be listed"
+        );
+        assert_eq!(
+            initial_mock.requests().len(),
+            1,
+            "initial refresh should only hit /models once"
+        );
+        assert_eq!(
+            refreshed_mock.requests().len(),
+            1,
+            "second refresh should only hit /models once"
+        );
+    }
+
+    #[tokio::test]
+    async fn listed_revision_survives_restarts_and_follows_config() {
+        let codex_home = tempdir().expect("temp dir");
+        let mut config = status_test_config(codex_home.path()).await;
+        let new_manager = || {
+            let auth_manager =
+                AuthManager::from_auth_for_testing(CodexAuth::from_api_key("Test API Key"));
+            ModelsManager::with_provider(auth_manager, provider_for("http://example.test".into()))
+        };
+
+        let first = new_manager().listed_revision(&config).await;
+        assert_eq!(
+            new_manager().listed_revision(&config).await,
+            first,
+            "a restarted process listing the same models must report the same revision"
+        );
+
+        config.effort_descriptions =
+            HashMap::from([(ReasoningEffort::High, "Shared wording".to_string())]);
+        assert_ne!(
+            new_manager().listed_revision(&config).await,
+            first,
+            "a config change that rewords the presets must change the revision"
+        );
+    }
+
+    #[tokio::test]
+    async fn listing_prunes_the_state_of_models_no_catalog_lists() {
+        let codex_home = tempdir().expect("temp dir");
+        let retired_at = Utc::now() - chrono::Duration::days(120);
+        std::fs::write(
+            codex_home.path().join("models_last_listed.json"),
+            serde_json::json!({"last_listed": {"oca-retired": retired_at}}).to_string(),
+        )
+        .expect("write last listed");
+        std::fs::write(
+            codex_home.path().join("models_seen.json"),
+            serde_json::json!({"version": 1, "ids": ["oca-retired"]}).to_string(),
+        )
+        .expect("write seen models");
+        let config = status_test_config(codex_home.path()).await;
+        let manager = ModelsManager::with_provider(
+            AuthManager::from_auth_for_testing_with_home(
+                CodexAuth::from_api_key("Test API Key"),
+                codex_home.path().to_path_buf(),
+            ),
+            provider_for("http://example.test".into()),
+        );
+
+        // No catalog handle is involved: the plain listing runs the pass.
+        manager.list_models(&config).await;
+
+        let seen: serde_json::Value = serde_json::from_slice(
+            &std::fs::read(codex_home.path().join("models_seen.json")).expect("read seen"),
+        )
+        .expect("json");
+        assert_eq!(seen["ids"], serde_json::json!([]));
+    }
+
+    #[tokio::test]
+    async fn catalog_revision_bumps_only_when_models_change() {
+        let server = MockServer::start().await;
+        let codex_home = tempdir().expect("temp dir");
+        let mut config = ConfigBuilder::default()
+            .codex_home(codex_home.path().to_path_buf())
+            .build()
+            .await
+            .expect("load default test config");
+        config.features.enable(Feature::RemoteModels);
+        let auth_manager =
+            AuthManager::from_auth_for_testing(CodexAuth::create_dummy_chatgpt_auth_for_testing());
+        let mut manager = ModelsManager::with_provider(auth_manager, provider_for(server.uri()));
+        manager.cache_ttl = Duration::ZERO;
+        config.model_cache_max_staleness = Duration::ZERO;
+        let mut revisions = manager.subscribe_catalog_revision();
+        let initial = manager.catalog_revision();
+
+        let models = vec![remote_model("remote-a", "Remote A", 1)];
+        mount_models_once(
+            &server,
+            ModelsResponse {
+                models: models.clone(),
+                min_client_version: None,
+            },
+        )
+        .await;
+        manager
+            .refresh_available_models_with_cache(&config)
+            .await
+            .expect("first refresh succeeds");
+        let changed = manager.catalog_revision();
+        assert!(changed > initial, "new models must bump the revision");
+        assert!(revisions.has_changed().expect("sender alive"));
+        revisions.mark_unchanged();
+
+        server.reset().await;
+        mount_models_once(
+            &server,
+            ModelsResponse {
+                models,
+                min_client_version: None,
+            },
+        )
+        .await;
+        manager
+            .refresh_available_models_with_cache(&config)
+            .await
+            .expect("identical refresh succeeds");
+        assert_eq!(manager.catalog_revision(), changed);
+        assert!(!revisions.has_changed().expect("sender alive"));
+
+        server.reset().await;
+        mount_models_once(
+            &server,
+            ModelsResponse {
+                models: vec![remote_model("remote-b", "Remote B", 1)],
+                min_client_version: None,
+            },
+        )
+        .await;
+        let listed = manager.model_list(&config).await;
+        assert_eq!(manager.catalog_revision(), changed + 1);
+        assert_eq!(listed.revision, manager.listed_revision(&config).await);
+        assert!(
+            listed
+                .models
+                .iter()
+                .any(|preset| preset.model == "remote-b")
+        );
+        let snapshot = PresetCatalog::new(&manager, &config).snapshot();
+        assert_eq!(snapshot.revision(), manager.catalog_revision());
+        assert_eq!(snapshot.presets(), listed.models);
+    }
+
+    /// The events `receiver` holds right now.
+    fn received_refresh_events(
+        receiver: &mut broadcast::Receiver<CatalogRefreshEvent>,
+    ) -> Vec<CatalogRefreshEvent> {
+        std::iter::from_fn(|| receiver.try_recv().ok()).collect()
+    }
+
+    #[tokio::test]
+    async fn successful_refreshes_are_reported_in_order() {
+        let server = MockServer::start().await;
+        let codex_home = tempdir().expect("temp dir");
+        let mut config = ConfigBuilder::default()
+            .codex_home(codex_home.path().to_path_buf())
+            .build()
+            .await
+            .expect("load default test config");
+        config.features.enable(Feature::RemoteModels);
+        let auth_manager =
+            AuthManager::from_auth_for_testing(CodexAuth::create_dummy_chatgpt_auth_for_testing());
+        let manager = ModelsManager::with_provider(auth_manager, provider_for(server.uri()));
+        let mut events = manager.subscribe_refresh_events();
+        // The first fetch replaces the catalog bundled with the binary.
+        let mut bundled: Vec<String> = ModelsManager::load_remote_models_from_file()
+            .expect("bundled catalog parses")
+            .into_iter()
+            .map(|model| model.slug)
+            .collect();
+        bundled.sort();
+
+        mount_models_once(
+            &server,
+            ModelsResponse {
+                models: vec![remote_model("remote-a", "Remote A", 1)],
+                min_client_version: None,
+            },
+        )
+        .await;
+        manager
+            .refresh_available_models_with_cache(&config)
+            .await
+            .expect("first refresh succeeds");
+        let first_revision = manager.catalog_revision();
+
+        server.reset().await;
+        mount_models_once(
+            &server,
+            ModelsResponse {
+                models: vec![remote_model("remote-b", "Remote B", 1)],
+                min_client_version: None,
+            },
+        )
+        .await;
+        manager
+            .refresh_all_models(&config)
+            .await
+            .expect("manual refresh succeeds");
+
+        assert_eq!(
+            received_refresh_events(&mut events),
+            vec![
+                CatalogRefreshEvent::RefreshStarted {
+                    reason: RefreshReason::CacheMiss,
+                },
+                CatalogRefreshEvent::RefreshSucceeded {
+                    revision: first_revision,
+                    added: vec!["remote-a".to_string()],
+                    removed: bundled,
+                },
+                CatalogRefreshEvent::RefreshStarted {
+                    reason: RefreshReason::Manual,
+                },
+                CatalogRefreshEvent::DefaultModelChanged {
+                    previous: "remote-a".to_string(),
+                    current: Some("remote-b".to_string()),
+                },
+                CatalogRefreshEvent::RefreshSucceeded {
+                    revision: manager.catalog_revision(),
+                    added: vec!["remote-b".to_string()],
+                    removed: vec!["remote-a".to_string()],
+                },
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn provider_catalog_refreshes_are_reported() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/v1/models"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "object": "list",
+                "data": [{"id": "oca-large", "object": "model"}],
+            })))
+            .up_to_n_times(1)
+            .mount(&server)
+            .await;
+        let codex_home = tempdir().expect("temp dir");
+        let mut config = status_test_config(codex_home.path()).await;
+        config.model_providers.insert(
+            "gateway".to_string(),
+            ModelProviderInfo {
+                model_catalog: Some(ModelCatalog::Openai),
+                ..provider_for(format!("{}/v1", server.uri()))
+            },
+        );
+        let auth_manager =
+            AuthManager::from_auth_for_testing(CodexAuth::from_api_key("Test API Key"));
+        let manager =
+            ModelsManager::with_provider(auth_manager, provider_for("http://example.test".into()));
+        let mut events = manager.subscribe_refresh_events();
+
+        manager.refresh_provider_models(&config).await;
+        assert_eq!(
+            received_refresh_events(&mut events),
+            vec![
+                CatalogRefreshEvent::RefreshStarted {
+                    reason: RefreshReason::Relist,
+                },
+                CatalogRefreshEvent::RefreshSucceeded {
+                    revision: manager.catalog_revision(),
+                    added: vec!["gateway/oca-large".to_string()],
+                    removed: Vec::new(),
+                },
+            ]
+        );
+
+        // The mock answered once, so the relist fails.
+        manager.refresh_provider_models(&config).await;
+        let events = received_refresh_events(&mut events);
+        assert_eq!(
+            events.first(),
+            Some(&CatalogRefreshEvent::RefreshStarted {
+                reason: RefreshReason::Relist,
+            })
+        );
+        assert!(
+            matches!(
+                events.as_slice(),
+                [_, CatalogRefreshEvent::RefreshFailed { error }]
+                    if error.starts_with("provider gateway: ")
+            ),
+            "unexpected events: {events:?}"
+        );
+    }
+
+    async fn listed_default(manager: &ModelsManager, config: &Config) -> Option<String> {
+        manager
+            .list_models(config)
+            .await
+            .into_iter()
+            .find(|preset| preset.is_default)
+            .map(|preset| preset.model)
+    }
+
+    async fn serve_catalog(server: &MockServer, models: Vec<ModelInfo>) {
+        server.reset().await;
+        mount_models_once(
+            server,
+            ModelsResponse {
+                models,
+                min_client_version: None,
+            },
+        )
+        .await;
+    }
+
+    #[test]
+    fn a_priority_tie_picks_the_same_default_in_any_order() {
+        let models = vec![
+            remote_model("zeta", "Zeta", 0),
+            remote_model("alpha", "Alpha", 0),
+            remote_model("later", "Later", 1),
+        ];
+        let mut reversed = models.clone();
+        reversed.reverse();
+        assert_eq!(sticky_default(None, &models), Some("alpha".to_string()));
+        assert_eq!(sticky_default(None, &reversed), Some("alpha".to_string()));
+    }
+
+    #[tokio::test]
+    async fn reordering_the_catalog_keeps_its_default() {
+        let server = MockServer::start().await;
+        let codex_home = tempdir().expect("temp dir");
+        let config = status_test_config(codex_home.path()).await;
+        let auth_manager =
+            AuthManager::from_auth_for_testing(CodexAuth::create_dummy_chatgpt_auth_for_testing());
+        let manager =
+            ModelsManager::with_provider(Arc::clone(&auth_manager), provider_for(server.uri()));
+
+        serve_catalog(
+            &server,
+            vec![remote_model("a", "A", 0), remote_model("b", "B", 0)],
+        )
+        .await;
+        manager
+            .refresh_available_models_with_cache(&config)
+            .await
+            .expect("first refresh succeeds");
+        assert_eq!(
+            listed_default(&manager, &config).await,
+            Some("a".to_string())
+        );
+
+        let mut events = manager.subscribe_refresh_events();
+        serve_catalog(
+            &server,
+            vec![remote_model("b", "B", 0), remote_model("a", "A", 0)],
+        )
+        .await;
+        manager
+            .refresh_all_models(&config)
+            .await
+            .expect("reordered refresh succeeds");
+        assert_eq!(
+            listed_default(&manager, &config).await,
+            Some("a".to_string())
+        );
+        assert!(
+            !received_refresh_events(&mut events)
+                .iter()
+                .any(|event| matches!(event, CatalogRefreshEvent::DefaultModelChanged { .. })),
+            "a reorder is not a change of default"
+        );
+
+        // A new process reading the reordered cache keeps the default too.
+        let restarted = ModelsManager::with_provider(auth_manager, provider_for(server.uri()));
+        assert_eq!(
+            listed_default(&restarted, &config).await,
+            Some("a".to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn the_default_moves_when_removed_or_relabeled() {
+        let server = MockServer::start().await;
+        let codex_home = tempdir().expect("temp dir");
+        let config = status_test_config(codex_home.path()).await;
+        let auth_manager =
+            AuthManager::from_auth_for_testing(CodexAuth::create_dummy_chatgpt_auth_for_testing());
+        let manager = ModelsManager::with_provider(auth_manager, provider_for(server.uri()));
+        serve_catalog(
+            &server,
+            vec![remote_model("a", "A", 0), remote_model("b", "B", 1)],
+        )
+        .await;
+        manager
+            .refresh_available_models_with_cache(&config)
+            .await
+            .expect("first refresh succeeds");
+        let mut events = manager.subscribe_refresh_events();
+
+        serve_catalog(&server, vec![remote_model("b", "B", 1)]).await;
+        manager
+            .refresh_all_models(&config)
+            .await
+            .expect("refresh without `a` succeeds");
+        assert_eq!(
+            listed_default(&manager, &config).await,
+            Some("b".to_string())
+        );
+
+        let labeled = ModelInfo {
+            is_default: true,
+            ..remote_model("c", "C", 2)
+        };
+        serve_catalog(&server, vec![remote_model("b", "B", 1), labeled]).await;
+        manager
+            .refresh_all_models(&config)
+            .await
+            .expect("refresh labeling `c` succeeds");
+        assert_eq!(
+            listed_default(&manager, &config).await,
+            Some("c".to_string())
+        );
+
+        let changes: Vec<CatalogRefreshEvent> = received_refresh_events(&mut events)
+            .into_iter()
+            .filter(|event| matches!(event, CatalogRefreshEvent::DefaultModelChanged { .. }))
+            .collect();
+        assert_eq!(
+            changes,
+            vec![
+                CatalogRefreshEvent::DefaultModelChanged {
+                    previous: "a".to_string(),
+                    current: Some("b".to_string()),
+                },
+                CatalogRefreshEvent::DefaultModelChanged {
+                    previous: "b".to_string(),
+                    current: Some("c".to_string()),
+                },
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn failed_refreshes_are_reported_in_order() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/models"))
+            .respond_with(ResponseTemplate::new(500))
+            .mount(&server)
+            .await;
+        let codex_home = tempdir().expect("temp dir");
+        let config = status_test_config(codex_home.path()).await;
+        let auth_manager =
+            AuthManager::from_auth_for_testing(CodexAuth::create_dummy_chatgpt_auth_for_testing());
+        let manager = ModelsManager::with_provider(auth_manager, provider_for(server.uri()));
+        let mut events = manager.subscribe_refresh_events();
+
+        let err = manager
+            .refresh_available_models_with_cache(&config)
+            .await
+            .expect_err("the catalog is unavailable");
+
+        assert_eq!(
+            received_refresh_events(&mut events),
+            vec![
+                CatalogRefreshEvent::RefreshStarted {
+                    reason: RefreshReason::CacheMiss,
+                },
+                CatalogRefreshEvent::RefreshFailed {
+                    error: err.to_string(),
+                },
+            ]
+        );
+    }
+
+    fn generation_presets(generation: usize) -> Vec<ModelPreset> {
+        (0..3)
+            .map(|index| {
+                ModelPreset::builder(
+                    format!("gw/gen-{generation}-{index}"),
+                    format!("gen-{generation}-{index}"),
+                )
+                .model_provider("gw".to_string())
+                .build()
+                .expect("valid preset")
+            })
+            .collect()
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn snapshot_readers_never_see_a_partial_refresh() {
+        let codex_home = tempdir().expect("temp dir");
+        let config = Arc::new(
+            ConfigBuilder::default()
+                .codex_home(codex_home.path().to_path_buf())
+                .build()
+                .await
+                .expect("load default test config"),
+        );
+        let manager = Arc::new(manager_with_local_models(Vec::new()));
+        let stop = Arc::new(std::sync::atomic::AtomicBool::new(false));
+
+        let readers: Vec<_> = (0..4)
+            .map(|_| {
+                let manager = Arc::clone(&manager);
+                let config = Arc::clone(&config);
+                let stop = Arc::clone(&stop);
+                std::thread::spawn(move || {
+                    let mut last_revision = 0;
+                    let mut reads = 0;
+                    while !stop.load(std::sync::atomic::Ordering::Relaxed) {
+                        let snapshot = PresetCatalog::new(&manager, &config).snapshot();
+                        assert!(
+                            snapshot.revision() >= last_revision,
+                            "revision went back from {last_revision} to {}",
+                            snapshot.revision()
+                        );
+                        last_revision = snapshot.revision();
+                        let generations: HashSet<&str> = snapshot
+                            .presets()
+                            .iter()
+                            .filter_map(|preset| preset.model.rsplit_once('-'))
+                            .map(|(generation, _)| generation)
+                            .collect();
+                        assert!(
+                            snapshot.presets().is_empty()
+                                || (snapshot.presets().len() == 3 && generations.len() == 1),
+                            "mixed snapshot: {:?}",
+                            snapshot.presets()
+                        );
+                        reads += 1;
+                    }
+                    reads
+                })
+            })
+            .collect();
+
+        for generation in 1..=200 {
+            *manager.provider_models.write().await = generation_presets(generation);
+            manager.bump_revision();
+            tokio::task::yield_now().await;
+        }
+        stop.store(true, std::sync::atomic::Ordering::Relaxed);
+        for reader in readers {
+            let reads = reader.join().expect("reader thread panicked");
+            assert!(reads > 0);
+        }
+
+        let snapshot = PresetCatalog::new(&manager, &config).snapshot();
+        assert_eq!(snapshot.revision(), manager.catalog_revision());
+        let ids: Vec<&str> = snapshot
+            .presets()
+            .iter()
+            .map(|preset| preset.id.as_str())
+            .collect();
+        assert_eq!(ids, vec!["gw/gen-200-0", "gw/gen-200-1", "gw/gen-200-2"]);
+    }
+
+    #[tokio::test]
+    async fn catalog_auth_follows_the_selected_provider() {
+        let codex_home = tempdir().expect("temp dir");
+        let mut config = ConfigBuilder::default()
+            .codex_home(codex_home.path().to_path_buf())
+            .build()
+            .await
+            .expect("load default test config");
+        config.offline = true;
+        let auth = CodexAuth::create_dummy_chatgpt_auth_for_testing();
+        let account = auth.get_account_email();
+        let auth_manager = AuthManager::from_auth_for_testing(auth);
+        let manager = ModelsManager::with_provider(
+            auth_manager,
+            provider_for("https://models.example.test/v1".to_string()),
+        );
+
+        let chatgpt = manager.model_list(&config).await;
+        assert_eq!(
+            chatgpt.auth,
+            CatalogAuth {
+                provider: "mock".to_string(),
+                host: Some("models.example.test".to_string()),
+                mode: Some(AuthMode::ChatGPT),
+                credential: Some(CredentialSource::Stored),
+                account,
+            }
+        );
+
+        let mut gateway = provider_for("http://127.0.0.1:9/v1".to_string());
+        gateway.name = "Gateway".to_string();
+        gateway.experimental_bearer_token = Some("token".to_string());
+        gateway.model_catalog = Some(ModelCatalog::None);
+        gateway.models = Some(vec!["oca-large".to_string()]);
+        config.model_provider = gateway;
+
+        let switched = manager.model_list(&config).await;
+        assert_eq!(
+            switched.auth,
+            CatalogAuth {
+                provider: "Gateway".to_string(),
+                host: Some("127.0.0.1:9".to_string()),
+                mode: Some(AuthMode::ApiKey),
+                credential: Some(CredentialSource::Config),
+                account: None,
+            }
+        );
+        assert_ne!(
+            switched.revision, chatgpt.revision,
+            "switching credentials must invalidate cached listings"
+        );
+        assert_eq!(
+            manager.model_list(&config).await.revision,
+            switched.revision
+        );
+    }
+
+    #[test]
+    fn build_available_models_picks_default_after_hiding_hidden_models() {
+        let auth_manager =
+            AuthManager::from_auth_for_testing(CodexAuth::from_api_key("Test API Key"));
+        let provider = provider_for("http://example.test".to_string());
+        let mut manager = ModelsManager::with_provider(auth_manager, provider);
+        manager.local_models = Vec::new();
+
+        let hidden_model = remote_model_with_visibility("hidden", "Hidden", 0, "hide");
+        let visible_model = remote_model_with_visibility("visible", "Visible", 1, "list");
+
+        let mut expected = ModelPreset::from(visible_model.clone());
+        expected.is_default = true;
+
+        let available = manager.build_available_models(
+            &ModelCatalogSource::DEFAULT_ORDER,
+            None,
+            vec![hidden_model, visible_model],
+            &[],
+        );
+
+        assert_eq!(available, vec![expected]);
+    }
+
+    fn default_ids(models: &[ModelPreset]) -> Vec<&str> {
+        models
+            .iter()
+            .filter(|preset| preset.is_default)
+            .map(|preset| preset.id.as_str())
+            .collect()
+    }
+
+    fn manager_with_local_models(local_models: Vec<ModelPreset>) -> ModelsManager {
+        let auth_manager =
+            AuthManager::from_auth_for_testing(CodexAuth::from_api_key("Test API Key"));
+        let provider = provider_for("http://example.test".to_string());
+        let mut manager = ModelsManager::with_provider(auth_manager, provider);
+        manager.local_models = local_models;
+        manager
+    }
+
+    fn local_preset(slug: &str, is_default: bool) -> ModelPreset {
+        let mut preset = ModelPreset::from(remote_model(slug, slug, 0));
+        preset.is_default = is_default;
+        preset
+    }
+
+    #[test]
+    fn a_catalog_without_a_default_gets_one() {
+        let manager =
+            manager_with_local_models(vec![local_preset("a", false), local_preset("b", false)]);
+
+        let available =
+            manager.build_available_models(&[ModelCatalogSource::Builtin], None, Vec::new(), &[]);
+
+        assert_eq!(default_ids(&available), vec!["a"]);
+    }
+
+    #[test]
+    fn only_the_first_of_several_defaults_is_kept() {
+        let manager = manager_with_local_models(vec![
+            local_preset("a", false),
+            local_preset("b", true),
+            local_preset("c", true),
+        ]);
+
+        let available =
+            manager.build_available_models(&[ModelCatalogSource::Builtin], None, Vec::new(), &[]);
+
+        assert_eq!(default_ids(&available), vec!["b"]);
+    }
+
+    #[test]
+    fn hidden_and_provider_defaults_give_way_to_a_listed_preset() {
+        let manager = manager_with_local_models(Vec::new());
+        let mut hidden = local_preset("hidden", true);
+        hidden.show_in_picker = false;
+        let mut served = local_preset("served", true);
+        served.id = "gateway/served".to_string();
+        served.model_provider = Some("gateway".to_string());
+        let mut models = vec![hidden, served, local_preset("listed", false)];
+
+        manager.enforce_single_default(&mut models);
+
+        assert_eq!(default_ids(&models), vec!["listed"]);
+    }
+
+    #[test]
+    fn catalog_order_decides_which_duplicate_wins() {
+        let mut builtin = builtin_model_presets(None).remove(0);
+        builtin.description = "built-in".to_string();
+        builtin.is_default = true;
+        let mut remote = builtin.clone();
+        remote.description = "remote".to_string();
+        remote.is_default = false;
+        let mut provider = builtin.clone();
+        provider.id = format!("gateway/{}", builtin.model);
+        provider.description = "provider".to_string();
+        provider.model_provider = Some("gateway".to_string());
+        provider.is_default = false;
+        let merged = |sources: &[ModelCatalogSource]| {
+            ModelsManager::merge_presets(
+                sources,
+                vec![remote.clone()],
+                vec![builtin.clone()],
+                vec![provider.clone()],
+            )
+            .into_iter()
+            .map(|preset| (preset.description, preset.is_default))
+            .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            merged(&ModelCatalogSource::DEFAULT_ORDER),
+            vec![
+                ("remote".to_string(), false),
+                ("provider".to_string(), false),
+            ]
+        );
+        assert_eq!(
+            merged(&[
+                ModelCatalogSource::Providers,
+                ModelCatalogSource::Builtin,
+                ModelCatalogSource::Remote,
+            ]),
+            vec![
+                ("provider".to_string(), false),
+                ("built-in".to_string(), false),
+            ]
+        );
+        assert_eq!(
+            merged(&[ModelCatalogSource::Builtin]),
+            vec![("built-in".to_string(), true)]
+        );
+    }
+
+    async fn mount_openai_catalog(server: &MockServer, ids: &[&str]) {
+        let data: Vec<serde_json::Value> = ids
+            .iter()
+            .map(|id| json!({"id": id, "object": "model"}))
+            .collect();
+        Mock::given(method("GET"))
+            .and(path("/models"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(json!({"object": "list", "data": data})),
+            )
+            .expect(1)
+            .mount(server)
+            .await;
+    }
+
+    #[tokio::test]
+    async fn list_models_includes_models_from_each_catalog_provider() {
+        let lmstudio = MockServer::start().await;
+        mount_openai_catalog(&lmstudio, &["qwen2.5-coder-7b"]).await;
+        let vllm = MockServer::start().await;
+        mount_openai_catalog(&vllm, &["qwen2.5-coder-7b", "llama-3.1-8b"]).await;
+
+        let codex_home = tempdir().expect("temp dir");
+        let mut config = ConfigBuilder::default()
+            .codex_home(codex_home.path().to_path_buf())
+            .build()
+            .await
+            .expect("load default test config");
+        config.model_providers.insert(
+            "lmstudio-local".to_string(),
+            ModelProviderInfo {
+                name: "LM Studio".into(),
+                model_catalog: Some(ModelCatalog::Openai),
+                ..provider_for(lmstudio.uri())
+            },
+        );
+        config.model_providers.insert(
+            "vllm".to_string(),
+            ModelProviderInfo {
+                name: "vLLM".into(),
+                model_catalog: Some(ModelCatalog::Openai),
+                ..provider_for(vllm.uri())
+            },
+        );
+        let auth_manager =
+            AuthManager::from_auth_for_testing(CodexAuth::from_api_key("Test API Key"));
+        let manager =
+            ModelsManager::with_provider(auth_manager, provider_for("http://example.test".into()));
+
+        let provider_models: Vec<ModelPreset> = manager
+            .list_models(&config)
+            .await
+            .into_iter()
+            .filter(|preset| preset.model_provider.is_some())
+            .collect();
+
+        let lmstudio_provider = &config.model_providers["lmstudio-local"];
+        let vllm_provider = &config.model_providers["vllm"];
+        let expected: Vec<ModelPreset> = [
+            ("lmstudio-local", lmstudio_provider, "qwen2.5-coder-7b"),
+            ("vllm", vllm_provider, "qwen2.5-coder-7b"),
+            ("vllm", vllm_provider, "llama-3.1-8b"),
+        ]
+        .into_iter()
+        .map(|(provider_id, provider, slug)| {
+            ModelsManager::provider_preset(provider_id, provider, slug.to_string())
+                .expect("valid preset")
+        })
+        .collect();
+        assert_eq!(provider_models, expected);
+        assert_eq!(provider_models[0].id, "lmstudio-local/qwen2.5-coder-7b");
+        assert_eq!(provider_models[1].description, "Served by vLLM");
+        assert_eq!(
+            PresetCatalog::new(&manager, &config)
+                .load_cached_or_static()
+                .expect("models should be available")
+                .into_iter()
+                .filter(|preset| preset.model_provider.is_some())
+                .collect::<Vec<_>>(),
+            provider_models,
+            "the in-memory catalog should serve the cached provider models"
+        );
+    }
+
+    #[tokio::test]
+    async fn failed_provider_refresh_keeps_models_and_warns_until_recovery() {
+        let gateway = MockServer::start().await;
+        let codex_home = tempdir().expect("temp dir");
+        let mut config = ConfigBuilder::default()
+            .codex_home(codex_home.path().to_path_buf())
+            .build()
+            .await
+            .expect("load default test config");
+        config.model_providers.insert(
+            "gateway".to_string(),
+            ModelProviderInfo {
+                model_catalog: Some(ModelCatalog::Openai),
+                ..provider_for(gateway.uri())
+            },
+        );
+        let auth_manager =
+            AuthManager::from_auth_for_testing(CodexAuth::from_api_key("Test API Key"));
+        let manager =
+            ModelsManager::with_provider(auth_manager, provider_for("http://example.test".into()));
+        let gateway_models = |list: &ModelList| -> Vec<String> {
+            list.models
+                .iter()
+                .filter(|preset| preset.model_provider.is_some())
+                .map(|preset| preset.id.clone())
+                .collect()
+        };
+
+        mount_openai_catalog(&gateway, &["oca-large"]).await;
+        let healthy = manager.model_list(&config).await;
+        assert_eq!(gateway_models(&healthy), vec!["gateway/oca-large"]);
+        assert_eq!(healthy.warnings, Vec::new());
+
+        gateway.reset().await;
+        Mock::given(method("GET"))
+            .and(path("/models"))
+            .respond_with(ResponseTemplate::new(503))
+            .mount(&gateway)
+            .await;
+        manager.refresh_provider_models(&config).await;
+        let failing = manager.model_list(&config).await;
+        assert_eq!(
+            gateway_models(&failing),
+            vec!["gateway/oca-large"],
+            "a failed refresh must not drop the models listed before"
+        );
+        assert_eq!(failing.revision, healthy.revision);
+        let [warning] = failing.warnings.as_slice() else {
+            panic!("expected one warning: {:?}", failing.warnings);
+        };
+        assert_eq!(warning.provider_id.as_deref(), Some("gateway"));
+        let fetched_at = warning.fetched_at.expect("first listing succeeded");
+        assert!(fetched_at <= warning.failed_at);
+        assert!(
+            warning
+                .summary(fetched_at + chrono::Duration::hours(3))
+                .starts_with("model list is 3h stale: gateway: "),
+            "unexpected summary: {warning}"
+        );
+        assert_eq!(
+            manager
+                .try_catalog_warnings()
+                .expect("bookkeeping is not locked"),
+            failing.warnings
+        );
+
+        gateway.reset().await;
+        mount_openai_catalog(&gateway, &["oca-large"]).await;
+        manager.refresh_provider_models(&config).await;
+        let recovered = manager.model_list(&config).await;
+        assert_eq!(gateway_models(&recovered), vec!["gateway/oca-large"]);
+        assert_eq!(recovered.warnings, Vec::new());
+    }
+
+    #[tokio::test]
+    async fn list_models_maps_azure_deployments() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/openai/deployments"))
+            .and(query_param("api-version", "2024-10-21"))
+            .and(header("api-key", "azure-test-key"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "object": "list",
+                "data": [{
+                    "id": "gpt4o-prod",
+                    "model": "gpt-4o",
+                    "sku": {"name": "GlobalStandard", "capacity": 50},
+                }],
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let codex_home = tempdir().expect("temp dir");
+        let mut config = ConfigBuilder::default()
+            .codex_home(codex_home.path().to_path_buf())
+            .build()
+            .await
+            .expect("load default test config");
+        config.model_providers.insert(
+            "azure".to_string(),
+            ModelProviderInfo {
+                name: "Azure".into(),
+                experimental_bearer_token: Some("azure-test-key".to_string()),
+                query_params: Some(
+                    [("api-version".to_string(), "2024-10-21".to_string())]
+                        .into_iter()
+                        .collect(),
+                ),
+                model_catalog: Some(ModelCatalog::Azure),
+                ..provider_for(format!("{}/openai", server.uri()))
+            },
+        );
+        let auth_manager =
+            AuthManager::from_auth_for_testing(CodexAuth::from_api_key("Test API Key"));
+        let manager =
+            ModelsManager::with_provider(auth_manager, provider_for("http://example.test".into()));
+
+        let provider_models: Vec<ModelPreset> = manager
+            .list_models(&config)
+            .await
+            .into_iter()
+            .filter(|preset| preset.model_provider.is_some())
+            .collect();
+
+        assert_eq!(
+            provider_models,
+            vec![
+                ModelPreset::builder("azure/gpt4o-prod", "gpt4o-prod")
+                    .display_name("gpt4o-prod")
+                    .description("Azure deployment of gpt-4o, GlobalStandard, capacity 50")
+                    .model_provider("azure".to_string())
+                    .upstream_model("gpt-4o".to_string())
+                    .supports_temperature(true)
+                    .supports_top_p(true)
+                    .build()
+                    .expect("valid preset")
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn refresh_fails_fast_when_models_endpoint_unreachable() {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").expect("bind");
+        let addr = listener.local_addr().expect("local addr");
+        drop(listener);
+
+        let codex_home = tempdir().expect("temp dir");
+        let mut config = ConfigBuilder::default()
+            .codex_home(codex_home.path().to_path_buf())
+            .build()
+            .await
+            .expect("load default test config");
+        config.features.enable(Feature::RemoteModels);
+        let auth_manager =
+            AuthManager::from_auth_for_testing(CodexAuth::create_dummy_chatgpt_auth_for_testing());
+        let manager =
+            ModelsManager::with_provider(auth_manager, provider_for(format!("http://{addr}")));
+        let bundled = manager.remote_models(&config).await;
+
+        let started = std::time::Instant::now();
+        let result = manager.refresh_available_models_with_cache(&config).await;
+
+        assert!(result.is_err(), "unreachable endpoint should fail");
+        assert!(started.elapsed() < PREFLIGHT_TIMEOUT + Duration::from_secs(1));
+        assert_eq!(
+            manager.remote_models(&config).await,
+            bundled,
+            "bundled models should remain available after a failed refresh"
+        );
+    }
+
+    async fn status_test_config(codex_home: &std::path::Path) -> Config {
+        let mut config = ConfigBuilder::default()
+            .codex_home(codex_home.to_path_buf())
+            .build()
+            .await
+            .expect("load default test config");
+        config.features.enable(Feature::RemoteModels);
+        config
+    }
+
+    fn remote_source(status: &CatalogStatus, id: &str) -> PresetSource {
+        status
+            .presets
+            .iter()
+            .find(|preset| preset.id == id)
+            .map(|preset| preset.source.clone())
+            .expect("preset should be listed")
+    }
+
+    #[tokio::test]
+    async fn catalog_status_reports_bundled_catalog_when_fetch_is_skipped() {
+        let codex_home = tempdir().expect("temp dir");
+        let config = status_test_config(codex_home.path()).await;
+        let auth_manager =
+            AuthManager::from_auth_for_testing(CodexAuth::from_api_key("Test API Key"));
+        let manager =
+            ModelsManager::with_provider(auth_manager, provider_for("http://example.test".into()));
+
+        manager.list_models(&config).await;
+        let status = manager.catalog_status(&config).await;
+
+        assert_eq!(status.remote, FetchRecord::default());
+        assert_eq!(status.providers, Vec::new());
+        assert!(
+            status.presets.iter().all(|preset| matches!(
+                preset.source,
+                PresetSource::Builtin
+                    | PresetSource::Remote {
+                        source: CatalogSource::Bundled
+                    }
+            )),
+            "nothing should come from the cache or network: {status:?}"
+        );
+    }
+
+    #[tokio::test]
+    async fn an_imported_export_replaces_every_catalog() {
+        let exporting_home = tempdir().expect("temp dir");
+        let config = status_test_config(exporting_home.path()).await;
+        let manager = ModelsManager::with_provider(
+            AuthManager::from_auth_for_testing(CodexAuth::from_api_key("Test API Key")),
+            provider_for("http://example.test".into()),
+        );
+        let mut export = manager.export_catalog(&config).await;
+        assert_eq!(export.presets, manager.list_all_models(&config).await);
+        assert_eq!(export.schema_version, CATALOG_EXPORT_SCHEMA_VERSION);
+        assert_eq!(manager.imported_catalog(&config), None);
+
+        // Keep one model so the import cannot be mistaken for the built-ins.
+        export.presets.retain(|preset| preset.is_default);
+        let importing_home = tempdir().expect("temp dir");
+        let (export, _) = export.scoped_to(&config.model_provider_id);
+        let path = install_imported_catalog(importing_home.path(), &export)
+            .await
+            .expect("install");
+        let config = status_test_config(importing_home.path()).await;
+        let manager = ModelsManager::with_provider(
+            AuthManager::from_auth_for_testing_with_home(
+                CodexAuth::from_api_key("Test API Key"),
+                importing_home.path().to_path_buf(),
+            ),
+            provider_for("http://example.test".into()),
+        );
+
+        assert_eq!(manager.list_all_models(&config).await, export.presets);
+        let status = manager.catalog_status(&config).await;
+        assert_eq!(status.imported, Some(export.info(&path)));
+        assert_eq!(status.presets, export.provenance);
+        assert_eq!(
+            PresetCatalog::new(&manager, &config)
+                .snapshot()
+                .status()
+                .imported,
+            Some(export.info(&path))
+        );
+
+        let mut other_provider = config.clone();
+        other_provider.model_provider_id = "gateway".to_string();
+        assert_eq!(manager.imported_catalog(&other_provider), None);
+        assert_ne!(
+            manager.list_all_models(&other_provider).await,
+            export.presets
+        );
+    }
+
+    #[tokio::test]
+    async fn configured_effort_descriptions_replace_preset_descriptions() {
+        let codex_home = tempdir().expect("temp dir");
+        let mut config = status_test_config(codex_home.path()).await;
+        let auth_manager =
+            AuthManager::from_auth_for_testing(CodexAuth::from_api_key("Test API Key"));
+        let manager =
+            ModelsManager::with_provider(auth_manager, provider_for("http://example.test".into()));
+        let builtin = manager.list_models(&config).await;
+        let builtin_family = manager
+            .construct_model_family("gpt-5.1-codex-max", &config)
+            .await;
+
+        config.effort_descriptions = HashMap::from([(
+            ReasoningEffort::High,
+            "Réflexion approfondie pour les problèmes complexes".to_string(),
+        )]);
+        let overridden = manager.list_models(&config).await;
+
+        let expected: Vec<ModelPreset> = builtin
+            .into_iter()
+            .map(|mut preset| {
+                for effort in &mut preset.supported_reasoning_efforts {
+                    if effort.effort == ReasoningEffort::High {
+                        effort.description =
+                            "Réflexion approfondie pour les problèmes complexes".to_string();
+                    }
+                }
+                preset
+            })
+            .collect();
+        assert_eq!(overridden, expected);
+
+        let family = manager
+            .construct_model_family("gpt-5.1-codex-max", &config)
+            .await;
+        assert_eq!(
+            family.effort_description(Some(ReasoningEffort::High)),
+            Some("Réflexion approfondie pour les problèmes complexes")
+        );
+        assert_eq!(
+            family.effort_description(Some(ReasoningEffort::Low)),
+            builtin_family.effort_description(Some(ReasoningEffort::Low))
+        );
+    }
+
+    #[tokio::test]
+    async fn model_preset_overrides_replace_wording_on_top_of_any_source() {
+        let codex_home = tempdir().expect("temp dir");
+        let mut config = status_test_config(codex_home.path()).await;
+        let auth_manager =
+            AuthManager::from_auth_for_testing(CodexAuth::from_api_key("Test API Key"));
+        let manager =
+            ModelsManager::with_provider(auth_manager, provider_for("http://example.test".into()));
+        *manager.provider_models.write().await = vec![
+            ModelPreset::builder("gw/qwen-coder", "qwen-coder")
+                .model_provider("gw".to_string())
+                .build()
+                .expect("valid preset"),
+        ];
+        let before = manager.known_models(&config).await;
+
+        config.effort_descriptions =
+            HashMap::from([(ReasoningEffort::High, "Shared wording".to_string())]);
+        config.model_preset_overrides = HashMap::from([
+            (
+                "gpt-5.1-codex-max".to_string(),
+                ModelPresetOverride {
+                    display_name: Some("Codex Max".to_string()),
+                    description: None,
+                    effort_descriptions: BTreeMap::from([
+                        ("LOW".to_string(), "fast".to_string()),
+                        ("high".to_string(), "deep".to_string()),
+                    ]),
+                    disabled_tools: Vec::new(),
+                    model_provider: None,
+                },
+            ),
+            (
+                "gw/qwen-coder".to_string(),
+                ModelPresetOverride {
+                    display_name: None,
+                    description: Some("Our standard local model".to_string()),
+                    effort_descriptions: BTreeMap::new(),
+                    disabled_tools: Vec::new(),
+                    model_provider: None,
+                },
+            ),
+        ]);
+        let after = manager.known_models(&config).await;
+
+        let find = |presets: &[ModelPreset], id: &str| {
+            presets
+                .iter()
+                .find(|preset| preset.id == id)
+                .cloned()
+                .expect("preset listed")
+        };
+        let max_before = find(&before, "gpt-5.1-codex-max");
+        let max_after = find(&after, "gpt-5.1-codex-max");
+        assert_eq!(max_after.display_name, "Codex Max");
+        assert_eq!(max_after.description, max_before.description);
+        let efforts: Vec<(ReasoningEffort, String)> = max_after
+            .supported_reasoning_efforts
+            .iter()
+            .map(|effort| (effort.effort, effort.description.clone()))
+            .collect();
+        let expected: Vec<(ReasoningEffort, String)> = max_before
+            .supported_reasoning_efforts
+            .iter()
+            .map(|effort| {
+                let description = match effort.effort {
+                    ReasoningEffort::Low => "fast".to_string(),
+                    ReasoningEffort::High => "deep".to_string(),
+                    _ => effort.description.clone(),
+                };
+                (effort.effort, description)
+            })
+            .collect();
+        assert_eq!(
+            efforts, expected,
+            "the preset's wording wins over the shared one"
+        );
+        assert_eq!(
+            find(&after, "gw/qwen-coder").description,
+            "Our standard local model"
+        );
+
+        let family = manager
+            .construct_model_family("gpt-5.1-codex-max", &config)
+            .await;
+        assert_eq!(
+            family.effort_description(Some(ReasoningEffort::High)),
+            Some("deep")
+        );
+
+        let overridden: Vec<String> = manager
+            .catalog_status(&config)
+            .await
+            .presets
+            .into_iter()
+            .filter(|preset| preset.overridden)
+            .map(|preset| preset.id)
+            .collect();
+        assert_eq!(
+            overridden,
+            vec!["gpt-5.1-codex-max".to_string(), "gw/qwen-coder".to_string()]
+        );
+        assert_eq!(
+            manager.preset_override_warnings(&config).await,
+            Vec::<String>::new()
+        );
+    }
+
+    #[tokio::test]
+    async fn model_preset_overrides_for_unknown_presets_and_efforts_only_warn() {
+        let codex_home = tempdir().expect("temp dir");
+        let mut config = status_test_config(codex_home.path()).await;
+        let auth_manager =
+            AuthManager::from_auth_for_testing(CodexAuth::from_api_key("Test API Key"));
+        let manager =
+            ModelsManager::with_provider(auth_manager, provider_for("http://example.test".into()));
+        let before = manager.list_models(&config).await;
+        config.model_preset_overrides = HashMap::from([
+            (
+                "gpt-4-turbo-internal".to_string(),
+                ModelPresetOverride {
+                    display_name: Some("Legacy".to_string()),
+                    ..Default::default()
+                },
+            ),
+            (
+                "gpt-5.1-codex-max".to_string(),
+                ModelPresetOverride {
+                    effort_descriptions: BTreeMap::from([
+                        ("none".to_string(), "instant".to_string()),
+                        ("turbo".to_string(), "fastest".to_string()),
+                    ]),
+                    ..Default::default()
+                },
+            ),
+        ]);
+
+        assert_eq!(
+            manager.preset_override_warnings(&config).await,
+            vec![
+                "`model_preset_overrides.\"gpt-4-turbo-internal\"` names no preset in the model catalog, so it is ignored.".to_string(),
+                "`gpt-5.1-codex-max` does not offer the `none` reasoning effort, so `model_preset_overrides.\"gpt-5.1-codex-max\".effort_descriptions.none` is ignored.".to_string(),
+                "`model_preset_overrides.\"gpt-5.1-codex-max\".effort_descriptions.turbo` is not a reasoning effort, so it is ignored.".to_string(),
+            ]
+        );
+        assert_eq!(
+            manager.list_models(&config).await,
+            before,
+            "entries that match nothing change nothing"
+        );
+    }
+
+    /// The session default and the listed default for `model` when signed in
+    /// with `auth`.
+    async fn default_efforts(
+        auth: CodexAuth,
+        config: &Config,
+        model: &str,
+    ) -> (Option<ReasoningEffort>, Option<ReasoningEffort>) {
+        let manager = ModelsManager::with_provider(
+            AuthManager::from_auth_for_testing(auth),
+            provider_for("http://example.test".into()),
+        );
+        let family = manager.construct_model_family(model, config).await;
+        let listed = manager
+            .list_all_models(config)
+            .await
+            .into_iter()
+            .find(|preset| preset.model == model)
+            .map(|preset| preset.default_reasoning_effort);
+        (family.default_reasoning_effort, listed)
+    }
+
+    #[tokio::test]
+    async fn default_effort_follows_the_auth_mode() {
+        let codex_home = tempdir().expect("temp dir");
+        let config = ConfigBuilder::default()
+            .codex_home(codex_home.path().to_path_buf())
+            .build()
+            .await
+            .expect("load default test config");
+
+        assert_eq!(
+            default_efforts(
+                CodexAuth::from_api_key("Test API Key"),
+                &config,
+                "gpt-5-codex"
+            )
+            .await,
+            (Some(ReasoningEffort::Low), Some(ReasoningEffort::Low))
+        );
+        assert_eq!(
+            default_efforts(
+                CodexAuth::create_dummy_chatgpt_auth_for_testing(),
+                &config,
+                "gpt-5-codex"
+            )
+            .await,
+            (Some(ReasoningEffort::Medium), Some(ReasoningEffort::Medium))
+        );
+    }
+
+    #[tokio::test]
+    async fn presets_without_an_auth_mapping_keep_their_default() {
+        let codex_home = tempdir().expect("temp dir");
+        let config = ConfigBuilder::default()
+            .codex_home(codex_home.path().to_path_buf())
+            .build()
+            .await
+            .expect("load default test config");
+        let preset = builtin_presets()
+            .iter()
+            .find(|preset| preset.model == "gpt-5.1-codex-max")
+            .expect("built-in preset");
+        assert_eq!(preset.default_reasoning_effort_by_auth, None);
+
+        for auth in [
+            CodexAuth::from_api_key("Test API Key"),
+            CodexAuth::create_dummy_chatgpt_auth_for_testing(),
+        ] {
+            let (_, listed) = default_efforts(auth, &config, "gpt-5.1-codex-max").await;
+            assert_eq!(listed, Some(preset.default_reasoning_effort));
+        }
+    }
+
+    #[tokio::test]
+    async fn configured_auth_defaults_win_over_the_preset_mapping() {
+        let codex_home = tempdir().expect("temp dir");
+        let mut config = ConfigBuilder::default()
+            .codex_home(codex_home.path().to_path_buf())
+            .build()
+            .await
+            .expect("load default test config");
+        config.preset_overrides = HashMap::from([(
+            "gpt-5-codex".to_string(),
+            PresetOverride {
+                default_effort_by_auth: Some(EffortByAuth {
+                    apikey: Some(ReasoningEffort::High),
+                    chatgpt: None,
+                }),
+                ..Default::default()
+            },
+        )]);
+
+        assert_eq!(
+            default_efforts(
+                CodexAuth::from_api_key("Test API Key"),
+                &config,
+                "gpt-5-codex"
+            )
+            .await,
+            (Some(ReasoningEffort::High), Some(ReasoningEffort::High))
+        );
+        // ChatGPT is left to the preset's own mapping.
+        assert_eq!(
+            default_efforts(
+                CodexAuth::create_dummy_chatgpt_auth_for_testing(),
+                &config,
+                "gpt-5-codex"
+            )
+            .await,
+            (Some(ReasoningEffort::Medium), Some(ReasoningEffort::Medium))
+        );
+    }
+
+    #[tokio::test]
+    async fn configured_effort_limits_hide_efforts_and_move_the_default() {
+        let codex_home = tempdir().expect("temp dir");
+        let mut config = status_test_config(codex_home.path()).await;
+        let auth_manager =
+            AuthManager::from_auth_for_testing(CodexAuth::from_api_key("Test API Key"));
+        let manager =
+            ModelsManager::with_provider(auth_manager, provider_for("http://example.test".into()));
+        let builtin = manager.list_models(&config).await;
+
+        config.preset_overrides = HashMap::from([(
+            "gpt-5.1-codex-max".to_string(),
+            PresetOverride {
+                min_effort: Some(ReasoningEffort::High),
+                max_effort: None,
+                default_effort_by_auth: None,
+            },
+        )]);
+        let limited = manager.list_models(&config).await;
+
+        let efforts = |presets: &[ModelPreset], model: &str| {
+            presets
+                .iter()
+                .find(|preset| preset.model == model)
+                .map(|preset| {
+                    (
+                        preset.default_reasoning_effort,
+                        preset
+                            .supported_reasoning_efforts
+                            .iter()
+                            .map(|effort| effort.effort)
+                            .collect::<Vec<_>>(),
+                    )
+                })
+        };
+        // The picker offers only the efforts in range, and `Auto` resolves to
+        // the lowest of them instead of the preset's `medium`.
+        assert_eq!(
+            efforts(&limited, "gpt-5.1-codex-max"),
+            Some((
+                ReasoningEffort::High,
+                vec![ReasoningEffort::High, ReasoningEffort::XHigh]
+            ))
+        );
+        assert_eq!(
+            efforts(&limited, "gpt-5.1-codex-mini"),
+            efforts(&builtin, "gpt-5.1-codex-mini")
+        );
+    }
+
+    #[tokio::test]
+    async fn compaction_threshold_follows_the_model_on_switch() {
+        let codex_home = tempdir().expect("temp dir");
+        let config = status_test_config(codex_home.path()).await;
+        let auth_manager =
+            AuthManager::from_auth_for_testing(CodexAuth::from_api_key("Test API Key"));
+        let mut manager =
+            ModelsManager::with_provider(auth_manager, provider_for("http://example.test".into()));
+        let template = manager.local_models[0].clone();
+        let preset = |slug: &str, context_window, ratio| {
+            let mut preset = template.clone();
+            preset.id = slug.to_string();
+            preset.model = slug.to_string();
+            preset.context_window = context_window;
+            preset.compaction_threshold_ratio = ratio;
+            preset
+        };
+        manager.local_models = vec![
+            preset("oca-small", Some(32_000), Some(0.5)),
+            preset("oca-large", Some(400_000), None),
+            preset("oca-unsized", None, Some(0.5)),
+        ];
+
+        let mut limits = Vec::new();
+        for model in ["oca-small", "oca-large", "oca-small", "oca-unsized"] {
+            let family = manager.construct_model_family(model, &config).await;
+            limits.push(family.auto_compact_token_limit());
+        }
+
+        assert_eq!(
+            limits,
+            vec![Some(16_000), Some(360_000), Some(16_000), None]
+        );
+    }
+
+    #[tokio::test]
+    async fn reasoning_summary_format_follows_the_model_unless_configured() {
+        let codex_home = tempdir().expect("temp dir");
+        let mut config = status_test_config(codex_home.path()).await;
+        let auth_manager =
+            AuthManager::from_auth_for_testing(CodexAuth::from_api_key("Test API Key"));
+        let mut manager =
+            ModelsManager::with_provider(auth_manager, provider_for("http://example.test".into()));
+        let template = manager.local_models[0].clone();
+        let preset = |slug: &str, format| {
+            let mut preset = template.clone();
+            preset.id = slug.to_string();
+            preset.model = slug.to_string();
+            preset.reasoning_summary_format = format;
+            preset
+        };
+        manager.local_models = vec![
+            preset("oca-plain", Some(ReasoningSummaryFormat::Plain)),
+            preset("oca-unhinted", None),
+        ];
+
+        let mut formats = Vec::new();
+        for model in ["oca-plain", "oca-unhinted", "oca-plain"] {
+            let family = manager.construct_model_family(model, &config).await;
+            formats.push(family.reasoning_summary_format);
+        }
+        assert_eq!(
+            formats,
+            vec![
+                ReasoningSummaryFormat::Plain,
+                ReasoningSummaryFormat::Headed,
+                ReasoningSummaryFormat::Plain,
+            ]
+        );
+
+        // An explicit setting wins over the hint, whichever way it points.
+        config.model_reasoning_summary_format = Some(ReasoningSummaryFormat::Headed);
+        let family = manager.construct_model_family("oca-plain", &config).await;
+        assert_eq!(
+            family.reasoning_summary_format,
+            ReasoningSummaryFormat::Headed
+        );
+        config.model_reasoning_summary_format = Some(ReasoningSummaryFormat::Plain);
+        let family = manager
+            .construct_model_family("oca-unhinted", &config)
+            .await;
+        assert_eq!(
+            family.reasoning_summary_format,
+            ReasoningSummaryFormat::Plain
+        );
+    }
+
+    #[tokio::test]
+    async fn catalog_status_reports_cache_hit() {
+        let codex_home = tempdir().expect("temp dir");
+        let config = status_test_config(codex_home.path()).await;
+        let fetched_at = Utc::now() - chrono::Duration::seconds(30);
+        cache::save_cache(
+            &codex_home.path().join(MODEL_CACHE_FILE),
+            &ModelsCache {
+                fetched_at,
+                etag: None,
+                models: vec![remote_model("cached", "Cached", 1)],
+                locale: None,
+                min_client_version: None,
+                default_model: None,
+            },
+        )
+        .await
+        .expect("cache write succeeds");
+        let auth_manager =
+            AuthManager::from_auth_for_testing(CodexAuth::create_dummy_chatgpt_auth_for_testing());
+        let manager =
+            ModelsManager::with_provider(auth_manager, provider_for("http://example.test".into()));
+
+        manager
+            .refresh_available_models_with_cache(&config)
+            .await
+            .expect("cache hit succeeds");
+        let status = manager.catalog_status(&config).await;
+
+        assert_eq!(
+            status.remote,
+            FetchRecord {
+                source: CatalogSource::DiskCache,
+                fetched_at: Some(fetched_at),
+                host: None,
+                cache: Some(CacheLookup::Hit),
+                last_error: None,
+                last_error_at: None,
+                min_client_version: None,
+                token_attempts: Vec::new(),
+            }
+        );
+        assert_eq!(
+            remote_source(&status, "cached"),
+            PresetSource::Remote {
+                source: CatalogSource::DiskCache
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn catalog_status_reports_live_fetch_after_cache_miss() {
+        let server = MockServer::start().await;
+        mount_models_once(
+            &server,
+            ModelsResponse {
+                models: vec![remote_model("live", "Live", 1)],
+                min_client_version: None,
+            },
+        )
+        .await;
+        let codex_home = tempdir().expect("temp dir");
+        let config = status_test_config(codex_home.path()).await;
+        let auth_manager =
+            AuthManager::from_auth_for_testing(CodexAuth::create_dummy_chatgpt_auth_for_testing());
+        let manager = ModelsManager::with_provider(auth_manager, provider_for(server.uri()));
+
+        manager
+            .refresh_available_models_with_cache(&config)
+            .await
+            .expect("refresh succeeds");
+        let status = manager.catalog_status(&config).await;
+
+        assert!(status.remote.fetched_at.is_some());
+        assert_eq!(
+            status.remote,
+            FetchRecord {
+                source: CatalogSource::Live,
+                fetched_at: status.remote.fetched_at,
+                host: Some(server.address().to_string()),
+                cache: Some(CacheLookup::Miss),
+                last_error: None,
+                last_error_at: None,
+                min_client_version: None,
+                token_attempts: Vec::new(),
+            }
+        );
+        assert_eq!(
+            remote_source(&status, "live"),
+            PresetSource::Remote {
+                source: CatalogSource::Live
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn catalog_status_reports_stale_cache_and_fetch_error() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/models"))
+            .respond_with(ResponseTemplate::new(500))
+            .mount(&server)
+            .await;
+        let codex_home = tempdir().expect("temp dir");
+        let config = status_test_config(codex_home.path()).await;
+        cache::save_cache(
+            &codex_home.path().join(MODEL_CACHE_FILE),
+            &ModelsCache {
+                fetched_at: Utc::now() - chrono::Duration::hours(25),
+                etag: None,
+                models: vec![remote_model("stale", "Stale", 1)],
+                locale: None,
+                min_client_version: None,
+                default_model: None,
+            },
+        )
+        .await
+        .expect("cache write succeeds");
+        let auth_manager =
+            AuthManager::from_auth_for_testing(CodexAuth::create_dummy_chatgpt_auth_for_testing());
+        let manager = ModelsManager::with_provider(auth_manager, provider_for(server.uri()));
+
+        manager.list_models(&config).await;
+        let status = manager.catalog_status(&config).await;
+
+        assert!(status.remote.last_error.is_some());
+        assert_eq!(
+            status.remote,
+            FetchRecord {
+                source: CatalogSource::Bundled,
+                fetched_at: None,
+                host: Some(server.address().to_string()),
+                cache: Some(CacheLookup::Stale),
+                last_error: status.remote.last_error.clone(),
+                last_error_at: status.remote.last_error_at,
+                min_client_version: None,
+                token_attempts: Vec::new(),
+            }
+        );
+        assert!(
+            status.presets.iter().all(|preset| preset.id != "stale"),
+            "stale cache entries should not be served: {status:?}"
+        );
+    }
+
+    #[tokio::test]
+    async fn catalog_status_redacts_provider_credentials() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/v1/models"))
+            .respond_with(
+                ResponseTemplate::new(401)
+                    .set_body_string("invalid credentials: Bearer sk-live-secret-token"),
+            )
+            .mount(&server)
+            .await;
+        let codex_home = tempdir().expect("temp dir");
+        let mut config = status_test_config(codex_home.path()).await;
+        let address = server.address();
+        config.model_providers.insert(
+            "gateway".to_string(),
+            ModelProviderInfo {
+                model_catalog: Some(ModelCatalog::Openai),
+                ..provider_for(format!("http://user:hunter2@{address}/v1"))
+            },
+        );
+        let auth_manager =
+            AuthManager::from_auth_for_testing(CodexAuth::from_api_key("Test API Key"));
+        let manager =
+            ModelsManager::with_provider(auth_manager, provider_for("http://example.test".into()));
+
+        manager.refresh_provider_models(&config).await;
+        let status = manager.catalog_status(&config).await;
+
+        let [record] = status.providers.as_slice() else {
+            panic!("expected one provider record: {status:?}");
+        };
+        assert_eq!(record.provider_id, "gateway");
+        assert_eq!(record.host, Some(address.to_string()));
+        assert_eq!(record.fetched_at, None);
+        let last_error = record.last_error.clone().expect("fetch should fail");
+        let serialized = serde_json::to_string(&status).expect("status serializes");
+        for secret in ["hunter2", "sk-live-secret-token"] {
+            assert!(
+                !serialized.contains(secret),
+                "{secret} leaked into {serialized}"
+            );
+        }
+        assert!(!last_error.is_empty());
+    }
+
+    fn gateway_catalog(server: &MockServer) -> ProviderCatalogConfig {
+        let mut provider = provider_for(format!("{}/v1", server.uri()));
+        provider.name = "Staging gateway".to_string();
+        provider.model_catalog = Some(ModelCatalog::Openai);
+        ProviderCatalogConfig {
+            provider_id: "staging".to_string(),
+            catalog: ModelCatalog::Openai,
+            path: None,
+            provider,
+            user_agent_suffix: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn catalogs_without_a_base_url_are_never_requested() {
+        let base_urls = [None, Some(String::new()), Some("  ".to_string())];
+        for base_url in base_urls {
+            for kind in [ModelCatalog::Openai, ModelCatalog::Azure] {
+                for skip_models_preflight in [false, true] {
+                    let mut provider = provider_for(String::new());
+                    provider.base_url = base_url.clone();
+                    provider.model_catalog = Some(kind);
+                    provider.skip_models_preflight = skip_models_preflight;
+                    let catalog = ProviderCatalogConfig {
+                        provider_id: "staging".to_string(),
+                        catalog: kind,
+                        path: None,
+                        provider,
+                        user_agent_suffix: None,
+                    };
+
+                    let err =
+                        ModelsManager::fetch_provider_presets(&catalog, None, &mut Vec::new())
+                            .await
+                            .expect_err("a catalog without a base URL is not listed");
+                    assert!(
+                        matches!(
+                            err,
+                            CodexErr::MissingConfiguration {
+                                ref provider,
+                                field: "base_url",
+                            } if provider == "staging"
+                        ),
+                        "{base_url:?} {kind:?}: {err:?}"
+                    );
+                    assert_eq!(
+                        err.to_string(),
+                        "model provider `staging` has no `base_url`; set it to list the provider's models"
+                    );
+                }
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn catalogs_with_a_blank_bearer_token_are_never_requested() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/v1/models"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "object": "list",
+                "data": [{"id": "qwen2.5-coder-7b", "object": "model"}]
+            })))
+            .mount(&server)
+            .await;
+
+        for token in ["", "  "] {
+            let mut catalog = gateway_catalog(&server);
+            catalog.provider.experimental_bearer_token = Some(token.to_string());
+            let err = ModelsManager::fetch_provider_presets(&catalog, None, &mut Vec::new())
+                .await
+                .expect_err("a blank token is not sent");
+            assert!(
+                matches!(
+                    err,
+                    CodexErr::MissingConfiguration {
+                        ref provider,
+                        field: "experimental_bearer_token",
+                    } if provider == "staging"
+                ),
+                "{token:?}: {err:?}"
+            );
+            assert_eq!(
+                err.to_string(),
+                "model provider `staging` has no `experimental_bearer_token`; set it to list the provider's models"
+            );
+        }
+        let requests = server.received_requests().await.expect("requests recorded");
+        assert_eq!(requests.len(), 0, "nothing is requested with a blank token");
+
+        let mut anonymous = gateway_catalog(&server);
+        anonymous.provider.experimental_bearer_token = Some(String::new());
+        anonymous.provider.allow_anonymous_catalog = true;
+        ModelsManager::fetch_provider_presets(&anonymous, None, &mut Vec::new())
+            .await
+            .expect("an anonymous catalog ignores the token");
+    }
+
+    #[tokio::test]
+    async fn anonymous_catalogs_are_read_without_an_authorization_header() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/v1/models"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "object": "list",
+                "data": [{"id": "qwen2.5-coder-7b", "object": "model"}]
+            })))
+            .mount(&server)
+            .await;
+        let authorization = |request: &wiremock::Request| {
+            request
+                .headers
+                .get("authorization")
+                .and_then(|value| value.to_str().ok())
+                .map(str::to_string)
+        };
+
+        let mut with_token = gateway_catalog(&server);
+        with_token.provider.experimental_bearer_token = Some("gateway-token".to_string());
+        ModelsManager::fetch_provider_presets(&with_token, None, &mut Vec::new())
+            .await
+            .expect("listing with a token succeeds");
+
+        let mut anonymous = with_token.clone();
+        anonymous.provider.allow_anonymous_catalog = true;
+        let presets = ModelsManager::fetch_provider_presets(&anonymous, None, &mut Vec::new())
+            .await
+            .expect("anonymous listing succeeds")
+            .entries;
+        assert_eq!(
+            presets
+                .iter()
+                .map(|preset| preset.id.as_str())
+                .collect::<Vec<_>>(),
+            vec!["staging/qwen2.5-coder-7b"]
+        );
+
+        let requests = server.received_requests().await.expect("requests recorded");
+        let listings: Vec<Option<String>> = requests
+            .iter()
+            .filter(|request| request.method.as_str() == "GET")
+            .map(authorization)
+            .collect();
+        assert_eq!(
+            listings,
+            vec![Some("Bearer gateway-token".to_string()), None]
+        );
+    }
+
+    #[tokio::test]
+    async fn catalogs_that_demand_credentials_report_that_login_is_required() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/v1/models"))
+            .respond_with(ResponseTemplate::new(401).set_body_string("missing credentials"))
+            .mount(&server)
+            .await;
+
+        let err =
+            ModelsManager::fetch_provider_presets(&gateway_catalog(&server), None, &mut Vec::new())
+                .await
+                .expect_err("the gateway demands credentials");
+        assert!(
+            matches!(err, CodexErr::CatalogRequiresLogin(ref provider) if provider == "Staging gateway")
+        );
+        assert!(
+            err.to_string().starts_with("catalog requires login"),
+            "unexpected error: {err}"
+        );
+
+        let mut with_token = gateway_catalog(&server);
+        with_token.provider.experimental_bearer_token = Some("expired".to_string());
+        let err = ModelsManager::fetch_provider_presets(&with_token, None, &mut Vec::new())
+            .await
+            .expect_err("the gateway rejects the token");
+        assert!(
+            !matches!(err, CodexErr::CatalogRequiresLogin(_)),
+            "a rejected token is not a missing login: {err}"
+        );
+    }
+
+    #[tokio::test]
+    async fn catalogs_served_a_login_page_point_at_the_proxy() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/v1/models"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_raw("<html><body>Sign in to continue</body></html>", "text/html"),
+            )
+            .mount(&server)
+            .await;
+
+        let err =
+            ModelsManager::fetch_provider_presets(&gateway_catalog(&server), None, &mut Vec::new())
+                .await
+                .expect_err("a login page is not a catalog");
+        assert!(
+            matches!(err, CodexErr::UnexpectedContent { content_type: Some(ref content_type), .. } if content_type == "text/html")
+        );
+        assert!(
+            err.to_string().contains("captive portal or SSO proxy"),
+            "unexpected error: {err}"
+        );
+        assert!(
+            matches!(
+                err.to_codex_protocol_error(),
+                CodexErrorInfo::HttpConnectionFailed {
+                    http_status_code: None
+                }
+            ),
+            "treated like a network failure"
+        );
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn a_rejected_command_token_is_minted_again_before_giving_up() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/v1/models"))
+            .and(header("authorization", "Bearer token-1"))
+            .respond_with(ResponseTemplate::new(401).set_body_string("token expired"))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/v1/models"))
+            .and(header("authorization", "Bearer token-2"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "object": "list",
+                "data": [{"id": "oca-large", "object": "model"}]
+            })))
+            .mount(&server)
+            .await;
+        let dir = tempdir().expect("tempdir");
+        let runs = dir.path().join("runs").display().to_string();
+
+        let mut catalog = gateway_catalog(&server);
+        catalog.provider.token_command = Some(vec![
+            "sh".to_string(),
+            "-c".to_string(),
+            format!("echo x >> '{runs}'; echo token-$(wc -l < '{runs}' | tr -d ' ')"),
+        ]);
+        let presets = ModelsManager::fetch_provider_presets(&catalog, None, &mut Vec::new())
+            .await
+            .expect("the second token is accepted")
+            .entries;
+
+        assert_eq!(
+            presets
+                .iter()
+                .map(|preset| preset.id.as_str())
+                .collect::<Vec<_>>(),
+            vec!["staging/oca-large"]
+        );
+        assert_eq!(
+            std::fs::read_to_string(dir.path().join("runs"))
+                .expect("runs recorded")
+                .lines()
+                .count(),
+            2
+        );
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn token_attempts_record_a_cached_token_then_the_refreshed_one() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/v1/models"))
+            .and(header("authorization", "Bearer token-1"))
+            .respond_with(ResponseTemplate::new(401).set_body_string("token expired"))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/v1/models"))
+            .and(header("authorization", "Bearer token-2"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "object": "list",
+                "data": [{"id": "oca-large", "object": "model"}]
+            })))
+            .mount(&server)
+            .await;
+        let dir = tempdir().expect("tempdir");
+        let runs = dir.path().join("runs").display().to_string();
+
+        let mut catalog = gateway_catalog(&server);
+        let command = vec![
+            "sh".to_string(),
+            "-c".to_string(),
+            format!("echo x >> '{runs}'; echo token-$(wc -l < '{runs}' | tr -d ' ')"),
+        ];
+        catalog.provider.token_command = Some(command.clone());
+        let ttl = catalog.provider.token_command_ttl();
+        // A session minted the first token before the catalog refresh.
+        crate::token_command::token_from_command(&command, ttl)
+            .await
+            .expect("first token minted");
+
+        let mut attempts = Vec::new();
+        ModelsManager::fetch_provider_presets(&catalog, None, &mut attempts)
+            .await
+            .expect("the second token is accepted");
+
+        assert_eq!(
+            attempts
+                .iter()
+                .map(|attempt| attempt.source)
+                .collect::<Vec<_>>(),
+            vec![TokenSource::Cached, TokenSource::Refreshed]
+        );
+        let refreshed = attempts[1];
+        assert!(refreshed.age_ms < 1_000, "{refreshed:?}");
+        assert!(u128::from(refreshed.expires_in_ms) <= ttl.as_millis());
+        assert!(refreshed.expires_in_ms >= attempts[0].expires_in_ms);
+    }
+
+    async fn mount_empty_catalog(server: &MockServer, path_str: &str) {
+        Mock::given(method("GET"))
+            .and(path(path_str))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(json!({"object": "list", "data": []})),
+            )
+            .mount(server)
+            .await;
+    }
+
+    #[tokio::test]
+    async fn empty_catalogs_are_an_error_naming_the_endpoint() {
+        let server = MockServer::start().await;
+        mount_empty_catalog(&server, "/v1/models").await;
+
+        let mut catalog = gateway_catalog(&server);
+        catalog.provider.query_params = Some(HashMap::from([(
+            "tenant".to_string(),
+            "staging".to_string(),
+        )]));
+        let err = ModelsManager::fetch_provider_presets(&catalog, None, &mut Vec::new())
+            .await
+            .expect_err("an empty catalog is an error");
+        let endpoint = format!("{}/v1/models", server.uri());
+        assert!(
+            matches!(err, CodexErr::EmptyCatalog(ref url) if *url == endpoint),
+            "unexpected error: {err:?}"
+        );
+        assert_eq!(
+            err.to_string(),
+            format!(
+                "model catalog at {endpoint} listed no models; check the provider's `base_url` and `models_path`, or set `allow_empty_catalog = true` if it is meant to list none"
+            )
+        );
+
+        catalog.provider.allow_empty_catalog = true;
+        let presets = ModelsManager::fetch_provider_presets(&catalog, None, &mut Vec::new())
+            .await
+            .expect("the provider allows an empty catalog")
+            .entries;
+        assert_eq!(presets, Vec::new());
+    }
+
+    #[tokio::test]
+    async fn empty_provider_refresh_keeps_the_models_listed_before() {
+        let gateway = MockServer::start().await;
+        let codex_home = tempdir().expect("temp dir");
+        let mut config = ConfigBuilder::default()
+            .codex_home(codex_home.path().to_path_buf())
+            .build()
+            .await
+            .expect("load default test config");
+        config.model_providers.insert(
+            "gateway".to_string(),
+            ModelProviderInfo {
+                model_catalog: Some(ModelCatalog::Openai),
+                ..provider_for(gateway.uri())
+            },
+        );
+        let auth_manager =
+            AuthManager::from_auth_for_testing(CodexAuth::from_api_key("Test API Key"));
+        let manager =
+            ModelsManager::with_provider(auth_manager, provider_for("http://example.test".into()));
+
+        mount_openai_catalog(&gateway, &["oca-large"]).await;
+        manager.refresh_provider_models(&config).await;
+        gateway.reset().await;
+        mount_empty_catalog(&gateway, "/models").await;
+        manager.refresh_provider_models(&config).await;
+        let list = manager.model_list(&config).await;
+
+        assert!(
+            list.models
+                .iter()
+                .any(|preset| preset.id == "gateway/oca-large"),
+            "an empty listing must not drop the models listed before"
+        );
+        let [warning] = list.warnings.as_slice() else {
+            panic!("expected one warning: {:?}", list.warnings);
+        };
+        assert!(
+            warning.error.contains("listed no models"),
+            "unexpected warning: {warning}"
+        );
+    }
+
+    #[tokio::test]
+    async fn provider_catalogs_are_listed_once_per_cache_ttl() {
+        let gateway = MockServer::start().await;
+        let codex_home = tempdir().expect("temp dir");
+        let mut config = ConfigBuilder::default()
+            .codex_home(codex_home.path().to_path_buf())
+            .build()
+            .await
+            .expect("load default test config");
+        config.model_providers.insert(
+            "gateway".to_string(),
+            ModelProviderInfo {
+                model_catalog: Some(ModelCatalog::Openai),
+                ..provider_for(gateway.uri())
+            },
+        );
+        let auth_manager =
+            AuthManager::from_auth_for_testing(CodexAuth::from_api_key("Test API Key"));
+        let mut manager =
+            ModelsManager::with_provider(auth_manager, provider_for("http://example.test".into()));
+        let listed = |list: &ModelList, id: &str| list.models.iter().any(|preset| preset.id == id);
+
+        mount_openai_catalog(&gateway, &["oca-large"]).await;
+        let first = manager.model_list(&config).await;
+        let second = manager.model_list(&config).await;
+        assert!(listed(&first, "gateway/oca-large"));
+        assert_eq!(second.revision, first.revision);
+        gateway.verify().await;
+
+        // Past the TTL the listing held in memory is served, and replaced
+        // once the background refresh lands.
+        manager.cache_ttl = Duration::ZERO;
+        gateway.reset().await;
+        mount_openai_catalog(&gateway, &["oca-xl"]).await;
+        let stale = manager.model_list(&config).await;
+        assert!(listed(&stale, "gateway/oca-large"));
+        manager.finish_revalidation().await;
+        manager.cache_ttl = DEFAULT_MODEL_CACHE_TTL;
+        let refreshed = manager.model_list(&config).await;
+        assert!(listed(&refreshed, "gateway/oca-xl"));
+        assert!(!listed(&refreshed, "gateway/oca-large"));
+    }
+
+    #[tokio::test]
+    async fn allowed_empty_catalogs_leave_nothing_to_pick_without_panicking() {
+        let gateway = MockServer::start().await;
+        mount_empty_catalog(&gateway, "/models").await;
+        let codex_home = tempdir().expect("temp dir");
+        let mut config = ConfigBuilder::default()
+            .codex_home(codex_home.path().to_path_buf())
+            .build()
+            .await
+            .expect("load default test config");
+        config.model_catalog_sources = vec![ModelCatalogSource::Providers];
+        config.model_providers.insert(
+            "gateway".to_string(),
+            ModelProviderInfo {
+                model_catalog: Some(ModelCatalog::Openai),
+                allow_empty_catalog: true,
+                ..provider_for(gateway.uri())
+            },
+        );
+        let auth_manager =
+            AuthManager::from_auth_for_testing(CodexAuth::from_api_key("Test API Key"));
+        let manager =
+            ModelsManager::with_provider(auth_manager, provider_for("http://example.test".into()));
+
+        let list = manager.model_list(&config).await;
+        assert_eq!(list.models, Vec::new());
+        assert_eq!(list.warnings, Vec::new());
+
+        let (model, _family) = manager.resolve_session_model(&config).await;
+        assert_eq!(model, OPENAI_DEFAULT_API_MODEL);
+        assert_eq!(
+            manager
+                .fallback_model(&["oca-large".to_string()], "gateway", &config)
+                .await,
+            None
+        );
+    }
+
+    #[tokio::test]
+    async fn feedback_snapshot_names_the_preset_and_redacts_the_catalog_error() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/v1/models"))
+            .respond_with(
+                ResponseTemplate::new(401)
+                    .set_body_string("invalid credentials: Bearer sk-live-secret-token"),
+            )
+            .mount(&server)
+            .await;
+        let codex_home = tempdir().expect("temp dir");
+        let mut config = status_test_config(codex_home.path()).await;
+        let address = server.address();
+        config.model_providers.insert(
+            "gateway".to_string(),
+            ModelProviderInfo {
+                model_catalog: Some(ModelCatalog::Openai),
+                ..provider_for(format!("http://user:hunter2@{address}/v1"))
+            },
+        );
+        let auth_manager =
+            AuthManager::from_auth_for_testing(CodexAuth::from_api_key("Test API Key"));
+        let manager =
+            ModelsManager::with_provider(auth_manager, provider_for("http://example.test".into()));
+        let model = builtin_model_presets(None).remove(0).model;
+
+        manager.refresh_provider_models(&config).await;
+        let snapshot = manager
+            .feedback_snapshot(&model, Some(ReasoningEffort::High), Some(272_000), &config)
+            .await;
+
+        assert_eq!(snapshot.model, model);
+        assert_eq!(snapshot.preset_id.as_ref(), Some(&model));
+        assert!(snapshot.preset_source.is_some());
+        assert_eq!(snapshot.context_window, Some(272_000));
+        assert_eq!(snapshot.effort, Some(ReasoningEffort::High));
+        let catalog_error = snapshot.catalog_error.clone().expect("the refresh failed");
+        assert!(
+            catalog_error.contains("gateway"),
+            "the error names the provider: {catalog_error}"
+        );
+        let serialized = serde_json::to_string(&snapshot).expect("snapshot serializes");
+        let full_url = format!("{address}/v1");
+        for secret in ["hunter2", "sk-live-secret-token", full_url.as_str()] {
+            assert!(
+                !serialized.contains(secret),
+                "{secret} leaked into {serialized}"
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn snapshot_status_follows_a_failed_refresh_redacted() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/v1/models"))
+            .respond_with(
+                ResponseTemplate::new(401)
+                    .set_body_string("invalid credentials: Bearer sk-live-secret-token"),
+            )
+            .mount(&server)
+            .await;
+        let codex_home = tempdir().expect("temp dir");
+        let mut config = status_test_config(codex_home.path()).await;
+        let address = server.address();
+        config.model_providers.insert(
+            "gateway".to_string(),
+            ModelProviderInfo {
+                model_catalog: Some(ModelCatalog::Openai),
+                ..provider_for(format!("http://user:hunter2@{address}/v1"))
+            },
+        );
+        let auth_manager =
+            AuthManager::from_auth_for_testing(CodexAuth::from_api_key("Test API Key"));
+        let manager =
+            ModelsManager::with_provider(auth_manager, provider_for("http://example.test".into()));
+        let catalog = PresetCatalog::new(&manager, &config);
+
+        let before = catalog.snapshot();
+        assert_eq!(before.status().last_error, None);
+        assert_eq!(
+            before.status().cache_path,
+            codex_home.path().join(MODEL_CACHE_FILE)
+        );
+
+        manager.refresh_provider_models(&config).await;
+        let after = catalog.snapshot();
+
+        assert_eq!(after.revision(), before.revision());
+        assert_eq!(after.status().source, CatalogSource::Bundled);
+        let (_, error) = after
+            .status()
+            .last_error
+            .clone()
+            .expect("the failure reaches the snapshot");
+        assert!(
+            error.contains("gateway"),
+            "the error names the provider: {error}"
+        );
+        let full_url = format!("{address}/v1");
+        for secret in ["hunter2", "sk-live-secret-token", full_url.as_str()] {
+            assert!(!error.contains(secret), "{secret} leaked into {error}");
         }
     }
 
+    #[test]
+    fn bundled_models_json_roundtrips() {
+        let file_contents = include_str!("../../models.json");
+        let response: ModelsResponse =
+            serde_json::from_str(file_contents).expect("bundled models.json should deserialize");
+
+        let serialized =
+            serde_json::to_string(&response).expect("bundled models.json should serialize");
+        let roundtripped: ModelsResponse =
+            serde_json::from_str(&serialized).expect("serialized models.json should deserialize");
+
+        assert_eq!(
+            response, roundtripped,
+            "bundled models.json should round trip through serde"
+        );
+        assert!(
+            !response.models.is_empty(),
+            "bundled models.json should contain at least one model"
+        );
+    }
+
     #[tokio::test]
-    async fn refresh_available_models_sorts_and_marks_default() {
-        let server = MockServer::start().await;
-        let remote_models = vec![
-            remote_model("priority-low", "Low", 1),
-            remote_model("priority-high", "High", 0),
-        ];
-        let models_mock = mount_models_once(
-            &server,
-            ModelsResponse {
-                models: remote_models.clone(),
-            },
-        )
-        .await;
+    async fn unknown_model_warning_waits_for_provider_catalog() {
+        let gateway = MockServer::start().await;
+        mount_openai_catalog(&gateway, &["oca-large", "oca-small"]).await;
 
         let codex_home = tempdir().expect("temp dir");
         let mut config = ConfigBuilder::default()
@@ -410,276 +6276,196 @@ mod tests {
             .build()
             .await
             .expect("load default test config");
-        config.features.enable(Feature::RemoteModels);
+        let provider = ModelProviderInfo {
+            name: "Gateway".into(),
+            model_catalog: Some(ModelCatalog::Openai),
+            ..provider_for(gateway.uri())
+        };
+        config
+            .model_providers
+            .insert("gateway".to_string(), provider.clone());
+        config.model_provider_id = "gateway".to_string();
+        config.model_provider = provider;
         let auth_manager =
-            AuthManager::from_auth_for_testing(CodexAuth::create_dummy_chatgpt_auth_for_testing());
-        let provider = provider_for(server.uri());
-        let manager = ModelsManager::with_provider(auth_manager, provider);
+            AuthManager::from_auth_for_testing(CodexAuth::from_api_key("Test API Key"));
+        let manager = ModelsManager::with_provider(auth_manager, config.model_provider.clone());
 
-        manager
-            .refresh_available_models_with_cache(&config)
-            .await
-            .expect("refresh succeeds");
-        let cached_remote = manager.remote_models(&config).await;
-        assert_eq!(cached_remote, remote_models);
+        assert_eq!(
+            manager.unknown_model_warning("oca-lrge", &config).await,
+            None,
+            "the check is skipped until the provider catalog has loaded"
+        );
 
-        let available = manager.list_models(&config).await;
-        let high_idx = available
-            .iter()
-            .position(|model| model.model == "priority-high")
-            .expect("priority-high should be listed");
-        let low_idx = available
-            .iter()
-            .position(|model| model.model == "priority-low")
-            .expect("priority-low should be listed");
-        assert!(
-            high_idx < low_idx,
-            "higher priority should be listed before lower priority"
+        manager.refresh_provider_models(&config).await;
+
+        assert_eq!(
+            manager.unknown_model_warning("oca-large", &config).await,
+            None
         );
-        assert!(
-            available[high_idx].is_default,
-            "highest priority should be default"
+        assert_eq!(
+            manager
+                .unknown_model_warning("gateway/oca-small", &config)
+                .await,
+            None
         );
-        assert!(!available[low_idx].is_default);
         assert_eq!(
-            models_mock.requests().len(),
-            1,
-            "expected a single /models request"
+            manager.unknown_model_warning("oca-lrge", &config).await,
+            Some(
+                "model `oca-lrge` is not in the model catalog for provider `Gateway`. Did you mean `oca-large`? Run `codex models list` to see the available models."
+                    .to_string()
+            )
         );
     }
 
     #[tokio::test]
-    async fn refresh_available_models_uses_cache_when_fresh() {
-        let server = MockServer::start().await;
-        let remote_models = vec![remote_model("cached", "Cached", 5)];
-        let models_mock = mount_models_once(
-            &server,
-            ModelsResponse {
-                models: remote_models.clone(),
-            },
-        )
-        .await;
-
+    async fn retired_models_resolve_silently_for_openai_only() {
         let codex_home = tempdir().expect("temp dir");
         let mut config = ConfigBuilder::default()
             .codex_home(codex_home.path().to_path_buf())
             .build()
             .await
             .expect("load default test config");
-        config.features.enable(Feature::RemoteModels);
-        let auth_manager = Arc::new(AuthManager::new(
-            codex_home.path().to_path_buf(),
-            false,
-            AuthCredentialsStoreMode::File,
-        ));
-        let provider = provider_for(server.uri());
-        let manager = ModelsManager::with_provider(auth_manager, provider);
+        config.model_provider = ModelProviderInfo {
+            name: "OpenAI".into(),
+            requires_openai_auth: true,
+            ..provider_for("http://example.test".into())
+        };
+        let auth_manager =
+            AuthManager::from_auth_for_testing(CodexAuth::from_api_key("Test API Key"));
+        let manager = ModelsManager::with_provider(auth_manager, config.model_provider.clone());
+        let retired = Some("codex-mini-latest".to_string());
 
-        manager
-            .refresh_available_models_with_cache(&config)
-            .await
-            .expect("first refresh succeeds");
         assert_eq!(
-            manager.remote_models(&config).await,
-            remote_models,
-            "remote cache should store fetched models"
+            manager.get_model(&retired, &config).await,
+            "gpt-5.1-codex-mini"
         );
-
-        // Second call should read from cache and avoid the network.
-        manager
-            .refresh_available_models_with_cache(&config)
-            .await
-            .expect("cached refresh succeeds");
         assert_eq!(
-            manager.remote_models(&config).await,
-            remote_models,
-            "cache path should not mutate stored models"
+            manager
+                .unknown_model_warning("codex-mini-latest", &config)
+                .await,
+            None
         );
+
+        config.model_provider.requires_openai_auth = false;
         assert_eq!(
-            models_mock.requests().len(),
-            1,
-            "cache hit should avoid a second /models request"
+            manager.get_model(&retired, &config).await,
+            "codex-mini-latest"
         );
     }
 
     #[tokio::test]
-    async fn refresh_available_models_refetches_when_cache_stale() {
-        let server = MockServer::start().await;
-        let initial_models = vec![remote_model("stale", "Stale", 1)];
-        let initial_mock = mount_models_once(
-            &server,
-            ModelsResponse {
-                models: initial_models.clone(),
-            },
-        )
-        .await;
-
+    async fn unknown_model_warning_accepts_unlisted_builtin_families() {
         let codex_home = tempdir().expect("temp dir");
         let mut config = ConfigBuilder::default()
             .codex_home(codex_home.path().to_path_buf())
             .build()
             .await
             .expect("load default test config");
-        config.features.enable(Feature::RemoteModels);
-        let auth_manager = Arc::new(AuthManager::new(
-            codex_home.path().to_path_buf(),
-            false,
-            AuthCredentialsStoreMode::File,
-        ));
-        let provider = provider_for(server.uri());
-        let manager = ModelsManager::with_provider(auth_manager, provider);
-
-        manager
-            .refresh_available_models_with_cache(&config)
-            .await
-            .expect("initial refresh succeeds");
-
-        // Rewrite cache with an old timestamp so it is treated as stale.
-        let cache_path = codex_home.path().join(MODEL_CACHE_FILE);
-        let contents =
-            std::fs::read_to_string(&cache_path).expect("cache file should exist after refresh");
-        let mut cache: ModelsCache =
-            serde_json::from_str(&contents).expect("cache should deserialize");
-        cache.fetched_at = Utc::now() - chrono::Duration::hours(1);
-        std::fs::write(&cache_path, serde_json::to_string_pretty(&cache).unwrap())
-            .expect("cache rewrite succeeds");
-
-        let updated_models = vec![remote_model("fresh", "Fresh", 9)];
-        server.reset().await;
-        let refreshed_mock = mount_models_once(
-            &server,
-            ModelsResponse {
-                models: updated_models.clone(),
-            },
-        )
-        .await;
+        config.model_provider = ModelProviderInfo {
+            name: "OpenAI".into(),
+            requires_openai_auth: true,
+            ..provider_for("http://example.test".into())
+        };
+        let auth_manager =
+            AuthManager::from_auth_for_testing(CodexAuth::from_api_key("Test API Key"));
+        let manager = ModelsManager::with_provider(auth_manager, config.model_provider.clone());
 
-        manager
-            .refresh_available_models_with_cache(&config)
-            .await
-            .expect("second refresh succeeds");
-        assert_eq!(
-            manager.remote_models(&config).await,
-            updated_models,
-            "stale cache should trigger refetch"
-        );
         assert_eq!(
-            initial_mock.requests().len(),
-            1,
-            "initial refresh should only hit /models once"
+            manager
+                .unknown_model_warning("gpt-5.1-codex", &config)
+                .await,
+            None
         );
+        assert_eq!(manager.unknown_model_warning("o3", &config).await, None);
         assert_eq!(
-            refreshed_mock.requests().len(),
-            1,
-            "stale cache refresh should fetch /models once"
+            manager.unknown_model_warning("gpt-5.1-codx", &config).await,
+            Some(
+                "model `gpt-5.1-codx` is not in the model catalog for provider `OpenAI`. Did you mean one of `gpt-5.1-codex`, `gpt-5.2-codex`, `gpt-5-codex`? Run `codex models list` to see the available models."
+                    .to_string()
+            )
         );
     }
 
     #[tokio::test]
-    async fn refresh_available_models_drops_removed_remote_models() {
-        let server = MockServer::start().await;
-        let initial_models = vec![remote_model("remote-old", "Remote Old", 1)];
-        let initial_mock = mount_models_once(
-            &server,
-            ModelsResponse {
-                models: initial_models,
-            },
-        )
-        .await;
-
+    async fn a_refused_model_is_greyed_out_until_the_mark_expires() {
         let codex_home = tempdir().expect("temp dir");
-        let mut config = ConfigBuilder::default()
+        let config = ConfigBuilder::default()
             .codex_home(codex_home.path().to_path_buf())
             .build()
             .await
             .expect("load default test config");
-        config.features.enable(Feature::RemoteModels);
         let auth_manager =
-            AuthManager::from_auth_for_testing(CodexAuth::create_dummy_chatgpt_auth_for_testing());
-        let provider = provider_for(server.uri());
-        let mut manager = ModelsManager::with_provider(auth_manager, provider);
-        manager.cache_ttl = Duration::ZERO;
-
-        manager
-            .refresh_available_models_with_cache(&config)
-            .await
-            .expect("initial refresh succeeds");
-
-        server.reset().await;
-        let refreshed_models = vec![remote_model("remote-new", "Remote New", 1)];
-        let refreshed_mock = mount_models_once(
-            &server,
-            ModelsResponse {
-                models: refreshed_models,
-            },
-        )
-        .await;
+            AuthManager::from_auth_for_testing(CodexAuth::from_api_key("Test API Key"));
+        let manager = ModelsManager::new(auth_manager);
+        let model = "gpt-5.1-codex-mini";
+        let reason = |models: Vec<ModelPreset>| {
+            models
+                .into_iter()
+                .find(|preset| preset.model == model)
+                .and_then(|preset| preset.unavailable_for_account)
+        };
 
         manager
-            .refresh_available_models_with_cache(&config)
-            .await
-            .expect("second refresh succeeds");
-
-        let available = manager
-            .try_list_models(&config)
-            .expect("models should be available");
-        assert!(
-            available.iter().any(|preset| preset.model == "remote-new"),
-            "new remote model should be listed"
-        );
-        assert!(
-            !available.iter().any(|preset| preset.model == "remote-old"),
-            "removed remote model should not be listed"
-        );
+            .mark_unavailable_for_account(model, "not on your plan", &config)
+            .await;
         assert_eq!(
-            initial_mock.requests().len(),
-            1,
-            "initial refresh should only hit /models once"
+            reason(manager.known_models(&config).await).as_deref(),
+            Some("not on your plan")
         );
         assert_eq!(
-            refreshed_mock.requests().len(),
+            manager
+                .known_models(&config)
+                .await
+                .iter()
+                .filter(|preset| preset.unavailable_for_account.is_some())
+                .count(),
             1,
-            "second refresh should only hit /models once"
+            "only the refused model is greyed out"
         );
+
+        // Age the mark past its TTL.
+        let account = manager.catalog_auth(&config).to_string();
+        let ttl = chrono::Duration::from_std(UNAVAILABLE_TTL).expect("ttl fits");
+        manager
+            .availability
+            .lock()
+            .expect("availability lock")
+            .mark(&account, model, "not on your plan", Utc::now() - ttl);
+        assert_eq!(reason(manager.known_models(&config).await), None);
     }
 
-    #[test]
-    fn build_available_models_picks_default_after_hiding_hidden_models() {
+    #[tokio::test]
+    async fn display_names_come_from_the_local_catalog_or_fall_back_to_the_slug() {
+        let codex_home = tempdir().expect("temp dir");
+        let config = ConfigBuilder::default()
+            .codex_home(codex_home.path().to_path_buf())
+            .build()
+            .await
+            .expect("load default test config");
         let auth_manager =
             AuthManager::from_auth_for_testing(CodexAuth::from_api_key("Test API Key"));
-        let provider = provider_for("http://example.test".to_string());
-        let mut manager = ModelsManager::with_provider(auth_manager, provider);
-        manager.local_models = Vec::new();
-
-        let hidden_model = remote_model_with_visibility("hidden", "Hidden", 0, "hide");
-        let visible_model = remote_model_with_visibility("visible", "Visible", 1, "list");
-
-        let mut expected = ModelPreset::from(visible_model.clone());
-        expected.is_default = true;
-
-        let available = manager.build_available_models(vec![hidden_model, visible_model]);
-
-        assert_eq!(available, vec![expected]);
-    }
-
-    #[test]
-    fn bundled_models_json_roundtrips() {
-        let file_contents = include_str!("../../models.json");
-        let response: ModelsResponse =
-            serde_json::from_str(file_contents).expect("bundled models.json should deserialize");
-
-        let serialized =
-            serde_json::to_string(&response).expect("bundled models.json should serialize");
-        let roundtripped: ModelsResponse =
-            serde_json::from_str(&serialized).expect("serialized models.json should deserialize");
+        let manager =
+            ModelsManager::with_provider(auth_manager, provider_for("http://example.test".into()));
+        *manager.provider_models.write().await = vec![
+            ModelPreset::builder("gw/qwen-coder", "qwen-coder")
+                .display_name("Qwen Coder")
+                .model_provider("gw".to_string())
+                .build()
+                .expect("valid preset"),
+        ];
 
         assert_eq!(
-            response, roundtripped,
-            "bundled models.json should round trip through serde"
+            manager.display_name("gw/qwen-coder", &config).await,
+            "Qwen Coder"
         );
-        assert!(
-            !response.models.is_empty(),
-            "bundled models.json should contain at least one model"
+        assert_eq!(
+            manager.display_name("qwen-coder", &config).await,
+            "Qwen Coder",
+            "a bare slug resolves too"
+        );
+        assert_eq!(
+            manager.display_name("cloud-only-model", &config).await,
+            "cloud-only-model"
         );
     }
 }
@@ -1,4 +1,27 @@
+pub mod availability;
+pub mod bundle;
 pub mod cache;
+pub mod catalog;
+pub mod catalog_export;
+pub mod catalog_handle;
+pub mod client_version;
+pub mod diff;
+pub mod feedback;
+pub mod fetch_diagnostics;
+pub(crate) mod jitter;
+pub(crate) mod locale;
 pub mod manager;
 pub mod model_family;
 pub mod model_presets;
+pub(crate) mod preflight;
+pub mod provenance;
+pub mod rebuild_queue;
+pub mod refresh_events;
+pub(crate) mod request_headers;
+pub mod resolution;
+mod sanitize;
+pub mod seen;
+pub(crate) mod state_dir;
+pub(crate) mod state_gc;
+pub(crate) mod tenants;
+pub mod validation;
@@ -1,10 +1,23 @@
+use std::collections::HashMap;
+use std::time::Duration;
+
+use codex_protocol::config_types::ReasoningSummary as ReasoningSummaryConfig;
 use codex_protocol::config_types::Verbosity;
+use codex_protocol::config_types::WireApi;
 use codex_protocol::openai_models::ApplyPatchToolType;
 use codex_protocol::openai_models::ConfigShellToolType;
 use codex_protocol::openai_models::ModelInfo;
+use codex_protocol::openai_models::ModelPreset;
+use codex_protocol::openai_models::PromptCacheKeyStrategy;
 use codex_protocol::openai_models::ReasoningEffort;
+use codex_protocol::openai_models::ReasoningEffortPreset;
+use codex_protocol::openai_models::ReasoningSummaryFormat;
 
 use crate::config::Config;
+use crate::config::types::ModelPresetOverride;
+use crate::model_provider_info::ModelProviderInfo;
+use crate::models_manager::request_headers::build_request_headers;
+use crate::models_manager::request_headers::sanitize_request_headers;
 use crate::truncate::TruncationPolicy;
 
 /// The `instructions` field in the payload sent to a model should always start
@@ -18,8 +31,54 @@ const GPT_5_1_CODEX_MAX_INSTRUCTIONS: &str = include_str!("../../gpt-5.1-codex-m
 const GPT_5_2_CODEX_INSTRUCTIONS: &str = include_str!("../../gpt-5.2-codex_prompt.md");
 pub(crate) const CONTEXT_WINDOW_272K: i64 = 272_000;
 
+/// Which setting a stream idle timeout came from, so a timeout error can say
+/// what to raise.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IdleTimeoutSource {
+    /// `stream_idle_timeout_secs` on the reasoning effort used.
+    Effort(ReasoningEffort),
+    /// `stream_idle_timeout_secs` on the model preset.
+    Preset,
+    /// The provider's `stream_idle_timeout_ms`.
+    Provider,
+    /// Nothing set one; the built-in default applies.
+    Default,
+}
+
+impl std::fmt::Display for IdleTimeoutSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Effort(effort) => {
+                write!(f, "`stream_idle_timeout_secs` on the {effort} effort")
+            }
+            Self::Preset => f.write_str("the model's `stream_idle_timeout_secs`"),
+            Self::Provider => f.write_str("the provider's `stream_idle_timeout_ms`"),
+            Self::Default => f.write_str("the default stream idle timeout"),
+        }
+    }
+}
+
+/// Which setting the whole-request budget of a model request came from, so a
+/// timeout error can say what to raise.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RequestTimeoutSource {
+    /// `request_timeout_secs` on the reasoning effort used.
+    Effort(ReasoningEffort),
+    /// `request_timeout_secs` on the model preset.
+    Preset,
+}
+
+impl std::fmt::Display for RequestTimeoutSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Effort(effort) => write!(f, "`request_timeout_secs` on the {effort} effort"),
+            Self::Preset => f.write_str("the model's `request_timeout_secs`"),
+        }
+    }
+}
+
 /// A model family is a group of models that share certain characteristics.
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct ModelFamily {
     /// The full model slug used to derive this model family, e.g.
     /// "gpt-4.1-2025-04-14".
@@ -39,6 +98,61 @@ pub struct ModelFamily {
     /// Token threshold for automatic compaction if config does not override it.
     auto_compact_token_limit: Option<i64>,
 
+    /// Fraction of the context window the default compaction threshold sits
+    /// at, from the active preset. `None` keeps the default of 0.9.
+    compaction_threshold_ratio: Option<f64>,
+
+    /// Input budget of one summarization request when compacting, from the
+    /// active preset. `None` budgets the whole context window.
+    summarization_max_tokens: Option<i64>,
+
+    /// Largest output budget the model accepts, if known. Also used as the
+    /// cap when neither the reasoning effort nor the config sets one.
+    pub max_output_tokens: Option<i64>,
+
+    /// Output-token caps that only apply to requests at a given effort.
+    pub effort_max_output_tokens: Vec<(ReasoningEffort, i64)>,
+
+    /// How long one request may take in total, from sending it until its
+    /// response stream ends, from the active preset, for efforts without
+    /// their own. `None` sets no limit.
+    pub request_timeout: Option<Duration>,
+
+    /// Whole-request budgets that only apply to requests at a given effort.
+    pub effort_request_timeouts: Vec<(ReasoningEffort, Duration)>,
+
+    /// How long a streamed response may go without an event, from the active
+    /// preset, for efforts without their own. `None` leaves it to the
+    /// provider.
+    pub stream_idle_timeout: Option<Duration>,
+
+    /// Stream idle timeouts that only apply to requests at a given effort.
+    pub effort_stream_idle_timeouts: Vec<(ReasoningEffort, Duration)>,
+
+    /// What each reasoning effort means for this model, as described by its
+    /// preset. Efforts without a description are left out.
+    pub effort_descriptions: Vec<(ReasoningEffort, String)>,
+
+    /// Whether requests may carry a reasoning effort. A preset that lists no
+    /// efforts turns this off: the model takes no effort parameter.
+    pub supports_reasoning_effort: bool,
+
+    /// The efforts the model's preset lists, in its order. Empty when no
+    /// preset said, in which case any effort may be sent.
+    pub reasoning_efforts: Vec<ReasoningEffort>,
+
+    /// Whether requests may set `temperature`. Reasoning models reject it.
+    pub supports_temperature: bool,
+
+    /// Temperature to send when the config does not set one.
+    pub default_temperature: Option<f32>,
+
+    /// Whether requests may set `top_p`.
+    pub supports_top_p: bool,
+
+    /// `top_p` to send when the config does not set one.
+    pub default_top_p: Option<f32>,
+
     // Whether the `reasoning` field can be set when making a request to this
     // model family. Note it has `effort` and `summary` subfields (though
     // `summary` is optional).
@@ -51,16 +165,85 @@ pub struct ModelFamily {
     /// Responses API.
     pub supports_parallel_tool_calls: bool,
 
+    /// What the active preset, or the catalog entry it came from, says about
+    /// parallel tool calls. `Some(false)` also serializes dispatch of calls the
+    /// model emits together anyway.
+    pub preset_supports_parallel_tool_calls: Option<bool>,
+
+    /// What the active preset says about reasoning summaries. `Some(false)`
+    /// keeps the summary out of requests while the effort is still sent.
+    pub preset_supports_reasoning_summaries: Option<bool>,
+
+    /// What the active preset, or the catalog entry it came from, says about
+    /// reasoning parameters at all. `Some(false)` keeps `reasoning` out of
+    /// requests whatever `model_supports_reasoning_summaries` says.
+    pub preset_supports_reasoning: Option<bool>,
+
+    /// How the model writes its reasoning summaries: the configured
+    /// `model_reasoning_summary_format`, else the hint on its preset or
+    /// catalog entry, else the default.
+    pub reasoning_summary_format: ReasoningSummaryFormat,
+
+    /// Whether requests to this model are stateful, from its preset or
+    /// catalog entry. `None` leaves it to the provider.
+    pub supports_stateful_responses: Option<bool>,
+
+    /// Most requests to this model allowed in flight at once across the
+    /// process, from its preset or catalog entry. `None` is unbounded.
+    pub max_concurrent_requests: Option<u32>,
+
     /// Present if the model performs better when `apply_patch` is provided as
     /// a tool call instead of just a bash command
     pub apply_patch_tool_type: Option<ApplyPatchToolType>,
 
+    /// What the active preset, or the catalog entry it came from, says about
+    /// offering `apply_patch` as a freeform tool. Consulted only when the
+    /// config leaves `apply_patch_freeform` unset.
+    pub preset_prefers_apply_patch_freeform: Option<bool>,
+
+    /// What the active preset, or the catalog entry it came from, says about
+    /// the web search tool. Consulted only when the config leaves
+    /// `web_search_request` unset.
+    pub preset_enables_web_search: Option<bool>,
+
     // Instructions to use for querying the model
     pub base_instructions: String,
 
+    /// Extra instructions appended after the base instructions, typically
+    /// mandated by the provider serving this model.
+    pub instructions_addendum: Option<String>,
+
+    /// Wire API this model must be called with, overriding the provider's
+    /// `wire_api` when set.
+    pub wire_api: Option<WireApi>,
+
+    /// Extra headers sent with every request made with this model, e.g. a
+    /// gateway routing hint, as the catalog served them. Names are lowercase;
+    /// values are sent verbatim.
+    pub extra_request_headers: HashMap<String, String>,
+
+    /// Headers the provider's `model_request_headers` config sets for this
+    /// model. Values may contain `${VAR}` references that are expanded when
+    /// the request is built, and win over [`Self::extra_request_headers`].
+    pub configured_request_headers: HashMap<String, String>,
+
+    /// How requests scope their `prompt_cache_key`, from the active preset.
+    pub prompt_cache_key_strategy: PromptCacheKeyStrategy,
+
+    /// Models to retry a turn on, in order, when this one is at capacity.
+    pub fallback_models: Vec<String>,
+
+    /// Whether the model accepts images in its input.
+    pub supports_images: bool,
+
     /// Names of beta tools that should be exposed to this model family.
     pub experimental_supported_tools: Vec<String>,
 
+    /// Names of tools kept out of the tool list for this model, whatever the
+    /// features enable. The preset, the catalog entry, and the config can
+    /// each add to it; none of them can lift another's restriction.
+    pub disabled_tools: Vec<String>,
+
     /// Percentage of the context window considered usable for inputs, after
     /// reserving headroom for system prompts, tool overhead, and model output.
     /// This is applied when computing the effective context window seen by
@@ -84,14 +267,156 @@ impl ModelFamily {
         if let Some(supports_reasoning_summaries) = config.model_supports_reasoning_summaries {
             self.supports_reasoning_summaries = supports_reasoning_summaries;
         }
+        if let Some(format) = config.model_reasoning_summary_format {
+            self.reasoning_summary_format = format;
+        }
         if let Some(context_window) = config.model_context_window {
             self.context_window = Some(context_window);
         }
         if let Some(auto_compact_token_limit) = config.model_auto_compact_token_limit {
             self.auto_compact_token_limit = Some(auto_compact_token_limit);
         }
+        for (effort, description) in &mut self.effort_descriptions {
+            if let Some(configured) = config.effort_descriptions.get(effort) {
+                description.clone_from(configured);
+            }
+        }
+        if let Some(limits) = config.preset_overrides.get(&self.slug) {
+            self.default_reasoning_effort = self
+                .default_reasoning_effort
+                .map(|effort| limits.clamp(effort));
+        }
+        if !config.disabled_reasoning_efforts.is_empty() {
+            self.disable_efforts(&config.disabled_reasoning_efforts);
+        }
+        if let Some(addendum) = &config.model_provider.instructions_addendum {
+            self.instructions_addendum = Some(addendum.clone());
+        }
+        if let Some(headers) = config.model_provider.request_headers_for(&self.slug) {
+            self.configured_request_headers.extend(headers.clone());
+        }
+        self
+    }
+
+    /// Effort descriptions from the preset's `model_preset_overrides` entry,
+    /// which win over the shared `effort_descriptions`, and the tools that
+    /// entry disables.
+    pub(super) fn with_preset_copy(mut self, overrides: Option<&ModelPresetOverride>) -> Self {
+        if let Some(overrides) = overrides {
+            for (effort, description) in &mut self.effort_descriptions {
+                if let Some(configured) = overrides.effort_description(*effort) {
+                    *description = configured.to_string();
+                }
+            }
+            self.disable_tools(&overrides.disabled_tools);
+        }
+        self
+    }
+
+    /// Drop the efforts in `disabled` as [`ModelPreset::disable_efforts`]
+    /// drops them from the preset: a disabled default moves to the nearest
+    /// effort left, and a model with none left takes no effort.
+    fn disable_efforts(&mut self, disabled: &[ReasoningEffort]) {
+        let offered = if self.reasoning_efforts.is_empty() {
+            ReasoningEffort::VARIANTS.to_vec()
+        } else {
+            self.reasoning_efforts.clone()
+        };
+        let left: Vec<ReasoningEffort> = offered
+            .into_iter()
+            .filter(|effort| !disabled.contains(effort))
+            .collect();
+        self.reasoning_efforts
+            .retain(|effort| !disabled.contains(effort));
+        self.effort_descriptions
+            .retain(|(effort, _)| !disabled.contains(effort));
+        if left.is_empty() {
+            self.supports_reasoning_effort = false;
+            self.default_reasoning_effort = None;
+        } else if let Some(default) = self.default_reasoning_effort
+            && disabled.contains(&default)
+        {
+            self.default_reasoning_effort = default.nearest_in(&left);
+        }
+    }
+
+    fn disable_tools(&mut self, tools: &[String]) {
+        for tool in tools {
+            if !self.disabled_tools.contains(tool) {
+                self.disabled_tools.push(tool.clone());
+            }
+        }
+    }
+
+    /// Pick up per-effort output caps, the instructions addendum, the wire API
+    /// override, the sampling parameters, image, parallel tool call, and
+    /// reasoning summary support, the reasoning summary format, stateful
+    /// response support, the request concurrency cap, the context window,
+    /// compaction ratio and summarization budget, the extra request headers, the prompt cache key
+    /// strategy, the fallback models, the disabled tools, and the
+    /// `apply_patch` and web search hints declared on the matching preset.
+    pub(super) fn with_preset_overrides(mut self, preset: Option<&ModelPreset>) -> Self {
+        if let Some(preset) = preset {
+            self.set_effort_levels(&preset.supported_reasoning_efforts);
+            if let Some(context_window) = preset.context_window {
+                self.context_window = Some(context_window);
+            }
+            self.compaction_threshold_ratio = preset.compaction_threshold_ratio;
+            self.summarization_max_tokens = preset.summarization_max_tokens;
+            if let Some(supports_temperature) = preset.supports_temperature {
+                self.supports_temperature = supports_temperature;
+            }
+            self.default_temperature = preset.default_temperature;
+            if let Some(supports_top_p) = preset.supports_top_p {
+                self.supports_top_p = supports_top_p;
+            }
+            self.default_top_p = preset.default_top_p;
+            self.supports_images = preset.supports_images;
+            if let Some(supports_parallel_tool_calls) = preset.supports_parallel_tool_calls {
+                self.supports_parallel_tool_calls = supports_parallel_tool_calls;
+                self.preset_supports_parallel_tool_calls = Some(supports_parallel_tool_calls);
+            }
+            self.preset_supports_reasoning_summaries = preset.supports_reasoning_summaries;
+            if let Some(supports_reasoning) = preset.supports_reasoning {
+                self.preset_supports_reasoning = Some(supports_reasoning);
+            }
+            if let Some(format) = preset.reasoning_summary_format {
+                self.reasoning_summary_format = format;
+            }
+            if let Some(stateful) = preset.supports_stateful_responses {
+                self.supports_stateful_responses = Some(stateful);
+            }
+            if let Some(max_concurrent_requests) = preset.max_concurrent_requests {
+                self.max_concurrent_requests = Some(max_concurrent_requests);
+            }
+            if let Some(secs) = preset.request_timeout_secs {
+                self.request_timeout = Some(Duration::from_secs(secs));
+            }
+            if let Some(secs) = preset.stream_idle_timeout_secs {
+                self.stream_idle_timeout = Some(Duration::from_secs(secs));
+            }
+            if let Some(addendum) = &preset.instructions_addendum {
+                self.instructions_addendum = Some(addendum.clone());
+            }
+            if let Some(wire_api) = preset.wire_api {
+                self.wire_api = Some(wire_api);
+            }
+            if let Some(headers) = &preset.extra_request_headers {
+                self.extra_request_headers
+                    .extend(sanitize_request_headers(&self.slug, headers));
+            }
+            if let Some(strategy) = preset.prompt_cache_key_strategy {
+                self.prompt_cache_key_strategy = strategy;
+            }
+            if let Some(fallbacks) = &preset.fallback_models {
+                self.fallback_models = fallbacks.clone();
+            }
+            self.disable_tools(&preset.disabled_tools);
+            self.set_preset_tool_hints(preset);
+        }
         self
     }
+
     pub(super) fn with_remote_overrides(mut self, remote_models: Vec<ModelInfo>) -> Self {
         for model in remote_models {
             if model.slug == self.slug {
@@ -104,24 +429,50 @@ impl ModelFamily {
     fn apply_remote_overrides(&mut self, model: ModelInfo) {
         let ModelInfo {
             slug: _,
+            preset_id: _,
             display_name: _,
             description: _,
             default_reasoning_level,
-            supported_reasoning_levels: _,
+            supported_reasoning_levels,
             shell_type,
             visibility: _,
+            is_default: _,
             supported_in_api: _,
             priority: _,
             upgrade: _,
             base_instructions,
+            instructions_addendum,
+            wire_api,
             supports_reasoning_summaries,
+            supports_reasoning,
+            reasoning_summary_format,
+            supports_stateful_responses,
             support_verbosity,
             default_verbosity,
             apply_patch_tool_type,
             truncation_policy,
             supports_parallel_tool_calls,
             context_window,
+            context_window_unit: _,
+            context_window_source: _,
+            max_output_tokens,
+            supports_temperature,
+            default_temperature,
+            supports_top_p,
+            default_top_p,
+            supports_images,
+            requires_network: _,
+            max_concurrent_requests,
+            request_headers,
+            prompt_cache_key_strategy,
+            fallback_models,
+            disabled_tools,
+            prefer_apply_patch_freeform,
+            enable_web_search,
+            knowledge_cutoff: _,
+            released_at: _,
             experimental_supported_tools,
+            extra: _,
         } = model;
 
         self.default_reasoning_effort = Some(default_reasoning_level);
@@ -129,23 +480,304 @@ impl ModelFamily {
         if let Some(base) = base_instructions {
             self.base_instructions = base;
         }
+        if let Some(addendum) = instructions_addendum {
+            self.instructions_addendum = Some(addendum);
+        }
+        if let Some(wire_api) = wire_api {
+            self.wire_api = Some(wire_api);
+        }
         self.supports_reasoning_summaries = supports_reasoning_summaries;
+        if let Some(supports_reasoning) = supports_reasoning {
+            self.preset_supports_reasoning = Some(supports_reasoning);
+        }
+        if let Some(format) = reasoning_summary_format {
+            self.reasoning_summary_format = format;
+        }
+        if let Some(stateful) = supports_stateful_responses {
+            self.supports_stateful_responses = Some(stateful);
+        }
         self.support_verbosity = support_verbosity;
         self.default_verbosity = default_verbosity;
         self.apply_patch_tool_type = apply_patch_tool_type;
         self.truncation_policy = truncation_policy.into();
         self.supports_parallel_tool_calls = supports_parallel_tool_calls;
+        self.preset_supports_parallel_tool_calls = Some(supports_parallel_tool_calls);
         self.context_window = context_window;
+        self.max_output_tokens = max_output_tokens;
+        if let Some(supports_temperature) = supports_temperature {
+            self.supports_temperature = supports_temperature;
+        }
+        self.default_temperature = default_temperature;
+        if let Some(supports_top_p) = supports_top_p {
+            self.supports_top_p = supports_top_p;
+        }
+        self.default_top_p = default_top_p;
+        self.supports_images = supports_images;
+        self.max_concurrent_requests = max_concurrent_requests;
+        self.set_effort_levels(&supported_reasoning_levels);
+        if let Some(headers) = request_headers {
+            self.extra_request_headers
+                .extend(sanitize_request_headers(&self.slug, &headers));
+        }
+        if let Some(strategy) = prompt_cache_key_strategy {
+            self.prompt_cache_key_strategy = strategy;
+        }
+        if let Some(fallbacks) = fallback_models {
+            self.fallback_models = fallbacks;
+        }
         self.experimental_supported_tools = experimental_supported_tools;
+        self.disable_tools(&disabled_tools);
+        if let Some(freeform) = prefer_apply_patch_freeform {
+            self.preset_prefers_apply_patch_freeform = Some(freeform);
+        }
+        if let Some(web_search) = enable_web_search {
+            self.preset_enables_web_search = Some(web_search);
+        }
+    }
+
+    /// Take the effort levels and the reasoning, reasoning summary format,
+    /// stateful response, and tool hints from the preset the active provider's
+    /// catalog serves this model with. Catalog presets that list no efforts
+    /// have their models sent no effort.
+    pub(super) fn with_catalog_preset(mut self, preset: Option<&ModelPreset>) -> Self {
+        if let Some(preset) = preset {
+            self.set_effort_levels(&preset.supported_reasoning_efforts);
+            if let Some(supports_reasoning) = preset.supports_reasoning {
+                self.preset_supports_reasoning = Some(supports_reasoning);
+            }
+            if let Some(format) = preset.reasoning_summary_format {
+                self.reasoning_summary_format = format;
+            }
+            if let Some(stateful) = preset.supports_stateful_responses {
+                self.supports_stateful_responses = Some(stateful);
+            }
+            self.set_preset_tool_hints(preset);
+        }
+        self
+    }
+
+    fn set_preset_tool_hints(&mut self, preset: &ModelPreset) {
+        if let Some(freeform) = preset.prefer_apply_patch_freeform {
+            self.preset_prefers_apply_patch_freeform = Some(freeform);
+        }
+        if let Some(web_search) = preset.enable_web_search {
+            self.preset_enables_web_search = Some(web_search);
+        }
+    }
+
+    fn set_effort_levels(&mut self, efforts: &[ReasoningEffortPreset]) {
+        self.supports_reasoning_effort = !efforts.is_empty();
+        self.reasoning_efforts = efforts.iter().map(|preset| preset.effort).collect();
+        self.effort_max_output_tokens = efforts
+            .iter()
+            .filter_map(|preset| preset.max_output_tokens.map(|cap| (preset.effort, cap)))
+            .collect();
+        self.effort_request_timeouts = efforts
+            .iter()
+            .filter_map(|preset| {
+                preset
+                    .request_timeout_secs
+                    .map(|secs| (preset.effort, Duration::from_secs(secs)))
+            })
+            .collect();
+        self.effort_stream_idle_timeouts = efforts
+            .iter()
+            .filter_map(|preset| {
+                preset
+                    .stream_idle_timeout_secs
+                    .map(|secs| (preset.effort, Duration::from_secs(secs)))
+            })
+            .collect();
+        self.effort_descriptions = efforts
+            .iter()
+            .filter(|preset| !preset.description.trim().is_empty())
+            .map(|preset| (preset.effort, preset.description.clone()))
+            .collect();
+    }
+
+    /// Whether requests to this model carry `reasoning`: not for models
+    /// without reasoning support, nor for those their preset says take no
+    /// reasoning parameters.
+    pub fn sends_reasoning(&self) -> bool {
+        self.supports_reasoning_summaries && self.preset_supports_reasoning != Some(false)
+    }
+
+    /// Whether requests leave out the effort whatever is configured, because
+    /// [`Self::sends_reasoning`] is false or the preset lists no efforts.
+    pub fn ignores_reasoning_effort(&self) -> bool {
+        !self.sends_reasoning() || !self.supports_reasoning_effort
+    }
+
+    /// The effort requests carry when the session is configured with
+    /// `configured`: the family default when unset, and none when the model
+    /// [ignores](Self::ignores_reasoning_effort) it.
+    pub fn request_reasoning_effort(
+        &self,
+        configured: Option<ReasoningEffort>,
+    ) -> Option<ReasoningEffort> {
+        if self.ignores_reasoning_effort() {
+            return None;
+        }
+        configured.or(self.default_reasoning_effort)
+    }
+
+    /// Description of the effort a request is made at, falling back to the
+    /// family default like [`Self::resolve_max_output_tokens`].
+    pub fn effort_description(&self, effort: Option<ReasoningEffort>) -> Option<&str> {
+        let effort = effort.or(self.default_reasoning_effort)?;
+        self.effort_descriptions
+            .iter()
+            .find(|(candidate, _)| *candidate == effort)
+            .map(|(_, description)| description.as_str())
+    }
+
+    /// Output-token cap for a request made at `effort`.
+    ///
+    /// Resolution order: the cap declared on the effort itself, then
+    /// `configured` (clamped to the model maximum), then the model default.
+    pub fn resolve_max_output_tokens(
+        &self,
+        effort: Option<ReasoningEffort>,
+        configured: Option<i64>,
+    ) -> Option<i64> {
+        let effort_cap = effort.or(self.default_reasoning_effort).and_then(|effort| {
+            self.effort_max_output_tokens
+                .iter()
+                .find(|(candidate, _)| *candidate == effort)
+                .map(|(_, cap)| *cap)
+        });
+        if effort_cap.is_some() {
+            return effort_cap;
+        }
+        match (configured, self.max_output_tokens) {
+            (Some(configured), Some(model_max)) => Some(configured.min(model_max)),
+            (configured, model_max) => configured.or(model_max),
+        }
+    }
+
+    /// How long a whole request at `effort` may take, from sending it until
+    /// its response stream ends, and where that is set. The budget declared
+    /// on the effort itself wins over the preset's; `None` when neither sets
+    /// one, leaving only the stream idle timeout.
+    pub fn resolve_request_timeout(
+        &self,
+        effort: Option<ReasoningEffort>,
+    ) -> Option<(Duration, RequestTimeoutSource)> {
+        effort
+            .or(self.default_reasoning_effort)
+            .and_then(|effort| {
+                self.effort_request_timeouts
+                    .iter()
+                    .find(|(candidate, _)| *candidate == effort)
+                    .map(|(_, timeout)| (*timeout, RequestTimeoutSource::Effort(effort)))
+            })
+            .or_else(|| {
+                self.request_timeout
+                    .map(|timeout| (timeout, RequestTimeoutSource::Preset))
+            })
+    }
+
+    /// How long a streamed response at `effort` may go without an event, and
+    /// which setting decided it: the timeout declared on the effort itself,
+    /// then the preset's, then `provider`'s, then the built-in default.
+    pub fn resolve_stream_idle_timeout(
+        &self,
+        effort: Option<ReasoningEffort>,
+        provider: &ModelProviderInfo,
+    ) -> (Duration, IdleTimeoutSource) {
+        effort
+            .or(self.default_reasoning_effort)
+            .and_then(|effort| {
+                self.effort_stream_idle_timeouts
+                    .iter()
+                    .find(|(candidate, _)| *candidate == effort)
+                    .map(|(_, timeout)| (*timeout, IdleTimeoutSource::Effort(effort)))
+            })
+            .or_else(|| {
+                self.stream_idle_timeout
+                    .map(|timeout| (timeout, IdleTimeoutSource::Preset))
+            })
+            .unwrap_or_else(|| {
+                let source = if provider.stream_idle_timeout_ms.is_some() {
+                    IdleTimeoutSource::Provider
+                } else {
+                    IdleTimeoutSource::Default
+                };
+                (provider.stream_idle_timeout(), source)
+            })
+    }
+
+    /// `temperature` for a request: `configured` when set, otherwise the model
+    /// default. Always `None` when the model does not accept the parameter.
+    pub fn resolve_temperature(&self, configured: Option<f32>) -> Option<f32> {
+        if !self.supports_temperature {
+            return None;
+        }
+        configured.or(self.default_temperature)
+    }
+
+    /// `top_p` for a request, resolved like [`Self::resolve_temperature`].
+    pub fn resolve_top_p(&self, configured: Option<f32>) -> Option<f32> {
+        if !self.supports_top_p {
+            return None;
+        }
+        configured.or(self.default_top_p)
+    }
+
+    /// The summary requests carry when the session is configured with
+    /// `configured`: none when it is off or the preset says the model emits
+    /// no summaries.
+    pub fn request_reasoning_summary(
+        &self,
+        configured: ReasoningSummaryConfig,
+    ) -> Option<ReasoningSummaryConfig> {
+        if configured == ReasoningSummaryConfig::None
+            || self.preset_supports_reasoning_summaries == Some(false)
+        {
+            return None;
+        }
+        Some(configured)
+    }
+
+    /// Whether the preset rules out parallel tool calls, overriding the
+    /// `parallel` feature.
+    pub fn preset_forbids_parallel_tool_calls(&self) -> bool {
+        self.preset_supports_parallel_tool_calls == Some(false)
     }
 
     pub fn auto_compact_token_limit(&self) -> Option<i64> {
-        self.auto_compact_token_limit
-            .or(self.context_window.map(Self::default_auto_compact_limit))
+        self.auto_compact_token_limit.or(self
+            .context_window
+            .map(|context_window| self.default_auto_compact_limit(context_window)))
+    }
+
+    /// Most input tokens one summarization request may carry when the
+    /// conversation is compacted: the preset's budget when it sets a positive
+    /// one, the context window otherwise. Compaction splits a history larger
+    /// than this into several passes.
+    pub fn summarization_max_tokens(&self) -> Option<i64> {
+        self.summarization_max_tokens
+            .filter(|tokens| *tokens > 0)
+            .or(self.context_window)
     }
 
-    const fn default_auto_compact_limit(context_window: i64) -> i64 {
-        (context_window * 9) / 10
+    /// The preset's ratio of the context window when it sets a usable one,
+    /// nine tenths otherwise. Computed wide, since catalogs may serve any
+    /// `i64` as the window.
+    fn default_auto_compact_limit(&self, context_window: i64) -> i64 {
+        match self.compaction_threshold_ratio {
+            Some(ratio) if ratio > 0.0 && ratio <= 1.0 => (context_window as f64 * ratio) as i64,
+            _ => (i128::from(context_window) * 9 / 10) as i64,
+        }
+    }
+
+    /// The extra request headers, with `${VAR}` references in the configured
+    /// ones expanded.
+    pub(crate) fn request_header_map(&self) -> http::HeaderMap {
+        build_request_headers(
+            &self.extra_request_headers,
+            &self.configured_request_headers,
+        )
     }
 
     pub fn get_model_slug(&self) -> &str {
@@ -165,17 +797,48 @@ macro_rules! model_family {
             needs_special_apply_patch_instructions: false,
             context_window: Some(CONTEXT_WINDOW_272K),
             auto_compact_token_limit: None,
+            compaction_threshold_ratio: None,
+            summarization_max_tokens: None,
+            max_output_tokens: None,
+            effort_max_output_tokens: Vec::new(),
+            request_timeout: None,
+            effort_request_timeouts: Vec::new(),
+            stream_idle_timeout: None,
+            effort_stream_idle_timeouts: Vec::new(),
+            effort_descriptions: Vec::new(),
+            supports_reasoning_effort: true,
+            reasoning_efforts: Vec::new(),
+            supports_temperature: false,
+            default_temperature: None,
+            supports_top_p: false,
+            default_top_p: None,
             supports_reasoning_summaries: false,
             supports_parallel_tool_calls: false,
+            preset_supports_parallel_tool_calls: None,
+            preset_supports_reasoning_summaries: None,
+            preset_supports_reasoning: None,
+            reasoning_summary_format: ReasoningSummaryFormat::default(),
+            supports_stateful_responses: None,
+            max_concurrent_requests: None,
             apply_patch_tool_type: None,
+            preset_prefers_apply_patch_freeform: None,
+            preset_enables_web_search: None,
             base_instructions: BASE_INSTRUCTIONS.to_string(),
+            instructions_addendum: None,
+            wire_api: None,
+            extra_request_headers: HashMap::new(),
+            configured_request_headers: HashMap::new(),
+            prompt_cache_key_strategy: PromptCacheKeyStrategy::default(),
+            fallback_models: Vec::new(),
             experimental_supported_tools: Vec::new(),
+            disabled_tools: Vec::new(),
             effective_context_window_percent: 95,
             support_verbosity: false,
             shell_type: ConfigShellToolType::Default,
             default_verbosity: None,
             default_reasoning_effort: None,
             truncation_policy: TruncationPolicy::Bytes(10_000),
+            supports_images: true,
         };
 
         // apply overrides
@@ -188,9 +851,14 @@ macro_rules! model_family {
 
 /// Internal offline helper for `ModelsManager` that returns a `ModelFamily` for the given
 /// model slug.
-#[allow(clippy::if_same_then_else)]
 pub(super) fn find_family_for_model(slug: &str) -> ModelFamily {
-    if slug.starts_with("o3") {
+    known_family_for_model(slug).unwrap_or_else(|| derive_default_model_family(slug))
+}
+
+/// Built-in family for `slug`, or `None` when no built-in prefix matches it.
+#[allow(clippy::if_same_then_else)]
+pub(super) fn known_family_for_model(slug: &str) -> Option<ModelFamily> {
+    let family = if slug.starts_with("o3") {
         model_family!(
             slug, "o3",
             supports_reasoning_summaries: true,
@@ -217,24 +885,32 @@ pub(super) fn find_family_for_model(slug: &str) -> ModelFamily {
             slug, "gpt-4.1",
             needs_special_apply_patch_instructions: true,
             context_window: Some(1_047_576),
+            supports_temperature: true,
+            supports_top_p: true,
         )
     } else if slug.starts_with("gpt-oss") || slug.starts_with("openai/gpt-oss") {
         model_family!(
             slug, "gpt-oss",
             apply_patch_tool_type: Some(ApplyPatchToolType::Function),
             context_window: Some(96_000),
+            supports_temperature: true,
+            supports_top_p: true,
         )
     } else if slug.starts_with("gpt-4o") {
         model_family!(
             slug, "gpt-4o",
             needs_special_apply_patch_instructions: true,
             context_window: Some(128_000),
+            supports_temperature: true,
+            supports_top_p: true,
         )
     } else if slug.starts_with("gpt-3.5") {
         model_family!(
             slug, "gpt-3.5",
             needs_special_apply_patch_instructions: true,
             context_window: Some(16_385),
+            supports_temperature: true,
+            supports_top_p: true,
         )
     } else if slug.starts_with("test-gpt-5") {
         model_family!(
@@ -387,8 +1063,9 @@ pub(super) fn find_family_for_model(slug: &str) -> ModelFamily {
             context_window: Some(CONTEXT_WINDOW_272K),
         )
     } else {
-        derive_default_model_family(slug)
-    }
+        return None;
+    };
+    Some(family)
 }
 
 fn derive_default_model_family(model: &str) -> ModelFamily {
@@ -399,23 +1076,55 @@ fn derive_default_model_family(model: &str) -> ModelFamily {
         needs_special_apply_patch_instructions: false,
         context_window: None,
         auto_compact_token_limit: None,
+        compaction_threshold_ratio: None,
+        summarization_max_tokens: None,
+        max_output_tokens: None,
+        effort_max_output_tokens: Vec::new(),
+        request_timeout: None,
+        effort_request_timeouts: Vec::new(),
+        stream_idle_timeout: None,
+        effort_stream_idle_timeouts: Vec::new(),
+        effort_descriptions: Vec::new(),
+        supports_reasoning_effort: true,
+        reasoning_efforts: Vec::new(),
+        supports_temperature: true,
+        default_temperature: None,
+        supports_top_p: true,
+        default_top_p: None,
         supports_reasoning_summaries: false,
         supports_parallel_tool_calls: false,
+        preset_supports_parallel_tool_calls: None,
+        preset_supports_reasoning_summaries: None,
+        preset_supports_reasoning: None,
+        reasoning_summary_format: ReasoningSummaryFormat::default(),
+        supports_stateful_responses: None,
+        max_concurrent_requests: None,
         apply_patch_tool_type: None,
+        preset_prefers_apply_patch_freeform: None,
+        preset_enables_web_search: None,
         base_instructions: BASE_INSTRUCTIONS.to_string(),
+        instructions_addendum: None,
+        wire_api: None,
+        extra_request_headers: HashMap::new(),
+        configured_request_headers: HashMap::new(),
+        prompt_cache_key_strategy: PromptCacheKeyStrategy::default(),
+        fallback_models: Vec::new(),
         experimental_supported_tools: Vec::new(),
+        disabled_tools: Vec::new(),
         effective_context_window_percent: 95,
         support_verbosity: false,
         shell_type: ConfigShellToolType::Default,
         default_verbosity: None,
         default_reasoning_effort: None,
         truncation_policy: TruncationPolicy::Bytes(10_000),
+        supports_images: true,
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::models_manager::model_presets::builtin_model_presets;
     use codex_protocol::openai_models::ModelVisibility;
     use codex_protocol::openai_models::ReasoningEffortPreset;
     use codex_protocol::openai_models::TruncationPolicyConfig;
@@ -423,27 +1132,56 @@ mod tests {
     fn remote(slug: &str, effort: ReasoningEffort, shell: ConfigShellToolType) -> ModelInfo {
         ModelInfo {
             slug: slug.to_string(),
+            preset_id: None,
             display_name: slug.to_string(),
             description: Some(format!("{slug} desc")),
             default_reasoning_level: effort,
             supported_reasoning_levels: vec![ReasoningEffortPreset {
                 effort,
                 description: effort.to_string(),
+                max_output_tokens: None,
+                request_timeout_secs: None,
+                stream_idle_timeout_secs: None,
             }],
             shell_type: shell,
             visibility: ModelVisibility::List,
+            is_default: false,
             supported_in_api: true,
             priority: 1,
             upgrade: None,
             base_instructions: None,
+            instructions_addendum: None,
+            wire_api: None,
             supports_reasoning_summaries: false,
+            supports_reasoning: None,
+            reasoning_summary_format: None,
+            supports_stateful_responses: None,
             support_verbosity: false,
             default_verbosity: None,
             apply_patch_tool_type: None,
             truncation_policy: TruncationPolicyConfig::bytes(10_000),
             supports_parallel_tool_calls: false,
             context_window: None,
+            context_window_unit: None,
+            context_window_source: None,
+            max_output_tokens: None,
+            supports_temperature: None,
+            default_temperature: None,
+            supports_top_p: None,
+            default_top_p: None,
+            max_concurrent_requests: None,
+            request_headers: None,
+            prompt_cache_key_strategy: None,
+            fallback_models: None,
+            disabled_tools: Vec::new(),
+            prefer_apply_patch_freeform: None,
+            enable_web_search: None,
+            knowledge_cutoff: None,
+            released_at: None,
             experimental_supported_tools: Vec::new(),
+            extra: HashMap::new(),
+            supports_images: true,
+            requires_network: false,
         }
     }
 
@@ -516,6 +1254,9 @@ mod tests {
             supported_reasoning_levels: vec![ReasoningEffortPreset {
                 effort: ReasoningEffort::High,
                 description: "High".to_string(),
+                max_output_tokens: None,
+                request_timeout_secs: None,
+                stream_idle_timeout_secs: None,
             }],
             shell_type: ConfigShellToolType::ShellCommand,
             visibility: ModelVisibility::List,
@@ -523,6 +1264,8 @@ mod tests {
             priority: 10,
             upgrade: None,
             base_instructions: Some("Remote instructions".to_string()),
+            instructions_addendum: None,
+            wire_api: None,
             supports_reasoning_summaries: true,
             support_verbosity: true,
             default_verbosity: Some(Verbosity::High),
@@ -530,7 +1273,31 @@ mod tests {
             truncation_policy: TruncationPolicyConfig::tokens(2_000),
             supports_parallel_tool_calls: true,
             context_window: Some(400_000),
+            context_window_unit: None,
+            context_window_source: None,
+            max_output_tokens: None,
+            supports_temperature: None,
+            default_temperature: None,
+            supports_top_p: None,
+            default_top_p: None,
+            request_headers: None,
             experimental_supported_tools: vec!["alpha".to_string(), "beta".to_string()],
+            extra: HashMap::new(),
+            max_concurrent_requests: None,
+            prompt_cache_key_strategy: None,
+            fallback_models: None,
+            knowledge_cutoff: None,
+            released_at: None,
+            supports_images: true,
+            preset_id: None,
+            requires_network: false,
+            disabled_tools: Vec::new(),
+            reasoning_summary_format: None,
+            is_default: false,
+            supports_stateful_responses: None,
+            supports_reasoning: None,
+            prefer_apply_patch_freeform: None,
+            enable_web_search: None,
         }]);
 
         assert_eq!(
@@ -554,4 +1321,619 @@ mod tests {
         );
         assert_eq!(updated.base_instructions, "Remote instructions");
     }
+
+    #[test]
+    fn remote_overrides_collect_effort_output_caps() {
+        let family = model_family!("gpt-5.1", "gpt-5.1");
+        let mut model = remote(
+            "gpt-5.1",
+            ReasoningEffort::Medium,
+            ConfigShellToolType::Default,
+        );
+        model.max_output_tokens = Some(64_000);
+        model.supported_reasoning_levels = vec![
+            ReasoningEffortPreset {
+                effort: ReasoningEffort::Low,
+                description: "Low".to_string(),
+                max_output_tokens: None,
+                request_timeout_secs: None,
+                stream_idle_timeout_secs: None,
+            },
+            ReasoningEffortPreset {
+                effort: ReasoningEffort::High,
+                description: "High".to_string(),
+                max_output_tokens: Some(128_000),
+                request_timeout_secs: None,
+                stream_idle_timeout_secs: None,
+            },
+        ];
+
+        let updated = family.with_remote_overrides(vec![model]);
+
+        assert_eq!(updated.max_output_tokens, Some(64_000));
+        assert_eq!(
+            updated.effort_max_output_tokens,
+            vec![(ReasoningEffort::High, 128_000)]
+        );
+    }
+
+    #[test]
+    fn effort_descriptions_fall_back_to_the_default_effort() {
+        let family = model_family!(
+            "gpt-5.1",
+            "gpt-5.1",
+            default_reasoning_effort: Some(ReasoningEffort::Medium),
+        );
+        let mut model = remote(
+            "gpt-5.1",
+            ReasoningEffort::Medium,
+            ConfigShellToolType::Default,
+        );
+        model.supported_reasoning_levels = vec![
+            ReasoningEffortPreset {
+                effort: ReasoningEffort::Medium,
+                description: "Balances speed and depth".to_string(),
+                max_output_tokens: None,
+                request_timeout_secs: None,
+                stream_idle_timeout_secs: None,
+            },
+            ReasoningEffortPreset {
+                effort: ReasoningEffort::High,
+                description: " ".to_string(),
+                max_output_tokens: None,
+                request_timeout_secs: None,
+                stream_idle_timeout_secs: None,
+            },
+        ];
+
+        let updated = family.with_remote_overrides(vec![model]);
+
+        assert_eq!(
+            updated.effort_description(None),
+            Some("Balances speed and depth")
+        );
+        assert_eq!(
+            updated.effort_description(Some(ReasoningEffort::High)),
+            None
+        );
+        assert_eq!(updated.effort_description(Some(ReasoningEffort::Low)), None);
+    }
+
+    #[test]
+    fn presets_without_efforts_send_no_effort() {
+        let family = model_family!(
+            "gpt-5.1",
+            "gpt-5.1",
+            supports_reasoning_summaries: true,
+            default_reasoning_effort: Some(ReasoningEffort::Medium),
+        );
+        assert_eq!(
+            family.request_reasoning_effort(Some(ReasoningEffort::High)),
+            Some(ReasoningEffort::High)
+        );
+
+        let mut model = remote(
+            "gpt-5.1",
+            ReasoningEffort::Medium,
+            ConfigShellToolType::Default,
+        );
+        model.supports_reasoning_summaries = true;
+        model.supported_reasoning_levels = Vec::new();
+        let updated = family.with_remote_overrides(vec![model]);
+
+        assert!(updated.supports_reasoning_summaries);
+        assert_eq!(
+            updated.request_reasoning_effort(Some(ReasoningEffort::High)),
+            None
+        );
+        assert_eq!(updated.request_reasoning_effort(None), None);
+    }
+
+    #[test]
+    fn resolve_max_output_tokens_prefers_effort_then_config_then_model() {
+        let family = model_family!(
+            "oca-tuned",
+            "oca-tuned",
+            default_reasoning_effort: Some(ReasoningEffort::Medium),
+            max_output_tokens: Some(32_000),
+            effort_max_output_tokens: vec![
+                (ReasoningEffort::Low, 4_000),
+                (ReasoningEffort::High, 100_000),
+            ],
+        );
+
+        assert_eq!(
+            family.resolve_max_output_tokens(Some(ReasoningEffort::High), Some(8_000)),
+            Some(100_000)
+        );
+        assert_eq!(
+            family.resolve_max_output_tokens(Some(ReasoningEffort::Low), Some(8_000)),
+            Some(4_000)
+        );
+        assert_eq!(
+            family.resolve_max_output_tokens(Some(ReasoningEffort::Medium), Some(8_000)),
+            Some(8_000)
+        );
+        assert_eq!(family.resolve_max_output_tokens(None, None), Some(32_000));
+    }
+
+    #[test]
+    fn resolve_max_output_tokens_uses_family_default_effort() {
+        let family = model_family!(
+            "oca-tuned",
+            "oca-tuned",
+            default_reasoning_effort: Some(ReasoningEffort::High),
+            effort_max_output_tokens: vec![(ReasoningEffort::High, 100_000)],
+        );
+
+        assert_eq!(family.resolve_max_output_tokens(None, None), Some(100_000));
+    }
+
+    #[test]
+    fn resolve_request_timeout_prefers_effort_then_preset() {
+        let family = find_family_for_model("gpt-5.1");
+        assert_eq!(
+            family.resolve_request_timeout(Some(ReasoningEffort::High)),
+            None
+        );
+
+        let mut preset = builtin_model_presets(None).remove(0);
+        preset.request_timeout_secs = Some(600);
+        for effort in &mut preset.supported_reasoning_efforts {
+            if effort.effort == ReasoningEffort::XHigh {
+                effort.request_timeout_secs = Some(1_800);
+            }
+        }
+        let family = family.with_preset_overrides(Some(&preset));
+
+        assert_eq!(
+            family.resolve_request_timeout(Some(ReasoningEffort::XHigh)),
+            Some((
+                Duration::from_secs(1_800),
+                RequestTimeoutSource::Effort(ReasoningEffort::XHigh)
+            ))
+        );
+        assert_eq!(
+            family.resolve_request_timeout(Some(ReasoningEffort::Low)),
+            Some((Duration::from_secs(600), RequestTimeoutSource::Preset))
+        );
+        assert_eq!(
+            RequestTimeoutSource::Effort(ReasoningEffort::High).to_string(),
+            "`request_timeout_secs` on the high effort"
+        );
+        assert_eq!(
+            RequestTimeoutSource::Preset.to_string(),
+            "the model's `request_timeout_secs`"
+        );
+    }
+
+    #[test]
+    fn resolve_request_timeout_uses_family_default_effort() {
+        let family = model_family!(
+            "oca-tuned",
+            "oca-tuned",
+            default_reasoning_effort: Some(ReasoningEffort::High),
+            effort_request_timeouts: vec![(ReasoningEffort::High, Duration::from_secs(900))],
+        );
+
+        assert_eq!(
+            family.resolve_request_timeout(None),
+            Some((
+                Duration::from_secs(900),
+                RequestTimeoutSource::Effort(ReasoningEffort::High)
+            ))
+        );
+    }
+
+    #[test]
+    fn resolve_stream_idle_timeout_prefers_effort_then_preset_then_provider() {
+        let mut provider = ModelProviderInfo::create_openai_provider();
+        let family = find_family_for_model("gpt-5.1");
+        assert_eq!(
+            family.resolve_stream_idle_timeout(Some(ReasoningEffort::High), &provider),
+            (provider.stream_idle_timeout(), IdleTimeoutSource::Default)
+        );
+        provider.stream_idle_timeout_ms = Some(30_000);
+        assert_eq!(
+            family.resolve_stream_idle_timeout(Some(ReasoningEffort::High), &provider),
+            (Duration::from_secs(30), IdleTimeoutSource::Provider)
+        );
+
+        let mut preset = builtin_model_presets(None).remove(0);
+        preset.stream_idle_timeout_secs = Some(120);
+        for effort in &mut preset.supported_reasoning_efforts {
+            if effort.effort == ReasoningEffort::XHigh {
+                effort.stream_idle_timeout_secs = Some(600);
+            }
+        }
+        let family = family.with_preset_overrides(Some(&preset));
+
+        assert_eq!(
+            family.resolve_stream_idle_timeout(Some(ReasoningEffort::XHigh), &provider),
+            (
+                Duration::from_secs(600),
+                IdleTimeoutSource::Effort(ReasoningEffort::XHigh)
+            )
+        );
+        assert_eq!(
+            family.resolve_stream_idle_timeout(Some(ReasoningEffort::Low), &provider),
+            (Duration::from_secs(120), IdleTimeoutSource::Preset)
+        );
+        assert_eq!(
+            IdleTimeoutSource::Effort(ReasoningEffort::XHigh).to_string(),
+            "`stream_idle_timeout_secs` on the xhigh effort"
+        );
+        assert_eq!(
+            IdleTimeoutSource::Preset.to_string(),
+            "the model's `stream_idle_timeout_secs`"
+        );
+    }
+
+    #[test]
+    fn resolve_max_output_tokens_clamps_config_to_model_max() {
+        let capped = model_family!(
+            "oca-tuned",
+            "oca-tuned",
+            max_output_tokens: Some(32_000),
+        );
+        assert_eq!(
+            capped.resolve_max_output_tokens(None, Some(64_000)),
+            Some(32_000)
+        );
+
+        let uncapped = model_family!("oca-tuned", "oca-tuned");
+        assert_eq!(
+            uncapped.resolve_max_output_tokens(None, Some(64_000)),
+            Some(64_000)
+        );
+        assert_eq!(uncapped.resolve_max_output_tokens(None, None), None);
+    }
+
+    #[test]
+    fn sampling_is_omitted_for_models_that_reject_it() {
+        let family = model_family!(
+            "gpt-5.1",
+            "gpt-5.1",
+            default_temperature: Some(0.7),
+            default_top_p: Some(0.9),
+        );
+        assert_eq!(family.resolve_temperature(Some(0.2)), None);
+        assert_eq!(family.resolve_top_p(Some(0.5)), None);
+        assert_eq!(family.resolve_temperature(None), None);
+    }
+
+    #[test]
+    fn configured_sampling_overrides_model_defaults() {
+        let family = model_family!(
+            "oca-chat",
+            "oca-chat",
+            supports_temperature: true,
+            default_temperature: Some(0.7),
+            supports_top_p: true,
+        );
+        assert_eq!(family.resolve_temperature(None), Some(0.7));
+        assert_eq!(family.resolve_temperature(Some(0.2)), Some(0.2));
+        assert_eq!(family.resolve_top_p(None), None);
+        assert_eq!(family.resolve_top_p(Some(0.5)), Some(0.5));
+    }
+
+    #[test]
+    fn preset_sampling_flags_override_family() {
+        let family = find_family_for_model("gpt-4o");
+        assert!(family.supports_temperature);
+
+        let mut preset = builtin_model_presets(None).remove(0);
+        preset.default_top_p = Some(0.8);
+        preset.supports_temperature = Some(false);
+        preset.supports_top_p = Some(true);
+        let updated = family.with_preset_overrides(Some(&preset));
+
+        assert!(!updated.supports_temperature);
+        assert_eq!(updated.resolve_temperature(Some(0.2)), None);
+        assert_eq!(updated.resolve_top_p(None), Some(0.8));
+    }
+
+    #[test]
+    fn unset_sampling_flags_keep_the_family_answer() {
+        let family = find_family_for_model("acme-chat");
+        assert!(family.supports_temperature);
+        assert!(family.supports_top_p);
+
+        let updated = family.with_remote_overrides(vec![remote(
+            "acme-chat",
+            ReasoningEffort::Medium,
+            ConfigShellToolType::ShellCommand,
+        )]);
+        let mut preset = builtin_model_presets(None).remove(0);
+        preset.supports_temperature = None;
+        preset.supports_top_p = None;
+        let updated = updated.with_preset_overrides(Some(&preset));
+
+        assert_eq!(updated.resolve_temperature(Some(0.2)), Some(0.2));
+        assert_eq!(updated.resolve_top_p(Some(0.5)), Some(0.5));
+    }
+
+    #[test]
+    fn preset_parallel_tool_calls_override_family() {
+        let family = find_family_for_model("gpt-5.1");
+        assert!(family.supports_parallel_tool_calls);
+
+        let mut preset = builtin_model_presets(None).remove(0);
+        preset.supports_parallel_tool_calls = Some(false);
+        let forbidden = family.clone().with_preset_overrides(Some(&preset));
+        assert!(!forbidden.supports_parallel_tool_calls);
+        assert!(forbidden.preset_forbids_parallel_tool_calls());
+
+        preset.supports_parallel_tool_calls = None;
+        let unset = family.with_preset_overrides(Some(&preset));
+        assert!(unset.supports_parallel_tool_calls);
+        assert!(!unset.preset_forbids_parallel_tool_calls());
+    }
+
+    #[test]
+    fn disabled_efforts_leave_the_family_what_they_leave_the_preset() {
+        let preset = builtin_model_presets(None).remove(0);
+        let offered: Vec<ReasoningEffort> = preset
+            .supported_reasoning_efforts
+            .iter()
+            .map(|option| option.effort)
+            .collect();
+        let mut family = find_family_for_model(&preset.model).with_preset_overrides(Some(&preset));
+        family.default_reasoning_effort = Some(preset.default_reasoning_effort);
+
+        let disabled = [preset.default_reasoning_effort];
+        let mut limited_preset = preset.clone();
+        limited_preset.disable_efforts(&disabled);
+        let mut limited = family.clone();
+        limited.disable_efforts(&disabled);
+        assert_eq!(
+            limited.default_reasoning_effort,
+            Some(limited_preset.default_reasoning_effort)
+        );
+        assert_eq!(
+            limited.reasoning_efforts,
+            limited_preset
+                .supported_reasoning_efforts
+                .iter()
+                .map(|option| option.effort)
+                .collect::<Vec<_>>()
+        );
+
+        // With every effort disabled the preset lists none, and the model is
+        // sent none.
+        let mut bare_preset = preset;
+        bare_preset.disable_efforts(&offered);
+        assert!(bare_preset.supported_reasoning_efforts.is_empty());
+        let mut bare = family;
+        bare.disable_efforts(&offered);
+        assert!(bare.ignores_reasoning_effort());
+        assert_eq!(bare.default_reasoning_effort, None);
+        assert_eq!(bare.request_reasoning_effort(None), None);
+    }
+
+    #[test]
+    fn disabled_tools_add_up_across_preset_catalog_and_config() {
+        let mut preset = builtin_model_presets(None).remove(0);
+        preset.disabled_tools = vec!["web_search".to_string()];
+        let catalog_entry = ModelInfo {
+            disabled_tools: vec!["view_image".to_string(), "web_search".to_string()],
+            ..remote(
+                &preset.model,
+                ReasoningEffort::Medium,
+                ConfigShellToolType::ShellCommand,
+            )
+        };
+        let overrides = ModelPresetOverride {
+            disabled_tools: vec!["apply_patch".to_string()],
+            ..Default::default()
+        };
+
+        let family = find_family_for_model(&preset.model)
+            .with_preset_overrides(Some(&preset))
+            .with_remote_overrides(vec![catalog_entry])
+            .with_preset_copy(Some(&overrides));
+
+        assert_eq!(
+            family.disabled_tools,
+            vec![
+                "web_search".to_string(),
+                "view_image".to_string(),
+                "apply_patch".to_string(),
+            ]
+        );
+        assert!(
+            find_family_for_model(&preset.model)
+                .disabled_tools
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn tool_hints_come_from_the_preset_and_its_catalog_entry() {
+        let mut preset = builtin_model_presets(None).remove(0);
+        preset.prefer_apply_patch_freeform = Some(false);
+        let mut catalog_preset = preset.clone();
+        catalog_preset.prefer_apply_patch_freeform = None;
+        catalog_preset.enable_web_search = Some(true);
+
+        let family = find_family_for_model(&preset.model)
+            .with_preset_overrides(Some(&preset))
+            .with_catalog_preset(Some(&catalog_preset));
+
+        assert_eq!(family.preset_prefers_apply_patch_freeform, Some(false));
+        assert_eq!(family.preset_enables_web_search, Some(true));
+        let plain = find_family_for_model(&preset.model);
+        assert_eq!(plain.preset_prefers_apply_patch_freeform, None);
+        assert_eq!(plain.preset_enables_web_search, None);
+    }
+
+    #[test]
+    fn tool_hints_come_from_the_remote_catalog() {
+        let family = find_family_for_model("gpt-5.1");
+        let mut model = remote(
+            "gpt-5.1",
+            ReasoningEffort::Medium,
+            ConfigShellToolType::Default,
+        );
+        model.prefer_apply_patch_freeform = Some(true);
+        model.enable_web_search = Some(false);
+
+        let updated = family.clone().with_remote_overrides(vec![model.clone()]);
+        assert_eq!(updated.preset_prefers_apply_patch_freeform, Some(true));
+        assert_eq!(updated.preset_enables_web_search, Some(false));
+
+        // An entry without hints leaves the preset's in place.
+        model.prefer_apply_patch_freeform = None;
+        model.enable_web_search = None;
+        let mut preset = builtin_model_presets(None).remove(0);
+        preset.enable_web_search = Some(true);
+        let updated = family
+            .with_preset_overrides(Some(&preset))
+            .with_remote_overrides(vec![model]);
+        assert_eq!(updated.preset_enables_web_search, Some(true));
+    }
+
+    #[test]
+    fn presets_without_summaries_keep_the_effort_but_drop_the_summary() {
+        let family = find_family_for_model("gpt-5.1");
+        let mut preset = builtin_model_presets(None).remove(0);
+
+        preset.supports_reasoning_summaries = Some(false);
+        let unsupported = family.clone().with_preset_overrides(Some(&preset));
+        assert_eq!(
+            unsupported.request_reasoning_summary(ReasoningSummaryConfig::Detailed),
+            None
+        );
+        assert_eq!(
+            unsupported.request_reasoning_effort(Some(ReasoningEffort::High)),
+            Some(ReasoningEffort::High)
+        );
+
+        preset.supports_reasoning_summaries = None;
+        let unknown = family.with_preset_overrides(Some(&preset));
+        assert_eq!(
+            unknown.request_reasoning_summary(ReasoningSummaryConfig::Detailed),
+            Some(ReasoningSummaryConfig::Detailed)
+        );
+        assert_eq!(
+            unknown.request_reasoning_summary(ReasoningSummaryConfig::None),
+            None
+        );
+    }
+
+    #[test]
+    fn presets_without_reasoning_send_no_reasoning_at_all() {
+        let family = find_family_for_model("gpt-5.1");
+        assert!(family.sends_reasoning());
+        let mut preset = builtin_model_presets(None).remove(0);
+
+        preset.supports_reasoning = Some(false);
+        let plain = family.clone().with_preset_overrides(Some(&preset));
+        assert!(!plain.sends_reasoning());
+        assert!(plain.ignores_reasoning_effort());
+        assert_eq!(
+            plain.request_reasoning_effort(Some(ReasoningEffort::High)),
+            None
+        );
+
+        let mut model = remote(
+            "gpt-5.1",
+            ReasoningEffort::Medium,
+            ConfigShellToolType::Default,
+        );
+        model.supports_reasoning_summaries = true;
+        model.supports_reasoning = Some(false);
+        assert!(!family.with_remote_overrides(vec![model]).sends_reasoning());
+    }
+
+    #[test]
+    fn preset_prompt_cache_key_strategy_overrides_the_default() {
+        let family = find_family_for_model("gpt-5.1");
+        assert_eq!(
+            family.prompt_cache_key_strategy,
+            PromptCacheKeyStrategy::PerConversation
+        );
+
+        let mut preset = builtin_model_presets(None).remove(0);
+        preset.prompt_cache_key_strategy = Some(PromptCacheKeyStrategy::PerUser);
+        let per_user = family.clone().with_preset_overrides(Some(&preset));
+        assert_eq!(
+            per_user.prompt_cache_key_strategy,
+            PromptCacheKeyStrategy::PerUser
+        );
+
+        preset.prompt_cache_key_strategy = None;
+        let unset = family.with_preset_overrides(Some(&preset));
+        assert_eq!(
+            unset.prompt_cache_key_strategy,
+            PromptCacheKeyStrategy::PerConversation
+        );
+    }
+
+    #[test]
+    fn preset_compaction_ratio_scales_the_threshold() {
+        let family = find_family_for_model("gpt-5.1");
+        assert_eq!(family.auto_compact_token_limit(), Some(244_800));
+
+        let mut preset = builtin_model_presets(None).remove(0);
+        preset.context_window = Some(32_000);
+        preset.compaction_threshold_ratio = Some(0.5);
+        let small = family.clone().with_preset_overrides(Some(&preset));
+        assert_eq!(small.context_window, Some(32_000));
+        assert_eq!(small.auto_compact_token_limit(), Some(16_000));
+
+        preset.compaction_threshold_ratio = Some(1.5);
+        let invalid = family.clone().with_preset_overrides(Some(&preset));
+        assert_eq!(invalid.auto_compact_token_limit(), Some(28_800));
+
+        preset.context_window = None;
+        preset.compaction_threshold_ratio = None;
+        let unset = family.with_preset_overrides(Some(&preset));
+        assert_eq!(unset.auto_compact_token_limit(), Some(244_800));
+    }
+
+    #[test]
+    fn summarization_budget_defaults_to_the_context_window() {
+        let mut preset = builtin_model_presets(None).remove(0);
+        preset.context_window = Some(8_000);
+        let family = find_family_for_model("gpt-5.1").with_preset_overrides(Some(&preset));
+        assert_eq!(family.summarization_max_tokens(), Some(8_000));
+
+        preset.summarization_max_tokens = Some(3_000);
+        let hinted = find_family_for_model("gpt-5.1").with_preset_overrides(Some(&preset));
+        assert_eq!(hinted.summarization_max_tokens(), Some(3_000));
+
+        preset.summarization_max_tokens = Some(0);
+        let invalid = find_family_for_model("gpt-5.1").with_preset_overrides(Some(&preset));
+        assert_eq!(invalid.summarization_max_tokens(), Some(8_000));
+    }
+
+    #[test]
+    fn the_largest_context_window_does_not_overflow_the_threshold() {
+        let mut preset = builtin_model_presets(None).remove(0);
+        preset.context_window = Some(i64::MAX);
+        let family = find_family_for_model("gpt-5.1").with_preset_overrides(Some(&preset));
+        assert_eq!(
+            family.auto_compact_token_limit(),
+            Some(8_301_034_833_169_298_226)
+        );
+    }
+
+    #[test]
+    fn preset_concurrency_cap_overrides_family() {
+        let family = find_family_for_model("gpt-5.1");
+        assert_eq!(family.max_concurrent_requests, None);
+
+        let mut preset = builtin_model_presets(None).remove(0);
+        preset.max_concurrent_requests = Some(4);
+        let capped = family.with_preset_overrides(Some(&preset));
+        assert_eq!(capped.max_concurrent_requests, Some(4));
+    }
+
+    #[test]
+    fn unknown_models_accept_sampling() {
+        let family = find_family_for_model("oca-large-chat");
+        assert_eq!(family.resolve_temperature(Some(0.3)), Some(0.3));
+        assert_eq!(family.resolve_top_p(Some(0.9)), Some(0.9));
+    }
 }
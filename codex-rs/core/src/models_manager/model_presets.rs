@@ -1,324 +1,178 @@
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::sync::LazyLock;
+
 use codex_app_server_protocol::AuthMode;
 use codex_protocol::openai_models::ModelPreset;
 use codex_protocol::openai_models::ModelUpgrade;
-use codex_protocol::openai_models::ReasoningEffort;
-use codex_protocol::openai_models::ReasoningEffortPreset;
-use once_cell::sync::Lazy;
+use serde::Deserialize;
+
+use crate::models_manager::catalog::CatalogLoadOptions;
+use crate::models_manager::catalog::PresetCatalog;
 
 pub const HIDE_GPT5_1_MIGRATION_PROMPT_CONFIG: &str = "hide_gpt5_1_migration_prompt";
 pub const HIDE_GPT_5_1_CODEX_MAX_MIGRATION_PROMPT_CONFIG: &str =
     "hide_gpt-5.1-codex-max_migration_prompt";
 
-static PRESETS: Lazy<Vec<ModelPreset>> = Lazy::new(|| {
-    vec![
-        ModelPreset {
-            id: "gpt-5.2-codex".to_string(),
-            model: "gpt-5.2-codex".to_string(),
-            display_name: "gpt-5.2-codex".to_string(),
-            description: "Latest frontier agentic coding model.".to_string(),
-            default_reasoning_effort: ReasoningEffort::Medium,
-            supported_reasoning_efforts: vec![
-                ReasoningEffortPreset {
-                    effort: ReasoningEffort::Low,
-                    description: "Fast responses with lighter reasoning".to_string(),
-                },
-                ReasoningEffortPreset {
-                    effort: ReasoningEffort::Medium,
-                    description: "Balances speed and reasoning depth for everyday tasks".to_string(),
-                },
-                ReasoningEffortPreset {
-                    effort: ReasoningEffort::High,
-                    description: "Greater reasoning depth for complex problems".to_string(),
-                },
-                ReasoningEffortPreset {
-                    effort: ReasoningEffort::XHigh,
-                    description: "Extra high reasoning depth for complex problems".to_string(),
-                },
-            ],
-            is_default: true,
-            upgrade: None,
-            show_in_picker: true,
-            supported_in_api: false,
-        },
-        ModelPreset {
-            id: "gpt-5.1-codex-max".to_string(),
-            model: "gpt-5.1-codex-max".to_string(),
-            display_name: "gpt-5.1-codex-max".to_string(),
-            description: "Codex-optimized flagship for deep and fast reasoning.".to_string(),
-            default_reasoning_effort: ReasoningEffort::Medium,
-            supported_reasoning_efforts: vec![
-                ReasoningEffortPreset {
-                    effort: ReasoningEffort::Low,
-                    description: "Fast responses with lighter reasoning".to_string(),
-                },
-                ReasoningEffortPreset {
-                    effort: ReasoningEffort::Medium,
-                    description: "Balances speed and reasoning depth for everyday tasks".to_string(),
-                },
-                ReasoningEffortPreset {
-                    effort: ReasoningEffort::High,
-                    description: "Greater reasoning depth for complex problems".to_string(),
-                },
-                ReasoningEffortPreset {
-                    effort: ReasoningEffort::XHigh,
-                    description: "Extra high reasoning depth for complex problems".to_string(),
-                },
-            ],
-            is_default: false,
-            upgrade: Some(gpt_52_codex_upgrade()),
-            show_in_picker: true,
-            supported_in_api: true,
-        },
-        ModelPreset {
-            id: "gpt-5.1-codex-mini".to_string(),
-            model: "gpt-5.1-codex-mini".to_string(),
-            display_name: "gpt-5.1-codex-mini".to_string(),
-            description: "Optimized for codex. Cheaper, faster, but less capable.".to_string(),
-            default_reasoning_effort: ReasoningEffort::Medium,
-            supported_reasoning_efforts: vec![
-                ReasoningEffortPreset {
-                    effort: ReasoningEffort::Medium,
-                    description: "Dynamically adjusts reasoning based on the task".to_string(),
-                },
-                ReasoningEffortPreset {
-                    effort: ReasoningEffort::High,
-                    description: "Maximizes reasoning depth for complex or ambiguous problems"
-                        .to_string(),
-                },
-            ],
-            is_default: false,
-            upgrade: Some(gpt_52_codex_upgrade()),
-            show_in_picker: true,
-            supported_in_api: true,
-        },
-        ModelPreset {
-            id: "gpt-5.2".to_string(),
-            model: "gpt-5.2".to_string(),
-            display_name: "gpt-5.2".to_string(),
-            description: "Latest frontier model with improvements across knowledge, reasoning and coding".to_string(),
-            default_reasoning_effort: ReasoningEffort::Medium,
-            supported_reasoning_efforts: vec![
-                ReasoningEffortPreset {
-                    effort: ReasoningEffort::Low,
-                    description: "Balances speed with some reasoning; useful for straightforward queries and short explanations".to_string(),
-                },
-                ReasoningEffortPreset {
-                    effort: ReasoningEffort::Medium,
-                    description: "Provides a solid balance of reasoning depth and latency for general-purpose tasks".to_string(),
-                },
-                ReasoningEffortPreset {
-                    effort: ReasoningEffort::High,
-                    description: "Maximizes reasoning depth for complex or ambiguous problems".to_string(),
-                },
-                ReasoningEffortPreset {
-                    effort: ReasoningEffort::XHigh,
-                    description: "Extra high reasoning for complex problems".to_string(),
-                },
-            ],
-            is_default: false,
-            upgrade: Some(gpt_52_codex_upgrade()),
-            show_in_picker: true,
-            supported_in_api: true,
-        },
-        ModelPreset {
-            id: "bengalfox".to_string(),
-            model: "bengalfox".to_string(),
-            display_name: "bengalfox".to_string(),
-            description: "bengalfox".to_string(),
-            default_reasoning_effort: ReasoningEffort::Medium,
-            supported_reasoning_efforts: vec![
-                ReasoningEffortPreset {
-                    effort: ReasoningEffort::Low,
-                    description: "Fast responses with lighter reasoning".to_string(),
-                },
-                ReasoningEffortPreset {
-                    effort: ReasoningEffort::Medium,
-                    description: "Balances speed and reasoning depth for everyday tasks".to_string(),
-                },
-                ReasoningEffortPreset {
-                    effort: ReasoningEffort::High,
-                    description: "Greater reasoning depth for complex problems".to_string(),
-                },
-                ReasoningEffortPreset {
-                    effort: ReasoningEffort::XHigh,
-                    description: "Extra high reasoning depth for complex problems".to_string(),
-                },
-            ],
-            is_default: false,
-            upgrade: None,
-            show_in_picker: false,
-            supported_in_api: true,
-        },
-        ModelPreset {
-            id: "boomslang".to_string(),
-            model: "boomslang".to_string(),
-            display_name: "boomslang".to_string(),
-            description: "boomslang".to_string(),
-            default_reasoning_effort: ReasoningEffort::Medium,
-            supported_reasoning_efforts: vec![
-                ReasoningEffortPreset {
-                    effort: ReasoningEffort::Low,
-                    description: "Balances speed with some reasoning; useful for straightforward queries and short explanations".to_string(),
-                },
-                ReasoningEffortPreset {
-                    effort: ReasoningEffort::Medium,
-                    description: "Provides a solid balance of reasoning depth and latency for general-purpose tasks".to_string(),
-                },
-                ReasoningEffortPreset {
-                    effort: ReasoningEffort::High,
-                    description: "Maximizes reasoning depth for complex or ambiguous problems".to_string(),
-                },
-                ReasoningEffortPreset {
-                    effort: ReasoningEffort::XHigh,
-                    description: "Extra high reasoning for complex problems".to_string(),
-                },
-            ],
-            is_default: false,
-            upgrade: None,
-            show_in_picker: false,
-            supported_in_api: true,
-        },
-        // Deprecated models.
-        ModelPreset {
-            id: "gpt-5-codex".to_string(),
-            model: "gpt-5-codex".to_string(),
-            display_name: "gpt-5-codex".to_string(),
-            description: "Optimized for codex.".to_string(),
-            default_reasoning_effort: ReasoningEffort::Medium,
-            supported_reasoning_efforts: vec![
-                ReasoningEffortPreset {
-                    effort: ReasoningEffort::Low,
-                    description: "Fastest responses with limited reasoning".to_string(),
-                },
-                ReasoningEffortPreset {
-                    effort: ReasoningEffort::Medium,
-                    description: "Dynamically adjusts reasoning based on the task".to_string(),
-                },
-                ReasoningEffortPreset {
-                    effort: ReasoningEffort::High,
-                    description: "Maximizes reasoning depth for complex or ambiguous problems".to_string(),
-                },
-            ],
-            is_default: false,
-            upgrade: Some(gpt_52_codex_upgrade()),
-            show_in_picker: false,
-            supported_in_api: true,
-        },
-        ModelPreset {
-            id: "gpt-5-codex-mini".to_string(),
-            model: "gpt-5-codex-mini".to_string(),
-            display_name: "gpt-5-codex-mini".to_string(),
-            description: "Optimized for codex. Cheaper, faster, but less capable.".to_string(),
-            default_reasoning_effort: ReasoningEffort::Medium,
-            supported_reasoning_efforts: vec![
-                ReasoningEffortPreset {
-                    effort: ReasoningEffort::Medium,
-                    description: "Dynamically adjusts reasoning based on the task".to_string(),
-                },
-                ReasoningEffortPreset {
-                    effort: ReasoningEffort::High,
-                    description: "Maximizes reasoning depth for complex or ambiguous problems".to_string(),
-                },
-            ],
-            is_default: false,
-            upgrade: Some(gpt_52_codex_upgrade()),
-            show_in_picker: false,
-            supported_in_api: true,
-        },
-        ModelPreset {
-            id: "gpt-5.1-codex".to_string(),
-            model: "gpt-5.1-codex".to_string(),
-            display_name: "gpt-5.1-codex".to_string(),
-            description: "Optimized for codex.".to_string(),
-            default_reasoning_effort: ReasoningEffort::Medium,
-            supported_reasoning_efforts: vec![
-                ReasoningEffortPreset {
-                    effort: ReasoningEffort::Low,
-                    description: "Fastest responses with limited reasoning".to_string(),
-                },
-                ReasoningEffortPreset {
-                    effort: ReasoningEffort::Medium,
-                    description: "Dynamically adjusts reasoning based on the task".to_string(),
-                },
-                ReasoningEffortPreset {
-                    effort: ReasoningEffort::High,
-                    description: "Maximizes reasoning depth for complex or ambiguous problems"
-                        .to_string(),
-                },
-            ],
-            is_default: false,
-            upgrade: Some(gpt_52_codex_upgrade()),
-            show_in_picker: false,
-            supported_in_api: true,
-        },
-        ModelPreset {
-            id: "gpt-5".to_string(),
-            model: "gpt-5".to_string(),
-            display_name: "gpt-5".to_string(),
-            description: "Broad world knowledge with strong general reasoning.".to_string(),
-            default_reasoning_effort: ReasoningEffort::Medium,
-            supported_reasoning_efforts: vec![
-                ReasoningEffortPreset {
-                    effort: ReasoningEffort::Minimal,
-                    description: "Fastest responses with little reasoning".to_string(),
-                },
-                ReasoningEffortPreset {
-                    effort: ReasoningEffort::Low,
-                    description: "Balances speed with some reasoning; useful for straightforward queries and short explanations".to_string(),
-                },
-                ReasoningEffortPreset {
-                    effort: ReasoningEffort::Medium,
-                    description: "Provides a solid balance of reasoning depth and latency for general-purpose tasks".to_string(),
-                },
-                ReasoningEffortPreset {
-                    effort: ReasoningEffort::High,
-                    description: "Maximizes reasoning depth for complex or ambiguous problems".to_string(),
-                },
-            ],
-            is_default: false,
-            upgrade: Some(gpt_52_codex_upgrade()),
-            show_in_picker: false,
-            supported_in_api: true,
-        },
-        ModelPreset {
-            id: "gpt-5.1".to_string(),
-            model: "gpt-5.1".to_string(),
-            display_name: "gpt-5.1".to_string(),
-            description: "Broad world knowledge with strong general reasoning.".to_string(),
-            default_reasoning_effort: ReasoningEffort::Medium,
-            supported_reasoning_efforts: vec![
-                ReasoningEffortPreset {
-                    effort: ReasoningEffort::Low,
-                    description: "Balances speed with some reasoning; useful for straightforward queries and short explanations".to_string(),
-                },
-                ReasoningEffortPreset {
-                    effort: ReasoningEffort::Medium,
-                    description: "Provides a solid balance of reasoning depth and latency for general-purpose tasks".to_string(),
-                },
-                ReasoningEffortPreset {
-                    effort: ReasoningEffort::High,
-                    description: "Maximizes reasoning depth for complex or ambiguous problems".to_string(),
-                },
-            ],
-            is_default: false,
-            upgrade: Some(gpt_52_codex_upgrade()),
-            show_in_picker: false,
-            supported_in_api: true,
-        },
-    ]
+// Built-in presets that code picks by name. Each is checked against
+// `model_presets.toml`, so renaming or retiring one of them there fails the
+// tests instead of leaving a stale string behind. Every one of these presets
+// serves the model of the same name, so the ids double as model slugs.
+
+/// Default model for ChatGPT sign-ins.
+pub const DEFAULT_CHATGPT_PRESET_ID: &str = "gpt-5.2-codex";
+/// Default model for API key sign-ins.
+pub const DEFAULT_API_PRESET_ID: &str = "gpt-5.1-codex-max";
+/// Default model for `/review`.
+pub const DEFAULT_REVIEW_PRESET_ID: &str = "gpt-5.1-codex-max";
+/// Model suggested when a conversation nears its rate limits.
+pub const RATE_LIMIT_NUDGE_PRESET_ID: &str = "gpt-5.1-codex-mini";
+
+/// Models whose highest reasoning effort uses up Plus plan rate limits fast
+/// enough that the effort picker warns about it.
+pub const PLUS_RATE_LIMIT_WARNING_PRESET_IDS: [&str; 5] = [
+    "gpt-5.2-codex",
+    "gpt-5.2",
+    "gpt-5.1-codex-max",
+    "gpt-5.1-codex-mini",
+    "gpt-5.1-codex",
+];
+
+/// The presets above, for checking them all at once.
+pub const NAMED_PRESET_IDS: [&str; 4] = [
+    DEFAULT_CHATGPT_PRESET_ID,
+    DEFAULT_API_PRESET_ID,
+    DEFAULT_REVIEW_PRESET_ID,
+    RATE_LIMIT_NUDGE_PRESET_ID,
+];
+
+const PRESETS_TOML: &str = include_str!("../../model_presets.toml");
+
+/// The embedded table is checked by `embedded_presets_are_valid`, so it
+/// cannot fail to parse in a build that passed its tests.
+#[expect(clippy::expect_used)]
+static PRESETS: LazyLock<Vec<ModelPreset>> = LazyLock::new(|| {
+    parse_presets(PRESETS_TOML).expect("model_presets.toml should hold valid presets")
 });
 
-fn gpt_52_codex_upgrade() -> ModelUpgrade {
-    ModelUpgrade {
-        id: "gpt-5.2-codex".to_string(),
-        reasoning_effort_mapping: None,
-        migration_config_key: "gpt-5.2-codex".to_string(),
-        model_link: Some("https://openai.com/index/introducing-gpt-5-2-codex".to_string()),
-        upgrade_copy: Some(
-            "Codex is now powered by gpt-5.2-codex, our latest frontier agentic coding model. It is smarter and faster than its predecessors and capable of long-running project-scale work."
-                .to_string(),
-        ),
+/// Retired ids and slugs, each mapped to the id of the preset replacing it.
+#[expect(clippy::expect_used)]
+static RETIRED: LazyLock<BTreeMap<String, String>> = LazyLock::new(|| {
+    parse_retired(PRESETS_TOML, &PRESETS)
+        .expect("model_presets.toml should hold a valid `[retired]` table")
+});
+
+/// Ids of [`PRESETS`], borrowed from it.
+static PRESET_IDS: LazyLock<Vec<&'static str>> =
+    LazyLock::new(|| PRESETS.iter().map(|preset| preset.id.as_str()).collect());
+
+/// Layout of `model_presets.toml`. Upgrades are shared by many presets, so
+/// each preset names one by key instead of repeating it inline.
+#[derive(Deserialize)]
+struct PresetsFile {
+    #[serde(default)]
+    upgrades: HashMap<String, ModelUpgrade>,
+    presets: Vec<toml::Table>,
+}
+
+fn parse_presets(source: &str) -> Result<Vec<ModelPreset>, String> {
+    let PresetsFile { upgrades, presets } =
+        toml::from_str(source).map_err(|err| err.to_string())?;
+    let presets = presets
+        .into_iter()
+        .enumerate()
+        .map(|(index, mut table)| {
+            let name = table
+                .get("id")
+                .and_then(toml::Value::as_str)
+                .map_or_else(|| format!("#{}", index + 1), str::to_string);
+            let upgrade = match table.remove("upgrade") {
+                None => None,
+                Some(toml::Value::String(key)) => match upgrades.get(&key) {
+                    Some(upgrade) => Some(upgrade.clone()),
+                    None => {
+                        return Err(format!(
+                            "preset `{name}` names unknown upgrade `{key}`; add it under `[upgrades]`"
+                        ));
+                    }
+                },
+                Some(_) => {
+                    return Err(format!(
+                        "preset `{name}`: `upgrade` must be the key of an entry under `[upgrades]`"
+                    ));
+                }
+            };
+            let mut preset: ModelPreset = toml::Value::Table(table)
+                .try_into()
+                .map_err(|err| format!("preset `{name}`: {err}"))?;
+            preset.upgrade = upgrade;
+            Ok(preset)
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    validate_presets(&presets)?;
+    Ok(presets)
+}
+
+#[derive(Deserialize)]
+struct RetiredFile {
+    #[serde(default)]
+    retired: BTreeMap<String, String>,
+}
+
+/// Read the `[retired]` table. Every successor must be a preset, and a
+/// retired value must not still name one, or it would never be reached.
+fn parse_retired(
+    source: &str,
+    presets: &[ModelPreset],
+) -> Result<BTreeMap<String, String>, String> {
+    let RetiredFile { retired } = toml::from_str(source).map_err(|err| err.to_string())?;
+    for (old, successor) in &retired {
+        if presets
+            .iter()
+            .any(|preset| preset.id == *old || preset.model == *old)
+        {
+            return Err(format!(
+                "retired model `{old}` is still a preset; remove it from `[retired]`"
+            ));
+        }
+        if !presets.iter().any(|preset| preset.id == *successor) {
+            return Err(format!(
+                "retired model `{old}` names unknown successor preset `{successor}`"
+            ));
+        }
+    }
+    Ok(retired)
+}
+
+/// Invariants the picker and the migration prompts rely on.
+fn validate_presets(presets: &[ModelPreset]) -> Result<(), String> {
+    let defaults: Vec<&str> = presets
+        .iter()
+        .filter(|preset| preset.is_default)
+        .map(|preset| preset.id.as_str())
+        .collect();
+    if defaults.len() != 1 {
+        let found = if defaults.is_empty() {
+            "none".to_string()
+        } else {
+            defaults.join(", ")
+        };
+        return Err(format!(
+            "exactly one preset must set `is_default = true`, found {found}"
+        ));
+    }
+
+    let mut ids = HashSet::new();
+    for preset in presets {
+        let id = &preset.id;
+        if !ids.insert(id.as_str()) {
+            return Err(format!("preset `{id}` is listed more than once"));
+        }
+        // The per-preset checks `ModelPreset::builder` makes.
+        preset.validate().map_err(|err| err.to_string())?;
     }
+    Ok(())
 }
 
 pub(super) fn builtin_model_presets(_auth_mode: Option<AuthMode>) -> Vec<ModelPreset> {
@@ -329,7 +183,42 @@ pub(super) fn builtin_model_presets(_auth_mode: Option<AuthMode>) -> Vec<ModelPr
         .collect()
 }
 
+/// Every built-in preset, including those hidden from the picker.
+pub(super) fn all_builtin_model_presets() -> Vec<ModelPreset> {
+    PRESETS.clone()
+}
+
+/// Every built-in preset, in table order.
+pub(super) fn builtin_presets() -> &'static [ModelPreset] {
+    &PRESETS
+}
+
+/// Ids of every built-in preset, including those hidden from the picker, in
+/// table order.
+pub fn builtin_preset_ids() -> &'static [&'static str] {
+    &PRESET_IDS
+}
+
+/// Built-in presets shown in the picker, for callers that need the catalog
+/// without a `ModelsManager`, such as `--help` output and shell completions.
+#[deprecated(note = "use `PresetCatalog::static_presets(CatalogLoadOptions::default())`")]
+pub fn builtin_picker_presets() -> impl Iterator<Item = &'static ModelPreset> {
+    PresetCatalog::static_presets(CatalogLoadOptions::default())
+}
+
+/// Slugs of every built-in preset, including those hidden from the picker.
+pub(super) fn builtin_model_slugs() -> impl Iterator<Item = &'static str> {
+    PRESETS.iter().map(|preset| preset.model.as_str())
+}
+
+/// The preset replacing `model` when it names a retired built-in model.
+pub fn retired_model_successor(model: &str) -> Option<&'static ModelPreset> {
+    let successor = RETIRED.get(model)?;
+    PRESETS.iter().find(|preset| preset.id == *successor)
+}
+
 #[cfg(any(test, feature = "test-support"))]
+#[deprecated(note = "use `PresetCatalog::static_presets` with `include_hidden` set")]
 pub fn all_model_presets() -> &'static Vec<ModelPreset> {
     &PRESETS
 }
@@ -337,10 +226,144 @@ pub fn all_model_presets() -> &'static Vec<ModelPreset> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use codex_protocol::openai_models::ReasoningEffort;
+    use pretty_assertions::assert_eq;
+
+    const TWO_PRESETS: &str = r#"
+        [upgrades.next]
+        id = "next"
+        migration_config_key = "next"
+
+        [[presets]]
+        id = "next"
+        model = "next"
+        display_name = "next"
+        description = "Newest."
+        default_reasoning_effort = "medium"
+        is_default = true
+        show_in_picker = true
+        supported_in_api = true
+        supported_reasoning_efforts = [{ effort = "medium", description = "Medium" }]
+
+        [[presets]]
+        id = "old"
+        model = "old"
+        display_name = "old"
+        description = "Older."
+        default_reasoning_effort = "low"
+        is_default = false
+        upgrade = "next"
+        show_in_picker = false
+        supported_in_api = true
+        supported_reasoning_efforts = [{ effort = "low", description = "Low" }]
+    "#;
 
     #[test]
-    fn only_one_default_model_is_configured() {
+    fn embedded_presets_are_valid() {
+        let presets = parse_presets(PRESETS_TOML).unwrap_or_else(|err| panic!("{err}"));
+        assert_eq!(*PRESETS, presets);
         let default_models = PRESETS.iter().filter(|preset| preset.is_default).count();
         assert!(default_models == 1);
+        assert_eq!(
+            parse_retired(PRESETS_TOML, &presets).map(|retired| retired.len()),
+            Ok(RETIRED.len())
+        );
+    }
+
+    #[test]
+    fn named_presets_are_in_the_table() {
+        for id in NAMED_PRESET_IDS
+            .into_iter()
+            .chain(PLUS_RATE_LIMIT_WARNING_PRESET_IDS)
+        {
+            let preset = PRESETS
+                .iter()
+                .find(|preset| preset.id == id)
+                .unwrap_or_else(|| panic!("`{id}` is not a built-in preset"));
+            assert_eq!(preset.model, id);
+        }
+        let default = PRESETS.iter().find(|preset| preset.is_default);
+        assert_eq!(
+            default.map(|preset| preset.id.as_str()),
+            Some(DEFAULT_CHATGPT_PRESET_ID)
+        );
+        assert_eq!(builtin_preset_ids().len(), PRESETS.len());
+    }
+
+    #[test]
+    fn retired_models_resolve_to_their_successor() {
+        let presets = parse_presets(TWO_PRESETS).unwrap_or_else(|err| panic!("{err}"));
+        let source = format!("{TWO_PRESETS}\n[retired]\n\"older\" = \"next\"\n");
+        assert_eq!(
+            parse_retired(&source, &presets),
+            Ok(BTreeMap::from([("older".to_string(), "next".to_string())]))
+        );
+        assert_eq!(
+            parse_retired(&source.replace("\"older\" =", "\"old\" ="), &presets),
+            Err("retired model `old` is still a preset; remove it from `[retired]`".to_string())
+        );
+        assert_eq!(
+            parse_retired(&source.replace("= \"next\"\n", "= \"later\"\n"), &presets),
+            Err("retired model `older` names unknown successor preset `later`".to_string())
+        );
+
+        assert_eq!(
+            retired_model_successor("codex-mini-latest").map(|preset| preset.model.as_str()),
+            Some("gpt-5.1-codex-mini")
+        );
+        assert_eq!(retired_model_successor("gpt-5.1-codex-mini"), None);
+    }
+
+    #[test]
+    fn upgrades_are_resolved_by_key() {
+        let presets = parse_presets(TWO_PRESETS).unwrap_or_else(|err| panic!("{err}"));
+
+        assert_eq!(presets[0].upgrade, None);
+        assert_eq!(
+            presets[1].upgrade,
+            Some(ModelUpgrade {
+                id: "next".to_string(),
+                reasoning_effort_mapping: None,
+                migration_config_key: "next".to_string(),
+                model_link: None,
+                upgrade_copy: None,
+            })
+        );
+        assert_eq!(presets[1].default_reasoning_effort, ReasoningEffort::Low);
+    }
+
+    #[test]
+    fn validation_errors_name_the_offending_preset() {
+        let cases = [
+            (
+                TWO_PRESETS.replace("is_default = false", "is_default = true"),
+                "exactly one preset must set `is_default = true`, found next, old",
+            ),
+            (
+                TWO_PRESETS.replace("id = \"old\"", "id = \"next\""),
+                "preset `next` is listed more than once",
+            ),
+            (
+                TWO_PRESETS.replace(
+                    "default_reasoning_effort = \"low\"",
+                    "default_reasoning_effort = \"high\"",
+                ),
+                "preset `old` defaults to reasoning effort `high`, which is not in its `supported_reasoning_efforts`",
+            ),
+            (
+                TWO_PRESETS.replace(
+                    "default_reasoning_effort = \"low\"",
+                    "default_reasoning_effort = \"low\"\ndefault_reasoning_effort_by_auth = { chatgpt = \"high\" }",
+                ),
+                "preset `old` defaults to reasoning effort `high` for `chatgpt`, which is not in its `supported_reasoning_efforts`",
+            ),
+            (
+                TWO_PRESETS.replace("upgrade = \"next\"", "upgrade = \"later\""),
+                "preset `old` names unknown upgrade `later`; add it under `[upgrades]`",
+            ),
+        ];
+        for (source, expected) in cases {
+            assert_eq!(parse_presets(&source), Err(expected.to_string()));
+        }
     }
 }
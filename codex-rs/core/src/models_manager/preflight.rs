@@ -0,0 +1,113 @@
+//! Cheap reachability probe run before model listing requests so an
+//! unreachable endpoint fails within seconds and callers fall back to cached
+//! or bundled presets instead of waiting out the full request timeout.
+
+use std::time::Duration;
+
+use codex_client::find_pin_mismatch;
+
+use crate::error::CodexErr;
+use crate::error::ConnectionFailedError;
+use crate::error::Result as CoreResult;
+
+/// How long the preflight may take to connect. It bounds only the
+/// connection of the preflight client, not the response to the preflight or
+/// the listing request after it.
+pub(crate) const PREFLIGHT_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Send a `HEAD` to `base_url` with `client`, built by
+/// [`crate::default_client::build_reqwest_client_for_preflight`]. Any HTTP
+/// response, whatever its status, counts as reachable; only failures to
+/// connect are reported as errors.
+pub(crate) async fn preflight(client: &reqwest::Client, base_url: &str) -> CoreResult<()> {
+    client
+        .head(base_url)
+        .send()
+        .await
+        .map(|_| ())
+        .map_err(|source| match find_pin_mismatch(&source) {
+            Some(mismatch) => CodexErr::SpkiPinMismatch(mismatch),
+            None => CodexErr::ConnectionFailed(ConnectionFailedError { source }),
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use std::time::Instant;
+    use wiremock::Mock;
+    use wiremock::MockServer;
+    use wiremock::ResponseTemplate;
+    use wiremock::matchers::method;
+
+    fn preflight_client(connect_timeout: Duration) -> reqwest::Client {
+        reqwest::Client::builder()
+            .connect_timeout(connect_timeout)
+            .build()
+            .expect("client")
+    }
+
+    #[tokio::test]
+    async fn preflight_accepts_any_status() {
+        let server = MockServer::start().await;
+        Mock::given(method("HEAD"))
+            .respond_with(ResponseTemplate::new(405))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let result = preflight(&preflight_client(PREFLIGHT_TIMEOUT), &server.uri()).await;
+
+        assert_eq!(result.ok(), Some(()));
+    }
+
+    #[tokio::test]
+    async fn preflight_fails_fast_when_connection_refused() {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").expect("bind");
+        let addr = listener.local_addr().expect("local addr");
+        drop(listener);
+
+        let started = Instant::now();
+        let result = preflight(
+            &preflight_client(PREFLIGHT_TIMEOUT),
+            &format!("http://{addr}"),
+        )
+        .await;
+
+        assert!(matches!(result, Err(CodexErr::ConnectionFailed(_))));
+        assert!(started.elapsed() < PREFLIGHT_TIMEOUT);
+    }
+
+    #[tokio::test]
+    async fn preflight_times_out_on_slow_connect() {
+        // Accepted by the kernel but never answered, so the TLS handshake
+        // never completes.
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").expect("bind");
+        let addr = listener.local_addr().expect("local addr");
+
+        let started = Instant::now();
+        let result = preflight(
+            &preflight_client(Duration::from_millis(200)),
+            &format!("https://{addr}"),
+        )
+        .await;
+
+        assert!(matches!(result, Err(CodexErr::ConnectionFailed(_))));
+        assert!(started.elapsed() < Duration::from_secs(2));
+        drop(listener);
+    }
+
+    #[tokio::test]
+    async fn preflight_waits_for_a_slow_response_once_connected() {
+        let server = MockServer::start().await;
+        Mock::given(method("HEAD"))
+            .respond_with(ResponseTemplate::new(200).set_delay(Duration::from_millis(500)))
+            .mount(&server)
+            .await;
+
+        let result = preflight(&preflight_client(Duration::from_millis(200)), &server.uri()).await;
+
+        assert_eq!(result.ok(), Some(()));
+    }
+}
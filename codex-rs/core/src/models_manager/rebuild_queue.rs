@@ -0,0 +1,319 @@
+//! Coalescing of catalog rebuilds.
+//!
+//! A [`ModelCatalogHandle`](super::catalog_handle::ModelCatalogHandle)
+//! rebuilds its presets when the config is reloaded, the credentials change,
+//! a refresh is asked for, or the cache TTL runs out. One user action may set
+//! off several of these at once, such as a login that also reloads the
+//! config. Each trigger only queues its reason; a single task takes every
+//! queued reason at once and rebuilds for all of them, so rebuilds never
+//! overlap and reasons that arrive during a rebuild make one rebuild after
+//! it. A caller that needs the outcome, such as the app server's
+//! `model/refresh`, waits for the rebuild that takes its request.
+//!
+//! The last finished rebuild of each catalog is also recorded in
+//! `$CODEX_HOME/models_last_rebuild.json`, keyed by the catalog's base URL and
+//! who it is listed as, so `codex models status` can report it from another
+//! process.
+
+use std::collections::BTreeMap;
+use std::io;
+use std::io::ErrorKind;
+use std::path::Path;
+use std::sync::Mutex;
+use std::sync::MutexGuard;
+use std::sync::PoisonError;
+
+use chrono::DateTime;
+use chrono::Utc;
+use serde::Deserialize;
+use serde::Serialize;
+use tokio::sync::Notify;
+use tokio::sync::watch;
+use tracing::warn;
+
+use super::catalog_handle::CatalogRevision;
+use super::state_dir::write_state_atomically;
+
+const LAST_REBUILD_FILE: &str = "models_last_rebuild.json";
+
+/// Why a catalog handle rebuilt its presets.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RebuildReason {
+    /// The handle was just created.
+    Started,
+    /// The embedder reloaded the config.
+    ConfigReloaded,
+    /// The embedder signed in, signed out, or switched credentials.
+    AuthChanged,
+    /// A refresh was asked for.
+    Manual,
+    /// The cache TTL ran out.
+    Scheduled,
+}
+
+impl RebuildReason {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Started => "started",
+            Self::ConfigReloaded => "config_reloaded",
+            Self::AuthChanged => "auth_changed",
+            Self::Manual => "manual",
+            Self::Scheduled => "scheduled",
+        }
+    }
+}
+
+/// A rebuild that finished, for diagnostics.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CatalogRebuild {
+    /// Every reason the rebuild ran for, in the order they first arrived.
+    pub reasons: Vec<RebuildReason>,
+    /// The revision the presets were at once the rebuild finished; the same
+    /// as before it when they did not change.
+    pub revision: CatalogRevision,
+    pub finished_at: DateTime<Utc>,
+    /// Why a manual refresh failed. The presets are then whatever the
+    /// catalogs still had.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+pub(crate) struct RebuildQueue {
+    state: Mutex<QueueState>,
+    wake: Notify,
+    /// The last request the last finished rebuild took, and that rebuild.
+    finished: watch::Sender<Option<(u64, CatalogRebuild)>>,
+}
+
+#[derive(Default)]
+struct QueueState {
+    pending: Vec<RebuildReason>,
+    /// The number of requests made so far, which numbers each request.
+    requested: u64,
+    /// The last request the running rebuild took.
+    taken: u64,
+}
+
+impl Default for RebuildQueue {
+    fn default() -> Self {
+        Self {
+            state: Mutex::default(),
+            wake: Notify::new(),
+            finished: watch::Sender::new(None),
+        }
+    }
+}
+
+impl RebuildQueue {
+    /// Queue a rebuild for `reason`, joining any rebuild not yet started.
+    /// Returns the request's number, for [`Self::wait_for`].
+    pub(crate) fn request(&self, reason: RebuildReason) -> u64 {
+        let ticket = {
+            let mut state = self.lock();
+            if !state.pending.contains(&reason) {
+                state.pending.push(reason);
+            }
+            state.requested += 1;
+            state.requested
+        };
+        self.wake.notify_one();
+        ticket
+    }
+
+    /// Wait for the rebuild that took request `ticket` to finish.
+    pub(crate) async fn wait_for(&self, ticket: u64) -> CatalogRebuild {
+        let mut finished = self.finished.subscribe();
+        loop {
+            if let Some((taken, rebuild)) = &*finished.borrow_and_update()
+                && *taken >= ticket
+            {
+                return rebuild.clone();
+            }
+            // `self` holds the sender, so the channel never closes under us.
+            let _ = finished.changed().await;
+        }
+    }
+
+    /// Wait until a rebuild is queued, then take every queued reason.
+    pub(crate) async fn next(&self) -> Vec<RebuildReason> {
+        loop {
+            let reasons = self.take();
+            if !reasons.is_empty() {
+                return reasons;
+            }
+            self.wake.notified().await;
+        }
+    }
+
+    /// Take every queued reason without waiting.
+    pub(crate) fn take(&self) -> Vec<RebuildReason> {
+        let mut state = self.lock();
+        state.taken = state.requested;
+        std::mem::take(&mut state.pending)
+    }
+
+    /// Record `rebuild` as the one for every reason last taken.
+    pub(crate) fn finished(&self, rebuild: CatalogRebuild) {
+        let taken = self.lock().taken;
+        self.finished.send_replace(Some((taken, rebuild)));
+    }
+
+    pub(crate) fn last(&self) -> Option<CatalogRebuild> {
+        self.finished
+            .borrow()
+            .as_ref()
+            .map(|(_, rebuild)| rebuild.clone())
+    }
+
+    fn lock(&self) -> MutexGuard<'_, QueueState> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+/// The last rebuild of each catalog, keyed by catalog. A file from before
+/// rebuilds were kept per catalog has no `catalogs` and reads as empty.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct LastRebuildFile {
+    #[serde(default)]
+    catalogs: BTreeMap<String, CatalogRebuild>,
+}
+
+/// Record `rebuild` as the last one of `catalog` finished under
+/// `codex_home`, keeping those of the other catalogs.
+pub(crate) async fn write_last_rebuild(
+    codex_home: &Path,
+    catalog: &str,
+    rebuild: &CatalogRebuild,
+) -> io::Result<()> {
+    let mut file = read_last_rebuild_file(codex_home);
+    file.catalogs.insert(catalog.to_string(), rebuild.clone());
+    let json = serde_json::to_vec_pretty(&file)
+        .map_err(|err| io::Error::new(ErrorKind::InvalidData, err.to_string()))?;
+    write_state_atomically(&codex_home.join(LAST_REBUILD_FILE), &json).await
+}
+
+/// The last rebuild of `catalog` any process recorded under `codex_home`, if
+/// any.
+pub(crate) fn read_last_rebuild(codex_home: &Path, catalog: &str) -> Option<CatalogRebuild> {
+    read_last_rebuild_file(codex_home).catalogs.remove(catalog)
+}
+
+fn read_last_rebuild_file(codex_home: &Path) -> LastRebuildFile {
+    let path = codex_home.join(LAST_REBUILD_FILE);
+    match std::fs::read(&path) {
+        Ok(contents) => serde_json::from_slice(&contents)
+            .inspect_err(|err| warn!("ignoring unreadable {}: {err}", path.display()))
+            .unwrap_or_default(),
+        Err(err) if err.kind() == ErrorKind::NotFound => LastRebuildFile::default(),
+        Err(err) => {
+            warn!("failed to read {}: {err}", path.display());
+            LastRebuildFile::default()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn a_burst_of_triggers_makes_one_rebuild() {
+        let queue = RebuildQueue::default();
+        queue.request(RebuildReason::ConfigReloaded);
+        queue.request(RebuildReason::AuthChanged);
+        queue.request(RebuildReason::ConfigReloaded);
+        queue.request(RebuildReason::Manual);
+
+        assert_eq!(
+            queue.next().await,
+            vec![
+                RebuildReason::ConfigReloaded,
+                RebuildReason::AuthChanged,
+                RebuildReason::Manual,
+            ]
+        );
+        // The burst was taken whole; nothing is left for a second rebuild.
+        assert!(
+            tokio::time::timeout(Duration::from_millis(50), queue.next())
+                .await
+                .is_err(),
+            "no rebuild should be queued"
+        );
+    }
+
+    #[tokio::test]
+    async fn a_waiter_gets_the_rebuild_that_took_its_request() {
+        let queue = RebuildQueue::default();
+        let first = queue.request(RebuildReason::Started);
+        assert_eq!(queue.take(), vec![RebuildReason::Started]);
+        // Arrives while the first rebuild runs, so only the next one takes it.
+        let second = queue.request(RebuildReason::Manual);
+        let rebuild = |revision| CatalogRebuild {
+            reasons: Vec::new(),
+            revision: CatalogRevision(revision),
+            finished_at: Utc::now(),
+            error: None,
+        };
+        queue.finished(rebuild(1));
+        assert_eq!(queue.wait_for(first).await.revision, CatalogRevision(1));
+        assert!(
+            tokio::time::timeout(Duration::from_millis(50), queue.wait_for(second))
+                .await
+                .is_err(),
+            "the second request has not been rebuilt for yet"
+        );
+
+        assert_eq!(queue.take(), vec![RebuildReason::Manual]);
+        queue.finished(rebuild(2));
+        assert_eq!(queue.wait_for(second).await.revision, CatalogRevision(2));
+    }
+
+    #[tokio::test]
+    async fn the_last_rebuild_is_read_back_per_catalog() {
+        let codex_home = tempfile::tempdir().expect("temp dir");
+        let main = "https://api.example.test/v1 as ChatGPT";
+        let tenant = "https://tenant.example.test/v1 as API key";
+        assert_eq!(read_last_rebuild(codex_home.path(), main), None);
+
+        let rebuild = CatalogRebuild {
+            reasons: vec![RebuildReason::ConfigReloaded, RebuildReason::Manual],
+            revision: CatalogRevision(3),
+            finished_at: Utc::now(),
+            error: None,
+        };
+        write_last_rebuild(codex_home.path(), main, &rebuild)
+            .await
+            .expect("write");
+        let tenant_rebuild = CatalogRebuild {
+            reasons: vec![RebuildReason::Started],
+            revision: CatalogRevision(1),
+            finished_at: Utc::now(),
+            error: Some("401 Unauthorized".to_string()),
+        };
+        write_last_rebuild(codex_home.path(), tenant, &tenant_rebuild)
+            .await
+            .expect("write");
+
+        // The tenant's rebuild did not overwrite the main catalog's.
+        assert_eq!(read_last_rebuild(codex_home.path(), main), Some(rebuild));
+        assert_eq!(
+            read_last_rebuild(codex_home.path(), tenant),
+            Some(tenant_rebuild)
+        );
+    }
+
+    #[test]
+    fn a_rebuild_recorded_before_catalogs_were_kept_apart_is_dropped() {
+        let codex_home = tempfile::tempdir().expect("temp dir");
+        std::fs::write(
+            codex_home.path().join(LAST_REBUILD_FILE),
+            r#"{"reasons": ["manual"], "revision": 2, "finished_at": "2025-01-01T00:00:00Z"}"#,
+        )
+        .expect("write");
+
+        assert_eq!(read_last_rebuild(codex_home.path(), "any"), None);
+    }
+}
@@ -0,0 +1,157 @@
+//! Lifecycle events of catalog refreshes, for status indicators.
+//!
+//! A [`ModelsManager`](super::manager::ModelsManager) announces when it
+//! starts fetching a catalog and how the fetch ended, whether the fetch was
+//! asked for or runs in the background, and when a fetch moved the default
+//! model. Each refresh sends [`CatalogRefreshEvent::RefreshStarted`], then
+//! [`CatalogRefreshEvent::RefreshSucceeded`] or
+//! [`CatalogRefreshEvent::RefreshFailed`]; a refresh that moves the default
+//! also sends [`CatalogRefreshEvent::DefaultModelChanged`] before it ends.
+//!
+//! The remote `/models` catalog and the provider catalogs are reported as
+//! separate refreshes, except that a manual refresh of every catalog is
+//! reported once. A provider refresh fails when any catalog it requests does,
+//! and lists provider-namespaced preset ids in `added` and `removed`.
+//!
+//! The events are best-effort: they go out over a [`broadcast`] channel, so
+//! events sent while nobody subscribes are dropped, and a subscriber that
+//! falls more than [`REFRESH_EVENTS_CAPACITY`] events behind loses the oldest
+//! ones and gets [`broadcast::error::RecvError::Lagged`]. They are meant for
+//! showing that something is happening; read the catalog itself for what it
+//! holds.
+
+use std::collections::HashSet;
+
+use tokio::sync::broadcast;
+
+/// How many events a subscriber may fall behind before it starts losing
+/// them.
+pub const REFRESH_EVENTS_CAPACITY: usize = 16;
+
+/// Why a catalog is being refreshed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RefreshReason {
+    /// There was no usable cache, so the listing waits on the fetch.
+    CacheMiss,
+    /// A cache past its TTL was served and is replaced in the background.
+    StaleCache,
+    /// A response named a catalog ETag other than the one loaded.
+    EtagChanged,
+    /// Every catalog was refreshed on request, e.g. by `codex models refresh`.
+    Manual,
+    /// Every provider catalog is listed again, as in the background when a
+    /// session starts.
+    Relist,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CatalogRefreshEvent {
+    RefreshStarted {
+        reason: RefreshReason,
+    },
+    /// The refresh finished; `added` and `removed` are the models it changed,
+    /// sorted. Both are empty when the catalog came back unchanged.
+    RefreshSucceeded {
+        revision: u64,
+        added: Vec<String>,
+        removed: Vec<String>,
+    },
+    /// The refresh failed; the models listed before it are kept.
+    RefreshFailed {
+        error: String,
+    },
+    /// A refresh moved the remote catalog's default, which conversations
+    /// started without a chosen model use. Sent before the refresh ends;
+    /// `current` is `None` when no listed model is left.
+    DefaultModelChanged {
+        previous: String,
+        current: Option<String>,
+    },
+}
+
+/// The sending side, shared by a manager and its background refreshes.
+#[derive(Debug, Clone)]
+pub(crate) struct RefreshEvents(broadcast::Sender<CatalogRefreshEvent>);
+
+impl RefreshEvents {
+    pub(crate) fn new() -> Self {
+        Self(broadcast::Sender::new(REFRESH_EVENTS_CAPACITY))
+    }
+
+    pub(crate) fn subscribe(&self) -> broadcast::Receiver<CatalogRefreshEvent> {
+        self.0.subscribe()
+    }
+
+    pub(crate) fn started(&self, reason: RefreshReason) {
+        self.send(CatalogRefreshEvent::RefreshStarted { reason });
+    }
+
+    /// Report a refresh that went from the models in `before` to those in
+    /// `after`, at `revision`.
+    pub(crate) fn succeeded(
+        &self,
+        revision: u64,
+        before: &HashSet<String>,
+        after: &HashSet<String>,
+    ) {
+        self.send(CatalogRefreshEvent::RefreshSucceeded {
+            revision,
+            added: sorted_difference(after, before),
+            removed: sorted_difference(before, after),
+        });
+    }
+
+    pub(crate) fn failed(&self, error: String) {
+        self.send(CatalogRefreshEvent::RefreshFailed { error });
+    }
+
+    pub(crate) fn default_changed(&self, previous: String, current: Option<String>) {
+        self.send(CatalogRefreshEvent::DefaultModelChanged { previous, current });
+    }
+
+    fn send(&self, event: CatalogRefreshEvent) {
+        // Nobody listening is not an error.
+        let _ = self.0.send(event);
+    }
+}
+
+fn sorted_difference(left: &HashSet<String>, right: &HashSet<String>) -> Vec<String> {
+    let mut difference: Vec<String> = left.difference(right).cloned().collect();
+    difference.sort();
+    difference
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use tokio::sync::broadcast::error::RecvError;
+
+    fn ids(ids: &[&str]) -> HashSet<String> {
+        ids.iter().map(ToString::to_string).collect()
+    }
+
+    #[tokio::test]
+    async fn a_lagging_subscriber_loses_the_oldest_events() {
+        let events = RefreshEvents::new();
+        let mut receiver = events.subscribe();
+        for _ in 0..=REFRESH_EVENTS_CAPACITY {
+            events.started(RefreshReason::StaleCache);
+        }
+        events.succeeded(7, &ids(&["gpt-5", "o3"]), &ids(&["gpt-5", "gpt-5.1"]));
+
+        assert_eq!(receiver.recv().await, Err(RecvError::Lagged(2)));
+        let mut last = None;
+        while let Ok(event) = receiver.try_recv() {
+            last = Some(event);
+        }
+        assert_eq!(
+            last,
+            Some(CatalogRefreshEvent::RefreshSucceeded {
+                revision: 7,
+                added: vec!["gpt-5.1".to_string()],
+                removed: vec!["o3".to_string()],
+            })
+        );
+    }
+}
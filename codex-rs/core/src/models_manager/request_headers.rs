@@ -0,0 +1,242 @@
+use std::collections::HashMap;
+
+use http::HeaderMap;
+use http::HeaderName;
+use http::HeaderValue;
+use tracing::warn;
+
+/// Credentials come from the provider config and the signed-in account, never
+/// from a preset, so a catalog entry cannot redirect or replace them.
+const CREDENTIAL_HEADERS: &[&str] = &[
+    "authorization",
+    "proxy-authorization",
+    "www-authenticate",
+    "proxy-authenticate",
+    "cookie",
+    "set-cookie",
+    "api-key",
+    "x-api-key",
+    "chatgpt-account-id",
+    "openai-organization",
+    "openai-project",
+];
+
+/// Headers that belong to the connection or to proxies between us and the
+/// server rather than to the request, which the HTTP client manages itself.
+const RESERVED_HEADERS: &[&str] = &[
+    "connection",
+    "keep-alive",
+    "proxy-connection",
+    "te",
+    "trailer",
+    "transfer-encoding",
+    "upgrade",
+    "host",
+    "content-length",
+    "forwarded",
+    "via",
+    "x-forwarded-for",
+    "x-forwarded-host",
+    "x-forwarded-proto",
+];
+
+/// Normalize a preset header name to lowercase, rejecting names that are not
+/// valid HTTP header names, that carry credentials, or that the HTTP client
+/// manages.
+pub(crate) fn sanitize_header_name(name: &str) -> Result<String, String> {
+    let normalized = name.trim().to_ascii_lowercase();
+    if HeaderName::from_bytes(normalized.as_bytes()).is_err() {
+        return Err(format!("`{name}` is not a valid HTTP header name"));
+    }
+    if carries_credentials(&normalized) {
+        return Err(format!(
+            "`{name}` carries credentials and cannot be set per model; configure it on the provider"
+        ));
+    }
+    if RESERVED_HEADERS.contains(&normalized.as_str()) || normalized.starts_with("proxy-") {
+        return Err(format!(
+            "`{name}` is a connection or proxy header and cannot be set per model"
+        ));
+    }
+    Ok(normalized)
+}
+
+/// Whether a header with this name holds credentials.
+pub(crate) fn carries_credentials(name: &str) -> bool {
+    CREDENTIAL_HEADERS.contains(&name.to_ascii_lowercase().as_str())
+}
+
+/// Sanitize every header name, failing on the first bad one.
+pub(crate) fn validate_request_headers(
+    headers: &HashMap<String, String>,
+) -> Result<HashMap<String, String>, String> {
+    let mut names: Vec<&String> = headers.keys().collect();
+    names.sort();
+    names
+        .into_iter()
+        .map(|name| Ok((sanitize_header_name(name)?, headers[name].clone())))
+        .collect()
+}
+
+/// Like [`validate_request_headers`] for headers served by a catalog, which
+/// cannot fail config loading: bad entries are dropped with a warning.
+pub(crate) fn sanitize_request_headers(
+    model: &str,
+    headers: &HashMap<String, String>,
+) -> HashMap<String, String> {
+    headers
+        .iter()
+        .filter_map(|(name, value)| match sanitize_header_name(name) {
+            Ok(name) => Some((name, value.clone())),
+            Err(err) => {
+                warn!("ignoring request header for model {model}: {err}");
+                None
+            }
+        })
+        .collect()
+}
+
+/// Build the headers to send. Only `configured` headers, which come from the
+/// user's own config, have `${VAR}` references in their values expanded from
+/// the environment; a catalog has no business reading it, so `catalog`
+/// values are sent as served. A configured header wins over a catalog one of
+/// the same name. A header whose variable is unset, or whose value is not a
+/// valid header value, is left out.
+pub(crate) fn build_request_headers(
+    catalog: &HashMap<String, String>,
+    configured: &HashMap<String, String>,
+) -> HeaderMap {
+    let mut header_map = HeaderMap::new();
+    for (name, value) in catalog {
+        insert_header(&mut header_map, name, value.clone());
+    }
+    for (name, value) in configured {
+        match expand_env_vars(value) {
+            Ok(value) => insert_header(&mut header_map, name, value),
+            Err(var) => {
+                header_map.remove(name.as_str());
+                warn!("not sending request header {name}: environment variable {var} is not set");
+            }
+        }
+    }
+    header_map
+}
+
+fn insert_header(header_map: &mut HeaderMap, name: &str, value: String) {
+    match (HeaderName::try_from(name), HeaderValue::try_from(value)) {
+        (Ok(name), Ok(value)) => {
+            header_map.insert(name, value);
+        }
+        _ => warn!("not sending request header {name}: invalid header value"),
+    }
+}
+
+/// Replace each `${VAR}` with the value of `VAR`. Returns the name of the
+/// first variable that is not set.
+fn expand_env_vars(value: &str) -> Result<String, String> {
+    let mut expanded = String::with_capacity(value.len());
+    let mut rest = value;
+    while let Some(start) = rest.find("${") {
+        let Some(len) = rest[start + 2..].find('}') else {
+            break;
+        };
+        let var = &rest[start + 2..start + 2 + len];
+        let var_value = std::env::var(var).map_err(|_| var.to_string())?;
+        expanded.push_str(&rest[..start]);
+        expanded.push_str(&var_value);
+        rest = &rest[start + 2 + len + 1..];
+    }
+    expanded.push_str(rest);
+    Ok(expanded)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn header_names_are_normalized_and_credentials_rejected() {
+        assert_eq!(
+            sanitize_header_name(" X-Model-Group "),
+            Ok("x-model-group".to_string())
+        );
+        assert_eq!(
+            sanitize_header_name("Authorization"),
+            Err("`Authorization` carries credentials and cannot be set per model; configure it on the provider".to_string())
+        );
+        assert_eq!(
+            sanitize_header_name("Proxy-Authorization"),
+            Err("`Proxy-Authorization` carries credentials and cannot be set per model; configure it on the provider".to_string())
+        );
+        assert_eq!(
+            sanitize_header_name("Transfer-Encoding"),
+            Err(
+                "`Transfer-Encoding` is a connection or proxy header and cannot be set per model"
+                    .to_string()
+            )
+        );
+        assert_eq!(
+            sanitize_header_name("x model"),
+            Err("`x model` is not a valid HTTP header name".to_string())
+        );
+    }
+
+    #[test]
+    fn catalog_headers_drop_only_the_bad_entries() {
+        let headers = HashMap::from([
+            ("X-Model-Group".to_string(), "large".to_string()),
+            ("api-key".to_string(), "sk-smuggled".to_string()),
+        ]);
+
+        assert_eq!(
+            sanitize_request_headers("oca-large", &headers),
+            HashMap::from([("x-model-group".to_string(), "large".to_string())])
+        );
+    }
+
+    #[test]
+    fn configured_values_expand_environment_variables() {
+        let path = std::env::var("PATH").unwrap_or_default();
+        let headers = HashMap::from([
+            ("x-path".to_string(), "path=${PATH};".to_string()),
+            ("x-literal".to_string(), "no ${ braces".to_string()),
+            (
+                "x-missing".to_string(),
+                "${CODEX_TEST_UNSET_HEADER_VAR}".to_string(),
+            ),
+        ]);
+
+        let built = build_request_headers(&HashMap::new(), &headers);
+
+        assert_eq!(
+            built.get("x-path").and_then(|value| value.to_str().ok()),
+            Some(format!("path={path};").as_str())
+        );
+        assert_eq!(
+            built.get("x-literal").and_then(|value| value.to_str().ok()),
+            Some("no ${ braces")
+        );
+        assert_eq!(built.get("x-missing"), None);
+    }
+
+    #[test]
+    fn catalog_values_cannot_read_the_environment() {
+        let catalog = HashMap::from([
+            ("x-path".to_string(), "${PATH}".to_string()),
+            ("x-group".to_string(), "large".to_string()),
+        ]);
+        let configured = HashMap::from([("x-group".to_string(), "small".to_string())]);
+
+        let built = build_request_headers(&catalog, &configured);
+
+        assert_eq!(
+            built.get("x-path").and_then(|value| value.to_str().ok()),
+            Some("${PATH}")
+        );
+        assert_eq!(
+            built.get("x-group").and_then(|value| value.to_str().ok()),
+            Some("small")
+        );
+    }
+}
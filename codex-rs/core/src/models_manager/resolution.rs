@@ -0,0 +1,265 @@
+//! Explains which model and reasoning effort a session would start with.
+//!
+//! The final values come from [`ModelsManager::resolve_session_model`], the
+//! same call session startup makes; this module only adds the list of
+//! settings that were considered on the way, for `codex models which`.
+
+use codex_app_server_protocol::ConfigLayerSource;
+use codex_protocol::openai_models::ReasoningEffort;
+use serde::Serialize;
+use toml::Value as TomlValue;
+
+use crate::config::Config;
+use crate::config_loader::ConfigLayerEntry;
+use crate::models_manager::manager::ModelsManager;
+
+/// One place a setting could have come from, highest precedence first.
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct ConsideredSetting {
+    /// Such as `--model`, `profile "fast" in /home/me/.codex/config.toml`, or
+    /// `default`.
+    pub source: String,
+    /// The value this source sets, `None` when it leaves the setting alone.
+    pub value: Option<String>,
+    /// Whether this source decided the setting.
+    pub won: bool,
+}
+
+/// The model and effort a session would use, and how they were chosen.
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct ModelResolution {
+    pub model_sources: Vec<ConsideredSetting>,
+    pub effort_sources: Vec<ConsideredSetting>,
+    /// Id of the catalog preset for `model`, if the catalog lists it.
+    pub preset_id: Option<String>,
+    /// The model slug sent to the provider.
+    pub model: String,
+    /// Set when the configured model is retired and `model` replaces it.
+    pub replaces_retired: Option<String>,
+    /// The effort sent with requests, `None` when the model takes none.
+    pub effort: Option<ReasoningEffort>,
+    /// Whether `effort` follows the model default rather than config.
+    pub effort_is_auto: bool,
+    pub provider_id: String,
+    pub context_window: Option<i64>,
+}
+
+/// Resolve the model for `config` as session startup does, and list every
+/// source considered. `model_flag` is the `--model` value `config` was loaded
+/// with, since [`Config`] does not remember where its model came from.
+pub async fn resolve_model(
+    models_manager: &ModelsManager,
+    config: &Config,
+    model_flag: Option<&str>,
+) -> ModelResolution {
+    let (model, model_family) = models_manager.resolve_session_model(config).await;
+    let presets = models_manager.list_all_models(config).await;
+    let preset_id = presets
+        .iter()
+        .find(|preset| {
+            preset.model == model
+                && preset
+                    .model_provider
+                    .as_deref()
+                    .is_none_or(|provider_id| provider_id == config.model_provider_id)
+        })
+        .map(|preset| preset.id.clone());
+
+    let layers = config.config_layer_stack.layers_high_to_low();
+    let profile = config.active_profile.as_deref();
+    let mut model_sources = considered_settings(&layers, profile, "model");
+    if let Some(flag) = model_flag {
+        model_sources.insert(
+            0,
+            ConsideredSetting {
+                source: "--model".to_string(),
+                value: Some(flag.to_string()),
+                won: false,
+            },
+        );
+    }
+    let configured_model = mark_winner(&mut model_sources, &model);
+    let replaces_retired = configured_model.filter(|configured| *configured != model);
+
+    let effort = model_family.request_reasoning_effort(config.model_reasoning_effort);
+    let mut effort_sources = considered_settings(&layers, profile, "model_reasoning_effort");
+    let default_effort = model_family
+        .default_reasoning_effort
+        .map_or_else(|| "none".to_string(), |effort| effort.to_string());
+    mark_winner(&mut effort_sources, &default_effort);
+
+    ModelResolution {
+        model_sources,
+        effort_sources,
+        preset_id,
+        model,
+        replaces_retired,
+        effort,
+        effort_is_auto: config.model_reasoning_effort.is_none(),
+        provider_id: config.model_provider_id.clone(),
+        context_window: model_family.context_window,
+    }
+}
+
+/// The active profile's value in each layer, then the top-level value in
+/// each layer: a profile setting wins over any top-level one, and within
+/// each group higher layers win.
+fn considered_settings(
+    layers: &[&ConfigLayerEntry],
+    profile: Option<&str>,
+    key: &str,
+) -> Vec<ConsideredSetting> {
+    let mut settings = Vec::new();
+    if let Some(profile) = profile {
+        settings.extend(layers.iter().map(|layer| {
+            ConsideredSetting {
+                source: format!("profile `{profile}` in {}", layer_label(&layer.name)),
+                value: layer
+                    .config
+                    .get("profiles")
+                    .and_then(|profiles| profiles.get(profile))
+                    .and_then(|table| setting_value(table, key)),
+                won: false,
+            }
+        }));
+    }
+    settings.extend(layers.iter().map(|layer| ConsideredSetting {
+        source: layer_label(&layer.name),
+        value: setting_value(&layer.config, key),
+        won: false,
+    }));
+    settings
+}
+
+/// Mark the first source that sets a value as the winner and return that
+/// value. When none does, a `default` entry holding `default` wins instead.
+fn mark_winner(settings: &mut Vec<ConsideredSetting>, default: &str) -> Option<String> {
+    if let Some(winner) = settings.iter_mut().find(|setting| setting.value.is_some()) {
+        winner.won = true;
+        return winner.value.clone();
+    }
+    settings.push(ConsideredSetting {
+        source: "default".to_string(),
+        value: Some(default.to_string()),
+        won: true,
+    });
+    None
+}
+
+fn setting_value(table: &TomlValue, key: &str) -> Option<String> {
+    match table.get(key)? {
+        TomlValue::String(value) => Some(value.clone()),
+        other => Some(other.to_string()),
+    }
+}
+
+fn layer_label(source: &ConfigLayerSource) -> String {
+    match source {
+        ConfigLayerSource::Mdm { domain, key } => format!("managed preferences ({domain} {key})"),
+        ConfigLayerSource::System { file } => format!("system config {}", file.display()),
+        ConfigLayerSource::User { file } => format!("user config {}", file.display()),
+        ConfigLayerSource::Project { dot_codex_folder } => {
+            format!("project config {}", dot_codex_folder.display())
+        }
+        ConfigLayerSource::SessionFlags => "-c overrides".to_string(),
+        ConfigLayerSource::LegacyManagedConfigTomlFromFile { file } => {
+            format!("managed config {}", file.display())
+        }
+        ConfigLayerSource::LegacyManagedConfigTomlFromMdm => "managed preferences".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn layer(source: ConfigLayerSource, toml: &str) -> ConfigLayerEntry {
+        ConfigLayerEntry::new(source, toml::from_str(toml).expect("valid toml"))
+    }
+
+    fn winners(settings: &[ConsideredSetting]) -> Vec<(&str, Option<&str>, bool)> {
+        settings
+            .iter()
+            .map(|setting| {
+                (
+                    setting.source.as_str(),
+                    setting.value.as_deref(),
+                    setting.won,
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn a_profile_in_a_lower_layer_beats_higher_top_level_settings() {
+        let managed = layer(
+            ConfigLayerSource::LegacyManagedConfigTomlFromMdm,
+            r#"model = "from-managed""#,
+        );
+        let flags = layer(
+            ConfigLayerSource::SessionFlags,
+            r#"
+model = "from-flags"
+
+[profiles.fast]
+model = "from-profile"
+"#,
+        );
+        let layers = [&managed, &flags];
+        let mut settings = considered_settings(&layers, Some("fast"), "model");
+
+        assert_eq!(
+            mark_winner(&mut settings, "unused").as_deref(),
+            Some("from-profile")
+        );
+        assert_eq!(
+            winners(&settings),
+            vec![
+                ("profile `fast` in managed preferences", None, false),
+                ("profile `fast` in -c overrides", Some("from-profile"), true),
+                ("managed preferences", Some("from-managed"), false),
+                ("-c overrides", Some("from-flags"), false),
+            ]
+        );
+    }
+
+    #[test]
+    fn the_highest_layer_wins_without_a_profile() {
+        let managed = layer(
+            ConfigLayerSource::LegacyManagedConfigTomlFromMdm,
+            r#"model = "from-managed""#,
+        );
+        let flags = layer(ConfigLayerSource::SessionFlags, r#"model = "from-flags""#);
+        let layers = [&managed, &flags];
+        let mut settings = considered_settings(&layers, None, "model");
+
+        assert_eq!(
+            mark_winner(&mut settings, "unused").as_deref(),
+            Some("from-managed")
+        );
+        assert_eq!(
+            winners(&settings),
+            vec![
+                ("managed preferences", Some("from-managed"), true),
+                ("-c overrides", Some("from-flags"), false),
+            ]
+        );
+    }
+
+    #[test]
+    fn an_unset_setting_falls_back_to_the_default() {
+        let flags = layer(ConfigLayerSource::SessionFlags, "");
+        let layers = [&flags];
+        let mut settings = considered_settings(&layers, None, "model_reasoning_effort");
+
+        assert_eq!(mark_winner(&mut settings, "medium"), None);
+        assert_eq!(
+            winners(&settings),
+            vec![
+                ("-c overrides", None, false),
+                ("default", Some("medium"), true),
+            ]
+        );
+    }
+}
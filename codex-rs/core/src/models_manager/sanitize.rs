@@ -0,0 +1,322 @@
+//! Sanitizing strings that arrive from model catalogs.
+//!
+//! Catalog text comes from gateways and cache files we don't control and is
+//! rendered straight into the terminal, so escape sequences, control
+//! characters, and invisible formatting characters are removed, text is
+//! normalized to NFC, and lengths are capped before anything else sees it. Identifiers are sent back to the
+//! server and matched against config, so they are never rewritten: an entry
+//! whose id is unusable is rejected instead.
+
+use codex_protocol::openai_models::ModelInfo;
+use codex_protocol::openai_models::ModelPreset;
+use codex_protocol::openai_models::ReasoningEffortPreset;
+use thiserror::Error;
+use unicode_normalization::UnicodeNormalization;
+
+/// Longest accepted model slug or upgrade target.
+pub(crate) const MAX_SLUG_CHARS: usize = 128;
+/// Longest display name shown in the picker.
+pub(crate) const MAX_DISPLAY_NAME_CHARS: usize = 80;
+/// Longest model or reasoning effort description.
+pub(crate) const MAX_DESCRIPTION_CHARS: usize = 500;
+/// Longest knowledge cutoff or release date.
+const MAX_DATE_CHARS: usize = 32;
+
+const ESC: char = '\u{1b}';
+const BEL: char = '\u{7}';
+const ELLIPSIS: char = '…';
+
+/// Clean text that may span several lines: ANSI escape sequences, control
+/// characters, and [invisible](is_invisible) characters are removed, `\r\n` and lone `\r` become `\n`, tabs become
+/// spaces, and the result is normalized to NFC, trimmed and capped at
+/// `max_chars` characters (the last of which is an ellipsis when the text was
+/// cut). NFC makes a name spelled with combining marks compare, measure and
+/// truncate like the same name spelled with precomposed characters.
+pub(crate) fn sanitize_catalog_text(input: &str, max_chars: usize) -> String {
+    let mut out = String::with_capacity(input.len().min(max_chars.saturating_mul(4)));
+    let mut chars = input.chars().peekable();
+    while let Some(ch) = chars.next() {
+        match ch {
+            ESC => skip_escape_sequence(&mut chars),
+            '\r' => {
+                chars.next_if_eq(&'\n');
+                out.push('\n');
+            }
+            '\n' => out.push('\n'),
+            '\t' => out.push(' '),
+            ch if ch.is_control() || is_invisible(ch) => {}
+            ch => out.push(ch),
+        }
+    }
+    let out: String = out.nfc().collect();
+    truncate_with_ellipsis(out.trim(), max_chars)
+}
+
+/// Like [`sanitize_catalog_text`], but collapses line breaks so the result
+/// fits on one line.
+pub(crate) fn sanitize_catalog_line(input: &str, max_chars: usize) -> String {
+    let text = sanitize_catalog_text(input, usize::MAX);
+    let line = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    truncate_with_ellipsis(&line, max_chars)
+}
+
+/// A catalog entry whose model id or preset id cannot be used as served.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[error(
+    "ignoring catalog entry `{}`: ids must be 1 to {MAX_SLUG_CHARS} characters, without control or invisible characters or surrounding whitespace",
+    .0.escape_debug()
+)]
+pub(crate) struct InvalidIdentifier(pub(crate) String);
+
+/// Whether `id` can be used as a model id or preset id as served.
+fn is_valid_identifier(id: &str) -> bool {
+    !id.is_empty()
+        && id.chars().count() <= MAX_SLUG_CHARS
+        && id.trim() == id
+        && !id.chars().any(|ch| ch.is_control() || is_invisible(ch))
+}
+
+fn check_identifier(id: &str) -> Result<(), InvalidIdentifier> {
+    if is_valid_identifier(id) {
+        Ok(())
+    } else {
+        Err(InvalidIdentifier(id.to_string()))
+    }
+}
+
+/// Sanitize every display string of a catalog entry, rejecting it when its
+/// slug or preset id is unusable. An unusable upgrade target is dropped,
+/// since it could not name another entry anyway.
+pub(crate) fn sanitize_model_info(mut info: ModelInfo) -> Result<ModelInfo, InvalidIdentifier> {
+    check_identifier(&info.slug)?;
+    if let Some(id) = &info.preset_id {
+        check_identifier(id)?;
+    }
+    info.display_name = sanitize_catalog_line(&info.display_name, MAX_DISPLAY_NAME_CHARS);
+    info.description = info
+        .description
+        .map(|description| sanitize_catalog_text(&description, MAX_DESCRIPTION_CHARS));
+    info.upgrade = info.upgrade.filter(|upgrade| is_valid_identifier(upgrade));
+    info.knowledge_cutoff = info
+        .knowledge_cutoff
+        .map(|date| sanitize_catalog_line(&date, MAX_DATE_CHARS));
+    info.released_at = info
+        .released_at
+        .map(|date| sanitize_catalog_line(&date, MAX_DATE_CHARS));
+    sanitize_effort_descriptions(&mut info.supported_reasoning_levels);
+    Ok(info)
+}
+
+/// Sanitize the display strings of a preset built from a provider catalog,
+/// rejecting it when its model id is unusable.
+pub(crate) fn sanitize_model_preset(
+    mut preset: ModelPreset,
+) -> Result<ModelPreset, InvalidIdentifier> {
+    check_identifier(&preset.model)?;
+    preset.display_name = sanitize_catalog_line(&preset.display_name, MAX_DISPLAY_NAME_CHARS);
+    preset.description = sanitize_catalog_text(&preset.description, MAX_DESCRIPTION_CHARS);
+    sanitize_effort_descriptions(&mut preset.supported_reasoning_efforts);
+    Ok(preset)
+}
+
+fn sanitize_effort_descriptions(efforts: &mut [ReasoningEffortPreset]) {
+    for effort in efforts {
+        effort.description = sanitize_catalog_line(&effort.description, MAX_DESCRIPTION_CHARS);
+    }
+}
+
+/// Consume the rest of an escape sequence whose `ESC` was already read.
+/// Handles CSI (`ESC [ … final`), OSC/DCS/APC/PM/SOS strings terminated by
+/// `BEL` or `ESC \`, and two-character escapes.
+fn skip_escape_sequence(chars: &mut std::iter::Peekable<std::str::Chars<'_>>) {
+    match chars.next() {
+        Some('[') => {
+            for ch in chars.by_ref() {
+                if ('\u{40}'..='\u{7e}').contains(&ch) {
+                    break;
+                }
+            }
+        }
+        Some(']' | 'P' | '_' | '^' | 'X') => {
+            while let Some(ch) = chars.next() {
+                if ch == BEL || (ch == ESC && chars.next_if_eq(&'\\').is_some()) {
+                    break;
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Zero-width and bidi formatting characters. They take no cell but still
+/// count as text, so names that look the same compare and truncate
+/// differently, and bidi overrides can reorder whatever the terminal draws
+/// after them. The zero-width joiner and variation selectors are kept, since
+/// emoji sequences need them.
+fn is_invisible(ch: char) -> bool {
+    matches!(
+        ch,
+        '\u{ad}'
+            | '\u{180e}'
+            | '\u{200b}'
+            | '\u{200e}'
+            | '\u{200f}'
+            | '\u{202a}'..='\u{202e}'
+            | '\u{2060}'..='\u{2064}'
+            | '\u{2066}'..='\u{2069}'
+            | '\u{feff}'
+    )
+}
+
+fn truncate_with_ellipsis(text: &str, max_chars: usize) -> String {
+    if text.chars().count() <= max_chars {
+        return text.to_string();
+    }
+    let mut truncated: String = text.chars().take(max_chars.saturating_sub(1)).collect();
+    truncated.truncate(truncated.trim_end().len());
+    truncated.push(ELLIPSIS);
+    truncated
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn strips_ansi_injection() {
+        let hostile = "\u{1b}[2J\u{1b}[HOCA \u{1b}[31;1mLarge\u{1b}[0m\u{1b}]0;pwned\u{7}\u{1b}]8;;https://evil.example\u{1b}\\link\u{1b}]8;;\u{1b}\\\u{9b}\u{8}\u{0}";
+        assert_eq!(sanitize_catalog_text(hostile, 100), "OCA Largelink");
+    }
+
+    #[test]
+    fn normalizes_newlines_and_drops_control_characters() {
+        assert_eq!(
+            sanitize_catalog_text("  first\r\nsecond\rthird\n\tfourth\u{7}  ", 100),
+            "first\nsecond\nthird\n fourth"
+        );
+        assert_eq!(
+            sanitize_catalog_line("first\r\n\r\nsecond\tthird", 100),
+            "first second third"
+        );
+    }
+
+    #[test]
+    fn drops_zero_width_and_bidi_characters() {
+        assert_eq!(
+            sanitize_catalog_line("\u{feff}OCA\u{200b} Large\u{ad}\u{2060}", 100),
+            "OCA Large"
+        );
+        assert_eq!(
+            sanitize_catalog_line("\u{202e}egral ACO\u{202c} \u{2067}模型\u{2069}", 100),
+            "egral ACO 模型"
+        );
+        // Joined emoji keep the joiner and the variation selector.
+        let emoji = "\u{1f469}\u{200d}\u{1f4bb} OCA \u{2764}\u{fe0f}";
+        assert_eq!(sanitize_catalog_line(emoji, 100), emoji);
+    }
+
+    #[test]
+    fn composes_combining_characters() {
+        let composed = "Mod\u{e8}le r\u{e9}sum\u{e9} \u{d55c}";
+        let decomposed = "Mode\u{300}le re\u{301}sume\u{301} \u{1112}\u{1161}\u{11ab}";
+        assert_eq!(sanitize_catalog_line(composed, 100), composed);
+        assert_eq!(sanitize_catalog_line(decomposed, 100), composed);
+        assert_eq!(
+            sanitize_catalog_line(decomposed, 7),
+            "Mod\u{e8}le…",
+            "a combining mark counts with its base character"
+        );
+    }
+
+    #[test]
+    fn caps_length_with_ellipsis() {
+        assert_eq!(sanitize_catalog_text("abcdef", 6), "abcdef");
+        assert_eq!(sanitize_catalog_text("abcdefg", 6), "abcde…");
+        assert_eq!(sanitize_catalog_text("abc   defg", 6), "abc…");
+        assert_eq!(
+            sanitize_catalog_line(&"é".repeat(10_000), MAX_DISPLAY_NAME_CHARS)
+                .chars()
+                .count(),
+            MAX_DISPLAY_NAME_CHARS
+        );
+    }
+
+    #[test]
+    fn sanitizes_every_visible_model_info_field() {
+        let info: ModelInfo = serde_json::from_value(serde_json::json!({
+            "slug": "oca-large",
+            "display_name": "OCA\nLarge",
+            "description": "Fast.\u{1b}[1A\u{1b}[2KTrusted!",
+            "default_reasoning_level": "medium",
+            "supported_reasoning_levels": [
+                {"effort": "low", "description": "Quick\u{1b}[5m"}
+            ],
+            "shell_type": "shell_command",
+            "visibility": "list",
+            "supported_in_api": true,
+            "priority": 1,
+            "upgrade": "oca-larger\u{7}",
+            "base_instructions": null,
+            "supports_reasoning_summaries": false,
+            "support_verbosity": false,
+            "default_verbosity": null,
+            "apply_patch_tool_type": null,
+            "truncation_policy": {"mode": "bytes", "limit": 10000},
+            "supports_parallel_tool_calls": false,
+            "context_window": null,
+            "experimental_supported_tools": [],
+        }))
+        .expect("valid model info");
+        let info = sanitize_model_info(info).expect("valid slug");
+        assert_eq!(
+            (
+                info.slug.as_str(),
+                info.display_name.as_str(),
+                info.description.as_deref(),
+                info.upgrade.as_deref(),
+                info.supported_reasoning_levels[0].description.as_str(),
+            ),
+            (
+                "oca-large",
+                "OCA Large",
+                Some("Fast.Trusted!"),
+                None,
+                "Quick"
+            )
+        );
+    }
+
+    #[test]
+    fn rejects_unusable_ids_instead_of_rewriting_them() {
+        let long = "m".repeat(MAX_SLUG_CHARS + 1);
+        let preset = |slug: &str| {
+            let mut preset = ModelPreset::builder("gw/oca-large", "oca-large")
+                .build()
+                .expect("valid preset");
+            preset.model = slug.to_string();
+            preset
+        };
+        for slug in [
+            "",
+            " oca-large",
+            "oca-large\u{1b}[8m",
+            "oca\u{200b}large",
+            &long,
+        ] {
+            assert_eq!(
+                sanitize_model_preset(preset(slug)),
+                Err(InvalidIdentifier(slug.to_string())),
+                "{slug:?} must be rejected"
+            );
+        }
+
+        let exact = "m".repeat(MAX_SLUG_CHARS);
+        for slug in ["oca-large", "org/Model Name:v2", exact.as_str()] {
+            assert_eq!(
+                sanitize_model_preset(preset(slug)).map(|preset| preset.model),
+                Ok(slug.to_string())
+            );
+        }
+    }
+}
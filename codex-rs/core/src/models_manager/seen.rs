@@ -0,0 +1,299 @@
+use codex_protocol::openai_models::ModelPreset;
+use serde::Deserialize;
+use serde::Serialize;
+use std::collections::BTreeSet;
+use std::collections::HashSet;
+use std::io;
+use std::io::ErrorKind;
+use std::path::Path;
+use std::path::PathBuf;
+use tokio::fs;
+use tracing::info;
+use tracing::warn;
+
+use super::catalog::PresetIndex;
+use super::diff::diff_catalogs;
+use super::state_dir::write_state;
+use super::state_dir::write_state_atomically;
+
+const SEEN_MODELS_FILE: &str = "models_seen.json";
+
+/// Files without a version predate keying on preset ids; their entries may be
+/// display names.
+const SEEN_MODELS_VERSION: u32 = 1;
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct SeenModelsFile {
+    #[serde(default)]
+    version: u32,
+    ids: BTreeSet<String>,
+}
+
+/// Preset ids the user has already been told about, persisted in
+/// `$CODEX_HOME/models_seen.json` so a "what's new" notice is shown once per
+/// model.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SeenModels {
+    path: PathBuf,
+    ids: BTreeSet<String>,
+    /// No seen-state file existed yet.
+    first_run: bool,
+    /// The file on disk is in the old format; see [`Self::migrate`].
+    needs_migration: bool,
+}
+
+impl SeenModels {
+    /// Read the seen state under `codex_home`. A missing or unreadable file
+    /// yields an empty state.
+    pub async fn load(codex_home: &Path) -> Self {
+        let path = codex_home.join(SEEN_MODELS_FILE);
+        let (ids, first_run, needs_migration) = match fs::read(&path).await {
+            Ok(contents) => match serde_json::from_slice::<SeenModelsFile>(&contents) {
+                Ok(file) => (file.ids, false, file.version < SEEN_MODELS_VERSION),
+                Err(err) => {
+                    warn!("ignoring unreadable {}: {err}", path.display());
+                    (BTreeSet::new(), false, false)
+                }
+            },
+            Err(err) if err.kind() == ErrorKind::NotFound => (BTreeSet::new(), true, false),
+            Err(err) => {
+                warn!("failed to read {}: {err}", path.display());
+                (BTreeSet::new(), false, false)
+            }
+        };
+        Self {
+            path,
+            ids,
+            first_run,
+            needs_migration,
+        }
+    }
+
+    /// Rewrite a state file from before entries were keyed on preset ids,
+    /// resolving each entry against `catalog` by id, slug, or display name.
+    /// Entries that match no preset are dropped. Runs once: the rewritten file
+    /// is in the current format.
+    pub async fn migrate(&mut self, catalog: &[ModelPreset]) -> io::Result<()> {
+        if !self.needs_migration {
+            return Ok(());
+        }
+        let index = PresetIndex::new(catalog);
+        let lookup = |entry: &str| {
+            index
+                .resolve(entry)
+                .map(|position| catalog[position].id.clone())
+        };
+        let mut ids = BTreeSet::new();
+        for entry in &self.ids {
+            match lookup(entry) {
+                Some(id) => {
+                    ids.insert(id);
+                }
+                None => info!("dropping seen model `{entry}`: it matches no model in the catalog"),
+            }
+        }
+        self.ids = ids;
+        self.needs_migration = false;
+        self.save().await
+    }
+
+    pub fn is_seen(&self, id: &str) -> bool {
+        self.ids.contains(id)
+    }
+
+    /// Presets that `current` adds over `previous` and that have not been
+    /// seen. On the first run every model counts as seen, so a fresh install
+    /// is not greeted with the whole catalog.
+    pub fn unseen_additions(
+        &self,
+        previous: &[ModelPreset],
+        current: &[ModelPreset],
+    ) -> Vec<ModelPreset> {
+        if self.first_run {
+            return Vec::new();
+        }
+        let added: HashSet<String> = diff_catalogs(previous, current)
+            .added
+            .into_iter()
+            .filter(|id| !self.is_seen(id))
+            .collect();
+        let mut emitted = HashSet::new();
+        current
+            .iter()
+            .filter(|preset| {
+                preset.show_in_picker
+                    && added.contains(&preset.id)
+                    && emitted.insert(preset.id.as_str())
+            })
+            .cloned()
+            .collect()
+    }
+
+    /// Record `ids` as seen and write the state back to disk. The file is
+    /// always written on the first run so later runs notice new models.
+    pub async fn mark_seen<I>(&mut self, ids: I) -> io::Result<()>
+    where
+        I: IntoIterator<Item = String>,
+    {
+        let before = self.ids.len();
+        self.ids.extend(ids);
+        if self.ids.len() == before && !self.first_run {
+            return Ok(());
+        }
+        self.first_run = false;
+        self.save().await
+    }
+
+    /// The ids recorded as seen, for [`super::state_gc`]. A file not yet
+    /// migrated to preset ids has none, since its entries may be display
+    /// names.
+    pub(crate) fn tracked_ids(&self) -> Vec<String> {
+        if self.needs_migration {
+            return Vec::new();
+        }
+        self.ids.iter().cloned().collect()
+    }
+
+    /// Drop the ids `keep` rejects. The file without them is written
+    /// atomically first, and on a failed write nothing is dropped. Returns
+    /// how many ids were dropped.
+    pub(crate) async fn retain(&mut self, keep: impl Fn(&str) -> bool) -> io::Result<usize> {
+        if self.needs_migration {
+            return Ok(0);
+        }
+        let ids: BTreeSet<String> = self
+            .ids
+            .iter()
+            .filter(|id| keep(id.as_str()))
+            .cloned()
+            .collect();
+        let pruned = self.ids.len() - ids.len();
+        if pruned == 0 {
+            return Ok(0);
+        }
+        write_state_atomically(&self.path, &Self::file_json(&ids)?).await?;
+        self.ids = ids;
+        Ok(pruned)
+    }
+
+    async fn save(&self) -> io::Result<()> {
+        write_state(&self.path, &Self::file_json(&self.ids)?).await
+    }
+
+    fn file_json(ids: &BTreeSet<String>) -> io::Result<Vec<u8>> {
+        let file = SeenModelsFile {
+            version: SEEN_MODELS_VERSION,
+            ids: ids.clone(),
+        };
+        serde_json::to_vec_pretty(&file)
+            .map_err(|err| io::Error::new(ErrorKind::InvalidData, err.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models_manager::model_presets::builtin_presets;
+    use pretty_assertions::assert_eq;
+    use tempfile::tempdir;
+
+    fn preset(id: &str) -> ModelPreset {
+        let mut preset = builtin_presets()[0].clone();
+        preset.id = id.to_string();
+        preset.model = id.to_string();
+        preset.show_in_picker = true;
+        preset
+    }
+
+    #[tokio::test]
+    async fn seen_state_persists_across_loads() {
+        let codex_home = tempdir().expect("temp dir");
+        let previous = vec![preset("oca-small")];
+        let current = vec![preset("oca-small"), preset("oca-large")];
+
+        let mut seen = SeenModels::load(codex_home.path()).await;
+        assert_eq!(seen.unseen_additions(&previous, &current), Vec::new());
+        seen.mark_seen(current.iter().map(|preset| preset.id.clone()))
+            .await
+            .expect("write seen state");
+
+        let newer = vec![preset("oca-small"), preset("oca-large"), preset("oca-xl")];
+        let mut seen = SeenModels::load(codex_home.path()).await;
+        assert!(seen.is_seen("oca-large"));
+        assert_eq!(
+            seen.unseen_additions(&current, &newer),
+            vec![preset("oca-xl")]
+        );
+        seen.mark_seen(["oca-xl".to_string()])
+            .await
+            .expect("write seen state");
+
+        // The addition is not announced again, even when a later refresh
+        // drops and re-adds it.
+        let seen = SeenModels::load(codex_home.path()).await;
+        assert_eq!(
+            seen.unseen_additions(&current, &newer),
+            Vec::<ModelPreset>::new()
+        );
+    }
+
+    #[tokio::test]
+    async fn old_state_files_are_rekeyed_on_preset_ids_once() {
+        let codex_home = tempdir().expect("temp dir");
+        let path = codex_home.path().join(SEEN_MODELS_FILE);
+        std::fs::write(
+            &path,
+            r#"{"ids": ["OCA Large", "oca-small", "gateway-slug", "Retired Model"]}"#,
+        )
+        .expect("write old state file");
+        let mut large = preset("gateway/oca-large");
+        large.display_name = "OCA Large".to_string();
+        let mut served = preset("gateway/gateway-slug");
+        served.model = "gateway-slug".to_string();
+        let catalog = vec![large, preset("oca-small"), served];
+
+        let mut seen = SeenModels::load(codex_home.path()).await;
+        seen.migrate(&catalog).await.expect("migrate seen state");
+
+        let contents = std::fs::read(&path).expect("read state file");
+        let value: serde_json::Value = serde_json::from_slice(&contents).expect("json");
+        assert_eq!(
+            value,
+            serde_json::json!({
+                "version": SEEN_MODELS_VERSION,
+                "ids": ["gateway/gateway-slug", "gateway/oca-large", "oca-small"],
+            })
+        );
+
+        // A migrated file is left alone, even against a catalog that no
+        // longer lists its models.
+        let mut seen = SeenModels::load(codex_home.path()).await;
+        seen.migrate(&[]).await.expect("nothing to migrate");
+        assert!(seen.is_seen("gateway/oca-large"));
+        assert!(!seen.is_seen("OCA Large"));
+        assert!(!seen.is_seen("Retired Model"));
+    }
+
+    #[tokio::test]
+    async fn nothing_new_yields_no_additions() {
+        let codex_home = tempdir().expect("temp dir");
+        let mut seen = SeenModels::load(codex_home.path()).await;
+        seen.mark_seen(["oca-small".to_string()])
+            .await
+            .expect("write seen state");
+        let seen = SeenModels::load(codex_home.path()).await;
+
+        let catalog = vec![preset("oca-small"), preset("oca-large")];
+        assert_eq!(
+            seen.unseen_additions(&catalog, &catalog),
+            Vec::<ModelPreset>::new()
+        );
+        let mut hidden = preset("oca-internal");
+        hidden.show_in_picker = false;
+        let with_hidden = vec![preset("oca-small"), preset("oca-large"), hidden];
+        assert_eq!(
+            seen.unseen_additions(&catalog, &with_hidden),
+            Vec::<ModelPreset>::new()
+        );
+    }
+}
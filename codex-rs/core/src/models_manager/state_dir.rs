@@ -0,0 +1,183 @@
+//! Catalog state kept under `$CODEX_HOME`: the models cache, the seen-models
+//! file, account availability, when each model was last listed, the last
+//! catalog rebuild, and the session status files.
+//!
+//! Some homes cannot be written to, such as NFS snapshots or hardened
+//! containers. Each directory is probed once per process. A read-only one is
+//! logged once, and from then on the state lives in memory for the rest of
+//! the process instead of failing every write.
+
+use std::collections::HashMap;
+use std::io;
+use std::io::ErrorKind;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::LazyLock;
+use std::sync::Mutex;
+use std::sync::PoisonError;
+
+use tokio::fs;
+use tracing::info;
+
+static WRITABLE: LazyLock<Mutex<HashMap<PathBuf, bool>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// Whether catalog state can be written to `dir`. The first call for a
+/// directory probes it, and notes in the log if it is read-only.
+pub(crate) fn is_writable(dir: &Path) -> bool {
+    let mut writable = WRITABLE.lock().unwrap_or_else(PoisonError::into_inner);
+    if let Some(known) = writable.get(dir) {
+        return *known;
+    }
+    let probed = probe(dir);
+    if !probed {
+        log_read_only(dir);
+    }
+    writable.insert(dir.to_path_buf(), probed);
+    probed
+}
+
+/// Write catalog state to `path`, creating its directory as needed. When the
+/// directory is read-only, whether the probe found it so or this write does,
+/// nothing is written and `Ok` is returned: callers already keep the state in
+/// memory.
+pub(crate) async fn write_state(path: &Path, contents: &[u8]) -> io::Result<()> {
+    persist(path, contents, false).await
+}
+
+/// Like [`write_state`], but through a temporary file next to `path` that is
+/// then renamed into place, so readers never see a partial file.
+pub(crate) async fn write_state_atomically(path: &Path, contents: &[u8]) -> io::Result<()> {
+    persist(path, contents, true).await
+}
+
+async fn persist(path: &Path, contents: &[u8], atomic: bool) -> io::Result<()> {
+    let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) else {
+        return write_file(path, contents, atomic).await;
+    };
+    if !is_writable(dir) {
+        return Ok(());
+    }
+    let result = match fs::create_dir_all(dir).await {
+        Ok(()) => write_file(path, contents, atomic).await,
+        Err(err) => Err(err),
+    };
+    match result {
+        Err(err) if is_read_only_error(&err) => {
+            mark_read_only(dir);
+            Ok(())
+        }
+        result => result,
+    }
+}
+
+async fn write_file(path: &Path, contents: &[u8], atomic: bool) -> io::Result<()> {
+    if !atomic {
+        return fs::write(path, contents).await;
+    }
+    let mut tmp = path.as_os_str().to_owned();
+    tmp.push(".tmp");
+    let tmp = PathBuf::from(tmp);
+    fs::write(&tmp, contents).await?;
+    fs::rename(&tmp, path).await
+}
+
+/// Try creating a file in `dir`, or in its closest existing ancestor when
+/// `dir` does not exist yet. The file is gone once the probe returns.
+fn probe(dir: &Path) -> bool {
+    dir.ancestors()
+        .find(|ancestor| ancestor.exists())
+        .is_some_and(|existing| tempfile::tempfile_in(existing).is_ok())
+}
+
+fn mark_read_only(dir: &Path) {
+    let previous = WRITABLE
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .insert(dir.to_path_buf(), false);
+    if previous != Some(false) {
+        log_read_only(dir);
+    }
+}
+
+fn log_read_only(dir: &Path) {
+    info!(
+        "{} is not writable; the model cache, seen models, model availability and status file are kept in memory for this session",
+        dir.display()
+    );
+}
+
+fn is_read_only_error(err: &io::Error) -> bool {
+    matches!(
+        err.kind(),
+        ErrorKind::PermissionDenied | ErrorKind::ReadOnlyFilesystem
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models_manager::cache::ModelsCache;
+    use crate::models_manager::cache::save_cache;
+    use crate::models_manager::seen::SeenModels;
+    use chrono::Utc;
+    use pretty_assertions::assert_eq;
+    use tempfile::tempdir;
+
+    /// Every catalog write site, run against `codex_home`.
+    async fn persist_everything(codex_home: &Path) -> io::Result<()> {
+        let cache = ModelsCache {
+            fetched_at: Utc::now(),
+            etag: None,
+            models: Vec::new(),
+            locale: None,
+            min_client_version: None,
+            default_model: None,
+        };
+        save_cache(&codex_home.join("models_cache.json"), &cache).await?;
+        let mut seen = SeenModels::load(codex_home).await;
+        seen.mark_seen(["gpt-5.1-codex".to_string()]).await?;
+        assert!(seen.is_seen("gpt-5.1-codex"), "kept in memory");
+        write_state(&codex_home.join("model_availability.json"), b"{}").await?;
+        write_state_atomically(&codex_home.join("run").join("session.json"), b"{}").await
+    }
+
+    #[tokio::test]
+    async fn a_home_that_cannot_be_created_keeps_state_in_memory() {
+        let root = tempdir().expect("temp dir");
+        let blocker = root.path().join("not-a-dir");
+        std::fs::write(&blocker, "").expect("write blocker");
+        let codex_home = blocker.join("codex");
+
+        assert!(!is_writable(&codex_home));
+        persist_everything(&codex_home)
+            .await
+            .expect("no error surfaces");
+        assert!(!codex_home.exists());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn a_read_only_home_keeps_state_in_memory() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let codex_home = tempdir().expect("temp dir");
+        let read_only = std::fs::Permissions::from_mode(0o555);
+        std::fs::set_permissions(codex_home.path(), read_only).expect("chmod");
+        if tempfile::tempfile_in(codex_home.path()).is_ok() {
+            // Running as root, which permissions do not stop.
+            return;
+        }
+
+        persist_everything(codex_home.path())
+            .await
+            .expect("no error surfaces");
+        let entries = std::fs::read_dir(codex_home.path())
+            .expect("read dir")
+            .count();
+        assert_eq!(entries, 0);
+
+        let writable = std::fs::Permissions::from_mode(0o755);
+        std::fs::set_permissions(codex_home.path(), writable).expect("chmod");
+    }
+}
@@ -0,0 +1,627 @@
+//! Garbage collection of per-model state under `$CODEX_HOME`.
+//!
+//! `models_seen.json`, `model_availability.json` and the sticky default kept
+//! in `models_cache.json` hold entries per model. Left alone they pile up for
+//! models that left the catalog months ago, and come back to life when a later
+//! model reuses the slug. Each listing of a catalog that changed it therefore
+//! records in `$CODEX_HOME/models_last_listed.json` when that catalog last
+//! listed every model, and prunes the entries of models no catalog has listed
+//! for the retention period, `model_state_retention_days`.
+//!
+//! The listings are kept per catalog, named by its base URL and credentials,
+//! so a tenant gateway's catalog or another account's does not age the models
+//! only the main catalog lists. Likewise only the availability marks of the
+//! account a pass lists with are pruned. A mark another provider or account
+//! made says nothing about this catalog, and is left for the passes that run
+//! with that account.
+//!
+//! Every file is replaced atomically, and the state held in memory only loses
+//! its entries once the pruned file is in place. A failed write leaves that
+//! file and its in-memory state as they were, and the next pass tries again.
+
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+use std::io;
+use std::io::ErrorKind;
+use std::path::Path;
+use std::sync::Mutex;
+use std::sync::PoisonError;
+use std::time::Duration;
+
+use chrono::DateTime;
+use chrono::Utc;
+use codex_protocol::openai_models::ModelPreset;
+use serde::Deserialize;
+use serde::Serialize;
+use tracing::debug;
+use tracing::warn;
+
+use super::availability::AccountAvailability;
+use super::cache;
+use super::manager::MODEL_CACHE_FILE;
+use super::seen::SeenModels;
+use super::state_dir::write_state_atomically;
+
+const LAST_LISTED_FILE: &str = "models_last_listed.json";
+
+/// The catalog listings were recorded under before they were kept per
+/// catalog. They still count as one catalog's until they age out.
+const UNSCOPED_CATALOG: &str = "";
+
+/// How long per-model state outlives the last catalog that listed the model,
+/// unless `model_state_retention_days` says otherwise.
+pub(crate) const DEFAULT_MODEL_STATE_RETENTION: Duration = Duration::from_secs(90 * 24 * 60 * 60);
+
+/// Preset ids and slugs, with when a catalog last listed them.
+type LastListed = BTreeMap<String, DateTime<Utc>>;
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct LastListedFile {
+    /// Per catalog, when it last listed each model.
+    #[serde(default)]
+    catalogs: BTreeMap<String, LastListed>,
+    /// Listings written before they were kept per catalog.
+    #[serde(default, skip_serializing)]
+    last_listed: LastListed,
+}
+
+/// What one [`collect_stale_model_state`] pass covers.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct ModelStateScope<'a> {
+    /// The catalog listed, by its base URL and credentials.
+    pub(crate) catalog: &'a str,
+    /// The account whose availability marks are pruned.
+    pub(crate) account: &'a str,
+}
+
+/// Record that `scope`'s catalog lists `listed`, and prune the per-model
+/// state under `codex_home` of models that no catalog has listed for
+/// `retention`. Of the availability marks, only those for the scope's account
+/// are considered. Returns how many entries were pruned.
+pub(crate) async fn collect_stale_model_state(
+    codex_home: &Path,
+    scope: ModelStateScope<'_>,
+    listed: &[ModelPreset],
+    availability: &Mutex<AccountAvailability>,
+    retention: Duration,
+    now: DateTime<Utc>,
+) -> io::Result<usize> {
+    let path = codex_home.join(LAST_LISTED_FILE);
+    let mut catalogs = load_last_listed(&path);
+    let own = catalogs.entry(scope.catalog.to_string()).or_default();
+    for preset in listed {
+        own.insert(preset.id.clone(), now);
+        own.insert(preset.model.clone(), now);
+    }
+
+    let mut seen = SeenModels::load(codex_home).await;
+    let mut tracked = seen.tracked_ids();
+    tracked.extend(
+        availability
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .models(scope.account),
+    );
+    let cache_path = codex_home.join(MODEL_CACHE_FILE);
+    tracked.extend(sticky_default(&cache_path).await);
+    // State written before any catalog listed it starts aging now instead of
+    // being pruned on the spot.
+    for key in tracked {
+        if !catalogs
+            .values()
+            .any(|listings| listings.contains_key(&key))
+        {
+            catalogs
+                .entry(scope.catalog.to_string())
+                .or_default()
+                .insert(key, now);
+        }
+    }
+
+    // A model is stale once the catalog that listed it last did so longer
+    // ago than the retention.
+    let mut latest: BTreeMap<&str, DateTime<Utc>> = BTreeMap::new();
+    for (key, listed_at) in catalogs.values().flatten() {
+        let latest = latest.entry(key.as_str()).or_insert(*listed_at);
+        *latest = (*latest).max(*listed_at);
+    }
+    let stale: BTreeSet<String> = latest
+        .into_iter()
+        .filter(|(_, listed_at)| {
+            now.signed_duration_since(*listed_at)
+                .to_std()
+                .is_ok_and(|age| age >= retention)
+        })
+        .map(|(key, _)| key.to_string())
+        .collect();
+    let keep = |key: &str| !stale.contains(key);
+
+    let mut pruned = seen.retain(keep).await?;
+    pruned += retain_availability(availability, scope.account, keep).await?;
+    pruned += retain_sticky_default(&cache_path, keep).await?;
+    // Stale keys are only forgotten once nothing refers to them any more, so
+    // a pass that failed above finds them again.
+    for listings in catalogs.values_mut() {
+        listings.retain(|key, _| keep(key.as_str()));
+    }
+    catalogs.retain(|_, listings| !listings.is_empty());
+    let json = serde_json::to_vec_pretty(&LastListedFile {
+        catalogs,
+        last_listed: LastListed::new(),
+    })
+    .map_err(|err| io::Error::new(ErrorKind::InvalidData, err.to_string()))?;
+    write_state_atomically(&path, &json).await?;
+
+    debug!(
+        pruned,
+        catalog = scope.catalog,
+        retention_days = retention.as_secs() / (24 * 60 * 60),
+        "pruned the state of models no catalog has listed"
+    );
+    Ok(pruned)
+}
+
+fn load_last_listed(path: &Path) -> BTreeMap<String, LastListed> {
+    let file = match std::fs::read(path) {
+        Ok(contents) => match serde_json::from_slice::<LastListedFile>(&contents) {
+            Ok(file) => file,
+            Err(err) => {
+                warn!("ignoring unreadable {}: {err}", path.display());
+                LastListedFile::default()
+            }
+        },
+        Err(err) if err.kind() == ErrorKind::NotFound => LastListedFile::default(),
+        Err(err) => {
+            warn!("failed to read {}: {err}", path.display());
+            LastListedFile::default()
+        }
+    };
+    let mut catalogs = file.catalogs;
+    if !file.last_listed.is_empty() {
+        catalogs
+            .entry(UNSCOPED_CATALOG.to_string())
+            .or_default()
+            .extend(file.last_listed);
+    }
+    catalogs
+}
+
+/// The model the cache at `cache_path` keeps as its catalog's default.
+async fn sticky_default(cache_path: &Path) -> Option<String> {
+    let cache = cache::load_cache(cache_path).await.ok().flatten()?;
+    cache.default_model.map(|default| default.model)
+}
+
+/// Forget the cache's sticky default when `keep` rejects its model, so a
+/// later model reusing the slug does not inherit the choice. The rest of the
+/// cache is written back as it was.
+async fn retain_sticky_default(
+    cache_path: &Path,
+    keep: impl Fn(&str) -> bool,
+) -> io::Result<usize> {
+    let Some(mut cache) = cache::load_cache(cache_path).await.ok().flatten() else {
+        return Ok(0);
+    };
+    if cache
+        .default_model
+        .as_ref()
+        .is_none_or(|default| keep(&default.model))
+    {
+        return Ok(0);
+    }
+    cache.default_model = None;
+    let json = serde_json::to_vec_pretty(&cache)
+        .map_err(|err| io::Error::new(ErrorKind::InvalidData, err.to_string()))?;
+    write_state_atomically(cache_path, &json).await?;
+    Ok(1)
+}
+
+/// Drop the marks for `account` on models `keep` rejects, once the file
+/// without them is written. The lock is not held across the write, so marks
+/// made meanwhile are kept in memory and written by their own call.
+async fn retain_availability(
+    availability: &Mutex<AccountAvailability>,
+    account: &str,
+    keep: impl Fn(&str) -> bool + Copy,
+) -> io::Result<usize> {
+    let (path, json, pruned) = {
+        let mut next = availability
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone();
+        let pruned = next.retain_models(account, keep);
+        (next.path().to_path_buf(), next.to_json(), pruned)
+    };
+    if pruned == 0 {
+        return Ok(0);
+    }
+    write_state_atomically(&path, &json?).await?;
+    availability
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .retain_models(account, keep);
+    Ok(pruned)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models_manager::model_presets::builtin_presets;
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+    use tempfile::TempDir;
+    use tempfile::tempdir;
+
+    const ACCOUNT: &str = "OpenAI via chatgpt.com, ChatGPT account me@example.com from auth.json";
+    const CATALOG: &str = "https://chatgpt.com/backend-api/codex";
+    const SCOPE: ModelStateScope<'static> = ModelStateScope {
+        catalog: CATALOG,
+        account: ACCOUNT,
+    };
+
+    fn preset(id: &str) -> ModelPreset {
+        let mut preset = builtin_presets()[0].clone();
+        preset.id = id.to_string();
+        preset.model = id.to_string();
+        preset
+    }
+
+    fn days_ago(now: DateTime<Utc>, days: i64) -> DateTime<Utc> {
+        now - chrono::Duration::days(days)
+    }
+
+    /// A home whose state mentions `oca-retired`, last listed 120 days ago,
+    /// `oca-paused`, last listed 30 days ago, and `oca-large`, still listed.
+    fn aged_home(now: DateTime<Utc>) -> (TempDir, Mutex<AccountAvailability>) {
+        let codex_home = tempdir().expect("temp dir");
+        let write = |file: &str, value: serde_json::Value| {
+            std::fs::write(codex_home.path().join(file), value.to_string()).expect("write fixture");
+        };
+        write(
+            LAST_LISTED_FILE,
+            json!({
+                "catalogs": {
+                    CATALOG: {
+                        "oca-retired": days_ago(now, 120),
+                        "oca-paused": days_ago(now, 30),
+                        "oca-large": days_ago(now, 1),
+                    }
+                }
+            }),
+        );
+        write(
+            "models_seen.json",
+            json!({"version": 1, "ids": ["oca-large", "oca-paused", "oca-retired"]}),
+        );
+        let mark = |model: &str| {
+            json!({
+                "account": ACCOUNT,
+                "model": model,
+                "reason": "not on your plan",
+                "marked_at": days_ago(now, 0),
+            })
+        };
+        write(
+            "model_availability.json",
+            json!({"unavailable": [mark("oca-retired"), mark("oca-large")]}),
+        );
+        let availability = Mutex::new(AccountAvailability::load(codex_home.path()));
+        (codex_home, availability)
+    }
+
+    fn read_json(codex_home: &Path, file: &str) -> serde_json::Value {
+        let contents = std::fs::read(codex_home.join(file)).expect("read state file");
+        serde_json::from_slice(&contents).expect("json")
+    }
+
+    fn marked_models(availability: &Mutex<AccountAvailability>) -> Vec<String> {
+        availability
+            .lock()
+            .expect("availability lock")
+            .models(ACCOUNT)
+    }
+
+    #[tokio::test]
+    async fn models_unlisted_past_the_retention_are_pruned() {
+        let now = Utc::now();
+        let (codex_home, availability) = aged_home(now);
+
+        let pruned = collect_stale_model_state(
+            codex_home.path(),
+            SCOPE,
+            &[preset("oca-large")],
+            &availability,
+            DEFAULT_MODEL_STATE_RETENTION,
+            now,
+        )
+        .await
+        .expect("prune");
+
+        assert_eq!(pruned, 2);
+        assert_eq!(
+            read_json(codex_home.path(), "models_seen.json")["ids"],
+            json!(["oca-large", "oca-paused"])
+        );
+        assert_eq!(marked_models(&availability), vec!["oca-large".to_string()]);
+        assert_eq!(
+            AccountAvailability::load(codex_home.path()).models(ACCOUNT),
+            vec!["oca-large".to_string()]
+        );
+        assert_eq!(
+            read_json(codex_home.path(), LAST_LISTED_FILE),
+            json!({
+                "catalogs": {
+                    CATALOG: {
+                        "oca-large": now,
+                        "oca-paused": days_ago(now, 30),
+                    }
+                }
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn entries_without_a_listing_start_aging_instead_of_being_pruned() {
+        let now = Utc::now();
+        let (codex_home, availability) = aged_home(now);
+        std::fs::remove_file(codex_home.path().join(LAST_LISTED_FILE)).expect("remove");
+
+        let pruned = collect_stale_model_state(
+            codex_home.path(),
+            SCOPE,
+            &[],
+            &availability,
+            DEFAULT_MODEL_STATE_RETENTION,
+            now,
+        )
+        .await
+        .expect("prune");
+        assert_eq!(pruned, 0);
+        assert_eq!(
+            read_json(codex_home.path(), "models_seen.json")["ids"],
+            json!(["oca-large", "oca-paused", "oca-retired"])
+        );
+        assert_eq!(
+            read_json(codex_home.path(), LAST_LISTED_FILE)["catalogs"][CATALOG]["oca-retired"],
+            json!(now)
+        );
+
+        // Once the retention has run out with no catalog listing them, they go.
+        let later = now + chrono::Duration::days(91);
+        let pruned = collect_stale_model_state(
+            codex_home.path(),
+            SCOPE,
+            &[],
+            &availability,
+            DEFAULT_MODEL_STATE_RETENTION,
+            later,
+        )
+        .await
+        .expect("prune");
+        assert_eq!(pruned, 5);
+        assert_eq!(
+            read_json(codex_home.path(), "models_seen.json")["ids"],
+            json!([])
+        );
+        assert_eq!(marked_models(&availability), Vec::<String>::new());
+    }
+
+    #[tokio::test]
+    async fn marks_for_other_accounts_are_not_pruned() {
+        let now = Utc::now();
+        let (codex_home, availability) = aged_home(now);
+        // A gateway whose catalog this pass does not see refused it too.
+        let gateway = "Gateway via gw.example.com, API key from $GATEWAY_KEY";
+        availability.lock().expect("availability lock").mark(
+            gateway,
+            "oca-retired",
+            "not on your plan",
+            now,
+        );
+
+        let pruned = collect_stale_model_state(
+            codex_home.path(),
+            SCOPE,
+            &[preset("oca-large")],
+            &availability,
+            DEFAULT_MODEL_STATE_RETENTION,
+            now,
+        )
+        .await
+        .expect("prune");
+
+        assert_eq!(pruned, 2);
+        assert_eq!(marked_models(&availability), vec!["oca-large".to_string()]);
+        assert_eq!(
+            availability
+                .lock()
+                .expect("availability lock")
+                .models(gateway),
+            vec!["oca-retired".to_string()]
+        );
+    }
+
+    #[tokio::test]
+    async fn a_failed_write_prunes_nothing_from_that_file() {
+        let now = Utc::now();
+        let (codex_home, availability) = aged_home(now);
+        let before =
+            std::fs::read(codex_home.path().join("model_availability.json")).expect("read marks");
+        // The atomic write goes through this path, which cannot be a file.
+        std::fs::create_dir(codex_home.path().join("model_availability.json.tmp"))
+            .expect("block the temporary file");
+
+        collect_stale_model_state(
+            codex_home.path(),
+            SCOPE,
+            &[preset("oca-large")],
+            &availability,
+            DEFAULT_MODEL_STATE_RETENTION,
+            now,
+        )
+        .await
+        .expect_err("the marks cannot be written");
+
+        assert_eq!(
+            std::fs::read(codex_home.path().join("model_availability.json")).expect("read marks"),
+            before
+        );
+        assert_eq!(
+            marked_models(&availability),
+            vec!["oca-large".to_string(), "oca-retired".to_string()]
+        );
+        // The stale model is still remembered, so the next pass retries.
+        assert_eq!(
+            read_json(codex_home.path(), LAST_LISTED_FILE)["catalogs"][CATALOG]["oca-retired"],
+            json!(days_ago(now, 120))
+        );
+
+        std::fs::remove_dir(codex_home.path().join("model_availability.json.tmp"))
+            .expect("unblock");
+        let pruned = collect_stale_model_state(
+            codex_home.path(),
+            SCOPE,
+            &[preset("oca-large")],
+            &availability,
+            DEFAULT_MODEL_STATE_RETENTION,
+            now,
+        )
+        .await
+        .expect("prune");
+        assert_eq!(pruned, 1);
+        assert_eq!(marked_models(&availability), vec!["oca-large".to_string()]);
+    }
+
+    #[tokio::test]
+    async fn another_catalogs_pass_leaves_the_models_this_one_lists() {
+        let now = Utc::now();
+        let (codex_home, availability) = aged_home(now);
+        let tenant = ModelStateScope {
+            catalog: "https://gw.example.com/v1",
+            account: "Gateway via gw.example.com, API key from $GATEWAY_KEY",
+        };
+
+        // Well within the retention for the main catalog's last listing, the
+        // tenant never listing `oca-large` or `oca-paused` prunes neither.
+        let later = now + chrono::Duration::days(30);
+        let pruned = collect_stale_model_state(
+            codex_home.path(),
+            tenant,
+            &[preset("oca-small")],
+            &availability,
+            DEFAULT_MODEL_STATE_RETENTION,
+            later,
+        )
+        .await
+        .expect("prune");
+
+        assert_eq!(pruned, 1);
+        assert_eq!(
+            read_json(codex_home.path(), "models_seen.json")["ids"],
+            json!(["oca-large", "oca-paused"])
+        );
+        // Marks belong to the main catalog's account, so they wait for its pass.
+        assert_eq!(
+            marked_models(&availability),
+            vec!["oca-large".to_string(), "oca-retired".to_string()]
+        );
+        assert_eq!(
+            read_json(codex_home.path(), LAST_LISTED_FILE),
+            json!({
+                "catalogs": {
+                    CATALOG: {
+                        "oca-large": days_ago(now, 1),
+                        "oca-paused": days_ago(now, 30),
+                    },
+                    "https://gw.example.com/v1": {"oca-small": later},
+                }
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn listings_from_before_catalogs_were_kept_apart_still_count() {
+        let now = Utc::now();
+        let (codex_home, availability) = aged_home(now);
+        std::fs::write(
+            codex_home.path().join(LAST_LISTED_FILE),
+            json!({"last_listed": {"oca-retired": days_ago(now, 120), "oca-large": now}})
+                .to_string(),
+        )
+        .expect("write fixture");
+
+        let pruned = collect_stale_model_state(
+            codex_home.path(),
+            SCOPE,
+            &[],
+            &availability,
+            DEFAULT_MODEL_STATE_RETENTION,
+            now,
+        )
+        .await
+        .expect("prune");
+
+        assert_eq!(pruned, 2);
+        assert_eq!(
+            read_json(codex_home.path(), LAST_LISTED_FILE),
+            json!({
+                "catalogs": {
+                    "": {"oca-large": now},
+                    CATALOG: {"oca-paused": now},
+                }
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn a_stale_sticky_default_is_forgotten() {
+        let now = Utc::now();
+        let (codex_home, availability) = aged_home(now);
+        let cache = |default: &str| {
+            json!({
+                "fetched_at": now,
+                "etag": "\"v1\"",
+                "models": [],
+                "default_model": {"base_url": CATALOG, "model": default},
+            })
+        };
+        let cache_path = codex_home.path().join("models_cache.json");
+        std::fs::write(&cache_path, cache("oca-retired").to_string()).expect("write cache");
+
+        let pruned = collect_stale_model_state(
+            codex_home.path(),
+            SCOPE,
+            &[preset("oca-large")],
+            &availability,
+            DEFAULT_MODEL_STATE_RETENTION,
+            now,
+        )
+        .await
+        .expect("prune");
+
+        assert_eq!(pruned, 3);
+        let mut expected = cache("oca-retired");
+        expected
+            .as_object_mut()
+            .expect("cache object")
+            .remove("default_model");
+        assert_eq!(read_json(codex_home.path(), "models_cache.json"), expected);
+
+        // A default still listed stays.
+        std::fs::write(&cache_path, cache("oca-large").to_string()).expect("write cache");
+        collect_stale_model_state(
+            codex_home.path(),
+            SCOPE,
+            &[preset("oca-large")],
+            &availability,
+            DEFAULT_MODEL_STATE_RETENTION,
+            now,
+        )
+        .await
+        .expect("prune");
+        assert_eq!(
+            read_json(codex_home.path(), "models_cache.json"),
+            cache("oca-large")
+        );
+    }
+}
@@ -0,0 +1,381 @@
+//! Catalogs of the tenant gateways app-server requests select with
+//! `catalogBaseUrl`, one per base URL, provider and credentials.
+//!
+//! Each tenant gets a [`ModelsManager`] of its own, so the models one
+//! tenant's gateway serves never show up in another tenant's listing, and a
+//! [`ModelCatalogHandle`] that refreshes it in the background with the config
+//! of the latest request for it. Requests for the same gateway with other
+//! credentials get a catalog of their own, since a gateway may list models
+//! per account. A tenant no request has used for the idle TTL is dropped,
+//! which stops its refresher; conversations already started on it keep their
+//! manager.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::sync::PoisonError;
+use std::sync::Weak;
+use std::time::Duration;
+
+use sha2::Digest;
+use sha2::Sha256;
+use tokio::time::Instant;
+
+use crate::AuthManager;
+use crate::config::Config;
+use crate::models_manager::catalog_handle::ModelCatalogHandle;
+use crate::models_manager::manager::ModelsManager;
+
+/// How long a tenant catalog no request has used keeps refreshing.
+pub(crate) const TENANT_IDLE_TTL: Duration = Duration::from_secs(30 * 60);
+
+/// Which tenant catalog a request lists: the gateway, the provider entry
+/// pointed at it, and a fingerprint of the credentials sent there.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub(crate) struct TenantKey {
+    base_url: String,
+    provider_id: String,
+    credentials: String,
+}
+
+impl TenantKey {
+    /// The key of `config`'s tenant, listed at `base_url` with the
+    /// credentials of its provider, or else those `auth_manager` holds.
+    pub(crate) fn new(base_url: &str, config: &Config, auth_manager: &AuthManager) -> Self {
+        let provider = &config.model_provider;
+        let secret = provider
+            .api_key()
+            .ok()
+            .flatten()
+            .or_else(|| {
+                provider
+                    .token_command
+                    .as_ref()
+                    .map(|command| command.join(" "))
+            })
+            .or_else(|| provider.experimental_bearer_token.clone())
+            .or_else(|| {
+                auth_manager
+                    .auth()
+                    .and_then(|auth| auth.get_account_id().or(auth.api_key))
+            });
+        // Hashed so the key never holds a credential.
+        let credentials = match secret {
+            Some(secret) => {
+                let digest = format!("{:x}", Sha256::digest(secret.as_bytes()));
+                digest.get(..16).unwrap_or(&digest).to_string()
+            }
+            None => "anonymous".to_string(),
+        };
+        Self {
+            base_url: base_url.to_string(),
+            provider_id: config.model_provider_id.clone(),
+            credentials,
+        }
+    }
+}
+
+pub(crate) struct TenantCatalogs {
+    idle_ttl: Duration,
+    tenants: Arc<Mutex<Tenants>>,
+}
+
+#[derive(Default)]
+struct Tenants {
+    by_key: HashMap<TenantKey, Tenant>,
+    /// Whether a task is evicting idle tenants.
+    sweeping: bool,
+}
+
+struct Tenant {
+    models_manager: Arc<ModelsManager>,
+    catalog: ModelCatalogHandle,
+    /// The config the refresher last got, so an unchanged one does not
+    /// trigger a rebuild.
+    config: Config,
+    last_used: Instant,
+}
+
+impl Tenants {
+    fn evict_idle(&mut self, idle_ttl: Duration, now: Instant) {
+        self.by_key
+            .retain(|_, tenant| now.duration_since(tenant.last_used) < idle_ttl);
+    }
+}
+
+impl TenantCatalogs {
+    pub(crate) fn new(idle_ttl: Duration) -> Self {
+        Self {
+            idle_ttl,
+            tenants: Arc::new(Mutex::new(Tenants::default())),
+        }
+    }
+
+    /// The manager listing the catalog of the tenant `key` names. When no
+    /// request used it within the idle TTL, `new_manager` builds one and a
+    /// refresher starts for it with `config`; otherwise the refresher moves
+    /// to `config` if it changed. Must be called from within a Tokio runtime.
+    pub(crate) fn models_manager(
+        &self,
+        key: TenantKey,
+        config: &Config,
+        new_manager: impl FnOnce() -> ModelsManager,
+    ) -> Arc<ModelsManager> {
+        let now = Instant::now();
+        let mut tenants = self.tenants.lock().unwrap_or_else(PoisonError::into_inner);
+        tenants.evict_idle(self.idle_ttl, now);
+        let tenant = tenants.by_key.entry(key).or_insert_with(|| {
+            let models_manager = Arc::new(new_manager());
+            let catalog = ModelCatalogHandle::new(Arc::clone(&models_manager), config.clone());
+            Tenant {
+                models_manager,
+                catalog,
+                config: config.clone(),
+                last_used: now,
+            }
+        });
+        if tenant.config != *config {
+            tenant.config = config.clone();
+            tenant.catalog.reload_config(config.clone());
+        }
+        tenant.last_used = now;
+        let models_manager = Arc::clone(&tenant.models_manager);
+        if !tenants.sweeping {
+            tenants.sweeping = true;
+            tokio::spawn(sweep(Arc::downgrade(&self.tenants), self.idle_ttl));
+        }
+        models_manager
+    }
+
+    /// The base URLs with a catalog kept up to date, sorted, each once.
+    pub(crate) fn base_urls(&self) -> Vec<String> {
+        let tenants = self.tenants.lock().unwrap_or_else(PoisonError::into_inner);
+        let mut base_urls: Vec<String> = tenants
+            .by_key
+            .keys()
+            .map(|key| key.base_url.clone())
+            .collect();
+        base_urls.sort();
+        base_urls.dedup();
+        base_urls
+    }
+}
+
+/// Evict idle tenants once per `idle_ttl` until none are left or the
+/// [`TenantCatalogs`] is dropped.
+async fn sweep(tenants: Weak<Mutex<Tenants>>, idle_ttl: Duration) {
+    loop {
+        tokio::time::sleep(idle_ttl).await;
+        let Some(shared) = tenants.upgrade() else {
+            return;
+        };
+        let mut state = shared.lock().unwrap_or_else(PoisonError::into_inner);
+        state.evict_idle(idle_ttl, Instant::now());
+        if state.by_key.is_empty() {
+            state.sweeping = false;
+            return;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::AuthManager;
+    use crate::CodexAuth;
+    use crate::config::ConfigBuilder;
+    use crate::model_provider_info::ModelCatalog;
+    use crate::model_provider_info::ModelProviderInfo;
+    use crate::model_provider_info::WireApi;
+    use crate::model_provider_info::create_oss_provider_with_base_url;
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+    use tempfile::TempDir;
+    use wiremock::Mock;
+    use wiremock::MockServer;
+    use wiremock::ResponseTemplate;
+    use wiremock::matchers::method;
+    use wiremock::matchers::path;
+
+    async fn gateway(model: &str) -> MockServer {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/v1/models"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "object": "list",
+                "data": [{"id": model, "object": "model"}]
+            })))
+            .mount(&server)
+            .await;
+        server
+    }
+
+    async fn test_config(codex_home: &TempDir) -> Config {
+        ConfigBuilder::default()
+            .codex_home(codex_home.path().to_path_buf())
+            .build()
+            .await
+            .expect("load default test config")
+    }
+
+    fn test_auth() -> Arc<AuthManager> {
+        AuthManager::from_auth_for_testing(CodexAuth::from_api_key("Test API Key"))
+    }
+
+    fn shared_manager() -> ModelsManager {
+        ModelsManager::with_provider(
+            test_auth(),
+            create_oss_provider_with_base_url("http://example.test", WireApi::Responses),
+        )
+    }
+
+    #[tokio::test]
+    async fn each_base_url_lists_its_own_gateway() {
+        let tenant_a = gateway("oca-a").await;
+        let tenant_b = gateway("oca-b").await;
+        let codex_home = TempDir::new().expect("temp dir");
+        let mut config = test_config(&codex_home).await;
+        let provider = ModelProviderInfo {
+            name: "Gateway".into(),
+            model_catalog: Some(ModelCatalog::Openai),
+            ..create_oss_provider_with_base_url("http://127.0.0.1:9/v1", WireApi::Responses)
+        };
+        config
+            .model_providers
+            .insert("gateway".to_string(), provider.clone());
+        config.model_provider_id = "gateway".to_string();
+        config.model_provider = provider;
+        config.allowed_catalog_base_urls = vec![
+            format!("{}/v1", tenant_a.uri()),
+            format!("{}/v1", tenant_b.uri()),
+        ];
+
+        let shared = shared_manager();
+        let auth = test_auth();
+        let tenants = TenantCatalogs::new(TENANT_IDLE_TTL);
+        let mut listed = Vec::new();
+        for server in [&tenant_a, &tenant_b] {
+            let mut tenant_config = config.clone();
+            tenant_config
+                .override_catalog_base_url(&format!("{}/v1", server.uri()))
+                .expect("allowed base URL");
+            let base_url = tenant_config
+                .catalog_base_url
+                .clone()
+                .expect("base URL set");
+            let key = TenantKey::new(&base_url, &tenant_config, &auth);
+            let manager = tenants.models_manager(key.clone(), &tenant_config, || {
+                shared.sibling(Arc::clone(&auth))
+            });
+            assert!(Arc::ptr_eq(
+                &manager,
+                &tenants.models_manager(key, &tenant_config, || shared.sibling(Arc::clone(&auth)))
+            ));
+            let ids: Vec<String> = manager
+                .list_models(&tenant_config)
+                .await
+                .into_iter()
+                .filter(|preset| preset.model_provider.as_deref() == Some("gateway"))
+                .map(|preset| preset.id)
+                .collect();
+            listed.push(ids);
+        }
+
+        assert_eq!(
+            listed,
+            vec![
+                vec!["gateway/oca-a".to_string()],
+                vec!["gateway/oca-b".to_string()],
+            ]
+        );
+        assert_eq!(tenants.base_urls().len(), 2);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn idle_tenants_are_evicted() {
+        let codex_home = TempDir::new().expect("temp dir");
+        let mut config = test_config(&codex_home).await;
+        // Nothing is fetched, so only the clock decides what happens.
+        config.offline = true;
+        let shared = shared_manager();
+        let auth = test_auth();
+        let idle_ttl = Duration::from_secs(60);
+        let tenants = TenantCatalogs::new(idle_ttl);
+        let key_a = TenantKey::new("https://tenant-a.example.com/v1", &config, &auth);
+        let key_b = TenantKey::new("https://tenant-b.example.com/v1", &config, &auth);
+
+        let first =
+            tenants.models_manager(key_a.clone(), &config, || shared.sibling(Arc::clone(&auth)));
+        tokio::time::sleep(idle_ttl / 2).await;
+        tenants.models_manager(key_b, &config, || shared.sibling(Arc::clone(&auth)));
+        tokio::time::sleep(idle_ttl / 2 + Duration::from_secs(1)).await;
+        assert_eq!(
+            tenants.base_urls(),
+            vec!["https://tenant-b.example.com/v1".to_string()],
+            "the tenant unused for the idle TTL is gone"
+        );
+
+        let second = tenants.models_manager(key_a, &config, || shared.sibling(Arc::clone(&auth)));
+        assert!(
+            !Arc::ptr_eq(&first, &second),
+            "an evicted tenant starts over"
+        );
+
+        tokio::time::sleep(idle_ttl * 2 + Duration::from_secs(1)).await;
+        assert_eq!(tenants.base_urls(), Vec::<String>::new());
+    }
+
+    #[tokio::test]
+    async fn other_credentials_get_a_catalog_of_their_own() {
+        let codex_home = TempDir::new().expect("temp dir");
+        let mut config = test_config(&codex_home).await;
+        config.offline = true;
+        let shared = shared_manager();
+        let tenants = TenantCatalogs::new(TENANT_IDLE_TTL);
+        let base_url = "https://tenant.example.com/v1";
+        let alice = test_auth();
+        let bob = AuthManager::from_auth_for_testing(CodexAuth::from_api_key("Other API Key"));
+
+        let for_alice =
+            tenants.models_manager(TenantKey::new(base_url, &config, &alice), &config, || {
+                shared.sibling(Arc::clone(&alice))
+            });
+        let for_bob =
+            tenants.models_manager(TenantKey::new(base_url, &config, &bob), &config, || {
+                shared.sibling(Arc::clone(&bob))
+            });
+
+        assert!(!Arc::ptr_eq(&for_alice, &for_bob));
+        assert_eq!(tenants.base_urls(), vec![base_url.to_string()]);
+        assert_ne!(
+            TenantKey::new(base_url, &config, &alice),
+            TenantKey::new(base_url, &config, &alice.signed_out()),
+            "a request without credentials does not share the signed-in catalog"
+        );
+    }
+
+    #[tokio::test]
+    async fn a_changed_config_reaches_the_refresher() {
+        let codex_home = TempDir::new().expect("temp dir");
+        let mut config = test_config(&codex_home).await;
+        config.offline = true;
+        let shared = shared_manager();
+        let auth = test_auth();
+        let tenants = TenantCatalogs::new(TENANT_IDLE_TTL);
+        let key = TenantKey::new("https://tenant.example.com/v1", &config, &auth);
+
+        let first =
+            tenants.models_manager(key.clone(), &config, || shared.sibling(Arc::clone(&auth)));
+        let mut changed = config.clone();
+        changed.model_context_window = Some(64_000);
+        let second =
+            tenants.models_manager(key.clone(), &changed, || shared.sibling(Arc::clone(&auth)));
+
+        assert!(Arc::ptr_eq(&first, &second), "the tenant keeps its manager");
+        let tenants = tenants
+            .tenants
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        assert_eq!(tenants.by_key[&key].config, changed);
+    }
+}
@@ -0,0 +1,588 @@
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::fmt;
+
+use codex_protocol::openai_models::Capability;
+use codex_protocol::openai_models::ModelPreset;
+use codex_protocol::openai_models::ReasoningEffort;
+
+use crate::config::types::ModelPresetOverride;
+use crate::models_manager::catalog::PresetCatalog;
+use crate::protocol::SandboxPolicy;
+
+const MAX_SUGGESTIONS: usize = 3;
+
+/// A model or reasoning effort the catalog of the active provider does not
+/// offer. Renders as the message shown to the user.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InvalidSelection {
+    UnknownModel {
+        model: String,
+        provider_name: String,
+        /// Every id and slug the catalog lists, in catalog order.
+        known: Vec<String>,
+        /// The entries of `known` closest to `model`, closest first.
+        suggestions: Vec<String>,
+    },
+    UnsupportedEffort {
+        model: String,
+        effort: ReasoningEffort,
+        supported: Vec<ReasoningEffort>,
+    },
+    /// `disabled_reasoning_efforts` removes `effort` from every model.
+    DisabledEffort { effort: ReasoningEffort },
+    /// A turn needs `capability`, which `model` lacks.
+    MissingCapability {
+        model: String,
+        capability: Capability,
+        /// Ids of the listed presets that have `capability`, in catalog order.
+        supporting: Vec<String>,
+    },
+}
+
+impl fmt::Display for InvalidSelection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InvalidSelection::UnknownModel {
+                model,
+                provider_name,
+                suggestions,
+                ..
+            } => {
+                write!(
+                    f,
+                    "model `{model}` is not in the model catalog for provider `{provider_name}`."
+                )?;
+                match suggestions.as_slice() {
+                    [] => {}
+                    [only] => write!(f, " Did you mean `{only}`?")?,
+                    many => write!(
+                        f,
+                        " Did you mean one of {}?",
+                        many.iter()
+                            .map(|slug| format!("`{slug}`"))
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    )?,
+                }
+                f.write_str(" Run `codex models list` to see the available models.")
+            }
+            InvalidSelection::UnsupportedEffort {
+                model,
+                effort,
+                supported,
+            } => write!(
+                f,
+                "model `{model}` does not support reasoning effort `{effort}`; it supports {}.",
+                supported
+                    .iter()
+                    .map(|effort| format!("`{effort}`"))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            InvalidSelection::DisabledEffort { effort } => write!(
+                f,
+                "reasoning effort `{effort}` is disabled by `disabled_reasoning_efforts`."
+            ),
+            InvalidSelection::MissingCapability {
+                model,
+                capability,
+                supporting,
+            } if supporting.is_empty() => write!(
+                f,
+                "model `{model}` does not support {capability}, and no model in your catalog does."
+            ),
+            InvalidSelection::MissingCapability {
+                model,
+                capability,
+                supporting,
+            } => write!(
+                f,
+                "model `{model}` does not support {capability}; these models in your catalog support {capability}: {}. Switch to one of them to use {capability}.",
+                supporting
+                    .iter()
+                    .map(|id| format!("`{id}`"))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+        }
+    }
+}
+
+/// Something about a requested selection that does not stop a session from
+/// starting with it, but changes what the session does. Renders as the
+/// message shown to the user.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SelectionWarning {
+    /// `model` is retired, so sessions run `successor` instead.
+    RetiredModel { model: String, successor: String },
+    /// The provider refused `model` to the signed-in account recently.
+    UnavailableForAccount { model: String, reason: String },
+    /// `model` is only served to ChatGPT sign-in, not to API keys.
+    RequiresChatgptAuth { model: String },
+    /// `preset_overrides` moves the requested effort into its range.
+    EffortClamped {
+        model: String,
+        requested: ReasoningEffort,
+        effort: ReasoningEffort,
+    },
+    /// `model` takes no reasoning effort, so requests omit it.
+    EffortIgnored {
+        model: String,
+        effort: ReasoningEffort,
+    },
+    /// `model` needs network access, which the sandbox blocks.
+    RequiresNetwork { model: String },
+}
+
+impl fmt::Display for SelectionWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SelectionWarning::RetiredModel { model, successor } => write!(
+                f,
+                "model `{model}` is retired; sessions use `{successor}` instead."
+            ),
+            SelectionWarning::UnavailableForAccount { model, reason } => write!(
+                f,
+                "model `{model}` was recently unavailable to your account: {reason}"
+            ),
+            SelectionWarning::RequiresChatgptAuth { model } => write!(
+                f,
+                "model `{model}` is only available when signed in with ChatGPT, not with an API key."
+            ),
+            SelectionWarning::EffortClamped {
+                model,
+                requested,
+                effort,
+            } => write!(
+                f,
+                "reasoning effort `{requested}` is outside the range `preset_overrides` sets for model `{model}`; `{effort}` will be used."
+            ),
+            SelectionWarning::EffortIgnored { model, effort } => write!(
+                f,
+                "model `{model}` does not support a reasoning effort; `{effort}` will be ignored."
+            ),
+            SelectionWarning::RequiresNetwork { model } => write!(
+                f,
+                "model `{model}` needs network access, but the sandbox blocks it; commands that reach the network will fail."
+            ),
+        }
+    }
+}
+
+/// The outcome of [`PresetCatalog::validate_options`]: what a session
+/// started with the options would run, and what is wrong with them.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SelectionReport {
+    /// The model slug the session would resolve to.
+    pub model: String,
+    /// Problems that would reject the options.
+    pub errors: Vec<InvalidSelection>,
+    pub warnings: Vec<SelectionWarning>,
+}
+
+impl SelectionReport {
+    pub fn is_valid(&self) -> bool {
+        self.errors.is_empty()
+    }
+}
+
+/// Check `model` against the ids and slugs in `known`, suggesting the
+/// closest entries when none matches. Returns `None` when `model` is known.
+pub(crate) fn check_model(
+    model: &str,
+    provider_name: &str,
+    mut known: Vec<String>,
+) -> Option<InvalidSelection> {
+    if known.iter().any(|candidate| candidate == model) {
+        return None;
+    }
+    let mut seen = HashSet::new();
+    known.retain(|candidate| seen.insert(candidate.clone()));
+    let suggestions = suggest_models(model, &known)
+        .into_iter()
+        .map(str::to_string)
+        .collect();
+    Some(InvalidSelection::UnknownModel {
+        model: model.to_string(),
+        provider_name: provider_name.to_string(),
+        known,
+        suggestions,
+    })
+}
+
+/// Check `effort` against the efforts of the preset serving `model` from
+/// `provider_id`. Models without a preset, or whose preset lists no
+/// efforts, are not checked.
+pub(crate) fn check_effort(
+    presets: &[ModelPreset],
+    model: &str,
+    provider_id: &str,
+    effort: ReasoningEffort,
+) -> Option<InvalidSelection> {
+    let preset = presets.iter().find(|preset| {
+        (preset.model == model || preset.id == model)
+            && preset
+                .model_provider
+                .as_deref()
+                .is_none_or(|served_by| served_by == provider_id)
+    })?;
+    let supported: Vec<ReasoningEffort> = preset
+        .supported_reasoning_efforts
+        .iter()
+        .map(|option| option.effort)
+        .collect();
+    if supported.is_empty() || supported.contains(&effort) {
+        return None;
+    }
+    Some(InvalidSelection::UnsupportedEffort {
+        model: model.to_string(),
+        effort,
+        supported,
+    })
+}
+
+/// The error for a requested `effort` that `disabled_reasoning_efforts`
+/// lists. No model is checked, since the effort is removed from all of them.
+pub(crate) fn disabled_effort(
+    disabled: &[ReasoningEffort],
+    effort: ReasoningEffort,
+) -> Option<InvalidSelection> {
+    disabled
+        .contains(&effort)
+        .then_some(InvalidSelection::DisabledEffort { effort })
+}
+
+/// The error for a turn that needs `capability` from `model`, which lacks
+/// it, naming the presets in `supporting`, those with it, other than `model`.
+pub(crate) fn missing_capability(
+    supporting: &[ModelPreset],
+    model: &str,
+    capability: Capability,
+) -> InvalidSelection {
+    let mut seen = HashSet::new();
+    let supporting = supporting
+        .iter()
+        .filter(|preset| preset.model != model && seen.insert(preset.id.as_str()))
+        .map(|preset| preset.id.clone())
+        .collect();
+    InvalidSelection::MissingCapability {
+        model: model.to_string(),
+        capability,
+        supporting,
+    }
+}
+
+/// The warning for running `preset` under `sandbox`, when the preset needs
+/// network access the sandbox does not allow. The sandbox is left as is.
+pub fn network_warning(preset: &ModelPreset, sandbox: &SandboxPolicy) -> Option<SelectionWarning> {
+    (preset.requires_network && !sandbox.has_full_network_access()).then(|| {
+        SelectionWarning::RequiresNetwork {
+            model: preset.model.clone(),
+        }
+    })
+}
+
+/// The warning for a configured `effort` on a preset that lists no efforts,
+/// which means the model takes no effort parameter and requests omit it.
+pub(crate) fn ignored_effort_warning(
+    preset: &ModelPreset,
+    effort: ReasoningEffort,
+) -> Option<String> {
+    preset.supported_reasoning_efforts.is_empty().then(|| {
+        format!(
+            "model `{}` does not support a reasoning effort; `model_reasoning_effort = \"{effort}\"` will be ignored.",
+            preset.model
+        )
+    })
+}
+
+/// Warnings for `model_preset_overrides` entries that name a preset missing
+/// from `presets`, or an effort the preset does not offer, sorted by preset
+/// id.
+pub(crate) fn preset_override_warnings(
+    overrides: &HashMap<String, ModelPresetOverride>,
+    presets: &[ModelPreset],
+) -> Vec<String> {
+    let mut ids: Vec<&String> = overrides.keys().collect();
+    ids.sort();
+    let mut warnings = Vec::new();
+    for id in ids {
+        let Some(preset) = presets.iter().find(|preset| &preset.id == id) else {
+            warnings.push(format!(
+                "`model_preset_overrides.\"{id}\"` names no preset in the model catalog, so it is ignored."
+            ));
+            continue;
+        };
+        for name in overrides[id].effort_descriptions.keys() {
+            let key = format!("model_preset_overrides.\"{id}\".effort_descriptions.{name}");
+            match name.parse::<ReasoningEffort>() {
+                Err(_) => warnings.push(format!(
+                    "`{key}` is not a reasoning effort, so it is ignored."
+                )),
+                Ok(effort)
+                    if !preset
+                        .supported_reasoning_efforts
+                        .iter()
+                        .any(|supported| supported.effort == effort) =>
+                {
+                    warnings.push(format!(
+                        "`{id}` does not offer the `{effort}` reasoning effort, so `{key}` is ignored."
+                    ));
+                }
+                Ok(_) => {}
+            }
+        }
+    }
+    warnings
+}
+
+/// Entries of `known` within a small edit distance of `model`, closest first.
+fn suggest_models<'a>(model: &str, known: &'a [String]) -> Vec<&'a str> {
+    let model = model.to_ascii_lowercase();
+    let threshold = (model.chars().count() / 3).max(2);
+    let mut scored: Vec<(usize, &str)> = known
+        .iter()
+        .filter_map(|candidate| {
+            let distance = edit_distance(&model, &candidate.to_ascii_lowercase());
+            (distance <= threshold).then_some((distance, candidate.as_str()))
+        })
+        .collect();
+    scored.sort_unstable();
+    scored.dedup();
+    scored
+        .into_iter()
+        .take(MAX_SUGGESTIONS)
+        .map(|(_, candidate)| candidate)
+        .collect()
+}
+
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, a_char) in a.chars().enumerate() {
+        let mut current = Vec::with_capacity(b.len() + 1);
+        current.push(i + 1);
+        for (j, b_char) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(a_char != *b_char);
+            current.push(substitution.min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models_manager::model_presets::builtin_presets;
+    use pretty_assertions::assert_eq;
+
+    fn known() -> Vec<String> {
+        ["gpt-5.1-codex", "gpt-5.1-codex-max", "gpt-5.1", "o3"]
+            .into_iter()
+            .map(str::to_string)
+            .collect()
+    }
+
+    fn unknown_model_message(model: &str) -> Option<String> {
+        check_model(model, "OpenAI", known()).map(|invalid| invalid.to_string())
+    }
+
+    #[test]
+    fn known_model_has_no_message() {
+        assert_eq!(unknown_model_message("gpt-5.1"), None);
+    }
+
+    #[test]
+    fn typo_suggests_closest_model() {
+        assert_eq!(
+            unknown_model_message("gpt-5.1-codx"),
+            Some(
+                "model `gpt-5.1-codx` is not in the model catalog for provider `OpenAI`. Did you mean `gpt-5.1-codex`? Run `codex models list` to see the available models."
+                    .to_string()
+            )
+        );
+    }
+
+    #[test]
+    fn suggestions_are_ordered_by_distance() {
+        assert_eq!(
+            suggest_models("GPT-5.1-codex-mx", &known()),
+            vec!["gpt-5.1-codex-max", "gpt-5.1-codex"]
+        );
+    }
+
+    #[test]
+    fn unrelated_model_has_no_suggestions() {
+        assert_eq!(
+            unknown_model_message("llama-3-70b-instruct"),
+            Some(
+                "model `llama-3-70b-instruct` is not in the model catalog for provider `OpenAI`. Run `codex models list` to see the available models."
+                    .to_string()
+            )
+        );
+    }
+
+    #[test]
+    fn unknown_model_lists_each_known_entry_once() {
+        let known = ["oca-large", "oca-large", "oca-small"]
+            .into_iter()
+            .map(str::to_string)
+            .collect();
+        assert_eq!(
+            check_model("oca-lrge", "Gateway", known),
+            Some(InvalidSelection::UnknownModel {
+                model: "oca-lrge".to_string(),
+                provider_name: "Gateway".to_string(),
+                known: vec!["oca-large".to_string(), "oca-small".to_string()],
+                suggestions: vec!["oca-large".to_string()],
+            })
+        );
+    }
+
+    #[test]
+    fn effort_is_checked_against_the_preset_efforts() {
+        let presets: Vec<ModelPreset> = builtin_presets()
+            .iter()
+            .filter(|preset| preset.model == "gpt-5.1-codex-mini")
+            .cloned()
+            .collect();
+        let preset = presets.first().expect("gpt-5.1-codex-mini is built in");
+        let supported: Vec<ReasoningEffort> = preset
+            .supported_reasoning_efforts
+            .iter()
+            .map(|option| option.effort)
+            .collect();
+
+        assert_eq!(
+            check_effort(&presets, "gpt-5.1-codex-mini", "openai", supported[0]),
+            None
+        );
+        assert_eq!(
+            check_effort(
+                &presets,
+                "some-other-model",
+                "openai",
+                ReasoningEffort::None
+            ),
+            None,
+            "models without a preset are not checked"
+        );
+        let invalid = check_effort(
+            &presets,
+            "gpt-5.1-codex-mini",
+            "openai",
+            ReasoningEffort::Minimal,
+        )
+        .expect("minimal is not offered");
+        assert_eq!(
+            invalid,
+            InvalidSelection::UnsupportedEffort {
+                model: "gpt-5.1-codex-mini".to_string(),
+                effort: ReasoningEffort::Minimal,
+                supported,
+            }
+        );
+        assert_eq!(
+            invalid.to_string(),
+            "model `gpt-5.1-codex-mini` does not support reasoning effort `minimal`; it supports `medium`, `high`."
+        );
+    }
+
+    #[test]
+    fn effort_on_a_preset_without_efforts_is_ignored_with_a_warning() {
+        let mut preset = builtin_presets()
+            .iter()
+            .find(|preset| preset.model == "gpt-5.1-codex-mini")
+            .cloned()
+            .expect("gpt-5.1-codex-mini is built in");
+        assert_eq!(ignored_effort_warning(&preset, ReasoningEffort::High), None);
+
+        preset.supported_reasoning_efforts = Vec::new();
+        assert_eq!(
+            ignored_effort_warning(&preset, ReasoningEffort::High),
+            Some(
+                "model `gpt-5.1-codex-mini` does not support a reasoning effort; `model_reasoning_effort = \"high\"` will be ignored."
+                    .to_string()
+            )
+        );
+        assert_eq!(
+            check_effort(
+                std::slice::from_ref(&preset),
+                "gpt-5.1-codex-mini",
+                "openai",
+                ReasoningEffort::High
+            ),
+            None,
+            "an ignored effort does not fail validation"
+        );
+    }
+
+    #[test]
+    fn network_warning_only_when_the_sandbox_blocks_a_required_network() {
+        let networked = ModelPreset::builder("web-agent", "web-agent")
+            .requires_network(true)
+            .build()
+            .expect("valid preset");
+        let offline = ModelPreset::builder("local", "local")
+            .build()
+            .expect("valid preset");
+        let mut workspace_with_network = SandboxPolicy::new_workspace_write_policy();
+        if let SandboxPolicy::WorkspaceWrite { network_access, .. } = &mut workspace_with_network {
+            *network_access = true;
+        }
+
+        let warning = network_warning(&networked, &SandboxPolicy::ReadOnly);
+        assert_eq!(
+            warning,
+            Some(SelectionWarning::RequiresNetwork {
+                model: "web-agent".to_string(),
+            })
+        );
+        assert_eq!(
+            warning.as_ref().map(ToString::to_string),
+            Some(
+                "model `web-agent` needs network access, but the sandbox blocks it; commands that reach the network will fail."
+                    .to_string()
+            )
+        );
+        assert!(
+            network_warning(&networked, &SandboxPolicy::new_workspace_write_policy()).is_some()
+        );
+        assert_eq!(network_warning(&networked, &workspace_with_network), None);
+        assert_eq!(
+            network_warning(&networked, &SandboxPolicy::DangerFullAccess),
+            None
+        );
+        assert_eq!(network_warning(&offline, &SandboxPolicy::ReadOnly), None);
+    }
+
+    fn image_presets() -> Vec<ModelPreset> {
+        ["gpt-5.1", "local-vision", "gpt-5.1"]
+            .into_iter()
+            .map(|model| {
+                ModelPreset::builder(model, model)
+                    .supports_images(true)
+                    .build()
+                    .expect("valid preset")
+            })
+            .collect()
+    }
+
+    #[test]
+    fn missing_image_support_names_the_models_that_have_it() {
+        assert_eq!(
+            missing_capability(&image_presets(), "local-text", Capability::Images).to_string(),
+            "model `local-text` does not support images; these models in your catalog support images: `gpt-5.1`, `local-vision`. Switch to one of them to use images."
+        );
+    }
+
+    #[test]
+    fn missing_image_support_without_alternatives_says_so() {
+        assert_eq!(
+            missing_capability(&[], "local-text", Capability::Images).to_string(),
+            "model `local-text` does not support images, and no model in your catalog does."
+        );
+    }
+}
@@ -17,6 +17,7 @@ use crate::config::Config;
 use crate::skills::SkillMetadata;
 use crate::skills::render_skills_section;
 use dunce::canonicalize as normalize_path;
+use std::path::Path;
 use std::path::PathBuf;
 use tokio::io::AsyncReadExt;
 use tracing::error;
@@ -26,6 +27,19 @@ pub const DEFAULT_PROJECT_DOC_FILENAME: &str = "AGENTS.md";
 /// Preferred local override for project-level docs.
 pub const LOCAL_PROJECT_DOC_FILENAME: &str = "AGENTS.override.md";
 
+/// Marks a model named in a project doc, as in `@model:gpt-5.1-codex`, so
+/// `validate_instruction_models` can check it against the model catalog.
+pub const MODEL_REFERENCE_MARKER: &str = "@model:";
+
+/// A model a project doc names with [`MODEL_REFERENCE_MARKER`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ModelReference {
+    pub model: String,
+    pub path: PathBuf,
+    /// 1-based line of the reference in `path`.
+    pub line: usize,
+}
+
 /// When both `Config::instructions` and the project doc are present, they will
 /// be concatenated with the following separator.
 const PROJECT_DOC_SEPARATOR: &str = "\n\n--- project-doc ---\n\n";
@@ -200,6 +214,91 @@ pub fn discover_project_doc_paths(config: &Config) -> std::io::Result<Vec<PathBu
     Ok(found)
 }
 
+/// Every [`MODEL_REFERENCE_MARKER`] reference in the docs
+/// [`discover_project_doc_paths`] finds, in discovery order. Files are read
+/// in full, and nothing about them is changed.
+pub async fn read_model_references(config: &Config) -> std::io::Result<Vec<ModelReference>> {
+    let mut references = Vec::new();
+    for path in discover_project_doc_paths(config)? {
+        let text = match tokio::fs::read(&path).await {
+            Ok(bytes) => String::from_utf8_lossy(&bytes).into_owned(),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => continue,
+            Err(e) => return Err(e),
+        };
+        references.extend(
+            find_model_references(&text)
+                .into_iter()
+                .map(|(line, model)| ModelReference {
+                    model,
+                    path: path.clone(),
+                    line,
+                }),
+        );
+    }
+    Ok(references)
+}
+
+/// The models `text` names with [`MODEL_REFERENCE_MARKER`], with their 1-based
+/// lines. A reference starts a word and runs over letters, digits, `.`, `-`,
+/// `_` and `/`; a trailing `.` ends the sentence rather than the id.
+fn find_model_references(text: &str) -> Vec<(usize, String)> {
+    let mut references = Vec::new();
+    for (index, line) in text.lines().enumerate() {
+        for (start, _) in line.match_indices(MODEL_REFERENCE_MARKER) {
+            // `name@model:...` is part of a longer word, such as an address.
+            if line[..start]
+                .chars()
+                .next_back()
+                .is_some_and(|c| c.is_ascii_alphanumeric())
+            {
+                continue;
+            }
+            let rest = &line[start + MODEL_REFERENCE_MARKER.len()..];
+            let end = rest
+                .find(|c: char| !is_model_id_char(c))
+                .unwrap_or(rest.len());
+            let model = rest[..end].trim_end_matches('.');
+            if !model.is_empty() {
+                references.push((index + 1, model.to_string()));
+            }
+        }
+    }
+    references
+}
+
+fn is_model_id_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '_' | '/')
+}
+
+/// The warning naming each of `unknown`, the references to models the catalog
+/// does not list, with the file and line it is on. Files under `cwd` are
+/// shown relative to it.
+pub(crate) fn unknown_model_references_message(
+    unknown: &[&ModelReference],
+    cwd: &Path,
+) -> Option<String> {
+    if unknown.is_empty() {
+        return None;
+    }
+    let cwd = normalize_path(cwd).unwrap_or_else(|_| cwd.to_path_buf());
+    let listed = unknown
+        .iter()
+        .map(|reference| {
+            let path = reference.path.strip_prefix(&cwd).unwrap_or(&reference.path);
+            format!(
+                "`{}` ({}:{})",
+                reference.model,
+                path.display(),
+                reference.line
+            )
+        })
+        .collect::<Vec<_>>()
+        .join(", ");
+    Some(format!(
+        "project docs reference models that are not in the model catalog: {listed}. The instructions are sent as written; run `codex models list` to see the available models."
+    ))
+}
+
 fn candidate_filenames<'a>(config: &'a Config) -> Vec<&'a str> {
     let mut names: Vec<&'a str> =
         Vec::with_capacity(2 + config.project_doc_fallback_filenames.len());
@@ -544,6 +643,48 @@ mod tests {
         assert_eq!(res, expected);
     }
 
+    #[test]
+    fn model_references_are_found_with_their_lines() {
+        let text = "# Agents\n\nUse @model:gpt-5.1-codex for refactors.\nReviews: `@model:o3`, then @model:oca/large_v2.\nAn email@model:example is not one, nor is a bare @model:.\n";
+        assert_eq!(
+            find_model_references(text),
+            vec![
+                (3, "gpt-5.1-codex".to_string()),
+                (4, "o3".to_string()),
+                (4, "oca/large_v2".to_string()),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn model_references_name_the_doc_and_line() {
+        let tmp = tempfile::tempdir().expect("tempdir");
+        fs::write(
+            tmp.path().join("AGENTS.md"),
+            "Use @model:gpt-5 for refactors.\n\nAsk @model:gpt-9-turbo for reviews.\n",
+        )
+        .unwrap();
+        let config = make_config(&tmp, 4096, None).await;
+
+        let references = read_model_references(&config).await.expect("read docs");
+        assert_eq!(
+            references
+                .iter()
+                .map(|reference| (reference.model.as_str(), reference.line))
+                .collect::<Vec<_>>(),
+            vec![("gpt-5", 1), ("gpt-9-turbo", 3)]
+        );
+
+        assert_eq!(
+            unknown_model_references_message(&[&references[1]], &config.cwd),
+            Some(
+                "project docs reference models that are not in the model catalog: `gpt-9-turbo` (AGENTS.md:3). The instructions are sent as written; run `codex models list` to see the available models."
+                    .to_string()
+            )
+        );
+        assert_eq!(unknown_model_references_message(&[], &config.cwd), None);
+    }
+
     fn create_skill(codex_home: PathBuf, name: &str, description: &str) {
         let skill_dir = codex_home.join(format!("skills/{name}"));
         fs::create_dir_all(&skill_dir).unwrap();
@@ -0,0 +1,173 @@
+//! Per-model caps on concurrent model requests.
+//!
+//! Some gateways reject requests beyond a per-model concurrency limit with
+//! 429s. When a preset or catalog entry sets `max_concurrent_requests`, every
+//! request to that model waits for a permit first. The permits are shared by
+//! all conversations in the process, so the app-server stays under the cap too.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::sync::LazyLock;
+use std::sync::Mutex;
+use std::sync::PoisonError;
+use std::task::Poll;
+
+use tokio::sync::OwnedSemaphorePermit;
+use tokio::sync::Semaphore;
+
+struct ModelLimit {
+    max: u32,
+    semaphore: Arc<Semaphore>,
+}
+
+static LIMITS: LazyLock<Mutex<HashMap<String, ModelLimit>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// Wait until another request to `model` on `provider` may start, when its
+/// concurrency is capped. The returned permit must be held for as long as the
+/// request (including its response stream) is in flight. `None` or `0` means
+/// unbounded, and nothing is waited for.
+pub(crate) async fn acquire_request_permit(
+    provider: &str,
+    model: &str,
+    max_concurrent_requests: Option<u32>,
+) -> Option<OwnedSemaphorePermit> {
+    let max = max_concurrent_requests.filter(|max| *max > 0)?;
+    let (semaphore, held) = semaphore_for(&format!("{provider}/{model}"), max);
+    if held > 0 {
+        take_when_released(&semaphore, held).await;
+    }
+    semaphore.acquire_owned().await.ok()
+}
+
+/// The semaphore for `key`, resized in place when the limit changed since it
+/// was created (for instance after a catalog refresh), so requests holding or
+/// waiting for its permits stay counted against the new limit. Also returns
+/// how many permits a lowered limit removed that are still held by requests
+/// in flight; see [`take_when_released`].
+fn semaphore_for(key: &str, max: u32) -> (Arc<Semaphore>, u32) {
+    let mut limits = LIMITS.lock().unwrap_or_else(PoisonError::into_inner);
+    let limit = limits.entry(key.to_string()).or_insert_with(|| ModelLimit {
+        max,
+        semaphore: Arc::new(Semaphore::new(max as usize)),
+    });
+    let mut held = 0;
+    if max > limit.max {
+        limit.semaphore.add_permits((max - limit.max) as usize);
+    } else if max < limit.max {
+        let removed = (limit.max - max) as usize;
+        let forgotten = limit.semaphore.forget_permits(removed);
+        held = (removed - forgotten) as u32;
+    }
+    limit.max = max;
+    (Arc::clone(&limit.semaphore), held)
+}
+
+/// Remove `count` more permits from `semaphore` as the requests holding them
+/// finish. The semaphore hands out permits in order, so this waits ahead of
+/// every request that starts waiting after it, and none of those start until
+/// the requests in flight are under the new limit.
+async fn take_when_released(semaphore: &Arc<Semaphore>, count: u32) {
+    let mut take = Box::pin(Arc::clone(semaphore).acquire_many_owned(count));
+    // Polled once here so it is queued before the caller's own request.
+    match futures::poll!(take.as_mut()) {
+        Poll::Ready(Ok(permits)) => permits.forget(),
+        Poll::Ready(Err(_)) => {}
+        Poll::Pending => {
+            tokio::spawn(async move {
+                if let Ok(permits) = take.await {
+                    permits.forget();
+                }
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use std::sync::atomic::AtomicUsize;
+    use std::sync::atomic::Ordering;
+    use std::time::Duration;
+
+    /// Run `requests` simulated requests at once and return the most that
+    /// were in flight together.
+    async fn peak_concurrency(model: &str, limit: Option<u32>, requests: usize) -> usize {
+        let in_flight = Arc::new(AtomicUsize::new(0));
+        let peak = Arc::new(AtomicUsize::new(0));
+        let tasks: Vec<_> = (0..requests)
+            .map(|_| {
+                let model = model.to_string();
+                let in_flight = Arc::clone(&in_flight);
+                let peak = Arc::clone(&peak);
+                tokio::spawn(async move {
+                    let _permit = acquire_request_permit("test", &model, limit).await;
+                    let now = in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+                    peak.fetch_max(now, Ordering::SeqCst);
+                    tokio::time::sleep(Duration::from_millis(20)).await;
+                    in_flight.fetch_sub(1, Ordering::SeqCst);
+                })
+            })
+            .collect();
+        for task in tasks {
+            task.await.expect("request task");
+        }
+        peak.load(Ordering::SeqCst)
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn capped_models_never_exceed_their_limit() {
+        assert_eq!(peak_concurrency("capped-model", Some(2), 8).await, 2);
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn uncapped_models_are_not_throttled() {
+        assert!(
+            acquire_request_permit("test", "free-model", None)
+                .await
+                .is_none()
+        );
+        assert_eq!(peak_concurrency("free-model", None, 4).await, 4);
+        assert_eq!(peak_concurrency("zero-model", Some(0), 4).await, 4);
+    }
+
+    #[tokio::test]
+    async fn a_changed_limit_resizes_the_semaphore_in_place() {
+        let (first, held) = semaphore_for("test/resized-model", 1);
+        assert_eq!(held, 0);
+        let (raised, _) = semaphore_for("test/resized-model", 3);
+        assert!(Arc::ptr_eq(&first, &raised));
+        assert_eq!(raised.available_permits(), 3);
+        let (lowered, _) = semaphore_for("test/resized-model", 2);
+        assert!(Arc::ptr_eq(&first, &lowered));
+        assert_eq!(lowered.available_permits(), 2);
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn a_lowered_limit_holds_back_new_requests_until_in_flight_ones_finish() {
+        let first = acquire_request_permit("test", "lowered-model", Some(3))
+            .await
+            .expect("permit");
+        let second = acquire_request_permit("test", "lowered-model", Some(3))
+            .await
+            .expect("permit");
+
+        // Two requests are in flight when the limit drops to one, so a new
+        // request waits until both have finished.
+        let waiting = tokio::spawn(acquire_request_permit("test", "lowered-model", Some(1)));
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        drop(first);
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert!(!waiting.is_finished());
+
+        drop(second);
+        let third = tokio::time::timeout(Duration::from_secs(1), waiting)
+            .await
+            .expect("request starts once the others finish")
+            .expect("request task");
+        assert!(third.is_some());
+        drop(third);
+        assert_eq!(peak_concurrency("lowered-model", Some(1), 4).await, 1);
+    }
+}
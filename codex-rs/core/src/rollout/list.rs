@@ -44,6 +44,8 @@ pub struct ConversationItem {
     pub created_at: Option<String>,
     /// RFC3339 timestamp string for the most recent update (from file mtime).
     pub updated_at: Option<String>,
+    /// Model slug of the first recorded turn, if one was found near the head.
+    pub model: Option<String>,
 }
 
 #[derive(Default)]
@@ -53,6 +55,7 @@ struct HeadTailSummary {
     saw_user_event: bool,
     source: Option<SessionSource>,
     model_provider: Option<String>,
+    model: Option<String>,
     created_at: Option<String>,
     updated_at: Option<String>,
 }
@@ -60,6 +63,9 @@ struct HeadTailSummary {
 /// Hard cap to bound worst‑case work per request.
 const MAX_SCAN_FILES: usize = 10000;
 const HEAD_RECORD_LIMIT: usize = 10;
+/// Lines read past the first user message while looking for the turn context
+/// that records the session's model.
+const TURN_CONTEXT_SCAN_LIMIT: usize = 10;
 
 /// Pagination cursor identifying a file by timestamp and UUID.
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -222,6 +228,7 @@ async fn traverse_directories_for_paths(
                     if summary.saw_session_meta && summary.saw_user_event {
                         let HeadTailSummary {
                             head,
+                            model,
                             created_at,
                             mut updated_at,
                             ..
@@ -237,6 +244,7 @@ async fn traverse_directories_for_paths(
                             head,
                             created_at,
                             updated_at,
+                            model,
                         });
                     }
                 }
@@ -417,8 +425,9 @@ async fn read_head_summary(path: &Path, head_limit: usize) -> io::Result<HeadTai
                     summary.head.push(val);
                 }
             }
-            RolloutItem::TurnContext(_) => {
-                // Not included in `head`; skip.
+            RolloutItem::TurnContext(turn_context) => {
+                // Not included in `head`; only the model is kept.
+                summary.model.get_or_insert(turn_context.model);
             }
             RolloutItem::Compacted(_) => {
                 // Not included in `head`; skip.
@@ -435,6 +444,24 @@ async fn read_head_summary(path: &Path, head_limit: usize) -> io::Result<HeadTai
         }
     }
 
+    // The first turn context is written just after the user message that
+    // starts the turn, so look a little further for it.
+    if summary.model.is_none() && summary.saw_session_meta && summary.saw_user_event {
+        for _ in 0..TURN_CONTEXT_SCAN_LIMIT {
+            let Some(line) = lines.next_line().await? else {
+                break;
+            };
+            if let Ok(RolloutLine {
+                item: RolloutItem::TurnContext(turn_context),
+                ..
+            }) = serde_json::from_str(line.trim())
+            {
+                summary.model = Some(turn_context.model);
+                break;
+            }
+        }
+    }
+
     Ok(summary)
 }
 
@@ -30,6 +30,7 @@ use codex_protocol::protocol::InitialHistory;
 use codex_protocol::protocol::ResumedHistory;
 use codex_protocol::protocol::RolloutItem;
 use codex_protocol::protocol::RolloutLine;
+use codex_protocol::protocol::SessionCatalog;
 use codex_protocol::protocol::SessionMeta;
 use codex_protocol::protocol::SessionMetaLine;
 use codex_protocol::protocol::SessionSource;
@@ -55,6 +56,7 @@ pub enum RolloutRecorderParams {
         conversation_id: ConversationId,
         instructions: Option<String>,
         source: SessionSource,
+        model_catalog: Option<SessionCatalog>,
     },
     Resume {
         path: PathBuf,
@@ -77,11 +79,13 @@ impl RolloutRecorderParams {
         conversation_id: ConversationId,
         instructions: Option<String>,
         source: SessionSource,
+        model_catalog: Option<SessionCatalog>,
     ) -> Self {
         Self::Create {
             conversation_id,
             instructions,
             source,
+            model_catalog,
         }
     }
 
@@ -120,6 +124,7 @@ impl RolloutRecorder {
                 conversation_id,
                 instructions,
                 source,
+                model_catalog,
             } => {
                 let LogFileInfo {
                     file,
@@ -148,6 +153,7 @@ impl RolloutRecorder {
                         instructions,
                         source,
                         model_provider: Some(config.model_provider_id.clone()),
+                        model_catalog,
                     }),
                 )
             }
@@ -13,20 +13,30 @@ use time::macros::format_description;
 use uuid::Uuid;
 
 use crate::rollout::INTERACTIVE_SESSION_SOURCES;
+use crate::rollout::RolloutRecorder;
+use crate::rollout::RolloutRecorderParams;
 use crate::rollout::list::ConversationItem;
 use crate::rollout::list::ConversationsPage;
 use crate::rollout::list::Cursor;
 use crate::rollout::list::get_conversations;
+use crate::rollout::list::read_head_for_summary;
 use anyhow::Result;
 use codex_protocol::ConversationId;
+use codex_protocol::config_types::ReasoningSummary;
 use codex_protocol::models::ContentItem;
 use codex_protocol::models::ResponseItem;
+use codex_protocol::protocol::AskForApproval;
 use codex_protocol::protocol::EventMsg;
+use codex_protocol::protocol::InitialHistory;
 use codex_protocol::protocol::RolloutItem;
 use codex_protocol::protocol::RolloutLine;
+use codex_protocol::protocol::SandboxPolicy;
+use codex_protocol::protocol::SessionCatalog;
+use codex_protocol::protocol::SessionCatalogSource;
 use codex_protocol::protocol::SessionMeta;
 use codex_protocol::protocol::SessionMetaLine;
 use codex_protocol::protocol::SessionSource;
+use codex_protocol::protocol::TurnContextItem;
 use codex_protocol::protocol::UserMessageEvent;
 
 const NO_SOURCE_FILTER: &[SessionSource] = &[];
@@ -234,18 +244,21 @@ async fn test_list_conversations_latest_first() {
                 head: head_3,
                 created_at: Some("2025-01-03T12-00-00".into()),
                 updated_at: updated_times.first().cloned().flatten(),
+                model: None,
             },
             ConversationItem {
                 path: p2,
                 head: head_2,
                 created_at: Some("2025-01-02T12-00-00".into()),
                 updated_at: updated_times.get(1).cloned().flatten(),
+                model: None,
             },
             ConversationItem {
                 path: p3,
                 head: head_1,
                 created_at: Some("2025-01-01T12-00-00".into()),
                 updated_at: updated_times.get(2).cloned().flatten(),
+                model: None,
             },
         ],
         next_cursor: None,
@@ -364,12 +377,14 @@ async fn test_pagination_cursor() {
                 head: head_5,
                 created_at: Some("2025-03-05T09-00-00".into()),
                 updated_at: updated_page1.first().cloned().flatten(),
+                model: None,
             },
             ConversationItem {
                 path: p4,
                 head: head_4,
                 created_at: Some("2025-03-04T09-00-00".into()),
                 updated_at: updated_page1.get(1).cloned().flatten(),
+                model: None,
             },
         ],
         next_cursor: Some(expected_cursor1.clone()),
@@ -431,12 +446,14 @@ async fn test_pagination_cursor() {
                 head: head_3,
                 created_at: Some("2025-03-03T09-00-00".into()),
                 updated_at: updated_page2.first().cloned().flatten(),
+                model: None,
             },
             ConversationItem {
                 path: p2,
                 head: head_2,
                 created_at: Some("2025-03-02T09-00-00".into()),
                 updated_at: updated_page2.get(1).cloned().flatten(),
+                model: None,
             },
         ],
         next_cursor: Some(expected_cursor2.clone()),
@@ -479,6 +496,7 @@ async fn test_pagination_cursor() {
             head: head_1,
             created_at: Some("2025-03-01T09-00-00".into()),
             updated_at: updated_page3.first().cloned().flatten(),
+            model: None,
         }],
         next_cursor: None,
         num_scanned_files: 5, // scanned 05, 04 (anchor), 03, 02 (anchor), 01
@@ -534,6 +552,7 @@ async fn test_get_conversation_contents() {
             head: expected_head,
             created_at: Some(ts.into()),
             updated_at: page.items[0].updated_at.clone(),
+            model: None,
         }],
         next_cursor: None,
         num_scanned_files: 1,
@@ -592,6 +611,7 @@ async fn test_updated_at_uses_file_mtime() -> Result<()> {
                 cli_version: "test_version".into(),
                 source: SessionSource::VSCode,
                 model_provider: Some("test-provider".into()),
+                model_catalog: None,
             },
             git: None,
         }),
@@ -708,12 +728,14 @@ async fn test_stable_ordering_same_second_pagination() {
                 head: head(u3),
                 created_at: Some(ts.to_string()),
                 updated_at: updated_page1.first().cloned().flatten(),
+                model: None,
             },
             ConversationItem {
                 path: p2,
                 head: head(u2),
                 created_at: Some(ts.to_string()),
                 updated_at: updated_page1.get(1).cloned().flatten(),
+                model: None,
             },
         ],
         next_cursor: Some(expected_cursor1.clone()),
@@ -746,6 +768,7 @@ async fn test_stable_ordering_same_second_pagination() {
             head: head(u1),
             created_at: Some(ts.to_string()),
             updated_at: updated_page2.first().cloned().flatten(),
+            model: None,
         }],
         next_cursor: None,
         num_scanned_files: 3, // scanned u3, u2 (anchor), u1
@@ -916,3 +939,125 @@ async fn test_model_provider_filter_selects_only_matching_sessions() -> Result<(
 
     Ok(())
 }
+
+#[tokio::test]
+async fn session_header_records_the_model_catalog() -> Result<()> {
+    let temp = TempDir::new().unwrap();
+    let mut config = crate::config::test_config();
+    config.codex_home = temp.path().to_path_buf();
+    let catalog = SessionCatalog {
+        revision: Some("W/\"catalog-7\"".to_string()),
+        source: SessionCatalogSource::Live,
+        fetched_at: Some("2025-06-01T08:00:00+00:00".to_string()),
+    };
+
+    let recorder = RolloutRecorder::new(
+        &config,
+        RolloutRecorderParams::new(
+            ConversationId::new(),
+            None,
+            SessionSource::Cli,
+            Some(catalog.clone()),
+        ),
+    )
+    .await?;
+    recorder.flush().await?;
+    recorder.shutdown().await?;
+
+    let head = read_head_for_summary(&recorder.rollout_path).await?;
+    let meta_line: SessionMetaLine = serde_json::from_value(head[0].clone())?;
+    assert_eq!(meta_line.meta.model_catalog, Some(catalog.clone()));
+
+    let InitialHistory::Resumed(resumed) =
+        RolloutRecorder::get_rollout_history(&recorder.rollout_path).await?
+    else {
+        panic!("expected a resumable rollout");
+    };
+    let catalogs: Vec<_> = resumed
+        .history
+        .iter()
+        .filter_map(|item| match item {
+            RolloutItem::SessionMeta(meta_line) => Some(meta_line.meta.model_catalog.clone()),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(catalogs, vec![Some(catalog)]);
+    Ok(())
+}
+
+#[test]
+fn session_header_without_model_catalog_still_loads() -> Result<()> {
+    let line: RolloutLine = serde_json::from_value(serde_json::json!({
+        "timestamp": "2025-01-01T00:00:00.000Z",
+        "type": "session_meta",
+        "payload": {
+            "id": "67e55044-10b1-426f-9247-bb680e5fe0c8",
+            "timestamp": "2025-01-01T00:00:00Z",
+            "cwd": ".",
+            "originator": "codex_cli_rs",
+            "cli_version": "0.60.0",
+            "instructions": null,
+            "source": "cli",
+            "model_provider": "openai"
+        }
+    }))?;
+    let RolloutItem::SessionMeta(meta_line) = line.item else {
+        panic!("expected a session header");
+    };
+    assert_eq!(meta_line.meta.model_catalog, None);
+
+    // Re-serializing leaves the header as older versions wrote it.
+    let value = serde_json::to_value(&meta_line)?;
+    assert_eq!(value.get("model_catalog"), None);
+    Ok(())
+}
+
+#[tokio::test]
+async fn listed_conversations_carry_the_first_turn_model() -> Result<()> {
+    let temp = TempDir::new().unwrap();
+    let home = temp.path();
+    let ts = "2025-09-02T12-00-00";
+    let with_turn = Uuid::from_u128(1);
+    let without_turn = Uuid::from_u128(2);
+    write_session_file(home, ts, with_turn, 2, Some(SessionSource::Cli))?;
+    write_session_file(
+        home,
+        "2025-09-02T11-00-00",
+        without_turn,
+        2,
+        Some(SessionSource::Cli),
+    )?;
+
+    let path = home
+        .join("sessions/2025/09/02")
+        .join(format!("rollout-{ts}-{with_turn}.jsonl"));
+    let turn_context = RolloutLine {
+        timestamp: ts.to_string(),
+        item: RolloutItem::TurnContext(TurnContextItem {
+            cwd: ".".into(),
+            approval_policy: AskForApproval::OnRequest,
+            sandbox_policy: SandboxPolicy::ReadOnly,
+            model: "gpt-5.1-codex".to_string(),
+            effort: None,
+            effort_description: None,
+            summary: ReasoningSummary::Auto,
+            base_instructions: None,
+            user_instructions: None,
+            developer_instructions: None,
+            final_output_json_schema: None,
+            truncation_policy: None,
+            preset: None,
+        }),
+    };
+    let mut file = fs::OpenOptions::new().append(true).open(&path)?;
+    writeln!(file, "{}", serde_json::to_string(&turn_context)?)?;
+
+    let page = get_conversations(home, 10, None, NO_SOURCE_FILTER, None, TEST_PROVIDER).await?;
+    let models: Vec<_> = page
+        .items
+        .iter()
+        .map(|item| item.model.as_deref())
+        .collect();
+    assert_eq!(models, vec![Some("gpt-5.1-codex"), None]);
+    Ok(())
+}
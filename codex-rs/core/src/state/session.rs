@@ -4,6 +4,7 @@ use codex_protocol::models::ResponseItem;
 
 use crate::codex::SessionConfiguration;
 use crate::context_manager::ContextManager;
+use crate::cost_guard::CostGuard;
 use crate::protocol::RateLimitSnapshot;
 use crate::protocol::TokenUsage;
 use crate::protocol::TokenUsageInfo;
@@ -14,6 +15,9 @@ pub(crate) struct SessionState {
     pub(crate) session_configuration: SessionConfiguration,
     pub(crate) history: ContextManager,
     pub(crate) latest_rate_limits: Option<RateLimitSnapshot>,
+    /// Whether we already logged that a preset turned off parallel tool calls.
+    pub(crate) parallel_tool_calls_disabled_logged: bool,
+    pub(crate) cost_guard: CostGuard,
 }
 
 impl SessionState {
@@ -24,6 +28,8 @@ impl SessionState {
             session_configuration,
             history,
             latest_rate_limits: None,
+            parallel_tool_calls_disabled_logged: false,
+            cost_guard: CostGuard::default(),
         }
     }
 
@@ -51,10 +57,12 @@ impl SessionState {
     // Token/rate limit helpers
     pub(crate) fn update_token_info_from_usage(
         &mut self,
+        model: &str,
         usage: &TokenUsage,
         model_context_window: Option<i64>,
     ) {
-        self.history.update_token_info(usage, model_context_window);
+        self.history
+            .update_token_info(model, usage, model_context_window);
     }
 
     pub(crate) fn token_info(&self) -> Option<TokenUsageInfo> {
@@ -11,6 +11,7 @@ use tokio_util::task::AbortOnDropHandle;
 use codex_protocol::models::ResponseInputItem;
 use tokio::sync::oneshot;
 
+use crate::codex::SessionSettingsUpdate;
 use crate::codex::TurnContext;
 use crate::protocol::ReviewDecision;
 use crate::tasks::SessionTask;
@@ -19,6 +20,9 @@ use crate::tasks::SessionTask;
 pub(crate) struct ActiveTurn {
     pub(crate) tasks: IndexMap<String, RunningTask>,
     pub(crate) turn_state: Arc<Mutex<TurnState>>,
+    /// Model, provider, and effort changes asked for while the turn runs,
+    /// applied once it ends.
+    pub(crate) queued_model_switch: Option<SessionSettingsUpdate>,
 }
 
 impl Default for ActiveTurn {
@@ -26,6 +30,7 @@ impl Default for ActiveTurn {
         Self {
             tasks: IndexMap::new(),
             turn_state: Arc::new(Mutex::new(TurnState::default())),
+            queued_model_switch: None,
         }
     }
 }
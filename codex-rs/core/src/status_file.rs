@@ -0,0 +1,175 @@
+//! A machine-readable status file per session, for shell prompts and status
+//! bars that show the active model.
+//!
+//! With `write_status_file = true`, each session keeps
+//! `$CODEX_HOME/run/<session>.json` up to date. The file is rewritten only
+//! when its contents change, always by writing a temporary file next to it
+//! and renaming it into place, so readers never see a partial file. A clean
+//! shutdown removes it.
+
+use std::io;
+use std::path::Path;
+use std::path::PathBuf;
+
+use codex_protocol::openai_models::ReasoningEffortPreset;
+use codex_protocol::protocol::SessionCatalog;
+use serde::Serialize;
+use tokio::sync::Mutex;
+use tracing::warn;
+
+use crate::models_manager::provenance::PresetSource;
+use crate::models_manager::state_dir::write_state_atomically;
+
+const RUN_DIR: &str = "run";
+
+/// Contents of `$CODEX_HOME/run/<session>.json`.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SessionStatus {
+    pub session_id: String,
+    /// Model slug sent to the provider.
+    pub model: String,
+    /// Catalog preset the model was selected from, when it is in the catalog.
+    pub preset_id: Option<String>,
+    pub display_name: Option<String>,
+    /// Effort in use, with the description the preset gives it. `None` for
+    /// models without configurable effort.
+    pub effort: Option<ReasoningEffortPreset>,
+    /// Share of the context window in use, from 0 to 100, once a turn has
+    /// reported token usage.
+    pub context_used_percent: Option<i64>,
+    /// The remote catalog as of session start.
+    pub catalog: SessionCatalog,
+    /// Which catalog the preset came from.
+    pub preset_source: Option<PresetSource>,
+}
+
+#[derive(Debug)]
+pub(crate) struct StatusFile {
+    path: PathBuf,
+    /// Last status written; held across the write so updates land in order.
+    last: Mutex<Option<SessionStatus>>,
+}
+
+impl StatusFile {
+    pub(crate) fn new(codex_home: &Path, session_id: &str) -> Self {
+        Self {
+            path: codex_home.join(RUN_DIR).join(format!("{session_id}.json")),
+            last: Mutex::new(None),
+        }
+    }
+
+    #[cfg(test)]
+    pub(crate) fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Write `status` unless it matches what is already on disk.
+    pub(crate) async fn update(&self, status: SessionStatus) {
+        let mut last = self.last.lock().await;
+        if last.as_ref() == Some(&status) {
+            return;
+        }
+        match write_atomically(&self.path, &status).await {
+            Ok(()) => *last = Some(status),
+            Err(err) => warn!("failed to write {}: {err}", self.path.display()),
+        }
+    }
+
+    pub(crate) async fn remove(&self) {
+        let mut last = self.last.lock().await;
+        match tokio::fs::remove_file(&self.path).await {
+            Ok(()) => {}
+            Err(err) if err.kind() == io::ErrorKind::NotFound => {}
+            Err(err) => warn!("failed to remove {}: {err}", self.path.display()),
+        }
+        *last = None;
+    }
+}
+
+/// Under a read-only home nothing is written; see [`write_state_atomically`].
+async fn write_atomically(path: &Path, status: &SessionStatus) -> io::Result<()> {
+    let contents = serde_json::to_vec_pretty(status)
+        .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err.to_string()))?;
+    write_state_atomically(path, &contents).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codex_protocol::openai_models::ReasoningEffort;
+    use codex_protocol::protocol::SessionCatalogSource;
+    use pretty_assertions::assert_eq;
+    use std::sync::Arc;
+    use std::sync::atomic::AtomicBool;
+    use std::sync::atomic::Ordering;
+    use tempfile::tempdir;
+
+    fn status(context_used_percent: i64) -> SessionStatus {
+        SessionStatus {
+            session_id: "session".to_string(),
+            model: "gpt-5.2-codex".to_string(),
+            preset_id: Some("gpt-5.2-codex".to_string()),
+            display_name: Some("gpt-5.2-codex".to_string()),
+            effort: Some(ReasoningEffortPreset {
+                effort: ReasoningEffort::High,
+                description: "Maximizes reasoning depth".to_string(),
+                max_output_tokens: None,
+                request_timeout_secs: None,
+                stream_idle_timeout_secs: None,
+            }),
+            context_used_percent: Some(context_used_percent),
+            catalog: SessionCatalog {
+                revision: None,
+                source: SessionCatalogSource::Bundled,
+                fetched_at: None,
+            },
+            preset_source: Some(PresetSource::Builtin),
+        }
+    }
+
+    #[tokio::test]
+    async fn readers_never_see_a_partial_file() {
+        let codex_home = tempdir().expect("temp dir");
+        let file = Arc::new(StatusFile::new(codex_home.path(), "session"));
+        file.update(status(0)).await;
+
+        let done = Arc::new(AtomicBool::new(false));
+        let reader = {
+            let path = file.path().to_path_buf();
+            let done = Arc::clone(&done);
+            std::thread::spawn(move || {
+                let mut reads = 0;
+                while !done.load(Ordering::Relaxed) {
+                    let contents = std::fs::read(&path).expect("status file stays in place");
+                    serde_json::from_slice::<serde_json::Value>(&contents)
+                        .expect("status file is always complete JSON");
+                    reads += 1;
+                }
+                reads
+            })
+        };
+        for percent in 1..=100 {
+            file.update(status(percent)).await;
+        }
+        done.store(true, Ordering::Relaxed);
+
+        assert!(reader.join().expect("reader thread") > 0);
+        let contents = std::fs::read(file.path()).expect("read status file");
+        let value: serde_json::Value = serde_json::from_slice(&contents).expect("json");
+        assert_eq!(value["context_used_percent"], 100);
+        assert_eq!(value["effort"]["effort"], "high");
+    }
+
+    #[tokio::test]
+    async fn remove_deletes_the_file() {
+        let codex_home = tempdir().expect("temp dir");
+        let file = StatusFile::new(codex_home.path(), "session");
+        file.update(status(10)).await;
+        assert!(file.path().exists());
+
+        file.remove().await;
+
+        assert!(!file.path().exists());
+        file.remove().await;
+    }
+}
@@ -25,13 +25,12 @@ impl SessionTask for CompactTask {
         _cancellation_token: CancellationToken,
     ) -> Option<String> {
         let session = session.clone_session();
-        if crate::compact::should_use_remote_compact_task(
-            session.as_ref(),
-            &ctx.client.get_provider(),
-        ) {
-            crate::compact_remote::run_remote_compact_task(session, ctx).await
+        let client = session.compaction_client(&ctx).await;
+        if crate::compact::should_use_remote_compact_task(session.as_ref(), &client.get_provider())
+        {
+            crate::compact_remote::run_remote_compact_task(session, ctx, client).await
         } else {
-            crate::compact::run_compact_task(session, ctx, input).await
+            crate::compact::run_compact_task(session, ctx, client, input).await
         }
 
         None
@@ -18,6 +18,7 @@ use tracing::warn;
 
 use crate::AuthManager;
 use crate::codex::Session;
+use crate::codex::SessionSettingsUpdate;
 use crate::codex::TurnContext;
 use crate::models_manager::manager::ModelsManager;
 use crate::protocol::EventMsg;
@@ -156,10 +157,16 @@ impl Session {
     }
 
     pub async fn abort_all_tasks(self: &Arc<Self>, reason: TurnAbortReason) {
-        for task in self.take_all_running_tasks().await {
+        let (tasks, queued_model_switch) = self.take_all_running_tasks().await;
+        for task in tasks {
             self.handle_task_abort(task, reason.clone()).await;
         }
         self.close_unified_exec_sessions().await;
+        if let Some(switch) = queued_model_switch
+            && let Some(change) = self.apply_queued_model_switch(switch).await
+        {
+            self.report_queued_model_switch(change).await;
+        }
     }
 
     pub async fn on_task_finished(
@@ -168,18 +175,29 @@ impl Session {
         last_agent_message: Option<String>,
     ) {
         let mut active = self.active_turn.lock().await;
+        let mut queued_model_switch = None;
         let should_close_sessions = if let Some(at) = active.as_mut()
             && at.remove_task(&turn_context.sub_id)
         {
+            queued_model_switch = at.queued_model_switch.take();
             *active = None;
             true
         } else {
             false
         };
+        // Switch while the turn lock is held, so the next turn cannot start
+        // on the old model.
+        let switched = match queued_model_switch {
+            Some(switch) => self.apply_queued_model_switch(switch).await,
+            None => None,
+        };
         drop(active);
         if should_close_sessions {
             self.close_unified_exec_sessions().await;
         }
+        if let Some(change) = switched {
+            self.report_queued_model_switch(change).await;
+        }
         let event = EventMsg::TaskComplete(TaskCompleteEvent { last_agent_message });
         self.send_event(turn_context.as_ref(), event).await;
     }
@@ -191,15 +209,15 @@ impl Session {
         *active = Some(turn);
     }
 
-    async fn take_all_running_tasks(&self) -> Vec<RunningTask> {
+    async fn take_all_running_tasks(&self) -> (Vec<RunningTask>, Option<SessionSettingsUpdate>) {
         let mut active = self.active_turn.lock().await;
         match active.take() {
             Some(mut at) => {
                 at.clear_pending().await;
 
-                at.drain_tasks()
+                (at.drain_tasks(), at.queued_model_switch.take())
             }
-            None => Vec::new(),
+            None => (Vec::new(), None),
         }
     }
 
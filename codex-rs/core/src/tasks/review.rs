@@ -88,10 +88,15 @@ async fn start_review_conversation(
         .disable(crate::features::Feature::WebSearchRequest)
         .disable(crate::features::Feature::ViewImageTool);
 
+    // The parent session guards the cost; nobody could answer the delegate's
+    // own cost-limit prompt.
+    sub_agent_config.session_cost_warning_usd = None;
+    sub_agent_config.session_cost_limit_usd = None;
+
     // Set explicit review rubric for the sub-agent
     sub_agent_config.base_instructions = Some(crate::REVIEW_PROMPT.to_string());
 
-    sub_agent_config.model = Some(config.review_model.clone());
+    sub_agent_config.model = Some(ctx.client.get_model());
     (run_codex_conversation_one_shot(
         sub_agent_config,
         session.auth_manager(),
@@ -223,7 +228,10 @@ pub(crate) async fn exit_review_mode(
     session
         .send_event(
             ctx.as_ref(),
-            EventMsg::ExitedReviewMode(ExitedReviewModeEvent { review_output }),
+            EventMsg::ExitedReviewMode(ExitedReviewModeEvent {
+                review_output,
+                model: Some(ctx.client.get_model()),
+            }),
         )
         .await;
     session
@@ -60,6 +60,9 @@ impl SessionTask for UserShellCommandTask {
     ) -> Option<String> {
         let event = EventMsg::TaskStarted(TaskStartedEvent {
             model_context_window: turn_context.client.get_model_context_window(),
+            reasoning_effort: None,
+            reasoning_effort_description: None,
+            reasoning_summary_format: None,
         });
         let session = session.clone_session();
         session.send_event(turn_context.as_ref(), event).await;
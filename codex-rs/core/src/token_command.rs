@@ -0,0 +1,312 @@
+//! Bearer tokens printed by a provider's `token_command`.
+//!
+//! Gateways that hand out short-lived session tokens can name a command that
+//! prints one. Its output is cached per command for the provider's TTL, so
+//! sessions and catalog refreshes in the process share a token, and dropped
+//! when the provider rejects it so the next request mints a fresh one.
+
+use std::collections::HashMap;
+use std::process::Stdio;
+use std::sync::LazyLock;
+use std::sync::Mutex;
+use std::sync::PoisonError;
+use std::time::Duration;
+use std::time::Instant;
+
+use codex_utils_string::take_bytes_at_char_boundary;
+use tokio::process::Command;
+
+use crate::error::CodexErr;
+use crate::error::Result;
+use crate::models_manager::provenance::TokenFreshness;
+use crate::models_manager::provenance::TokenSource;
+use crate::models_manager::provenance::redact_error;
+
+/// How long the command may run before it is killed.
+const TOKEN_COMMAND_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Most bytes of the command's stderr quoted in an error.
+const MAX_STDERR_BYTES: usize = 512;
+
+struct CachedToken {
+    token: String,
+    minted_at: Instant,
+    expires_at: Instant,
+}
+
+impl CachedToken {
+    fn freshness(&self, source: TokenSource, now: Instant) -> TokenFreshness {
+        TokenFreshness {
+            source,
+            age_ms: duration_ms(now.saturating_duration_since(self.minted_at)),
+            expires_in_ms: duration_ms(self.expires_at.saturating_duration_since(now)),
+        }
+    }
+}
+
+/// A token printed by a `token_command`, with where it came from.
+#[derive(Debug, Clone)]
+pub(crate) struct CommandToken {
+    pub(crate) token: String,
+    pub(crate) freshness: TokenFreshness,
+}
+
+static TOKENS: LazyLock<Mutex<HashMap<Vec<String>, CachedToken>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// The token `command` prints, trimmed. A token printed less than `ttl` ago
+/// is reused instead of running the command again.
+pub(crate) async fn token_from_command(command: &[String], ttl: Duration) -> Result<CommandToken> {
+    if let Some(token) = cached_token(command) {
+        return Ok(token);
+    }
+    let token = run_token_command(command).await?;
+    let now = Instant::now();
+    let cached = CachedToken {
+        token,
+        minted_at: now,
+        expires_at: now + ttl,
+    };
+    let minted = CommandToken {
+        token: cached.token.clone(),
+        freshness: cached.freshness(TokenSource::Refreshed, now),
+    };
+    TOKENS
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .insert(command.to_vec(), cached);
+    Ok(minted)
+}
+
+/// Forget the token cached for `command`, after the provider turned it away,
+/// so the next request runs the command again.
+pub(crate) fn invalidate_token(command: &[String]) {
+    TOKENS
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .remove(command);
+}
+
+fn cached_token(command: &[String]) -> Option<CommandToken> {
+    let now = Instant::now();
+    let tokens = TOKENS.lock().unwrap_or_else(PoisonError::into_inner);
+    tokens
+        .get(command)
+        .filter(|cached| cached.expires_at > now)
+        .map(|cached| CommandToken {
+            token: cached.token.clone(),
+            freshness: cached.freshness(TokenSource::Cached, now),
+        })
+}
+
+fn duration_ms(duration: Duration) -> u64 {
+    u64::try_from(duration.as_millis()).unwrap_or(u64::MAX)
+}
+
+async fn run_token_command(command: &[String]) -> Result<String> {
+    let Some((program, args)) = command.split_first() else {
+        return Err(failed("", "the command is empty".to_string()));
+    };
+    let output = Command::new(program)
+        .args(args)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true)
+        .output();
+    let output = match tokio::time::timeout(TOKEN_COMMAND_TIMEOUT, output).await {
+        Ok(Ok(output)) => output,
+        Ok(Err(err)) => return Err(failed(program, format!("could not be started: {err}"))),
+        Err(_) => {
+            return Err(failed(
+                program,
+                format!(
+                    "did not finish within {} seconds",
+                    TOKEN_COMMAND_TIMEOUT.as_secs()
+                ),
+            ));
+        }
+    };
+
+    if !output.status.success() {
+        let status = match output.status.code() {
+            Some(code) => format!("exited with status {code}"),
+            None => "was terminated by a signal".to_string(),
+        };
+        let stderr = stderr_excerpt(&String::from_utf8_lossy(&output.stderr));
+        let message = if stderr.is_empty() {
+            status
+        } else {
+            format!("{status}: {stderr}")
+        };
+        return Err(failed(program, message));
+    }
+
+    let token = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    if token.is_empty() {
+        return Err(failed(program, "printed no token".to_string()));
+    }
+    Ok(token)
+}
+
+/// `stderr` on one line, with secrets redacted and cut to
+/// [`MAX_STDERR_BYTES`].
+fn stderr_excerpt(stderr: &str) -> String {
+    let redacted = redact_error(&stderr.split_whitespace().collect::<Vec<_>>().join(" "));
+    let excerpt = take_bytes_at_char_boundary(&redacted, MAX_STDERR_BYTES);
+    if excerpt.len() < redacted.len() {
+        format!("{excerpt}…")
+    } else {
+        redacted
+    }
+}
+
+fn failed(program: &str, message: String) -> CodexErr {
+    CodexErr::TokenCommandFailed {
+        program: program.to_string(),
+        message,
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use std::os::unix::fs::PermissionsExt;
+    use std::path::Path;
+    use tempfile::TempDir;
+
+    /// A script that counts its runs in `runs` next to it and prints
+    /// `token-<run>`.
+    fn counting_script(dir: &Path) -> Vec<String> {
+        let script = dir.join("mint-token.sh");
+        let runs = dir.join("runs");
+        std::fs::write(
+            &script,
+            format!(
+                "#!/bin/sh\necho x >> '{runs}'\necho \"  token-$(wc -l < '{runs}' | tr -d ' ')\"\n",
+                runs = runs.display()
+            ),
+        )
+        .unwrap();
+        std::fs::set_permissions(&script, std::fs::Permissions::from_mode(0o755)).unwrap();
+        vec![script.display().to_string()]
+    }
+
+    #[tokio::test]
+    async fn tokens_are_trimmed_and_reused_until_they_expire() {
+        let dir = TempDir::new().unwrap();
+        let command = counting_script(dir.path());
+
+        let ttl = Duration::from_millis(200);
+        assert_eq!(
+            token_from_command(&command, ttl).await.unwrap().token,
+            "token-1"
+        );
+        assert_eq!(
+            token_from_command(&command, ttl).await.unwrap().token,
+            "token-1"
+        );
+
+        tokio::time::sleep(Duration::from_millis(300)).await;
+        assert_eq!(
+            token_from_command(&command, ttl).await.unwrap().token,
+            "token-2"
+        );
+    }
+
+    #[tokio::test]
+    async fn an_invalidated_token_is_minted_again() {
+        let dir = TempDir::new().unwrap();
+        let command = counting_script(dir.path());
+
+        let ttl = Duration::from_secs(300);
+        assert_eq!(
+            token_from_command(&command, ttl).await.unwrap().token,
+            "token-1"
+        );
+        invalidate_token(&command);
+        assert_eq!(
+            token_from_command(&command, ttl).await.unwrap().token,
+            "token-2"
+        );
+    }
+
+    #[tokio::test]
+    async fn tokens_report_whether_they_were_cached_or_minted() {
+        let dir = TempDir::new().unwrap();
+        let command = counting_script(dir.path());
+        let ttl = Duration::from_secs(300);
+
+        let minted = token_from_command(&command, ttl).await.unwrap().freshness;
+        assert_eq!(minted.source, TokenSource::Refreshed);
+        assert_eq!(minted.age_ms, 0);
+        assert_eq!(minted.expires_in_ms, 300_000);
+
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        let cached = token_from_command(&command, ttl).await.unwrap().freshness;
+        assert_eq!(cached.source, TokenSource::Cached);
+        assert!(cached.age_ms >= 50, "{cached:?}");
+        assert!(cached.expires_in_ms <= 300_000 - 50, "{cached:?}");
+
+        invalidate_token(&command);
+        let refreshed = token_from_command(&command, ttl).await.unwrap().freshness;
+        assert_eq!(refreshed.source, TokenSource::Refreshed);
+        assert_eq!(refreshed.age_ms, 0);
+    }
+
+    #[tokio::test]
+    async fn a_failing_command_reports_its_redacted_stderr() {
+        let command = vec![
+            "sh".to_string(),
+            "-c".to_string(),
+            "echo 'login expired for https://user:pw@idp.example.com/token' >&2; echo 'Authorization: Bearer abc123' >&2; exit 3".to_string(),
+        ];
+
+        let err = token_from_command(&command, Duration::from_secs(300))
+            .await
+            .unwrap_err();
+
+        assert_eq!(
+            err.to_string(),
+            "token command `sh` failed: exited with status 3: login expired for https://idp.example.com Authorization: Bearer [REDACTED]"
+        );
+    }
+
+    #[tokio::test]
+    async fn long_stderr_is_truncated() {
+        let command = vec![
+            "sh".to_string(),
+            "-c".to_string(),
+            "printf 'e%.0s' $(seq 1 2000) >&2; exit 1".to_string(),
+        ];
+
+        let CodexErr::TokenCommandFailed { message, .. } =
+            token_from_command(&command, Duration::from_secs(300))
+                .await
+                .unwrap_err()
+        else {
+            panic!("expected a token command error");
+        };
+
+        let prefix = "exited with status 1: ";
+        assert_eq!(
+            message,
+            format!("{prefix}{}…", "e".repeat(MAX_STDERR_BYTES))
+        );
+    }
+
+    #[tokio::test]
+    async fn empty_output_is_not_a_token() {
+        let command = vec!["true".to_string()];
+
+        let err = token_from_command(&command, Duration::from_secs(300))
+            .await
+            .unwrap_err();
+
+        assert_eq!(
+            err.to_string(),
+            "token command `true` failed: printed no token"
+        );
+    }
+}
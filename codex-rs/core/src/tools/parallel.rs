@@ -27,6 +27,9 @@ pub(crate) struct ToolCallRuntime {
     turn_context: Arc<TurnContext>,
     tracker: SharedTurnDiffTracker,
     parallel_execution: Arc<RwLock<()>>,
+    /// Cleared when the active preset forbids parallel tool calls, so even
+    /// tools that support it run one at a time.
+    parallel_dispatch: bool,
 }
 
 impl ToolCallRuntime {
@@ -36,12 +39,17 @@ impl ToolCallRuntime {
         turn_context: Arc<TurnContext>,
         tracker: SharedTurnDiffTracker,
     ) -> Self {
+        let parallel_dispatch = !turn_context
+            .client
+            .get_model_family()
+            .preset_forbids_parallel_tool_calls();
         Self {
             router,
             session,
             turn_context,
             tracker,
             parallel_execution: Arc::new(RwLock::new(())),
+            parallel_dispatch,
         }
     }
 
@@ -51,7 +59,8 @@ impl ToolCallRuntime {
         call: ToolCall,
         cancellation_token: CancellationToken,
     ) -> impl std::future::Future<Output = Result<ResponseInputItem, CodexErr>> {
-        let supports_parallel = self.router.tool_supports_parallel(&call.tool_name);
+        let supports_parallel =
+            self.parallel_dispatch && self.router.tool_supports_parallel(&call.tool_name);
 
         let router = Arc::clone(&self.router);
         let session = Arc::clone(&self.session);
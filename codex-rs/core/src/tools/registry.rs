@@ -195,6 +195,17 @@ impl ToolRegistryBuilder {
         }
     }
 
+    /// Drop the specs and handlers of the tools named in `names`, so they are
+    /// neither advertised to the model nor run if it calls them anyway.
+    pub fn remove_tools(&mut self, names: &[String]) {
+        if names.is_empty() {
+            return;
+        }
+        self.specs
+            .retain(|configured| !names.iter().any(|name| name == configured.spec.name()));
+        self.handlers.retain(|name, _| !names.contains(name));
+    }
+
     // TODO(jif) for dynamic tools.
     // pub fn register_many<I>(&mut self, names: I, handler: Arc<dyn ToolHandler>)
     // where
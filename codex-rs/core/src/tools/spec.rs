@@ -16,13 +16,27 @@ use serde_json::json;
 use std::collections::BTreeMap;
 use std::collections::HashMap;
 
+/// What decided how a tool is configured, for debug output.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ToolSettingSource {
+    /// The config, an override, or the session set the feature.
+    Config,
+    /// The active preset's hint, with the feature left unset.
+    Preset,
+    /// The model family, or the feature's default.
+    Default,
+}
+
 #[derive(Debug, Clone)]
 pub(crate) struct ToolsConfig {
     pub shell_type: ConfigShellToolType,
     pub apply_patch_tool_type: Option<ApplyPatchToolType>,
+    pub apply_patch_tool_source: ToolSettingSource,
     pub web_search_request: bool,
+    pub web_search_request_source: ToolSettingSource,
     pub include_view_image_tool: bool,
     pub experimental_supported_tools: Vec<String>,
+    pub disabled_tools: Vec<String>,
 }
 
 pub(crate) struct ToolsConfigParams<'a> {
@@ -53,25 +67,58 @@ impl ToolsConfig {
             model_family.shell_type
         };
 
-        let apply_patch_tool_type = match model_family.apply_patch_tool_type {
-            Some(ApplyPatchToolType::Freeform) => Some(ApplyPatchToolType::Freeform),
-            Some(ApplyPatchToolType::Function) => Some(ApplyPatchToolType::Function),
-            None => {
-                if include_apply_patch_tool {
-                    Some(ApplyPatchToolType::Freeform)
+        let apply_patch_configured = features.is_configured(Feature::ApplyPatchFreeform);
+        let (apply_patch_tool_type, apply_patch_tool_source) = match (
+            model_family.preset_prefers_apply_patch_freeform,
+            &model_family.apply_patch_tool_type,
+        ) {
+            (Some(true), _) if !apply_patch_configured => (
+                Some(ApplyPatchToolType::Freeform),
+                ToolSettingSource::Preset,
+            ),
+            (Some(false), _) if !apply_patch_configured => (
+                Some(ApplyPatchToolType::Function),
+                ToolSettingSource::Preset,
+            ),
+            (_, Some(tool_type)) => (Some(tool_type.clone()), ToolSettingSource::Default),
+            (_, None) => (
+                include_apply_patch_tool.then_some(ApplyPatchToolType::Freeform),
+                if apply_patch_configured {
+                    ToolSettingSource::Config
                 } else {
-                    None
-                }
-            }
+                    ToolSettingSource::Default
+                },
+            ),
         };
 
-        Self {
+        let (web_search_request, web_search_request_source) =
+            if features.is_configured(Feature::WebSearchRequest) {
+                (include_web_search_request, ToolSettingSource::Config)
+            } else if let Some(enabled) = model_family.preset_enables_web_search {
+                (enabled, ToolSettingSource::Preset)
+            } else {
+                (include_web_search_request, ToolSettingSource::Default)
+            };
+
+        let config = Self {
             shell_type,
             apply_patch_tool_type,
-            web_search_request: include_web_search_request,
+            apply_patch_tool_source,
+            web_search_request,
+            web_search_request_source,
             include_view_image_tool,
             experimental_supported_tools: model_family.experimental_supported_tools.clone(),
-        }
+            disabled_tools: model_family.disabled_tools.clone(),
+        };
+        tracing::debug!(
+            model = %model_family.slug,
+            apply_patch_tool_type = ?config.apply_patch_tool_type,
+            apply_patch_tool_source = ?config.apply_patch_tool_source,
+            web_search_request = config.web_search_request,
+            web_search_request_source = ?config.web_search_request_source,
+            "configured tools"
+        );
+        config
     }
 }
 
@@ -1119,6 +1166,7 @@ pub(crate) fn build_specs(
         }
     }
 
+    builder.remove_tools(&config.disabled_tools);
     builder
 }
 
@@ -1280,6 +1328,106 @@ mod tests {
         }
     }
 
+    #[test]
+    fn tools_disabled_for_a_model_come_back_when_the_model_changes() {
+        let config = test_config();
+        let mut features = Features::with_defaults();
+        features.enable(Feature::WebSearchRequest);
+        features.enable(Feature::ViewImageTool);
+        let unrestricted = ModelsManager::construct_model_family_offline("gpt-5.1-codex", &config);
+        let mut restricted = unrestricted.clone();
+        restricted.disabled_tools = vec!["web_search".to_string(), "view_image".to_string()];
+        let build = |model_family: &ModelFamily| {
+            let tools_config = ToolsConfig::new(&ToolsConfigParams {
+                model_family,
+                features: &features,
+            });
+            build_specs(&tools_config, None).build()
+        };
+
+        let (tools, registry) = build(&restricted);
+        let names = tools.iter().map(|t| t.spec.name()).collect::<Vec<_>>();
+        assert!(!names.contains(&"web_search"), "had: {names:?}");
+        assert!(!names.contains(&"view_image"), "had: {names:?}");
+        assert!(registry.handler("view_image").is_none());
+        assert_contains_tool_names(&tools, &["update_plan", "apply_patch"]);
+
+        let (tools, registry) = build(&unrestricted);
+        assert_contains_tool_names(&tools, &["web_search", "view_image"]);
+        assert!(registry.handler("view_image").is_some());
+    }
+
+    #[test]
+    fn preset_tool_hints_apply_only_while_the_config_leaves_the_feature_unset() {
+        let config = test_config();
+        let plain = ModelsManager::construct_model_family_offline("gpt-5-codex", &config);
+        let mut hinted = plain.clone();
+        hinted.preset_prefers_apply_patch_freeform = Some(false);
+        hinted.preset_enables_web_search = Some(true);
+        let build = |model_family: &ModelFamily, features: &Features| {
+            let tools_config = ToolsConfig::new(&ToolsConfigParams {
+                model_family,
+                features,
+            });
+            let (tools, _) = build_specs(&tools_config, None).build();
+            (tools_config, tools)
+        };
+        let apply_patch = |tools: &[ConfiguredToolSpec]| {
+            tools
+                .iter()
+                .find(|tool| tool.spec.name() == "apply_patch")
+                .map(|tool| tool.spec.clone())
+        };
+
+        let defaults = Features::with_defaults();
+        let (tools_config, tools) = build(&hinted, &defaults);
+        assert_eq!(
+            tools_config.apply_patch_tool_source,
+            ToolSettingSource::Preset
+        );
+        assert_eq!(
+            tools_config.web_search_request_source,
+            ToolSettingSource::Preset
+        );
+        assert_eq!(apply_patch(&tools), Some(create_apply_patch_json_tool()));
+        assert_contains_tool_names(&tools, &["web_search"]);
+
+        // Switching to a model without hints goes back to the family.
+        let (tools_config, tools) = build(&plain, &defaults);
+        assert_eq!(
+            tools_config.apply_patch_tool_source,
+            ToolSettingSource::Default
+        );
+        assert_eq!(
+            apply_patch(&tools),
+            Some(create_apply_patch_freeform_tool())
+        );
+        assert!(
+            !tools.iter().any(|tool| tool.spec.name() == "web_search"),
+            "web search is off by default"
+        );
+
+        // Either setting in the config wins over the hint, even when it
+        // matches the default.
+        let mut configured = Features::with_defaults();
+        configured
+            .disable(Feature::WebSearchRequest)
+            .enable(Feature::ApplyPatchFreeform);
+        let (tools_config, tools) = build(&hinted, &configured);
+        assert_eq!(
+            tools_config.web_search_request_source,
+            ToolSettingSource::Config
+        );
+        assert_eq!(
+            apply_patch(&tools),
+            Some(create_apply_patch_freeform_tool())
+        );
+        assert!(
+            !tools.iter().any(|tool| tool.spec.name() == "web_search"),
+            "the config turned web search off"
+        );
+    }
+
     fn assert_model_tools(model_slug: &str, features: &Features, expected_tools: &[&str]) {
         let config = test_config();
         let model_family = ModelsManager::construct_model_family_offline(model_slug, &config);
@@ -58,6 +58,21 @@ async fn run_stream_with_bytes(sse_body: &[u8]) -> Vec<ResponseEvent> {
         stream_max_retries: Some(0),
         stream_idle_timeout_ms: Some(5_000),
         requires_openai_auth: false,
+        model_catalog: None,
+        skip_models_preflight: false,
+        allow_anonymous_catalog: false,
+        allow_empty_catalog: false,
+        instructions_addendum: None,
+        supported_wire_apis: None,
+        model_pricing: None,
+        models_max_response_bytes: None,
+        models: None,
+        model_request_headers: None,
+        models_path: None,
+        allow_insecure_catalog: false,
+        pinned_spki_sha256: None,
+        token_command: None,
+        token_command_ttl_secs: None,
     };
 
     let codex_home = match TempDir::new() {
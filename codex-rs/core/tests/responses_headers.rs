@@ -53,6 +53,21 @@ async fn responses_stream_includes_subagent_header_on_review() {
         stream_max_retries: Some(0),
         stream_idle_timeout_ms: Some(5_000),
         requires_openai_auth: false,
+        model_catalog: None,
+        skip_models_preflight: false,
+        allow_anonymous_catalog: false,
+        allow_empty_catalog: false,
+        instructions_addendum: None,
+        supported_wire_apis: None,
+        model_pricing: None,
+        models_max_response_bytes: None,
+        models: None,
+        model_request_headers: None,
+        models_path: None,
+        allow_insecure_catalog: false,
+        pinned_spki_sha256: None,
+        token_command: None,
+        token_command_ttl_secs: None,
     };
 
     let codex_home = TempDir::new().expect("failed to create TempDir");
@@ -147,6 +162,21 @@ async fn responses_stream_includes_subagent_header_on_other() {
         stream_max_retries: Some(0),
         stream_idle_timeout_ms: Some(5_000),
         requires_openai_auth: false,
+        model_catalog: None,
+        skip_models_preflight: false,
+        allow_anonymous_catalog: false,
+        allow_empty_catalog: false,
+        instructions_addendum: None,
+        supported_wire_apis: None,
+        model_pricing: None,
+        models_max_response_bytes: None,
+        models: None,
+        model_request_headers: None,
+        models_path: None,
+        allow_insecure_catalog: false,
+        pinned_spki_sha256: None,
+        token_command: None,
+        token_command_ttl_secs: None,
     };
 
     let codex_home = TempDir::new().expect("failed to create TempDir");
@@ -237,6 +267,21 @@ async fn responses_respects_model_family_overrides_from_config() {
         stream_max_retries: Some(0),
         stream_idle_timeout_ms: Some(5_000),
         requires_openai_auth: false,
+        model_catalog: None,
+        skip_models_preflight: false,
+        allow_anonymous_catalog: false,
+        allow_empty_catalog: false,
+        instructions_addendum: None,
+        supported_wire_apis: None,
+        model_pricing: None,
+        models_max_response_bytes: None,
+        models: None,
+        model_request_headers: None,
+        models_path: None,
+        allow_insecure_catalog: false,
+        pinned_spki_sha256: None,
+        token_command: None,
+        token_command_ttl_secs: None,
     };
 
     let codex_home = TempDir::new().expect("failed to create TempDir");
@@ -317,3 +362,107 @@ async fn responses_respects_model_family_overrides_from_config() {
         Some("detailed")
     );
 }
+
+#[tokio::test]
+async fn responses_stream_is_requested_uncompressed() {
+    core_test_support::skip_if_no_network!();
+
+    let server = responses::start_mock_server().await;
+    let response_body = responses::sse(vec![
+        responses::ev_response_created("resp-1"),
+        responses::ev_completed("resp-1"),
+    ]);
+
+    let request_recorder = responses::mount_sse_once(&server, response_body).await;
+
+    let provider = ModelProviderInfo {
+        name: "mock".into(),
+        base_url: Some(format!("{}/v1", server.uri())),
+        env_key: None,
+        env_key_instructions: None,
+        experimental_bearer_token: None,
+        wire_api: WireApi::Responses,
+        query_params: None,
+        http_headers: None,
+        env_http_headers: None,
+        request_max_retries: Some(0),
+        stream_max_retries: Some(0),
+        stream_idle_timeout_ms: Some(5_000),
+        requires_openai_auth: false,
+        model_catalog: None,
+        skip_models_preflight: false,
+        allow_anonymous_catalog: false,
+        allow_empty_catalog: false,
+        instructions_addendum: None,
+        supported_wire_apis: None,
+        model_pricing: None,
+        models_max_response_bytes: None,
+        models: None,
+        model_request_headers: None,
+        models_path: None,
+        allow_insecure_catalog: false,
+        pinned_spki_sha256: None,
+        token_command: None,
+        token_command_ttl_secs: None,
+    };
+
+    let codex_home = TempDir::new().expect("failed to create TempDir");
+    let mut config = load_default_config_for_test(&codex_home).await;
+    config.model_provider_id = provider.name.clone();
+    config.model_provider = provider.clone();
+    let effort = config.model_reasoning_effort;
+    let summary = config.model_reasoning_summary;
+    let model = ModelsManager::get_model_offline(config.model.as_deref());
+    config.model = Some(model.clone());
+    let config = Arc::new(config);
+
+    let conversation_id = ConversationId::new();
+    let auth_mode = AuthMode::ChatGPT;
+    let session_source = SessionSource::Exec;
+    let model_family = ModelsManager::construct_model_family_offline(model.as_str(), &config);
+
+    let otel_manager = OtelManager::new(
+        conversation_id,
+        model.as_str(),
+        model_family.slug.as_str(),
+        None,
+        Some("test@test.com".to_string()),
+        Some(auth_mode),
+        false,
+        "test".to_string(),
+        session_source.clone(),
+    );
+
+    let client = ModelClient::new(
+        Arc::clone(&config),
+        None,
+        model_family,
+        otel_manager,
+        provider,
+        effort,
+        summary,
+        conversation_id,
+        session_source,
+    );
+
+    let mut prompt = Prompt::default();
+    prompt.input = vec![ResponseItem::Message {
+        id: None,
+        role: "user".into(),
+        content: vec![ContentItem::InputText {
+            text: "hello".into(),
+        }],
+    }];
+
+    let mut stream = client.stream(&prompt).await.expect("stream failed");
+    while let Some(event) = stream.next().await {
+        if matches!(event, Ok(ResponseEvent::Completed { .. })) {
+            break;
+        }
+    }
+
+    // Only catalog fetches negotiate compression; a compressed event stream
+    // would have to be decoded before its events could be read.
+    let request = request_recorder.single_request();
+    assert_eq!(request.header("accept-encoding"), None);
+}
@@ -3,25 +3,34 @@ use anyhow::Result;
 use base64::Engine;
 use chrono::Duration;
 use chrono::Utc;
+use codex_core::AuthManager;
 use codex_core::CodexAuth;
+use codex_core::ModelProviderInfo;
 use codex_core::auth::AuthCredentialsStoreMode;
 use codex_core::auth::AuthDotJson;
 use codex_core::auth::REFRESH_TOKEN_URL_OVERRIDE_ENV_VAR;
 use codex_core::auth::RefreshTokenError;
 use codex_core::auth::load_auth_dot_json;
 use codex_core::auth::save_auth;
+use codex_core::built_in_model_providers;
 use codex_core::error::RefreshTokenFailedReason;
+use codex_core::features::Feature;
+use codex_core::models_manager::manager::ModelsManager;
+use codex_core::models_manager::provenance::TokenSource;
 use codex_core::token_data::IdTokenInfo;
 use codex_core::token_data::TokenData;
+use core_test_support::load_default_config_for_test;
 use core_test_support::skip_if_no_network;
 use pretty_assertions::assert_eq;
 use serde::Serialize;
 use serde_json::json;
 use std::ffi::OsString;
+use std::sync::Arc;
 use tempfile::TempDir;
 use wiremock::Mock;
 use wiremock::MockServer;
 use wiremock::ResponseTemplate;
+use wiremock::matchers::header;
 use wiremock::matchers::method;
 use wiremock::matchers::path;
 
@@ -161,6 +170,75 @@ async fn refresh_token_returns_transient_error_on_server_failure() -> Result<()>
     Ok(())
 }
 
+#[serial_test::serial(auth_refresh)]
+#[tokio::test]
+async fn catalog_refresh_records_the_cached_then_refreshed_chatgpt_token() -> Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/oauth/token"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "access_token": "new-access-token",
+            "refresh_token": "new-refresh-token"
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v1/models"))
+        .and(header(
+            "authorization",
+            format!("Bearer {INITIAL_ACCESS_TOKEN}").as_str(),
+        ))
+        .respond_with(ResponseTemplate::new(401).set_body_string("token expired"))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v1/models"))
+        .and(header("authorization", "Bearer new-access-token"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({ "models": [] })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let ctx = RefreshTokenTestContext::new(&server)?;
+    let auth_manager = Arc::new(AuthManager::new(
+        ctx.codex_home.path().to_path_buf(),
+        false,
+        AuthCredentialsStoreMode::File,
+    ));
+    let provider = ModelProviderInfo {
+        base_url: Some(format!("{}/v1", server.uri())),
+        ..built_in_model_providers()["openai"].clone()
+    };
+    let manager = ModelsManager::with_provider(auth_manager, provider);
+    let mut config = load_default_config_for_test(&ctx.codex_home).await;
+    config.features.enable(Feature::RemoteModels);
+
+    manager
+        .refresh_available_models_with_cache(&config)
+        .await
+        .context("the refreshed token is accepted")?;
+
+    let attempts = manager.catalog_status(&config).await.remote.token_attempts;
+    assert_eq!(
+        attempts
+            .iter()
+            .map(|attempt| attempt.source)
+            .collect::<Vec<_>>(),
+        vec![TokenSource::Cached, TokenSource::Refreshed]
+    );
+    let a_day_ms = 24 * 60 * 60 * 1_000;
+    assert!(attempts[0].age_ms >= a_day_ms - 60_000, "{attempts:?}");
+    assert!(attempts[1].age_ms < 60_000, "{attempts:?}");
+    assert!(attempts[1].expires_in_ms > attempts[0].expires_in_ms);
+
+    server.verify().await;
+    Ok(())
+}
+
 struct RefreshTokenTestContext {
     codex_home: TempDir,
     auth: CodexAuth,
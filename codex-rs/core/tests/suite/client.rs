@@ -18,8 +18,10 @@ use codex_core::error::CodexErr;
 use codex_core::features::Feature;
 use codex_core::models_manager::manager::ModelsManager;
 use codex_core::protocol::EventMsg;
+use codex_core::protocol::ModelTokenUsage;
 use codex_core::protocol::Op;
 use codex_core::protocol::SessionSource;
+use codex_core::protocol::TokenUsage;
 use codex_otel::otel_manager::OtelManager;
 use codex_protocol::ConversationId;
 use codex_protocol::config_types::ReasoningSummary;
@@ -35,6 +37,7 @@ use core_test_support::responses::ev_completed_with_tokens;
 use core_test_support::responses::get_responses_requests;
 use core_test_support::responses::mount_sse_once;
 use core_test_support::responses::mount_sse_once_match;
+use core_test_support::responses::mount_sse_sequence;
 use core_test_support::responses::sse;
 use core_test_support::responses::sse_failed;
 use core_test_support::skip_if_no_network;
@@ -44,6 +47,7 @@ use core_test_support::wait_for_event;
 use dunce::canonicalize as normalize_path;
 use futures::StreamExt;
 use serde_json::json;
+use std::collections::HashMap;
 use std::io::Write;
 use std::sync::Arc;
 use tempfile::TempDir;
@@ -1016,6 +1020,120 @@ async fn configured_verbosity_is_sent() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn configured_sampling_not_sent_for_models_without_support() -> anyhow::Result<()> {
+    skip_if_no_network!(Ok(()));
+    let server = MockServer::start().await;
+
+    let resp_mock = mount_sse_once(&server, sse_completed("resp1")).await;
+    let TestCodex { codex, .. } = test_codex()
+        .with_model("gpt-5.1-codex")
+        .with_config(|config| {
+            config.model_temperature = Some(0.5);
+            config.model_top_p = Some(0.5);
+        })
+        .build(&server)
+        .await?;
+
+    codex
+        .submit(Op::UserInput {
+            items: vec![UserInput::Text {
+                text: "hello".into(),
+            }],
+        })
+        .await
+        .unwrap();
+
+    wait_for_event(&codex, |ev| matches!(ev, EventMsg::TaskComplete(_))).await;
+
+    let request_body = resp_mock.single_request().body_json();
+    assert_eq!(request_body.get("temperature"), None);
+    assert_eq!(request_body.get("top_p"), None);
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn configured_sampling_is_sent() -> anyhow::Result<()> {
+    skip_if_no_network!(Ok(()));
+    let server = MockServer::start().await;
+
+    let resp_mock = mount_sse_once(&server, sse_completed("resp1")).await;
+    let TestCodex { codex, .. } = test_codex()
+        .with_model("gpt-4o")
+        .with_config(|config| {
+            config.model_temperature = Some(0.25);
+            config.model_top_p = Some(0.5);
+        })
+        .build(&server)
+        .await?;
+
+    codex
+        .submit(Op::UserInput {
+            items: vec![UserInput::Text {
+                text: "hello".into(),
+            }],
+        })
+        .await
+        .unwrap();
+
+    wait_for_event(&codex, |ev| matches!(ev, EventMsg::TaskComplete(_))).await;
+
+    let request_body = resp_mock.single_request().body_json();
+    assert_eq!(request_body["temperature"], 0.25);
+    assert_eq!(request_body["top_p"], 0.5);
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn model_request_headers_are_sent_for_the_selected_model_only() -> anyhow::Result<()> {
+    skip_if_no_network!(Ok(()));
+    let server = MockServer::start().await;
+
+    let resp_mock = mount_sse_once(&server, sse_completed("resp1")).await;
+    let TestCodex { codex, .. } = test_codex()
+        .with_model("gpt-4o")
+        .with_config(|config| {
+            config.model_provider.model_request_headers = Some(HashMap::from([
+                (
+                    "gpt-4o".to_string(),
+                    HashMap::from([
+                        ("x-model-group".to_string(), "small".to_string()),
+                        (
+                            "x-unset".to_string(),
+                            "${CODEX_TEST_UNSET_HEADER_VAR}".to_string(),
+                        ),
+                    ]),
+                ),
+                (
+                    "gpt-5.1-codex".to_string(),
+                    HashMap::from([("x-codex-group".to_string(), "large".to_string())]),
+                ),
+            ]));
+        })
+        .build(&server)
+        .await?;
+
+    codex
+        .submit(Op::UserInput {
+            items: vec![UserInput::Text {
+                text: "hello".into(),
+            }],
+        })
+        .await
+        .unwrap();
+
+    wait_for_event(&codex, |ev| matches!(ev, EventMsg::TaskComplete(_))).await;
+
+    let request = resp_mock.single_request();
+    assert_eq!(request.header("x-model-group"), Some("small".to_string()));
+    assert_eq!(request.header("x-unset"), None);
+    assert_eq!(request.header("x-codex-group"), None);
+
+    Ok(())
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
 async fn includes_developer_instructions_message_in_request() {
     skip_if_no_network!();
@@ -1116,6 +1234,21 @@ async fn azure_responses_request_includes_store_and_reasoning_ids() {
         stream_max_retries: Some(0),
         stream_idle_timeout_ms: Some(5_000),
         requires_openai_auth: false,
+        model_catalog: None,
+        skip_models_preflight: false,
+        allow_anonymous_catalog: false,
+        allow_empty_catalog: false,
+        instructions_addendum: None,
+        supported_wire_apis: None,
+        model_pricing: None,
+        models_max_response_bytes: None,
+        models: None,
+        model_request_headers: None,
+        models_path: None,
+        allow_insecure_catalog: false,
+        pinned_spki_sha256: None,
+        token_command: None,
+        token_command_ttl_secs: None,
     };
 
     let codex_home = TempDir::new().unwrap();
@@ -1343,7 +1476,17 @@ async fn token_count_includes_rate_limits_snapshot() {
                     "total_tokens": 123
                 },
                 // Default model is gpt-5.1-codex-max in tests → 95% usable context window
-                "model_context_window": 258400
+                "model_context_window": 258400,
+                "per_model_usage": [{
+                    "model": "gpt-5.1-codex-max",
+                    "token_usage": {
+                        "input_tokens": 123,
+                        "cached_input_tokens": 0,
+                        "output_tokens": 0,
+                        "reasoning_output_tokens": 0,
+                        "total_tokens": 123
+                    }
+                }]
             },
             "rate_limits": {
                 "primary": {
@@ -1386,6 +1529,88 @@ async fn token_count_includes_rate_limits_snapshot() {
     wait_for_event(&codex, |msg| matches!(msg, EventMsg::TaskComplete(_))).await;
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn token_count_splits_usage_by_model_after_mid_session_switch() -> anyhow::Result<()> {
+    skip_if_no_network!(Ok(()));
+    let server = MockServer::start().await;
+
+    mount_sse_sequence(
+        &server,
+        vec![
+            sse(vec![ev_completed_with_tokens("resp-1", 100)]),
+            sse(vec![ev_completed_with_tokens("resp-2", 40)]),
+        ],
+    )
+    .await;
+
+    let TestCodex { codex, .. } = test_codex().with_model("gpt-5.1").build(&server).await?;
+
+    codex
+        .submit(Op::UserInput {
+            items: vec![UserInput::Text {
+                text: "first".into(),
+            }],
+        })
+        .await?;
+    wait_for_event(&codex, |ev| matches!(ev, EventMsg::TaskComplete(_))).await;
+
+    codex
+        .submit(Op::OverrideTurnContext {
+            cwd: None,
+            approval_policy: None,
+            sandbox_policy: None,
+            model: Some("gpt-5.1-codex".to_string()),
+            model_provider: None,
+            effort: None,
+            summary: None,
+        })
+        .await?;
+    codex
+        .submit(Op::UserInput {
+            items: vec![UserInput::Text {
+                text: "second".into(),
+            }],
+        })
+        .await?;
+
+    let token_event = wait_for_event(&codex, |ev| {
+        matches!(
+            ev,
+            EventMsg::TokenCount(payload)
+                if payload.info.as_ref().is_some_and(|info| info.per_model_usage.len() == 2)
+        )
+    })
+    .await;
+    let EventMsg::TokenCount(payload) = token_event else {
+        unreachable!("wait_for_event returned unexpected event");
+    };
+    let info = payload.info.expect("token usage info present");
+
+    let usage = |tokens| TokenUsage {
+        input_tokens: tokens,
+        cached_input_tokens: 0,
+        output_tokens: 0,
+        reasoning_output_tokens: 0,
+        total_tokens: tokens,
+    };
+    pretty_assertions::assert_eq!(info.total_token_usage, usage(140));
+    pretty_assertions::assert_eq!(
+        info.per_model_usage,
+        vec![
+            ModelTokenUsage {
+                model: "gpt-5.1".to_string(),
+                token_usage: usage(100),
+            },
+            ModelTokenUsage {
+                model: "gpt-5.1-codex".to_string(),
+                token_usage: usage(40),
+            },
+        ]
+    );
+
+    Ok(())
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
 async fn usage_limit_error_emits_rate_limit_event() -> anyhow::Result<()> {
     skip_if_no_network!(Ok(()));
@@ -1612,6 +1837,21 @@ async fn azure_overrides_assign_properties_used_for_responses_url() {
         stream_max_retries: None,
         stream_idle_timeout_ms: None,
         requires_openai_auth: false,
+        model_catalog: None,
+        skip_models_preflight: false,
+        allow_anonymous_catalog: false,
+        allow_empty_catalog: false,
+        instructions_addendum: None,
+        supported_wire_apis: None,
+        model_pricing: None,
+        models_max_response_bytes: None,
+        models: None,
+        model_request_headers: None,
+        models_path: None,
+        allow_insecure_catalog: false,
+        pinned_spki_sha256: None,
+        token_command: None,
+        token_command_ttl_secs: None,
     };
 
     // Init session
@@ -1694,6 +1934,21 @@ async fn env_var_overrides_loaded_auth() {
         stream_max_retries: None,
         stream_idle_timeout_ms: None,
         requires_openai_auth: false,
+        model_catalog: None,
+        skip_models_preflight: false,
+        allow_anonymous_catalog: false,
+        allow_empty_catalog: false,
+        instructions_addendum: None,
+        supported_wire_apis: None,
+        model_pricing: None,
+        models_max_response_bytes: None,
+        models: None,
+        model_request_headers: None,
+        models_path: None,
+        allow_insecure_catalog: false,
+        pinned_spki_sha256: None,
+        token_command: None,
+        token_command_ttl_secs: None,
     };
 
     // Init session
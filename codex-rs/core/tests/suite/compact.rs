@@ -8,6 +8,7 @@ use codex_core::compact::SUMMARIZATION_PROMPT;
 use codex_core::compact::SUMMARY_PREFIX;
 use codex_core::config::Config;
 use codex_core::features::Feature;
+use codex_core::models_manager::manager::ModelsManager;
 use codex_core::protocol::AskForApproval;
 use codex_core::protocol::EventMsg;
 use codex_core::protocol::Op;
@@ -397,6 +398,66 @@ async fn manual_compact_uses_custom_prompt() {
     }
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn manual_compact_runs_on_compact_model_preset() {
+    skip_if_no_network!();
+
+    let server = start_mock_server().await;
+    let summary = mount_sse_once(
+        &server,
+        sse(vec![
+            ev_assistant_message("m1", SUMMARY_TEXT),
+            ev_completed("r1"),
+        ]),
+    )
+    .await;
+    let follow_up = mount_sse_once(&server, sse(vec![ev_completed("r2")])).await;
+
+    let home = TempDir::new().unwrap();
+    let mut config = load_default_config_for_test(&home).await;
+    config.model_provider = non_openai_model_provider(&server);
+    config.model = Some("gpt-5.1-codex".to_string());
+    config.compact_model_preset = Some("gpt-5.1-codex-mini".to_string());
+
+    let conversation_manager = ConversationManager::with_models_provider(
+        CodexAuth::from_api_key("dummy"),
+        config.model_provider.clone(),
+    );
+    let codex = conversation_manager
+        .new_conversation(config)
+        .await
+        .expect("create conversation")
+        .conversation;
+
+    codex.submit(Op::Compact).await.expect("trigger compact");
+    let compacted = wait_for_event_match(&codex, |ev| match ev {
+        EventMsg::ContextCompacted(event) => Some(event.model.clone()),
+        _ => None,
+    })
+    .await;
+    assert_eq!(compacted.as_deref(), Some("gpt-5.1-codex-mini"));
+    wait_for_event(&codex, |ev| matches!(ev, EventMsg::TaskComplete(_))).await;
+
+    codex
+        .submit(Op::UserInput {
+            items: vec![UserInput::Text {
+                text: THIRD_USER_MSG.to_string(),
+            }],
+        })
+        .await
+        .expect("submit follow-up");
+    wait_for_event(&codex, |ev| matches!(ev, EventMsg::TaskComplete(_))).await;
+
+    let summary_body = summary.single_request().body_json();
+    assert_eq!(summary_body["model"].as_str(), Some("gpt-5.1-codex-mini"));
+    assert_eq!(summary_body["reasoning"]["effort"].as_str(), Some("medium"));
+    assert_eq!(
+        follow_up.single_request().body_json()["model"].as_str(),
+        Some("gpt-5.1-codex"),
+        "turns after compacting stay on the session model"
+    );
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
 async fn manual_compact_emits_api_and_local_token_usage_events() {
     skip_if_no_network!();
@@ -1593,6 +1654,129 @@ async fn manual_compact_retries_after_context_window_error() {
     }
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn manual_compact_summarizes_in_passes_when_the_history_exceeds_the_window() {
+    skip_if_no_network!();
+
+    let server = start_mock_server().await;
+    let turn_messages: Vec<String> = (1..=3)
+        .map(|turn| format!("TURN_{turn} {}", "word ".repeat(1_200)))
+        .collect();
+    let mut bodies: Vec<String> = (1..=3)
+        .map(|turn| {
+            sse(vec![
+                ev_assistant_message(&format!("m{turn}"), &format!("REPLY_{turn}")),
+                ev_completed(&format!("r{turn}")),
+            ])
+        })
+        .collect();
+    bodies.extend((1..=3).map(|pass| {
+        sse(vec![
+            ev_assistant_message(&format!("s{pass}"), &format!("PASS_{pass}_SUMMARY")),
+            ev_completed(&format!("c{pass}")),
+        ])
+    }));
+    bodies.push(sse(vec![
+        ev_assistant_message("m4", FINAL_REPLY),
+        ev_completed("r4"),
+    ]));
+    let request_log = mount_sse_sequence(&server, bodies).await;
+
+    let home = TempDir::new().unwrap();
+    let mut config = load_default_config_for_test(&home).await;
+    config.model_provider = non_openai_model_provider(&server);
+    config.model = Some("gpt-5.1-codex-max".to_string());
+    set_test_compact_prompt(&mut config);
+    // A window with room for the instructions and about two of the turns, so
+    // a summary request can hold one turn with room left for the summary.
+    let instructions = ModelsManager::construct_model_family_offline("gpt-5.1-codex-max", &config)
+        .base_instructions;
+    config.model_context_window = Some(instructions.len().div_ceil(4) as i64 + 3_000);
+    let codex = ConversationManager::with_models_provider(
+        CodexAuth::from_api_key("dummy"),
+        config.model_provider.clone(),
+    )
+    .new_conversation(config)
+    .await
+    .unwrap()
+    .conversation;
+
+    for text in &turn_messages {
+        codex
+            .submit(Op::UserInput {
+                items: vec![UserInput::Text { text: text.clone() }],
+            })
+            .await
+            .unwrap();
+        wait_for_event(&codex, |ev| matches!(ev, EventMsg::TaskComplete(_))).await;
+    }
+
+    codex.submit(Op::Compact).await.unwrap();
+    let passes_note = wait_for_event_match(&codex, |ev| match ev {
+        EventMsg::BackgroundEvent(event) if event.message.contains("passes") => {
+            Some(event.message.clone())
+        }
+        _ => None,
+    })
+    .await;
+    assert_eq!(
+        passes_note,
+        "Summarized the conversation in 3 passes so each summary request fits the model's summarization budget."
+    );
+    wait_for_event(&codex, |ev| matches!(ev, EventMsg::TaskComplete(_))).await;
+
+    codex
+        .submit(Op::UserInput {
+            items: vec![UserInput::Text {
+                text: THIRD_USER_MSG.to_string(),
+            }],
+        })
+        .await
+        .unwrap();
+    wait_for_event(&codex, |ev| matches!(ev, EventMsg::TaskComplete(_))).await;
+
+    let requests = request_log.requests();
+    assert_eq!(requests.len(), 7);
+    let bodies: Vec<String> = requests
+        .iter()
+        .map(|request| request.body_json().to_string())
+        .collect();
+    let passes = &bodies[3..6];
+    for (pass, body) in passes.iter().enumerate() {
+        assert!(
+            body_contains_text(body, SUMMARIZATION_PROMPT),
+            "pass {pass} asks for a summary"
+        );
+        // Each pass covers the next turn, in order, and no other.
+        for turn in 1..=3 {
+            assert_eq!(
+                body.contains(&format!("TURN_{turn} ")),
+                turn == pass + 1,
+                "pass {pass}, turn {turn}"
+            );
+        }
+    }
+    // Later passes build on the summary before them.
+    assert!(!passes[0].contains("PASS_1_SUMMARY"));
+    assert!(body_contains_text(
+        &passes[1],
+        &summary_with_prefix("PASS_1_SUMMARY")
+    ));
+    assert!(body_contains_text(
+        &passes[2],
+        &summary_with_prefix("PASS_2_SUMMARY")
+    ));
+
+    // The compacted history keeps the summary of the last pass only.
+    let follow_up = &bodies[6];
+    assert!(body_contains_text(
+        follow_up,
+        &summary_with_prefix("PASS_3_SUMMARY")
+    ));
+    assert!(!follow_up.contains("PASS_1_SUMMARY"));
+    assert!(!follow_up.contains("PASS_2_SUMMARY"));
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
 async fn manual_compact_twice_preserves_latest_user_messages() {
     skip_if_no_network!();
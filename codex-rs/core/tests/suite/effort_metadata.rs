@@ -0,0 +1,111 @@
+use codex_core::protocol::EventMsg;
+use codex_core::protocol::Op;
+use codex_core::protocol::TaskStartedEvent;
+use codex_protocol::openai_models::ReasoningEffort;
+use codex_protocol::openai_models::ReasoningSummaryFormat;
+use codex_protocol::user_input::UserInput;
+use core_test_support::responses::ev_assistant_message;
+use core_test_support::responses::ev_completed;
+use core_test_support::responses::ev_response_created;
+use core_test_support::responses::mount_sse_once;
+use core_test_support::responses::sse;
+use core_test_support::responses::start_mock_server;
+use core_test_support::skip_if_no_network;
+use core_test_support::test_codex::TestCodex;
+use core_test_support::test_codex::test_codex;
+use core_test_support::wait_for_event;
+use core_test_support::wait_for_event_match;
+use pretty_assertions::assert_eq;
+use serde_json::Value;
+
+/// Run one turn of `model` at high effort and return the `TaskStarted` event
+/// along with the raw turn context lines from the rollout.
+async fn run_high_effort_turn(model: &str) -> anyhow::Result<(TaskStartedEvent, Vec<Value>)> {
+    let server = start_mock_server().await;
+    mount_sse_once(
+        &server,
+        sse(vec![
+            ev_response_created("resp-1"),
+            ev_assistant_message("msg-1", "done"),
+            ev_completed("resp-1"),
+        ]),
+    )
+    .await;
+
+    let TestCodex {
+        codex,
+        session_configured,
+        ..
+    } = test_codex()
+        .with_model(model)
+        .with_config(|config| config.model_reasoning_effort = Some(ReasoningEffort::High))
+        .build(&server)
+        .await?;
+
+    codex
+        .submit(Op::UserInput {
+            items: vec![UserInput::Text {
+                text: "hello".into(),
+            }],
+        })
+        .await?;
+    let started = wait_for_event_match(&codex, |event| match event {
+        EventMsg::TaskStarted(started) => Some(started.clone()),
+        _ => None,
+    })
+    .await;
+    wait_for_event(&codex, |event| matches!(event, EventMsg::TaskComplete(_))).await;
+    codex.submit(Op::Shutdown).await?;
+    wait_for_event(&codex, |event| matches!(event, EventMsg::ShutdownComplete)).await;
+
+    let rollout = std::fs::read_to_string(&session_configured.rollout_path)?;
+    let turn_contexts = rollout
+        .lines()
+        .filter_map(|line| serde_json::from_str::<Value>(line).ok())
+        .filter(|line| line["type"] == "turn_context")
+        .map(|line| line["payload"].clone())
+        .collect();
+    Ok((started, turn_contexts))
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn turn_metadata_records_the_preset_effort_description() -> anyhow::Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let (started, turn_contexts) = run_high_effort_turn("gpt-5.1-codex").await?;
+
+    let description = "Maximizes reasoning depth for complex or ambiguous problems";
+    assert_eq!(started.reasoning_effort, Some(ReasoningEffort::High));
+    assert_eq!(
+        started.reasoning_effort_description.as_deref(),
+        Some(description)
+    );
+    assert_eq!(
+        started.reasoning_summary_format,
+        Some(ReasoningSummaryFormat::Headed)
+    );
+    let [turn_context] = turn_contexts.as_slice() else {
+        panic!("expected one turn context, got {turn_contexts:?}");
+    };
+    assert_eq!(turn_context["effort"], "high");
+    assert_eq!(turn_context["effort_description"], description);
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn turn_metadata_omits_effort_description_without_preset() -> anyhow::Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let (started, turn_contexts) = run_high_effort_turn("oca-large").await?;
+
+    assert_eq!(started.reasoning_effort, Some(ReasoningEffort::High));
+    assert_eq!(started.reasoning_effort_description, None);
+    let [turn_context] = turn_contexts.as_slice() else {
+        panic!("expected one turn context, got {turn_contexts:?}");
+    };
+    assert_eq!(turn_context["effort"], "high");
+    assert_eq!(turn_context.get("effort_description"), None);
+
+    Ok(())
+}
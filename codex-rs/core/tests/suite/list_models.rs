@@ -57,13 +57,10 @@ fn expected_models_for_chatgpt() -> Vec<ModelPreset> {
 }
 
 fn gpt_52_codex() -> ModelPreset {
-    ModelPreset {
-        id: "gpt-5.2-codex".to_string(),
-        model: "gpt-5.2-codex".to_string(),
-        display_name: "gpt-5.2-codex".to_string(),
-        description: "Latest frontier agentic coding model.".to_string(),
-        default_reasoning_effort: ReasoningEffort::Medium,
-        supported_reasoning_efforts: vec![
+    ModelPreset::builder("gpt-5.2-codex", "gpt-5.2-codex")
+        .description("Latest frontier agentic coding model.")
+        .default_reasoning_effort(ReasoningEffort::Medium)
+        .supported_reasoning_efforts(vec![
             effort(
                 ReasoningEffort::Low,
                 "Fast responses with lighter reasoning",
@@ -80,22 +77,20 @@ fn gpt_52_codex() -> ModelPreset {
                 ReasoningEffort::XHigh,
                 "Extra high reasoning depth for complex problems",
             ),
-        ],
-        is_default: true,
-        upgrade: None,
-        show_in_picker: true,
-        supported_in_api: false,
-    }
+        ])
+        .is_default(true)
+        .supported_in_api(false)
+        .supports_parallel_tool_calls(true)
+        .supports_reasoning_summaries(true)
+        .build()
+        .expect("valid preset")
 }
 
 fn gpt_5_1_codex_max() -> ModelPreset {
-    ModelPreset {
-        id: "gpt-5.1-codex-max".to_string(),
-        model: "gpt-5.1-codex-max".to_string(),
-        display_name: "gpt-5.1-codex-max".to_string(),
-        description: "Codex-optimized flagship for deep and fast reasoning.".to_string(),
-        default_reasoning_effort: ReasoningEffort::Medium,
-        supported_reasoning_efforts: vec![
+    ModelPreset::builder("gpt-5.1-codex-max", "gpt-5.1-codex-max")
+        .description("Codex-optimized flagship for deep and fast reasoning.")
+        .default_reasoning_effort(ReasoningEffort::Medium)
+        .supported_reasoning_efforts(vec![
             effort(
                 ReasoningEffort::Low,
                 "Fast responses with lighter reasoning",
@@ -112,22 +107,20 @@ fn gpt_5_1_codex_max() -> ModelPreset {
                 ReasoningEffort::XHigh,
                 "Extra high reasoning depth for complex problems",
             ),
-        ],
-        is_default: true,
-        upgrade: Some(gpt52_codex_upgrade()),
-        show_in_picker: true,
-        supported_in_api: true,
-    }
+        ])
+        .is_default(true)
+        .upgrade(gpt52_codex_upgrade())
+        .supports_parallel_tool_calls(false)
+        .supports_reasoning_summaries(true)
+        .build()
+        .expect("valid preset")
 }
 
 fn gpt_5_1_codex_mini() -> ModelPreset {
-    ModelPreset {
-        id: "gpt-5.1-codex-mini".to_string(),
-        model: "gpt-5.1-codex-mini".to_string(),
-        display_name: "gpt-5.1-codex-mini".to_string(),
-        description: "Optimized for codex. Cheaper, faster, but less capable.".to_string(),
-        default_reasoning_effort: ReasoningEffort::Medium,
-        supported_reasoning_efforts: vec![
+    ModelPreset::builder("gpt-5.1-codex-mini", "gpt-5.1-codex-mini")
+        .description("Optimized for codex. Cheaper, faster, but less capable.")
+        .default_reasoning_effort(ReasoningEffort::Medium)
+        .supported_reasoning_efforts(vec![
             effort(
                 ReasoningEffort::Medium,
                 "Dynamically adjusts reasoning based on the task",
@@ -136,24 +129,21 @@ fn gpt_5_1_codex_mini() -> ModelPreset {
                 ReasoningEffort::High,
                 "Maximizes reasoning depth for complex or ambiguous problems",
             ),
-        ],
-        is_default: false,
-        upgrade: Some(gpt52_codex_upgrade()),
-        show_in_picker: true,
-        supported_in_api: true,
-    }
+        ])
+        .upgrade(gpt52_codex_upgrade())
+        .supports_parallel_tool_calls(false)
+        .supports_reasoning_summaries(true)
+        .build()
+        .expect("valid preset")
 }
 
 fn gpt_5_2() -> ModelPreset {
-    ModelPreset {
-        id: "gpt-5.2".to_string(),
-        model: "gpt-5.2".to_string(),
-        display_name: "gpt-5.2".to_string(),
-        description:
-            "Latest frontier model with improvements across knowledge, reasoning and coding"
-                .to_string(),
-        default_reasoning_effort: ReasoningEffort::Medium,
-        supported_reasoning_efforts: vec![
+    ModelPreset::builder("gpt-5.2", "gpt-5.2")
+        .description(
+            "Latest frontier model with improvements across knowledge, reasoning and coding",
+        )
+        .default_reasoning_effort(ReasoningEffort::Medium)
+        .supported_reasoning_efforts(vec![
             effort(
                 ReasoningEffort::Low,
                 "Balances speed with some reasoning; useful for straightforward queries and short explanations",
@@ -170,12 +160,14 @@ fn gpt_5_2() -> ModelPreset {
                 ReasoningEffort::XHigh,
                 "Extra high reasoning for complex problems",
             ),
-        ],
-        is_default: false,
-        upgrade: Some(gpt52_codex_upgrade()),
-        show_in_picker: true,
-        supported_in_api: true,
-    }
+        ])
+        .upgrade(gpt52_codex_upgrade())
+        .supports_parallel_tool_calls(true)
+        .supports_reasoning_summaries(true)
+        .knowledge_cutoff("2025-08-31".to_string())
+        .released_at("2025-12-11".to_string())
+        .build()
+        .expect("valid preset")
 }
 
 fn gpt52_codex_upgrade() -> codex_protocol::openai_models::ModelUpgrade {
@@ -195,5 +187,8 @@ fn effort(reasoning_effort: ReasoningEffort, description: &str) -> ReasoningEffo
     ReasoningEffortPreset {
         effort: reasoning_effort,
         description: description.to_string(),
+        max_output_tokens: None,
+        request_timeout_secs: None,
+        stream_idle_timeout_secs: None,
     }
 }
@@ -26,6 +26,7 @@ mod compact;
 mod compact_remote;
 mod compact_resume_fork;
 mod deprecation_notice;
+mod effort_metadata;
 mod exec;
 mod exec_policy;
 mod fork_conversation;
@@ -37,9 +38,12 @@ mod list_models;
 mod live_cli;
 mod model_overrides;
 mod model_tools;
+mod model_unavailable;
+mod model_validation;
 mod models_etag_responses;
 mod otel;
 mod prompt_caching;
+mod prompt_model_variables;
 mod quota_exceeded;
 mod read_file;
 mod remote_models;
@@ -53,6 +57,7 @@ mod shell_command;
 mod shell_serialization;
 mod shell_snapshot;
 mod skills;
+mod status_file;
 mod stream_error_allows_next_turn;
 mod stream_no_completed;
 mod text_encoding_fix;
@@ -62,6 +67,7 @@ mod tools;
 mod truncation;
 mod undo;
 mod unified_exec;
+mod user_agent_suffix;
 mod user_notification;
 mod user_shell_cmd;
 mod view_image;
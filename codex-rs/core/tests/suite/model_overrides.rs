@@ -1,11 +1,28 @@
 use codex_core::CodexAuth;
 use codex_core::ConversationManager;
+use codex_core::ModelProviderInfo;
 use codex_core::protocol::EventMsg;
+use codex_core::protocol::ModelChangeSource;
 use codex_core::protocol::Op;
 use codex_protocol::openai_models::ReasoningEffort;
+use codex_protocol::user_input::UserInput;
 use core_test_support::load_default_config_for_test;
+use core_test_support::responses::ev_assistant_message;
+use core_test_support::responses::ev_completed;
+use core_test_support::responses::ev_function_call;
+use core_test_support::responses::ev_response_created;
+use core_test_support::responses::mount_response_once;
+use core_test_support::responses::mount_sse_once;
+use core_test_support::responses::sse;
+use core_test_support::responses::sse_response;
+use core_test_support::responses::start_mock_server;
+use core_test_support::skip_if_no_network;
+use core_test_support::test_codex::TestCodex;
+use core_test_support::test_codex::test_codex;
 use core_test_support::wait_for_event;
+use core_test_support::wait_for_event_match;
 use pretty_assertions::assert_eq;
+use std::time::Duration;
 use tempfile::TempDir;
 
 const CONFIG_TOML: &str = "config.toml";
@@ -38,6 +55,7 @@ async fn override_turn_context_does_not_persist_when_config_exists() {
             approval_policy: None,
             sandbox_policy: None,
             model: Some("o3".to_string()),
+            model_provider: None,
             effort: Some(Some(ReasoningEffort::High)),
             summary: None,
         })
@@ -80,6 +98,7 @@ async fn override_turn_context_does_not_create_config_file() {
             approval_policy: None,
             sandbox_policy: None,
             model: Some("o3".to_string()),
+            model_provider: None,
             effort: Some(Some(ReasoningEffort::Medium)),
             summary: None,
         })
@@ -94,3 +113,198 @@ async fn override_turn_context_does_not_create_config_file() {
         "override should not create config.toml"
     );
 }
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn override_turn_context_switches_provider_and_keeps_the_history() -> anyhow::Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let server = start_mock_server().await;
+    let gateway = start_mock_server().await;
+    let first = mount_sse_once(
+        &server,
+        sse(vec![
+            ev_response_created("resp-1"),
+            ev_assistant_message("msg-1", "answered by openai"),
+            ev_completed("resp-1"),
+        ]),
+    )
+    .await;
+    let second = mount_sse_once(
+        &gateway,
+        sse(vec![
+            ev_response_created("resp-2"),
+            ev_assistant_message("msg-2", "answered by the gateway"),
+            ev_completed("resp-2"),
+        ]),
+    )
+    .await;
+
+    let gateway_url = format!("{}/v1", gateway.uri());
+    let TestCodex { codex, config, .. } = test_codex()
+        .with_config(move |config| {
+            let provider = ModelProviderInfo {
+                name: "Gateway".to_string(),
+                base_url: Some(gateway_url),
+                ..config.model_provider.clone()
+            };
+            config
+                .model_providers
+                .insert("gateway".to_string(), provider);
+        })
+        .build(&server)
+        .await?;
+    let user_input = |text: &str| Op::UserInput {
+        items: vec![UserInput::Text {
+            text: text.to_string(),
+        }],
+    };
+
+    codex.submit(user_input("first turn")).await?;
+    wait_for_event(&codex, |event| matches!(event, EventMsg::TaskComplete(_))).await;
+
+    codex
+        .submit(Op::OverrideTurnContext {
+            cwd: None,
+            approval_policy: None,
+            sandbox_policy: None,
+            model: Some("oca-large".to_string()),
+            model_provider: Some("gateway".to_string()),
+            effort: None,
+            summary: None,
+        })
+        .await?;
+    let changed = wait_for_event_match(&codex, |event| match event {
+        EventMsg::ModelChanged(event) => Some(event.clone()),
+        _ => None,
+    })
+    .await;
+    assert_eq!(
+        (
+            changed.model.as_str(),
+            changed.source,
+            changed.previous_model_provider.as_deref(),
+            changed.model_provider.as_deref(),
+        ),
+        (
+            "oca-large",
+            ModelChangeSource::Selected,
+            Some(config.model_provider_id.as_str()),
+            Some("gateway"),
+        )
+    );
+
+    codex.submit(user_input("second turn")).await?;
+    wait_for_event(&codex, |event| matches!(event, EventMsg::TaskComplete(_))).await;
+
+    assert_eq!(first.requests().len(), 1);
+    let request = second.single_request();
+    assert_eq!(request.body_json()["model"], "oca-large");
+    let input = serde_json::to_string(&request.input())?;
+    for text in ["first turn", "answered by openai", "second turn"] {
+        assert!(input.contains(text), "`{text}` is missing from {input}");
+    }
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn model_switch_during_a_turn_applies_when_the_turn_ends() -> anyhow::Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let server = start_mock_server().await;
+    // The first response is slow enough for the switch to arrive while it
+    // streams, and calls a tool so the turn makes a second request.
+    let tool_call = mount_response_once(
+        &server,
+        sse_response(sse(vec![
+            ev_response_created("resp-1"),
+            ev_function_call("call-1", "nonexistent", "{}"),
+            ev_completed("resp-1"),
+        ]))
+        .set_delay(Duration::from_millis(500)),
+    )
+    .await;
+    let tool_output = mount_sse_once(
+        &server,
+        sse(vec![
+            ev_response_created("resp-2"),
+            ev_assistant_message("msg-2", "first turn done"),
+            ev_completed("resp-2"),
+        ]),
+    )
+    .await;
+    let next_turn = mount_sse_once(
+        &server,
+        sse(vec![
+            ev_response_created("resp-3"),
+            ev_assistant_message("msg-3", "second turn done"),
+            ev_completed("resp-3"),
+        ]),
+    )
+    .await;
+
+    let TestCodex {
+        codex,
+        session_configured,
+        ..
+    } = test_codex().build(&server).await?;
+    let original_model = session_configured.model.clone();
+    let user_input = |text: &str| Op::UserInput {
+        items: vec![UserInput::Text {
+            text: text.to_string(),
+        }],
+    };
+
+    codex.submit(user_input("first turn")).await?;
+    wait_for_event(&codex, |event| matches!(event, EventMsg::TaskStarted(_))).await;
+    codex
+        .submit(Op::OverrideTurnContext {
+            cwd: None,
+            approval_policy: None,
+            sandbox_policy: None,
+            model: Some("o3".to_string()),
+            model_provider: None,
+            effort: Some(Some(ReasoningEffort::High)),
+            summary: None,
+        })
+        .await?;
+
+    let changed = wait_for_event_match(&codex, |event| match event {
+        EventMsg::ModelChanged(event) => Some(event.clone()),
+        _ => None,
+    })
+    .await;
+    assert_eq!(
+        (changed.previous_model.as_str(), changed.model.as_str()),
+        (original_model.as_str(), "o3")
+    );
+    assert_eq!(
+        tool_output.requests().len(),
+        1,
+        "the switch is reported once the turn has made its last request"
+    );
+    wait_for_event(&codex, |event| matches!(event, EventMsg::TaskComplete(_))).await;
+
+    codex.submit(user_input("second turn")).await?;
+    wait_for_event(&codex, |event| matches!(event, EventMsg::TaskComplete(_))).await;
+
+    let models: Vec<String> = [&tool_call, &tool_output, &next_turn]
+        .into_iter()
+        .map(|mock| {
+            mock.single_request().body_json()["model"]
+                .as_str()
+                .unwrap_or_default()
+                .to_string()
+        })
+        .collect();
+    assert_eq!(
+        models,
+        vec![original_model.clone(), original_model, "o3".to_string()]
+    );
+    assert_eq!(
+        next_turn.single_request().body_json()["reasoning"]["effort"],
+        "high"
+    );
+
+    Ok(())
+}
@@ -1,10 +1,17 @@
 #![allow(clippy::unwrap_used)]
 
+use codex_core::config::types::ModelPresetOverride;
+use codex_core::features::Feature;
+use codex_core::protocol::EventMsg;
+use codex_core::protocol::Op;
+use codex_protocol::user_input::UserInput;
 use core_test_support::load_sse_fixture_with_id;
 use core_test_support::responses;
 use core_test_support::responses::start_mock_server;
 use core_test_support::skip_if_no_network;
+use core_test_support::test_codex::TestCodex;
 use core_test_support::test_codex::test_codex;
+use core_test_support::wait_for_event;
 
 fn sse_completed(id: &str) -> String {
     load_sse_fixture_with_id("tests/fixtures/completed_template.json", id)
@@ -137,3 +144,69 @@ async fn model_selects_expected_tools() {
         "exp-5.1 should expose the apply_patch tool",
     );
 }
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn preset_disabled_tools_return_after_switching_models() -> anyhow::Result<()> {
+    skip_if_no_network!(Ok(()));
+    use pretty_assertions::assert_eq;
+
+    let server = start_mock_server().await;
+    let restricted_turn = responses::mount_sse_once(&server, sse_completed("resp-1")).await;
+    let unrestricted_turn = responses::mount_sse_once(&server, sse_completed("resp-2")).await;
+
+    let TestCodex {
+        codex,
+        session_configured,
+        ..
+    } = test_codex()
+        .with_model("gpt-5.1-codex")
+        .with_config(|config| {
+            config.features.enable(Feature::WebSearchRequest);
+            config.model_preset_overrides.insert(
+                "gpt-5.1-codex".to_string(),
+                ModelPresetOverride {
+                    disabled_tools: vec!["web_search".to_string()],
+                    ..Default::default()
+                },
+            );
+        })
+        .build(&server)
+        .await?;
+    assert_eq!(
+        session_configured.disabled_tools,
+        vec!["web_search".to_string()]
+    );
+
+    let send = |text: &str| Op::UserInput {
+        items: vec![UserInput::Text { text: text.into() }],
+    };
+    codex.submit(send("restricted")).await?;
+    wait_for_event(&codex, |ev| matches!(ev, EventMsg::TaskComplete(_))).await;
+
+    codex
+        .submit(Op::OverrideTurnContext {
+            cwd: None,
+            approval_policy: None,
+            sandbox_policy: None,
+            model: Some("gpt-5.1".to_string()),
+            model_provider: None,
+            effort: None,
+            summary: None,
+        })
+        .await?;
+    codex.submit(send("unrestricted")).await?;
+    wait_for_event(&codex, |ev| matches!(ev, EventMsg::TaskComplete(_))).await;
+
+    let restricted_tools = tool_identifiers(&restricted_turn.single_request().body_json());
+    let unrestricted_tools = tool_identifiers(&unrestricted_turn.single_request().body_json());
+    assert!(
+        !restricted_tools.contains(&"web_search".to_string()),
+        "web_search should be withheld from gpt-5.1-codex: {restricted_tools:?}"
+    );
+    assert!(
+        unrestricted_tools.contains(&"web_search".to_string()),
+        "web_search should be back for gpt-5.1: {unrestricted_tools:?}"
+    );
+
+    Ok(())
+}
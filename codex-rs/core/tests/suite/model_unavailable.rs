@@ -0,0 +1,198 @@
+use codex_core::ModelCatalog;
+use codex_core::protocol::CodexErrorInfo;
+use codex_core::protocol::EventMsg;
+use codex_core::protocol::Op;
+use codex_protocol::user_input::UserInput;
+use core_test_support::responses::ev_assistant_message;
+use core_test_support::responses::ev_completed;
+use core_test_support::responses::ev_response_created;
+use core_test_support::responses::mount_sse_once;
+use core_test_support::responses::sse;
+use core_test_support::responses::start_mock_server;
+use core_test_support::skip_if_no_network;
+use core_test_support::test_codex::TestCodex;
+use core_test_support::test_codex::test_codex;
+use core_test_support::wait_for_event;
+use core_test_support::wait_for_event_match;
+use pretty_assertions::assert_eq;
+use serde_json::json;
+use wiremock::Mock;
+use wiremock::MockServer;
+use wiremock::ResponseTemplate;
+use wiremock::matchers::method;
+use wiremock::matchers::path;
+use wiremock::matchers::path_regex;
+
+const GATEWAY_PROVIDER_ID: &str = "gateway";
+
+async fn catalog_requests(server: &MockServer) -> usize {
+    server
+        .received_requests()
+        .await
+        .unwrap_or_default()
+        .iter()
+        .filter(|request| request.url.path() == "/v1/models")
+        .count()
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn rejected_model_reports_preset_and_refreshes_catalog() -> anyhow::Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let server = start_mock_server().await;
+    Mock::given(method("GET"))
+        .and(path("/v1/models"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "object": "list",
+            "data": [{"id": "oca-large", "object": "model"}],
+        })))
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path_regex(".*/responses$"))
+        .respond_with(ResponseTemplate::new(404).set_body_json(json!({
+            "error": {
+                "code": "model_not_found",
+                "message": "The model `oca-large` does not exist.",
+            }
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let TestCodex { codex, .. } = test_codex()
+        .with_model("oca-large")
+        .with_config(|config| {
+            config.model_provider.name = "Gateway".to_string();
+            config.model_provider.model_catalog = Some(ModelCatalog::Openai);
+            config.model_provider_id = GATEWAY_PROVIDER_ID.to_string();
+            config.model_providers.insert(
+                GATEWAY_PROVIDER_ID.to_string(),
+                config.model_provider.clone(),
+            );
+            // Strict validation waits for the catalog before the session starts.
+            config.strict_model_validation = true;
+        })
+        .build(&server)
+        .await?;
+    let fetched_at_startup = catalog_requests(&server).await;
+
+    codex
+        .submit(Op::UserInput {
+            items: vec![UserInput::Text {
+                text: "hello".to_string(),
+            }],
+        })
+        .await?;
+
+    let error = wait_for_event_match(&codex, |event| match event {
+        EventMsg::Error(ev) => Some(ev.clone()),
+        _ => None,
+    })
+    .await;
+    assert_eq!(
+        error.codex_error_info,
+        Some(CodexErrorInfo::ModelUnavailable {
+            preset_id: "gateway/oca-large".to_string(),
+        })
+    );
+    assert!(
+        error
+            .message
+            .starts_with("model `oca-large` is not available from the provider"),
+        "unexpected message: {}",
+        error.message
+    );
+    assert!(
+        catalog_requests(&server).await > fetched_at_startup,
+        "catalog was not refreshed after the model was rejected"
+    );
+    wait_for_event(&codex, |event| matches!(event, EventMsg::TaskComplete(_))).await;
+
+    Ok(())
+}
+
+/// Why the catalog greys `model` out for the session's account, if it does.
+async fn unavailable_reason(test: &TestCodex, model: &str) -> Option<String> {
+    test.conversation_manager
+        .get_models_manager()
+        .list_models(&test.config)
+        .await
+        .into_iter()
+        .find(|preset| preset.model == model)
+        .and_then(|preset| preset.unavailable_for_account)
+}
+
+async fn send_hello(test: &TestCodex) -> anyhow::Result<()> {
+    test.codex
+        .submit(Op::UserInput {
+            items: vec![UserInput::Text {
+                text: "hello".to_string(),
+            }],
+        })
+        .await?;
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn refused_model_is_marked_for_the_account_until_it_works_again() -> anyhow::Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    const MODEL: &str = "gpt-5.1-codex-mini";
+    let server = start_mock_server().await;
+    Mock::given(method("POST"))
+        .and(path_regex(".*/responses$"))
+        .respond_with(ResponseTemplate::new(403).set_body_json(json!({
+            "error": {
+                "code": "model_not_permitted",
+                "message": "Your plan does not include this model.",
+            }
+        })))
+        .up_to_n_times(1)
+        .mount(&server)
+        .await;
+
+    let test = test_codex().with_model(MODEL).build(&server).await?;
+    assert_eq!(unavailable_reason(&test, MODEL).await, None);
+
+    send_hello(&test).await?;
+    let error = wait_for_event_match(&test.codex, |event| match event {
+        EventMsg::Error(ev) => Some(ev.clone()),
+        _ => None,
+    })
+    .await;
+    assert!(
+        error
+            .message
+            .starts_with("model `gpt-5.1-codex-mini` is not available to this account"),
+        "unexpected message: {}",
+        error.message
+    );
+    wait_for_event(&test.codex, |event| {
+        matches!(event, EventMsg::TaskComplete(_))
+    })
+    .await;
+    assert_eq!(
+        unavailable_reason(&test, MODEL).await.as_deref(),
+        Some("Your plan does not include this model.")
+    );
+
+    // Once the plan includes the model again, a successful turn clears the mark.
+    mount_sse_once(
+        &server,
+        sse(vec![
+            ev_response_created("resp-1"),
+            ev_assistant_message("msg-1", "hi"),
+            ev_completed("resp-1"),
+        ]),
+    )
+    .await;
+    send_hello(&test).await?;
+    wait_for_event(&test.codex, |event| {
+        matches!(event, EventMsg::TaskComplete(_))
+    })
+    .await;
+    assert_eq!(unavailable_reason(&test, MODEL).await, None);
+
+    Ok(())
+}
@@ -0,0 +1,376 @@
+use std::time::Duration;
+
+use codex_core::ModelCatalog;
+use codex_core::config::CONFIG_TOML_FILE;
+use codex_core::models_manager::resolution::resolve_model;
+use codex_core::protocol::EventMsg;
+use codex_core::protocol::Op;
+use codex_protocol::openai_models::ReasoningEffort;
+use codex_protocol::user_input::UserInput;
+use core_test_support::responses::ev_completed;
+use core_test_support::responses::ev_response_created;
+use core_test_support::responses::mount_sse_once;
+use core_test_support::responses::sse;
+use core_test_support::responses::start_mock_server;
+use core_test_support::skip_if_no_network;
+use core_test_support::test_codex::TestCodex;
+use core_test_support::test_codex::test_codex;
+use core_test_support::wait_for_event;
+use core_test_support::wait_for_event_match;
+use pretty_assertions::assert_eq;
+use serde_json::json;
+use wiremock::Mock;
+use wiremock::MockServer;
+use wiremock::ResponseTemplate;
+use wiremock::matchers::method;
+use wiremock::matchers::path;
+
+const GATEWAY_PROVIDER_ID: &str = "gateway";
+
+/// Serve `ids` from `/v1/models` after `delay`, mimicking a slow gateway.
+async fn mount_gateway_catalog(server: &MockServer, ids: &[&str], delay: Duration) {
+    let data: Vec<serde_json::Value> = ids
+        .iter()
+        .map(|id| json!({"id": id, "object": "model"}))
+        .collect();
+    Mock::given(method("GET"))
+        .and(path("/v1/models"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(json!({"object": "list", "data": data}))
+                .set_delay(delay),
+        )
+        .mount(server)
+        .await;
+}
+
+fn use_gateway_catalog(config: &mut codex_core::config::Config) {
+    config.model_provider.name = "Gateway".to_string();
+    config.model_provider.model_catalog = Some(ModelCatalog::Openai);
+    config.model_provider_id = GATEWAY_PROVIDER_ID.to_string();
+    config.model_providers.insert(
+        GATEWAY_PROVIDER_ID.to_string(),
+        config.model_provider.clone(),
+    );
+}
+
+async fn next_warning(codex: &codex_core::CodexConversation) -> String {
+    wait_for_event_match(codex, |event| match event {
+        EventMsg::Warning(ev) => Some(ev.message.clone()),
+        _ => None,
+    })
+    .await
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn warns_when_configured_model_is_not_in_catalog() -> anyhow::Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let server = start_mock_server().await;
+    let TestCodex { codex, .. } = test_codex()
+        .with_model("gpt-5.1-codx")
+        .build(&server)
+        .await?;
+
+    assert_eq!(
+        next_warning(&codex).await,
+        "model `gpt-5.1-codx` is not in the model catalog for provider `OpenAI`. Did you mean one of `gpt-5.1-codex`, `gpt-5.2-codex`, `gpt-5-codex`? Run `codex models list` to see the available models."
+    );
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn strict_model_validation_fails_session_start() -> anyhow::Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let server = start_mock_server().await;
+    let result = test_codex()
+        .with_model("gpt-5.1-codx")
+        .with_config(|config| config.strict_model_validation = true)
+        .build(&server)
+        .await;
+
+    let Err(err) = result else {
+        panic!("session should not start with an unknown model in strict mode");
+    };
+    let message = err.to_string();
+    assert!(
+        message.contains("model `gpt-5.1-codx` is not in the model catalog")
+            && message.ends_with("Set `strict_model_validation = false` to start anyway."),
+        "unexpected error: {message}"
+    );
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn catalog_provider_check_runs_after_listing_loads() -> anyhow::Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let server = start_mock_server().await;
+    mount_gateway_catalog(&server, &["oca-large"], Duration::from_millis(500)).await;
+
+    // The session starts before the slow listing arrives; the warning follows
+    // once the catalog has loaded instead of firing against an empty list.
+    let TestCodex {
+        codex,
+        session_configured,
+        ..
+    } = test_codex()
+        .with_model("oca-lrge")
+        .with_config(use_gateway_catalog)
+        .build(&server)
+        .await?;
+    assert_eq!(session_configured.model, "oca-lrge");
+
+    assert_eq!(
+        next_warning(&codex).await,
+        "model `oca-lrge` is not in the model catalog for provider `Gateway`. Did you mean `oca-large`? Run `codex models list` to see the available models."
+    );
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn strict_catalog_provider_check_waits_for_listing() -> anyhow::Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let server = start_mock_server().await;
+    mount_gateway_catalog(&server, &["oca-large"], Duration::from_millis(200)).await;
+
+    let listed = test_codex()
+        .with_model("oca-large")
+        .with_config(|config| {
+            use_gateway_catalog(config);
+            config.strict_model_validation = true;
+        })
+        .build(&server)
+        .await;
+    assert!(listed.is_ok(), "listed model should start in strict mode");
+
+    let result = test_codex()
+        .with_model("oca-lrge")
+        .with_config(|config| {
+            use_gateway_catalog(config);
+            config.strict_model_validation = true;
+        })
+        .build(&server)
+        .await;
+    let Err(err) = result else {
+        panic!("strict mode should wait for the listing and reject the typo");
+    };
+    assert!(
+        err.to_string().contains("Did you mean `oca-large`?"),
+        "unexpected error: {err}"
+    );
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn catalog_preset_without_efforts_omits_the_configured_effort() -> anyhow::Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let server = start_mock_server().await;
+    mount_gateway_catalog(&server, &["gpt-5.1-codex"], Duration::ZERO).await;
+    let responses = mount_sse_once(
+        &server,
+        sse(vec![ev_response_created("resp-1"), ev_completed("resp-1")]),
+    )
+    .await;
+
+    // Catalog presets list no efforts, so the model takes no effort parameter
+    // even though its family reasons.
+    let test = test_codex()
+        .with_model("gpt-5.1-codex")
+        .with_config(|config| {
+            use_gateway_catalog(config);
+            config.strict_model_validation = true;
+            config.model_reasoning_effort = Some(ReasoningEffort::High);
+        })
+        .build(&server)
+        .await?;
+
+    assert_eq!(
+        next_warning(&test.codex).await,
+        "model `gpt-5.1-codex` does not support a reasoning effort; `model_reasoning_effort = \"high\"` will be ignored."
+    );
+
+    test.submit_turn("hello").await?;
+    let body = responses.single_request().body_json();
+    assert!(
+        body["reasoning"].is_object(),
+        "reasoning summaries are still requested: {body}"
+    );
+    assert_eq!(body["reasoning"].get("effort"), None);
+
+    Ok(())
+}
+
+/// An AGENTS.md naming one listed and one unlisted gateway model.
+fn write_agents_md(config: &codex_core::config::Config) {
+    std::fs::write(
+        config.cwd.join("AGENTS.md"),
+        "Use @model:oca-large for refactors.\nSend reviews to @model:oca-xl.\n",
+    )
+    .expect("write AGENTS.md");
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn warns_about_project_doc_models_missing_from_the_catalog() -> anyhow::Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let server = start_mock_server().await;
+    mount_gateway_catalog(&server, &["oca-large"], Duration::ZERO).await;
+
+    let TestCodex { codex, .. } = test_codex()
+        .with_model("oca-large")
+        .with_config(|config| {
+            use_gateway_catalog(config);
+            config.validate_instruction_models = true;
+            write_agents_md(config);
+        })
+        .build(&server)
+        .await?;
+
+    assert_eq!(
+        next_warning(&codex).await,
+        "project docs reference models that are not in the model catalog: `oca-xl` (AGENTS.md:2). The instructions are sent as written; run `codex models list` to see the available models."
+    );
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn project_doc_models_are_not_checked_by_default() -> anyhow::Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let server = start_mock_server().await;
+    mount_gateway_catalog(&server, &["oca-large"], Duration::ZERO).await;
+    mount_sse_once(
+        &server,
+        sse(vec![ev_response_created("resp-1"), ev_completed("resp-1")]),
+    )
+    .await;
+
+    let TestCodex { codex, .. } = test_codex()
+        .with_model("oca-large")
+        .with_config(|config| {
+            use_gateway_catalog(config);
+            write_agents_md(config);
+        })
+        .build(&server)
+        .await?;
+
+    codex
+        .submit(Op::UserInput {
+            items: vec![UserInput::Text {
+                text: "hello".into(),
+            }],
+        })
+        .await?;
+    let event = wait_for_event(&codex, |event| {
+        matches!(event, EventMsg::Warning(_) | EventMsg::TaskComplete(_))
+    })
+    .await;
+    assert!(
+        matches!(event, EventMsg::TaskComplete(_)),
+        "unexpected warning: {event:?}"
+    );
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn retired_model_resolves_to_its_successor_with_a_notice() -> anyhow::Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let server = start_mock_server().await;
+    let test = test_codex()
+        .with_model("codex-mini-latest")
+        .build(&server)
+        .await?;
+
+    assert_eq!(test.session_configured.model, "gpt-5.1-codex-mini");
+    let notice = wait_for_event_match(&test.codex, |event| match event {
+        EventMsg::DeprecationNotice(ev) => Some(ev.summary.clone()),
+        _ => None,
+    })
+    .await;
+    assert_eq!(
+        notice,
+        "Model `codex-mini-latest` has been retired. Using `gpt-5.1-codex-mini` instead."
+    );
+
+    // The notice is recorded so later runs stay quiet.
+    let config_toml: toml::Value = toml::from_str(&std::fs::read_to_string(
+        test.config.codex_home.join(CONFIG_TOML_FILE),
+    )?)?;
+    assert_eq!(
+        config_toml
+            .get("notice")
+            .and_then(|notice| notice.get("model_migrations"))
+            .and_then(|migrations| migrations.get("codex-mini-latest"))
+            .and_then(toml::Value::as_str),
+        Some("gpt-5.1-codex-mini")
+    );
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn model_resolution_matches_the_session_start() -> anyhow::Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let server = start_mock_server().await;
+    let test = test_codex()
+        .with_model("codex-mini-latest")
+        .build(&server)
+        .await?;
+
+    let models_manager = test.conversation_manager.get_models_manager();
+    let resolution = resolve_model(&models_manager, &test.config, None).await;
+    assert_eq!(resolution.model, test.session_configured.model);
+    assert_eq!(resolution.preset_id.as_deref(), Some("gpt-5.1-codex-mini"));
+    assert_eq!(
+        resolution.provider_id,
+        test.session_configured.model_provider_id
+    );
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn rewrite_retired_models_updates_config_toml() -> anyhow::Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let server = start_mock_server().await;
+    let test = test_codex()
+        .with_pre_build_hook(|home| {
+            std::fs::write(
+                home.join(CONFIG_TOML_FILE),
+                "model = \"codex-mini-latest\"\n\n[profiles.fast]\nmodel = \"codex-mini-latest\"\n",
+            )
+            .expect("seed config.toml");
+        })
+        .with_model("codex-mini-latest")
+        .with_config(|config| {
+            config.rewrite_retired_models = true;
+            config.notices.model_migrations.insert(
+                "codex-mini-latest".to_string(),
+                "gpt-5.1-codex-mini".to_string(),
+            );
+        })
+        .build(&server)
+        .await?;
+
+    assert_eq!(test.session_configured.model, "gpt-5.1-codex-mini");
+    let contents = std::fs::read_to_string(test.config.codex_home.join(CONFIG_TOML_FILE))?;
+    assert_eq!(
+        contents,
+        "model = \"gpt-5.1-codex-mini\"\n\n[profiles.fast]\nmodel = \"gpt-5.1-codex-mini\"\n"
+    );
+
+    Ok(())
+}
@@ -36,12 +36,8 @@ async fn refresh_models_on_models_etag_mismatch_and_avoid_duplicate_models_fetch
     let server = MockServer::start().await;
 
     // 1) On spawn, Codex fetches /models and stores the ETag.
-    let spawn_models_mock = responses::mount_models_once_with_etag(
-        &server,
-        ModelsResponse { models: Vec::new() },
-        ETAG_1,
-    )
-    .await;
+    let spawn_models_mock =
+        responses::mount_models_once_with_etag(&server, ModelsResponse::default(), ETAG_1).await;
 
     let auth = CodexAuth::create_dummy_chatgpt_auth_for_testing();
     let mut builder = test_codex()
@@ -63,12 +59,8 @@ async fn refresh_models_on_models_etag_mismatch_and_avoid_duplicate_models_fetch
     assert_eq!(spawn_models_mock.single_request_path(), "/v1/models");
 
     // 2) If the server sends a different X-Models-Etag on /responses, Codex refreshes /models.
-    let refresh_models_mock = responses::mount_models_once_with_etag(
-        &server,
-        ModelsResponse { models: Vec::new() },
-        ETAG_2,
-    )
-    .await;
+    let refresh_models_mock =
+        responses::mount_models_once_with_etag(&server, ModelsResponse::default(), ETAG_2).await;
 
     // First /responses request (user message) succeeds and returns a tool call.
     // It also includes a mismatched X-Models-Etag, which should trigger a /models refresh.
@@ -1,11 +1,23 @@
+use codex_core::AuthManager;
+use codex_core::CodexAuth;
+use codex_core::ModelCatalog;
+use codex_core::ModelProviderInfo;
+use codex_core::built_in_model_providers;
 use codex_core::config::Constrained;
+use codex_core::config::types::OtelExporterKind;
+use codex_core::config::types::OtelHttpProtocol;
 use codex_core::features::Feature;
+use codex_core::models_manager::manager::ModelsManager;
+use codex_otel::catalog::ModelIdKey;
+use codex_otel::catalog::hash_model_id;
+use codex_protocol::openai_models::ReasoningEffort;
 use codex_protocol::protocol::AskForApproval;
 use codex_protocol::protocol::EventMsg;
 use codex_protocol::protocol::Op;
 use codex_protocol::protocol::ReviewDecision;
 use codex_protocol::protocol::SandboxPolicy;
 use codex_protocol::user_input::UserInput;
+use core_test_support::load_default_config_for_test;
 use core_test_support::responses::ev_assistant_message;
 use core_test_support::responses::ev_completed;
 use core_test_support::responses::ev_custom_tool_call;
@@ -25,7 +37,9 @@ use core_test_support::responses::start_mock_server;
 use core_test_support::test_codex::TestCodex;
 use core_test_support::test_codex::test_codex;
 use core_test_support::wait_for_event;
+use std::collections::HashMap;
 use std::sync::Mutex;
+use tempfile::TempDir;
 use tracing::Level;
 use tracing_test::traced_test;
 
@@ -1307,3 +1321,161 @@ async fn handle_sandbox_error_user_denies_records_tool_decision() {
         "user",
     ));
 }
+
+fn otlp_exporter() -> OtelExporterKind {
+    OtelExporterKind::OtlpHttp {
+        endpoint: "http://127.0.0.1:4318/v1/logs".to_string(),
+        headers: HashMap::new(),
+        protocol: OtelHttpProtocol::Json,
+        tls: None,
+    }
+}
+
+#[tokio::test]
+#[traced_test]
+async fn session_start_emits_model_selected_event() {
+    let server = start_mock_server().await;
+
+    let TestCodex { .. } = test_codex()
+        .with_model("gpt-5.1-codex")
+        .with_config(|config| {
+            config.otel.exporter = otlp_exporter();
+            config.model_reasoning_effort = Some(ReasoningEffort::High);
+        })
+        .build(&server)
+        .await
+        .unwrap();
+
+    logs_assert(|lines: &[&str]| {
+        lines
+            .iter()
+            .find(|line| {
+                line.contains("codex.model_selected")
+                    && line.contains("preset_id=gpt-5.1-codex")
+                    && line.contains("reasoning_effort=\"high\"")
+                    && line.contains("selection.source=config")
+            })
+            .map(|_| Ok(()))
+            .unwrap_or_else(|| Err("expected codex.model_selected event".to_string()))
+    });
+}
+
+#[tokio::test]
+#[traced_test]
+async fn model_selected_event_hashes_model_ids_when_configured() {
+    let server = start_mock_server().await;
+
+    let key = ModelIdKey::random();
+    let TestCodex { .. } = test_codex()
+        .with_model("gpt-5.1-codex")
+        .with_config(move |config| {
+            config.otel.exporter = otlp_exporter();
+            config.otel.model_id_key = Some(key);
+        })
+        .build(&server)
+        .await
+        .unwrap();
+
+    let hashed = format!("preset_id={}", hash_model_id(&key, "gpt-5.1-codex"));
+    logs_assert(|lines: &[&str]| {
+        let selected: Vec<&&str> = lines
+            .iter()
+            .filter(|line| line.contains("codex.model_selected"))
+            .collect();
+        match selected.as_slice() {
+            [line] if line.contains(&hashed) && !line.contains("gpt-5.1-codex") => Ok(()),
+            _ => Err(format!(
+                "expected one hashed model_selected event: {selected:?}"
+            )),
+        }
+    });
+}
+
+#[tokio::test]
+#[traced_test]
+async fn model_selected_event_is_suppressed_without_an_exporter() {
+    let server = start_mock_server().await;
+
+    let TestCodex { .. } = test_codex()
+        .with_model("gpt-5.1-codex")
+        .build(&server)
+        .await
+        .unwrap();
+
+    logs_assert(|lines: &[&str]| {
+        match lines
+            .iter()
+            .find(|line| line.contains("codex.model_selected"))
+        {
+            Some(line) => Err(format!("unexpected model_selected event: {line}")),
+            None => Ok(()),
+        }
+    });
+}
+
+#[tokio::test]
+#[traced_test]
+async fn provider_catalog_fetch_emits_catalog_fetch_completed_event() {
+    let codex_home = TempDir::new().unwrap();
+    let mut config = load_default_config_for_test(&codex_home).await;
+    config.otel.exporter = otlp_exporter();
+    config.model_providers.insert(
+        "gateway".to_string(),
+        ModelProviderInfo {
+            model_catalog: Some(ModelCatalog::None),
+            models: Some(vec!["oca-large".to_string(), "oca-small".to_string()]),
+            ..built_in_model_providers()["openai"].clone()
+        },
+    );
+    let manager = ModelsManager::new(AuthManager::from_auth_for_testing(CodexAuth::from_api_key(
+        "Test API Key",
+    )));
+
+    manager.refresh_provider_models(&config).await;
+
+    let revision = format!("catalog.revision={}", manager.catalog_revision());
+    logs_assert(|lines: &[&str]| {
+        lines
+            .iter()
+            .find(|line| {
+                line.contains("codex.catalog_fetch_completed")
+                    && line.contains("catalog.source=\"provider\"")
+                    && line.contains("catalog.model_count=2")
+                    && line.contains(&revision)
+                    && line.contains("duration_ms=")
+                    && !line.contains("oca-large")
+            })
+            .map(|_| Ok(()))
+            .unwrap_or_else(|| Err("expected codex.catalog_fetch_completed event".to_string()))
+    });
+}
+
+#[tokio::test]
+#[traced_test]
+async fn catalog_fetch_completed_event_is_suppressed_without_an_exporter() {
+    let codex_home = TempDir::new().unwrap();
+    let mut config = load_default_config_for_test(&codex_home).await;
+    config.model_providers.insert(
+        "gateway".to_string(),
+        ModelProviderInfo {
+            model_catalog: Some(ModelCatalog::None),
+            models: Some(vec!["oca-large".to_string()]),
+            ..built_in_model_providers()["openai"].clone()
+        },
+    );
+    let manager = ModelsManager::new(AuthManager::from_auth_for_testing(CodexAuth::from_api_key(
+        "Test API Key",
+    )));
+
+    manager.refresh_provider_models(&config).await;
+
+    logs_assert(|lines: &[&str]| {
+        match lines
+            .iter()
+            .find(|line| line.contains("codex.catalog_fetch_completed"))
+        {
+            Some(line) => Err(format!("unexpected catalog_fetch_completed event: {line}")),
+            None => Ok(()),
+        }
+    });
+}
@@ -323,6 +323,7 @@ async fn overrides_turn_context_but_keeps_cached_prefix_and_key_constant() -> an
                 exclude_slash_tmp: true,
             }),
             model: Some("o3".to_string()),
+            model_provider: None,
             effort: Some(Some(ReasoningEffort::High)),
             summary: Some(ReasoningSummary::Detailed),
         })
@@ -402,6 +403,7 @@ async fn override_before_first_turn_emits_environment_context() -> anyhow::Resul
             approval_policy: Some(AskForApproval::Never),
             sandbox_policy: None,
             model: None,
+            model_provider: None,
             effort: None,
             summary: None,
         })
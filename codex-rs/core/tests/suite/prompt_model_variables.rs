@@ -0,0 +1,76 @@
+use codex_core::config::types::PresetOverride;
+use codex_core::protocol::EventMsg;
+use codex_core::protocol::Op;
+use codex_protocol::openai_models::ReasoningEffort;
+use codex_protocol::user_input::UserInput;
+use core_test_support::responses::ev_assistant_message;
+use core_test_support::responses::ev_completed;
+use core_test_support::responses::ev_response_created;
+use core_test_support::responses::mount_sse_once;
+use core_test_support::responses::sse;
+use core_test_support::responses::start_mock_server;
+use core_test_support::skip_if_no_network;
+use core_test_support::test_codex::TestCodex;
+use core_test_support::test_codex::test_codex;
+use core_test_support::wait_for_event;
+use core_test_support::wait_for_event_match;
+use pretty_assertions::assert_eq;
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn prompt_model_variables_are_filled_from_the_turn_model() -> anyhow::Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let server = start_mock_server().await;
+    let response = mount_sse_once(
+        &server,
+        sse(vec![
+            ev_response_created("resp-1"),
+            ev_assistant_message("msg-1", "done"),
+            ev_completed("resp-1"),
+        ]),
+    )
+    .await;
+
+    let TestCodex { codex, .. } = test_codex()
+        .with_model("gpt-5.1-codex-max")
+        .with_config(|config| {
+            config.model_reasoning_effort = Some(ReasoningEffort::High);
+            config.preset_overrides.insert(
+                "gpt-5.1-codex-max".to_string(),
+                PresetOverride {
+                    display_name: Some("Codex Max".to_string()),
+                    ..Default::default()
+                },
+            );
+        })
+        .build(&server)
+        .await?;
+
+    codex
+        .submit(Op::UserInput {
+            items: vec![UserInput::Text {
+                text: "{{model.display_name}}, {{ model.context_window }} tokens, \
+                       {{model.effort}} effort{{model.vendor}}, {{other}}"
+                    .into(),
+            }],
+        })
+        .await?;
+    let warning = wait_for_event_match(&codex, |event| match event {
+        EventMsg::Warning(warning) => Some(warning.message.clone()),
+        _ => None,
+    })
+    .await;
+    wait_for_event(&codex, |event| matches!(event, EventMsg::TaskComplete(_))).await;
+
+    assert_eq!(
+        warning,
+        "Unknown prompt variable(s) {{model.vendor}} rendered empty."
+    );
+    let texts = response.single_request().message_input_texts("user");
+    assert_eq!(
+        texts.last().map(String::as_str),
+        Some("Codex Max, 272000 tokens, high effort, {{other}}")
+    );
+
+    Ok(())
+}
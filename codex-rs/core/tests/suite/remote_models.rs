@@ -7,6 +7,7 @@ use codex_core::CodexAuth;
 use codex_core::CodexConversation;
 use codex_core::ConversationManager;
 use codex_core::ModelProviderInfo;
+use codex_core::WireApi;
 use codex_core::built_in_model_providers;
 use codex_core::config::Config;
 use codex_core::features::Feature;
@@ -14,6 +15,7 @@ use codex_core::models_manager::manager::ModelsManager;
 use codex_core::protocol::AskForApproval;
 use codex_core::protocol::EventMsg;
 use codex_core::protocol::ExecCommandSource;
+use codex_core::protocol::ModelChangeSource;
 use codex_core::protocol::Op;
 use codex_core::protocol::SandboxPolicy;
 use codex_protocol::config_types::ReasoningSummary;
@@ -22,6 +24,7 @@ use codex_protocol::openai_models::ModelInfo;
 use codex_protocol::openai_models::ModelPreset;
 use codex_protocol::openai_models::ModelVisibility;
 use codex_protocol::openai_models::ModelsResponse;
+use codex_protocol::openai_models::PromptCacheKeyStrategy;
 use codex_protocol::openai_models::ReasoningEffort;
 use codex_protocol::openai_models::ReasoningEffortPreset;
 use codex_protocol::openai_models::TruncationPolicyConfig;
@@ -31,7 +34,10 @@ use core_test_support::responses::ev_assistant_message;
 use core_test_support::responses::ev_completed;
 use core_test_support::responses::ev_function_call;
 use core_test_support::responses::ev_response_created;
+use core_test_support::responses::get_responses_request_bodies;
 use core_test_support::responses::mount_models_once;
+use core_test_support::responses::mount_response_once;
+use core_test_support::responses::mount_response_once_match;
 use core_test_support::responses::mount_sse_once;
 use core_test_support::responses::mount_sse_sequence;
 use core_test_support::responses::sse;
@@ -40,6 +46,7 @@ use core_test_support::skip_if_sandbox;
 use core_test_support::wait_for_event;
 use core_test_support::wait_for_event_match;
 use pretty_assertions::assert_eq;
+use serde_json::Value;
 use serde_json::json;
 use tempfile::TempDir;
 use tokio::time::Duration;
@@ -47,6 +54,8 @@ use tokio::time::Instant;
 use tokio::time::sleep;
 use wiremock::BodyPrintLimit;
 use wiremock::MockServer;
+use wiremock::ResponseTemplate;
+use wiremock::matchers::body_partial_json;
 
 const REMOTE_MODEL_SLUG: &str = "codex-test";
 
@@ -62,33 +71,63 @@ async fn remote_models_remote_model_uses_unified_exec() -> Result<()> {
 
     let remote_model = ModelInfo {
         slug: REMOTE_MODEL_SLUG.to_string(),
+        preset_id: None,
         display_name: "Remote Test".to_string(),
         description: Some("A remote model that requires the test shell".to_string()),
         default_reasoning_level: ReasoningEffort::Medium,
         supported_reasoning_levels: vec![ReasoningEffortPreset {
             effort: ReasoningEffort::Medium,
             description: ReasoningEffort::Medium.to_string(),
+            max_output_tokens: None,
+            request_timeout_secs: None,
+            stream_idle_timeout_secs: None,
         }],
         shell_type: ConfigShellToolType::UnifiedExec,
         visibility: ModelVisibility::List,
+        is_default: false,
         supported_in_api: true,
         priority: 1,
         upgrade: None,
         base_instructions: None,
+        instructions_addendum: None,
+        wire_api: None,
         supports_reasoning_summaries: false,
+        supports_reasoning: None,
+        reasoning_summary_format: None,
+        supports_stateful_responses: None,
         support_verbosity: false,
         default_verbosity: None,
         apply_patch_tool_type: None,
         truncation_policy: TruncationPolicyConfig::bytes(10_000),
         supports_parallel_tool_calls: false,
         context_window: None,
+        context_window_unit: None,
+        context_window_source: None,
+        max_output_tokens: None,
+        supports_temperature: None,
+        default_temperature: None,
+        supports_top_p: None,
+        default_top_p: None,
+        max_concurrent_requests: None,
+        request_headers: None,
+        prompt_cache_key_strategy: None,
+        fallback_models: None,
+        disabled_tools: Vec::new(),
+        prefer_apply_patch_freeform: None,
+        enable_web_search: None,
+        knowledge_cutoff: None,
+        released_at: None,
         experimental_supported_tools: Vec::new(),
+        extra: Default::default(),
+        supports_images: true,
+        requires_network: false,
     };
 
     let models_mock = mount_models_once(
         &server,
         ModelsResponse {
             models: vec![remote_model],
+            min_client_version: None,
         },
     )
     .await;
@@ -132,6 +171,7 @@ async fn remote_models_remote_model_uses_unified_exec() -> Result<()> {
             approval_policy: None,
             sandbox_policy: None,
             model: Some(REMOTE_MODEL_SLUG.to_string()),
+            model_provider: None,
             effort: None,
             summary: None,
         })
@@ -199,32 +239,62 @@ async fn remote_models_apply_remote_base_instructions() -> Result<()> {
     let remote_base = "Use the remote base instructions only.";
     let remote_model = ModelInfo {
         slug: model.to_string(),
+        preset_id: None,
         display_name: "Parallel Remote".to_string(),
         description: Some("A remote model with custom instructions".to_string()),
         default_reasoning_level: ReasoningEffort::Medium,
         supported_reasoning_levels: vec![ReasoningEffortPreset {
             effort: ReasoningEffort::Medium,
             description: ReasoningEffort::Medium.to_string(),
+            max_output_tokens: None,
+            request_timeout_secs: None,
+            stream_idle_timeout_secs: None,
         }],
         shell_type: ConfigShellToolType::ShellCommand,
         visibility: ModelVisibility::List,
+        is_default: false,
         supported_in_api: true,
         priority: 1,
         upgrade: None,
         base_instructions: Some(remote_base.to_string()),
+        instructions_addendum: None,
+        wire_api: None,
         supports_reasoning_summaries: false,
+        supports_reasoning: None,
+        reasoning_summary_format: None,
+        supports_stateful_responses: None,
         support_verbosity: false,
         default_verbosity: None,
         apply_patch_tool_type: None,
         truncation_policy: TruncationPolicyConfig::bytes(10_000),
         supports_parallel_tool_calls: false,
         context_window: None,
+        context_window_unit: None,
+        context_window_source: None,
+        max_output_tokens: None,
+        supports_temperature: None,
+        default_temperature: None,
+        supports_top_p: None,
+        default_top_p: None,
+        max_concurrent_requests: None,
+        request_headers: None,
+        prompt_cache_key_strategy: None,
+        fallback_models: None,
+        disabled_tools: Vec::new(),
+        prefer_apply_patch_freeform: None,
+        enable_web_search: None,
+        knowledge_cutoff: None,
+        released_at: None,
         experimental_supported_tools: Vec::new(),
+        extra: Default::default(),
+        supports_images: true,
+        requires_network: false,
     };
     mount_models_once(
         &server,
         ModelsResponse {
             models: vec![remote_model],
+            min_client_version: None,
         },
     )
     .await;
@@ -262,6 +332,7 @@ async fn remote_models_apply_remote_base_instructions() -> Result<()> {
             approval_policy: None,
             sandbox_policy: None,
             model: Some(model.to_string()),
+            model_provider: None,
             effort: None,
             summary: None,
         })
@@ -291,6 +362,613 @@ async fn remote_models_apply_remote_base_instructions() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn remote_models_swap_instructions_addendum_mid_session() -> Result<()> {
+    skip_if_no_network!(Ok(()));
+    skip_if_sandbox!(Ok(()));
+
+    let server = MockServer::builder()
+        .body_print_limit(BodyPrintLimit::Limited(80_000))
+        .start()
+        .await;
+
+    let model = "test-gpt-5-addendum";
+    let addendum = "Respond in the internal citation format.";
+    let mut remote_model = test_remote_model(model, ModelVisibility::List, 1);
+    remote_model.instructions_addendum = Some(addendum.to_string());
+    mount_models_once(
+        &server,
+        ModelsResponse {
+            models: vec![remote_model],
+            min_client_version: None,
+        },
+    )
+    .await;
+
+    let response_mock = mount_sse_sequence(
+        &server,
+        (1..=3)
+            .map(|turn| {
+                sse(vec![
+                    ev_response_created(&format!("resp-{turn}")),
+                    ev_assistant_message(&format!("msg-{turn}"), "done"),
+                    ev_completed(&format!("resp-{turn}")),
+                ])
+            })
+            .collect(),
+    )
+    .await;
+
+    let harness = build_remote_models_harness(&server, |config| {
+        config.features.enable(Feature::RemoteModels);
+        config.model = Some("gpt-5.1".to_string());
+    })
+    .await?;
+
+    let RemoteModelsHarness {
+        codex,
+        cwd,
+        config,
+        conversation_manager,
+        ..
+    } = harness;
+
+    let models_manager = conversation_manager.get_models_manager();
+    wait_for_model_available(&models_manager, model, &config).await;
+
+    for turn_model in ["gpt-5.1", model, "gpt-5.1"] {
+        codex
+            .submit(Op::OverrideTurnContext {
+                cwd: None,
+                approval_policy: None,
+                sandbox_policy: None,
+                model: Some(turn_model.to_string()),
+                model_provider: None,
+                effort: None,
+                summary: None,
+            })
+            .await?;
+
+        codex
+            .submit(Op::UserTurn {
+                items: vec![UserInput::Text {
+                    text: format!("hello from {turn_model}"),
+                }],
+                final_output_json_schema: None,
+                cwd: cwd.path().to_path_buf(),
+                approval_policy: AskForApproval::Never,
+                sandbox_policy: SandboxPolicy::DangerFullAccess,
+                model: turn_model.to_string(),
+                effort: None,
+                summary: ReasoningSummary::Auto,
+            })
+            .await?;
+
+        wait_for_event(&codex, |event| matches!(event, EventMsg::TaskComplete(_))).await;
+    }
+
+    let has_addendum: Vec<bool> = response_mock
+        .requests()
+        .iter()
+        .map(|request| {
+            request.body_json()["instructions"]
+                .as_str()
+                .is_some_and(|instructions| instructions.ends_with(addendum))
+        })
+        .collect();
+    assert_eq!(has_addendum, vec![false, true, false]);
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn remote_models_prompt_cache_key_follows_the_strategy() -> Result<()> {
+    skip_if_no_network!(Ok(()));
+    skip_if_sandbox!(Ok(()));
+
+    let server = MockServer::builder()
+        .body_print_limit(BodyPrintLimit::Limited(80_000))
+        .start()
+        .await;
+
+    let mut no_key = test_remote_model("test-cache-none", ModelVisibility::List, 1);
+    no_key.prompt_cache_key_strategy = Some(PromptCacheKeyStrategy::None);
+    let mut per_user = test_remote_model("test-cache-per-user", ModelVisibility::List, 2);
+    per_user.prompt_cache_key_strategy = Some(PromptCacheKeyStrategy::PerUser);
+    mount_models_once(
+        &server,
+        ModelsResponse {
+            models: vec![no_key, per_user],
+            min_client_version: None,
+        },
+    )
+    .await;
+
+    let turn_models = [
+        "gpt-5.1",
+        "test-cache-none",
+        "test-cache-per-user",
+        "gpt-5.1",
+    ];
+    let response_mock = mount_sse_sequence(
+        &server,
+        (1..=turn_models.len())
+            .map(|turn| {
+                sse(vec![
+                    ev_response_created(&format!("resp-{turn}")),
+                    ev_assistant_message(&format!("msg-{turn}"), "done"),
+                    ev_completed(&format!("resp-{turn}")),
+                ])
+            })
+            .collect(),
+    )
+    .await;
+
+    let harness = build_remote_models_harness(&server, |config| {
+        config.features.enable(Feature::RemoteModels);
+        config.model = Some("gpt-5.1".to_string());
+    })
+    .await?;
+
+    let RemoteModelsHarness {
+        codex,
+        cwd,
+        config,
+        conversation_manager,
+        ..
+    } = harness;
+
+    let models_manager = conversation_manager.get_models_manager();
+    wait_for_model_available(&models_manager, "test-cache-per-user", &config).await;
+
+    for turn_model in turn_models {
+        codex
+            .submit(Op::OverrideTurnContext {
+                cwd: None,
+                approval_policy: None,
+                sandbox_policy: None,
+                model: Some(turn_model.to_string()),
+                model_provider: None,
+                effort: None,
+                summary: None,
+            })
+            .await?;
+
+        codex
+            .submit(Op::UserTurn {
+                items: vec![UserInput::Text {
+                    text: format!("hello from {turn_model}"),
+                }],
+                final_output_json_schema: None,
+                cwd: cwd.path().to_path_buf(),
+                approval_policy: AskForApproval::Never,
+                sandbox_policy: SandboxPolicy::DangerFullAccess,
+                model: turn_model.to_string(),
+                effort: None,
+                summary: ReasoningSummary::Auto,
+            })
+            .await?;
+
+        wait_for_event(&codex, |event| matches!(event, EventMsg::TaskComplete(_))).await;
+    }
+
+    let keys: Vec<Option<String>> = response_mock
+        .requests()
+        .iter()
+        .map(|request| {
+            request
+                .body_json()
+                .get("prompt_cache_key")
+                .and_then(Value::as_str)
+                .map(str::to_string)
+        })
+        .collect();
+    let [conversation_key, no_key, user_key, back_to_conversation] = keys.as_slice() else {
+        panic!("expected one request per turn, got {keys:?}");
+    };
+
+    let conversation_key = conversation_key
+        .as_deref()
+        .expect("the default strategy keys by conversation");
+    assert_eq!(no_key, &None, "the `none` strategy sends no key");
+    let user_key = user_key
+        .as_deref()
+        .expect("the `per_user` strategy sends a key");
+    assert!(
+        user_key.starts_with("user-") && user_key != conversation_key,
+        "the per-user key must not reuse the conversation key: {user_key}"
+    );
+    assert_eq!(back_to_conversation.as_deref(), Some(conversation_key));
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn remote_models_rebuild_stateless_context_after_a_switch() -> Result<()> {
+    skip_if_no_network!(Ok(()));
+    skip_if_sandbox!(Ok(()));
+
+    let server = MockServer::builder()
+        .body_print_limit(BodyPrintLimit::Limited(80_000))
+        .start()
+        .await;
+
+    let mut stateful = test_remote_model("test-stateful", ModelVisibility::List, 1);
+    stateful.supports_stateful_responses = Some(true);
+    let mut stateless = test_remote_model("test-stateless", ModelVisibility::List, 2);
+    stateless.supports_stateful_responses = Some(false);
+    mount_models_once(
+        &server,
+        ModelsResponse {
+            models: vec![stateful, stateless],
+            min_client_version: None,
+        },
+    )
+    .await;
+
+    let turn_models = ["test-stateful", "test-stateless", "test-stateful"];
+    let response_mock = mount_sse_sequence(
+        &server,
+        (1..=turn_models.len())
+            .map(|turn| {
+                let mut events = vec![ev_response_created(&format!("resp-{turn}"))];
+                if turn == 1 {
+                    // Reasoning the server keeps only in its store.
+                    events.push(json!({
+                        "type": "response.output_item.done",
+                        "item": {"type": "reasoning", "id": "rs-1", "summary": []}
+                    }));
+                }
+                events.push(ev_assistant_message(&format!("msg-{turn}"), "done"));
+                events.push(ev_completed(&format!("resp-{turn}")));
+                sse(events)
+            })
+            .collect(),
+    )
+    .await;
+
+    let harness = build_remote_models_harness(&server, |config| {
+        config.features.enable(Feature::RemoteModels);
+        config.model = Some("gpt-5.1".to_string());
+    })
+    .await?;
+
+    let RemoteModelsHarness {
+        codex,
+        cwd,
+        config,
+        conversation_manager,
+        ..
+    } = harness;
+
+    let models_manager = conversation_manager.get_models_manager();
+    wait_for_model_available(&models_manager, "test-stateless", &config).await;
+
+    for turn_model in turn_models {
+        codex
+            .submit(Op::OverrideTurnContext {
+                cwd: None,
+                approval_policy: None,
+                sandbox_policy: None,
+                model: Some(turn_model.to_string()),
+                model_provider: None,
+                effort: None,
+                summary: None,
+            })
+            .await?;
+        submit_user_turn(
+            &codex,
+            &cwd,
+            turn_model,
+            &format!("hello from {turn_model}"),
+        )
+        .await?;
+        wait_for_event(&codex, |event| matches!(event, EventMsg::TaskComplete(_))).await;
+    }
+
+    // Each request lists its store flag, then the history items from earlier
+    // turns by type and id.
+    let shapes: Vec<(bool, Vec<(String, Option<String>)>)> = response_mock
+        .requests()
+        .iter()
+        .map(|request| {
+            let body = request.body_json();
+            let items = body["input"]
+                .as_array()
+                .into_iter()
+                .flatten()
+                .filter(|item| item["type"] != "message" || item["role"] == "assistant")
+                .map(|item| {
+                    (
+                        item["type"].as_str().unwrap_or_default().to_string(),
+                        item.get("id").and_then(Value::as_str).map(str::to_string),
+                    )
+                })
+                .collect();
+            (body["store"] == Value::Bool(true), items)
+        })
+        .collect();
+    let item = |kind: &str, id: Option<&str>| (kind.to_string(), id.map(str::to_string));
+    assert_eq!(
+        shapes,
+        vec![
+            (true, vec![]),
+            (false, vec![item("message", None)]),
+            (
+                true,
+                vec![
+                    item("reasoning", Some("rs-1")),
+                    item("message", Some("msg-1")),
+                    item("message", Some("msg-2")),
+                ]
+            ),
+        ]
+    );
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn remote_models_capacity_error_retries_once_on_the_fallback() -> Result<()> {
+    skip_if_no_network!(Ok(()));
+    skip_if_sandbox!(Ok(()));
+
+    let server = MockServer::builder()
+        .body_print_limit(BodyPrintLimit::Limited(80_000))
+        .start()
+        .await;
+
+    let mut primary = test_remote_model("test-primary", ModelVisibility::List, 1);
+    primary.fallback_models = Some(vec![
+        "test-not-in-catalog".to_string(),
+        "test-fallback".to_string(),
+    ]);
+    let fallback = test_remote_model("test-fallback", ModelVisibility::List, 2);
+    mount_models_once(
+        &server,
+        ModelsResponse {
+            models: vec![primary, fallback],
+            min_client_version: None,
+        },
+    )
+    .await;
+
+    mount_response_once_match(
+        &server,
+        body_partial_json(json!({"model": "test-primary"})),
+        at_capacity(503),
+    )
+    .await;
+    mount_sse_sequence(
+        &server,
+        (1..=2)
+            .map(|turn| {
+                sse(vec![
+                    ev_response_created(&format!("resp-{turn}")),
+                    ev_assistant_message(&format!("msg-{turn}"), "done"),
+                    ev_completed(&format!("resp-{turn}")),
+                ])
+            })
+            .collect(),
+    )
+    .await;
+
+    let RemoteModelsHarness {
+        codex,
+        cwd,
+        config,
+        conversation_manager,
+    } = build_remote_models_harness(&server, |config| {
+        config.model = Some("test-primary".to_string());
+        config.model_provider.request_max_retries = Some(0);
+    })
+    .await?;
+    let models_manager = conversation_manager.get_models_manager();
+    wait_for_model_available(&models_manager, "test-fallback", &config).await;
+
+    submit_user_turn(&codex, &cwd, "test-primary", "first turn").await?;
+    let changed = wait_for_event_match(&codex, |event| match event {
+        EventMsg::ModelChanged(event) => Some(event.clone()),
+        _ => None,
+    })
+    .await;
+    assert_eq!(
+        (
+            changed.previous_model.as_str(),
+            changed.model.as_str(),
+            changed.source
+        ),
+        ("test-primary", "test-fallback", ModelChangeSource::Fallback)
+    );
+    wait_for_event(&codex, |event| matches!(event, EventMsg::TaskComplete(_))).await;
+
+    submit_user_turn(&codex, &cwd, "test-primary", "second turn").await?;
+    let next = wait_for_event(&codex, |event| {
+        matches!(event, EventMsg::ModelChanged(_) | EventMsg::TaskComplete(_))
+    })
+    .await;
+    assert!(
+        matches!(next, EventMsg::TaskComplete(_)),
+        "the next turn runs on the primary model: {next:?}"
+    );
+
+    let models: Vec<String> = get_responses_request_bodies(&server)
+        .await
+        .iter()
+        .map(|body| body["model"].as_str().unwrap_or_default().to_string())
+        .collect();
+    assert_eq!(
+        models,
+        vec!["test-primary", "test-fallback", "test-primary"]
+    );
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn remote_models_other_errors_never_switch_to_the_fallback() -> Result<()> {
+    skip_if_no_network!(Ok(()));
+    skip_if_sandbox!(Ok(()));
+
+    let server = MockServer::builder()
+        .body_print_limit(BodyPrintLimit::Limited(80_000))
+        .start()
+        .await;
+
+    let mut primary = test_remote_model("test-primary", ModelVisibility::List, 1);
+    primary.fallback_models = Some(vec!["test-fallback".to_string()]);
+    let fallback = test_remote_model("test-fallback", ModelVisibility::List, 2);
+    mount_models_once(
+        &server,
+        ModelsResponse {
+            models: vec![primary, fallback],
+            min_client_version: None,
+        },
+    )
+    .await;
+
+    mount_response_once(&server, at_capacity(502)).await;
+    mount_sse_once(
+        &server,
+        sse(vec![
+            ev_response_created("resp-1"),
+            ev_assistant_message("msg-1", "done"),
+            ev_completed("resp-1"),
+        ]),
+    )
+    .await;
+
+    let RemoteModelsHarness {
+        codex,
+        cwd,
+        config,
+        conversation_manager,
+    } = build_remote_models_harness(&server, |config| {
+        config.model = Some("test-primary".to_string());
+        config.model_provider.request_max_retries = Some(0);
+        config.model_provider.stream_max_retries = Some(1);
+    })
+    .await?;
+    let models_manager = conversation_manager.get_models_manager();
+    wait_for_model_available(&models_manager, "test-fallback", &config).await;
+
+    submit_user_turn(&codex, &cwd, "test-primary", "first turn").await?;
+    let next = wait_for_event(&codex, |event| {
+        matches!(event, EventMsg::ModelChanged(_) | EventMsg::TaskComplete(_))
+    })
+    .await;
+    assert!(
+        matches!(next, EventMsg::TaskComplete(_)),
+        "a 502 is retried on the same model: {next:?}"
+    );
+
+    let models: Vec<String> = get_responses_request_bodies(&server)
+        .await
+        .iter()
+        .map(|body| body["model"].as_str().unwrap_or_default().to_string())
+        .collect();
+    assert_eq!(models, vec!["test-primary", "test-primary"]);
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn remote_models_wire_api_overrides_provider_default() -> Result<()> {
+    skip_if_no_network!(Ok(()));
+    skip_if_sandbox!(Ok(()));
+
+    let server = MockServer::start().await;
+
+    let model = "test-responses-only";
+    let mut remote_model = test_remote_model(model, ModelVisibility::List, 1);
+    remote_model.wire_api = Some(WireApi::Responses);
+    mount_models_once(
+        &server,
+        ModelsResponse {
+            models: vec![remote_model],
+            min_client_version: None,
+        },
+    )
+    .await;
+
+    let response_mock = mount_sse_once(
+        &server,
+        sse(vec![
+            ev_response_created("resp-1"),
+            ev_assistant_message("msg-1", "done"),
+            ev_completed("resp-1"),
+        ]),
+    )
+    .await;
+
+    let harness = build_remote_models_harness(&server, |config| {
+        config.model = Some(model.to_string());
+        config.model_provider.wire_api = WireApi::Chat;
+        config.model_provider.supported_wire_apis = Some(vec![WireApi::Chat, WireApi::Responses]);
+    })
+    .await?;
+
+    let RemoteModelsHarness { codex, cwd, .. } = harness;
+
+    codex
+        .submit(Op::UserTurn {
+            items: vec![UserInput::Text {
+                text: "hello gateway".into(),
+            }],
+            final_output_json_schema: None,
+            cwd: cwd.path().to_path_buf(),
+            approval_policy: AskForApproval::Never,
+            sandbox_policy: SandboxPolicy::DangerFullAccess,
+            model: model.to_string(),
+            effort: None,
+            summary: ReasoningSummary::Auto,
+        })
+        .await?;
+
+    wait_for_event(&codex, |event| matches!(event, EventMsg::TaskComplete(_))).await;
+
+    let body = response_mock.single_request().body_json();
+    assert_eq!(body["model"].as_str(), Some(model));
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn remote_models_wire_api_mismatch_fails_session_start() -> Result<()> {
+    skip_if_no_network!(Ok(()));
+    skip_if_sandbox!(Ok(()));
+
+    let server = MockServer::start().await;
+
+    let model = "test-chat-only";
+    let mut remote_model = test_remote_model(model, ModelVisibility::List, 1);
+    remote_model.wire_api = Some(WireApi::Chat);
+    mount_models_once(
+        &server,
+        ModelsResponse {
+            models: vec![remote_model],
+            min_client_version: None,
+        },
+    )
+    .await;
+
+    let result = build_remote_models_harness(&server, |config| {
+        config.model = Some(model.to_string());
+    })
+    .await;
+
+    let Err(err) = result else {
+        panic!("session start should fail when the provider cannot serve the model's wire API");
+    };
+    assert!(
+        err.to_string()
+            .contains("model `test-chat-only` requires the `chat` wire API"),
+        "unexpected error: {err}"
+    );
+
+    Ok(())
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
 async fn remote_models_preserve_builtin_presets() -> Result<()> {
     skip_if_no_network!(Ok(()));
@@ -302,6 +980,7 @@ async fn remote_models_preserve_builtin_presets() -> Result<()> {
         &server,
         ModelsResponse {
             models: vec![remote_model.clone()],
+            min_client_version: None,
         },
     )
     .await;
@@ -359,6 +1038,7 @@ async fn remote_models_hide_picker_only_models() -> Result<()> {
         &server,
         ModelsResponse {
             models: vec![remote_model],
+            min_client_version: None,
         },
     )
     .await;
@@ -391,6 +1071,235 @@ async fn remote_models_hide_picker_only_models() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn remote_models_parallel_tool_calls_follow_the_preset() -> Result<()> {
+    skip_if_no_network!(Ok(()));
+    skip_if_sandbox!(Ok(()));
+
+    let server = MockServer::builder()
+        .body_print_limit(BodyPrintLimit::Limited(80_000))
+        .start()
+        .await;
+
+    let remote_model = |slug: &str, supports_parallel_tool_calls: bool| ModelInfo {
+        supports_parallel_tool_calls,
+        experimental_supported_tools: vec!["test_sync_tool".to_string()],
+        ..test_remote_model(slug, ModelVisibility::List, 1)
+    };
+    mount_models_once(
+        &server,
+        ModelsResponse {
+            models: vec![
+                remote_model("oca-serial", false),
+                remote_model("oca-parallel", true),
+            ],
+            min_client_version: None,
+        },
+    )
+    .await;
+
+    let sleep_args = json!({ "sleep_after_ms": 400 }).to_string();
+    let two_sleeps = |id: &str| {
+        sse(vec![
+            ev_response_created(id),
+            ev_function_call(&format!("{id}-call-1"), "test_sync_tool", &sleep_args),
+            ev_function_call(&format!("{id}-call-2"), "test_sync_tool", &sleep_args),
+            ev_completed(id),
+        ])
+    };
+    let done = |id: &str| {
+        sse(vec![
+            ev_response_created(id),
+            ev_assistant_message(&format!("{id}-msg"), "done"),
+            ev_completed(id),
+        ])
+    };
+    let response_mock = mount_sse_sequence(
+        &server,
+        vec![
+            two_sleeps("resp-1"),
+            done("resp-2"),
+            two_sleeps("resp-3"),
+            done("resp-4"),
+        ],
+    )
+    .await;
+
+    let RemoteModelsHarness {
+        codex,
+        cwd,
+        config,
+        conversation_manager,
+    } = build_remote_models_harness(&server, |config| {
+        config.model = Some("gpt-5.1".to_string());
+    })
+    .await?;
+    let models_manager = conversation_manager.get_models_manager();
+    wait_for_model_available(&models_manager, "oca-serial", &config).await;
+
+    let mut durations = Vec::new();
+    for model in ["oca-serial", "oca-parallel"] {
+        let started = Instant::now();
+        codex
+            .submit(Op::UserTurn {
+                items: vec![UserInput::Text {
+                    text: "sleep twice".into(),
+                }],
+                final_output_json_schema: None,
+                cwd: cwd.path().to_path_buf(),
+                approval_policy: AskForApproval::Never,
+                sandbox_policy: SandboxPolicy::DangerFullAccess,
+                model: model.to_string(),
+                effort: None,
+                summary: ReasoningSummary::Auto,
+            })
+            .await?;
+        wait_for_event(&codex, |event| matches!(event, EventMsg::TaskComplete(_))).await;
+        durations.push(started.elapsed());
+    }
+
+    let parallel_flags: Vec<Value> = response_mock
+        .requests()
+        .iter()
+        .map(|request| request.body_json()["parallel_tool_calls"].clone())
+        .collect();
+    assert_eq!(
+        parallel_flags,
+        vec![json!(false), json!(false), json!(true), json!(true)]
+    );
+    assert!(
+        durations[0] >= Duration::from_millis(800),
+        "calls for the serial preset should run one at a time, took {:?}",
+        durations[0]
+    );
+    assert!(
+        durations[1] < Duration::from_millis(750),
+        "calls for the parallel preset should overlap, took {:?}",
+        durations[1]
+    );
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn remote_models_without_reasoning_ignore_the_effort_once() -> Result<()> {
+    skip_if_no_network!(Ok(()));
+    skip_if_sandbox!(Ok(()));
+
+    let server = MockServer::builder()
+        .body_print_limit(BodyPrintLimit::Limited(80_000))
+        .start()
+        .await;
+
+    let plain_chat = ModelInfo {
+        supported_reasoning_levels: Vec::new(),
+        supports_reasoning_summaries: true,
+        supports_reasoning: Some(false),
+        ..test_remote_model("oca-plain-chat", ModelVisibility::List, 1)
+    };
+    mount_models_once(
+        &server,
+        ModelsResponse {
+            models: vec![plain_chat],
+            min_client_version: None,
+        },
+    )
+    .await;
+
+    let turn_models = ["gpt-5.1", "oca-plain-chat", "oca-plain-chat"];
+    let response_mock = mount_sse_sequence(
+        &server,
+        (1..=turn_models.len())
+            .map(|turn| {
+                sse(vec![
+                    ev_response_created(&format!("resp-{turn}")),
+                    ev_assistant_message(&format!("msg-{turn}"), "done"),
+                    ev_completed(&format!("resp-{turn}")),
+                ])
+            })
+            .collect(),
+    )
+    .await;
+
+    let RemoteModelsHarness {
+        codex,
+        cwd,
+        config,
+        conversation_manager,
+    } = build_remote_models_harness(&server, |config| {
+        config.model = Some("gpt-5.1".to_string());
+        config.model_reasoning_effort = Some(ReasoningEffort::High);
+    })
+    .await?;
+    let models_manager = conversation_manager.get_models_manager();
+    wait_for_model_available(&models_manager, "oca-plain-chat", &config).await;
+
+    let mut notices = Vec::new();
+    for (turn, model) in turn_models.into_iter().enumerate() {
+        if turn == 1 {
+            codex
+                .submit(Op::OverrideTurnContext {
+                    cwd: None,
+                    approval_policy: None,
+                    sandbox_policy: None,
+                    model: Some(model.to_string()),
+                    model_provider: None,
+                    effort: Some(Some(ReasoningEffort::High)),
+                    summary: None,
+                })
+                .await?;
+        }
+        codex
+            .submit(Op::UserTurn {
+                items: vec![UserInput::Text {
+                    text: format!("hello from {model}"),
+                }],
+                final_output_json_schema: None,
+                cwd: cwd.path().to_path_buf(),
+                approval_policy: AskForApproval::Never,
+                sandbox_policy: SandboxPolicy::DangerFullAccess,
+                model: model.to_string(),
+                effort: Some(ReasoningEffort::High),
+                summary: ReasoningSummary::Auto,
+            })
+            .await?;
+        loop {
+            match codex.next_event().await?.msg {
+                EventMsg::Warning(warning) if warning.message.contains("reasoning effort") => {
+                    notices.push(warning.message);
+                }
+                EventMsg::TaskComplete(_) => break,
+                _ => {}
+            }
+        }
+    }
+
+    assert_eq!(
+        notices,
+        vec![
+            "`oca-plain-chat` does not take a reasoning effort; ignoring the configured `high` effort."
+                .to_string()
+        ]
+    );
+    let bodies: Vec<Value> = response_mock
+        .requests()
+        .iter()
+        .map(|request| request.body_json())
+        .collect();
+    assert_eq!(bodies.len(), turn_models.len());
+    assert_eq!(bodies[0]["reasoning"]["effort"], json!("high"));
+    for body in &bodies[1..] {
+        assert_eq!(
+            body.get("reasoning"),
+            None,
+            "plain chat models get no reasoning"
+        );
+        assert_eq!(body["include"], json!([]));
+    }
+
+    Ok(())
+}
+
 async fn wait_for_model_available(
     manager: &Arc<ModelsManager>,
     slug: &str,
@@ -411,6 +1320,37 @@ async fn wait_for_model_available(
     }
 }
 
+fn at_capacity(status: u16) -> ResponseTemplate {
+    ResponseTemplate::new(status)
+        .insert_header("content-type", "application/json")
+        .set_body_json(json!({
+            "error": {"type": "server_error", "message": "the model is at capacity"}
+        }))
+}
+
+async fn submit_user_turn(
+    codex: &CodexConversation,
+    cwd: &TempDir,
+    model: &str,
+    text: &str,
+) -> Result<()> {
+    codex
+        .submit(Op::UserTurn {
+            items: vec![UserInput::Text {
+                text: text.to_string(),
+            }],
+            final_output_json_schema: None,
+            cwd: cwd.path().to_path_buf(),
+            approval_policy: AskForApproval::Never,
+            sandbox_policy: SandboxPolicy::DangerFullAccess,
+            model: model.to_string(),
+            effort: None,
+            summary: ReasoningSummary::Auto,
+        })
+        .await?;
+    Ok(())
+}
+
 struct RemoteModelsHarness {
     codex: Arc<CodexConversation>,
     cwd: Arc<TempDir>,
@@ -459,26 +1399,55 @@ where
 fn test_remote_model(slug: &str, visibility: ModelVisibility, priority: i32) -> ModelInfo {
     ModelInfo {
         slug: slug.to_string(),
+        preset_id: None,
         display_name: format!("{slug} display"),
         description: Some(format!("{slug} description")),
         default_reasoning_level: ReasoningEffort::Medium,
         supported_reasoning_levels: vec![ReasoningEffortPreset {
             effort: ReasoningEffort::Medium,
             description: ReasoningEffort::Medium.to_string(),
+            max_output_tokens: None,
+            request_timeout_secs: None,
+            stream_idle_timeout_secs: None,
         }],
         shell_type: ConfigShellToolType::ShellCommand,
         visibility,
+        is_default: false,
         supported_in_api: true,
         priority,
         upgrade: None,
         base_instructions: None,
+        instructions_addendum: None,
+        wire_api: None,
         supports_reasoning_summaries: false,
+        supports_reasoning: None,
+        reasoning_summary_format: None,
+        supports_stateful_responses: None,
         support_verbosity: false,
         default_verbosity: None,
         apply_patch_tool_type: None,
         truncation_policy: TruncationPolicyConfig::bytes(10_000),
         supports_parallel_tool_calls: false,
         context_window: None,
+        context_window_unit: None,
+        context_window_source: None,
+        max_output_tokens: None,
+        supports_temperature: None,
+        default_temperature: None,
+        supports_top_p: None,
+        default_top_p: None,
+        max_concurrent_requests: None,
+        request_headers: None,
+        prompt_cache_key_strategy: None,
+        fallback_models: None,
+        disabled_tools: Vec::new(),
+        prefer_apply_patch_freeform: None,
+        enable_web_search: None,
+        knowledge_cutoff: None,
+        released_at: None,
         experimental_supported_tools: Vec::new(),
+        extra: Default::default(),
+        supports_images: true,
+        requires_network: false,
     }
 }
@@ -33,6 +33,8 @@ fn resume_history(
         developer_instructions: None,
         final_output_json_schema: None,
         truncation_policy: None,
+        preset: None,
+        effort_description: None,
     };
 
     InitialHistory::Resumed(ResumedHistory {
@@ -418,7 +418,8 @@ async fn review_uses_custom_review_model_from_config() {
         matches!(
             ev,
             EventMsg::ExitedReviewMode(ExitedReviewModeEvent {
-                review_output: None
+                review_output: None,
+                ..
             })
         )
     })
@@ -436,6 +437,55 @@ async fn review_uses_custom_review_model_from_config() {
     server.verify().await;
 }
 
+/// A `review_model_preset` takes precedence over `review_model`: the review
+/// runs on the preset's model at its default effort, and the exit event names
+/// that model.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn review_uses_review_model_preset_and_reports_it() {
+    skip_if_no_network!();
+
+    let sse_raw = r#"[
+        {"type":"response.completed", "response": {"id": "__ID__"}}
+    ]"#;
+    let server = start_responses_server_with_sse(sse_raw, 1).await;
+    let codex_home = TempDir::new().unwrap();
+    let codex = new_conversation_for_server(&server, &codex_home, |cfg| {
+        cfg.model = Some("gpt-4.1".to_string());
+        cfg.review_model = "gpt-5.1".to_string();
+        cfg.review_model_preset = Some("gpt-5.1-codex-mini".to_string());
+    })
+    .await;
+
+    codex
+        .submit(Op::Review {
+            review_request: ReviewRequest {
+                target: ReviewTarget::Custom {
+                    instructions: "use the review preset".to_string(),
+                },
+                user_facing_hint: None,
+            },
+        })
+        .await
+        .unwrap();
+
+    let closed = wait_for_event(&codex, |ev| matches!(ev, EventMsg::ExitedReviewMode(_))).await;
+    let EventMsg::ExitedReviewMode(ExitedReviewModeEvent { model, .. }) = closed else {
+        unreachable!("matched ExitedReviewMode above");
+    };
+    assert_eq!(model.as_deref(), Some("gpt-5.1-codex-mini"));
+    let _complete = wait_for_event(&codex, |ev| matches!(ev, EventMsg::TaskComplete(_))).await;
+
+    let requests = get_responses_requests(&server).await;
+    let request = requests
+        .first()
+        .expect("expected POST request to /responses");
+    let body = request.body_json::<serde_json::Value>().unwrap();
+    assert_eq!(body["model"].as_str().unwrap(), "gpt-5.1-codex-mini");
+    assert_eq!(body["reasoning"]["effort"].as_str(), Some("medium"));
+
+    server.verify().await;
+}
+
 /// When a review session begins, it must not prepend prior chat history from
 /// the parent session. The request `input` should contain only the review
 /// prompt from the user.
@@ -539,7 +589,8 @@ async fn review_input_isolated_from_parent_history() {
         matches!(
             ev,
             EventMsg::ExitedReviewMode(ExitedReviewModeEvent {
-                review_output: None
+                review_output: None,
+                ..
             })
         )
     })
@@ -651,7 +702,8 @@ async fn review_history_surfaces_in_parent_session() {
         matches!(
             ev,
             EventMsg::ExitedReviewMode(ExitedReviewModeEvent {
-                review_output: Some(_)
+                review_output: Some(_),
+                ..
             })
         )
     })
@@ -770,6 +822,7 @@ async fn review_uses_overridden_cwd_for_base_branch_merge_base() {
             approval_policy: None,
             sandbox_policy: None,
             model: None,
+            model_provider: None,
             effort: None,
             summary: None,
         })
@@ -0,0 +1,107 @@
+use std::path::Path;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use codex_core::CodexAuth;
+use codex_core::ConversationManager;
+use codex_core::NewConversation;
+use codex_core::protocol::EventMsg;
+use codex_core::protocol::Op;
+use codex_protocol::openai_models::ReasoningEffort;
+use core_test_support::load_default_config_for_test;
+use core_test_support::wait_for_event;
+use pretty_assertions::assert_eq;
+use serde_json::Value;
+use tempfile::TempDir;
+
+async fn start_session(codex_home: &TempDir, write_status_file: bool) -> NewConversation {
+    let mut config = load_default_config_for_test(codex_home).await;
+    config.model = Some("gpt-5.1-codex-max".to_string());
+    config.model_reasoning_effort = Some(ReasoningEffort::High);
+    config.write_status_file = write_status_file;
+
+    let conversation_manager = ConversationManager::with_models_provider(
+        CodexAuth::from_api_key("Test API Key"),
+        config.model_provider.clone(),
+    );
+    conversation_manager
+        .new_conversation(config)
+        .await
+        .expect("create conversation")
+}
+
+fn status_path(codex_home: &Path, session: &NewConversation) -> PathBuf {
+    codex_home
+        .join("run")
+        .join(format!("{}.json", session.session_configured.session_id))
+}
+
+/// Poll the status file until `done` accepts it.
+async fn wait_for_status(path: &Path, done: impl Fn(&Value) -> bool) -> Value {
+    for _ in 0..100 {
+        if let Ok(contents) = tokio::fs::read(path).await
+            && let Ok(status) = serde_json::from_slice::<Value>(&contents)
+            && done(&status)
+        {
+            return status;
+        }
+        tokio::time::sleep(Duration::from_millis(50)).await;
+    }
+    panic!(
+        "status file {} never reached the expected state",
+        path.display()
+    );
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn status_file_follows_the_session_and_is_removed_on_shutdown() {
+    let codex_home = TempDir::new().unwrap();
+    let session = start_session(&codex_home, true).await;
+    let path = status_path(codex_home.path(), &session);
+
+    let status = wait_for_status(&path, |status| status["model"] == "gpt-5.1-codex-max").await;
+    assert_eq!(status["preset_id"], "gpt-5.1-codex-max");
+    assert_eq!(status["effort"]["effort"], "high");
+    assert!(
+        status["effort"]["description"]
+            .as_str()
+            .is_some_and(|description| !description.is_empty()),
+        "effort should carry the preset's description: {status}"
+    );
+    assert_eq!(status["preset_source"]["kind"], "builtin");
+
+    let codex = session.conversation;
+    codex
+        .submit(Op::OverrideTurnContext {
+            cwd: None,
+            approval_policy: None,
+            sandbox_policy: None,
+            model: None,
+            model_provider: None,
+            effort: Some(Some(ReasoningEffort::Low)),
+            summary: None,
+        })
+        .await
+        .expect("submit override");
+    wait_for_status(&path, |status| status["effort"]["effort"] == "low").await;
+
+    codex.submit(Op::Shutdown).await.expect("request shutdown");
+    wait_for_event(&codex, |ev| matches!(ev, EventMsg::ShutdownComplete)).await;
+
+    assert!(!path.exists(), "status file should be removed on shutdown");
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn no_status_file_unless_enabled() {
+    let codex_home = TempDir::new().unwrap();
+    let session = start_session(&codex_home, false).await;
+
+    let codex = session.conversation;
+    codex.submit(Op::Shutdown).await.expect("request shutdown");
+    wait_for_event(&codex, |ev| matches!(ev, EventMsg::ShutdownComplete)).await;
+
+    assert!(
+        !codex_home.path().join("run").exists(),
+        "nothing should be written without write_status_file"
+    );
+}
@@ -0,0 +1,91 @@
+use codex_core::ModelCatalog;
+use codex_core::default_client::get_codex_user_agent;
+use codex_core::default_client::get_codex_user_agent_with_suffix;
+use codex_core::default_client::sanitize_user_agent_suffix;
+use core_test_support::responses;
+use core_test_support::skip_if_no_network;
+use core_test_support::test_codex::test_codex;
+use pretty_assertions::assert_eq;
+use serde_json::json;
+use wiremock::Mock;
+use wiremock::ResponseTemplate;
+use wiremock::matchers::method;
+use wiremock::matchers::path;
+
+const GATEWAY_PROVIDER_ID: &str = "gateway";
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn suffix_is_sent_on_catalog_and_model_requests() -> anyhow::Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let server = responses::start_mock_server().await;
+    Mock::given(method("GET"))
+        .and(path("/v1/models"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "object": "list",
+            "data": [{"id": "oca-large", "object": "model"}],
+        })))
+        .mount(&server)
+        .await;
+    let turn = responses::mount_sse_once(
+        &server,
+        responses::sse(vec![
+            responses::ev_response_created("resp-1"),
+            responses::ev_completed("resp-1"),
+        ]),
+    )
+    .await;
+
+    let suffix = sanitize_user_agent_suffix(" acme-gateway/1.2 (tenant=\u{e9}) ");
+    let expected = get_codex_user_agent_with_suffix(suffix.as_deref());
+    assert_eq!(
+        expected,
+        format!("{} acme-gateway/1.2 (tenant=_)", get_codex_user_agent())
+    );
+
+    let test = test_codex()
+        .with_model("oca-large")
+        .with_config(|config| {
+            config.model_provider.name = "Gateway".to_string();
+            config.model_provider.model_catalog = Some(ModelCatalog::Openai);
+            config.model_provider_id = GATEWAY_PROVIDER_ID.to_string();
+            config.model_providers.insert(
+                GATEWAY_PROVIDER_ID.to_string(),
+                config.model_provider.clone(),
+            );
+            // Strict validation waits for the catalog before the session starts.
+            config.strict_model_validation = true;
+            config.user_agent_suffix = suffix;
+        })
+        .build(&server)
+        .await?;
+    test.submit_turn("hello").await?;
+
+    assert_eq!(
+        turn.single_request().header("user-agent"),
+        Some(expected.clone())
+    );
+    let catalog_user_agents: Vec<Option<String>> = server
+        .received_requests()
+        .await
+        .unwrap_or_default()
+        .iter()
+        .filter(|request| request.url.path() == "/v1/models")
+        .map(|request| {
+            request
+                .headers
+                .get("user-agent")
+                .and_then(|value| value.to_str().ok())
+                .map(str::to_string)
+        })
+        .collect();
+    assert!(!catalog_user_agents.is_empty(), "catalog was never fetched");
+    assert!(
+        catalog_user_agents
+            .iter()
+            .all(|user_agent| user_agent.as_deref() == Some(expected.as_str())),
+        "unexpected catalog user agents: {catalog_user_agents:?}"
+    );
+
+    Ok(())
+}
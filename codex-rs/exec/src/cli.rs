@@ -1,6 +1,7 @@
 use clap::Parser;
 use clap::ValueEnum;
 use codex_common::CliConfigOverrides;
+use codex_common::ModelValueParser;
 use std::path::PathBuf;
 
 #[derive(Parser, Debug)]
@@ -15,7 +16,7 @@ pub struct Cli {
     pub images: Vec<PathBuf>,
 
     /// Model the agent should use.
-    #[arg(long, short = 'm')]
+    #[arg(long, short = 'm', value_parser = ModelValueParser)]
     pub model: Option<String>,
 
     /// Use open-source provider.
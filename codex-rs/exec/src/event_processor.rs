@@ -4,6 +4,8 @@ use codex_core::config::Config;
 use codex_core::protocol::Event;
 use codex_core::protocol::SessionConfiguredEvent;
 
+use crate::exec_events::SessionModel;
+
 pub(crate) enum CodexStatus {
     Running,
     InitiateShutdown,
@@ -11,6 +13,10 @@ pub(crate) enum CodexStatus {
 }
 
 pub(crate) trait EventProcessor {
+    /// Record the resolved model details for the session, before
+    /// `print_config_summary`.
+    fn set_session_model(&mut self, _model: SessionModel) {}
+
     /// Print summary of effective configuration and user prompt.
     fn print_config_summary(
         &mut self,
@@ -14,6 +14,8 @@ use codex_core::protocol::FileChange;
 use codex_core::protocol::McpInvocation;
 use codex_core::protocol::McpToolCallBeginEvent;
 use codex_core::protocol::McpToolCallEndEvent;
+use codex_core::protocol::ModelChangeSource;
+use codex_core::protocol::ModelChangedEvent;
 use codex_core::protocol::PatchApplyBeginEvent;
 use codex_core::protocol::PatchApplyEndEvent;
 use codex_core::protocol::SessionConfiguredEvent;
@@ -233,6 +235,19 @@ impl EventProcessor for EventProcessorWithHumanOutput {
                 };
                 ts_msg!(self, "{}", message.style(self.dimmed));
             }
+            EventMsg::ModelChanged(ModelChangedEvent {
+                previous_model,
+                model,
+                source: ModelChangeSource::Fallback,
+                ..
+            }) => {
+                ts_msg!(
+                    self,
+                    "{}",
+                    format!("{previous_model} is at capacity; retrying this turn on {model}")
+                        .style(self.dimmed)
+                );
+            }
             EventMsg::TaskStarted(_) => {
                 // Ignore.
             }
@@ -249,6 +264,20 @@ impl EventProcessor for EventProcessorWithHumanOutput {
                     "auto-cancelling (not supported in exec mode)".style(self.dimmed)
                 );
             }
+            EventMsg::CostLimitApprovalRequest(ev) => {
+                ts_msg!(
+                    self,
+                    "{} ${:.2} of ${:.2}",
+                    "session cost limit reached".style(self.magenta),
+                    ev.cost_usd,
+                    ev.limit_usd
+                );
+                ts_msg!(
+                    self,
+                    "{}",
+                    "declining (not supported in exec mode)".style(self.dimmed)
+                );
+            }
             EventMsg::TaskComplete(TaskCompleteEvent { last_agent_message }) => {
                 let last_message = last_agent_message.as_deref();
                 if let Some(output_file) = self.last_message_path.as_deref() {
@@ -595,7 +624,8 @@ impl EventProcessor for EventProcessorWithHumanOutput {
             | EventMsg::ReasoningRawContentDelta(_)
             | EventMsg::SkillsUpdateAvailable
             | EventMsg::UndoCompleted(_)
-            | EventMsg::UndoStarted(_) => {}
+            | EventMsg::UndoStarted(_)
+            | EventMsg::ModelChanged(_) => {}
         }
         CodexStatus::Running
     }
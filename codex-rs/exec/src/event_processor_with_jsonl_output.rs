@@ -21,6 +21,7 @@ use crate::exec_events::McpToolCallStatus;
 use crate::exec_events::PatchApplyStatus;
 use crate::exec_events::PatchChangeKind;
 use crate::exec_events::ReasoningItem;
+use crate::exec_events::SessionModel;
 use crate::exec_events::ThreadErrorEvent;
 use crate::exec_events::ThreadEvent;
 use crate::exec_events::ThreadItem;
@@ -67,6 +68,7 @@ pub struct EventProcessorWithJsonOutput {
     last_total_token_usage: Option<codex_core::protocol::TokenUsage>,
     running_mcp_tool_calls: HashMap<String, RunningMcpToolCall>,
     last_critical_error: Option<ThreadErrorEvent>,
+    session_model: Option<SessionModel>,
 }
 
 #[derive(Debug, Clone)]
@@ -101,9 +103,15 @@ impl EventProcessorWithJsonOutput {
             last_total_token_usage: None,
             running_mcp_tool_calls: HashMap::new(),
             last_critical_error: None,
+            session_model: None,
         }
     }
 
+    /// Model details to report in `thread.started`.
+    pub fn set_session_model(&mut self, model: SessionModel) {
+        self.session_model = Some(model);
+    }
+
     pub fn collect_thread_events(&mut self, event: &Event) -> Vec<ThreadEvent> {
         match &event.msg {
             EventMsg::SessionConfigured(ev) => self.handle_session_configured(ev),
@@ -170,6 +178,7 @@ impl EventProcessorWithJsonOutput {
     fn handle_session_configured(&self, payload: &SessionConfiguredEvent) -> Vec<ThreadEvent> {
         vec![ThreadEvent::ThreadStarted(ThreadStartedEvent {
             thread_id: payload.session_id.to_string(),
+            model: self.session_model.clone(),
         })]
     }
 
@@ -459,9 +468,13 @@ impl EventProcessorWithJsonOutput {
         vec![ThreadEvent::ItemStarted(ItemStartedEvent { item })]
     }
 
-    fn handle_task_started(&mut self, _: &TaskStartedEvent) -> Vec<ThreadEvent> {
+    fn handle_task_started(&mut self, ev: &TaskStartedEvent) -> Vec<ThreadEvent> {
         self.last_critical_error = None;
-        vec![ThreadEvent::TurnStarted(TurnStartedEvent {})]
+        vec![ThreadEvent::TurnStarted(TurnStartedEvent {
+            reasoning_effort: ev.reasoning_effort,
+            reasoning_effort_description: ev.reasoning_effort_description.clone(),
+            reasoning_summary_format: ev.reasoning_summary_format,
+        })]
     }
 
     fn handle_task_complete(&mut self) -> Vec<ThreadEvent> {
@@ -513,6 +526,10 @@ impl EventProcessorWithJsonOutput {
 }
 
 impl EventProcessor for EventProcessorWithJsonOutput {
+    fn set_session_model(&mut self, model: SessionModel) {
+        EventProcessorWithJsonOutput::set_session_model(self, model);
+    }
+
     fn print_config_summary(&mut self, _: &Config, _: &str, ev: &SessionConfiguredEvent) {
         self.process_event(Event {
             id: "".to_string(),
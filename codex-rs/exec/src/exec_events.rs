@@ -1,3 +1,5 @@
+use codex_protocol::openai_models::ReasoningEffort;
+use codex_protocol::openai_models::ReasoningSummaryFormat;
 use mcp_types::ContentBlock as McpContentBlock;
 use serde::Deserialize;
 use serde::Serialize;
@@ -39,11 +41,77 @@ pub enum ThreadEvent {
 pub struct ThreadStartedEvent {
     /// The identified of the new thread. Can be used to resume the thread later.
     pub thread_id: String,
+    /// The model the thread runs and the defaults resolved from its preset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model: Option<SessionModel>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, TS)]
+pub struct SessionModel {
+    /// Model slug sent to the provider.
+    pub slug: String,
+    /// The preset the model was selected from. Absent for models that are
+    /// not in the catalog.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub preset: Option<SessionPreset>,
+    /// The reasoning effort turns are sampled at unless they override it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reasoning_effort: Option<ReasoningEffort>,
+    /// The usable context window, in tokens, when known.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub context_window: Option<i64>,
+    /// Which catalog listed the preset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub catalog: Option<ModelCatalogSource>,
+}
+
+/// The catalog preset a session's model was selected from.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, TS)]
+pub struct SessionPreset {
+    pub id: String,
+    pub model: String,
+    pub display_name: String,
+    pub description: String,
+    pub supported_reasoning_efforts: Vec<ReasoningEffort>,
+    pub default_reasoning_effort: ReasoningEffort,
+    pub is_default: bool,
+}
+
+/// Where the session's preset came from.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, TS)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ModelCatalogSource {
+    /// The preset table built into Codex.
+    Builtin,
+    /// Remote model metadata from the bundled snapshot, the on-disk cache, or
+    /// a live request.
+    Remote { source: RemoteCatalogSource },
+    /// A configured catalog provider.
+    Provider { provider_id: String },
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, TS)]
+#[serde(rename_all = "snake_case")]
+pub enum RemoteCatalogSource {
+    Bundled,
+    DiskCache,
+    Live,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, TS, Default)]
 
-pub struct TurnStartedEvent {}
+pub struct TurnStartedEvent {
+    /// The reasoning effort the turn is sampled at, when it calls the model.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reasoning_effort: Option<ReasoningEffort>,
+    /// What the effort means for the active model, when its preset says.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reasoning_effort_description: Option<String>,
+    /// How the model writes the reasoning summaries of the turn, when it
+    /// calls the model.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reasoning_summary_format: Option<ReasoningSummaryFormat>,
+}
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, TS)]
 pub struct TurnCompletedEvent {
@@ -87,6 +155,40 @@ pub struct ThreadErrorEvent {
     pub message: String,
 }
 
+/// Written to stderr as the last line of a `--json` run that stops for a
+/// reason scripts can act on. `code` and `exit_code` never change meaning.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, TS)]
+pub struct ExecFailure {
+    pub code: ExecErrorCode,
+    /// The process exit status, the same for every failure with `code`.
+    pub exit_code: i32,
+    pub message: String,
+    /// Values to try instead, such as the closest model ids or the efforts
+    /// the model supports. Empty when there are none to offer.
+    pub suggestions: Vec<String>,
+    /// Whether running again unchanged may succeed.
+    pub retriable: bool,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, TS)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecErrorCode {
+    /// The provider no longer serves the selected model.
+    ModelUnavailable,
+    /// The session reached `session_cost_limit_usd`.
+    CostLimit,
+    /// The model is not in the active provider's catalog.
+    UnknownModel,
+    /// The model does not offer the requested reasoning effort.
+    UnsupportedEffort,
+    /// The model lacks something the run needs, such as image input.
+    MissingCapability,
+    /// The active provider's catalog could not be listed.
+    CatalogUnreachable,
+    /// The provider turned away the credentials, or the lack of them.
+    AuthFailed,
+}
+
 /// Canonical representation of a thread item and its domain-specific payload.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, TS)]
 pub struct ThreadItem {
@@ -0,0 +1,157 @@
+//! Exit statuses and the `--json` error line for runs that stop for a reason
+//! scripts can act on, so wrappers do not have to match on stderr prose.
+
+use codex_core::config::Config;
+use codex_core::models_manager::catalog::PresetCatalog;
+use codex_core::models_manager::manager::ModelsManager;
+use codex_core::models_manager::provenance::CatalogWarning;
+use codex_core::models_manager::validation::InvalidSelection;
+use codex_core::protocol::CodexErrorInfo;
+
+use crate::AUTH_FAILED_EXIT_CODE;
+use crate::CATALOG_UNREACHABLE_EXIT_CODE;
+use crate::COST_LIMIT_EXIT_CODE;
+use crate::MISSING_CAPABILITY_EXIT_CODE;
+use crate::MODEL_UNAVAILABLE_EXIT_CODE;
+use crate::UNKNOWN_MODEL_EXIT_CODE;
+use crate::UNSUPPORTED_EFFORT_EXIT_CODE;
+use crate::exec_events::ExecErrorCode;
+use crate::exec_events::ExecFailure;
+
+impl ExecErrorCode {
+    pub fn exit_code(self) -> i32 {
+        match self {
+            ExecErrorCode::ModelUnavailable => MODEL_UNAVAILABLE_EXIT_CODE,
+            ExecErrorCode::CostLimit => COST_LIMIT_EXIT_CODE,
+            ExecErrorCode::UnknownModel => UNKNOWN_MODEL_EXIT_CODE,
+            ExecErrorCode::UnsupportedEffort => UNSUPPORTED_EFFORT_EXIT_CODE,
+            ExecErrorCode::MissingCapability => MISSING_CAPABILITY_EXIT_CODE,
+            ExecErrorCode::CatalogUnreachable => CATALOG_UNREACHABLE_EXIT_CODE,
+            ExecErrorCode::AuthFailed => AUTH_FAILED_EXIT_CODE,
+        }
+    }
+
+    /// Only an unreachable catalog may come back on its own; the rest need
+    /// the command or config changed.
+    fn retriable(self) -> bool {
+        matches!(self, ExecErrorCode::CatalogUnreachable)
+    }
+}
+
+impl ExecFailure {
+    fn new(code: ExecErrorCode, message: String, suggestions: Vec<String>) -> Self {
+        Self {
+            code,
+            exit_code: code.exit_code(),
+            message,
+            suggestions,
+            retriable: code.retriable(),
+        }
+    }
+
+    /// Write the failure to stderr: as JSON in `--json` mode, otherwise as
+    /// its message unless the run has shown that already.
+    #[allow(clippy::print_stderr)]
+    pub(crate) fn report(&self, json_mode: bool, message_shown: bool) {
+        match serde_json::to_string(self) {
+            Ok(line) if json_mode => eprintln!("{line}"),
+            _ if message_shown => {}
+            _ => eprintln!("{}", self.message),
+        }
+    }
+
+    /// Exit with the failure's status, after [`Self::report`].
+    pub(crate) fn exit(&self, json_mode: bool, message_shown: bool) -> ! {
+        self.report(json_mode, message_shown);
+        std::process::exit(self.exit_code);
+    }
+}
+
+impl std::fmt::Display for ExecFailure {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl std::error::Error for ExecFailure {}
+
+/// The status to exit with when [`crate::run_main`] failed with `err`, if
+/// the run stopped for a reason with a status of its own. The failure has
+/// been reported on stderr already.
+pub fn failure_exit_code(err: &anyhow::Error) -> Option<i32> {
+    err.downcast_ref::<ExecFailure>()
+        .map(|failure| failure.exit_code)
+}
+
+impl From<&InvalidSelection> for ExecFailure {
+    fn from(invalid: &InvalidSelection) -> Self {
+        let (code, suggestions) = match invalid {
+            InvalidSelection::UnknownModel { suggestions, .. } => {
+                (ExecErrorCode::UnknownModel, suggestions.clone())
+            }
+            InvalidSelection::UnsupportedEffort { supported, .. } => (
+                ExecErrorCode::UnsupportedEffort,
+                supported.iter().map(ToString::to_string).collect(),
+            ),
+            InvalidSelection::DisabledEffort { .. } => {
+                (ExecErrorCode::UnsupportedEffort, Vec::new())
+            }
+            InvalidSelection::MissingCapability { supporting, .. } => {
+                (ExecErrorCode::MissingCapability, supporting.clone())
+            }
+        };
+        Self::new(code, invalid.to_string(), suggestions)
+    }
+}
+
+impl From<&CatalogWarning> for ExecFailure {
+    fn from(warning: &CatalogWarning) -> Self {
+        let code = if warning.unauthorized {
+            ExecErrorCode::AuthFailed
+        } else {
+            ExecErrorCode::CatalogUnreachable
+        };
+        Self::new(code, warning.to_string(), Vec::new())
+    }
+}
+
+/// The failure a turn error stands for, when it has a code of its own.
+pub(crate) fn turn_failure(info: Option<&CodexErrorInfo>, message: &str) -> Option<ExecFailure> {
+    let code = match info? {
+        CodexErrorInfo::ModelUnavailable { .. } => ExecErrorCode::ModelUnavailable,
+        CodexErrorInfo::Unauthorized => ExecErrorCode::AuthFailed,
+        _ => return None,
+    };
+    Some(ExecFailure::new(code, message.to_string(), Vec::new()))
+}
+
+pub(crate) fn cost_limit_failure() -> ExecFailure {
+    ExecFailure::new(
+        ExecErrorCode::CostLimit,
+        "the session reached `session_cost_limit_usd`; raise the limit to continue".to_string(),
+        Vec::new(),
+    )
+}
+
+/// Check the configured model and effort against the active provider's
+/// catalog before a session is started with them, as
+/// `strict_model_validation` asks. A catalog that could not be listed fails
+/// the check, since nothing can be validated against it.
+pub(crate) async fn check_selection(
+    models_manager: &ModelsManager,
+    config: &Config,
+) -> Result<(), ExecFailure> {
+    let result = PresetCatalog::new(models_manager, config)
+        .check_selection(config.model.as_deref(), config.model_reasoning_effort)
+        .await;
+    if config.model_provider.model_catalog.is_some()
+        && let Some(warning) = models_manager
+            .catalog_warnings()
+            .await
+            .iter()
+            .find(|warning| warning.provider_id.as_ref() == Some(&config.model_provider_id))
+    {
+        return Err(warning.into());
+    }
+    result.map_err(|invalid| ExecFailure::from(&invalid))
+}
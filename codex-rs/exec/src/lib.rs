@@ -9,6 +9,7 @@ mod event_processor;
 mod event_processor_with_human_output;
 pub mod event_processor_with_jsonl_output;
 pub mod exec_events;
+mod failure;
 
 pub use cli::Cli;
 pub use cli::Command;
@@ -27,19 +28,32 @@ use codex_core::config::find_codex_home;
 use codex_core::config::load_config_as_toml_with_cli_overrides;
 use codex_core::config::resolve_oss_provider;
 use codex_core::git_info::get_git_repo_root;
+use codex_core::models_manager::catalog::CatalogLoadOptions;
+use codex_core::models_manager::catalog::PresetCatalog;
+use codex_core::models_manager::provenance::CatalogSource;
+use codex_core::models_manager::provenance::PresetSource;
 use codex_core::protocol::AskForApproval;
+use codex_core::protocol::ErrorEvent;
 use codex_core::protocol::Event;
 use codex_core::protocol::EventMsg;
 use codex_core::protocol::Op;
+use codex_core::protocol::ReviewDecision;
 use codex_core::protocol::ReviewRequest;
 use codex_core::protocol::ReviewTarget;
+use codex_core::protocol::SessionConfiguredEvent;
 use codex_core::protocol::SessionSource;
 use codex_protocol::approvals::ElicitationAction;
 use codex_protocol::config_types::SandboxMode;
+use codex_protocol::openai_models::ModelPreset;
 use codex_protocol::user_input::UserInput;
 use codex_utils_absolute_path::AbsolutePathBuf;
 use event_processor_with_human_output::EventProcessorWithHumanOutput;
 use event_processor_with_jsonl_output::EventProcessorWithJsonOutput;
+use exec_events::ModelCatalogSource;
+use exec_events::RemoteCatalogSource;
+use exec_events::SessionModel;
+use exec_events::SessionPreset;
+pub use failure::failure_exit_code;
 use serde_json::Value;
 use std::io::IsTerminal;
 use std::io::Read;
@@ -57,6 +71,32 @@ use crate::event_processor::EventProcessor;
 use codex_core::default_client::set_default_originator;
 use codex_core::find_conversation_path_by_id_str;
 
+/// Exit status when the provider rejects the selected model, so scripts can
+/// tell a model that needs replacing apart from other failures.
+pub const MODEL_UNAVAILABLE_EXIT_CODE: i32 = 3;
+
+/// Exit status when the session's estimated cost reached
+/// `session_cost_limit_usd`, which exec declines to go past.
+pub const COST_LIMIT_EXIT_CODE: i32 = 4;
+
+/// Exit status under `strict_model_validation` when the model is not in the
+/// active provider's catalog.
+pub const UNKNOWN_MODEL_EXIT_CODE: i32 = 5;
+
+/// Exit status under `strict_model_validation` when the model does not offer
+/// the configured reasoning effort.
+pub const UNSUPPORTED_EFFORT_EXIT_CODE: i32 = 6;
+
+/// Exit status under `strict_model_validation` when the active provider's
+/// catalog could not be listed, so the model could not be checked.
+pub const CATALOG_UNREACHABLE_EXIT_CODE: i32 = 7;
+
+/// Exit status when the provider or its catalog turned away the credentials.
+pub const AUTH_FAILED_EXIT_CODE: i32 = 8;
+
+/// Exit status when the model lacks something the run needs.
+pub const MISSING_CAPABILITY_EXIT_CODE: i32 = 9;
+
 enum InitialOperation {
     UserTurn {
         items: Vec<UserInput>,
@@ -215,7 +255,7 @@ pub async fn run_main(cli: Cli, codex_linux_sandbox_exe: Option<PathBuf>) -> any
         additional_writable_roots: add_dir,
     };
 
-    let config =
+    let mut config =
         Config::load_with_cli_overrides_and_harness_overrides(cli_kv_overrides, overrides).await?;
 
     if let Err(err) = enforce_login_restrictions(&config).await {
@@ -273,7 +313,6 @@ pub async fn run_main(cli: Cli, codex_linux_sandbox_exe: Option<PathBuf>) -> any
     let default_cwd = config.cwd.to_path_buf();
     let default_approval_policy = config.approval_policy.value();
     let default_sandbox_policy = config.sandbox_policy.get();
-    let default_effort = config.model_reasoning_effort;
     let default_summary = config.model_reasoning_summary;
 
     if !skip_git_repo_check && get_git_repo_root(&default_cwd).is_none() {
@@ -287,6 +326,29 @@ pub async fn run_main(cli: Cli, codex_linux_sandbox_exe: Option<PathBuf>) -> any
         config.cli_auth_credentials_store_mode,
     );
     let conversation_manager = ConversationManager::new(auth_manager.clone(), SessionSource::Exec);
+    if config.strict_model_validation
+        && let Err(failure) =
+            failure::check_selection(&conversation_manager.get_models_manager(), &config).await
+    {
+        failure.exit(json_mode, false);
+    }
+    // Without strict validation, an effort the model does not offer is
+    // replaced by the closest one it does, as the app server does.
+    if !config.strict_model_validation
+        && let Some(effort) = config.model_reasoning_effort
+    {
+        let models_manager = conversation_manager.get_models_manager();
+        let resolved = PresetCatalog::new(&models_manager, &config)
+            .resolve_effort(None, effort)
+            .await;
+        if let Ok(resolution) = resolved {
+            if let Some(warning) = &resolution.warning {
+                eprintln!("{warning}");
+            }
+            config.model_reasoning_effort = Some(resolution.effort);
+        }
+    }
+    let default_effort = config.model_reasoning_effort;
     let default_model = conversation_manager
         .get_models_manager()
         .get_model(&config.model, &config)
@@ -371,6 +433,9 @@ pub async fn run_main(cli: Cli, codex_linux_sandbox_exe: Option<PathBuf>) -> any
 
     // Print the effective configuration and initial request so users can see what Codex
     // is using.
+    let session_model =
+        resolve_session_model(&conversation_manager, &config, &session_configured).await;
+    event_processor.set_session_model(session_model);
     event_processor.print_config_summary(&config, &prompt_summary, &session_configured);
 
     info!("Codex initialized with event: {session_configured:?}");
@@ -445,6 +510,8 @@ pub async fn run_main(cli: Cli, codex_linux_sandbox_exe: Option<PathBuf>) -> any
     // Track whether a fatal error was reported by the server so we can
     // exit with a non-zero status for automation-friendly signaling.
     let mut error_seen = false;
+    let mut coded_failure = None;
+    let mut cost_limit_reached = false;
     while let Some(event) = rx.recv().await {
         if let EventMsg::ElicitationRequest(ev) = &event.msg {
             // Automatically cancel elicitation requests in exec mode.
@@ -456,8 +523,25 @@ pub async fn run_main(cli: Cli, codex_linux_sandbox_exe: Option<PathBuf>) -> any
                 })
                 .await?;
         }
-        if matches!(event.msg, EventMsg::Error(_)) {
+        if let EventMsg::CostLimitApprovalRequest(ev) = &event.msg {
+            // Nobody can confirm going past the limit, so the turn stops.
+            cost_limit_reached = true;
+            conversation
+                .submit(Op::CostLimitApproval {
+                    id: ev.turn_id.clone(),
+                    decision: ReviewDecision::Denied,
+                })
+                .await?;
+        }
+        if let EventMsg::Error(ErrorEvent {
+            message,
+            codex_error_info,
+        }) = &event.msg
+        {
             error_seen = true;
+            if coded_failure.is_none() {
+                coded_failure = failure::turn_failure(codex_error_info.as_ref(), message);
+            }
         }
         let shutdown: CodexStatus = event_processor.process_event(event);
         match shutdown {
@@ -471,6 +555,14 @@ pub async fn run_main(cli: Cli, codex_linux_sandbox_exe: Option<PathBuf>) -> any
         }
     }
     event_processor.print_final_output();
+    if cost_limit_reached {
+        let failure = failure::cost_limit_failure();
+        failure.report(json_mode, true);
+        return Err(failure.into());
+    }
+    if let Some(failure) = coded_failure {
+        failure.exit(json_mode, true);
+    }
     if error_seen {
         std::process::exit(1);
     }
@@ -478,6 +570,84 @@ pub async fn run_main(cli: Cli, codex_linux_sandbox_exe: Option<PathBuf>) -> any
     Ok(())
 }
 
+/// The session's model together with the preset it was selected from, the
+/// effort turns default to, and the catalog that listed the preset. Only
+/// what session startup already loaded is read, so this makes no request.
+async fn resolve_session_model(
+    conversation_manager: &ConversationManager,
+    config: &Config,
+    session_configured: &SessionConfiguredEvent,
+) -> SessionModel {
+    let models_manager = conversation_manager.get_models_manager();
+    let (slug, model_family) = models_manager.resolve_session_model(config).await;
+    let preset = PresetCatalog::new(&models_manager, config)
+        .load_cached_or_static()
+        .unwrap_or_else(|_| {
+            PresetCatalog::static_presets(CatalogLoadOptions::default())
+                .cloned()
+                .collect()
+        })
+        .into_iter()
+        .find(|preset| {
+            preset.model == slug
+                && preset
+                    .model_provider
+                    .as_deref()
+                    .is_none_or(|provider_id| provider_id == config.model_provider_id)
+        });
+    let catalog = match &preset {
+        Some(preset) => models_manager
+            .catalog_status(config)
+            .await
+            .presets
+            .into_iter()
+            .find(|provenance| provenance.id == preset.id)
+            .map(|provenance| catalog_source(provenance.source)),
+        None => None,
+    };
+    SessionModel {
+        reasoning_effort: session_configured.reasoning_effort.or_else(|| {
+            preset
+                .as_ref()
+                .map(|preset| preset.default_reasoning_effort)
+        }),
+        preset: preset.map(session_preset),
+        slug,
+        context_window: model_family.context_window,
+        catalog,
+    }
+}
+
+fn session_preset(preset: ModelPreset) -> SessionPreset {
+    SessionPreset {
+        supported_reasoning_efforts: preset
+            .supported_reasoning_efforts
+            .iter()
+            .map(|effort| effort.effort)
+            .collect(),
+        id: preset.id,
+        model: preset.model,
+        display_name: preset.display_name,
+        description: preset.description,
+        default_reasoning_effort: preset.default_reasoning_effort,
+        is_default: preset.is_default,
+    }
+}
+
+fn catalog_source(source: PresetSource) -> ModelCatalogSource {
+    match source {
+        PresetSource::Builtin => ModelCatalogSource::Builtin,
+        PresetSource::Remote { source } => ModelCatalogSource::Remote {
+            source: match source {
+                CatalogSource::Bundled => RemoteCatalogSource::Bundled,
+                CatalogSource::DiskCache => RemoteCatalogSource::DiskCache,
+                CatalogSource::Live => RemoteCatalogSource::Live,
+            },
+        },
+        PresetSource::Provider { provider_id } => ModelCatalogSource::Provider { provider_id },
+    }
+}
+
 async fn resolve_resume_path(
     config: &Config,
     args: &crate::cli::ResumeArgs,
@@ -596,6 +766,9 @@ fn build_review_request(args: ReviewArgs) -> anyhow::Result<ReviewRequest> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use codex_common::test_catalog::PresetBuilderExt;
+    use codex_common::test_catalog::preset;
+    use codex_protocol::openai_models::ReasoningEffort;
     use pretty_assertions::assert_eq;
 
     #[test]
@@ -659,4 +832,28 @@ mod tests {
 
         assert_eq!(request, expected);
     }
+
+    #[test]
+    fn session_preset_keeps_the_catalog_entry() {
+        let preset = preset("oca-large")
+            .display_name("OCA Large")
+            .description("Large model")
+            .efforts([ReasoningEffort::High, ReasoningEffort::Low])
+            .default()
+            .build()
+            .expect("valid preset");
+
+        assert_eq!(
+            session_preset(preset),
+            SessionPreset {
+                id: "oca-large".to_string(),
+                model: "oca-large".to_string(),
+                display_name: "OCA Large".to_string(),
+                description: "Large model".to_string(),
+                supported_reasoning_efforts: vec![ReasoningEffort::High, ReasoningEffort::Low],
+                default_reasoning_effort: ReasoningEffort::High,
+                is_default: true,
+            }
+        );
+    }
 }
@@ -13,6 +13,7 @@ use clap::Parser;
 use codex_arg0::arg0_dispatch_or_else;
 use codex_common::CliConfigOverrides;
 use codex_exec::Cli;
+use codex_exec::failure_exit_code;
 use codex_exec::run_main;
 
 #[derive(Parser, Debug)]
@@ -34,7 +35,12 @@ fn main() -> anyhow::Result<()> {
             .raw_overrides
             .splice(0..0, top_cli.config_overrides.raw_overrides);
 
-        run_main(inner, codex_linux_sandbox_exe).await?;
+        if let Err(err) = run_main(inner, codex_linux_sandbox_exe).await {
+            if let Some(code) = failure_exit_code(&err) {
+                std::process::exit(code);
+            }
+            return Err(err);
+        }
         Ok(())
     })
 }
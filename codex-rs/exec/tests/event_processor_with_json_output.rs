@@ -29,9 +29,11 @@ use codex_exec::exec_events::McpToolCallItem;
 use codex_exec::exec_events::McpToolCallItemError;
 use codex_exec::exec_events::McpToolCallItemResult;
 use codex_exec::exec_events::McpToolCallStatus;
+use codex_exec::exec_events::ModelCatalogSource;
 use codex_exec::exec_events::PatchApplyStatus;
 use codex_exec::exec_events::PatchChangeKind;
 use codex_exec::exec_events::ReasoningItem;
+use codex_exec::exec_events::SessionModel;
 use codex_exec::exec_events::ThreadErrorEvent;
 use codex_exec::exec_events::ThreadEvent;
 use codex_exec::exec_events::ThreadItem;
@@ -44,6 +46,8 @@ use codex_exec::exec_events::TurnFailedEvent;
 use codex_exec::exec_events::TurnStartedEvent;
 use codex_exec::exec_events::Usage;
 use codex_exec::exec_events::WebSearchItem;
+use codex_protocol::openai_models::ReasoningEffort;
+use codex_protocol::openai_models::ReasoningSummaryFormat;
 use codex_protocol::plan_tool::PlanItemArg;
 use codex_protocol::plan_tool::StepStatus;
 use codex_protocol::plan_tool::UpdatePlanArgs;
@@ -78,6 +82,8 @@ fn session_configured_produces_thread_started_event() {
             session_id,
             model: "codex-mini-latest".to_string(),
             model_provider_id: "test-provider".to_string(),
+            model_catalog_offline: false,
+            instructions_addendum_active: false,
             approval_policy: AskForApproval::Never,
             sandbox_policy: SandboxPolicy::ReadOnly,
             cwd: PathBuf::from("/home/user/project"),
@@ -86,6 +92,7 @@ fn session_configured_produces_thread_started_event() {
             history_entry_count: 0,
             initial_messages: None,
             rollout_path,
+            disabled_tools: Vec::new(),
         }),
     );
     let out = ep.collect_thread_events(&ev);
@@ -93,10 +100,69 @@ fn session_configured_produces_thread_started_event() {
         out,
         vec![ThreadEvent::ThreadStarted(ThreadStartedEvent {
             thread_id: "67e55044-10b1-426f-9247-bb680e5fe0c8".to_string(),
+            model: None,
         })]
     );
 }
 
+#[test]
+fn thread_started_carries_the_session_model() {
+    let mut ep = EventProcessorWithJsonOutput::new(None);
+    let session_model = SessionModel {
+        slug: "oca-large".to_string(),
+        preset: None,
+        reasoning_effort: Some(ReasoningEffort::High),
+        context_window: Some(128_000),
+        catalog: Some(ModelCatalogSource::Provider {
+            provider_id: "oca".to_string(),
+        }),
+    };
+    ep.set_session_model(session_model.clone());
+    let session_id =
+        codex_protocol::ConversationId::from_string("67e55044-10b1-426f-9247-bb680e5fe0c8")
+            .unwrap();
+    let ev = event(
+        "e1",
+        EventMsg::SessionConfigured(SessionConfiguredEvent {
+            session_id,
+            model: "oca-large".to_string(),
+            model_provider_id: "oca".to_string(),
+            model_catalog_offline: false,
+            instructions_addendum_active: false,
+            approval_policy: AskForApproval::Never,
+            sandbox_policy: SandboxPolicy::ReadOnly,
+            cwd: PathBuf::from("/home/user/project"),
+            reasoning_effort: Some(ReasoningEffort::High),
+            history_log_id: 0,
+            history_entry_count: 0,
+            initial_messages: None,
+            rollout_path: PathBuf::from("/tmp/rollout.json"),
+            disabled_tools: Vec::new(),
+        }),
+    );
+    let out = ep.collect_thread_events(&ev);
+    assert_eq!(
+        out,
+        vec![ThreadEvent::ThreadStarted(ThreadStartedEvent {
+            thread_id: "67e55044-10b1-426f-9247-bb680e5fe0c8".to_string(),
+            model: Some(session_model),
+        })]
+    );
+    assert_eq!(
+        serde_json::to_value(&out[0]).unwrap(),
+        json!({
+            "type": "thread.started",
+            "thread_id": "67e55044-10b1-426f-9247-bb680e5fe0c8",
+            "model": {
+                "slug": "oca-large",
+                "reasoning_effort": "high",
+                "context_window": 128000,
+                "catalog": {"kind": "provider", "provider_id": "oca"},
+            },
+        })
+    );
+}
+
 #[test]
 fn task_started_produces_turn_started_event() {
     let mut ep = EventProcessorWithJsonOutput::new(None);
@@ -104,10 +170,52 @@ fn task_started_produces_turn_started_event() {
         "t1",
         EventMsg::TaskStarted(codex_core::protocol::TaskStartedEvent {
             model_context_window: Some(32_000),
+            reasoning_effort: None,
+            reasoning_effort_description: None,
+            reasoning_summary_format: None,
+        }),
+    ));
+
+    assert_eq!(
+        out,
+        vec![ThreadEvent::TurnStarted(TurnStartedEvent::default())]
+    );
+}
+
+#[test]
+fn task_started_carries_the_effort_description_into_turn_started() {
+    let mut ep = EventProcessorWithJsonOutput::new(None);
+    let out = ep.collect_thread_events(&event(
+        "t1",
+        EventMsg::TaskStarted(codex_core::protocol::TaskStartedEvent {
+            model_context_window: Some(32_000),
+            reasoning_effort: Some(ReasoningEffort::High),
+            reasoning_effort_description: Some(
+                "Maximizes reasoning depth for complex or ambiguous problems".to_string(),
+            ),
+            reasoning_summary_format: Some(ReasoningSummaryFormat::Plain),
         }),
     ));
 
-    assert_eq!(out, vec![ThreadEvent::TurnStarted(TurnStartedEvent {})]);
+    assert_eq!(
+        out,
+        vec![ThreadEvent::TurnStarted(TurnStartedEvent {
+            reasoning_effort: Some(ReasoningEffort::High),
+            reasoning_effort_description: Some(
+                "Maximizes reasoning depth for complex or ambiguous problems".to_string(),
+            ),
+            reasoning_summary_format: Some(ReasoningSummaryFormat::Plain),
+        })]
+    );
+    assert_eq!(
+        serde_json::to_value(&out[0]).expect("serialize"),
+        json!({
+            "type": "turn.started",
+            "reasoning_effort": "high",
+            "reasoning_effort_description": "Maximizes reasoning depth for complex or ambiguous problems",
+            "reasoning_summary_format": "plain",
+        })
+    );
 }
 
 #[test]
@@ -1048,6 +1156,7 @@ fn task_complete_produces_turn_completed_with_usage() {
         total_token_usage: usage.clone(),
         last_token_usage: usage,
         model_context_window: None,
+        per_model_usage: Vec::new(),
     };
     let token_count_event = event(
         "e1",
@@ -0,0 +1,68 @@
+#![cfg(not(target_os = "windows"))]
+#![allow(clippy::expect_used, clippy::unwrap_used)]
+
+use codex_exec::COST_LIMIT_EXIT_CODE;
+use core_test_support::responses;
+use core_test_support::test_codex_exec::test_codex_exec;
+use pretty_assertions::assert_eq;
+
+/// `codex exec` cannot ask whether to go past `session_cost_limit_usd`, so a
+/// turn that starts past it is declined before it sends anything, and the
+/// run exits with its own status.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn exits_with_cost_limit_code_when_the_limit_is_reached() -> anyhow::Result<()> {
+    let test = test_codex_exec();
+    let server = responses::start_mock_server().await;
+    std::fs::write(
+        test.home_path().join("config.toml"),
+        format!(
+            r#"
+model = "gpt-5.1-codex-max"
+model_provider = "priced"
+session_cost_limit_usd = 1.0
+
+[model_providers.priced]
+name = "Priced"
+base_url = "{}/v1"
+wire_api = "responses"
+request_max_retries = 0
+stream_max_retries = 0
+
+[model_providers.priced.model_pricing."gpt-5.1-codex-max"]
+input_per_million = 10.0
+output_per_million = 10.0
+"#,
+            server.uri()
+        ),
+    )?;
+
+    // 200k input tokens at $10 per million cost $2. The limit is checked
+    // when a turn starts, so this turn runs to the end.
+    let body = responses::sse(vec![
+        responses::ev_response_created("resp-1"),
+        responses::ev_assistant_message("msg-1", "done"),
+        responses::ev_completed_with_tokens("resp-1", 200_000),
+    ]);
+    let response_mock = responses::mount_sse_once(&server, body).await;
+    test.cmd()
+        .arg("--skip-git-repo-check")
+        .arg("tell me something")
+        .assert()
+        .success();
+
+    // The resumed session starts its next turn past the limit.
+    test.cmd()
+        .arg("--skip-git-repo-check")
+        .arg("resume")
+        .arg("--last")
+        .arg("tell me more")
+        .assert()
+        .code(COST_LIMIT_EXIT_CODE);
+
+    assert_eq!(
+        response_mock.requests().len(),
+        1,
+        "no request is sent past the limit"
+    );
+    Ok(())
+}
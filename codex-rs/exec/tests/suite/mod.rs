@@ -2,8 +2,11 @@
 mod add_dir;
 mod apply_patch;
 mod auth_env;
+mod cost_limit;
+mod model_resolution_exit;
 mod originator;
 mod output_schema;
 mod resume;
 mod sandbox;
 mod server_error_exit;
+mod session_model;
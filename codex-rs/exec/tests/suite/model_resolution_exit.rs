@@ -0,0 +1,146 @@
+#![cfg(not(target_os = "windows"))]
+#![allow(clippy::expect_used, clippy::unwrap_used)]
+
+use codex_exec::CATALOG_UNREACHABLE_EXIT_CODE;
+use codex_exec::UNKNOWN_MODEL_EXIT_CODE;
+use codex_exec::UNSUPPORTED_EFFORT_EXIT_CODE;
+use core_test_support::test_codex_exec::TestCodexExecBuilder;
+use core_test_support::test_codex_exec::test_codex_exec;
+use pretty_assertions::assert_eq;
+use serde_json::Value;
+use serde_json::json;
+
+fn write_config(test: &TestCodexExecBuilder, config: &str) {
+    std::fs::write(test.home_path().join("config.toml"), config).unwrap();
+}
+
+/// The last stderr line of a `--json` run, which carries the failure.
+fn failure_line(stderr: &[u8]) -> Value {
+    let stderr = String::from_utf8_lossy(stderr);
+    let last = stderr.lines().last().expect("stderr is not empty");
+    serde_json::from_str(last).unwrap_or_else(|err| panic!("{err}: {stderr}"))
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn unknown_model_exits_with_its_code_and_suggestions() -> anyhow::Result<()> {
+    let test = test_codex_exec();
+    write_config(
+        &test,
+        r#"
+model_provider = "gateway"
+strict_model_validation = true
+
+[model_providers.gateway]
+name = "Gateway"
+base_url = "http://127.0.0.1:9/v1"
+model_catalog = "none"
+models = ["oca-large"]
+"#,
+    );
+
+    let output = test
+        .cmd()
+        .arg("--skip-git-repo-check")
+        .arg("--json")
+        .arg("-m")
+        .arg("oca-lrage")
+        .arg("tell me something")
+        .output()?;
+
+    assert_eq!(output.status.code(), Some(UNKNOWN_MODEL_EXIT_CODE));
+    let failure = failure_line(&output.stderr);
+    assert_eq!(
+        json!({
+            "code": failure["code"],
+            "exit_code": failure["exit_code"],
+            "suggestions": failure["suggestions"],
+            "retriable": failure["retriable"],
+        }),
+        json!({
+            "code": "unknown_model",
+            "exit_code": UNKNOWN_MODEL_EXIT_CODE,
+            "suggestions": ["oca-large"],
+            "retriable": false,
+        })
+    );
+    assert!(
+        failure["message"]
+            .as_str()
+            .is_some_and(|message| message.contains("`oca-lrage`")),
+        "{failure}"
+    );
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn unsupported_effort_exits_with_its_code() -> anyhow::Result<()> {
+    let test = test_codex_exec();
+    write_config(
+        &test,
+        r#"
+strict_model_validation = true
+model_reasoning_effort = "minimal"
+"#,
+    );
+
+    let output = test
+        .cmd()
+        .arg("--skip-git-repo-check")
+        .arg("--json")
+        .arg("-m")
+        .arg("gpt-5.1-codex-max")
+        .arg("tell me something")
+        .output()?;
+
+    assert_eq!(output.status.code(), Some(UNSUPPORTED_EFFORT_EXIT_CODE));
+    let failure = failure_line(&output.stderr);
+    assert_eq!(failure["code"], "unsupported_effort");
+    assert_eq!(
+        failure["suggestions"],
+        json!(["low", "medium", "high", "xhigh"])
+    );
+    assert_eq!(failure["retriable"], false);
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn unreachable_catalog_exits_with_a_retriable_code() -> anyhow::Result<()> {
+    let test = test_codex_exec();
+    write_config(
+        &test,
+        r#"
+model = "oca-large"
+model_provider = "gateway"
+strict_model_validation = true
+
+[model_providers.gateway]
+name = "Gateway"
+base_url = "http://127.0.0.1:9/v1"
+model_catalog = "openai"
+"#,
+    );
+
+    let output = test
+        .cmd()
+        .arg("--skip-git-repo-check")
+        .arg("--json")
+        .arg("tell me something")
+        .output()?;
+
+    assert_eq!(output.status.code(), Some(CATALOG_UNREACHABLE_EXIT_CODE));
+    let failure = failure_line(&output.stderr);
+    assert_eq!(failure["code"], "catalog_unreachable");
+    assert_eq!(failure["exit_code"], CATALOG_UNREACHABLE_EXIT_CODE);
+    assert_eq!(failure["retriable"], true);
+
+    // Without `--json` the same failure is reported as prose.
+    let output = test
+        .cmd()
+        .arg("--skip-git-repo-check")
+        .arg("tell me something")
+        .output()?;
+    assert_eq!(output.status.code(), Some(CATALOG_UNREACHABLE_EXIT_CODE));
+    let stderr = String::from_utf8(output.stderr)?;
+    assert!(stderr.contains("gateway:"), "{stderr}");
+    Ok(())
+}
@@ -1,8 +1,13 @@
 #![cfg(not(target_os = "windows"))]
 #![allow(clippy::expect_used, clippy::unwrap_used)]
 
+use codex_exec::MODEL_UNAVAILABLE_EXIT_CODE;
 use core_test_support::responses;
 use core_test_support::test_codex_exec::test_codex_exec;
+use wiremock::Mock;
+use wiremock::ResponseTemplate;
+use wiremock::matchers::method;
+use wiremock::matchers::path_regex;
 
 /// Verify that when the server reports an error, `codex-exec` exits with a
 /// non-zero status code so automation can detect failures.
@@ -31,3 +36,31 @@ async fn exits_non_zero_when_server_reports_error() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+/// A provider that no longer serves the selected model gets its own exit
+/// status so scripts can switch models instead of retrying.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn exits_with_model_unavailable_code_when_model_is_rejected() -> anyhow::Result<()> {
+    let test = test_codex_exec();
+
+    let server = responses::start_mock_server().await;
+    Mock::given(method("POST"))
+        .and(path_regex(".*/responses$"))
+        .respond_with(ResponseTemplate::new(404).set_body_json(serde_json::json!({
+            "error": {
+                "code": "model_not_found",
+                "message": "The model `gpt-5.1-codex` does not exist."
+            }
+        })))
+        .mount(&server)
+        .await;
+
+    test.cmd_with_server(&server)
+        .arg("--skip-git-repo-check")
+        .arg("tell me something")
+        .arg("--experimental-json")
+        .assert()
+        .code(MODEL_UNAVAILABLE_EXIT_CODE);
+
+    Ok(())
+}
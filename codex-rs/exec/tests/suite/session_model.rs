@@ -0,0 +1,69 @@
+#![cfg(not(target_os = "windows"))]
+#![allow(clippy::expect_used, clippy::unwrap_used)]
+
+use core_test_support::responses;
+use core_test_support::test_codex_exec::test_codex_exec;
+use pretty_assertions::assert_eq;
+use serde_json::Value;
+use serde_json::json;
+
+/// `thread.started` is the first line of `--json` output and carries the
+/// model defaults resolved from the preset, so automation does not have to
+/// guess them.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn thread_started_reports_the_resolved_model() -> anyhow::Result<()> {
+    let test = test_codex_exec();
+
+    let server = responses::start_mock_server().await;
+    let body = responses::sse(vec![
+        responses::ev_response_created("response_1"),
+        responses::ev_assistant_message("response_1", "Hello, world!"),
+        responses::ev_completed("response_1"),
+    ]);
+    responses::mount_sse_once(&server, body).await;
+
+    let output = test
+        .cmd_with_server(&server)
+        .arg("--skip-git-repo-check")
+        .arg("--json")
+        .arg("-m")
+        .arg("gpt-5.1-codex-max")
+        .arg("tell me something")
+        .output()?;
+    assert!(output.status.success(), "codex-exec failed: {output:?}");
+
+    let stdout = String::from_utf8(output.stdout)?;
+    let first_line = stdout.lines().next().expect("at least one JSONL line");
+    let event: Value = serde_json::from_str(first_line)?;
+    assert_eq!(event["type"], "thread.started");
+
+    let model = &event["model"];
+    assert_eq!(
+        json!({
+            "slug": model["slug"],
+            "preset_id": model["preset"]["id"],
+            "preset_model": model["preset"]["model"],
+            "display_name": model["preset"]["display_name"],
+            "reasoning_effort": model["reasoning_effort"],
+            "context_window": model["context_window"],
+            "catalog": model["catalog"],
+        }),
+        json!({
+            "slug": "gpt-5.1-codex-max",
+            "preset_id": "gpt-5.1-codex-max",
+            "preset_model": "gpt-5.1-codex-max",
+            "display_name": "gpt-5.1-codex-max",
+            "reasoning_effort": "medium",
+            "context_window": 272_000,
+            "catalog": {"kind": "builtin"},
+        })
+    );
+    assert!(
+        model["preset"]["supported_reasoning_efforts"]
+            .as_array()
+            .is_some_and(|efforts| efforts.contains(&json!("medium"))),
+        "preset should list its efforts: {model}"
+    );
+
+    Ok(())
+}
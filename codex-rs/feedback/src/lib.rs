@@ -210,6 +210,12 @@ impl CodexLogSnapshot {
         &self.bytes
     }
 
+    /// Tags recorded through the `feedback_tags` target, as they will be
+    /// attached to the upload.
+    pub fn tags(&self) -> &BTreeMap<String, String> {
+        &self.tags
+    }
+
     pub fn save_to_temp_file(&self) -> io::Result<PathBuf> {
         let dir = std::env::temp_dir();
         let filename = format!("codex-feedback-{}.log", self.thread_id);
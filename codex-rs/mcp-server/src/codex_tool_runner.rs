@@ -5,6 +5,7 @@
 use std::collections::HashMap;
 use std::sync::Arc;
 
+use crate::cost_limit_approval::handle_cost_limit_approval_request;
 use crate::exec_approval::handle_exec_approval_request;
 use crate::outgoing_message::OutgoingMessageSender;
 use crate::outgoing_message::OutgoingNotificationMeta;
@@ -211,6 +212,19 @@ async fn run_codex_tool_session_inner(
                         // TODO: forward elicitation requests to the client?
                         continue;
                     }
+                    EventMsg::CostLimitApprovalRequest(ev) => {
+                        handle_cost_limit_approval_request(
+                            ev.cost_usd,
+                            ev.limit_usd,
+                            outgoing.clone(),
+                            codex.clone(),
+                            request_id.clone(),
+                            request_id_str.clone(),
+                            ev.turn_id,
+                        )
+                        .await;
+                        continue;
+                    }
                     EventMsg::ApplyPatchApprovalRequest(ApplyPatchApprovalRequestEvent {
                         call_id,
                         turn_id: _,
@@ -286,6 +300,7 @@ async fn run_codex_tool_session_inner(
                     | EventMsg::ExecCommandEnd(_)
                     | EventMsg::BackgroundEvent(_)
                     | EventMsg::StreamError(_)
+                    | EventMsg::ModelChanged(_)
                     | EventMsg::PatchApplyBegin(_)
                     | EventMsg::PatchApplyEnd(_)
                     | EventMsg::TurnDiff(_)
@@ -0,0 +1,119 @@
+use std::sync::Arc;
+
+use codex_core::CodexConversation;
+use codex_core::protocol::Op;
+use codex_core::protocol::ReviewDecision;
+use mcp_types::ElicitRequest;
+use mcp_types::ElicitRequestParamsRequestedSchema;
+use mcp_types::JSONRPCErrorError;
+use mcp_types::ModelContextProtocolRequest;
+use mcp_types::RequestId;
+use serde::Deserialize;
+use serde::Serialize;
+use serde_json::json;
+use tracing::error;
+
+use crate::codex_tool_runner::INVALID_PARAMS_ERROR_CODE;
+use crate::outgoing_message::OutgoingMessageSender;
+
+#[derive(Debug, Serialize)]
+pub struct CostLimitApprovalElicitRequestParams {
+    pub message: String,
+    #[serde(rename = "requestedSchema")]
+    pub requested_schema: ElicitRequestParamsRequestedSchema,
+    pub codex_elicitation: String,
+    pub codex_mcp_tool_call_id: String,
+    pub codex_event_id: String,
+    pub codex_cost_usd: f64,
+    pub codex_limit_usd: f64,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct CostLimitApprovalResponse {
+    pub decision: ReviewDecision,
+}
+
+pub(crate) async fn handle_cost_limit_approval_request(
+    cost_usd: f64,
+    limit_usd: f64,
+    outgoing: Arc<OutgoingMessageSender>,
+    codex: Arc<CodexConversation>,
+    request_id: RequestId,
+    tool_call_id: String,
+    event_id: String,
+) {
+    let params = CostLimitApprovalElicitRequestParams {
+        message: format!(
+            "Estimated session cost is ${cost_usd:.2}, past the ${limit_usd:.2} limit. Allow Codex to keep going?"
+        ),
+        requested_schema: ElicitRequestParamsRequestedSchema {
+            r#type: "object".to_string(),
+            properties: json!({}),
+            required: None,
+        },
+        codex_elicitation: "cost-limit-approval".to_string(),
+        codex_mcp_tool_call_id: tool_call_id,
+        codex_event_id: event_id.clone(),
+        codex_cost_usd: cost_usd,
+        codex_limit_usd: limit_usd,
+    };
+    let params_json = match serde_json::to_value(&params) {
+        Ok(value) => value,
+        Err(err) => {
+            let message =
+                format!("Failed to serialize CostLimitApprovalElicitRequestParams: {err}");
+            error!("{message}");
+
+            outgoing
+                .send_error(
+                    request_id.clone(),
+                    JSONRPCErrorError {
+                        code: INVALID_PARAMS_ERROR_CODE,
+                        message,
+                        data: None,
+                    },
+                )
+                .await;
+
+            return;
+        }
+    };
+
+    let on_response = outgoing
+        .send_request(ElicitRequest::METHOD, Some(params_json))
+        .await;
+
+    // Listen for the response on a separate task so we don't block the main agent loop.
+    tokio::spawn(async move {
+        on_cost_limit_approval_response(event_id, on_response, codex).await;
+    });
+}
+
+async fn on_cost_limit_approval_response(
+    event_id: String,
+    receiver: tokio::sync::oneshot::Receiver<mcp_types::Result>,
+    codex: Arc<CodexConversation>,
+) {
+    let decision = match receiver.await {
+        Ok(value) => serde_json::from_value::<CostLimitApprovalResponse>(value)
+            .map(|response| response.decision)
+            .unwrap_or_else(|err| {
+                error!("failed to deserialize CostLimitApprovalResponse: {err}");
+                ReviewDecision::Denied
+            }),
+        Err(err) => {
+            error!("request failed: {err:?}");
+            ReviewDecision::Denied
+        }
+    };
+
+    if let Err(err) = codex
+        .submit(Op::CostLimitApproval {
+            id: event_id,
+            decision,
+        })
+        .await
+    {
+        error!("failed to submit CostLimitApproval: {err}");
+    }
+}
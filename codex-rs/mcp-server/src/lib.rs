@@ -21,6 +21,7 @@ use tracing_subscriber::EnvFilter;
 
 mod codex_tool_config;
 mod codex_tool_runner;
+mod cost_limit_approval;
 mod error_code;
 mod exec_approval;
 pub(crate) mod message_processor;
@@ -33,6 +34,8 @@ use crate::outgoing_message::OutgoingMessageSender;
 
 pub use crate::codex_tool_config::CodexToolCallParam;
 pub use crate::codex_tool_config::CodexToolCallReplyParam;
+pub use crate::cost_limit_approval::CostLimitApprovalElicitRequestParams;
+pub use crate::cost_limit_approval::CostLimitApprovalResponse;
 pub use crate::exec_approval::ExecApprovalElicitRequestParams;
 pub use crate::exec_approval::ExecApprovalResponse;
 pub use crate::patch_approval::PatchApprovalElicitRequestParams;
@@ -259,6 +259,8 @@ mod tests {
                 session_id: conversation_id,
                 model: "gpt-4o".to_string(),
                 model_provider_id: "test-provider".to_string(),
+                model_catalog_offline: false,
+                instructions_addendum_active: false,
                 approval_policy: AskForApproval::Never,
                 sandbox_policy: SandboxPolicy::ReadOnly,
                 cwd: PathBuf::from("/home/user/project"),
@@ -267,6 +269,7 @@ mod tests {
                 history_entry_count: 1000,
                 initial_messages: None,
                 rollout_path: rollout_file.path().to_path_buf(),
+                disabled_tools: Vec::new(),
             }),
         };
 
@@ -298,6 +301,9 @@ mod tests {
             session_id: conversation_id,
             model: "gpt-4o".to_string(),
             model_provider_id: "test-provider".to_string(),
+            model_catalog_offline: false,
+            instructions_addendum_active: false,
+            disabled_tools: Vec::new(),
             approval_policy: AskForApproval::Never,
             sandbox_policy: SandboxPolicy::ReadOnly,
             cwd: PathBuf::from("/home/user/project"),
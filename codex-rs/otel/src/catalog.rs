@@ -0,0 +1,188 @@
+//! Optional events about the model catalog: what each catalog fetch returned
+//! and which model a session selected. They are only emitted when telemetry is
+//! exported, and never carry credentials, URLs, or model descriptions.
+
+use std::borrow::Cow;
+use std::fmt;
+use std::io;
+use std::path::Path;
+use std::time::Duration;
+
+use hmac::Hmac;
+use hmac::Mac;
+use sha2::Sha256;
+use strum_macros::Display;
+
+use crate::otel_manager::timestamp;
+
+/// How the catalog events are emitted, from the `[otel]` config.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CatalogTelemetry {
+    /// Emit the events at all. Off unless an exporter is configured.
+    pub enabled: bool,
+    /// Report model and preset ids keyed with this rather than as written.
+    pub model_id_key: Option<ModelIdKey>,
+}
+
+/// File under `CODEX_HOME` holding the install's [`ModelIdKey`].
+pub const MODEL_ID_KEY_FILE: &str = "telemetry_model_id.key";
+
+/// Secret key of the HMAC that stands in for model and preset ids, created
+/// once per install so that the hashes of well-known model names cannot be
+/// looked up by anyone without it.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct ModelIdKey([u8; 32]);
+
+impl ModelIdKey {
+    /// The key kept in [`MODEL_ID_KEY_FILE`] under `codex_home`, written
+    /// there first if it is missing or unreadable.
+    pub fn load_or_create(codex_home: &Path) -> io::Result<Self> {
+        let path = codex_home.join(MODEL_ID_KEY_FILE);
+        if let Some(key) = std::fs::read_to_string(&path)
+            .ok()
+            .and_then(|hex| Self::from_hex(hex.trim()))
+        {
+            return Ok(key);
+        }
+        let key = Self::random();
+        std::fs::create_dir_all(codex_home)?;
+        std::fs::write(&path, key.to_hex())?;
+        Ok(key)
+    }
+
+    /// A key of this process only, for when none can be stored. Ids hashed
+    /// with it stay hidden but differ from run to run.
+    pub fn random() -> Self {
+        Self(rand::random())
+    }
+
+    fn from_hex(hex: &str) -> Option<Self> {
+        let mut key = [0u8; 32];
+        if hex.len() != key.len() * 2 {
+            return None;
+        }
+        for (byte, pair) in key.iter_mut().zip(hex.as_bytes().chunks(2)) {
+            *byte = u8::from_str_radix(std::str::from_utf8(pair).ok()?, 16).ok()?;
+        }
+        Some(Self(key))
+    }
+
+    fn to_hex(self) -> String {
+        self.0.iter().map(|byte| format!("{byte:02x}")).collect()
+    }
+}
+
+impl fmt::Debug for ModelIdKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("ModelIdKey(..)")
+    }
+}
+
+/// What chose the model a session uses.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Display)]
+#[strum(serialize_all = "snake_case")]
+pub enum ModelSelectionSource {
+    /// `model` in config or on the command line.
+    Config,
+    /// The catalog default, since nothing set a model.
+    Default,
+    /// The user switched models during the session.
+    User,
+}
+
+impl CatalogTelemetry {
+    /// `id` as it is reported: unchanged, or hashed with
+    /// [`hash_model_id`] when `model_id_key` is set.
+    pub fn model_id<'a>(&self, id: &'a str) -> Cow<'a, str> {
+        match &self.model_id_key {
+            Some(key) => Cow::Owned(hash_model_id(key, id)),
+            None => Cow::Borrowed(id),
+        }
+    }
+
+    /// Record that a catalog fetch from `source` finished with `model_count`
+    /// models, leaving the catalog at `revision`.
+    pub fn catalog_fetch_completed(
+        &self,
+        source: &str,
+        model_count: usize,
+        revision: u64,
+        duration: Duration,
+    ) {
+        if !self.enabled {
+            return;
+        }
+        tracing::event!(
+            tracing::Level::INFO,
+            event.name = "codex.catalog_fetch_completed",
+            event.timestamp = %timestamp(),
+            app.version = %env!("CARGO_PKG_VERSION"),
+            catalog.source = source,
+            catalog.model_count = model_count,
+            catalog.revision = revision,
+            duration_ms = %duration.as_millis(),
+        );
+    }
+}
+
+/// A stand-in for a model or preset id that is stable for one install and
+/// cannot be reversed without its key: `hmac:` followed by the first 16 hex
+/// digits of the id's HMAC-SHA256 under `key`.
+pub fn hash_model_id(key: &ModelIdKey, id: &str) -> String {
+    #[expect(clippy::expect_used)]
+    let mut mac = Hmac::<Sha256>::new_from_slice(&key.0).expect("HMAC takes keys of any length");
+    mac.update(id.as_bytes());
+    let hex: String = mac
+        .finalize()
+        .into_bytes()
+        .iter()
+        .take(8)
+        .map(|byte| format!("{byte:02x}"))
+        .collect();
+    format!("hmac:{hex}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use tempfile::TempDir;
+
+    #[test]
+    fn hashed_ids_are_stable_per_key_and_hide_the_id() {
+        let key = ModelIdKey::random();
+        let hashing = CatalogTelemetry {
+            enabled: true,
+            model_id_key: Some(key),
+        };
+        let hashed = hashing.model_id("gpt-5.1-codex");
+        assert_eq!(hashed, hash_model_id(&key, "gpt-5.1-codex"));
+        assert!(hashed.starts_with("hmac:"));
+        assert_eq!(hashed.len(), "hmac:".len() + 16);
+        assert!(!hashed.contains("gpt"));
+        assert_ne!(hashed, hashing.model_id("gpt-5.1"));
+        assert_ne!(
+            hashed,
+            hash_model_id(&ModelIdKey::random(), "gpt-5.1-codex"),
+            "another install hashes the same id differently"
+        );
+
+        let plain = CatalogTelemetry {
+            enabled: true,
+            model_id_key: None,
+        };
+        assert_eq!(plain.model_id("gpt-5.1-codex"), "gpt-5.1-codex");
+    }
+
+    #[test]
+    fn the_key_is_created_once_per_codex_home() {
+        let codex_home = TempDir::new().expect("temp home");
+        let created = ModelIdKey::load_or_create(codex_home.path()).expect("create key");
+        let loaded = ModelIdKey::load_or_create(codex_home.path()).expect("load key");
+        assert_eq!(created, loaded);
+
+        std::fs::write(codex_home.path().join(MODEL_ID_KEY_FILE), "not a key").expect("write");
+        let replaced = ModelIdKey::load_or_create(codex_home.path()).expect("replace key");
+        assert_ne!(replaced, created);
+    }
+}
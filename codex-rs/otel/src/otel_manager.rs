@@ -1,3 +1,5 @@
+use crate::catalog::CatalogTelemetry;
+use crate::catalog::ModelSelectionSource;
 use crate::otel_provider::traceparent_context_from_env;
 use chrono::SecondsFormat;
 use chrono::Utc;
@@ -46,6 +48,7 @@ pub struct OtelEventMetadata {
     log_user_prompts: bool,
     app_version: &'static str,
     terminal_type: String,
+    catalog: CatalogTelemetry,
 }
 
 #[derive(Debug, Clone)]
@@ -84,6 +87,7 @@ impl OtelManager {
                 log_user_prompts,
                 app_version: env!("CARGO_PKG_VERSION"),
                 terminal_type,
+                catalog: CatalogTelemetry::default(),
             },
             session_span,
         }
@@ -91,11 +95,21 @@ impl OtelManager {
 
     pub fn with_model(&self, model: &str, slug: &str) -> Self {
         let mut manager = self.clone();
-        manager.metadata.model = model.to_owned();
-        manager.metadata.slug = slug.to_owned();
+        let catalog = manager.metadata.catalog;
+        manager.metadata.model = catalog.model_id(model).into_owned();
+        manager.metadata.slug = catalog.model_id(slug).into_owned();
         manager
     }
 
+    /// Emit the catalog events according to `catalog`. When it hashes model
+    /// ids, the model and slug of every event are hashed too.
+    pub fn with_catalog_telemetry(mut self, catalog: CatalogTelemetry) -> Self {
+        self.metadata.model = catalog.model_id(&self.metadata.model).into_owned();
+        self.metadata.slug = catalog.model_id(&self.metadata.slug).into_owned();
+        self.metadata.catalog = catalog;
+        self
+    }
+
     pub fn current_span(&self) -> &Span {
         &self.session_span
     }
@@ -157,6 +171,34 @@ impl OtelManager {
         )
     }
 
+    /// Record that the session selected `model`, listed in the catalog as
+    /// `preset_id`. Only emitted when catalog telemetry is enabled.
+    pub fn model_selected(
+        &self,
+        model: &str,
+        preset_id: &str,
+        reasoning_effort: Option<ReasoningEffort>,
+        source: ModelSelectionSource,
+    ) {
+        let catalog = self.metadata.catalog;
+        if !catalog.enabled {
+            return;
+        }
+        tracing::event!(
+            tracing::Level::INFO,
+            event.name = "codex.model_selected",
+            event.timestamp = %timestamp(),
+            conversation.id = %self.metadata.conversation_id,
+            app.version = %self.metadata.app_version,
+            auth_mode = self.metadata.auth_mode,
+            terminal.type = %self.metadata.terminal_type,
+            model = %catalog.model_id(model),
+            preset_id = %catalog.model_id(preset_id),
+            reasoning_effort = reasoning_effort.map(|e| e.to_string()),
+            selection.source = %source,
+        );
+    }
+
     pub async fn log_request<F, Fut>(&self, attempt: u64, f: F) -> Result<Response, Error>
     where
         F: FnOnce() -> Fut,
@@ -532,6 +574,6 @@ impl OtelManager {
     }
 }
 
-fn timestamp() -> String {
+pub(crate) fn timestamp() -> String {
     Utc::now().to_rfc3339_opts(SecondsFormat::Millis, true)
 }
@@ -59,6 +59,18 @@ pub struct ExecApprovalRequestEvent {
     pub parsed_cmd: Vec<ParsedCommand>,
 }
 
+/// Asks whether to go on once a session's estimated cost reached
+/// `session_cost_limit_usd`, before a turn sends its first model request.
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
+pub struct CostLimitApprovalRequestEvent {
+    /// Turn ID that is waiting for the decision.
+    pub turn_id: String,
+    /// Estimated cost of the session so far, in USD.
+    pub cost_usd: f64,
+    /// The configured `session_cost_limit_usd`.
+    pub limit_usd: f64,
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
 pub struct ElicitationRequestEvent {
     pub server_name: String,
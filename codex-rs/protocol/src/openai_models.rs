@@ -1,33 +1,24 @@
 use std::collections::HashMap;
+use std::str::FromStr;
 
 use schemars::JsonSchema;
 use serde::Deserialize;
 use serde::Serialize;
-use strum::IntoEnumIterator;
 use strum_macros::Display;
 use strum_macros::EnumIter;
 use ts_rs::TS;
 
 use crate::config_types::Verbosity;
+use crate::config_types::WireApi;
+use crate::protocol::TokenUsage;
 
 /// See https://platform.openai.com/docs/guides/reasoning?api-mode=responses#get-started-with-reasoning
-#[derive(
-    Debug,
-    Serialize,
-    Deserialize,
-    Default,
-    Clone,
-    Copy,
-    PartialEq,
-    Eq,
-    Display,
-    JsonSchema,
-    TS,
-    EnumIter,
-    Hash,
-)]
+///
+/// The lowercase names from [`ReasoningEffort::as_str`] are the one spelling
+/// used in config, on the wire, and in flags and environment variables.
+/// Parsing ignores case.
+#[derive(Debug, Serialize, Default, Clone, Copy, PartialEq, Eq, JsonSchema, TS, EnumIter, Hash)]
 #[serde(rename_all = "lowercase")]
-#[strum(serialize_all = "lowercase")]
 pub enum ReasoningEffort {
     None,
     Minimal,
@@ -38,6 +29,207 @@ pub enum ReasoningEffort {
     XHigh,
 }
 
+impl ReasoningEffort {
+    /// Every effort, lowest [`rank`](Self::rank) first.
+    pub const VARIANTS: [ReasoningEffort; 6] = [
+        ReasoningEffort::None,
+        ReasoningEffort::Minimal,
+        ReasoningEffort::Low,
+        ReasoningEffort::Medium,
+        ReasoningEffort::High,
+        ReasoningEffort::XHigh,
+    ];
+
+    /// The lowercase name, e.g. `xhigh`.
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            ReasoningEffort::None => "none",
+            ReasoningEffort::Minimal => "minimal",
+            ReasoningEffort::Low => "low",
+            ReasoningEffort::Medium => "medium",
+            ReasoningEffort::High => "high",
+            ReasoningEffort::XHigh => "xhigh",
+        }
+    }
+
+    /// Where `self` falls from least to most reasoning: `None` < `Minimal` <
+    /// `Low` < `Medium` < `High` < `XHigh`. Comparisons between efforts go
+    /// through this rather than the order the variants are declared in, and
+    /// a new variant has to be given its place here.
+    pub const fn rank(self) -> u8 {
+        match self {
+            ReasoningEffort::None => 0,
+            ReasoningEffort::Minimal => 1,
+            ReasoningEffort::Low => 2,
+            ReasoningEffort::Medium => 3,
+            ReasoningEffort::High => 4,
+            ReasoningEffort::XHigh => 5,
+        }
+    }
+
+    /// Whether `self` lies within `min..=max`. A missing bound does not limit.
+    pub fn is_within(self, min: Option<Self>, max: Option<Self>) -> bool {
+        let rank = self.rank();
+        min.is_none_or(|min| rank >= min.rank()) && max.is_none_or(|max| rank <= max.rank())
+    }
+
+    /// The entry of `supported` closest to `self`, the first listed on a tie.
+    pub fn nearest_in(self, supported: &[Self]) -> Option<Self> {
+        let rank = self.rank();
+        supported
+            .iter()
+            .copied()
+            .min_by_key(|candidate| candidate.rank().abs_diff(rank))
+    }
+
+    /// `self` moved into `min..=max`: raised to `min` when below it, lowered
+    /// to `max` when above it.
+    pub fn clamp_to(self, min: Option<Self>, max: Option<Self>) -> Self {
+        match (min, max) {
+            (Some(min), _) if self.rank() < min.rank() => min,
+            (_, Some(max)) if self.rank() > max.rank() => max,
+            _ => self,
+        }
+    }
+}
+
+impl std::fmt::Display for ReasoningEffort {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// A string that names no [`ReasoningEffort`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseReasoningEffortError {
+    input: String,
+}
+
+impl std::fmt::Display for ParseReasoningEffortError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "unknown reasoning effort `{}`; expected one of: {}",
+            self.input,
+            effort_names()
+        )
+    }
+}
+
+impl std::error::Error for ParseReasoningEffortError {}
+
+impl FromStr for ReasoningEffort {
+    type Err = ParseReasoningEffortError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let name = s.trim();
+        Self::VARIANTS
+            .into_iter()
+            .find(|effort| effort.as_str().eq_ignore_ascii_case(name))
+            .ok_or_else(|| ParseReasoningEffortError {
+                input: s.to_string(),
+            })
+    }
+}
+
+impl<'de> Deserialize<'de> for ReasoningEffort {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = String::deserialize(deserializer)?;
+        value.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+/// The effort names, comma-separated, for error messages.
+fn effort_names() -> String {
+    ReasoningEffort::VARIANTS
+        .map(ReasoningEffort::as_str)
+        .join(", ")
+}
+
+/// How the reasoning effort of a session was chosen. `Auto` defers to the
+/// active model's default, so switching models changes the effort without the
+/// user picking it again. Written as `"auto"` or an effort name in config.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum EffortSelection {
+    #[default]
+    Auto,
+    Explicit(ReasoningEffort),
+}
+
+impl EffortSelection {
+    /// How `Auto` is spelled in config files and labels.
+    pub const AUTO: &'static str = "auto";
+
+    /// The explicitly chosen effort, or `None` for `Auto`.
+    pub fn explicit(self) -> Option<ReasoningEffort> {
+        match self {
+            EffortSelection::Auto => None,
+            EffortSelection::Explicit(effort) => Some(effort),
+        }
+    }
+
+    /// The effort to run at when the active model defaults to `default`.
+    pub fn resolve(self, default: ReasoningEffort) -> ReasoningEffort {
+        self.explicit().unwrap_or(default)
+    }
+
+    /// A label such as `auto (medium)` or `high`.
+    pub fn label(self, default: ReasoningEffort) -> String {
+        match self {
+            EffortSelection::Auto => format!("{} ({default})", Self::AUTO),
+            EffortSelection::Explicit(effort) => effort.to_string(),
+        }
+    }
+}
+
+impl From<Option<ReasoningEffort>> for EffortSelection {
+    fn from(effort: Option<ReasoningEffort>) -> Self {
+        effort.map_or(EffortSelection::Auto, EffortSelection::Explicit)
+    }
+}
+
+impl FromStr for EffortSelection {
+    type Err = ParseReasoningEffortError;
+
+    /// `auto` or an effort name, ignoring case.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.trim().eq_ignore_ascii_case(Self::AUTO) {
+            return Ok(EffortSelection::Auto);
+        }
+        s.parse().map(EffortSelection::Explicit)
+    }
+}
+
+impl std::fmt::Display for EffortSelection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            EffortSelection::Auto => f.write_str(Self::AUTO),
+            EffortSelection::Explicit(effort) => effort.fmt(f),
+        }
+    }
+}
+
+impl Serialize for EffortSelection {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            EffortSelection::Auto => serializer.serialize_str(Self::AUTO),
+            EffortSelection::Explicit(effort) => effort.serialize(serializer),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for EffortSelection {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = String::deserialize(deserializer)?;
+        value.parse().map_err(|_| {
+            serde::de::Error::custom(format!(
+                "unknown reasoning effort `{value}`; expected `auto` or one of: {}",
+                effort_names()
+            ))
+        })
+    }
+}
+
 /// A reasoning effort option that can be surfaced for a model.
 #[derive(Debug, Clone, Deserialize, Serialize, TS, JsonSchema, PartialEq, Eq)]
 pub struct ReasoningEffortPreset {
@@ -45,6 +237,41 @@ pub struct ReasoningEffortPreset {
     pub effort: ReasoningEffort,
     /// Short human description shown next to the effort in UIs.
     pub description: String,
+    /// Output-token cap for requests made at this effort. Takes precedence
+    /// over `model_max_output_tokens` and the model's own default.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_output_tokens: Option<i64>,
+    /// Longest a whole request at this effort may take, from sending it to
+    /// the last streamed event. Takes precedence over the preset's
+    /// `request_timeout_secs`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub request_timeout_secs: Option<u64>,
+    /// How long a streamed response at this effort may go without an event
+    /// before it is abandoned. Takes precedence over the preset's
+    /// `stream_idle_timeout_secs` and the provider's `stream_idle_timeout_ms`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stream_idle_timeout_secs: Option<u64>,
+}
+
+/// Default reasoning efforts that apply instead of a preset's
+/// `default_reasoning_effort` for one way of signing in. Keys match the
+/// serialized auth modes: `apikey` and `chatgpt`.
+#[derive(Debug, Clone, Copy, Default, Deserialize, Serialize, TS, JsonSchema, PartialEq, Eq)]
+pub struct EffortByAuth {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub apikey: Option<ReasoningEffort>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub chatgpt: Option<ReasoningEffort>,
+}
+
+impl EffortByAuth {
+    /// Fill in the auth modes `self` leaves unset from `fallback`.
+    pub fn or(self, fallback: EffortByAuth) -> EffortByAuth {
+        EffortByAuth {
+            apikey: self.apikey.or(fallback.apikey),
+            chatgpt: self.chatgpt.or(fallback.chatgpt),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, TS, JsonSchema, PartialEq)]
@@ -57,7 +284,16 @@ pub struct ModelUpgrade {
 }
 
 /// Metadata describing a Codex-supported model.
+///
+/// Cloning a preset copies every string, list, and map it holds, which is
+/// far from free in a render loop. Code that only displays a preset should
+/// borrow it, or take the [`PresetRef`] returned by [`ModelPreset::view`],
+/// which is `Copy` and allocates nothing.
+///
+/// Code outside this crate builds presets with [`ModelPreset::builder`], so
+/// adding a field is not a breaking change.
 #[derive(Debug, Clone, Deserialize, Serialize, TS, JsonSchema, PartialEq)]
+#[non_exhaustive]
 pub struct ModelPreset {
     /// Stable identifier for the preset.
     pub id: String,
@@ -69,6 +305,10 @@ pub struct ModelPreset {
     pub description: String,
     /// Reasoning effort applied when none is explicitly chosen.
     pub default_reasoning_effort: ReasoningEffort,
+    /// Replaces `default_reasoning_effort` for sessions signed in one way,
+    /// e.g. a lower default for API keys, which are billed per token.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_reasoning_effort_by_auth: Option<EffortByAuth>,
     /// Supported reasoning effort options.
     pub supported_reasoning_efforts: Vec<ReasoningEffortPreset>,
     /// Whether this is the default model for new users.
@@ -79,6 +319,770 @@ pub struct ModelPreset {
     pub show_in_picker: bool,
     /// whether this model is supported in the api
     pub supported_in_api: bool,
+    /// Id of the `model_providers` entry that serves this preset. Unset for
+    /// presets that come from the built-in list or the remote models catalog.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model_provider: Option<String>,
+    /// Model that serves `model` behind a gateway alias or an Azure
+    /// deployment, which requests never name. Its built-in metadata is used
+    /// for the preset when the catalog serves none of its own.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub upstream_model: Option<String>,
+    /// Extra system instructions appended to the base instructions while this
+    /// preset is the active model.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub instructions_addendum: Option<String>,
+    /// Wire API to use for this preset instead of the provider's `wire_api`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub wire_api: Option<WireApi>,
+    /// Token prices used to estimate session cost for this preset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pricing: Option<ModelPricing>,
+    /// Context window in tokens, when the catalog reports one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub context_window: Option<i64>,
+    /// Where a `/models` entry's context window came from and in what unit.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub context_window_source: Option<ContextWindowSource>,
+    /// Fraction of `context_window` at which the conversation is compacted
+    /// automatically, in place of the default 0.9. Small windows want a lower
+    /// ratio so compaction leaves room for the next turn.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub compaction_threshold_ratio: Option<f64>,
+    /// Most input tokens one summarization request may carry when the
+    /// conversation is compacted; a longer history is summarized in several
+    /// passes. Defaults to `context_window`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub summarization_max_tokens: Option<i64>,
+    /// Whether requests to this preset may set `temperature`. Reasoning
+    /// models reject it. Unset keeps the model family's answer.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub supports_temperature: Option<bool>,
+    /// Temperature sent when the user has not configured one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_temperature: Option<f32>,
+    /// Whether requests to this preset may set `top_p`. Unset keeps the
+    /// model family's answer.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub supports_top_p: Option<bool>,
+    /// `top_p` sent when the user has not configured one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_top_p: Option<f32>,
+    /// Whether the model accepts images in its input. Assumed unless the
+    /// catalog says otherwise.
+    #[serde(default = "default_supports_images")]
+    pub supports_images: bool,
+    /// Whether the model copes with several tool calls in one turn. `false`
+    /// turns parallel tool calls off for this preset even when they are
+    /// enabled globally; `None` leaves the decision to the model family.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub supports_parallel_tool_calls: Option<bool>,
+    /// Whether the model emits reasoning summaries. `false` leaves the
+    /// summary out of requests and the summary setting out of the UI;
+    /// `None` is unknown and treated as supported.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub supports_reasoning_summaries: Option<bool>,
+    /// Whether the model takes reasoning parameters at all. `false` marks
+    /// plain chat models, which reject requests that carry any: requests
+    /// leave reasoning out entirely and a configured effort is ignored.
+    /// `None` leaves it to the model family.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub supports_reasoning: Option<bool>,
+    /// How the model writes its reasoning summaries, for rendering them when
+    /// `model_reasoning_summary_format` is not configured. `None` is the
+    /// default, [`ReasoningSummaryFormat::Headed`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reasoning_summary_format: Option<ReasoningSummaryFormat>,
+    /// Whether the model accepts stateful Responses API requests, which
+    /// store their output and refer to earlier items by id. `Some(false)`
+    /// sends every turn stateless, with the full context inline. `None`
+    /// follows the provider: stateful on Azure, stateless elsewhere.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub supports_stateful_responses: Option<bool>,
+    /// Whether the model needs the sandbox to allow network access, e.g.
+    /// because it runs tools that fetch from the web. Codex warns before a
+    /// turn when the sandbox blocks the network but never changes it.
+    #[serde(default)]
+    pub requires_network: bool,
+    /// Most requests to this model allowed in flight at once, across every
+    /// conversation in the process. `None` (or `0`) leaves them unbounded.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_concurrent_requests: Option<u32>,
+    /// Longest a whole request to this model may take, from sending it to
+    /// the last streamed event, for efforts that do not set their own.
+    /// `None` leaves requests bounded only by the stream idle timeout.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub request_timeout_secs: Option<u64>,
+    /// How long a streamed response from this model may go without an event
+    /// before it is abandoned, for efforts that do not set their own. Takes
+    /// precedence over the provider's `stream_idle_timeout_ms`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stream_idle_timeout_secs: Option<u64>,
+    /// Headers added to every model request made with this preset, such as
+    /// gateway routing hints. Values may reference `${ENV_VAR}`s.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub extra_request_headers: Option<HashMap<String, String>>,
+    /// Which `prompt_cache_key` requests made with this preset carry. Unset
+    /// keeps one key per conversation.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prompt_cache_key_strategy: Option<PromptCacheKeyStrategy>,
+    /// Models to retry a turn on, in order, when this one is at capacity.
+    /// The first one the catalog lists is used, for that turn only.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fallback_models: Option<Vec<String>>,
+    /// Names of tools, such as `web_search`, left out of the tool list while
+    /// this preset is active, for models hosted where policy forbids them.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub disabled_tools: Vec<String>,
+    /// Whether `apply_patch` is offered to this model as a freeform tool
+    /// (`true`) or a function tool (`false`), unless the config sets
+    /// `apply_patch_freeform`. `None` leaves it to the model family.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prefer_apply_patch_freeform: Option<bool>,
+    /// Whether the model gets the web search tool, unless the config sets
+    /// `web_search_request`. `None` leaves it to the feature's default.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub enable_web_search: Option<bool>,
+    /// Date the model's training data ends, as an ISO date such as
+    /// `2024-09-30`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub knowledge_cutoff: Option<String>,
+    /// Date the model was released, as an ISO date.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub released_at: Option<String>,
+    /// Set when the provider recently refused this model to the signed-in
+    /// account, with the provider's reason. Filled in by the catalog, never
+    /// read from preset files.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub unavailable_for_account: Option<String>,
+}
+
+fn default_supports_images() -> bool {
+    true
+}
+
+/// Something a model may or may not be able to do, as checked by
+/// [`ModelPreset::supports`].
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Hash,
+    Serialize,
+    Deserialize,
+    Display,
+    EnumIter,
+    JsonSchema,
+    TS,
+)]
+#[serde(rename_all = "lowercase")]
+#[strum(serialize_all = "lowercase")]
+pub enum Capability {
+    /// Images in the input.
+    Images,
+    /// A configurable reasoning effort.
+    Reasoning,
+}
+
+/// A borrowed view of the displayed fields of a [`ModelPreset`]. Copying it
+/// copies a few pointers; nothing is allocated.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PresetRef<'a> {
+    pub id: &'a str,
+    pub model: &'a str,
+    pub display_name: &'a str,
+    pub description: &'a str,
+    pub default_reasoning_effort: ReasoningEffort,
+    pub supported_reasoning_efforts: &'a [ReasoningEffortPreset],
+    pub is_default: bool,
+    pub model_provider: Option<&'a str>,
+    pub knowledge_cutoff: Option<&'a str>,
+    pub released_at: Option<&'a str>,
+    pub supports_reasoning_summaries: Option<bool>,
+    pub unavailable_for_account: Option<&'a str>,
+}
+
+impl ModelPreset {
+    /// Drop the supported efforts outside `min..=max` and move the default
+    /// into that range. When no supported effort is in range, the one nearest
+    /// to it is kept so the model still has an effort to run with.
+    pub fn limit_efforts(&mut self, min: Option<ReasoningEffort>, max: Option<ReasoningEffort>) {
+        let default = self.default_reasoning_effort.clamp_to(min, max);
+        let supported: Vec<ReasoningEffort> = self
+            .supported_reasoning_efforts
+            .iter()
+            .map(|preset| preset.effort)
+            .collect();
+        if supported.iter().any(|effort| effort.is_within(min, max)) {
+            self.supported_reasoning_efforts
+                .retain(|preset| preset.effort.is_within(min, max));
+        } else if !supported.is_empty() {
+            let nearest = default.nearest_in(&supported).unwrap_or(default);
+            self.supported_reasoning_efforts
+                .retain(|preset| preset.effort == nearest);
+        }
+        let supported: Vec<ReasoningEffort> = self
+            .supported_reasoning_efforts
+            .iter()
+            .map(|preset| preset.effort)
+            .collect();
+        self.default_reasoning_effort = if supported.is_empty() {
+            default
+        } else {
+            default.nearest_in(&supported).unwrap_or(default)
+        };
+    }
+
+    /// Drop the supported efforts in `disabled` and move a disabled default
+    /// to the nearest effort left. When every supported effort is disabled,
+    /// none is left: the preset then lists no efforts, like a model that
+    /// takes no effort parameter.
+    pub fn disable_efforts(&mut self, disabled: &[ReasoningEffort]) {
+        self.supported_reasoning_efforts
+            .retain(|preset| !disabled.contains(&preset.effort));
+        let supported: Vec<ReasoningEffort> = self
+            .supported_reasoning_efforts
+            .iter()
+            .map(|preset| preset.effort)
+            .collect();
+        if disabled.contains(&self.default_reasoning_effort)
+            && let Some(nearest) = self.default_reasoning_effort.nearest_in(&supported)
+        {
+            self.default_reasoning_effort = nearest;
+        }
+    }
+
+    /// Borrow the fields UIs display, without cloning anything.
+    pub fn view(&self) -> PresetRef<'_> {
+        PresetRef {
+            id: &self.id,
+            model: &self.model,
+            display_name: &self.display_name,
+            description: &self.description,
+            default_reasoning_effort: self.default_reasoning_effort,
+            supported_reasoning_efforts: &self.supported_reasoning_efforts,
+            is_default: self.is_default,
+            model_provider: self.model_provider.as_deref(),
+            knowledge_cutoff: self.knowledge_cutoff.as_deref(),
+            released_at: self.released_at.as_deref(),
+            supports_reasoning_summaries: self.supports_reasoning_summaries,
+            unavailable_for_account: self.unavailable_for_account.as_deref(),
+        }
+    }
+
+    /// Whether the model has `capability`.
+    pub fn supports(&self, capability: Capability) -> bool {
+        match capability {
+            Capability::Images => self.supports_images,
+            Capability::Reasoning => !self.supported_reasoning_efforts.is_empty(),
+        }
+    }
+
+    /// Start building a preset for `model`, identified by `id`.
+    pub fn builder(id: impl Into<String>, model: impl Into<String>) -> ModelPresetBuilder {
+        ModelPresetBuilder::new(id, model)
+    }
+
+    /// Check what [`ModelPresetBuilder::build`] checks: `id` and `model` are
+    /// set, no stream idle timeout is zero, no effort is listed twice, and
+    /// every default effort is one the preset supports. Presets listing no
+    /// efforts take no effort parameter, so their defaults are not checked.
+    pub fn validate(&self) -> Result<(), ModelPresetError> {
+        for (field, value) in [("id", &self.id), ("model", &self.model)] {
+            if value.trim().is_empty() {
+                return Err(ModelPresetError::MissingField(field));
+            }
+        }
+        let idle_timeouts = std::iter::once((None, self.stream_idle_timeout_secs)).chain(
+            self.supported_reasoning_efforts
+                .iter()
+                .map(|option| (Some(option.effort), option.stream_idle_timeout_secs)),
+        );
+        for (effort, secs) in idle_timeouts {
+            if secs == Some(0) {
+                return Err(ModelPresetError::ZeroStreamIdleTimeout {
+                    id: self.id.clone(),
+                    effort,
+                });
+            }
+        }
+        let mut efforts: Vec<ReasoningEffort> = Vec::new();
+        for option in &self.supported_reasoning_efforts {
+            if efforts.contains(&option.effort) {
+                return Err(ModelPresetError::DuplicateEffort {
+                    id: self.id.clone(),
+                    effort: option.effort,
+                });
+            }
+            efforts.push(option.effort);
+        }
+        if efforts.is_empty() {
+            return Ok(());
+        }
+        let by_auth = self.default_reasoning_effort_by_auth.unwrap_or_default();
+        let defaults = [
+            (None, Some(self.default_reasoning_effort)),
+            (Some("apikey"), by_auth.apikey),
+            (Some("chatgpt"), by_auth.chatgpt),
+        ];
+        for (auth_mode, effort) in defaults {
+            if let Some(effort) = effort
+                && !efforts.contains(&effort)
+            {
+                return Err(ModelPresetError::UnsupportedDefaultEffort {
+                    id: self.id.clone(),
+                    effort,
+                    auth_mode,
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Why a [`ModelPreset`] is invalid.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ModelPresetError {
+    /// `id` or `model` is empty.
+    MissingField(&'static str),
+    DuplicateEffort {
+        id: String,
+        effort: ReasoningEffort,
+    },
+    /// A default effort is not among the supported efforts. `auth_mode` is
+    /// set when it is the default for that auth mode.
+    UnsupportedDefaultEffort {
+        id: String,
+        effort: ReasoningEffort,
+        auth_mode: Option<&'static str>,
+    },
+    /// `stream_idle_timeout_secs` is zero, which would abandon every stream
+    /// at once. `effort` is set when an effort declares it.
+    ZeroStreamIdleTimeout {
+        id: String,
+        effort: Option<ReasoningEffort>,
+    },
+}
+
+impl std::fmt::Display for ModelPresetError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::MissingField(field) => write!(f, "preset has an empty `{field}`"),
+            Self::DuplicateEffort { id, effort } => {
+                write!(
+                    f,
+                    "preset `{id}` lists reasoning effort `{effort}` more than once"
+                )
+            }
+            Self::UnsupportedDefaultEffort {
+                id,
+                effort,
+                auth_mode,
+            } => {
+                write!(f, "preset `{id}` defaults to reasoning effort `{effort}`")?;
+                if let Some(auth_mode) = auth_mode {
+                    write!(f, " for `{auth_mode}`")?;
+                }
+                write!(f, ", which is not in its `supported_reasoning_efforts`")
+            }
+            Self::ZeroStreamIdleTimeout { id, effort } => {
+                write!(f, "preset `{id}` sets `stream_idle_timeout_secs` to 0")?;
+                if let Some(effort) = effort {
+                    write!(f, " on the `{effort}` effort")?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl std::error::Error for ModelPresetError {}
+
+/// Builds a [`ModelPreset`]. Fields left unset take these defaults: the
+/// display name is the id, the description is empty, the default effort is
+/// [`ReasoningEffort::default`] with no efforts listed, the preset is shown in
+/// the picker and supported in the API, it is not the default model, and
+/// every optional field is unset.
+#[derive(Debug, Clone)]
+#[must_use]
+pub struct ModelPresetBuilder {
+    preset: ModelPreset,
+}
+
+impl ModelPresetBuilder {
+    pub fn new(id: impl Into<String>, model: impl Into<String>) -> Self {
+        let id = id.into();
+        Self {
+            preset: ModelPreset {
+                display_name: id.clone(),
+                id,
+                model: model.into(),
+                description: String::new(),
+                default_reasoning_effort: ReasoningEffort::default(),
+                default_reasoning_effort_by_auth: None,
+                supported_reasoning_efforts: Vec::new(),
+                is_default: false,
+                upgrade: None,
+                show_in_picker: true,
+                supported_in_api: true,
+                model_provider: None,
+                upstream_model: None,
+                instructions_addendum: None,
+                wire_api: None,
+                pricing: None,
+                context_window: None,
+                context_window_source: None,
+                compaction_threshold_ratio: None,
+                summarization_max_tokens: None,
+                supports_temperature: None,
+                default_temperature: None,
+                supports_top_p: None,
+                default_top_p: None,
+                supports_images: true,
+                supports_parallel_tool_calls: None,
+                supports_reasoning_summaries: None,
+                supports_reasoning: None,
+                reasoning_summary_format: None,
+                supports_stateful_responses: None,
+                requires_network: false,
+                max_concurrent_requests: None,
+                request_timeout_secs: None,
+                stream_idle_timeout_secs: None,
+                extra_request_headers: None,
+                prompt_cache_key_strategy: None,
+                fallback_models: None,
+                disabled_tools: Vec::new(),
+                prefer_apply_patch_freeform: None,
+                enable_web_search: None,
+                knowledge_cutoff: None,
+                released_at: None,
+                unavailable_for_account: None,
+            },
+        }
+    }
+
+    pub fn display_name(mut self, display_name: impl Into<String>) -> Self {
+        self.preset.display_name = display_name.into();
+        self
+    }
+
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        self.preset.description = description.into();
+        self
+    }
+
+    pub fn default_reasoning_effort(mut self, effort: ReasoningEffort) -> Self {
+        self.preset.default_reasoning_effort = effort;
+        self
+    }
+
+    pub fn default_reasoning_effort_by_auth(
+        mut self,
+        by_auth: impl Into<Option<EffortByAuth>>,
+    ) -> Self {
+        self.preset.default_reasoning_effort_by_auth = by_auth.into();
+        self
+    }
+
+    pub fn supported_reasoning_efforts(mut self, efforts: Vec<ReasoningEffortPreset>) -> Self {
+        self.preset.supported_reasoning_efforts = efforts;
+        self
+    }
+
+    pub fn is_default(mut self, is_default: bool) -> Self {
+        self.preset.is_default = is_default;
+        self
+    }
+
+    pub fn upgrade(mut self, upgrade: impl Into<Option<ModelUpgrade>>) -> Self {
+        self.preset.upgrade = upgrade.into();
+        self
+    }
+
+    pub fn show_in_picker(mut self, show_in_picker: bool) -> Self {
+        self.preset.show_in_picker = show_in_picker;
+        self
+    }
+
+    pub fn supported_in_api(mut self, supported_in_api: bool) -> Self {
+        self.preset.supported_in_api = supported_in_api;
+        self
+    }
+
+    pub fn model_provider(mut self, provider_id: impl Into<Option<String>>) -> Self {
+        self.preset.model_provider = provider_id.into();
+        self
+    }
+
+    pub fn instructions_addendum(mut self, addendum: impl Into<Option<String>>) -> Self {
+        self.preset.instructions_addendum = addendum.into();
+        self
+    }
+
+    pub fn wire_api(mut self, wire_api: impl Into<Option<WireApi>>) -> Self {
+        self.preset.wire_api = wire_api.into();
+        self
+    }
+
+    pub fn pricing(mut self, pricing: impl Into<Option<ModelPricing>>) -> Self {
+        self.preset.pricing = pricing.into();
+        self
+    }
+
+    pub fn context_window(mut self, context_window: impl Into<Option<i64>>) -> Self {
+        self.preset.context_window = context_window.into();
+        self
+    }
+
+    pub fn context_window_source(mut self, source: impl Into<Option<ContextWindowSource>>) -> Self {
+        self.preset.context_window_source = source.into();
+        self
+    }
+
+    pub fn compaction_threshold_ratio(mut self, ratio: impl Into<Option<f64>>) -> Self {
+        self.preset.compaction_threshold_ratio = ratio.into();
+        self
+    }
+
+    pub fn summarization_max_tokens(mut self, tokens: impl Into<Option<i64>>) -> Self {
+        self.preset.summarization_max_tokens = tokens.into();
+        self
+    }
+
+    pub fn supports_temperature(mut self, supports_temperature: impl Into<Option<bool>>) -> Self {
+        self.preset.supports_temperature = supports_temperature.into();
+        self
+    }
+
+    pub fn default_temperature(mut self, temperature: impl Into<Option<f32>>) -> Self {
+        self.preset.default_temperature = temperature.into();
+        self
+    }
+
+    pub fn supports_top_p(mut self, supports_top_p: impl Into<Option<bool>>) -> Self {
+        self.preset.supports_top_p = supports_top_p.into();
+        self
+    }
+
+    pub fn supports_images(mut self, supports_images: bool) -> Self {
+        self.preset.supports_images = supports_images;
+        self
+    }
+
+    pub fn default_top_p(mut self, top_p: impl Into<Option<f32>>) -> Self {
+        self.preset.default_top_p = top_p.into();
+        self
+    }
+
+    pub fn supports_parallel_tool_calls(mut self, supported: impl Into<Option<bool>>) -> Self {
+        self.preset.supports_parallel_tool_calls = supported.into();
+        self
+    }
+
+    pub fn supports_reasoning_summaries(mut self, supported: impl Into<Option<bool>>) -> Self {
+        self.preset.supports_reasoning_summaries = supported.into();
+        self
+    }
+
+    pub fn supports_reasoning(mut self, supported: impl Into<Option<bool>>) -> Self {
+        self.preset.supports_reasoning = supported.into();
+        self
+    }
+
+    pub fn reasoning_summary_format(
+        mut self,
+        format: impl Into<Option<ReasoningSummaryFormat>>,
+    ) -> Self {
+        self.preset.reasoning_summary_format = format.into();
+        self
+    }
+
+    pub fn supports_stateful_responses(mut self, supported: impl Into<Option<bool>>) -> Self {
+        self.preset.supports_stateful_responses = supported.into();
+        self
+    }
+
+    pub fn requires_network(mut self, requires_network: bool) -> Self {
+        self.preset.requires_network = requires_network;
+        self
+    }
+
+    pub fn max_concurrent_requests(mut self, max: impl Into<Option<u32>>) -> Self {
+        self.preset.max_concurrent_requests = max.into();
+        self
+    }
+
+    pub fn request_timeout_secs(mut self, secs: impl Into<Option<u64>>) -> Self {
+        self.preset.request_timeout_secs = secs.into();
+        self
+    }
+
+    pub fn stream_idle_timeout_secs(mut self, secs: impl Into<Option<u64>>) -> Self {
+        self.preset.stream_idle_timeout_secs = secs.into();
+        self
+    }
+
+    pub fn extra_request_headers(
+        mut self,
+        headers: impl Into<Option<HashMap<String, String>>>,
+    ) -> Self {
+        self.preset.extra_request_headers = headers.into();
+        self
+    }
+
+    pub fn prompt_cache_key_strategy(
+        mut self,
+        strategy: impl Into<Option<PromptCacheKeyStrategy>>,
+    ) -> Self {
+        self.preset.prompt_cache_key_strategy = strategy.into();
+        self
+    }
+
+    pub fn fallback_models(mut self, models: impl Into<Option<Vec<String>>>) -> Self {
+        self.preset.fallback_models = models.into();
+        self
+    }
+
+    pub fn disabled_tools<I, S>(mut self, tools: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.preset.disabled_tools = tools.into_iter().map(Into::into).collect();
+        self
+    }
+
+    pub fn prefer_apply_patch_freeform(mut self, freeform: impl Into<Option<bool>>) -> Self {
+        self.preset.prefer_apply_patch_freeform = freeform.into();
+        self
+    }
+
+    pub fn enable_web_search(mut self, enabled: impl Into<Option<bool>>) -> Self {
+        self.preset.enable_web_search = enabled.into();
+        self
+    }
+
+    pub fn upstream_model(mut self, model: impl Into<Option<String>>) -> Self {
+        self.preset.upstream_model = model.into();
+        self
+    }
+
+    pub fn knowledge_cutoff(mut self, date: impl Into<Option<String>>) -> Self {
+        self.preset.knowledge_cutoff = date.into();
+        self
+    }
+
+    pub fn released_at(mut self, date: impl Into<Option<String>>) -> Self {
+        self.preset.released_at = date.into();
+        self
+    }
+
+    pub fn unavailable_for_account(mut self, reason: impl Into<Option<String>>) -> Self {
+        self.preset.unavailable_for_account = reason.into();
+        self
+    }
+
+    /// The preset, once [`ModelPreset::validate`] accepts it.
+    pub fn build(self) -> Result<ModelPreset, ModelPresetError> {
+        self.preset.validate()?;
+        Ok(self.preset)
+    }
+}
+
+/// How requests scope the `prompt_cache_key` that lets a gateway reuse its
+/// prompt cache between them.
+#[derive(
+    Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq, TS, JsonSchema, Display,
+)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum PromptCacheKeyStrategy {
+    /// Send no key, for models whose gateway rejects one.
+    None,
+    /// One key per conversation, stable across its turns.
+    #[default]
+    PerConversation,
+    /// One key per signed-in user, shared by all of their conversations.
+    PerUser,
+}
+
+/// How a model writes its reasoning summaries, which decides how they are
+/// rendered.
+#[derive(
+    Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq, TS, JsonSchema, Display,
+)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum ReasoningSummaryFormat {
+    /// Each section opens with a `**bold**` header, shown as the status while
+    /// the model reasons and left out of the summary kept in the history.
+    /// Older catalogs spell it `experimental`.
+    #[default]
+    #[serde(alias = "experimental")]
+    Headed,
+    /// Prose without section headers, kept in the history as written.
+    /// Older catalogs spell it `none`.
+    #[serde(alias = "none")]
+    Plain,
+}
+
+/// Smallest `context_window` taken as a count of tokens when the entry does
+/// not name its unit. Proxies that report kilotokens send values like `128`,
+/// which as tokens would leave no room for a single turn.
+pub const MIN_UNHINTED_CONTEXT_WINDOW: i64 = 2_048;
+
+/// Unit a catalog entry reports its `context_window` in.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, TS, JsonSchema, Display)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum ContextWindowUnit {
+    Tokens,
+    /// Thousands of tokens: `128` means 128,000.
+    Kilotokens,
+}
+
+/// How a catalog entry's context window was read, as served.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, TS, JsonSchema)]
+pub struct ContextWindowSource {
+    /// Field of the entry the value was read from, e.g. `max_input_tokens`
+    /// or `model_info.context_window`.
+    pub field: String,
+    /// The value as served, before any unit was applied.
+    pub value: i64,
+    /// The entry's `context_window_unit`. Without one the value is taken as
+    /// tokens.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub unit: Option<ContextWindowUnit>,
+    /// Whether the value was ignored: it is below
+    /// [`MIN_UNHINTED_CONTEXT_WINDOW`] with no unit, or does not fit in
+    /// tokens.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub ignored: bool,
+}
+
+/// Token prices for a model, in USD per million tokens.
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, TS, JsonSchema, PartialEq)]
+pub struct ModelPricing {
+    pub input_per_million: f64,
+    /// Price for cached input tokens. Defaults to `input_per_million`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cached_input_per_million: Option<f64>,
+    pub output_per_million: f64,
+}
+
+impl ModelPricing {
+    /// Estimated cost in USD of `usage` at these prices.
+    pub fn estimate_cost(&self, usage: &TokenUsage) -> f64 {
+        let cached_price = self
+            .cached_input_per_million
+            .unwrap_or(self.input_per_million);
+        let cost = usage.non_cached_input() as f64 * self.input_per_million
+            + usage.cached_input() as f64 * cached_price
+            + usage.output_tokens.max(0) as f64 * self.output_per_million;
+        cost / 1_000_000.0
+    }
 }
 
 /// Visibility of a model in the picker or APIs.
@@ -160,46 +1164,218 @@ impl TruncationPolicyConfig {
 pub struct ClientVersion(pub i32, pub i32, pub i32);
 
 /// Model metadata returned by the Codex backend `/models` endpoint.
-#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, TS, JsonSchema)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, TS, JsonSchema)]
 pub struct ModelInfo {
     pub slug: String,
+    /// Id of the preset built from this entry, when it differs from `slug`:
+    /// LiteLLM entries that share an underlying model are told apart by the
+    /// name each is listed under.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub preset_id: Option<String>,
+    /// Also accepted as `model_name`, the name older LiteLLM proxies send.
+    #[serde(alias = "model_name")]
     pub display_name: String,
     pub description: Option<String>,
     pub default_reasoning_level: ReasoningEffort,
     pub supported_reasoning_levels: Vec<ReasoningEffortPreset>,
     pub shell_type: ConfigShellToolType,
     pub visibility: ModelVisibility,
+    /// Whether the catalog names this model its default. When no entry
+    /// does, the default stays on the one chosen before as long as it is
+    /// still listed, and otherwise is the listed model of highest priority.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub is_default: bool,
     pub supported_in_api: bool,
     pub priority: i32,
     pub upgrade: Option<String>,
     pub base_instructions: Option<String>,
+    /// Appended to the base instructions rather than replacing them.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub instructions_addendum: Option<String>,
+    /// Wire API the model must be called with, when it differs by model.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub wire_api: Option<WireApi>,
     pub supports_reasoning_summaries: bool,
+    /// See [`ModelPreset::supports_reasoning`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub supports_reasoning: Option<bool>,
+    /// See [`ModelPreset::reasoning_summary_format`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reasoning_summary_format: Option<ReasoningSummaryFormat>,
+    /// See [`ModelPreset::supports_stateful_responses`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub supports_stateful_responses: Option<bool>,
     pub support_verbosity: bool,
     pub default_verbosity: Option<Verbosity>,
     pub apply_patch_tool_type: Option<ApplyPatchToolType>,
     pub truncation_policy: TruncationPolicyConfig,
     pub supports_parallel_tool_calls: bool,
+    /// Also accepted as `max_input_tokens`, the name some LiteLLM proxy
+    /// versions send.
+    #[serde(
+        default,
+        alias = "max_input_tokens",
+        deserialize_with = "deserialize_token_count"
+    )]
     pub context_window: Option<i64>,
+    /// Unit of `context_window`, for proxies that do not report it in
+    /// tokens. Only read: decoding a `/models` response applies it, so
+    /// `context_window` is in tokens from then on.
+    #[serde(default, skip_serializing)]
+    #[ts(skip)]
+    pub context_window_unit: Option<ContextWindowUnit>,
+    /// How decoding a `/models` response read `context_window`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub context_window_source: Option<ContextWindowSource>,
+    /// Largest output budget the model accepts; also the cap used when
+    /// neither the effort nor the config sets one.
+    #[serde(
+        default,
+        deserialize_with = "deserialize_token_count",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub max_output_tokens: Option<i64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub supports_temperature: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_temperature: Option<f32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub supports_top_p: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_top_p: Option<f32>,
+    /// Also accepted as `supports_vision`, the name LiteLLM uses.
+    #[serde(default = "default_supports_images", alias = "supports_vision")]
+    pub supports_images: bool,
+    /// Whether the model needs network access in the sandbox.
+    #[serde(default)]
+    pub requires_network: bool,
+    /// Cap on concurrent requests to this model, for gateways that answer
+    /// with 429s beyond it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_concurrent_requests: Option<u32>,
+    /// Headers to send on every request to this model, e.g. an
+    /// `x-model-group` routing hint for LiteLLM.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub request_headers: Option<HashMap<String, String>>,
+    /// How requests to this model scope their `prompt_cache_key`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prompt_cache_key_strategy: Option<PromptCacheKeyStrategy>,
+    /// Models to retry a turn on, in order, when this one is at capacity.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fallback_models: Option<Vec<String>>,
+    /// Tools to leave out of the tool list for this model, by name.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub disabled_tools: Vec<String>,
+    /// See [`ModelPreset::prefer_apply_patch_freeform`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prefer_apply_patch_freeform: Option<bool>,
+    /// See [`ModelPreset::enable_web_search`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub enable_web_search: Option<bool>,
+    /// ISO date the model's training data ends.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub knowledge_cutoff: Option<String>,
+    /// ISO date the model was released.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub released_at: Option<String>,
     pub experimental_supported_tools: Vec<String>,
+    /// Fields this version does not know, kept as served so the cache and
+    /// the app server pass them on. They never reach [`ModelPreset`].
+    #[serde(default, flatten)]
+    pub extra: HashMap<String, serde_json::Value>,
+}
+
+/// Token limits as served by LiteLLM and similar gateways, which may encode
+/// them as integers, integer-valued floats (`128000.0`), or numeric strings
+/// (`"128000"`). Zero, negative, and null values mean "unknown" and become
+/// `None`; anything else that is not an integer is an error.
+fn deserialize_token_count<'de, D>(deserializer: D) -> Result<Option<i64>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    struct TokenCountVisitor;
+
+    impl<'de> serde::de::Visitor<'de> for TokenCountVisitor {
+        type Value = Option<i64>;
+
+        fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+            formatter
+                .write_str("a token count as an integer, integer-valued float, or numeric string")
+        }
+
+        fn visit_i64<E: serde::de::Error>(self, value: i64) -> Result<Self::Value, E> {
+            Ok((value > 0).then_some(value))
+        }
+
+        fn visit_u64<E: serde::de::Error>(self, value: u64) -> Result<Self::Value, E> {
+            match i64::try_from(value) {
+                Ok(value) => self.visit_i64(value),
+                Err(_) => Err(E::invalid_value(
+                    serde::de::Unexpected::Unsigned(value),
+                    &self,
+                )),
+            }
+        }
+
+        fn visit_f64<E: serde::de::Error>(self, value: f64) -> Result<Self::Value, E> {
+            if !value.is_finite() || value.fract() != 0.0 || value.abs() >= i64::MAX as f64 {
+                return Err(E::invalid_value(serde::de::Unexpected::Float(value), &self));
+            }
+            self.visit_i64(value as i64)
+        }
+
+        fn visit_str<E: serde::de::Error>(self, value: &str) -> Result<Self::Value, E> {
+            let trimmed = value.trim();
+            if let Ok(value) = trimmed.parse::<i64>() {
+                return self.visit_i64(value);
+            }
+            match trimmed.parse::<f64>() {
+                Ok(value) => self.visit_f64(value),
+                Err(_) => Err(E::invalid_value(serde::de::Unexpected::Str(value), &self)),
+            }
+        }
+
+        fn visit_none<E: serde::de::Error>(self) -> Result<Self::Value, E> {
+            Ok(None)
+        }
+
+        fn visit_unit<E: serde::de::Error>(self) -> Result<Self::Value, E> {
+            Ok(None)
+        }
+
+        fn visit_some<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+        where
+            D: serde::Deserializer<'de>,
+        {
+            deserializer.deserialize_any(self)
+        }
+    }
+
+    deserializer.deserialize_any(TokenCountVisitor)
 }
 
 /// Response wrapper for `/models`.
-#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, TS, JsonSchema, Default)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, TS, JsonSchema, Default)]
 pub struct ModelsResponse {
     pub models: Vec<ModelInfo>,
+    /// Oldest client version the catalog recommends, e.g. `0.60.0`. Older
+    /// clients keep working but are asked to upgrade.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_client_version: Option<String>,
 }
 
 // convert ModelInfo to ModelPreset
 impl From<ModelInfo> for ModelPreset {
     fn from(info: ModelInfo) -> Self {
         ModelPreset {
-            id: info.slug.clone(),
+            id: info.preset_id.unwrap_or_else(|| info.slug.clone()),
             model: info.slug.clone(),
             display_name: info.display_name,
             description: info.description.unwrap_or_default(),
             default_reasoning_effort: info.default_reasoning_level,
+            default_reasoning_effort_by_auth: None,
             supported_reasoning_efforts: info.supported_reasoning_levels.clone(),
-            is_default: false, // default is the highest priority available model
+            is_default: info.is_default,
             upgrade: info.upgrade.as_ref().map(|upgrade_slug| ModelUpgrade {
                 id: upgrade_slug.clone(),
                 reasoning_effort_mapping: reasoning_effort_mapping_from_presets(
@@ -212,6 +1388,38 @@ impl From<ModelInfo> for ModelPreset {
             }),
             show_in_picker: info.visibility == ModelVisibility::List,
             supported_in_api: info.supported_in_api,
+            model_provider: None,
+            upstream_model: None,
+            instructions_addendum: info.instructions_addendum,
+            wire_api: info.wire_api,
+            pricing: None,
+            context_window: info.context_window,
+            context_window_source: info.context_window_source,
+            compaction_threshold_ratio: None,
+            summarization_max_tokens: None,
+            supports_temperature: info.supports_temperature,
+            default_temperature: info.default_temperature,
+            supports_top_p: info.supports_top_p,
+            default_top_p: info.default_top_p,
+            supports_images: info.supports_images,
+            supports_parallel_tool_calls: Some(info.supports_parallel_tool_calls),
+            supports_reasoning_summaries: Some(info.supports_reasoning_summaries),
+            supports_reasoning: info.supports_reasoning,
+            reasoning_summary_format: info.reasoning_summary_format,
+            supports_stateful_responses: info.supports_stateful_responses,
+            requires_network: info.requires_network,
+            max_concurrent_requests: info.max_concurrent_requests,
+            request_timeout_secs: None,
+            stream_idle_timeout_secs: None,
+            extra_request_headers: info.request_headers,
+            prompt_cache_key_strategy: info.prompt_cache_key_strategy,
+            fallback_models: info.fallback_models,
+            disabled_tools: info.disabled_tools,
+            prefer_apply_patch_freeform: info.prefer_apply_patch_freeform,
+            enable_web_search: info.enable_web_search,
+            knowledge_cutoff: info.knowledge_cutoff,
+            released_at: info.released_at,
+            unavailable_for_account: None,
         }
     }
 }
@@ -226,29 +1434,619 @@ fn reasoning_effort_mapping_from_presets(
     // Map every canonical effort to the closest supported effort for the new model.
     let supported: Vec<ReasoningEffort> = presets.iter().map(|p| p.effort).collect();
     let mut map = HashMap::new();
-    for effort in ReasoningEffort::iter() {
-        let nearest = nearest_effort(effort, &supported);
-        map.insert(effort, nearest);
+    for effort in ReasoningEffort::VARIANTS {
+        map.insert(effort, effort.nearest_in(&supported).unwrap_or(effort));
     }
     Some(map)
 }
 
-fn effort_rank(effort: ReasoningEffort) -> i32 {
-    match effort {
-        ReasoningEffort::None => 0,
-        ReasoningEffort::Minimal => 1,
-        ReasoningEffort::Low => 2,
-        ReasoningEffort::Medium => 3,
-        ReasoningEffort::High => 4,
-        ReasoningEffort::XHigh => 5,
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+    use strum::IntoEnumIterator;
+
+    #[test]
+    fn effort_selection_reads_and_writes_auto_or_an_effort() {
+        let parsed: Vec<EffortSelection> =
+            serde_json::from_value(json!(["auto", "high"])).expect("selections parse");
+        assert_eq!(
+            parsed,
+            vec![
+                EffortSelection::Auto,
+                EffortSelection::Explicit(ReasoningEffort::High)
+            ]
+        );
+        assert_eq!(
+            serde_json::to_value(&parsed).expect("serialize"),
+            json!(["auto", "high"])
+        );
+
+        let err = serde_json::from_value::<EffortSelection>(json!("turbo"))
+            .expect_err("unknown efforts are rejected");
+        assert!(
+            err.to_string()
+                .contains("expected `auto` or one of: none, minimal, low, medium, high, xhigh"),
+            "unexpected error: {err}"
+        );
+        assert_eq!("AUTO".parse::<EffortSelection>(), Ok(EffortSelection::Auto));
+    }
+
+    #[test]
+    fn reasoning_efforts_round_trip_through_display_and_from_str() {
+        for effort in ReasoningEffort::VARIANTS {
+            let name = effort.to_string();
+            assert_eq!(name, name.to_ascii_lowercase());
+            assert_eq!(name.parse::<ReasoningEffort>(), Ok(effort));
+            assert_eq!(
+                name.to_ascii_uppercase().parse::<ReasoningEffort>(),
+                Ok(effort)
+            );
+            assert_eq!(
+                serde_json::from_value::<ReasoningEffort>(json!(name.to_ascii_uppercase()))
+                    .expect("uppercase efforts deserialize"),
+                effort
+            );
+            assert_eq!(
+                serde_json::to_value(effort).expect("serialize"),
+                json!(name)
+            );
+        }
+        assert_eq!(
+            " xHigh ".parse::<ReasoningEffort>(),
+            Ok(ReasoningEffort::XHigh)
+        );
+        assert_eq!(
+            ReasoningEffort::VARIANTS.to_vec(),
+            ReasoningEffort::iter().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn junk_reasoning_efforts_are_rejected() {
+        for junk in ["", "extreme", "hi gh", "high!"] {
+            let err = junk
+                .parse::<ReasoningEffort>()
+                .expect_err("junk is rejected");
+            assert_eq!(
+                err.to_string(),
+                format!(
+                    "unknown reasoning effort `{junk}`; expected one of: none, minimal, low, medium, high, xhigh"
+                )
+            );
+        }
+        assert!(serde_json::from_value::<ReasoningEffort>(json!("extreme")).is_err());
+    }
+
+    #[test]
+    fn limit_efforts_hides_efforts_outside_the_range() {
+        let effort = |effort| ReasoningEffortPreset {
+            effort,
+            description: effort.to_string(),
+            max_output_tokens: None,
+            request_timeout_secs: None,
+            stream_idle_timeout_secs: None,
+        };
+        let mut preset: ModelPreset = serde_json::from_value(json!({
+            "id": "gpt-5.1-codex",
+            "model": "gpt-5.1-codex",
+            "display_name": "gpt-5.1-codex",
+            "description": "",
+            "default_reasoning_effort": "low",
+            "supported_reasoning_efforts": [],
+            "is_default": false,
+            "upgrade": null,
+            "show_in_picker": true,
+            "supported_in_api": true
+        }))
+        .expect("preset");
+        preset.supported_reasoning_efforts = vec![
+            effort(ReasoningEffort::Low),
+            effort(ReasoningEffort::Medium),
+            effort(ReasoningEffort::High),
+        ];
+
+        let mut limited = preset.clone();
+        limited.limit_efforts(Some(ReasoningEffort::Medium), None);
+        assert_eq!(
+            limited.supported_reasoning_efforts,
+            vec![
+                effort(ReasoningEffort::Medium),
+                effort(ReasoningEffort::High)
+            ]
+        );
+        assert_eq!(limited.default_reasoning_effort, ReasoningEffort::Medium);
+
+        let mut limited = preset;
+        limited.limit_efforts(Some(ReasoningEffort::XHigh), None);
+        assert_eq!(
+            limited.supported_reasoning_efforts,
+            vec![effort(ReasoningEffort::High)]
+        );
+        assert_eq!(limited.default_reasoning_effort, ReasoningEffort::High);
+    }
+
+    #[test]
+    fn disable_efforts_moves_the_default_to_the_nearest_effort_left() {
+        let effort = |effort| ReasoningEffortPreset {
+            effort,
+            description: effort.to_string(),
+            max_output_tokens: None,
+            request_timeout_secs: None,
+            stream_idle_timeout_secs: None,
+        };
+        let mut preset: ModelPreset = serde_json::from_value(json!({
+            "id": "gpt-5.1-codex",
+            "model": "gpt-5.1-codex",
+            "display_name": "gpt-5.1-codex",
+            "description": "",
+            "default_reasoning_effort": "high",
+            "supported_reasoning_efforts": [],
+            "is_default": false,
+            "upgrade": null,
+            "show_in_picker": true,
+            "supported_in_api": true
+        }))
+        .expect("preset");
+        preset.supported_reasoning_efforts = vec![
+            effort(ReasoningEffort::Low),
+            effort(ReasoningEffort::Medium),
+            effort(ReasoningEffort::High),
+            effort(ReasoningEffort::XHigh),
+        ];
+
+        let mut disabled = preset.clone();
+        disabled.disable_efforts(&[ReasoningEffort::High]);
+        assert_eq!(
+            disabled.supported_reasoning_efforts,
+            vec![
+                effort(ReasoningEffort::Low),
+                effort(ReasoningEffort::Medium),
+                effort(ReasoningEffort::XHigh)
+            ]
+        );
+        assert_eq!(disabled.default_reasoning_effort, ReasoningEffort::Medium);
+
+        let mut disabled = preset.clone();
+        disabled.disable_efforts(&[ReasoningEffort::Low]);
+        assert_eq!(disabled.default_reasoning_effort, ReasoningEffort::High);
+
+        let mut disabled = preset;
+        disabled.disable_efforts(&ReasoningEffort::VARIANTS);
+        assert_eq!(disabled.supported_reasoning_efforts, Vec::new());
+    }
+
+    #[test]
+    fn efforts_clamp_into_a_range() {
+        let (min, max) = (Some(ReasoningEffort::Low), Some(ReasoningEffort::High));
+        assert_eq!(
+            ReasoningEffort::Minimal.clamp_to(min, max),
+            ReasoningEffort::Low
+        );
+        assert_eq!(
+            ReasoningEffort::XHigh.clamp_to(min, max),
+            ReasoningEffort::High
+        );
+        assert_eq!(
+            ReasoningEffort::Medium.clamp_to(min, max),
+            ReasoningEffort::Medium
+        );
+        assert_eq!(
+            ReasoningEffort::None.clamp_to(None, None),
+            ReasoningEffort::None
+        );
+        assert!(ReasoningEffort::High.is_within(min, max));
+        assert!(!ReasoningEffort::Minimal.is_within(min, None));
+        assert!(ReasoningEffort::XHigh.is_within(min, None));
+    }
+
+    #[test]
+    fn efforts_rank_from_least_to_most_reasoning() {
+        assert_eq!(
+            ReasoningEffort::VARIANTS.map(ReasoningEffort::rank),
+            [0, 1, 2, 3, 4, 5]
+        );
+        assert!(ReasoningEffort::Minimal.rank() < ReasoningEffort::Low.rank());
+        assert!(ReasoningEffort::Low.rank() < ReasoningEffort::Medium.rank());
+        assert!(ReasoningEffort::Medium.rank() < ReasoningEffort::High.rank());
+        assert!(ReasoningEffort::High.rank() < ReasoningEffort::XHigh.rank());
     }
-}
 
-fn nearest_effort(target: ReasoningEffort, supported: &[ReasoningEffort]) -> ReasoningEffort {
-    let target_rank = effort_rank(target);
-    supported
-        .iter()
-        .copied()
-        .min_by_key(|candidate| (effort_rank(*candidate) - target_rank).abs())
-        .unwrap_or(target)
+    #[test]
+    fn nearest_effort_prefers_the_first_listed_on_a_tie() {
+        let supported = [ReasoningEffort::Low, ReasoningEffort::High];
+        assert_eq!(
+            ReasoningEffort::Minimal.nearest_in(&supported),
+            Some(ReasoningEffort::Low)
+        );
+        assert_eq!(
+            ReasoningEffort::Medium.nearest_in(&supported),
+            Some(ReasoningEffort::Low)
+        );
+        assert_eq!(
+            ReasoningEffort::XHigh.nearest_in(&supported),
+            Some(ReasoningEffort::High)
+        );
+        assert_eq!(ReasoningEffort::High.nearest_in(&[]), None);
+    }
+
+    #[test]
+    fn auto_effort_follows_the_model_default() {
+        assert_eq!(
+            EffortSelection::Auto.resolve(ReasoningEffort::Medium),
+            ReasoningEffort::Medium
+        );
+        assert_eq!(
+            EffortSelection::Auto.resolve(ReasoningEffort::Low),
+            ReasoningEffort::Low
+        );
+        assert_eq!(
+            EffortSelection::Explicit(ReasoningEffort::High).resolve(ReasoningEffort::Low),
+            ReasoningEffort::High
+        );
+        assert_eq!(
+            EffortSelection::Auto.label(ReasoningEffort::Medium),
+            "auto (medium)"
+        );
+        assert_eq!(EffortSelection::from(None), EffortSelection::Auto);
+    }
+
+    #[derive(Debug, Deserialize)]
+    struct Limits {
+        #[serde(default, deserialize_with = "deserialize_token_count")]
+        context_window: Option<i64>,
+    }
+
+    fn context_window(value: serde_json::Value) -> Result<Option<i64>, String> {
+        serde_json::from_value::<Limits>(json!({ "context_window": value }))
+            .map(|limits| limits.context_window)
+            .map_err(|err| err.to_string())
+    }
+
+    #[test]
+    fn token_counts_accept_integers_floats_and_numeric_strings() {
+        assert_eq!(context_window(json!(128000)), Ok(Some(128_000)));
+        assert_eq!(context_window(json!(128000.0)), Ok(Some(128_000)));
+        assert_eq!(context_window(json!("128000")), Ok(Some(128_000)));
+        assert_eq!(context_window(json!(" 128000.0 ")), Ok(Some(128_000)));
+    }
+
+    #[test]
+    fn token_counts_that_are_not_positive_are_absent() {
+        assert_eq!(context_window(json!(null)), Ok(None));
+        assert_eq!(context_window(json!(0)), Ok(None));
+        assert_eq!(context_window(json!(-1)), Ok(None));
+        assert_eq!(context_window(json!("-4096")), Ok(None));
+        assert_eq!(
+            serde_json::from_value::<Limits>(json!({}))
+                .map(|limits| limits.context_window)
+                .map_err(|err| err.to_string()),
+            Ok(None)
+        );
+    }
+
+    #[test]
+    fn token_counts_reject_non_numeric_values() {
+        let expected = "a token count as an integer, integer-valued float, or numeric string";
+        for value in [json!("128k"), json!(1.5), json!(true), json!([128000])] {
+            let err = context_window(value.clone()).expect_err("value should be rejected");
+            assert!(
+                err.contains(expected),
+                "unexpected error for {value}: {err}"
+            );
+        }
+    }
+
+    #[test]
+    fn max_concurrent_requests_round_trips_from_model_info_to_preset() {
+        let info: ModelInfo = serde_json::from_value(json!({
+            "slug": "oca-large",
+            "display_name": "OCA Large",
+            "default_reasoning_level": "medium",
+            "supported_reasoning_levels": [],
+            "shell_type": "shell_command",
+            "visibility": "list",
+            "supported_in_api": true,
+            "priority": 1,
+            "base_instructions": null,
+            "supports_reasoning_summaries": false,
+            "support_verbosity": false,
+            "default_verbosity": null,
+            "apply_patch_tool_type": null,
+            "truncation_policy": {"mode": "bytes", "limit": 10000},
+            "supports_parallel_tool_calls": true,
+            "context_window": null,
+            "max_concurrent_requests": 2,
+            "experimental_supported_tools": [],
+        }))
+        .expect("valid model info");
+        assert_eq!(info.max_concurrent_requests, Some(2));
+
+        let mut preset = ModelPreset::from(info);
+        assert_eq!(preset.max_concurrent_requests, Some(2));
+        let serialized = serde_json::to_value(&preset).expect("serialize preset");
+        assert_eq!(serialized["max_concurrent_requests"], json!(2));
+
+        preset.max_concurrent_requests = None;
+        let serialized = serde_json::to_value(&preset).expect("serialize preset");
+        assert_eq!(serialized.get("max_concurrent_requests"), None);
+    }
+
+    #[test]
+    fn model_dates_are_read_from_model_info_and_left_out_when_absent() {
+        let info = |dates: serde_json::Value| -> ModelInfo {
+            let mut value = json!({
+                "slug": "oca-large",
+                "display_name": "OCA Large",
+                "default_reasoning_level": "medium",
+                "supported_reasoning_levels": [],
+                "shell_type": "shell_command",
+                "visibility": "list",
+                "supported_in_api": true,
+                "priority": 1,
+                "base_instructions": null,
+                "supports_reasoning_summaries": false,
+                "support_verbosity": false,
+                "default_verbosity": null,
+                "apply_patch_tool_type": null,
+                "truncation_policy": {"mode": "bytes", "limit": 10000},
+                "supports_parallel_tool_calls": true,
+                "context_window": null,
+                "experimental_supported_tools": [],
+            });
+            if let (Some(value), Some(dates)) = (value.as_object_mut(), dates.as_object()) {
+                value.extend(dates.clone());
+            }
+            serde_json::from_value(value).expect("valid model info")
+        };
+
+        let dated = info(json!({
+            "knowledge_cutoff": "2024-09-30",
+            "released_at": "2025-08-07",
+        }));
+        assert_eq!(dated.knowledge_cutoff.as_deref(), Some("2024-09-30"));
+        assert_eq!(dated.released_at.as_deref(), Some("2025-08-07"));
+        let preset = ModelPreset::from(dated);
+        assert_eq!(
+            (preset.view().knowledge_cutoff, preset.view().released_at),
+            (Some("2024-09-30"), Some("2025-08-07"))
+        );
+        let serialized = serde_json::to_value(&preset).expect("serialize preset");
+        assert_eq!(serialized["knowledge_cutoff"], json!("2024-09-30"));
+        assert_eq!(serialized["released_at"], json!("2025-08-07"));
+
+        let undated = ModelPreset::from(info(json!({})));
+        assert_eq!(
+            (undated.view().knowledge_cutoff, undated.view().released_at),
+            (None, None)
+        );
+        let serialized = serde_json::to_value(&undated).expect("serialize preset");
+        assert_eq!(serialized.get("knowledge_cutoff"), None);
+        assert_eq!(serialized.get("released_at"), None);
+    }
+
+    #[test]
+    fn reasoning_summary_format_accepts_older_spellings() {
+        let formats: Vec<ReasoningSummaryFormat> =
+            serde_json::from_value(json!(["headed", "experimental", "plain", "none"]))
+                .expect("valid formats");
+        assert_eq!(
+            formats,
+            vec![
+                ReasoningSummaryFormat::Headed,
+                ReasoningSummaryFormat::Headed,
+                ReasoningSummaryFormat::Plain,
+                ReasoningSummaryFormat::Plain,
+            ]
+        );
+    }
+
+    #[test]
+    fn preset_view_borrows_instead_of_cloning() {
+        let preset = ModelPreset::builder("vllm/gpt-test", "gpt-test")
+            .display_name("GPT Test")
+            .default_reasoning_effort(ReasoningEffort::Medium)
+            .supported_reasoning_efforts(vec![
+                effort(ReasoningEffort::Low),
+                effort(ReasoningEffort::Medium),
+            ])
+            .model_provider(Some("vllm".to_string()))
+            .supports_reasoning_summaries(false)
+            .unavailable_for_account(Some("not on this plan".to_string()))
+            .build()
+            .expect("valid preset");
+
+        let view = preset.view();
+        let copy = view;
+
+        assert_eq!(copy, view);
+        assert!(std::ptr::eq(view.model, preset.model.as_str()));
+        assert!(std::ptr::eq(
+            view.display_name,
+            preset.display_name.as_str()
+        ));
+        assert!(std::ptr::eq(
+            view.supported_reasoning_efforts,
+            preset.supported_reasoning_efforts.as_slice()
+        ));
+        assert_eq!(view.model_provider, Some("vllm"));
+        assert_eq!(view.unavailable_for_account, Some("not on this plan"));
+        assert_eq!(view.supports_reasoning_summaries, Some(false));
+        assert_eq!(view.default_reasoning_effort, ReasoningEffort::Medium);
+    }
+
+    #[test]
+    fn stream_idle_timeout_secs_is_its_own_setting() {
+        let effort: ReasoningEffortPreset = serde_json::from_value(json!({
+            "effort": "high",
+            "description": "Deep reasoning",
+            "stream_idle_timeout_secs": 180,
+        }))
+        .expect("valid effort preset");
+        assert_eq!(effort.stream_idle_timeout_secs, Some(180));
+        assert_eq!(effort.request_timeout_secs, None);
+        assert_eq!(
+            serde_json::to_value(&effort).expect("serialize")["stream_idle_timeout_secs"],
+            json!(180)
+        );
+    }
+
+    fn effort(effort: ReasoningEffort) -> ReasoningEffortPreset {
+        ReasoningEffortPreset {
+            effort,
+            description: effort.to_string(),
+            max_output_tokens: None,
+            request_timeout_secs: None,
+            stream_idle_timeout_secs: None,
+        }
+    }
+
+    #[test]
+    fn builder_defaults_leave_optional_fields_unset() {
+        let preset = ModelPreset::builder("vllm/qwen", "qwen")
+            .build()
+            .expect("valid preset");
+
+        assert_eq!(
+            serde_json::to_value(&preset).expect("serialize preset"),
+            json!({
+                "id": "vllm/qwen",
+                "model": "qwen",
+                "display_name": "vllm/qwen",
+                "description": "",
+                "default_reasoning_effort": "medium",
+                "supported_reasoning_efforts": [],
+                "is_default": false,
+                "upgrade": null,
+                "show_in_picker": true,
+                "supported_in_api": true,
+                "supports_images": true,
+                "requires_network": false,
+            })
+        );
+    }
+
+    #[test]
+    fn builder_sets_what_it_is_given() {
+        let preset = ModelPreset::builder("gpt-test", "gpt-test")
+            .display_name("GPT Test")
+            .default_reasoning_effort(ReasoningEffort::Low)
+            .supported_reasoning_efforts(vec![
+                effort(ReasoningEffort::Low),
+                effort(ReasoningEffort::High),
+            ])
+            .context_window(272_000)
+            .model_provider(Some("vllm".to_string()))
+            .show_in_picker(false)
+            .build()
+            .expect("valid preset");
+
+        assert_eq!(preset.display_name, "GPT Test");
+        assert_eq!(preset.default_reasoning_effort, ReasoningEffort::Low);
+        assert_eq!(preset.context_window, Some(272_000));
+        assert_eq!(preset.model_provider.as_deref(), Some("vllm"));
+        assert!(!preset.show_in_picker);
+    }
+
+    #[test]
+    fn builder_rejects_invalid_presets() {
+        let supported = || {
+            vec![
+                effort(ReasoningEffort::Low),
+                effort(ReasoningEffort::Medium),
+            ]
+        };
+        let cases = [
+            (
+                ModelPreset::builder("", "gpt-test"),
+                ModelPresetError::MissingField("id"),
+            ),
+            (
+                ModelPreset::builder("gpt-test", " "),
+                ModelPresetError::MissingField("model"),
+            ),
+            (
+                ModelPreset::builder("gpt-test", "gpt-test").supported_reasoning_efforts(vec![
+                    effort(ReasoningEffort::Low),
+                    effort(ReasoningEffort::Low),
+                ]),
+                ModelPresetError::DuplicateEffort {
+                    id: "gpt-test".to_string(),
+                    effort: ReasoningEffort::Low,
+                },
+            ),
+            (
+                ModelPreset::builder("gpt-test", "gpt-test")
+                    .supported_reasoning_efforts(supported())
+                    .default_reasoning_effort(ReasoningEffort::High),
+                ModelPresetError::UnsupportedDefaultEffort {
+                    id: "gpt-test".to_string(),
+                    effort: ReasoningEffort::High,
+                    auth_mode: None,
+                },
+            ),
+            (
+                ModelPreset::builder("gpt-test", "gpt-test")
+                    .supported_reasoning_efforts(supported())
+                    .default_reasoning_effort_by_auth(EffortByAuth {
+                        apikey: Some(ReasoningEffort::Low),
+                        chatgpt: Some(ReasoningEffort::XHigh),
+                    }),
+                ModelPresetError::UnsupportedDefaultEffort {
+                    id: "gpt-test".to_string(),
+                    effort: ReasoningEffort::XHigh,
+                    auth_mode: Some("chatgpt"),
+                },
+            ),
+            (
+                ModelPreset::builder("gpt-test", "gpt-test").stream_idle_timeout_secs(0),
+                ModelPresetError::ZeroStreamIdleTimeout {
+                    id: "gpt-test".to_string(),
+                    effort: None,
+                },
+            ),
+            (
+                ModelPreset::builder("gpt-test", "gpt-test")
+                    .supported_reasoning_efforts(vec![ReasoningEffortPreset {
+                        stream_idle_timeout_secs: Some(0),
+                        ..effort(ReasoningEffort::Low)
+                    }])
+                    .default_reasoning_effort(ReasoningEffort::Low),
+                ModelPresetError::ZeroStreamIdleTimeout {
+                    id: "gpt-test".to_string(),
+                    effort: Some(ReasoningEffort::Low),
+                },
+            ),
+        ];
+        for (builder, expected) in cases {
+            assert_eq!(builder.build(), Err(expected));
+        }
+
+        // Without supported efforts the model takes no effort parameter, so
+        // any default is accepted.
+        assert!(
+            ModelPreset::builder("gpt-test", "gpt-test")
+                .default_reasoning_effort(ReasoningEffort::High)
+                .build()
+                .is_ok()
+        );
+        assert_eq!(
+            ModelPresetError::UnsupportedDefaultEffort {
+                id: "gpt-test".to_string(),
+                effort: ReasoningEffort::XHigh,
+                auth_mode: Some("chatgpt"),
+            }
+            .to_string(),
+            "preset `gpt-test` defaults to reasoning effort `xhigh` for `chatgpt`, which is not in its `supported_reasoning_efforts`"
+        );
+        assert_eq!(
+            ModelPresetError::ZeroStreamIdleTimeout {
+                id: "gpt-test".to_string(),
+                effort: Some(ReasoningEffort::High),
+            }
+            .to_string(),
+            "preset `gpt-test` sets `stream_idle_timeout_secs` to 0 on the `high` effort"
+        );
+    }
 }
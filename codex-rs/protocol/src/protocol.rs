@@ -20,6 +20,7 @@ use crate::models::ContentItem;
 use crate::models::ResponseItem;
 use crate::num_format::format_with_separators;
 use crate::openai_models::ReasoningEffort as ReasoningEffortConfig;
+use crate::openai_models::ReasoningSummaryFormat;
 use crate::parse_command::ParsedCommand;
 use crate::plan_tool::UpdatePlanArgs;
 use crate::user_input::UserInput;
@@ -39,6 +40,7 @@ use tracing::error;
 use ts_rs::TS;
 
 pub use crate::approvals::ApplyPatchApprovalRequestEvent;
+pub use crate::approvals::CostLimitApprovalRequestEvent;
 pub use crate::approvals::ElicitationAction;
 pub use crate::approvals::ExecApprovalRequestEvent;
 pub use crate::approvals::ExecPolicyAmendment;
@@ -129,6 +131,11 @@ pub enum Op {
         #[serde(skip_serializing_if = "Option::is_none")]
         model: Option<String>,
 
+        /// Updated model provider id, resolved against the configured
+        /// `model_providers`.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        model_provider: Option<String>,
+
         /// Updated reasoning effort (honored only for reasoning-capable models).
         ///
         /// Use `Some(Some(_))` to set a specific effort, `Some(None)` to clear
@@ -157,6 +164,16 @@ pub enum Op {
         decision: ReviewDecision,
     },
 
+    /// Decide whether a turn may go on past `session_cost_limit_usd`.
+    CostLimitApproval {
+        /// The id of the turn waiting for the decision
+        id: String,
+        /// `Approved` lets this turn run, `ApprovedForSession` stops asking
+        /// for the rest of the session, and anything else ends the turn
+        /// before its first model request.
+        decision: ReviewDecision,
+    },
+
     /// Resolve an MCP elicitation request.
     ResolveElicitation {
         /// Name of the MCP server that issued the request.
@@ -608,6 +625,10 @@ pub enum EventMsg {
 
     ApplyPatchApprovalRequest(ApplyPatchApprovalRequestEvent),
 
+    /// The session's estimated cost reached `session_cost_limit_usd`; the
+    /// turn waits for an [`Op::CostLimitApproval`] before it runs.
+    CostLimitApprovalRequest(CostLimitApprovalRequestEvent),
+
     /// Notification advising the user that something they are using has been
     /// deprecated and should be phased out.
     DeprecationNotice(DeprecationNoticeEvent),
@@ -622,6 +643,10 @@ pub enum EventMsg {
     /// and the system is handling it (e.g., retrying with backoff).
     StreamError(StreamErrorEvent),
 
+    /// The model serving the current turn changed without the user picking
+    /// a new one.
+    ModelChanged(ModelChangedEvent),
+
     /// Notification that the agent is about to apply a code patch. Mirrors
     /// `ExecCommandBegin` so front‑ends can show progress indicators.
     PatchApplyBegin(PatchApplyBeginEvent),
@@ -695,6 +720,11 @@ pub enum CodexErrorInfo {
     ResponseTooManyFailedAttempts {
         http_status_code: Option<u16>,
     },
+    /// The provider no longer serves the selected model. `preset_id` names
+    /// the catalog entry it was selected from.
+    ModelUnavailable {
+        preset_id: String,
+    },
     Other,
 }
 
@@ -815,6 +845,10 @@ impl HasLegacyEvent for EventMsg {
 #[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
 pub struct ExitedReviewModeEvent {
     pub review_output: Option<ReviewOutputEvent>,
+    /// The model the review ran on.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub model: Option<String>,
 }
 
 // Individual event payload types matching each `EventMsg` variant.
@@ -832,7 +866,12 @@ pub struct WarningEvent {
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
-pub struct ContextCompactedEvent;
+pub struct ContextCompactedEvent {
+    /// The model that wrote the summary.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub model: Option<String>,
+}
 
 #[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
 pub struct TaskCompleteEvent {
@@ -842,6 +881,18 @@ pub struct TaskCompleteEvent {
 #[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
 pub struct TaskStartedEvent {
     pub model_context_window: Option<i64>,
+    /// Reasoning effort the turn is sampled at. Unset for turns that do not
+    /// call the model, such as user shell commands.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reasoning_effort: Option<ReasoningEffortConfig>,
+    /// What `reasoning_effort` means for the active model, from its preset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reasoning_effort_description: Option<String>,
+    /// How the active model writes its reasoning summaries, resolved from
+    /// `model_reasoning_summary_format` and the model's preset. Unset for
+    /// turns that do not call the model.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reasoning_summary_format: Option<ReasoningSummaryFormat>,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, Default, PartialEq, Eq, JsonSchema, TS)]
@@ -864,6 +915,17 @@ pub struct TokenUsageInfo {
     pub last_token_usage: TokenUsage,
     #[ts(type = "number | null")]
     pub model_context_window: Option<i64>,
+    /// Session totals split by the model that produced each response, in
+    /// order of first use.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub per_model_usage: Vec<ModelTokenUsage>,
+}
+
+/// Token usage attributed to a single model slug within a session.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, JsonSchema, TS)]
+pub struct ModelTokenUsage {
+    pub model: String,
+    pub token_usage: TokenUsage,
 }
 
 impl TokenUsageInfo {
@@ -882,6 +944,7 @@ impl TokenUsageInfo {
                 total_token_usage: TokenUsage::default(),
                 last_token_usage: TokenUsage::default(),
                 model_context_window,
+                per_model_usage: Vec::new(),
             },
         };
         if let Some(last) = last {
@@ -895,6 +958,21 @@ impl TokenUsageInfo {
         self.last_token_usage = last.clone();
     }
 
+    /// Add `last` to the running total for `model`.
+    pub fn append_model_usage(&mut self, model: &str, last: &TokenUsage) {
+        match self
+            .per_model_usage
+            .iter_mut()
+            .find(|entry| entry.model == model)
+        {
+            Some(entry) => entry.token_usage.add_assign(last),
+            None => self.per_model_usage.push(ModelTokenUsage {
+                model: model.to_string(),
+                token_usage: last.clone(),
+            }),
+        }
+    }
+
     pub fn fill_to_context_window(&mut self, context_window: i64) {
         let previous_total = self.total_token_usage.total_tokens;
         let delta = (context_window - previous_total).max(0);
@@ -915,6 +993,7 @@ impl TokenUsageInfo {
             total_token_usage: TokenUsage::default(),
             last_token_usage: TokenUsage::default(),
             model_context_window: Some(context_window),
+            per_model_usage: Vec::new(),
         };
         info.fill_to_context_window(context_window);
         info
@@ -1261,6 +1340,10 @@ pub struct SessionMeta {
     #[serde(default)]
     pub source: SessionSource,
     pub model_provider: Option<String>,
+    /// The model catalog the session was started with. Absent in rollouts
+    /// written before it was recorded.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model_catalog: Option<SessionCatalog>,
 }
 
 impl Default for SessionMeta {
@@ -1274,10 +1357,47 @@ impl Default for SessionMeta {
             instructions: None,
             source: SessionSource::default(),
             model_provider: None,
+            model_catalog: None,
         }
     }
 }
 
+/// State of the remote model catalog when a session started, so a change in
+/// model behavior can be matched with a catalog update.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema, TS)]
+pub struct SessionCatalog {
+    /// ETag of the `/models` response, when the server sent one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub revision: Option<String>,
+    pub source: SessionCatalogSource,
+    /// When the catalog was fetched (RFC 3339). Absent for the bundled
+    /// snapshot.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fetched_at: Option<String>,
+}
+
+/// Where the remote model catalog of a session was loaded from.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, JsonSchema, TS)]
+#[serde(rename_all = "snake_case")]
+pub enum SessionCatalogSource {
+    /// The snapshot compiled into the binary.
+    Bundled,
+    /// `models_cache.json` under `CODEX_HOME`.
+    DiskCache,
+    /// A `/models` request made by the process that started the session.
+    Live,
+}
+
+impl fmt::Display for SessionCatalogSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            SessionCatalogSource::Bundled => "bundled",
+            SessionCatalogSource::DiskCache => "disk cache",
+            SessionCatalogSource::Live => "live",
+        })
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, JsonSchema, TS)]
 pub struct SessionMetaLine {
     #[serde(flatten)]
@@ -1323,6 +1443,9 @@ pub struct TurnContextItem {
     pub model: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub effort: Option<ReasoningEffortConfig>,
+    /// What `effort` meant for `model` when the turn ran, from its preset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub effort_description: Option<String>,
     pub summary: ReasoningSummaryConfig,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub base_instructions: Option<String>,
@@ -1334,6 +1457,46 @@ pub struct TurnContextItem {
     pub final_output_json_schema: Option<Value>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub truncation_policy: Option<TruncationPolicy>,
+    /// The catalog preset that served `model` when the turn ran. Absent for
+    /// models without a preset and in rollouts written before it was
+    /// recorded.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub preset: Option<TurnPreset>,
+}
+
+/// What the catalog said about a turn's model at the time, so a transcript
+/// still describes it after the catalog changes.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema, TS)]
+pub struct TurnPreset {
+    pub id: String,
+    pub display_name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub context_window: Option<i64>,
+    pub source: TurnPresetSource,
+}
+
+/// Where the preset of a turn's model came from.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema, TS)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum TurnPresetSource {
+    /// The presets compiled into the binary.
+    Builtin,
+    /// The remote model catalog, loaded from `catalog`.
+    Remote { catalog: SessionCatalogSource },
+    /// The catalog of a configured model provider.
+    Provider { provider_id: String },
+}
+
+impl fmt::Display for TurnPresetSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TurnPresetSource::Builtin => f.write_str("built-in presets"),
+            TurnPresetSource::Remote { catalog } => write!(f, "remote catalog ({catalog})"),
+            TurnPresetSource::Provider { provider_id } => {
+                write!(f, "provider `{provider_id}` catalog")
+            }
+        }
+    }
 }
 
 #[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq, JsonSchema, TS)]
@@ -1607,6 +1770,34 @@ pub struct StreamErrorEvent {
     pub additional_details: Option<String>,
 }
 
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
+pub struct ModelChangedEvent {
+    pub previous_model: String,
+    pub model: String,
+    pub source: ModelChangeSource,
+    /// Id of the `model_providers` entry that served `previous_model`. Unset
+    /// in events recorded before providers were reported.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub previous_model_provider: Option<String>,
+    /// Id of the `model_providers` entry serving `model`, which differs from
+    /// `previous_model_provider` when the switch also moved providers.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model_provider: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq, JsonSchema, TS)]
+#[serde(rename_all = "snake_case")]
+#[ts(rename_all = "snake_case")]
+pub enum ModelChangeSource {
+    /// `previous_model` was at capacity, so the turn is retried once on the
+    /// first fallback its preset lists. The next turn goes back to
+    /// `previous_model`.
+    Fallback,
+    /// A client switched the session to another model or provider, e.g.
+    /// from the model picker. Later turns stay on `model`.
+    Selected,
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
 pub struct StreamInfoEvent {
     pub message: String,
@@ -1780,6 +1971,21 @@ pub struct SessionConfiguredEvent {
 
     pub model_provider_id: String,
 
+    /// True when the session was started with `offline = true`, meaning the
+    /// model catalog was built from bundled presets and the on-disk cache only.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub model_catalog_offline: bool,
+
+    /// True when the active model appends an instructions addendum to the
+    /// base instructions (from its preset, model metadata, or provider).
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub instructions_addendum_active: bool,
+
+    /// Tools the active model's preset keeps out of the tool list, such as
+    /// `web_search` for a model hosted where it is not allowed.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub disabled_tools: Vec<String>,
+
     /// When to escalate for approval for execution
     pub approval_policy: AskForApproval,
 
@@ -1940,6 +2146,8 @@ mod tests {
                 session_id: conversation_id,
                 model: "codex-mini-latest".to_string(),
                 model_provider_id: "openai".to_string(),
+                model_catalog_offline: false,
+                instructions_addendum_active: false,
                 approval_policy: AskForApproval::Never,
                 sandbox_policy: SandboxPolicy::ReadOnly,
                 cwd: PathBuf::from("/home/user/project"),
@@ -1948,6 +2156,7 @@ mod tests {
                 history_entry_count: 0,
                 initial_messages: None,
                 rollout_path: rollout_file.path().to_path_buf(),
+                disabled_tools: Vec::new(),
             }),
         };
 
@@ -90,6 +90,10 @@ pub fn run_main(args: Args) -> Result<()> {
         Client::builder()
             // Disable reqwest's 30s default so long-lived response streams keep flowing.
             .timeout(None::<Duration>)
+            // Relay response streams as they arrive instead of negotiating
+            // compression that would have to be decoded first.
+            .no_gzip()
+            .no_deflate()
             .build()
             .context("building reqwest client")?,
     );
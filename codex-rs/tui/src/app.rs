@@ -28,9 +28,14 @@ use codex_core::config::edit::ConfigEdit;
 use codex_core::config::edit::ConfigEditsBuilder;
 #[cfg(target_os = "windows")]
 use codex_core::features::Feature;
+use codex_core::models_manager::catalog::CatalogLoadOptions;
+use codex_core::models_manager::catalog::PresetCatalog;
+use codex_core::models_manager::client_version::ClientVersionNotice;
 use codex_core::models_manager::manager::ModelsManager;
 use codex_core::models_manager::model_presets::HIDE_GPT_5_1_CODEX_MAX_MIGRATION_PROMPT_CONFIG;
 use codex_core::models_manager::model_presets::HIDE_GPT5_1_MIGRATION_PROMPT_CONFIG;
+use codex_core::models_manager::refresh_events::CatalogRefreshEvent;
+use codex_core::models_manager::seen::SeenModels;
 use codex_core::protocol::EventMsg;
 use codex_core::protocol::FinalOutput;
 use codex_core::protocol::ListSkillsResponseEvent;
@@ -39,6 +44,7 @@ use codex_core::protocol::SessionSource;
 use codex_core::protocol::SkillErrorInfo;
 use codex_core::protocol::TokenUsage;
 use codex_protocol::ConversationId;
+use codex_protocol::openai_models::EffortSelection;
 use codex_protocol::openai_models::ModelPreset;
 use codex_protocol::openai_models::ModelUpgrade;
 use codex_protocol::openai_models::ReasoningEffort as ReasoningEffortConfig;
@@ -60,7 +66,9 @@ use std::sync::atomic::Ordering;
 use std::thread;
 use std::time::Duration;
 use tokio::select;
+use tokio::sync::broadcast::error::RecvError;
 use tokio::sync::mpsc::unbounded_channel;
+use tokio::task::JoinHandle;
 
 #[cfg(not(debug_assertions))]
 use crate::history_cell::UpdateAvailableHistoryCell;
@@ -182,6 +190,129 @@ fn target_preset_for_upgrade<'a>(
         .find(|preset| preset.model == target_model)
 }
 
+/// Start the `prefetch_model_catalog` fetch in the background. Returns at
+/// once, so neither the first frame nor the first turn waits on the catalog.
+/// Skipped in offline mode.
+fn spawn_model_catalog_prefetch(
+    models_manager: Arc<ModelsManager>,
+    config: &Config,
+) -> Option<JoinHandle<()>> {
+    if !config.prefetch_model_catalog || config.offline {
+        return None;
+    }
+    let config = config.clone();
+    Some(tokio::spawn(async move {
+        models_manager.prefetch_catalog(&config).await;
+    }))
+}
+
+/// Watch for catalog refreshes, from startup, the model picker, or
+/// a background refresh, and announce the models they add. Every listed id is
+/// then recorded in the seen state, so each model is announced at most once.
+/// A catalog that recommends a newer Codex is announced once per minimum.
+fn spawn_new_model_watcher(
+    models_manager: Arc<ModelsManager>,
+    config: &Config,
+    app_event_tx: AppEventSender,
+) -> JoinHandle<()> {
+    let config = config.clone();
+    let mut revisions = models_manager.subscribe_catalog_revision();
+    tokio::spawn(async move {
+        let mut seen = SeenModels::load(&config.codex_home).await;
+        let mut previous = models_manager.known_models(&config).await;
+        let mut announced_notice = None;
+        announce_client_version_notice(
+            &models_manager,
+            &config,
+            &app_event_tx,
+            &mut announced_notice,
+        )
+        .await;
+        while revisions.changed().await.is_ok() {
+            revisions.borrow_and_update();
+            let current = models_manager.known_models(&config).await;
+            app_event_tx.send(AppEvent::ModelCatalogChanged);
+            announce_client_version_notice(
+                &models_manager,
+                &config,
+                &app_event_tx,
+                &mut announced_notice,
+            )
+            .await;
+            if let Err(err) = seen.migrate(&current).await {
+                tracing::warn!("failed to migrate seen models: {err}");
+            }
+            let added = seen.unseen_additions(&previous, &current);
+            if !added.is_empty() {
+                app_event_tx.send(AppEvent::NewModelsAvailable { models: added });
+            }
+            let ids = current.iter().map(|preset| preset.id.clone());
+            if let Err(err) = seen.mark_seen(ids).await {
+                tracing::warn!("failed to record seen models: {err}");
+            }
+            previous = current;
+        }
+    })
+}
+
+/// Watch catalog refreshes and tell the footer whether any is in flight, for
+/// its "syncing models" indicator, and the history when one moved the default
+/// model.
+fn spawn_catalog_sync_watcher(
+    models_manager: &ModelsManager,
+    app_event_tx: AppEventSender,
+) -> JoinHandle<()> {
+    let mut events = models_manager.subscribe_refresh_events();
+    tokio::spawn(async move {
+        let mut in_flight = 0;
+        loop {
+            let event = events.recv().await;
+            if matches!(event, Err(RecvError::Closed)) {
+                return;
+            }
+            let was_syncing = in_flight > 0;
+            in_flight = refreshes_in_flight(in_flight, &event);
+            if was_syncing != (in_flight > 0) {
+                app_event_tx.send(AppEvent::CatalogSyncing(in_flight > 0));
+            }
+            if let Ok(CatalogRefreshEvent::DefaultModelChanged { previous, current }) = event {
+                app_event_tx.send(AppEvent::DefaultModelChanged { previous, current });
+            }
+        }
+    })
+}
+
+/// How many refreshes are running after `event`. Events are lossy, so after
+/// missing some the count starts over rather than risk an indicator that
+/// never clears.
+fn refreshes_in_flight(in_flight: usize, event: &Result<CatalogRefreshEvent, RecvError>) -> usize {
+    match event {
+        Ok(CatalogRefreshEvent::RefreshStarted { .. }) => in_flight + 1,
+        Ok(
+            CatalogRefreshEvent::RefreshSucceeded { .. }
+            | CatalogRefreshEvent::RefreshFailed { .. },
+        ) => in_flight.saturating_sub(1),
+        Ok(CatalogRefreshEvent::DefaultModelChanged { .. }) => in_flight,
+        Err(_) => 0,
+    }
+}
+
+/// Send the catalog's client version notice unless it is the one last sent.
+async fn announce_client_version_notice(
+    models_manager: &ModelsManager,
+    config: &Config,
+    app_event_tx: &AppEventSender,
+    announced: &mut Option<ClientVersionNotice>,
+) {
+    let Some(notice) = models_manager.client_version_notice(config).await else {
+        return;
+    };
+    if announced.as_ref() != Some(&notice) {
+        *announced = Some(notice.clone());
+        app_event_tx.send(AppEvent::ClientVersionBelowMinimum { notice });
+    }
+}
+
 async fn handle_model_migration_prompt_if_needed(
     tui: &mut tui::Tui,
     config: &mut Config,
@@ -189,7 +320,15 @@ async fn handle_model_migration_prompt_if_needed(
     app_event_tx: &AppEventSender,
     models_manager: Arc<ModelsManager>,
 ) -> Option<AppExitInfo> {
-    let available_models = models_manager.list_models(config).await;
+    // With a prefetch running, the live catalog arrives in the background;
+    // upgrades are declared on built-in and remote presets, which are known
+    // without waiting on the network.
+    let available_models = PresetCatalog::new(&models_manager, config)
+        .load(CatalogLoadOptions {
+            cache_only: config.prefetch_model_catalog,
+            ..Default::default()
+        })
+        .await;
     let upgrade = available_models
         .iter()
         .find(|preset| preset.model == model)
@@ -352,6 +491,16 @@ impl App {
             auth_manager.clone(),
             SessionSource::Cli,
         ));
+        spawn_new_model_watcher(
+            conversation_manager.get_models_manager(),
+            &config,
+            app_event_tx.clone(),
+        );
+        spawn_catalog_sync_watcher(
+            &conversation_manager.get_models_manager(),
+            app_event_tx.clone(),
+        );
+        spawn_model_catalog_prefetch(conversation_manager.get_models_manager(), &config);
         let mut model = conversation_manager
             .get_models_manager()
             .get_model(&config.model, &config)
@@ -595,8 +744,8 @@ impl App {
             AppEvent::OpenResumePicker => {
                 match crate::resume_picker::run_resume_picker(
                     tui,
-                    &self.config.codex_home,
-                    &self.config.model_provider_id,
+                    &self.config,
+                    self.server.get_models_manager(),
                     false,
                 )
                 .await?
@@ -767,11 +916,27 @@ impl App {
                 self.current_model = model;
             }
             AppEvent::OpenReasoningPopup { model } => {
-                self.chat_widget.open_reasoning_popup(model);
+                self.chat_widget.open_reasoning_popup(model.view());
             }
             AppEvent::OpenAllModelsPopup { models } => {
                 self.chat_widget.open_all_models_popup(models);
             }
+            AppEvent::ModelCatalogChanged => {
+                self.chat_widget.refresh_model_completions();
+            }
+            AppEvent::NewModelsAvailable { models } => {
+                self.chat_widget.open_new_models_notice(models);
+            }
+            AppEvent::ClientVersionBelowMinimum { notice } => {
+                self.chat_widget.on_client_version_notice(&notice);
+            }
+            AppEvent::CatalogSyncing(syncing) => {
+                self.chat_widget.set_catalog_syncing(syncing);
+            }
+            AppEvent::DefaultModelChanged { previous, current } => {
+                self.chat_widget
+                    .on_default_model_changed(&previous, current.as_deref());
+            }
             AppEvent::OpenFullAccessConfirmation { preset } => {
                 self.chat_widget.open_full_access_confirmation(preset);
             }
@@ -837,6 +1002,7 @@ impl App {
                                         approval_policy: Some(preset.approval),
                                         sandbox_policy: Some(preset.sandbox.clone()),
                                         model: None,
+                                        model_provider: None,
                                         effort: None,
                                         summary: None,
                                     },
@@ -871,7 +1037,10 @@ impl App {
                 let profile = self.active_profile.as_deref();
                 match ConfigEditsBuilder::new(&self.config.codex_home)
                     .with_profile(profile)
-                    .set_model(Some(model.as_str()), effort)
+                    .set_model_with_effort_selection(
+                        Some(model.as_str()),
+                        Some(EffortSelection::from(effort)),
+                    )
                     .apply()
                     .await
                 {
@@ -1122,6 +1291,8 @@ impl App {
                         "E L I C I T A T I O N".to_string(),
                     ));
                 }
+                // The prompt already shows everything there is to see.
+                ApprovalRequest::CostLimit { .. } => {}
             },
         }
         Ok(true)
@@ -1129,12 +1300,9 @@ impl App {
 
     fn reasoning_label(reasoning_effort: Option<ReasoningEffortConfig>) -> &'static str {
         match reasoning_effort {
-            Some(ReasoningEffortConfig::Minimal) => "minimal",
-            Some(ReasoningEffortConfig::Low) => "low",
-            Some(ReasoningEffortConfig::Medium) => "medium",
-            Some(ReasoningEffortConfig::High) => "high",
-            Some(ReasoningEffortConfig::XHigh) => "xhigh",
-            None | Some(ReasoningEffortConfig::None) => "default",
+            Some(ReasoningEffortConfig::None) => "default",
+            Some(effort) => effort.as_str(),
+            None => EffortSelection::AUTO,
         }
     }
 
@@ -1334,6 +1502,8 @@ mod tests {
     use codex_core::AuthManager;
     use codex_core::CodexAuth;
     use codex_core::ConversationManager;
+    use codex_core::ModelCatalog;
+    use codex_core::ModelProviderInfo;
     use codex_core::protocol::AskForApproval;
     use codex_core::protocol::Event;
     use codex_core::protocol::EventMsg;
@@ -1344,6 +1514,7 @@ mod tests {
     use std::path::PathBuf;
     use std::sync::Arc;
     use std::sync::atomic::AtomicBool;
+    use std::time::Instant;
 
     async fn make_test_app() -> App {
         let (chat_widget, app_event_tx, _rx, _op_rx) = make_chatwidget_manual_with_sender().await;
@@ -1424,7 +1595,54 @@ mod tests {
     }
 
     fn all_model_presets() -> Vec<ModelPreset> {
-        codex_core::models_manager::model_presets::all_model_presets().clone()
+        PresetCatalog::static_presets(CatalogLoadOptions {
+            include_hidden: true,
+            ..Default::default()
+        })
+        .cloned()
+        .collect()
+    }
+
+    #[tokio::test]
+    async fn catalog_prefetch_runs_in_the_background() {
+        let (chat_widget, _app_event_tx, _rx, _op_rx) = make_chatwidget_manual_with_sender().await;
+        let mut config = chat_widget.config_ref().clone();
+        // Accepts connections but never answers, so the fetch stays in flight.
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").expect("bind");
+        let addr = listener.local_addr().expect("local addr");
+        config.model_providers.insert(
+            "gateway".to_string(),
+            ModelProviderInfo {
+                name: "Gateway".to_string(),
+                base_url: Some(format!("http://{addr}/v1")),
+                model_catalog: Some(ModelCatalog::Openai),
+                ..config.model_provider.clone()
+            },
+        );
+        let models_manager = Arc::new(ModelsManager::new(AuthManager::from_auth_for_testing(
+            CodexAuth::from_api_key("Test API Key"),
+        )));
+
+        assert!(spawn_model_catalog_prefetch(models_manager.clone(), &config).is_none());
+
+        config.prefetch_model_catalog = true;
+        let started = Instant::now();
+        let handle = spawn_model_catalog_prefetch(models_manager.clone(), &config)
+            .expect("prefetch enabled");
+        assert!(started.elapsed() < Duration::from_millis(500));
+
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        let stats = models_manager.prefetch_stats();
+        assert!(stats.started && !stats.completed, "{stats:?}");
+        models_manager.note_picker_opened();
+        assert_eq!(models_manager.prefetch_stats().picker_served_cold, 1);
+
+        handle.await.expect("prefetch task");
+        assert!(models_manager.prefetch_stats().completed);
+        drop(listener);
+
+        config.offline = true;
+        assert!(spawn_model_catalog_prefetch(models_manager, &config).is_none());
     }
 
     #[tokio::test]
@@ -1548,6 +1766,9 @@ mod tests {
                 session_id: ConversationId::new(),
                 model: "gpt-test".to_string(),
                 model_provider_id: "test-provider".to_string(),
+                model_catalog_offline: false,
+                instructions_addendum_active: false,
+                disabled_tools: Vec::new(),
                 approval_policy: AskForApproval::Never,
                 sandbox_policy: SandboxPolicy::ReadOnly,
                 cwd: PathBuf::from("/home/user/project"),
@@ -1603,6 +1824,9 @@ mod tests {
             session_id: conversation_id,
             model: "gpt-test".to_string(),
             model_provider_id: "test-provider".to_string(),
+            model_catalog_offline: false,
+            instructions_addendum_active: false,
+            disabled_tools: Vec::new(),
             approval_policy: AskForApproval::Never,
             sandbox_policy: SandboxPolicy::ReadOnly,
             cwd: PathBuf::from("/home/user/project"),
@@ -1630,6 +1854,38 @@ mod tests {
         }
     }
 
+    #[test]
+    fn overlapping_refreshes_keep_the_indicator_until_the_last_ends() {
+        use codex_core::models_manager::refresh_events::RefreshReason;
+
+        let started = Ok(CatalogRefreshEvent::RefreshStarted {
+            reason: RefreshReason::StaleCache,
+        });
+        let succeeded = Ok(CatalogRefreshEvent::RefreshSucceeded {
+            revision: 1,
+            added: Vec::new(),
+            removed: Vec::new(),
+        });
+        let failed = Ok(CatalogRefreshEvent::RefreshFailed {
+            error: "unreachable".to_string(),
+        });
+
+        let mut in_flight = refreshes_in_flight(0, &started);
+        in_flight = refreshes_in_flight(in_flight, &started);
+        in_flight = refreshes_in_flight(in_flight, &succeeded);
+        assert_eq!(in_flight, 1, "one refresh is still running");
+        in_flight = refreshes_in_flight(in_flight, &failed);
+        assert_eq!(in_flight, 0);
+        assert_eq!(refreshes_in_flight(in_flight, &succeeded), 0);
+
+        // After missed events the indicator clears rather than stick.
+        in_flight = refreshes_in_flight(in_flight, &started);
+        assert_eq!(
+            refreshes_in_flight(in_flight, &Err(RecvError::Lagged(3))),
+            0
+        );
+    }
+
     #[tokio::test]
     async fn session_summary_skip_zero_usage() {
         assert!(session_summary(TokenUsage::default(), None).is_none());
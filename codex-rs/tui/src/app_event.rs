@@ -1,6 +1,7 @@
 use std::path::PathBuf;
 
 use codex_common::approval_presets::ApprovalPreset;
+use codex_core::models_manager::client_version::ClientVersionNotice;
 use codex_core::protocol::ConversationPathResponseEvent;
 use codex_core::protocol::Event;
 use codex_core::protocol::RateLimitSnapshot;
@@ -80,6 +81,29 @@ pub(crate) enum AppEvent {
         models: Vec<ModelPreset>,
     },
 
+    /// The model catalog changed; reload what `/model` completes from.
+    ModelCatalogChanged,
+
+    /// A catalog refresh added models the user has not been told about yet.
+    NewModelsAvailable {
+        models: Vec<ModelPreset>,
+    },
+
+    /// The catalog recommends a newer Codex than this one.
+    ClientVersionBelowMinimum {
+        notice: ClientVersionNotice,
+    },
+
+    /// Whether a catalog refresh is running, for the footer's indicator.
+    CatalogSyncing(bool),
+
+    /// A catalog refresh moved the default model; `current` is `None` when
+    /// the catalog lists no model any more.
+    DefaultModelChanged {
+        previous: String,
+        current: Option<String>,
+    },
+
     /// Open the confirmation prompt before enabling full access mode.
     OpenFullAccessConfirmation {
         preset: ApprovalPreset,
@@ -56,6 +56,11 @@ pub(crate) enum ApprovalRequest {
         request_id: RequestId,
         message: String,
     },
+    CostLimit {
+        id: String,
+        cost_usd: f64,
+        limit_usd: f64,
+    },
 }
 
 /// Modal overlay asking the user to approve or deny one or more requests.
@@ -123,6 +128,10 @@ impl ApprovalOverlay {
                 elicitation_options(),
                 format!("{server_name} needs your approval."),
             ),
+            ApprovalVariant::CostLimit { .. } => (
+                cost_limit_options(),
+                "Keep going past the session cost limit?".to_string(),
+            ),
         };
 
         let header = Box::new(ColumnRenderable::with([
@@ -183,6 +192,9 @@ impl ApprovalOverlay {
                 ) => {
                     self.handle_elicitation_decision(server_name, request_id, *decision);
                 }
+                (ApprovalVariant::CostLimit { id }, ApprovalDecision::Review(decision)) => {
+                    self.handle_cost_limit_decision(id, decision.clone());
+                }
                 _ => {}
             }
         }
@@ -221,6 +233,14 @@ impl ApprovalOverlay {
             }));
     }
 
+    fn handle_cost_limit_decision(&self, id: &str, decision: ReviewDecision) {
+        self.app_event_tx
+            .send(AppEvent::CodexOp(Op::CostLimitApproval {
+                id: id.to_string(),
+                decision,
+            }));
+    }
+
     fn advance_queue(&mut self) {
         if let Some(next) = self.queue.pop() {
             self.set_current(next);
@@ -296,6 +316,9 @@ impl BottomPaneView for ApprovalOverlay {
                         ElicitationAction::Cancel,
                     );
                 }
+                ApprovalVariant::CostLimit { id } => {
+                    self.handle_cost_limit_decision(id, ReviewDecision::Abort);
+                }
             }
         }
         self.queue.clear();
@@ -405,6 +428,26 @@ impl From<ApprovalRequest> for ApprovalRequestState {
                     header: Box::new(header),
                 }
             }
+            ApprovalRequest::CostLimit {
+                id,
+                cost_usd,
+                limit_usd,
+            } => {
+                let header = Paragraph::new(vec![
+                    Line::from(vec![
+                        "Estimated session cost: ".into(),
+                        format!("${cost_usd:.2}").bold(),
+                        format!(" (limit ${limit_usd:.2})").dim(),
+                    ]),
+                    Line::from(""),
+                    Line::from("Codex stops before its next model request unless you continue."),
+                ])
+                .wrap(Wrap { trim: false });
+                Self {
+                    variant: ApprovalVariant::CostLimit { id },
+                    header: Box::new(header),
+                }
+            }
         }
     }
 }
@@ -423,6 +466,9 @@ enum ApprovalVariant {
         server_name: String,
         request_id: RequestId,
     },
+    CostLimit {
+        id: String,
+    },
 }
 
 #[derive(Clone)]
@@ -526,6 +572,29 @@ fn elicitation_options() -> Vec<ApprovalOption> {
     ]
 }
 
+fn cost_limit_options() -> Vec<ApprovalOption> {
+    vec![
+        ApprovalOption {
+            label: "Yes, run this turn".to_string(),
+            decision: ApprovalDecision::Review(ReviewDecision::Approved),
+            display_shortcut: None,
+            additional_shortcuts: vec![key_hint::plain(KeyCode::Char('y'))],
+        },
+        ApprovalOption {
+            label: "Yes, and don't ask again this session".to_string(),
+            decision: ApprovalDecision::Review(ReviewDecision::ApprovedForSession),
+            display_shortcut: None,
+            additional_shortcuts: vec![key_hint::plain(KeyCode::Char('a'))],
+        },
+        ApprovalOption {
+            label: "No, stop here".to_string(),
+            decision: ApprovalDecision::Review(ReviewDecision::Denied),
+            display_shortcut: Some(key_hint::plain(KeyCode::Esc)),
+            additional_shortcuts: vec![key_hint::plain(KeyCode::Char('n'))],
+        },
+    ]
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -635,6 +704,33 @@ mod tests {
         assert!(rx.try_recv().is_err());
     }
 
+    #[test]
+    fn cost_limit_prompt_can_stop_asking_for_the_session() {
+        let (tx, mut rx) = unbounded_channel::<AppEvent>();
+        let tx = AppEventSender::new(tx);
+        let mut view = ApprovalOverlay::new(
+            ApprovalRequest::CostLimit {
+                id: "turn-1".to_string(),
+                cost_usd: 12.5,
+                limit_usd: 10.0,
+            },
+            tx,
+            Features::with_defaults(),
+        );
+        view.handle_key_event(KeyEvent::new(KeyCode::Char('a'), KeyModifiers::NONE));
+        let mut decisions = Vec::new();
+        while let Ok(ev) = rx.try_recv() {
+            if let AppEvent::CodexOp(Op::CostLimitApproval { id, decision }) = ev {
+                decisions.push((id, decision));
+            }
+        }
+        assert_eq!(
+            decisions,
+            vec![("turn-1".to_string(), ReviewDecision::ApprovedForSession)]
+        );
+        assert!(view.is_complete());
+    }
+
     #[test]
     fn header_includes_command_snippet() {
         let (tx, _rx) = unbounded_channel::<AppEvent>();
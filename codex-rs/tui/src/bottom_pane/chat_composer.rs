@@ -27,6 +27,7 @@ use super::footer::footer_height;
 use super::footer::render_footer;
 use super::footer::reset_mode_after_activity;
 use super::footer::toggle_shortcut_mode;
+use super::model_popup::ModelPopup;
 use super::paste_burst::CharDecision;
 use super::paste_burst::PasteBurst;
 use super::skill_popup::SkillPopup;
@@ -57,6 +58,7 @@ use crate::history_cell;
 use crate::ui_consts::LIVE_PREFIX_COLS;
 use codex_core::skills::model::SkillMetadata;
 use codex_file_search::FileMatch;
+use codex_protocol::openai_models::ModelPreset;
 use std::cell::RefCell;
 use std::collections::HashMap;
 use std::path::Path;
@@ -118,8 +120,12 @@ pub(crate) struct ChatComposer {
     footer_hint_override: Option<Vec<(String, String)>>,
     context_window_percent: Option<i64>,
     context_window_used_tokens: Option<i64>,
+    catalog_syncing: bool,
     skills: Option<Vec<SkillMetadata>>,
     dismissed_skill_popup_token: Option<String>,
+    // Presets offered as completions for the argument of `/model`.
+    model_completions: Vec<ModelPreset>,
+    dismissed_model_popup_token: Option<String>,
 }
 
 /// Popup state – at most one can be visible at any time.
@@ -128,6 +134,7 @@ enum ActivePopup {
     Command(CommandPopup),
     File(FileSearchPopup),
     Skill(SkillPopup),
+    Model(ModelPopup),
 }
 
 const FOOTER_SPACING_HEIGHT: u16 = 0;
@@ -166,8 +173,11 @@ impl ChatComposer {
             footer_hint_override: None,
             context_window_percent: None,
             context_window_used_tokens: None,
+            catalog_syncing: false,
             skills: None,
             dismissed_skill_popup_token: None,
+            model_completions: Vec::new(),
+            dismissed_model_popup_token: None,
         };
         // Apply configuration via the setter to keep side-effects centralized.
         this.set_disable_paste_burst(disable_paste_burst);
@@ -178,6 +188,15 @@ impl ChatComposer {
         self.skills = skills;
     }
 
+    /// Replace the presets `/model` completes from, e.g. after a catalog
+    /// refresh. An open completion list is updated in place.
+    pub(crate) fn set_model_completions(&mut self, presets: Vec<ModelPreset>) {
+        if let ActivePopup::Model(popup) = &mut self.active_popup {
+            popup.set_presets(presets.clone());
+        }
+        self.model_completions = presets;
+    }
+
     fn layout_areas(&self, area: Rect) -> [Rect; 3] {
         let footer_props = self.footer_props();
         let footer_hint_height = self
@@ -193,6 +212,9 @@ impl ChatComposer {
             ActivePopup::Skill(popup) => {
                 Constraint::Max(popup.calculate_required_height(area.width))
             }
+            ActivePopup::Model(popup) => {
+                Constraint::Max(popup.calculate_required_height(area.width))
+            }
             ActivePopup::None => Constraint::Max(footer_total_height),
         };
         let [composer_rect, popup_rect] =
@@ -491,6 +513,7 @@ impl ChatComposer {
             ActivePopup::Command(_) => self.handle_key_event_with_slash_popup(key_event),
             ActivePopup::File(_) => self.handle_key_event_with_file_popup(key_event),
             ActivePopup::Skill(_) => self.handle_key_event_with_skill_popup(key_event),
+            ActivePopup::Model(_) => self.handle_key_event_with_model_popup(key_event),
             ActivePopup::None => self.handle_key_event_without_popup(key_event),
         };
 
@@ -889,6 +912,111 @@ impl ChatComposer {
         }
     }
 
+    fn handle_key_event_with_model_popup(&mut self, key_event: KeyEvent) -> (InputResult, bool) {
+        if self.handle_shortcut_overlay_key(&key_event) {
+            return (InputResult::None, true);
+        }
+        if key_event.code == KeyCode::Esc {
+            let next_mode = esc_hint_mode(self.footer_mode, self.is_task_running);
+            if next_mode != self.footer_mode {
+                self.footer_mode = next_mode;
+                return (InputResult::None, true);
+            }
+        } else {
+            self.footer_mode = reset_mode_after_activity(self.footer_mode);
+        }
+
+        let ActivePopup::Model(popup) = &mut self.active_popup else {
+            unreachable!();
+        };
+
+        match key_event {
+            KeyEvent {
+                code: KeyCode::Up, ..
+            }
+            | KeyEvent {
+                code: KeyCode::Char('p'),
+                modifiers: KeyModifiers::CONTROL,
+                ..
+            } => {
+                popup.move_up();
+                (InputResult::None, true)
+            }
+            KeyEvent {
+                code: KeyCode::Down,
+                ..
+            }
+            | KeyEvent {
+                code: KeyCode::Char('n'),
+                modifiers: KeyModifiers::CONTROL,
+                ..
+            } => {
+                popup.move_down();
+                (InputResult::None, true)
+            }
+            KeyEvent {
+                code: KeyCode::Esc, ..
+            } => {
+                if let Some(tok) = self.current_model_token() {
+                    self.dismissed_model_popup_token = Some(tok);
+                }
+                self.active_popup = ActivePopup::None;
+                (InputResult::None, true)
+            }
+            KeyEvent {
+                code: KeyCode::Tab, ..
+            } => {
+                // Complete the argument to the preset id and leave the list
+                // open, so Enter then switches to it.
+                if let Some(id) = popup.selected_preset().map(|preset| preset.id.clone()) {
+                    let text = format!("/{} {id}", SlashCommand::Model.command());
+                    self.textarea.set_text(&text);
+                    self.textarea.set_cursor(text.len());
+                }
+                (InputResult::None, true)
+            }
+            KeyEvent {
+                code: KeyCode::Enter,
+                modifiers: KeyModifiers::NONE,
+                ..
+            } => {
+                let Some(preset) = popup.selected_preset().cloned() else {
+                    return self.handle_key_event_without_popup(key_event);
+                };
+                self.select_model_completion(preset);
+                self.active_popup = ActivePopup::None;
+                (InputResult::None, true)
+            }
+            input => self.handle_input_basic(input),
+        }
+    }
+
+    /// Switch to `preset` the way choosing it in the `/model` picker does.
+    fn select_model_completion(&mut self, preset: ModelPreset) {
+        self.textarea.set_text("");
+        self.app_event_tx
+            .send(AppEvent::OpenReasoningPopup { model: preset });
+    }
+
+    /// The preset named by a complete `/model <id>` line, matched by id or
+    /// model slug.
+    fn model_completion_for_line(&self, first_line: &str) -> Option<ModelPreset> {
+        let (name, rest) = parse_slash_name(first_line)?;
+        if name != SlashCommand::Model.command() || rest.is_empty() {
+            return None;
+        }
+        let wanted = rest.trim();
+        self.model_completions
+            .iter()
+            .find(|preset| preset.id == wanted)
+            .or_else(|| {
+                self.model_completions
+                    .iter()
+                    .find(|preset| preset.model == wanted)
+            })
+            .cloned()
+    }
+
     fn is_image_path(path: &str) -> bool {
         let lower = path.to_ascii_lowercase();
         lower.ends_with(".png") || lower.ends_with(".jpg") || lower.ends_with(".jpeg")
@@ -1022,6 +1150,27 @@ impl ChatComposer {
         Self::current_prefixed_token(&self.textarea, '$', true)
     }
 
+    /// The argument typed after `/model` when the cursor is on it. The whole
+    /// first line must be `/model` followed by at most one argument.
+    fn current_model_token(&self) -> Option<String> {
+        if self.model_completions.is_empty() {
+            return None;
+        }
+        let text = self.textarea.text();
+        if text.contains('\n') {
+            return None;
+        }
+        let rest = text
+            .strip_prefix('/')?
+            .strip_prefix(SlashCommand::Model.command())?;
+        let arg = rest.strip_prefix(char::is_whitespace)?.trim_start();
+        let arg_start = text.len() - arg.len();
+        if arg.contains(char::is_whitespace) || self.textarea.cursor() < arg_start {
+            return None;
+        }
+        Some(arg.to_string())
+    }
+
     /// Replace the active `@token` (the one under the cursor) with `path`.
     ///
     /// The algorithm mirrors `current_at_token` so replacement works no matter
@@ -1179,6 +1328,10 @@ impl ChatComposer {
                 // but Enter should still dispatch the command rather than submit
                 // literal text.
                 let first_line = self.textarea.text().lines().next().unwrap_or("");
+                if let Some(preset) = self.model_completion_for_line(first_line) {
+                    self.select_model_completion(preset);
+                    return (InputResult::None, true);
+                }
                 if let Some((name, rest)) = parse_slash_name(first_line)
                     && rest.is_empty()
                     && let Some((_n, cmd)) = built_in_slash_commands()
@@ -1628,6 +1781,7 @@ impl ChatComposer {
             is_task_running: self.is_task_running,
             context_window_percent: self.context_window_percent,
             context_window_used_tokens: self.context_window_used_tokens,
+            catalog_syncing: self.catalog_syncing,
         }
     }
 
@@ -1651,15 +1805,24 @@ impl ChatComposer {
     fn sync_popups(&mut self) {
         let file_token = Self::current_at_token(&self.textarea);
         let skill_token = self.current_skill_token();
+        let model_token = self.current_model_token();
 
-        let allow_command_popup = file_token.is_none() && skill_token.is_none();
+        let allow_command_popup =
+            file_token.is_none() && skill_token.is_none() && model_token.is_none();
         self.sync_command_popup(allow_command_popup);
 
         if matches!(self.active_popup, ActivePopup::Command(_)) {
             self.dismissed_file_popup_token = None;
             self.dismissed_skill_popup_token = None;
+            self.dismissed_model_popup_token = None;
+            return;
+        }
+
+        if let Some(token) = model_token {
+            self.sync_model_popup(token);
             return;
         }
+        self.dismissed_model_popup_token = None;
 
         if let Some(token) = skill_token {
             self.sync_skill_popup(token);
@@ -1675,7 +1838,7 @@ impl ChatComposer {
         self.dismissed_file_popup_token = None;
         if matches!(
             self.active_popup,
-            ActivePopup::File(_) | ActivePopup::Skill(_)
+            ActivePopup::File(_) | ActivePopup::Skill(_) | ActivePopup::Model(_)
         ) {
             self.active_popup = ActivePopup::None;
         }
@@ -1850,6 +2013,21 @@ impl ChatComposer {
         }
     }
 
+    fn sync_model_popup(&mut self, query: String) {
+        if self.dismissed_model_popup_token.as_ref() == Some(&query) {
+            return;
+        }
+
+        match &mut self.active_popup {
+            ActivePopup::Model(popup) => popup.set_query(&query),
+            _ => {
+                let mut popup = ModelPopup::new(self.model_completions.clone());
+                popup.set_query(&query);
+                self.active_popup = ActivePopup::Model(popup);
+            }
+        }
+    }
+
     fn set_has_focus(&mut self, has_focus: bool) {
         self.has_focus = has_focus;
     }
@@ -1867,6 +2045,13 @@ impl ChatComposer {
         self.context_window_used_tokens = used_tokens;
     }
 
+    /// Returns whether the footer changed.
+    pub(crate) fn set_catalog_syncing(&mut self, syncing: bool) -> bool {
+        let changed = self.catalog_syncing != syncing;
+        self.catalog_syncing = syncing;
+        changed
+    }
+
     pub(crate) fn set_esc_backtrack_hint(&mut self, show: bool) {
         self.esc_backtrack_hint = show;
         if show {
@@ -1900,6 +2085,7 @@ impl Renderable for ChatComposer {
                 ActivePopup::Command(c) => c.calculate_required_height(width),
                 ActivePopup::File(c) => c.calculate_required_height(),
                 ActivePopup::Skill(c) => c.calculate_required_height(width),
+                ActivePopup::Model(c) => c.calculate_required_height(width),
             }
     }
 
@@ -1915,6 +2101,9 @@ impl Renderable for ChatComposer {
             ActivePopup::Skill(popup) => {
                 popup.render_ref(popup_rect, buf);
             }
+            ActivePopup::Model(popup) => {
+                popup.render_ref(popup_rect, buf);
+            }
             ActivePopup::None => {
                 let footer_props = self.footer_props();
                 let custom_height = self.custom_footer_height();
@@ -2025,6 +2214,9 @@ fn prompt_selection_action(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use codex_common::test_catalog::catalog;
+    use codex_common::test_catalog::preset;
+    use codex_common::test_catalog::provider_preset;
     use image::ImageBuffer;
     use image::Rgba;
     use pretty_assertions::assert_eq;
@@ -4266,4 +4458,143 @@ mod tests {
         );
         assert_eq!(composer.attached_images.len(), 1);
     }
+
+    fn model_composer() -> (ChatComposer, tokio::sync::mpsc::UnboundedReceiver<AppEvent>) {
+        let (tx, rx) = unbounded_channel::<AppEvent>();
+        let mut composer = ChatComposer::new(
+            true,
+            AppEventSender::new(tx),
+            false,
+            "Ask Codex to do anything".to_string(),
+            false,
+        );
+        composer.set_model_completions(catalog([
+            preset("gpt-5.1-codex").display_name("GPT-5.1 Codex"),
+            preset("gpt-5.1-codex-max").display_name("GPT-5.1 Codex Max"),
+            provider_preset("lmstudio", "qwen2.5-coder-7b").display_name("Qwen 2.5 Coder 7B"),
+        ]));
+        (composer, rx)
+    }
+
+    fn rendered_composer(composer: &ChatComposer) -> String {
+        use ratatui::Terminal;
+        use ratatui::backend::TestBackend;
+
+        let mut terminal = Terminal::new(TestBackend::new(60, 8)).expect("terminal");
+        terminal
+            .draw(|f| composer.render(f.area(), f.buffer_mut()))
+            .expect("draw composer");
+        let buffer = terminal.backend().buffer();
+        (0..buffer.area.height)
+            .map(|y| {
+                (0..buffer.area.width)
+                    .map(|x| buffer[(x, y)].symbol())
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    fn selected_model_id(composer: &ChatComposer) -> Option<String> {
+        match &composer.active_popup {
+            ActivePopup::Model(popup) => popup.selected_preset().map(|preset| preset.id.clone()),
+            _ => panic!("model completions are not shown"),
+        }
+    }
+
+    #[test]
+    fn model_argument_lists_matching_presets_by_display_name() {
+        let (mut composer, _rx) = model_composer();
+        type_chars_humanlike(&mut composer, &"/model gpt".chars().collect::<Vec<_>>());
+
+        let rendered = rendered_composer(&composer);
+        assert!(rendered.contains("GPT-5.1 Codex Max"), "{rendered}");
+        assert!(rendered.contains("gpt-5.1-codex-max"), "{rendered}");
+        assert!(!rendered.contains("Qwen"), "{rendered}");
+        assert_eq!(
+            selected_model_id(&composer).as_deref(),
+            Some("gpt-5.1-codex")
+        );
+
+        let _ = composer.handle_key_event(KeyEvent::new(KeyCode::Down, KeyModifiers::NONE));
+        assert_eq!(
+            selected_model_id(&composer).as_deref(),
+            Some("gpt-5.1-codex-max")
+        );
+    }
+
+    #[test]
+    fn tab_inserts_the_id_of_the_selected_model() {
+        let (mut composer, _rx) = model_composer();
+        type_chars_humanlike(&mut composer, &"/model qwen".chars().collect::<Vec<_>>());
+
+        let _ = composer.handle_key_event(KeyEvent::new(KeyCode::Tab, KeyModifiers::NONE));
+
+        assert_eq!(composer.textarea.text(), "/model lmstudio/qwen2.5-coder-7b");
+        assert_eq!(
+            selected_model_id(&composer).as_deref(),
+            Some("lmstudio/qwen2.5-coder-7b")
+        );
+    }
+
+    #[test]
+    fn selecting_a_model_completion_switches_like_the_picker() {
+        let (mut composer, mut rx) = model_composer();
+        type_chars_humanlike(&mut composer, &"/model qwen".chars().collect::<Vec<_>>());
+
+        let (result, _) =
+            composer.handle_key_event(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+
+        assert!(matches!(result, InputResult::None));
+        assert_eq!(composer.textarea.text(), "");
+        assert!(matches!(composer.active_popup, ActivePopup::None));
+        match rx.try_recv() {
+            Ok(AppEvent::OpenReasoningPopup { model }) => {
+                assert_eq!(model.id, "lmstudio/qwen2.5-coder-7b");
+            }
+            other => panic!("expected the reasoning popup: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn submitting_a_complete_model_line_switches_to_it() {
+        let (mut composer, mut rx) = model_composer();
+        type_chars_humanlike(
+            &mut composer,
+            &"/model gpt-5.1-codex-max".chars().collect::<Vec<_>>(),
+        );
+        // The first Esc shows the footer hint; the second dismisses the list.
+        let _ = composer.handle_key_event(KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE));
+        let _ = composer.handle_key_event(KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE));
+        assert!(matches!(composer.active_popup, ActivePopup::None));
+
+        let (result, _) =
+            composer.handle_key_event(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+
+        assert!(matches!(result, InputResult::None));
+        match rx.try_recv() {
+            Ok(AppEvent::OpenReasoningPopup { model }) => {
+                assert_eq!(model.id, "gpt-5.1-codex-max");
+            }
+            other => panic!("expected the reasoning popup: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn model_completions_follow_catalog_refreshes() {
+        let (mut composer, _rx) = model_composer();
+        type_chars_humanlike(&mut composer, &"/model llama".chars().collect::<Vec<_>>());
+        assert_eq!(selected_model_id(&composer), None);
+        assert!(rendered_composer(&composer).contains("no matching models"));
+
+        composer.set_model_completions(catalog([
+            provider_preset("lmstudio", "llama-3.1-8b").display_name("Llama 3.1 8B")
+        ]));
+
+        assert_eq!(
+            selected_model_id(&composer).as_deref(),
+            Some("lmstudio/llama-3.1-8b")
+        );
+        assert!(rendered_composer(&composer).contains("Llama 3.1 8B"));
+    }
 }
@@ -185,6 +185,7 @@ impl CommandPopup {
                     display_shortcut: None,
                     description: Some(description),
                     wrap_indent: None,
+                    max_lines: None,
                     disabled_reason: None,
                 }
             })
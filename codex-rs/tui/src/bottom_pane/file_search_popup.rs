@@ -133,6 +133,7 @@ impl WidgetRef for &FileSearchPopup {
                     description: None,
                     wrap_indent: None,
                     disabled_reason: None,
+                    max_lines: None,
                 })
                 .collect()
         };
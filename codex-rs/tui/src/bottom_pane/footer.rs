@@ -22,6 +22,8 @@ pub(crate) struct FooterProps {
     pub(crate) is_task_running: bool,
     pub(crate) context_window_percent: Option<i64>,
     pub(crate) context_window_used_tokens: Option<i64>,
+    /// A catalog refresh is running; see `AppEvent::CatalogSyncing`.
+    pub(crate) catalog_syncing: bool,
 }
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
@@ -89,6 +91,7 @@ fn footer_lines(props: FooterProps) -> Vec<Line<'static>> {
                 props.context_window_percent,
                 props.context_window_used_tokens,
             );
+            push_catalog_syncing(&mut line, props.catalog_syncing);
             line.push_span(" · ".dim());
             line.extend(vec![
                 key_hint::plain(KeyCode::Char('?')).into(),
@@ -110,10 +113,21 @@ fn footer_lines(props: FooterProps) -> Vec<Line<'static>> {
             shortcut_overlay_lines(state)
         }
         FooterMode::EscHint => vec![esc_hint_line(props.esc_backtrack_hint)],
-        FooterMode::ContextOnly => vec![context_window_line(
-            props.context_window_percent,
-            props.context_window_used_tokens,
-        )],
+        FooterMode::ContextOnly => {
+            let mut line = context_window_line(
+                props.context_window_percent,
+                props.context_window_used_tokens,
+            );
+            push_catalog_syncing(&mut line, props.catalog_syncing);
+            vec![line]
+        }
+    }
+}
+
+fn push_catalog_syncing(line: &mut Line<'static>, catalog_syncing: bool) {
+    if catalog_syncing {
+        line.push_span(" · ".dim());
+        line.push_span("syncing models…".dim());
     }
 }
 
@@ -453,6 +467,7 @@ mod tests {
                 is_task_running: false,
                 context_window_percent: None,
                 context_window_used_tokens: None,
+                catalog_syncing: false,
             },
         );
 
@@ -465,6 +480,7 @@ mod tests {
                 is_task_running: false,
                 context_window_percent: None,
                 context_window_used_tokens: None,
+                catalog_syncing: false,
             },
         );
 
@@ -477,6 +493,7 @@ mod tests {
                 is_task_running: false,
                 context_window_percent: None,
                 context_window_used_tokens: None,
+                catalog_syncing: false,
             },
         );
 
@@ -489,6 +506,7 @@ mod tests {
                 is_task_running: true,
                 context_window_percent: None,
                 context_window_used_tokens: None,
+                catalog_syncing: false,
             },
         );
 
@@ -501,6 +519,7 @@ mod tests {
                 is_task_running: false,
                 context_window_percent: None,
                 context_window_used_tokens: None,
+                catalog_syncing: false,
             },
         );
 
@@ -513,6 +532,7 @@ mod tests {
                 is_task_running: false,
                 context_window_percent: None,
                 context_window_used_tokens: None,
+                catalog_syncing: false,
             },
         );
 
@@ -525,6 +545,7 @@ mod tests {
                 is_task_running: true,
                 context_window_percent: Some(72),
                 context_window_used_tokens: None,
+                catalog_syncing: false,
             },
         );
 
@@ -537,7 +558,51 @@ mod tests {
                 is_task_running: false,
                 context_window_percent: None,
                 context_window_used_tokens: Some(123_456),
+                catalog_syncing: false,
             },
         );
     }
+
+    #[test]
+    fn catalog_syncing_follows_the_context_indicator() {
+        let props = FooterProps {
+            mode: FooterMode::ShortcutSummary,
+            esc_backtrack_hint: false,
+            use_shift_enter_hint: false,
+            is_task_running: false,
+            context_window_percent: Some(72),
+            context_window_used_tokens: None,
+            catalog_syncing: true,
+        };
+        let text = |props: FooterProps| {
+            footer_lines(props)
+                .iter()
+                .map(|line| {
+                    line.spans
+                        .iter()
+                        .map(|span| span.content.as_ref())
+                        .collect::<String>()
+                })
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            text(props),
+            vec!["72% context left · syncing models… · ? for shortcuts".to_string()]
+        );
+        assert_eq!(
+            text(FooterProps {
+                mode: FooterMode::ContextOnly,
+                ..props
+            }),
+            vec!["72% context left · syncing models…".to_string()]
+        );
+        assert_eq!(
+            text(FooterProps {
+                catalog_syncing: false,
+                ..props
+            }),
+            vec!["72% context left · ? for shortcuts".to_string()]
+        );
+    }
 }
@@ -12,6 +12,10 @@ use ratatui::text::Span;
 use ratatui::widgets::Block;
 use ratatui::widgets::Paragraph;
 use ratatui::widgets::Widget;
+use ratatui::widgets::Wrap;
+
+use codex_common::fuzzy_match::fuzzy_indices;
+use codex_common::fuzzy_match::fuzzy_match;
 
 use crate::app_event_sender::AppEventSender;
 use crate::key_hint::KeyBinding;
@@ -44,7 +48,15 @@ pub(crate) struct SelectionItem {
     pub actions: Vec<SelectionAction>,
     pub dismiss_on_select: bool,
     pub search_value: Option<String>,
+    /// Extra terms matched by fuzzy search alongside `name` and `search_value`.
+    pub search_aliases: Vec<String>,
+    /// Hidden until a search query is entered, letting grouped views collapse
+    /// into one ranked list while filtering.
+    pub search_only: bool,
     pub disabled_reason: Option<String>,
+    /// Most lines the item wraps to while it is not selected. The selected
+    /// item always shows its full description.
+    pub description_max_lines: Option<usize>,
 }
 
 pub(crate) struct SelectionViewParams {
@@ -53,9 +65,17 @@ pub(crate) struct SelectionViewParams {
     pub footer_hint: Option<Line<'static>>,
     pub items: Vec<SelectionItem>,
     pub is_searchable: bool,
+    /// Rank search results by fuzzy subsequence match instead of substring.
+    pub fuzzy_search: bool,
     pub search_placeholder: Option<String>,
+    /// Query the view opens with, already applied as a filter.
+    pub initial_search_query: Option<String>,
     pub header: Box<dyn Renderable>,
     pub initial_selected_idx: Option<usize>,
+    /// Show the selected item's description under the list, one line per
+    /// line of it, instead of in a column beside the names. Screen readers
+    /// read a row's columns as one run, so the accessible layout uses this.
+    pub details_below: bool,
 }
 
 impl Default for SelectionViewParams {
@@ -66,9 +86,12 @@ impl Default for SelectionViewParams {
             footer_hint: None,
             items: Vec::new(),
             is_searchable: false,
+            fuzzy_search: false,
             search_placeholder: None,
+            initial_search_query: None,
             header: Box::new(()),
             initial_selected_idx: None,
+            details_below: false,
         }
     }
 }
@@ -80,12 +103,14 @@ pub(crate) struct ListSelectionView {
     complete: bool,
     app_event_tx: AppEventSender,
     is_searchable: bool,
+    fuzzy_search: bool,
     search_query: String,
     search_placeholder: Option<String>,
     filtered_indices: Vec<usize>,
     last_selected_actual_idx: Option<usize>,
     header: Box<dyn Renderable>,
     initial_selected_idx: Option<usize>,
+    details_below: bool,
 }
 
 impl ListSelectionView {
@@ -107,7 +132,11 @@ impl ListSelectionView {
             complete: false,
             app_event_tx,
             is_searchable: params.is_searchable,
-            search_query: String::new(),
+            fuzzy_search: params.fuzzy_search,
+            search_query: params
+                .initial_search_query
+                .filter(|_| params.is_searchable)
+                .unwrap_or_default(),
             search_placeholder: if params.is_searchable {
                 params.search_placeholder
             } else {
@@ -117,6 +146,7 @@ impl ListSelectionView {
             last_selected_actual_idx: None,
             header,
             initial_selected_idx: params.initial_selected_idx,
+            details_below: params.details_below,
         };
         s.apply_filter();
         s
@@ -142,6 +172,17 @@ impl ListSelectionView {
             })
             .or_else(|| self.initial_selected_idx.take());
 
+        if self.is_searchable && self.fuzzy_search && !self.search_query.is_empty() {
+            self.filtered_indices = fuzzy_rank(&self.items, &self.search_query);
+            // Ranking reorders the rows, so follow the best match.
+            let len = self.filtered_indices.len();
+            self.state.selected_idx = (len > 0).then_some(0);
+            let visible = Self::max_visible_rows(len);
+            self.state.clamp_selection(len);
+            self.state.ensure_visible(len, visible);
+            return;
+        }
+
         if self.is_searchable && !self.search_query.is_empty() {
             let query_lower = self.search_query.to_lowercase();
             self.filtered_indices = self
@@ -154,7 +195,11 @@ impl ListSelectionView {
                 })
                 .collect();
         } else {
-            self.filtered_indices = (0..self.items.len()).collect();
+            self.filtered_indices = self
+                .items
+                .iter()
+                .positions(|item| !item.search_only)
+                .collect();
         }
 
         let len = self.filtered_indices.len();
@@ -207,18 +252,32 @@ impl ListSelectionView {
                     };
                     let wrap_prefix_width = UnicodeWidthStr::width(wrap_prefix.as_str());
                     let display_name = format!("{wrap_prefix}{name_with_marker}");
-                    let description = is_selected
-                        .then(|| item.selected_description.clone())
-                        .flatten()
-                        .or_else(|| item.description.clone());
+                    let description = if self.details_below {
+                        None
+                    } else {
+                        is_selected
+                            .then(|| item.selected_description.clone())
+                            .flatten()
+                            .or_else(|| item.description.clone())
+                    };
                     let wrap_indent = description.is_none().then_some(wrap_prefix_width);
+                    let match_indices = (self.fuzzy_search && !self.search_query.is_empty())
+                        .then(|| fuzzy_indices(name, &self.search_query))
+                        .flatten()
+                        .map(|indices| {
+                            let offset = wrap_prefix.chars().count();
+                            indices.into_iter().map(|idx| idx + offset).collect()
+                        });
                     GenericDisplayRow {
                         name: display_name,
                         display_shortcut: item.display_shortcut,
-                        match_indices: None,
+                        match_indices,
                         description,
                         wrap_indent,
                         disabled_reason: item.disabled_reason.clone(),
+                        max_lines: (!is_selected)
+                            .then_some(item.description_max_lines)
+                            .flatten(),
                     }
                 })
             })
@@ -269,6 +328,37 @@ impl ListSelectionView {
         self.last_selected_actual_idx.take()
     }
 
+    /// The selected item's description, for views that show it under the
+    /// list.
+    fn details_below_list(&self) -> Option<Paragraph<'static>> {
+        if !self.details_below {
+            return None;
+        }
+        let item = self
+            .state
+            .selected_idx
+            .and_then(|visible_idx| self.filtered_indices.get(visible_idx))
+            .and_then(|actual_idx| self.items.get(*actual_idx))?;
+        let details = item
+            .selected_description
+            .as_ref()
+            .or(item.description.as_ref())?;
+        let lines: Vec<Line<'static>> = details
+            .lines()
+            .map(|line| Line::from(line.to_string()))
+            .collect();
+        Some(Paragraph::new(lines).wrap(Wrap { trim: false }))
+    }
+
+    /// Rows the details under the list take at `width`, with the blank row
+    /// that separates them from the list.
+    fn details_height(details: Option<&Paragraph<'static>>, width: u16) -> u16 {
+        details.map_or(0, |details| {
+            // Subtract 4 for the padding on the left and right, as the header.
+            (details.line_count(width.saturating_sub(4)) as u16).saturating_add(1)
+        })
+    }
+
     fn rows_width(total_width: u16) -> u16 {
         total_width.saturating_sub(2)
     }
@@ -431,6 +521,10 @@ impl Renderable for ListSelectionView {
         // Subtract 4 for the padding on the left and right of the header.
         let mut height = self.header.desired_height(width.saturating_sub(4));
         height = height.saturating_add(rows_height + 3);
+        height = height.saturating_add(Self::details_height(
+            self.details_below_list().as_ref(),
+            width,
+        ));
         if self.is_searchable {
             height = height.saturating_add(1);
         }
@@ -467,11 +561,15 @@ impl Renderable for ListSelectionView {
             MAX_POPUP_ROWS,
             rows_width.saturating_add(1),
         );
-        let [header_area, _, search_area, list_area] = Layout::vertical([
+        let details = self.details_below_list();
+        let details_height = Self::details_height(details.as_ref(), content_area.width);
+        let [header_area, _, search_area, list_area, _, details_area] = Layout::vertical([
             Constraint::Max(header_height),
             Constraint::Max(1),
             Constraint::Length(if self.is_searchable { 1 } else { 0 }),
             Constraint::Length(rows_height),
+            Constraint::Length(details_height.min(1)),
+            Constraint::Length(details_height.saturating_sub(1)),
         ])
         .areas(content_area.inset(Insets::vh(1, 2)));
 
@@ -517,6 +615,10 @@ impl Renderable for ListSelectionView {
             );
         }
 
+        if let Some(details) = details {
+            details.render(details_area, buf);
+        }
+
         if let Some(hint) = &self.footer_hint {
             let hint_area = Rect {
                 x: footer_area.x + 2,
@@ -529,12 +631,34 @@ impl Renderable for ListSelectionView {
     }
 }
 
+/// Rank the items matching `query` by their best fuzzy score across `name`,
+/// `search_value`, and `search_aliases`. Items without a `search_value` never
+/// match. Ties keep their original order.
+fn fuzzy_rank(items: &[SelectionItem], query: &str) -> Vec<usize> {
+    let mut ranked: Vec<(i32, usize)> = items
+        .iter()
+        .enumerate()
+        .filter_map(|(idx, item)| {
+            let search_value = item.search_value.as_deref()?;
+            std::iter::once(item.name.as_str())
+                .chain(std::iter::once(search_value))
+                .chain(item.search_aliases.iter().map(String::as_str))
+                .filter_map(|candidate| fuzzy_match(candidate, query).map(|(_, score)| score))
+                .min()
+                .map(|score| (score, idx))
+        })
+        .collect();
+    ranked.sort_by_key(|(score, _)| *score);
+    ranked.into_iter().map(|(_, idx)| idx).collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::app_event::AppEvent;
     use crate::bottom_pane::popup_consts::standard_popup_hint_line;
     use insta::assert_snapshot;
+    use pretty_assertions::assert_eq;
     use ratatui::layout::Rect;
     use tokio::sync::mpsc::unbounded_channel;
 
@@ -838,4 +962,89 @@ mod tests {
             render_lines_with_width(&view, 24)
         );
     }
+
+    fn fuzzy_items() -> Vec<SelectionItem> {
+        let model = |name: &str, aliases: &[&str]| SelectionItem {
+            name: name.to_string(),
+            search_value: Some(name.to_string()),
+            search_aliases: aliases.iter().map(ToString::to_string).collect(),
+            dismiss_on_select: true,
+            ..Default::default()
+        };
+        vec![
+            model("gpt-5.1-codex-max", &[]),
+            model("gpt-5.1-codex-mini", &[]),
+            model("gpt-5.2", &[]),
+            model("codex-auto-fast", &[]),
+            model("qwen2.5-coder-7b", &["lmstudio"]),
+            SelectionItem {
+                name: "All models".to_string(),
+                dismiss_on_select: true,
+                ..Default::default()
+            },
+        ]
+    }
+
+    #[test]
+    fn fuzzy_rank_prefers_prefix_matches_and_keeps_ties_in_order() {
+        let items = fuzzy_items();
+        assert_eq!(fuzzy_rank(&items, "codex"), vec![3, 0, 1]);
+        assert_eq!(fuzzy_rank(&items, "CODEX"), vec![3, 0, 1]);
+    }
+
+    #[test]
+    fn fuzzy_rank_matches_subsequences_and_aliases() {
+        let items = fuzzy_items();
+        assert_eq!(fuzzy_rank(&items, "g52"), vec![2]);
+        assert_eq!(fuzzy_rank(&items, "lms"), vec![4]);
+        assert_eq!(fuzzy_rank(&items, "zzz"), Vec::<usize>::new());
+    }
+
+    #[test]
+    fn fuzzy_search_reveals_search_only_items_until_query_cleared() {
+        let (tx_raw, _rx) = unbounded_channel::<AppEvent>();
+        let tx = AppEventSender::new(tx_raw);
+        let mut items = fuzzy_items();
+        items[0].search_only = true;
+        items[1].search_only = true;
+        let mut view = ListSelectionView::new(
+            SelectionViewParams {
+                title: Some("Select Model".to_string()),
+                items,
+                is_searchable: true,
+                fuzzy_search: true,
+                ..Default::default()
+            },
+            tx,
+        );
+        assert_eq!(view.filtered_indices, vec![2, 3, 4, 5]);
+
+        view.set_search_query("codex".to_string());
+        assert_eq!(view.filtered_indices, vec![3, 0, 1]);
+        assert_eq!(view.state.selected_idx, Some(0));
+
+        view.set_search_query(String::new());
+        assert_eq!(view.filtered_indices, vec![2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn fuzzy_search_highlights_matched_name_characters() {
+        let (tx_raw, _rx) = unbounded_channel::<AppEvent>();
+        let tx = AppEventSender::new(tx_raw);
+        let mut view = ListSelectionView::new(
+            SelectionViewParams {
+                items: fuzzy_items(),
+                is_searchable: true,
+                fuzzy_search: true,
+                ..Default::default()
+            },
+            tx,
+        );
+        view.set_search_query("g52".to_string());
+
+        let rows = view.build_rows();
+        assert_eq!(rows.len(), 1);
+        // Indices are offset past the "› " selection prefix.
+        assert_eq!(rows[0].match_indices, Some(vec![2, 6, 8]));
+    }
 }
@@ -30,6 +30,7 @@ mod experimental_features_view;
 mod file_search_popup;
 mod footer;
 mod list_selection_view;
+mod model_popup;
 mod prompt_args;
 mod skill_popup;
 pub(crate) use list_selection_view::SelectionViewParams;
@@ -54,6 +55,7 @@ pub(crate) enum CancellationEvent {
 pub(crate) use chat_composer::ChatComposer;
 pub(crate) use chat_composer::InputResult;
 use codex_protocol::custom_prompts::CustomPrompt;
+use codex_protocol::openai_models::ModelPreset;
 
 use crate::status_indicator_widget::StatusIndicatorWidget;
 pub(crate) use experimental_features_view::BetaFeatureItem;
@@ -144,6 +146,11 @@ impl BottomPane {
         self.request_redraw();
     }
 
+    pub(crate) fn set_model_completions(&mut self, presets: Vec<ModelPreset>) {
+        self.composer.set_model_completions(presets);
+        self.request_redraw();
+    }
+
     pub fn status_widget(&self) -> Option<&StatusIndicatorWidget> {
         self.status.as_ref()
     }
@@ -404,6 +411,12 @@ impl BottomPane {
         self.request_redraw();
     }
 
+    pub(crate) fn set_catalog_syncing(&mut self, syncing: bool) {
+        if self.composer.set_catalog_syncing(syncing) {
+            self.request_redraw();
+        }
+    }
+
     /// Show a generic list selection view with the provided items.
     pub(crate) fn show_selection_view(&mut self, params: list_selection_view::SelectionViewParams) {
         let view = list_selection_view::ListSelectionView::new(params, self.app_event_tx.clone());
@@ -0,0 +1,336 @@
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::style::Color;
+use ratatui::style::Modifier;
+use ratatui::style::Style;
+use ratatui::widgets::WidgetRef;
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
+
+use super::popup_consts::MAX_POPUP_ROWS;
+use super::scroll_state::ScrollState;
+use crate::render::Insets;
+use crate::render::RectExt;
+use codex_common::fuzzy_match::fuzzy_match;
+use codex_protocol::openai_models::ModelPreset;
+use codex_protocol::openai_models::PresetRef;
+
+/// A preset that matches the query: its index in the popup's presets and,
+/// for a display-name match, the matched char positions.
+struct PresetMatch {
+    idx: usize,
+    indices: Option<Vec<usize>>,
+}
+
+/// Completions for the argument of `/model`: presets matching the typed text
+/// by display name or id.
+///
+/// The matches are worked out when the query or presets change, and each
+/// frame draws them from [`PresetRef`] views, so rendering allocates nothing.
+pub(crate) struct ModelPopup {
+    query: String,
+    presets: Vec<ModelPreset>,
+    matches: Vec<PresetMatch>,
+    state: ScrollState,
+}
+
+impl ModelPopup {
+    pub(crate) fn new(presets: Vec<ModelPreset>) -> Self {
+        let mut popup = Self {
+            query: String::new(),
+            presets,
+            matches: Vec::new(),
+            state: ScrollState::new(),
+        };
+        popup.matches = popup.filtered();
+        popup
+    }
+
+    pub(crate) fn set_presets(&mut self, presets: Vec<ModelPreset>) {
+        self.presets = presets;
+        self.refresh_matches();
+    }
+
+    pub(crate) fn set_query(&mut self, query: &str) {
+        self.query = query.to_string();
+        self.refresh_matches();
+    }
+
+    pub(crate) fn calculate_required_height(&self, _width: u16) -> u16 {
+        let visible = self.matches.len().clamp(1, MAX_POPUP_ROWS);
+        visible as u16
+    }
+
+    pub(crate) fn move_up(&mut self) {
+        let len = self.matches.len();
+        self.state.move_up_wrap(len);
+        self.state.ensure_visible(len, MAX_POPUP_ROWS.min(len));
+    }
+
+    pub(crate) fn move_down(&mut self) {
+        let len = self.matches.len();
+        self.state.move_down_wrap(len);
+        self.state.ensure_visible(len, MAX_POPUP_ROWS.min(len));
+    }
+
+    pub(crate) fn selected_preset(&self) -> Option<&ModelPreset> {
+        let idx = self.state.selected_idx?;
+        let preset_match = self.matches.get(idx)?;
+        self.presets.get(preset_match.idx)
+    }
+
+    fn refresh_matches(&mut self) {
+        self.matches = self.filtered();
+        let len = self.matches.len();
+        self.state.clamp_selection(len);
+        self.state.ensure_visible(len, MAX_POPUP_ROWS.min(len));
+    }
+
+    /// Presets matching the query, best first. Match indices are only kept
+    /// for display-name matches, since the display name is what is rendered.
+    fn filtered(&self) -> Vec<PresetMatch> {
+        let filter = self.query.trim();
+        if filter.is_empty() {
+            return (0..self.presets.len())
+                .map(|idx| PresetMatch { idx, indices: None })
+                .collect();
+        }
+
+        let mut out: Vec<(PresetMatch, i32)> = Vec::new();
+        for (idx, preset) in self.presets.iter().enumerate() {
+            let by_name = fuzzy_match(&preset.display_name, filter);
+            let by_id = fuzzy_match(&preset.id, filter);
+            let best = match (by_name, by_id) {
+                (Some((_, name_score)), Some((_, id_score))) if id_score < name_score => {
+                    Some((None, id_score))
+                }
+                (Some((indices, score)), _) => Some((Some(indices), score)),
+                (None, Some((_, score))) => Some((None, score)),
+                (None, None) => None,
+            };
+            if let Some((indices, score)) = best {
+                out.push((PresetMatch { idx, indices }, score));
+            }
+        }
+
+        out.sort_by(|(a, a_score), (b, b_score)| {
+            a_score.cmp(b_score).then_with(|| {
+                let an = &self.presets[a.idx].display_name;
+                let bn = &self.presets[b.idx].display_name;
+                an.cmp(bn)
+            })
+        });
+
+        out.into_iter()
+            .map(|(preset_match, _)| preset_match)
+            .collect()
+    }
+
+    /// First match shown: the scroll position, moved so the selection is in
+    /// view.
+    fn start_idx(&self, visible_items: usize) -> usize {
+        let mut start_idx = self
+            .state
+            .scroll_top
+            .min(self.matches.len().saturating_sub(1));
+        if let Some(sel) = self.state.selected_idx {
+            if sel < start_idx {
+                start_idx = sel;
+            } else if visible_items > 0 {
+                let bottom = start_idx + visible_items - 1;
+                if sel > bottom {
+                    start_idx = sel + 1 - visible_items;
+                }
+            }
+        }
+        start_idx
+    }
+}
+
+impl WidgetRef for ModelPopup {
+    fn render_ref(&self, area: Rect, buf: &mut Buffer) {
+        let area = area.inset(Insets::tlbr(0, 2, 0, 0));
+        if area.height == 0 {
+            return;
+        }
+        if self.matches.is_empty() {
+            let style = Style::default().add_modifier(Modifier::DIM | Modifier::ITALIC);
+            buf.set_stringn(
+                area.x,
+                area.y,
+                "no matching models",
+                area.width as usize,
+                style,
+            );
+            return;
+        }
+
+        let visible_items = MAX_POPUP_ROWS
+            .min(self.matches.len())
+            .min(area.height as usize);
+        let start_idx = self.start_idx(visible_items);
+        let visible = self
+            .matches
+            .iter()
+            .enumerate()
+            .skip(start_idx)
+            .take(visible_items);
+
+        // Descriptions line up two cells after the widest visible name.
+        let name_width = visible
+            .clone()
+            .map(|(_, preset_match)| self.presets[preset_match.idx].display_name.width())
+            .max()
+            .unwrap_or(0);
+        let desc_col = name_width
+            .saturating_add(2)
+            .min(area.width.saturating_sub(1) as usize);
+
+        for (row, (i, preset_match)) in visible.enumerate() {
+            let preset = self.presets[preset_match.idx].view();
+            let selected = Some(i) == self.state.selected_idx;
+            render_preset_row(
+                Rect {
+                    x: area.x,
+                    y: area.y + row as u16,
+                    width: area.width,
+                    height: 1,
+                },
+                buf,
+                preset,
+                preset_match.indices.as_deref().unwrap_or_default(),
+                desc_col,
+                selected,
+            );
+        }
+    }
+}
+
+/// One popup row: the display name with the matched chars in bold, then the
+/// id dimmed from `desc_col` on, the whole row cyan and bold when selected.
+/// Text past the row's end is cut with an ellipsis. Writes straight into
+/// `buf`, without building owned spans.
+fn render_preset_row(
+    area: Rect,
+    buf: &mut Buffer,
+    preset: PresetRef<'_>,
+    match_indices: &[usize],
+    desc_col: usize,
+    selected: bool,
+) {
+    let selected_style = Style::default()
+        .fg(Color::Cyan)
+        .add_modifier(Modifier::BOLD);
+    let (name_style, desc_style) = if selected {
+        (selected_style, selected_style)
+    } else {
+        (
+            Style::default(),
+            Style::default().add_modifier(Modifier::DIM),
+        )
+    };
+    let right = area.right();
+    let name_right = right.min(area.x.saturating_add(desc_col.saturating_sub(2) as u16));
+
+    // Walk whole grapheme clusters, as the shared row renderer does; a
+    // cluster is bold when any of its chars matched.
+    let mut x = area.x;
+    let mut char_idx = 0usize;
+    for grapheme in preset.display_name.graphemes(true) {
+        let cluster = char_idx..char_idx + grapheme.chars().count();
+        char_idx = cluster.end;
+        let width = grapheme.width() as u16;
+        if x.saturating_add(width) > name_right {
+            if x < right {
+                buf.set_stringn(x, area.y, "…", 1, name_style);
+            }
+            break;
+        }
+        let style = if match_indices.iter().any(|idx| cluster.contains(idx)) {
+            name_style.add_modifier(Modifier::BOLD)
+        } else {
+            name_style
+        };
+        (x, _) = buf.set_stringn(x, area.y, grapheme, width as usize, style);
+    }
+
+    let desc_x = area.x.saturating_add(desc_col as u16).max(x);
+    if desc_x >= right {
+        return;
+    }
+    let room = (right - desc_x) as usize;
+    if preset.id.width() <= room {
+        buf.set_stringn(desc_x, area.y, preset.id, room, desc_style);
+    } else {
+        let (end, _) = buf.set_stringn(desc_x, area.y, preset.id, room - 1, desc_style);
+        buf.set_stringn(end, area.y, "…", 1, desc_style);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::alloc_counter::allocations_during;
+    use codex_common::test_catalog::catalog;
+    use codex_common::test_catalog::preset;
+    use codex_common::test_catalog::provider_preset;
+    use pretty_assertions::assert_eq;
+
+    fn render_lines(popup: &ModelPopup, width: u16) -> Vec<String> {
+        let area = Rect::new(0, 0, width, popup.calculate_required_height(width));
+        let mut buf = Buffer::empty(area);
+        popup.render_ref(area, &mut buf);
+        (0..area.height)
+            .map(|y| {
+                (0..area.width)
+                    .map(|x| buf[(x, y)].symbol())
+                    .collect::<String>()
+                    .trim_end()
+                    .to_string()
+            })
+            .collect()
+    }
+
+    fn popup() -> ModelPopup {
+        ModelPopup::new(catalog([
+            preset("gpt-5.1-codex").display_name("GPT-5.1 Codex"),
+            preset("gpt-5.1-codex-max").display_name("GPT-5.1 Codex Max"),
+            provider_preset("lmstudio", "qwen2.5-coder-7b").display_name("Qwen 2.5 Coder 7B"),
+        ]))
+    }
+
+    #[test]
+    fn rows_show_the_name_then_the_id() {
+        let mut popup = popup();
+        popup.set_query("max");
+
+        assert_eq!(
+            render_lines(&popup, 60),
+            vec!["  GPT-5.1 Codex Max  gpt-5.1-codex-max".to_string()]
+        );
+    }
+
+    #[test]
+    fn long_ids_end_with_an_ellipsis() {
+        let mut popup = popup();
+        popup.set_query("qwen");
+
+        assert_eq!(
+            render_lines(&popup, 32),
+            vec!["  Qwen 2.5 Coder 7B  lmstudio/q…".to_string()]
+        );
+    }
+
+    #[test]
+    fn rendering_a_frame_does_not_allocate() {
+        let mut popup = popup();
+        popup.set_query("codex");
+        popup.move_down();
+        let area = Rect::new(0, 0, 40, MAX_POPUP_ROWS as u16);
+        let mut buf = Buffer::empty(area);
+
+        let allocations = allocations_during(|| popup.render_ref(area, &mut buf));
+
+        assert_eq!(allocations, 0);
+    }
+}
@@ -8,10 +8,11 @@ use ratatui::style::Stylize;
 use ratatui::text::Line;
 use ratatui::text::Span;
 use ratatui::widgets::Widget;
-use unicode_width::UnicodeWidthChar;
+use unicode_segmentation::UnicodeSegmentation;
 use unicode_width::UnicodeWidthStr;
 
 use crate::key_hint::KeyBinding;
+use crate::render::line_utils::line_to_static;
 
 use super::scroll_state::ScrollState;
 
@@ -24,6 +25,9 @@ pub(crate) struct GenericDisplayRow {
     pub description: Option<String>,       // optional grey text after the name
     pub disabled_reason: Option<String>,   // optional disabled message
     pub wrap_indent: Option<usize>,        // optional indent for wrapped lines
+    /// Most lines the row may wrap to. Longer rows are cut at a word boundary
+    /// and end with an ellipsis.
+    pub max_lines: Option<usize>,
 }
 
 fn line_width(line: &Line<'_>) -> usize {
@@ -61,13 +65,13 @@ fn truncate_line_to_width(line: Line<'static>, max_width: usize) -> Line<'static
         }
 
         let mut truncated = String::new();
-        for ch in text.chars() {
-            let ch_width = UnicodeWidthChar::width(ch).unwrap_or(0);
-            if used + ch_width > max_width {
+        for grapheme in text.graphemes(true) {
+            let grapheme_width = UnicodeWidthStr::width(grapheme);
+            if used + grapheme_width > max_width {
                 break;
             }
-            truncated.push(ch);
-            used += ch_width;
+            truncated.push_str(grapheme);
+            used += grapheme_width;
         }
 
         if !truncated.is_empty() {
@@ -162,34 +166,27 @@ fn build_full_line(row: &GenericDisplayRow, desc_col: usize) -> Line<'static> {
     let mut used_width = 0usize;
     let mut truncated = false;
 
-    if let Some(idxs) = row.match_indices.as_ref() {
-        let mut idx_iter = idxs.iter().peekable();
-        for (char_idx, ch) in row.name.chars().enumerate() {
-            let ch_w = UnicodeWidthChar::width(ch).unwrap_or(0);
-            let next_width = used_width.saturating_add(ch_w);
-            if next_width > name_limit {
-                truncated = true;
-                break;
-            }
-            used_width = next_width;
-
-            if idx_iter.peek().is_some_and(|next| **next == char_idx) {
-                idx_iter.next();
-                name_spans.push(ch.to_string().bold());
-            } else {
-                name_spans.push(ch.to_string().into());
-            }
+    // Walk whole grapheme clusters so emoji sequences and combining marks are
+    // never split. Match indices are char positions, so a cluster is bold when
+    // any of its chars matched.
+    let match_indices = row.match_indices.as_deref().unwrap_or_default();
+    let mut char_idx = 0usize;
+    for grapheme in row.name.graphemes(true) {
+        let grapheme_chars = grapheme.chars().count();
+        let cluster = char_idx..char_idx + grapheme_chars;
+        char_idx += grapheme_chars;
+
+        let next_width = used_width.saturating_add(UnicodeWidthStr::width(grapheme));
+        if next_width > name_limit {
+            truncated = true;
+            break;
         }
-    } else {
-        for ch in row.name.chars() {
-            let ch_w = UnicodeWidthChar::width(ch).unwrap_or(0);
-            let next_width = used_width.saturating_add(ch_w);
-            if next_width > name_limit {
-                truncated = true;
-                break;
-            }
-            used_width = next_width;
-            name_spans.push(ch.to_string().into());
+        used_width = next_width;
+
+        if match_indices.iter().any(|idx| cluster.contains(idx)) {
+            name_spans.push(grapheme.to_string().bold());
+        } else {
+            name_spans.push(grapheme.to_string().into());
         }
     }
 
@@ -220,6 +217,54 @@ fn build_full_line(row: &GenericDisplayRow, desc_col: usize) -> Line<'static> {
     Line::from(full_spans)
 }
 
+/// Word-wrap `line` for `row` at `width`, aligning continuation lines with
+/// the description column and applying the row's `max_lines` cap.
+fn wrap_row_line(
+    row: &GenericDisplayRow,
+    line: &Line<'static>,
+    desc_col: usize,
+    width: u16,
+) -> Vec<Line<'static>> {
+    use crate::wrapping::RtOptions;
+    use crate::wrapping::word_wrap_line;
+    let continuation_indent = wrap_indent(row, desc_col, width);
+    let options = RtOptions::new(width as usize)
+        .initial_indent(Line::from(""))
+        .subsequent_indent(Line::from(" ".repeat(continuation_indent)));
+    let mut wrapped: Vec<Line<'static>> = word_wrap_line(line, options)
+        .iter()
+        .map(line_to_static)
+        .collect();
+    if let Some(max_lines) = row.max_lines.filter(|max| *max > 0)
+        && wrapped.len() > max_lines
+    {
+        wrapped.truncate(max_lines);
+        if let Some(last) = wrapped.pop() {
+            wrapped.push(end_with_ellipsis(last, width as usize));
+        }
+    }
+    wrapped
+}
+
+/// Mark a line whose continuation was dropped: trailing spaces are removed
+/// and an ellipsis appended, cutting the line further if it would not fit.
+fn end_with_ellipsis(line: Line<'static>, max_width: usize) -> Line<'static> {
+    let style = line.style;
+    let mut spans = line.spans;
+    while let Some(span) = spans.pop() {
+        let trimmed = span.content.trim_end();
+        if !trimmed.is_empty() {
+            spans.push(Span::styled(trimmed.to_string(), span.style));
+            break;
+        }
+    }
+    let line = truncate_line_to_width(Line::from(spans), max_width.saturating_sub(1));
+    let mut spans = line.spans;
+    let ellipsis_style = spans.last().map(|span| span.style).unwrap_or_default();
+    spans.push(Span::styled("…", ellipsis_style));
+    Line::from(spans).style(style)
+}
+
 /// Render a list of rows using the provided ScrollState, with shared styling
 /// and behavior for selection popups.
 pub(crate) fn render_rows(
@@ -280,13 +325,7 @@ pub(crate) fn render_rows(
         }
 
         // Wrap with subsequent indent aligned to the description column.
-        use crate::wrapping::RtOptions;
-        use crate::wrapping::word_wrap_line;
-        let continuation_indent = wrap_indent(row, desc_col, area.width);
-        let options = RtOptions::new(area.width as usize)
-            .initial_indent(Line::from(""))
-            .subsequent_indent(Line::from(" ".repeat(continuation_indent)));
-        let wrapped = word_wrap_line(&full_line, options);
+        let wrapped = wrap_row_line(row, &full_line, desc_col, area.width);
 
         // Render the wrapped lines.
         for line in wrapped {
@@ -404,8 +443,6 @@ pub(crate) fn measure_rows_height(
 
     let desc_col = compute_desc_col(rows_all, start_idx, visible_items, content_width);
 
-    use crate::wrapping::RtOptions;
-    use crate::wrapping::word_wrap_line;
     let mut total: u16 = 0;
     for row in rows_all
         .iter()
@@ -415,12 +452,102 @@ pub(crate) fn measure_rows_height(
         .map(|(_, r)| r)
     {
         let full_line = build_full_line(row, desc_col);
-        let continuation_indent = wrap_indent(row, desc_col, content_width);
-        let opts = RtOptions::new(content_width as usize)
-            .initial_indent(Line::from(""))
-            .subsequent_indent(Line::from(" ".repeat(continuation_indent)));
-        let wrapped_lines = word_wrap_line(&full_line, opts).len();
+        let wrapped_lines = wrap_row_line(row, &full_line, desc_col, content_width).len();
         total = total.saturating_add(wrapped_lines as u16);
     }
     total.max(1)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn row(name: &str, description: &str) -> GenericDisplayRow {
+        GenericDisplayRow {
+            name: name.to_string(),
+            description: Some(description.to_string()),
+            ..Default::default()
+        }
+    }
+
+    fn text(line: &Line<'_>) -> String {
+        line.iter().map(|span| span.content.as_ref()).collect()
+    }
+
+    /// Where the dimmed description starts, in terminal cells.
+    fn description_column(line: &Line<'_>, description: &str) -> usize {
+        let text = text(line);
+        let start = text.find(description).expect("description in line");
+        UnicodeWidthStr::width(&text[..start])
+    }
+
+    #[test]
+    fn wide_names_keep_descriptions_aligned() {
+        let rows = vec![
+            row("gpt-5.1-codex", "plain"),
+            row("模型-large", "cjk"),
+            row("🚀 rocket", "emoji"),
+            row("cafe\u{301}", "combining"),
+        ];
+        let desc_col = compute_desc_col(&rows, 0, rows.len(), 80);
+        assert_eq!(desc_col, "gpt-5.1-codex".len() + 2);
+
+        for row in &rows {
+            let line = build_full_line(row, desc_col);
+            let description = row.description.as_deref().unwrap_or_default();
+            assert_eq!(
+                description_column(&line, description),
+                desc_col,
+                "{:?}",
+                text(&line)
+            );
+        }
+    }
+
+    #[test]
+    fn narrow_columns_cut_wide_names_between_graphemes() {
+        // Room for 5 cells of name: the third CJK character would straddle
+        // the edge, so it is dropped whole.
+        let line = build_full_line(&row("模型大全", "cjk"), 7);
+        assert_eq!(text(&line), "模型…  cjk");
+        assert_eq!(description_column(&line, "cjk"), 7);
+
+        let line = build_full_line(&row("🚀🚀🚀", "emoji"), 5);
+        assert_eq!(text(&line), "🚀…  emoji");
+
+        // The accent stays on its letter instead of dangling on its own.
+        let line = build_full_line(&row("cafe\u{301}s-model", "combining"), 6);
+        assert_eq!(text(&line), "cafe\u{301}… combining");
+    }
+
+    #[test]
+    fn matched_chars_bold_their_whole_grapheme() {
+        let row = GenericDisplayRow {
+            name: "cafe\u{301}".to_string(),
+            // Index 4 is the combining accent, part of the `é` cluster.
+            match_indices: Some(vec![0, 4]),
+            ..Default::default()
+        };
+        let line = build_full_line(&row, 0);
+        let bold: Vec<&str> = line
+            .iter()
+            .filter(|span| span.style == Style::default().bold())
+            .map(|span| span.content.as_ref())
+            .collect();
+        assert_eq!(bold, vec!["c", "e\u{301}"]);
+    }
+
+    #[test]
+    fn overflowing_lines_never_exceed_the_width() {
+        for width in 1..12 {
+            let line = Line::from(vec!["模型".into(), "🚀-codex".dim(), " 大全".into()]);
+            let truncated = truncate_line_with_ellipsis_if_overflow(line, width);
+            assert!(
+                line_width(&truncated) <= width,
+                "{:?} at {width}",
+                text(&truncated)
+            );
+        }
+    }
+}
@@ -94,6 +94,7 @@ impl SkillPopup {
                     description: Some(description),
                     disabled_reason: None,
                     wrap_indent: None,
+                    max_lines: None,
                 }
             })
             .collect()
@@ -14,7 +14,13 @@ use codex_core::features::FEATURES;
 use codex_core::features::Feature;
 use codex_core::git_info::current_branch_name;
 use codex_core::git_info::local_git_branches;
+use codex_core::models_manager::catalog::CatalogSnapshot;
+use codex_core::models_manager::catalog::PresetCatalog;
+use codex_core::models_manager::client_version::ClientVersionNotice;
 use codex_core::models_manager::manager::ModelsManager;
+use codex_core::models_manager::model_presets::PLUS_RATE_LIMIT_WARNING_PRESET_IDS;
+use codex_core::models_manager::model_presets::RATE_LIMIT_NUDGE_PRESET_ID;
+use codex_core::models_manager::validation::network_warning;
 use codex_core::project_doc::DEFAULT_PROJECT_DOC_FILENAME;
 use codex_core::protocol::AgentMessageDeltaEvent;
 use codex_core::protocol::AgentMessageEvent;
@@ -24,6 +30,8 @@ use codex_core::protocol::AgentReasoningRawContentDeltaEvent;
 use codex_core::protocol::AgentReasoningRawContentEvent;
 use codex_core::protocol::ApplyPatchApprovalRequestEvent;
 use codex_core::protocol::BackgroundEventEvent;
+use codex_core::protocol::CodexErrorInfo;
+use codex_core::protocol::CostLimitApprovalRequestEvent;
 use codex_core::protocol::CreditsSnapshot;
 use codex_core::protocol::DeprecationNoticeEvent;
 use codex_core::protocol::ErrorEvent;
@@ -42,6 +50,8 @@ use codex_core::protocol::McpStartupStatus;
 use codex_core::protocol::McpStartupUpdateEvent;
 use codex_core::protocol::McpToolCallBeginEvent;
 use codex_core::protocol::McpToolCallEndEvent;
+use codex_core::protocol::ModelChangeSource;
+use codex_core::protocol::ModelChangedEvent;
 use codex_core::protocol::Op;
 use codex_core::protocol::PatchApplyBeginEvent;
 use codex_core::protocol::RateLimitSnapshot;
@@ -50,6 +60,7 @@ use codex_core::protocol::ReviewTarget;
 use codex_core::protocol::SkillsListEntry;
 use codex_core::protocol::StreamErrorEvent;
 use codex_core::protocol::TaskCompleteEvent;
+use codex_core::protocol::TaskStartedEvent;
 use codex_core::protocol::TerminalInteractionEvent;
 use codex_core::protocol::TokenUsage;
 use codex_core::protocol::TokenUsageInfo;
@@ -80,6 +91,7 @@ use ratatui::style::Stylize;
 use ratatui::text::Line;
 use ratatui::widgets::Paragraph;
 use ratatui::widgets::Wrap;
+use tokio::sync::TryLockError;
 use tokio::sync::mpsc::UnboundedSender;
 use tokio::task::JoinHandle;
 use tracing::debug;
@@ -134,16 +146,20 @@ use std::path::Path;
 use chrono::Local;
 use codex_common::approval_presets::ApprovalPreset;
 use codex_common::approval_presets::builtin_approval_presets;
+use codex_common::plain_model_fields;
 use codex_core::AuthManager;
 use codex_core::CodexAuth;
 use codex_core::ConversationManager;
 use codex_core::protocol::AskForApproval;
+use codex_core::protocol::NetworkAccess;
 use codex_core::protocol::SandboxPolicy;
 use codex_file_search::FileMatch;
+use codex_protocol::openai_models::EffortSelection;
 use codex_protocol::openai_models::ModelPreset;
+use codex_protocol::openai_models::PresetRef;
 use codex_protocol::openai_models::ReasoningEffort as ReasoningEffortConfig;
+use codex_protocol::openai_models::ReasoningSummaryFormat;
 use codex_protocol::plan_tool::UpdatePlanArgs;
-use strum::IntoEnumIterator;
 
 const USER_SHELL_COMMAND_HELP_TITLE: &str = "Prefix a command with ! to run it locally";
 const USER_SHELL_COMMAND_HELP_HINT: &str = "Example: !ls";
@@ -188,8 +204,9 @@ fn is_standard_tool_call(parsed_cmd: &[ParsedCommand]) -> bool {
 }
 
 const RATE_LIMIT_WARNING_THRESHOLDS: [f64; 3] = [75.0, 90.0, 95.0];
-const NUDGE_MODEL_SLUG: &str = "gpt-5.1-codex-mini";
+const NUDGE_MODEL_SLUG: &str = RATE_LIMIT_NUDGE_PRESET_ID;
 const RATE_LIMIT_SWITCH_PROMPT_THRESHOLD: f64 = 90.0;
+const MODEL_SEARCH_PLACEHOLDER: &str = "Type to search models";
 
 #[derive(Default)]
 struct RateLimitWarningState {
@@ -325,6 +342,9 @@ pub(crate) struct ChatWidget {
     plan_type: Option<PlanType>,
     rate_limit_warnings: RateLimitWarningState,
     rate_limit_switch_prompt: RateLimitSwitchPromptState,
+    /// Models already warned about needing network access the sandbox
+    /// blocks, so each is flagged once per session.
+    network_warned_models: HashSet<String>,
     rate_limit_poller: Option<JoinHandle<()>>,
     // Stream lifecycle controller
     stream_controller: Option<StreamController>,
@@ -340,6 +360,9 @@ pub(crate) struct ChatWidget {
     reasoning_buffer: String,
     // Accumulates full reasoning content for transcript-only recording
     full_reasoning_buffer: String,
+    // How the current turn's model writes reasoning summaries, as core
+    // reported when the turn started
+    reasoning_summary_format: ReasoningSummaryFormat,
     // Current status header shown in the status indicator.
     current_status_header: String,
     // Previous status header to restore after a transient stream retry.
@@ -435,6 +458,7 @@ impl ChatWidget {
         self.bottom_pane
             .set_history_metadata(event.history_log_id, event.history_entry_count);
         self.set_skills(None);
+        self.refresh_model_completions();
         self.conversation_id = Some(event.session_id);
         self.current_rollout_path = Some(event.rollout_path.clone());
         let initial_messages = event.initial_messages.clone();
@@ -449,6 +473,7 @@ impl ChatWidget {
         if let Some(messages) = initial_messages {
             self.replay_initial_messages(messages);
         }
+        self.maybe_warn_network_required(&model_for_header);
         // Ask codex-core to enumerate custom prompts for this session.
         self.submit_op(Op::ListCustomPrompts);
         self.submit_op(Op::ListSkills {
@@ -526,7 +551,9 @@ impl ChatWidget {
         // (between **/**) as the chunk header. Show this header as status.
         self.reasoning_buffer.push_str(&delta);
 
-        if let Some(header) = extract_first_bold(&self.reasoning_buffer) {
+        if self.reasoning_summary_format == ReasoningSummaryFormat::Headed
+            && let Some(header) = extract_first_bold(&self.reasoning_buffer)
+        {
             // Update the shimmer header to the extracted reasoning chunk header.
             self.set_status_header(header);
         } else {
@@ -539,8 +566,10 @@ impl ChatWidget {
         // At the end of a reasoning block, record transcript-only content.
         self.full_reasoning_buffer.push_str(&self.reasoning_buffer);
         if !self.full_reasoning_buffer.is_empty() {
-            let cell =
-                history_cell::new_reasoning_summary_block(self.full_reasoning_buffer.clone());
+            let cell = history_cell::new_reasoning_summary_block(
+                self.full_reasoning_buffer.clone(),
+                self.reasoning_summary_format,
+            );
             self.add_boxed_history(cell);
         }
         self.reasoning_buffer.clear();
@@ -557,7 +586,18 @@ impl ChatWidget {
 
     // Raw reasoning uses the same flow as summarized reasoning
 
-    fn on_task_started(&mut self) {
+    fn on_task_started(&mut self, ev: TaskStartedEvent) {
+        if let Some(format) = ev.reasoning_summary_format {
+            self.reasoning_summary_format = format;
+        }
+        if let TaskStartedEvent {
+            reasoning_effort: Some(effort),
+            reasoning_effort_description: Some(description),
+            ..
+        } = ev
+        {
+            self.add_to_history(history_cell::TurnEffortCell::new(effort, description));
+        }
         self.bottom_pane.clear_ctrl_c_quit_hint();
         self.bottom_pane.set_task_running(true);
         self.retry_status_header = None;
@@ -722,11 +762,43 @@ impl ChatWidget {
         self.maybe_send_next_queued_input();
     }
 
+    /// Unlike other errors, queued input is held back: it would be sent to the
+    /// same unavailable model. The picker offers the rest of the family.
+    fn on_model_unavailable(&mut self, message: String, preset_id: &str) {
+        self.finalize_turn();
+        self.add_to_history(history_cell::new_error_event(message));
+        self.open_model_replacement_popup(preset_id);
+        self.request_redraw();
+    }
+
     fn on_warning(&mut self, message: impl Into<String>) {
         self.add_to_history(history_cell::new_warning_event(message.into()));
         self.request_redraw();
     }
 
+    /// The catalog recommends a newer Codex than this one. Only a warning:
+    /// nothing stops working.
+    pub(crate) fn on_client_version_notice(&mut self, notice: &ClientVersionNotice) {
+        self.on_warning(notice.to_string());
+    }
+
+    /// A catalog refresh moved its default. Only conversations started
+    /// without a chosen model follow it, so this is a notice, not a switch.
+    pub(crate) fn on_default_model_changed(&mut self, previous: &str, current: Option<&str>) {
+        let message = match current {
+            Some(current) => format!(
+                "The default model changed from {previous} to {current}; new conversations without a chosen model use it."
+            ),
+            None => format!("The model catalog no longer lists its default model {previous}."),
+        };
+        self.add_info_message(message, None);
+    }
+
+    /// Show or hide the footer's "syncing models" indicator.
+    pub(crate) fn set_catalog_syncing(&mut self, syncing: bool) {
+        self.bottom_pane.set_catalog_syncing(syncing);
+    }
+
     fn on_mcp_startup_update(&mut self, ev: McpStartupUpdateEvent) {
         let mut status = self.mcp_startup_status.take().unwrap_or_default();
         if let McpStartupStatus::Failed { error } = &ev.status {
@@ -863,6 +935,19 @@ impl ChatWidget {
         );
     }
 
+    fn on_cost_limit_approval_request(&mut self, ev: CostLimitApprovalRequestEvent) {
+        // Sent between model requests, so no answer is streaming to defer behind.
+        self.flush_answer_stream_with_separator();
+        let request = ApprovalRequest::CostLimit {
+            id: ev.turn_id,
+            cost_usd: ev.cost_usd,
+            limit_usd: ev.limit_usd,
+        };
+        self.bottom_pane
+            .push_approval_request(request, &self.config.features);
+        self.request_redraw();
+    }
+
     fn on_exec_command_begin(&mut self, ev: ExecCommandBeginEvent) {
         self.flush_answer_stream_with_separator();
         if is_unified_exec_source(ev.source) {
@@ -1100,6 +1185,22 @@ impl ChatWidget {
         self.set_status(message, additional_details);
     }
 
+    fn on_model_changed(&mut self, event: ModelChangedEvent) {
+        let ModelChangedEvent {
+            previous_model,
+            model,
+            source,
+            ..
+        } = event;
+        match source {
+            ModelChangeSource::Fallback => self.on_warning(format!(
+                "{previous_model} is at capacity, so this turn is retried on {model}. The next turn goes back to {previous_model}."
+            )),
+            // The picker that asked for the switch already shows it.
+            ModelChangeSource::Selected => {}
+        }
+    }
+
     /// Periodic tick to commit at most one queued line to history with a small delay,
     /// animating the output.
     pub(crate) fn on_commit_tick(&mut self) {
@@ -1451,6 +1552,7 @@ impl ChatWidget {
             plan_type: None,
             rate_limit_warnings: RateLimitWarningState::default(),
             rate_limit_switch_prompt: RateLimitSwitchPromptState::default(),
+            network_warned_models: HashSet::new(),
             rate_limit_poller: None,
             stream_controller: None,
             running_commands: HashMap::new(),
@@ -1462,6 +1564,7 @@ impl ChatWidget {
             interrupts: InterruptManager::new(),
             reasoning_buffer: String::new(),
             full_reasoning_buffer: String::new(),
+            reasoning_summary_format: ReasoningSummaryFormat::default(),
             current_status_header: String::from("Working"),
             retry_status_header: None,
             conversation_id: None,
@@ -1537,6 +1640,7 @@ impl ChatWidget {
             plan_type: None,
             rate_limit_warnings: RateLimitWarningState::default(),
             rate_limit_switch_prompt: RateLimitSwitchPromptState::default(),
+            network_warned_models: HashSet::new(),
             rate_limit_poller: None,
             stream_controller: None,
             running_commands: HashMap::new(),
@@ -1548,6 +1652,7 @@ impl ChatWidget {
             interrupts: InterruptManager::new(),
             reasoning_buffer: String::new(),
             full_reasoning_buffer: String::new(),
+            reasoning_summary_format: ReasoningSummaryFormat::default(),
             current_status_header: String::from("Working"),
             retry_status_header: None,
             conversation_id: None,
@@ -2031,7 +2136,7 @@ impl ChatWidget {
                 self.on_agent_reasoning_final();
             }
             EventMsg::AgentReasoningSectionBreak(_) => self.on_reasoning_section_break(),
-            EventMsg::TaskStarted(_) => self.on_task_started(),
+            EventMsg::TaskStarted(ev) => self.on_task_started(ev),
             EventMsg::TaskComplete(TaskCompleteEvent { last_agent_message }) => {
                 self.on_task_complete(last_agent_message)
             }
@@ -2040,6 +2145,10 @@ impl ChatWidget {
                 self.on_rate_limit_snapshot(ev.rate_limits);
             }
             EventMsg::Warning(WarningEvent { message }) => self.on_warning(message),
+            EventMsg::Error(ErrorEvent {
+                message,
+                codex_error_info: Some(CodexErrorInfo::ModelUnavailable { preset_id }),
+            }) => self.on_model_unavailable(message, &preset_id),
             EventMsg::Error(ErrorEvent { message, .. }) => self.on_error(message),
             EventMsg::McpStartupUpdate(ev) => self.on_mcp_startup_update(ev),
             EventMsg::McpStartupComplete(ev) => self.on_mcp_startup_complete(ev),
@@ -2065,6 +2174,7 @@ impl ChatWidget {
             EventMsg::ElicitationRequest(ev) => {
                 self.on_elicitation_request(ev);
             }
+            EventMsg::CostLimitApprovalRequest(ev) => self.on_cost_limit_approval_request(ev),
             EventMsg::ExecCommandBegin(ev) => self.on_exec_command_begin(ev),
             EventMsg::TerminalInteraction(delta) => self.on_terminal_interaction(delta),
             EventMsg::ExecCommandOutputDelta(delta) => self.on_exec_command_output_delta(delta),
@@ -2099,6 +2209,7 @@ impl ChatWidget {
                 additional_details,
                 ..
             }) => self.on_stream_error(message, additional_details),
+            EventMsg::ModelChanged(ev) => self.on_model_changed(ev),
             EventMsg::UserMessage(ev) => {
                 if from_replay {
                     self.on_user_message_event(ev);
@@ -2245,6 +2356,8 @@ impl ChatWidget {
         let total_usage = token_info
             .map(|ti| &ti.total_token_usage)
             .unwrap_or(&default_usage);
+        let catalog = self.catalog_snapshot();
+        let model = crate::status::StatusModel::from_catalog(&self.model, &self.config, &catalog);
         self.add_to_history(crate::status::new_status_output(
             &self.config,
             self.auth_manager.as_ref(),
@@ -2254,7 +2367,7 @@ impl ChatWidget {
             self.rate_limit_snapshot.as_ref(),
             self.plan_type,
             Local::now(),
-            &self.model,
+            model,
         ));
     }
 
@@ -2300,12 +2413,32 @@ impl ChatWidget {
         self.rate_limit_poller = Some(handle);
     }
 
-    fn lower_cost_preset(&self) -> Option<ModelPreset> {
-        let models = self.models_manager.try_list_models(&self.config).ok()?;
-        models
+    /// Offer the catalog snapshot as `/model` completions, leaving out the
+    /// presets the account cannot use.
+    pub(crate) fn refresh_model_completions(&mut self) {
+        let presets = self
+            .catalog_snapshot()
+            .presets()
             .iter()
-            .find(|preset| preset.model == NUDGE_MODEL_SLUG)
+            .filter(|preset| preset.unavailable_for_account.is_none())
             .cloned()
+            .collect();
+        self.bottom_pane.set_model_completions(presets);
+    }
+
+    /// The model presets already loaded, for pickers that can tell the user
+    /// to retry while a refresh holds the catalog.
+    fn loaded_presets(&self) -> Result<Vec<ModelPreset>, TryLockError> {
+        PresetCatalog::new(&self.models_manager, &self.config).load_cached_or_static()
+    }
+
+    /// The presets as last published; rendering cannot wait on a refresh.
+    fn catalog_snapshot(&self) -> Arc<CatalogSnapshot> {
+        PresetCatalog::new(&self.models_manager, &self.config).snapshot()
+    }
+
+    fn lower_cost_preset(&self) -> Option<ModelPreset> {
+        self.catalog_snapshot().find(NUDGE_MODEL_SLUG).cloned()
     }
 
     fn rate_limit_switch_prompt_hidden(&self) -> bool {
@@ -2345,6 +2478,7 @@ impl ChatWidget {
                 approval_policy: None,
                 sandbox_policy: None,
                 model: Some(switch_model.clone()),
+                model_provider: None,
                 effort: Some(Some(default_effort)),
                 summary: None,
             }));
@@ -2404,12 +2538,60 @@ impl ChatWidget {
         });
     }
 
+    /// Announce models a catalog refresh added. Picking one switches to it at
+    /// its default effort; "Dismiss" or Esc closes the notice.
+    pub(crate) fn open_new_models_notice(&mut self, models: Vec<ModelPreset>) {
+        if models.is_empty() {
+            return;
+        }
+        let subtitle = match models.len() {
+            1 => "1 model was added since you last looked.".to_string(),
+            count => format!("{count} models were added since you last looked."),
+        };
+        let mut items: Vec<SelectionItem> = models
+            .into_iter()
+            .map(|preset| {
+                let description = preset
+                    .description
+                    .lines()
+                    .next()
+                    .filter(|line| !line.is_empty())
+                    .map(str::to_string);
+                SelectionItem {
+                    name: format!("Try {}", preset.display_name),
+                    description,
+                    actions: Self::model_selection_actions(
+                        preset.model,
+                        preset.model_provider,
+                        Some(preset.default_reasoning_effort),
+                    ),
+                    dismiss_on_select: true,
+                    ..Default::default()
+                }
+            })
+            .collect();
+        items.push(SelectionItem {
+            name: "Dismiss".to_string(),
+            dismiss_on_select: true,
+            ..Default::default()
+        });
+
+        self.bottom_pane.show_selection_view(SelectionViewParams {
+            title: Some("New in your model catalog".to_string()),
+            subtitle: Some(subtitle),
+            footer_hint: Some(standard_popup_hint_line()),
+            items,
+            ..Default::default()
+        });
+        self.request_redraw();
+    }
+
     /// Open a popup to choose a quick auto model. Selecting "All models"
     /// opens the full picker with every available preset.
     pub(crate) fn open_model_popup(&mut self) {
         let presets: Vec<ModelPreset> =
             // todo(aibrahim): make this async function
-            match self.models_manager.try_list_models(&self.config) {
+            match self.loaded_presets() {
                 Ok(models) => models,
                 Err(_) => {
                     self.add_info_message(
@@ -2420,11 +2602,13 @@ impl ChatWidget {
                     return;
                 }
             };
+        self.models_manager.note_picker_opened();
 
-        let current_label = presets
-            .iter()
-            .find(|preset| preset.model == self.model)
-            .map(|preset| preset.display_name.to_string())
+        let active_preset = self.active_preset();
+        let current_item = self.current_model_item(active_preset.as_ref());
+        let active_id = active_preset.as_ref().map(|preset| preset.id.clone());
+        let current_label = active_preset
+            .map(|preset| preset.display_name)
             .unwrap_or_else(|| self.model.clone());
 
         let (mut auto_presets, other_presets): (Vec<ModelPreset>, Vec<ModelPreset>) = presets
@@ -2438,41 +2622,51 @@ impl ChatWidget {
 
         auto_presets.sort_by_key(|preset| Self::auto_model_order(&preset.model));
 
-        let mut items: Vec<SelectionItem> = auto_presets
-            .into_iter()
-            .map(|preset| {
-                let description =
-                    (!preset.description.is_empty()).then_some(preset.description.clone());
-                let model = preset.model.clone();
-                let actions = Self::model_selection_actions(
-                    model.clone(),
-                    Some(preset.default_reasoning_effort),
-                );
-                SelectionItem {
-                    name: preset.display_name.clone(),
-                    description,
-                    is_current: model == self.model,
-                    is_default: preset.is_default,
-                    actions,
-                    dismiss_on_select: true,
-                    ..Default::default()
-                }
-            })
-            .collect();
+        let mut items: Vec<SelectionItem> = current_item.into_iter().collect();
+        items.extend(auto_presets.into_iter().map(|preset| {
+            let description =
+                (!preset.description.is_empty()).then_some(preset.description.clone());
+            let actions = Self::model_selection_actions(
+                preset.model.clone(),
+                preset.model_provider.clone(),
+                Some(preset.default_reasoning_effort),
+            );
+            let selected_description = self
+                .accessible_pickers()
+                .then(|| Self::plain_model_details(&preset));
+            SelectionItem {
+                name: preset.display_name.clone(),
+                description,
+                selected_description,
+                is_current: active_id.as_ref() == Some(&preset.id),
+                is_default: preset.is_default,
+                actions,
+                dismiss_on_select: true,
+                search_value: Some(preset.id.clone()),
+                search_aliases: Self::model_search_aliases(preset.view()),
+                disabled_reason: Self::unavailable_reason(preset.view()),
+                ..Default::default()
+            }
+        }));
 
+        let mut all_models_idx = None;
+        let default_in_all_models = other_presets.iter().any(|preset| preset.is_default);
         if !other_presets.is_empty() {
-            let all_models = other_presets;
+            let all_models = other_presets.clone();
             let actions: Vec<SelectionAction> = vec![Box::new(move |tx| {
                 tx.send(AppEvent::OpenAllModelsPopup {
                     models: all_models.clone(),
                 });
             })];
 
-            let is_current = !items.iter().any(|item| item.is_current);
+            let is_current = active_id.as_ref().is_some_and(|active_id| {
+                other_presets.iter().any(|preset| &preset.id == active_id)
+            });
             let description = Some(format!(
                 "Choose a specific model and reasoning level (current: {current_label})"
             ));
 
+            all_models_idx = Some(items.len());
             items.push(SelectionItem {
                 name: "All models".to_string(),
                 description,
@@ -2483,15 +2677,79 @@ impl ChatWidget {
             });
         }
 
+        // Typing searches every model, so the quick picks and the "All models"
+        // entry collapse into one ranked list while a filter is active.
+        items.extend(other_presets.into_iter().map(|preset| SelectionItem {
+            search_only: true,
+            ..self.all_models_item(preset, active_id.as_deref())
+        }));
+        // A current model left out of the catalog highlights the default,
+        // which may sit behind "All models".
+        let initial_selected_idx = items
+            .iter()
+            .position(|item| item.is_current)
+            .or_else(|| {
+                items
+                    .iter()
+                    .position(|item| item.is_default && !item.search_only)
+            })
+            .or(all_models_idx.filter(|_| default_in_all_models));
+
         self.bottom_pane.show_selection_view(SelectionViewParams {
+            header: self.model_picker_header(),
             title: Some("Select Model".to_string()),
             subtitle: Some("Pick a quick auto mode or browse all models.".to_string()),
             footer_hint: Some(standard_popup_hint_line()),
             items,
+            is_searchable: true,
+            fuzzy_search: true,
+            search_placeholder: Some(MODEL_SEARCH_PLACEHOLDER.to_string()),
+            initial_selected_idx,
+            details_below: self.accessible_pickers(),
             ..Default::default()
         });
     }
 
+    /// A dim line per catalog whose last refresh failed, shown above the model
+    /// pickers so a stale list is not taken for a current one. Empty once
+    /// every catalog has refreshed successfully again. A catalog that
+    /// recommends a newer Codex adds a line too, and so does a running turn.
+    fn model_picker_header(&self) -> Box<dyn Renderable> {
+        let warnings = self
+            .models_manager
+            .try_catalog_warnings()
+            .unwrap_or_default();
+        let notice = self
+            .models_manager
+            .try_client_version_notice(&self.config)
+            .ok()
+            .flatten();
+        let pending_note = self.pending_switch_note();
+        if warnings.is_empty() && notice.is_none() && pending_note.is_none() {
+            return Box::new(());
+        }
+        let now = chrono::Utc::now();
+        let mut header = ColumnRenderable::new();
+        if let Some(note) = pending_note {
+            header.push(note);
+        }
+        if let Some(notice) = notice {
+            header.push(Line::from(vec!["⚠ ".yellow(), notice.to_string().yellow()]));
+        }
+        for warning in &warnings {
+            header.push(Line::from(vec!["⚠ ".yellow(), warning.summary(now).dim()]));
+        }
+        Box::new(header)
+    }
+
+    /// While a turn runs the session holds a model switch back until the turn
+    /// ends, so the pickers say when a choice takes effect.
+    fn pending_switch_note(&self) -> Option<Line<'static>> {
+        self.bottom_pane
+            .is_task_running()
+            .then(|| Line::from("Will apply after current turn.".dim()))
+    }
+
     fn is_auto_model(model: &str) -> bool {
         model.starts_with("codex-auto-")
     }
@@ -2506,6 +2764,38 @@ impl ChatWidget {
     }
 
     pub(crate) fn open_all_models_popup(&mut self, presets: Vec<ModelPreset>) {
+        self.show_all_models_popup(presets, None);
+    }
+
+    /// Full model list without the unavailable preset, filtered to the models
+    /// in its family when any are left, otherwise unfiltered.
+    fn open_model_replacement_popup(&mut self, unavailable_preset_id: &str) {
+        let Ok(presets) = self.loaded_presets() else {
+            self.add_info_message(
+                "Models are being updated; run /model to choose another model.".to_string(),
+                None,
+            );
+            return;
+        };
+        let presets: Vec<ModelPreset> = presets
+            .into_iter()
+            .filter(|preset| preset.id != unavailable_preset_id)
+            .collect();
+        let query = model_family_query(unavailable_preset_id).filter(|query| {
+            presets.iter().any(|preset| {
+                [&preset.id, &preset.model, &preset.display_name]
+                    .into_iter()
+                    .any(|name| name.to_lowercase().contains(query.as_str()))
+            })
+        });
+        self.show_all_models_popup(presets, query);
+    }
+
+    fn show_all_models_popup(
+        &mut self,
+        presets: Vec<ModelPreset>,
+        initial_search_query: Option<String>,
+    ) {
         if presets.is_empty() {
             self.add_info_message(
                 "No additional models are available right now.".to_string(),
@@ -2514,31 +2804,24 @@ impl ChatWidget {
             return;
         }
 
-        let mut items: Vec<SelectionItem> = Vec::new();
-        for preset in presets.into_iter() {
-            let description =
-                (!preset.description.is_empty()).then_some(preset.description.to_string());
-            let is_current = preset.model == self.model;
-            let single_supported_effort = preset.supported_reasoning_efforts.len() == 1;
-            let preset_for_action = preset.clone();
-            let actions: Vec<SelectionAction> = vec![Box::new(move |tx| {
-                let preset_for_event = preset_for_action.clone();
-                tx.send(AppEvent::OpenReasoningPopup {
-                    model: preset_for_event,
-                });
-            })];
-            items.push(SelectionItem {
-                name: preset.display_name.clone(),
-                description,
-                is_current,
-                is_default: preset.is_default,
-                actions,
-                dismiss_on_select: single_supported_effort,
-                ..Default::default()
-            });
-        }
+        let active_preset = self.active_preset();
+        let mut items: Vec<SelectionItem> = self
+            .current_model_item(active_preset.as_ref())
+            .into_iter()
+            .collect();
+        let active_id = active_preset.map(|preset| preset.id);
+        items.extend(
+            presets
+                .into_iter()
+                .map(|preset| self.all_models_item(preset, active_id.as_deref())),
+        );
+        let initial_selected_idx = items
+            .iter()
+            .position(|item| item.is_current)
+            .or_else(|| items.iter().position(|item| item.is_default));
 
         self.bottom_pane.show_selection_view(SelectionViewParams {
+            header: self.model_picker_header(),
             title: Some("Select Model and Effort".to_string()),
             subtitle: Some(
                 "Access legacy models by running codex -m <model_name> or in your config.toml"
@@ -2546,32 +2829,175 @@ impl ChatWidget {
             ),
             footer_hint: Some("Press enter to select reasoning effort, or esc to dismiss.".into()),
             items,
+            is_searchable: true,
+            fuzzy_search: true,
+            search_placeholder: Some(MODEL_SEARCH_PLACEHOLDER.to_string()),
+            initial_search_query,
+            initial_selected_idx,
+            details_below: self.accessible_pickers(),
             ..Default::default()
         });
     }
 
+    /// Row for a preset in the full model list; selecting it moves on to the
+    /// reasoning effort picker. The row is the current one when the preset's
+    /// id is `active_id`.
+    fn all_models_item(&self, preset: ModelPreset, active_id: Option<&str>) -> SelectionItem {
+        let description =
+            (!preset.description.is_empty()).then_some(preset.description.to_string());
+        let is_current = active_id == Some(preset.id.as_str());
+        let single_supported_effort = preset.supported_reasoning_efforts.len() <= 1;
+        let search_value = Some(preset.id.clone());
+        let search_aliases = Self::model_search_aliases(preset.view());
+        let disabled_reason = Self::unavailable_reason(preset.view());
+        let selected_description = if self.accessible_pickers() {
+            Some(Self::plain_model_details(&preset))
+        } else {
+            Self::model_dates(preset.view()).map(|dates| match &description {
+                Some(description) => format!("{description}\n{dates}"),
+                None => dates,
+            })
+        };
+        let name = preset.display_name.clone();
+        let is_default = preset.is_default;
+        let actions: Vec<SelectionAction> = vec![Box::new(move |tx| {
+            tx.send(AppEvent::OpenReasoningPopup {
+                model: preset.clone(),
+            });
+        })];
+        SelectionItem {
+            name,
+            description,
+            is_current,
+            is_default,
+            actions,
+            dismiss_on_select: single_supported_effort,
+            search_value,
+            search_aliases,
+            disabled_reason,
+            selected_description,
+            ..Default::default()
+        }
+    }
+
+    /// The preset the session runs on, looked up by model slug or id among
+    /// every preset the catalog knows, hidden ones included, so the pickers
+    /// highlight the same row the catalog resolves to.
+    fn active_preset(&self) -> Option<ModelPreset> {
+        PresetCatalog::new(&self.models_manager, &self.config).find_known(&self.model)
+    }
+
+    /// A top row for a session model the picker does not list, so it still
+    /// shows what is running: a hidden preset under its own name, as the
+    /// current row, or a model the catalog does not know at all, marked so.
+    /// Choosing it keeps the model.
+    fn current_model_item(&self, active: Option<&ModelPreset>) -> Option<SelectionItem> {
+        let listed = |active: &ModelPreset| {
+            self.catalog_snapshot()
+                .find_by_model_or_id(&active.id)
+                .is_some()
+        };
+        match active {
+            Some(active) if listed(active) => None,
+            Some(active) => Some(SelectionItem {
+                name: active.display_name.clone(),
+                description: (!active.description.is_empty()).then(|| active.description.clone()),
+                selected_description: self
+                    .accessible_pickers()
+                    .then(|| Self::plain_model_details(active)),
+                is_current: true,
+                dismiss_on_select: true,
+                search_value: Some(active.id.clone()),
+                search_aliases: Self::model_search_aliases(active.view()),
+                ..Default::default()
+            }),
+            None => Some(SelectionItem {
+                name: format!("{} (current, not in catalog)", self.model),
+                dismiss_on_select: true,
+                search_value: Some(self.model.clone()),
+                ..Default::default()
+            }),
+        }
+    }
+
+    /// Whether the model pickers use the layout for screen readers: the
+    /// highlighted model's fields as labeled lines under the list rather than
+    /// a description column. Turning off `tui.animations` selects it, as the
+    /// TUI's one setting for assistive use.
+    fn accessible_pickers(&self) -> bool {
+        !self.config.animations
+    }
+
+    /// The preset's fields, one labeled line each, in the order the plain
+    /// `codex models list` prints them.
+    fn plain_model_details(preset: &ModelPreset) -> String {
+        plain_model_fields(preset)
+            .into_iter()
+            .map(|(label, value)| format!("{label}: {value}"))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// How current the model is, for the highlighted row of the model list.
+    /// `None` when the catalog gives neither date.
+    fn model_dates(preset: PresetRef<'_>) -> Option<String> {
+        let dates: Vec<String> = [
+            preset
+                .knowledge_cutoff
+                .map(|date| format!("Knowledge cutoff {date}")),
+            preset.released_at.map(|date| format!("Released {date}")),
+        ]
+        .into_iter()
+        .flatten()
+        .collect();
+        (!dates.is_empty()).then(|| dates.join(" · "))
+    }
+
+    /// Why a preset is greyed out: the provider refused it to this account.
+    fn unavailable_reason(preset: PresetRef<'_>) -> Option<String> {
+        preset
+            .unavailable_for_account
+            .map(|reason| format!("unavailable for this account: {reason}"))
+    }
+
+    /// Terms besides the display name and id that model search matches: the
+    /// model slug and, for provider-served models, the provider id.
+    fn model_search_aliases(preset: PresetRef<'_>) -> Vec<String> {
+        std::iter::once(preset.model)
+            .chain(preset.model_provider)
+            .map(str::to_string)
+            .collect()
+    }
+
+    /// Models served by a catalog provider switch `model_provider` for the
+    /// session only; they are not persisted because the saved `model` would
+    /// otherwise be paired with the default provider on the next launch.
     fn model_selection_actions(
         model_for_action: String,
+        model_provider_for_action: Option<String>,
         effort_for_action: Option<ReasoningEffortConfig>,
     ) -> Vec<SelectionAction> {
         vec![Box::new(move |tx| {
             let effort_label = effort_for_action
                 .map(|effort| effort.to_string())
-                .unwrap_or_else(|| "default".to_string());
+                .unwrap_or_else(|| EffortSelection::AUTO.to_string());
             tx.send(AppEvent::CodexOp(Op::OverrideTurnContext {
                 cwd: None,
                 approval_policy: None,
                 sandbox_policy: None,
                 model: Some(model_for_action.clone()),
+                model_provider: model_provider_for_action.clone(),
                 effort: Some(effort_for_action),
                 summary: None,
             }));
             tx.send(AppEvent::UpdateModel(model_for_action.clone()));
             tx.send(AppEvent::UpdateReasoningEffort(effort_for_action));
-            tx.send(AppEvent::PersistModelSelection {
-                model: model_for_action.clone(),
-                effort: effort_for_action,
-            });
+            if model_provider_for_action.is_none() {
+                tx.send(AppEvent::PersistModelSelection {
+                    model: model_for_action.clone(),
+                    effort: effort_for_action,
+                });
+            }
             tracing::info!(
                 "Selected model: {}, Selected effort: {}",
                 model_for_action,
@@ -2581,7 +3007,12 @@ impl ChatWidget {
     }
 
     /// Open a popup to choose the reasoning effort (stage 2) for the given model.
-    pub(crate) fn open_reasoning_popup(&mut self, preset: ModelPreset) {
+    pub(crate) fn open_reasoning_popup(&mut self, preset: PresetRef<'_>) {
+        if preset.supported_reasoning_efforts.is_empty() {
+            self.open_effort_unsupported_popup(preset);
+            return;
+        }
+        let summaries_item = Self::summaries_unsupported_item(preset);
         let default_effort: ReasoningEffortConfig = preset.default_reasoning_effort;
         let supported = preset.supported_reasoning_efforts;
 
@@ -2602,16 +3033,14 @@ impl ChatWidget {
             let effort_label = Self::reasoning_effort_label(effort);
             format!("⚠ {effort_label} reasoning effort can quickly consume Plus plan rate limits.")
         });
-        let warn_for_model = preset.model.starts_with("gpt-5.1-codex")
-            || preset.model.starts_with("gpt-5.1-codex-max")
-            || preset.model.starts_with("gpt-5.2");
+        let warn_for_model = PLUS_RATE_LIMIT_WARNING_PRESET_IDS.contains(&preset.model);
 
         struct EffortChoice {
             stored: Option<ReasoningEffortConfig>,
             display: ReasoningEffortConfig,
         }
         let mut choices: Vec<EffortChoice> = Vec::new();
-        for effort in ReasoningEffortConfig::iter() {
+        for effort in ReasoningEffortConfig::VARIANTS {
             if supported.iter().any(|option| option.effort == effort) {
                 choices.push(EffortChoice {
                     stored: Some(effort),
@@ -2619,19 +3048,13 @@ impl ChatWidget {
                 });
             }
         }
-        if choices.is_empty() {
-            choices.push(EffortChoice {
-                stored: Some(default_effort),
-                display: default_effort,
-            });
-        }
-
         if choices.len() == 1 {
-            if let Some(effort) = choices.first().and_then(|c| c.stored) {
-                self.apply_model_and_effort(preset.model, Some(effort));
-            } else {
-                self.apply_model_and_effort(preset.model, None);
-            }
+            let effort = choices.first().and_then(|c| c.stored);
+            self.apply_model_and_effort(
+                preset.model.to_string(),
+                preset.model_provider.map(str::to_string),
+                effort,
+            );
             return;
         }
 
@@ -2643,6 +3066,16 @@ impl ChatWidget {
             .or_else(|| choices.iter().find_map(|choice| choice.stored))
             .or(Some(default_effort));
 
+        // "Auto" stores no effort, so the model's default applies and keeps
+        // applying after later model switches.
+        choices.insert(
+            0,
+            EffortChoice {
+                stored: None,
+                display: default_effort,
+            },
+        );
+
         let model_slug = preset.model.to_string();
         let is_current_model = self.model == preset.model;
         let highlight_choice = if is_current_model {
@@ -2650,33 +3083,38 @@ impl ChatWidget {
         } else {
             default_choice
         };
-        let selection_choice = highlight_choice.or(default_choice);
         let initial_selected_idx = choices
             .iter()
-            .position(|choice| choice.stored == selection_choice)
+            .position(|choice| choice.stored == highlight_choice)
             .or_else(|| {
-                selection_choice
+                highlight_choice
                     .and_then(|effort| choices.iter().position(|choice| choice.display == effort))
             });
         let mut items: Vec<SelectionItem> = Vec::new();
         for choice in choices.iter() {
             let effort = choice.display;
-            let mut effort_label = Self::reasoning_effort_label(effort).to_string();
+            let mut effort_label = match choice.stored {
+                Some(_) => Self::reasoning_effort_label(effort).to_string(),
+                None => format!(
+                    "Auto ({})",
+                    Self::reasoning_effort_label(effort).to_lowercase()
+                ),
+            };
             if choice.stored == default_choice {
                 effort_label.push_str(" (default)");
             }
 
-            let description = choice
-                .stored
-                .and_then(|effort| {
-                    supported
-                        .iter()
-                        .find(|option| option.effort == effort)
-                        .map(|option| option.description.to_string())
-                })
-                .filter(|text| !text.is_empty());
+            let description = match choice.stored {
+                None => Some("Follow the model's default effort".to_string()),
+                Some(effort) => supported
+                    .iter()
+                    .find(|option| option.effort == effort)
+                    .map(|option| option.description.to_string()),
+            }
+            .filter(|text| !text.is_empty());
 
-            let show_warning = warn_for_model && warn_effort == Some(effort);
+            let show_warning =
+                warn_for_model && choice.stored.is_some() && warn_effort == choice.stored;
             let selected_description = if show_warning {
                 warning_text.as_ref().map(|warning_message| {
                     description.as_ref().map_or_else(
@@ -2689,7 +3127,11 @@ impl ChatWidget {
             };
 
             let model_for_action = model_slug.clone();
-            let actions = Self::model_selection_actions(model_for_action, choice.stored);
+            let actions = Self::model_selection_actions(
+                model_for_action,
+                preset.model_provider.map(str::to_string),
+                choice.stored,
+            );
 
             items.push(SelectionItem {
                 name: effort_label,
@@ -2698,14 +3140,20 @@ impl ChatWidget {
                 is_current: is_current_model && choice.stored == highlight_choice,
                 actions,
                 dismiss_on_select: true,
+                description_max_lines: Some(1),
                 ..Default::default()
             });
         }
 
+        items.extend(summaries_item);
+
         let mut header = ColumnRenderable::new();
         header.push(Line::from(
             format!("Select Reasoning Level for {model_slug}").bold(),
         ));
+        if let Some(note) = self.pending_switch_note() {
+            header.push(note);
+        }
 
         self.bottom_pane.show_selection_view(SelectionViewParams {
             header: Box::new(header),
@@ -2716,7 +3164,7 @@ impl ChatWidget {
         });
     }
 
-    fn reasoning_effort_label(effort: ReasoningEffortConfig) -> &'static str {
+    pub(crate) fn reasoning_effort_label(effort: ReasoningEffortConfig) -> &'static str {
         match effort {
             ReasoningEffortConfig::None => "None",
             ReasoningEffortConfig::Minimal => "Minimal",
@@ -2727,23 +3175,92 @@ impl ChatWidget {
         }
     }
 
-    fn apply_model_and_effort(&self, model: String, effort: Option<ReasoningEffortConfig>) {
+    /// A disabled row for a model that writes no reasoning summaries, so the
+    /// picker does not suggest the summary setting applies to it. `None`
+    /// when the model does or the catalog does not say.
+    fn summaries_unsupported_item(preset: PresetRef<'_>) -> Option<SelectionItem> {
+        (preset.supports_reasoning_summaries == Some(false)).then(|| SelectionItem {
+            name: "Reasoning summaries".to_string(),
+            disabled_reason: Some(format!("not supported by {}", preset.model)),
+            ..Default::default()
+        })
+    }
+
+    /// The effort picker for a preset that lists no efforts: the model takes
+    /// no effort parameter, so the only choice is to continue without one.
+    fn open_effort_unsupported_popup(&mut self, preset: PresetRef<'_>) {
+        let summaries_item = Self::summaries_unsupported_item(preset);
+        let model_slug = preset.model;
+        let is_current_model = self.model == model_slug;
+        let mut items = vec![
+            SelectionItem {
+                name: "Reasoning effort".to_string(),
+                disabled_reason: Some(format!("not supported by {model_slug}")),
+                ..Default::default()
+            },
+            SelectionItem {
+                name: format!("Continue with {model_slug}"),
+                is_current: is_current_model,
+                actions: Self::model_selection_actions(
+                    model_slug.to_string(),
+                    preset.model_provider.map(str::to_string),
+                    None,
+                ),
+                dismiss_on_select: true,
+                ..Default::default()
+            },
+        ];
+        items.extend(summaries_item);
+
+        let mut header = ColumnRenderable::new();
+        header.push(Line::from(
+            format!("Select Reasoning Level for {model_slug}").bold(),
+        ));
+        header.push(Line::from(
+            format!(
+                "{model_slug} does not take a reasoning effort; requests are sent without one."
+            )
+            .dim(),
+        ));
+        if let Some(note) = self.pending_switch_note() {
+            header.push(note);
+        }
+
+        self.bottom_pane.show_selection_view(SelectionViewParams {
+            header: Box::new(header),
+            footer_hint: Some(standard_popup_hint_line()),
+            items,
+            initial_selected_idx: Some(1),
+            ..Default::default()
+        });
+    }
+
+    fn apply_model_and_effort(
+        &self,
+        model: String,
+        model_provider: Option<String>,
+        effort: Option<ReasoningEffortConfig>,
+    ) {
+        let persist = model_provider.is_none();
         self.app_event_tx
             .send(AppEvent::CodexOp(Op::OverrideTurnContext {
                 cwd: None,
                 approval_policy: None,
                 sandbox_policy: None,
                 model: Some(model.clone()),
+                model_provider,
                 effort: Some(effort),
                 summary: None,
             }));
         self.app_event_tx.send(AppEvent::UpdateModel(model.clone()));
         self.app_event_tx
             .send(AppEvent::UpdateReasoningEffort(effort));
-        self.app_event_tx.send(AppEvent::PersistModelSelection {
-            model: model.clone(),
-            effort,
-        });
+        if persist {
+            self.app_event_tx.send(AppEvent::PersistModelSelection {
+                model: model.clone(),
+                effort,
+            });
+        }
         tracing::info!(
             "Selected model: {}, Selected effort: {}",
             model,
@@ -2864,6 +3381,7 @@ impl ChatWidget {
                 approval_policy: Some(approval),
                 sandbox_policy: Some(sandbox_clone.clone()),
                 model: None,
+                model_provider: None,
                 effort: None,
                 summary: None,
             }));
@@ -3237,6 +3755,82 @@ impl ChatWidget {
     pub(crate) fn set_model(&mut self, model: &str) {
         self.session_header.set_model(model);
         self.model = model.to_string();
+        self.maybe_warn_network_required(model);
+    }
+
+    /// Warn, ahead of the next turn, that `model` needs network access the
+    /// sandbox blocks, and offer to allow it. Each model is flagged once per
+    /// session, and the sandbox only changes if the user takes the offer.
+    fn maybe_warn_network_required(&mut self, model: &str) {
+        let catalog = self.catalog_snapshot();
+        if let Some(preset) = catalog.find(model) {
+            self.warn_if_sandbox_blocks_network(preset);
+        }
+    }
+
+    fn warn_if_sandbox_blocks_network(&mut self, preset: &ModelPreset) {
+        let Some(warning) = network_warning(preset, self.config.sandbox_policy.get()) else {
+            return;
+        };
+        if !self.network_warned_models.insert(preset.model.clone()) {
+            return;
+        }
+        self.on_warning(warning.to_string());
+        self.open_network_access_prompt(&preset.display_name);
+    }
+
+    fn open_network_access_prompt(&mut self, display_name: &str) {
+        let current_sandbox = self.config.sandbox_policy.get();
+        let sandbox = sandbox_with_network_access(current_sandbox);
+        let allow_description = if matches!(current_sandbox, SandboxPolicy::ReadOnly) {
+            "Switch to a workspace-write sandbox that can reach the network."
+        } else {
+            "Let commands in the sandbox reach the network."
+        };
+        let disabled_reason = match self.config.sandbox_policy.can_set(&sandbox) {
+            Ok(()) => None,
+            Err(err) => Some(err.to_string()),
+        };
+        let allow_actions: Vec<SelectionAction> = vec![Box::new(move |tx| {
+            tx.send(AppEvent::CodexOp(Op::OverrideTurnContext {
+                cwd: None,
+                approval_policy: None,
+                sandbox_policy: Some(sandbox.clone()),
+                model: None,
+                model_provider: None,
+                effort: None,
+                summary: None,
+            }));
+            tx.send(AppEvent::UpdateSandboxPolicy(sandbox.clone()));
+        })];
+
+        let items = vec![
+            SelectionItem {
+                name: "Allow network access".to_string(),
+                description: Some(allow_description.to_string()),
+                actions: allow_actions,
+                dismiss_on_select: true,
+                disabled_reason,
+                ..Default::default()
+            },
+            SelectionItem {
+                name: "Keep current sandbox".to_string(),
+                description: Some("Commands that need the network will fail.".to_string()),
+                actions: Vec::new(),
+                dismiss_on_select: true,
+                ..Default::default()
+            },
+        ];
+
+        self.bottom_pane.show_selection_view(SelectionViewParams {
+            title: Some("Network access required".to_string()),
+            subtitle: Some(format!(
+                "{display_name} needs network access, which the current sandbox blocks."
+            )),
+            footer_hint: Some(standard_popup_hint_line()),
+            items,
+            ..Default::default()
+        });
     }
 
     pub(crate) fn add_info_message(&mut self, message: String, hint: Option<String>) {
@@ -3637,6 +4231,48 @@ const EXAMPLE_PROMPTS: [&str; 6] = [
     "Improve documentation in @filename",
 ];
 
+/// Search query matching the models in the same family as `preset_id`: the
+/// slug's name segments before its version, e.g. `gpt` for `gpt-5.1-codex`
+/// and `llama` for `gateway/llama-3.1-70b`. `None` for a slug without a
+/// version, such as `oca-large`, which names no family beyond itself.
+fn model_family_query(preset_id: &str) -> Option<String> {
+    let slug = preset_id.rsplit('/').next().unwrap_or(preset_id);
+    let segments: Vec<&str> = slug.split('-').collect();
+    let version = segments
+        .iter()
+        .skip(1)
+        .position(|segment| segment.starts_with(|c: char| c.is_ascii_digit()))?;
+    Some(segments[..=version].join("-").to_lowercase())
+}
+
+/// `policy` with network access allowed. A read-only sandbox has no network
+/// setting, so it becomes a workspace-write sandbox.
+fn sandbox_with_network_access(policy: &SandboxPolicy) -> SandboxPolicy {
+    match policy {
+        SandboxPolicy::WorkspaceWrite {
+            writable_roots,
+            exclude_tmpdir_env_var,
+            exclude_slash_tmp,
+            network_access: _,
+        } => SandboxPolicy::WorkspaceWrite {
+            writable_roots: writable_roots.clone(),
+            network_access: true,
+            exclude_tmpdir_env_var: *exclude_tmpdir_env_var,
+            exclude_slash_tmp: *exclude_slash_tmp,
+        },
+        SandboxPolicy::ReadOnly => SandboxPolicy::WorkspaceWrite {
+            writable_roots: Vec::new(),
+            network_access: true,
+            exclude_tmpdir_env_var: false,
+            exclude_slash_tmp: false,
+        },
+        SandboxPolicy::ExternalSandbox { .. } => SandboxPolicy::ExternalSandbox {
+            network_access: NetworkAccess::Enabled,
+        },
+        SandboxPolicy::DangerFullAccess => SandboxPolicy::DangerFullAccess,
+    }
+}
+
 // Extract the first bold (Markdown) element in the form **...** from `s`.
 // Returns the inner text if found; otherwise `None`.
 fn extract_first_bold(s: &str) -> Option<String> {
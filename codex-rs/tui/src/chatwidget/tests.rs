@@ -5,13 +5,19 @@ use crate::test_backend::VT100Backend;
 use crate::tui::FrameRequester;
 use assert_matches::assert_matches;
 use codex_common::approval_presets::builtin_approval_presets;
+use codex_common::test_catalog::PresetBuilderExt;
+use codex_common::test_catalog::preset;
+use codex_common::test_catalog::provider_preset;
 use codex_core::AuthManager;
 use codex_core::CodexAuth;
+use codex_core::ModelCatalog;
+use codex_core::ModelProviderInfo;
 use codex_core::config::Config;
 use codex_core::config::ConfigBuilder;
 use codex_core::config::Constrained;
 use codex_core::config::ConstraintError;
 use codex_core::models_manager::manager::ModelsManager;
+use codex_core::models_manager::provenance::PrefetchStats;
 use codex_core::protocol::AgentMessageDeltaEvent;
 use codex_core::protocol::AgentMessageEvent;
 use codex_core::protocol::AgentReasoningDeltaEvent;
@@ -30,6 +36,8 @@ use codex_core::protocol::ExitedReviewModeEvent;
 use codex_core::protocol::FileChange;
 use codex_core::protocol::McpStartupStatus;
 use codex_core::protocol::McpStartupUpdateEvent;
+use codex_core::protocol::ModelChangeSource;
+use codex_core::protocol::ModelChangedEvent;
 use codex_core::protocol::Op;
 use codex_core::protocol::PatchApplyBeginEvent;
 use codex_core::protocol::PatchApplyEndEvent;
@@ -49,8 +57,9 @@ use codex_core::protocol::ViewImageToolCallEvent;
 use codex_core::protocol::WarningEvent;
 use codex_protocol::ConversationId;
 use codex_protocol::account::PlanType;
+use codex_protocol::custom_prompts::CustomPrompt;
 use codex_protocol::openai_models::ModelPreset;
-use codex_protocol::openai_models::ReasoningEffortPreset;
+use codex_protocol::openai_models::ReasoningSummaryFormat;
 use codex_protocol::parse_command::ParsedCommand;
 use codex_protocol::plan_tool::PlanItemArg;
 use codex_protocol::plan_tool::StepStatus;
@@ -107,6 +116,9 @@ async fn resumed_initial_messages_render_history() {
         session_id: conversation_id,
         model: "test-model".to_string(),
         model_provider_id: "test-provider".to_string(),
+        model_catalog_offline: false,
+        instructions_addendum_active: false,
+        disabled_tools: Vec::new(),
         approval_policy: AskForApproval::Never,
         sandbox_policy: SandboxPolicy::ReadOnly,
         cwd: PathBuf::from("/home/user/project"),
@@ -233,6 +245,7 @@ async fn review_restores_context_window_indicator() {
         id: "review-end".into(),
         msg: EventMsg::ExitedReviewMode(ExitedReviewModeEvent {
             review_output: None,
+            model: None,
         }),
     });
     let _ = drain_insert_history(&mut rx);
@@ -286,6 +299,7 @@ async fn context_indicator_shows_used_tokens_when_window_unknown() {
         total_token_usage: token_usage.clone(),
         last_token_usage: token_usage,
         model_context_window: None,
+        per_model_usage: Vec::new(),
     };
 
     chat.handle_codex_event(Event {
@@ -381,6 +395,7 @@ async fn make_chatwidget_manual(
         plan_type: None,
         rate_limit_warnings: RateLimitWarningState::default(),
         rate_limit_switch_prompt: RateLimitSwitchPromptState::default(),
+        network_warned_models: HashSet::new(),
         rate_limit_poller: None,
         stream_controller: None,
         running_commands: HashMap::new(),
@@ -467,6 +482,7 @@ fn make_token_info(total_tokens: i64, context_window: i64) -> TokenUsageInfo {
         total_token_usage: usage(total_tokens),
         last_token_usage: usage(total_tokens),
         model_context_window: Some(context_window),
+        per_model_usage: Vec::new(),
     }
 }
 
@@ -960,10 +976,7 @@ fn active_blob(chat: &ChatWidget) -> String {
 }
 
 fn get_available_model(chat: &ChatWidget, model: &str) -> ModelPreset {
-    let models = chat
-        .models_manager
-        .try_list_models(&chat.config)
-        .expect("models lock available");
+    let models = chat.loaded_presets().expect("models lock available");
     models
         .iter()
         .find(|&preset| preset.model == model)
@@ -1743,6 +1756,9 @@ async fn interrupted_turn_error_message_snapshot() {
         id: "task-1".into(),
         msg: EventMsg::TaskStarted(TaskStartedEvent {
             model_context_window: None,
+            reasoning_effort: None,
+            reasoning_effort_description: None,
+            reasoning_summary_format: None,
         }),
     });
 
@@ -1961,6 +1977,499 @@ async fn model_selection_popup_snapshot() {
     assert_snapshot!("model_selection_popup", popup);
 }
 
+#[tokio::test]
+async fn model_selection_popup_accessible_snapshot() {
+    let (mut chat, _rx, _op_rx) = make_chatwidget_manual(Some("gpt-5-codex")).await;
+    chat.config.animations = false;
+    chat.open_model_popup();
+
+    let popup = render_bottom_popup(&chat, 80);
+    assert_snapshot!("model_selection_popup_accessible", popup);
+}
+
+#[tokio::test]
+async fn model_selection_popup_filters_as_you_type() {
+    let (mut chat, _rx, _op_rx) = make_chatwidget_manual(Some("gpt-5-codex")).await;
+    chat.open_model_popup();
+    let unfiltered = render_bottom_popup(&chat, 80);
+
+    for ch in "mini".chars() {
+        chat.handle_key_event(KeyEvent::new(KeyCode::Char(ch), KeyModifiers::NONE));
+    }
+    let popup = render_bottom_popup(&chat, 80);
+    assert_snapshot!("model_selection_popup_filtered", popup);
+
+    for _ in 0.."mini".len() {
+        chat.handle_key_event(KeyEvent::new(KeyCode::Backspace, KeyModifiers::NONE));
+    }
+    assert_eq!(render_bottom_popup(&chat, 80), unfiltered);
+}
+
+#[tokio::test]
+async fn model_selection_popup_shows_dates_of_the_highlighted_model() {
+    let (mut chat, _rx, _op_rx) = make_chatwidget_manual(Some("gpt-5.2")).await;
+    chat.open_model_popup();
+
+    let popup = render_bottom_popup(&chat, 120);
+    assert!(
+        popup.contains("Knowledge cutoff 2025-08-31 · Released 2025-12-11"),
+        "{popup}"
+    );
+    assert_eq!(popup.matches("Knowledge cutoff").count(), 1, "{popup}");
+}
+
+/// The row the picker opens on, without the `›` that marks it.
+fn highlighted_row(popup: &str) -> String {
+    popup
+        .lines()
+        .find_map(|line| line.strip_prefix('›'))
+        .unwrap_or_else(|| panic!("no highlighted row:\n{popup}"))
+        .trim()
+        .to_string()
+}
+
+#[tokio::test]
+async fn model_picker_opens_on_the_active_preset() {
+    let (mut chat, _rx, _op_rx) = make_chatwidget_manual(Some("gpt-5.1-codex-mini")).await;
+    chat.open_model_popup();
+
+    let popup = render_bottom_popup(&chat, 80);
+    assert!(
+        highlighted_row(&popup).starts_with("gpt-5.1-codex-mini (current)"),
+        "{popup}"
+    );
+    assert!(!popup.contains("not in catalog"), "{popup}");
+}
+
+#[tokio::test]
+async fn model_picker_lists_a_model_outside_the_catalog_above_it() {
+    let (mut chat, mut rx, _op_rx) = make_chatwidget_manual(Some("my-finetune")).await;
+    chat.open_model_popup();
+
+    let popup = render_bottom_popup(&chat, 80);
+    assert!(
+        highlighted_row(&popup).starts_with("gpt-5.1-codex-max (default)"),
+        "the catalog default is highlighted:\n{popup}"
+    );
+    let rows: Vec<&str> = popup.lines().map(str::trim).collect();
+    let unlisted = rows
+        .iter()
+        .position(|row| row.starts_with("my-finetune (current, not in catalog)"))
+        .unwrap_or_else(|| panic!("no row for the current model:\n{popup}"));
+    let default = rows
+        .iter()
+        .position(|row| row.starts_with("› gpt-5.1-codex-max"))
+        .unwrap_or_else(|| panic!("no default row:\n{popup}"));
+    assert_eq!(unlisted + 1, default, "{popup}");
+
+    while rx.try_recv().is_ok() {}
+    chat.handle_key_event(KeyEvent::new(KeyCode::Up, KeyModifiers::NONE));
+    chat.handle_key_event(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+    assert!(
+        rx.try_recv().is_err(),
+        "choosing the current model changes nothing"
+    );
+    assert_eq!(chat.model, "my-finetune");
+}
+
+#[tokio::test]
+async fn model_picker_opens_on_a_hidden_active_preset() {
+    let (mut chat, mut rx, _op_rx) = make_chatwidget_manual(Some("gpt-5-codex")).await;
+    chat.open_model_popup();
+
+    let popup = render_bottom_popup(&chat, 80);
+    assert!(!popup.contains("not in catalog"), "{popup}");
+    assert!(
+        highlighted_row(&popup).starts_with("gpt-5-codex (current)"),
+        "{popup}"
+    );
+
+    while rx.try_recv().is_ok() {}
+    chat.handle_key_event(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+    assert!(
+        rx.try_recv().is_err(),
+        "choosing the current model changes nothing"
+    );
+    assert_eq!(chat.model, "gpt-5-codex");
+}
+
+#[tokio::test]
+async fn model_popup_shows_catalog_warning_until_refresh_recovers() {
+    let (mut chat, _rx, _op_rx) = make_chatwidget_manual(Some("gpt-5-codex")).await;
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").expect("bind");
+    let closed_port = listener.local_addr().expect("local addr").port();
+    drop(listener);
+    let listed = ModelProviderInfo {
+        name: "Gateway".to_string(),
+        base_url: Some(format!("http://127.0.0.1:{closed_port}/v1")),
+        model_catalog: Some(ModelCatalog::None),
+        models: Some(vec!["oca-large".to_string()]),
+        ..chat.config.model_provider.clone()
+    };
+    let unreachable = ModelProviderInfo {
+        model_catalog: Some(ModelCatalog::Openai),
+        ..listed.clone()
+    };
+    let mut config = chat.config.clone();
+
+    for (provider, expect_banner) in [
+        (listed.clone(), false),
+        (unreachable, true),
+        (listed, false),
+    ] {
+        config
+            .model_providers
+            .insert("gateway".to_string(), provider);
+        chat.models_manager.refresh_provider_models(&config).await;
+        chat.open_model_popup();
+
+        let popup = render_bottom_popup(&chat, 120);
+        assert_eq!(
+            popup.contains("stale: gateway:"),
+            expect_banner,
+            "unexpected popup:\n{popup}"
+        );
+        assert!(popup.contains("Select Model"), "unexpected popup:\n{popup}");
+        chat.handle_key_event(KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE));
+    }
+}
+
+#[tokio::test]
+async fn model_popup_is_served_from_the_prefetched_catalog() {
+    let (mut chat, _rx, _op_rx) = make_chatwidget_manual(Some("gpt-5-codex")).await;
+    chat.config.prefetch_model_catalog = true;
+    chat.config.model_providers.insert(
+        "gateway".to_string(),
+        ModelProviderInfo {
+            name: "Gateway".to_string(),
+            model_catalog: Some(ModelCatalog::None),
+            models: Some(vec!["oca-large".to_string()]),
+            ..chat.config.model_provider.clone()
+        },
+    );
+
+    chat.models_manager.prefetch_catalog(&chat.config).await;
+    chat.open_model_popup();
+    for ch in "oca".chars() {
+        chat.handle_key_event(KeyEvent::new(KeyCode::Char(ch), KeyModifiers::NONE));
+    }
+
+    let popup = render_bottom_popup(&chat, 120);
+    assert!(popup.contains("oca-large"), "unexpected popup:\n{popup}");
+    assert_eq!(
+        chat.models_manager.prefetch_stats(),
+        PrefetchStats {
+            started: true,
+            completed: true,
+            picker_served_from_prefetch: 1,
+            picker_served_cold: 0,
+        }
+    );
+}
+
+#[tokio::test]
+async fn custom_prompt_model_variables_are_left_for_core() {
+    let (mut chat, _rx, mut op_rx) = make_chatwidget_manual(Some("gpt-5.1-codex")).await;
+    chat.bottom_pane.set_custom_prompts(vec![CustomPrompt {
+        name: "whoami".to_string(),
+        path: PathBuf::from("/tmp/whoami.md"),
+        content: "Running on {{model.display_name}} at {{model.effort}}".to_string(),
+        description: None,
+        argument_hint: None,
+    }]);
+
+    chat.bottom_pane
+        .set_composer_text("/prompts:whoami".to_string());
+    chat.handle_key_event(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+    let items = loop {
+        match op_rx.try_recv() {
+            Ok(Op::UserInput { items }) => break items,
+            Ok(_) => continue,
+            Err(err) => panic!("expected Op::UserInput, got {err:?}"),
+        }
+    };
+
+    // Core fills the variables in from the model the turn runs on.
+    assert_eq!(
+        items,
+        vec![UserInput::Text {
+            text: "Running on {{model.display_name}} at {{model.effort}}".to_string(),
+        }]
+    );
+}
+
+#[tokio::test]
+async fn new_models_notice_lists_additions_and_switches_on_select() {
+    let (mut chat, mut rx, _op_rx) = make_chatwidget_manual(None).await;
+    let mut preset = chat.loaded_presets().expect("models available")[0].clone();
+    preset.id = "oca-large".to_string();
+    preset.model = "oca-large".to_string();
+    preset.display_name = "OCA Large".to_string();
+    preset.description = "Largest gateway model.\nSecond line.".to_string();
+
+    chat.open_new_models_notice(Vec::new());
+    assert!(
+        !render_bottom_popup(&chat, 80).contains("New in your model catalog"),
+        "no notice expected without new models"
+    );
+
+    chat.open_new_models_notice(vec![preset]);
+    let popup = render_bottom_popup(&chat, 80);
+    for expected in [
+        "New in your model catalog",
+        "1 model was added since you last looked.",
+        "Try OCA Large",
+        "Largest gateway model.",
+        "Dismiss",
+    ] {
+        assert!(
+            popup.contains(expected),
+            "missing {expected:?} in:\n{popup}"
+        );
+    }
+    assert!(
+        !popup.contains("Second line."),
+        "unexpected popup:\n{popup}"
+    );
+
+    chat.handle_key_event(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+    let mut switched = false;
+    while let Ok(event) = rx.try_recv() {
+        if let AppEvent::UpdateModel(model) = event {
+            assert_eq!(model, "oca-large");
+            switched = true;
+        }
+    }
+    assert!(switched, "expected selecting the model to switch to it");
+}
+
+#[tokio::test]
+async fn turn_effort_description_is_recorded_in_the_transcript_only() {
+    let (mut chat, mut rx, _op_rx) = make_chatwidget_manual(None).await;
+
+    for (id, description) in [
+        (
+            "task-1",
+            Some("Greater reasoning depth for complex problems"),
+        ),
+        ("task-2", None),
+    ] {
+        chat.handle_codex_event(Event {
+            id: id.into(),
+            msg: EventMsg::TaskStarted(TaskStartedEvent {
+                model_context_window: None,
+                reasoning_effort: Some(ReasoningEffortConfig::High),
+                reasoning_effort_description: description.map(str::to_string),
+                reasoning_summary_format: None,
+            }),
+        });
+    }
+
+    let mut cells = Vec::new();
+    while let Ok(ev) = rx.try_recv() {
+        if let AppEvent::InsertHistoryCell(cell) = ev {
+            cells.push(cell);
+        }
+    }
+    let [cell] = cells.as_slice() else {
+        panic!("expected one effort cell, got {cells:?}");
+    };
+    assert!(cell.display_lines(80).is_empty());
+    assert_eq!(
+        lines_to_single_string(&cell.transcript_lines(80)),
+        "• effort: high · Greater reasoning depth for complex problems\n"
+    );
+}
+
+#[tokio::test]
+async fn model_unavailable_error_opens_filtered_model_popup() {
+    let (mut chat, mut rx, mut op_rx) = make_chatwidget_manual(Some("gpt-5.1-codex-max")).await;
+
+    chat.handle_codex_event(Event {
+        id: "sub-1".into(),
+        msg: EventMsg::Error(ErrorEvent {
+            message: "model `gpt-5.1-codex-max` is not available from the provider".to_string(),
+            codex_error_info: Some(CodexErrorInfo::ModelUnavailable {
+                preset_id: "gpt-5.1-codex-max".to_string(),
+            }),
+        }),
+    });
+
+    let cells = drain_insert_history(&mut rx);
+    assert!(
+        cells
+            .iter()
+            .map(|lines| lines_to_single_string(lines))
+            .any(|text| text.contains("is not available from the provider")),
+        "expected the error in history: {cells:?}"
+    );
+    let popup = render_bottom_popup(&chat, 80);
+    assert!(
+        popup.contains("Select Model and Effort") && popup.contains("gpt-5.1-codex-mini"),
+        "expected the model picker: {popup}"
+    );
+    assert!(
+        !popup.contains("gpt-5.1-codex-max"),
+        "unavailable model should not be offered: {popup}"
+    );
+    assert!(
+        op_rx.try_recv().is_err(),
+        "no op should be sent before a model is chosen"
+    );
+}
+
+#[tokio::test]
+async fn model_unavailable_without_a_version_lists_every_model() {
+    let (mut chat, _rx, _op_rx) = make_chatwidget_manual(Some("bengalfox")).await;
+
+    chat.handle_codex_event(Event {
+        id: "sub-1".into(),
+        msg: EventMsg::Error(ErrorEvent {
+            message: "model `bengalfox` is not available from the provider".to_string(),
+            codex_error_info: Some(CodexErrorInfo::ModelUnavailable {
+                preset_id: "bengalfox".to_string(),
+            }),
+        }),
+    });
+
+    let popup = render_bottom_popup(&chat, 80);
+    assert!(
+        popup.contains("Select Model and Effort")
+            && popup.contains("gpt-5.1-codex-max")
+            && popup.contains("gpt-5.1-codex-mini"),
+        "expected the unfiltered model picker: {popup}"
+    );
+    assert!(
+        !popup.contains("bengalfox"),
+        "unavailable model should not be offered: {popup}"
+    );
+}
+
+#[tokio::test]
+async fn models_refused_to_the_account_are_greyed_out_with_the_reason() {
+    let (mut chat, _rx, _op_rx) = make_chatwidget_manual(Some("gpt-5.1-codex-max")).await;
+    chat.models_manager
+        .mark_unavailable_for_account("gpt-5.1-codex-mini", "not on your plan", &chat.config)
+        .await;
+
+    chat.open_model_popup();
+
+    let popup = render_bottom_popup(&chat, 200);
+    assert!(
+        popup.contains("gpt-5.1-codex-mini (disabled)")
+            && popup.contains("unavailable for this account: not on your plan"),
+        "expected the refused model to be greyed out with its reason: {popup}"
+    );
+    assert!(
+        !popup.contains("gpt-5.1-codex-max (disabled)"),
+        "other models stay selectable: {popup}"
+    );
+}
+
+fn network_preset() -> ModelPreset {
+    preset("web-agent")
+        .display_name("Web Agent")
+        .requires_network(true)
+        .build()
+        .expect("valid preset")
+}
+
+#[tokio::test]
+async fn network_model_under_a_blocking_sandbox_warns_once_and_offers_access() {
+    let (mut chat, mut rx, _op_rx) = make_chatwidget_manual(None).await;
+    chat.set_sandbox_policy(SandboxPolicy::new_workspace_write_policy())
+        .expect("set sandbox");
+
+    chat.warn_if_sandbox_blocks_network(&network_preset());
+
+    let history = drain_insert_history(&mut rx)
+        .iter()
+        .map(|lines| lines_to_single_string(lines))
+        .collect::<String>();
+    assert!(
+        history.contains("model `web-agent` needs network access"),
+        "expected a network warning: {history}"
+    );
+    let popup = render_bottom_popup(&chat, 120);
+    assert!(
+        popup.contains("Network access required")
+            && popup.contains("Allow network access")
+            && popup.contains("Keep current sandbox"),
+        "unexpected popup:\n{popup}"
+    );
+    assert_eq!(
+        chat.config.sandbox_policy.get(),
+        &SandboxPolicy::new_workspace_write_policy(),
+        "the sandbox is not changed until the user picks the offer"
+    );
+
+    chat.handle_key_event(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+    let mut events = Vec::new();
+    while let Ok(ev) = rx.try_recv() {
+        events.push(ev);
+    }
+    let allowed = SandboxPolicy::WorkspaceWrite {
+        writable_roots: Vec::new(),
+        network_access: true,
+        exclude_tmpdir_env_var: false,
+        exclude_slash_tmp: false,
+    };
+    assert!(
+        events.iter().any(|ev| matches!(
+            ev,
+            AppEvent::CodexOp(Op::OverrideTurnContext {
+                sandbox_policy: Some(policy),
+                ..
+            }) if *policy == allowed
+        )),
+        "expected the turn context to allow network access; events: {events:?}"
+    );
+    assert!(
+        events
+            .iter()
+            .any(|ev| matches!(ev, AppEvent::UpdateSandboxPolicy(policy) if *policy == allowed)),
+        "expected the sandbox to allow network access; events: {events:?}"
+    );
+
+    chat.warn_if_sandbox_blocks_network(&network_preset());
+    assert!(
+        drain_insert_history(&mut rx).is_empty(),
+        "each model is flagged once per session"
+    );
+}
+
+#[tokio::test]
+async fn no_network_warning_when_the_sandbox_allows_it_or_the_model_needs_none() {
+    let (mut chat, mut rx, _op_rx) = make_chatwidget_manual(None).await;
+
+    chat.set_sandbox_policy(SandboxPolicy::DangerFullAccess)
+        .expect("set sandbox");
+    chat.warn_if_sandbox_blocks_network(&network_preset());
+
+    chat.set_sandbox_policy(SandboxPolicy::ReadOnly)
+        .expect("set sandbox");
+    let offline = preset("local").build().expect("valid preset");
+    chat.warn_if_sandbox_blocks_network(&offline);
+
+    assert!(drain_insert_history(&mut rx).is_empty());
+    let popup = render_bottom_popup(&chat, 120);
+    assert!(
+        !popup.contains("Network access required"),
+        "unexpected popup:\n{popup}"
+    );
+}
+
+#[test]
+fn model_family_query_keeps_leading_name_segments() {
+    assert_eq!(model_family_query("gpt-5.1-codex").as_deref(), Some("gpt"));
+    assert_eq!(
+        model_family_query("gateway/llama-3.1-70b").as_deref(),
+        Some("llama")
+    );
+    assert_eq!(model_family_query("gateway/oca-large"), None);
+    assert_eq!(model_family_query("bengalfox"), None);
+}
+
 #[tokio::test]
 async fn approvals_selection_popup_snapshot() {
     let (mut chat, _rx, _op_rx) = make_chatwidget_manual(None).await;
@@ -2063,12 +2572,66 @@ async fn model_reasoning_selection_popup_snapshot() {
     chat.config.model_reasoning_effort = Some(ReasoningEffortConfig::High);
 
     let preset = get_available_model(&chat, "gpt-5.1-codex-max");
-    chat.open_reasoning_popup(preset);
+    chat.open_reasoning_popup(preset.view());
 
     let popup = render_bottom_popup(&chat, 80);
     assert_snapshot!("model_reasoning_selection_popup", popup);
 }
 
+#[tokio::test]
+async fn reasoning_popup_leaves_out_disabled_efforts() {
+    let (mut chat, _rx, _op_rx) = make_chatwidget_manual(Some("gpt-5.1-codex-max")).await;
+    set_chatgpt_auth(&mut chat);
+
+    chat.config.disabled_reasoning_efforts =
+        vec![ReasoningEffortConfig::High, ReasoningEffortConfig::XHigh];
+    let preset = get_available_model(&chat, "gpt-5.1-codex-max");
+    chat.open_reasoning_popup(preset.view());
+    let popup = render_bottom_popup(&chat, 80);
+    assert!(popup.contains("Medium"), "{popup}");
+    assert!(!popup.contains("High"), "{popup}");
+
+    // With every effort disabled the model is offered without one.
+    chat.config.disabled_reasoning_efforts = ReasoningEffortConfig::VARIANTS.to_vec();
+    let preset = get_available_model(&chat, "gpt-5.1-codex-max");
+    assert!(preset.supported_reasoning_efforts.is_empty());
+    chat.open_reasoning_popup(preset.view());
+    let popup = render_bottom_popup(&chat, 80);
+    assert!(
+        popup.contains("gpt-5.1-codex-max does not take a reasoning effort"),
+        "{popup}"
+    );
+}
+
+#[tokio::test]
+async fn model_pickers_say_a_switch_waits_for_the_running_turn() {
+    let (mut chat, mut rx, _op_rx) = make_chatwidget_manual(Some("gpt-5.1-codex-max")).await;
+    chat.bottom_pane.set_task_running(true);
+
+    chat.dispatch_command(SlashCommand::Model);
+    assert!(
+        drain_insert_history(&mut rx).is_empty(),
+        "/model stays available during a turn"
+    );
+    let popup = render_bottom_popup(&chat, 80);
+    assert!(
+        popup.contains("Will apply after current turn."),
+        "model picker should note the pending switch: {popup}"
+    );
+
+    let preset = get_available_model(&chat, "gpt-5.1-codex-max");
+    chat.open_reasoning_popup(preset.view());
+    let popup = render_bottom_popup(&chat, 80);
+    assert!(
+        popup.contains("Will apply after current turn."),
+        "effort picker should note the pending switch: {popup}"
+    );
+
+    chat.bottom_pane.set_task_running(false);
+    chat.open_model_popup();
+    assert!(!render_bottom_popup(&chat, 80).contains("Will apply after current turn."));
+}
+
 #[tokio::test]
 async fn model_reasoning_selection_popup_extra_high_warning_snapshot() {
     let (mut chat, _rx, _op_rx) = make_chatwidget_manual(Some("gpt-5.1-codex-max")).await;
@@ -2077,12 +2640,96 @@ async fn model_reasoning_selection_popup_extra_high_warning_snapshot() {
     chat.config.model_reasoning_effort = Some(ReasoningEffortConfig::XHigh);
 
     let preset = get_available_model(&chat, "gpt-5.1-codex-max");
-    chat.open_reasoning_popup(preset);
+    chat.open_reasoning_popup(preset.view());
 
     let popup = render_bottom_popup(&chat, 80);
     assert_snapshot!("model_reasoning_selection_popup_extra_high_warning", popup);
 }
 
+#[tokio::test]
+async fn reasoning_popup_cuts_unselected_descriptions_at_word_boundaries() {
+    for width in [50, 80, 120] {
+        let (mut chat, _rx, _op_rx) = make_chatwidget_manual(Some("gpt-5.1-codex-max")).await;
+        set_chatgpt_auth(&mut chat);
+        chat.config.model_reasoning_effort = Some(ReasoningEffortConfig::Low);
+
+        let preset = get_available_model(&chat, "gpt-5.1-codex-max");
+        let medium = preset
+            .supported_reasoning_efforts
+            .iter()
+            .find(|effort| effort.effort == ReasoningEffortConfig::Medium)
+            .expect("medium effort")
+            .description
+            .clone();
+        chat.open_reasoning_popup(preset.view());
+
+        let popup = render_bottom_popup(&chat, width);
+        let lines: Vec<&str> = popup.lines().collect();
+        for line in &lines {
+            assert!(
+                line.chars().count() <= usize::from(width),
+                "line wider than {width}: {line:?}"
+            );
+        }
+        let index = lines
+            .iter()
+            .position(|line| line.contains("3. Medium"))
+            .unwrap_or_else(|| panic!("medium row missing; popup: {popup}"));
+        let shown = lines[index].trim_end();
+        match shown.strip_suffix('…') {
+            Some(cut) => {
+                let cut = cut.trim_end();
+                let kept = cut.split("  ").last().unwrap_or_default().trim();
+                assert!(
+                    medium.starts_with(kept) && medium[kept.len()..].starts_with(' '),
+                    "description not cut at a word boundary at width {width}: {shown:?}"
+                );
+            }
+            None => assert!(
+                shown.ends_with(medium.as_str()),
+                "expected the full description at width {width}: {shown:?}"
+            ),
+        }
+        assert!(
+            lines[index + 1].trim_start().starts_with("4."),
+            "medium row should take one line at width {width}; popup: {popup}"
+        );
+    }
+}
+
+#[tokio::test]
+async fn reasoning_popup_auto_row_follows_the_model_default() {
+    let (mut chat, mut rx, _op_rx) = make_chatwidget_manual(Some("gpt-5.1-codex-max")).await;
+    chat.config.model_reasoning_effort = None;
+
+    let preset = get_available_model(&chat, "gpt-5.1-codex-max");
+    chat.open_reasoning_popup(preset.view());
+
+    let popup = render_bottom_popup(&chat, 80);
+    assert!(
+        popup.contains("› 1. Auto (medium) (current)"),
+        "expected the auto row to be current; popup: {popup}"
+    );
+
+    chat.handle_key_event(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+    let mut events = Vec::new();
+    while let Ok(ev) = rx.try_recv() {
+        events.push(ev);
+    }
+    assert!(
+        events
+            .iter()
+            .any(|ev| matches!(ev, AppEvent::UpdateReasoningEffort(None))),
+        "expected auto to clear the effort; events: {events:?}"
+    );
+    assert!(
+        events
+            .iter()
+            .any(|ev| matches!(ev, AppEvent::PersistModelSelection { effort: None, .. })),
+        "expected auto to be persisted; events: {events:?}"
+    );
+}
+
 #[tokio::test]
 async fn reasoning_popup_shows_extra_high_with_space() {
     let (mut chat, _rx, _op_rx) = make_chatwidget_manual(Some("gpt-5.1-codex-max")).await;
@@ -2090,7 +2737,7 @@ async fn reasoning_popup_shows_extra_high_with_space() {
     set_chatgpt_auth(&mut chat);
 
     let preset = get_available_model(&chat, "gpt-5.1-codex-max");
-    chat.open_reasoning_popup(preset);
+    chat.open_reasoning_popup(preset.view());
 
     let popup = render_bottom_popup(&chat, 120);
     assert!(
@@ -2107,23 +2754,11 @@ async fn reasoning_popup_shows_extra_high_with_space() {
 async fn single_reasoning_option_skips_selection() {
     let (mut chat, mut rx, _op_rx) = make_chatwidget_manual(None).await;
 
-    let single_effort = vec![ReasoningEffortPreset {
-        effort: ReasoningEffortConfig::High,
-        description: "Greater reasoning depth for complex or ambiguous problems".to_string(),
-    }];
-    let preset = ModelPreset {
-        id: "model-with-single-reasoning".to_string(),
-        model: "model-with-single-reasoning".to_string(),
-        display_name: "model-with-single-reasoning".to_string(),
-        description: "".to_string(),
-        default_reasoning_effort: ReasoningEffortConfig::High,
-        supported_reasoning_efforts: single_effort,
-        is_default: false,
-        upgrade: None,
-        show_in_picker: true,
-        supported_in_api: true,
-    };
-    chat.open_reasoning_popup(preset);
+    let preset = preset("model-with-single-reasoning")
+        .efforts([ReasoningEffortConfig::High])
+        .build()
+        .expect("valid preset");
+    chat.open_reasoning_popup(preset.view());
 
     let popup = render_bottom_popup(&chat, 80);
     assert!(
@@ -2144,6 +2779,112 @@ async fn single_reasoning_option_skips_selection() {
     );
 }
 
+#[tokio::test]
+async fn provider_model_selection_switches_provider_for_session() {
+    let (mut chat, mut rx, _op_rx) = make_chatwidget_manual(None).await;
+
+    let preset = provider_preset("lmstudio", "qwen2.5-coder-7b")
+        .display_name("qwen2.5-coder-7b")
+        .description("Served by LM Studio")
+        .build()
+        .expect("valid preset");
+    chat.open_reasoning_popup(preset.view());
+    chat.handle_key_event(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+
+    let mut events = Vec::new();
+    while let Ok(ev) = rx.try_recv() {
+        events.push(ev);
+    }
+
+    assert!(
+        events.iter().any(|ev| matches!(
+            ev,
+            AppEvent::CodexOp(Op::OverrideTurnContext {
+                model: Some(model),
+                model_provider: Some(provider),
+                ..
+            }) if model == "qwen2.5-coder-7b" && provider == "lmstudio"
+        )),
+        "expected model and provider override; events: {events:?}"
+    );
+    assert!(
+        !events
+            .iter()
+            .any(|ev| matches!(ev, AppEvent::PersistModelSelection { .. })),
+        "provider-served models should not be persisted; events: {events:?}"
+    );
+}
+
+#[tokio::test]
+async fn reasoning_popup_disables_summaries_for_models_without_them() {
+    let (mut chat, _rx, _op_rx) = make_chatwidget_manual(None).await;
+
+    let mut preset = get_available_model(&chat, "gpt-5.1-codex-max");
+    preset.supports_reasoning_summaries = Some(false);
+    chat.open_reasoning_popup(preset.view());
+    let popup = render_bottom_popup(&chat, 100);
+    assert!(
+        popup.contains("Reasoning summaries")
+            && popup.contains("not supported by gpt-5.1-codex-max"),
+        "expected a disabled summaries row: {popup}"
+    );
+
+    let preset = get_available_model(&chat, "gpt-5.1-codex-max");
+    chat.open_reasoning_popup(preset.view());
+    let popup = render_bottom_popup(&chat, 100);
+    assert!(
+        !popup.contains("Reasoning summaries"),
+        "models that may write summaries get no summaries row: {popup}"
+    );
+}
+
+#[tokio::test]
+async fn effort_picker_is_disabled_for_presets_without_efforts() {
+    let (mut chat, mut rx, _op_rx) = make_chatwidget_manual(None).await;
+
+    let mut preset = get_available_model(&chat, "gpt-5.1-codex-max");
+    preset.supported_reasoning_efforts = Vec::new();
+    chat.open_reasoning_popup(preset.view());
+
+    let popup = render_bottom_popup(&chat, 100);
+    assert!(
+        popup.contains("not supported by gpt-5.1-codex-max"),
+        "expected the effort row to be disabled: {popup}"
+    );
+    assert!(
+        popup.contains("does not take a reasoning effort"),
+        "expected an explanation of the disabled picker: {popup}"
+    );
+    assert!(
+        rx.try_recv().is_err(),
+        "nothing is applied until the picker is confirmed"
+    );
+
+    chat.handle_key_event(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+
+    let mut events = Vec::new();
+    while let Ok(ev) = rx.try_recv() {
+        events.push(ev);
+    }
+    assert!(
+        events.iter().any(|ev| matches!(
+            ev,
+            AppEvent::CodexOp(Op::OverrideTurnContext {
+                model: Some(model),
+                effort: Some(None),
+                ..
+            }) if model == "gpt-5.1-codex-max"
+        )),
+        "expected the model to be applied without an effort; events: {events:?}"
+    );
+    assert!(
+        events
+            .iter()
+            .any(|ev| matches!(ev, AppEvent::UpdateReasoningEffort(None))),
+        "expected the effort to be cleared; events: {events:?}"
+    );
+}
+
 #[tokio::test]
 async fn feedback_selection_popup_snapshot() {
     let (mut chat, _rx, _op_rx) = make_chatwidget_manual(None).await;
@@ -2172,7 +2913,7 @@ async fn reasoning_popup_escape_returns_to_model_popup() {
     chat.open_model_popup();
 
     let preset = get_available_model(&chat, "gpt-5.1-codex-max");
-    chat.open_reasoning_popup(preset);
+    chat.open_reasoning_popup(preset.view());
 
     let before_escape = render_bottom_popup(&chat, 80);
     assert!(before_escape.contains("Select Reasoning Level"));
@@ -2243,8 +2984,8 @@ async fn disabled_slash_command_while_task_running_snapshot() {
     let (mut chat, mut rx, _op_rx) = make_chatwidget_manual(None).await;
     chat.bottom_pane.set_task_running(true);
 
-    // Dispatch a command that is unavailable while a task runs (e.g., /model)
-    chat.dispatch_command(SlashCommand::Model);
+    // Dispatch a command that is unavailable while a task runs (e.g., /approvals)
+    chat.dispatch_command(SlashCommand::Approvals);
 
     // Drain history and snapshot the rendered error line(s)
     let cells = drain_insert_history(&mut rx);
@@ -2609,6 +3350,9 @@ async fn ui_snapshots_small_heights_task_running() {
         id: "task-1".into(),
         msg: EventMsg::TaskStarted(TaskStartedEvent {
             model_context_window: None,
+            reasoning_effort: None,
+            reasoning_effort_description: None,
+            reasoning_summary_format: None,
         }),
     });
     chat.handle_codex_event(Event {
@@ -2640,6 +3384,9 @@ async fn status_widget_and_approval_modal_snapshot() {
         id: "task-1".into(),
         msg: EventMsg::TaskStarted(TaskStartedEvent {
             model_context_window: None,
+            reasoning_effort: None,
+            reasoning_effort_description: None,
+            reasoning_summary_format: None,
         }),
     });
     // Provide a deterministic header for the status line.
@@ -2692,6 +3439,9 @@ async fn status_widget_active_snapshot() {
         id: "task-1".into(),
         msg: EventMsg::TaskStarted(TaskStartedEvent {
             model_context_window: None,
+            reasoning_effort: None,
+            reasoning_effort_description: None,
+            reasoning_summary_format: None,
         }),
     });
     // Provide a deterministic header via a bold reasoning chunk.
@@ -3264,6 +4014,69 @@ async fn warning_event_adds_warning_history_cell() {
     );
 }
 
+#[tokio::test]
+async fn client_version_notice_adds_warning_history_cell() {
+    let (mut chat, mut rx, _op_rx) = make_chatwidget_manual(None).await;
+    chat.on_client_version_notice(&ClientVersionNotice {
+        min_client_version: "0.60.0".to_string(),
+        client_version: "0.59.0".to_string(),
+    });
+
+    let cells = drain_insert_history(&mut rx);
+    assert_eq!(cells.len(), 1, "expected one warning history cell");
+    let rendered = lines_to_single_string(&cells[0]);
+    assert!(
+        rendered.contains("your Codex version (0.59.0) is below the minimum recommended"),
+        "warning cell missing content: {rendered}"
+    );
+}
+
+#[tokio::test]
+async fn default_model_change_adds_info_history_cell() {
+    let (mut chat, mut rx, _op_rx) = make_chatwidget_manual(None).await;
+    chat.on_default_model_changed("remote-a", Some("remote-b"));
+    chat.on_default_model_changed("remote-b", None);
+
+    let cells = drain_insert_history(&mut rx);
+    assert_eq!(cells.len(), 2, "expected one info cell per change");
+    let moved = lines_to_single_string(&cells[0]);
+    assert!(
+        moved.contains("The default model changed from remote-a to remote-b"),
+        "info cell missing content: {moved}"
+    );
+    let gone = lines_to_single_string(&cells[1]);
+    assert!(
+        gone.contains("no longer lists its default model remote-b"),
+        "info cell missing content: {gone}"
+    );
+}
+
+#[tokio::test]
+async fn fallback_model_change_adds_warning_history_cell() {
+    let (mut chat, mut rx, _op_rx) = make_chatwidget_manual(None).await;
+    chat.handle_codex_event(Event {
+        id: "sub-1".into(),
+        msg: EventMsg::ModelChanged(ModelChangedEvent {
+            previous_model: "gpt-5.1-codex".to_string(),
+            model: "gpt-5".to_string(),
+            source: ModelChangeSource::Fallback,
+            previous_model_provider: None,
+            model_provider: None,
+        }),
+    });
+
+    let cells = drain_insert_history(&mut rx);
+    assert_eq!(cells.len(), 1, "expected one warning history cell");
+    let rendered = lines_to_single_string(&cells[0])
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ");
+    assert!(
+        rendered.contains("gpt-5.1-codex is at capacity, so this turn is retried on gpt-5."),
+        "warning cell missing the models: {rendered}"
+    );
+}
+
 #[tokio::test]
 async fn stream_recovery_restores_previous_status_header() {
     let (mut chat, mut rx, _op_rx) = make_chatwidget_manual(None).await;
@@ -3271,6 +4084,9 @@ async fn stream_recovery_restores_previous_status_header() {
         id: "task".into(),
         msg: EventMsg::TaskStarted(TaskStartedEvent {
             model_context_window: None,
+            reasoning_effort: None,
+            reasoning_effort_description: None,
+            reasoning_summary_format: None,
         }),
     });
     drain_insert_history(&mut rx);
@@ -3308,6 +4124,9 @@ async fn multiple_agent_messages_in_single_turn_emit_multiple_headers() {
         id: "s1".into(),
         msg: EventMsg::TaskStarted(TaskStartedEvent {
             model_context_window: None,
+            reasoning_effort: None,
+            reasoning_effort_description: None,
+            reasoning_summary_format: None,
         }),
     });
 
@@ -3380,6 +4199,46 @@ async fn final_reasoning_then_message_without_deltas_are_rendered() {
     assert_snapshot!(combined);
 }
 
+#[tokio::test]
+async fn plain_reasoning_summaries_keep_their_bold_text() {
+    let (mut chat, mut rx, _op_rx) = make_chatwidget_manual(None).await;
+    let reasoning = |chat: &mut ChatWidget, text: &str| {
+        chat.handle_codex_event(Event {
+            id: "s1".into(),
+            msg: EventMsg::AgentReasoningDelta(AgentReasoningDeltaEvent { delta: text.into() }),
+        });
+        chat.handle_codex_event(Event {
+            id: "s1".into(),
+            msg: EventMsg::AgentReasoning(AgentReasoningEvent { text: text.into() }),
+        });
+    };
+    let rendered = |rx: &mut tokio::sync::mpsc::UnboundedReceiver<AppEvent>| {
+        drain_insert_history(rx)
+            .iter()
+            .map(|lines| lines_to_single_string(lines))
+            .collect::<String>()
+    };
+
+    reasoning(&mut chat, "We should **not** fix the bug yet.");
+    let headed = rendered(&mut rx);
+    assert!(!headed.contains("We should"), "{headed}");
+
+    // Core resolves the format for the turn's model and reports it when the
+    // turn starts.
+    chat.handle_codex_event(Event {
+        id: "s2".into(),
+        msg: EventMsg::TaskStarted(TaskStartedEvent {
+            model_context_window: None,
+            reasoning_effort: None,
+            reasoning_effort_description: None,
+            reasoning_summary_format: Some(ReasoningSummaryFormat::Plain),
+        }),
+    });
+    reasoning(&mut chat, "We should **not** fix the bug yet.");
+    let plain = rendered(&mut rx);
+    assert!(plain.contains("We should not fix the bug yet."), "{plain}");
+}
+
 #[tokio::test]
 async fn deltas_then_same_final_message_are_rendered_snapshot() {
     let (mut chat, mut rx, _op_rx) = make_chatwidget_manual(None).await;
@@ -3502,6 +4361,9 @@ async fn chatwidget_exec_and_status_layout_vt100_snapshot() {
         id: "t1".into(),
         msg: EventMsg::TaskStarted(TaskStartedEvent {
             model_context_window: None,
+            reasoning_effort: None,
+            reasoning_effort_description: None,
+            reasoning_summary_format: None,
         }),
     });
     chat.handle_codex_event(Event {
@@ -3546,6 +4408,9 @@ async fn chatwidget_markdown_code_blocks_vt100_snapshot() {
         id: "t1".into(),
         msg: EventMsg::TaskStarted(TaskStartedEvent {
             model_context_window: None,
+            reasoning_effort: None,
+            reasoning_effort_description: None,
+            reasoning_summary_format: None,
         }),
     });
     // Build a vt100 visual from the history insertions only (no UI overlay)
@@ -3634,6 +4499,9 @@ async fn chatwidget_tall() {
         id: "t1".into(),
         msg: EventMsg::TaskStarted(TaskStartedEvent {
             model_context_window: None,
+            reasoning_effort: None,
+            reasoning_effort_description: None,
+            reasoning_summary_format: None,
         }),
     });
     for i in 0..30 {
@@ -2,6 +2,7 @@ use clap::Parser;
 use clap::ValueHint;
 use codex_common::ApprovalModeCliArg;
 use codex_common::CliConfigOverrides;
+use codex_common::ModelValueParser;
 use std::path::PathBuf;
 
 #[derive(Parser, Debug)]
@@ -33,7 +34,7 @@ pub struct Cli {
     pub resume_show_all: bool,
 
     /// Model the agent should use.
-    #[arg(long, short = 'm')]
+    #[arg(long, short = 'm', value_parser = ModelValueParser)]
     pub model: Option<String>,
 
     /// Convenience flag to select the local open source model provider. Equivalent to -c
@@ -33,6 +33,7 @@ use codex_core::protocol::McpAuthStatus;
 use codex_core::protocol::McpInvocation;
 use codex_core::protocol::SessionConfiguredEvent;
 use codex_protocol::openai_models::ReasoningEffort as ReasoningEffortConfig;
+use codex_protocol::openai_models::ReasoningSummaryFormat;
 use codex_protocol::plan_tool::PlanItemArg;
 use codex_protocol::plan_tool::StepStatus;
 use codex_protocol::plan_tool::UpdatePlanArgs;
@@ -232,6 +233,55 @@ impl HistoryCell for ReasoningSummaryCell {
     }
 }
 
+/// Transcript-only note of the reasoning effort a turn ran at and what the
+/// active preset said it meant.
+#[derive(Debug)]
+pub(crate) struct TurnEffortCell {
+    effort: ReasoningEffortConfig,
+    description: String,
+}
+
+impl TurnEffortCell {
+    pub(crate) fn new(effort: ReasoningEffortConfig, description: String) -> Self {
+        Self {
+            effort,
+            description,
+        }
+    }
+
+    fn lines(&self, width: u16) -> Vec<Line<'static>> {
+        let line = Line::from(vec![
+            format!("effort: {}", self.effort).dim(),
+            " · ".dim(),
+            self.description.clone().dim().italic(),
+        ]);
+        word_wrap_lines(
+            &[line],
+            RtOptions::new(width as usize)
+                .initial_indent("• ".dim().into())
+                .subsequent_indent("  ".into()),
+        )
+    }
+}
+
+impl HistoryCell for TurnEffortCell {
+    fn display_lines(&self, _width: u16) -> Vec<Line<'static>> {
+        Vec::new()
+    }
+
+    fn desired_height(&self, _width: u16) -> u16 {
+        0
+    }
+
+    fn transcript_lines(&self, width: u16) -> Vec<Line<'static>> {
+        self.lines(width)
+    }
+
+    fn desired_transcript_height(&self, width: u16) -> u16 {
+        self.lines(width).len() as u16
+    }
+}
+
 #[derive(Debug)]
 pub(crate) struct AgentMessageCell {
     lines: Vec<Line<'static>>,
@@ -989,14 +1039,7 @@ impl SessionHeaderHistoryCell {
     }
 
     fn reasoning_label(&self) -> Option<&'static str> {
-        self.reasoning_effort.map(|effort| match effort {
-            ReasoningEffortConfig::Minimal => "minimal",
-            ReasoningEffortConfig::Low => "low",
-            ReasoningEffortConfig::Medium => "medium",
-            ReasoningEffortConfig::High => "high",
-            ReasoningEffortConfig::XHigh => "xhigh",
-            ReasoningEffortConfig::None => "none",
-        })
+        self.reasoning_effort.map(ReasoningEffortConfig::as_str)
     }
 }
 
@@ -1660,8 +1703,21 @@ pub(crate) fn new_view_image_tool_call(path: PathBuf, cwd: &Path) -> PlainHistor
     PlainHistoryCell { lines }
 }
 
-pub(crate) fn new_reasoning_summary_block(full_reasoning_buffer: String) -> Box<dyn HistoryCell> {
+/// A headed summary has its header, the first bold span, dropped, and one
+/// without a header is kept in the transcript only. Plain summaries are kept
+/// whole, since bold text in them is no header.
+pub(crate) fn new_reasoning_summary_block(
+    full_reasoning_buffer: String,
+    format: ReasoningSummaryFormat,
+) -> Box<dyn HistoryCell> {
     let full_reasoning_buffer = full_reasoning_buffer.trim();
+    if format == ReasoningSummaryFormat::Plain {
+        return Box::new(ReasoningSummaryCell::new(
+            String::new(),
+            full_reasoning_buffer.to_string(),
+            false,
+        ));
+    }
     if let Some(open) = full_reasoning_buffer.find("**") {
         let after_open = &full_reasoning_buffer[(open + 2)..];
         if let Some(close) = after_open.find("**") {
@@ -2661,6 +2717,7 @@ mod tests {
     fn reasoning_summary_block() {
         let cell = new_reasoning_summary_block(
             "**High level reasoning**\n\nDetailed reasoning goes here.".to_string(),
+            ReasoningSummaryFormat::Headed,
         );
 
         let rendered_display = render_lines(&cell.display_lines(80));
@@ -2670,9 +2727,26 @@ mod tests {
         assert_eq!(rendered_transcript, vec!["• Detailed reasoning goes here."]);
     }
 
+    #[test]
+    fn turn_effort_cell_only_renders_in_transcript() {
+        let cell = TurnEffortCell::new(
+            ReasoningEffortConfig::High,
+            "Maximizes reasoning depth for complex or ambiguous problems".to_string(),
+        );
+
+        assert_eq!(render_lines(&cell.display_lines(80)), Vec::<String>::new());
+        assert_eq!(
+            render_transcript(&cell),
+            vec!["• effort: high · Maximizes reasoning depth for complex or ambiguous problems"]
+        );
+    }
+
     #[test]
     fn reasoning_summary_block_returns_reasoning_cell_when_feature_disabled() {
-        let cell = new_reasoning_summary_block("Detailed reasoning goes here.".to_string());
+        let cell = new_reasoning_summary_block(
+            "Detailed reasoning goes here.".to_string(),
+            ReasoningSummaryFormat::Headed,
+        );
 
         let rendered = render_transcript(cell.as_ref());
         assert_eq!(rendered, vec!["• Detailed reasoning goes here."]);
@@ -2685,6 +2759,7 @@ mod tests {
         config.model_supports_reasoning_summaries = Some(true);
         let cell = new_reasoning_summary_block(
             "**High level reasoning**\n\nDetailed reasoning goes here.".to_string(),
+            ReasoningSummaryFormat::Headed,
         );
 
         let rendered_display = render_lines(&cell.display_lines(80));
@@ -2693,8 +2768,10 @@ mod tests {
 
     #[test]
     fn reasoning_summary_block_falls_back_when_header_is_missing() {
-        let cell =
-            new_reasoning_summary_block("**High level reasoning without closing".to_string());
+        let cell = new_reasoning_summary_block(
+            "**High level reasoning without closing".to_string(),
+            ReasoningSummaryFormat::Headed,
+        );
 
         let rendered = render_transcript(cell.as_ref());
         assert_eq!(rendered, vec!["• **High level reasoning without closing"]);
@@ -2702,14 +2779,17 @@ mod tests {
 
     #[test]
     fn reasoning_summary_block_falls_back_when_summary_is_missing() {
-        let cell =
-            new_reasoning_summary_block("**High level reasoning without closing**".to_string());
+        let cell = new_reasoning_summary_block(
+            "**High level reasoning without closing**".to_string(),
+            ReasoningSummaryFormat::Headed,
+        );
 
         let rendered = render_transcript(cell.as_ref());
         assert_eq!(rendered, vec!["• High level reasoning without closing"]);
 
         let cell = new_reasoning_summary_block(
             "**High level reasoning without closing**\n\n  ".to_string(),
+            ReasoningSummaryFormat::Headed,
         );
 
         let rendered = render_transcript(cell.as_ref());
@@ -2720,6 +2800,7 @@ mod tests {
     fn reasoning_summary_block_splits_header_and_summary_when_present() {
         let cell = new_reasoning_summary_block(
             "**High level plan**\n\nWe should fix the bug next.".to_string(),
+            ReasoningSummaryFormat::Headed,
         );
 
         let rendered_display = render_lines(&cell.display_lines(80));
@@ -2729,6 +2810,38 @@ mod tests {
         assert_eq!(rendered_transcript, vec!["• We should fix the bug next."]);
     }
 
+    #[test]
+    fn plain_reasoning_summaries_are_kept_whole() {
+        let reasoning = "We should **not** fix the bug yet.".to_string();
+
+        let headed = new_reasoning_summary_block(reasoning.clone(), ReasoningSummaryFormat::Headed);
+        assert_eq!(
+            render_lines(&headed.display_lines(80)),
+            vec!["• fix the bug yet."],
+            "a headed summary loses everything through its first bold span"
+        );
+
+        let plain = new_reasoning_summary_block(reasoning, ReasoningSummaryFormat::Plain);
+        assert_eq!(
+            render_lines(&plain.display_lines(80)),
+            vec!["• We should not fix the bug yet."]
+        );
+        assert_eq!(
+            render_transcript(plain.as_ref()),
+            vec!["• We should not fix the bug yet."]
+        );
+
+        // Without any bold span it is still shown, not left to the transcript.
+        let plain = new_reasoning_summary_block(
+            "Checking the tests first.".to_string(),
+            ReasoningSummaryFormat::Plain,
+        );
+        assert_eq!(
+            render_lines(&plain.display_lines(80)),
+            vec!["• Checking the tests first."]
+        );
+    }
+
     #[test]
     fn deprecation_notice_renders_summary_with_details() {
         let cell = new_deprecation_notice(
@@ -21,11 +21,15 @@ use codex_core::config::load_config_as_toml_with_cli_overrides;
 use codex_core::config::resolve_oss_provider;
 use codex_core::find_conversation_path_by_id_str;
 use codex_core::get_platform_sandbox;
+use codex_core::models_manager::catalog::CatalogLoadOptions;
+use codex_core::models_manager::catalog::PresetCatalog;
+use codex_core::models_manager::manager::ModelsManager;
 use codex_core::protocol::AskForApproval;
 use codex_protocol::config_types::SandboxMode;
 use codex_utils_absolute_path::AbsolutePathBuf;
 use std::fs::OpenOptions;
 use std::path::PathBuf;
+use std::sync::Arc;
 use tracing::error;
 use tracing_appender::non_blocking;
 use tracing_subscriber::EnvFilter;
@@ -85,6 +89,8 @@ mod version;
 
 mod wrapping;
 
+#[cfg(test)]
+mod alloc_counter;
 #[cfg(test)]
 pub mod test_backend;
 
@@ -392,12 +398,27 @@ async fn run_ratatui_app(
         should_show_onboarding(login_status, &initial_config, should_show_trust_screen);
 
     let config = if should_show_onboarding {
+        let show_defaults_screen = should_show_login_screen(login_status, &initial_config)
+            && initial_config.model.is_none();
+        let default_presets = if show_defaults_screen {
+            let models_manager = ModelsManager::with_provider(
+                auth_manager.clone(),
+                initial_config.model_provider.clone(),
+            );
+            PresetCatalog::new(&models_manager, &initial_config)
+                .load(CatalogLoadOptions::default())
+                .await
+        } else {
+            Vec::new()
+        };
         let onboarding_result = run_onboarding_app(
             OnboardingScreenArgs {
                 show_login_screen: should_show_login_screen(login_status, &initial_config),
+                show_defaults_screen,
                 show_trust_screen: should_show_trust_screen,
                 login_status,
                 auth_manager: auth_manager.clone(),
+                default_presets,
                 config: initial_config.clone(),
             },
             &mut tui,
@@ -413,11 +434,12 @@ async fn run_ratatui_app(
                 update_action: None,
             });
         }
-        // if the user acknowledged windows or made an explicit decision ato trust the directory, reload the config accordingly
-        if onboarding_result
-            .directory_trust_decision
-            .map(|d| d == TrustDirectorySelection::Trust)
-            .unwrap_or(false)
+        // if the user acknowledged windows, made an explicit decision ato trust the directory, or picked a default model, reload the config accordingly
+        if onboarding_result.defaults_chosen
+            || onboarding_result
+                .directory_trust_decision
+                .map(|d| d == TrustDirectorySelection::Trust)
+                .unwrap_or(false)
         {
             load_config_or_exit(cli_kv_overrides, overrides).await
         } else {
@@ -469,10 +491,14 @@ async fn run_ratatui_app(
             Err(_) => resume_picker::ResumeSelection::StartFresh,
         }
     } else if cli.resume_picker {
+        let models_manager = Arc::new(ModelsManager::with_provider(
+            auth_manager.clone(),
+            config.model_provider.clone(),
+        ));
         match resume_picker::run_resume_picker(
             &mut tui,
-            &config.codex_home,
-            &config.model_provider_id,
+            &config,
+            models_manager,
             cli.resume_show_all,
         )
         .await?
@@ -0,0 +1,462 @@
+use std::path::PathBuf;
+
+use codex_core::config::edit::ConfigEditsBuilder;
+use codex_protocol::openai_models::ModelPreset;
+use codex_protocol::openai_models::ReasoningEffort;
+use crossterm::event::KeyCode;
+use crossterm::event::KeyEvent;
+use crossterm::event::KeyEventKind;
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::style::Stylize;
+use ratatui::text::Line;
+use ratatui::widgets::Paragraph;
+use ratatui::widgets::WidgetRef;
+use ratatui::widgets::Wrap;
+
+use crate::chatwidget::ChatWidget;
+use crate::key_hint;
+use crate::onboarding::onboarding_screen::KeyboardHandler;
+use crate::onboarding::onboarding_screen::StepState;
+use crate::onboarding::onboarding_screen::StepStateProvider;
+use crate::render::Insets;
+use crate::render::renderable::ColumnRenderable;
+use crate::render::renderable::Renderable;
+use crate::render::renderable::RenderableExt as _;
+use crate::selection_list::selection_option_row;
+
+/// First-run choice of the model and reasoning effort sessions start with.
+/// The effort part is skipped for models that take no effort.
+pub(crate) struct ChooseDefaultsWidget {
+    pub codex_home: PathBuf,
+    /// Whether the configured `model_provider` has a `model_catalog`. Such a
+    /// provider only serves its own presets, so it is removed when a preset
+    /// without a provider is chosen.
+    pub provider_has_catalog: bool,
+    pub presets: Vec<ModelPreset>,
+    pub stage: DefaultsStage,
+    pub highlighted_model: usize,
+    pub highlighted_effort: usize,
+    /// The defaults written to config, once chosen and saved.
+    pub selection: Option<DefaultsSelection>,
+    pub error: Option<String>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct DefaultsSelection {
+    pub model_provider: Option<String>,
+    pub model: String,
+    pub effort: Option<ReasoningEffort>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum DefaultsStage {
+    Model,
+    Effort,
+}
+
+impl ChooseDefaultsWidget {
+    pub(crate) fn new(
+        codex_home: PathBuf,
+        provider_has_catalog: bool,
+        presets: Vec<ModelPreset>,
+    ) -> Self {
+        let highlighted_model = presets
+            .iter()
+            .position(|preset| preset.is_default)
+            .unwrap_or(0);
+        Self {
+            codex_home,
+            provider_has_catalog,
+            presets,
+            stage: DefaultsStage::Model,
+            highlighted_model,
+            highlighted_effort: 0,
+            selection: None,
+            error: None,
+        }
+    }
+
+    fn highlighted_preset(&self) -> Option<&ModelPreset> {
+        self.presets.get(self.highlighted_model)
+    }
+
+    fn option_count(&self) -> usize {
+        match self.stage {
+            DefaultsStage::Model => self.presets.len(),
+            DefaultsStage::Effort => self
+                .highlighted_preset()
+                .map_or(0, |preset| preset.supported_reasoning_efforts.len()),
+        }
+    }
+
+    fn highlighted_mut(&mut self) -> &mut usize {
+        match self.stage {
+            DefaultsStage::Model => &mut self.highlighted_model,
+            DefaultsStage::Effort => &mut self.highlighted_effort,
+        }
+    }
+
+    fn confirm(&mut self) {
+        let Some(preset) = self.highlighted_preset() else {
+            return;
+        };
+        let model_provider = preset.model_provider.clone();
+        let model = preset.model.clone();
+        match self.stage {
+            DefaultsStage::Model if preset.supported_reasoning_efforts.is_empty() => {
+                self.persist(DefaultsSelection {
+                    model_provider,
+                    model,
+                    effort: None,
+                });
+            }
+            DefaultsStage::Model => {
+                self.highlighted_effort = preset
+                    .supported_reasoning_efforts
+                    .iter()
+                    .position(|option| option.effort == preset.default_reasoning_effort)
+                    .unwrap_or(0);
+                self.stage = DefaultsStage::Effort;
+            }
+            DefaultsStage::Effort => {
+                let effort = preset
+                    .supported_reasoning_efforts
+                    .get(self.highlighted_effort)
+                    .map(|option| option.effort);
+                self.persist(DefaultsSelection {
+                    model_provider,
+                    model,
+                    effort,
+                });
+            }
+        }
+    }
+
+    /// Writes `selection` to config. The step completes only once it is
+    /// saved; on failure the error is shown and the choice can be retried.
+    fn persist(&mut self, selection: DefaultsSelection) {
+        let mut edits = ConfigEditsBuilder::new(&self.codex_home);
+        if selection.model_provider.is_some() || self.provider_has_catalog {
+            edits = edits.set_model_provider(selection.model_provider.as_deref());
+        }
+        match edits
+            .set_model(Some(&selection.model), selection.effort)
+            .apply_blocking()
+        {
+            Ok(()) => {
+                self.error = None;
+                self.selection = Some(selection);
+            }
+            Err(err) => {
+                tracing::error!("Failed to save the default model: {err:?}");
+                self.error = Some(format!(
+                    "Failed to save {} as the default model: {err}",
+                    selection.model
+                ));
+            }
+        }
+    }
+
+    fn render_models(&self, column: &mut ColumnRenderable) {
+        column.push(Line::from(vec![
+            "> ".into(),
+            "Choose the model Codex starts with".bold(),
+        ]));
+        column.push("");
+        for (idx, preset) in self.presets.iter().enumerate() {
+            let label = if preset.description.is_empty() {
+                preset.display_name.clone()
+            } else {
+                format!("{} — {}", preset.display_name, preset.description)
+            };
+            column.push(selection_option_row(
+                idx,
+                label,
+                idx == self.highlighted_model,
+            ));
+        }
+    }
+
+    fn render_efforts(&self, column: &mut ColumnRenderable, preset: &ModelPreset) {
+        column.push(Line::from(vec![
+            "> ".into(),
+            "Choose the reasoning effort for ".bold(),
+            preset.display_name.clone().bold(),
+        ]));
+        column.push("");
+        for (idx, option) in preset.supported_reasoning_efforts.iter().enumerate() {
+            let mut label = ChatWidget::reasoning_effort_label(option.effort).to_string();
+            if option.effort == preset.default_reasoning_effort {
+                label.push_str(" (default)");
+            }
+            if !option.description.is_empty() {
+                label.push_str(" — ");
+                label.push_str(&option.description);
+            }
+            column.push(selection_option_row(
+                idx,
+                label,
+                idx == self.highlighted_effort,
+            ));
+        }
+    }
+}
+
+impl WidgetRef for &ChooseDefaultsWidget {
+    fn render_ref(&self, area: Rect, buf: &mut Buffer) {
+        let mut column = ColumnRenderable::new();
+        match (self.stage, self.highlighted_preset()) {
+            (DefaultsStage::Effort, Some(preset)) => self.render_efforts(&mut column, preset),
+            _ => self.render_models(&mut column),
+        }
+        column.push("");
+
+        if let Some(error) = &self.error {
+            column.push(
+                Paragraph::new(error.to_string())
+                    .red()
+                    .wrap(Wrap { trim: true })
+                    .inset(Insets::tlbr(0, 2, 0, 0)),
+            );
+            column.push("");
+        }
+
+        let mut hint = vec![
+            "Press ".dim(),
+            key_hint::plain(KeyCode::Enter).into(),
+            " to continue".dim(),
+        ];
+        if self.stage == DefaultsStage::Effort {
+            hint.extend([
+                ", ".dim(),
+                key_hint::plain(KeyCode::Esc).into(),
+                " to pick another model".dim(),
+            ]);
+        }
+        column.push(Line::from(hint).inset(Insets::tlbr(0, 2, 0, 0)));
+
+        column.render(area, buf);
+    }
+}
+
+impl KeyboardHandler for ChooseDefaultsWidget {
+    fn handle_key_event(&mut self, key_event: KeyEvent) {
+        if key_event.kind == KeyEventKind::Release || self.selection.is_some() {
+            return;
+        }
+
+        let count = self.option_count();
+        match key_event.code {
+            KeyCode::Up | KeyCode::Char('k') => {
+                let highlighted = self.highlighted_mut();
+                *highlighted = highlighted.saturating_sub(1);
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                let highlighted = self.highlighted_mut();
+                *highlighted = (*highlighted + 1).min(count.saturating_sub(1));
+            }
+            KeyCode::Char(digit @ '1'..='9') => {
+                let index = (digit as usize) - ('1' as usize);
+                if index < count {
+                    *self.highlighted_mut() = index;
+                    self.confirm();
+                }
+            }
+            KeyCode::Enter => self.confirm(),
+            KeyCode::Esc | KeyCode::Left if self.stage == DefaultsStage::Effort => {
+                self.stage = DefaultsStage::Model;
+            }
+            _ => {}
+        }
+    }
+}
+
+impl StepStateProvider for ChooseDefaultsWidget {
+    fn get_step_state(&self) -> StepState {
+        if self.presets.is_empty() {
+            return StepState::Hidden;
+        }
+        match self.selection {
+            Some(_) => StepState::Complete,
+            None => StepState::InProgress,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_backend::VT100Backend;
+    use codex_common::test_catalog::PresetBuilderExt;
+    use codex_common::test_catalog::catalog;
+    use codex_common::test_catalog::preset;
+    use codex_common::test_catalog::provider_preset;
+    use codex_core::config::CONFIG_TOML_FILE;
+    use crossterm::event::KeyModifiers;
+    use pretty_assertions::assert_eq;
+    use ratatui::Terminal;
+    use tempfile::TempDir;
+
+    fn widget(codex_home: PathBuf) -> ChooseDefaultsWidget {
+        ChooseDefaultsWidget::new(
+            codex_home,
+            false,
+            catalog([
+                preset("gpt-5.1-codex")
+                    .display_name("GPT-5.1 Codex")
+                    .description("Tuned for coding")
+                    .efforts([
+                        ReasoningEffort::Low,
+                        ReasoningEffort::Medium,
+                        ReasoningEffort::High,
+                    ])
+                    .default_reasoning_effort(ReasoningEffort::Medium),
+                preset("gpt-5.1-codex-max")
+                    .display_name("GPT-5.1 Codex Max")
+                    .efforts([ReasoningEffort::High, ReasoningEffort::XHigh])
+                    .default(),
+                provider_preset("ollama", "oss-small").display_name("OSS Small"),
+            ]),
+        )
+    }
+
+    fn press(widget: &mut ChooseDefaultsWidget, code: KeyCode) {
+        widget.handle_key_event(KeyEvent::new(code, KeyModifiers::NONE));
+    }
+
+    fn terminal(widget: &ChooseDefaultsWidget) -> Terminal<VT100Backend> {
+        let mut terminal = Terminal::new(VT100Backend::new(70, 10)).expect("terminal");
+        terminal
+            .draw(|f| widget.render_ref(f.area(), f.buffer_mut()))
+            .expect("draw");
+        terminal
+    }
+
+    fn written_config(codex_home: &TempDir) -> String {
+        std::fs::read_to_string(codex_home.path().join(CONFIG_TOML_FILE)).expect("config written")
+    }
+
+    #[test]
+    fn renders_models_with_the_default_highlighted() {
+        let codex_home = TempDir::new().expect("temp home");
+        let widget = widget(codex_home.path().to_path_buf());
+
+        insta::assert_snapshot!(terminal(&widget).backend());
+    }
+
+    #[test]
+    fn renders_efforts_with_the_preset_default_highlighted() {
+        let codex_home = TempDir::new().expect("temp home");
+        let mut widget = widget(codex_home.path().to_path_buf());
+
+        press(&mut widget, KeyCode::Up);
+        press(&mut widget, KeyCode::Enter);
+        assert_eq!(widget.stage, DefaultsStage::Effort);
+        assert_eq!(widget.get_step_state(), StepState::InProgress);
+
+        insta::assert_snapshot!(terminal(&widget).backend());
+    }
+
+    #[test]
+    fn the_chosen_model_and_effort_are_written_to_config() {
+        let codex_home = TempDir::new().expect("temp home");
+        let mut widget = widget(codex_home.path().to_path_buf());
+
+        press(&mut widget, KeyCode::Enter);
+        press(&mut widget, KeyCode::Down);
+        press(&mut widget, KeyCode::Enter);
+
+        assert_eq!(
+            widget.selection,
+            Some(DefaultsSelection {
+                model_provider: None,
+                model: "gpt-5.1-codex-max".to_string(),
+                effort: Some(ReasoningEffort::XHigh),
+            })
+        );
+        assert_eq!(widget.get_step_state(), StepState::Complete);
+        assert_eq!(
+            written_config(&codex_home),
+            "model = \"gpt-5.1-codex-max\"\nmodel_reasoning_effort = \"xhigh\"\n"
+        );
+    }
+
+    #[test]
+    fn going_back_from_efforts_lets_another_model_be_picked() {
+        let codex_home = TempDir::new().expect("temp home");
+        let mut widget = widget(codex_home.path().to_path_buf());
+
+        press(&mut widget, KeyCode::Enter);
+        assert_eq!(widget.stage, DefaultsStage::Effort);
+        press(&mut widget, KeyCode::Esc);
+        assert_eq!(widget.stage, DefaultsStage::Model);
+        assert_eq!(widget.highlighted_model, 1, "the model stays highlighted");
+
+        press(&mut widget, KeyCode::Char('1'));
+        assert_eq!(widget.stage, DefaultsStage::Effort);
+        assert_eq!(widget.highlighted_effort, 1, "the preset's default effort");
+    }
+
+    #[test]
+    fn models_without_efforts_skip_the_effort_step_and_keep_their_provider() {
+        let codex_home = TempDir::new().expect("temp home");
+        let mut widget = widget(codex_home.path().to_path_buf());
+
+        press(&mut widget, KeyCode::Char('3'));
+
+        assert_eq!(
+            widget.selection,
+            Some(DefaultsSelection {
+                model_provider: Some("ollama".to_string()),
+                model: "oss-small".to_string(),
+                effort: None,
+            })
+        );
+        assert_eq!(
+            written_config(&codex_home),
+            "model_provider = \"ollama\"\nmodel = \"oss-small\"\n"
+        );
+    }
+
+    #[test]
+    fn a_catalog_provider_is_removed_for_presets_it_does_not_serve() {
+        let codex_home = TempDir::new().expect("temp home");
+        std::fs::write(
+            codex_home.path().join(CONFIG_TOML_FILE),
+            "model_provider = \"gateway\"\n",
+        )
+        .expect("write config");
+        let mut widget = widget(codex_home.path().to_path_buf());
+        widget.provider_has_catalog = true;
+
+        press(&mut widget, KeyCode::Enter);
+        press(&mut widget, KeyCode::Enter);
+
+        assert_eq!(
+            written_config(&codex_home),
+            "model = \"gpt-5.1-codex-max\"\nmodel_reasoning_effort = \"high\"\n"
+        );
+    }
+
+    #[test]
+    fn a_failed_save_leaves_the_step_in_progress() {
+        let dir = TempDir::new().expect("temp dir");
+        let not_a_dir = dir.path().join("codex-home");
+        std::fs::write(&not_a_dir, "").expect("write file");
+        let mut widget = widget(not_a_dir);
+
+        press(&mut widget, KeyCode::Char('3'));
+
+        assert_eq!(widget.selection, None);
+        assert_eq!(widget.get_step_state(), StepState::InProgress);
+        assert!(
+            widget
+                .error
+                .as_deref()
+                .is_some_and(|error| error.starts_with("Failed to save oss-small")),
+            "{:?}",
+            widget.error
+        );
+    }
+}
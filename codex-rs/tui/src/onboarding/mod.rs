@@ -1,4 +1,5 @@
 mod auth;
+mod choose_defaults;
 pub mod onboarding_screen;
 mod trust_directory;
 pub use trust_directory::TrustDirectorySelection;
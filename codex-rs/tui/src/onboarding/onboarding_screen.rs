@@ -13,10 +13,12 @@ use ratatui::widgets::WidgetRef;
 
 use codex_app_server_protocol::AuthMode;
 use codex_protocol::config_types::ForcedLoginMethod;
+use codex_protocol::openai_models::ModelPreset;
 
 use crate::LoginStatus;
 use crate::onboarding::auth::AuthModeWidget;
 use crate::onboarding::auth::SignInState;
+use crate::onboarding::choose_defaults::ChooseDefaultsWidget;
 use crate::onboarding::trust_directory::TrustDirectorySelection;
 use crate::onboarding::trust_directory::TrustDirectoryWidget;
 use crate::onboarding::welcome::WelcomeWidget;
@@ -31,6 +33,7 @@ use std::sync::RwLock;
 enum Step {
     Welcome(WelcomeWidget),
     Auth(AuthModeWidget),
+    ChooseDefaults(ChooseDefaultsWidget),
     TrustDirectory(TrustDirectoryWidget),
 }
 
@@ -60,13 +63,18 @@ pub(crate) struct OnboardingScreen {
 pub(crate) struct OnboardingScreenArgs {
     pub show_trust_screen: bool,
     pub show_login_screen: bool,
+    pub show_defaults_screen: bool,
     pub login_status: LoginStatus,
     pub auth_manager: Arc<AuthManager>,
+    /// The loaded catalog's picker presets, offered by the defaults step.
+    pub default_presets: Vec<ModelPreset>,
     pub config: Config,
 }
 
 pub(crate) struct OnboardingResult {
     pub directory_trust_decision: Option<TrustDirectorySelection>,
+    /// Whether a default model was written to config during onboarding.
+    pub defaults_chosen: bool,
     pub should_exit: bool,
 }
 
@@ -75,8 +83,10 @@ impl OnboardingScreen {
         let OnboardingScreenArgs {
             show_trust_screen,
             show_login_screen,
+            show_defaults_screen,
             login_status,
             auth_manager,
+            default_presets,
             config,
         } = args;
         let cwd = config.cwd.clone();
@@ -109,6 +119,13 @@ impl OnboardingScreen {
                 animations_enabled: config.animations,
             }))
         }
+        if show_defaults_screen {
+            steps.push(Step::ChooseDefaults(ChooseDefaultsWidget::new(
+                codex_home.clone(),
+                config.model_provider.model_catalog.is_some(),
+                default_presets,
+            )));
+        }
         let is_git_repo = get_git_repo_root(&cwd).is_some();
         let highlighted = if is_git_repo {
             TrustDirectorySelection::Trust
@@ -192,6 +209,18 @@ impl OnboardingScreen {
             .flatten()
     }
 
+    fn defaults_chosen(&self) -> bool {
+        self.steps.iter().any(|step| {
+            matches!(
+                step,
+                Step::ChooseDefaults(ChooseDefaultsWidget {
+                    selection: Some(_),
+                    ..
+                })
+            )
+        })
+    }
+
     pub fn should_exit(&self) -> bool {
         self.should_exit
     }
@@ -334,6 +363,7 @@ impl KeyboardHandler for Step {
         match self {
             Step::Welcome(widget) => widget.handle_key_event(key_event),
             Step::Auth(widget) => widget.handle_key_event(key_event),
+            Step::ChooseDefaults(widget) => widget.handle_key_event(key_event),
             Step::TrustDirectory(widget) => widget.handle_key_event(key_event),
         }
     }
@@ -342,6 +372,7 @@ impl KeyboardHandler for Step {
         match self {
             Step::Welcome(_) => {}
             Step::Auth(widget) => widget.handle_paste(pasted),
+            Step::ChooseDefaults(_) => {}
             Step::TrustDirectory(widget) => widget.handle_paste(pasted),
         }
     }
@@ -352,6 +383,7 @@ impl StepStateProvider for Step {
         match self {
             Step::Welcome(w) => w.get_step_state(),
             Step::Auth(w) => w.get_step_state(),
+            Step::ChooseDefaults(w) => w.get_step_state(),
             Step::TrustDirectory(w) => w.get_step_state(),
         }
     }
@@ -366,6 +398,9 @@ impl WidgetRef for Step {
             Step::Auth(widget) => {
                 widget.render_ref(area, buf);
             }
+            Step::ChooseDefaults(widget) => {
+                widget.render_ref(area, buf);
+            }
             Step::TrustDirectory(widget) => {
                 widget.render_ref(area, buf);
             }
@@ -439,6 +474,7 @@ pub(crate) async fn run_onboarding_app(
     }
     Ok(OnboardingResult {
         directory_trust_decision: onboarding_screen.directory_trust_decision(),
+        defaults_chosen: onboarding_screen.defaults_chosen(),
         should_exit: onboarding_screen.should_exit(),
     })
 }
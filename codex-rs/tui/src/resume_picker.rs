@@ -1,3 +1,4 @@
+use std::collections::HashMap;
 use std::collections::HashSet;
 use std::path::Path;
 use std::path::PathBuf;
@@ -10,6 +11,9 @@ use codex_core::ConversationsPage;
 use codex_core::Cursor;
 use codex_core::INTERACTIVE_SESSION_SOURCES;
 use codex_core::RolloutRecorder;
+use codex_core::config::Config;
+use codex_core::models_manager::catalog::PresetCatalog;
+use codex_core::models_manager::manager::ModelsManager;
 use codex_core::path_utils;
 use codex_protocol::items::TurnItem;
 use color_eyre::eyre::Result;
@@ -34,6 +38,7 @@ use crate::tui::FrameRequester;
 use crate::tui::Tui;
 use crate::tui::TuiEvent;
 use codex_protocol::models::ResponseItem;
+use codex_protocol::protocol::SessionCatalog;
 use codex_protocol::protocol::SessionMetaLine;
 
 const PAGE_SIZE: usize = 25;
@@ -62,6 +67,8 @@ enum BackgroundEvent {
         request_token: usize,
         search_token: Option<usize>,
         page: std::io::Result<ConversationsPage>,
+        /// Display names for the page's model slugs, where the catalog knows them.
+        model_names: HashMap<String, String>,
     },
 }
 
@@ -70,14 +77,15 @@ enum BackgroundEvent {
 /// time (e.g., "5 seconds ago"), and the absolute path.
 pub async fn run_resume_picker(
     tui: &mut Tui,
-    codex_home: &Path,
-    default_provider: &str,
+    config: &Config,
+    models_manager: Arc<ModelsManager>,
     show_all: bool,
 ) -> Result<ResumeSelection> {
     let alt = AltScreenGuard::enter(tui);
     let (bg_tx, bg_rx) = mpsc::unbounded_channel();
 
-    let default_provider = default_provider.to_string();
+    let default_provider = config.model_provider_id.clone();
+    let loader_config = Arc::new(config.clone());
     let filter_cwd = if show_all {
         None
     } else {
@@ -87,6 +95,8 @@ pub async fn run_resume_picker(
     let loader_tx = bg_tx.clone();
     let page_loader: PageLoader = Arc::new(move |request: PageLoadRequest| {
         let tx = loader_tx.clone();
+        let models_manager = Arc::clone(&models_manager);
+        let config = Arc::clone(&loader_config);
         tokio::spawn(async move {
             let provider_filter = vec![request.default_provider.clone()];
             let page = RolloutRecorder::list_conversations(
@@ -98,16 +108,21 @@ pub async fn run_resume_picker(
                 request.default_provider.as_str(),
             )
             .await;
+            let model_names = match &page {
+                Ok(page) => model_display_names(&models_manager, &config, &page.items),
+                Err(_) => HashMap::new(),
+            };
             let _ = tx.send(BackgroundEvent::PageLoaded {
                 request_token: request.request_token,
                 search_token: request.search_token,
                 page,
+                model_names,
             });
         });
     });
 
     let mut state = PickerState::new(
-        codex_home.to_path_buf(),
+        config.codex_home.clone(),
         alt.tui.frame_requester(),
         page_loader,
         default_provider.clone(),
@@ -134,7 +149,7 @@ pub async fn run_resume_picker(
                     }
                     TuiEvent::Draw => {
                         if let Ok(size) = alt.tui.terminal.size() {
-                            let list_height = size.height.saturating_sub(4) as usize;
+                            let list_height = size.height.saturating_sub(5) as usize;
                             state.update_view_rows(list_height);
                             state.ensure_minimum_rows_for_view(list_height);
                         }
@@ -249,6 +264,10 @@ struct Row {
     updated_at: Option<DateTime<Utc>>,
     cwd: Option<PathBuf>,
     git_branch: Option<String>,
+    catalog: Option<SessionCatalog>,
+    /// Display name of the session's model, or its slug when the catalog
+    /// does not know it.
+    model: Option<String>,
 }
 
 impl PickerState {
@@ -390,6 +409,7 @@ impl PickerState {
                 request_token,
                 search_token,
                 page,
+                model_names,
             } => {
                 let pending = match self.pagination.loading {
                     LoadingState::Pending(pending) => pending,
@@ -400,7 +420,7 @@ impl PickerState {
                 }
                 self.pagination.loading = LoadingState::Idle;
                 let page = page.map_err(color_eyre::Report::from)?;
-                self.ingest_page(page);
+                self.ingest_page(page, &model_names);
                 let completed_token = pending.search_token.or(search_token);
                 self.continue_search_if_token_matches(completed_token);
             }
@@ -415,7 +435,7 @@ impl PickerState {
         self.pagination.loading = LoadingState::Idle;
     }
 
-    fn ingest_page(&mut self, page: ConversationsPage) {
+    fn ingest_page(&mut self, page: ConversationsPage, model_names: &HashMap<String, String>) {
         if let Some(cursor) = page.next_cursor.clone() {
             self.pagination.next_cursor = Some(cursor);
         } else {
@@ -429,7 +449,7 @@ impl PickerState {
             self.pagination.reached_scan_cap = true;
         }
 
-        let rows = rows_from_items(page.items);
+        let rows = rows_from_items(page.items, model_names);
         for row in rows {
             if self.seen_paths.insert(row.path.clone()) {
                 self.all_rows.push(row);
@@ -627,8 +647,37 @@ impl PickerState {
     }
 }
 
-fn rows_from_items(items: Vec<ConversationItem>) -> Vec<Row> {
-    items.into_iter().map(|item| head_to_row(&item)).collect()
+fn rows_from_items(
+    items: Vec<ConversationItem>,
+    model_names: &HashMap<String, String>,
+) -> Vec<Row> {
+    items
+        .into_iter()
+        .map(|item| {
+            let mut row = head_to_row(&item);
+            if let Some(name) = row.model.as_ref().and_then(|slug| model_names.get(slug)) {
+                row.model = Some(name.clone());
+            }
+            row
+        })
+        .collect()
+}
+
+/// Resolve every model slug on a page from the catalog already loaded.
+fn model_display_names(
+    models_manager: &ModelsManager,
+    config: &Config,
+    items: &[ConversationItem],
+) -> HashMap<String, String> {
+    let slugs: Vec<&str> = items
+        .iter()
+        .filter_map(|item| item.model.as_deref())
+        .collect();
+    PresetCatalog::new(models_manager, config)
+        .find_many(&slugs)
+        .into_iter()
+        .map(|(slug, preset)| (slug.to_string(), preset.display_name))
+        .collect()
 }
 
 fn head_to_row(item: &ConversationItem) -> Row {
@@ -643,7 +692,7 @@ fn head_to_row(item: &ConversationItem) -> Row {
         .and_then(parse_timestamp_str)
         .or(created_at);
 
-    let (cwd, git_branch) = extract_session_meta_from_head(&item.head);
+    let (cwd, git_branch, catalog) = extract_session_meta_from_head(&item.head);
     let preview = preview_from_head(&item.head)
         .map(|s| s.trim().to_string())
         .filter(|s| !s.is_empty())
@@ -656,18 +705,22 @@ fn head_to_row(item: &ConversationItem) -> Row {
         updated_at,
         cwd,
         git_branch,
+        catalog,
+        model: item.model.clone(),
     }
 }
 
-fn extract_session_meta_from_head(head: &[serde_json::Value]) -> (Option<PathBuf>, Option<String>) {
+fn extract_session_meta_from_head(
+    head: &[serde_json::Value],
+) -> (Option<PathBuf>, Option<String>, Option<SessionCatalog>) {
     for value in head {
         if let Ok(meta_line) = serde_json::from_value::<SessionMetaLine>(value.clone()) {
             let cwd = Some(meta_line.meta.cwd);
             let git_branch = meta_line.git.and_then(|git| git.branch);
-            return (cwd, git_branch);
+            return (cwd, git_branch, meta_line.meta.model_catalog);
         }
     }
-    (None, None)
+    (None, None, None)
 }
 
 fn paths_match(a: &Path, b: &Path) -> bool {
@@ -708,11 +761,12 @@ fn draw_picker(tui: &mut Tui, state: &PickerState) -> std::io::Result<()> {
     let height = tui.terminal.size()?.height;
     tui.draw(height, |frame| {
         let area = frame.area();
-        let [header, search, columns, list, hint] = Layout::vertical([
+        let [header, search, columns, list, detail, hint] = Layout::vertical([
+            Constraint::Length(1),
             Constraint::Length(1),
             Constraint::Length(1),
+            Constraint::Min(area.height.saturating_sub(5)),
             Constraint::Length(1),
-            Constraint::Min(area.height.saturating_sub(4)),
             Constraint::Length(1),
         ])
         .areas(area);
@@ -736,6 +790,7 @@ fn draw_picker(tui: &mut Tui, state: &PickerState) -> std::io::Result<()> {
         // Column headers and list
         render_column_headers(frame, columns, &metrics);
         render_list(frame, list, state, &metrics);
+        frame.render_widget_ref(selected_detail_line(state), detail);
 
         // Hint line
         let hint_line: Line = vec![
@@ -874,6 +929,40 @@ fn render_list(
     }
 }
 
+/// Details of the selected session that don't fit in the table.
+fn selected_detail_line(state: &PickerState) -> Line<'static> {
+    let Some(row) = state.filtered_rows.get(state.selected) else {
+        return Line::default();
+    };
+    let catalog = row
+        .catalog
+        .as_ref()
+        .map_or_else(|| "not recorded".to_string(), format_catalog);
+    let model = row
+        .model
+        .clone()
+        .unwrap_or_else(|| "not recorded".to_string());
+    vec![
+        "  Model: ".dim(),
+        model.dim(),
+        "  Model catalog: ".dim(),
+        catalog.dim(),
+    ]
+    .into()
+}
+
+/// `live, revision abc123, fetched 2 hours ago`.
+fn format_catalog(catalog: &SessionCatalog) -> String {
+    let mut parts = vec![catalog.source.to_string()];
+    if let Some(revision) = &catalog.revision {
+        parts.push(format!("revision {revision}"));
+    }
+    if let Some(fetched_at) = catalog.fetched_at.as_deref().and_then(parse_timestamp_str) {
+        parts.push(format!("fetched {}", human_time_ago(fetched_at)));
+    }
+    parts.join(", ")
+}
+
 fn render_empty_state_line(state: &PickerState) -> Line<'static> {
     if !state.query.is_empty() {
         if state.search_state.is_active()
@@ -1083,6 +1172,7 @@ mod tests {
             head: head_with_ts_and_user_text(ts, &[preview]),
             created_at: Some(ts.to_string()),
             updated_at: Some(ts.to_string()),
+            model: None,
         }
     }
 
@@ -1149,14 +1239,16 @@ mod tests {
             head: head_with_ts_and_user_text("2025-01-01T00:00:00Z", &["A"]),
             created_at: Some("2025-01-01T00:00:00Z".into()),
             updated_at: Some("2025-01-01T00:00:00Z".into()),
+            model: None,
         };
         let b = ConversationItem {
             path: PathBuf::from("/tmp/b.jsonl"),
             head: head_with_ts_and_user_text("2025-01-02T00:00:00Z", &["B"]),
             created_at: Some("2025-01-02T00:00:00Z".into()),
             updated_at: Some("2025-01-02T00:00:00Z".into()),
+            model: None,
         };
-        let rows = rows_from_items(vec![a, b]);
+        let rows = rows_from_items(vec![a, b], &HashMap::new());
         assert_eq!(rows.len(), 2);
         // Preserve the given order even if timestamps differ; backend already provides newest-first.
         assert!(rows[0].preview.contains('A'));
@@ -1171,6 +1263,7 @@ mod tests {
             head,
             created_at: Some("2025-01-01T00:00:00Z".into()),
             updated_at: Some("2025-01-01T01:00:00Z".into()),
+            model: None,
         };
 
         let row = head_to_row(&item);
@@ -1185,6 +1278,77 @@ mod tests {
         assert_eq!(row.updated_at, Some(expected_updated));
     }
 
+    #[test]
+    fn selected_detail_shows_the_recorded_catalog() {
+        let meta = |model_catalog: serde_json::Value| {
+            let mut meta = json!({
+                "id": "67e55044-10b1-426f-9247-bb680e5fe0c8",
+                "timestamp": "2025-01-01T00:00:00Z",
+                "cwd": "/tmp/project",
+                "originator": "codex_cli_rs",
+                "cli_version": "0.0.0",
+                "instructions": null,
+                "source": "cli",
+                "model_provider": "openai",
+            });
+            if !model_catalog.is_null() {
+                meta["model_catalog"] = model_catalog;
+            }
+            meta
+        };
+        let with_catalog = ConversationItem {
+            path: PathBuf::from("/tmp/a.jsonl"),
+            head: vec![meta(json!({
+                "revision": "abc123",
+                "source": "disk_cache",
+            }))],
+            created_at: Some("2025-01-01T00:00:00Z".into()),
+            updated_at: Some("2025-01-01T00:00:00Z".into()),
+            model: Some("gpt-5.1-codex".into()),
+        };
+        // Rollouts written before the catalog was recorded.
+        let without_catalog = ConversationItem {
+            path: PathBuf::from("/tmp/b.jsonl"),
+            head: vec![meta(serde_json::Value::Null)],
+            created_at: Some("2025-01-01T00:00:00Z".into()),
+            updated_at: Some("2025-01-01T00:00:00Z".into()),
+            model: Some("retired-model".into()),
+        };
+
+        let loader: PageLoader = Arc::new(|_| {});
+        let mut state = PickerState::new(
+            PathBuf::from("/tmp"),
+            FrameRequester::test_dummy(),
+            loader,
+            String::from("openai"),
+            true,
+            None,
+        );
+        // Only models the catalog knows get a display name; the rest keep
+        // their slug.
+        let model_names =
+            HashMap::from([("gpt-5.1-codex".to_string(), "GPT-5.1 Codex".to_string())]);
+        state.filtered_rows = rows_from_items(vec![with_catalog, without_catalog], &model_names);
+
+        let detail = |state: &PickerState| {
+            selected_detail_line(state)
+                .spans
+                .iter()
+                .map(|span| span.content.as_ref())
+                .collect::<String>()
+        };
+        state.selected = 0;
+        assert_eq!(
+            detail(&state),
+            "  Model: GPT-5.1 Codex  Model catalog: disk cache, revision abc123"
+        );
+        state.selected = 1;
+        assert_eq!(
+            detail(&state),
+            "  Model: retired-model  Model catalog: not recorded"
+        );
+    }
+
     #[test]
     fn resume_table_snapshot() {
         use crate::custom_terminal::Terminal;
@@ -1211,6 +1375,8 @@ mod tests {
                 updated_at: Some(now - Duration::seconds(42)),
                 cwd: None,
                 git_branch: None,
+                catalog: None,
+                model: None,
             },
             Row {
                 path: PathBuf::from("/tmp/b.jsonl"),
@@ -1219,6 +1385,8 @@ mod tests {
                 updated_at: Some(now - Duration::minutes(35)),
                 cwd: None,
                 git_branch: None,
+                catalog: None,
+                model: None,
             },
             Row {
                 path: PathBuf::from("/tmp/c.jsonl"),
@@ -1227,6 +1395,8 @@ mod tests {
                 updated_at: Some(now - Duration::hours(2)),
                 cwd: None,
                 git_branch: None,
+                catalog: None,
+                model: None,
             },
         ];
         state.all_rows = rows.clone();
@@ -1362,7 +1532,7 @@ mod tests {
         .await
         .expect("list conversations");
 
-        let rows = rows_from_items(page.items);
+        let rows = rows_from_items(page.items, &HashMap::new());
         state.all_rows = rows.clone();
         state.filtered_rows = rows;
         state.view_rows = Some(4);
@@ -1432,40 +1602,49 @@ mod tests {
         );
 
         state.reset_pagination();
-        state.ingest_page(page(
-            vec![
-                make_item("/tmp/a.jsonl", "2025-01-03T00:00:00Z", "third"),
-                make_item("/tmp/b.jsonl", "2025-01-02T00:00:00Z", "second"),
-            ],
-            Some(cursor_from_str(
-                "2025-01-02T00-00-00|00000000-0000-0000-0000-000000000000",
-            )),
-            2,
-            false,
-        ));
-
-        state.ingest_page(page(
-            vec![
-                make_item("/tmp/a.jsonl", "2025-01-03T00:00:00Z", "duplicate"),
-                make_item("/tmp/c.jsonl", "2025-01-01T00:00:00Z", "first"),
-            ],
-            Some(cursor_from_str(
-                "2025-01-01T00-00-00|00000000-0000-0000-0000-000000000001",
-            )),
-            2,
-            false,
-        ));
-
-        state.ingest_page(page(
-            vec![make_item(
-                "/tmp/d.jsonl",
-                "2024-12-31T23:00:00Z",
-                "very old",
-            )],
-            None,
-            1,
-            false,
-        ));
+        state.ingest_page(
+            page(
+                vec![
+                    make_item("/tmp/a.jsonl", "2025-01-03T00:00:00Z", "third"),
+                    make_item("/tmp/b.jsonl", "2025-01-02T00:00:00Z", "second"),
+                ],
+                Some(cursor_from_str(
+                    "2025-01-02T00-00-00|00000000-0000-0000-0000-000000000000",
+                )),
+                2,
+                false,
+            ),
+            &HashMap::new(),
+        );
+
+        state.ingest_page(
+            page(
+                vec![
+                    make_item("/tmp/a.jsonl", "2025-01-03T00:00:00Z", "duplicate"),
+                    make_item("/tmp/c.jsonl", "2025-01-01T00:00:00Z", "first"),
+                ],
+                Some(cursor_from_str(
+                    "2025-01-01T00-00-00|00000000-0000-0000-0000-000000000001",
+                )),
+                2,
+                false,
+            ),
+            &HashMap::new(),
+        );
+
+        state.ingest_page(
+            page(
+                vec![make_item(
+                    "/tmp/d.jsonl",
+                    "2024-12-31T23:00:00Z",
+                    "very old",
+                )],
+                None,
+                1,
+                false,
+            ),
+            &HashMap::new(),
+        );
 
         let previews: Vec<_> = state
             .filtered_rows
@@ -1499,17 +1678,20 @@ mod tests {
             None,
         );
         state.reset_pagination();
-        state.ingest_page(page(
-            vec![
-                make_item("/tmp/a.jsonl", "2025-01-01T00:00:00Z", "one"),
-                make_item("/tmp/b.jsonl", "2025-01-02T00:00:00Z", "two"),
-            ],
-            Some(cursor_from_str(
-                "2025-01-03T00-00-00|00000000-0000-0000-0000-000000000000",
-            )),
-            2,
-            false,
-        ));
+        state.ingest_page(
+            page(
+                vec![
+                    make_item("/tmp/a.jsonl", "2025-01-01T00:00:00Z", "one"),
+                    make_item("/tmp/b.jsonl", "2025-01-02T00:00:00Z", "two"),
+                ],
+                Some(cursor_from_str(
+                    "2025-01-03T00-00-00|00000000-0000-0000-0000-000000000000",
+                )),
+                2,
+                false,
+            ),
+            &HashMap::new(),
+        );
 
         assert!(recorded_requests.lock().unwrap().is_empty());
         state.ensure_minimum_rows_for_view(10);
@@ -1539,7 +1721,7 @@ mod tests {
         }
 
         state.reset_pagination();
-        state.ingest_page(page(items, None, 20, false));
+        state.ingest_page(page(items, None, 20, false), &HashMap::new());
         state.update_view_rows(5);
 
         assert_eq!(state.selected, 0);
@@ -1583,7 +1765,7 @@ mod tests {
         }
 
         state.reset_pagination();
-        state.ingest_page(page(items, None, 10, false));
+        state.ingest_page(page(items, None, 10, false), &HashMap::new());
         state.update_view_rows(5);
 
         state.selected = state.filtered_rows.len().saturating_sub(1);
@@ -1618,18 +1800,21 @@ mod tests {
             None,
         );
         state.reset_pagination();
-        state.ingest_page(page(
-            vec![make_item(
-                "/tmp/start.jsonl",
-                "2025-01-01T00:00:00Z",
-                "alpha",
-            )],
-            Some(cursor_from_str(
-                "2025-01-02T00-00-00|00000000-0000-0000-0000-000000000000",
-            )),
-            1,
-            false,
-        ));
+        state.ingest_page(
+            page(
+                vec![make_item(
+                    "/tmp/start.jsonl",
+                    "2025-01-01T00:00:00Z",
+                    "alpha",
+                )],
+                Some(cursor_from_str(
+                    "2025-01-02T00-00-00|00000000-0000-0000-0000-000000000000",
+                )),
+                1,
+                false,
+            ),
+            &HashMap::new(),
+        );
         recorded_requests.lock().unwrap().clear();
 
         state.set_query("target".to_string());
@@ -1651,6 +1836,7 @@ mod tests {
                     5,
                     false,
                 )),
+                model_names: HashMap::new(),
             })
             .unwrap();
 
@@ -1678,6 +1864,7 @@ mod tests {
                     7,
                     false,
                 )),
+                model_names: HashMap::new(),
             })
             .unwrap();
 
@@ -1697,6 +1884,7 @@ mod tests {
                 request_token: second_request.request_token,
                 search_token: second_request.search_token,
                 page: Ok(page(Vec::new(), None, 0, false)),
+                model_names: HashMap::new(),
             })
             .unwrap();
         assert_eq!(recorded_requests.lock().unwrap().len(), 1);
@@ -1706,6 +1894,7 @@ mod tests {
                 request_token: active_request.request_token,
                 search_token: active_request.search_token,
                 page: Ok(page(Vec::new(), None, 3, true)),
+                model_names: HashMap::new(),
             })
             .unwrap();
 
@@ -7,15 +7,20 @@ use chrono::DateTime;
 use chrono::Local;
 use codex_common::create_config_summary_entries;
 use codex_core::config::Config;
+use codex_core::models_manager::catalog::CatalogSnapshot;
 use codex_core::protocol::NetworkAccess;
 use codex_core::protocol::SandboxPolicy;
 use codex_core::protocol::TokenUsage;
 use codex_core::protocol::TokenUsageInfo;
 use codex_protocol::ConversationId;
 use codex_protocol::account::PlanType;
+use codex_protocol::openai_models::EffortSelection;
+use codex_protocol::openai_models::ModelPricing;
+use codex_protocol::openai_models::ReasoningEffort;
 use ratatui::prelude::*;
 use ratatui::style::Stylize;
 use std::collections::BTreeSet;
+use std::collections::HashMap;
 use std::path::PathBuf;
 
 use super::account::StatusAccountDisplay;
@@ -26,6 +31,7 @@ use super::format::truncate_line_to_width;
 use super::helpers::compose_account_display;
 use super::helpers::compose_agents_summary;
 use super::helpers::compose_model_display;
+use super::helpers::format_age;
 use super::helpers::format_directory_display;
 use super::helpers::format_tokens_compact;
 use super::rate_limits::RateLimitSnapshotDisplay;
@@ -52,6 +58,163 @@ pub(crate) struct StatusTokenUsageData {
     input: i64,
     output: i64,
     context_window: Option<StatusContextWindowData>,
+    per_model: Vec<StatusModelUsageData>,
+}
+
+#[derive(Debug, Clone)]
+struct StatusModelUsageData {
+    model: String,
+    total: i64,
+    input: i64,
+    output: i64,
+    estimated_cost: Option<f64>,
+}
+
+/// The model catalog as `/status` shows it, from the session's
+/// [`CatalogSnapshot`].
+#[derive(Debug, Clone)]
+struct StatusCatalogData {
+    /// The active preset, when the model is in the catalog.
+    preset: Option<StatusPresetData>,
+    source: String,
+    last_fetch: StatusCatalogFetch,
+    cache_path: PathBuf,
+    hidden_count: usize,
+}
+
+#[derive(Debug, Clone)]
+struct StatusPresetData {
+    display_name: String,
+    model: String,
+    /// The effort and context window, where the preset has them.
+    details: Vec<String>,
+}
+
+#[derive(Debug, Clone)]
+enum StatusCatalogFetch {
+    Never,
+    Succeeded(String),
+    Failed { age: String, error: String },
+}
+
+impl StatusCatalogData {
+    fn new(
+        catalog: &CatalogSnapshot,
+        config: &Config,
+        model: &StatusModel<'_>,
+        now: DateTime<Local>,
+    ) -> Self {
+        let preset = catalog.find(model.name).map(|preset| {
+            let mut details = Vec::new();
+            if let Some(effort) = model.reasoning_effort {
+                details.push(format!("{effort} effort"));
+            }
+            if let Some(window) = preset.context_window {
+                details.push(format!("{} context", format_tokens_compact(window)));
+            }
+            StatusPresetData {
+                display_name: preset.display_name.clone(),
+                model: preset.model.clone(),
+                details,
+            }
+        });
+        let status = catalog.status();
+        let mut source = match &status.imported {
+            Some(imported) => format!(
+                "imported (read-only) from {}, revision {}, exported {} by Codex {}",
+                imported.path.display(),
+                imported.revision,
+                format_age(imported.exported_at, now),
+                imported.codex_version
+            ),
+            None => {
+                let mut source = status.source.to_string();
+                if let Some(host) = &status.host {
+                    source = format!("{source} from {host}");
+                }
+                source = format!("{source}, revision {}", catalog.revision());
+                if let Some(etag) = &status.etag {
+                    source = format!("{source} (etag {etag})");
+                }
+                source
+            }
+        };
+        if config.offline {
+            source = format!("{source}, offline");
+        }
+        let last_fetch = match (&status.last_error, status.fetched_at) {
+            (Some((failed_at, error)), _) => StatusCatalogFetch::Failed {
+                age: format_age(*failed_at, now),
+                error: error.clone(),
+            },
+            (None, Some(fetched_at)) => StatusCatalogFetch::Succeeded(format_age(fetched_at, now)),
+            (None, None) => StatusCatalogFetch::Never,
+        };
+        Self {
+            preset,
+            source,
+            last_fetch,
+            cache_path: status.cache_path.clone(),
+            hidden_count: status.hidden_count,
+        }
+    }
+
+    const LABELS: [&str; 5] = [
+        "Preset",
+        "Catalog",
+        "Last fetch",
+        "Models cache",
+        "Hidden models",
+    ];
+
+    fn lines(&self, formatter: &FieldFormatter, value_width: usize) -> Vec<Line<'static>> {
+        let mut lines = Vec::new();
+        match &self.preset {
+            Some(preset) => {
+                lines.push(formatter.line(
+                    "Preset",
+                    vec![
+                        Span::from(preset.display_name.clone()),
+                        Span::from(format!(" ({})", preset.model)).dim(),
+                    ],
+                ));
+                if !preset.details.is_empty() {
+                    lines.push(
+                        formatter.continuation(vec![Span::from(preset.details.join(", ")).dim()]),
+                    );
+                }
+            }
+            None => {
+                lines.push(formatter.line("Preset", vec![Span::from("not in the catalog").dim()]))
+            }
+        }
+        let last_fetch = match &self.last_fetch {
+            StatusCatalogFetch::Never => vec![Span::from("never").dim()],
+            StatusCatalogFetch::Succeeded(age) => vec![Span::from(format!("succeeded {age}"))],
+            StatusCatalogFetch::Failed { age, error } => vec![
+                Span::from(format!("failed {age}")).red(),
+                Span::from(format!(": {error}")).dim(),
+            ],
+        };
+        let hidden = if self.hidden_count == 0 {
+            "none".to_string()
+        } else {
+            self.hidden_count.to_string()
+        };
+        lines.extend([
+            formatter.line("Catalog", vec![Span::from(self.source.clone())]),
+            formatter.line("Last fetch", last_fetch),
+            formatter.line(
+                "Models cache",
+                vec![Span::from(format_directory_display(
+                    &self.cache_path,
+                    Some(value_width),
+                ))],
+            ),
+            formatter.line("Hidden models", vec![Span::from(hidden)]),
+        ]);
+        lines
+    }
 }
 
 #[derive(Debug)]
@@ -64,10 +227,58 @@ struct StatusHistoryCell {
     agents_summary: String,
     account: Option<StatusAccountDisplay>,
     session_id: Option<String>,
+    catalog: Option<StatusCatalogData>,
     token_usage: StatusTokenUsageData,
     rate_limits: StatusRateLimitData,
 }
 
+/// The active model as `/status` describes it: what the session runs it at
+/// and what its catalog entry says about it.
+#[derive(Default)]
+pub(crate) struct StatusModel<'a> {
+    pub(crate) name: &'a str,
+    /// The effort turns are sampled at: the configured one, or the model's
+    /// default when none is. Unset when the model takes no effort.
+    pub(crate) reasoning_effort: Option<ReasoningEffort>,
+    pub(crate) supports_reasoning_summaries: Option<bool>,
+    /// Prices by model slug, for the per-model usage estimate.
+    pub(crate) pricing: HashMap<String, ModelPricing>,
+    pub(crate) catalog: Option<&'a CatalogSnapshot>,
+}
+
+impl<'a> StatusModel<'a> {
+    /// Describes `name` as the session runs it: at the effort `config` sets,
+    /// or its preset's default, with what its `catalog` entry supports.
+    pub(crate) fn from_catalog(
+        name: &'a str,
+        config: &Config,
+        catalog: &'a CatalogSnapshot,
+    ) -> Self {
+        let preset = catalog.find(name);
+        let reasoning_effort = match preset {
+            Some(preset) if preset.supported_reasoning_efforts.is_empty() => None,
+            Some(preset) => Some(
+                config
+                    .model_reasoning_effort
+                    .unwrap_or(preset.default_reasoning_effort),
+            ),
+            None => config.model_reasoning_effort,
+        };
+        Self {
+            name,
+            reasoning_effort,
+            supports_reasoning_summaries: preset
+                .and_then(|preset| preset.supports_reasoning_summaries),
+            pricing: catalog
+                .presets()
+                .iter()
+                .filter_map(|preset| Some((preset.model.clone(), preset.pricing.clone()?)))
+                .collect(),
+            catalog: Some(catalog),
+        }
+    }
+}
+
 #[allow(clippy::too_many_arguments)]
 pub(crate) fn new_status_output(
     config: &Config,
@@ -78,7 +289,7 @@ pub(crate) fn new_status_output(
     rate_limits: Option<&RateLimitSnapshotDisplay>,
     plan_type: Option<PlanType>,
     now: DateTime<Local>,
-    model_name: &str,
+    model: StatusModel<'_>,
 ) -> CompositeHistoryCell {
     let command = PlainHistoryCell::new(vec!["/status".magenta().into()]);
     let card = StatusHistoryCell::new(
@@ -90,7 +301,7 @@ pub(crate) fn new_status_output(
         rate_limits,
         plan_type,
         now,
-        model_name,
+        model,
     );
 
     CompositeHistoryCell::new(vec![Box::new(command), Box::new(card)])
@@ -107,10 +318,33 @@ impl StatusHistoryCell {
         rate_limits: Option<&RateLimitSnapshotDisplay>,
         plan_type: Option<PlanType>,
         now: DateTime<Local>,
-        model_name: &str,
+        model: StatusModel<'_>,
     ) -> Self {
-        let config_entries = create_config_summary_entries(config, model_name);
-        let (model_name, model_details) = compose_model_display(model_name, &config_entries);
+        let catalog = model
+            .catalog
+            .map(|catalog| StatusCatalogData::new(catalog, config, &model, now));
+        let mut config_entries = create_config_summary_entries(config, model.name);
+        // Show the effort turns run at; with none configured that is the
+        // model's default, so say it was picked automatically.
+        if let Some(effective) = model.reasoning_effort
+            && let Some((_, effort)) = config_entries
+                .iter_mut()
+                .find(|(key, _)| *key == "reasoning effort")
+        {
+            *effort = match config.model_reasoning_effort {
+                Some(_) => effective.to_string(),
+                None => EffortSelection::Auto.label(effective),
+            };
+        }
+        // The summary setting has no effect on a model that emits none.
+        if model.supports_reasoning_summaries == Some(false)
+            && let Some((_, summary)) = config_entries
+                .iter_mut()
+                .find(|(key, _)| *key == "reasoning summaries")
+        {
+            *summary = "unsupported".to_string();
+        }
+        let (model_name, model_details) = compose_model_display(model.name, &config_entries);
         let approval = config_entries
             .iter()
             .find(|(k, _)| *k == "approval")
@@ -142,11 +376,30 @@ impl StatusHistoryCell {
             window,
         });
 
+        let per_model = token_info
+            .map(|info| {
+                info.per_model_usage
+                    .iter()
+                    .map(|entry| StatusModelUsageData {
+                        model: entry.model.clone(),
+                        total: entry.token_usage.blended_total(),
+                        input: entry.token_usage.non_cached_input(),
+                        output: entry.token_usage.output_tokens,
+                        estimated_cost: model
+                            .pricing
+                            .get(&entry.model)
+                            .map(|pricing| pricing.estimate_cost(&entry.token_usage)),
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+
         let token_usage = StatusTokenUsageData {
             total: total_usage.blended_total(),
             input: total_usage.non_cached_input(),
             output: total_usage.output_tokens,
             context_window,
+            per_model,
         };
         let rate_limits = compose_rate_limit_data(rate_limits, now);
 
@@ -159,6 +412,7 @@ impl StatusHistoryCell {
             agents_summary,
             account,
             session_id,
+            catalog,
             token_usage,
             rate_limits,
         }
@@ -182,6 +436,47 @@ impl StatusHistoryCell {
         ]
     }
 
+    /// The per-model breakdown is shown once the session has used more than
+    /// one model or when prices are known for the models it used.
+    fn shows_per_model_usage(&self) -> bool {
+        let per_model = &self.token_usage.per_model;
+        per_model.len() > 1 || per_model.iter().any(|entry| entry.estimated_cost.is_some())
+    }
+
+    fn per_model_usage_lines(&self, formatter: &FieldFormatter) -> Vec<Line<'static>> {
+        if !self.shows_per_model_usage() {
+            return Vec::new();
+        }
+
+        self.token_usage
+            .per_model
+            .iter()
+            .enumerate()
+            .map(|(idx, entry)| {
+                let mut spans = vec![
+                    Span::from(format!("{}: ", entry.model)),
+                    Span::from(format_tokens_compact(entry.total)),
+                    Span::from(" total "),
+                    Span::from(" (").dim(),
+                    Span::from(format_tokens_compact(entry.input)).dim(),
+                    Span::from(" input").dim(),
+                    Span::from(" + ").dim(),
+                    Span::from(format_tokens_compact(entry.output)).dim(),
+                    Span::from(" output").dim(),
+                    Span::from(")").dim(),
+                ];
+                if let Some(cost) = entry.estimated_cost {
+                    spans.push(Span::from(format!(" ~${cost:.2}")).cyan());
+                }
+                if idx == 0 {
+                    formatter.line("By model", spans)
+                } else {
+                    formatter.continuation(spans)
+                }
+            })
+            .collect()
+    }
+
     fn context_window_spans(&self) -> Option<Vec<Span<'static>>> {
         let context = self.token_usage.context_window.as_ref()?;
         let percent = context.percent_remaining;
@@ -344,7 +639,15 @@ impl HistoryCell for StatusHistoryCell {
         if self.session_id.is_some() {
             push_label(&mut labels, &mut seen, "Session");
         }
+        if self.catalog.is_some() {
+            for label in StatusCatalogData::LABELS {
+                push_label(&mut labels, &mut seen, label);
+            }
+        }
         push_label(&mut labels, &mut seen, "Token usage");
+        if self.shows_per_model_usage() {
+            push_label(&mut labels, &mut seen, "By model");
+        }
         if self.token_usage.context_window.is_some() {
             push_label(&mut labels, &mut seen, "Context window");
         }
@@ -394,10 +697,16 @@ impl HistoryCell for StatusHistoryCell {
             lines.push(formatter.line("Session", vec![Span::from(session.clone())]));
         }
 
+        if let Some(catalog) = &self.catalog {
+            lines.push(Line::from(Vec::<Span<'static>>::new()));
+            lines.extend(catalog.lines(&formatter, value_width));
+        }
+
         lines.push(Line::from(Vec::<Span<'static>>::new()));
         // Hide token usage only for ChatGPT subscribers
         if !matches!(self.account, Some(StatusAccountDisplay::ChatGpt { .. })) {
             lines.push(formatter.line("Token usage", self.token_usage_spans()));
+            lines.extend(self.per_model_usage_lines(&formatter));
         }
 
         if let Some(spans) = self.context_window_spans() {
@@ -1,23 +1,33 @@
+use super::StatusModel;
 use super::new_status_output;
 use super::rate_limit_snapshot_display;
 use crate::history_cell::HistoryCell;
 use chrono::Duration as ChronoDuration;
 use chrono::TimeZone;
 use chrono::Utc;
+use codex_common::test_catalog;
+use codex_common::test_catalog::PresetBuilderExt;
 use codex_core::AuthManager;
 use codex_core::config::Config;
 use codex_core::config::ConfigBuilder;
+use codex_core::models_manager::catalog::CatalogSnapshot;
+use codex_core::models_manager::catalog::CatalogSnapshotStatus;
+use codex_core::models_manager::catalog_export::ImportedCatalogInfo;
 use codex_core::models_manager::manager::ModelsManager;
+use codex_core::models_manager::provenance::CatalogSource;
 use codex_core::protocol::CreditsSnapshot;
+use codex_core::protocol::ModelTokenUsage;
 use codex_core::protocol::RateLimitSnapshot;
 use codex_core::protocol::RateLimitWindow;
 use codex_core::protocol::SandboxPolicy;
 use codex_core::protocol::TokenUsage;
 use codex_core::protocol::TokenUsageInfo;
 use codex_protocol::config_types::ReasoningSummary;
+use codex_protocol::openai_models::ModelPricing;
 use codex_protocol::openai_models::ReasoningEffort;
 use insta::assert_snapshot;
 use ratatui::prelude::*;
+use std::collections::HashMap;
 use std::path::PathBuf;
 use tempfile::TempDir;
 
@@ -45,6 +55,7 @@ fn token_info_for(model_slug: &str, config: &Config, usage: &TokenUsage) -> Toke
         total_token_usage: usage.clone(),
         last_token_usage: usage.clone(),
         model_context_window: context_window,
+        per_model_usage: Vec::new(),
     }
 }
 
@@ -89,6 +100,93 @@ fn reset_at_from(captured_at: &chrono::DateTime<chrono::Local>, seconds: i64) ->
         .timestamp()
 }
 
+#[tokio::test]
+async fn status_shows_auto_effort_with_the_model_default() {
+    let temp_home = TempDir::new().expect("temp home");
+    let mut config = test_config(&temp_home).await;
+    config.model = Some("gpt-5.1-codex-max".to_string());
+    config.model_provider_id = "openai".to_string();
+    config.model_reasoning_effort = None;
+
+    let auth_manager = test_auth_manager(&config);
+    let usage = TokenUsage::default();
+    let captured_at = chrono::Local
+        .with_ymd_and_hms(2024, 1, 2, 3, 4, 5)
+        .single()
+        .expect("timestamp");
+    let model_slug = ModelsManager::get_model_offline(config.model.as_deref());
+
+    let composite = new_status_output(
+        &config,
+        &auth_manager,
+        None,
+        &usage,
+        &None,
+        None,
+        None,
+        captured_at,
+        StatusModel {
+            name: &model_slug,
+            reasoning_effort: Some(ReasoningEffort::Medium),
+            ..Default::default()
+        },
+    );
+    let rendered = render_lines(&composite.display_lines(120)).join("\n");
+    assert!(
+        rendered.contains("reasoning auto (medium)"),
+        "expected the resolved auto effort; status: {rendered}"
+    );
+}
+
+#[tokio::test]
+async fn status_marks_summaries_unsupported_for_models_without_them() {
+    let temp_home = TempDir::new().expect("temp home");
+    let mut config = test_config(&temp_home).await;
+    config.model = Some("gpt-5.1-codex-max".to_string());
+    config.model_provider_id = "openai".to_string();
+    config.model_reasoning_summary = ReasoningSummary::Detailed;
+
+    let auth_manager = test_auth_manager(&config);
+    let usage = TokenUsage::default();
+    let captured_at = chrono::Local
+        .with_ymd_and_hms(2024, 1, 2, 3, 4, 5)
+        .single()
+        .expect("timestamp");
+    let model_slug = ModelsManager::get_model_offline(config.model.as_deref());
+
+    let render = |supports_summaries| {
+        let composite = new_status_output(
+            &config,
+            &auth_manager,
+            None,
+            &usage,
+            &None,
+            None,
+            None,
+            captured_at,
+            StatusModel {
+                name: &model_slug,
+                reasoning_effort: Some(ReasoningEffort::Medium),
+                supports_reasoning_summaries: supports_summaries,
+                ..Default::default()
+            },
+        );
+        render_lines(&composite.display_lines(120)).join("\n")
+    };
+
+    let unsupported = render(Some(false));
+    assert!(
+        unsupported.contains("summaries unsupported")
+            && !unsupported.contains("summaries detailed"),
+        "expected the summary setting to be marked unsupported; status: {unsupported}"
+    );
+    let unknown = render(None);
+    assert!(
+        unknown.contains("summaries detailed"),
+        "expected the configured summary setting; status: {unknown}"
+    );
+}
+
 #[tokio::test]
 async fn status_snapshot_includes_reasoning_details() {
     let temp_home = TempDir::new().expect("temp home");
@@ -150,7 +248,10 @@ async fn status_snapshot_includes_reasoning_details() {
         Some(&rate_display),
         None,
         captured_at,
-        &model_slug,
+        StatusModel {
+            name: &model_slug,
+            ..Default::default()
+        },
     );
     let mut rendered_lines = render_lines(&composite.display_lines(80));
     if cfg!(windows) {
@@ -206,7 +307,10 @@ async fn status_snapshot_includes_monthly_limit() {
         Some(&rate_display),
         None,
         captured_at,
-        &model_slug,
+        StatusModel {
+            name: &model_slug,
+            ..Default::default()
+        },
     );
     let mut rendered_lines = render_lines(&composite.display_lines(80));
     if cfg!(windows) {
@@ -250,7 +354,10 @@ async fn status_snapshot_shows_unlimited_credits() {
         Some(&rate_display),
         None,
         captured_at,
-        &model_slug,
+        StatusModel {
+            name: &model_slug,
+            ..Default::default()
+        },
     );
     let rendered = render_lines(&composite.display_lines(120));
     assert!(
@@ -293,7 +400,10 @@ async fn status_snapshot_shows_positive_credits() {
         Some(&rate_display),
         None,
         captured_at,
-        &model_slug,
+        StatusModel {
+            name: &model_slug,
+            ..Default::default()
+        },
     );
     let rendered = render_lines(&composite.display_lines(120));
     assert!(
@@ -336,7 +446,10 @@ async fn status_snapshot_hides_zero_credits() {
         Some(&rate_display),
         None,
         captured_at,
-        &model_slug,
+        StatusModel {
+            name: &model_slug,
+            ..Default::default()
+        },
     );
     let rendered = render_lines(&composite.display_lines(120));
     assert!(
@@ -377,7 +490,10 @@ async fn status_snapshot_hides_when_has_no_credits_flag() {
         Some(&rate_display),
         None,
         captured_at,
-        &model_slug,
+        StatusModel {
+            name: &model_slug,
+            ..Default::default()
+        },
     );
     let rendered = render_lines(&composite.display_lines(120));
     assert!(
@@ -418,7 +534,10 @@ async fn status_card_token_usage_excludes_cached_tokens() {
         None,
         None,
         now,
-        &model_slug,
+        StatusModel {
+            name: &model_slug,
+            ..Default::default()
+        },
     );
     let rendered = render_lines(&composite.display_lines(120));
 
@@ -474,7 +593,10 @@ async fn status_snapshot_truncates_in_narrow_terminal() {
         Some(&rate_display),
         None,
         captured_at,
-        &model_slug,
+        StatusModel {
+            name: &model_slug,
+            ..Default::default()
+        },
     );
     let mut rendered_lines = render_lines(&composite.display_lines(70));
     if cfg!(windows) {
@@ -519,7 +641,10 @@ async fn status_snapshot_shows_missing_limits_message() {
         None,
         None,
         now,
-        &model_slug,
+        StatusModel {
+            name: &model_slug,
+            ..Default::default()
+        },
     );
     let mut rendered_lines = render_lines(&composite.display_lines(80));
     if cfg!(windows) {
@@ -531,6 +656,181 @@ async fn status_snapshot_shows_missing_limits_message() {
     assert_snapshot!(sanitized);
 }
 
+fn catalog_status(now: chrono::DateTime<chrono::Local>) -> CatalogSnapshotStatus {
+    CatalogSnapshotStatus {
+        source: CatalogSource::Live,
+        host: Some("gateway.example.com".to_string()),
+        etag: None,
+        fetched_at: Some(now.with_timezone(&Utc) - ChronoDuration::minutes(5)),
+        last_error: None,
+        cache_path: PathBuf::from("/codex-home/models_cache.json"),
+        hidden_count: 3,
+        imported: None,
+    }
+}
+
+fn render_status_with_catalog(
+    config: &Config,
+    catalog: &CatalogSnapshot,
+    now: chrono::DateTime<chrono::Local>,
+) -> String {
+    let auth_manager = test_auth_manager(config);
+    let usage = TokenUsage {
+        input_tokens: 500,
+        cached_input_tokens: 0,
+        output_tokens: 250,
+        reasoning_output_tokens: 0,
+        total_tokens: 750,
+    };
+    let model_slug = ModelsManager::get_model_offline(config.model.as_deref());
+    let token_info = token_info_for(&model_slug, config, &usage);
+    let composite = new_status_output(
+        config,
+        &auth_manager,
+        Some(&token_info),
+        &usage,
+        &None,
+        None,
+        None,
+        now,
+        StatusModel::from_catalog(&model_slug, config, catalog),
+    );
+    let mut rendered_lines = render_lines(&composite.display_lines(80));
+    if cfg!(windows) {
+        for line in &mut rendered_lines {
+            *line = line.replace('\\', "/");
+        }
+    }
+    sanitize_directory(rendered_lines).join("\n")
+}
+
+#[tokio::test]
+async fn status_snapshot_shows_the_model_catalog() {
+    let temp_home = TempDir::new().expect("temp home");
+    let mut config = test_config(&temp_home).await;
+    config.model = Some("gpt-5.1-codex-max".to_string());
+    config.model_reasoning_effort = Some(ReasoningEffort::High);
+    config.model_reasoning_summary = ReasoningSummary::Detailed;
+    config.cwd = PathBuf::from("/workspace/tests");
+
+    let now = chrono::Local
+        .with_ymd_and_hms(2024, 2, 3, 4, 5, 6)
+        .single()
+        .expect("timestamp");
+    let mut preset = test_catalog::preset("gpt-5.1-codex-max")
+        .display_name("GPT-5.1 Codex Max")
+        .efforts([
+            ReasoningEffort::Low,
+            ReasoningEffort::Medium,
+            ReasoningEffort::High,
+        ])
+        .default_reasoning_effort(ReasoningEffort::Medium)
+        .build()
+        .expect("valid preset");
+    preset.context_window = Some(272_000);
+    let catalog = CatalogSnapshot::new(7, vec![preset]).with_status(catalog_status(now));
+
+    let sanitized = render_status_with_catalog(&config, &catalog, now);
+    assert_snapshot!(sanitized);
+}
+
+#[tokio::test]
+async fn status_snapshot_shows_an_offline_fallback_catalog() {
+    let temp_home = TempDir::new().expect("temp home");
+    let mut config = test_config(&temp_home).await;
+    config.model = Some("gpt-5.1-codex-max".to_string());
+    config.cwd = PathBuf::from("/workspace/tests");
+    config.offline = true;
+
+    let now = chrono::Local
+        .with_ymd_and_hms(2024, 2, 3, 4, 5, 6)
+        .single()
+        .expect("timestamp");
+    let catalog = CatalogSnapshot::new(0, Vec::new()).with_status(CatalogSnapshotStatus {
+        cache_path: PathBuf::from("/codex-home/models_cache.json"),
+        ..CatalogSnapshotStatus::default()
+    });
+
+    let sanitized = render_status_with_catalog(&config, &catalog, now);
+    assert_snapshot!(sanitized);
+}
+
+#[tokio::test]
+async fn status_catalog_shows_the_effort_the_session_runs_at() {
+    let temp_home = TempDir::new().expect("temp home");
+    let mut config = test_config(&temp_home).await;
+    config.model = Some("gpt-5.1-codex-max".to_string());
+    config.model_reasoning_effort = None;
+
+    let now = chrono::Local
+        .with_ymd_and_hms(2024, 2, 3, 4, 5, 6)
+        .single()
+        .expect("timestamp");
+    let preset = test_catalog::preset("gpt-5.1-codex-max")
+        .efforts([ReasoningEffort::Medium, ReasoningEffort::High])
+        .default_reasoning_effort(ReasoningEffort::High)
+        .build()
+        .expect("valid preset");
+    let catalog = CatalogSnapshot::new(7, vec![preset]).with_status(catalog_status(now));
+
+    let rendered = render_status_with_catalog(&config, &catalog, now);
+    assert!(rendered.contains("reasoning auto (high)"), "{rendered}");
+    assert!(rendered.contains("high effort"), "{rendered}");
+}
+
+#[tokio::test]
+async fn status_catalog_shows_the_failed_refresh() {
+    let temp_home = TempDir::new().expect("temp home");
+    let mut config = test_config(&temp_home).await;
+    config.model = Some("gpt-5.1-codex-max".to_string());
+
+    let now = chrono::Local
+        .with_ymd_and_hms(2024, 2, 3, 4, 5, 6)
+        .single()
+        .expect("timestamp");
+    let catalog = CatalogSnapshot::new(7, Vec::new()).with_status(CatalogSnapshotStatus {
+        last_error: Some((
+            now.with_timezone(&Utc) - ChronoDuration::minutes(2),
+            "gateway.example.com returned 401 Unauthorized".to_string(),
+        )),
+        ..catalog_status(now)
+    });
+
+    let rendered = render_status_with_catalog(&config, &catalog, now);
+    assert!(
+        rendered.contains("Last fetch:       failed 2m ago: gateway.example.com returned 401"),
+        "{rendered}"
+    );
+}
+
+#[tokio::test]
+async fn status_catalog_marks_an_imported_catalog() {
+    let temp_home = TempDir::new().expect("temp home");
+    let mut config = test_config(&temp_home).await;
+    config.model = Some("gpt-5.1-codex-max".to_string());
+
+    let now = chrono::Local
+        .with_ymd_and_hms(2024, 2, 3, 4, 5, 6)
+        .single()
+        .expect("timestamp");
+    let catalog = CatalogSnapshot::new(1, Vec::new()).with_status(CatalogSnapshotStatus {
+        imported: Some(ImportedCatalogInfo {
+            path: PathBuf::from("/repro/cat.json"),
+            codex_version: "0.0.0".to_string(),
+            exported_at: now.with_timezone(&Utc) - ChronoDuration::hours(3),
+            revision: 12,
+        }),
+        ..catalog_status(now)
+    });
+
+    let rendered = render_status_with_catalog(&config, &catalog, now);
+    assert!(
+        rendered.contains("Catalog:          imported (read-only) from /repro/cat.json"),
+        "{rendered}"
+    );
+    assert!(!rendered.contains("gateway.example.com"), "{rendered}");
+}
+
 #[tokio::test]
 async fn status_snapshot_includes_credits_and_limits() {
     let temp_home = TempDir::new().expect("temp home");
@@ -582,7 +882,10 @@ async fn status_snapshot_includes_credits_and_limits() {
         Some(&rate_display),
         None,
         captured_at,
-        &model_slug,
+        StatusModel {
+            name: &model_slug,
+            ..Default::default()
+        },
     );
     let mut rendered_lines = render_lines(&composite.display_lines(80));
     if cfg!(windows) {
@@ -633,7 +936,10 @@ async fn status_snapshot_shows_empty_limits_message() {
         Some(&rate_display),
         None,
         captured_at,
-        &model_slug,
+        StatusModel {
+            name: &model_slug,
+            ..Default::default()
+        },
     );
     let mut rendered_lines = render_lines(&composite.display_lines(80));
     if cfg!(windows) {
@@ -693,7 +999,10 @@ async fn status_snapshot_shows_stale_limits_message() {
         Some(&rate_display),
         None,
         now,
-        &model_slug,
+        StatusModel {
+            name: &model_slug,
+            ..Default::default()
+        },
     );
     let mut rendered_lines = render_lines(&composite.display_lines(80));
     if cfg!(windows) {
@@ -757,7 +1066,10 @@ async fn status_snapshot_cached_limits_hide_credits_without_flag() {
         Some(&rate_display),
         None,
         now,
-        &model_slug,
+        StatusModel {
+            name: &model_slug,
+            ..Default::default()
+        },
     );
     let mut rendered_lines = render_lines(&composite.display_lines(80));
     if cfg!(windows) {
@@ -801,6 +1113,7 @@ async fn status_context_window_uses_last_usage() {
         total_token_usage: total_usage.clone(),
         last_token_usage: last_usage,
         model_context_window: config.model_context_window,
+        per_model_usage: Vec::new(),
     };
     let composite = new_status_output(
         &config,
@@ -811,7 +1124,10 @@ async fn status_context_window_uses_last_usage() {
         None,
         None,
         now,
-        &model_slug,
+        StatusModel {
+            name: &model_slug,
+            ..Default::default()
+        },
     );
     let rendered_lines = render_lines(&composite.display_lines(80));
     let context_line = rendered_lines
@@ -828,3 +1144,82 @@ async fn status_context_window_uses_last_usage() {
         "context line should not use total aggregated tokens, got: {context_line}"
     );
 }
+
+#[tokio::test]
+async fn status_splits_token_usage_by_model_with_estimated_cost() {
+    let temp_home = TempDir::new().expect("temp home");
+    let mut config = test_config(&temp_home).await;
+    config.model_context_window = Some(272_000);
+
+    let auth_manager = test_auth_manager(&config);
+    let usage = |input_tokens, output_tokens| TokenUsage {
+        input_tokens,
+        cached_input_tokens: 0,
+        output_tokens,
+        reasoning_output_tokens: 0,
+        total_tokens: input_tokens + output_tokens,
+    };
+    let total_usage = usage(250_000, 15_000);
+    let token_info = TokenUsageInfo {
+        total_token_usage: total_usage.clone(),
+        last_token_usage: usage(50_000, 5_000),
+        model_context_window: config.model_context_window,
+        per_model_usage: vec![
+            ModelTokenUsage {
+                model: "gpt-5.1".to_string(),
+                token_usage: usage(200_000, 10_000),
+            },
+            ModelTokenUsage {
+                model: "gpt-5.1-codex".to_string(),
+                token_usage: usage(50_000, 5_000),
+            },
+        ],
+    };
+    let model_pricing = HashMap::from([(
+        "gpt-5.1".to_string(),
+        ModelPricing {
+            input_per_million: 1.25,
+            cached_input_per_million: None,
+            output_per_million: 10.0,
+        },
+    )]);
+
+    let now = chrono::Local
+        .with_ymd_and_hms(2024, 6, 1, 12, 0, 0)
+        .single()
+        .expect("timestamp");
+
+    let composite = new_status_output(
+        &config,
+        &auth_manager,
+        Some(&token_info),
+        &total_usage,
+        &None,
+        None,
+        None,
+        now,
+        StatusModel {
+            name: "gpt-5.1-codex",
+            pricing: model_pricing,
+            ..Default::default()
+        },
+    );
+    let rendered_lines = render_lines(&composite.display_lines(100));
+    let first_model_line = rendered_lines
+        .iter()
+        .find(|line| line.contains("By model"))
+        .expect("per-model line");
+    let second_model_line = rendered_lines
+        .iter()
+        .find(|line| line.contains("gpt-5.1-codex: "))
+        .expect("second per-model line");
+
+    assert!(
+        first_model_line.contains("gpt-5.1: 210K total") && first_model_line.contains("~$0.35"),
+        "expected priced usage for the first model, got: {first_model_line}"
+    );
+    assert!(
+        second_model_line.contains("55K total") && !second_model_line.contains('$'),
+        "expected unpriced usage for the second model, got: {second_model_line}"
+    );
+}
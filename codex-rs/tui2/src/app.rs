@@ -40,6 +40,7 @@ use codex_core::config::Config;
 use codex_core::config::edit::ConfigEditsBuilder;
 #[cfg(target_os = "windows")]
 use codex_core::features::Feature;
+use codex_core::models_manager::client_version::ClientVersionNotice;
 use codex_core::models_manager::manager::ModelsManager;
 use codex_core::models_manager::model_presets::HIDE_GPT_5_1_CODEX_MAX_MIGRATION_PROMPT_CONFIG;
 use codex_core::models_manager::model_presets::HIDE_GPT5_1_MIGRATION_PROMPT_CONFIG;
@@ -52,6 +53,7 @@ use codex_core::protocol::SkillErrorInfo;
 use codex_core::protocol::TokenUsage;
 use codex_core::terminal::terminal_info;
 use codex_protocol::ConversationId;
+use codex_protocol::openai_models::EffortSelection;
 use codex_protocol::openai_models::ModelPreset;
 use codex_protocol::openai_models::ModelUpgrade;
 use codex_protocol::openai_models::ReasoningEffort as ReasoningEffortConfig;
@@ -79,6 +81,7 @@ use std::thread;
 use std::time::Duration;
 use tokio::select;
 use tokio::sync::mpsc::unbounded_channel;
+use tokio::task::JoinHandle;
 
 #[cfg(not(debug_assertions))]
 use crate::history_cell::UpdateAvailableHistoryCell;
@@ -205,6 +208,32 @@ fn migration_prompt_hidden(config: &Config, migration_config_key: &str) -> bool
     }
 }
 
+/// Announce a catalog that recommends a newer Codex, at startup and after
+/// each catalog refresh, once per minimum.
+fn spawn_client_version_watcher(
+    models_manager: Arc<ModelsManager>,
+    config: &Config,
+    app_event_tx: AppEventSender,
+) -> JoinHandle<()> {
+    let config = config.clone();
+    let mut revisions = models_manager.subscribe_catalog_revision();
+    tokio::spawn(async move {
+        let mut announced: Option<ClientVersionNotice> = None;
+        loop {
+            if let Some(notice) = models_manager.client_version_notice(&config).await
+                && announced.as_ref() != Some(&notice)
+            {
+                announced = Some(notice.clone());
+                app_event_tx.send(AppEvent::ClientVersionBelowMinimum { notice });
+            }
+            if revisions.changed().await.is_err() {
+                break;
+            }
+            revisions.borrow_and_update();
+        }
+    })
+}
+
 async fn handle_model_migration_prompt_if_needed(
     tui: &mut tui::Tui,
     config: &mut Config,
@@ -393,6 +422,11 @@ impl App {
             auth_manager.clone(),
             SessionSource::Cli,
         ));
+        spawn_client_version_watcher(
+            conversation_manager.get_models_manager(),
+            &config,
+            app_event_tx.clone(),
+        );
         let mut model = conversation_manager
             .get_models_manager()
             .get_model(&config.model, &config)
@@ -1500,6 +1534,9 @@ impl App {
             AppEvent::OpenAllModelsPopup { models } => {
                 self.chat_widget.open_all_models_popup(models);
             }
+            AppEvent::ClientVersionBelowMinimum { notice } => {
+                self.chat_widget.on_client_version_notice(&notice);
+            }
             AppEvent::OpenFullAccessConfirmation { preset } => {
                 self.chat_widget.open_full_access_confirmation(preset);
             }
@@ -1560,6 +1597,7 @@ impl App {
                                         approval_policy: Some(preset.approval),
                                         sandbox_policy: Some(preset.sandbox.clone()),
                                         model: None,
+                                        model_provider: None,
                                         effort: None,
                                         summary: None,
                                     },
@@ -1594,7 +1632,10 @@ impl App {
                 let profile = self.active_profile.as_deref();
                 match ConfigEditsBuilder::new(&self.config.codex_home)
                     .with_profile(profile)
-                    .set_model(Some(model.as_str()), effort)
+                    .set_model_with_effort_selection(
+                        Some(model.as_str()),
+                        Some(EffortSelection::from(effort)),
+                    )
                     .apply()
                     .await
                 {
@@ -1816,12 +1857,9 @@ impl App {
 
     fn reasoning_label(reasoning_effort: Option<ReasoningEffortConfig>) -> &'static str {
         match reasoning_effort {
-            Some(ReasoningEffortConfig::Minimal) => "minimal",
-            Some(ReasoningEffortConfig::Low) => "low",
-            Some(ReasoningEffortConfig::Medium) => "medium",
-            Some(ReasoningEffortConfig::High) => "high",
-            Some(ReasoningEffortConfig::XHigh) => "xhigh",
-            None | Some(ReasoningEffortConfig::None) => "default",
+            Some(ReasoningEffortConfig::None) => "default",
+            Some(effort) => effort.as_str(),
+            None => EffortSelection::AUTO,
         }
     }
 
@@ -2040,6 +2078,8 @@ mod tests {
     use codex_core::AuthManager;
     use codex_core::CodexAuth;
     use codex_core::ConversationManager;
+    use codex_core::models_manager::catalog::CatalogLoadOptions;
+    use codex_core::models_manager::catalog::PresetCatalog;
     use codex_core::protocol::AskForApproval;
     use codex_core::protocol::Event;
     use codex_core::protocol::EventMsg;
@@ -2155,7 +2195,12 @@ mod tests {
     }
 
     fn all_model_presets() -> Vec<ModelPreset> {
-        codex_core::models_manager::model_presets::all_model_presets().clone()
+        PresetCatalog::static_presets(CatalogLoadOptions {
+            include_hidden: true,
+            ..Default::default()
+        })
+        .cloned()
+        .collect()
     }
 
     #[tokio::test]
@@ -2283,6 +2328,9 @@ mod tests {
                 session_id: ConversationId::new(),
                 model: "gpt-test".to_string(),
                 model_provider_id: "test-provider".to_string(),
+                model_catalog_offline: false,
+                instructions_addendum_active: false,
+                disabled_tools: Vec::new(),
                 approval_policy: AskForApproval::Never,
                 sandbox_policy: SandboxPolicy::ReadOnly,
                 cwd: PathBuf::from("/home/user/project"),
@@ -2578,6 +2626,9 @@ mod tests {
             session_id: conversation_id,
             model: "gpt-test".to_string(),
             model_provider_id: "test-provider".to_string(),
+            model_catalog_offline: false,
+            instructions_addendum_active: false,
+            disabled_tools: Vec::new(),
             approval_policy: AskForApproval::Never,
             sandbox_policy: SandboxPolicy::ReadOnly,
             cwd: PathBuf::from("/home/user/project"),
@@ -1,6 +1,7 @@
 use std::path::PathBuf;
 
 use codex_common::approval_presets::ApprovalPreset;
+use codex_core::models_manager::client_version::ClientVersionNotice;
 use codex_core::protocol::ConversationPathResponseEvent;
 use codex_core::protocol::Event;
 use codex_core::protocol::RateLimitSnapshot;
@@ -79,6 +80,11 @@ pub(crate) enum AppEvent {
         models: Vec<ModelPreset>,
     },
 
+    /// The catalog recommends a newer Codex than this one.
+    ClientVersionBelowMinimum {
+        notice: ClientVersionNotice,
+    },
+
     /// Open the confirmation prompt before enabling full access mode.
     OpenFullAccessConfirmation {
         preset: ApprovalPreset,
@@ -12,7 +12,12 @@ use codex_core::config::ConstraintResult;
 use codex_core::config::types::Notifications;
 use codex_core::git_info::current_branch_name;
 use codex_core::git_info::local_git_branches;
+use codex_core::models_manager::catalog::CatalogSnapshot;
+use codex_core::models_manager::catalog::PresetCatalog;
+use codex_core::models_manager::client_version::ClientVersionNotice;
 use codex_core::models_manager::manager::ModelsManager;
+use codex_core::models_manager::model_presets::PLUS_RATE_LIMIT_WARNING_PRESET_IDS;
+use codex_core::models_manager::model_presets::RATE_LIMIT_NUDGE_PRESET_ID;
 use codex_core::project_doc::DEFAULT_PROJECT_DOC_FILENAME;
 use codex_core::protocol::AgentMessageDeltaEvent;
 use codex_core::protocol::AgentMessageEvent;
@@ -22,6 +27,7 @@ use codex_core::protocol::AgentReasoningRawContentDeltaEvent;
 use codex_core::protocol::AgentReasoningRawContentEvent;
 use codex_core::protocol::ApplyPatchApprovalRequestEvent;
 use codex_core::protocol::BackgroundEventEvent;
+use codex_core::protocol::CostLimitApprovalRequestEvent;
 use codex_core::protocol::CreditsSnapshot;
 use codex_core::protocol::DeprecationNoticeEvent;
 use codex_core::protocol::ErrorEvent;
@@ -43,11 +49,13 @@ use codex_core::protocol::McpToolCallEndEvent;
 use codex_core::protocol::Op;
 use codex_core::protocol::PatchApplyBeginEvent;
 use codex_core::protocol::RateLimitSnapshot;
+use codex_core::protocol::ReviewDecision;
 use codex_core::protocol::ReviewRequest;
 use codex_core::protocol::ReviewTarget;
 use codex_core::protocol::SkillsListEntry;
 use codex_core::protocol::StreamErrorEvent;
 use codex_core::protocol::TaskCompleteEvent;
+use codex_core::protocol::TaskStartedEvent;
 use codex_core::protocol::TerminalInteractionEvent;
 use codex_core::protocol::TokenUsage;
 use codex_core::protocol::TokenUsageInfo;
@@ -135,10 +143,11 @@ use codex_core::ConversationManager;
 use codex_core::protocol::AskForApproval;
 use codex_core::protocol::SandboxPolicy;
 use codex_file_search::FileMatch;
+use codex_protocol::openai_models::EffortSelection;
 use codex_protocol::openai_models::ModelPreset;
 use codex_protocol::openai_models::ReasoningEffort as ReasoningEffortConfig;
+use codex_protocol::openai_models::ReasoningSummaryFormat;
 use codex_protocol::plan_tool::UpdatePlanArgs;
-use strum::IntoEnumIterator;
 
 const USER_SHELL_COMMAND_HELP_TITLE: &str = "Prefix a command with ! to run it locally";
 const USER_SHELL_COMMAND_HELP_HINT: &str = "Example: !ls";
@@ -164,7 +173,7 @@ impl UnifiedExecWaitState {
 }
 
 const RATE_LIMIT_WARNING_THRESHOLDS: [f64; 3] = [75.0, 90.0, 95.0];
-const NUDGE_MODEL_SLUG: &str = "gpt-5.1-codex-mini";
+const NUDGE_MODEL_SLUG: &str = RATE_LIMIT_NUDGE_PRESET_ID;
 const RATE_LIMIT_SWITCH_PROMPT_THRESHOLD: f64 = 90.0;
 
 #[derive(Default)]
@@ -307,6 +316,9 @@ pub(crate) struct ChatWidget {
     reasoning_buffer: String,
     // Accumulates full reasoning content for transcript-only recording
     full_reasoning_buffer: String,
+    // How the current turn's model writes reasoning summaries, as core
+    // reported when the turn started
+    reasoning_summary_format: ReasoningSummaryFormat,
     // Current status header shown in the status indicator.
     current_status_header: String,
     // Previous status header to restore after a transient stream retry.
@@ -492,7 +504,9 @@ impl ChatWidget {
         // (between **/**) as the chunk header. Show this header as status.
         self.reasoning_buffer.push_str(&delta);
 
-        if let Some(header) = extract_first_bold(&self.reasoning_buffer) {
+        if self.reasoning_summary_format == ReasoningSummaryFormat::Headed
+            && let Some(header) = extract_first_bold(&self.reasoning_buffer)
+        {
             // Update the shimmer header to the extracted reasoning chunk header.
             self.set_status_header(header);
         } else {
@@ -505,8 +519,10 @@ impl ChatWidget {
         // At the end of a reasoning block, record transcript-only content.
         self.full_reasoning_buffer.push_str(&self.reasoning_buffer);
         if !self.full_reasoning_buffer.is_empty() {
-            let cell =
-                history_cell::new_reasoning_summary_block(self.full_reasoning_buffer.clone());
+            let cell = history_cell::new_reasoning_summary_block(
+                self.full_reasoning_buffer.clone(),
+                self.reasoning_summary_format,
+            );
             self.add_boxed_history(cell);
         }
         self.reasoning_buffer.clear();
@@ -523,7 +539,10 @@ impl ChatWidget {
 
     // Raw reasoning uses the same flow as summarized reasoning
 
-    fn on_task_started(&mut self) {
+    fn on_task_started(&mut self, ev: TaskStartedEvent) {
+        if let Some(format) = ev.reasoning_summary_format {
+            self.reasoning_summary_format = format;
+        }
         self.bottom_pane.clear_ctrl_c_quit_hint();
         self.bottom_pane.set_task_running(true);
         self.retry_status_header = None;
@@ -692,6 +711,12 @@ impl ChatWidget {
         self.request_redraw();
     }
 
+    /// The catalog recommends a newer Codex than this one. Only a warning:
+    /// nothing stops working.
+    pub(crate) fn on_client_version_notice(&mut self, notice: &ClientVersionNotice) {
+        self.on_warning(notice.to_string());
+    }
+
     fn on_mcp_startup_update(&mut self, ev: McpStartupUpdateEvent) {
         let mut status = self.mcp_startup_status.take().unwrap_or_default();
         if let McpStartupStatus::Failed { error } = &ev.status {
@@ -820,6 +845,26 @@ impl ChatWidget {
         );
     }
 
+    /// There is no cost limit prompt here yet, so the turn is declined right
+    /// away instead of waiting on an answer that never comes.
+    fn on_cost_limit_approval_request(&mut self, ev: CostLimitApprovalRequestEvent) {
+        let CostLimitApprovalRequestEvent {
+            turn_id,
+            cost_usd,
+            limit_usd,
+        } = ev;
+        self.submit_op(Op::CostLimitApproval {
+            id: turn_id,
+            decision: ReviewDecision::Denied,
+        });
+        self.add_info_message(
+            format!(
+                "Stopped at the session cost limit: ${cost_usd:.2} spent (limit ${limit_usd:.2})."
+            ),
+            None,
+        );
+    }
+
     fn on_elicitation_request(&mut self, ev: ElicitationRequestEvent) {
         let ev2 = ev.clone();
         self.defer_or_handle(
@@ -1326,6 +1371,7 @@ impl ChatWidget {
             interrupts: InterruptManager::new(),
             reasoning_buffer: String::new(),
             full_reasoning_buffer: String::new(),
+            reasoning_summary_format: ReasoningSummaryFormat::default(),
             current_status_header: String::from("Working"),
             retry_status_header: None,
             conversation_id: None,
@@ -1410,6 +1456,7 @@ impl ChatWidget {
             interrupts: InterruptManager::new(),
             reasoning_buffer: String::new(),
             full_reasoning_buffer: String::new(),
+            reasoning_summary_format: ReasoningSummaryFormat::default(),
             current_status_header: String::from("Working"),
             retry_status_header: None,
             conversation_id: None,
@@ -1839,7 +1886,7 @@ impl ChatWidget {
                 self.on_agent_reasoning_final();
             }
             EventMsg::AgentReasoningSectionBreak(_) => self.on_reasoning_section_break(),
-            EventMsg::TaskStarted(_) => self.on_task_started(),
+            EventMsg::TaskStarted(ev) => self.on_task_started(ev),
             EventMsg::TaskComplete(TaskCompleteEvent { last_agent_message }) => {
                 self.on_task_complete(last_agent_message)
             }
@@ -1900,6 +1947,7 @@ impl ChatWidget {
             EventMsg::BackgroundEvent(BackgroundEventEvent { message }) => {
                 self.on_background_event(message)
             }
+            EventMsg::CostLimitApprovalRequest(ev) => self.on_cost_limit_approval_request(ev),
             EventMsg::UndoStarted(ev) => self.on_undo_started(ev),
             EventMsg::UndoCompleted(ev) => self.on_undo_completed(ev),
             EventMsg::StreamError(StreamErrorEvent {
@@ -1918,6 +1966,7 @@ impl ChatWidget {
             EventMsg::ExitedReviewMode(review) => self.on_exited_review_mode(review),
             EventMsg::ContextCompacted(_) => self.on_agent_message("Context compacted".to_owned()),
             EventMsg::RawResponseItem(_)
+            | EventMsg::ModelChanged(_)
             | EventMsg::ItemStarted(_)
             | EventMsg::ItemCompleted(_)
             | EventMsg::AgentMessageContentDelta(_)
@@ -2053,6 +2102,8 @@ impl ChatWidget {
         let total_usage = token_info
             .map(|ti| &ti.total_token_usage)
             .unwrap_or(&default_usage);
+        let catalog = self.catalog_snapshot();
+        let model = crate::status::StatusModel::from_catalog(&self.model, &self.config, &catalog);
         self.add_to_history(crate::status::new_status_output(
             &self.config,
             self.auth_manager.as_ref(),
@@ -2062,7 +2113,7 @@ impl ChatWidget {
             self.rate_limit_snapshot.as_ref(),
             self.plan_type,
             Local::now(),
-            &self.model,
+            model,
         ));
     }
     fn stop_rate_limit_poller(&mut self) {
@@ -2098,12 +2149,13 @@ impl ChatWidget {
         self.rate_limit_poller = Some(handle);
     }
 
+    /// The presets as last published; rendering cannot wait on a refresh.
+    fn catalog_snapshot(&self) -> Arc<CatalogSnapshot> {
+        PresetCatalog::new(&self.models_manager, &self.config).snapshot()
+    }
+
     fn lower_cost_preset(&self) -> Option<ModelPreset> {
-        let models = self.models_manager.try_list_models(&self.config).ok()?;
-        models
-            .iter()
-            .find(|preset| preset.model == NUDGE_MODEL_SLUG)
-            .cloned()
+        self.catalog_snapshot().find(NUDGE_MODEL_SLUG).cloned()
     }
 
     fn rate_limit_switch_prompt_hidden(&self) -> bool {
@@ -2143,6 +2195,7 @@ impl ChatWidget {
                 approval_policy: None,
                 sandbox_policy: None,
                 model: Some(switch_model.clone()),
+                model_provider: None,
                 effort: Some(Some(default_effort)),
                 summary: None,
             }));
@@ -2205,24 +2258,13 @@ impl ChatWidget {
     /// Open a popup to choose a quick auto model. Selecting "All models"
     /// opens the full picker with every available preset.
     pub(crate) fn open_model_popup(&mut self) {
-        let presets: Vec<ModelPreset> =
-            // todo(aibrahim): make this async function
-            match self.models_manager.try_list_models(&self.config) {
-                Ok(models) => models,
-                Err(_) => {
-                    self.add_info_message(
-                        "Models are being updated; please try /model again in a moment."
-                            .to_string(),
-                        None,
-                    );
-                    return;
-                }
-            };
+        let presets: Vec<ModelPreset> = self.catalog_snapshot().presets().to_vec();
 
-        let current_label = presets
-            .iter()
-            .find(|preset| preset.model == self.model)
-            .map(|preset| preset.display_name.to_string())
+        let active_preset = self.active_preset();
+        let current_item = self.current_model_item(active_preset.as_ref());
+        let active_id = active_preset.as_ref().map(|preset| preset.id.clone());
+        let current_label = active_preset
+            .map(|preset| preset.display_name)
             .unwrap_or_else(|| self.model.clone());
 
         let (mut auto_presets, other_presets): (Vec<ModelPreset>, Vec<ModelPreset>) = presets
@@ -2236,29 +2278,32 @@ impl ChatWidget {
 
         auto_presets.sort_by_key(|preset| Self::auto_model_order(&preset.model));
 
-        let mut items: Vec<SelectionItem> = auto_presets
-            .into_iter()
-            .map(|preset| {
-                let description =
-                    (!preset.description.is_empty()).then_some(preset.description.clone());
-                let model = preset.model.clone();
-                let actions = Self::model_selection_actions(
-                    model.clone(),
-                    Some(preset.default_reasoning_effort),
-                );
-                SelectionItem {
-                    name: preset.display_name.clone(),
-                    description,
-                    is_current: model == self.model,
-                    is_default: preset.is_default,
-                    actions,
-                    dismiss_on_select: true,
-                    ..Default::default()
-                }
-            })
-            .collect();
+        let mut items: Vec<SelectionItem> = current_item.into_iter().collect();
+        items.extend(auto_presets.into_iter().map(|preset| {
+            let description =
+                (!preset.description.is_empty()).then_some(preset.description.clone());
+            let actions = Self::model_selection_actions(
+                preset.model.clone(),
+                preset.model_provider.clone(),
+                Some(preset.default_reasoning_effort),
+            );
+            SelectionItem {
+                name: preset.display_name.clone(),
+                description,
+                is_current: active_id.as_ref() == Some(&preset.id),
+                is_default: preset.is_default,
+                actions,
+                dismiss_on_select: true,
+                ..Default::default()
+            }
+        }));
 
+        let mut all_models_idx = None;
+        let default_in_all_models = other_presets.iter().any(|preset| preset.is_default);
         if !other_presets.is_empty() {
+            let is_current = active_id.as_ref().is_some_and(|active_id| {
+                other_presets.iter().any(|preset| &preset.id == active_id)
+            });
             let all_models = other_presets;
             let actions: Vec<SelectionAction> = vec![Box::new(move |tx| {
                 tx.send(AppEvent::OpenAllModelsPopup {
@@ -2266,11 +2311,11 @@ impl ChatWidget {
                 });
             })];
 
-            let is_current = !items.iter().any(|item| item.is_current);
             let description = Some(format!(
                 "Choose a specific model and reasoning level (current: {current_label})"
             ));
 
+            all_models_idx = Some(items.len());
             items.push(SelectionItem {
                 name: "All models".to_string(),
                 description,
@@ -2281,11 +2326,19 @@ impl ChatWidget {
             });
         }
 
+        // A current model left out of the catalog highlights the default,
+        // which may sit behind "All models".
+        let initial_selected_idx = items
+            .iter()
+            .position(|item| item.is_default)
+            .or(all_models_idx.filter(|_| default_in_all_models));
+
         self.bottom_pane.show_selection_view(SelectionViewParams {
             title: Some("Select Model".to_string()),
             subtitle: Some("Pick a quick auto mode or browse all models.".to_string()),
             footer_hint: Some(standard_popup_hint_line()),
             items,
+            initial_selected_idx,
             ..Default::default()
         });
     }
@@ -2312,11 +2365,16 @@ impl ChatWidget {
             return;
         }
 
-        let mut items: Vec<SelectionItem> = Vec::new();
+        let active_preset = self.active_preset();
+        let mut items: Vec<SelectionItem> = self
+            .current_model_item(active_preset.as_ref())
+            .into_iter()
+            .collect();
+        let active_id = active_preset.map(|preset| preset.id);
         for preset in presets.into_iter() {
             let description =
                 (!preset.description.is_empty()).then_some(preset.description.to_string());
-            let is_current = preset.model == self.model;
+            let is_current = active_id.as_ref() == Some(&preset.id);
             let single_supported_effort = preset.supported_reasoning_efforts.len() == 1;
             let preset_for_action = preset.clone();
             let actions: Vec<SelectionAction> = vec![Box::new(move |tx| {
@@ -2336,6 +2394,8 @@ impl ChatWidget {
             });
         }
 
+        let initial_selected_idx = items.iter().position(|item| item.is_default);
+
         self.bottom_pane.show_selection_view(SelectionViewParams {
             title: Some("Select Model and Effort".to_string()),
             subtitle: Some(
@@ -2344,32 +2404,74 @@ impl ChatWidget {
             ),
             footer_hint: Some("Press enter to select reasoning effort, or esc to dismiss.".into()),
             items,
+            initial_selected_idx,
             ..Default::default()
         });
     }
 
+    /// The preset the session runs on, looked up by model slug or id among
+    /// every preset the catalog knows, hidden ones included, so the pickers
+    /// highlight the same row the catalog resolves to.
+    fn active_preset(&self) -> Option<ModelPreset> {
+        PresetCatalog::new(&self.models_manager, &self.config).find_known(&self.model)
+    }
+
+    /// A top row for a session model the picker does not list, so it still
+    /// shows what is running: a hidden preset under its own name, as the
+    /// current row, or a model the catalog does not know at all, marked so.
+    /// Choosing it keeps the model.
+    fn current_model_item(&self, active: Option<&ModelPreset>) -> Option<SelectionItem> {
+        let listed = |active: &ModelPreset| {
+            self.catalog_snapshot()
+                .find_by_model_or_id(&active.id)
+                .is_some()
+        };
+        match active {
+            Some(active) if listed(active) => None,
+            Some(active) => Some(SelectionItem {
+                name: active.display_name.clone(),
+                description: (!active.description.is_empty()).then(|| active.description.clone()),
+                is_current: true,
+                dismiss_on_select: true,
+                ..Default::default()
+            }),
+            None => Some(SelectionItem {
+                name: format!("{} (current, not in catalog)", self.model),
+                dismiss_on_select: true,
+                ..Default::default()
+            }),
+        }
+    }
+
+    /// Models served by a catalog provider switch `model_provider` for the
+    /// session only; they are not persisted because the saved `model` would
+    /// otherwise be paired with the default provider on the next launch.
     fn model_selection_actions(
         model_for_action: String,
+        model_provider_for_action: Option<String>,
         effort_for_action: Option<ReasoningEffortConfig>,
     ) -> Vec<SelectionAction> {
         vec![Box::new(move |tx| {
             let effort_label = effort_for_action
                 .map(|effort| effort.to_string())
-                .unwrap_or_else(|| "default".to_string());
+                .unwrap_or_else(|| EffortSelection::AUTO.to_string());
             tx.send(AppEvent::CodexOp(Op::OverrideTurnContext {
                 cwd: None,
                 approval_policy: None,
                 sandbox_policy: None,
                 model: Some(model_for_action.clone()),
+                model_provider: model_provider_for_action.clone(),
                 effort: Some(effort_for_action),
                 summary: None,
             }));
             tx.send(AppEvent::UpdateModel(model_for_action.clone()));
             tx.send(AppEvent::UpdateReasoningEffort(effort_for_action));
-            tx.send(AppEvent::PersistModelSelection {
-                model: model_for_action.clone(),
-                effort: effort_for_action,
-            });
+            if model_provider_for_action.is_none() {
+                tx.send(AppEvent::PersistModelSelection {
+                    model: model_for_action.clone(),
+                    effort: effort_for_action,
+                });
+            }
             tracing::info!(
                 "Selected model: {}, Selected effort: {}",
                 model_for_action,
@@ -2380,6 +2482,7 @@ impl ChatWidget {
 
     /// Open a popup to choose the reasoning effort (stage 2) for the given model.
     pub(crate) fn open_reasoning_popup(&mut self, preset: ModelPreset) {
+        let summaries_unsupported = preset.supports_reasoning_summaries == Some(false);
         let default_effort: ReasoningEffortConfig = preset.default_reasoning_effort;
         let supported = preset.supported_reasoning_efforts;
 
@@ -2400,16 +2503,14 @@ impl ChatWidget {
             let effort_label = Self::reasoning_effort_label(effort);
             format!("⚠ {effort_label} reasoning effort can quickly consume Plus plan rate limits.")
         });
-        let warn_for_model = preset.model.starts_with("gpt-5.1-codex")
-            || preset.model.starts_with("gpt-5.1-codex-max")
-            || preset.model.starts_with("gpt-5.2");
+        let warn_for_model = PLUS_RATE_LIMIT_WARNING_PRESET_IDS.contains(&preset.model.as_str());
 
         struct EffortChoice {
             stored: Option<ReasoningEffortConfig>,
             display: ReasoningEffortConfig,
         }
         let mut choices: Vec<EffortChoice> = Vec::new();
-        for effort in ReasoningEffortConfig::iter() {
+        for effort in ReasoningEffortConfig::VARIANTS {
             if supported.iter().any(|option| option.effort == effort) {
                 choices.push(EffortChoice {
                     stored: Some(effort),
@@ -2426,9 +2527,9 @@ impl ChatWidget {
 
         if choices.len() == 1 {
             if let Some(effort) = choices.first().and_then(|c| c.stored) {
-                self.apply_model_and_effort(preset.model, Some(effort));
+                self.apply_model_and_effort(preset.model, preset.model_provider, Some(effort));
             } else {
-                self.apply_model_and_effort(preset.model, None);
+                self.apply_model_and_effort(preset.model, preset.model_provider, None);
             }
             return;
         }
@@ -2441,6 +2542,16 @@ impl ChatWidget {
             .or_else(|| choices.iter().find_map(|choice| choice.stored))
             .or(Some(default_effort));
 
+        // "Auto" stores no effort, so the model's default applies and keeps
+        // applying after later model switches.
+        choices.insert(
+            0,
+            EffortChoice {
+                stored: None,
+                display: default_effort,
+            },
+        );
+
         let model_slug = preset.model.to_string();
         let is_current_model = self.model == preset.model;
         let highlight_choice = if is_current_model {
@@ -2448,33 +2559,38 @@ impl ChatWidget {
         } else {
             default_choice
         };
-        let selection_choice = highlight_choice.or(default_choice);
         let initial_selected_idx = choices
             .iter()
-            .position(|choice| choice.stored == selection_choice)
+            .position(|choice| choice.stored == highlight_choice)
             .or_else(|| {
-                selection_choice
+                highlight_choice
                     .and_then(|effort| choices.iter().position(|choice| choice.display == effort))
             });
         let mut items: Vec<SelectionItem> = Vec::new();
         for choice in choices.iter() {
             let effort = choice.display;
-            let mut effort_label = Self::reasoning_effort_label(effort).to_string();
+            let mut effort_label = match choice.stored {
+                Some(_) => Self::reasoning_effort_label(effort).to_string(),
+                None => format!(
+                    "Auto ({})",
+                    Self::reasoning_effort_label(effort).to_lowercase()
+                ),
+            };
             if choice.stored == default_choice {
                 effort_label.push_str(" (default)");
             }
 
-            let description = choice
-                .stored
-                .and_then(|effort| {
-                    supported
-                        .iter()
-                        .find(|option| option.effort == effort)
-                        .map(|option| option.description.to_string())
-                })
-                .filter(|text| !text.is_empty());
+            let description = match choice.stored {
+                None => Some("Follow the model's default effort".to_string()),
+                Some(effort) => supported
+                    .iter()
+                    .find(|option| option.effort == effort)
+                    .map(|option| option.description.to_string()),
+            }
+            .filter(|text| !text.is_empty());
 
-            let show_warning = warn_for_model && warn_effort == Some(effort);
+            let show_warning =
+                warn_for_model && choice.stored.is_some() && warn_effort == choice.stored;
             let selected_description = if show_warning {
                 warning_text.as_ref().map(|warning_message| {
                     description.as_ref().map_or_else(
@@ -2487,7 +2603,11 @@ impl ChatWidget {
             };
 
             let model_for_action = model_slug.clone();
-            let actions = Self::model_selection_actions(model_for_action, choice.stored);
+            let actions = Self::model_selection_actions(
+                model_for_action,
+                preset.model_provider.clone(),
+                choice.stored,
+            );
 
             items.push(SelectionItem {
                 name: effort_label,
@@ -2504,6 +2624,13 @@ impl ChatWidget {
         header.push(Line::from(
             format!("Select Reasoning Level for {model_slug}").bold(),
         ));
+        // tui2's lists have no disabled rows, so the header says what the
+        // summary setting does not do for this model.
+        if summaries_unsupported {
+            header.push(Line::from(
+                format!("{model_slug} does not write reasoning summaries.").dim(),
+            ));
+        }
 
         self.bottom_pane.show_selection_view(SelectionViewParams {
             header: Box::new(header),
@@ -2525,23 +2652,32 @@ impl ChatWidget {
         }
     }
 
-    fn apply_model_and_effort(&self, model: String, effort: Option<ReasoningEffortConfig>) {
+    fn apply_model_and_effort(
+        &self,
+        model: String,
+        model_provider: Option<String>,
+        effort: Option<ReasoningEffortConfig>,
+    ) {
+        let persist = model_provider.is_none();
         self.app_event_tx
             .send(AppEvent::CodexOp(Op::OverrideTurnContext {
                 cwd: None,
                 approval_policy: None,
                 sandbox_policy: None,
                 model: Some(model.clone()),
+                model_provider,
                 effort: Some(effort),
                 summary: None,
             }));
         self.app_event_tx.send(AppEvent::UpdateModel(model.clone()));
         self.app_event_tx
             .send(AppEvent::UpdateReasoningEffort(effort));
-        self.app_event_tx.send(AppEvent::PersistModelSelection {
-            model: model.clone(),
-            effort,
-        });
+        if persist {
+            self.app_event_tx.send(AppEvent::PersistModelSelection {
+                model: model.clone(),
+                effort,
+            });
+        }
         tracing::info!(
             "Selected model: {}, Selected effort: {}",
             model,
@@ -2639,6 +2775,7 @@ impl ChatWidget {
                 approval_policy: Some(approval),
                 sandbox_policy: Some(sandbox_clone.clone()),
                 model: None,
+                model_provider: None,
                 effort: None,
                 summary: None,
             }));
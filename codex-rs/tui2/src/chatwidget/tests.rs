@@ -5,6 +5,9 @@ use crate::test_backend::VT100Backend;
 use crate::tui::FrameRequester;
 use assert_matches::assert_matches;
 use codex_common::approval_presets::builtin_approval_presets;
+use codex_common::test_catalog::PresetBuilderExt;
+use codex_common::test_catalog::preset;
+use codex_common::test_catalog::provider_preset;
 use codex_core::AuthManager;
 use codex_core::CodexAuth;
 use codex_core::config::Config;
@@ -48,7 +51,6 @@ use codex_core::protocol::WarningEvent;
 use codex_protocol::ConversationId;
 use codex_protocol::account::PlanType;
 use codex_protocol::openai_models::ModelPreset;
-use codex_protocol::openai_models::ReasoningEffortPreset;
 use codex_protocol::parse_command::ParsedCommand;
 use codex_protocol::plan_tool::PlanItemArg;
 use codex_protocol::plan_tool::StepStatus;
@@ -105,6 +107,9 @@ async fn resumed_initial_messages_render_history() {
         session_id: conversation_id,
         model: "test-model".to_string(),
         model_provider_id: "test-provider".to_string(),
+        model_catalog_offline: false,
+        instructions_addendum_active: false,
+        disabled_tools: Vec::new(),
         approval_policy: AskForApproval::Never,
         sandbox_policy: SandboxPolicy::ReadOnly,
         cwd: PathBuf::from("/home/user/project"),
@@ -231,6 +236,7 @@ async fn review_restores_context_window_indicator() {
         id: "review-end".into(),
         msg: EventMsg::ExitedReviewMode(ExitedReviewModeEvent {
             review_output: None,
+            model: None,
         }),
     });
     let _ = drain_insert_history(&mut rx);
@@ -284,6 +290,7 @@ async fn context_indicator_shows_used_tokens_when_window_unknown() {
         total_token_usage: token_usage.clone(),
         last_token_usage: token_usage,
         model_context_window: None,
+        per_model_usage: Vec::new(),
     };
 
     chat.handle_codex_event(Event {
@@ -463,6 +470,7 @@ fn make_token_info(total_tokens: i64, context_window: i64) -> TokenUsageInfo {
         total_token_usage: usage(total_tokens),
         last_token_usage: usage(total_tokens),
         model_context_window: Some(context_window),
+        per_model_usage: Vec::new(),
     }
 }
 
@@ -920,10 +928,7 @@ fn active_blob(chat: &ChatWidget) -> String {
 }
 
 fn get_available_model(chat: &ChatWidget, model: &str) -> ModelPreset {
-    let models = chat
-        .models_manager
-        .try_list_models(&chat.config)
-        .expect("models lock available");
+    let models = chat.loaded_presets().expect("models lock available");
     models
         .iter()
         .find(|&preset| preset.model == model)
@@ -1262,6 +1267,35 @@ async fn slash_init_skips_when_project_doc_exists() {
     );
 }
 
+#[tokio::test]
+async fn cost_limit_approval_is_declined_right_away() {
+    let (mut chat, mut rx, mut op_rx) = make_chatwidget_manual(None).await;
+
+    chat.handle_codex_event(Event {
+        id: "sub-cost".into(),
+        msg: EventMsg::CostLimitApprovalRequest(CostLimitApprovalRequestEvent {
+            turn_id: "turn-1".into(),
+            cost_usd: 5.25,
+            limit_usd: 5.0,
+        }),
+    });
+
+    assert_matches!(
+        op_rx.try_recv(),
+        Ok(Op::CostLimitApproval {
+            id,
+            decision: ReviewDecision::Denied,
+        }) if id == "turn-1"
+    );
+    let cells = drain_insert_history(&mut rx);
+    assert_eq!(cells.len(), 1, "expected one info message");
+    let rendered = lines_to_single_string(&cells[0]);
+    assert!(
+        rendered.contains("$5.25 spent (limit $5.00)"),
+        "info message should show the spend and the limit: {rendered:?}"
+    );
+}
+
 #[tokio::test]
 async fn slash_quit_requests_exit() {
     let (mut chat, mut rx, _op_rx) = make_chatwidget_manual(None).await;
@@ -1578,6 +1612,9 @@ async fn interrupted_turn_error_message_snapshot() {
         id: "task-1".into(),
         msg: EventMsg::TaskStarted(TaskStartedEvent {
             model_context_window: None,
+            reasoning_effort: None,
+            reasoning_effort_description: None,
+            reasoning_summary_format: None,
         }),
     });
 
@@ -1731,6 +1768,22 @@ async fn model_selection_popup_snapshot() {
     assert_snapshot!("model_selection_popup", popup);
 }
 
+#[tokio::test]
+async fn model_picker_lists_a_model_outside_the_catalog_above_it() {
+    let (mut chat, _rx, _op_rx) = make_chatwidget_manual(Some("my-finetune")).await;
+    chat.open_model_popup();
+
+    let popup = render_bottom_popup(&chat, 80);
+    assert!(
+        popup.contains("  1. my-finetune (current, not in catalog)"),
+        "{popup}"
+    );
+    assert!(
+        popup.contains("› 2. gpt-5.1-codex-max (default)"),
+        "the catalog default is highlighted:\n{popup}"
+    );
+}
+
 #[tokio::test]
 async fn approvals_selection_popup_snapshot() {
     let (mut chat, _rx, _op_rx) = make_chatwidget_manual(None).await;
@@ -1853,6 +1906,75 @@ async fn model_reasoning_selection_popup_extra_high_warning_snapshot() {
     assert_snapshot!("model_reasoning_selection_popup_extra_high_warning", popup);
 }
 
+#[tokio::test]
+async fn reasoning_popup_auto_row_follows_the_model_default() {
+    let (mut chat, mut rx, _op_rx) = make_chatwidget_manual(Some("gpt-5.1-codex-max")).await;
+    chat.config.model_reasoning_effort = None;
+
+    let preset = get_available_model(&chat, "gpt-5.1-codex-max");
+    chat.open_reasoning_popup(preset);
+
+    let popup = render_bottom_popup(&chat, 80);
+    assert!(
+        popup.contains("› 1. Auto (medium) (current)"),
+        "expected the auto row to be current; popup: {popup}"
+    );
+
+    chat.handle_key_event(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+    let mut events = Vec::new();
+    while let Ok(ev) = rx.try_recv() {
+        events.push(ev);
+    }
+    assert!(
+        events
+            .iter()
+            .any(|ev| matches!(ev, AppEvent::UpdateReasoningEffort(None))),
+        "expected auto to clear the effort; events: {events:?}"
+    );
+    assert!(
+        events
+            .iter()
+            .any(|ev| matches!(ev, AppEvent::PersistModelSelection { effort: None, .. })),
+        "expected auto to be persisted; events: {events:?}"
+    );
+}
+
+#[tokio::test]
+async fn provider_model_selection_switches_provider_for_session() {
+    let (mut chat, mut rx, _op_rx) = make_chatwidget_manual(None).await;
+
+    let preset = provider_preset("lmstudio", "qwen2.5-coder-7b")
+        .display_name("qwen2.5-coder-7b")
+        .description("Served by LM Studio")
+        .build()
+        .expect("valid preset");
+    chat.open_reasoning_popup(preset);
+    chat.handle_key_event(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+
+    let mut events = Vec::new();
+    while let Ok(ev) = rx.try_recv() {
+        events.push(ev);
+    }
+
+    assert!(
+        events.iter().any(|ev| matches!(
+            ev,
+            AppEvent::CodexOp(Op::OverrideTurnContext {
+                model: Some(model),
+                model_provider: Some(provider),
+                ..
+            }) if model == "qwen2.5-coder-7b" && provider == "lmstudio"
+        )),
+        "expected model and provider override; events: {events:?}"
+    );
+    assert!(
+        !events
+            .iter()
+            .any(|ev| matches!(ev, AppEvent::PersistModelSelection { .. })),
+        "provider-served models should not be persisted; events: {events:?}"
+    );
+}
+
 #[tokio::test]
 async fn reasoning_popup_shows_extra_high_with_space() {
     let (mut chat, _rx, _op_rx) = make_chatwidget_manual(Some("gpt-5.1-codex-max")).await;
@@ -1873,26 +1995,36 @@ async fn reasoning_popup_shows_extra_high_with_space() {
     );
 }
 
+#[tokio::test]
+async fn reasoning_popup_notes_models_without_summaries() {
+    let (mut chat, _rx, _op_rx) = make_chatwidget_manual(None).await;
+
+    let mut preset = get_available_model(&chat, "gpt-5.1-codex-max");
+    preset.supports_reasoning_summaries = Some(false);
+    chat.open_reasoning_popup(preset);
+    let popup = render_bottom_popup(&chat, 100);
+    assert!(
+        popup.contains("gpt-5.1-codex-max does not write reasoning summaries"),
+        "expected a note on summaries: {popup}"
+    );
+
+    let preset = get_available_model(&chat, "gpt-5.1-codex-max");
+    chat.open_reasoning_popup(preset);
+    let popup = render_bottom_popup(&chat, 100);
+    assert!(
+        !popup.contains("reasoning summaries"),
+        "models that may write summaries get no note: {popup}"
+    );
+}
+
 #[tokio::test]
 async fn single_reasoning_option_skips_selection() {
     let (mut chat, mut rx, _op_rx) = make_chatwidget_manual(None).await;
 
-    let single_effort = vec![ReasoningEffortPreset {
-        effort: ReasoningEffortConfig::High,
-        description: "Greater reasoning depth for complex or ambiguous problems".to_string(),
-    }];
-    let preset = ModelPreset {
-        id: "model-with-single-reasoning".to_string(),
-        model: "model-with-single-reasoning".to_string(),
-        display_name: "model-with-single-reasoning".to_string(),
-        description: "".to_string(),
-        default_reasoning_effort: ReasoningEffortConfig::High,
-        supported_reasoning_efforts: single_effort,
-        is_default: false,
-        upgrade: None,
-        show_in_picker: true,
-        supported_in_api: true,
-    };
+    let preset = preset("model-with-single-reasoning")
+        .efforts([ReasoningEffortConfig::High])
+        .build()
+        .expect("valid preset");
     chat.open_reasoning_popup(preset);
 
     let popup = render_bottom_popup(&chat, 80);
@@ -2265,6 +2397,9 @@ async fn ui_snapshots_small_heights_task_running() {
         id: "task-1".into(),
         msg: EventMsg::TaskStarted(TaskStartedEvent {
             model_context_window: None,
+            reasoning_effort: None,
+            reasoning_effort_description: None,
+            reasoning_summary_format: None,
         }),
     });
     chat.handle_codex_event(Event {
@@ -2296,6 +2431,9 @@ async fn status_widget_and_approval_modal_snapshot() {
         id: "task-1".into(),
         msg: EventMsg::TaskStarted(TaskStartedEvent {
             model_context_window: None,
+            reasoning_effort: None,
+            reasoning_effort_description: None,
+            reasoning_summary_format: None,
         }),
     });
     // Provide a deterministic header for the status line.
@@ -2348,6 +2486,9 @@ async fn status_widget_active_snapshot() {
         id: "task-1".into(),
         msg: EventMsg::TaskStarted(TaskStartedEvent {
             model_context_window: None,
+            reasoning_effort: None,
+            reasoning_effort_description: None,
+            reasoning_summary_format: None,
         }),
     });
     // Provide a deterministic header via a bold reasoning chunk.
@@ -2897,6 +3038,23 @@ async fn stream_error_updates_status_indicator() {
     assert_eq!(status.details(), Some(details));
 }
 
+#[tokio::test]
+async fn client_version_notice_adds_warning_history_cell() {
+    let (mut chat, mut rx, _op_rx) = make_chatwidget_manual(None).await;
+    chat.on_client_version_notice(&ClientVersionNotice {
+        min_client_version: "0.60.0".to_string(),
+        client_version: "0.59.0".to_string(),
+    });
+
+    let cells = drain_insert_history(&mut rx);
+    assert_eq!(cells.len(), 1, "expected one warning history cell");
+    let rendered = lines_to_single_string(&cells[0]);
+    assert!(
+        rendered.contains("your Codex version (0.59.0) is below the minimum recommended"),
+        "warning cell missing content: {rendered}"
+    );
+}
+
 #[tokio::test]
 async fn warning_event_adds_warning_history_cell() {
     let (mut chat, mut rx, _op_rx) = make_chatwidget_manual(None).await;
@@ -2923,6 +3081,9 @@ async fn stream_recovery_restores_previous_status_header() {
         id: "task".into(),
         msg: EventMsg::TaskStarted(TaskStartedEvent {
             model_context_window: None,
+            reasoning_effort: None,
+            reasoning_effort_description: None,
+            reasoning_summary_format: None,
         }),
     });
     drain_insert_history(&mut rx);
@@ -2960,6 +3121,9 @@ async fn multiple_agent_messages_in_single_turn_emit_multiple_headers() {
         id: "s1".into(),
         msg: EventMsg::TaskStarted(TaskStartedEvent {
             model_context_window: None,
+            reasoning_effort: None,
+            reasoning_effort_description: None,
+            reasoning_summary_format: None,
         }),
     });
 
@@ -3032,6 +3196,46 @@ async fn final_reasoning_then_message_without_deltas_are_rendered() {
     assert_snapshot!(combined);
 }
 
+#[tokio::test]
+async fn plain_reasoning_summaries_keep_their_bold_text() {
+    let (mut chat, mut rx, _op_rx) = make_chatwidget_manual(None).await;
+    let reasoning = |chat: &mut ChatWidget, text: &str| {
+        chat.handle_codex_event(Event {
+            id: "s1".into(),
+            msg: EventMsg::AgentReasoningDelta(AgentReasoningDeltaEvent { delta: text.into() }),
+        });
+        chat.handle_codex_event(Event {
+            id: "s1".into(),
+            msg: EventMsg::AgentReasoning(AgentReasoningEvent { text: text.into() }),
+        });
+    };
+    let rendered = |rx: &mut tokio::sync::mpsc::UnboundedReceiver<AppEvent>| {
+        drain_insert_history(rx)
+            .iter()
+            .map(|lines| lines_to_single_string(lines))
+            .collect::<String>()
+    };
+
+    reasoning(&mut chat, "We should **not** fix the bug yet.");
+    let headed = rendered(&mut rx);
+    assert!(!headed.contains("We should"), "{headed}");
+
+    // Core resolves the format for the turn's model and reports it when the
+    // turn starts.
+    chat.handle_codex_event(Event {
+        id: "s2".into(),
+        msg: EventMsg::TaskStarted(TaskStartedEvent {
+            model_context_window: None,
+            reasoning_effort: None,
+            reasoning_effort_description: None,
+            reasoning_summary_format: Some(ReasoningSummaryFormat::Plain),
+        }),
+    });
+    reasoning(&mut chat, "We should **not** fix the bug yet.");
+    let plain = rendered(&mut rx);
+    assert!(plain.contains("We should not fix the bug yet."), "{plain}");
+}
+
 #[tokio::test]
 async fn deltas_then_same_final_message_are_rendered_snapshot() {
     let (mut chat, mut rx, _op_rx) = make_chatwidget_manual(None).await;
@@ -3154,6 +3358,9 @@ async fn chatwidget_exec_and_status_layout_vt100_snapshot() {
         id: "t1".into(),
         msg: EventMsg::TaskStarted(TaskStartedEvent {
             model_context_window: None,
+            reasoning_effort: None,
+            reasoning_effort_description: None,
+            reasoning_summary_format: None,
         }),
     });
     chat.handle_codex_event(Event {
@@ -3198,6 +3405,9 @@ async fn chatwidget_markdown_code_blocks_vt100_snapshot() {
         id: "t1".into(),
         msg: EventMsg::TaskStarted(TaskStartedEvent {
             model_context_window: None,
+            reasoning_effort: None,
+            reasoning_effort_description: None,
+            reasoning_summary_format: None,
         }),
     });
     // Build a vt100 visual from the history insertions only (no UI overlay)
@@ -3286,6 +3496,9 @@ async fn chatwidget_tall() {
         id: "t1".into(),
         msg: EventMsg::TaskStarted(TaskStartedEvent {
             model_context_window: None,
+            reasoning_effort: None,
+            reasoning_effort_description: None,
+            reasoning_summary_format: None,
         }),
     });
     for i in 0..30 {
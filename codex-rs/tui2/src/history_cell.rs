@@ -29,6 +29,7 @@ use codex_core::protocol::McpAuthStatus;
 use codex_core::protocol::McpInvocation;
 use codex_core::protocol::SessionConfiguredEvent;
 use codex_protocol::openai_models::ReasoningEffort as ReasoningEffortConfig;
+use codex_protocol::openai_models::ReasoningSummaryFormat;
 use codex_protocol::plan_tool::PlanItemArg;
 use codex_protocol::plan_tool::StepStatus;
 use codex_protocol::plan_tool::UpdatePlanArgs;
@@ -872,14 +873,7 @@ impl SessionHeaderHistoryCell {
     }
 
     fn reasoning_label(&self) -> Option<&'static str> {
-        self.reasoning_effort.map(|effort| match effort {
-            ReasoningEffortConfig::Minimal => "minimal",
-            ReasoningEffortConfig::Low => "low",
-            ReasoningEffortConfig::Medium => "medium",
-            ReasoningEffortConfig::High => "high",
-            ReasoningEffortConfig::XHigh => "xhigh",
-            ReasoningEffortConfig::None => "none",
-        })
+        self.reasoning_effort.map(ReasoningEffortConfig::as_str)
     }
 }
 
@@ -1543,7 +1537,13 @@ pub(crate) fn new_view_image_tool_call(path: PathBuf, cwd: &Path) -> PlainHistor
     PlainHistoryCell { lines }
 }
 
-pub(crate) fn new_reasoning_summary_block(full_reasoning_buffer: String) -> Box<dyn HistoryCell> {
+/// A headed summary has its header, the first bold span, dropped, and one
+/// without a header is kept in the transcript only. Plain summaries are kept
+/// whole, since bold text in them is no header.
+pub(crate) fn new_reasoning_summary_block(
+    full_reasoning_buffer: String,
+    format: ReasoningSummaryFormat,
+) -> Box<dyn HistoryCell> {
     // Experimental format is following:
     // ** header **
     //
@@ -1551,6 +1551,13 @@ pub(crate) fn new_reasoning_summary_block(full_reasoning_buffer: String) -> Box<
     //
     // So we need to strip header from reasoning summary
     let full_reasoning_buffer = full_reasoning_buffer.trim();
+    if format == ReasoningSummaryFormat::Plain {
+        return Box::new(ReasoningSummaryCell::new(
+            String::new(),
+            full_reasoning_buffer.to_string(),
+            false,
+        ));
+    }
     if let Some(open) = full_reasoning_buffer.find("**") {
         let after_open = &full_reasoning_buffer[(open + 2)..];
         if let Some(close) = after_open.find("**") {
@@ -2484,6 +2491,7 @@ mod tests {
     fn reasoning_summary_block() {
         let cell = new_reasoning_summary_block(
             "**High level reasoning**\n\nDetailed reasoning goes here.".to_string(),
+            ReasoningSummaryFormat::Headed,
         );
 
         let rendered_display = render_lines(&cell.display_lines(80));
@@ -2495,7 +2503,10 @@ mod tests {
 
     #[test]
     fn reasoning_summary_block_returns_reasoning_cell_when_feature_disabled() {
-        let cell = new_reasoning_summary_block("Detailed reasoning goes here.".to_string());
+        let cell = new_reasoning_summary_block(
+            "Detailed reasoning goes here.".to_string(),
+            ReasoningSummaryFormat::Headed,
+        );
 
         let rendered = render_transcript(cell.as_ref());
         assert_eq!(rendered, vec!["• Detailed reasoning goes here."]);
@@ -2509,6 +2520,7 @@ mod tests {
 
         let cell = new_reasoning_summary_block(
             "**High level reasoning**\n\nDetailed reasoning goes here.".to_string(),
+            ReasoningSummaryFormat::Headed,
         );
 
         let rendered_display = render_lines(&cell.display_lines(80));
@@ -2517,8 +2529,10 @@ mod tests {
 
     #[test]
     fn reasoning_summary_block_falls_back_when_header_is_missing() {
-        let cell =
-            new_reasoning_summary_block("**High level reasoning without closing".to_string());
+        let cell = new_reasoning_summary_block(
+            "**High level reasoning without closing".to_string(),
+            ReasoningSummaryFormat::Headed,
+        );
 
         let rendered = render_transcript(cell.as_ref());
         assert_eq!(rendered, vec!["• **High level reasoning without closing"]);
@@ -2526,14 +2540,17 @@ mod tests {
 
     #[test]
     fn reasoning_summary_block_falls_back_when_summary_is_missing() {
-        let cell =
-            new_reasoning_summary_block("**High level reasoning without closing**".to_string());
+        let cell = new_reasoning_summary_block(
+            "**High level reasoning without closing**".to_string(),
+            ReasoningSummaryFormat::Headed,
+        );
 
         let rendered = render_transcript(cell.as_ref());
         assert_eq!(rendered, vec!["• High level reasoning without closing"]);
 
         let cell = new_reasoning_summary_block(
             "**High level reasoning without closing**\n\n  ".to_string(),
+            ReasoningSummaryFormat::Headed,
         );
 
         let rendered = render_transcript(cell.as_ref());
@@ -2544,6 +2561,7 @@ mod tests {
     fn reasoning_summary_block_splits_header_and_summary_when_present() {
         let cell = new_reasoning_summary_block(
             "**High level plan**\n\nWe should fix the bug next.".to_string(),
+            ReasoningSummaryFormat::Headed,
         );
 
         let rendered_display = render_lines(&cell.display_lines(80));
@@ -2,6 +2,7 @@ use crate::exec_command::relativize_to_home;
 use crate::text_formatting;
 use chrono::DateTime;
 use chrono::Local;
+use chrono::Utc;
 use codex_app_server_protocol::AuthMode;
 use codex_core::AuthManager;
 use codex_core::config::Config;
@@ -175,6 +176,17 @@ pub(crate) fn format_reset_timestamp(dt: DateTime<Local>, captured_at: DateTime<
     }
 }
 
+/// How long before `now` the moment `at` was, e.g. `5m ago`.
+pub(crate) fn format_age(at: DateTime<Utc>, now: DateTime<Local>) -> String {
+    let secs = (now.with_timezone(&Utc) - at).num_seconds().max(0);
+    match secs {
+        0..60 => format!("{secs}s ago"),
+        60..3_600 => format!("{}m ago", secs / 60),
+        3_600..86_400 => format!("{}h ago", secs / 3_600),
+        _ => format!("{}d ago", secs / 86_400),
+    }
+}
+
 pub(crate) fn title_case(s: &str) -> String {
     if s.is_empty() {
         return String::new();
@@ -1,13 +1,19 @@
+use super::StatusModel;
 use super::new_status_output;
 use super::rate_limit_snapshot_display;
 use crate::history_cell::HistoryCell;
 use chrono::Duration as ChronoDuration;
 use chrono::TimeZone;
 use chrono::Utc;
+use codex_common::test_catalog;
+use codex_common::test_catalog::PresetBuilderExt;
 use codex_core::AuthManager;
 use codex_core::config::Config;
 use codex_core::config::ConfigBuilder;
+use codex_core::models_manager::catalog::CatalogSnapshot;
+use codex_core::models_manager::catalog::CatalogSnapshotStatus;
 use codex_core::models_manager::manager::ModelsManager;
+use codex_core::models_manager::provenance::CatalogSource;
 use codex_core::protocol::CreditsSnapshot;
 use codex_core::protocol::RateLimitSnapshot;
 use codex_core::protocol::RateLimitWindow;
@@ -45,6 +51,7 @@ fn token_info_for(model_slug: &str, config: &Config, usage: &TokenUsage) -> Toke
         total_token_usage: usage.clone(),
         last_token_usage: usage.clone(),
         model_context_window: context_window,
+        per_model_usage: Vec::new(),
     }
 }
 
@@ -89,6 +96,54 @@ fn reset_at_from(captured_at: &chrono::DateTime<chrono::Local>, seconds: i64) ->
         .timestamp()
 }
 
+#[tokio::test]
+async fn status_marks_summaries_unsupported_for_models_without_them() {
+    let temp_home = TempDir::new().expect("temp home");
+    let mut config = test_config(&temp_home).await;
+    config.model = Some("gpt-5.1-codex-max".to_string());
+    config.model_provider_id = "openai".to_string();
+    config.model_reasoning_summary = ReasoningSummary::Detailed;
+
+    let auth_manager = test_auth_manager(&config);
+    let usage = TokenUsage::default();
+    let captured_at = chrono::Local
+        .with_ymd_and_hms(2024, 1, 2, 3, 4, 5)
+        .single()
+        .expect("timestamp");
+    let model_slug = ModelsManager::get_model_offline(config.model.as_deref());
+
+    let render = |supports_summaries| {
+        let composite = new_status_output(
+            &config,
+            &auth_manager,
+            None,
+            &usage,
+            &None,
+            None,
+            None,
+            captured_at,
+            StatusModel {
+                name: &model_slug,
+                supports_reasoning_summaries: supports_summaries,
+                ..Default::default()
+            },
+        );
+        render_lines(&composite.display_lines(120)).join("\n")
+    };
+
+    let unsupported = render(Some(false));
+    assert!(
+        unsupported.contains("summaries unsupported")
+            && !unsupported.contains("summaries detailed"),
+        "expected the summary setting to be marked unsupported; status: {unsupported}"
+    );
+    let unknown = render(None);
+    assert!(
+        unknown.contains("summaries detailed"),
+        "expected the configured summary setting; status: {unknown}"
+    );
+}
+
 #[tokio::test]
 async fn status_snapshot_includes_reasoning_details() {
     let temp_home = TempDir::new().expect("temp home");
@@ -150,7 +205,10 @@ async fn status_snapshot_includes_reasoning_details() {
         Some(&rate_display),
         None,
         captured_at,
-        &model_slug,
+        StatusModel {
+            name: &model_slug,
+            ..Default::default()
+        },
     );
     let mut rendered_lines = render_lines(&composite.display_lines(80));
     if cfg!(windows) {
@@ -205,7 +263,10 @@ async fn status_snapshot_includes_monthly_limit() {
         Some(&rate_display),
         None,
         captured_at,
-        &model_slug,
+        StatusModel {
+            name: &model_slug,
+            ..Default::default()
+        },
     );
     let mut rendered_lines = render_lines(&composite.display_lines(80));
     if cfg!(windows) {
@@ -248,7 +309,10 @@ async fn status_snapshot_shows_unlimited_credits() {
         Some(&rate_display),
         None,
         captured_at,
-        &model_slug,
+        StatusModel {
+            name: &model_slug,
+            ..Default::default()
+        },
     );
     let rendered = render_lines(&composite.display_lines(120));
     assert!(
@@ -291,7 +355,10 @@ async fn status_snapshot_shows_positive_credits() {
         Some(&rate_display),
         None,
         captured_at,
-        &model_slug,
+        StatusModel {
+            name: &model_slug,
+            ..Default::default()
+        },
     );
     let rendered = render_lines(&composite.display_lines(120));
     assert!(
@@ -334,7 +401,10 @@ async fn status_snapshot_hides_zero_credits() {
         Some(&rate_display),
         None,
         captured_at,
-        &model_slug,
+        StatusModel {
+            name: &model_slug,
+            ..Default::default()
+        },
     );
     let rendered = render_lines(&composite.display_lines(120));
     assert!(
@@ -375,7 +445,10 @@ async fn status_snapshot_hides_when_has_no_credits_flag() {
         Some(&rate_display),
         None,
         captured_at,
-        &model_slug,
+        StatusModel {
+            name: &model_slug,
+            ..Default::default()
+        },
     );
     let rendered = render_lines(&composite.display_lines(120));
     assert!(
@@ -416,7 +489,10 @@ async fn status_card_token_usage_excludes_cached_tokens() {
         None,
         None,
         now,
-        &model_slug,
+        StatusModel {
+            name: &model_slug,
+            ..Default::default()
+        },
     );
     let rendered = render_lines(&composite.display_lines(120));
 
@@ -472,7 +548,10 @@ async fn status_snapshot_truncates_in_narrow_terminal() {
         Some(&rate_display),
         None,
         captured_at,
-        &model_slug,
+        StatusModel {
+            name: &model_slug,
+            ..Default::default()
+        },
     );
     let mut rendered_lines = render_lines(&composite.display_lines(70));
     if cfg!(windows) {
@@ -517,7 +596,10 @@ async fn status_snapshot_shows_missing_limits_message() {
         None,
         None,
         now,
-        &model_slug,
+        StatusModel {
+            name: &model_slug,
+            ..Default::default()
+        },
     );
     let mut rendered_lines = render_lines(&composite.display_lines(80));
     if cfg!(windows) {
@@ -528,6 +610,128 @@ async fn status_snapshot_shows_missing_limits_message() {
     let sanitized = sanitize_directory(rendered_lines).join("\n");
     assert_snapshot!(sanitized);
 }
+fn catalog_status(now: chrono::DateTime<chrono::Local>) -> CatalogSnapshotStatus {
+    CatalogSnapshotStatus {
+        source: CatalogSource::Live,
+        host: Some("gateway.example.com".to_string()),
+        etag: None,
+        fetched_at: Some(now.with_timezone(&Utc) - ChronoDuration::minutes(5)),
+        last_error: None,
+        cache_path: PathBuf::from("/codex-home/models_cache.json"),
+        hidden_count: 3,
+        imported: None,
+    }
+}
+
+fn render_status_with_catalog(
+    config: &Config,
+    catalog: &CatalogSnapshot,
+    now: chrono::DateTime<chrono::Local>,
+) -> String {
+    let auth_manager = test_auth_manager(config);
+    let usage = TokenUsage {
+        input_tokens: 500,
+        cached_input_tokens: 0,
+        output_tokens: 250,
+        reasoning_output_tokens: 0,
+        total_tokens: 750,
+    };
+    let model_slug = ModelsManager::get_model_offline(config.model.as_deref());
+    let token_info = token_info_for(&model_slug, config, &usage);
+    let composite = new_status_output(
+        config,
+        &auth_manager,
+        Some(&token_info),
+        &usage,
+        &None,
+        None,
+        None,
+        now,
+        StatusModel::from_catalog(&model_slug, config, catalog),
+    );
+    let mut rendered_lines = render_lines(&composite.display_lines(80));
+    if cfg!(windows) {
+        for line in &mut rendered_lines {
+            *line = line.replace('\\', "/");
+        }
+    }
+    sanitize_directory(rendered_lines).join("\n")
+}
+
+#[tokio::test]
+async fn status_snapshot_shows_the_model_catalog() {
+    let temp_home = TempDir::new().expect("temp home");
+    let mut config = test_config(&temp_home).await;
+    config.model = Some("gpt-5.1-codex-max".to_string());
+    config.model_reasoning_effort = Some(ReasoningEffort::High);
+    config.model_reasoning_summary = ReasoningSummary::Detailed;
+    config.cwd = PathBuf::from("/workspace/tests");
+
+    let now = chrono::Local
+        .with_ymd_and_hms(2024, 2, 3, 4, 5, 6)
+        .single()
+        .expect("timestamp");
+    let mut preset = test_catalog::preset("gpt-5.1-codex-max")
+        .display_name("GPT-5.1 Codex Max")
+        .efforts([
+            ReasoningEffort::Low,
+            ReasoningEffort::Medium,
+            ReasoningEffort::High,
+        ])
+        .default_reasoning_effort(ReasoningEffort::Medium)
+        .build()
+        .expect("valid preset");
+    preset.context_window = Some(272_000);
+    let catalog = CatalogSnapshot::new(7, vec![preset]).with_status(catalog_status(now));
+
+    let sanitized = render_status_with_catalog(&config, &catalog, now);
+    assert_snapshot!(sanitized);
+}
+
+#[tokio::test]
+async fn status_snapshot_shows_an_offline_fallback_catalog() {
+    let temp_home = TempDir::new().expect("temp home");
+    let mut config = test_config(&temp_home).await;
+    config.model = Some("gpt-5.1-codex-max".to_string());
+    config.cwd = PathBuf::from("/workspace/tests");
+    config.offline = true;
+
+    let now = chrono::Local
+        .with_ymd_and_hms(2024, 2, 3, 4, 5, 6)
+        .single()
+        .expect("timestamp");
+    let catalog = CatalogSnapshot::new(0, Vec::new()).with_status(CatalogSnapshotStatus {
+        cache_path: PathBuf::from("/codex-home/models_cache.json"),
+        ..CatalogSnapshotStatus::default()
+    });
+
+    let sanitized = render_status_with_catalog(&config, &catalog, now);
+    assert_snapshot!(sanitized);
+}
+
+#[tokio::test]
+async fn status_catalog_shows_the_effort_the_session_runs_at() {
+    let temp_home = TempDir::new().expect("temp home");
+    let mut config = test_config(&temp_home).await;
+    config.model = Some("gpt-5.1-codex-max".to_string());
+    config.model_reasoning_effort = None;
+
+    let now = chrono::Local
+        .with_ymd_and_hms(2024, 2, 3, 4, 5, 6)
+        .single()
+        .expect("timestamp");
+    let preset = test_catalog::preset("gpt-5.1-codex-max")
+        .efforts([ReasoningEffort::Medium, ReasoningEffort::High])
+        .default_reasoning_effort(ReasoningEffort::High)
+        .build()
+        .expect("valid preset");
+    let catalog = CatalogSnapshot::new(7, vec![preset]).with_status(catalog_status(now));
+
+    let rendered = render_status_with_catalog(&config, &catalog, now);
+    assert!(rendered.contains("reasoning auto (high)"), "{rendered}");
+    assert!(rendered.contains("high effort"), "{rendered}");
+}
+
 #[tokio::test]
 async fn status_snapshot_includes_credits_and_limits() {
     let temp_home = TempDir::new().expect("temp home");
@@ -579,7 +783,10 @@ async fn status_snapshot_includes_credits_and_limits() {
         Some(&rate_display),
         None,
         captured_at,
-        &model_slug,
+        StatusModel {
+            name: &model_slug,
+            ..Default::default()
+        },
     );
     let mut rendered_lines = render_lines(&composite.display_lines(80));
     if cfg!(windows) {
@@ -630,7 +837,10 @@ async fn status_snapshot_shows_empty_limits_message() {
         Some(&rate_display),
         None,
         captured_at,
-        &model_slug,
+        StatusModel {
+            name: &model_slug,
+            ..Default::default()
+        },
     );
     let mut rendered_lines = render_lines(&composite.display_lines(80));
     if cfg!(windows) {
@@ -690,7 +900,10 @@ async fn status_snapshot_shows_stale_limits_message() {
         Some(&rate_display),
         None,
         now,
-        &model_slug,
+        StatusModel {
+            name: &model_slug,
+            ..Default::default()
+        },
     );
     let mut rendered_lines = render_lines(&composite.display_lines(80));
     if cfg!(windows) {
@@ -754,7 +967,10 @@ async fn status_snapshot_cached_limits_hide_credits_without_flag() {
         Some(&rate_display),
         None,
         now,
-        &model_slug,
+        StatusModel {
+            name: &model_slug,
+            ..Default::default()
+        },
     );
     let mut rendered_lines = render_lines(&composite.display_lines(80));
     if cfg!(windows) {
@@ -798,6 +1014,7 @@ async fn status_context_window_uses_last_usage() {
         total_token_usage: total_usage.clone(),
         last_token_usage: last_usage,
         model_context_window: config.model_context_window,
+        per_model_usage: Vec::new(),
     };
     let composite = new_status_output(
         &config,
@@ -808,7 +1025,10 @@ async fn status_context_window_uses_last_usage() {
         None,
         None,
         now,
-        &model_slug,
+        StatusModel {
+            name: &model_slug,
+            ..Default::default()
+        },
     );
     let rendered_lines = render_lines(&composite.display_lines(80));
     let context_line = rendered_lines